@@ -17,11 +17,40 @@
 #[cfg(feature = "authorization-handler-rbac")]
 mod rbac;
 
+#[cfg(any(feature = "metrics-snapshot", feature = "node-metadata"))]
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
 use reqwest::blocking::Client;
+#[cfg(any(feature = "health-service", feature = "peer-endpoint-pin"))]
+use reqwest::StatusCode;
 use serde::Deserialize;
+#[cfg(any(
+    feature = "node-metadata",
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin"
+))]
+use serde::Serialize;
 
 use super::CliError;
 
+/// How long an idle connection is kept open for reuse by a later request
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// The interval between TCP keep-alive probes on open connections
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+#[cfg(feature = "health-service")]
+const HEALTH_PROTOCOL_VERSION: u32 = 2;
+#[cfg(feature = "metrics-snapshot")]
+const METRICS_PROTOCOL_VERSION: u32 = 2;
+#[cfg(feature = "peer-disconnect")]
+const PEER_DISCONNECT_PROTOCOL_VERSION: u32 = 2;
+#[cfg(feature = "peer-endpoint-pin")]
+const PEER_ENDPOINT_PIN_PROTOCOL_VERSION: u32 = 2;
+#[cfg(feature = "peer-ping")]
+const PEER_PING_PROTOCOL_VERSION: u32 = 2;
+
 #[cfg(feature = "authorization-handler-rbac")]
 pub use rbac::{
     assignments::{
@@ -30,6 +59,27 @@ pub use rbac::{
     roles::{Role, RoleBuilder, RoleUpdate, RoleUpdateBuilder},
 };
 
+static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .build()
+        .expect("Unable to initialize the TLS backend of the HTTP client")
+});
+
+/// Returns the HTTP client used for every REST API request made by the CLI.
+///
+/// The client keeps idle connections open, so actions that make several requests to the same
+/// node, such as watching or paging through a list, reuse one connection rather than opening a
+/// new one per request. HTTPS connections use HTTP/2 when the server supports it.
+///
+/// # Panics
+///
+/// Panics if the TLS backend cannot be initialized, as `Client::new` does.
+pub fn http_client() -> &'static Client {
+    &HTTP_CLIENT
+}
+
 #[derive(Default)]
 pub struct SplinterRestClientBuilder {
     pub url: Option<String>,
@@ -74,7 +124,7 @@ pub struct SplinterRestClient {
 impl SplinterRestClient {
     /// Gets the Splinter node's status.
     pub fn get_node_status(&self) -> Result<NodeStatus, CliError> {
-        Client::new()
+        http_client()
             .get(&format!("{}/status", self.url))
             .header("Authorization", &self.auth)
             .send()
@@ -107,10 +157,301 @@ impl SplinterRestClient {
             })
     }
 
+    /// Updates the Splinter node's display name and/or metadata.
+    #[cfg(feature = "node-metadata")]
+    pub fn update_node_status(&self, update: &NodeStatusUpdate) -> Result<(), CliError> {
+        http_client()
+            .patch(&format!("{}/status", self.url))
+            .header("Authorization", &self.auth)
+            .json(update)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to update node status: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Node status update request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to update node status: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Gets the results of the Splinter node's most recent health checks.
+    ///
+    /// A node that is not ready responds with a `503 Service Unavailable` status; its results are
+    /// returned all the same.
+    #[cfg(feature = "health-service")]
+    pub fn get_health(&self) -> Result<HealthStatus, CliError> {
+        http_client()
+            .get(&format!("{}/health/ready", self.url))
+            .header("Authorization", &self.auth)
+            .header("SplinterProtocolVersion", HEALTH_PROTOCOL_VERSION)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to fetch health: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() || status == StatusCode::SERVICE_UNAVAILABLE {
+                    res.json::<HealthStatus>().map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Health request returned status code '{}', but the response was \
+                             not valid",
+                            status
+                        ))
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Health request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to fetch health: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Gets the latest value of every metric the Splinter node has recorded.
+    #[cfg(feature = "metrics-snapshot")]
+    pub fn get_metrics(&self) -> Result<Vec<Metric>, CliError> {
+        http_client()
+            .get(&format!("{}/metrics", self.url))
+            .header("Authorization", &self.auth)
+            .header("SplinterProtocolVersion", METRICS_PROTOCOL_VERSION)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to fetch metrics: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<MetricsResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Metrics request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to fetch metrics: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Closes all connections to a peer of the Splinter node, optionally banning it from
+    /// reconnecting for the given number of seconds.
+    ///
+    /// Returns the number of connections that were closed.
+    #[cfg(feature = "peer-disconnect")]
+    pub fn disconnect_peer(&self, peer_id: &str, ban_secs: Option<u64>) -> Result<usize, CliError> {
+        http_client()
+            .post(&format!(
+                "{}/network/peers/{}/disconnect",
+                self.url, peer_id
+            ))
+            .header("Authorization", &self.auth)
+            .header("SplinterProtocolVersion", PEER_DISCONNECT_PROTOCOL_VERSION)
+            .json(&PeerDisconnectRequest { ban_secs })
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to disconnect peer: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<PeerDisconnectResponse>()
+                        .map(|response| response.connections_closed)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer disconnect request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to disconnect peer: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Pins a peer of the Splinter node to the given endpoint, so that all outbound connections
+    /// to the peer use that endpoint until it is unpinned.
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn pin_peer_endpoint(&self, peer_id: &str, endpoint: &str) -> Result<(), CliError> {
+        http_client()
+            .put(&format!("{}/network/peers/{}/endpoint", self.url, peer_id))
+            .header("Authorization", &self.auth)
+            .header(
+                "SplinterProtocolVersion",
+                PEER_ENDPOINT_PIN_PROTOCOL_VERSION,
+            )
+            .json(&PeerEndpointPinRequest { endpoint })
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to pin peer: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer pin request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to pin peer: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Removes the pinned endpoint of a peer of the Splinter node.
+    ///
+    /// Returns the previously pinned endpoint, or `None` if the peer was not pinned.
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn unpin_peer_endpoint(&self, peer_id: &str) -> Result<Option<String>, CliError> {
+        http_client()
+            .delete(&format!("{}/network/peers/{}/endpoint", self.url, peer_id))
+            .header("Authorization", &self.auth)
+            .header(
+                "SplinterProtocolVersion",
+                PEER_ENDPOINT_PIN_PROTOCOL_VERSION,
+            )
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to unpin peer: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<PeerEndpointPinResponse>()
+                        .map(|response| Some(response.endpoint))
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else if status == StatusCode::NOT_FOUND {
+                    Ok(None)
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer unpin request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to unpin peer: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Pings a peer of the Splinter node, waiting up to `timeout_secs` seconds for the reply if
+    /// given, or the node's default timeout otherwise.
+    ///
+    /// Returns the round-trip time in milliseconds.
+    #[cfg(feature = "peer-ping")]
+    pub fn ping_peer(&self, peer_id: &str, timeout_secs: Option<u64>) -> Result<f64, CliError> {
+        let mut request = http_client()
+            .post(&format!("{}/network/peers/{}/ping", self.url, peer_id))
+            .header("Authorization", &self.auth)
+            .header("SplinterProtocolVersion", PEER_PING_PROTOCOL_VERSION);
+        if let Some(timeout_secs) = timeout_secs {
+            request = request.query(&[("timeout", timeout_secs)]);
+        }
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to ping peer: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<PeerPingResponse>()
+                        .map(|response| response.rtt_ms)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer ping request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to ping peer: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     /// Checks whether or not maintenance mode is enabled for the Splinter node.
     #[cfg(feature = "authorization-handler-maintenance")]
     pub fn is_maintenance_mode_enabled(&self) -> Result<bool, CliError> {
-        Client::new()
+        http_client()
             .get(&format!("{}/authorization/maintenance", self.url))
             .header("Authorization", &self.auth)
             .send()
@@ -156,7 +497,7 @@ impl SplinterRestClient {
     /// Turns maintenance mode on or off for the Splinter node.
     #[cfg(feature = "authorization-handler-maintenance")]
     pub fn set_maintenance_mode(&self, enabled: bool) -> Result<(), CliError> {
-        Client::new()
+        http_client()
             .post(&format!("{}/authorization/maintenance", self.url))
             .query(&[("enabled", enabled)])
             .header("Authorization", &self.auth)
@@ -190,7 +531,7 @@ impl SplinterRestClient {
 
     /// Lists all REST API permissions for a Splinter node.
     pub fn list_permissions(&self) -> Result<Vec<Permission>, CliError> {
-        Client::new()
+        http_client()
             .get(&format!("{}/authorization/permissions", self.url))
             .header("Authorization", &self.auth)
             .send()
@@ -302,6 +643,122 @@ pub struct NodeStatus {
     pub network_endpoints: Vec<String>,
     pub advertised_endpoints: Vec<String>,
     pub version: String,
+    #[cfg(feature = "state-dir-health")]
+    #[serde(default)]
+    pub health: Option<String>,
+    #[cfg(feature = "state-dir-health")]
+    #[serde(default)]
+    pub state_dir: Option<StateDirUsage>,
+    #[cfg(feature = "node-metadata")]
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// An update to a node's display name and/or metadata; fields that are `None` are left unchanged.
+#[cfg(feature = "node-metadata")]
+#[derive(Default, Serialize)]
+pub struct NodeStatusUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, String>>,
+}
+
+#[cfg(feature = "state-dir-health")]
+#[derive(Deserialize)]
+pub struct StateDirUsage {
+    pub path: String,
+    pub total_bytes: u64,
+    pub lmdb_bytes: u64,
+    pub yaml_bytes: u64,
+    pub receipt_bytes: u64,
+    pub other_bytes: u64,
+    pub volume_size_bytes: Option<u64>,
+    pub volume_available_bytes: Option<u64>,
+    pub health: String,
+}
+
+#[cfg(feature = "health-service")]
+#[derive(Deserialize)]
+pub struct HealthStatus {
+    pub live: bool,
+    pub ready: bool,
+    pub status: Option<String>,
+    pub completed_at: Option<u64>,
+    pub checks: Vec<HealthCheck>,
+}
+
+#[cfg(feature = "health-service")]
+#[derive(Deserialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: String,
+    pub details: String,
+    pub duration_ms: u64,
+}
+
+#[cfg(feature = "metrics-snapshot")]
+#[derive(Deserialize)]
+struct MetricsResponse {
+    data: Vec<Metric>,
+}
+
+#[cfg(feature = "metrics-snapshot")]
+#[derive(Deserialize)]
+pub struct Metric {
+    pub name: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub value: MetricValue,
+}
+
+#[cfg(feature = "metrics-snapshot")]
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MetricValue {
+    Counter {
+        value: u64,
+    },
+    Gauge {
+        value: f64,
+    },
+    Histogram {
+        count: u64,
+        sum: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+#[cfg(feature = "peer-disconnect")]
+#[derive(Serialize)]
+struct PeerDisconnectRequest {
+    ban_secs: Option<u64>,
+}
+
+#[cfg(feature = "peer-disconnect")]
+#[derive(Deserialize)]
+struct PeerDisconnectResponse {
+    connections_closed: usize,
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+#[derive(Serialize)]
+struct PeerEndpointPinRequest<'a> {
+    endpoint: &'a str,
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+#[derive(Deserialize)]
+struct PeerEndpointPinResponse {
+    endpoint: String,
+}
+
+#[cfg(feature = "peer-ping")]
+#[derive(Deserialize)]
+struct PeerPingResponse {
+    rtt_ms: f64,
 }
 
 #[derive(Deserialize)]
@@ -314,4 +771,6 @@ pub struct Permission {
     pub permission_id: String,
     pub permission_display_name: String,
     pub permission_description: String,
+    #[serde(default)]
+    pub routes: Vec<String>,
 }
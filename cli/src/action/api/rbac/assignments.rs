@@ -12,10 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 
-use crate::action::api::ServerError;
+use crate::action::api::{http_client, ServerError};
 use crate::error::CliError;
 
 use super::{Pageable, RBAC_PROTOCOL_VERSION};
@@ -153,7 +152,7 @@ pub fn create_assignment(
     auth: &str,
     assignment: Assignment,
 ) -> Result<(), CliError> {
-    Client::new()
+    http_client()
         .post(&format!("{}/authorization/assignments", base_url))
         .header("SplinterProtocolVersion", RBAC_PROTOCOL_VERSION)
         .header("Authorization", auth)
@@ -195,7 +194,7 @@ pub fn get_assignment(
 ) -> Result<Option<Assignment>, CliError> {
     let (id_value, id_type) = identity.parts();
 
-    Client::new()
+    http_client()
         .get(&format!(
             "{}/authorization/assignments/{}/{}",
             base_url, id_type, id_value
@@ -250,7 +249,7 @@ pub fn update_assignment(
 ) -> Result<(), CliError> {
     let (id_value, id_type) = assignment_update.identity.parts();
 
-    Client::new()
+    http_client()
         .patch(&format!("{}/authorization/assignments/{}/{}", base_url, id_type, id_value))
         .header("SplinterProtocolVersion", RBAC_PROTOCOL_VERSION)
         .header("Authorization", auth)
@@ -293,7 +292,7 @@ pub fn update_assignment(
 pub fn delete_assignment(base_url: &str, auth: &str, identity: &Identity) -> Result<(), CliError> {
     let (id_value, id_type) = identity.parts();
 
-    Client::new()
+    http_client()
         .delete(&format!(
             "{}/authorization/assignments/{}/{}",
             base_url, id_type, id_value
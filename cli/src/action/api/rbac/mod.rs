@@ -17,9 +17,9 @@ pub mod roles;
 
 use std::collections::VecDeque;
 
-use reqwest::blocking::Client;
 use serde::{de::DeserializeOwned, Deserialize};
 
+use crate::action::api::http_client;
 use crate::CliError;
 
 const RBAC_PROTOCOL_VERSION: u32 = 1;
@@ -135,7 +135,7 @@ fn load_page<T>(base_url: &str, auth: &str, link: &str, label: &str) -> Result<P
 where
     T: DeserializeOwned,
 {
-    Client::new()
+    http_client()
         .get(&format!("{}{}", base_url, link))
         .header("SplinterProtocolVersion", RBAC_PROTOCOL_VERSION)
         .header("Authorization", auth)
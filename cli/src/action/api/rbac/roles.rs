@@ -14,10 +14,9 @@
 
 use std::fmt;
 
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 
-use crate::action::api::ServerError;
+use crate::action::api::{http_client, ServerError};
 use crate::error::CliError;
 
 use super::{Pageable, RBAC_PROTOCOL_VERSION};
@@ -189,7 +188,7 @@ struct RoleGet {
 }
 
 pub fn get_role(base_url: &str, auth: &str, role_id: &str) -> Result<Option<Role>, CliError> {
-    Client::new()
+    http_client()
         .get(&format!("{}/authorization/roles/{}", base_url, role_id))
         .header("SplinterProtocolVersion", RBAC_PROTOCOL_VERSION)
         .header("Authorization", auth)
@@ -230,7 +229,7 @@ pub fn get_role(base_url: &str, auth: &str, role_id: &str) -> Result<Option<Role
 }
 
 pub fn create_role(base_url: &str, auth: &str, role: Role) -> Result<(), CliError> {
-    Client::new()
+    http_client()
         .post(&format!("{}/authorization/roles", base_url))
         .header("SplinterProtocolVersion", RBAC_PROTOCOL_VERSION)
         .header("Authorization", auth)
@@ -264,7 +263,7 @@ pub fn create_role(base_url: &str, auth: &str, role: Role) -> Result<(), CliErro
 }
 
 pub fn update_role(base_url: &str, auth: &str, role_update: RoleUpdate) -> Result<(), CliError> {
-    Client::new()
+    http_client()
         .patch(&format!(
             "{}/authorization/roles/{}",
             base_url, role_update.role_id
@@ -306,7 +305,7 @@ pub fn update_role(base_url: &str, auth: &str, role_update: RoleUpdate) -> Resul
 }
 
 pub fn delete_role(base_url: &str, auth: &str, role_id: &str) -> Result<(), CliError> {
-    Client::new()
+    http_client()
         .delete(&format!("{}/authorization/roles/{}", base_url, role_id))
         .header("SplinterProtocolVersion", RBAC_PROTOCOL_VERSION)
         .header("Authorization", auth)
@@ -16,12 +16,12 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Write as _;
 
-use reqwest::{blocking::Client, header, StatusCode};
+use reqwest::{header, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::error::Result as JsonResult;
 use splinter::admin::messages::CircuitStatus;
 
-use crate::action::api::{ServerError, SplinterRestClient};
+use crate::action::api::{http_client, ServerError, SplinterRestClient};
 use crate::error::CliError;
 
 const PAGING_LIMIT: &str = "1000";
@@ -31,7 +31,7 @@ const CLI_ADMIN_PROTOCOL_VERSION: &str = "2";
 impl SplinterRestClient {
     /// Submits an admin payload to this client's Splinter node.
     pub fn submit_admin_payload(&self, payload: Vec<u8>) -> Result<(), CliError> {
-        Client::new()
+        http_client()
             .post(&format!("{}/admin/submit", self.url))
             .header(header::CONTENT_TYPE, "octet-stream")
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
@@ -65,10 +65,58 @@ impl SplinterRestClient {
             })
     }
 
+    /// Validates an admin payload against this client's Splinter node without submitting it.
+    #[cfg(feature = "circuit-propose-validate")]
+    pub fn validate_admin_payload(
+        &self,
+        payload: Vec<u8>,
+    ) -> Result<ProposalValidationSlice, CliError> {
+        http_client()
+            .post(&format!("{}/admin/proposals/validate", self.url))
+            .header(header::CONTENT_TYPE, "octet-stream")
+            .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .body(payload)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to validate admin payload: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<ProposalValidationSlice>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Admin payload validate request failed with status code '{}', \
+                                 but error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to validate admin payload: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     pub fn list_circuits(
         &self,
         member_filter: Option<&str>,
         status_filter: Option<&str>,
+        requester_filter: Option<&str>,
+        management_type_filter: Option<&str>,
+        service_type_filter: Option<&str>,
+        include_deleted: bool,
     ) -> Result<CircuitListSlice, CliError> {
         let mut url = format!("{}/admin/circuits?limit={}", self.url, PAGING_LIMIT);
         if let Some(member_filter) = member_filter {
@@ -77,8 +125,20 @@ impl SplinterRestClient {
         if let Some(status_filter) = status_filter {
             url = format!("{}&status={}", &url, &status_filter);
         }
+        if let Some(requester_filter) = requester_filter {
+            url = format!("{}&requester={}", &url, &requester_filter);
+        }
+        if let Some(management_type_filter) = management_type_filter {
+            url = format!("{}&management_type={}", &url, &management_type_filter);
+        }
+        if let Some(service_type_filter) = service_type_filter {
+            url = format!("{}&service_type={}", &url, &service_type_filter);
+        }
+        if include_deleted {
+            url = format!("{}&include_deleted=true", &url);
+        }
 
-        Client::new()
+        http_client()
             .get(&url)
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
             .header("Authorization", &self.auth)
@@ -113,7 +173,7 @@ impl SplinterRestClient {
     }
 
     pub fn fetch_circuit(&self, circuit_id: &str) -> Result<Option<CircuitSlice>, CliError> {
-        Client::new()
+        http_client()
             .get(&format!("{}/admin/circuits/{}", self.url, circuit_id))
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
             .header("Authorization", &self.auth)
@@ -149,10 +209,130 @@ impl SplinterRestClient {
             })
     }
 
+    /// Fetches the purge report for the given circuit: a preview of the local services that
+    /// purging the circuit would remove, or the results of a recent purge. Returns `None` if the
+    /// circuit does not exist and was not recently purged.
+    #[cfg(feature = "circuit-purge-preview")]
+    pub fn fetch_circuit_purge_report(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<CircuitPurgeReportSlice>, CliError> {
+        http_client()
+            .get(&format!("{}/admin/circuits/{}/purge", self.url, circuit_id))
+            .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to fetch circuit purge report: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<CircuitPurgeReportSlice>()
+                        .map(Some)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else if status == StatusCode::NOT_FOUND {
+                    Ok(None)
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Circuit purge report request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to fetch circuit purge report: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Fetches the circuit schema report: the number of circuits stored at each schema version,
+    /// and what migrating the circuits with an older version would change.
+    #[cfg(feature = "circuit-schema-migration")]
+    pub fn fetch_circuit_schema_report(&self) -> Result<CircuitSchemaReportSlice, CliError> {
+        self.handle_circuit_schema_response(
+            http_client()
+                .get(&format!("{}/admin/circuits/schema", self.url))
+                .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+                .header("Authorization", &self.auth)
+                .send(),
+            "fetch circuit schema report",
+        )
+    }
+
+    /// Migrates the circuits stored with an older schema version to the current one. If
+    /// `dry_run` is true, the report describes the changes without applying them.
+    #[cfg(feature = "circuit-schema-migration")]
+    pub fn migrate_circuit_schemas(
+        &self,
+        dry_run: bool,
+    ) -> Result<CircuitSchemaReportSlice, CliError> {
+        self.handle_circuit_schema_response(
+            http_client()
+                .post(&format!(
+                    "{}/admin/circuits/schema?dry_run={}",
+                    self.url, dry_run
+                ))
+                .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+                .header("Authorization", &self.auth)
+                .send(),
+            "migrate circuit schemas",
+        )
+    }
+
+    #[cfg(feature = "circuit-schema-migration")]
+    fn handle_circuit_schema_response(
+        &self,
+        response: Result<reqwest::blocking::Response, reqwest::Error>,
+        operation: &str,
+    ) -> Result<CircuitSchemaReportSlice, CliError> {
+        response
+            .map_err(|err| CliError::ActionError(format!("Failed to {}: {}", operation, err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<CircuitSchemaReportSlice>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Request to {} failed with status code '{}', but error response \
+                                 was not valid",
+                                operation, status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to {}: {}",
+                        operation, message
+                    )))
+                }
+            })
+    }
+
     pub fn list_proposals(
         &self,
         management_type_filter: Option<&str>,
         member_filter: Option<&str>,
+        requester_filter: Option<&str>,
+        vote_status_filter: Option<&str>,
     ) -> Result<ProposalListSlice, CliError> {
         let mut filters = vec![];
         if let Some(management_type) = management_type_filter {
@@ -161,6 +341,12 @@ impl SplinterRestClient {
         if let Some(member) = member_filter {
             filters.push(format!("member={}", member));
         }
+        if let Some(requester) = requester_filter {
+            filters.push(format!("requester={}", requester));
+        }
+        if let Some(vote_status) = vote_status_filter {
+            filters.push(format!("vote_status={}", vote_status));
+        }
 
         let mut url = format!("{}/admin/proposals?limit={}", self.url, PAGING_LIMIT);
         if !filters.is_empty() {
@@ -168,7 +354,7 @@ impl SplinterRestClient {
                 .map_err(|e| CliError::ActionError(e.to_string()))?;
         }
 
-        Client::new()
+        http_client()
             .get(&url)
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
             .header("Authorization", &self.auth)
@@ -203,7 +389,7 @@ impl SplinterRestClient {
     }
 
     pub fn fetch_proposal(&self, circuit_id: &str) -> Result<Option<ProposalSlice>, CliError> {
-        Client::new()
+        http_client()
             .get(&format!("{}/admin/proposals/{}", self.url, circuit_id))
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
             .header("Authorization", &self.auth)
@@ -238,6 +424,128 @@ impl SplinterRestClient {
                 }
             })
     }
+
+    /// Adds a signed vote delegation, given as a serialized `VoteDelegation` protobuf message,
+    /// to this client's Splinter node.
+    #[cfg(feature = "circuit-vote-delegation")]
+    pub fn add_vote_delegation(&self, delegation: Vec<u8>) -> Result<(), CliError> {
+        http_client()
+            .post(&format!("{}/admin/vote_delegations", self.url))
+            .header(header::CONTENT_TYPE, "octet-stream")
+            .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .body(delegation)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to add vote delegation: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Vote delegation add request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to add vote delegation: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the vote delegations held by this client's Splinter node.
+    #[cfg(feature = "circuit-vote-delegation")]
+    pub fn list_vote_delegations(&self) -> Result<Vec<VoteDelegationSlice>, CliError> {
+        http_client()
+            .get(&format!("{}/admin/vote_delegations", self.url))
+            .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to list vote delegations: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<VoteDelegationListSlice>()
+                        .map(|list| list.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Vote delegation list request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to list vote delegations: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Removes the vote delegation between the given hex-encoded public keys. Returns false if
+    /// the node does not hold such a delegation.
+    #[cfg(feature = "circuit-vote-delegation")]
+    pub fn remove_vote_delegation(
+        &self,
+        delegator: &str,
+        delegate: &str,
+    ) -> Result<bool, CliError> {
+        http_client()
+            .delete(&format!(
+                "{}/admin/vote_delegations/{}/{}",
+                self.url, delegator, delegate
+            ))
+            .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to remove vote delegation: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(true)
+                } else if status == StatusCode::NOT_FOUND {
+                    Ok(false)
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Vote delegation remove request failed with status code '{}', \
+                                 but error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to remove vote delegation: {}",
+                        message
+                    )))
+                }
+            })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -249,6 +557,12 @@ pub struct CircuitSlice {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: Option<CircuitStatus>,
+    #[cfg(feature = "circuit-tombstones")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_reason: Option<String>,
 }
 
 impl fmt::Display for CircuitSlice {
@@ -267,6 +581,11 @@ impl fmt::Display for CircuitSlice {
             display_string += "    Circuit Status: Active\n";
         }
 
+        #[cfg(feature = "circuit-tombstones")]
+        if let Some(reason) = &self.deleted_reason {
+            writeln!(display_string, "    Deleted: {}", reason)?;
+        }
+
         writeln!(
             display_string,
             "    Schema Version: {}\n    Management Type: {}",
@@ -329,6 +648,75 @@ pub struct CircuitServiceSlice {
     pub arguments: BTreeMap<String, String>,
 }
 
+#[cfg(feature = "circuit-purge-preview")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CircuitPurgeReportSlice {
+    pub circuit_id: String,
+    pub purged: bool,
+    pub services: Vec<ServicePurgeReportSlice>,
+}
+
+#[cfg(feature = "circuit-purge-preview")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ServicePurgeReportSlice {
+    pub service_id: String,
+    pub service_type: String,
+    pub state_size: Option<u64>,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "circuit-schema-migration")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CircuitSchemaReportSlice {
+    pub dry_run: bool,
+    pub versions: BTreeMap<String, usize>,
+    pub circuits: Vec<CircuitSchemaMigrationSlice>,
+}
+
+#[cfg(feature = "circuit-schema-migration")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CircuitSchemaMigrationSlice {
+    pub circuit_id: String,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub eligible: bool,
+    pub reason: Option<String>,
+    pub migrated: bool,
+    pub changes: Vec<CircuitFieldChangeSlice>,
+}
+
+#[cfg(feature = "circuit-schema-migration")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CircuitFieldChangeSlice {
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[cfg(feature = "circuit-propose-validate")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProposalValidationSlice {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[cfg(feature = "circuit-vote-delegation")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct VoteDelegationSlice {
+    pub delegator_public_key: String,
+    pub delegate_public_key: String,
+    pub node_id: String,
+    pub circuit_management_types: Vec<String>,
+}
+
+#[cfg(feature = "circuit-vote-delegation")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct VoteDelegationListSlice {
+    data: Vec<VoteDelegationSlice>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct CircuitListSlice {
     pub data: Vec<CircuitSlice>,
@@ -344,6 +732,9 @@ pub struct ProposalSlice {
     pub votes: Vec<VoteRecord>,
     pub requester: String,
     pub requester_node_id: String,
+    #[cfg(feature = "circuit-rejection-reasons")]
+    #[serde(default)]
+    pub rejection: Option<ProposalRejection>,
 }
 
 impl fmt::Display for ProposalSlice {
@@ -372,6 +763,19 @@ impl fmt::Display for ProposalSlice {
             self.circuit.circuit_version, self.circuit.management_type
         )?;
 
+        if let Some(max_message_size) = self.circuit.max_message_size {
+            writeln!(display_string, "    Max Message Size: {}", max_message_size)?;
+        }
+
+        if !self.circuit.attachments.is_empty() {
+            display_string += "    Attachments:\n";
+            for attachment in self.circuit.attachments.iter() {
+                writeln!(display_string, "        {}", attachment.name)?;
+                writeln!(display_string, "            SHA-256: {}", attachment.sha256)?;
+                writeln!(display_string, "            URI: {}", attachment.uri)?;
+            }
+        }
+
         for member in self.circuit.members.iter() {
             write!(display_string, "\n    {}\n", member.node_id)?;
             if let Some(public_key) = &member.public_key {
@@ -385,7 +789,11 @@ impl fmt::Display for ProposalSlice {
                 for vote in self.votes.iter() {
                     if vote.voter_node_id == member.node_id {
                         vote_string =
-                            format!("        Vote: ACCEPT\n             {}", vote.public_key)
+                            format!("        Vote: ACCEPT\n             {}", vote.public_key);
+                        #[cfg(feature = "circuit-rejection-reasons")]
+                        if let Some(comment) = &vote.comment {
+                            write!(vote_string, "\n        Comment: {}", comment)?;
+                        }
                     }
                 }
                 writeln!(display_string, "{}", vote_string)?;
@@ -429,10 +837,43 @@ impl fmt::Display for ProposalSlice {
             }
         }
 
+        #[cfg(feature = "circuit-rejection-reasons")]
+        match &self.rejection {
+            Some(ProposalRejection::ValidationFailed { node_id, reason }) => {
+                write!(
+                    display_string,
+                    "\n    Rejected: failed validation on {}\n        Reason: {}\n",
+                    node_id, reason
+                )?;
+            }
+            Some(ProposalRejection::Vote(vote)) => {
+                write!(
+                    display_string,
+                    "\n    Rejected: voted down by {}\n        {}\n",
+                    vote.voter_node_id, vote.public_key
+                )?;
+                if let Some(comment) = &vote.comment {
+                    writeln!(display_string, "        Comment: {}", comment)?;
+                }
+            }
+            None => (),
+        }
+
         write!(f, "{}", display_string)
     }
 }
 
+/// The reason a circuit proposal was rejected
+#[cfg(feature = "circuit-rejection-reasons")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum ProposalRejection {
+    /// The proposal failed validation on a member node
+    ValidationFailed { node_id: String, reason: String },
+    /// A member voted to reject the proposal
+    Vote(VoteRecord),
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ProposalCircuitSlice {
     pub circuit_id: String,
@@ -443,6 +884,18 @@ pub struct ProposalCircuitSlice {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: Option<CircuitStatus>,
+    #[serde(default)]
+    pub attachments: Vec<ProposalAttachment>,
+    #[serde(default)]
+    pub max_message_size: Option<u64>,
+}
+
+/// A document attached to a circuit proposal
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProposalAttachment {
+    pub name: String,
+    pub sha256: String,
+    pub uri: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -471,6 +924,9 @@ pub struct VoteRecord {
     pub public_key: String,
     pub vote: String,
     pub voter_node_id: String,
+    #[cfg(feature = "circuit-rejection-reasons")]
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -718,6 +1174,10 @@ mod tests {
             display_name: Some("circuit_scabbard".into()),
             circuit_version: 2,
             circuit_status: Some(CircuitStatus::Active),
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_at: None,
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_reason: None,
         };
         assert_eq!(format!("{}", circuit), CIRCUIT_STRING);
     }
@@ -781,6 +1241,10 @@ mod tests {
             display_name: None,
             circuit_version: 2,
             circuit_status: None,
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_at: None,
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_reason: None,
         };
         assert_eq!(format!("{}", circuit), CIRCUIT_NONE_STRING);
     }
@@ -850,6 +1314,8 @@ mod tests {
             circuit_version: 2,
             circuit_status: Some(CircuitStatus::Active),
             comments: None,
+            attachments: vec![],
+            max_message_size: None,
         };
 
         let proposal = ProposalSlice {
@@ -860,6 +1326,8 @@ mod tests {
             votes: vec![],
             requester: "03f91f722329b99234be43f962e7ce33bbd4f2e72634a1a68f12ad908ca5693f03".into(),
             requester_node_id: "n20959".into(),
+            #[cfg(feature = "circuit-rejection-reasons")]
+            rejection: None,
         };
 
         assert_eq!(format!("{}", proposal), PROPOSAL_STRING);
@@ -926,6 +1394,8 @@ mod tests {
             circuit_version: 2,
             circuit_status: None,
             comments: None,
+            attachments: vec![],
+            max_message_size: None,
         };
 
         let proposal = ProposalSlice {
@@ -936,6 +1406,8 @@ mod tests {
             votes: vec![],
             requester: "03f91f722329b99234be43f962e7ce33bbd4f2e72634a1a68f12ad908ca5693f03".into(),
             requester_node_id: "n20959".into(),
+            #[cfg(feature = "circuit-rejection-reasons")]
+            rejection: None,
         };
 
         assert_eq!(format!("{}", proposal), PROPOSAL_NONE_STRING);
@@ -1006,6 +1478,8 @@ mod tests {
             circuit_version: 2,
             circuit_status: Some(CircuitStatus::Active),
             comments: None,
+            attachments: vec![],
+            max_message_size: None,
         };
 
         let proposal = ProposalSlice {
@@ -1018,9 +1492,13 @@ mod tests {
                     .into(),
                 vote: "Accepted".into(),
                 voter_node_id: "n8198".into(),
+                #[cfg(feature = "circuit-rejection-reasons")]
+                comment: None,
             }],
             requester: "03f91f722329b99234be43f962e7ce33bbd4f2e72634a1a68f12ad908ca5693f03".into(),
             requester_node_id: "n20959".into(),
+            #[cfg(feature = "circuit-rejection-reasons")]
+            rejection: None,
         };
 
         assert_eq!(format!("{}", proposal), PROPOSAL_VOTE_STRING);
@@ -16,8 +16,8 @@ use std::fmt::Write as _;
 
 use splinter::admin::messages::AuthorizationType;
 use splinter::admin::messages::{
-    BuilderError, CircuitStatus, CreateCircuit, CreateCircuitBuilder, SplinterNode,
-    SplinterNodeBuilder, SplinterServiceBuilder,
+    BuilderError, CircuitAttachment, CircuitStatus, CreateCircuit, CreateCircuitBuilder,
+    SplinterNode, SplinterNodeBuilder, SplinterServiceBuilder,
 };
 
 use crate::error::CliError;
@@ -37,6 +37,8 @@ pub struct CreateCircuitMessageBuilder {
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
+    attachments: Vec<CircuitAttachment>,
+    max_message_size: Option<u64>,
 }
 
 impl CreateCircuitMessageBuilder {
@@ -52,6 +54,8 @@ impl CreateCircuitMessageBuilder {
             display_name: None,
             circuit_version: None,
             circuit_status: None,
+            attachments: vec![],
+            max_message_size: None,
         }
     }
 
@@ -215,6 +219,36 @@ impl CreateCircuitMessageBuilder {
         Ok(())
     }
 
+    /// Adds a public key that may authorize in place of the given node's public key.
+    #[cfg(feature = "challenge-trust-policy")]
+    pub fn add_authorized_public_key(
+        &mut self,
+        node_id: &str,
+        public_key: &str,
+    ) -> Result<(), CliError> {
+        let node = self
+            .nodes
+            .iter_mut()
+            .find(|node| node.node_id == node_id)
+            .ok_or_else(|| {
+                CliError::ActionError(format!(
+                    "Authorized public key given for unknown node: {}",
+                    node_id
+                ))
+            })?;
+
+        let public_key = parse_hex(public_key)?;
+        if node.authorized_public_keys.contains(&public_key) {
+            return Err(CliError::ActionError(format!(
+                "Duplicate authorized public key detected for node {}",
+                node_id
+            )));
+        }
+        node.authorized_public_keys.push(public_key);
+
+        Ok(())
+    }
+
     pub fn set_management_type(&mut self, management_type: &str) {
         self.management_type = Some(management_type.into());
     }
@@ -255,6 +289,42 @@ impl CreateCircuitMessageBuilder {
         self.circuit_status = Some(circuit_status);
     }
 
+    /// Attaches a document to the proposal, identified by the sha256 hash of its contents.
+    #[cfg(feature = "circuit-attachments")]
+    pub fn add_attachment(&mut self, name: &str, sha256: &str, uri: &str) -> Result<(), CliError> {
+        if self
+            .attachments
+            .iter()
+            .any(|attachment| attachment.name == name)
+        {
+            return Err(CliError::ActionError(format!(
+                "Duplicate attachment name detected: {}",
+                name
+            )));
+        }
+
+        if !is_sha256(sha256) {
+            return Err(CliError::ActionError(format!(
+                "Invalid sha256 hash for attachment '{}': must be 64 hex characters",
+                name
+            )));
+        }
+
+        self.attachments.push(CircuitAttachment {
+            name: name.into(),
+            sha256: sha256.to_lowercase(),
+            uri: uri.into(),
+        });
+
+        Ok(())
+    }
+
+    /// Sets the largest message, in bytes, the circuit will route between its services.
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn set_max_message_size(&mut self, max_message_size: u64) {
+        self.max_message_size = Some(max_message_size);
+    }
+
     pub fn build(mut self) -> Result<CreateCircuit, CliError> {
         let circuit_builder = self.create_circuit_builder();
 
@@ -331,6 +401,17 @@ impl CreateCircuitMessageBuilder {
             create_circuit_builder = create_circuit_builder.with_circuit_status(&circuit_status);
         }
 
+        let mut attachments = circuit_builder.attachments();
+        attachments.extend(self.attachments);
+        create_circuit_builder = create_circuit_builder.with_attachments(&attachments);
+
+        if let Some(max_message_size) = self
+            .max_message_size
+            .or_else(|| circuit_builder.max_message_size())
+        {
+            create_circuit_builder = create_circuit_builder.with_max_message_size(max_message_size);
+        }
+
         let create_circuit_builder = match self.authorization_type {
             Some(authorization_type) => {
                 create_circuit_builder.with_authorization_type(&authorization_type)
@@ -405,6 +486,12 @@ fn make_splinter_node(
     Ok(node)
 }
 
+/// Returns whether the given string is a hex-encoded sha256 hash.
+#[cfg(feature = "circuit-attachments")]
+pub fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 pub fn parse_hex(hex: &str) -> Result<Vec<u8>, CliError> {
     if hex.len() % 2 != 0 {
         return Err(CliError::ActionError(format!(
@@ -0,0 +1,251 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Actions for managing vote delegations, which allow a delegate key to vote on circuit
+//! proposals in place of a delegator key.
+
+use clap::ArgMatches;
+use cylinder::Signer;
+use protobuf::{Message, RepeatedField};
+use splinter::protos::admin::{VoteDelegation, VoteDelegation_Header};
+
+use crate::circuit::builder::parse_hex;
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::super::api::{SplinterRestClient, SplinterRestClientBuilder};
+use super::super::{print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+use super::to_hex;
+
+pub struct CircuitDelegationAddAction;
+
+impl Action for CircuitDelegationAddAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = build_client(args, signer.clone())?;
+
+        let delegate = args
+            .value_of("delegate")
+            .ok_or_else(|| CliError::ActionError("'delegate' argument is required".into()))?;
+        let delegate = parse_hex(delegate).map_err(|_| {
+            CliError::ActionError(format!("Invalid delegate public key: {}", delegate))
+        })?;
+
+        let management_types = args
+            .values_of("management_type")
+            .ok_or_else(|| CliError::ActionError("'management-type' argument is required".into()))?
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+
+        let node_id = match args.value_of("node_id") {
+            Some(node_id) => node_id.to_string(),
+            None => client.get_node_status()?.node_id,
+        };
+
+        let delegation = make_signed_delegation(&*signer, delegate, &node_id, management_types)?;
+        client.add_vote_delegation(delegation)?;
+
+        info!("Vote delegation added for node {}", node_id);
+        Ok(())
+    }
+}
+
+pub struct CircuitDelegationListAction;
+
+impl Action for CircuitDelegationListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = build_client(args, signer)?;
+
+        let delegations = client.list_vote_delegations()?;
+
+        match args.value_of("format") {
+            Some("json") => println!(
+                "\n {}",
+                serde_json::to_string(&delegations).map_err(|err| CliError::ActionError(
+                    format!("Cannot format vote delegations into json: {}", err)
+                ))?
+            ),
+            _ => {
+                let mut data = vec![vec![
+                    "DELEGATOR".to_string(),
+                    "DELEGATE".to_string(),
+                    "NODE".to_string(),
+                    "MANAGEMENT TYPES".to_string(),
+                ]];
+                for delegation in delegations {
+                    data.push(vec![
+                        delegation.delegator_public_key,
+                        delegation.delegate_public_key,
+                        delegation.node_id,
+                        delegation.circuit_management_types.join(","),
+                    ]);
+                }
+                print_table(data);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CircuitDelegationRemoveAction;
+
+impl Action for CircuitDelegationRemoveAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let delegator = match args.value_of("delegator") {
+            Some(delegator) => delegator.to_string(),
+            None => to_hex(
+                signer
+                    .public_key()
+                    .map_err(|err| {
+                        CliError::ActionError(format!("Unable to get public key: {}", err))
+                    })?
+                    .as_slice(),
+            ),
+        };
+        let delegate = args
+            .value_of("delegate")
+            .ok_or_else(|| CliError::ActionError("'delegate' argument is required".into()))?;
+
+        let client = build_client(args, signer)?;
+
+        if client.remove_vote_delegation(&delegator, delegate)? {
+            info!("Vote delegation from {} to {} removed", delegator, delegate);
+            Ok(())
+        } else {
+            Err(CliError::ActionError(format!(
+                "No vote delegation from {} to {} was found",
+                delegator, delegate
+            )))
+        }
+    }
+}
+
+fn build_client(
+    args: &ArgMatches,
+    signer: Box<dyn Signer>,
+) -> Result<SplinterRestClient, CliError> {
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+/// Creates a serialized `VoteDelegation`, signed by the delegator's signer.
+fn make_signed_delegation(
+    delegator: &dyn Signer,
+    delegate_public_key: Vec<u8>,
+    node_id: &str,
+    circuit_management_types: Vec<String>,
+) -> Result<Vec<u8>, CliError> {
+    let delegator_public_key = delegator
+        .public_key()
+        .map_err(|err| CliError::ActionError(format!("Unable to get public key: {}", err)))?
+        .into_bytes();
+    if delegator_public_key == delegate_public_key {
+        return Err(CliError::ActionError(
+            "A key cannot delegate its vote to itself".into(),
+        ));
+    }
+
+    let mut header = VoteDelegation_Header::new();
+    header.set_delegator_public_key(delegator_public_key);
+    header.set_delegate_public_key(delegate_public_key);
+    header.set_node_id(node_id.to_string());
+    header.set_circuit_management_types(RepeatedField::from_vec(circuit_management_types));
+    let header = header.write_to_bytes().map_err(|err| {
+        CliError::ActionError(format!("Unable to serialize vote delegation: {}", err))
+    })?;
+
+    let signature = delegator
+        .sign(&header)
+        .map_err(|err| CliError::ActionError(format!("Unable to sign vote delegation: {}", err)))?
+        .take_bytes();
+
+    let mut delegation = VoteDelegation::new();
+    delegation.set_header(header);
+    delegation.set_signature(signature);
+    delegation.write_to_bytes().map_err(|err| {
+        CliError::ActionError(format!("Unable to serialize vote delegation: {}", err))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    /// Verify that the created delegation carries a header signed by the delegator key.
+    #[test]
+    fn test_make_signed_delegation() {
+        let context = Secp256k1Context::new();
+        let delegator = context.new_signer(context.new_random_private_key());
+        let delegate = context
+            .get_public_key(&context.new_random_private_key())
+            .expect("Unable to get public key")
+            .into_bytes();
+
+        let bytes = make_signed_delegation(
+            &*delegator,
+            delegate.clone(),
+            "node-a",
+            vec!["gameroom".into()],
+        )
+        .expect("Unable to make delegation");
+
+        let delegation: VoteDelegation =
+            Message::parse_from_bytes(&bytes).expect("Unable to parse delegation");
+        let header: VoteDelegation_Header =
+            Message::parse_from_bytes(delegation.get_header()).expect("Unable to parse header");
+        assert_eq!(header.get_delegate_public_key(), delegate.as_slice());
+        assert_eq!(header.get_node_id(), "node-a");
+        assert_eq!(
+            header.get_circuit_management_types(),
+            ["gameroom".to_string()]
+        );
+
+        assert!(context
+            .new_verifier()
+            .verify(
+                delegation.get_header(),
+                &cylinder::Signature::new(delegation.get_signature().to_vec()),
+                &delegator.public_key().expect("Unable to get public key"),
+            )
+            .expect("Unable to verify signature"));
+
+        // a key cannot delegate to itself
+        assert!(make_signed_delegation(
+            &*delegator,
+            delegator
+                .public_key()
+                .expect("Unable to get public key")
+                .into_bytes(),
+            "node-a",
+            vec!["gameroom".into()],
+        )
+        .is_err());
+    }
+}
@@ -14,14 +14,26 @@
 
 mod api;
 mod builder;
+#[cfg(feature = "circuit-vote-delegation")]
+pub mod delegation;
 mod payload;
+#[cfg(feature = "circuit-schema-migration")]
+pub mod schema;
 #[cfg(feature = "circuit-template")]
 pub mod template;
+#[cfg(feature = "circuit-propose-wait")]
+mod wait;
+#[cfg(feature = "circuit-proposals-watch")]
+mod watch;
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::Write;
 use std::fs::File;
+#[cfg(feature = "circuit-purge-preview")]
+use std::io::{self, BufRead};
+#[cfg(any(feature = "circuit-propose-wait", feature = "circuit-proposals-watch"))]
+use std::time::Duration;
 
 use clap::ArgMatches;
 use cylinder::Signer;
@@ -37,15 +49,21 @@ use crate::signing::{create_cylinder_jwt_auth, load_signer};
 #[cfg(feature = "circuit-template")]
 use crate::template::CircuitTemplate;
 
-use super::api::SplinterRestClientBuilder;
+use super::api::{SplinterRestClient, SplinterRestClientBuilder};
 use super::{
     msg_from_io_error, print_table, Action, DEFAULT_SPLINTER_REST_API_URL,
     SPLINTER_REST_API_URL_ENV,
 };
 
+#[cfg(feature = "circuit-purge-preview")]
+use super::format_size;
+#[cfg(feature = "circuit-purge-preview")]
+use api::CircuitPurgeReportSlice;
 use api::{CircuitMembers, CircuitServiceSlice, CircuitSlice};
 pub(crate) use builder::CreateCircuitMessageBuilder;
 use payload::make_signed_payload;
+#[cfg(any(feature = "circuit-propose-timeout", feature = "circuit-propose-retry"))]
+use payload::{make_signed_payload_with_options, HeaderOptions};
 
 pub struct CircuitProposeAction;
 
@@ -58,11 +76,20 @@ impl Action for CircuitProposeAction {
         let mut public_keys = HashMap::new();
         if let Some(nodes_public_keys) = args.values_of("node_public_key") {
             for node_argument in nodes_public_keys {
-                let (node, public_key) = parse_node_public_key(node_argument)?;
+                let (node, public_key) = parse_node_public_key(node_argument, "node-public-key")?;
                 public_keys.insert(node, public_key);
             }
         }
 
+        #[cfg(feature = "challenge-trust-policy")]
+        let mut authorized_keys: Vec<(String, String)> = vec![];
+        #[cfg(feature = "challenge-trust-policy")]
+        if let Some(nodes_keys) = args.values_of("node_key") {
+            for node_argument in nodes_keys {
+                authorized_keys.push(parse_node_public_key(node_argument, "node-key")?);
+            }
+        }
+
         if let Some(node_file) = args.value_of("node_file") {
             for node in load_nodes_from_file(node_file)? {
                 builder.add_node(
@@ -82,6 +109,11 @@ impl Action for CircuitProposeAction {
             }
         }
 
+        #[cfg(feature = "challenge-trust-policy")]
+        for (node, public_key) in authorized_keys {
+            builder.add_authorized_public_key(&node, &public_key)?;
+        }
+
         #[cfg(feature = "circuit-template")]
         {
             if let Some(template_name) = args.value_of("template") {
@@ -197,6 +229,40 @@ impl Action for CircuitProposeAction {
             builder.set_display_name(display_name);
         }
 
+        #[cfg(feature = "circuit-attachments")]
+        if let Some(attachments) = args.values_of("attachment") {
+            if args.value_of("compat_version") == Some("0.4") {
+                return Err(CliError::ActionError(
+                    "Attachments are not compatible with Splinter v0.4".to_string(),
+                ));
+            }
+            for attachment in attachments {
+                let (name, sha256, uri) = parse_attachment(attachment)?;
+                builder.add_attachment(&name, &sha256, &uri)?;
+            }
+        }
+
+        #[cfg(feature = "circuit-max-message-size")]
+        if let Some(max_message_size) = args.value_of("max_message_size") {
+            if args.value_of("compat_version") == Some("0.4") {
+                return Err(CliError::ActionError(
+                    "Max message size is not compatible with Splinter v0.4".to_string(),
+                ));
+            }
+            let max_message_size = max_message_size.parse::<u64>().map_err(|_| {
+                CliError::ActionError(format!(
+                    "Invalid max message size '{}': must be a positive integer",
+                    max_message_size
+                ))
+            })?;
+            if max_message_size == 0 {
+                return Err(CliError::ActionError(
+                    "Max message size must be greater than 0".to_string(),
+                ));
+            }
+            builder.set_max_message_size(max_message_size);
+        }
+
         if args.value_of("compat_version") != Some("0.4") {
             builder.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
             builder.set_circuit_status(CircuitStatus::Active);
@@ -206,7 +272,8 @@ impl Action for CircuitProposeAction {
 
         let circuit_slice = CircuitSlice::try_from(&create_circuit)?;
 
-        if !args.is_present("dry_run") {
+        // With proposal validation, a dry run is checked by the node rather than only printed
+        if !args.is_present("dry_run") || cfg!(feature = "circuit-propose-validate") {
             let url = args
                 .value_of("url")
                 .map(ToOwned::to_owned)
@@ -222,13 +289,88 @@ impl Action for CircuitProposeAction {
 
             let requester_node = client.get_node_status()?.node_id;
 
+            #[cfg(not(any(
+                feature = "circuit-propose-timeout",
+                feature = "circuit-propose-retry"
+            )))]
             let signed_payload = make_signed_payload(&requester_node, signer, create_circuit)?;
+            #[cfg(any(feature = "circuit-propose-timeout", feature = "circuit-propose-retry"))]
+            let signed_payload = {
+                let options = HeaderOptions {
+                    #[cfg(feature = "circuit-propose-timeout")]
+                    coordinator_timeout: parse_optional_arg(
+                        args,
+                        "timeout",
+                        "coordinator timeout in seconds",
+                    )?,
+                    #[cfg(feature = "circuit-propose-retry")]
+                    verification_timeout: parse_optional_arg(
+                        args,
+                        "verification_timeout",
+                        "verification timeout in seconds",
+                    )?,
+                    #[cfg(feature = "circuit-propose-retry")]
+                    verification_retries: parse_optional_arg(
+                        args,
+                        "verification_retries",
+                        "number of verification retries",
+                    )?,
+                    #[cfg(feature = "circuit-propose-retry")]
+                    verification_backoff: parse_optional_arg(
+                        args,
+                        "verification_backoff",
+                        "verification backoff factor",
+                    )?,
+                };
+                make_signed_payload_with_options(&requester_node, signer, create_circuit, options)?
+            };
+
+            #[cfg(feature = "circuit-propose-validate")]
+            {
+                if args.is_present("dry_run") {
+                    let validation = client.validate_admin_payload(signed_payload)?;
+                    for warning in &validation.warnings {
+                        warn!("Warning: {}", warning);
+                    }
+                    for error in &validation.errors {
+                        error!("Error: {}", error);
+                    }
+                    info!("{}", circuit_slice);
+                    if !validation.valid {
+                        return Err(CliError::ActionError(
+                            "The circuit proposal is not valid".into(),
+                        ));
+                    }
+                    info!("The circuit proposal is valid");
+                    return Ok(());
+                }
+            }
+
             client.submit_admin_payload(signed_payload)?;
 
             info!("The circuit proposal was submitted successfully");
-        }
+            info!("{}", circuit_slice);
 
-        info!("{}", circuit_slice);
+            #[cfg(feature = "circuit-propose-wait")]
+            {
+                if let Some(wait) = args.value_of("wait") {
+                    let wait = wait.parse::<u64>().map_err(|_| {
+                        CliError::ActionError(format!(
+                            "'{}' is not a valid number of seconds to wait",
+                            wait
+                        ))
+                    })?;
+                    wait::wait_for_circuit(
+                        &client,
+                        &circuit_slice.id,
+                        &requester_node,
+                        Duration::from_secs(wait),
+                    )?;
+                }
+            }
+        } else {
+            info!("{}", circuit_slice);
+        }
 
         Ok(())
     }
@@ -334,7 +476,10 @@ fn parse_node_argument(node_argument: &str) -> Result<(String, Vec<String>), Cli
     Ok((node_id, endpoints))
 }
 
-fn parse_node_public_key(node_argument: &str) -> Result<(String, String), CliError> {
+fn parse_node_public_key(
+    node_argument: &str,
+    arg_name: &str,
+) -> Result<(String, String), CliError> {
     let mut iter = node_argument.split("::");
 
     let node_id = iter
@@ -342,9 +487,10 @@ fn parse_node_public_key(node_argument: &str) -> Result<(String, String), CliErr
         .expect("str::split cannot return an empty iterator")
         .to_string();
     if node_id.is_empty() {
-        return Err(CliError::ActionError(
-            "Empty '--node-public-key' argument detected".into(),
-        ));
+        return Err(CliError::ActionError(format!(
+            "Empty '--{}' argument detected",
+            arg_name
+        )));
     }
 
     let public_key = iter
@@ -578,6 +724,55 @@ fn parse_service_type_argument(service_type: &str) -> Result<(String, String), C
     Ok((service_id, service_type))
 }
 
+/// Parses an attachment argument of the form `<name>,<sha256-or-file>,<uri>`. If the second
+/// value is not a sha256 hash, it is treated as the path of the attached document and the hash
+/// is computed from the file's contents.
+#[cfg(feature = "circuit-attachments")]
+fn parse_attachment(attachment: &str) -> Result<(String, String, String), CliError> {
+    let mut iter = attachment.splitn(3, ',');
+
+    let name = iter
+        .next()
+        .expect("str::splitn cannot return an empty iterator")
+        .to_string();
+    if name.is_empty() {
+        return Err(CliError::ActionError(
+            "Empty '--attachment' argument detected".into(),
+        ));
+    }
+
+    let hash_or_file = iter
+        .next()
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Missing sha256 hash or file for attachment '{}'",
+                name
+            ))
+        })?;
+
+    let uri = iter
+        .next()
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| CliError::ActionError(format!("Missing URI for attachment '{}'", name)))?
+        .to_string();
+
+    let sha256 = if builder::is_sha256(hash_or_file) {
+        hash_or_file.to_lowercase()
+    } else {
+        let contents = std::fs::read(hash_or_file).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to read attachment file '{}': {}",
+                hash_or_file,
+                msg_from_io_error(err)
+            ))
+        })?;
+        to_hex(&openssl::sha::sha256(&contents))
+    };
+
+    Ok((name, sha256, uri))
+}
+
 impl TryFrom<&CreateCircuit> for CircuitSlice {
     type Error = CliError;
 
@@ -598,6 +793,10 @@ impl TryFrom<&CreateCircuit> for CircuitSlice {
             display_name: circuit.display_name.clone(),
             circuit_version: circuit.circuit_version,
             circuit_status: Some(circuit.circuit_status.clone()),
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_at: None,
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_reason: None,
         })
     }
 }
@@ -639,6 +838,22 @@ impl TryFrom<&SplinterNode> for CircuitMembers {
     }
 }
 
+/// Parses the value of an optional numeric argument, naming what it should be if it is invalid.
+#[cfg(any(feature = "circuit-propose-timeout", feature = "circuit-propose-retry"))]
+fn parse_optional_arg<T: std::str::FromStr>(
+    args: &ArgMatches,
+    name: &str,
+    description: &str,
+) -> Result<Option<T>, CliError> {
+    args.value_of(name)
+        .map(|value| {
+            value.parse::<T>().map_err(|_| {
+                CliError::ActionError(format!("'{}' is not a valid {}", value, description))
+            })
+        })
+        .transpose()
+}
+
 fn to_hex(bytes: &[u8]) -> String {
     let mut buf = String::new();
     for b in bytes {
@@ -657,6 +872,8 @@ struct CircuitVote {
     circuit_id: String,
     circuit_hash: String,
     vote: Vote,
+    #[cfg(feature = "circuit-rejection-reasons")]
+    comment: Option<String>,
 }
 
 pub struct CircuitVoteAction;
@@ -685,7 +902,17 @@ impl Action for CircuitVoteAction {
             }
         };
 
-        vote_on_circuit_proposal(&url, signer, circuit_id, vote)
+        #[cfg(feature = "circuit-rejection-reasons")]
+        let comment = args.value_of("comment").map(ToOwned::to_owned);
+
+        vote_on_circuit_proposal(
+            &url,
+            signer,
+            circuit_id,
+            vote,
+            #[cfg(feature = "circuit-rejection-reasons")]
+            comment,
+        )
     }
 }
 
@@ -694,6 +921,7 @@ fn vote_on_circuit_proposal(
     signer: Box<dyn Signer>,
     circuit_id: &str,
     vote: Vote,
+    #[cfg(feature = "circuit-rejection-reasons")] comment: Option<String>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
         .with_url(url.to_string())
@@ -708,6 +936,8 @@ fn vote_on_circuit_proposal(
             circuit_id: circuit_id.into(),
             circuit_hash: proposal.circuit_hash,
             vote,
+            #[cfg(feature = "circuit-rejection-reasons")]
+            comment,
         };
         let signed_payload = make_signed_payload(&requester_node, signer, circuit_vote)?;
         client.submit_admin_payload(signed_payload)
@@ -773,6 +1003,7 @@ fn propose_circuit_disband(
 
 struct CircuitPurge {
     circuit_id: String,
+    keep_state: bool,
 }
 
 pub struct CircuitPurgeAction;
@@ -792,7 +1023,17 @@ impl Action for CircuitPurgeAction {
             .value_of("circuit_id")
             .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
 
-        request_purge_circuit(&url, signer, circuit_id)
+        #[cfg(feature = "circuit-purge-keep-state")]
+        let keep_state = args.is_present("keep_state");
+        #[cfg(not(feature = "circuit-purge-keep-state"))]
+        let keep_state = false;
+
+        #[cfg(feature = "circuit-purge-preview")]
+        let force = args.is_present("force");
+        #[cfg(not(feature = "circuit-purge-preview"))]
+        let force = false;
+
+        request_purge_circuit(&url, signer, circuit_id, keep_state, force)
     }
 }
 
@@ -800,6 +1041,8 @@ fn request_purge_circuit(
     url: &str,
     signer: Box<dyn Signer>,
     circuit_id: &str,
+    keep_state: bool,
+    force: bool,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
         .with_url(url.to_string())
@@ -819,11 +1062,23 @@ fn request_purge_circuit(
                 circuit_id
             )));
         }
+
+        if !confirm_purge(&client, circuit_id, keep_state, force)? {
+            info!("Purge of circuit {} cancelled", circuit_id);
+            return Ok(());
+        }
+
         let circuit_purge_request = CircuitPurge {
             circuit_id: circuit_id.into(),
+            keep_state,
         };
         let signed_payload = make_signed_payload(&requester_node, signer, circuit_purge_request)?;
-        client.submit_admin_payload(signed_payload)
+        client.submit_admin_payload(signed_payload)?;
+
+        #[cfg(feature = "circuit-purge-preview")]
+        report_purge_results(&client, circuit_id)?;
+
+        Ok(())
     } else {
         Err(CliError::ActionError(format!(
             "Circuit '{}' does not exist",
@@ -832,8 +1087,130 @@ fn request_purge_circuit(
     }
 }
 
+/// Displays the local services and state that purging the circuit would remove, then asks the
+/// user to confirm the purge unless `force` is set. Returns whether the purge should proceed.
+#[cfg(feature = "circuit-purge-preview")]
+fn confirm_purge(
+    client: &SplinterRestClient,
+    circuit_id: &str,
+    keep_state: bool,
+    force: bool,
+) -> Result<bool, CliError> {
+    let preview = client
+        .fetch_circuit_purge_report(circuit_id)?
+        .ok_or_else(|| CliError::ActionError(format!("Circuit '{}' does not exist", circuit_id)))?;
+
+    if preview.services.is_empty() {
+        info!("Circuit {} has no local services", circuit_id);
+    } else {
+        info!(
+            "Purging circuit {} will {} the state of the following local services:",
+            circuit_id,
+            if keep_state { "keep" } else { "remove" }
+        );
+        print_purge_report(&preview, false);
+    }
+
+    if force {
+        return Ok(true);
+    }
+
+    warn!(
+        "Are you sure you wish to purge circuit {}? [y/N]",
+        circuit_id
+    );
+    let stdin = io::stdin();
+    let line = stdin.lock().lines().next();
+    match line {
+        Some(Ok(input)) => Ok(matches!(input.trim(), "y" | "Y" | "yes")),
+        _ => Err(CliError::ActionError(
+            "Unable to get prompt response".to_string(),
+        )),
+    }
+}
+
+#[cfg(not(feature = "circuit-purge-preview"))]
+fn confirm_purge(
+    _client: &SplinterRestClient,
+    _circuit_id: &str,
+    _keep_state: bool,
+    _force: bool,
+) -> Result<bool, CliError> {
+    Ok(true)
+}
+
+/// Displays the outcome of purging each local service of the circuit, returning an error if any
+/// service failed to be purged.
+#[cfg(feature = "circuit-purge-preview")]
+fn report_purge_results(client: &SplinterRestClient, circuit_id: &str) -> Result<(), CliError> {
+    let report = match client.fetch_circuit_purge_report(circuit_id)? {
+        Some(report) if report.purged => report,
+        _ => {
+            info!(
+                "Purge of circuit {} was submitted, but no results are available",
+                circuit_id
+            );
+            return Ok(());
+        }
+    };
+
+    info!("Purged circuit {}", circuit_id);
+    if !report.services.is_empty() {
+        print_purge_report(&report, true);
+    }
+
+    let failed = report
+        .services
+        .iter()
+        .filter(|service| service.status == "failed")
+        .count();
+    if failed > 0 {
+        return Err(CliError::ActionError(format!(
+            "Failed to purge the state of {} service(s) on circuit {}",
+            failed, circuit_id
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "circuit-purge-preview")]
+fn print_purge_report(report: &CircuitPurgeReportSlice, show_status: bool) {
+    let mut header = vec![
+        "SERVICE ID".to_string(),
+        "SERVICE TYPE".to_string(),
+        "STATE SIZE".to_string(),
+    ];
+    if show_status {
+        header.push("STATUS".to_string());
+    }
+
+    let mut data = vec![header];
+    for service in report.services.iter() {
+        let mut row = vec![
+            service.service_id.to_string(),
+            service.service_type.to_string(),
+            service
+                .state_size
+                .map(format_size)
+                .unwrap_or_else(|| "unknown".to_string()),
+        ];
+        if show_status {
+            match &service.error {
+                Some(err) => row.push(format!("{}: {}", service.status, err)),
+                None => row.push(service.status.to_string()),
+            }
+        }
+        data.push(row);
+    }
+
+    print_table(data);
+}
+
 struct AbandonedCircuit {
     circuit_id: String,
+    purge: bool,
+    keep_state: bool,
 }
 
 pub struct CircuitAbandonAction;
@@ -853,7 +1230,23 @@ impl Action for CircuitAbandonAction {
             .value_of("circuit_id")
             .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
 
-        request_abandon_circuit(&url, signer, circuit_id)
+        #[cfg(feature = "circuit-abandon-purge")]
+        let purge = args.is_present("purge");
+        #[cfg(not(feature = "circuit-abandon-purge"))]
+        let purge = false;
+
+        #[cfg(all(
+            feature = "circuit-abandon-purge",
+            feature = "circuit-purge-keep-state"
+        ))]
+        let keep_state = args.is_present("keep_state");
+        #[cfg(not(all(
+            feature = "circuit-abandon-purge",
+            feature = "circuit-purge-keep-state"
+        )))]
+        let keep_state = false;
+
+        request_abandon_circuit(&url, signer, circuit_id, purge, keep_state)
     }
 }
 
@@ -861,6 +1254,8 @@ fn request_abandon_circuit(
     url: &str,
     signer: Box<dyn Signer>,
     circuit_id: &str,
+    purge: bool,
+    keep_state: bool,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
         .with_url(url.to_string())
@@ -882,8 +1277,23 @@ fn request_abandon_circuit(
 
         let circuit_abandon = AbandonedCircuit {
             circuit_id: circuit_id.into(),
+            purge,
+            keep_state,
         };
-        let signed_payload = make_signed_payload(&requester_node, signer, circuit_abandon)?;
+        let signed_payload = make_signed_payload(&requester_node, signer.clone(), circuit_abandon)?;
+
+        #[cfg(feature = "circuit-abandon-purge")]
+        if purge {
+            return submit_abandon_and_purge(
+                &client,
+                &requester_node,
+                signer,
+                circuit_id,
+                keep_state,
+                signed_payload,
+            );
+        }
+
         client.submit_admin_payload(signed_payload)
     } else {
         Err(CliError::ActionError(format!(
@@ -893,6 +1303,85 @@ fn request_abandon_circuit(
     }
 }
 
+/// Submits an abandon request that also asks the daemon to purge the circuit, then verifies the
+/// circuit was purged. Daemons that do not support purging on abandon only abandon the circuit, in
+/// which case a separate purge request is submitted once the abandon is in effect.
+#[cfg(feature = "circuit-abandon-purge")]
+fn submit_abandon_and_purge(
+    client: &SplinterRestClient,
+    requester_node: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    keep_state: bool,
+    signed_payload: Vec<u8>,
+) -> Result<(), CliError> {
+    info!("Step 1/2: abandoning circuit {}", circuit_id);
+    if let Err(err) = client.submit_admin_payload(signed_payload) {
+        // The daemon abandons the circuit before purging it, so a failure may have happened after
+        // the abandon was applied
+        return match client.fetch_circuit(circuit_id) {
+            Ok(Some(circuit)) if circuit.circuit_status == Some(CircuitStatus::Abandoned) => {
+                Err(purge_failed_error(circuit_id, keep_state, err))
+            }
+            _ => Err(CliError::ActionError(format!(
+                "Failed to abandon circuit {}, the circuit was not changed: {}",
+                circuit_id, err
+            ))),
+        };
+    }
+    info!("Abandoned circuit {}", circuit_id);
+
+    info!("Step 2/2: purging circuit {}", circuit_id);
+    match client.fetch_circuit(circuit_id)? {
+        None => (),
+        Some(circuit) if circuit.circuit_status == Some(CircuitStatus::Abandoned) => {
+            debug!(
+                "Circuit {} was not purged by the daemon, submitting purge request",
+                circuit_id
+            );
+            let circuit_purge_request = CircuitPurge {
+                circuit_id: circuit_id.into(),
+                keep_state,
+            };
+            let signed_payload =
+                make_signed_payload(requester_node, signer, circuit_purge_request)?;
+            client
+                .submit_admin_payload(signed_payload)
+                .map_err(|err| purge_failed_error(circuit_id, keep_state, err))?;
+        }
+        Some(_) => {
+            return Err(CliError::ActionError(format!(
+                "Circuit {} was not abandoned by the daemon",
+                circuit_id
+            )))
+        }
+    }
+
+    #[cfg(feature = "circuit-purge-preview")]
+    {
+        report_purge_results(client, circuit_id)
+    }
+    #[cfg(not(feature = "circuit-purge-preview"))]
+    {
+        info!("Purged circuit {}", circuit_id);
+        Ok(())
+    }
+}
+
+/// Builds the error reported when a circuit was abandoned but could not be purged, including the
+/// command that retries the purge.
+#[cfg(feature = "circuit-abandon-purge")]
+fn purge_failed_error(circuit_id: &str, keep_state: bool, err: CliError) -> CliError {
+    CliError::ActionError(format!(
+        "Circuit {} was abandoned, but could not be purged: {}. Retry the purge with \
+        `splinter circuit purge{} {}`",
+        circuit_id,
+        err,
+        if keep_state { " --keep-state" } else { "" },
+        circuit_id
+    ))
+}
+
 struct RemoveProposal {
     circuit_id: String,
 }
@@ -957,6 +1446,24 @@ impl Action for CircuitListAction {
 
         let member_filter = arg_matches.and_then(|args| args.value_of("member"));
         let status_filter = arg_matches.and_then(|args| args.value_of("circuit_status"));
+        #[cfg(feature = "circuit-requester-filter")]
+        let requester_filter = arg_matches.and_then(|args| args.value_of("requester"));
+        #[cfg(not(feature = "circuit-requester-filter"))]
+        let requester_filter = None;
+        #[cfg(feature = "circuit-type-filter")]
+        let management_type_filter = arg_matches.and_then(|args| args.value_of("management_type"));
+        #[cfg(not(feature = "circuit-type-filter"))]
+        let management_type_filter = None;
+        #[cfg(feature = "circuit-type-filter")]
+        let service_type_filter = arg_matches.and_then(|args| args.value_of("service_type"));
+        #[cfg(not(feature = "circuit-type-filter"))]
+        let service_type_filter = None;
+        #[cfg(feature = "circuit-tombstones")]
+        let include_deleted = arg_matches
+            .map(|args| args.is_present("include_deleted"))
+            .unwrap_or(false);
+        #[cfg(not(feature = "circuit-tombstones"))]
+        let include_deleted = false;
 
         let format = arg_matches
             .and_then(|args| {
@@ -970,14 +1477,29 @@ impl Action for CircuitListAction {
 
         let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
 
-        list_circuits(&url, member_filter, status_filter, format, signer)
+        list_circuits(
+            &url,
+            member_filter,
+            status_filter,
+            requester_filter,
+            management_type_filter,
+            service_type_filter,
+            include_deleted,
+            format,
+            signer,
+        )
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn list_circuits(
     url: &str,
     member_filter: Option<&str>,
     status_filter: Option<&str>,
+    requester_filter: Option<&str>,
+    management_type_filter: Option<&str>,
+    service_type_filter: Option<&str>,
+    include_deleted: bool,
     format: &str,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
@@ -986,7 +1508,38 @@ fn list_circuits(
         .with_auth(create_cylinder_jwt_auth(signer)?)
         .build()?;
 
-    let circuits = client.list_circuits(member_filter, status_filter)?;
+    let circuits = client.list_circuits(
+        member_filter,
+        status_filter,
+        requester_filter,
+        management_type_filter,
+        service_type_filter,
+        include_deleted,
+    )?;
+    match format {
+        "json" => {
+            println!(
+                "{}",
+                serde_json::to_string(&circuits).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format circuit list into json: {}",
+                    err
+                )))?
+            );
+            return Ok(());
+        }
+        "yaml" => {
+            println!(
+                "{}",
+                serde_yaml::to_string(&circuits).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format circuit list into yaml: {}",
+                    err
+                )))?
+            );
+            return Ok(());
+        }
+        _ => (),
+    }
+
     let mut data = vec![
         // Header
         vec![
@@ -996,6 +1549,10 @@ fn list_circuits(
             "MEMBERS".to_string(),
         ],
     ];
+    #[cfg(feature = "circuit-tombstones")]
+    if include_deleted {
+        data[0].push("DELETED".to_string());
+    }
     circuits.data.iter().for_each(|circuit| {
         let members = circuit
             .members
@@ -1013,12 +1570,23 @@ fn list_circuits(
                     .unwrap_or_else(|| "-".to_string())
             }
         };
-        data.push(vec![
+        #[cfg_attr(not(feature = "circuit-tombstones"), allow(unused_mut))]
+        let mut row = vec![
             circuit.id.to_string(),
             display_name,
             circuit.management_type.to_string(),
             members,
-        ]);
+        ];
+        #[cfg(feature = "circuit-tombstones")]
+        if include_deleted {
+            row.push(
+                circuit
+                    .deleted_reason
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+        }
+        data.push(row);
     });
 
     if format == "csv" {
@@ -1139,6 +1707,21 @@ impl Action for CircuitProposalsAction {
         let management_type_filter = arg_matches.and_then(|args| args.value_of("management_type"));
 
         let member_filter = arg_matches.and_then(|args| args.value_of("member"));
+        #[cfg(feature = "circuit-requester-filter")]
+        let requester_filter = arg_matches.and_then(|args| args.value_of("requester"));
+        #[cfg(not(feature = "circuit-requester-filter"))]
+        let requester_filter = None;
+        #[cfg(feature = "circuit-vote-status-filter")]
+        let vote_status_filter = if arg_matches
+            .map(|args| args.is_present("awaiting_my_vote"))
+            .unwrap_or(false)
+        {
+            Some("pending")
+        } else {
+            None
+        };
+        #[cfg(not(feature = "circuit-vote-status-filter"))]
+        let vote_status_filter = None;
 
         let format = arg_matches
             .and_then(|args| {
@@ -1152,7 +1735,49 @@ impl Action for CircuitProposalsAction {
 
         let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
 
-        list_proposals(&url, management_type_filter, member_filter, format, signer)
+        #[cfg(feature = "circuit-proposals-watch")]
+        if arg_matches
+            .map(|args| args.is_present("watch"))
+            .unwrap_or(false)
+        {
+            let interval = match arg_matches.and_then(|args| args.value_of("interval")) {
+                Some(interval) => match interval.parse::<u64>() {
+                    Ok(secs) if secs > 0 => Duration::from_secs(secs),
+                    _ => {
+                        return Err(CliError::ActionError(format!(
+                            "'{}' is not a valid number of seconds between polls",
+                            interval
+                        )))
+                    }
+                },
+                None => watch::DEFAULT_INTERVAL,
+            };
+
+            let client = SplinterRestClientBuilder::new()
+                .with_url(url)
+                .with_auth(create_cylinder_jwt_auth(signer)?)
+                .build()?;
+
+            return watch::watch_proposals(
+                &client,
+                management_type_filter,
+                member_filter,
+                requester_filter,
+                vote_status_filter,
+                format,
+                interval,
+            );
+        }
+
+        list_proposals(
+            &url,
+            management_type_filter,
+            member_filter,
+            requester_filter,
+            vote_status_filter,
+            format,
+            signer,
+        )
     }
 }
 
@@ -1160,6 +1785,8 @@ fn list_proposals(
     url: &str,
     management_type_filter: Option<&str>,
     member_filter: Option<&str>,
+    requester_filter: Option<&str>,
+    vote_status_filter: Option<&str>,
     format: &str,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
@@ -1168,59 +1795,43 @@ fn list_proposals(
         .with_auth(create_cylinder_jwt_auth(signer)?)
         .build()?;
 
-    let proposals = client.list_proposals(management_type_filter, member_filter)?;
-    let mut data = vec![
-        // header
-        vec![
-            "ID".to_string(),
-            "NAME".to_string(),
-            "MANAGEMENT".to_string(),
-            "MEMBERS".to_string(),
-            "COMMENTS".to_string(),
-            "PROPOSAL_TYPE".to_string(),
-        ],
-    ];
-    proposals.data.iter().for_each(|proposal| {
-        let display_name = {
-            if format == "csv" {
-                proposal.circuit.display_name.clone().unwrap_or_default()
-            } else {
-                proposal
-                    .circuit
-                    .display_name
-                    .clone()
-                    .unwrap_or_else(|| "-".to_string())
-            }
-        };
-
-        let comments = {
-            if format == "csv" {
-                proposal.circuit.comments.clone().unwrap_or_default()
-            } else {
-                proposal
-                    .circuit
-                    .comments
-                    .clone()
-                    .unwrap_or_else(|| "-".to_string())
-            }
-        };
+    let proposals = client.list_proposals(
+        management_type_filter,
+        member_filter,
+        requester_filter,
+        vote_status_filter,
+    )?;
+    match format {
+        "json" => {
+            println!(
+                "{}",
+                serde_json::to_string(&proposals).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format proposal list into json: {}",
+                    err
+                )))?
+            );
+            return Ok(());
+        }
+        "yaml" => {
+            println!(
+                "{}",
+                serde_yaml::to_string(&proposals).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format proposal list into yaml: {}",
+                    err
+                )))?
+            );
+            return Ok(());
+        }
+        _ => (),
+    }
 
-        let members = proposal
-            .circuit
-            .members
+    let mut data = vec![proposal_header()];
+    data.extend(
+        proposals
+            .data
             .iter()
-            .map(|member| member.node_id.to_string())
-            .collect::<Vec<String>>()
-            .join(";");
-        data.push(vec![
-            proposal.circuit_id.to_string(),
-            display_name,
-            proposal.circuit.management_type.to_string(),
-            members,
-            comments,
-            proposal.proposal_type.to_string(),
-        ]);
-    });
+            .map(|proposal| proposal_row(proposal, format)),
+    );
 
     if format == "csv" {
         for row in data {
@@ -1232,3 +1843,59 @@ fn list_proposals(
 
     Ok(())
 }
+
+/// Returns the column titles of the proposal list
+fn proposal_header() -> Vec<String> {
+    vec![
+        "ID".to_string(),
+        "NAME".to_string(),
+        "MANAGEMENT".to_string(),
+        "MEMBERS".to_string(),
+        "COMMENTS".to_string(),
+        "PROPOSAL_TYPE".to_string(),
+    ]
+}
+
+/// Returns the columns of the proposal list for the given proposal
+fn proposal_row(proposal: &api::ProposalSlice, format: &str) -> Vec<String> {
+    let display_name = {
+        if format == "csv" {
+            proposal.circuit.display_name.clone().unwrap_or_default()
+        } else {
+            proposal
+                .circuit
+                .display_name
+                .clone()
+                .unwrap_or_else(|| "-".to_string())
+        }
+    };
+
+    let comments = {
+        if format == "csv" {
+            proposal.circuit.comments.clone().unwrap_or_default()
+        } else {
+            proposal
+                .circuit
+                .comments
+                .clone()
+                .unwrap_or_else(|| "-".to_string())
+        }
+    };
+
+    let members = proposal
+        .circuit
+        .members
+        .iter()
+        .map(|member| member.node_id.to_string())
+        .collect::<Vec<String>>()
+        .join(";");
+
+    vec![
+        proposal.circuit_id.to_string(),
+        display_name,
+        proposal.circuit.management_type.to_string(),
+        members,
+        comments,
+        proposal.proposal_type.to_string(),
+    ]
+}
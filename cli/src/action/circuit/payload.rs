@@ -48,6 +48,55 @@ pub fn make_signed_payload<M, A>(
     signer: Box<dyn Signer>,
     action: A,
 ) -> Result<Vec<u8>, CliError>
+where
+    M: Message + ApplyToEnvelope,
+    A: CircuitAction<M>,
+{
+    build_signed_payload(requester_node, signer, action, HeaderOptions::default())
+}
+
+/// Consensus settings that a payload's header may request for the resulting proposal. Unset
+/// values use the receiving node's configuration.
+#[derive(Default)]
+pub struct HeaderOptions {
+    /// The coordinator timeout, in seconds; the receiving node bounds it by its configured
+    /// maximum
+    #[cfg(feature = "circuit-propose-timeout")]
+    pub coordinator_timeout: Option<u64>,
+    /// How long, in seconds, the coordinator waits for verification responses before re-sending
+    /// its verification request
+    #[cfg(feature = "circuit-propose-retry")]
+    pub verification_timeout: Option<u64>,
+    /// How many times the coordinator re-sends its verification request
+    #[cfg(feature = "circuit-propose-retry")]
+    pub verification_retries: Option<u32>,
+    /// The factor by which the coordinator multiplies its wait after each retry
+    #[cfg(feature = "circuit-propose-retry")]
+    pub verification_backoff: Option<u32>,
+}
+
+/// Makes a signed, circuit management payload whose header requests the given consensus
+/// settings for the resulting proposal.
+#[cfg(any(feature = "circuit-propose-timeout", feature = "circuit-propose-retry"))]
+pub fn make_signed_payload_with_options<M, A>(
+    requester_node: &str,
+    signer: Box<dyn Signer>,
+    action: A,
+    options: HeaderOptions,
+) -> Result<Vec<u8>, CliError>
+where
+    M: Message + ApplyToEnvelope,
+    A: CircuitAction<M>,
+{
+    build_signed_payload(requester_node, signer, action, options)
+}
+
+fn build_signed_payload<M, A>(
+    requester_node: &str,
+    signer: Box<dyn Signer>,
+    action: A,
+    #[allow(unused_variables)] options: HeaderOptions,
+) -> Result<Vec<u8>, CliError>
 where
     M: Message + ApplyToEnvelope,
     A: CircuitAction<M>,
@@ -75,6 +124,22 @@ where
     header.set_payload_sha512(hashed_bytes.to_vec());
     header.set_requester(public_key);
     header.set_requester_node_id(requester_node.into());
+    #[cfg(feature = "circuit-propose-timeout")]
+    if let Some(coordinator_timeout) = options.coordinator_timeout {
+        header.set_coordinator_timeout(coordinator_timeout);
+    }
+    #[cfg(feature = "circuit-propose-retry")]
+    {
+        if let Some(verification_timeout) = options.verification_timeout {
+            header.set_verification_timeout(verification_timeout);
+        }
+        if let Some(verification_retries) = options.verification_retries {
+            header.set_verification_retries(verification_retries);
+        }
+        if let Some(verification_backoff) = options.verification_backoff {
+            header.set_verification_backoff(verification_backoff);
+        }
+    }
     let header_bytes = header.write_to_bytes().map_err(|err| {
         CliError::ActionError(format!("Failed to serialize payload header: {}", err))
     })?;
@@ -129,6 +194,10 @@ impl CircuitAction<CircuitProposalVote> for CircuitVote {
         });
         vote.set_circuit_id(self.circuit_id);
         vote.set_circuit_hash(self.circuit_hash);
+        #[cfg(feature = "circuit-rejection-reasons")]
+        if let Some(comment) = self.comment {
+            vote.set_comment(comment);
+        }
 
         Ok(vote)
     }
@@ -166,6 +235,7 @@ impl CircuitAction<CircuitPurgeRequest> for CircuitPurge {
     fn into_proto(self) -> Result<CircuitPurgeRequest, CliError> {
         let mut purge_request = CircuitPurgeRequest::new();
         purge_request.set_circuit_id(self.circuit_id);
+        purge_request.set_keep_state(self.keep_state);
         Ok(purge_request)
     }
 }
@@ -184,6 +254,8 @@ impl CircuitAction<CircuitAbandon> for AbandonedCircuit {
     fn into_proto(self) -> Result<CircuitAbandon, CliError> {
         let mut abandon = CircuitAbandon::new();
         abandon.set_circuit_id(self.circuit_id);
+        abandon.set_purge(self.purge);
+        abandon.set_keep_state(self.keep_state);
         Ok(abandon)
     }
 }
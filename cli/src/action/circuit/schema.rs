@@ -0,0 +1,138 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Actions for reporting the schema versions of a node's stored circuits and migrating circuits
+//! stored with an older schema version to the current one.
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::super::api::{SplinterRestClient, SplinterRestClientBuilder};
+use super::super::{print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+use super::api::CircuitSchemaReportSlice;
+
+pub struct CircuitSchemaStatusAction;
+
+impl Action for CircuitSchemaStatusAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let client = build_client(args)?;
+
+        let report = client.fetch_circuit_schema_report()?;
+        print_report(&report, args.value_of("format"))
+    }
+}
+
+pub struct CircuitSchemaMigrateAction;
+
+impl Action for CircuitSchemaMigrateAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let client = build_client(args)?;
+
+        let report = client.migrate_circuit_schemas(args.is_present("dry_run"))?;
+        print_report(&report, args.value_of("format"))?;
+
+        if !report.dry_run {
+            let migrated = report
+                .circuits
+                .iter()
+                .filter(|circuit| circuit.migrated)
+                .count();
+            info!(
+                "Migrated {} of {} circuit(s)",
+                migrated,
+                report.circuits.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn build_client(args: &ArgMatches) -> Result<SplinterRestClient, CliError> {
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+    let signer = load_signer(args.value_of("private_key_file"))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+fn print_report(report: &CircuitSchemaReportSlice, format: Option<&str>) -> Result<(), CliError> {
+    if format == Some("json") {
+        println!(
+            "\n {}",
+            serde_json::to_string(report).map_err(|err| CliError::ActionError(format!(
+                "Cannot format circuit schema report into json: {}",
+                err
+            )))?
+        );
+        return Ok(());
+    }
+
+    let mut versions = vec![vec!["VERSION".to_string(), "CIRCUITS".to_string()]];
+    for (version, count) in &report.versions {
+        versions.push(vec![version.clone(), count.to_string()]);
+    }
+    print_table(versions);
+
+    if report.circuits.is_empty() {
+        println!("\nAll circuits use the current schema version");
+        return Ok(());
+    }
+
+    let mut circuits = vec![vec![
+        "CIRCUIT".to_string(),
+        "FROM".to_string(),
+        "TO".to_string(),
+        "STATUS".to_string(),
+        "CHANGES".to_string(),
+    ]];
+    for circuit in &report.circuits {
+        let status = if circuit.migrated {
+            "migrated".to_string()
+        } else if circuit.eligible {
+            "eligible".to_string()
+        } else {
+            format!(
+                "ineligible: {}",
+                circuit.reason.as_deref().unwrap_or("unknown reason")
+            )
+        };
+        let changes = circuit
+            .changes
+            .iter()
+            .map(|change| format!("{}: {} -> {}", change.field, change.from, change.to))
+            .collect::<Vec<_>>()
+            .join(", ");
+        circuits.push(vec![
+            circuit.circuit_id.clone(),
+            circuit.from_version.to_string(),
+            circuit.to_version.to_string(),
+            status,
+            changes,
+        ]);
+    }
+    println!();
+    print_table(circuits);
+
+    Ok(())
+}
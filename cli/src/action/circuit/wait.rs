@@ -0,0 +1,136 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Tracks the progress of a circuit proposal until the circuit is ready for use.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::action::api::SplinterRestClient;
+use crate::error::CliError;
+
+use super::api::ProposalSlice;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Polls the node's admin REST API until the proposed circuit has been created, reporting each
+/// stage of progress along the way.
+///
+/// # Arguments
+///
+/// * `client` - The REST client for the node the proposal was submitted to
+/// * `circuit_id` - The ID of the proposed circuit
+/// * `local_node_id` - The ID of the node the proposal was submitted to
+/// * `timeout` - How long to wait for the circuit to be created
+///
+/// Returns an error if the proposal is rejected or the circuit is not created before the timeout
+/// expires.
+pub(super) fn wait_for_circuit(
+    client: &SplinterRestClient,
+    circuit_id: &str,
+    local_node_id: &str,
+    timeout: Duration,
+) -> Result<(), CliError> {
+    let deadline = Instant::now() + timeout;
+    // The number of votes received, once the proposal has been validated by the local node
+    let mut votes_received: Option<usize> = None;
+
+    info!(
+        "Waiting up to {}s for circuit {}",
+        timeout.as_secs(),
+        circuit_id
+    );
+
+    loop {
+        if let Some(circuit) = client.fetch_circuit(circuit_id)? {
+            info!("Circuit {} has been created", circuit_id);
+
+            let local_services = circuit
+                .roster
+                .iter()
+                .filter(|service| service.node_id == local_node_id)
+                .map(|service| format!("{} ({})", service.service_id, service.service_type))
+                .collect::<Vec<_>>();
+            if local_services.is_empty() {
+                info!("No services are run by this node on circuit {}", circuit_id);
+            } else {
+                info!("Local services ready: {}", local_services.join(", "));
+            }
+
+            return Ok(());
+        }
+
+        match client.fetch_proposal(circuit_id)? {
+            Some(proposal) => {
+                if votes_received != Some(proposal.votes.len()) {
+                    if votes_received.is_none() {
+                        info!("Proposal for circuit {} has been validated", circuit_id);
+                    }
+                    report_votes(&proposal);
+                    votes_received = Some(proposal.votes.len());
+                }
+            }
+            None if votes_received.is_some() => {
+                return Err(CliError::ActionError(format!(
+                    "Proposal for circuit {} was removed before the circuit was created; it \
+                     may have been rejected",
+                    circuit_id
+                )));
+            }
+            None => (),
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(CliError::ActionError(format!(
+                "Timed out waiting for circuit {} to be created",
+                circuit_id
+            )));
+        }
+
+        thread::sleep(POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+fn report_votes(proposal: &ProposalSlice) {
+    // The requester's vote is implied, so it is not counted as a required vote
+    let required = proposal.circuit.members.len().saturating_sub(1);
+    let pending = proposal
+        .circuit
+        .members
+        .iter()
+        .filter(|member| member.node_id != proposal.requester_node_id)
+        .filter(|member| {
+            !proposal
+                .votes
+                .iter()
+                .any(|vote| vote.voter_node_id == member.node_id)
+        })
+        .map(|member| member.node_id.as_str())
+        .collect::<Vec<_>>();
+
+    if pending.is_empty() {
+        info!(
+            "Received {} of {} votes; waiting for the circuit to be created",
+            proposal.votes.len(),
+            required
+        );
+    } else {
+        info!(
+            "Received {} of {} votes; waiting on {}",
+            proposal.votes.len(),
+            required,
+            pending.join(", ")
+        );
+    }
+}
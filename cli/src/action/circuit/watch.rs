@@ -0,0 +1,262 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Reports changes to a node's circuit proposals as they happen.
+
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use splinter::admin::messages::CircuitStatus;
+
+use crate::action::api::SplinterRestClient;
+use crate::error::CliError;
+
+use super::api::ProposalSlice;
+use super::{proposal_header, proposal_row};
+
+/// How long to wait between polls if no interval is given
+pub(super) const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A change to a proposal observed between two polls of the admin REST API
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProposalEventType {
+    /// The proposal was submitted, or was present when the watch started
+    Added,
+    /// A vote was recorded on the proposal
+    Voted,
+    /// The proposal was accepted by all members
+    Accepted,
+    /// The proposal was rejected by a member
+    Rejected,
+    /// The proposal was removed, but the outcome could not be determined
+    Removed,
+}
+
+impl ProposalEventType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ProposalEventType::Added => "ADDED",
+            ProposalEventType::Voted => "VOTED",
+            ProposalEventType::Accepted => "ACCEPTED",
+            ProposalEventType::Rejected => "REJECTED",
+            ProposalEventType::Removed => "REMOVED",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ProposalEvent<'a> {
+    event: ProposalEventType,
+    proposal: &'a ProposalSlice,
+}
+
+/// Polls the node's admin REST API, printing an event whenever a proposal is added, voted on, or
+/// removed. Proposals that exist when the watch starts are reported as added.
+///
+/// This only returns if the initial list of proposals cannot be fetched or the output cannot be
+/// written; errors while polling after that are logged and the watch continues.
+///
+/// # Arguments
+///
+/// * `client` - The REST client for the node to watch
+/// * `management_type_filter` - Only watch proposals with this management type
+/// * `member_filter` - Only watch proposals that include this node
+/// * `requester_filter` - Only watch proposals submitted by this public key
+/// * `vote_status_filter` - Only watch proposals with this vote status relative to the caller
+/// * `format` - The output format: `human`, `csv`, `json` or `yaml`
+/// * `interval` - How long to wait between polls
+pub(super) fn watch_proposals(
+    client: &SplinterRestClient,
+    management_type_filter: Option<&str>,
+    member_filter: Option<&str>,
+    requester_filter: Option<&str>,
+    vote_status_filter: Option<&str>,
+    format: &str,
+    interval: Duration,
+) -> Result<(), CliError> {
+    let mut printer = EventPrinter::new(format);
+    let mut known: HashMap<String, ProposalSlice> = HashMap::new();
+    let mut first_poll = true;
+
+    loop {
+        let proposals = match client.list_proposals(
+            management_type_filter,
+            member_filter,
+            requester_filter,
+            vote_status_filter,
+        ) {
+            Ok(proposals) => proposals.data,
+            Err(err) if first_poll => return Err(err),
+            Err(err) => {
+                warn!("Unable to list proposals: {}", err);
+                thread::sleep(interval);
+                continue;
+            }
+        };
+        first_poll = false;
+
+        let mut current = HashMap::with_capacity(proposals.len());
+        for proposal in proposals {
+            let event = match known.remove(&proposal.circuit_id) {
+                Some(previous) if previous.circuit_hash != proposal.circuit_hash => {
+                    Some(ProposalEventType::Added)
+                }
+                Some(previous) if previous.votes.len() != proposal.votes.len() => {
+                    Some(ProposalEventType::Voted)
+                }
+                Some(_) => None,
+                None => Some(ProposalEventType::Added),
+            };
+            if let Some(event) = event {
+                printer.print(event, &proposal)?;
+            }
+            current.insert(proposal.circuit_id.clone(), proposal);
+        }
+
+        // Anything left in known is no longer pending
+        for (_, proposal) in known.drain() {
+            let event = resolve_outcome(client, &proposal);
+            printer.print(event, &proposal)?;
+        }
+
+        known = current;
+        printer.flush();
+        thread::sleep(interval);
+    }
+}
+
+/// Determines whether a proposal that is no longer pending was accepted or rejected, based on the
+/// current state of its circuit.
+fn resolve_outcome(client: &SplinterRestClient, proposal: &ProposalSlice) -> ProposalEventType {
+    let circuit = match client.fetch_circuit(&proposal.circuit_id) {
+        Ok(circuit) => circuit,
+        Err(err) => {
+            warn!(
+                "Unable to fetch circuit {} to determine the proposal outcome: {}",
+                proposal.circuit_id, err
+            );
+            return ProposalEventType::Removed;
+        }
+    };
+
+    match (proposal.proposal_type.as_str(), circuit) {
+        ("Create", Some(_)) => ProposalEventType::Accepted,
+        ("Create", None) => ProposalEventType::Rejected,
+        ("Disband", Some(circuit)) if circuit.circuit_status == Some(CircuitStatus::Disbanded) => {
+            ProposalEventType::Accepted
+        }
+        ("Disband", _) => ProposalEventType::Rejected,
+        _ => ProposalEventType::Removed,
+    }
+}
+
+/// Prints proposal events in the requested format.
+///
+/// For `human` output, the column widths of earlier rows are remembered so that the table stays
+/// aligned as rows are added; a column only grows when a value does not fit.
+struct EventPrinter<'a> {
+    format: &'a str,
+    widths: Vec<usize>,
+    rows: Vec<Vec<String>>,
+}
+
+impl<'a> EventPrinter<'a> {
+    fn new(format: &'a str) -> Self {
+        let mut printer = EventPrinter {
+            format,
+            widths: vec![],
+            rows: vec![],
+        };
+
+        if format == "human" || format == "csv" {
+            let mut header = vec!["EVENT".to_string()];
+            header.extend(proposal_header());
+            header.push("VOTES".to_string());
+            printer.rows.push(header);
+        }
+
+        printer
+    }
+
+    fn print(
+        &mut self,
+        event: ProposalEventType,
+        proposal: &ProposalSlice,
+    ) -> Result<(), CliError> {
+        match self.format {
+            "json" => println!(
+                "{}",
+                serde_json::to_string(&ProposalEvent { event, proposal }).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Cannot format proposal event into json: {}",
+                        err
+                    ))
+                })?
+            ),
+            "yaml" => print!(
+                "{}",
+                serde_yaml::to_string(&ProposalEvent { event, proposal }).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Cannot format proposal event into yaml: {}",
+                        err
+                    ))
+                })?
+            ),
+            _ => {
+                let mut row = vec![event.as_str().to_string()];
+                row.extend(proposal_row(proposal, self.format));
+                row.push(format!(
+                    "{}/{}",
+                    proposal.votes.len(),
+                    proposal.circuit.members.len().saturating_sub(1)
+                ));
+                self.rows.push(row);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints the rows buffered since the last flush.
+    fn flush(&mut self) {
+        if self.format == "csv" {
+            for row in self.rows.drain(..) {
+                println!("{}", row.join(","));
+            }
+            return;
+        }
+
+        for row in self.rows.iter() {
+            for (i, col) in row.iter().enumerate() {
+                match self.widths.get_mut(i) {
+                    Some(width) if col.len() > *width => *width = col.len(),
+                    Some(_) => (),
+                    None => self.widths.push(col.len()),
+                }
+            }
+        }
+
+        for row in self.rows.drain(..) {
+            let line = row
+                .iter()
+                .zip(self.widths.iter())
+                .map(|(col, width)| format!("{:width$}", col, width = width))
+                .collect::<Vec<_>>()
+                .join(" ");
+            println!("{}", line.trim_end());
+        }
+    }
+}
@@ -14,7 +14,7 @@
 
 use clap::ArgMatches;
 use protobuf::{Message, RepeatedField};
-use reqwest::{blocking::Client, header};
+use reqwest::header;
 use serde::Deserialize;
 use transact::protocol::batch::BatchPair;
 use transact::protocol::sabre::payload::ExecuteContractActionBuilder;
@@ -25,6 +25,7 @@ use transact::protos::{
     IntoBytes, IntoProto,
 };
 
+use crate::action::api::http_client;
 use crate::error::CliError;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
@@ -154,7 +155,7 @@ impl Action for CommandSetStateAction {
         let auth = create_cylinder_jwt_auth(signer)?;
 
         // send batch to target
-        Client::new()
+        http_client()
             .post(&format!("{}/batches", target))
             .header(header::CONTENT_TYPE, "octet-stream")
             .header("Authorization", auth)
@@ -302,7 +303,7 @@ impl Action for CommandGetStateAction {
         let auth = create_cylinder_jwt_auth(signer)?;
 
         // send batch to target
-        Client::new()
+        http_client()
             .post(&format!("{}/batches", target))
             .header(header::CONTENT_TYPE, "octet-stream")
             .header("Authorization", auth)
@@ -353,7 +354,7 @@ impl Action for CommandShowStateAction {
             .value_of("address")
             .ok_or_else(|| CliError::ActionError("'address' is required".into()))?;
 
-        Client::new()
+        http_client()
             .get(&format!("{}/state/{}", target, address))
             .header("Authorization", auth)
             .send()
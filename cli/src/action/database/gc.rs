@@ -0,0 +1,74 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `splinter database gc` command, which removes the circuits that were purged but
+//! kept as tombstones
+
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+
+use super::stores::new_upgrade_stores;
+use super::{get_default_database, Action, ConnectionUri};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub struct GcAction;
+
+impl Action for GcAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let before = args
+            .value_of("older_than")
+            .map(|days| {
+                days.parse::<u64>()
+                    .ok()
+                    .and_then(|days| {
+                        SystemTime::now().checked_sub(Duration::from_secs(days * SECONDS_PER_DAY))
+                    })
+                    .ok_or_else(|| {
+                        CliError::ActionError(format!("'{}' is not a valid number of days", days))
+                    })
+            })
+            .transpose()?;
+
+        let url = match args.value_of("connect") {
+            Some(url) => url.to_owned(),
+            None => get_default_database()?,
+        };
+        let connection_uri = ConnectionUri::from_str(&url)?;
+
+        let upgrade_stores = new_upgrade_stores(&connection_uri).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to open database {}: {}",
+                connection_uri, err
+            ))
+        })?;
+
+        let removed = upgrade_stores
+            .new_admin_service_store()
+            .remove_tombstones(before)
+            .map_err(|err| {
+                CliError::ActionError(format!("Unable to remove circuit tombstones: {}", err))
+            })?;
+
+        info!("Removed {} tombstoned circuit(s)", removed);
+
+        Ok(())
+    }
+}
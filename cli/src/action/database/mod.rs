@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "database-gc")]
+mod gc;
 #[cfg(feature = "postgres")]
 mod postgres;
+#[cfg(feature = "database-reset")]
+mod reset;
 #[cfg(feature = "sqlite")]
 mod sqlite;
 
@@ -21,6 +25,8 @@ mod state;
 mod stores;
 #[cfg(feature = "upgrade")]
 mod upgrade;
+#[cfg(feature = "database-vacuum")]
+mod vacuum;
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -28,13 +34,21 @@ use std::{env, fs};
 
 use clap::ArgMatches;
 
+#[cfg(feature = "database-gc")]
+pub use self::gc::GcAction;
 #[cfg(not(feature = "sqlite"))]
 use self::postgres::get_default_database;
+#[cfg(feature = "database-reset")]
+pub use self::reset::ResetAction;
 #[cfg(feature = "sqlite")]
 use self::sqlite::{get_default_database, sqlite_migrations};
 pub use self::state::StateMigrateAction;
+#[cfg(feature = "state-export")]
+pub use self::state::{StateExportAction, StateImportAction};
 #[cfg(feature = "upgrade")]
 pub use self::upgrade::UpgradeAction;
+#[cfg(feature = "database-vacuum")]
+pub use self::vacuum::VacuumAction;
 use crate::error::CliError;
 
 use super::Action;
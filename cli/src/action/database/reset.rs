@@ -0,0 +1,339 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides the `splinter database reset` command, which removes all data from a node's database
+
+use std::str::FromStr;
+
+use clap::ArgMatches;
+#[cfg(feature = "postgres")]
+use diesel::pg::PgConnection;
+#[cfg(feature = "sqlite")]
+use diesel::sqlite::SqliteConnection;
+use diesel::{connection::SimpleConnection, sql_query, sql_types::Text, Connection, RunQueryDsl};
+use scabbard::store::transact::factory::LmdbDatabaseFactory;
+use splinter::admin::store::{CircuitPredicate, CircuitStatus};
+
+use crate::error::CliError;
+
+use super::state::{get_state_dir, LmdbStateTreeStore, StateTreeStore};
+use super::stores::{new_upgrade_stores, UpgradeStores, UpgradeStoresWithLmdb};
+use super::{get_default_database, Action, ConnectionUri};
+
+/// The tables backing splinter's stores (circuits, proposals, registry, biome and node ID) that are
+/// cleared by a reset. Tables are ordered so that referencing tables are cleared first.
+const SPLINTER_TABLES: &[&str] = &[
+    // admin service events
+    "admin_event_proposed_service_argument",
+    "admin_event_proposed_service",
+    "admin_event_proposed_node_endpoint",
+    "admin_event_proposed_node",
+    "admin_event_vote_record",
+    "admin_event_proposed_circuit",
+    "admin_event_circuit_proposal",
+    "admin_service_event",
+    "admin_service_event_truncation",
+    // proposals
+    "proposed_service_argument",
+    "proposed_service",
+    "proposed_node_endpoint",
+    "proposed_node",
+    "vote_record",
+    "proposed_circuit",
+    "circuit_proposal",
+    // circuits
+    "service_lifecycle_argument",
+    "service_lifecycle_status",
+    "service_argument",
+    "service",
+    "node_endpoint",
+    "circuit_member",
+    "circuit",
+    // registry
+    "splinter_nodes_metadata",
+    "splinter_nodes_keys",
+    "splinter_nodes_endpoints",
+    "splinter_nodes",
+    // biome
+    "oauth_user_sessions",
+    "oauth_users",
+    "refresh_tokens",
+    "keys",
+    "user_profile",
+    "user_credentials",
+    // node ID
+    "node_id",
+];
+
+/// The table name prefixes of tables holding scabbard state, which are only cleared when
+/// requested.
+const SCABBARD_TABLE_PREFIXES: &[&str] = &[
+    "consensus_",
+    "merkle_radix_",
+    "scabbard_",
+    "supervisor_notification",
+    "transaction_receipt",
+];
+
+#[derive(diesel::QueryableByName)]
+struct TableName {
+    #[sql_type = "Text"]
+    name: String,
+}
+
+pub struct ResetAction;
+
+impl Action for ResetAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let url = match args.value_of("connect") {
+            Some(url) => url.to_owned(),
+            None => get_default_database()?,
+        };
+        let connection_uri = ConnectionUri::from_str(&url)?;
+
+        if !args.is_present("yes") {
+            return Err(CliError::ActionError(format!(
+                "Resetting the database will permanently delete all data in {}; \
+                 rerun with --yes to confirm",
+                connection_uri
+            )));
+        }
+
+        let upgrade_stores = new_upgrade_stores(&connection_uri).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to open database {}: {}",
+                connection_uri, err
+            ))
+        })?;
+
+        let active_circuits = upgrade_stores
+            .new_admin_service_store()
+            .count_circuits(&[CircuitPredicate::CircuitStatus(CircuitStatus::Active)])
+            .map_err(|err| CliError::ActionError(format!("Unable to count circuits: {}", err)))?;
+
+        if active_circuits > 0 {
+            if !args.is_present("force") {
+                return Err(CliError::ActionError(format!(
+                    "Database {} contains {} active circuit(s); rerun with --force to reset \
+                     it anyway",
+                    connection_uri, active_circuits
+                )));
+            }
+            warn!(
+                "Resetting database containing {} active circuit(s)",
+                active_circuits
+            );
+        }
+
+        let remove_scabbard_state = args.is_present("remove_scabbard_state");
+
+        // LMDB state is stored outside of the database, so the local scabbard services must be
+        // found before the circuits are removed
+        if remove_scabbard_state {
+            let state_dir = get_state_dir(arg_matches)?;
+            remove_lmdb_state(
+                UpgradeStoresWithLmdb::new(
+                    upgrade_stores,
+                    LmdbDatabaseFactory::new_state_db_factory(&state_dir, None),
+                ),
+                LmdbStateTreeStore::new(LmdbDatabaseFactory::new_state_db_factory(
+                    &state_dir, None,
+                )),
+            )?;
+        }
+
+        match connection_uri {
+            #[cfg(feature = "postgres")]
+            ConnectionUri::Postgres(url) => {
+                let conn = PgConnection::establish(&url).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to establish database connection to '{}': {}",
+                        url, err
+                    ))
+                })?;
+                reset_postgres(&conn, remove_scabbard_state)?
+            }
+            #[cfg(feature = "sqlite")]
+            ConnectionUri::Sqlite(conn_str) => {
+                let conn = SqliteConnection::establish(&conn_str).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to establish database connection to '{}': {}",
+                        conn_str, err
+                    ))
+                })?;
+                reset_sqlite(&conn, remove_scabbard_state)?
+            }
+        }
+
+        info!("Database {} has been reset", url);
+
+        Ok(())
+    }
+}
+
+/// Removes the LMDB state files of all scabbard services run by the local node.
+fn remove_lmdb_state(
+    upgrade_stores: UpgradeStoresWithLmdb,
+    state_tree_store: LmdbStateTreeStore,
+) -> Result<(), CliError> {
+    let node_id = match upgrade_stores
+        .new_node_id_store()
+        .get_node_id()
+        .map_err(|err| CliError::ActionError(err.to_string()))?
+    {
+        Some(node_id) => node_id,
+        // Without a node ID, the node cannot have any local services
+        None => return Ok(()),
+    };
+
+    let circuits = upgrade_stores
+        .new_admin_service_store()
+        .list_circuits(&[])
+        .map_err(|err| CliError::ActionError(err.to_string()))?;
+
+    for circuit in circuits {
+        for service in circuit.roster() {
+            if service.node_id() != node_id || service.service_type() != "scabbard" {
+                continue;
+            }
+
+            let circuit_id = circuit.circuit_id();
+            let service_id = service.service_id();
+            if !state_tree_store
+                .has_tree(circuit_id, service_id)
+                .map_err(|err| CliError::ActionError(err.to_string()))?
+            {
+                continue;
+            }
+
+            info!("Removing LMDB state for {}::{}", circuit_id, service_id);
+            upgrade_stores
+                .get_merkle_state(circuit_id, service_id, false)
+                .map_err(|err| CliError::ActionError(err.to_string()))?
+                .delete_tree()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the tables to clear, out of the given existing tables.
+fn tables_to_reset(existing: &[String], remove_scabbard_state: bool) -> Vec<String> {
+    let mut tables = vec![];
+
+    if remove_scabbard_state {
+        let mut scabbard_tables = existing
+            .iter()
+            .filter(|table| {
+                SCABBARD_TABLE_PREFIXES
+                    .iter()
+                    .any(|prefix| table.starts_with(prefix))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        scabbard_tables.sort();
+        tables.append(&mut scabbard_tables);
+    }
+
+    tables.extend(
+        SPLINTER_TABLES
+            .iter()
+            .filter(|table| existing.iter().any(|existing| existing == *table))
+            .map(|table| table.to_string()),
+    );
+
+    tables
+}
+
+#[cfg(feature = "postgres")]
+fn reset_postgres(conn: &PgConnection, remove_scabbard_state: bool) -> Result<(), CliError> {
+    let existing = sql_query(
+        "SELECT tablename::text AS name FROM pg_tables WHERE schemaname = current_schema()",
+    )
+    .load::<TableName>(conn)
+    .map_err(|err| CliError::ActionError(format!("Unable to list tables: {}", err)))?
+    .into_iter()
+    .map(|table| table.name)
+    .collect::<Vec<_>>();
+
+    let tables = tables_to_reset(&existing, remove_scabbard_state);
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    debug!("Truncating tables: {}", tables.join(", "));
+    conn.batch_execute(&format!("TRUNCATE TABLE {} CASCADE", tables.join(", ")))
+        .map_err(|err| CliError::ActionError(format!("Unable to reset database: {}", err)))
+}
+
+#[cfg(feature = "sqlite")]
+fn reset_sqlite(conn: &SqliteConnection, remove_scabbard_state: bool) -> Result<(), CliError> {
+    let existing = sql_query("SELECT name FROM sqlite_master WHERE type = 'table'")
+        .load::<TableName>(conn)
+        .map_err(|err| CliError::ActionError(format!("Unable to list tables: {}", err)))?
+        .into_iter()
+        .map(|table| table.name)
+        .collect::<Vec<_>>();
+
+    let tables = tables_to_reset(&existing, remove_scabbard_state);
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    debug!("Deleting from tables: {}", tables.join(", "));
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+        for table in &tables {
+            conn.batch_execute(&format!("DELETE FROM {}", table))?;
+        }
+        Ok(())
+    })
+    .map_err(|err| CliError::ActionError(format!("Unable to reset database: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that only existing splinter tables are reset, in dependency order, and that
+    /// scabbard tables are only included when requested.
+    #[test]
+    fn test_tables_to_reset() {
+        let existing = vec![
+            "__diesel_schema_migrations".to_string(),
+            "circuit".to_string(),
+            "node_id".to_string(),
+            "rbac_roles".to_string(),
+            "scabbard_service".to_string(),
+            "merkle_radix_leaf".to_string(),
+            "service".to_string(),
+        ];
+
+        assert_eq!(
+            tables_to_reset(&existing, false),
+            vec!["service", "circuit", "node_id"]
+        );
+
+        assert_eq!(
+            tables_to_reset(&existing, true),
+            vec![
+                "merkle_radix_leaf",
+                "scabbard_service",
+                "service",
+                "circuit",
+                "node_id"
+            ]
+        );
+    }
+}
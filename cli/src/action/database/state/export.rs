@@ -0,0 +1,436 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides export and import of scabbard state to and from a portable file
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::str::FromStr;
+
+use clap::ArgMatches;
+use scabbard::store::transact::factory::LmdbDatabaseFactory;
+use serde::{Deserialize, Serialize};
+use splinter::error::InternalError;
+use splinter::hex::{parse_hex, to_hex};
+use transact::state::{Reader, StateChange};
+
+use crate::action::database::{
+    get_default_database,
+    stores::{new_upgrade_stores, TransactionalUpgradeStores, UpgradeStoresWithLmdb},
+    ConnectionUri,
+};
+
+use super::{get_state_dir, write_and_prune_with_cleanup, Action, CliError, MerkleState};
+
+/// The number of state entries written per commit when importing
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+/// A single line of an exported state file.
+///
+/// An export is a sequence of JSON lines. Each service begins with a `service` record, which is
+/// followed by one `entry` record for every state entry at the service's state root.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StateRecord {
+    Service {
+        circuit_id: String,
+        service_id: String,
+        state_root: String,
+    },
+    Entry {
+        key: String,
+        value: String,
+    },
+}
+
+pub struct StateExportAction;
+
+impl Action for StateExportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let output = args
+            .value_of("output")
+            .ok_or_else(|| CliError::ActionError("'output' argument is required".into()))?;
+        let circuit_filter = args.value_of("circuit");
+
+        let upgrade_stores = get_upgrade_stores(args)?;
+        let services = local_scabbard_services(&*upgrade_stores)?;
+
+        let file = File::create(output).map_err(|err| {
+            CliError::ActionError(format!("Unable to create {}: {}", output, err))
+        })?;
+        let mut writer = BufWriter::new(file);
+
+        let mut exported = 0;
+        for (circuit_id, service_id) in services {
+            if circuit_filter.map(|id| id != circuit_id).unwrap_or(false) {
+                continue;
+            }
+
+            let state_root = upgrade_stores
+                .new_commit_hash_store(&circuit_id, &service_id)
+                .get_current_commit_hash()
+                .map_err(|err| CliError::ActionError(err.to_string()))?
+                .ok_or_else(|| {
+                    CliError::ActionError(format!(
+                        "No commit hash for service {}::{}",
+                        circuit_id, service_id,
+                    ))
+                })?;
+
+            info!("Exporting state data for {}::{}", circuit_id, service_id);
+
+            let state_reader = upgrade_stores
+                .get_merkle_state(&circuit_id, &service_id, false)
+                .map_err(|err| CliError::ActionError(err.to_string()))?;
+
+            write_record(
+                &mut writer,
+                &StateRecord::Service {
+                    circuit_id: circuit_id.clone(),
+                    service_id: service_id.clone(),
+                    state_root: state_root.clone(),
+                },
+            )?;
+            let count = export_state(&state_reader, &state_root, &mut writer)?;
+
+            debug!(
+                "Exported {} entries for {}::{} at state root {}",
+                count, circuit_id, service_id, state_root
+            );
+            exported += 1;
+        }
+
+        writer
+            .flush()
+            .map_err(|err| CliError::ActionError(format!("Unable to write {}: {}", output, err)))?;
+
+        info!(
+            "Exported scabbard state for {} service(s) to {}",
+            exported, output
+        );
+
+        Ok(())
+    }
+}
+
+pub struct StateImportAction;
+
+impl Action for StateImportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let input = args
+            .value_of("input")
+            .ok_or_else(|| CliError::ActionError("'input' argument is required".into()))?;
+        let force = args.is_present("force");
+
+        let upgrade_stores = get_upgrade_stores(args)?;
+
+        let file = File::open(input)
+            .map_err(|err| CliError::ActionError(format!("Unable to open {}: {}", input, err)))?;
+        let mut records = BufReader::new(file)
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_record(index + 1, line))
+            .peekable();
+
+        let mut imported = 0;
+        while let Some(record) = records.next() {
+            let (circuit_id, service_id, state_root) = match record? {
+                StateRecord::Service {
+                    circuit_id,
+                    service_id,
+                    state_root,
+                } => (circuit_id, service_id, state_root),
+                StateRecord::Entry { .. } => {
+                    return Err(CliError::ActionError(
+                        "State entry found before a service record".into(),
+                    ))
+                }
+            };
+
+            info!("Importing state data for {}::{}", circuit_id, service_id);
+
+            if !force
+                && upgrade_stores
+                    .new_state_tree_store()
+                    .has_tree(&circuit_id, &service_id)
+                    .map_err(|err| CliError::ActionError(err.to_string()))?
+            {
+                return Err(CliError::ActionError(format!(
+                    "Merkle Tree for {}::{} already exists",
+                    circuit_id, service_id
+                )));
+            }
+
+            let records = &mut records;
+            upgrade_stores
+                .in_transaction(Box::new(|txn_stores| {
+                    let commit_hash_store =
+                        txn_stores.new_commit_hash_store(&circuit_id, &service_id);
+                    match commit_hash_store
+                        .get_current_commit_hash()
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?
+                    {
+                        Some(current) if current != state_root && !force => {
+                            return Err(InternalError::with_message(format!(
+                                "Commit hash for {}::{} is {}, but the exported state root \
+                                 is {}",
+                                circuit_id, service_id, current, state_root
+                            )));
+                        }
+                        _ => (),
+                    }
+
+                    let state_writer =
+                        txn_stores.get_merkle_state(&circuit_id, &service_id, true)?;
+
+                    // Consume entries up to, but not including, the next service record
+                    let entries = std::iter::from_fn(|| match records.peek() {
+                        Some(Ok(StateRecord::Service { .. })) | None => None,
+                        _ => records.next(),
+                    });
+
+                    if let Err(err) = import_state(entries, &state_root, &state_writer) {
+                        // delete the partially imported state, so that it doesn't exist
+                        state_writer
+                            .delete_tree()
+                            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+                        return Err(err);
+                    }
+
+                    commit_hash_store
+                        .set_current_commit_hash(&state_root)
+                        .map_err(|err| InternalError::from_source(Box::new(err)))
+                }))
+                .map_err(|err| CliError::ActionError(err.to_string()))?;
+
+            imported += 1;
+        }
+
+        info!(
+            "Imported scabbard state for {} service(s) from {}",
+            imported, input
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the stores for the database given by `--connect`, reading merkle state from LMDB
+/// files if `--lmdb` is set.
+fn get_upgrade_stores(args: &ArgMatches) -> Result<Box<dyn TransactionalUpgradeStores>, CliError> {
+    let database_uri = match args.value_of("connect") {
+        Some(uri) => uri.to_string(),
+        None => get_default_database()?,
+    };
+
+    let upgrade_stores =
+        new_upgrade_stores(&ConnectionUri::from_str(&database_uri)?).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to get stores for database {}: {}",
+                database_uri, err
+            ))
+        })?;
+
+    if args.is_present("lmdb") {
+        let state_dir = get_state_dir(Some(args))?;
+        let lmdb_db_factory = LmdbDatabaseFactory::new_state_db_factory(&state_dir, None);
+        Ok(Box::new(UpgradeStoresWithLmdb::new(
+            upgrade_stores,
+            lmdb_db_factory,
+        )))
+    } else {
+        Ok(upgrade_stores)
+    }
+}
+
+/// Lists the circuit and service IDs of the scabbard services run by the local node.
+fn local_scabbard_services(
+    upgrade_stores: &dyn TransactionalUpgradeStores,
+) -> Result<Vec<(String, String)>, CliError> {
+    let node_id = match upgrade_stores
+        .new_node_id_store()
+        .get_node_id()
+        .map_err(|err| CliError::ActionError(err.to_string()))?
+    {
+        Some(node_id) => node_id,
+        // This node has not set a node id, so it cannot have any circuits.
+        None => return Ok(vec![]),
+    };
+
+    let circuits = upgrade_stores
+        .new_admin_service_store()
+        .list_circuits(&[])
+        .map_err(|err| CliError::ActionError(err.to_string()))?;
+
+    Ok(circuits
+        .into_iter()
+        .flat_map(|circuit| {
+            circuit
+                .roster()
+                .iter()
+                .filter(|svc| svc.node_id() == node_id && svc.service_type() == "scabbard")
+                .map(|svc| {
+                    (
+                        circuit.circuit_id().to_string(),
+                        svc.service_id().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+/// Writes every state entry at the given state root, returning the number of entries written.
+fn export_state<W: Write>(
+    state_reader: &MerkleState,
+    state_root: &str,
+    writer: &mut W,
+) -> Result<usize, CliError> {
+    let entries = state_reader
+        .filter_iter(&state_root.to_string(), None)
+        .map_err(|err| {
+            CliError::ActionError(format!("Unable to get leaves for commit hash: {}", err))
+        })?;
+
+    let mut count = 0;
+    for entry in entries {
+        let (key, value) = entry
+            .map_err(|err| CliError::ActionError(format!("Cannot get state entry: {}", err)))?;
+        write_record(
+            writer,
+            &StateRecord::Entry {
+                key,
+                value: to_hex(&value),
+            },
+        )?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Commits the given entries to the state writer and verifies that the resulting state root
+/// matches the exported one.
+fn import_state<I>(
+    entries: I,
+    expected_state_root: &str,
+    state_writer: &MerkleState,
+) -> Result<(), InternalError>
+where
+    I: Iterator<Item = Result<StateRecord, CliError>>,
+{
+    let mut last_state_id = state_writer
+        .get_state_root()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    let mut state_changes = vec![];
+    for entry in entries {
+        match entry.map_err(|err| InternalError::with_message(err.to_string()))? {
+            StateRecord::Entry { key, value } => {
+                let value = parse_hex(&value).map_err(|err| {
+                    InternalError::with_message(format!("Invalid value for key {}: {}", key, err))
+                })?;
+                state_changes.push(StateChange::Set { key, value });
+            }
+            StateRecord::Service { .. } => unreachable!("service records end the entries"),
+        }
+
+        if state_changes.len() >= IMPORT_BATCH_SIZE {
+            last_state_id =
+                write_and_prune_with_cleanup(state_writer, &last_state_id, &state_changes)?;
+            state_changes.clear();
+        }
+    }
+
+    last_state_id = write_and_prune_with_cleanup(state_writer, &last_state_id, &state_changes)?;
+
+    if last_state_id != expected_state_root {
+        return Err(InternalError::with_message(format!(
+            "Imported state root did not match the exported state root {} != {}",
+            last_state_id, expected_state_root
+        )));
+    }
+
+    Ok(())
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &StateRecord) -> Result<(), CliError> {
+    serde_json::to_writer(&mut *writer, record)
+        .map_err(|err| CliError::ActionError(format!("Unable to serialize state: {}", err)))?;
+    writer
+        .write_all(b"\n")
+        .map_err(|err| CliError::ActionError(format!("Unable to write state: {}", err)))
+}
+
+fn parse_record(
+    line_number: usize,
+    line: std::io::Result<String>,
+) -> Result<StateRecord, CliError> {
+    let line = line.map_err(|err| {
+        CliError::ActionError(format!("Unable to read line {}: {}", line_number, err))
+    })?;
+    serde_json::from_str(&line).map_err(|err| {
+        CliError::ActionError(format!(
+            "Invalid state record on line {}: {}",
+            line_number, err
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that state records are written as single JSON lines that can be parsed back.
+    #[test]
+    fn test_state_record_round_trip() {
+        let mut buffer = vec![];
+        write_record(
+            &mut buffer,
+            &StateRecord::Service {
+                circuit_id: "abcde-01234".into(),
+                service_id: "a000".into(),
+                state_root: "0123".into(),
+            },
+        )
+        .expect("Unable to write service record");
+        write_record(
+            &mut buffer,
+            &StateRecord::Entry {
+                key: "0a0b".into(),
+                value: to_hex(b"value"),
+            },
+        )
+        .expect("Unable to write entry record");
+
+        let text = String::from_utf8(buffer).expect("Export is not UTF-8");
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            r#"{"type":"service","circuit_id":"abcde-01234","service_id":"a000","state_root":"0123"}"#
+        );
+
+        match parse_record(2, Ok(lines[1].to_string())).expect("Unable to parse entry") {
+            StateRecord::Entry { key, value } => {
+                assert_eq!(key, "0a0b");
+                assert_eq!(parse_hex(&value).unwrap(), b"value".to_vec());
+            }
+            record => panic!("Unexpected record: {:?}", record),
+        }
+
+        assert!(parse_record(3, Ok("not json".into())).is_err());
+    }
+}
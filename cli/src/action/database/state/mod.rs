@@ -14,6 +14,8 @@
 
 //! Provides scabbard state migration functionality
 
+#[cfg(feature = "state-export")]
+mod export;
 mod merkle;
 
 use std::io;
@@ -33,6 +35,8 @@ use crate::action::database::{
 
 use super::{Action, CliError};
 
+#[cfg(feature = "state-export")]
+pub use self::export::{StateExportAction, StateImportAction};
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 pub use self::merkle::{DieselInTransactionStateTreeStore, DieselStateTreeStore};
 pub use self::merkle::{LazyLmdbMerkleState, LmdbStateTreeStore, MerkleState};
@@ -297,7 +301,7 @@ impl Action for StateMigrateAction {
 ///
 /// * PathBuf to state_dir if present in arg_matches, otherwise just the default from
 /// SplinterEnvironment
-fn get_state_dir(arg_matches: Option<&ArgMatches>) -> Result<PathBuf, CliError> {
+pub(super) fn get_state_dir(arg_matches: Option<&ArgMatches>) -> Result<PathBuf, CliError> {
     if let Some(arg_matches) = arg_matches {
         match arg_matches.value_of("state_dir") {
             Some(state_dir) => {
@@ -0,0 +1,553 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides a guided upgrade that detects which upgrade steps a node requires, runs them and
+//! reports the results
+
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use diesel::Connection;
+use serde::Serialize;
+use splinter::error::InternalError;
+use splinter::store::StoreFactory;
+
+#[cfg(feature = "postgres")]
+use crate::action::database::postgres::postgres_migrations;
+#[cfg(feature = "sqlite")]
+use crate::action::database::sqlite::sqlite_migrations;
+use crate::action::database::ConnectionUri;
+use crate::error::CliError;
+
+use super::yaml::{CIRCUITS_FILE, PROPOSALS_FILE};
+use super::{create_store_factory, get_database_uri, get_state_dir, node_id, receipt_store, yaml};
+
+const NODE_ID_FILE: &str = "node_id";
+const RECEIPT_FILE_SUFFIX: &str = "-receipts.lmdb";
+#[cfg(feature = "sqlite")]
+const MEMORY: &str = ":memory:";
+
+/// The steps that may be required to upgrade a node, in the order they must be run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UpgradeStep {
+    Migrations,
+    NodeId,
+    YamlState,
+    ScabbardCommitHashes,
+    ScabbardReceipts,
+}
+
+impl UpgradeStep {
+    const ALL: [UpgradeStep; 5] = [
+        UpgradeStep::Migrations,
+        UpgradeStep::NodeId,
+        UpgradeStep::YamlState,
+        UpgradeStep::ScabbardCommitHashes,
+        UpgradeStep::ScabbardReceipts,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            UpgradeStep::Migrations => "migrations",
+            UpgradeStep::NodeId => "node-id",
+            UpgradeStep::YamlState => "yaml-state",
+            UpgradeStep::ScabbardCommitHashes => "scabbard-commit-hashes",
+            UpgradeStep::ScabbardReceipts => "scabbard-receipts",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            UpgradeStep::Migrations => "Run the database migrations",
+            UpgradeStep::NodeId => "Import the node_id file into the database",
+            UpgradeStep::YamlState => {
+                "Import the YAML circuit and proposal state into the database"
+            }
+            UpgradeStep::ScabbardCommitHashes => {
+                "Import the scabbard commit hashes from the LMDB state files into the database"
+            }
+            UpgradeStep::ScabbardReceipts => {
+                "Import the scabbard transaction receipts from the LMDB receipt files into the \
+                 database"
+            }
+        }
+    }
+}
+
+/// The version of the node's state, as detected from the state directory and the database
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StateVersion {
+    /// No database exists and no file-backed state was found
+    Uninitialized,
+    /// The node still has file-backed state, as used by Splinter 0.4
+    FileBacked,
+    /// The node's state is in a database that requires migrations
+    DatabaseOutdated,
+    /// The node's state is up to date
+    Current,
+}
+
+impl fmt::Display for StateVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StateVersion::Uninitialized => f.write_str("uninitialized, no state found"),
+            StateVersion::FileBacked => f.write_str("file-backed state (Splinter 0.4)"),
+            StateVersion::DatabaseOutdated => f.write_str("database state with pending migrations"),
+            StateVersion::Current => f.write_str("current"),
+        }
+    }
+}
+
+/// What was found in the state directory and the database before upgrading
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DetectedState {
+    database_exists: bool,
+    pending_migrations: bool,
+    node_id_file: bool,
+    yaml_state_files: bool,
+    scabbard_lmdb_files: bool,
+    receipt_lmdb_files: bool,
+}
+
+impl DetectedState {
+    fn detect(state_dir: &Path, database_uri: &ConnectionUri) -> Result<Self, CliError> {
+        let mut detected = Self::detect_files(state_dir)?;
+        let (database_exists, pending_migrations) = detect_database(database_uri)?;
+        detected.database_exists = database_exists;
+        detected.pending_migrations = pending_migrations;
+        Ok(detected)
+    }
+
+    fn detect_files(state_dir: &Path) -> Result<Self, CliError> {
+        if !state_dir.is_dir() {
+            return Ok(Self::default());
+        }
+
+        let receipt_lmdb_files = std::fs::read_dir(state_dir)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<Result<Vec<_>, _>>()?
+            .iter()
+            .any(|name| name.to_string_lossy().ends_with(RECEIPT_FILE_SUFFIX));
+
+        Ok(Self {
+            node_id_file: state_dir.join(NODE_ID_FILE).exists(),
+            yaml_state_files: state_dir.join(CIRCUITS_FILE).exists()
+                || state_dir.join(PROPOSALS_FILE).exists(),
+            scabbard_lmdb_files: super::scabbard::check_for_lmdb_files(state_dir)
+                .map_err(|err| CliError::ActionError(err.to_string()))?,
+            receipt_lmdb_files,
+            ..Self::default()
+        })
+    }
+
+    fn state_version(&self) -> StateVersion {
+        if self.node_id_file || self.yaml_state_files {
+            StateVersion::FileBacked
+        } else if !self.database_exists {
+            StateVersion::Uninitialized
+        } else if self.pending_migrations {
+            StateVersion::DatabaseOutdated
+        } else {
+            StateVersion::Current
+        }
+    }
+
+    fn requires(&self, step: UpgradeStep) -> bool {
+        match step {
+            UpgradeStep::Migrations => self.pending_migrations,
+            UpgradeStep::NodeId => self.node_id_file,
+            UpgradeStep::YamlState => self.yaml_state_files,
+            // Repeating this step is harmless, so it is run whenever LMDB state is present
+            UpgradeStep::ScabbardCommitHashes => self.scabbard_lmdb_files,
+            UpgradeStep::ScabbardReceipts => self.receipt_lmdb_files,
+        }
+    }
+}
+
+/// Returns whether the database exists and whether it has pending migrations.
+fn detect_database(database_uri: &ConnectionUri) -> Result<(bool, bool), CliError> {
+    match database_uri {
+        #[cfg(feature = "postgres")]
+        ConnectionUri::Postgres(url) => {
+            let connection = diesel::pg::PgConnection::establish(url).map_err(|err| {
+                CliError::ActionError(format!(
+                    "Failed to establish database connection to '{}': {}",
+                    url, err
+                ))
+            })?;
+            let pending = splinter::migrations::any_pending_postgres_migrations(&connection)
+                .map_err(migration_check_error)?
+                || ::scabbard::migrations::any_pending_postgres_migrations(&connection)
+                    .map_err(migration_check_error)?;
+            Ok((true, pending))
+        }
+        #[cfg(feature = "sqlite")]
+        ConnectionUri::Sqlite(connection_string) => {
+            if connection_string != MEMORY && !Path::new(connection_string).exists() {
+                return Ok((false, true));
+            }
+            let connection = diesel::sqlite::SqliteConnection::establish(connection_string)
+                .map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to open SQLite database '{}': {}",
+                        connection_string, err
+                    ))
+                })?;
+            let pending = splinter::migrations::any_pending_sqlite_migrations(&connection)
+                .map_err(migration_check_error)?
+                || ::scabbard::migrations::any_pending_sqlite_migrations(&connection)
+                    .map_err(migration_check_error)?;
+            Ok((true, pending))
+        }
+    }
+}
+
+fn migration_check_error(err: InternalError) -> CliError {
+    CliError::ActionError(format!("Unable to check for pending migrations: {}", err))
+}
+
+/// The status of an upgrade step in the report
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StepStatus {
+    /// The step is required, but has not been run
+    Required,
+    /// The step is not required
+    UpToDate,
+    /// The step was run successfully
+    Completed,
+    /// The step was run and failed
+    Failed,
+}
+
+impl fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StepStatus::Required => f.write_str("required"),
+            StepStatus::UpToDate => f.write_str("up to date"),
+            StepStatus::Completed => f.write_str("completed"),
+            StepStatus::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct StepReport {
+    step: &'static str,
+    description: &'static str,
+    status: StepStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The report written by the upgrade assistant
+#[derive(Debug, Serialize)]
+struct UpgradeReport {
+    splinter_version: &'static str,
+    /// Seconds since the Unix epoch at which the upgrade was started
+    started_at: u64,
+    state_dir: String,
+    database: String,
+    detected_state: StateVersion,
+    plan_only: bool,
+    steps: Vec<StepReport>,
+}
+
+impl UpgradeReport {
+    fn new(
+        state_dir: &Path,
+        database_uri: &ConnectionUri,
+        detected: &DetectedState,
+        plan_only: bool,
+    ) -> Self {
+        Self {
+            splinter_version: env!("CARGO_PKG_VERSION"),
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            state_dir: state_dir.display().to_string(),
+            database: database_uri.to_string(),
+            detected_state: detected.state_version(),
+            plan_only,
+            steps: UpgradeStep::ALL
+                .iter()
+                .map(|step| StepReport {
+                    step: step.name(),
+                    description: step.description(),
+                    status: if detected.requires(*step) {
+                        StepStatus::Required
+                    } else {
+                        StepStatus::UpToDate
+                    },
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn write(&self, path: &Path) -> Result<(), CliError> {
+        let file = File::create(path).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to create upgrade report {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        serde_yaml::to_writer(file, self).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to write upgrade report {}: {}",
+                path.display(),
+                err
+            ))
+        })
+    }
+}
+
+/// Detects the steps required to upgrade the node, then runs them in order unless only a plan was
+/// requested.
+///
+/// Each step is only run if it is required, and each step may be safely repeated, so the
+/// assistant may be run again after a failure or an interrupted upgrade. If a step fails, the
+/// remaining steps are not run.
+pub(super) fn run_upgrade_assistant(arg_matches: Option<&ArgMatches>) -> Result<(), CliError> {
+    let state_dir = get_state_dir(arg_matches)?;
+    let database_uri = get_database_uri(arg_matches)?;
+    let plan_only = arg_matches
+        .map(|args| args.is_present("plan"))
+        .unwrap_or(false);
+    let report_path = arg_matches
+        .and_then(|args| args.value_of("report"))
+        .map(PathBuf::from);
+
+    let detected = DetectedState::detect(&state_dir, &database_uri)?;
+    let mut report = UpgradeReport::new(&state_dir, &database_uri, &detected, plan_only);
+
+    info!("State directory: {}", state_dir.display());
+    info!("Database: {}", database_uri);
+    info!("Detected state: {}", report.detected_state);
+    info!("Upgrade steps:");
+    for step in &report.steps {
+        info!(
+            "  {:<24} {:<11} {}",
+            step.step, step.status, step.description
+        );
+    }
+
+    let result = if plan_only {
+        Ok(())
+    } else {
+        run_steps(&mut report, &state_dir, &database_uri)
+    };
+
+    if let Some(report_path) = report_path {
+        report.write(&report_path)?;
+        info!("Wrote upgrade report to {}", report_path.display());
+    }
+
+    result
+}
+
+fn run_steps(
+    report: &mut UpgradeReport,
+    state_dir: &Path,
+    database_uri: &ConnectionUri,
+) -> Result<(), CliError> {
+    let mut store_factory = None;
+
+    for (step, step_report) in UpgradeStep::ALL.iter().zip(report.steps.iter_mut()) {
+        if step_report.status != StepStatus::Required {
+            continue;
+        }
+
+        info!("Running upgrade step {}", step.name());
+        match run_step(*step, state_dir, database_uri, &mut store_factory) {
+            Ok(()) => step_report.status = StepStatus::Completed,
+            Err(err) => {
+                step_report.status = StepStatus::Failed;
+                step_report.error = Some(err.to_string());
+                return Err(CliError::ActionError(format!(
+                    "Upgrade step {} failed: {}",
+                    step.name(),
+                    err
+                )));
+            }
+        }
+    }
+
+    if report
+        .steps
+        .iter()
+        .all(|step| step.status == StepStatus::UpToDate)
+    {
+        info!("No upgrade required");
+    } else {
+        info!("Upgrade complete");
+    }
+
+    Ok(())
+}
+
+fn run_step(
+    step: UpgradeStep,
+    state_dir: &Path,
+    database_uri: &ConnectionUri,
+    store_factory: &mut Option<Box<dyn StoreFactory>>,
+) -> Result<(), CliError> {
+    match step {
+        UpgradeStep::Migrations => match database_uri {
+            #[cfg(feature = "postgres")]
+            ConnectionUri::Postgres(url) => postgres_migrations(url),
+            #[cfg(feature = "sqlite")]
+            ConnectionUri::Sqlite(connection_string) => {
+                sqlite_migrations(connection_string.to_string())
+            }
+        },
+        UpgradeStep::NodeId => node_id::migrate_node_id_to_db(
+            state_dir.to_path_buf(),
+            &*get_stores(database_uri, store_factory)?.get_node_id_store(),
+        ),
+        UpgradeStep::YamlState => yaml::import_yaml_state_to_database(
+            state_dir,
+            &*get_stores(database_uri, store_factory)?.get_admin_service_store(),
+        ),
+        UpgradeStep::ScabbardCommitHashes => {
+            super::scabbard::upgrade_scabbard_commit_hash_state(state_dir, database_uri).map_err(
+                |err| {
+                    CliError::ActionError(format!(
+                        "failed to upgrade scabbard commit hash state: {}",
+                        err
+                    ))
+                },
+            )
+        }
+        UpgradeStep::ScabbardReceipts => {
+            receipt_store::upgrade_scabbard_receipt_store(state_dir, database_uri)
+        }
+    }
+}
+
+/// Returns the store factory, creating it on first use so that the stores are only created once
+/// the migrations have been run.
+fn get_stores<'a>(
+    database_uri: &ConnectionUri,
+    store_factory: &'a mut Option<Box<dyn StoreFactory>>,
+) -> Result<&'a dyn StoreFactory, CliError> {
+    if store_factory.is_none() {
+        *store_factory = Some(create_store_factory(database_uri).map_err(|err| {
+            CliError::ActionError(format!("failed to initialized store factory: {}", err))
+        })?);
+    }
+    Ok(store_factory
+        .as_deref()
+        .expect("store factory was not initialized"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::Builder;
+
+    /// Verify that the file-backed state in a state directory is detected, along with the steps
+    /// it requires.
+    #[test]
+    fn test_detect_files() {
+        let temp_dir = Builder::new()
+            .prefix("test_detect_files")
+            .tempdir()
+            .expect("Failed to create temp dir");
+
+        let detected =
+            DetectedState::detect_files(temp_dir.path()).expect("Failed to detect state");
+        assert_eq!(detected, DetectedState::default());
+        assert_eq!(detected.state_version(), StateVersion::Uninitialized);
+
+        File::create(temp_dir.path().join(NODE_ID_FILE)).expect("Failed to create node_id");
+        File::create(temp_dir.path().join(CIRCUITS_FILE)).expect("Failed to create circuits");
+        File::create(temp_dir.path().join("abcd-receipts.lmdb"))
+            .expect("Failed to create receipts");
+
+        let detected =
+            DetectedState::detect_files(temp_dir.path()).expect("Failed to detect state");
+        assert!(detected.node_id_file);
+        assert!(detected.yaml_state_files);
+        assert!(detected.scabbard_lmdb_files);
+        assert!(detected.receipt_lmdb_files);
+        assert_eq!(detected.state_version(), StateVersion::FileBacked);
+        assert!(detected.requires(UpgradeStep::NodeId));
+        assert!(!detected.requires(UpgradeStep::Migrations));
+    }
+
+    /// Verify the state version detected from the database alone.
+    #[test]
+    fn test_state_version_from_database() {
+        let mut detected = DetectedState {
+            database_exists: true,
+            pending_migrations: true,
+            ..DetectedState::default()
+        };
+        assert_eq!(detected.state_version(), StateVersion::DatabaseOutdated);
+        assert!(detected.requires(UpgradeStep::Migrations));
+
+        detected.pending_migrations = false;
+        assert_eq!(detected.state_version(), StateVersion::Current);
+        assert!(UpgradeStep::ALL
+            .iter()
+            .all(|step| !detected.requires(*step)));
+    }
+
+    /// Verify that a plan-only report lists every step with its status.
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_report_plan() {
+        let detected = DetectedState {
+            database_exists: true,
+            yaml_state_files: true,
+            ..DetectedState::default()
+        };
+        let report = UpgradeReport::new(
+            Path::new("/var/lib/splinter"),
+            &ConnectionUri::Sqlite("/var/lib/splinter/splinter_state.db".into()),
+            &detected,
+            true,
+        );
+
+        assert_eq!(report.detected_state, StateVersion::FileBacked);
+        assert_eq!(report.steps.len(), UpgradeStep::ALL.len());
+        let statuses = report
+            .steps
+            .iter()
+            .map(|step| (step.step, step.status.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ("migrations", StepStatus::UpToDate),
+                ("node-id", StepStatus::UpToDate),
+                ("yaml-state", StepStatus::Required),
+                ("scabbard-commit-hashes", StepStatus::UpToDate),
+                ("scabbard-receipts", StepStatus::UpToDate),
+            ]
+        );
+
+        let yaml = serde_yaml::to_string(&report).expect("Failed to serialize report");
+        assert!(yaml.contains("detected_state: file_backed"));
+        assert!(yaml.contains("status: required"));
+    }
+}
@@ -14,6 +14,8 @@
 
 //! Provides database upgrade functionality
 
+#[cfg(feature = "upgrade-assistant")]
+mod assistant;
 mod error;
 mod node_id;
 mod receipt_store;
@@ -43,42 +45,55 @@ pub struct UpgradeAction;
 
 impl Action for UpgradeAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let state_dir = get_state_dir(arg_matches)?;
-        let database_uri = get_database_uri(arg_matches)?;
-        let store_factory = create_store_factory(database_uri).map_err(|err| {
-            CliError::ActionError(format!("failed to initialized store factory: {}", err))
-        })?;
-        info!("Upgrading splinterd state");
-
-        #[cfg(any(feature = "sqlite", feature = "postgres"))]
+        #[cfg(feature = "upgrade-assistant")]
+        {
+            assistant::run_upgrade_assistant(arg_matches)
+        }
+        #[cfg(not(feature = "upgrade-assistant"))]
         {
-            let db_store = store_factory.get_node_id_store();
-            node_id::migrate_node_id_to_db(state_dir.clone(), &*db_store)?;
+            run_upgrade(arg_matches)
         }
+    }
+}
 
-        info!(
-            "Source yaml state directory: {}",
-            state_dir.to_string_lossy()
-        );
-        let database_uri = get_database_uri(arg_matches)?;
-        info!("Destination database uri: {}", database_uri);
-        info!("Loading YAML datastore... ");
-        let db_store = store_factory.get_admin_service_store();
-        yaml::import_yaml_state_to_database(state_dir.as_path(), &*db_store)?;
-
-        scabbard::upgrade_scabbard_commit_hash_state(state_dir.as_path(), &database_uri).map_err(
-            |err| {
-                CliError::ActionError(format!(
-                    "failed to upgrade scabbard commit hash state: {}",
-                    err
-                ))
-            },
-        )?;
-
-        receipt_store::upgrade_scabbard_receipt_store(state_dir.as_path(), &database_uri)?;
-
-        Ok(())
+/// Imports the file system state to the database, without checking which steps are required
+#[cfg(not(feature = "upgrade-assistant"))]
+fn run_upgrade(arg_matches: Option<&ArgMatches>) -> Result<(), CliError> {
+    let state_dir = get_state_dir(arg_matches)?;
+    let database_uri = get_database_uri(arg_matches)?;
+    let store_factory = create_store_factory(&database_uri).map_err(|err| {
+        CliError::ActionError(format!("failed to initialized store factory: {}", err))
+    })?;
+    info!("Upgrading splinterd state");
+
+    #[cfg(any(feature = "sqlite", feature = "postgres"))]
+    {
+        let db_store = store_factory.get_node_id_store();
+        node_id::migrate_node_id_to_db(state_dir.clone(), &*db_store)?;
     }
+
+    info!(
+        "Source yaml state directory: {}",
+        state_dir.to_string_lossy()
+    );
+    let database_uri = get_database_uri(arg_matches)?;
+    info!("Destination database uri: {}", database_uri);
+    info!("Loading YAML datastore... ");
+    let db_store = store_factory.get_admin_service_store();
+    yaml::import_yaml_state_to_database(state_dir.as_path(), &*db_store)?;
+
+    scabbard::upgrade_scabbard_commit_hash_state(state_dir.as_path(), &database_uri).map_err(
+        |err| {
+            CliError::ActionError(format!(
+                "failed to upgrade scabbard commit hash state: {}",
+                err
+            ))
+        },
+    )?;
+
+    receipt_store::upgrade_scabbard_receipt_store(state_dir.as_path(), &database_uri)?;
+
+    Ok(())
 }
 
 /// Gets the path of splinterd's state directory
@@ -146,17 +161,17 @@ fn get_database_uri(arg_matches: Option<&ArgMatches>) -> Result<ConnectionUri, C
 }
 
 fn create_store_factory(
-    connection_uri: ConnectionUri,
+    connection_uri: &ConnectionUri,
 ) -> Result<Box<dyn StoreFactory>, InternalError> {
     match connection_uri {
         #[cfg(feature = "postgres")]
         ConnectionUri::Postgres(url) => {
-            let pool = postgres::create_postgres_connection_pool(&url)?;
+            let pool = postgres::create_postgres_connection_pool(url)?;
             Ok(Box::new(postgres::PgStoreFactory::new(pool)))
         }
         #[cfg(feature = "sqlite")]
         ConnectionUri::Sqlite(conn_str) => {
-            let pool = sqlite::create_sqlite_connection_pool(&conn_str)?;
+            let pool = sqlite::create_sqlite_connection_pool(conn_str)?;
             Ok(Box::new(sqlite::SqliteStoreFactory::new(pool)))
         }
     }
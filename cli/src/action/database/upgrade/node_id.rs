@@ -37,6 +37,13 @@ fn import_store(
             .set_node_id(id)
             .map_err(|e| CliError::ActionError(format!("{}", e)))
             .map(|_| WarningEmitted::No),
+        (Ok(Some(id)), Ok(Some(existing_id))) if id == existing_id => {
+            info!(
+                "Skipping node_id import: node_id {} has already been imported",
+                id
+            );
+            Ok(WarningEmitted::No)
+        }
         (Ok(Some(_)), Ok(Some(_))) => Err(CliError::ActionError(
             "Skipping node_id import: destination store already has node_id set".to_string(),
         )),
@@ -90,9 +97,9 @@ enum WarningEmitted {
 
 #[cfg(test)]
 mod tests {
-    use std::cell::RefCell;
     use std::fs::File;
     use std::io::Write;
+    use std::sync::Mutex;
 
     use splinter::error::InternalError;
     use splinter::node_id::store::{error::NodeIdStoreError, NodeIdStore};
@@ -104,15 +111,15 @@ mod tests {
     const ALT_NODE_ID: &str = "yuiop";
 
     struct MockNodeIdStore {
-        pub value: RefCell<Option<String>>,
+        pub value: Mutex<Option<String>>,
     }
 
     impl NodeIdStore for MockNodeIdStore {
         fn get_node_id(&self) -> Result<Option<String>, NodeIdStoreError> {
-            Ok(self.value.borrow().to_owned())
+            Ok(self.value.lock().unwrap().to_owned())
         }
         fn set_node_id(&self, node_id: String) -> Result<(), NodeIdStoreError> {
-            self.value.replace(Some(node_id));
+            *self.value.lock().unwrap() = Some(node_id);
             Ok(())
         }
     }
@@ -120,7 +127,7 @@ mod tests {
     impl MockNodeIdStore {
         fn new(value: Option<String>) -> Self {
             Self {
-                value: RefCell::new(value),
+                value: Mutex::new(value),
             }
         }
     }
@@ -149,10 +156,10 @@ mod tests {
     // Simply tests that in the ideal case there are no errors or debug messages posted.
     fn test_import_to_empty_store() {
         let empty_store = MockNodeIdStore::new(None);
-        assert!(empty_store.value.borrow().is_none());
+        assert!(empty_store.value.lock().unwrap().is_none());
         let non_empty_store = MockNodeIdStore::new(Some(NODE_ID.to_string()));
-        assert!(non_empty_store.value.borrow().as_ref().is_some());
-        assert!(non_empty_store.value.borrow().as_ref().unwrap() == NODE_ID);
+        assert!(non_empty_store.value.lock().unwrap().as_ref().is_some());
+        assert!(non_empty_store.value.lock().unwrap().as_ref().unwrap() == NODE_ID);
         let import_result = import_store(&empty_store, &non_empty_store);
         assert!(import_result.is_ok());
         assert!(matches!(import_result.unwrap(), WarningEmitted::No));
@@ -178,6 +185,17 @@ mod tests {
         assert!(main_store.get_node_id().unwrap() == Some(String::from(NODE_ID)));
     }
 
+    #[test]
+    // Test that importing a node_id to a store that already has the same node_id set succeeds, so
+    // that the import may be repeated.
+    fn test_import_to_store_with_same_node_id() {
+        let main_store = MockNodeIdStore::new(Some(String::from(NODE_ID)));
+        let file_store = MockNodeIdStore::new(Some(String::from(NODE_ID)));
+        let import_result = import_store(&main_store, &file_store);
+        assert!(matches!(import_result, Ok(WarningEmitted::No)));
+        assert!(main_store.get_node_id().unwrap() == Some(String::from(NODE_ID)));
+    }
+
     #[test]
     // Test that a debug message is shown if the store being written too has some sort of an error.
     fn test_import_store_write_error() {
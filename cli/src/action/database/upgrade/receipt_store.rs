@@ -76,7 +76,16 @@ pub(super) fn upgrade_scabbard_receipt_store(
                     Err(e) => Err(CliError::ActionError(format!("{}", e))),
                 }
             })
-            .collect::<Result<Vec<(_, _, _)>, _>>()?;
+            .collect::<Result<Vec<(_, _, _)>, _>>()?
+            .into_iter()
+            // Receipt files that have already been imported are renamed, and are skipped here
+            .filter(|(_, _, file)| receipt_db_dir.join(file).exists())
+            .collect::<Vec<_>>();
+
+        if local_services_with_file.is_empty() {
+            info!("Skipping scabbard receipt store upgrade, no LMDB receipt files found");
+            return Ok(());
+        }
 
         let lmdb_file_names: Vec<String> = local_services_with_file
             .iter()
@@ -98,7 +98,7 @@ pub(super) fn upgrade_scabbard_commit_hash_state(
     Ok(())
 }
 
-fn check_for_lmdb_files(lmdb_path: &Path) -> Result<bool, InvalidStateError> {
+pub(super) fn check_for_lmdb_files(lmdb_path: &Path) -> Result<bool, InvalidStateError> {
     if !lmdb_path.is_dir() {
         return Err(InvalidStateError::with_message(format!(
             "{} is not a directory",
@@ -109,7 +109,6 @@ fn check_for_lmdb_files(lmdb_path: &Path) -> Result<bool, InvalidStateError> {
     match std::fs::read_dir(lmdb_path) {
         Ok(entries) => {
             for entry in entries {
-                error!("{:?}", entry);
                 let entry = entry.map_err(|err| {
                     InvalidStateError::with_message(format!(
                         "Unable to list files in {}: {}",
@@ -16,20 +16,31 @@
 
 use std::collections::HashMap;
 use std::error::Error;
+#[cfg(feature = "upgrade-yaml-verification")]
+use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::{fmt, fs};
 
+#[cfg(feature = "upgrade-yaml-verification")]
+use serde::Serialize;
 use splinter::admin::store::error::AdminServiceStoreError;
 use splinter::admin::store::yaml::YamlAdminServiceStore;
 use splinter::admin::store::AdminServiceStore;
+#[cfg(feature = "upgrade-yaml-verification")]
+use splinter::admin::store::Circuit;
 use splinter::admin::store::CircuitNodeBuilder;
 
 use crate::error::CliError;
 
-const CIRCUITS_FILE: &str = "circuits.yaml";
-const PROPOSALS_FILE: &str = "circuit_proposals.yaml";
+pub(super) const CIRCUITS_FILE: &str = "circuits.yaml";
+pub(super) const PROPOSALS_FILE: &str = "circuit_proposals.yaml";
+#[cfg(feature = "upgrade-yaml-verification")]
+const REPORT_FILE: &str = "yaml_import_report.yaml";
 
 /// Import all the data from one store to another store
+///
+/// Circuits and proposals that already exist in the destination store are skipped, so that an
+/// interrupted import may be repeated.
 fn import_store(
     to: &'_ dyn AdminServiceStore,
     from: &'_ dyn AdminServiceStore,
@@ -47,6 +58,11 @@ fn import_store(
     for circuit in circuits {
         let id = circuit.circuit_id().to_string();
 
+        if to.get_circuit(&id).map_err(ImportError::Store)?.is_some() {
+            debug!("Skipping circuit {}: already imported", id);
+            continue;
+        }
+
         // Yaml circuits do not store the endpoints, so we're adding them from the nodes
         // definition. Not doing this will cause the database to enter an invalid state,
         // and listing the circuits will fail
@@ -78,6 +94,17 @@ fn import_store(
         .list_proposals(predicates)
         .map_err(ImportError::Store)?;
     for proposal in proposals {
+        if to
+            .get_proposal(proposal.circuit_id())
+            .map_err(ImportError::Store)?
+            .is_some()
+        {
+            debug!(
+                "Skipping proposal for circuit {}: already imported",
+                proposal.circuit_id()
+            );
+            continue;
+        }
         let requester = proposal.requester_node_id().to_string();
         to.add_proposal(proposal)
             .map_err(|e| ImportError::Proposal(requester, e))?;
@@ -87,6 +114,114 @@ fn import_store(
     Ok(import_result)
 }
 
+/// Verify that all of the state in one store was imported into another store
+///
+/// Every circuit and proposal in the source store must exist in the destination store, and the
+/// fields that identify them must match. Circuits and proposals skipped by the import, because
+/// they already existed in the destination store, are verified as well.
+#[cfg(feature = "upgrade-yaml-verification")]
+fn verify_import(
+    to: &'_ dyn AdminServiceStore,
+    from: &'_ dyn AdminServiceStore,
+    import_result: &ImportResult,
+) -> Result<VerificationReport, ImportError> {
+    let predicates = &[];
+    let mut circuits = IdVerification {
+        database_count: to
+            .list_circuits(predicates)
+            .map_err(ImportError::Store)?
+            .count(),
+        ..Default::default()
+    };
+
+    for circuit in from.list_circuits(predicates).map_err(ImportError::Store)? {
+        circuits.yaml_count += 1;
+        let id = circuit.circuit_id().to_string();
+
+        let imported = match to.get_circuit(&id).map_err(ImportError::Store)? {
+            Some(imported) => imported,
+            None => {
+                circuits.missing.push(id);
+                continue;
+            }
+        };
+
+        let node_ids = |circuit: &Circuit| {
+            let mut node_ids = circuit
+                .members()
+                .iter()
+                .map(|node| node.node_id().to_string())
+                .collect::<Vec<_>>();
+            node_ids.sort();
+            node_ids
+        };
+        let service_ids = |circuit: &Circuit| {
+            let mut service_ids = circuit
+                .roster()
+                .iter()
+                .map(|service| service.service_id().to_string())
+                .collect::<Vec<_>>();
+            service_ids.sort();
+            service_ids
+        };
+
+        if node_ids(&circuit) != node_ids(&imported) {
+            circuits.mismatch(&id, "members");
+        }
+        if service_ids(&circuit) != service_ids(&imported) {
+            circuits.mismatch(&id, "roster");
+        }
+        if circuit.circuit_management_type() != imported.circuit_management_type() {
+            circuits.mismatch(&id, "circuit_management_type");
+        }
+    }
+
+    let mut proposals = IdVerification {
+        database_count: to
+            .list_proposals(predicates)
+            .map_err(ImportError::Store)?
+            .count(),
+        ..Default::default()
+    };
+
+    for proposal in from
+        .list_proposals(predicates)
+        .map_err(ImportError::Store)?
+    {
+        proposals.yaml_count += 1;
+        let id = proposal.circuit_id().to_string();
+
+        let imported = match to.get_proposal(&id).map_err(ImportError::Store)? {
+            Some(imported) => imported,
+            None => {
+                proposals.missing.push(id);
+                continue;
+            }
+        };
+
+        if proposal.proposal_type() != imported.proposal_type() {
+            proposals.mismatch(&id, "proposal_type");
+        }
+        if proposal.circuit_hash() != imported.circuit_hash() {
+            proposals.mismatch(&id, "circuit_hash");
+        }
+        if proposal.requester_node_id() != imported.requester_node_id() {
+            proposals.mismatch(&id, "requester_node_id");
+        }
+        if proposal.votes().len() != imported.votes().len() {
+            proposals.mismatch(&id, "votes");
+        }
+    }
+
+    Ok(VerificationReport {
+        passed: circuits.passed() && proposals.passed(),
+        imported_circuits: import_result.circuits,
+        imported_proposals: import_result.proposals,
+        circuits,
+        proposals,
+    })
+}
+
 /// Import yaml state from the specified directory to a database
 pub fn import_yaml_state_to_database(
     state_dir: &Path,
@@ -120,12 +255,28 @@ pub fn import_yaml_state_to_database(
     })?;
 
     info!("Processing import data... ");
-    let result = import_store(db_store, &yaml_admin_service_store).map_err(|e| {
-        CliError::ActionError(match e.source() {
-            Some(source) => format!("{}: {}", e, source),
-            None => e.to_string(),
-        })
-    })?;
+    let result =
+        import_store(db_store, &yaml_admin_service_store).map_err(import_error_to_cli_error)?;
+
+    #[cfg(feature = "upgrade-yaml-verification")]
+    {
+        info!("Verifying imported state... ");
+        let report = verify_import(db_store, &yaml_admin_service_store, &result)
+            .map_err(import_error_to_cli_error)?;
+        report.log();
+
+        let report_location = state_dir.join(REPORT_FILE);
+        report.write(&report_location)?;
+        info!("Wrote import report to {}", report_location.display());
+
+        if !report.passed {
+            return Err(CliError::ActionError(format!(
+                "imported state does not match the YAML state files, which have not been moved; \
+                 see {} for details",
+                report_location.display()
+            )));
+        }
+    }
 
     info!("Backing up state files... ");
     let new_circuits_location = circuits_location.with_extension("yaml.old");
@@ -143,6 +294,13 @@ pub fn import_yaml_state_to_database(
     Ok(())
 }
 
+fn import_error_to_cli_error(err: ImportError) -> CliError {
+    CliError::ActionError(match err.source() {
+        Some(source) => format!("{}: {}", err, source),
+        None => err.to_string(),
+    })
+}
+
 /// Represents errors that may occur during the import process
 #[derive(Debug)]
 enum ImportError {
@@ -184,6 +342,110 @@ struct ImportResult {
     proposals: usize,
 }
 
+/// The result of verifying an import, written to the state directory
+#[cfg(feature = "upgrade-yaml-verification")]
+#[derive(Debug, Serialize)]
+struct VerificationReport {
+    /// Whether all of the YAML state was found in the database
+    passed: bool,
+    /// The number of circuits added to the database by this import
+    imported_circuits: usize,
+    /// The number of proposals added to the database by this import
+    imported_proposals: usize,
+    circuits: IdVerification,
+    proposals: IdVerification,
+}
+
+#[cfg(feature = "upgrade-yaml-verification")]
+impl VerificationReport {
+    fn log(&self) {
+        info!(
+            "Circuits: {} in YAML state, {} in database, {} missing, {} mismatched",
+            self.circuits.yaml_count,
+            self.circuits.database_count,
+            self.circuits.missing.len(),
+            self.circuits.mismatched.len(),
+        );
+        info!(
+            "Proposals: {} in YAML state, {} in database, {} missing, {} mismatched",
+            self.proposals.yaml_count,
+            self.proposals.database_count,
+            self.proposals.missing.len(),
+            self.proposals.mismatched.len(),
+        );
+        for id in &self.circuits.missing {
+            error!("Circuit {} was not imported", id);
+        }
+        for mismatch in &self.circuits.mismatched {
+            error!(
+                "Circuit {} does not match the YAML state: {} differs",
+                mismatch.id, mismatch.field
+            );
+        }
+        for id in &self.proposals.missing {
+            error!("Proposal for circuit {} was not imported", id);
+        }
+        for mismatch in &self.proposals.mismatched {
+            error!(
+                "Proposal for circuit {} does not match the YAML state: {} differs",
+                mismatch.id, mismatch.field
+            );
+        }
+    }
+
+    fn write(&self, path: &Path) -> Result<(), CliError> {
+        let file = File::create(path).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to create import report {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        serde_yaml::to_writer(file, self).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to write import report {}: {}",
+                path.display(),
+                err
+            ))
+        })
+    }
+}
+
+/// The comparison of one kind of YAML state with the database
+#[cfg(feature = "upgrade-yaml-verification")]
+#[derive(Debug, Default, Serialize)]
+struct IdVerification {
+    /// The number of entries in the YAML state
+    yaml_count: usize,
+    /// The number of entries in the database, which may include entries not in the YAML state
+    database_count: usize,
+    /// The IDs of entries in the YAML state that are not in the database
+    missing: Vec<String>,
+    /// Entries in the database that do not match the YAML state
+    mismatched: Vec<Mismatch>,
+}
+
+#[cfg(feature = "upgrade-yaml-verification")]
+impl IdVerification {
+    fn mismatch(&mut self, id: &str, field: &'static str) {
+        self.mismatched.push(Mismatch {
+            id: id.to_string(),
+            field,
+        });
+    }
+
+    fn passed(&self) -> bool {
+        self.missing.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+#[cfg(feature = "upgrade-yaml-verification")]
+#[derive(Debug, Serialize)]
+struct Mismatch {
+    id: String,
+    field: &'static str,
+}
+
 #[cfg(test)]
 mod action_tests {
     use super::*;
@@ -327,6 +589,62 @@ proposals:
         assert_eq!(db_store.list_proposals(&[]).unwrap().count(), 1);
     }
 
+    // Validate that importing the same YAML state twice succeeds without duplicating state
+    //
+    // 1. Imports the YAML state files into a test database
+    // 2. Recreates the YAML state files and imports them again into the same database
+    // 3. Validates that the second import succeeds and the database is unchanged
+    #[test]
+    fn test_import_command_repeated() {
+        let pool = create_connection_pool_and_migrate();
+        let db_store = DieselAdminServiceStore::new(pool);
+
+        let first = create_temp_files_from_data(CIRCUIT_STATE, PROPOSAL_STATE);
+        import_yaml_state_to_database(&first.temp_dir.path(), &db_store)
+            .expect("Failed to import state");
+
+        let second = create_temp_files_from_data(CIRCUIT_STATE, PROPOSAL_STATE);
+        import_yaml_state_to_database(&second.temp_dir.path(), &db_store)
+            .expect("Failed to import state a second time");
+
+        assert!(!Path::new(&second.circuits_path).exists());
+        assert!(!Path::new(&second.proposals_path).exists());
+        assert_eq!(db_store.list_circuits(&[]).unwrap().count(), 1);
+        assert_eq!(db_store.list_proposals(&[]).unwrap().count(), 1);
+    }
+
+    // Validate that the YAML state files are not moved if the imported state does not match
+    //
+    // 1. Imports YAML state with a different circuit management type into a test database
+    // 2. Imports the original YAML state into the same database, which skips the existing circuit
+    // 3. Validates that the import fails verification, leaving the YAML state files in place, and
+    //    that the report lists the mismatched circuit
+    #[cfg(feature = "upgrade-yaml-verification")]
+    #[test]
+    fn test_import_command_verification_fails() {
+        let pool = create_connection_pool_and_migrate();
+        let db_store = DieselAdminServiceStore::new(pool);
+
+        let modified_state = String::from_utf8_lossy(CIRCUIT_STATE).replace(
+            "circuit_management_type: gameroom",
+            "circuit_management_type: other",
+        );
+        let first = create_temp_files_from_data(modified_state.as_bytes(), PROPOSAL_STATE);
+        import_yaml_state_to_database(&first.temp_dir.path(), &db_store)
+            .expect("Failed to import state");
+
+        let second = create_temp_files_from_data(CIRCUIT_STATE, PROPOSAL_STATE);
+        assert!(import_yaml_state_to_database(&second.temp_dir.path(), &db_store).is_err());
+
+        assert!(Path::new(&second.circuits_path).exists());
+        assert!(Path::new(&second.proposals_path).exists());
+
+        let report = fs::read_to_string(second.temp_dir.path().join(REPORT_FILE))
+            .expect("Failed to read import report");
+        assert!(report.contains("passed: false"));
+        assert!(report.contains("field: circuit_management_type"));
+    }
+
     #[test]
     fn test_import_command_files_do_not_exist_aborts() {
         // Create only the temporary directory, but no state files
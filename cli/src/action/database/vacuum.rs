@@ -0,0 +1,130 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `splinter database vacuum` command, which removes old admin service events and
+//! reclaims the space they used
+
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use clap::ArgMatches;
+#[cfg(feature = "postgres")]
+use diesel::pg::PgConnection;
+#[cfg(feature = "sqlite")]
+use diesel::sqlite::SqliteConnection;
+use diesel::{connection::SimpleConnection, Connection};
+
+use crate::error::CliError;
+
+use super::stores::new_upgrade_stores;
+use super::{get_default_database, Action, ConnectionUri};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub struct VacuumAction;
+
+impl Action for VacuumAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let max_age = args
+            .value_of("max_age")
+            .map(|days| {
+                days.parse::<u64>().map_err(|_| {
+                    CliError::ActionError(format!("'{}' is not a valid number of days", days))
+                })
+            })
+            .transpose()?;
+        let max_count = args
+            .value_of("max_count")
+            .map(|count| {
+                count.parse::<u64>().map_err(|_| {
+                    CliError::ActionError(format!("'{}' is not a valid event count", count))
+                })
+            })
+            .transpose()?;
+
+        if max_age.is_none() && max_count.is_none() {
+            return Err(CliError::ActionError(
+                "At least one of --max-age or --max-count must be given".into(),
+            ));
+        }
+
+        let before = max_age
+            .map(|days| {
+                SystemTime::now()
+                    .checked_sub(Duration::from_secs(days * SECONDS_PER_DAY))
+                    .ok_or_else(|| {
+                        CliError::ActionError(format!("'{}' is not a valid number of days", days))
+                    })
+            })
+            .transpose()?;
+
+        let url = match args.value_of("connect") {
+            Some(url) => url.to_owned(),
+            None => get_default_database()?,
+        };
+        let connection_uri = ConnectionUri::from_str(&url)?;
+
+        let upgrade_stores = new_upgrade_stores(&connection_uri).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to open database {}: {}",
+                connection_uri, err
+            ))
+        })?;
+
+        let removed = upgrade_stores
+            .new_admin_service_store()
+            .prune_events(before, max_count)
+            .map_err(|err| {
+                CliError::ActionError(format!("Unable to remove admin service events: {}", err))
+            })?;
+
+        info!("Removed {} admin service event(s)", removed);
+
+        match connection_uri {
+            #[cfg(feature = "postgres")]
+            ConnectionUri::Postgres(url) => {
+                let conn = PgConnection::establish(&url).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to establish database connection to '{}': {}",
+                        url, err
+                    ))
+                })?;
+                vacuum(&conn)?
+            }
+            #[cfg(feature = "sqlite")]
+            ConnectionUri::Sqlite(conn_str) => {
+                let conn = SqliteConnection::establish(&conn_str).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to establish database connection to '{}': {}",
+                        conn_str, err
+                    ))
+                })?;
+                vacuum(&conn)?
+            }
+        }
+
+        info!("Database {} has been vacuumed", url);
+
+        Ok(())
+    }
+}
+
+/// Reclaims the space used by removed rows. Both PostgreSQL and SQLite refuse to vacuum inside a
+/// transaction, so this must be run on its own connection.
+fn vacuum<C: SimpleConnection>(conn: &C) -> Result<(), CliError> {
+    conn.batch_execute("VACUUM")
+        .map_err(|err| CliError::ActionError(format!("Unable to vacuum database: {}", err)))
+}
@@ -0,0 +1,356 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides the `health` subcommand actions.
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+#[cfg(feature = "health-service")]
+use super::api::HealthStatus;
+use super::api::SplinterRestClientBuilder;
+#[cfg(feature = "metrics-snapshot")]
+use super::api::{Metric, MetricValue};
+use super::{print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+
+#[cfg(feature = "health-service")]
+pub struct StatusAction;
+
+#[cfg(feature = "health-service")]
+impl Action for StatusAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let url = arg_matches
+            .and_then(|args| args.value_of("url"))
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+        let health = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .get_health()?;
+
+        println!("Live: {}", health.live);
+        println!("Ready: {}", health.ready);
+        if health.checks.is_empty() {
+            println!("The node has not completed its health checks");
+        } else {
+            println!();
+            print_table(check_table(&health));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "health-service")]
+fn check_table(health: &HealthStatus) -> Vec<Vec<String>> {
+    let mut table = vec![vec!["CHECK".into(), "STATUS".into(), "DETAILS".into()]];
+    for check in &health.checks {
+        table.push(vec![
+            check.name.clone(),
+            check.status.to_uppercase(),
+            check.details.clone(),
+        ]);
+    }
+    table
+}
+
+#[cfg(feature = "metrics-snapshot")]
+pub struct MetricsAction;
+
+#[cfg(feature = "metrics-snapshot")]
+impl Action for MetricsAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let url = arg_matches
+            .and_then(|args| args.value_of("url"))
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+        let metrics = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .get_metrics()?;
+
+        if arg_matches
+            .map(|args| args.is_present("all"))
+            .unwrap_or(false)
+        {
+            print_all_metrics(&metrics);
+        } else {
+            print_table(key_indicators(&metrics));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "metrics-snapshot")]
+/// Summarizes the metrics most useful for triage. Metrics reported per connection, circuit or
+/// service are combined; indicators the node has not reported are shown as `-`.
+fn key_indicators(metrics: &[Metric]) -> Vec<Vec<String>> {
+    let gauge = |name: &str| {
+        sum_gauges(metrics, name)
+            .map(|value| format!("{}", value))
+            .unwrap_or_else(|| "-".into())
+    };
+
+    let counter = |name: &str| {
+        sum_counters(metrics, name)
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "-".into())
+    };
+
+    let (average_latency, max_latency) = match latency(metrics, "splinter.scabbard.commit_latency")
+    {
+        Some((average, max)) => (format_secs(average), format_secs(max)),
+        None => ("-".into(), "-".into()),
+    };
+
+    vec![
+        vec!["INDICATOR".into(), "VALUE".into()],
+        vec!["Peers".into(), gauge("splinter.peer_manager.peers")],
+        vec![
+            "Dispatch queue depth".into(),
+            gauge("splinter.dispatch.queue_depth"),
+        ],
+        vec![
+            "Mesh outbound queue depth".into(),
+            gauge("splinter.mesh.outbound_queue_depth"),
+        ],
+        vec![
+            "Active circuits".into(),
+            gauge("splinter.admin.circuits.active"),
+        ],
+        vec!["Proposal backlog".into(), gauge("splinter.admin.proposals")],
+        vec![
+            "Scabbard pending batches".into(),
+            gauge("splinter.scabbard.pending_batches"),
+        ],
+        vec![
+            "Scabbard committed batches".into(),
+            counter("splinter.scabbard.committed_batches"),
+        ],
+        vec!["Scabbard commit latency (avg)".into(), average_latency],
+        vec!["Scabbard commit latency (max)".into(), max_latency],
+    ]
+}
+
+#[cfg(feature = "metrics-snapshot")]
+fn sum_gauges(metrics: &[Metric], name: &str) -> Option<f64> {
+    metrics
+        .iter()
+        .filter(|metric| metric.name == name)
+        .filter_map(|metric| match metric.value {
+            MetricValue::Gauge { value } => Some(value),
+            _ => None,
+        })
+        .fold(None, |total, value| Some(total.unwrap_or(0.0) + value))
+}
+
+#[cfg(feature = "metrics-snapshot")]
+fn sum_counters(metrics: &[Metric], name: &str) -> Option<u64> {
+    metrics
+        .iter()
+        .filter(|metric| metric.name == name)
+        .filter_map(|metric| match metric.value {
+            MetricValue::Counter { value } => Some(value),
+            _ => None,
+        })
+        .fold(None, |total, value| Some(total.unwrap_or(0) + value))
+}
+
+#[cfg(feature = "metrics-snapshot")]
+/// Returns the average and maximum of every value recorded by the named histogram.
+fn latency(metrics: &[Metric], name: &str) -> Option<(f64, f64)> {
+    let (count, sum, max) = metrics
+        .iter()
+        .filter(|metric| metric.name == name)
+        .filter_map(|metric| match metric.value {
+            MetricValue::Histogram {
+                count, sum, max, ..
+            } => Some((count, sum, max)),
+            _ => None,
+        })
+        .fold((0, 0.0, 0.0_f64), |(count, sum, max), (c, s, m)| {
+            (count + c, sum + s, max.max(m))
+        });
+
+    if count == 0 {
+        None
+    } else {
+        Some((sum / count as f64, max))
+    }
+}
+
+#[cfg(feature = "metrics-snapshot")]
+fn format_secs(secs: f64) -> String {
+    if secs < 1.0 {
+        format!("{:.1} ms", secs * 1000.0)
+    } else {
+        format!("{:.2} s", secs)
+    }
+}
+
+#[cfg(feature = "metrics-snapshot")]
+fn print_all_metrics(metrics: &[Metric]) {
+    let mut table = vec![vec!["NAME".into(), "LABELS".into(), "VALUE".into()]];
+    for metric in metrics {
+        let labels = metric
+            .labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(",");
+        let value = match metric.value {
+            MetricValue::Counter { value } => value.to_string(),
+            MetricValue::Gauge { value } => value.to_string(),
+            MetricValue::Histogram {
+                count,
+                sum,
+                min,
+                max,
+            } => format!("count={} sum={} min={} max={}", count, sum, min, max),
+        };
+        table.push(vec![metric.name.clone(), labels, value]);
+    }
+    print_table(table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "metrics-snapshot")]
+    use std::collections::BTreeMap;
+
+    #[cfg(feature = "health-service")]
+    use crate::action::api::HealthCheck;
+
+    #[cfg(feature = "metrics-snapshot")]
+    fn metric(name: &str, labels: &[(&str, &str)], value: MetricValue) -> Metric {
+        Metric {
+            name: name.into(),
+            labels: labels
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+            value,
+        }
+    }
+
+    /// Verify that metrics reported per connection or service are combined into a single
+    /// indicator, and that indicators without metrics are shown as `-`.
+    #[cfg(feature = "metrics-snapshot")]
+    #[test]
+    fn test_key_indicators() {
+        let metrics = vec![
+            metric(
+                "splinter.peer_manager.peers",
+                &[],
+                MetricValue::Gauge { value: 3.0 },
+            ),
+            metric(
+                "splinter.mesh.outbound_queue_depth",
+                &[("connection", "a")],
+                MetricValue::Gauge { value: 2.0 },
+            ),
+            metric(
+                "splinter.mesh.outbound_queue_depth",
+                &[("connection", "b")],
+                MetricValue::Gauge { value: 5.0 },
+            ),
+            metric(
+                "splinter.scabbard.commit_latency",
+                &[("service", "abcde-01234::a000")],
+                MetricValue::Histogram {
+                    count: 2,
+                    sum: 0.5,
+                    min: 0.1,
+                    max: 0.4,
+                },
+            ),
+            metric(
+                "splinter.scabbard.commit_latency",
+                &[("service", "abcde-01234::b000")],
+                MetricValue::Histogram {
+                    count: 2,
+                    sum: 4.5,
+                    min: 1.5,
+                    max: 3.0,
+                },
+            ),
+        ];
+
+        let table = key_indicators(&metrics);
+        let value = |indicator: &str| {
+            table
+                .iter()
+                .find(|row| row[0] == indicator)
+                .map(|row| row[1].clone())
+                .expect("missing indicator")
+        };
+
+        assert_eq!(value("Peers"), "3");
+        assert_eq!(value("Mesh outbound queue depth"), "7");
+        assert_eq!(value("Proposal backlog"), "-");
+        assert_eq!(value("Scabbard commit latency (avg)"), "1.25 s");
+        assert_eq!(value("Scabbard commit latency (max)"), "3.00 s");
+    }
+
+    /// Verify that each check is displayed as a row with its status in upper case.
+    #[cfg(feature = "health-service")]
+    #[test]
+    fn test_check_table() {
+        let health = HealthStatus {
+            live: true,
+            ready: false,
+            status: Some("fail".into()),
+            completed_at: Some(0),
+            checks: vec![
+                HealthCheck {
+                    name: "store".into(),
+                    status: "pass".into(),
+                    details: "2 circuit(s) in the admin store".into(),
+                    duration_ms: 1,
+                },
+                HealthCheck {
+                    name: "rest_api".into(),
+                    status: "fail".into(),
+                    details: "Unable to connect to 127.0.0.1:8080".into(),
+                    duration_ms: 5000,
+                },
+            ],
+        };
+
+        let table = check_table(&health);
+        assert_eq!(table.len(), 3);
+        assert_eq!(
+            table[1],
+            vec!["store", "PASS", "2 circuit(s) in the admin store"]
+        );
+        assert_eq!(
+            table[2],
+            vec!["rest_api", "FAIL", "Unable to connect to 127.0.0.1:8080"]
+        );
+    }
+}
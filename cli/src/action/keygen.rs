@@ -27,6 +27,8 @@ use std::os::unix::fs::MetadataExt;
 use clap::ArgMatches;
 use cylinder::{secp256k1::Secp256k1Context, Context};
 use cylinder::{PrivateKey, PublicKey};
+#[cfg(feature = "key-pem")]
+use splinter::keys::encoding::{encode_private_key, encode_public_key, KeyEncoding};
 use users::{get_group_by_gid, get_group_by_name};
 
 use crate::error::CliError;
@@ -160,6 +162,14 @@ impl Action for KeyGenAction {
             CliError::EnvironmentError(format!("Failed to create keys directory: {}", err))
         })?;
 
+        #[cfg(feature = "key-pem")]
+        let key_encoding = args
+            .value_of("key_format")
+            .map(KeyEncoding::from_str)
+            .transpose()
+            .map_err(|err| CliError::ActionError(err.to_string()))?
+            .unwrap_or(KeyEncoding::Hex);
+
         let private_key_path = key_dir.join(&key_name).with_extension("priv");
         let public_key_path = key_dir.join(&key_name).with_extension("pub");
 
@@ -171,6 +181,8 @@ impl Action for KeyGenAction {
             args.is_present("force"),
             args.is_present("skip"),
             group,
+            #[cfg(feature = "key-pem")]
+            key_encoding,
         )?;
 
         Ok(())
@@ -185,6 +197,7 @@ fn write_keys(
     force_create: bool,
     skip_create: bool,
     group: Option<ValidatedGroupOptions>,
+    #[cfg(feature = "key-pem")] key_encoding: KeyEncoding,
 ) -> Result<(), CliError> {
     let (private_key, public_key) = keys;
     if !force_create {
@@ -258,6 +271,7 @@ fn write_keys(
         let private_key_file = OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .mode(0o640)
             .open(private_key_path.as_path())
             .map_err(|err| {
@@ -268,7 +282,13 @@ fn write_keys(
                 ))
             })?;
 
-        writeln!(&private_key_file, "{}", private_key.as_hex()).map_err(|err| {
+        #[cfg(feature = "key-pem")]
+        let private_key_contents = encode_private_key(&private_key, key_encoding)
+            .map_err(|err| CliError::ActionError(err.to_string()))?;
+        #[cfg(not(feature = "key-pem"))]
+        let private_key_contents = format!("{}\n", private_key.as_hex());
+
+        write!(&private_key_file, "{}", private_key_contents).map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to write to private key file '{}': {}",
                 private_key_path.display(),
@@ -287,6 +307,7 @@ fn write_keys(
         let public_key_file = OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .mode(0o644)
             .open(public_key_path.as_path())
             .map_err(|err| {
@@ -297,7 +318,13 @@ fn write_keys(
                 ))
             })?;
 
-        writeln!(&public_key_file, "{}", public_key.as_hex()).map_err(|err| {
+        #[cfg(feature = "key-pem")]
+        let public_key_contents = encode_public_key(&public_key, key_encoding)
+            .map_err(|err| CliError::ActionError(err.to_string()))?;
+        #[cfg(not(feature = "key-pem"))]
+        let public_key_contents = format!("{}\n", public_key.as_hex());
+
+        write!(&public_key_file, "{}", public_key_contents).map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to write to public key file '{}': {}",
                 public_key_path.display(),
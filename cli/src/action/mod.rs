@@ -20,12 +20,26 @@ pub mod circuit;
 pub mod command;
 #[cfg(feature = "database")]
 pub mod database;
+#[cfg(any(feature = "health-service", feature = "metrics-snapshot"))]
+pub mod health;
 pub mod keygen;
 #[cfg(feature = "authorization-handler-maintenance")]
 pub mod maintenance;
+#[cfg(any(feature = "node-metadata", feature = "state-dir-health"))]
+pub mod node;
+#[cfg(any(
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ping"
+))]
+pub mod peer;
 pub mod permissions;
 #[cfg(feature = "playlist-smallbank")]
 pub mod playlist;
+#[cfg(feature = "profile")]
+pub mod profile;
+#[cfg(all(feature = "progress", feature = "registry-import"))]
+mod progress;
 #[cfg(feature = "authorization-handler-rbac")]
 pub mod rbac;
 pub mod registry;
@@ -47,7 +61,7 @@ use clap::ArgMatches;
 use super::error::CliError;
 
 const DEFAULT_SPLINTER_REST_API_URL: &str = "http://127.0.0.1:8080";
-const SPLINTER_REST_API_URL_ENV: &str = "SPLINTER_REST_API_URL";
+pub(crate) const SPLINTER_REST_API_URL_ENV: &str = "SPLINTER_REST_API_URL";
 
 #[cfg(any(feature = "workload", feature = "playlist-smallbank"))]
 const DEFAULT_LOG_TIME_SECS: u32 = 30; // time in seconds
@@ -154,3 +168,25 @@ fn print_table(table: Vec<Vec<String>>) {
         println!("{}", col_string);
     }
 }
+
+/// Formats a number of bytes using binary units, e.g. `1.5 MiB`.
+#[cfg(any(feature = "circuit-purge-preview", feature = "state-dir-health"))]
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in UNITS.iter().skip(1) {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{:.1} {}", size, unit)
+}
@@ -0,0 +1,172 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides the `node` subcommand actions.
+
+#[cfg(feature = "node-metadata")]
+use std::collections::BTreeMap;
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+#[cfg(feature = "node-metadata")]
+use super::api::NodeStatusUpdate;
+use super::api::SplinterRestClientBuilder;
+#[cfg(feature = "state-dir-health")]
+use super::{api::NodeStatus, format_size, print_table};
+use super::{Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+
+#[cfg(feature = "state-dir-health")]
+pub struct StatusAction;
+
+#[cfg(feature = "state-dir-health")]
+
+impl Action for StatusAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let url = arg_matches
+            .and_then(|args| args.value_of("url"))
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+        let status = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .get_node_status()?;
+
+        print_status(&status);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "node-metadata")]
+pub struct SetAction;
+
+#[cfg(feature = "node-metadata")]
+impl Action for SetAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let update = NodeStatusUpdate {
+            display_name: args.value_of("display_name").map(ToOwned::to_owned),
+            metadata: args
+                .values_of("metadata")
+                .map(|values| parse_metadata(values))
+                .transpose()?,
+        };
+
+        if update.display_name.is_none() && update.metadata.is_none() {
+            return Err(CliError::ActionError(
+                "At least one of '--display-name' or '--metadata' must be specified".into(),
+            ));
+        }
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .update_node_status(&update)?;
+
+        info!("Node status updated");
+
+        Ok(())
+    }
+}
+
+/// Parses `key=value` arguments into a metadata map.
+#[cfg(feature = "node-metadata")]
+fn parse_metadata<'a, I>(values: I) -> Result<BTreeMap<String, String>, CliError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    values
+        .into_iter()
+        .map(|kv| match kv.split_once('=') {
+            Some((key, _)) if key.is_empty() => Err(CliError::ActionError(
+                "Empty '--metadata' argument detected".into(),
+            )),
+            Some((key, value)) if value.is_empty() => Err(CliError::ActionError(format!(
+                "Empty value detected for metadata key '{}'",
+                key
+            ))),
+            Some((key, value)) => Ok((key.to_string(), value.to_string())),
+            None => Err(CliError::ActionError(format!(
+                "Missing value for metadata key '{}'",
+                kv
+            ))),
+        })
+        .collect()
+}
+
+#[cfg(feature = "state-dir-health")]
+fn print_status(status: &NodeStatus) {
+    println!("Node ID: {}", status.node_id);
+    println!("Display name: {}", status.display_name);
+    println!("Version: {}", status.version);
+    println!("Network endpoints: {}", status.network_endpoints.join(", "));
+    println!(
+        "Advertised endpoints: {}",
+        status.advertised_endpoints.join(", ")
+    );
+    println!("Health: {}", status.health.as_deref().unwrap_or("unknown"));
+
+    let state_dir = match &status.state_dir {
+        Some(state_dir) => state_dir,
+        None => return,
+    };
+
+    println!();
+    println!("State directory: {}", state_dir.path);
+    print_table(vec![
+        vec!["KIND".into(), "SIZE".into()],
+        vec!["lmdb".into(), format_size(state_dir.lmdb_bytes)],
+        vec!["yaml".into(), format_size(state_dir.yaml_bytes)],
+        vec!["receipts".into(), format_size(state_dir.receipt_bytes)],
+        vec!["other".into(), format_size(state_dir.other_bytes)],
+        vec!["total".into(), format_size(state_dir.total_bytes)],
+    ]);
+
+    if let (Some(size), Some(available)) = (
+        state_dir.volume_size_bytes,
+        state_dir.volume_available_bytes,
+    ) {
+        let used = size.saturating_sub(available);
+        let percent = if size > 0 { used * 100 / size } else { 0 };
+        println!();
+        println!(
+            "Volume: {} of {} used ({}%), {} available",
+            format_size(used),
+            format_size(size),
+            percent,
+            format_size(available)
+        );
+    }
+
+    if state_dir.health == "degraded" {
+        warn!("The state directory's volume is nearly full; the node is degraded");
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `peer` subcommand actions.
+
+#[cfg(feature = "peer-ping")]
+use std::thread;
+#[cfg(feature = "peer-ping")]
+use std::time::Duration;
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::SplinterRestClientBuilder, Action, DEFAULT_SPLINTER_REST_API_URL,
+    SPLINTER_REST_API_URL_ENV,
+};
+
+#[cfg(feature = "peer-ping")]
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "peer-disconnect")]
+pub struct DisconnectAction;
+
+#[cfg(feature = "peer-disconnect")]
+impl Action for DisconnectAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let peer_id = args
+            .value_of("peer_id")
+            .ok_or_else(|| CliError::ActionError("A peer ID must be specified".into()))?;
+
+        let ban_secs = args
+            .value_of("ban")
+            .map(|ban| {
+                ban.parse::<u64>().map_err(|_| {
+                    CliError::ActionError(format!(
+                        "'{}' is not a valid number of seconds for --ban",
+                        ban
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let connections_closed = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .disconnect_peer(peer_id, ban_secs)?;
+
+        info!(
+            "Disconnected peer {} ({} connection(s) closed)",
+            peer_id, connections_closed
+        );
+        if let Some(ban_secs) = ban_secs {
+            info!(
+                "Peer {} may not reconnect for {} seconds",
+                peer_id, ban_secs
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+pub struct PinAction;
+
+#[cfg(feature = "peer-endpoint-pin")]
+impl Action for PinAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let peer_id = args
+            .value_of("peer_id")
+            .ok_or_else(|| CliError::ActionError("A peer ID must be specified".into()))?;
+
+        let endpoint = args
+            .value_of("endpoint")
+            .ok_or_else(|| CliError::ActionError("An endpoint must be specified".into()))?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .pin_peer_endpoint(peer_id, endpoint)?;
+
+        info!("Pinned peer {} to endpoint {}", peer_id, endpoint);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+pub struct UnpinAction;
+
+#[cfg(feature = "peer-endpoint-pin")]
+impl Action for UnpinAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let peer_id = args
+            .value_of("peer_id")
+            .ok_or_else(|| CliError::ActionError("A peer ID must be specified".into()))?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let unpinned = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .unpin_peer_endpoint(peer_id)?;
+
+        match unpinned {
+            Some(endpoint) => info!("Unpinned peer {} from endpoint {}", peer_id, endpoint),
+            None => info!("Peer {} is not pinned to an endpoint", peer_id),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "peer-ping")]
+pub struct PingAction;
+
+#[cfg(feature = "peer-ping")]
+impl Action for PingAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let peer_id = args
+            .value_of("peer_id")
+            .ok_or_else(|| CliError::ActionError("A peer ID must be specified".into()))?;
+
+        let count = match args.value_of("count") {
+            Some(count) => match count.parse::<u64>() {
+                Ok(count) if count > 0 => count,
+                _ => {
+                    return Err(CliError::ActionError(format!(
+                        "'{}' is not a valid number of pings for --count",
+                        count
+                    )))
+                }
+            },
+            None => 1,
+        };
+
+        let timeout_secs = args
+            .value_of("timeout")
+            .map(|timeout| {
+                timeout.parse::<u64>().map_err(|_| {
+                    CliError::ActionError(format!(
+                        "'{}' is not a valid number of seconds for --timeout",
+                        timeout
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let client = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?;
+
+        let mut rtts = Vec::with_capacity(count as usize);
+        for seq in 1..=count {
+            if seq > 1 {
+                thread::sleep(PING_INTERVAL);
+            }
+
+            let rtt = client.ping_peer(peer_id, timeout_secs)?;
+            info!("Reply from {}: seq={} time={:.3} ms", peer_id, seq, rtt);
+            rtts.push(rtt);
+        }
+
+        if rtts.len() > 1 {
+            let min = rtts.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = rtts.iter().cloned().fold(0.0, f64::max);
+            let avg = rtts.iter().sum::<f64>() / rtts.len() as f64;
+            info!(
+                "{} pings to {}: min/avg/max = {:.3}/{:.3}/{:.3} ms",
+                rtts.len(),
+                peer_id,
+                min,
+                avg,
+                max
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -53,16 +53,21 @@ impl Action for ListAction {
             .build()?
             .list_permissions()?;
 
+        // Commas would break up the csv columns, so routes are separated by semicolons instead
+        let route_separator = if format == "csv" { ";" } else { ", " };
+
         let data = std::iter::once(vec![
             "ID".to_string(),
             "NAME".to_string(),
             "DESCRIPTION".to_string(),
+            "ROUTES".to_string(),
         ])
         .chain(permissions.into_iter().map(|perm| {
             vec![
                 perm.permission_id,
                 perm.permission_display_name,
                 perm.permission_description,
+                perm.routes.join(route_separator),
             ]
         }));
 
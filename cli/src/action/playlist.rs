@@ -19,7 +19,7 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use clap::ArgMatches;
-use reqwest::{blocking::Client, header, StatusCode};
+use reqwest::{header, StatusCode};
 use serde::Deserialize;
 use transact::families::smallbank::workload::playlist::{
     generate_smallbank_playlist, process_smallbank_playlist,
@@ -28,6 +28,7 @@ use transact::protos::IntoBytes;
 use transact::workload::batch_gen::{BatchListFeeder, SignedBatchProducer};
 use transact::workload::HttpRequestCounter;
 
+use crate::action::api::http_client;
 use crate::action::request_logger::RequestLogger;
 use crate::action::time::Time;
 use crate::error::CliError;
@@ -327,7 +328,7 @@ pub fn submit_batches_from_source(
         };
 
         // submit batch to the target
-        match Client::new()
+        match http_client()
             .post(&format!("{}/batches", target))
             .header(header::CONTENT_TYPE, "octet-stream")
             .header("Authorization", &auth)
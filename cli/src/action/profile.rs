@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `profile` subcommand actions.
+
+use std::fs;
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::profile::{
+    delete_profile, list_profiles, load_profile, profiles_dir, save_profile, Profile,
+};
+
+use super::{print_table, Action};
+
+pub struct CreateProfileAction;
+
+impl Action for CreateProfileAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let name = args
+            .value_of("name")
+            .ok_or_else(|| CliError::ActionError("A profile name must be specified".into()))?;
+
+        // Key paths are stored as absolute paths so the profile works from any directory; key
+        // names are looked up in the key search path when the profile is used.
+        let key = args
+            .value_of("private_key_file")
+            .map(|key| {
+                if key.contains('/') {
+                    fs::canonicalize(key)
+                        .map(|path| path.display().to_string())
+                        .map_err(|err| {
+                            CliError::ActionError(format!("Invalid key file '{}': {}", key, err))
+                        })
+                } else {
+                    Ok(key.to_string())
+                }
+            })
+            .transpose()?;
+
+        let profile = Profile {
+            name: name.to_string(),
+            url: args.value_of("url").map(ToOwned::to_owned),
+            key,
+        };
+
+        save_profile(&profiles_dir()?, &profile, args.is_present("force"))?;
+
+        info!("Created profile '{}'", name);
+
+        Ok(())
+    }
+}
+
+pub struct ListProfilesAction;
+
+impl Action for ListProfilesAction {
+    fn run<'a>(&mut self, _arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let mut table = vec![vec!["NAME".into(), "URL".into(), "KEY".into()]];
+        table.extend(list_profiles(&profiles_dir()?)?.into_iter().map(|profile| {
+            vec![
+                profile.name,
+                profile.url.unwrap_or_else(|| "-".into()),
+                profile.key.unwrap_or_else(|| "-".into()),
+            ]
+        }));
+        print_table(table);
+
+        Ok(())
+    }
+}
+
+pub struct ShowProfileAction;
+
+impl Action for ShowProfileAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let name = arg_matches
+            .and_then(|args| args.value_of("name"))
+            .ok_or(CliError::RequiresArgs)?;
+
+        let profile = load_profile(&profiles_dir()?, name)?;
+
+        println!("Name: {}", profile.name);
+        println!("URL: {}", profile.url.as_deref().unwrap_or("-"));
+        println!("Key: {}", profile.key.as_deref().unwrap_or("-"));
+
+        Ok(())
+    }
+}
+
+pub struct DeleteProfileAction;
+
+impl Action for DeleteProfileAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let name = arg_matches
+            .and_then(|args| args.value_of("name"))
+            .ok_or(CliError::RequiresArgs)?;
+
+        delete_profile(&profiles_dir()?, name)?;
+
+        info!("Deleted profile '{}'", name);
+
+        Ok(())
+    }
+}
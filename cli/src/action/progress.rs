@@ -0,0 +1,58 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Progress indication for long-running operations.
+
+use std::borrow::Cow;
+
+use clap::ArgMatches;
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// How often, in milliseconds, the spinner is redrawn.
+const TICK_INTERVAL_MILLIS: u64 = 100;
+
+/// A spinner that is shown on stderr while a long-running operation is in progress, and is
+/// cleared when dropped.
+///
+/// The spinner is hidden if stderr is not a terminal, so it never ends up in redirected output.
+pub struct Spinner {
+    bar: ProgressBar,
+}
+
+impl Spinner {
+    /// Starts a spinner with the given message.
+    ///
+    /// The spinner is hidden if `--quiet` was given, or if `--verbose` was given, since the
+    /// operation's logs already report its progress.
+    pub fn start<M>(args: &ArgMatches, message: M) -> Self
+    where
+        M: Into<Cow<'static, str>>,
+    {
+        let bar = if args.is_present("quiet") || args.occurrences_of("verbose") > 0 {
+            ProgressBar::hidden()
+        } else {
+            ProgressBar::new_spinner()
+        };
+        bar.set_style(ProgressStyle::default_spinner().template("{spinner} {msg} ({elapsed})"));
+        bar.set_message(message);
+        bar.enable_steady_tick(TICK_INTERVAL_MILLIS);
+
+        Self { bar }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+    }
+}
@@ -16,6 +16,8 @@
 
 mod assignments;
 mod roles;
+#[cfg(feature = "role-templates")]
+mod templates;
 
 use clap::ArgMatches;
 
@@ -31,6 +33,8 @@ pub use assignments::{
 pub use roles::{
     CreateRoleAction, DeleteRoleAction, ListRolesAction, ShowRoleAction, UpdateRoleAction,
 };
+#[cfg(feature = "role-templates")]
+pub use templates::{ListRoleTemplatesAction, ShowRoleTemplateAction};
 
 /// Constructs a new Splinter REST client from the CLI arguments.
 fn new_client(arg_matches: &Option<&ArgMatches<'_>>) -> Result<SplinterRestClient, CliError> {
@@ -15,6 +15,8 @@
 //! Actions to support the RBAC subcommands related to roles.
 
 use std::collections::BTreeSet;
+#[cfg(feature = "role-templates")]
+use std::collections::HashSet;
 
 use clap::ArgMatches;
 
@@ -25,6 +27,8 @@ use crate::action::{
 use crate::error::CliError;
 
 use super::new_client;
+#[cfg(feature = "role-templates")]
+use super::templates::get_role_template;
 
 /// The action responsible for listing roles.
 ///
@@ -114,6 +118,7 @@ impl Action for ShowRoleAction {
 /// * role_id: the specified role ID
 /// * display_name: the role's display name
 /// * permission: a permission granted by the resulting role; repeated
+/// * template: a built-in role template whose permissions are granted by the resulting role
 /// * dry_run: validate the inputs but do not submit the role
 pub struct CreateRoleAction;
 
@@ -123,17 +128,53 @@ impl Action for CreateRoleAction {
             .and_then(|args| args.value_of("role_id"))
             .ok_or_else(|| CliError::ActionError("A role must have an ID".into()))?;
 
-        let display_name = arg_matches
-            .and_then(|args| args.value_of("display_name"))
+        #[cfg(feature = "role-templates")]
+        let template = arg_matches
+            .and_then(|args| args.value_of("template"))
+            .map(get_role_template)
+            .transpose()?;
+
+        let display_name = arg_matches.and_then(|args| args.value_of("display_name"));
+        #[cfg(feature = "role-templates")]
+        let display_name = display_name.or_else(|| template.map(|t| t.display_name));
+        let display_name = display_name
             .ok_or_else(|| CliError::ActionError("A role must have a display name".into()))?;
 
-        let permissions = arg_matches
+        #[allow(unused_mut)]
+        let mut permissions: Vec<String> = arg_matches
             .and_then(|args| args.values_of("permission"))
-            .ok_or_else(|| {
-                CliError::ActionError("A role must have at least one permission".into())
-            })?
-            .map(|s| s.to_owned())
-            .collect();
+            .map(|vals| vals.map(|s| s.to_owned()).collect())
+            .unwrap_or_else(Vec::new);
+
+        let client = new_client(&arg_matches)?;
+
+        #[cfg(feature = "role-templates")]
+        if let Some(template) = template {
+            // Only grant the template's permissions that this node offers, as the permissions
+            // available depend on the features the node was built with
+            let offered = client
+                .list_permissions()?
+                .into_iter()
+                .map(|perm| perm.permission_id)
+                .collect::<HashSet<_>>();
+            let (granted, skipped): (Vec<&str>, Vec<&str>) = template
+                .permissions
+                .iter()
+                .partition(|perm| offered.contains(**perm));
+
+            if !skipped.is_empty() {
+                println!(
+                    "Skipping permissions not offered by this node: {}",
+                    skipped.join(", ")
+                );
+            }
+
+            for perm in granted {
+                if !permissions.iter().any(|p| p == perm) {
+                    permissions.push(perm.to_string());
+                }
+            }
+        }
 
         let role = RoleBuilder::default()
             .with_role_id(role_id.into())
@@ -141,7 +182,6 @@ impl Action for CreateRoleAction {
             .with_permissions(permissions)
             .build()?;
 
-        let client = new_client(&arg_matches)?;
         if !is_dry_run(&arg_matches) {
             client.create_role(role)
         } else if client.get_role(role_id)?.is_some() {
@@ -0,0 +1,242 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A built-in catalog of role templates for common personas.
+
+use std::fmt;
+
+use clap::ArgMatches;
+use serde::Serialize;
+
+use crate::action::{print_table, Action};
+use crate::error::CliError;
+
+/// A named set of permissions that may be used to create a role.
+#[derive(Debug, Serialize)]
+pub struct RoleTemplate {
+    pub name: &'static str,
+    pub display_name: &'static str,
+    pub description: &'static str,
+    pub permissions: &'static [&'static str],
+}
+
+impl fmt::Display for RoleTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Template: {}", self.name)?;
+        write!(f, "\n    Name: {}", self.display_name)?;
+        write!(f, "\n    Description: {}", self.description)?;
+        f.write_str("\n    Permissions:")?;
+
+        for perm in self.permissions.iter() {
+            write!(f, "\n        {}", perm)?;
+        }
+
+        Ok(())
+    }
+}
+
+const ROLE_TEMPLATES: &[RoleTemplate] = &[
+    RoleTemplate {
+        name: "auditor",
+        display_name: "Auditor",
+        description: "Read-only access to the node's configuration, circuits, and state",
+        permissions: &[
+            "authorization.maintenance.read",
+            "authorization.permissions.read",
+            "authorization.rbac.read",
+            "biome.user.read",
+            "circuit.read",
+            "metrics.read",
+            "network.audit.read",
+            "network.peers.read",
+            "network.throttle.read",
+            "oauth.users.read",
+            "registry.read",
+            "scabbard.read",
+            "status.read",
+        ],
+    },
+    RoleTemplate {
+        name: "circuit-admin",
+        display_name: "Circuit Administrator",
+        description: "Proposes and votes on circuits, and manages the node registry",
+        permissions: &[
+            "authorization.permissions.read",
+            "circuit.read",
+            "circuit.write",
+            "registry.read",
+            "registry.write",
+            "status.read",
+        ],
+    },
+    RoleTemplate {
+        name: "node-operator",
+        display_name: "Node Operator",
+        description: "Manages the node's peers, network throttling, and maintenance mode",
+        permissions: &[
+            "authorization.maintenance.read",
+            "authorization.maintenance.write",
+            "authorization.permissions.read",
+            "metrics.read",
+            "network.audit.read",
+            "network.peers.read",
+            "network.peers.write",
+            "network.throttle.read",
+            "network.throttle.write",
+            "status.read",
+            "status.write",
+        ],
+    },
+    RoleTemplate {
+        name: "service-operator",
+        display_name: "Service Operator",
+        description: "Reads circuits and submits to the services running on them",
+        permissions: &[
+            "authorization.permissions.read",
+            "circuit.read",
+            "echo.read",
+            "relay.read",
+            "relay.write",
+            "scabbard.read",
+            "scabbard.write",
+            "status.read",
+        ],
+    },
+];
+
+/// Returns the built-in role template with the given name.
+pub fn get_role_template(name: &str) -> Result<&'static RoleTemplate, CliError> {
+    ROLE_TEMPLATES
+        .iter()
+        .find(|template| template.name == name)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Role template {} does not exist; available templates are: {}",
+                name,
+                ROLE_TEMPLATES
+                    .iter()
+                    .map(|template| template.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+}
+
+/// The action responsible for listing the built-in role templates.
+///
+/// The specific args for this action:
+///
+/// * format: specifies the output format; one of "human" or "csv"
+pub struct ListRoleTemplatesAction;
+
+impl Action for ListRoleTemplatesAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+
+        let header = vec![
+            "NAME".to_string(),
+            "DISPLAY NAME".to_string(),
+            "DESCRIPTION".to_string(),
+        ];
+
+        if format == "csv" {
+            println!("{}", header.join(","));
+            for template in ROLE_TEMPLATES {
+                println!(
+                    "{},{},{}",
+                    template.name, template.display_name, template.description
+                );
+            }
+        } else {
+            let mut rows = vec![header];
+            for template in ROLE_TEMPLATES {
+                rows.push(vec![
+                    template.name.to_string(),
+                    template.display_name.to_string(),
+                    template.description.to_string(),
+                ]);
+            }
+            print_table(rows);
+        }
+
+        Ok(())
+    }
+}
+
+/// The action responsible for showing a built-in role template.
+///
+/// The specific args for this action:
+///
+/// * template: the name of the template
+/// * format: specifies the output format; one of "human", "json", or "yaml"
+pub struct ShowRoleTemplateAction;
+
+impl Action for ShowRoleTemplateAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+
+        let template = arg_matches
+            .and_then(|args| args.value_of("template"))
+            .ok_or_else(|| CliError::ActionError("A template name must be specified".into()))
+            .and_then(get_role_template)?;
+
+        match format {
+            "json" => println!(
+                "\n {}",
+                serde_json::to_string(template).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format role template into json: {}",
+                    err
+                )))?
+            ),
+            "yaml" => println!(
+                "{}",
+                serde_yaml::to_string(template).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format role template into yaml: {}",
+                    err
+                )))?
+            ),
+            _ => println!("{}", template),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    /// Verify that template names are unique and that every template grants at least one
+    /// permission, without repeating any.
+    #[test]
+    fn role_templates_are_well_formed() {
+        let mut names = HashSet::new();
+        for template in ROLE_TEMPLATES {
+            assert!(names.insert(template.name), "{} repeated", template.name);
+            assert!(!template.permissions.is_empty());
+
+            let permissions = template.permissions.iter().collect::<HashSet<_>>();
+            assert_eq!(permissions.len(), template.permissions.len());
+        }
+
+        assert!(get_role_template("auditor").is_ok());
+        assert!(get_role_template("superuser").is_err());
+    }
+}
@@ -15,17 +15,16 @@
 use std::fmt;
 use std::fmt::Write as _;
 
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::action::api::{ServerError, SplinterRestClient};
+use crate::action::api::{http_client, ServerError, SplinterRestClient};
 use crate::error::CliError;
 
 impl SplinterRestClient {
     /// Adds a new node to the registry.
     pub fn add_node(&self, node: &RegistryNode) -> Result<(), CliError> {
-        let request = Client::new()
+        let request = http_client()
             .post(&format!("{}/registry/nodes", self.url))
             .json(&node)
             .header("Authorization", &self.auth);
@@ -58,7 +57,7 @@ impl SplinterRestClient {
 
     /// Retrieves the node with the given identity from the registry.
     pub fn get_node(&self, identity: &str) -> Result<Option<RegistryNode>, CliError> {
-        let request = Client::new()
+        let request = http_client()
             .get(&format!("{}/registry/nodes/{}", self.url, &identity))
             .header("Authorization", &self.auth);
 
@@ -90,10 +89,330 @@ impl SplinterRestClient {
                 }
             })
     }
+
+    /// Replaces the node with the same identity in the local registry.
+    #[cfg(feature = "registry-update")]
+    pub fn update_node(&self, node: &RegistryNode) -> Result<(), CliError> {
+        let request = http_client()
+            .put(&format!("{}/registry/nodes/{}", self.url, &node.identity))
+            .json(&node)
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to update node: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Registry update node request failed with status code '{}', but error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to update node: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Removes the node with the given identity from the local registry.
+    #[cfg(feature = "registry-update")]
+    pub fn delete_node(&self, identity: &str) -> Result<(), CliError> {
+        let request = http_client()
+            .delete(&format!("{}/registry/nodes/{}", self.url, identity))
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to delete node: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Registry delete node request failed with status code '{}', but error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to delete node: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Applies the given node operations to the registry in a single transaction.
+    ///
+    /// The per-operation results are returned whether or not the batch was committed.
+    #[cfg(feature = "registry-import")]
+    pub fn apply_node_batch(
+        &self,
+        operations: Vec<RegistryNodeOperation>,
+    ) -> Result<NodeBatchResponse, CliError> {
+        let request = http_client()
+            .post(&format!("{}/registry/nodes/batch", self.url))
+            .json(&NodeBatchRequest { operations })
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to apply registry batch: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                let body = res.text().map_err(|err| {
+                    CliError::ActionError(format!("Failed to read registry batch response: {}", err))
+                })?;
+
+                // A batch that was rolled back is reported with an error status, but still
+                // includes the result of each operation.
+                if let Ok(response) = serde_json::from_str::<NodeBatchResponse>(&body) {
+                    return Ok(response);
+                }
+
+                if status.is_success() {
+                    return Err(CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    ));
+                }
+
+                let message = serde_json::from_str::<ServerError>(&body)
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Registry batch request failed with status code '{}', but error response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to apply registry batch: {}",
+                    message
+                )))
+            })
+    }
+
+    /// Forces the node to immediately refresh its remote registries.
+    #[cfg(feature = "registry-refresh")]
+    pub fn refresh_registries(&self) -> Result<Vec<RegistryRefreshResult>, CliError> {
+        let request = http_client()
+            .post(&format!("{}/registry/refresh", self.url))
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to refresh registries: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<RefreshRegistriesResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Registry refresh request failed with status code '{}', but error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to refresh registries: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the remote registries of the node and their status.
+    #[cfg(feature = "registry-sources")]
+    pub fn list_registry_sources(&self) -> Result<Vec<RegistrySource>, CliError> {
+        let request = http_client()
+            .get(&format!("{}/registry/sources", self.url))
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to list registry sources: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<ListRegistrySourcesResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    Err(registry_source_error(res, "list registry sources"))
+                }
+            })
+    }
+
+    /// Adds a remote registry to the node, returning its status after the initial fetch.
+    #[cfg(feature = "registry-sources")]
+    pub fn add_registry_source(&self, url: &str) -> Result<RegistrySource, CliError> {
+        let request = http_client()
+            .post(&format!("{}/registry/sources", self.url))
+            .json(&NewRegistrySource { url })
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to add registry source: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<RegistrySource>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    Err(registry_source_error(res, "add registry source"))
+                }
+            })
+    }
+
+    /// Removes a remote registry that was added to the node at runtime.
+    #[cfg(feature = "registry-sources")]
+    pub fn remove_registry_source(&self, url: &str) -> Result<(), CliError> {
+        let request = http_client()
+            .delete(&format!("{}/registry/sources", self.url))
+            .query(&[("url", url)])
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to remove registry source: {}", err))
+            })
+            .and_then(|res| {
+                if res.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(registry_source_error(res, "remove registry source"))
+                }
+            })
+    }
 }
 
-#[cfg(feature = "registry")]
+/// Builds the error for a failed registry sources request from the server's error response.
+#[cfg(feature = "registry-sources")]
+fn registry_source_error(res: reqwest::blocking::Response, operation: &str) -> CliError {
+    let status = res.status();
+    match res.json::<ServerError>() {
+        Ok(err) => CliError::ActionError(format!("Failed to {}: {}", operation, err.message)),
+        Err(_) => CliError::ActionError(format!(
+            "Request to {} failed with status code '{}', but error response was not valid",
+            operation, status
+        )),
+    }
+}
+
+#[cfg(feature = "registry-import")]
+#[derive(Debug, Serialize)]
+struct NodeBatchRequest {
+    operations: Vec<RegistryNodeOperation>,
+}
+
+/// A single change to submit as part of a registry batch
+#[cfg(feature = "registry-import")]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum RegistryNodeOperation {
+    Create { node: RegistryNode },
+    Update { node: RegistryNode },
+}
+
+/// The outcome of a registry batch
+#[cfg(feature = "registry-import")]
+#[derive(Debug, Deserialize)]
+pub struct NodeBatchResponse {
+    pub committed: bool,
+    pub data: Vec<NodeOperationResult>,
+}
+
+/// The outcome of a single operation within a registry batch
+#[cfg(feature = "registry-import")]
+#[derive(Debug, Deserialize)]
+pub struct NodeOperationResult {
+    pub identity: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "registry-refresh")]
+#[derive(Debug, Deserialize)]
+struct RefreshRegistriesResponse {
+    data: Vec<RegistryRefreshResult>,
+}
+
+/// The outcome of refreshing a single remote registry
+#[cfg(feature = "registry-refresh")]
+#[derive(Debug, Deserialize)]
+pub struct RegistryRefreshResult {
+    pub url: String,
+    pub success: bool,
+    pub node_count: Option<usize>,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "registry-sources")]
+#[derive(Debug, Serialize)]
+struct NewRegistrySource<'a> {
+    url: &'a str,
+}
+
+#[cfg(feature = "registry-sources")]
+#[derive(Debug, Deserialize)]
+struct ListRegistrySourcesResponse {
+    data: Vec<RegistrySource>,
+}
+
+/// The status of a remote registry
+#[cfg(feature = "registry-sources")]
 #[derive(Debug, Deserialize, Serialize)]
+pub struct RegistrySource {
+    pub url: String,
+    pub dynamic: bool,
+    /// Seconds since the Unix epoch of the last successful refresh, if any
+    pub last_refresh: Option<u64>,
+    pub node_count: usize,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "registry")]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct RegistryNode {
     pub identity: String,
     pub endpoints: Vec<String>,
@@ -14,11 +14,17 @@
 
 #[cfg(feature = "registry")]
 mod api;
+#[cfg(feature = "registry-sources")]
+pub mod source;
 
 use clap::ArgMatches;
+#[cfg(feature = "registry-node-signing")]
+use splinter::registry::sign_node;
 use splinter::registry::{Node, YamlNode};
 #[cfg(feature = "registry")]
 use std::collections::HashMap;
+#[cfg(feature = "registry-import")]
+use std::convert::TryFrom;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
@@ -26,9 +32,13 @@ use std::path::Path;
 use crate::error::CliError;
 #[cfg(feature = "registry")]
 use crate::registry::api::RegistryNode;
+#[cfg(feature = "registry-import")]
+use crate::registry::api::RegistryNodeOperation;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
 use super::api::SplinterRestClientBuilder;
+#[cfg(any(feature = "registry-import", feature = "registry-refresh"))]
+use super::print_table;
 use super::{msg_from_io_error, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
 
 const DEFAULT_OUTPUT_FILE: &str = "./nodes.yaml";
@@ -85,6 +95,12 @@ impl Action for RegistryGenerateAction {
             .with_endpoints(node_status.advertised_endpoints)
             .with_display_name(node_status.display_name);
 
+        // Metadata set on the node itself is included, but may be overridden by `--metadata`
+        #[cfg(feature = "node-metadata")]
+        for (key, value) in node_status.metadata {
+            node_builder = node_builder.with_metadata(key, value);
+        }
+
         if let Some(metadata) = args.values_of("metadata") {
             for kv in metadata {
                 let mut kv_iter = kv.splitn(2, '=');
@@ -119,6 +135,25 @@ impl Action for RegistryGenerateAction {
             .build()
             .map_err(|err| CliError::ActionError(format!("Unable to build node: {}", err)))?;
 
+        #[cfg(feature = "registry-node-signing")]
+        let node = if args.is_present("sign") {
+            let signer = load_signer(args.value_of("private_key_file"))?;
+            let public_key = signer
+                .public_key()
+                .map_err(|err| CliError::ActionError(format!("Unable to get public key: {}", err)))?
+                .as_hex();
+            if !node.has_key(&public_key) {
+                return Err(CliError::ActionError(format!(
+                    "Cannot sign node: public key {} is not one of the node's keys",
+                    public_key
+                )));
+            }
+            sign_node(node, &*signer)
+                .map_err(|err| CliError::ActionError(format!("Unable to sign node: {}", err)))?
+        } else {
+            node
+        };
+
         if let Some(idx) = nodes
             .iter()
             .position(|existing_node| existing_node.identity() == node.identity())
@@ -263,6 +298,415 @@ impl Action for RegistryAddAction {
     }
 }
 
+#[cfg(feature = "registry-update")]
+pub struct RegistryUpdateAction;
+
+#[cfg(feature = "registry-update")]
+impl Action for RegistryUpdateAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let identity = args
+            .value_of("identity")
+            .ok_or_else(|| CliError::ActionError("Identity must be specified".into()))?;
+
+        let update = NodeUpdate::from_args(args)?;
+        if update.is_empty() {
+            return Err(CliError::ActionError(
+                "No changes were specified for the node".into(),
+            ));
+        }
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let client = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?;
+
+        let node = client
+            .get_node(identity)?
+            .ok_or_else(|| CliError::ActionError(format!("Node '{}' does not exist", identity)))?;
+
+        let updated_node = update.apply(node)?;
+
+        if !args.is_present("dry_run") {
+            client.update_node(&updated_node)?;
+        }
+
+        info!("{}", updated_node);
+
+        Ok(())
+    }
+}
+
+/// The changes to make to an existing registry node. Any part of the node that is not mentioned
+/// is left unchanged.
+#[cfg(feature = "registry-update")]
+#[derive(Default)]
+struct NodeUpdate {
+    display_name: Option<String>,
+    endpoints: Option<Vec<String>>,
+    add_endpoints: Vec<String>,
+    remove_endpoints: Vec<String>,
+    keys: Option<Vec<String>>,
+    add_keys: Vec<String>,
+    remove_keys: Vec<String>,
+    metadata: Vec<(String, String)>,
+    remove_metadata: Vec<String>,
+}
+
+#[cfg(feature = "registry-update")]
+impl NodeUpdate {
+    fn from_args(args: &ArgMatches) -> Result<Self, CliError> {
+        let values = |name: &str| -> Vec<String> {
+            args.values_of(name)
+                .map(|values| values.map(String::from).collect())
+                .unwrap_or_default()
+        };
+        let key_files = |name: &str| -> Result<Vec<String>, CliError> {
+            args.values_of(name)
+                .map(|files| files.map(read_private_key).collect())
+                .unwrap_or_else(|| Ok(vec![]))
+        };
+
+        Ok(NodeUpdate {
+            display_name: args.value_of("display_name").map(String::from),
+            endpoints: args
+                .values_of("endpoint")
+                .map(|values| values.map(String::from).collect()),
+            add_endpoints: values("add_endpoint"),
+            remove_endpoints: values("remove_endpoint"),
+            keys: if args.is_present("key_files") {
+                Some(key_files("key_files")?)
+            } else {
+                None
+            },
+            add_keys: key_files("add_key_files")?,
+            remove_keys: key_files("remove_key_files")?,
+            metadata: args
+                .values_of("metadata")
+                .map(|values| values.map(parse_metadata).collect())
+                .unwrap_or_else(|| Ok(vec![]))?,
+            remove_metadata: values("remove_metadata"),
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.display_name.is_none()
+            && self.endpoints.is_none()
+            && self.add_endpoints.is_empty()
+            && self.remove_endpoints.is_empty()
+            && self.keys.is_none()
+            && self.add_keys.is_empty()
+            && self.remove_keys.is_empty()
+            && self.metadata.is_empty()
+            && self.remove_metadata.is_empty()
+    }
+
+    /// Returns the node with the changes applied. Replacements are applied before additions,
+    /// which are applied before removals.
+    fn apply(self, mut node: RegistryNode) -> Result<RegistryNode, CliError> {
+        if let Some(display_name) = self.display_name {
+            node.display_name = display_name;
+        }
+
+        if let Some(endpoints) = self.endpoints {
+            node.endpoints = endpoints;
+        }
+        for endpoint in self.add_endpoints {
+            if !node.endpoints.contains(&endpoint) {
+                node.endpoints.push(endpoint);
+            }
+        }
+        for endpoint in self.remove_endpoints {
+            if !node.endpoints.contains(&endpoint) {
+                return Err(CliError::ActionError(format!(
+                    "Node '{}' does not have endpoint '{}'",
+                    node.identity, endpoint
+                )));
+            }
+            node.endpoints.retain(|existing| existing != &endpoint);
+        }
+        if node.endpoints.is_empty() {
+            return Err(CliError::ActionError(
+                "Node must have at least one endpoint".into(),
+            ));
+        }
+
+        if let Some(keys) = self.keys {
+            node.keys = keys;
+        }
+        for key in self.add_keys {
+            if !node.keys.contains(&key) {
+                node.keys.push(key);
+            }
+        }
+        for key in self.remove_keys {
+            if !node.keys.contains(&key) {
+                return Err(CliError::ActionError(format!(
+                    "Node '{}' does not have key '{}'",
+                    node.identity, key
+                )));
+            }
+            node.keys.retain(|existing| existing != &key);
+        }
+        if node.keys.is_empty() {
+            return Err(CliError::ActionError(
+                "Node must have at least one key".into(),
+            ));
+        }
+
+        for (key, value) in self.metadata {
+            node.metadata.insert(key, value);
+        }
+        for key in self.remove_metadata {
+            if node.metadata.remove(&key).is_none() {
+                return Err(CliError::ActionError(format!(
+                    "Node '{}' does not have metadata key '{}'",
+                    node.identity, key
+                )));
+            }
+        }
+
+        Ok(node)
+    }
+}
+
+#[cfg(feature = "registry-update")]
+pub struct RegistryDeleteAction;
+
+#[cfg(feature = "registry-update")]
+impl Action for RegistryDeleteAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let identity = args
+            .value_of("identity")
+            .ok_or_else(|| CliError::ActionError("Identity must be specified".into()))?;
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let client = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?;
+
+        if args.is_present("dry_run") {
+            let node = client.get_node(identity)?.ok_or_else(|| {
+                CliError::ActionError(format!("Node '{}' does not exist", identity))
+            })?;
+            info!("Would delete node:\n{}", node);
+        } else {
+            client.delete_node(identity)?;
+            info!("Deleted node '{}'", identity);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry-import")]
+pub struct RegistryImportAction;
+
+#[cfg(feature = "registry-import")]
+impl Action for RegistryImportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let file = args
+            .value_of("file")
+            .ok_or_else(|| CliError::ActionError("A registry file must be specified".into()))?;
+
+        let operations = read_node_operations(file, args.is_present("update"))?;
+        if operations.is_empty() {
+            info!("No nodes to import from '{}'", file);
+            return Ok(());
+        }
+
+        if args.is_present("dry_run") {
+            for operation in &operations {
+                match operation {
+                    RegistryNodeOperation::Create { node } => {
+                        info!("Would add node:\n{}", node)
+                    }
+                    RegistryNodeOperation::Update { node } => {
+                        info!("Would update node:\n{}", node)
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let client = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?;
+
+        let total = operations.len();
+        #[cfg(feature = "progress")]
+        let spinner = crate::action::progress::Spinner::start(
+            args,
+            format!("Importing {} nodes from '{}'", total, file),
+        );
+        let response = client.apply_node_batch(operations)?;
+        #[cfg(feature = "progress")]
+        drop(spinner);
+
+        let mut data = vec![vec![
+            "IDENTITY".to_string(),
+            "STATUS".to_string(),
+            "ERROR".to_string(),
+        ]];
+        for result in response.data {
+            data.push(vec![
+                result.identity,
+                if result.success { "OK" } else { "FAILED" }.to_string(),
+                result.error.unwrap_or_default(),
+            ]);
+        }
+        print_table(data);
+
+        if !response.committed {
+            return Err(CliError::ActionError(format!(
+                "Failed to import nodes from '{}'; none of the {} nodes were imported",
+                file, total
+            )));
+        }
+
+        info!("Imported {} nodes from '{}'", total, file);
+
+        Ok(())
+    }
+}
+
+/// Reads a YAML registry file and returns an operation for each node in it, creating the nodes or,
+/// if `update` is set, replacing existing nodes.
+#[cfg(feature = "registry-import")]
+fn read_node_operations(
+    file_name: &str,
+    update: bool,
+) -> Result<Vec<RegistryNodeOperation>, CliError> {
+    let file = File::open(file_name).map_err(|err| {
+        CliError::ActionError(format!(
+            "Failed to open '{}': {}",
+            file_name,
+            msg_from_io_error(err)
+        ))
+    })?;
+    let nodes: Vec<YamlNode> = serde_yaml::from_reader(file).map_err(|_| {
+        CliError::ActionError(format!(
+            "Failed to read registry file '{}': Not a valid YAML sequence of nodes",
+            file_name
+        ))
+    })?;
+
+    nodes
+        .into_iter()
+        .map(|yaml_node| {
+            let node = Node::try_from(yaml_node).map_err(|err| {
+                CliError::ActionError(format!(
+                    "Invalid node in registry file '{}': {}",
+                    file_name, err
+                ))
+            })?;
+            let node = RegistryNode {
+                identity: node.identity().to_string(),
+                endpoints: node.endpoints().to_vec(),
+                display_name: node.display_name().to_string(),
+                keys: node.keys().to_vec(),
+                metadata: node.metadata().clone(),
+            };
+            Ok(if update {
+                RegistryNodeOperation::Update { node }
+            } else {
+                RegistryNodeOperation::Create { node }
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "registry-refresh")]
+pub struct RegistryRefreshAction;
+
+#[cfg(feature = "registry-refresh")]
+impl Action for RegistryRefreshAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let url = arg_matches
+            .and_then(|args| args.value_of("url"))
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+        let client = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?;
+
+        let results = client.refresh_registries()?;
+
+        if results.is_empty() {
+            info!("The node has no remote registries to refresh");
+            return Ok(());
+        }
+
+        let total = results.len();
+        let mut failed = 0;
+        let mut data = vec![vec![
+            "URL".to_string(),
+            "STATUS".to_string(),
+            "NODES".to_string(),
+            "ERROR".to_string(),
+        ]];
+        for result in results {
+            if !result.success {
+                failed += 1;
+            }
+            data.push(vec![
+                result.url,
+                if result.success { "OK" } else { "FAILED" }.to_string(),
+                result
+                    .node_count
+                    .map(|count| count.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                result.error.unwrap_or_default(),
+            ]);
+        }
+        print_table(data);
+
+        if failed > 0 {
+            return Err(CliError::ActionError(format!(
+                "Failed to refresh {} of {} remote registries",
+                failed, total
+            )));
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "registry")]
 fn parse_metadata(metadata: &str) -> Result<(String, String), CliError> {
     let mut parts = metadata.splitn(2, ':');
@@ -309,3 +753,140 @@ fn read_private_key(file_name: &str) -> Result<String, CliError> {
 
     Ok(key)
 }
+
+#[cfg(all(test, feature = "registry-update"))]
+mod tests {
+    use super::*;
+
+    fn node() -> RegistryNode {
+        let mut metadata = HashMap::new();
+        metadata.insert("company".to_string(), "Cargill".to_string());
+        RegistryNode {
+            identity: "node-1".into(),
+            endpoints: vec!["tcps://node-1:8044".into()],
+            display_name: "Node 1".into(),
+            keys: vec!["aaaa".into()],
+            metadata,
+        }
+    }
+
+    /// Verify that only the parts of the node named in the update are changed.
+    #[test]
+    fn partial_update() {
+        let update = NodeUpdate {
+            add_keys: vec!["bbbb".into()],
+            metadata: vec![("region".into(), "us".into())],
+            ..Default::default()
+        };
+        let updated = update.apply(node()).expect("Unable to apply update");
+
+        assert_eq!(updated.display_name, "Node 1");
+        assert_eq!(updated.endpoints, vec!["tcps://node-1:8044".to_string()]);
+        assert_eq!(updated.keys, vec!["aaaa".to_string(), "bbbb".to_string()]);
+        assert_eq!(
+            updated.metadata.get("company"),
+            Some(&"Cargill".to_string())
+        );
+        assert_eq!(updated.metadata.get("region"), Some(&"us".to_string()));
+    }
+
+    /// Verify that replacements are applied before additions and removals.
+    #[test]
+    fn replace_then_add_and_remove() {
+        let update = NodeUpdate {
+            endpoints: Some(vec!["tcps://new:8044".into(), "tcp://new:8045".into()]),
+            add_endpoints: vec!["tcps://other:8044".into()],
+            remove_endpoints: vec!["tcp://new:8045".into()],
+            remove_metadata: vec!["company".into()],
+            ..Default::default()
+        };
+        let updated = update.apply(node()).expect("Unable to apply update");
+
+        assert_eq!(
+            updated.endpoints,
+            vec![
+                "tcps://new:8044".to_string(),
+                "tcps://other:8044".to_string()
+            ]
+        );
+        assert!(updated.metadata.is_empty());
+    }
+
+    /// Verify that an update is rejected if it removes something the node does not have, or if it
+    /// would leave the node without endpoints or keys.
+    #[test]
+    fn invalid_update() {
+        let update = NodeUpdate {
+            remove_endpoints: vec!["tcps://unknown:8044".into()],
+            ..Default::default()
+        };
+        assert!(update.apply(node()).is_err());
+
+        let update = NodeUpdate {
+            remove_keys: vec!["aaaa".into()],
+            ..Default::default()
+        };
+        assert!(update.apply(node()).is_err());
+
+        let update = NodeUpdate {
+            remove_metadata: vec!["unknown".into()],
+            ..Default::default()
+        };
+        assert!(update.apply(node()).is_err());
+
+        assert!(NodeUpdate::default().is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "registry-import"))]
+mod import_tests {
+    use super::*;
+
+    use tempfile::Builder;
+
+    /// Verify that each node in a registry file becomes a create operation, or an update operation
+    /// when updating, and that an invalid node is rejected.
+    #[test]
+    fn read_node_operations_from_file() {
+        let temp_dir = Builder::new()
+            .prefix("read_node_operations_from_file")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let registry_file = temp_dir.path().join("nodes.yaml");
+
+        std::fs::write(
+            &registry_file,
+            "- identity: node-1\n  endpoints:\n    - tcps://node-1:8044\n  display_name: Node 1\n  \
+             keys:\n    - aaaa\n  metadata:\n    company: Cargill\n",
+        )
+        .expect("Failed to write registry file");
+        let file_name = registry_file.to_str().expect("Invalid path");
+
+        let mut metadata = HashMap::new();
+        metadata.insert("company".to_string(), "Cargill".to_string());
+        let node = RegistryNode {
+            identity: "node-1".into(),
+            endpoints: vec!["tcps://node-1:8044".into()],
+            display_name: "Node 1".into(),
+            keys: vec!["aaaa".into()],
+            metadata,
+        };
+
+        assert_eq!(
+            read_node_operations(file_name, false).expect("Failed to read operations"),
+            vec![RegistryNodeOperation::Create { node: node.clone() }]
+        );
+        assert_eq!(
+            read_node_operations(file_name, true).expect("Failed to read operations"),
+            vec![RegistryNodeOperation::Update { node }]
+        );
+
+        std::fs::write(
+            &registry_file,
+            "- identity: node-1\n  endpoints: []\n  display_name: Node 1\n  keys:\n    - aaaa\n  \
+             metadata: {}\n",
+        )
+        .expect("Failed to write registry file");
+        assert!(read_node_operations(file_name, false).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for managing the remote registries of a node at runtime.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use cylinder::Signer;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::super::api::{SplinterRestClient, SplinterRestClientBuilder};
+use super::super::{print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+use super::api::RegistrySource;
+
+pub struct RegistrySourceAddAction;
+
+impl Action for RegistrySourceAddAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let source_url = args
+            .value_of("source_url")
+            .ok_or_else(|| CliError::ActionError("'source-url' argument is required".into()))?;
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = build_client(args, signer)?;
+
+        let source = client.add_registry_source(source_url)?;
+        match &source.error {
+            Some(err) => warn!(
+                "Added registry source {}, but the initial fetch failed: {}",
+                source.url, err
+            ),
+            None => info!(
+                "Added registry source {} with {} nodes",
+                source.url, source.node_count
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RegistrySourceListAction;
+
+impl Action for RegistrySourceListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = build_client(args, signer)?;
+
+        let sources = client.list_registry_sources()?;
+
+        match args.value_of("format") {
+            Some("json") => println!(
+                "\n {}",
+                serde_json::to_string(&sources).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format registry sources into json: {}",
+                    err
+                )))?
+            ),
+            _ => {
+                let mut data = vec![vec![
+                    "URL".to_string(),
+                    "TYPE".to_string(),
+                    "NODES".to_string(),
+                    "LAST REFRESH".to_string(),
+                    "ERROR".to_string(),
+                ]];
+                for source in sources {
+                    data.push(vec![
+                        source.url.clone(),
+                        if source.dynamic { "dynamic" } else { "static" }.to_string(),
+                        source.node_count.to_string(),
+                        format_last_refresh(&source),
+                        source.error.unwrap_or_default(),
+                    ]);
+                }
+                print_table(data);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RegistrySourceRemoveAction;
+
+impl Action for RegistrySourceRemoveAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let source_url = args
+            .value_of("source_url")
+            .ok_or_else(|| CliError::ActionError("'source-url' argument is required".into()))?;
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = build_client(args, signer)?;
+
+        client.remove_registry_source(source_url)?;
+        info!("Removed registry source {}", source_url);
+
+        Ok(())
+    }
+}
+
+/// Formats the time since the source was last refreshed, or "never" if it has not been.
+fn format_last_refresh(source: &RegistrySource) -> String {
+    match source.last_refresh {
+        Some(last_refresh) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(last_refresh);
+            format!("{}s ago", now.saturating_sub(last_refresh))
+        }
+        None => "never".to_string(),
+    }
+}
+
+fn build_client(
+    args: &ArgMatches,
+    signer: Box<dyn Signer>,
+) -> Result<SplinterRestClient, CliError> {
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
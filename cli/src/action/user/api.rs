@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use reqwest::blocking::Client;
+#[cfg(feature = "user-deactivation")]
+use reqwest::StatusCode;
 use serde::Deserialize;
 
-use crate::action::api::{ServerError, SplinterRestClient};
+use crate::action::api::{http_client, ServerError, SplinterRestClient};
 use crate::error::CliError;
 
 pub(super) const PAGING_LIMIT: &str = "1000";
@@ -23,9 +24,16 @@ pub(super) const PAGING_LIMIT: &str = "1000";
 pub(super) const CLI_SPLINTER_USER_PROTOCOL_VERSION: &str = "1";
 
 impl SplinterRestClient {
-    pub fn list_biome_users(&self) -> Result<Vec<ClientBiomeUser>, CliError> {
-        Client::new()
-            .get(&format!("{}/biome/users", self.url))
+    /// Submits a request to list Biome users, optionally filtered by whether or not the users are
+    /// active
+    pub fn list_biome_users(&self, active: Option<bool>) -> Result<Vec<ClientBiomeUser>, CliError> {
+        let url = match active {
+            Some(active) => format!("{}/biome/users?active={}", self.url, active),
+            None => format!("{}/biome/users", self.url),
+        };
+
+        http_client()
+            .get(&url)
             .header(
                 "SplinterProtocolVersion",
                 CLI_SPLINTER_USER_PROTOCOL_VERSION,
@@ -63,10 +71,58 @@ impl SplinterRestClient {
             })
     }
 
+    /// Submits a request to list Biome's user profiles
+    pub fn list_biome_profiles(&self) -> Result<Vec<ClientBiomeProfile>, CliError> {
+        http_client()
+            .get(&format!("{}/biome/profiles", self.url))
+            .header(
+                "SplinterProtocolVersion",
+                CLI_SPLINTER_USER_PROTOCOL_VERSION,
+            )
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to list biome profiles: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<Vec<ClientBiomeProfile>>().map_err(|_| {
+                        CliError::ActionError(
+                            "List Biome profiles request succeeded, but response was not valid"
+                                .to_string(),
+                        )
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "List Biome profiles request failed with status code '{}', but \
+                            error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to list Biome profiles: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     /// Submits a request to list Biome's OAuth users
-    pub fn list_oauth_users(&self) -> Result<ClientOAuthUserListResponse, CliError> {
-        Client::new()
-            .get(&format!("{}/oauth/users?limit={}", self.url, PAGING_LIMIT))
+    pub fn list_oauth_users(
+        &self,
+        active: Option<bool>,
+    ) -> Result<ClientOAuthUserListResponse, CliError> {
+        let mut url = format!("{}/oauth/users?limit={}", self.url, PAGING_LIMIT);
+        if let Some(active) = active {
+            url.push_str(&format!("&active={}", active));
+        }
+
+        http_client()
+            .get(&url)
             .header(
                 "SplinterProtocolVersion",
                 CLI_SPLINTER_USER_PROTOCOL_VERSION,
@@ -104,6 +160,57 @@ impl SplinterRestClient {
                 }
             })
     }
+
+    /// Submits a request to deactivate or reactivate a user.
+    ///
+    /// The `user_type` is the route prefix of the user, either `biome` or `oauth`. Returns
+    /// `Ok(false)` if the user does not exist.
+    #[cfg(feature = "user-deactivation")]
+    pub fn set_user_active(
+        &self,
+        user_type: &str,
+        user_id: &str,
+        active: bool,
+    ) -> Result<bool, CliError> {
+        let operation = if active { "reactivate" } else { "deactivate" };
+
+        http_client()
+            .post(&format!(
+                "{}/{}/users/{}/{}",
+                self.url, user_type, user_id, operation
+            ))
+            .header(
+                "SplinterProtocolVersion",
+                CLI_SPLINTER_USER_PROTOCOL_VERSION,
+            )
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to {} user: {}", operation, err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(true)
+                } else if status == StatusCode::NOT_FOUND {
+                    Ok(false)
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Request to {} user failed with status code '{}', but error \
+                                 response was not valid",
+                                operation, status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to {} user: {}",
+                        operation, message
+                    )))
+                }
+            })
+    }
 }
 
 /// Biome OAuth user details.
@@ -111,6 +218,9 @@ impl SplinterRestClient {
 pub struct ClientOAuthUser {
     pub subject: String,
     pub user_id: String,
+    #[cfg(feature = "user-deactivation")]
+    #[serde(default = "default_active")]
+    pub active: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -119,11 +229,28 @@ pub struct ClientOAuthUserListResponse {
     pub paging: Paging,
 }
 
+/// Biome user profile details.
+#[derive(Debug, Deserialize)]
+pub struct ClientBiomeProfile {
+    pub user_id: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
 /// Biome user details, specific to the client to allow for deserializing the response data.
 #[derive(Debug, Deserialize)]
 pub struct ClientBiomeUser {
     pub username: String,
     pub user_id: String,
+    #[cfg(feature = "user-deactivation")]
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+/// Users returned by servers that do not support deactivation are always active.
+#[cfg(feature = "user-deactivation")]
+fn default_active() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize)]
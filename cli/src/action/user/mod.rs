@@ -14,6 +14,8 @@
 
 mod api;
 
+use std::collections::HashMap;
+
 use clap::ArgMatches;
 use cylinder::Signer;
 
@@ -38,7 +40,80 @@ impl Action for ListSplinterUsersAction {
             .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
             .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
 
-        display_splinter_users(&url, format, signer)
+        #[cfg(feature = "user-deactivation")]
+        let active = if args.is_present("active") {
+            Some(true)
+        } else if args.is_present("inactive") {
+            Some(false)
+        } else {
+            None
+        };
+        #[cfg(not(feature = "user-deactivation"))]
+        let active = None;
+
+        display_splinter_users(&url, format, signer, active)
+    }
+}
+
+/// Deactivates a Splinter user, preventing the user from logging in or using existing tokens.
+#[cfg(feature = "user-deactivation")]
+pub struct DeactivateSplinterUserAction;
+
+#[cfg(feature = "user-deactivation")]
+impl Action for DeactivateSplinterUserAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        set_splinter_user_active(arg_matches, false)
+    }
+}
+
+/// Reactivates a previously deactivated Splinter user.
+#[cfg(feature = "user-deactivation")]
+pub struct ReactivateSplinterUserAction;
+
+#[cfg(feature = "user-deactivation")]
+impl Action for ReactivateSplinterUserAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        set_splinter_user_active(arg_matches, true)
+    }
+}
+
+/// Sets the active state of the user, trying Biome users first and then OAuth users.
+#[cfg(feature = "user-deactivation")]
+fn set_splinter_user_active(
+    arg_matches: Option<&ArgMatches>,
+    active: bool,
+) -> Result<(), CliError> {
+    let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+    let user_id = args
+        .value_of("user_id")
+        .ok_or_else(|| CliError::ActionError("'user_id' argument is required".into()))?;
+    let signer = load_signer(args.value_of("private_key_file"))?;
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()?;
+
+    if client.set_user_active("biome", user_id, active)?
+        || client.set_user_active("oauth", user_id, active)?
+    {
+        info!(
+            "User {} has been {}",
+            user_id,
+            if active { "reactivated" } else { "deactivated" }
+        );
+        Ok(())
+    } else {
+        Err(CliError::ActionError(format!(
+            "User {} does not exist",
+            user_id
+        )))
     }
 }
 
@@ -46,13 +121,14 @@ fn display_splinter_users(
     url: &str,
     format: &str,
     signer: Box<dyn Signer>,
+    active: Option<bool>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
         .with_url(url.to_string())
         .with_auth(create_cylinder_jwt_auth(signer.clone())?)
         .build()?;
 
-    let biome_users = match client.list_biome_users() {
+    let biome_users = match client.list_biome_users(active) {
         Ok(users) => Some(users.into_iter().map(ClientSplinterUser::from)),
         Err(e) => {
             info!("Unable to retrieve Biome users: {}", e);
@@ -60,7 +136,7 @@ fn display_splinter_users(
         }
     };
 
-    let biome_oauth_users = match client.list_oauth_users() {
+    let biome_oauth_users = match client.list_oauth_users(active) {
         Ok(users) => Some(users.data.into_iter().map(ClientSplinterUser::from)),
         Err(e) => {
             info!("Unable to retrieve OAuth users: {}", e);
@@ -68,10 +144,30 @@ fn display_splinter_users(
         }
     };
 
+    // Profiles are only available for users that have logged in with OAuth
+    let profiles = match client.list_biome_profiles() {
+        Ok(profiles) => profiles
+            .into_iter()
+            .map(|profile| (profile.user_id.clone(), profile))
+            .collect::<HashMap<_, _>>(),
+        Err(e) => {
+            info!("Unable to retrieve Biome profiles: {}", e);
+            HashMap::new()
+        }
+    };
+
     let mut data = vec![
         // headers
-        vec!["ID".to_string(), "USERNAME".to_string(), "TYPE".to_string()],
+        vec![
+            "ID".to_string(),
+            "USERNAME".to_string(),
+            "TYPE".to_string(),
+            "NAME".to_string(),
+            "EMAIL".to_string(),
+        ],
     ];
+    #[cfg(feature = "user-deactivation")]
+    data[0].push("STATUS".to_string());
 
     let users = match (biome_users, biome_oauth_users) {
         (Some(biome_users), Some(biome_oauth_users)) => biome_users
@@ -86,13 +182,30 @@ fn display_splinter_users(
             ))
         }
     };
-    users.into_iter().for_each(|user| match user {
-        ClientSplinterUser::Biome(user) => {
-            data.push(vec![user.user_id, user.username, "Biome".to_string()])
-        }
-        ClientSplinterUser::OAuth(user) => {
-            data.push(vec![user.user_id, user.subject, "OAuth".to_string()])
-        }
+    users.into_iter().for_each(|user| {
+        #[cfg(feature = "user-deactivation")]
+        let user_active = match &user {
+            ClientSplinterUser::Biome(user) => user.active,
+            ClientSplinterUser::OAuth(user) => user.active,
+        };
+        let (user_id, username, user_type) = match user {
+            ClientSplinterUser::Biome(user) => (user.user_id, user.username, "Biome"),
+            ClientSplinterUser::OAuth(user) => (user.user_id, user.subject, "OAuth"),
+        };
+        let (name, email) = profiles
+            .get(&user_id)
+            .map(|profile| {
+                (
+                    profile.name.clone().unwrap_or_default(),
+                    profile.email.clone().unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default();
+        #[allow(unused_mut)]
+        let mut row = vec![user_id, username, user_type.to_string(), name, email];
+        #[cfg(feature = "user-deactivation")]
+        row.push(if user_active { "active" } else { "inactive" }.to_string());
+        data.push(row)
     });
 
     if format == "csv" {
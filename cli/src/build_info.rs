@@ -0,0 +1,57 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Information about how the CLI was built, reported by `splinter --version --verbose`.
+
+use std::ffi::OsString;
+
+/// Returns true if the arguments request both the version and verbose output, in any order.
+///
+/// This is checked before the arguments are parsed, as clap exits as soon as it sees the version
+/// flag.
+pub fn is_verbose_version_request(args: &[OsString]) -> bool {
+    let is_version = |arg: &OsString| arg == "--version" || arg == "-V";
+    let is_verbose = |arg: &OsString| {
+        arg == "--verbose"
+            || arg
+                .to_str()
+                .and_then(|arg| arg.strip_prefix('-'))
+                .map(|flags| !flags.is_empty() && flags.chars().all(|flag| flag == 'v'))
+                .unwrap_or(false)
+    };
+
+    args.iter().skip(1).any(is_version) && args.iter().skip(1).any(is_verbose)
+}
+
+/// Formats the version of the CLI along with the commit and cargo features it was built with
+/// and the current version of each protocol supported by its Splinter library.
+pub fn verbose_version() -> String {
+    let features = option_env!("SPLINTER_FEATURES")
+        .unwrap_or("")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut version = format!(
+        "{} {}\ngit hash: {}\nfeatures: {}\nprotocol versions:",
+        super::APP_NAME,
+        super::VERSION,
+        option_env!("SPLINTER_GIT_HASH").unwrap_or("unknown"),
+        features.join(", "),
+    );
+    for (name, protocol_version) in splinter::protocol::protocol_versions() {
+        version.push_str(&format!("\n  {}: {}", name, protocol_version));
+    }
+    version
+}
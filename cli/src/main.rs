@@ -18,7 +18,11 @@ extern crate log;
 extern crate diesel;
 
 mod action;
+#[cfg(feature = "build-info")]
+mod build_info;
 mod error;
+#[cfg(feature = "profile")]
+mod profile;
 mod signing;
 #[cfg(test)]
 mod tests;
@@ -85,58 +89,152 @@ pub fn log_format(
 }
 
 fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<(), CliError> {
+    let args = args.into_iter().map(Into::into).collect::<Vec<OsString>>();
+
+    #[cfg(feature = "build-info")]
+    {
+        if build_info::is_verbose_version_request(&args) {
+            println!("{}", build_info::verbose_version());
+            return Ok(());
+        }
+    }
+
     let mut app = clap_app!(myapp =>
         (name: APP_NAME)
         (version: VERSION)
         (author: "Cargill")
         (about: "Command line for Splinter")
-        (@arg verbose: -v +multiple +global "Log verbosely")
+        (@arg verbose: -v --verbose +multiple +global "Log verbosely")
         (@arg quiet: -q --quiet +global "Do not display output")
         (@setting SubcommandRequiredElseHelp)
     );
 
-    app = app
-        .subcommand(
-        SubCommand::with_name("keygen")
-            .about("Generates secp256k1 keys")
-            .arg(
-                Arg::with_name("key-name")
-                    .takes_value(true)
-                    .help("Name of keys generated; defaults to user name"),
-            )
+    #[cfg(feature = "profile")]
+    {
+        app = app
             .arg(
-                Arg::with_name("key_dir")
-                    .long("key-dir")
+                Arg::with_name("profile")
+                    .long("profile")
                     .takes_value(true)
-                    .conflicts_with("system")
+                    .global(true)
                     .help(
-                        "Name of the directory in which to create the keys; defaults to \
-                             $HOME/splinter/keys",
+                        "Name of the profile that provides the default REST API URL and signing \
+                         key; may also be set with SPLINTER_PROFILE",
                     ),
             )
-            .arg(
-                Arg::with_name("force")
-                    .short("f")
-                    .long("force")
-                    .help("Overwrite files if they exist"),
-            )
-            .arg(
-                Arg::with_name("skip")
-                    .long("skip")
-                    .conflicts_with("force")
-                    .help("Skip generating the files if they exist"),
-            )
-            .arg(
-                Arg::with_name("system")
-                    .long("system")
-                    .help("Generate system keys in /etc/splinter/keys"),
-            )
-            .arg(Arg::with_name("group").long("group").help(
-                "Key file owning group, options are none|auto|<name_of_group>|<gid_of_group>",
-            )
-            .takes_value(true)),
+            .subcommand(
+                SubCommand::with_name("profile")
+                    .about("Manages profiles of REST API URLs and signing keys")
+                    .setting(AppSettings::SubcommandRequiredElseHelp)
+                    .subcommand(
+                        SubCommand::with_name("create")
+                            .about("Creates a profile")
+                            .arg(
+                                Arg::with_name("name")
+                                    .long("name")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Name of the profile"),
+                            )
+                            .arg(
+                                Arg::with_name("url")
+                                    .short("U")
+                                    .long("url")
+                                    .takes_value(true)
+                                    .help("URL of the Splinter daemon REST API"),
+                            )
+                            .arg(
+                                Arg::with_name("private_key_file")
+                                    .value_name("private-key-file")
+                                    .short("k")
+                                    .long("key")
+                                    .takes_value(true)
+                                    .help("Name or path of private key"),
+                            )
+                            .arg(
+                                Arg::with_name("force")
+                                    .short("f")
+                                    .long("force")
+                                    .help("Replace the profile if it already exists"),
+                            ),
+                    )
+                    .subcommand(SubCommand::with_name("list").about("Lists the profiles"))
+                    .subcommand(
+                        SubCommand::with_name("show")
+                            .about("Displays a profile")
+                            .arg(
+                                Arg::with_name("name")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Name of the profile"),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("delete")
+                            .about("Deletes a profile")
+                            .arg(
+                                Arg::with_name("name")
+                                    .takes_value(true)
+                                    .required(true)
+                                    .help("Name of the profile"),
+                            ),
+                    ),
+            );
+    }
+
+    let keygen = SubCommand::with_name("keygen")
+        .about("Generates secp256k1 keys")
+        .arg(
+            Arg::with_name("key-name")
+                .takes_value(true)
+                .help("Name of keys generated; defaults to user name"),
+        )
+        .arg(
+            Arg::with_name("key_dir")
+                .long("key-dir")
+                .takes_value(true)
+                .conflicts_with("system")
+                .help(
+                    "Name of the directory in which to create the keys; defaults to \
+                             $HOME/splinter/keys",
+                ),
+        )
+        .arg(
+            Arg::with_name("force")
+                .short("f")
+                .long("force")
+                .help("Overwrite files if they exist"),
+        )
+        .arg(
+            Arg::with_name("skip")
+                .long("skip")
+                .conflicts_with("force")
+                .help("Skip generating the files if they exist"),
+        )
+        .arg(
+            Arg::with_name("system")
+                .long("system")
+                .help("Generate system keys in /etc/splinter/keys"),
+        )
+        .arg(
+            Arg::with_name("group")
+                .long("group")
+                .help("Key file owning group, options are none|auto|<name_of_group>|<gid_of_group>")
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "key-pem")]
+    let keygen = keygen.arg(
+        Arg::with_name("key_format")
+            .long("key-format")
+            .takes_value(true)
+            .possible_values(&["hex", "pem"])
+            .default_value("hex")
+            .help("Encoding of the generated key files"),
     );
 
+    app = app.subcommand(keygen);
+
     let propose_circuit = SubCommand::with_name("propose")
         .about("Propose that a new circuit is created")
         .arg(
@@ -282,6 +380,98 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    #[cfg(feature = "challenge-trust-policy")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("node_key")
+            .long("node-key")
+            .takes_value(true)
+            .multiple(true)
+            .help(
+                "Additional public key that may authorize as a node in challenge \
+                 authorization; may be repeated (<node_id>::<public_key>)",
+            ),
+    );
+
+    #[cfg(feature = "circuit-attachments")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("attachment")
+            .long("attachment")
+            .takes_value(true)
+            .multiple(true)
+            .help(
+                "Document to attach to the proposal; the hash may be replaced by a local file \
+                 path to compute it; may be repeated (<name>,<sha256|file>,<uri>)",
+            ),
+    );
+
+    #[cfg(feature = "circuit-max-message-size")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("max_message_size")
+            .long("max-message-size")
+            .value_name("bytes")
+            .takes_value(true)
+            .help("Largest message, in bytes, the circuit will route between its services"),
+    );
+
+    #[cfg(feature = "circuit-propose-timeout")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("timeout")
+            .long("timeout")
+            .value_name("seconds")
+            .takes_value(true)
+            .conflicts_with("dry_run")
+            .help(
+                "Coordinator timeout, in seconds, to request for this proposal; bounded by the \
+                 node's configured maximum",
+            ),
+    );
+
+    #[cfg(feature = "circuit-propose-retry")]
+    let propose_circuit = propose_circuit
+        .arg(
+            Arg::with_name("verification_timeout")
+                .long("verification-timeout")
+                .value_name("seconds")
+                .takes_value(true)
+                .conflicts_with("dry_run")
+                .help(
+                    "Time, in seconds, the coordinator waits for verification responses before \
+                     re-sending its verification request",
+                ),
+        )
+        .arg(
+            Arg::with_name("verification_retries")
+                .long("verification-retries")
+                .value_name("count")
+                .takes_value(true)
+                .conflicts_with("dry_run")
+                .help(
+                    "Number of times the coordinator re-sends its verification request before \
+                     rejecting the proposal",
+                ),
+        )
+        .arg(
+            Arg::with_name("verification_backoff")
+                .long("verification-backoff")
+                .value_name("factor")
+                .takes_value(true)
+                .conflicts_with("dry_run")
+                .help("Factor by which the coordinator multiplies its wait after each retry"),
+        );
+
+    #[cfg(feature = "circuit-propose-wait")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("wait")
+            .long("wait")
+            .value_name("seconds")
+            .takes_value(true)
+            .conflicts_with("dry_run")
+            .help(
+                "Wait up to the given number of seconds for the circuit to be created, \
+                 reporting progress",
+            ),
+    );
+
     #[cfg(feature = "circuit-template")]
     let propose_circuit = propose_circuit
         .arg(
@@ -303,100 +493,222 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 ),
         );
 
-    let circuit_command = SubCommand::with_name("circuit")
-        .about("Provides circuit management functionality")
-        .setting(AppSettings::SubcommandRequiredElseHelp)
-        .subcommand(propose_circuit)
-        .subcommand(
-            SubCommand::with_name("vote")
-                .about("Vote on a new circuit proposal")
-                .arg(
-                    Arg::with_name("url")
-                        .short("U")
-                        .long("url")
-                        .takes_value(true)
-                        .help("URL of Splinter Daemon"),
-                )
-                .arg(
-                    Arg::with_name("private_key_file")
-                        .value_name("private-key-file")
-                        .short("k")
-                        .long("key")
-                        .takes_value(true)
-                        .help("Path to private key file"),
-                )
-                .arg(
-                    Arg::with_name("circuit_id")
-                        .value_name("circuit-id")
-                        .takes_value(true)
-                        .required(true)
-                        .help("ID of the proposed circuit"),
-                )
-                .arg(
-                    Arg::with_name("accept")
-                        .required(true)
-                        .long("accept")
-                        .conflicts_with("reject")
-                        .help("Accept the proposal"),
-                )
-                .arg(
-                    Arg::with_name("reject")
-                        .required(true)
-                        .long("reject")
-                        .conflicts_with("accept")
-                        .help("Reject the proposal"),
-                ),
+    let list_circuits = SubCommand::with_name("list")
+        .about("List the circuits")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .help("URL of the Splinter daemon REST API")
+                .takes_value(true),
         )
-        .subcommand(
-            SubCommand::with_name("list")
-                .about("List the circuits")
-                .arg(
-                    Arg::with_name("url")
-                        .short("U")
-                        .long("url")
-                        .help("URL of the Splinter daemon REST API")
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("member")
-                        .short("m")
-                        .long("member")
-                        .help("Filter circuits by a node ID in the member list")
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("circuit_status")
-                        .long("circuit-status")
-                        .help("Filter circuits by a circuit status")
-                        .possible_values(&["active", "disbanded", "abandoned"])
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("format")
-                        .short("F")
-                        .long("format")
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .default_value("human")
-                        .takes_value(true),
+        .arg(
+            Arg::with_name("member")
+                .short("m")
+                .long("member")
+                .help("Filter circuits by a node ID in the member list")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("circuit_status")
+                .long("circuit-status")
+                .help("Filter circuits by a circuit status")
+                .possible_values(&["active", "disbanded", "abandoned"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("F")
+                .long("format")
+                .help("Output format")
+                .possible_values(&["human", "csv", "json", "yaml"])
+                .default_value("human")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("hidden_format")
+                .short("f")
+                .hidden(true)
+                .help("Output format")
+                .possible_values(&["human", "csv", "json", "yaml"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Name or path of private key"),
+        );
+
+    #[cfg(feature = "circuit-requester-filter")]
+    let list_circuits = list_circuits.arg(
+        Arg::with_name("requester")
+            .long("requester")
+            .help("Filter circuits by the public key of the requester that proposed them")
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "circuit-type-filter")]
+    let list_circuits = list_circuits
+        .arg(
+            Arg::with_name("management_type")
+                .long("management-type")
+                .help("Filter circuits by circuit management type")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("service_type")
+                .long("service-type")
+                .help("Filter circuits by the type of a service in the roster")
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "circuit-tombstones")]
+    let list_circuits = list_circuits.arg(
+        Arg::with_name("include_deleted")
+            .long("include-deleted")
+            .help("Include circuits that were purged but kept as tombstones"),
+    );
+
+    let list_proposals = SubCommand::with_name("proposals")
+        .about("List the circuit proposals")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .help("URL of the Splinter daemon REST API")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("management_type")
+                .long("management-type")
+                .help(
+                    "Filter circuit proposals by circuit \
+                     management type",
                 )
-                .arg(
-                    Arg::with_name("hidden_format")
-                        .short("f")
-                        .hidden(true)
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .takes_value(true),
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("member")
+                .long("member")
+                .help(
+                    "Show proposals with the given node ID in \
+                    its member list",
                 )
-                .arg(
-                    Arg::with_name("private_key_file")
-                        .value_name("private-key-file")
-                        .short("k")
-                        .long("key")
-                        .takes_value(true)
-                        .help("Name or path of private key"),
-                ),
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("F")
+                .long("format")
+                .help("Output format")
+                .possible_values(&["human", "csv", "json", "yaml"])
+                .default_value("human")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("hidden_format")
+                .short("f")
+                .hidden(true)
+                .help("Output format")
+                .possible_values(&["human", "csv", "json", "yaml"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Name or path of private key"),
+        );
+
+    #[cfg(feature = "circuit-requester-filter")]
+    let list_proposals = list_proposals.arg(
+        Arg::with_name("requester")
+            .long("requester")
+            .help("Show proposals submitted by the given requester public key")
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "circuit-vote-status-filter")]
+    let list_proposals = list_proposals.arg(
+        Arg::with_name("awaiting_my_vote")
+            .long("awaiting-my-vote")
+            .help("Only show proposals that are still waiting on a vote from the signing key"),
+    );
+
+    #[cfg(feature = "circuit-proposals-watch")]
+    let list_proposals = list_proposals
+        .arg(Arg::with_name("watch").short("w").long("watch").help(
+            "After listing the proposals, keep watching for proposals that are \
+                     added, voted on, accepted or rejected",
+        ))
+        .arg(
+            Arg::with_name("interval")
+                .long("interval")
+                .value_name("seconds")
+                .takes_value(true)
+                .requires("watch")
+                .help("Number of seconds between polls of the REST API (default: 2)"),
+        );
+
+    let circuit_vote = SubCommand::with_name("vote")
+        .about("Vote on a new circuit proposal")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .takes_value(true)
+                .help("URL of Splinter Daemon"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Path to private key file"),
+        )
+        .arg(
+            Arg::with_name("circuit_id")
+                .value_name("circuit-id")
+                .takes_value(true)
+                .required(true)
+                .help("ID of the proposed circuit"),
         )
+        .arg(
+            Arg::with_name("accept")
+                .required(true)
+                .long("accept")
+                .conflicts_with("reject")
+                .help("Accept the proposal"),
+        )
+        .arg(
+            Arg::with_name("reject")
+                .required(true)
+                .long("reject")
+                .conflicts_with("accept")
+                .help("Reject the proposal"),
+        );
+
+    #[cfg(feature = "circuit-rejection-reasons")]
+    let circuit_vote = circuit_vote.arg(
+        Arg::with_name("comment")
+            .long("comment")
+            .takes_value(true)
+            .requires("reject")
+            .help("Reason for rejecting the proposal, shared with the requester"),
+    );
+
+    let circuit_command = SubCommand::with_name("circuit")
+        .about("Provides circuit management functionality")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(propose_circuit)
+        .subcommand(circuit_vote)
+        .subcommand(list_circuits)
         .subcommand(
             SubCommand::with_name("show")
                 .about("Show a specific circuit or proposal")
@@ -439,60 +751,7 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         .help("Name or path of private key"),
                 ),
         )
-        .subcommand(
-            SubCommand::with_name("proposals")
-                .about("List the circuit proposals")
-                .arg(
-                    Arg::with_name("url")
-                        .short("U")
-                        .long("url")
-                        .help("URL of the Splinter daemon REST API")
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("management_type")
-                        .long("management-type")
-                        .help(
-                            "Filter circuit proposals by circuit \
-                             management type",
-                        )
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("member")
-                        .long("member")
-                        .help(
-                            "Show proposals with the given node ID in \
-                            its member list",
-                        )
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("format")
-                        .short("F")
-                        .long("format")
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .default_value("human")
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("hidden_format")
-                        .short("f")
-                        .hidden(true)
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("private_key_file")
-                        .value_name("private-key-file")
-                        .short("k")
-                        .long("key")
-                        .takes_value(true)
-                        .help("Name or path of private key"),
-                ),
-        );
+        .subcommand(list_proposals);
 
     let circuit_command = circuit_command.subcommand(
         SubCommand::with_name("disband")
@@ -521,36 +780,96 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
-    let circuit_command = circuit_command.subcommand(
-        SubCommand::with_name("purge")
-            .about("Purge an existing inactive circuit")
-            .arg(
-                Arg::with_name("url")
-                    .short("U")
-                    .long("url")
-                    .takes_value(true)
-                    .help("URL of Splinter Daemon"),
-            )
-            .arg(
-                Arg::with_name("private_key_file")
-                    .value_name("private-key-file")
-                    .short("k")
-                    .long("key")
-                    .takes_value(true)
-                    .help("Path to private key file"),
-            )
-            .arg(
-                Arg::with_name("circuit_id")
-                    .value_name("circuit-id")
-                    .takes_value(true)
-                    .required(true)
-                    .help("ID of the circuit to be purged"),
-            ),
+    let purge_circuit = SubCommand::with_name("purge")
+        .about("Purge an existing inactive circuit")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .takes_value(true)
+                .help("URL of Splinter Daemon"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Path to private key file"),
+        )
+        .arg(
+            Arg::with_name("circuit_id")
+                .value_name("circuit-id")
+                .takes_value(true)
+                .required(true)
+                .help("ID of the circuit to be purged"),
+        );
+
+    #[cfg(feature = "circuit-purge-keep-state")]
+    let purge_circuit = purge_circuit.arg(
+        Arg::with_name("keep_state")
+            .long("keep-state")
+            .help("Leave the service state stores on disk after the circuit is purged"),
+    );
+
+    #[cfg(feature = "circuit-purge-preview")]
+    let purge_circuit = purge_circuit.arg(
+        Arg::with_name("force")
+            .short("f")
+            .long("force")
+            .help("Purge the circuit without prompting for confirmation"),
+    );
+
+    let circuit_command = circuit_command.subcommand(purge_circuit);
+
+    let abandon_circuit = SubCommand::with_name("abandon")
+        .about("Abandon an existing circuit")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .takes_value(true)
+                .help("URL of Splinter Daemon"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Path to private key file"),
+        )
+        .arg(
+            Arg::with_name("circuit_id")
+                .value_name("circuit-id")
+                .takes_value(true)
+                .required(true)
+                .help("ID of the circuit to be abandoned"),
+        );
+
+    #[cfg(feature = "circuit-abandon-purge")]
+    let abandon_circuit = abandon_circuit.arg(
+        Arg::with_name("purge")
+            .long("purge")
+            .help("Purge the circuit once it has been abandoned"),
+    );
+
+    #[cfg(all(
+        feature = "circuit-abandon-purge",
+        feature = "circuit-purge-keep-state"
+    ))]
+    let abandon_circuit = abandon_circuit.arg(
+        Arg::with_name("keep_state")
+            .long("keep-state")
+            .requires("purge")
+            .help("Leave the service state stores on disk after the circuit is purged"),
     );
 
+    let circuit_command = circuit_command.subcommand(abandon_circuit);
+
     let circuit_command = circuit_command.subcommand(
-        SubCommand::with_name("abandon")
-            .about("Abandon an existing circuit")
+        SubCommand::with_name("remove-proposal")
+            .about("Remove a circuit proposal")
             .arg(
                 Arg::with_name("url")
                     .short("U")
@@ -571,37 +890,188 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                     .value_name("circuit-id")
                     .takes_value(true)
                     .required(true)
-                    .help("ID of the circuit to be abandoned"),
+                    .help("ID of the circuit proposal to remove"),
             ),
     );
 
+    #[cfg(feature = "circuit-vote-delegation")]
     let circuit_command = circuit_command.subcommand(
-        SubCommand::with_name("remove-proposal")
-            .about("Remove a circuit proposal")
-            .arg(
-                Arg::with_name("url")
-                    .short("U")
-                    .long("url")
-                    .takes_value(true)
-                    .help("URL of Splinter Daemon"),
+        SubCommand::with_name("delegation")
+            .about("Manage keys that may vote on circuit proposals on behalf of another key")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("add")
+                    .about("Allow another key to vote on behalf of the signing key")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL of Splinter Daemon"),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Path to the private key file of the delegating key"),
+                    )
+                    .arg(
+                        Arg::with_name("delegate")
+                            .long("delegate")
+                            .takes_value(true)
+                            .required(true)
+                            .help("Public key, in hex, that may vote in place of the signing key"),
+                    )
+                    .arg(
+                        Arg::with_name("management_type")
+                            .value_name("management-type")
+                            .long("management-type")
+                            .takes_value(true)
+                            .multiple(true)
+                            .required(true)
+                            .help("Circuit management type the delegate may vote on"),
+                    )
+                    .arg(
+                        Arg::with_name("node_id")
+                            .value_name("node-id")
+                            .long("node-id")
+                            .takes_value(true)
+                            .help(
+                                "Node the delegate may vote for (defaults to the node at the URL)",
+                            ),
+                    ),
             )
-            .arg(
-                Arg::with_name("private_key_file")
-                    .value_name("private-key-file")
-                    .short("k")
-                    .long("key")
-                    .takes_value(true)
-                    .help("Path to private key file"),
+            .subcommand(
+                SubCommand::with_name("list")
+                    .about("List the vote delegations held by a node")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL of Splinter Daemon"),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Path to private key file"),
+                    )
+                    .arg(
+                        Arg::with_name("format")
+                            .short("F")
+                            .long("format")
+                            .help("Output format")
+                            .possible_values(&["human", "json"])
+                            .default_value("human")
+                            .takes_value(true),
+                    ),
             )
-            .arg(
-                Arg::with_name("circuit_id")
-                    .value_name("circuit-id")
-                    .takes_value(true)
-                    .required(true)
-                    .help("ID of the circuit proposal to remove"),
+            .subcommand(
+                SubCommand::with_name("remove")
+                    .about("Remove a vote delegation")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL of Splinter Daemon"),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Path to private key file"),
+                    )
+                    .arg(
+                        Arg::with_name("delegate")
+                            .long("delegate")
+                            .takes_value(true)
+                            .required(true)
+                            .help("Public key, in hex, of the delegate"),
+                    )
+                    .arg(
+                        Arg::with_name("delegator")
+                            .long("delegator")
+                            .takes_value(true)
+                            .help("Public key, in hex, of the delegator (defaults to the signing key)"),
+                    ),
             ),
     );
 
+    #[cfg(feature = "circuit-schema-migration")]
+    let circuit_command =
+        circuit_command.subcommand(
+            SubCommand::with_name("schema")
+                .about("Report and migrate the schema versions of stored circuits")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("status")
+                        .about("Report the schema versions of the node's stored circuits")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .takes_value(true)
+                                .help("URL of Splinter Daemon"),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Path to private key file"),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "json"])
+                                .default_value("human")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("migrate")
+                        .about("Migrate stored circuits to the current schema version")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .takes_value(true)
+                                .help("URL of Splinter Daemon"),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Path to private key file"),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "json"])
+                                .default_value("human")
+                                .takes_value(true),
+                        )
+                        .arg(Arg::with_name("dry_run").long("dry-run").help(
+                            "Show the changes the migration would make without applying them",
+                        )),
+                ),
+        );
+
     #[cfg(not(feature = "https-certs"))]
     let cert_generate_subcommand = SubCommand::with_name("generate")
         .long_about(
@@ -794,50 +1264,57 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     app = app.subcommand(circuit_command);
 
+    let registry_build_command = SubCommand::with_name("build")
+        .about("Add a node to a YAML file")
+        .arg(Arg::with_name("file").long("file").takes_value(true).help(
+            "Path of registry file to add node to; defaults to \
+                            './nodes.yaml'",
+        ))
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .help("Overwrite node if it already exists"),
+        )
+        .arg(
+            Arg::with_name("status_url")
+                .takes_value(true)
+                .help("URL of splinter REST API to query for node data"),
+        )
+        .arg(
+            Arg::with_name("key_files")
+                .long("key-file")
+                .takes_value(true)
+                .multiple(true)
+                .required(true)
+                .help("Path of public key file to include with node"),
+        )
+        .arg(
+            Arg::with_name("metadata")
+                .long("metadata")
+                .takes_value(true)
+                .multiple(true)
+                .help("Metadata to include with node (<key>=<value>)"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Name or path of private key"),
+        );
+
+    #[cfg(feature = "registry-node-signing")]
+    let registry_build_command =
+        registry_build_command.arg(Arg::with_name("sign").long("sign").help(
+            "Sign the node entry with the private key; the corresponding public key must be \
+             one of the node's keys",
+        ));
+
     let registry_command = SubCommand::with_name("registry")
         .about("Splinter registry commands")
         .setting(AppSettings::SubcommandRequiredElseHelp)
-        .subcommand(
-            SubCommand::with_name("build")
-                .about("Add a node to a YAML file")
-                .arg(Arg::with_name("file").long("file").takes_value(true).help(
-                    "Path of registry file to add node to; defaults to \
-                                './nodes.yaml'",
-                ))
-                .arg(
-                    Arg::with_name("force")
-                        .long("force")
-                        .help("Overwrite node if it already exists"),
-                )
-                .arg(
-                    Arg::with_name("status_url")
-                        .takes_value(true)
-                        .help("URL of splinter REST API to query for node data"),
-                )
-                .arg(
-                    Arg::with_name("key_files")
-                        .long("key-file")
-                        .takes_value(true)
-                        .multiple(true)
-                        .required(true)
-                        .help("Path of public key file to include with node"),
-                )
-                .arg(
-                    Arg::with_name("metadata")
-                        .long("metadata")
-                        .takes_value(true)
-                        .multiple(true)
-                        .help("Metadata to include with node (<key>=<value>)"),
-                )
-                .arg(
-                    Arg::with_name("private_key_file")
-                        .value_name("private-key-file")
-                        .short("k")
-                        .long("key")
-                        .takes_value(true)
-                        .help("Name or path of private key"),
-                ),
-        );
+        .subcommand(registry_build_command);
 
     #[cfg(feature = "registry")]
     let registry_command = registry_command.subcommand(
@@ -905,106 +1382,629 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    #[cfg(feature = "registry-update")]
+    let registry_command = registry_command.subcommand(
+        SubCommand::with_name("delete")
+            .about("Remove a node from the local registry")
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .help("Show the node that would be deleted without deleting it"),
+            )
+            .arg(
+                Arg::with_name("identity")
+                    .required(true)
+                    .help("Identity of the node to delete"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key to be used for REST API authorization"),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of the splinter REST API"),
+            ),
+    );
+
+    #[cfg(feature = "registry-update")]
+    let registry_command = registry_command.subcommand(
+        SubCommand::with_name("update")
+            .about("Change a node in the local registry")
+            .arg(
+                Arg::with_name("add_endpoint")
+                    .long("add-endpoint")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Network endpoint to add to the node"),
+            )
+            .arg(
+                Arg::with_name("add_key_files")
+                    .long("add-key-file")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Path of public key file to add to the node"),
+            )
+            .arg(
+                Arg::with_name("display_name")
+                    .long("display-name")
+                    .takes_value(true)
+                    .help("New human-readable name for the node"),
+            )
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .help("Show the updated node without submitting it"),
+            )
+            .arg(
+                Arg::with_name("endpoint")
+                    .long("endpoint")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Network endpoint that replaces all of the node's endpoints"),
+            )
+            .arg(
+                Arg::with_name("identity")
+                    .required(true)
+                    .help("Identity of the node to update"),
+            )
+            .arg(
+                Arg::with_name("key_files")
+                    .long("key-file")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Path of public key file that replaces all of the node's keys"),
+            )
+            .arg(
+                Arg::with_name("metadata")
+                    .long("metadata")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Metadata to add or replace on the node (<key>:<value>)"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key to be used for REST API authorization"),
+            )
+            .arg(
+                Arg::with_name("remove_endpoint")
+                    .long("remove-endpoint")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Network endpoint to remove from the node"),
+            )
+            .arg(
+                Arg::with_name("remove_key_files")
+                    .long("remove-key-file")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Path of public key file to remove from the node"),
+            )
+            .arg(
+                Arg::with_name("remove_metadata")
+                    .long("remove-metadata")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("Metadata key to remove from the node"),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of the splinter REST API"),
+            ),
+    );
+
+    #[cfg(feature = "registry-import")]
+    let registry_command = registry_command.subcommand(
+        SubCommand::with_name("import")
+            .about("Add or update many nodes in the local registry in a single transaction")
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .help("Show the nodes that would be imported without submitting them"),
+            )
+            .arg(
+                Arg::with_name("file")
+                    .required(true)
+                    .help("Path of a YAML registry file containing the nodes to import"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key to be used for REST API authorization"),
+            )
+            .arg(
+                Arg::with_name("update")
+                    .long("update")
+                    .help("Replace existing nodes instead of adding new ones"),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of the splinter REST API"),
+            ),
+    );
+
+    #[cfg(feature = "registry-refresh")]
+    let registry_command = registry_command.subcommand(
+        SubCommand::with_name("refresh")
+            .about("Force the node to immediately re-fetch its remote registries")
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key to be used for REST API authorization"),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of the splinter REST API"),
+            ),
+    );
+
+    #[cfg(feature = "registry-sources")]
+    let registry_command = registry_command.subcommand(
+        SubCommand::with_name("source")
+            .about("Manage the remote registries of a node")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("add")
+                    .about("Add a remote registry to a node and fetch it")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL of the splinter REST API"),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help(
+                                "Name or path of private key to be used for REST API authorization",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("source_url")
+                            .value_name("source-url")
+                            .takes_value(true)
+                            .required(true)
+                            .help("HTTP(S) URL of the remote registry file"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("list")
+                    .about("List the remote registries of a node and their status")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL of the splinter REST API"),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help(
+                                "Name or path of private key to be used for REST API authorization",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("format")
+                            .short("F")
+                            .long("format")
+                            .help("Output format")
+                            .possible_values(&["human", "json"])
+                            .default_value("human")
+                            .takes_value(true),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("remove")
+                    .about("Remove a remote registry that was added to a node")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .help("URL of the splinter REST API"),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help(
+                                "Name or path of private key to be used for REST API authorization",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("source_url")
+                            .value_name("source-url")
+                            .takes_value(true)
+                            .required(true)
+                            .help("URL of the remote registry file to remove"),
+                    ),
+            ),
+    );
+
     app = app.subcommand(registry_command);
 
     #[cfg(feature = "database")]
     {
-        app = app.subcommand(
-            SubCommand::with_name("database")
-                .about("Database commands")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("migrate")
-                        .about("Runs database migrations Splinter")
-                        .arg(
-                            Arg::with_name("connect")
-                                .short("C")
-                                .takes_value(true)
-                                .help("Database connection URI"),
-                        ),
+        let database_command = SubCommand::with_name("database")
+            .about("Database commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("migrate")
+                    .about("Runs database migrations Splinter")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    ),
+            );
+
+        #[cfg(feature = "database-reset")]
+        let database_command = database_command.subcommand(
+            SubCommand::with_name("reset")
+                .about("Removes all data from the Splinter database")
+                .arg(
+                    Arg::with_name("connect")
+                        .short("C")
+                        .takes_value(true)
+                        .help("Database connection URI"),
+                )
+                .arg(
+                    Arg::with_name("yes")
+                        .short("y")
+                        .long("yes")
+                        .help("Confirm that all data in the database should be deleted"),
+                )
+                .arg(
+                    Arg::with_name("force")
+                        .short("f")
+                        .long("force")
+                        .help("Reset the database even if it contains active circuits"),
+                )
+                .arg(
+                    Arg::with_name("remove_scabbard_state")
+                        .long("remove-scabbard-state")
+                        .help("Also remove the state of the node's scabbard services"),
+                )
+                .arg(
+                    Arg::with_name("state_dir")
+                        .long("state-dir")
+                        .long_help(
+                            "The location of the state directory for scabbard's LMDB files. \
+                            Defaults to /var/lib/splinter. This location can also be \
+                            changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                            environment variables",
+                        )
+                        .takes_value(true)
+                        .requires("remove_scabbard_state"),
                 ),
         );
 
-        app = app.subcommand(
-            SubCommand::with_name("state")
-                .about("Commands to manage scabbard state")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("migrate")
-                        .about(
-                            "Move scabbard state to or from LMDB, deleting from the \
-                            input database",
-                        )
-                        .arg(
-                            Arg::with_name("in")
-                                .long("in")
-                                .help(
-                                    "Database URI that currently contains the scabbard state. If \
-                                    state is in individual LMDB files, provide `lmdb`",
-                                )
-                                .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("out")
-                                .long("out")
-                                .help(
-                                    "The database URI the scabbard state should end up in. \
+        #[cfg(feature = "database-vacuum")]
+        let database_command = database_command.subcommand(
+            SubCommand::with_name("vacuum")
+                .about("Removes old admin service events and reclaims unused database space")
+                .arg(
+                    Arg::with_name("connect")
+                        .short("C")
+                        .takes_value(true)
+                        .help("Database connection URI"),
+                )
+                .arg(
+                    Arg::with_name("max_age")
+                        .long("max-age")
+                        .value_name("DAYS")
+                        .takes_value(true)
+                        .help("Remove admin service events older than this number of days"),
+                )
+                .arg(
+                    Arg::with_name("max_count")
+                        .long("max-count")
+                        .value_name("COUNT")
+                        .takes_value(true)
+                        .help("Keep at most this number of the newest admin service events"),
+                ),
+        );
+
+        #[cfg(feature = "database-gc")]
+        let database_command = database_command.subcommand(
+            SubCommand::with_name("gc")
+                .about("Removes circuits that were purged but kept as tombstones")
+                .arg(
+                    Arg::with_name("connect")
+                        .short("C")
+                        .takes_value(true)
+                        .help("Database connection URI"),
+                )
+                .arg(
+                    Arg::with_name("older_than")
+                        .long("older-than")
+                        .value_name("DAYS")
+                        .takes_value(true)
+                        .help("Only remove circuits purged more than this number of days ago"),
+                ),
+        );
+
+        app = app.subcommand(database_command);
+
+        let state_command = SubCommand::with_name("state")
+            .about("Commands to manage scabbard state")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("migrate")
+                    .about(
+                        "Move scabbard state to or from LMDB, deleting from the \
+                            input database",
+                    )
+                    .arg(
+                        Arg::with_name("in")
+                            .long("in")
+                            .help(
+                                "Database URI that currently contains the scabbard state. If \
+                                    state is in individual LMDB files, provide `lmdb`",
+                            )
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("out")
+                            .long("out")
+                            .help(
+                                "The database URI the scabbard state should end up in. \
                                     If state should be put into individual LMDB files, provide \
                                     `lmdb`",
-                                )
-                                .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("state_dir")
-                                .long("state-dir")
-                                .long_help(
-                                    "The location of the state directory for the LMDB files. \
+                            )
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("state_dir")
+                            .long("state-dir")
+                            .long_help(
+                                "The location of the state directory for the LMDB files. \
                                     Defaults to /var/lib/splinter. This location can also be \
                                     changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
                                     environment variables",
-                                )
-                                .takes_value(true),
-                        )
-                        .arg(Arg::with_name("force").short("f").long("force").help(
-                            "Always attempt to move state, regardless of if there is \
+                            )
+                            .takes_value(true),
+                    )
+                    .arg(Arg::with_name("force").short("f").long("force").help(
+                        "Always attempt to move state, regardless of if there is \
                                     existing data in the out database",
-                        ))
-                        .arg(
-                            Arg::with_name("yes")
-                                .short("y")
-                                .long("yes")
-                                .help("Do not prompt for confirmation"),
-                        )
-                        .arg(Arg::with_name("dry_run").long("dry-run").long_help(
-                            "Check that the in and out databases are available and that \
+                    ))
+                    .arg(
+                        Arg::with_name("yes")
+                            .short("y")
+                            .long("yes")
+                            .help("Do not prompt for confirmation"),
+                    )
+                    .arg(Arg::with_name("dry_run").long("dry-run").long_help(
+                        "Check that the in and out databases are available and that \
                             the in database has a commit hash. The command will not \
                             attempt to move the state",
-                        )),
-                ),
-        );
+                    )),
+            );
+
+        #[cfg(feature = "state-export")]
+        let state_command = state_command
+            .subcommand(
+                SubCommand::with_name("export")
+                    .about("Export the scabbard state of the local node's services to a file")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    )
+                    .arg(
+                        Arg::with_name("lmdb")
+                            .long("lmdb")
+                            .help("Read scabbard state from individual LMDB files"),
+                    )
+                    .arg(
+                        Arg::with_name("state_dir")
+                            .long("state-dir")
+                            .long_help(
+                                "The location of the state directory for the LMDB files. \
+                                Defaults to /var/lib/splinter. This location can also be \
+                                changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                                environment variables",
+                            )
+                            .takes_value(true)
+                            .requires("lmdb"),
+                    )
+                    .arg(
+                        Arg::with_name("output")
+                            .short("o")
+                            .long("output")
+                            .value_name("file")
+                            .required(true)
+                            .takes_value(true)
+                            .help("File to write the exported state to"),
+                    )
+                    .arg(
+                        Arg::with_name("circuit")
+                            .long("circuit")
+                            .value_name("circuit-id")
+                            .takes_value(true)
+                            .help("Only export the state of services on the given circuit"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("import")
+                    .about("Import scabbard state from a file created by `state export`")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    )
+                    .arg(
+                        Arg::with_name("lmdb")
+                            .long("lmdb")
+                            .help("Write scabbard state to individual LMDB files"),
+                    )
+                    .arg(
+                        Arg::with_name("state_dir")
+                            .long("state-dir")
+                            .long_help(
+                                "The location of the state directory for the LMDB files. \
+                                Defaults to /var/lib/splinter. This location can also be \
+                                changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                                environment variables",
+                            )
+                            .takes_value(true)
+                            .requires("lmdb"),
+                    )
+                    .arg(
+                        Arg::with_name("input")
+                            .short("i")
+                            .long("input")
+                            .value_name("file")
+                            .required(true)
+                            .takes_value(true)
+                            .help("File containing the exported state"),
+                    )
+                    .arg(Arg::with_name("force").short("f").long("force").help(
+                        "Import state even if a tree or a different commit hash already \
+                        exists for a service",
+                    )),
+            );
+
+        app = app.subcommand(state_command);
     }
 
     #[cfg(feature = "upgrade")]
     {
-        app = app.subcommand(
-            SubCommand::with_name("upgrade")
-                .about("Upgrade splinter by importing file system state files to a database")
+        let upgrade_command = SubCommand::with_name("upgrade")
+            .about("Upgrade splinter by importing file system state files to a database")
+            .arg(
+                Arg::with_name("state_dir")
+                    .value_name("state-dir")
+                    .long("state-dir")
+                    .short("S")
+                    .takes_value(true)
+                    .help("State directory for splinterd"),
+            )
+            .arg(
+                Arg::with_name("connect")
+                    .short("C")
+                    .long("connect")
+                    .takes_value(true)
+                    .help("Database connection URI"),
+            );
+
+        #[cfg(feature = "upgrade-assistant")]
+        let upgrade_command = upgrade_command
+            .arg(
+                Arg::with_name("plan")
+                    .long("plan")
+                    .help("List the upgrade steps required by the node without running them"),
+            )
+            .arg(
+                Arg::with_name("report")
+                    .long("report")
+                    .value_name("file")
+                    .takes_value(true)
+                    .help("Write a YAML report of the detected state and upgrade steps to a file"),
+            );
+
+        app = app.subcommand(upgrade_command);
+    }
+
+    #[cfg(any(feature = "health-service", feature = "metrics-snapshot"))]
+    {
+        let health_command = SubCommand::with_name("health")
+            .about("Node health commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp);
+
+        #[cfg(feature = "health-service")]
+        let health_command = health_command.subcommand(
+            SubCommand::with_name("status")
+                .about(
+                    "Displays whether a Splinter node is live and ready, and the results of \
+                     its most recent health checks",
+                )
                 .arg(
-                    Arg::with_name("state_dir")
-                        .value_name("state-dir")
-                        .long("state-dir")
-                        .short("S")
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
                         .takes_value(true)
-                        .help("State directory for splinterd"),
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        #[cfg(feature = "metrics-snapshot")]
+        let health_command = health_command.subcommand(
+            SubCommand::with_name("metrics")
+                .about(
+                    "Displays key indicators from a Splinter node's metrics, such as peer \
+                     count, queue depths, proposal backlog and scabbard commit latency",
                 )
                 .arg(
-                    Arg::with_name("connect")
-                        .short("C")
-                        .long("connect")
+                    Arg::with_name("all")
+                        .long("all")
+                        .help("Display every metric instead of the key indicators"),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
                         .takes_value(true)
-                        .help("Database connection URI"),
+                        .help("Name or path of private key"),
                 ),
         );
+
+        app = app.subcommand(health_command);
     }
 
     #[cfg(feature = "authorization-handler-maintenance")]
@@ -1073,234 +2073,525 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         )
     }
 
-    #[cfg(feature = "authorization-handler-rbac")]
+    #[cfg(any(feature = "node-metadata", feature = "state-dir-health"))]
     {
-        app = app.subcommand(
-            SubCommand::with_name("role")
-                .about("Role-based authorization role-related commands")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("list")
-                        .about("Lists the available roles for a Splinter node")
-                        .arg(
-                            Arg::with_name("url")
-                                .short("U")
-                                .long("url")
-                                .help("URL of the Splinter daemon REST API")
-                                .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("private_key_file")
-                                .value_name("private-key-file")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        )
-                        .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "csv"])
-                                .default_value("human")
-                                .takes_value(true),
+        let node_command = SubCommand::with_name("node")
+            .about("Node-related commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp);
+
+        #[cfg(feature = "state-dir-health")]
+        let node_command = node_command.subcommand(
+            SubCommand::with_name("status")
+                .about(
+                    "Displays a Splinter node's status, including the disk usage of its \
+                     state directory",
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        #[cfg(feature = "node-metadata")]
+        let node_command = node_command.subcommand(
+            SubCommand::with_name("set")
+                .about("Updates a Splinter node's display name and/or metadata")
+                .arg(
+                    Arg::with_name("display_name")
+                        .long("display-name")
+                        .takes_value(true)
+                        .help("New display name for the node"),
+                )
+                .arg(
+                    Arg::with_name("metadata")
+                        .long("metadata")
+                        .value_name("KEY=VALUE")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help(
+                            "Metadata for the node, as a 'key=value' pair; replaces all existing \
+                             metadata (may be specified multiple times)",
                         ),
                 )
-                .subcommand(
-                    SubCommand::with_name("show")
-                        .about("Show a specific role for a Splinter node")
-                        .arg(
-                            Arg::with_name("url")
-                                .short("U")
-                                .long("url")
-                                .help("URL of the Splinter daemon REST API")
-                                .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("private_key_file")
-                                .value_name("private-key-file")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        )
-                        .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "json", "yaml"])
-                                .default_value("human")
-                                .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("role_id")
-                                .required(true)
-                                .takes_value(true)
-                                .value_name("ROLE ID")
-                                .help("ID of role to be shown"),
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        app = app.subcommand(node_command);
+    }
+
+    #[cfg(any(
+        feature = "peer-disconnect",
+        feature = "peer-endpoint-pin",
+        feature = "peer-ping"
+    ))]
+    {
+        let peer_command = SubCommand::with_name("peer")
+            .about("Peer-related commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp);
+
+        #[cfg(feature = "peer-disconnect")]
+        let peer_command = peer_command.subcommand(
+            SubCommand::with_name("disconnect")
+                .about("Closes all connections to a peer, optionally banning it from reconnecting")
+                .arg(
+                    Arg::with_name("peer_id")
+                        .value_name("peer-id")
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Node ID of the peer, or 'public_key::<hex>' for peers using \
+                             challenge authorization",
                         ),
                 )
-                .subcommand(
-                    SubCommand::with_name("create")
-                        .about("Create a new role for a Splinter node")
-                        .arg(
-                            Arg::with_name("url")
-                                .short("U")
-                                .long("url")
-                                .help("URL of the Splinter daemon REST API")
-                                .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("private_key_file")
-                                .value_name("private-key-file")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        )
-                        .arg(
-                            Arg::with_name("display_name")
-                                .value_name("display-name")
-                                .short("D")
-                                .long("display")
-                                .takes_value(true)
-                                .required(true)
-                                .help("Display name of the role"),
-                        )
-                        .arg(
-                            Arg::with_name("permission")
-                                .value_name("permission")
-                                .short("P")
-                                .long("permission")
-                                .takes_value(true)
-                                .multiple(true)
-                                .number_of_values(1)
-                                .required(true)
-                                .help("A permission allowed by the role"),
-                        )
-                        .arg(
-                            Arg::with_name("role_id")
-                                .required(true)
-                                .takes_value(true)
-                                .value_name("ROLE ID")
-                                .help("ID of role to be created"),
-                        )
-                        .arg(
-                            Arg::with_name("dry_run")
-                                .long("dry-run")
-                                .short("n")
-                                .help("Validate the command without performing the role creation"),
+                .arg(
+                    Arg::with_name("ban")
+                        .long("ban")
+                        .value_name("seconds")
+                        .takes_value(true)
+                        .help("Number of seconds to prevent the peer from reconnecting"),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        #[cfg(feature = "peer-endpoint-pin")]
+        let peer_command = peer_command.subcommand(
+            SubCommand::with_name("pin")
+                .about("Forces all outbound connections to a peer to use the given endpoint")
+                .arg(
+                    Arg::with_name("peer_id")
+                        .value_name("peer-id")
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Node ID of the peer, or 'public_key::<hex>' for peers using \
+                             challenge authorization",
                         ),
                 )
+                .arg(
+                    Arg::with_name("endpoint")
+                        .value_name("endpoint")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Endpoint to connect to the peer on, e.g. tcps://10.8.0.2:8044"),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        #[cfg(feature = "peer-endpoint-pin")]
+        let peer_command = peer_command.subcommand(
+            SubCommand::with_name("unpin")
+                .about("Allows any of a peer's endpoints to be used for outbound connections")
+                .arg(
+                    Arg::with_name("peer_id")
+                        .value_name("peer-id")
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Node ID of the peer, or 'public_key::<hex>' for peers using \
+                             challenge authorization",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        #[cfg(feature = "peer-ping")]
+        let peer_command = peer_command.subcommand(
+            SubCommand::with_name("ping")
+                .about("Measures the round-trip time from the Splinter node to a peer")
+                .arg(
+                    Arg::with_name("peer_id")
+                        .value_name("peer-id")
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Node ID of the peer, or 'public_key::<hex>' for peers using \
+                             challenge authorization",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("count")
+                        .short("c")
+                        .long("count")
+                        .value_name("count")
+                        .takes_value(true)
+                        .help("Number of pings to send, one per second (default: 1)"),
+                )
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .value_name("seconds")
+                        .takes_value(true)
+                        .help("Number of seconds to wait for each reply (default: 5)"),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ),
+        );
+
+        app = app.subcommand(peer_command);
+    }
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    let create_role = SubCommand::with_name("create")
+        .about("Create a new role for a Splinter node")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .help("URL of the Splinter daemon REST API")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Name or path of private key"),
+        )
+        .arg(
+            Arg::with_name("display_name")
+                .value_name("display-name")
+                .short("D")
+                .long("display")
+                .takes_value(true)
+                .required_unless("template")
+                .help("Display name of the role"),
+        )
+        .arg(
+            Arg::with_name("permission")
+                .value_name("permission")
+                .short("P")
+                .long("permission")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .required_unless("template")
+                .help("A permission allowed by the role"),
+        )
+        .arg(
+            Arg::with_name("role_id")
+                .required(true)
+                .takes_value(true)
+                .value_name("ROLE ID")
+                .help("ID of role to be created"),
+        )
+        .arg(
+            Arg::with_name("dry_run")
+                .long("dry-run")
+                .short("n")
+                .help("Validate the command without performing the role creation"),
+        );
+
+    #[cfg(feature = "role-templates")]
+    let create_role = create_role.arg(
+        Arg::with_name("template")
+            .long("from-template")
+            .value_name("template")
+            .takes_value(true)
+            .help(
+                "Grant the permissions of a built-in role template, skipping any that the node \
+                 does not offer",
+            ),
+    );
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    {
+        let role_command = SubCommand::with_name("role")
+            .about("Role-based authorization role-related commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("list")
+                    .about("Lists the available roles for a Splinter node")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    )
+                    .arg(
+                        Arg::with_name("format")
+                            .short("F")
+                            .long("format")
+                            .help("Output format")
+                            .possible_values(&["human", "csv"])
+                            .default_value("human")
+                            .takes_value(true),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("show")
+                    .about("Show a specific role for a Splinter node")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    )
+                    .arg(
+                        Arg::with_name("format")
+                            .short("F")
+                            .long("format")
+                            .help("Output format")
+                            .possible_values(&["human", "json", "yaml"])
+                            .default_value("human")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("role_id")
+                            .required(true)
+                            .takes_value(true)
+                            .value_name("ROLE ID")
+                            .help("ID of role to be shown"),
+                    ),
+            )
+            .subcommand(create_role)
+            .subcommand(
+                SubCommand::with_name("update")
+                    .about("Update a specific role on a Splinter node")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    )
+                    .arg(
+                        Arg::with_name("display_name")
+                            .value_name("display-name")
+                            .short("D")
+                            .long("display")
+                            .takes_value(true)
+                            .help("Display name of the role"),
+                    )
+                    .arg(
+                        Arg::with_name("add_permission")
+                            .value_name("permission")
+                            .long("add-perm")
+                            .takes_value(true)
+                            .multiple(true)
+                            .number_of_values(1)
+                            .help("A permission to be added to the role"),
+                    )
+                    .arg(
+                        Arg::with_name("rm_permission")
+                            .value_name("permission")
+                            .long("rm-perm")
+                            .takes_value(true)
+                            .multiple(true)
+                            .number_of_values(1)
+                            .conflicts_with("rm_all")
+                            .help("A permission to be removed from the role"),
+                    )
+                    .arg(
+                        Arg::with_name("rm_all")
+                            .long("rm-all")
+                            .conflicts_with("rm_permission")
+                            .help(
+                                "Remove all of the permissions currently associated with the \
+                                    role",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("force")
+                            .short("f")
+                            .long("force")
+                            .help("Ignore errors, such as adding and removing the same value"),
+                    )
+                    .arg(
+                        Arg::with_name("role_id")
+                            .required(true)
+                            .takes_value(true)
+                            .value_name("ROLE ID")
+                            .help("ID of role to be updated"),
+                    )
+                    .arg(
+                        Arg::with_name("dry_run")
+                            .long("dry-run")
+                            .short("n")
+                            .help("Validate the command without performing the role update"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("delete")
+                    .about("Delete a specific role from a Splinter node")
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    )
+                    .arg(
+                        Arg::with_name("role_id")
+                            .required(true)
+                            .takes_value(true)
+                            .value_name("ROLE ID")
+                            .help("ID of role to be deleted"),
+                    )
+                    .arg(
+                        Arg::with_name("dry_run")
+                            .long("dry-run")
+                            .short("n")
+                            .help("Validate the command without performing the role deletion"),
+                    ),
+            );
+
+        #[cfg(feature = "role-templates")]
+        let role_command = role_command.subcommand(
+            SubCommand::with_name("template")
+                .about("Built-in role template commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
                 .subcommand(
-                    SubCommand::with_name("update")
-                        .about("Update a specific role on a Splinter node")
+                    SubCommand::with_name("list")
+                        .about("Lists the built-in role templates")
                         .arg(
-                            Arg::with_name("url")
-                                .short("U")
-                                .long("url")
-                                .help("URL of the Splinter daemon REST API")
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "csv"])
+                                .default_value("human")
                                 .takes_value(true),
-                        )
-                        .arg(
-                            Arg::with_name("private_key_file")
-                                .value_name("private-key-file")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        )
-                        .arg(
-                            Arg::with_name("display_name")
-                                .value_name("display-name")
-                                .short("D")
-                                .long("display")
-                                .takes_value(true)
-                                .help("Display name of the role"),
-                        )
-                        .arg(
-                            Arg::with_name("add_permission")
-                                .value_name("permission")
-                                .long("add-perm")
-                                .takes_value(true)
-                                .multiple(true)
-                                .number_of_values(1)
-                                .help("A permission to be added to the role"),
-                        )
-                        .arg(
-                            Arg::with_name("rm_permission")
-                                .value_name("permission")
-                                .long("rm-perm")
-                                .takes_value(true)
-                                .multiple(true)
-                                .number_of_values(1)
-                                .conflicts_with("rm_all")
-                                .help("A permission to be removed from the role"),
-                        )
-                        .arg(
-                            Arg::with_name("rm_all")
-                                .long("rm-all")
-                                .conflicts_with("rm_permission")
-                                .help(
-                                    "Remove all of the permissions currently associated with the \
-                                    role",
-                                ),
-                        )
-                        .arg(
-                            Arg::with_name("force")
-                                .short("f")
-                                .long("force")
-                                .help("Ignore errors, such as adding and removing the same value"),
-                        )
-                        .arg(
-                            Arg::with_name("role_id")
-                                .required(true)
-                                .takes_value(true)
-                                .value_name("ROLE ID")
-                                .help("ID of role to be updated"),
-                        )
-                        .arg(
-                            Arg::with_name("dry_run")
-                                .long("dry-run")
-                                .short("n")
-                                .help("Validate the command without performing the role update"),
                         ),
                 )
                 .subcommand(
-                    SubCommand::with_name("delete")
-                        .about("Delete a specific role from a Splinter node")
+                    SubCommand::with_name("show")
+                        .about("Shows the permissions granted by a built-in role template")
                         .arg(
-                            Arg::with_name("url")
-                                .short("U")
-                                .long("url")
-                                .help("URL of the Splinter daemon REST API")
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "json", "yaml"])
+                                .default_value("human")
                                 .takes_value(true),
                         )
                         .arg(
-                            Arg::with_name("private_key_file")
-                                .value_name("private-key-file")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        )
-                        .arg(
-                            Arg::with_name("role_id")
+                            Arg::with_name("template")
                                 .required(true)
                                 .takes_value(true)
-                                .value_name("ROLE ID")
-                                .help("ID of role to be deleted"),
-                        )
-                        .arg(
-                            Arg::with_name("dry_run")
-                                .long("dry-run")
-                                .short("n")
-                                .help("Validate the command without performing the role deletion"),
+                                .value_name("TEMPLATE")
+                                .help("Name of the role template to be shown"),
                         ),
                 ),
-        ).subcommand(
+        );
+
+        app = app.subcommand(role_command).subcommand(
             SubCommand::with_name("authid")
                 .about("Role-based authorization role assignment commands")
                 .setting(AppSettings::SubcommandRequiredElseHelp)
@@ -1491,7 +2782,7 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .number_of_values(1)
                                 .conflicts_with("rm_all")
                                 .help(
-                                    "A role to be removed from the provided identity's assignments"
+                                    "A role to be removed from the provided identity's assignments",
                                 ),
                         )
                         .arg(
@@ -1503,13 +2794,10 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                     identity",
                                 ),
                         )
-                        .arg(
-                            Arg::with_name("dry_run")
-                                .long("dry-run")
-                                .short("n")
-                                .help("Validate the command without updating the identity's \
-                                    authorizations"),
-                        ),
+                        .arg(Arg::with_name("dry_run").long("dry-run").short("n").help(
+                            "Validate the command without updating the identity's \
+                                    authorizations",
+                        )),
                 )
                 .subcommand(
                     SubCommand::with_name("delete")
@@ -1547,14 +2835,11 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .conflicts_with("id_key")
                                 .help("The user identity being deleted"),
                         )
-                        .arg(
-                            Arg::with_name("dry_run")
-                                .long("dry-run")
-                                .short("n")
-                                .help("Validate the command without deleting the identity's \
-                                    authorizations"),
-                        ),
-                )
+                        .arg(Arg::with_name("dry_run").long("dry-run").short("n").help(
+                            "Validate the command without deleting the identity's \
+                                    authorizations",
+                        )),
+                ),
         );
     }
 
@@ -1589,21 +2874,95 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     #[cfg(feature = "user")]
     {
-        app = app.subcommand(
-            SubCommand::with_name("user")
-                .about("Splinter user commands")
+        #[allow(unused_mut)]
+        let mut list_command = SubCommand::with_name("list")
+            .about("List Splinter users, including Biome and OAuth users")
+            .arg(
+                Arg::with_name("format")
+                    .short("F")
+                    .long("format")
+                    .help("Output format")
+                    .possible_values(&["human", "csv"])
+                    .default_value("human")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .help("URL of the Splinter daemon REST API")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key"),
+            );
+
+        #[cfg(feature = "user-deactivation")]
+        {
+            list_command = list_command
+                .arg(
+                    Arg::with_name("active")
+                        .long("active")
+                        .conflicts_with("inactive")
+                        .help("Only list active users"),
+                )
+                .arg(
+                    Arg::with_name("inactive")
+                        .long("inactive")
+                        .help("Only list deactivated users"),
+                );
+        }
+
+        #[allow(unused_mut)]
+        let mut user_command = SubCommand::with_name("user")
+            .about("Splinter user commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(list_command);
+
+        #[cfg(feature = "user-deactivation")]
+        {
+            user_command = user_command
                 .subcommand(
-                    SubCommand::with_name("list")
-                        .about("List Splinter users, including Biome and OAuth users")
+                    SubCommand::with_name("deactivate")
+                        .about("Deactivate a Splinter user, preventing the user from logging in")
                         .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "csv"])
-                                .default_value("human")
+                            Arg::with_name("user_id")
+                                .value_name("user-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("ID of the Biome or OAuth user"),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("reactivate")
+                        .about("Reactivate a deactivated Splinter user")
+                        .arg(
+                            Arg::with_name("user_id")
+                                .value_name("user-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("ID of the Biome or OAuth user"),
+                        )
                         .arg(
                             Arg::with_name("url")
                                 .short("U")
@@ -1619,8 +2978,10 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .takes_value(true)
                                 .help("Name or path of private key"),
                         ),
-                ),
-        );
+                );
+        }
+
+        app = app.subcommand(user_command);
     }
 
     #[cfg(feature = "workload")]
@@ -2046,6 +3407,23 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
     let circuit_command =
         circuit_command.with_command("remove-proposal", circuit::RemoveProposalAction);
 
+    #[cfg(feature = "circuit-vote-delegation")]
+    let circuit_command = circuit_command.with_command(
+        "delegation",
+        SubcommandActions::new()
+            .with_command("add", circuit::delegation::CircuitDelegationAddAction)
+            .with_command("list", circuit::delegation::CircuitDelegationListAction)
+            .with_command("remove", circuit::delegation::CircuitDelegationRemoveAction),
+    );
+
+    #[cfg(feature = "circuit-schema-migration")]
+    let circuit_command = circuit_command.with_command(
+        "schema",
+        SubcommandActions::new()
+            .with_command("status", circuit::schema::CircuitSchemaStatusAction)
+            .with_command("migrate", circuit::schema::CircuitSchemaMigrateAction),
+    );
+
     #[cfg(feature = "circuit-template")]
     let circuit_command = circuit_command.with_command(
         "template",
@@ -2063,20 +3441,55 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
     #[cfg(feature = "registry")]
     let registry_command = registry_command.with_command("add", registry::RegistryAddAction);
 
+    #[cfg(feature = "registry-update")]
+    let registry_command = registry_command
+        .with_command("delete", registry::RegistryDeleteAction)
+        .with_command("update", registry::RegistryUpdateAction);
+
+    #[cfg(feature = "registry-import")]
+    let registry_command = registry_command.with_command("import", registry::RegistryImportAction);
+
+    #[cfg(feature = "registry-refresh")]
+    let registry_command =
+        registry_command.with_command("refresh", registry::RegistryRefreshAction);
+
+    #[cfg(feature = "registry-sources")]
+    let registry_command = registry_command.with_command(
+        "source",
+        SubcommandActions::new()
+            .with_command("add", registry::source::RegistrySourceAddAction)
+            .with_command("list", registry::source::RegistrySourceListAction)
+            .with_command("remove", registry::source::RegistrySourceRemoveAction),
+    );
+
     subcommands = subcommands.with_command("registry", registry_command);
 
     #[cfg(feature = "database")]
     {
         use action::database;
-        subcommands = subcommands.with_command(
-            "database",
-            SubcommandActions::new().with_command("migrate", database::MigrateAction),
-        );
+        let database_command =
+            SubcommandActions::new().with_command("migrate", database::MigrateAction);
 
-        subcommands = subcommands.with_command(
-            "state",
-            SubcommandActions::new().with_command("migrate", database::StateMigrateAction),
-        );
+        #[cfg(feature = "database-gc")]
+        let database_command = database_command.with_command("gc", database::GcAction);
+
+        #[cfg(feature = "database-reset")]
+        let database_command = database_command.with_command("reset", database::ResetAction);
+
+        #[cfg(feature = "database-vacuum")]
+        let database_command = database_command.with_command("vacuum", database::VacuumAction);
+
+        subcommands = subcommands.with_command("database", database_command);
+
+        let state_command =
+            SubcommandActions::new().with_command("migrate", database::StateMigrateAction);
+
+        #[cfg(feature = "state-export")]
+        let state_command = state_command
+            .with_command("export", database::StateExportAction)
+            .with_command("import", database::StateImportAction);
+
+        subcommands = subcommands.with_command("state", state_command);
     }
 
     #[cfg(feature = "upgrade")]
@@ -2085,6 +3498,16 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         subcommands = subcommands.with_command("upgrade", database::UpgradeAction);
     }
 
+    #[cfg(any(feature = "health-service", feature = "metrics-snapshot"))]
+    {
+        use action::health;
+        let health_commands = SubcommandActions::new();
+        #[cfg(feature = "health-service")]
+        let health_commands = health_commands.with_command("status", health::StatusAction);
+        #[cfg(feature = "metrics-snapshot")]
+        let health_commands = health_commands.with_command("metrics", health::MetricsAction);
+        subcommands = subcommands.with_command("health", health_commands);
+    }
     #[cfg(feature = "authorization-handler-maintenance")]
     {
         use action::maintenance;
@@ -2096,19 +3519,52 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .with_command("disable", maintenance::DisableAction),
         )
     }
+    #[cfg(any(feature = "node-metadata", feature = "state-dir-health"))]
+    {
+        use action::node;
+        let node_commands = SubcommandActions::new();
+        #[cfg(feature = "state-dir-health")]
+        let node_commands = node_commands.with_command("status", node::StatusAction);
+        #[cfg(feature = "node-metadata")]
+        let node_commands = node_commands.with_command("set", node::SetAction);
+        subcommands = subcommands.with_command("node", node_commands);
+    }
+    #[cfg(any(
+        feature = "peer-disconnect",
+        feature = "peer-endpoint-pin",
+        feature = "peer-ping"
+    ))]
+    {
+        use action::peer;
+        let peer_commands = SubcommandActions::new();
+        #[cfg(feature = "peer-disconnect")]
+        let peer_commands = peer_commands.with_command("disconnect", peer::DisconnectAction);
+        #[cfg(feature = "peer-endpoint-pin")]
+        let peer_commands = peer_commands
+            .with_command("pin", peer::PinAction)
+            .with_command("unpin", peer::UnpinAction);
+        #[cfg(feature = "peer-ping")]
+        let peer_commands = peer_commands.with_command("ping", peer::PingAction);
+        subcommands = subcommands.with_command("peer", peer_commands);
+    }
     #[cfg(feature = "authorization-handler-rbac")]
     {
         use action::rbac;
+        let role_commands = SubcommandActions::new()
+            .with_command("create", rbac::CreateRoleAction)
+            .with_command("update", rbac::UpdateRoleAction)
+            .with_command("delete", rbac::DeleteRoleAction)
+            .with_command("list", rbac::ListRolesAction)
+            .with_command("show", rbac::ShowRoleAction);
+        #[cfg(feature = "role-templates")]
+        let role_commands = role_commands.with_command(
+            "template",
+            SubcommandActions::new()
+                .with_command("list", rbac::ListRoleTemplatesAction)
+                .with_command("show", rbac::ShowRoleTemplateAction),
+        );
         subcommands = subcommands
-            .with_command(
-                "role",
-                SubcommandActions::new()
-                    .with_command("create", rbac::CreateRoleAction)
-                    .with_command("update", rbac::UpdateRoleAction)
-                    .with_command("delete", rbac::DeleteRoleAction)
-                    .with_command("list", rbac::ListRolesAction)
-                    .with_command("show", rbac::ShowRoleAction),
-            )
+            .with_command("role", role_commands)
             .with_command(
                 "authid",
                 SubcommandActions::new()
@@ -2125,10 +3581,18 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
     #[cfg(feature = "user")]
     {
         use action::user;
-        subcommands = subcommands.with_command(
-            "user",
-            SubcommandActions::new().with_command("list", user::ListSplinterUsersAction),
-        )
+        #[allow(unused_mut)]
+        let mut user_actions =
+            SubcommandActions::new().with_command("list", user::ListSplinterUsersAction);
+
+        #[cfg(feature = "user-deactivation")]
+        {
+            user_actions = user_actions
+                .with_command("deactivate", user::DeactivateSplinterUserAction)
+                .with_command("reactivate", user::ReactivateSplinterUserAction);
+        }
+
+        subcommands = subcommands.with_command("user", user_actions)
     }
 
     #[cfg(feature = "workload")]
@@ -2159,9 +3623,52 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         );
     }
 
+    #[cfg(feature = "profile")]
+    {
+        use action::profile;
+        subcommands = subcommands.with_command(
+            "profile",
+            SubcommandActions::new()
+                .with_command("create", profile::CreateProfileAction)
+                .with_command("list", profile::ListProfilesAction)
+                .with_command("show", profile::ShowProfileAction)
+                .with_command("delete", profile::DeleteProfileAction),
+        );
+
+        // Profile management does not use the active profile, so a missing profile named by
+        // SPLINTER_PROFILE does not prevent it from being created
+        if matches.subcommand_name() != Some("profile") {
+            apply_profile(&matches)?;
+        }
+    }
+
     subcommands.run(Some(&matches))
 }
 
+/// Selects the profile named by `--profile` (or `SPLINTER_PROFILE`) and makes its REST API URL
+/// the default for all actions. Its signing key is picked up by `signing::load_signer`.
+#[cfg(feature = "profile")]
+fn apply_profile(matches: &clap::ArgMatches) -> Result<(), CliError> {
+    // `--profile` is global, so it may have been given to any of the nested subcommands
+    fn profile_arg<'a>(matches: &'a clap::ArgMatches) -> Option<&'a str> {
+        matches
+            .subcommand()
+            .1
+            .and_then(profile_arg)
+            .or_else(|| matches.value_of("profile"))
+    }
+
+    if let Some(name) = profile_arg(matches) {
+        std::env::set_var(profile::SPLINTER_PROFILE_ENV, name);
+    }
+
+    if let Some(url) = profile::active_profile()?.and_then(|profile| profile.url) {
+        std::env::set_var(action::SPLINTER_REST_API_URL_ENV, url);
+    }
+
+    Ok(())
+}
+
 fn main() {
     match run(std::env::args_os()) {
         Ok(_) => {}
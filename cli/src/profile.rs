@@ -0,0 +1,224 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Named CLI profiles, each holding a Splinter REST API URL and signing key.
+//!
+//! Profiles are stored as YAML files in `$HOME/.splinter/profiles`. The active profile is
+//! selected with the global `--profile` option or the `SPLINTER_PROFILE` environment variable.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CliError;
+
+pub const SPLINTER_PROFILE_ENV: &str = "SPLINTER_PROFILE";
+
+const PROFILE_FILE_EXTENSION: &str = "yaml";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// Returns the directory that profiles are stored in.
+pub fn profiles_dir() -> Result<PathBuf, CliError> {
+    let mut path = dirs::home_dir().ok_or_else(|| {
+        CliError::EnvironmentError("Unable to determine the home directory".into())
+    })?;
+    path.push(".splinter");
+    path.push("profiles");
+    Ok(path)
+}
+
+/// Checks that a profile name is non-empty and only contains ASCII alphanumerics, `-` and `_`,
+/// so that it is always a valid file name.
+pub fn validate_name(name: &str) -> Result<(), CliError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(CliError::ActionError(format!(
+            "Invalid profile name '{}': must only contain letters, numbers, '-' and '_'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn profile_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(name).with_extension(PROFILE_FILE_EXTENSION)
+}
+
+/// Loads the profile with the given name from the directory.
+pub fn load_profile(dir: &Path, name: &str) -> Result<Profile, CliError> {
+    validate_name(name)?;
+    let path = profile_path(dir, name);
+    let contents = fs::read_to_string(&path).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => CliError::ActionError(format!("Profile '{}' does not exist", name)),
+        _ => CliError::ActionError(format!(
+            "Unable to read profile '{}' from {}: {}",
+            name,
+            path.display(),
+            err
+        )),
+    })?;
+    let profile: Profile = serde_yaml::from_str(&contents)
+        .map_err(|err| CliError::ActionError(format!("Profile '{}' is invalid: {}", name, err)))?;
+    Ok(Profile {
+        name: name.to_string(),
+        ..profile
+    })
+}
+
+/// Saves the profile to the directory, creating the directory if necessary. An existing profile
+/// with the same name is only replaced if `overwrite` is true.
+pub fn save_profile(dir: &Path, profile: &Profile, overwrite: bool) -> Result<(), CliError> {
+    validate_name(&profile.name)?;
+    let path = profile_path(dir, &profile.name);
+    if path.exists() && !overwrite {
+        return Err(CliError::ActionError(format!(
+            "Profile '{}' already exists; use --force to replace it",
+            profile.name
+        )));
+    }
+
+    fs::create_dir_all(dir).map_err(|err| {
+        CliError::EnvironmentError(format!("Unable to create {}: {}", dir.display(), err))
+    })?;
+    let contents = serde_yaml::to_string(profile)
+        .map_err(|err| CliError::ActionError(format!("Unable to serialize profile: {}", err)))?;
+    fs::write(&path, contents).map_err(|err| {
+        CliError::ActionError(format!("Unable to write {}: {}", path.display(), err))
+    })
+}
+
+/// Lists the profiles in the directory, sorted by name.
+pub fn list_profiles(dir: &Path) -> Result<Vec<Profile>, CliError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => {
+            return Err(CliError::ActionError(format!(
+                "Unable to read {}: {}",
+                dir.display(),
+                err
+            )))
+        }
+    };
+
+    let mut profiles = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .map(|ext| ext == PROFILE_FILE_EXTENSION)
+                .unwrap_or(false)
+        })
+        .filter_map(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(ToOwned::to_owned)
+        })
+        .filter(|name| validate_name(name).is_ok())
+        .map(|name| load_profile(dir, &name))
+        .collect::<Result<Vec<_>, _>>()?;
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(profiles)
+}
+
+/// Removes the profile with the given name from the directory.
+pub fn delete_profile(dir: &Path, name: &str) -> Result<(), CliError> {
+    validate_name(name)?;
+    fs::remove_file(profile_path(dir, name)).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => CliError::ActionError(format!("Profile '{}' does not exist", name)),
+        _ => CliError::ActionError(format!("Unable to delete profile '{}': {}", name, err)),
+    })
+}
+
+/// Loads the profile named by `SPLINTER_PROFILE`, if it is set.
+pub fn active_profile() -> Result<Option<Profile>, CliError> {
+    match std::env::var(SPLINTER_PROFILE_ENV) {
+        Ok(name) if !name.is_empty() => load_profile(&profiles_dir()?, &name).map(Some),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    /// Verifies that profiles can be saved, loaded, listed and deleted, and that an existing
+    /// profile is only replaced when requested.
+    #[test]
+    fn profile_lifecycle() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+
+        let prod = Profile {
+            name: "prod".into(),
+            url: Some("https://prod:8080".into()),
+            key: Some("/keys/prod.priv".into()),
+        };
+        let dev = Profile {
+            name: "dev".into(),
+            url: Some("http://localhost:8080".into()),
+            key: None,
+        };
+
+        save_profile(dir.path(), &prod, false).expect("Failed to save prod");
+        save_profile(dir.path(), &dev, false).expect("Failed to save dev");
+        assert!(save_profile(dir.path(), &prod, false).is_err());
+
+        assert_eq!(load_profile(dir.path(), "prod").unwrap(), prod);
+        assert_eq!(list_profiles(dir.path()).unwrap(), vec![dev.clone(), prod]);
+
+        let dev_update = Profile {
+            key: Some("dev".into()),
+            ..dev
+        };
+        save_profile(dir.path(), &dev_update, true).expect("Failed to replace dev");
+        assert_eq!(load_profile(dir.path(), "dev").unwrap(), dev_update);
+
+        delete_profile(dir.path(), "prod").expect("Failed to delete prod");
+        assert!(load_profile(dir.path(), "prod").is_err());
+        assert!(delete_profile(dir.path(), "prod").is_err());
+    }
+
+    /// Verifies that listing a missing directory returns no profiles.
+    #[test]
+    fn list_missing_dir() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        assert!(list_profiles(&dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Verifies that names which are not valid file names are rejected.
+    #[test]
+    fn invalid_names() {
+        assert!(validate_name("prod-1_a").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../prod").is_err());
+        assert!(validate_name("a b").is_err());
+    }
+}
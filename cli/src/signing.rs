@@ -12,12 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "key-pem")]
+use std::fs;
 use std::{env, path::Path, path::PathBuf};
 
 use cylinder::{
-    current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder, load_key,
-    load_key_from_path, secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
+    current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder,
+    secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
 };
+#[cfg(not(feature = "key-pem"))]
+use cylinder::{load_key, load_key_from_path};
+#[cfg(feature = "key-pem")]
+use splinter::keys::encoding::decode_private_key;
 
 use crate::error::CliError;
 
@@ -40,6 +46,27 @@ fn splinter_user_search_path() -> Vec<PathBuf> {
     }
 }
 
+// Loads the private key stored at the given path, accepting either hex or PEM contents.
+#[cfg(feature = "key-pem")]
+fn load_key_from_path(path: &Path) -> Result<PrivateKey, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read key file {}: {}", path.display(), err))?;
+    decode_private_key(&contents)
+        .map_err(|err| format!("Unable to load key file {}: {}", path.display(), err))
+}
+
+// Loads the private key `<name>.priv` from the first directory in `search_path` that contains it,
+// accepting either hex or PEM contents.
+#[cfg(feature = "key-pem")]
+fn load_key(name: &str, search_path: &[PathBuf]) -> Result<Option<PrivateKey>, String> {
+    search_path
+        .iter()
+        .map(|dir| dir.join(format!("{}.priv", name)))
+        .find(|path| path.is_file())
+        .map(|path| load_key_from_path(&path))
+        .transpose()
+}
+
 fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
     let private_key = if let Some(key_name) = key_name {
         if key_name.contains('/') {
@@ -63,6 +90,13 @@ fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
                 })?
         }
     } else {
+        #[cfg(feature = "profile")]
+        {
+            if let Some(key) = crate::profile::active_profile()?.and_then(|profile| profile.key) {
+                return load_private_key(Some(&key));
+            }
+        }
+
         let path = splinter_user_search_path();
         load_key(&current_user_key_name(), &path)
             .map_err(|err| CliError::ActionError(err.to_string()))?
@@ -58,9 +58,22 @@ pub struct AdminServiceEvent {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum EventType {
     ProposalSubmitted,
-    ProposalVote { requester: PublicKey },
-    ProposalAccepted { requester: PublicKey },
-    ProposalRejected { requester: PublicKey },
+    ProposalVote {
+        requester: PublicKey,
+    },
+    ProposalAccepted {
+        requester: PublicKey,
+    },
+    ProposalRejected {
+        requester: PublicKey,
+    },
+    /// A member node failed to validate the proposed change
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    ProposalInvalid {
+        requester: PublicKey,
+        node_id: String,
+        reason: String,
+    },
     CircuitReady,
     CircuitDisbanded,
 }
@@ -84,12 +97,32 @@ impl AdminServiceEvent {
 
 /// EventQuery represents common event types that can be queried for
 pub enum EventQuery<'a> {
-    ProposalSubmitted { circuit_id: &'a str },
-    ProposalVote { circuit_id: &'a str, key: PublicKey },
-    ProposalAccepted { circuit_id: &'a str, key: PublicKey },
-    ProposalRejected { circuit_id: &'a str, key: PublicKey },
-    CircuitReady { circuit_id: &'a str },
-    CircuitDisbanded { circuit_id: &'a str },
+    ProposalSubmitted {
+        circuit_id: &'a str,
+    },
+    ProposalVote {
+        circuit_id: &'a str,
+        key: PublicKey,
+    },
+    ProposalAccepted {
+        circuit_id: &'a str,
+        key: PublicKey,
+    },
+    ProposalRejected {
+        circuit_id: &'a str,
+        key: PublicKey,
+    },
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    ProposalInvalid {
+        circuit_id: &'a str,
+        key: PublicKey,
+    },
+    CircuitReady {
+        circuit_id: &'a str,
+    },
+    CircuitDisbanded {
+        circuit_id: &'a str,
+    },
 }
 
 impl<'a> EventQuery<'a> {
@@ -118,6 +151,13 @@ impl<'a> EventQuery<'a> {
                 }
                 _ => false,
             },
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            EventQuery::ProposalInvalid { circuit_id, key } => match event.event_type() {
+                EventType::ProposalInvalid { requester, .. } => {
+                    requester == key && &event.proposal().circuit_id == circuit_id
+                }
+                _ => false,
+            },
             EventQuery::CircuitReady { circuit_id } => {
                 event.event_type() == &EventType::CircuitReady
                     && &event.proposal().circuit_id == circuit_id
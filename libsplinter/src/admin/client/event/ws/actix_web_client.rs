@@ -332,6 +332,15 @@ impl ParseBytes<AdminServiceEvent> for AdminServiceEvent {
                     requester: PublicKey(pub_key_bytes),
                 },
             ),
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            ProposalInvalid((proposal, pub_key_bytes, failure)) => (
+                proposal,
+                EventType::ProposalInvalid {
+                    requester: PublicKey(pub_key_bytes),
+                    node_id: failure.node_id,
+                    reason: failure.reason,
+                },
+            ),
             CircuitReady(proposal) => (proposal, EventType::CircuitReady),
             CircuitDisbanded(proposal) => (proposal, EventType::CircuitDisbanded),
         };
@@ -407,6 +416,7 @@ impl From<messages::VoteRecord> for VoteRecord {
             }
             .into(),
             voter_node_id: vote_record.voter_node_id,
+            comment: vote_record.comment,
         }
     }
 }
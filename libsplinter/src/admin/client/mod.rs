@@ -94,6 +94,8 @@ pub struct VoteRecord {
     pub public_key: String,
     pub vote: String,
     pub voter_node_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +19,7 @@ use std::fmt::Write as _;
 use reqwest::{blocking::Client, header, StatusCode};
 
 use crate::error::InternalError;
+use crate::http_client::build_client;
 use crate::rest_api::SPLINTER_PROTOCOL_VERSION;
 
 use super::{AdminServiceClient, CircuitListSlice, CircuitSlice, ProposalListSlice, ProposalSlice};
@@ -33,18 +34,25 @@ struct ServerError {
 pub struct ReqwestAdminServiceClient {
     url: String,
     auth: String,
+    // Shared by every request, so that connections are reused
+    client: Client,
 }
 
 impl ReqwestAdminServiceClient {
     pub fn new(url: String, auth: String) -> Self {
-        ReqwestAdminServiceClient { url, auth }
+        ReqwestAdminServiceClient {
+            url,
+            auth,
+            client: build_client(),
+        }
     }
 }
 
 impl AdminServiceClient for ReqwestAdminServiceClient {
     /// Submits an admin payload to this client's Splinter node.
     fn submit_admin_payload(&self, payload: Vec<u8>) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/admin/submit", self.url))
             .header(header::CONTENT_TYPE, "octet-stream")
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
@@ -93,7 +101,8 @@ impl AdminServiceClient for ReqwestAdminServiceClient {
             }
         }
 
-        let request = Client::new()
+        let request = self
+            .client
             .get(&url)
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
@@ -139,7 +148,8 @@ impl AdminServiceClient for ReqwestAdminServiceClient {
     }
 
     fn fetch_circuit(&self, circuit_id: &str) -> Result<Option<CircuitSlice>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/admin/circuits/{}", self.url, circuit_id))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
@@ -202,7 +212,8 @@ impl AdminServiceClient for ReqwestAdminServiceClient {
             }
         }
 
-        let request = Client::new()
+        let request = self
+            .client
             .get(&url)
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
@@ -244,7 +255,8 @@ impl AdminServiceClient for ReqwestAdminServiceClient {
     }
 
     fn fetch_proposal(&self, circuit_id: &str) -> Result<Option<ProposalSlice>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/admin/proposals/{}", self.url, circuit_id))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
@@ -0,0 +1,295 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Vote delegation records.
+//!
+//! A vote delegation allows a delegate key to vote on circuit proposals in place of a delegator
+//! key, for a single node and a set of circuit management types. A delegation is signed by the
+//! delegator key, so every member of a circuit can check a vote cast by a delegate.
+
+pub mod store;
+
+use std::convert::TryFrom;
+
+use cylinder::{PublicKey as CylinderPublicKey, Signature, VerificationError, Verifier};
+use protobuf::Message;
+
+use crate::error::InvalidArgumentError;
+use crate::protos::admin;
+use crate::public_key::PublicKey;
+
+pub use store::{VoteDelegationStore, VoteDelegationStoreError};
+
+/// Authorizes a delegate key to vote on behalf of a delegator key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoteDelegation {
+    delegator_public_key: PublicKey,
+    delegate_public_key: PublicKey,
+    node_id: String,
+    circuit_management_types: Vec<String>,
+    header: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl VoteDelegation {
+    /// Creates a delegation from a serialized `VoteDelegation_Header` and the delegator key's
+    /// signature of it.
+    ///
+    /// The signature is not checked; see [`VoteDelegation::verify_signature`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the header cannot be parsed, if either key or the
+    /// node ID is missing, if the delegator and delegate keys are the same or if no circuit
+    /// management types are given.
+    pub fn from_signed_header(
+        header: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<Self, InvalidArgumentError> {
+        let mut parsed: admin::VoteDelegation_Header =
+            Message::parse_from_bytes(&header).map_err(|err| {
+                InvalidArgumentError::new(
+                    "header",
+                    format!("unable to parse vote delegation header: {}", err),
+                )
+            })?;
+
+        if parsed.get_delegator_public_key().is_empty() {
+            return Err(InvalidArgumentError::new(
+                "header",
+                "delegator_public_key must be set",
+            ));
+        }
+        if parsed.get_delegate_public_key().is_empty() {
+            return Err(InvalidArgumentError::new(
+                "header",
+                "delegate_public_key must be set",
+            ));
+        }
+        if parsed.get_delegator_public_key() == parsed.get_delegate_public_key() {
+            return Err(InvalidArgumentError::new(
+                "header",
+                "a key cannot delegate its vote to itself",
+            ));
+        }
+        if parsed.get_node_id().is_empty() {
+            return Err(InvalidArgumentError::new("header", "node_id must be set"));
+        }
+        if parsed.get_circuit_management_types().is_empty() {
+            return Err(InvalidArgumentError::new(
+                "header",
+                "at least one circuit management type must be given",
+            ));
+        }
+        if signature.is_empty() {
+            return Err(InvalidArgumentError::new(
+                "signature",
+                "signature must be set",
+            ));
+        }
+
+        Ok(Self {
+            delegator_public_key: PublicKey::from_bytes(parsed.take_delegator_public_key()),
+            delegate_public_key: PublicKey::from_bytes(parsed.take_delegate_public_key()),
+            node_id: parsed.take_node_id(),
+            circuit_management_types: parsed.take_circuit_management_types().into_vec(),
+            header,
+            signature,
+        })
+    }
+
+    /// Returns the public key of the key granting its voting rights.
+    pub fn delegator_public_key(&self) -> &PublicKey {
+        &self.delegator_public_key
+    }
+
+    /// Returns the public key of the key that may vote in the delegator's place.
+    pub fn delegate_public_key(&self) -> &PublicKey {
+        &self.delegate_public_key
+    }
+
+    /// Returns the ID of the node the delegator votes for.
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Returns the circuit management types the delegation applies to.
+    pub fn circuit_management_types(&self) -> &[String] {
+        &self.circuit_management_types
+    }
+
+    /// Returns the serialized header.
+    pub fn header(&self) -> &[u8] {
+        &self.header
+    }
+
+    /// Returns the delegator key's signature of the header.
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Returns true if the delegation allows the given key to vote for the given node on a
+    /// proposal with the given circuit management type.
+    pub fn permits(&self, delegate: &[u8], node_id: &str, circuit_management_type: &str) -> bool {
+        self.delegate_public_key.as_slice() == delegate
+            && self.node_id == node_id
+            && self
+                .circuit_management_types
+                .iter()
+                .any(|management_type| management_type == circuit_management_type)
+    }
+
+    /// Checks that the header was signed by the delegator key.
+    pub fn verify_signature(&self, verifier: &dyn Verifier) -> Result<bool, VerificationError> {
+        verifier.verify(
+            &self.header,
+            &Signature::new(self.signature.clone()),
+            &CylinderPublicKey::new(self.delegator_public_key.as_slice().to_vec()),
+        )
+    }
+}
+
+impl TryFrom<admin::VoteDelegation> for VoteDelegation {
+    type Error = InvalidArgumentError;
+
+    fn try_from(mut delegation: admin::VoteDelegation) -> Result<Self, Self::Error> {
+        Self::from_signed_header(delegation.take_header(), delegation.take_signature())
+    }
+}
+
+impl From<VoteDelegation> for admin::VoteDelegation {
+    fn from(delegation: VoteDelegation) -> Self {
+        let mut proto = admin::VoteDelegation::new();
+        proto.set_header(delegation.header);
+        proto.set_signature(delegation.signature);
+        proto
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+    use protobuf::RepeatedField;
+
+    /// Creates a delegation signed by the given delegator signer.
+    pub(crate) fn signed_delegation(
+        delegator: &dyn Signer,
+        delegate_public_key: &[u8],
+        node_id: &str,
+        circuit_management_types: &[&str],
+    ) -> VoteDelegation {
+        let mut header = admin::VoteDelegation_Header::new();
+        header.set_delegator_public_key(
+            delegator
+                .public_key()
+                .expect("Unable to get public key")
+                .into_bytes(),
+        );
+        header.set_delegate_public_key(delegate_public_key.to_vec());
+        header.set_node_id(node_id.to_string());
+        header.set_circuit_management_types(RepeatedField::from_vec(
+            circuit_management_types
+                .iter()
+                .map(|management_type| management_type.to_string())
+                .collect(),
+        ));
+        let header = header.write_to_bytes().expect("Unable to write header");
+        let signature = delegator
+            .sign(&header)
+            .expect("Unable to sign header")
+            .take_bytes();
+
+        VoteDelegation::from_signed_header(header, signature).expect("Invalid delegation")
+    }
+
+    /// Verify that a delegation only permits the delegate key, for its node and management types.
+    #[test]
+    fn test_permits() {
+        let context = Secp256k1Context::new();
+        let delegator = context.new_signer(context.new_random_private_key());
+        let delegate = context
+            .get_public_key(&context.new_random_private_key())
+            .expect("Unable to get public key")
+            .into_bytes();
+
+        let delegation = signed_delegation(&*delegator, &delegate, "node-a", &["gameroom"]);
+
+        assert!(delegation.permits(&delegate, "node-a", "gameroom"));
+        assert!(!delegation.permits(&delegate, "node-b", "gameroom"));
+        assert!(!delegation.permits(&delegate, "node-a", "other"));
+        assert!(!delegation.permits(
+            delegation.delegator_public_key().as_slice(),
+            "node-a",
+            "gameroom"
+        ));
+    }
+
+    /// Verify that the signature is checked against the delegator key and that a delegation
+    /// round-trips through its protobuf representation.
+    #[test]
+    fn test_verify_signature_and_proto_round_trip() {
+        let context = Secp256k1Context::new();
+        let delegator = context.new_signer(context.new_random_private_key());
+        let delegate = context
+            .get_public_key(&context.new_random_private_key())
+            .expect("Unable to get public key")
+            .into_bytes();
+        let verifier = context.new_verifier();
+
+        let delegation = signed_delegation(&*delegator, &delegate, "node-a", &["gameroom"]);
+        assert!(delegation
+            .verify_signature(&*verifier)
+            .expect("Unable to verify signature"));
+
+        let round_tripped =
+            VoteDelegation::try_from(admin::VoteDelegation::from(delegation.clone()))
+                .expect("Unable to convert delegation");
+        assert_eq!(round_tripped, delegation);
+
+        // a signature made by a different key does not verify
+        let other = context.new_signer(context.new_random_private_key());
+        let forged = VoteDelegation::from_signed_header(
+            delegation.header().to_vec(),
+            other
+                .sign(delegation.header())
+                .expect("Unable to sign header")
+                .take_bytes(),
+        )
+        .expect("Invalid delegation");
+        assert!(!forged
+            .verify_signature(&*verifier)
+            .expect("Unable to verify signature"));
+    }
+
+    /// Verify that a key cannot delegate its vote to itself.
+    #[test]
+    fn test_self_delegation_rejected() {
+        let context = Secp256k1Context::new();
+        let delegator = context.new_signer(context.new_random_private_key());
+        let public_key = delegator
+            .public_key()
+            .expect("Unable to get public key")
+            .into_bytes();
+
+        let mut header = admin::VoteDelegation_Header::new();
+        header.set_delegator_public_key(public_key.clone());
+        header.set_delegate_public_key(public_key);
+        header.set_node_id("node-a".into());
+        header.set_circuit_management_types(RepeatedField::from_vec(vec!["gameroom".into()]));
+        let header = header.write_to_bytes().expect("Unable to write header");
+
+        assert!(VoteDelegation::from_signed_header(header, vec![1]).is_err());
+    }
+}
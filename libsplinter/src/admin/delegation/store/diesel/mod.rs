@@ -0,0 +1,239 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A database-backed [VoteDelegationStore](super::VoteDelegationStore), powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::admin::delegation::VoteDelegation;
+use crate::public_key::PublicKey;
+use crate::store::pool::ConnectionPool;
+
+use super::{VoteDelegationStore, VoteDelegationStoreError};
+
+use operations::{
+    add_delegation::VoteDelegationStoreAddDelegationOperation,
+    list_delegations::VoteDelegationStoreListDelegationsOperation,
+    remove_delegation::VoteDelegationStoreRemoveDelegationOperation, VoteDelegationStoreOperations,
+};
+
+pub struct DieselVoteDelegationStore<C: diesel::Connection + 'static> {
+    pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselVoteDelegationStore<C> {
+    /// Creates a new `DieselVoteDelegationStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: connection pool for the database
+    pub fn new(pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselVoteDelegationStore { pool: pool.into() }
+    }
+
+    /// Create a new `DieselVoteDelegationStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(pool: Arc<RwLock<Pool<ConnectionManager<C>>>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselVoteDelegationStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl VoteDelegationStore for DieselVoteDelegationStore<diesel::pg::PgConnection> {
+    fn add_delegation(&self, delegation: VoteDelegation) -> Result<(), VoteDelegationStoreError> {
+        self.pool.execute_write(|conn| {
+            VoteDelegationStoreOperations::new(conn).add_delegation(delegation)
+        })
+    }
+
+    fn remove_delegation(
+        &self,
+        delegator: &PublicKey,
+        delegate: &PublicKey,
+    ) -> Result<bool, VoteDelegationStoreError> {
+        self.pool.execute_write(|conn| {
+            VoteDelegationStoreOperations::new(conn).remove_delegation(delegator, delegate)
+        })
+    }
+
+    fn list_delegations(
+        &self,
+        delegate: Option<&PublicKey>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = VoteDelegation>>, VoteDelegationStoreError> {
+        self.pool
+            .execute_read(|conn| {
+                VoteDelegationStoreOperations::new(conn).list_delegations(delegate)
+            })
+            .map(|delegations| Box::new(delegations.into_iter()) as Box<_>)
+    }
+
+    fn clone_box(&self) -> Box<dyn VoteDelegationStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl VoteDelegationStore for DieselVoteDelegationStore<diesel::sqlite::SqliteConnection> {
+    fn add_delegation(&self, delegation: VoteDelegation) -> Result<(), VoteDelegationStoreError> {
+        self.pool.execute_write(|conn| {
+            VoteDelegationStoreOperations::new(conn).add_delegation(delegation)
+        })
+    }
+
+    fn remove_delegation(
+        &self,
+        delegator: &PublicKey,
+        delegate: &PublicKey,
+    ) -> Result<bool, VoteDelegationStoreError> {
+        self.pool.execute_write(|conn| {
+            VoteDelegationStoreOperations::new(conn).remove_delegation(delegator, delegate)
+        })
+    }
+
+    fn list_delegations(
+        &self,
+        delegate: Option<&PublicKey>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = VoteDelegation>>, VoteDelegationStoreError> {
+        self.pool
+            .execute_read(|conn| {
+                VoteDelegationStoreOperations::new(conn).list_delegations(delegate)
+            })
+            .map(|delegations| Box::new(delegations.into_iter()) as Box<_>)
+    }
+
+    fn clone_box(&self) -> Box<dyn VoteDelegationStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::admin::delegation::tests::signed_delegation;
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that delegations can be added to a SQLite-backed store, replaced, listed by
+    /// delegate and removed.
+    #[test]
+    fn sqlite_add_list_and_remove_delegations() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselVoteDelegationStore::new(pool);
+
+        let context = Secp256k1Context::new();
+        let delegator = context.new_signer(context.new_random_private_key());
+        let delegate_a = context
+            .get_public_key(&context.new_random_private_key())
+            .expect("Unable to get public key")
+            .into_bytes();
+        let delegate_b = context
+            .get_public_key(&context.new_random_private_key())
+            .expect("Unable to get public key")
+            .into_bytes();
+
+        store
+            .add_delegation(signed_delegation(
+                &*delegator,
+                &delegate_a,
+                "node-a",
+                &["gameroom"],
+            ))
+            .expect("Unable to add delegation");
+        store
+            .add_delegation(signed_delegation(
+                &*delegator,
+                &delegate_b,
+                "node-a",
+                &["gameroom"],
+            ))
+            .expect("Unable to add delegation");
+        // replaces the first delegation
+        let replacement =
+            signed_delegation(&*delegator, &delegate_a, "node-a", &["gameroom", "other"]);
+        store
+            .add_delegation(replacement.clone())
+            .expect("Unable to add delegation");
+
+        assert_eq!(
+            store
+                .list_delegations(None)
+                .expect("Unable to list delegations")
+                .len(),
+            2
+        );
+
+        let delegate_a = PublicKey::from_bytes(delegate_a);
+        let for_delegate = store
+            .list_delegations(Some(&delegate_a))
+            .expect("Unable to list delegations")
+            .collect::<Vec<_>>();
+        assert_eq!(for_delegate, vec![replacement.clone()]);
+
+        assert!(store
+            .remove_delegation(replacement.delegator_public_key(), &delegate_a)
+            .expect("Unable to remove delegation"));
+        assert!(!store
+            .remove_delegation(replacement.delegator_public_key(), &delegate_a)
+            .expect("Unable to remove delegation"));
+        assert_eq!(
+            store
+                .list_delegations(None)
+                .expect("Unable to list delegations")
+                .len(),
+            1
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
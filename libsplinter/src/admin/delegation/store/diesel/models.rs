@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryFrom;
+
+use crate::admin::delegation::{VoteDelegation, VoteDelegationStoreError};
+use crate::error::InternalError;
+
+use super::schema::admin_vote_delegation;
+
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "admin_vote_delegation"]
+pub struct VoteDelegationModel {
+    pub delegator_public_key: Vec<u8>,
+    pub delegate_public_key: Vec<u8>,
+    pub node_id: String,
+    pub header: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl From<VoteDelegation> for VoteDelegationModel {
+    fn from(delegation: VoteDelegation) -> Self {
+        VoteDelegationModel {
+            delegator_public_key: delegation.delegator_public_key.into_bytes(),
+            delegate_public_key: delegation.delegate_public_key.into_bytes(),
+            node_id: delegation.node_id,
+            header: delegation.header,
+            signature: delegation.signature,
+        }
+    }
+}
+
+impl TryFrom<VoteDelegationModel> for VoteDelegation {
+    type Error = VoteDelegationStoreError;
+
+    fn try_from(model: VoteDelegationModel) -> Result<Self, Self::Error> {
+        VoteDelegation::from_signed_header(model.header, model.signature).map_err(|err| {
+            VoteDelegationStoreError::Internal(InternalError::from_source_with_message(
+                Box::new(err),
+                "stored vote delegation is invalid".to_string(),
+            ))
+        })
+    }
+}
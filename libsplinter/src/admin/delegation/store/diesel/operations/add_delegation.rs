@@ -0,0 +1,82 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+
+use crate::admin::delegation::{
+    store::diesel::{models::VoteDelegationModel, schema::admin_vote_delegation},
+    VoteDelegation, VoteDelegationStoreError,
+};
+
+use super::VoteDelegationStoreOperations;
+
+pub(in crate::admin::delegation::store::diesel) trait VoteDelegationStoreAddDelegationOperation {
+    fn add_delegation(&self, delegation: VoteDelegation) -> Result<(), VoteDelegationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> VoteDelegationStoreAddDelegationOperation
+    for VoteDelegationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_delegation(&self, delegation: VoteDelegation) -> Result<(), VoteDelegationStoreError> {
+        let model = VoteDelegationModel::from(delegation);
+        self.conn.transaction::<_, VoteDelegationStoreError, _>(|| {
+            delete(
+                admin_vote_delegation::table
+                    .filter(
+                        admin_vote_delegation::delegator_public_key.eq(&model.delegator_public_key),
+                    )
+                    .filter(
+                        admin_vote_delegation::delegate_public_key.eq(&model.delegate_public_key),
+                    ),
+            )
+            .execute(self.conn)?;
+
+            insert_into(admin_vote_delegation::table)
+                .values(&model)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> VoteDelegationStoreAddDelegationOperation
+    for VoteDelegationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_delegation(&self, delegation: VoteDelegation) -> Result<(), VoteDelegationStoreError> {
+        let model = VoteDelegationModel::from(delegation);
+        self.conn.transaction::<_, VoteDelegationStoreError, _>(|| {
+            delete(
+                admin_vote_delegation::table
+                    .filter(
+                        admin_vote_delegation::delegator_public_key.eq(&model.delegator_public_key),
+                    )
+                    .filter(
+                        admin_vote_delegation::delegate_public_key.eq(&model.delegate_public_key),
+                    ),
+            )
+            .execute(self.conn)?;
+
+            insert_into(admin_vote_delegation::table)
+                .values(&model)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::admin::delegation::{
+    store::diesel::{models::VoteDelegationModel, schema::admin_vote_delegation},
+    VoteDelegation, VoteDelegationStoreError,
+};
+use crate::public_key::PublicKey;
+
+use super::VoteDelegationStoreOperations;
+
+pub(in crate::admin::delegation::store::diesel) trait VoteDelegationStoreListDelegationsOperation {
+    fn list_delegations(
+        &self,
+        delegate: Option<&PublicKey>,
+    ) -> Result<Vec<VoteDelegation>, VoteDelegationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> VoteDelegationStoreListDelegationsOperation
+    for VoteDelegationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_delegations(
+        &self,
+        delegate: Option<&PublicKey>,
+    ) -> Result<Vec<VoteDelegation>, VoteDelegationStoreError> {
+        let mut query = admin_vote_delegation::table.into_boxed::<diesel::pg::Pg>();
+
+        if let Some(delegate) = delegate {
+            query = query.filter(
+                admin_vote_delegation::delegate_public_key.eq(delegate.as_slice().to_vec()),
+            );
+        }
+
+        query
+            .order((
+                admin_vote_delegation::delegator_public_key.asc(),
+                admin_vote_delegation::delegate_public_key.asc(),
+            ))
+            .load::<VoteDelegationModel>(self.conn)?
+            .into_iter()
+            .map(VoteDelegation::try_from)
+            .collect()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> VoteDelegationStoreListDelegationsOperation
+    for VoteDelegationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_delegations(
+        &self,
+        delegate: Option<&PublicKey>,
+    ) -> Result<Vec<VoteDelegation>, VoteDelegationStoreError> {
+        let mut query = admin_vote_delegation::table.into_boxed::<diesel::sqlite::Sqlite>();
+
+        if let Some(delegate) = delegate {
+            query = query.filter(
+                admin_vote_delegation::delegate_public_key.eq(delegate.as_slice().to_vec()),
+            );
+        }
+
+        query
+            .order((
+                admin_vote_delegation::delegator_public_key.asc(),
+                admin_vote_delegation::delegate_public_key.asc(),
+            ))
+            .load::<VoteDelegationModel>(self.conn)?
+            .into_iter()
+            .map(VoteDelegation::try_from)
+            .collect()
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{dsl::delete, prelude::*};
+
+use crate::admin::delegation::{
+    store::diesel::schema::admin_vote_delegation, VoteDelegationStoreError,
+};
+use crate::public_key::PublicKey;
+
+use super::VoteDelegationStoreOperations;
+
+pub(in crate::admin::delegation::store::diesel) trait VoteDelegationStoreRemoveDelegationOperation {
+    fn remove_delegation(
+        &self,
+        delegator: &PublicKey,
+        delegate: &PublicKey,
+    ) -> Result<bool, VoteDelegationStoreError>;
+}
+
+impl<'a, C> VoteDelegationStoreRemoveDelegationOperation for VoteDelegationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    Vec<u8>: diesel::serialize::ToSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn remove_delegation(
+        &self,
+        delegator: &PublicKey,
+        delegate: &PublicKey,
+    ) -> Result<bool, VoteDelegationStoreError> {
+        delete(
+            admin_vote_delegation::table
+                .filter(
+                    admin_vote_delegation::delegator_public_key.eq(delegator.as_slice().to_vec()),
+                )
+                .filter(
+                    admin_vote_delegation::delegate_public_key.eq(delegate.as_slice().to_vec()),
+                ),
+        )
+        .execute(self.conn)
+        .map(|removed| removed > 0)
+        .map_err(VoteDelegationStoreError::from)
+    }
+}
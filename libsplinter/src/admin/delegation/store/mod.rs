@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Storage of vote delegations.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+pub use error::VoteDelegationStoreError;
+
+use crate::public_key::PublicKey;
+
+use super::VoteDelegation;
+
+pub trait VoteDelegationStore: Send + Sync {
+    /// Adds a delegation to the store, replacing any existing delegation between the same
+    /// delegator and delegate keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `delegation` - the delegation to add
+    fn add_delegation(&self, delegation: VoteDelegation) -> Result<(), VoteDelegationStoreError>;
+
+    /// Removes the delegation between the given keys, returning false if there was none.
+    ///
+    /// # Arguments
+    ///
+    /// * `delegator` - the public key that granted its voting rights
+    /// * `delegate` - the public key that was allowed to vote in its place
+    fn remove_delegation(
+        &self,
+        delegator: &PublicKey,
+        delegate: &PublicKey,
+    ) -> Result<bool, VoteDelegationStoreError>;
+
+    /// Lists the delegations in the store, optionally only those granted to the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `delegate` - if set, only delegations to this public key are listed
+    fn list_delegations(
+        &self,
+        delegate: Option<&PublicKey>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = VoteDelegation>>, VoteDelegationStoreError>;
+
+    fn clone_box(&self) -> Box<dyn VoteDelegationStore>;
+}
+
+impl Clone for Box<dyn VoteDelegationStore> {
+    fn clone(&self) -> Box<dyn VoteDelegationStore> {
+        self.clone_box()
+    }
+}
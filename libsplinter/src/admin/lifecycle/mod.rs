@@ -46,6 +46,38 @@ pub trait LifecycleDispatch: Send {
 
     fn shutdown_all_services(&self) -> Result<(), InternalError>;
 
+    /// Returns the number of bytes of persistent state that `purge_service` would remove for the
+    /// given service, if known.
+    ///
+    /// The default implementation reports that the size is unknown.
+    #[cfg(feature = "circuit-purge-preview")]
+    fn service_state_size(
+        &self,
+        _circuit_id: &str,
+        _service_id: &str,
+        _service_type: &str,
+    ) -> Result<Option<u64>, InternalError> {
+        Ok(None)
+    }
+
+    /// Signals that all services on the given circuit have been successfully added.
+    ///
+    /// This is called once `add_service` has succeeded for every local service on a circuit,
+    /// across all of the admin service's lifecycle dispatches. The default implementation does
+    /// nothing.
+    #[cfg(feature = "circuit-ready-hooks")]
+    fn circuit_ready(&self, _circuit_id: &str) -> Result<(), InternalError> {
+        Ok(())
+    }
+
+    /// Returns whether services of the given type can be run by this dispatch, if known.
+    ///
+    /// The default implementation reports that support is unknown.
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn supports_service_type(&self, _service_type: &str) -> Option<bool> {
+        None
+    }
+
     fn add_stopped_service(
         &self,
         circuit_id: &str,
@@ -122,12 +122,48 @@ impl LifecycleDispatch for ServiceOrchestrator {
             .map_err(|err| InternalError::from_source(Box::new(err)))
     }
 
+    #[cfg(feature = "circuit-purge-preview")]
+    fn service_state_size(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        service_type: &str,
+    ) -> Result<Option<u64>, InternalError> {
+        if !self
+            .supported_service_types()
+            .contains(&service_type.to_string())
+        {
+            return Ok(None);
+        }
+
+        let service_definition = ServiceDefinition {
+            circuit: circuit_id.to_string(),
+            service_id: service_id.to_string(),
+            service_type: service_type.to_string(),
+        };
+
+        ServiceOrchestrator::service_state_size(self, &service_definition)
+    }
+
+    #[cfg(feature = "circuit-ready-hooks")]
+    fn circuit_ready(&self, circuit_id: &str) -> Result<(), InternalError> {
+        ServiceOrchestrator::notify_circuit_ready(self, circuit_id)
+    }
+
     fn shutdown_all_services(&self) -> Result<(), InternalError> {
         debug!("Shutdown all services");
         ServiceOrchestrator::shutdown_all_services(self)
             .map_err(|err| InternalError::from_source(Box::new(err)))
     }
 
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn supports_service_type(&self, service_type: &str) -> Option<bool> {
+        Some(
+            self.supported_service_types()
+                .contains(&service_type.to_string()),
+        )
+    }
+
     fn add_stopped_service(
         &self,
         circuit_id: &str,
@@ -20,3 +20,6 @@ pub use super::service::messages::{
     DurabilityType, PersistenceType, ProposalType, RouteType, SplinterNode, SplinterNodeBuilder,
     SplinterService, SplinterServiceBuilder, Vote, VoteRecord,
 };
+
+#[cfg(feature = "admin-service-payload-builder")]
+pub use super::service::messages::PayloadError;
@@ -16,6 +16,8 @@
 
 #[cfg(feature = "admin-service-client")]
 pub mod client;
+#[cfg(feature = "admin-service-vote-delegation")]
+pub mod delegation;
 pub mod error;
 pub mod lifecycle;
 pub mod messages;
@@ -20,19 +20,29 @@ use std::time::Duration;
 
 use cylinder::Verifier as SignatureVerifier;
 
+#[cfg(feature = "admin-service-vote-delegation")]
+use crate::admin::delegation::VoteDelegationStore;
 use crate::admin::lifecycle::LifecycleDispatch;
-use crate::admin::store::AdminServiceStore;
+use crate::admin::store::{AdminServiceEventStore, AdminServiceStore};
 use crate::circuit::routing::RoutingTableWriter;
+#[cfg(feature = "admin-service-consensus-retry")]
+use crate::consensus::two_phase::v1::VerificationRetry;
 use crate::error::InvalidStateError;
 use crate::keys::KeyPermissionManager;
+#[cfg(feature = "challenge-trust-policy")]
+use crate::network::auth::ChallengeTrustPolicy;
 use crate::peer::PeerManagerConnector;
 use crate::public_key::PublicKey;
 use crate::service::instance::ServiceArgValidator;
 
+#[cfg(feature = "admin-service-proposal-limits")]
+use super::limits::ProposalLimits;
 use super::shared::AdminServiceShared;
 use super::{admin_service_id, AdminKeyVerifier, AdminService};
 
 const DEFAULT_COORDINATOR_TIMEOUT: u64 = 30; // 30 seconds
+#[cfg(feature = "admin-service-proposal-timeout")]
+const DEFAULT_MAX_COORDINATOR_TIMEOUT: u64 = 300; // 5 minutes
 
 /// AdminService builder.
 ///
@@ -50,9 +60,23 @@ pub struct AdminServiceBuilder {
     key_verifier: Option<Box<dyn AdminKeyVerifier>>,
     key_permission_manager: Option<Box<dyn KeyPermissionManager>>,
     coordinator_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    max_coordinator_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    verification_retry: Option<VerificationRetry>,
     routing_table_writer: Option<Box<dyn RoutingTableWriter>>,
-    event_store: Option<Box<dyn AdminServiceStore>>,
+    event_store: Option<Box<dyn AdminServiceEventStore>>,
     public_keys: Option<Vec<PublicKey>>,
+    #[cfg(feature = "challenge-trust-policy")]
+    trust_policy: Option<ChallengeTrustPolicy>,
+    #[cfg(feature = "admin-service-vote-delegation")]
+    vote_delegation_store: Option<Box<dyn VoteDelegationStore>>,
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: bool,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    proposal_limits: Option<ProposalLimits>,
 }
 
 impl AdminServiceBuilder {
@@ -135,6 +159,24 @@ impl AdminServiceBuilder {
         self
     }
 
+    /// Sets the maximum coordinator timeout that a circuit management payload may request for its
+    /// proposal. If not set, the maximum is 5 minutes, or the coordinator timeout if that is
+    /// longer.
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    pub fn with_max_coordinator_timeout(mut self, max_coordinator_timeout: Duration) -> Self {
+        self.max_coordinator_timeout = Some(max_coordinator_timeout);
+        self
+    }
+
+    /// Sets how the two-phase commit coordinator re-sends verification requests that have not
+    /// been answered. If not set, verification requests are only retried when a circuit
+    /// management payload requests it.
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn with_verification_retry(mut self, verification_retry: VerificationRetry) -> Self {
+        self.verification_retry = Some(verification_retry);
+        self
+    }
+
     /// Sets the routing table writer instance.
     pub fn with_routing_table_writer(
         mut self,
@@ -145,7 +187,10 @@ impl AdminServiceBuilder {
     }
 
     /// Sets the admin event store instance.
-    pub fn with_admin_event_store(mut self, event_store: Box<dyn AdminServiceStore>) -> Self {
+    ///
+    /// A boxed `AdminServiceStore` may be used by wrapping it in a `Box`; tests and deployments
+    /// without a database may use a `MemoryAdminServiceEventStore`.
+    pub fn with_admin_event_store(mut self, event_store: Box<dyn AdminServiceEventStore>) -> Self {
         self.event_store = Some(event_store);
 
         self
@@ -158,6 +203,66 @@ impl AdminServiceBuilder {
         self
     }
 
+    /// Sets the challenge trust policy that the admin service will keep up to date with the
+    /// authorized public keys of the members of active circuits.
+    ///
+    /// The same policy should be given to the `AuthorizationManager`.
+    #[cfg(feature = "challenge-trust-policy")]
+    pub fn with_challenge_trust_policy(mut self, trust_policy: ChallengeTrustPolicy) -> Self {
+        self.trust_policy = Some(trust_policy);
+
+        self
+    }
+
+    /// Sets the store of vote delegations.
+    ///
+    /// Votes submitted by a delegate key will carry the delegation that allows the key to vote
+    /// for the node.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    pub fn with_vote_delegation_store(
+        mut self,
+        vote_delegation_store: Box<dyn VoteDelegationStore>,
+    ) -> Self {
+        self.vote_delegation_store = Some(vote_delegation_store);
+
+        self
+    }
+
+    /// Sets the largest service message payload, in bytes, that this node accepts on a circuit.
+    ///
+    /// The limit is shared with the other members when a circuit is created; the circuit uses the
+    /// smallest of the limits requested by the proposal and configured by its members. If not
+    /// set, this node does not lower the limit of new circuits.
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn with_max_message_size(mut self, max_message_size: u64) -> Self {
+        self.max_message_size = Some(max_message_size);
+
+        self
+    }
+
+    /// Sets whether purged circuits are kept in the admin store as tombstones.
+    ///
+    /// A tombstoned circuit is excluded from circuit listings by default, but remains available
+    /// for reporting until its tombstone is removed. Defaults to `false`, in which case purged
+    /// circuits are removed from the store.
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn with_circuit_tombstones(mut self, circuit_tombstones: bool) -> Self {
+        self.circuit_tombstones = circuit_tombstones;
+
+        self
+    }
+
+    /// Sets the limits on proposals submitted to this node.
+    ///
+    /// Proposals that exceed a limit are rejected with a throttling error. If not set, proposals
+    /// are not limited.
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_proposal_limits(mut self, proposal_limits: ProposalLimits) -> Self {
+        self.proposal_limits = Some(proposal_limits);
+
+        self
+    }
+
     /// Constructs the AdminService.
     ///
     /// # Errors
@@ -168,6 +273,12 @@ impl AdminServiceBuilder {
             .coordinator_timeout
             .unwrap_or_else(|| Duration::from_secs(DEFAULT_COORDINATOR_TIMEOUT));
 
+        #[cfg(feature = "admin-service-proposal-timeout")]
+        let max_coordinator_timeout = self
+            .max_coordinator_timeout
+            .unwrap_or_else(|| Duration::from_secs(DEFAULT_MAX_COORDINATOR_TIMEOUT))
+            .max(coordinator_timeout);
+
         let lifecycle_dispatch = self.lifecycle_dispatch.ok_or_else(|| {
             InvalidStateError::with_message("An admin service requires a lifecycle_dispatch".into())
         })?;
@@ -219,7 +330,8 @@ impl AdminServiceBuilder {
 
         let public_keys = self.public_keys.unwrap_or_default();
 
-        let admin_service_shared = Arc::new(Mutex::new(AdminServiceShared::new(
+        #[allow(unused_mut)]
+        let mut admin_service_shared = AdminServiceShared::new(
             node_id.clone(),
             lifecycle_dispatch,
             service_arg_validators,
@@ -231,13 +343,54 @@ impl AdminServiceBuilder {
             routing_table_writer,
             admin_event_store,
             public_keys,
-        )));
+        );
+
+        #[cfg(feature = "challenge-trust-policy")]
+        if let Some(trust_policy) = self.trust_policy {
+            admin_service_shared
+                .set_trust_policy(trust_policy)
+                .map_err(|err| {
+                    InvalidStateError::with_message(format!(
+                        "Unable to load the challenge trust policy: {}",
+                        err
+                    ))
+                })?;
+        }
+
+        #[cfg(feature = "admin-service-vote-delegation")]
+        if let Some(vote_delegation_store) = self.vote_delegation_store {
+            admin_service_shared.set_vote_delegation_store(vote_delegation_store);
+        }
+
+        #[cfg(feature = "circuit-max-message-size")]
+        if let Some(max_message_size) = self.max_message_size {
+            if max_message_size == 0 {
+                return Err(InvalidStateError::with_message(
+                    "The max message size must be greater than 0".into(),
+                ));
+            }
+            admin_service_shared.set_max_message_size(max_message_size);
+        }
+
+        #[cfg(feature = "circuit-tombstones")]
+        admin_service_shared.set_circuit_tombstones(self.circuit_tombstones);
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let Some(proposal_limits) = self.proposal_limits {
+            admin_service_shared.set_proposal_limits(proposal_limits);
+        }
+
+        let admin_service_shared = Arc::new(Mutex::new(admin_service_shared));
 
         Ok(AdminService {
             service_id,
             node_id,
             admin_service_shared,
             coordinator_timeout,
+            #[cfg(feature = "admin-service-proposal-timeout")]
+            max_coordinator_timeout,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            verification_retry: self.verification_retry,
             consensus: None,
             peer_connector,
             peer_notification_run_state: None,
@@ -23,6 +23,8 @@ use protobuf::{Message, RepeatedField};
 
 use crate::admin::token::PeerAuthorizationTokenReader;
 use crate::consensus::two_phase::v1::TwoPhaseEngine;
+#[cfg(feature = "admin-service-consensus-retry")]
+use crate::consensus::two_phase::v1::VerificationRetry;
 use crate::consensus::{
     error::{ConsensusSendError, ProposalManagerError},
     ConsensusMessage, ConsensusNetworkSender, PeerId, Proposal, ProposalId, ProposalManager,
@@ -32,6 +34,11 @@ use crate::consensus::{ConsensusEngine, StartupState};
 use crate::error::InvalidStateError;
 use crate::hex::to_hex;
 use crate::peer::PeerTokenPair;
+#[cfg(any(
+    feature = "admin-service-proposal-timeout",
+    feature = "admin-service-consensus-retry"
+))]
+use crate::protos::admin::CircuitManagementPayload_Header;
 use crate::protos::admin::{AdminMessage, AdminMessage_Type, ProposedCircuit};
 use crate::protos::two_phase::RequiredVerifiers;
 use crate::service::instance::ServiceError;
@@ -55,6 +62,10 @@ impl AdminConsensusManager {
         shared: Arc<Mutex<AdminServiceShared>>,
         // The coordinator timeout for the two-phase commit consensus engine
         coordinator_timeout: Duration,
+        // The maximum coordinator timeout that a proposal may request
+        #[cfg(feature = "admin-service-proposal-timeout")] max_coordinator_timeout: Duration,
+        // How the coordinator retries verification requests, if at all
+        #[cfg(feature = "admin-service-consensus-retry")] retry_policy: Option<VerificationRetry>,
     ) -> Result<Self, AdminConsensusManagerError> {
         let (consensus_msg_tx, consensus_msg_rx) = channel();
         let (proposal_update_tx, proposal_update_rx) = channel();
@@ -71,7 +82,15 @@ impl AdminConsensusManager {
         let thread_handle = Builder::new()
             .name(format!("consensus-{}", service_id))
             .spawn(move || {
+                #[cfg(not(feature = "admin-service-proposal-timeout"))]
                 let mut two_phase_engine = TwoPhaseEngine::new(coordinator_timeout);
+                #[cfg(feature = "admin-service-proposal-timeout")]
+                let mut two_phase_engine = TwoPhaseEngine::new(coordinator_timeout)
+                    .with_max_coordinator_timeout(max_coordinator_timeout);
+                #[cfg(feature = "admin-service-consensus-retry")]
+                if let Some(retry_policy) = retry_policy {
+                    two_phase_engine = two_phase_engine.with_verification_retry(retry_policy);
+                }
                 if let Err(err) = two_phase_engine.run(
                     consensus_msg_rx,
                     proposal_update_rx,
@@ -190,6 +209,27 @@ impl ProposalManager for AdminProposalManager {
                 verifiers.push(admin_service_id(member.get_node_id()).as_bytes().to_vec());
             }
             required_verifiers.set_verifiers(RepeatedField::from_vec(verifiers));
+            #[cfg(any(
+                feature = "admin-service-proposal-timeout",
+                feature = "admin-service-consensus-retry"
+            ))]
+            {
+                let header: CircuitManagementPayload_Header =
+                    Message::parse_from_bytes(circuit_payload.get_header())
+                        .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+                #[cfg(feature = "admin-service-proposal-timeout")]
+                required_verifiers.set_coordinator_timeout_millis(
+                    header.get_coordinator_timeout().saturating_mul(1000),
+                );
+                #[cfg(feature = "admin-service-consensus-retry")]
+                {
+                    required_verifiers.set_verification_timeout_millis(
+                        header.get_verification_timeout().saturating_mul(1000),
+                    );
+                    required_verifiers.set_verification_retries(header.get_verification_retries());
+                    required_verifiers.set_verification_backoff(header.get_verification_backoff());
+                }
+            }
             let required_verifiers_bytes = required_verifiers
                 .write_to_bytes()
                 .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
@@ -264,6 +304,15 @@ impl ProposalManager for AdminProposalManager {
             .ok_or_else(|| ProposalManagerError::UnknownProposal(id.clone()))?
             .clone();
 
+        #[cfg(feature = "admin-service-rejection-reasons")]
+        let (hash, _) = match shared.propose_change(circuit_payload.clone()) {
+            Ok(change) => change,
+            Err(err) => {
+                shared.notify_validation_failure(&circuit_payload, &err.to_string());
+                return Err(ProposalManagerError::Internal(Box::new(err)));
+            }
+        };
+        #[cfg(not(feature = "admin-service-rejection-reasons"))]
         let (hash, _) = shared
             .propose_change(circuit_payload)
             .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
@@ -276,6 +325,12 @@ impl ProposalManager for AdminProposalManager {
                 to_hex(hash.as_bytes())
             );
 
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            shared.notify_validation_failure(
+                &circuit_payload,
+                "The proposal does not match the requester's proposal",
+            );
+
             self.proposal_update_sender
                 .send(ProposalUpdate::ProposalInvalid(id.clone()))?;
         } else {
@@ -15,6 +15,8 @@
 use std::error::Error;
 use std::fmt;
 
+#[cfg(feature = "admin-service-vote-delegation")]
+use crate::admin::delegation::VoteDelegationStoreError;
 use crate::admin::store::error::AdminServiceStoreError;
 use crate::consensus::error::ProposalManagerError;
 use crate::runtime::service::instance::{InitializeServiceError, ShutdownServiceError};
@@ -22,6 +24,9 @@ use crate::service::instance::{ServiceError, ServiceSendError};
 
 use protobuf::error;
 
+#[cfg(feature = "admin-service-proposal-limits")]
+use super::limits::ProposalThrottled;
+
 #[derive(Debug)]
 pub enum AdminServiceError {
     ServiceError(ServiceError),
@@ -30,6 +35,18 @@ pub enum AdminServiceError {
         context: String,
         source: Option<Box<dyn Error>>,
     },
+
+    /// The requested events are older than the event retention policy allows and have been
+    /// removed.
+    #[cfg(feature = "admin-service-event-retention")]
+    EventsTruncated {
+        since: i64,
+        truncated_to: i64,
+    },
+
+    /// A submitted proposal exceeded the admin service's proposal limits.
+    #[cfg(feature = "admin-service-proposal-limits")]
+    Throttled(ProposalThrottled),
 }
 
 impl AdminServiceError {
@@ -53,6 +70,10 @@ impl Error for AdminServiceError {
         match self {
             AdminServiceError::ServiceError(err) => Some(err),
             AdminServiceError::GeneralError { source, .. } => source.as_ref().map(|err| &**err),
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminServiceError::EventsTruncated { .. } => None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            AdminServiceError::Throttled(err) => Some(err),
         }
     }
 }
@@ -68,6 +89,17 @@ impl fmt::Display for AdminServiceError {
                     f.write_str(context)
                 }
             }
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminServiceError::EventsTruncated {
+                since,
+                truncated_to,
+            } => write!(
+                f,
+                "Events since {} are no longer available; events up to {} have been removed",
+                since, truncated_to
+            ),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            AdminServiceError::Throttled(err) => write!(f, "Proposal throttled: {}", err),
         }
     }
 }
@@ -164,6 +196,17 @@ pub enum AdminSharedError {
 
     // An error occurred while trying to negotiated protocol versions
     ServiceProtocolError(String),
+
+    /// The requested events have been removed by the event retention policy.
+    #[cfg(feature = "admin-service-event-retention")]
+    EventsTruncated {
+        since: i64,
+        truncated_to: i64,
+    },
+
+    /// A submitted proposal exceeded the proposal limits.
+    #[cfg(feature = "admin-service-proposal-limits")]
+    Throttled(ProposalThrottled),
 }
 
 impl Error for AdminSharedError {
@@ -192,6 +235,10 @@ impl Error for AdminSharedError {
             AdminSharedError::ValidationFailed(_) => None,
             AdminSharedError::UnableToAddSubscriber(_) => None,
             AdminSharedError::ServiceProtocolError(_) => None,
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminSharedError::EventsTruncated { .. } => None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            AdminSharedError::Throttled(err) => Some(err),
         }
     }
 }
@@ -236,6 +283,17 @@ impl fmt::Display for AdminSharedError {
                 "error occurred while trying to agree on protocol: {}",
                 msg
             ),
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminSharedError::EventsTruncated {
+                since,
+                truncated_to,
+            } => write!(
+                f,
+                "events since {} are no longer available; events up to {} have been removed",
+                since, truncated_to
+            ),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            AdminSharedError::Throttled(err) => write!(f, "proposal throttled: {}", err),
         }
     }
 }
@@ -258,6 +316,13 @@ impl From<AdminServiceStoreError> for AdminSharedError {
     }
 }
 
+#[cfg(feature = "admin-service-vote-delegation")]
+impl From<VoteDelegationStoreError> for AdminSharedError {
+    fn from(err: VoteDelegationStoreError) -> Self {
+        AdminSharedError::SplinterStateError(err.to_string())
+    }
+}
+
 impl From<AdminKeyVerifierError> for AdminSharedError {
     fn from(err: AdminKeyVerifierError) -> Self {
         AdminSharedError::ValidationFailed(format!("unable to verify key permissions: {}", err))
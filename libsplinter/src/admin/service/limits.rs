@@ -0,0 +1,272 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Limits on the proposals that may be submitted to the admin service.
+//!
+//! Each requester key may submit a limited number of proposals within a sliding window, and no
+//! proposals are accepted while the number of pending proposals is at the configured cap. Votes
+//! are not limited, as they are needed to clear pending proposals.
+//!
+//! Throttled submissions are logged to the `splinter::audit` target and, with the
+//! `admin-service-metrics` feature, counted by the `splinter.admin.proposals.throttled` metric,
+//! labeled by reason.
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The log target used for records of throttled proposals; the same target is used for the
+/// connection audit log, so that one appender may capture both.
+const AUDIT_LOG_TARGET: &str = "splinter::audit";
+
+/// The limits applied to proposals submitted to the admin service.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProposalLimits {
+    requester_rate: Option<(u32, Duration)>,
+    max_pending: Option<u32>,
+}
+
+impl ProposalLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits each requester key to `max_proposals` proposals within the given window.
+    pub fn with_requester_rate(mut self, max_proposals: u32, window: Duration) -> Self {
+        self.requester_rate = Some((max_proposals, window));
+        self
+    }
+
+    /// Rejects new proposals while `max_pending` proposals are waiting to be accepted or
+    /// rejected.
+    pub fn with_max_pending(mut self, max_pending: u32) -> Self {
+        self.max_pending = Some(max_pending);
+        self
+    }
+
+    /// Returns the number of proposals each requester key may submit, and the window in which
+    /// they are counted, if limited.
+    pub fn requester_rate(&self) -> Option<(u32, Duration)> {
+        self.requester_rate
+    }
+
+    /// Returns the maximum number of pending proposals, if limited.
+    pub fn max_pending(&self) -> Option<u32> {
+        self.max_pending
+    }
+}
+
+/// A proposal was rejected because it exceeded one of the admin service's proposal limits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProposalThrottled {
+    /// The requester has submitted the maximum number of proposals within the window.
+    RequesterRateExceeded {
+        requester: String,
+        max_proposals: u32,
+        window: Duration,
+        /// How long until the requester's oldest counted proposal leaves the window
+        retry_after: Duration,
+    },
+    /// The maximum number of proposals are pending.
+    PendingLimitReached { max_pending: u32 },
+}
+
+impl ProposalThrottled {
+    /// Returns how long the requester should wait before submitting again, if known.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ProposalThrottled::RequesterRateExceeded { retry_after, .. } => Some(*retry_after),
+            ProposalThrottled::PendingLimitReached { .. } => None,
+        }
+    }
+
+    #[cfg(feature = "admin-service-metrics")]
+    fn reason(&self) -> &'static str {
+        match self {
+            ProposalThrottled::RequesterRateExceeded { .. } => "requester_rate",
+            ProposalThrottled::PendingLimitReached { .. } => "max_pending",
+        }
+    }
+}
+
+impl Error for ProposalThrottled {}
+
+impl fmt::Display for ProposalThrottled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProposalThrottled::RequesterRateExceeded {
+                requester,
+                max_proposals,
+                window,
+                retry_after,
+            } => write!(
+                f,
+                "requester {} has submitted {} proposals in the last {}s; retry in {}s",
+                requester,
+                max_proposals,
+                window.as_secs(),
+                retry_after.as_secs().max(1)
+            ),
+            ProposalThrottled::PendingLimitReached { max_pending } => write!(
+                f,
+                "{} proposals are pending; no more proposals are accepted until some are \
+                 resolved",
+                max_pending
+            ),
+        }
+    }
+}
+
+/// Tracks the proposals submitted by each requester to enforce the proposal limits.
+#[derive(Default)]
+pub(super) struct ProposalLimiter {
+    limits: ProposalLimits,
+    // The times at which each requester's proposals within the window were submitted, oldest
+    // first
+    submissions: HashMap<String, VecDeque<Instant>>,
+}
+
+impl ProposalLimiter {
+    pub fn new(limits: ProposalLimits) -> Self {
+        Self {
+            limits,
+            submissions: HashMap::new(),
+        }
+    }
+
+    /// Checks a proposal from the given requester against the limits, recording it if it is
+    /// allowed.
+    ///
+    /// `pending` is only called if the number of pending proposals is limited.
+    pub fn check<E>(
+        &mut self,
+        requester: &str,
+        pending: impl FnOnce() -> Result<u32, E>,
+        now: Instant,
+    ) -> Result<Result<(), ProposalThrottled>, E> {
+        if let Some(max_pending) = self.limits.max_pending {
+            if pending()? >= max_pending {
+                return Ok(self.throttled(ProposalThrottled::PendingLimitReached { max_pending }));
+            }
+        }
+
+        if let Some((max_proposals, window)) = self.limits.requester_rate {
+            // Forget requesters whose submissions have all left the window
+            self.submissions.retain(|_, times| {
+                while times
+                    .front()
+                    .map(|time| now.duration_since(*time) >= window)
+                    .unwrap_or(false)
+                {
+                    times.pop_front();
+                }
+                !times.is_empty()
+            });
+
+            let times = self.submissions.entry(requester.to_string()).or_default();
+            if times.len() >= max_proposals as usize {
+                let retry_after = times
+                    .front()
+                    .map(|oldest| window - now.duration_since(*oldest))
+                    .unwrap_or(window);
+                return Ok(self.throttled(ProposalThrottled::RequesterRateExceeded {
+                    requester: requester.to_string(),
+                    max_proposals,
+                    window,
+                    retry_after,
+                }));
+            }
+            times.push_back(now);
+        }
+
+        Ok(Ok(()))
+    }
+
+    fn throttled(&self, throttled: ProposalThrottled) -> Result<(), ProposalThrottled> {
+        warn!(target: AUDIT_LOG_TARGET, "Proposal throttled: {}", throttled);
+        #[cfg(feature = "admin-service-metrics")]
+        counter!(
+            "splinter.admin.proposals.throttled",
+            1,
+            "reason" => throttled.reason()
+        );
+        Err(throttled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::Infallible;
+
+    /// Verify that a requester is throttled once it has submitted the maximum number of
+    /// proposals in the window, without affecting other requesters, and may submit again once
+    /// its oldest proposal leaves the window.
+    #[test]
+    fn test_requester_rate() {
+        let mut limiter = ProposalLimiter::new(
+            ProposalLimits::new().with_requester_rate(2, Duration::from_secs(60)),
+        );
+        let start = Instant::now();
+        let pending = || Ok::<_, Infallible>(0);
+
+        assert_eq!(Ok(Ok(())), limiter.check("alice", pending, start));
+        assert_eq!(
+            Ok(Ok(())),
+            limiter.check("alice", pending, start + Duration::from_secs(10))
+        );
+
+        let throttled = limiter
+            .check("alice", pending, start + Duration::from_secs(20))
+            .expect("infallible")
+            .expect_err("third proposal should be throttled");
+        assert_eq!(Some(Duration::from_secs(40)), throttled.retry_after());
+
+        assert_eq!(
+            Ok(Ok(())),
+            limiter.check("bob", pending, start + Duration::from_secs(20))
+        );
+        assert_eq!(
+            Ok(Ok(())),
+            limiter.check("alice", pending, start + Duration::from_secs(60))
+        );
+    }
+
+    /// Verify that proposals are throttled while the maximum number of proposals are pending,
+    /// and that the pending count is not read if it is not limited.
+    #[test]
+    fn test_max_pending() {
+        let mut limiter = ProposalLimiter::new(ProposalLimits::new().with_max_pending(3));
+        let now = Instant::now();
+
+        assert_eq!(
+            Ok(Ok(())),
+            limiter.check("alice", || Ok::<_, Infallible>(2), now)
+        );
+        assert_eq!(
+            Ok(Err(ProposalThrottled::PendingLimitReached {
+                max_pending: 3
+            })),
+            limiter.check("alice", || Ok::<_, Infallible>(3), now)
+        );
+
+        let mut unlimited = ProposalLimiter::default();
+        assert_eq!(
+            Ok(Ok(())),
+            unlimited.check("alice", || Err("pending count read"), now)
+        );
+    }
+}
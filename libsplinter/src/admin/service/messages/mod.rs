@@ -18,8 +18,13 @@ pub mod v2;
 pub use self::v2::builders::{
     BuilderError, CreateCircuitBuilder, SplinterNodeBuilder, SplinterServiceBuilder,
 };
+#[cfg(feature = "admin-service-payload-builder")]
+pub use self::v2::payload::PayloadError;
 pub use self::v2::{
     is_valid_circuit_id, is_valid_service_id, AdminServiceEvent, AuthorizationType,
-    CircuitProposal, CircuitProposalVote, CircuitStatus, CreateCircuit, DurabilityType,
-    PersistenceType, ProposalType, RouteType, SplinterNode, SplinterService, Vote, VoteRecord,
+    CircuitAttachment, CircuitProposal, CircuitProposalVote, CircuitStatus, CreateCircuit,
+    DurabilityType, PersistenceType, ProposalType, RouteType, SplinterNode, SplinterService, Vote,
+    VoteRecord,
 };
+#[cfg(feature = "admin-service-rejection-reasons")]
+pub use self::v2::{ProposalRejection, ValidationFailure};
@@ -487,6 +487,11 @@ impl TryFrom<&store::AdminServiceEvent> for AdminServiceEvent {
             EventType::ProposalRejected { requester } => {
                 AdminServiceEvent::ProposalRejected((admin_proposal, requester.to_vec()))
             }
+            // Version 1 clients are only told that the proposal was rejected
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            EventType::ProposalInvalid { requester, .. } => {
+                AdminServiceEvent::ProposalRejected((admin_proposal, requester.to_vec()))
+            }
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
             EventType::CircuitDisbanded => {
                 return Err(MarshallingError::UnsetField(
@@ -14,11 +14,13 @@
 
 use std::error::Error as StdError;
 
+#[cfg(feature = "admin-service-payload-builder")]
+use crate::admin::store;
 use crate::base62::generate_random_base62_string;
 
 use super::{
-    is_valid_circuit_id, is_valid_service_id, AuthorizationType, CircuitStatus, CreateCircuit,
-    DurabilityType, PersistenceType, RouteType, SplinterNode, SplinterService,
+    is_valid_circuit_id, is_valid_service_id, AuthorizationType, CircuitAttachment, CircuitStatus,
+    CreateCircuit, DurabilityType, PersistenceType, RouteType, SplinterNode, SplinterService,
     UNSET_CIRCUIT_VERSION,
 };
 
@@ -37,6 +39,8 @@ pub struct CreateCircuitBuilder {
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
+    attachments: Vec<CircuitAttachment>,
+    max_message_size: Option<u64>,
 }
 
 impl CreateCircuitBuilder {
@@ -96,6 +100,14 @@ impl CreateCircuitBuilder {
         self.circuit_status.clone()
     }
 
+    pub fn attachments(&self) -> Vec<CircuitAttachment> {
+        self.attachments.clone()
+    }
+
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
+
     pub fn with_circuit_id(mut self, circuit_id: &str) -> CreateCircuitBuilder {
         self.circuit_id = Some(circuit_id.into());
         self
@@ -170,6 +182,73 @@ impl CreateCircuitBuilder {
         self
     }
 
+    pub fn with_attachments(mut self, attachments: &[CircuitAttachment]) -> CreateCircuitBuilder {
+        self.attachments = attachments.to_vec();
+        self
+    }
+
+    pub fn with_max_message_size(mut self, max_message_size: u64) -> CreateCircuitBuilder {
+        self.max_message_size = Some(max_message_size);
+        self
+    }
+
+    /// Adds a member to the circuit.
+    ///
+    /// Returns an error if a member with the same node ID has already been added.
+    #[cfg(feature = "admin-service-payload-builder")]
+    pub fn add_member(
+        mut self,
+        member: SplinterNode,
+    ) -> Result<CreateCircuitBuilder, BuilderError> {
+        let members = self.members.get_or_insert_with(Vec::new);
+        if members
+            .iter()
+            .any(|existing| existing.node_id == member.node_id)
+        {
+            return Err(BuilderError::InvalidField(format!(
+                "members: node {} is already a member",
+                member.node_id
+            )));
+        }
+        members.push(member);
+        Ok(self)
+    }
+
+    /// Adds a service to the circuit's roster.
+    ///
+    /// Returns an error if the service's ID is invalid, if a service with the same ID has already
+    /// been added, or if the service is not allowed to run on any node.
+    #[cfg(feature = "admin-service-payload-builder")]
+    pub fn add_service(
+        mut self,
+        service: SplinterService,
+    ) -> Result<CreateCircuitBuilder, BuilderError> {
+        if !is_valid_service_id(&service.service_id) {
+            return Err(BuilderError::InvalidField(format!(
+                "roster: service_id is invalid ({}): must be a 4 character base62 string",
+                service.service_id,
+            )));
+        }
+        if service.allowed_nodes.is_empty() {
+            return Err(BuilderError::InvalidField(format!(
+                "roster: service {} has no allowed nodes",
+                service.service_id
+            )));
+        }
+        let roster = self.roster.get_or_insert_with(Vec::new);
+        if roster
+            .iter()
+            .any(|existing| existing.service_id == service.service_id)
+        {
+            return Err(BuilderError::InvalidField(format!(
+                "roster: service {} is already in the roster",
+                service.service_id
+            )));
+        }
+        roster.push(service);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<CreateCircuit, BuilderError> {
         let circuit_id = match self.circuit_id {
             Some(circuit_id) if is_valid_circuit_id(&circuit_id) => circuit_id,
@@ -237,6 +316,12 @@ impl CreateCircuitBuilder {
 
         let circuit_status = self.circuit_status.unwrap_or_default();
 
+        if self.max_message_size == Some(0) {
+            return Err(BuilderError::InvalidField(
+                "max_message_size must be greater than 0".to_string(),
+            ));
+        }
+
         let create_circuit_message = CreateCircuit {
             circuit_id,
             roster,
@@ -251,12 +336,95 @@ impl CreateCircuitBuilder {
             display_name,
             circuit_version,
             circuit_status,
+            attachments: self.attachments,
+            max_message_size: self.max_message_size,
         };
 
         Ok(create_circuit_message)
     }
 }
 
+/// Creates a builder from a proposed circuit, so that a proposal may be modified and proposed
+/// again.
+#[cfg(feature = "admin-service-payload-builder")]
+impl From<CreateCircuit> for CreateCircuitBuilder {
+    fn from(circuit: CreateCircuit) -> Self {
+        CreateCircuitBuilder {
+            circuit_id: Some(circuit.circuit_id),
+            roster: Some(circuit.roster),
+            members: Some(circuit.members),
+            authorization_type: Some(circuit.authorization_type),
+            persistence: Some(circuit.persistence),
+            durability: Some(circuit.durability),
+            routes: Some(circuit.routes),
+            circuit_management_type: Some(circuit.circuit_management_type),
+            application_metadata: Some(circuit.application_metadata),
+            comments: circuit.comments,
+            display_name: circuit.display_name,
+            circuit_version: Some(circuit.circuit_version),
+            circuit_status: Some(circuit.circuit_status),
+            attachments: circuit.attachments,
+            max_message_size: circuit.max_message_size,
+        }
+    }
+}
+
+/// Creates a builder from an existing circuit, so that an update to the circuit may be proposed.
+///
+/// The application metadata, comments and attachments of the original proposal are not stored
+/// with the circuit, and are left unset.
+#[cfg(feature = "admin-service-payload-builder")]
+impl From<&store::Circuit> for CreateCircuitBuilder {
+    fn from(circuit: &store::Circuit) -> Self {
+        CreateCircuitBuilder {
+            circuit_id: Some(circuit.circuit_id().into()),
+            roster: Some(
+                circuit
+                    .roster()
+                    .iter()
+                    .map(|service| SplinterService {
+                        service_id: service.service_id().into(),
+                        service_type: service.service_type().into(),
+                        allowed_nodes: vec![service.node_id().into()],
+                        arguments: service.arguments().to_vec(),
+                    })
+                    .collect(),
+            ),
+            members: Some(
+                circuit
+                    .members()
+                    .iter()
+                    .map(|node| SplinterNode {
+                        node_id: node.node_id().into(),
+                        endpoints: node.endpoints().to_vec(),
+                        public_key: node
+                            .public_key()
+                            .clone()
+                            .map(|public_key| public_key.into_bytes()),
+                        authorized_public_keys: node
+                            .authorized_public_keys()
+                            .iter()
+                            .map(|public_key| public_key.as_slice().to_vec())
+                            .collect(),
+                    })
+                    .collect(),
+            ),
+            authorization_type: Some(AuthorizationType::from(circuit.authorization_type())),
+            persistence: Some(PersistenceType::from(circuit.persistence())),
+            durability: Some(DurabilityType::from(circuit.durability())),
+            routes: Some(RouteType::from(circuit.routes())),
+            circuit_management_type: Some(circuit.circuit_management_type().into()),
+            application_metadata: None,
+            comments: None,
+            display_name: circuit.display_name().clone(),
+            circuit_version: Some(circuit.circuit_version()),
+            circuit_status: Some(CircuitStatus::from(circuit.circuit_status())),
+            attachments: vec![],
+            max_message_size: circuit.max_message_size(),
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct SplinterServiceBuilder {
     service_id: Option<String>,
@@ -344,6 +512,7 @@ pub struct SplinterNodeBuilder {
     node_id: Option<String>,
     endpoints: Option<Vec<String>>,
     public_key: Option<Vec<u8>>,
+    authorized_public_keys: Vec<Vec<u8>>,
 }
 
 impl SplinterNodeBuilder {
@@ -363,6 +532,10 @@ impl SplinterNodeBuilder {
         self.public_key.clone()
     }
 
+    pub fn authorized_public_keys(&self) -> Vec<Vec<u8>> {
+        self.authorized_public_keys.clone()
+    }
+
     pub fn with_node_id(mut self, node_id: &str) -> SplinterNodeBuilder {
         self.node_id = Some(node_id.into());
         self
@@ -378,6 +551,11 @@ impl SplinterNodeBuilder {
         self
     }
 
+    pub fn with_authorized_public_keys(mut self, public_keys: &[Vec<u8>]) -> SplinterNodeBuilder {
+        self.authorized_public_keys = public_keys.to_vec();
+        self
+    }
+
     pub fn build(self) -> Result<SplinterNode, BuilderError> {
         let node_id = self
             .node_id
@@ -391,6 +569,7 @@ impl SplinterNodeBuilder {
             node_id,
             endpoints,
             public_key: self.public_key,
+            authorized_public_keys: self.authorized_public_keys,
         };
 
         Ok(node)
@@ -788,4 +967,58 @@ mod tests {
             Err(err) => panic!("Got unexpected error: {}", err),
         }
     }
+
+    /// Verify that a builder created from an existing circuit builds a proposal with the
+    /// circuit's members, services and settings.
+    #[cfg(feature = "admin-service-payload-builder")]
+    #[test]
+    fn create_circuit_builder_from_circuit() {
+        let circuit = store::CircuitBuilder::new()
+            .with_circuit_id("0123a-bcDEF")
+            .with_roster(&[store::ServiceBuilder::new()
+                .with_service_id("a000")
+                .with_service_type("scabbard")
+                .with_node_id("node_id")
+                .with_arguments(&[("admin_keys".into(), "[]".into())])
+                .build()
+                .expect("failed to build service")])
+            .with_members(&[store::CircuitNodeBuilder::new()
+                .with_node_id("node_id")
+                .with_endpoints(&["endpoint".into()])
+                .build()
+                .expect("failed to build node")])
+            .with_circuit_management_type("mgmt_type")
+            .with_display_name("circuit")
+            .with_circuit_version(2)
+            .build()
+            .expect("failed to build store circuit");
+
+        let proposed = CreateCircuitBuilder::from(&circuit)
+            .build()
+            .expect("failed to build circuit");
+
+        assert_eq!(&proposed.circuit_id, "0123a-bcDEF");
+        assert_eq!(
+            proposed.roster,
+            vec![SplinterService {
+                service_id: "a000".into(),
+                service_type: "scabbard".into(),
+                allowed_nodes: vec!["node_id".into()],
+                arguments: vec![("admin_keys".into(), "[]".into())],
+            }]
+        );
+        assert_eq!(
+            proposed.members,
+            vec![SplinterNode {
+                node_id: "node_id".into(),
+                endpoints: vec!["endpoint".into()],
+                public_key: None,
+                authorized_public_keys: vec![],
+            }]
+        );
+        assert_eq!(&proposed.circuit_management_type, "mgmt_type");
+        assert_eq!(proposed.display_name, Some("circuit".into()));
+        assert_eq!(proposed.circuit_version, 2);
+        assert_eq!(proposed.circuit_status, CircuitStatus::Active);
+    }
 }
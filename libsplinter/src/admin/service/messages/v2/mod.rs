@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub mod builders;
+#[cfg(feature = "admin-service-payload-builder")]
+pub mod payload;
 
 use protobuf::{self, RepeatedField};
 use std::convert::TryInto;
@@ -47,6 +49,10 @@ pub struct CreateCircuit {
     #[serde(default)]
     pub circuit_version: i32,
     pub circuit_status: CircuitStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<CircuitAttachment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u64>,
 }
 
 impl CreateCircuit {
@@ -116,6 +122,11 @@ impl CreateCircuit {
             proto.get_circuit_version()
         };
 
+        let max_message_size = match proto.get_max_message_size() {
+            0 => None,
+            max_message_size => Some(max_message_size),
+        };
+
         Ok(Self {
             circuit_id: proto.take_circuit_id(),
             roster: proto
@@ -138,6 +149,12 @@ impl CreateCircuit {
             display_name,
             circuit_version,
             circuit_status,
+            attachments: proto
+                .take_attachments()
+                .into_iter()
+                .map(CircuitAttachment::from_proto)
+                .collect(),
+            max_message_size,
         })
     }
 
@@ -169,6 +186,17 @@ impl CreateCircuit {
             circuit.set_display_name(display_name);
         }
 
+        circuit.set_attachments(RepeatedField::from_vec(
+            self.attachments
+                .into_iter()
+                .map(CircuitAttachment::into_proto)
+                .collect(),
+        ));
+
+        if let Some(max_message_size) = self.max_message_size {
+            circuit.set_max_message_size(max_message_size);
+        }
+
         if self.circuit_version != UNSET_CIRCUIT_VERSION {
             circuit.set_circuit_version(self.circuit_version);
         }
@@ -252,6 +280,17 @@ impl TryInto<admin::Circuit> for CreateCircuit {
             circuit.set_display_name(display_name);
         }
 
+        circuit.set_attachments(RepeatedField::from_vec(
+            self.attachments
+                .into_iter()
+                .map(CircuitAttachment::into_proto)
+                .collect(),
+        ));
+
+        if let Some(max_message_size) = self.max_message_size {
+            circuit.set_max_message_size(max_message_size);
+        }
+
         if self.circuit_version != UNSET_CIRCUIT_VERSION {
             circuit.set_circuit_version(self.circuit_version);
         }
@@ -416,6 +455,8 @@ pub struct SplinterNode {
     pub node_id: String,
     pub endpoints: Vec<String>,
     pub public_key: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorized_public_keys: Vec<Vec<u8>>,
 }
 
 impl SplinterNode {
@@ -429,6 +470,8 @@ impl SplinterNode {
             proto.set_public_key(public_key);
         }
 
+        proto.set_authorized_public_keys(self.authorized_public_keys.into());
+
         proto
     }
 
@@ -446,10 +489,38 @@ impl SplinterNode {
             node_id: proto.take_node_id(),
             endpoints: proto.take_endpoints().into(),
             public_key,
+            authorized_public_keys: proto.take_authorized_public_keys().into(),
         })
     }
 }
 
+/// A document attached to a circuit proposal, identified by the sha256 hash of its contents
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct CircuitAttachment {
+    pub name: String,
+    pub sha256: String,
+    pub uri: String,
+}
+
+impl CircuitAttachment {
+    pub fn into_proto(self) -> admin::CircuitAttachment {
+        let mut proto = admin::CircuitAttachment::new();
+        proto.set_name(self.name);
+        proto.set_sha256(self.sha256);
+        proto.set_uri(self.uri);
+
+        proto
+    }
+
+    pub fn from_proto(mut proto: admin::CircuitAttachment) -> Self {
+        Self {
+            name: proto.take_name(),
+            sha256: proto.take_sha256(),
+            uri: proto.take_uri(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct SplinterService {
     pub service_id: String,
@@ -618,6 +689,11 @@ impl From<store::CircuitProposal> for CircuitProposal {
                         .public_key()
                         .clone()
                         .map(|public_key| public_key.into_bytes()),
+                    authorized_public_keys: node
+                        .authorized_public_keys()
+                        .iter()
+                        .map(|public_key| public_key.as_slice().to_vec())
+                        .collect(),
                 })
                 .collect::<Vec<SplinterNode>>(),
             authorization_type: AuthorizationType::from(store_circuit.authorization_type()),
@@ -633,6 +709,16 @@ impl From<store::CircuitProposal> for CircuitProposal {
             display_name: store_circuit.display_name().clone(),
             circuit_version: store_circuit.circuit_version(),
             circuit_status: CircuitStatus::from(&store_circuit.circuit_status().clone()),
+            attachments: store_circuit
+                .attachments()
+                .iter()
+                .map(|attachment| CircuitAttachment {
+                    name: attachment.name().into(),
+                    sha256: attachment.sha256().into(),
+                    uri: attachment.uri().into(),
+                })
+                .collect(),
+            max_message_size: store_circuit.max_message_size(),
         };
 
         Self {
@@ -653,6 +739,7 @@ impl From<store::CircuitProposal> for CircuitProposal {
                         public_key: vote_record.public_key().as_slice().to_vec(),
                         vote,
                         voter_node_id: vote_record.voter_node_id().into(),
+                        comment: vote_record.comment().map(String::from),
                     }
                 })
                 .collect(),
@@ -676,6 +763,8 @@ pub struct CircuitProposalVote {
     pub circuit_id: String,
     pub circuit_hash: String,
     pub vote: Vote,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 impl CircuitProposalVote {
@@ -688,10 +777,16 @@ impl CircuitProposalVote {
             }
         };
 
+        let comment = match proto.take_comment() {
+            comment if comment.is_empty() => None,
+            comment => Some(comment),
+        };
+
         Ok(CircuitProposalVote {
             circuit_id: proto.take_circuit_id(),
             circuit_hash: proto.take_circuit_hash(),
             vote,
+            comment,
         })
     }
 
@@ -703,6 +798,9 @@ impl CircuitProposalVote {
             Vote::Accept => vote.set_vote(admin::CircuitProposalVote_Vote::ACCEPT),
             Vote::Reject => vote.set_vote(admin::CircuitProposalVote_Vote::REJECT),
         }
+        if let Some(comment) = self.comment {
+            vote.set_comment(comment);
+        }
         vote
     }
 }
@@ -714,6 +812,8 @@ pub struct VoteRecord {
     pub public_key: Vec<u8>,
     pub vote: Vote,
     pub voter_node_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 impl VoteRecord {
@@ -726,10 +826,16 @@ impl VoteRecord {
             }
         };
 
+        let comment = match proto.take_comment() {
+            comment if comment.is_empty() => None,
+            comment => Some(comment),
+        };
+
         Ok(Self {
             public_key: proto.take_public_key(),
             vote,
             voter_node_id: proto.take_voter_node_id(),
+            comment,
         })
     }
 
@@ -743,6 +849,9 @@ impl VoteRecord {
         vote_record.set_vote(vote);
         vote_record.set_public_key(self.public_key);
         vote_record.set_voter_node_id(self.voter_node_id);
+        if let Some(comment) = self.comment {
+            vote_record.set_comment(comment);
+        }
 
         vote_record
     }
@@ -757,6 +866,24 @@ pub enum Vote {
 /// Represents the `requester`'s public key associated with an `AdminServiceEvent`
 pub type PublicKey = Vec<u8>;
 
+/// Describes why a member node failed to validate a proposed change
+#[cfg(feature = "admin-service-rejection-reasons")]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ValidationFailure {
+    pub node_id: String,
+    pub reason: String,
+}
+
+/// Describes why a circuit proposal was rejected
+#[cfg(feature = "admin-service-rejection-reasons")]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum ProposalRejection {
+    /// A member node failed to validate the proposed change
+    ValidationFailed(ValidationFailure),
+    /// A member voted to reject the proposal
+    Vote(VoteRecord),
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 #[serde(tag = "eventType", content = "message")]
 pub enum AdminServiceEvent {
@@ -764,6 +891,8 @@ pub enum AdminServiceEvent {
     ProposalVote((CircuitProposal, PublicKey)),
     ProposalAccepted((CircuitProposal, PublicKey)),
     ProposalRejected((CircuitProposal, PublicKey)),
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    ProposalInvalid((CircuitProposal, PublicKey, ValidationFailure)),
     CircuitReady(CircuitProposal),
     CircuitDisbanded(CircuitProposal),
 }
@@ -775,6 +904,8 @@ impl AdminServiceEvent {
             AdminServiceEvent::ProposalVote((proposal, _)) => proposal,
             AdminServiceEvent::ProposalAccepted((proposal, _)) => proposal,
             AdminServiceEvent::ProposalRejected((proposal, _)) => proposal,
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            AdminServiceEvent::ProposalInvalid((proposal, _, _)) => proposal,
             AdminServiceEvent::CircuitReady(proposal) => proposal,
             AdminServiceEvent::CircuitDisbanded(proposal) => proposal,
         }
@@ -795,6 +926,19 @@ impl From<&store::AdminServiceEvent> for AdminServiceEvent {
             EventType::ProposalRejected { requester } => {
                 AdminServiceEvent::ProposalRejected((admin_proposal, requester.to_vec()))
             }
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            EventType::ProposalInvalid {
+                requester,
+                node_id,
+                reason,
+            } => AdminServiceEvent::ProposalInvalid((
+                admin_proposal,
+                requester.to_vec(),
+                ValidationFailure {
+                    node_id: node_id.to_string(),
+                    reason: reason.to_string(),
+                },
+            )),
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
             EventType::CircuitDisbanded => AdminServiceEvent::CircuitDisbanded(admin_proposal),
         }
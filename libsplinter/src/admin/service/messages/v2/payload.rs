@@ -0,0 +1,320 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Signed circuit management payloads, ready to be submitted to a node's admin service.
+
+use std::error::Error;
+use std::fmt;
+
+use cylinder::{Signer, SigningError};
+use openssl::hash::{hash, MessageDigest};
+use protobuf::Message;
+
+use crate::error::InternalError;
+use crate::protos::admin::{
+    CircuitManagementPayload, CircuitManagementPayload_Action, CircuitManagementPayload_Header,
+};
+
+use super::builders::{BuilderError, CreateCircuitBuilder};
+use super::CreateCircuit;
+
+impl CreateCircuit {
+    /// Proposes the circuit in a `CircuitManagementPayload` signed by the given signer, returning
+    /// the serialized payload.
+    ///
+    /// The payload is only created if the requester node and each service's allowed nodes are
+    /// members of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `requester_node_id` - The ID of the node the payload is submitted to
+    /// * `signer` - The signer of the payload; its public key is the requester of the proposal
+    pub fn into_signed_payload(
+        self,
+        requester_node_id: &str,
+        signer: &dyn Signer,
+    ) -> Result<Vec<u8>, PayloadError> {
+        self.validate_members(requester_node_id)?;
+
+        let create_request = self.into_proto().map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "unable to convert circuit to protobuf".into(),
+            )
+        })?;
+        let serialized_request = create_request.write_to_bytes().map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "unable to serialize `CircuitCreateRequest`".into(),
+            )
+        })?;
+        let payload_sha512 = hash(MessageDigest::sha512(), &serialized_request).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "unable to hash `CircuitCreateRequest`".into(),
+            )
+        })?;
+
+        let public_key = signer.public_key().map_err(PayloadError::SigningError)?;
+
+        let mut header = CircuitManagementPayload_Header::new();
+        header.set_action(CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_payload_sha512(payload_sha512.to_vec());
+        header.set_requester(public_key.into_bytes());
+        header.set_requester_node_id(requester_node_id.into());
+        let header_bytes = header.write_to_bytes().map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "unable to serialize payload header".into(),
+            )
+        })?;
+
+        let signature = signer
+            .sign(&header_bytes)
+            .map_err(PayloadError::SigningError)?;
+
+        let mut payload = CircuitManagementPayload::new();
+        payload.set_header(header_bytes);
+        payload.set_signature(signature.take_bytes());
+        payload.set_circuit_create_request(create_request);
+
+        payload.write_to_bytes().map_err(|err| {
+            PayloadError::InternalError(InternalError::from_source_with_message(
+                Box::new(err),
+                "unable to serialize `CircuitManagementPayload`".into(),
+            ))
+        })
+    }
+
+    fn validate_members(&self, requester_node_id: &str) -> Result<(), BuilderError> {
+        let is_member = |node_id: &str| self.members.iter().any(|node| node.node_id == node_id);
+
+        if !is_member(requester_node_id) {
+            return Err(BuilderError::InvalidField(format!(
+                "members: requester node {} is not a member",
+                requester_node_id
+            )));
+        }
+
+        for service in &self.roster {
+            if let Some(node_id) = service
+                .allowed_nodes
+                .iter()
+                .find(|node_id| !is_member(node_id))
+            {
+                return Err(BuilderError::InvalidField(format!(
+                    "roster: service {} is allowed on node {}, which is not a member",
+                    service.service_id, node_id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CreateCircuitBuilder {
+    /// Builds the circuit and proposes it in a signed `CircuitManagementPayload`, returning the
+    /// serialized payload.
+    ///
+    /// See [`CreateCircuit::into_signed_payload`] for the arguments.
+    pub fn build_signed_payload(
+        self,
+        requester_node_id: &str,
+        signer: &dyn Signer,
+    ) -> Result<Vec<u8>, PayloadError> {
+        self.build()?.into_signed_payload(requester_node_id, signer)
+    }
+}
+
+/// Errors that may occur when creating a signed circuit management payload.
+#[derive(Debug)]
+pub enum PayloadError {
+    /// The circuit is not valid
+    InvalidCircuit(BuilderError),
+    /// The payload could not be signed
+    SigningError(SigningError),
+    /// The payload could not be serialized
+    InternalError(InternalError),
+}
+
+impl Error for PayloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PayloadError::InvalidCircuit(err) => Some(err),
+            PayloadError::SigningError(err) => Some(err),
+            PayloadError::InternalError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PayloadError::InvalidCircuit(err) => write!(f, "invalid circuit: {}", err),
+            PayloadError::SigningError(err) => write!(f, "unable to sign payload: {}", err),
+            PayloadError::InternalError(err) => f.write_str(&err.to_string()),
+        }
+    }
+}
+
+impl From<BuilderError> for PayloadError {
+    fn from(err: BuilderError) -> Self {
+        PayloadError::InvalidCircuit(err)
+    }
+}
+
+impl From<InternalError> for PayloadError {
+    fn from(err: InternalError) -> Self {
+        PayloadError::InternalError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, PublicKey, Signature};
+
+    use crate::admin::service::messages::v2::builders::{
+        SplinterNodeBuilder, SplinterServiceBuilder,
+    };
+    use crate::admin::service::messages::v2::{SplinterNode, SplinterService};
+
+    fn node(node_id: &str) -> SplinterNode {
+        SplinterNodeBuilder::new()
+            .with_node_id(node_id)
+            .with_endpoints(&[format!("tcps://{}:8044", node_id)])
+            .build()
+            .expect("failed to build node")
+    }
+
+    fn service(service_id: &str, node_id: &str) -> SplinterService {
+        SplinterServiceBuilder::new()
+            .with_service_id(service_id)
+            .with_service_type("scabbard")
+            .with_allowed_nodes(&[node_id.into()])
+            .build()
+            .expect("failed to build service")
+    }
+
+    /// Verify that a circuit built with `add_member` and `add_service` is proposed in a payload
+    /// whose header is signed by the signer and whose request round-trips to the same circuit.
+    #[test]
+    fn signed_payload_round_trip() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+
+        let builder = CreateCircuitBuilder::new()
+            .with_circuit_id("0123a-bcDEF")
+            .with_circuit_management_type("mgmt_type")
+            .add_member(node("alpha"))
+            .and_then(|builder| builder.add_member(node("beta")))
+            .and_then(|builder| builder.add_service(service("a000", "alpha")))
+            .and_then(|builder| builder.add_service(service("b000", "beta")))
+            .expect("failed to add members and services");
+        let circuit = builder.clone().build().expect("failed to build circuit");
+
+        let payload_bytes = builder
+            .build_signed_payload("alpha", &*signer)
+            .expect("failed to build payload");
+
+        let mut payload: CircuitManagementPayload =
+            Message::parse_from_bytes(&payload_bytes).expect("failed to parse payload");
+        let header: CircuitManagementPayload_Header =
+            Message::parse_from_bytes(payload.get_header()).expect("failed to parse header");
+
+        assert_eq!(
+            header.get_action(),
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST
+        );
+        assert_eq!(header.get_requester_node_id(), "alpha");
+        assert_eq!(
+            header.get_requester(),
+            signer.public_key().expect("no public key").as_slice()
+        );
+        assert!(context
+            .new_verifier()
+            .verify(
+                payload.get_header(),
+                &Signature::new(payload.get_signature().to_vec()),
+                &PublicKey::new(header.get_requester().to_vec()),
+            )
+            .expect("failed to verify signature"));
+
+        let proposed =
+            CreateCircuit::from_proto(payload.take_circuit_create_request().take_circuit())
+                .expect("failed to convert proposed circuit");
+        assert_eq!(proposed, circuit);
+
+        // the proposed circuit may be turned back into a builder for another proposal
+        let rebuilt = CreateCircuitBuilder::from(proposed)
+            .build()
+            .expect("failed to rebuild circuit");
+        assert_eq!(rebuilt, circuit);
+    }
+
+    /// Verify that the builder rejects duplicate members and services as they are added.
+    #[test]
+    fn add_duplicates() {
+        let builder = CreateCircuitBuilder::new()
+            .add_member(node("alpha"))
+            .expect("failed to add member");
+        assert!(matches!(
+            builder.clone().add_member(node("alpha")),
+            Err(BuilderError::InvalidField(_))
+        ));
+
+        let builder = builder
+            .add_service(service("a000", "alpha"))
+            .expect("failed to add service");
+        assert!(matches!(
+            builder.clone().add_service(service("a000", "alpha")),
+            Err(BuilderError::InvalidField(_))
+        ));
+        let invalid_service = SplinterService {
+            service_id: "not-valid".into(),
+            ..service("a001", "alpha")
+        };
+        assert!(matches!(
+            builder.add_service(invalid_service),
+            Err(BuilderError::InvalidField(_))
+        ));
+    }
+
+    /// Verify that no payload is created if the requester or a service's allowed node is not a
+    /// member of the circuit.
+    #[test]
+    fn signed_payload_non_member() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+
+        let builder = CreateCircuitBuilder::new()
+            .with_circuit_management_type("mgmt_type")
+            .with_members(&[node("alpha")]);
+
+        assert!(matches!(
+            builder
+                .clone()
+                .with_roster(&[service("a000", "alpha")])
+                .build_signed_payload("beta", &*signer),
+            Err(PayloadError::InvalidCircuit(_))
+        ));
+        assert!(matches!(
+            builder
+                .with_roster(&[service("b000", "beta")])
+                .build_signed_payload("alpha", &*signer),
+            Err(PayloadError::InvalidCircuit(_))
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Metrics that follow circuit proposals from validation to the circuit being ready.
+//!
+//! The following metrics are reported:
+//!
+//! - `splinter.admin.proposals.validation_time`: time taken to validate a proposed change
+//! - `splinter.admin.proposals.failures`: proposed changes that failed, by reason
+//! - `splinter.admin.proposals.submitted`: committed proposals, by management and proposal type
+//! - `splinter.admin.proposals.votes`: committed votes, by management type and vote
+//! - `splinter.admin.proposals.accepted`/`rejected`: proposals that completed voting
+//! - `splinter.admin.proposals.voting_time`: time from a proposal's commit until it is accepted
+//!   or rejected
+//! - `splinter.admin.proposals.creation_time`: time from a create proposal's acceptance until
+//!   its circuit is ready
+//! - `splinter.admin.proposals.latency`: time from a create proposal's commit until its circuit
+//!   is ready
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::protos::admin::{CircuitProposalVote_Vote, CircuitProposal_ProposalType};
+
+use super::error::AdminSharedError;
+
+/// When a proposal reached each phase, by circuit ID.
+///
+/// Only proposals committed since the admin service started are tracked; proposals loaded from
+/// the store are not timed.
+#[derive(Default)]
+pub struct ProposalMetrics {
+    proposals: HashMap<String, ProposalTimes>,
+}
+
+struct ProposalTimes {
+    committed: Instant,
+    accepted: Option<Instant>,
+}
+
+impl ProposalMetrics {
+    /// Records the time taken to validate a proposed change and, if it failed, the reason.
+    pub fn validated<T>(&self, elapsed: Duration, result: &Result<T, AdminSharedError>) {
+        let outcome = match result {
+            Ok(_) => "ok",
+            Err(err) => {
+                counter!("splinter.admin.proposals.failures", 1, "reason" => failure_reason(err));
+                "failed"
+            }
+        };
+        histogram!(
+            "splinter.admin.proposals.validation_time",
+            elapsed.as_secs_f64(),
+            "outcome" => outcome
+        );
+    }
+
+    /// Records that a new proposal has been committed.
+    pub fn submitted(
+        &mut self,
+        circuit_id: &str,
+        management_type: &str,
+        proposal_type: CircuitProposal_ProposalType,
+    ) {
+        counter!(
+            "splinter.admin.proposals.submitted",
+            1,
+            "management_type" => management_type.to_string(),
+            "proposal_type" => proposal_type_label(proposal_type)
+        );
+        self.proposals.insert(
+            circuit_id.to_string(),
+            ProposalTimes {
+                committed: Instant::now(),
+                accepted: None,
+            },
+        );
+    }
+
+    /// Records a committed vote on a proposal.
+    pub fn voted(&self, management_type: &str, vote: CircuitProposalVote_Vote) {
+        let vote = match vote {
+            CircuitProposalVote_Vote::ACCEPT => "accept",
+            CircuitProposalVote_Vote::REJECT => "reject",
+            CircuitProposalVote_Vote::UNSET_VOTE => "unset",
+        };
+        counter!(
+            "splinter.admin.proposals.votes",
+            1,
+            "management_type" => management_type.to_string(),
+            "vote" => vote
+        );
+    }
+
+    /// Records that a proposal has been accepted by all members.
+    ///
+    /// Create proposals continue to be tracked until their circuit is ready.
+    pub fn accepted(
+        &mut self,
+        circuit_id: &str,
+        management_type: &str,
+        proposal_type: CircuitProposal_ProposalType,
+    ) {
+        counter!(
+            "splinter.admin.proposals.accepted",
+            1,
+            "management_type" => management_type.to_string(),
+            "proposal_type" => proposal_type_label(proposal_type)
+        );
+
+        if proposal_type == CircuitProposal_ProposalType::CREATE {
+            if let Some(times) = self.proposals.get_mut(circuit_id) {
+                times.accepted = Some(Instant::now());
+                record_voting_time(times, management_type);
+            }
+        } else if let Some(times) = self.proposals.remove(circuit_id) {
+            record_voting_time(&times, management_type);
+        }
+    }
+
+    /// Records that a proposal has been rejected.
+    pub fn rejected(
+        &mut self,
+        circuit_id: &str,
+        management_type: &str,
+        proposal_type: CircuitProposal_ProposalType,
+    ) {
+        counter!(
+            "splinter.admin.proposals.rejected",
+            1,
+            "management_type" => management_type.to_string(),
+            "proposal_type" => proposal_type_label(proposal_type)
+        );
+        if let Some(times) = self.proposals.remove(circuit_id) {
+            record_voting_time(&times, management_type);
+        }
+    }
+
+    /// Records that the circuit created by an accepted proposal is ready.
+    pub fn circuit_ready(&mut self, circuit_id: &str, management_type: &str) {
+        if let Some(times) = self.proposals.remove(circuit_id) {
+            if let Some(accepted) = times.accepted {
+                histogram!(
+                    "splinter.admin.proposals.creation_time",
+                    accepted.elapsed().as_secs_f64(),
+                    "management_type" => management_type.to_string()
+                );
+            }
+            histogram!(
+                "splinter.admin.proposals.latency",
+                times.committed.elapsed().as_secs_f64(),
+                "management_type" => management_type.to_string()
+            );
+        }
+    }
+
+    /// Stops tracking a proposal that was removed before completing.
+    pub fn removed(&mut self, circuit_id: &str) {
+        self.proposals.remove(circuit_id);
+    }
+}
+
+fn record_voting_time(times: &ProposalTimes, management_type: &str) {
+    let voting_time = times
+        .accepted
+        .unwrap_or_else(Instant::now)
+        .duration_since(times.committed);
+    histogram!(
+        "splinter.admin.proposals.voting_time",
+        voting_time.as_secs_f64(),
+        "management_type" => management_type.to_string()
+    );
+}
+
+fn proposal_type_label(proposal_type: CircuitProposal_ProposalType) -> &'static str {
+    match proposal_type {
+        CircuitProposal_ProposalType::CREATE => "create",
+        CircuitProposal_ProposalType::UPDATE_ROSTER => "update_roster",
+        CircuitProposal_ProposalType::ADD_NODE => "add_node",
+        CircuitProposal_ProposalType::REMOVE_NODE => "remove_node",
+        CircuitProposal_ProposalType::DISBAND => "disband",
+        CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => "unset",
+    }
+}
+
+fn failure_reason(err: &AdminSharedError) -> &'static str {
+    match err {
+        AdminSharedError::ValidationFailed(_) => "validation_failed",
+        AdminSharedError::UnknownAction(_) => "unknown_action",
+        AdminSharedError::InvalidMessageFormat(_) => "invalid_message_format",
+        AdminSharedError::HashError(_) => "hash_error",
+        AdminSharedError::SplinterStateError(_) => "state_error",
+        AdminSharedError::ServiceProtocolError(_) => "protocol_error",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a create proposal is tracked until its circuit is ready and that other
+    /// proposals are no longer tracked once voting completes.
+    #[test]
+    fn test_proposal_tracking() {
+        let mut metrics = ProposalMetrics::default();
+
+        metrics.submitted("abcde-01234", "test", CircuitProposal_ProposalType::CREATE);
+        metrics.submitted("abcde-56789", "test", CircuitProposal_ProposalType::DISBAND);
+        metrics.submitted("fghij-01234", "test", CircuitProposal_ProposalType::CREATE);
+        assert_eq!(metrics.proposals.len(), 3);
+
+        metrics.accepted("abcde-01234", "test", CircuitProposal_ProposalType::CREATE);
+        assert!(metrics.proposals["abcde-01234"].accepted.is_some());
+
+        metrics.accepted("abcde-56789", "test", CircuitProposal_ProposalType::DISBAND);
+        assert!(!metrics.proposals.contains_key("abcde-56789"));
+
+        metrics.rejected("fghij-01234", "test", CircuitProposal_ProposalType::CREATE);
+        assert!(!metrics.proposals.contains_key("fghij-01234"));
+
+        metrics.circuit_ready("abcde-01234", "test");
+        assert!(metrics.proposals.is_empty());
+    }
+}
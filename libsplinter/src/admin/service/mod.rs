@@ -15,10 +15,18 @@
 mod builder;
 mod consensus;
 pub(crate) mod error;
+#[cfg(feature = "admin-service-proposal-limits")]
+mod limits;
 pub(crate) mod messages;
+#[cfg(feature = "admin-service-metrics")]
+mod metrics;
 pub mod proposal_store;
+#[cfg(feature = "circuit-purge-preview")]
+mod purge;
 mod shared;
 mod subscriber;
+#[cfg(feature = "admin-service-proposal-validation")]
+mod validation;
 
 use std::any::Any;
 use std::collections::HashMap;
@@ -30,10 +38,14 @@ use cylinder::Verifier as SignatureVerifier;
 use openssl::hash::{hash, MessageDigest};
 use protobuf::{self, Message};
 
+#[cfg(feature = "admin-service-vote-delegation")]
+use crate::admin::delegation::VoteDelegation;
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::{self, AdminServiceStore};
 use crate::admin::token::PeerAuthorizationTokenReader;
 use crate::circuit::routing::{self, RoutingTableWriter};
+#[cfg(feature = "admin-service-consensus-retry")]
+use crate::consensus::two_phase::v1::VerificationRetry;
 use crate::consensus::Proposal;
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
@@ -41,6 +53,8 @@ use crate::peer::{PeerManagerConnector, PeerManagerNotification, PeerTokenPair};
 use crate::protos::admin::{
     AdminMessage, AdminMessage_Type, CircuitManagementPayload, ServiceProtocolVersionResponse,
 };
+#[cfg(feature = "registry-node-signing")]
+use crate::registry::verify_node_signature;
 #[cfg(feature = "registry")]
 use crate::registry::RegistryReader;
 use crate::service::instance::{
@@ -57,8 +71,14 @@ pub use self::builder::AdminServiceBuilder;
 pub use self::error::AdminKeyVerifierError;
 pub use self::error::AdminServiceError;
 pub use self::error::AdminSubscriberError;
+#[cfg(feature = "admin-service-proposal-limits")]
+pub use self::limits::{ProposalLimits, ProposalThrottled};
+#[cfg(feature = "circuit-purge-preview")]
+pub use self::purge::{CircuitPurgeReport, ServicePurgeReport, ServicePurgeStatus};
 pub use self::shared::AdminServiceStatus;
 pub use self::subscriber::AdminServiceEventSubscriber;
+#[cfg(feature = "admin-service-proposal-validation")]
+pub use self::validation::PayloadValidation;
 
 const ADMIN_SERVICE_PROTOCOL_MIN: u32 = 1;
 pub(crate) const ADMIN_SERVICE_PROTOCOL_VERSION: u32 = 2;
@@ -69,6 +89,13 @@ pub trait AdminCommands: Send + Sync {
         circuit_change: CircuitManagementPayload,
     ) -> Result<(), AdminServiceError>;
 
+    /// Validates a circuit create request as if it had been submitted, without proposing it.
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn validate_circuit_change(
+        &self,
+        circuit_change: &CircuitManagementPayload,
+    ) -> Result<PayloadValidation, AdminServiceError>;
+
     fn add_event_subscriber(
         &self,
         event_type: &str,
@@ -83,6 +110,31 @@ pub trait AdminCommands: Send + Sync {
 
     fn admin_service_status(&self) -> Result<AdminServiceStatus, AdminServiceError>;
 
+    /// Returns the purge report for the given circuit: a preview of the local services that
+    /// would be purged if the circuit exists, or the per-service results if it was recently
+    /// purged.
+    #[cfg(feature = "circuit-purge-preview")]
+    fn purge_report(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<CircuitPurgeReport>, AdminServiceError>;
+
+    /// Adds a vote delegation, after checking that it was signed by the delegator key.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn add_vote_delegation(&self, delegation: VoteDelegation) -> Result<(), AdminServiceError>;
+
+    /// Removes the vote delegation between the given keys, returning false if there was none.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn remove_vote_delegation(
+        &self,
+        delegator: &crate::public_key::PublicKey,
+        delegate: &crate::public_key::PublicKey,
+    ) -> Result<bool, AdminServiceError>;
+
+    /// Lists the vote delegations held by this node.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn list_vote_delegations(&self) -> Result<Vec<VoteDelegation>, AdminServiceError>;
+
     fn clone_boxed(&self) -> Box<dyn AdminCommands>;
 }
 
@@ -123,6 +175,63 @@ impl AdminKeyVerifier for Box<dyn RegistryReader> {
     }
 }
 
+/// An `AdminKeyVerifier` that only trusts registry entries that were signed by the node itself.
+///
+/// The key is permitted if and only if the node with the given `node_id` exists in the registry,
+/// the node's entry has a valid signature made by one of the node's keys, and the node has the
+/// given key.
+#[cfg(feature = "registry-node-signing")]
+pub struct SignedRegistryKeyVerifier {
+    registry: Box<dyn RegistryReader>,
+    signature_verifier: Box<dyn SignatureVerifier>,
+}
+
+#[cfg(feature = "registry-node-signing")]
+impl SignedRegistryKeyVerifier {
+    /// Create a new verifier that reads nodes from the given registry and checks their signatures
+    /// with the given signature verifier.
+    pub fn new(
+        registry: Box<dyn RegistryReader>,
+        signature_verifier: Box<dyn SignatureVerifier>,
+    ) -> Self {
+        Self {
+            registry,
+            signature_verifier,
+        }
+    }
+}
+
+#[cfg(feature = "registry-node-signing")]
+impl AdminKeyVerifier for SignedRegistryKeyVerifier {
+    fn is_permitted(&self, node_id: &str, key: &[u8]) -> Result<bool, AdminKeyVerifierError> {
+        let node = match self.registry.get_node(node_id).map_err(|err| {
+            AdminKeyVerifierError::new_with_source(
+                &format!("Failed to lookup node '{}' in registry", node_id),
+                Box::new(err),
+            )
+        })? {
+            Some(node) => node,
+            None => return Ok(false),
+        };
+
+        let signed = verify_node_signature(&node, &*self.signature_verifier).map_err(|err| {
+            AdminKeyVerifierError::new_with_source(
+                &format!("Failed to verify registry entry for node '{}'", node_id),
+                Box::new(err),
+            )
+        })?;
+        if !signed {
+            warn!(
+                "Registry entry for node '{}' is not signed by the node; its keys are not trusted",
+                node_id
+            );
+            return Ok(false);
+        }
+
+        Ok(node.has_key(&to_hex(key)))
+    }
+}
+
 pub struct Events {
     inner: Box<dyn ExactSizeIterator<Item = store::AdminServiceEvent> + Send>,
 }
@@ -141,6 +250,12 @@ pub struct AdminService {
     admin_service_shared: Arc<Mutex<AdminServiceShared>>,
     /// The coordinator timeout for the two-phase commit consensus engine
     coordinator_timeout: Duration,
+    /// The maximum coordinator timeout that a proposal may request
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    max_coordinator_timeout: Duration,
+    /// How the coordinator re-sends unanswered verification requests
+    #[cfg(feature = "admin-service-consensus-retry")]
+    verification_retry: Option<VerificationRetry>,
     consensus: Option<AdminConsensusManager>,
     peer_connector: PeerManagerConnector,
     peer_notification_run_state: Option<(usize, JoinHandle<()>)>,
@@ -174,7 +289,7 @@ impl AdminService {
             .with_admin_key_verifier(key_verifier)
             .with_key_permission_manager(key_permission_manager)
             .with_routing_table_writer(routing_table_writer)
-            .with_admin_event_store(event_store)
+            .with_admin_event_store(Box::new(event_store))
             .with_service_arg_validators(service_arg_validators);
 
         if let Some(coordinator_timeout) = coordinator_timeout {
@@ -344,7 +459,8 @@ impl AdminService {
                             .map(|node| node.node_id().to_string())
                             .collect(),
                         circuit.authorization_type().into(),
-                    ),
+                    )
+                    .with_max_message_size(circuit.max_message_size()),
                     routing_members,
                 )
                 .map_err(|err| ServiceStartError::Internal(err.reduce_to_string()))?;
@@ -580,6 +696,10 @@ impl ServiceInstance for AdminService {
             self.service_id().into(),
             self.admin_service_shared.clone(),
             self.coordinator_timeout,
+            #[cfg(feature = "admin-service-proposal-timeout")]
+            self.max_coordinator_timeout,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            self.verification_retry,
         )
         .map_err(|err| {
             ServiceStartError::Internal(format!("Unable to start consensus: {}", err))
@@ -739,6 +859,13 @@ impl ServiceInstance for AdminService {
                     ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
                 })?;
 
+                #[cfg(feature = "circuit-max-message-size")]
+                shared.add_member_max_message_size(
+                    circuit_id,
+                    member_node_id,
+                    member_ready.get_max_message_size(),
+                );
+
                 shared
                     .add_ready_member(circuit_id, member_node_id.into())
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
@@ -843,6 +970,61 @@ impl ServiceInstance for AdminService {
                 );
                 Ok(())
             }
+            #[cfg(feature = "admin-service-partition-recovery")]
+            AdminMessage_Type::CIRCUIT_SYNC_REQUEST => {
+                let mut admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?;
+
+                admin_service_shared
+                    .on_circuit_sync_request(&message_context.sender)
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+            }
+            #[cfg(feature = "admin-service-partition-recovery")]
+            AdminMessage_Type::CIRCUIT_SYNC_RESPONSE => {
+                let mut admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?;
+
+                admin_service_shared
+                    .on_circuit_sync_response(
+                        &message_context.sender,
+                        admin_message.get_circuit_sync_response().clone(),
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+            }
+            #[cfg(not(feature = "admin-service-partition-recovery"))]
+            AdminMessage_Type::CIRCUIT_SYNC_REQUEST | AdminMessage_Type::CIRCUIT_SYNC_RESPONSE => {
+                debug!("Ignoring circuit sync message; partition recovery is not enabled");
+                Ok(())
+            }
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            AdminMessage_Type::PROPOSAL_VALIDATION_FAILED => {
+                let validation_failed = admin_message.get_proposal_validation_failed();
+
+                let mut admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?;
+
+                admin_service_shared.on_proposal_validation_failed(
+                    validation_failed.get_circuit_id(),
+                    validation_failed.get_member_node_id(),
+                    validation_failed.get_reason(),
+                );
+                Ok(())
+            }
+            #[cfg(not(feature = "admin-service-rejection-reasons"))]
+            AdminMessage_Type::PROPOSAL_VALIDATION_FAILED => {
+                let validation_failed = admin_message.get_proposal_validation_failed();
+
+                warn!(
+                    "Member {} failed to validate the proposal for circuit {}: {}",
+                    validation_failed.get_member_node_id(),
+                    validation_failed.get_circuit_id(),
+                    validation_failed.get_reason()
+                );
+                Ok(())
+            }
             AdminMessage_Type::UNSET => Err(ServiceError::InvalidMessageFormat(Box::new(
                 AdminError::MessageTypeUnset,
             ))),
@@ -885,11 +1067,38 @@ impl AdminCommands for AdminServiceCommands {
         self.shared
             .lock()
             .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
-            .submit(circuit_change)?;
+            .submit(circuit_change)
+            .map_err(|err| match err {
+                #[cfg(feature = "admin-service-proposal-limits")]
+                ServiceError::UnableToHandleMessage(err) => {
+                    match err.downcast::<AdminSharedError>() {
+                        Ok(err) => match *err {
+                            AdminSharedError::Throttled(throttled) => {
+                                AdminServiceError::Throttled(throttled)
+                            }
+                            err => ServiceError::UnableToHandleMessage(Box::new(err)).into(),
+                        },
+                        Err(err) => ServiceError::UnableToHandleMessage(err).into(),
+                    }
+                }
+                err => err.into(),
+            })?;
 
         Ok(())
     }
 
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn validate_circuit_change(
+        &self,
+        circuit_change: &CircuitManagementPayload,
+    ) -> Result<PayloadValidation, AdminServiceError> {
+        Ok(self
+            .shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .validate_payload(circuit_change)?)
+    }
+
     fn add_event_subscriber(
         &self,
         event_type: &str,
@@ -916,8 +1125,19 @@ impl AdminCommands for AdminServiceCommands {
             .lock()
             .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
             .get_events_since(since_event_id, event_type)
-            .map_err(|err| {
-                AdminServiceError::general_error_with_source("Unable to get events", Box::new(err))
+            .map_err(|err| match err {
+                #[cfg(feature = "admin-service-event-retention")]
+                AdminSharedError::EventsTruncated {
+                    since,
+                    truncated_to,
+                } => AdminServiceError::EventsTruncated {
+                    since,
+                    truncated_to,
+                },
+                err => AdminServiceError::general_error_with_source(
+                    "Unable to get events",
+                    Box::new(err),
+                ),
             })
     }
 
@@ -929,6 +1149,72 @@ impl AdminCommands for AdminServiceCommands {
             .admin_service_status())
     }
 
+    #[cfg(feature = "circuit-purge-preview")]
+    fn purge_report(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<CircuitPurgeReport>, AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .purge_report(circuit_id)
+            .map_err(|err| {
+                AdminServiceError::general_error_with_source(
+                    "Unable to get purge report",
+                    Box::new(err),
+                )
+            })
+    }
+
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn add_vote_delegation(&self, delegation: VoteDelegation) -> Result<(), AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .add_vote_delegation(delegation)
+            .map_err(|err| match err {
+                AdminSharedError::ValidationFailed(_) => AdminServiceError::ServiceError(
+                    ServiceError::UnableToHandleMessage(Box::new(err)),
+                ),
+                _ => AdminServiceError::general_error_with_source(
+                    "Unable to add vote delegation",
+                    Box::new(err),
+                ),
+            })
+    }
+
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn remove_vote_delegation(
+        &self,
+        delegator: &crate::public_key::PublicKey,
+        delegate: &crate::public_key::PublicKey,
+    ) -> Result<bool, AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .remove_vote_delegation(delegator, delegate)
+            .map_err(|err| {
+                AdminServiceError::general_error_with_source(
+                    "Unable to remove vote delegation",
+                    Box::new(err),
+                )
+            })
+    }
+
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn list_vote_delegations(&self) -> Result<Vec<VoteDelegation>, AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .list_vote_delegations()
+            .map_err(|err| {
+                AdminServiceError::general_error_with_source(
+                    "Unable to list vote delegations",
+                    Box::new(err),
+                )
+            })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminCommands> {
         Box::new(self.clone())
     }
@@ -1111,7 +1397,7 @@ mod tests {
             .with_admin_key_verifier(Box::new(MockAdminKeyVerifier))
             .with_key_permission_manager(Box::new(AllowAllKeyPermissionManager))
             .with_routing_table_writer(writer)
-            .with_admin_event_store(event_store);
+            .with_admin_event_store(Box::new(event_store));
 
         let mut admin_service = admin_service_builder
             .build()
@@ -13,8 +13,12 @@
 // limitations under the License.
 
 use crate::admin::service::messages::CircuitProposal;
+#[cfg(feature = "admin-service-rejection-reasons")]
+use crate::admin::service::messages::{ProposalRejection, ValidationFailure, Vote};
 use crate::admin::store::AdminServiceStore;
 use crate::admin::store::CircuitPredicate;
+#[cfg(feature = "admin-service-rejection-reasons")]
+use crate::admin::store::EventType;
 
 use super::error::ProposalStoreError;
 use super::proposal_iter::ProposalIter;
@@ -59,4 +63,51 @@ impl ProposalStore for AdminServiceProposals {
             })
             .transpose()
     }
+
+    /// Finds the rejected proposal in the admin service events that are still retained.
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    fn rejected_proposal(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<(CircuitProposal, ProposalRejection)>, ProposalStoreError> {
+        let rejected_event = self
+            .admin_store
+            .list_events_since(0)
+            .map_err(|err| ProposalStoreError::from_source("Unable to get events", Box::new(err)))?
+            .filter(|event| {
+                event.proposal().circuit_id() == circuit_id
+                    && matches!(
+                        event.event_type(),
+                        EventType::ProposalRejected { .. } | EventType::ProposalInvalid { .. }
+                    )
+            })
+            .last();
+
+        let event = match rejected_event {
+            Some(event) => event,
+            None => return Ok(None),
+        };
+
+        let proposal = CircuitProposal::from(event.proposal().clone());
+
+        let rejection = match event.event_type() {
+            EventType::ProposalInvalid {
+                node_id, reason, ..
+            } => ProposalRejection::ValidationFailed(ValidationFailure {
+                node_id: node_id.to_string(),
+                reason: reason.to_string(),
+            }),
+            _ => match proposal
+                .votes
+                .iter()
+                .rev()
+                .find(|vote_record| vote_record.vote == Vote::Reject)
+            {
+                Some(vote_record) => ProposalRejection::Vote(vote_record.clone()),
+                None => return Ok(None),
+            },
+        };
+
+        Ok(Some((proposal, rejection)))
+    }
 }
@@ -15,6 +15,8 @@
 use crate::admin::store::CircuitPredicate;
 
 use crate::admin::service::messages::CircuitProposal;
+#[cfg(feature = "admin-service-rejection-reasons")]
+use crate::admin::service::messages::ProposalRejection;
 
 use super::error::ProposalStoreError;
 use super::proposal_iter::ProposalIter;
@@ -26,4 +28,14 @@ pub trait ProposalStore: Send + Sync {
         -> Result<ProposalIter, ProposalStoreError>;
 
     fn proposal(&self, circuit_id: &str) -> Result<Option<CircuitProposal>, ProposalStoreError>;
+
+    /// Return the most recently rejected proposal for the given circuit, along with the reason it
+    /// was rejected. Stores that do not keep rejected proposals return `None`.
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    fn rejected_proposal(
+        &self,
+        _circuit_id: &str,
+    ) -> Result<Option<(CircuitProposal, ProposalRejection)>, ProposalStoreError> {
+        Ok(None)
+    }
 }
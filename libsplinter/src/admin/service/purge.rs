@@ -0,0 +1,159 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Reports describing the local services affected by purging a circuit.
+
+/// The local services affected by purging a circuit.
+///
+/// Before the circuit is purged, the report previews which services would be purged and how much
+/// state they hold. Once the circuit has been purged, it records the outcome for each service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitPurgeReport {
+    circuit_id: String,
+    services: Vec<ServicePurgeReport>,
+}
+
+impl CircuitPurgeReport {
+    pub(crate) fn new(circuit_id: &str, services: Vec<ServicePurgeReport>) -> Self {
+        Self {
+            circuit_id: circuit_id.to_string(),
+            services,
+        }
+    }
+
+    /// Returns the ID of the circuit being purged.
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the reports for the local services on the circuit.
+    pub fn services(&self) -> &[ServicePurgeReport] {
+        &self.services
+    }
+
+    /// Returns true if the circuit has been purged, i.e. no service is still pending.
+    pub fn is_complete(&self) -> bool {
+        !self
+            .services
+            .iter()
+            .any(|service| service.status == ServicePurgeStatus::Pending)
+    }
+
+    /// Sets the status of every service in the report.
+    pub(crate) fn set_all_statuses(&mut self, status: ServicePurgeStatus) {
+        for service in self.services.iter_mut() {
+            service.status = status.clone();
+        }
+    }
+
+    /// Sets the status of the service with the given ID.
+    pub(crate) fn set_status(&mut self, service_id: &str, status: ServicePurgeStatus) {
+        if let Some(service) = self
+            .services
+            .iter_mut()
+            .find(|service| service.service_id == service_id)
+        {
+            service.status = status;
+        }
+    }
+}
+
+/// A local service that is affected by purging a circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServicePurgeReport {
+    service_id: String,
+    service_type: String,
+    state_size: Option<u64>,
+    status: ServicePurgeStatus,
+}
+
+impl ServicePurgeReport {
+    pub(crate) fn new(service_id: &str, service_type: &str, state_size: Option<u64>) -> Self {
+        Self {
+            service_id: service_id.to_string(),
+            service_type: service_type.to_string(),
+            state_size,
+            status: ServicePurgeStatus::Pending,
+        }
+    }
+
+    /// Returns the ID of the service.
+    pub fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    /// Returns the type of the service.
+    pub fn service_type(&self) -> &str {
+        &self.service_type
+    }
+
+    /// Returns the number of bytes of state held by the service before it was purged, if known.
+    pub fn state_size(&self) -> Option<u64> {
+        self.state_size
+    }
+
+    /// Returns the purge status of the service.
+    pub fn status(&self) -> &ServicePurgeStatus {
+        &self.status
+    }
+}
+
+/// The outcome of purging a single service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServicePurgeStatus {
+    /// The circuit has not been purged yet.
+    Pending,
+    /// The service's state was removed.
+    Purged,
+    /// The circuit was purged, but the service's state was kept.
+    Kept,
+    /// The service's state could not be removed; contains the error message.
+    Failed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a report is only complete once every service has a final status, and that
+    /// the status of a single service can be updated.
+    #[test]
+    fn test_purge_report_statuses() {
+        let mut report = CircuitPurgeReport::new(
+            "abcde-01234",
+            vec![
+                ServicePurgeReport::new("a000", "scabbard", Some(1024)),
+                ServicePurgeReport::new("a001", "scabbard", None),
+            ],
+        );
+        assert!(!report.is_complete());
+
+        report.set_status("a000", ServicePurgeStatus::Purged);
+        assert!(!report.is_complete());
+
+        report.set_status("a001", ServicePurgeStatus::Failed("error".into()));
+        assert!(report.is_complete());
+        assert_eq!(report.services()[0].status(), &ServicePurgeStatus::Purged);
+        assert_eq!(report.services()[0].state_size(), Some(1024));
+        assert_eq!(
+            report.services()[1].status(),
+            &ServicePurgeStatus::Failed("error".into())
+        );
+
+        report.set_all_statuses(ServicePurgeStatus::Kept);
+        assert!(report
+            .services()
+            .iter()
+            .all(|service| service.status() == &ServicePurgeStatus::Kept));
+    }
+}
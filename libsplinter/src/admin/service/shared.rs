@@ -21,11 +21,14 @@ use std::time::Instant;
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
 use protobuf::{Message, RepeatedField};
 
+#[cfg(feature = "admin-service-vote-delegation")]
+use crate::admin::delegation::{VoteDelegation, VoteDelegationStore};
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::{
-    AdminServiceStore, Circuit as StoreCircuit, CircuitBuilder as StoreCircuitBuilder,
-    CircuitPredicate, CircuitProposal as StoreProposal, CircuitStatus as StoreCircuitStatus,
-    ProposalType, ProposedCircuit, Service as StoreService, Vote, VoteRecordBuilder,
+    error::AdminServiceStoreError, AdminServiceEventStore, AdminServiceStore,
+    Circuit as StoreCircuit, CircuitBuilder as StoreCircuitBuilder, CircuitPredicate,
+    CircuitProposal as StoreProposal, CircuitStatus as StoreCircuitStatus, ProposalType,
+    ProposedCircuit, Service as StoreService, Vote, VoteRecordBuilder,
 };
 use crate::admin::token::{PeerAuthorizationTokenReader, PeerNode};
 use crate::admin::CIRCUIT_PROTOCOL_VERSION;
@@ -35,7 +38,11 @@ use crate::error::InternalError;
 use crate::hex::parse_hex;
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
+#[cfg(feature = "challenge-trust-policy")]
+use crate::network::auth::ChallengeTrustPolicy;
 use crate::peer::{PeerAuthorizationToken, PeerManagerConnector, PeerRef, PeerTokenPair};
+#[cfg(feature = "admin-service-rejection-reasons")]
+use crate::protos::admin::ProposalValidationFailed;
 use crate::protos::admin::{
     AbandonedCircuit, AdminMessage, AdminMessage_Type, Circuit, CircuitManagementPayload,
     CircuitManagementPayload_Action, CircuitManagementPayload_Header, CircuitProposal,
@@ -44,12 +51,24 @@ use crate::protos::admin::{
     Circuit_PersistenceType, Circuit_RouteType, MemberReady, RemovedProposal,
     ServiceProtocolVersionRequest, SplinterNode, SplinterService,
 };
+#[cfg(feature = "admin-service-partition-recovery")]
+use crate::protos::admin::{CircuitSyncRequest, CircuitSyncResponse};
 use crate::public_key;
 use crate::service::instance::{ServiceArgValidator, ServiceError, ServiceNetworkSender};
+#[cfg(feature = "tracing")]
+use crate::tracing::Span;
 
 use super::error::{AdminSharedError, MarshallingError};
+#[cfg(feature = "admin-service-proposal-limits")]
+use super::limits::{ProposalLimiter, ProposalLimits};
 use super::messages;
+#[cfg(feature = "admin-service-metrics")]
+use super::metrics::ProposalMetrics;
+#[cfg(feature = "circuit-purge-preview")]
+use super::purge::{CircuitPurgeReport, ServicePurgeReport, ServicePurgeStatus};
 use super::subscriber::SubscriberMap;
+#[cfg(feature = "admin-service-proposal-validation")]
+use super::validation::PayloadValidation;
 use super::{admin_service_id, sha256, AdminKeyVerifier, AdminServiceEventSubscriber, Events};
 use super::{ADMIN_SERVICE_PROTOCOL_MIN, ADMIN_SERVICE_PROTOCOL_VERSION};
 
@@ -57,6 +76,9 @@ static VOTER_ROLE: &str = "voter";
 static PROPOSER_ROLE: &str = "proposer";
 const ADMIN_SERVICE_PUBLIC_KEY_PREFIX: &str = "public_key";
 const DEFAULT_HOLD_PEER_SECS: u64 = 10;
+// The number of completed purge reports that are kept after their circuits have been removed
+#[cfg(feature = "circuit-purge-preview")]
+const MAX_RETAINED_PURGE_REPORTS: usize = 32;
 
 pub enum PayloadType {
     Circuit(CircuitManagementPayload),
@@ -147,12 +169,44 @@ pub struct AdminServiceShared {
     admin_service_status: AdminServiceStatus,
     routing_table_writer: Box<dyn RoutingTableWriter>,
     // Mailbox of AdminServiceEvent values
-    event_store: Box<dyn AdminServiceStore>,
+    event_store: Box<dyn AdminServiceEventStore>,
     public_keys: Vec<public_key::PublicKey>,
     token_to_peer: HashMap<PeerTokenPair, PeerNodePair>,
     // Temporarily hold on to peers that should be removed. This helps avoid dropping messages
     // when removing a proposal.
     peers_to_be_removed: Vec<(Instant, Vec<PeerTokenPair>)>,
+    // Peers that have disconnected, kept so the circuits and proposals shared with them can be
+    // re-validated when they reconnect
+    #[cfg(feature = "admin-service-partition-recovery")]
+    partitioned_peers: HashMap<PeerTokenPair, PeerNodePair>,
+    // Reconnected peers whose shared state will be re-validated once a protocol is agreed on
+    #[cfg(feature = "admin-service-partition-recovery")]
+    pending_reconciliations: HashSet<PeerTokenPair>,
+    // Reports for the most recently purged circuits, oldest first
+    #[cfg(feature = "circuit-purge-preview")]
+    purge_reports: VecDeque<CircuitPurgeReport>,
+    // Authorized public keys of the members of active circuits, shared with the authorization
+    // manager
+    #[cfg(feature = "challenge-trust-policy")]
+    trust_policy: Option<ChallengeTrustPolicy>,
+    // Delegations that are attached to the votes of local delegate keys
+    #[cfg(feature = "admin-service-vote-delegation")]
+    vote_delegation_store: Option<Box<dyn VoteDelegationStore>>,
+    // Tracks proposals through their lifecycle to report latency metrics
+    #[cfg(feature = "admin-service-metrics")]
+    proposal_metrics: ProposalMetrics,
+    // The largest message payload this node accepts on a circuit, if limited
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<u64>,
+    // The message size limits reported by the members of uninitialized circuits, by circuit ID
+    #[cfg(feature = "circuit-max-message-size")]
+    member_max_message_sizes: HashMap<String, HashMap<String, u64>>,
+    // Whether purged circuits are tombstoned in the admin store rather than removed
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: bool,
+    // Enforces the limits on proposals submitted by local clients
+    #[cfg(feature = "admin-service-proposal-limits")]
+    proposal_limiter: ProposalLimiter,
 }
 
 impl AdminServiceShared {
@@ -167,7 +221,7 @@ impl AdminServiceShared {
         key_verifier: Box<dyn AdminKeyVerifier>,
         key_permission_manager: Box<dyn KeyPermissionManager>,
         routing_table_writer: Box<dyn RoutingTableWriter>,
-        admin_service_event_store: Box<dyn AdminServiceStore>,
+        admin_service_event_store: Box<dyn AdminServiceEventStore>,
         public_keys: Vec<public_key::PublicKey>,
     ) -> Self {
         AdminServiceShared {
@@ -197,7 +251,166 @@ impl AdminServiceShared {
             public_keys,
             token_to_peer: HashMap::new(),
             peers_to_be_removed: Vec::new(),
+            #[cfg(feature = "admin-service-partition-recovery")]
+            partitioned_peers: HashMap::new(),
+            #[cfg(feature = "admin-service-partition-recovery")]
+            pending_reconciliations: HashSet::new(),
+            #[cfg(feature = "circuit-purge-preview")]
+            purge_reports: VecDeque::new(),
+            #[cfg(feature = "challenge-trust-policy")]
+            trust_policy: None,
+            #[cfg(feature = "admin-service-vote-delegation")]
+            vote_delegation_store: None,
+            #[cfg(feature = "admin-service-metrics")]
+            proposal_metrics: ProposalMetrics::default(),
+            #[cfg(feature = "circuit-max-message-size")]
+            max_message_size: None,
+            #[cfg(feature = "circuit-max-message-size")]
+            member_max_message_sizes: HashMap::new(),
+            #[cfg(feature = "circuit-tombstones")]
+            circuit_tombstones: false,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            proposal_limiter: ProposalLimiter::default(),
+        }
+    }
+
+    /// Sets the limits on proposals submitted by local clients.
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn set_proposal_limits(&mut self, proposal_limits: ProposalLimits) {
+        self.proposal_limiter = ProposalLimiter::new(proposal_limits);
+    }
+
+    /// Sets whether purged circuits are tombstoned in the admin store instead of being removed.
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn set_circuit_tombstones(&mut self, circuit_tombstones: bool) {
+        self.circuit_tombstones = circuit_tombstones;
+    }
+
+    /// Sets the largest message payload, in bytes, that this node accepts on a circuit.
+    ///
+    /// The limit is sent to the other members when a circuit is created, and new circuits are
+    /// limited to the smallest value configured by their members.
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn set_max_message_size(&mut self, max_message_size: u64) {
+        self.max_message_size = Some(max_message_size);
+    }
+
+    /// Records the message size limit a member reported when it became ready for a circuit.
+    ///
+    /// A limit of 0 means the member does not limit message size.
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn add_member_max_message_size(
+        &mut self,
+        circuit_id: &str,
+        member_node_id: &str,
+        max_message_size: u64,
+    ) {
+        if max_message_size > 0 {
+            self.member_max_message_sizes
+                .entry(circuit_id.to_string())
+                .or_default()
+                .insert(member_node_id.to_string(), max_message_size);
+        }
+    }
+
+    /// Sets the store of vote delegations.
+    ///
+    /// When a key submits a vote for a node it is not registered for, a delegation from the
+    /// store that allows the key to vote for that node is attached to the vote.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    pub fn set_vote_delegation_store(&mut self, store: Box<dyn VoteDelegationStore>) {
+        self.vote_delegation_store = Some(store);
+    }
+
+    /// Adds a vote delegation to the store, after checking that it was signed by the delegator
+    /// key.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    pub fn add_vote_delegation(&self, delegation: VoteDelegation) -> Result<(), AdminSharedError> {
+        let store = self.vote_delegation_store()?;
+
+        let verified = delegation
+            .verify_signature(&*self.signature_verifier)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Unable to verify vote delegation signature: {}",
+                    err
+                ))
+            })?;
+        if !verified {
+            return Err(AdminSharedError::ValidationFailed(
+                "Vote delegation was not signed by the delegator key".to_string(),
+            ));
+        }
+
+        Ok(store.add_delegation(delegation)?)
+    }
+
+    /// Removes the vote delegation between the given keys, returning false if there was none.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    pub fn remove_vote_delegation(
+        &self,
+        delegator: &public_key::PublicKey,
+        delegate: &public_key::PublicKey,
+    ) -> Result<bool, AdminSharedError> {
+        Ok(self
+            .vote_delegation_store()?
+            .remove_delegation(delegator, delegate)?)
+    }
+
+    /// Lists the stored vote delegations.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    pub fn list_vote_delegations(&self) -> Result<Vec<VoteDelegation>, AdminSharedError> {
+        Ok(self
+            .vote_delegation_store()?
+            .list_delegations(None)?
+            .collect())
+    }
+
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn vote_delegation_store(&self) -> Result<&dyn VoteDelegationStore, AdminSharedError> {
+        self.vote_delegation_store.as_deref().ok_or_else(|| {
+            AdminSharedError::SplinterStateError(
+                "Admin service has no vote delegation store".to_string(),
+            )
+        })
+    }
+
+    /// Sets the challenge trust policy, populating it with the authorized public keys of the
+    /// members of the active circuits in the admin store.
+    ///
+    /// Only active circuits, which every member has voted to accept, contribute keys; a proposal
+    /// cannot grant a key the right to stand in for another node.
+    #[cfg(feature = "challenge-trust-policy")]
+    pub fn set_trust_policy(
+        &mut self,
+        trust_policy: ChallengeTrustPolicy,
+    ) -> Result<(), AdminSharedError> {
+        for circuit in self
+            .admin_store
+            .list_circuits(&[CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active)])?
+        {
+            trust_policy.set_circuit_keys(circuit.circuit_id(), member_authorized_keys(&circuit));
+        }
+        self.trust_policy = Some(trust_policy);
+        Ok(())
+    }
+
+    /// Updates the challenge trust policy after the circuit with the given ID has been changed
+    /// in the admin store.
+    #[cfg(feature = "challenge-trust-policy")]
+    fn sync_trust_policy(&self, circuit_id: &str) -> Result<(), AdminSharedError> {
+        let trust_policy = match &self.trust_policy {
+            Some(trust_policy) => trust_policy,
+            None => return Ok(()),
+        };
+
+        match self.admin_store.get_circuit(circuit_id)? {
+            Some(circuit) if circuit.circuit_status() == &StoreCircuitStatus::Active => {
+                trust_policy.set_circuit_keys(circuit_id, member_authorized_keys(&circuit))
+            }
+            _ => trust_policy.remove_circuit(circuit_id),
         }
+        Ok(())
     }
 
     pub fn node_id(&self) -> &str {
@@ -358,6 +571,14 @@ impl AdminServiceShared {
                     .circuit_management_type
                     .clone();
 
+                #[cfg(feature = "admin-service-metrics")]
+                if action == CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE {
+                    if let Some(vote_record) = circuit_proposal.get_votes().last() {
+                        self.proposal_metrics
+                            .voted(&mgmt_type, vote_record.get_vote());
+                    }
+                }
+
                 match self.check_approved(&circuit_proposal) {
                     CircuitProposalStatus::Accepted => {
                         let status = circuit_proposal.get_circuit_proposal().get_circuit_status();
@@ -395,6 +616,8 @@ impl AdminServiceShared {
                                     ))
                                 })
                                 .and_then(|_| self.remove_proposal(store_circuit.circuit_id()))?;
+                            #[cfg(feature = "challenge-trust-policy")]
+                            self.sync_trust_policy(circuit_id)?;
 
                             self.update_metrics()?;
 
@@ -440,6 +663,8 @@ impl AdminServiceShared {
                         } else {
                             // commit new circuit
                             self.admin_store.upgrade_proposal_to_circuit(circuit_id)?;
+                            #[cfg(feature = "challenge-trust-policy")]
+                            self.sync_trust_policy(circuit_id)?;
 
                             self.update_metrics()?;
 
@@ -471,7 +696,8 @@ impl AdminServiceShared {
                                     .map(|node| node.node_id().to_string())
                                     .collect(),
                                 circuit.authorization_type().into(),
-                            );
+                            )
+                            .with_max_message_size(circuit.max_message_size());
 
                             let routing_members = circuit_proposal
                                 .get_circuit_proposal()
@@ -520,6 +746,10 @@ impl AdminServiceShared {
                                 let mut member_ready = MemberReady::new();
                                 member_ready.set_circuit_id(circuit_id.to_string());
                                 member_ready.set_member_node_id(self.node_id.clone());
+                                #[cfg(feature = "circuit-max-message-size")]
+                                if let Some(max_message_size) = self.max_message_size {
+                                    member_ready.set_max_message_size(max_message_size);
+                                }
                                 let mut msg = AdminMessage::new();
                                 msg.set_message_type(AdminMessage_Type::MEMBER_READY);
                                 msg.set_member_ready(member_ready);
@@ -546,6 +776,13 @@ impl AdminServiceShared {
                                 }
                             }
                         }
+                        #[cfg(feature = "admin-service-metrics")]
+                        self.proposal_metrics.accepted(
+                            circuit_id,
+                            &mgmt_type,
+                            circuit_proposal.get_proposal_type(),
+                        );
+
                         // add circuit as pending further service handling
                         self.add_uninitialized_circuit(circuit_proposal.clone())?;
 
@@ -556,6 +793,12 @@ impl AdminServiceShared {
                             CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
                                 self.add_proposal(circuit_proposal.clone())?;
                                 self.update_metrics()?;
+                                #[cfg(feature = "admin-service-metrics")]
+                                self.proposal_metrics.submitted(
+                                    circuit_id,
+                                    &mgmt_type,
+                                    circuit_proposal.get_proposal_type(),
+                                );
                                 // notify registered application authorization handlers of the
                                 // committed circuit proposal
                                 let event = messages::AdminServiceEvent::ProposalSubmitted(
@@ -592,6 +835,12 @@ impl AdminServiceShared {
                             CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
                                 self.add_proposal(circuit_proposal.clone())?;
                                 self.update_metrics()?;
+                                #[cfg(feature = "admin-service-metrics")]
+                                self.proposal_metrics.submitted(
+                                    circuit_id,
+                                    &mgmt_type,
+                                    circuit_proposal.get_proposal_type(),
+                                );
                                 // notify registered application authorization handlers of the
                                 // committed disband circuit proposal
                                 let event = messages::AdminServiceEvent::ProposalSubmitted(
@@ -617,6 +866,12 @@ impl AdminServiceShared {
                         // remove circuit
                         let proposal = self.remove_proposal(circuit_id)?;
                         self.update_metrics()?;
+                        #[cfg(feature = "admin-service-metrics")]
+                        self.proposal_metrics.rejected(
+                            circuit_id,
+                            &mgmt_type,
+                            circuit_proposal.get_proposal_type(),
+                        );
                         if let Some(proposal) = proposal {
                             self.peers_to_be_removed.push((
                                 Instant::now(),
@@ -662,7 +917,140 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Tells the requester's node that this node failed to validate a proposed change, so that
+    /// the requester can see why the proposal was rejected.
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    pub fn notify_validation_failure(&mut self, payload: &CircuitManagementPayload, reason: &str) {
+        let header: CircuitManagementPayload_Header =
+            match Message::parse_from_bytes(payload.get_header()) {
+                Ok(header) => header,
+                Err(err) => {
+                    debug!("Unable to report validation failure: {}", err);
+                    return;
+                }
+            };
+
+        let requester_node_id = header.get_requester_node_id();
+        if requester_node_id == self.node_id {
+            return;
+        }
+
+        let circuit_id = match header.get_action() {
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => payload
+                .get_circuit_create_request()
+                .get_circuit()
+                .get_circuit_id(),
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+                payload.get_circuit_proposal_vote().get_circuit_id()
+            }
+            CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+                payload.get_circuit_disband_request().get_circuit_id()
+            }
+            _ => return,
+        };
+
+        let (peer_id, local_peer_token) = match self
+            .token_to_peer
+            .iter()
+            .find(|(_, peer_node_pair)| peer_node_pair.peer_node.node_id == requester_node_id)
+        {
+            Some((peer_id, peer_node_pair)) => {
+                (peer_id.clone(), peer_node_pair.local_peer_token.clone())
+            }
+            None => {
+                debug!(
+                    "Unable to report validation failure for circuit {}; requester node {} is \
+                     unknown",
+                    circuit_id, requester_node_id
+                );
+                return;
+            }
+        };
+
+        let mut validation_failed = ProposalValidationFailed::new();
+        validation_failed.set_circuit_id(circuit_id.to_string());
+        validation_failed.set_member_node_id(self.node_id.to_string());
+        validation_failed.set_reason(reason.to_string());
+
+        let mut msg = AdminMessage::new();
+        msg.set_message_type(AdminMessage_Type::PROPOSAL_VALIDATION_FAILED);
+        msg.set_proposal_validation_failed(validation_failed);
+
+        if let Err(err) = self.send_to_peer_admin_service(&peer_id, local_peer_token, msg) {
+            warn!(
+                "Unable to report validation failure for circuit {} to {}: {}",
+                circuit_id, requester_node_id, err
+            );
+        }
+    }
+
+    /// Records that a member failed to validate the change this node requested for the given
+    /// circuit, emitting a `ProposalInvalid` event with the member's reason.
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    pub fn on_proposal_validation_failed(
+        &mut self,
+        circuit_id: &str,
+        member_node_id: &str,
+        reason: &str,
+    ) {
+        warn!(
+            "Member {} failed to validate the proposal for circuit {}: {}",
+            member_node_id, circuit_id, reason
+        );
+
+        let context = match &self.pending_changes {
+            Some(context) if context.circuit_proposal.get_circuit_id() == circuit_id => context,
+            _ => return,
+        };
+
+        let mgmt_type = context
+            .circuit_proposal
+            .get_circuit_proposal()
+            .get_circuit_management_type()
+            .to_string();
+        let requester = context.signer_public_key.clone();
+
+        let proposal = match messages::CircuitProposal::from_proto(context.circuit_proposal.clone())
+        {
+            Ok(proposal) => proposal,
+            Err(err) => {
+                error!(
+                    "Unable to convert proposal for circuit {}: {}",
+                    circuit_id, err
+                );
+                return;
+            }
+        };
+
+        let event = messages::AdminServiceEvent::ProposalInvalid((
+            proposal,
+            requester,
+            messages::ValidationFailure {
+                node_id: member_node_id.to_string(),
+                reason: reason.to_string(),
+            },
+        ));
+        self.send_event(&mgmt_type, event);
+    }
+
     pub fn propose_change(
+        &mut self,
+        circuit_payload: CircuitManagementPayload,
+    ) -> Result<(String, CircuitProposal), AdminSharedError> {
+        #[cfg(feature = "admin-service-metrics")]
+        let validation_start = Instant::now();
+
+        let result = self.prepare_change(circuit_payload);
+
+        #[cfg(feature = "admin-service-metrics")]
+        self.proposal_metrics
+            .validated(validation_start.elapsed(), &result);
+
+        result
+    }
+
+    /// Validates a proposed change and sets it as the pending change.
+    fn prepare_change(
         &mut self,
         mut circuit_payload: CircuitManagementPayload,
     ) -> Result<(String, CircuitProposal), AdminSharedError> {
@@ -786,12 +1174,20 @@ impl AdminServiceShared {
                 }
                 let signer_public_key = header.get_requester();
 
-                self.validate_circuit_vote(
-                    proposal_vote,
+                self.voter_public_key(
+                    &circuit_payload,
                     signer_public_key,
-                    &circuit_proposal,
                     header.get_requester_node_id(),
+                    &circuit_proposal,
                 )
+                .and_then(|voter_public_key| {
+                    self.validate_circuit_vote(
+                        proposal_vote,
+                        &voter_public_key,
+                        &circuit_proposal,
+                        header.get_requester_node_id(),
+                    )
+                })
                 .map_err(|err| {
                     if circuit_proposal.proposal_type() == &ProposalType::Create {
                         match circuit_proposal.circuit().list_tokens(&self.node_id) {
@@ -819,19 +1215,24 @@ impl AdminServiceShared {
                     }
                 };
 
-                let vote_record = VoteRecordBuilder::new()
+                let mut vote_record_builder = VoteRecordBuilder::new()
                     .with_public_key(&public_key::PublicKey::from_bytes(
                         signer_public_key.to_vec(),
                     ))
                     .with_vote(&vote)
-                    .with_voter_node_id(header.get_requester_node_id())
-                    .build()
-                    .map_err(|err| {
-                        AdminSharedError::SplinterStateError(format!(
-                            "Unable to build vote record: {}",
-                            err
-                        ))
-                    })?;
+                    .with_voter_node_id(header.get_requester_node_id());
+
+                if !proposal_vote.get_comment().is_empty() {
+                    vote_record_builder =
+                        vote_record_builder.with_comment(proposal_vote.get_comment());
+                }
+
+                let vote_record = vote_record_builder.build().map_err(|err| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to build vote record: {}",
+                        err
+                    ))
+                })?;
 
                 let mut votes = circuit_proposal.votes().to_vec();
                 votes.push(vote_record);
@@ -1147,8 +1548,9 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    /// Attempts to purge a circuit and the associated internal Splinter services
-    fn purge_circuit(&mut self, circuit_id: &str) -> Result<(), ServiceError> {
+    /// Attempts to purge a circuit and the associated internal Splinter services. If `keep_state`
+    /// is true, the services' state is left in place.
+    fn purge_circuit(&mut self, circuit_id: &str, keep_state: bool) -> Result<(), ServiceError> {
         // Verifying the circuit is able to be purged
         let stored_circuit = self
             .admin_store
@@ -1167,14 +1569,50 @@ impl AdminServiceShared {
                 )))
             })?;
 
-        self.purge_services(circuit_id, stored_circuit.roster())
-            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+        #[cfg(feature = "circuit-purge-preview")]
+        let mut report = self.preview_purge(circuit_id, stored_circuit.roster());
 
-        if let Some(circuit) = self
-            .remove_circuit(circuit_id)
-            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
+        if keep_state {
+            info!(
+                "Keeping state of local services while purging circuit {}",
+                circuit_id
+            );
+            #[cfg(feature = "circuit-purge-preview")]
+            report.set_all_statuses(ServicePurgeStatus::Kept);
+        } else {
+            let _failures = self
+                .purge_services(circuit_id, stored_circuit.roster())
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+            #[cfg(feature = "circuit-purge-preview")]
+            {
+                report.set_all_statuses(ServicePurgeStatus::Purged);
+                for (service_id, err) in _failures {
+                    report.set_status(&service_id, ServicePurgeStatus::Failed(err));
+                }
+            }
+        }
+
+        #[cfg(feature = "circuit-tombstones")]
+        let removed = if self.circuit_tombstones {
+            let reason = if keep_state {
+                "purged; service state kept"
+            } else {
+                "purged"
+            };
+            self.tombstone_circuit(circuit_id, reason)
+        } else {
+            self.remove_circuit(circuit_id)
+        };
+        #[cfg(not(feature = "circuit-tombstones"))]
+        let removed = self.remove_circuit(circuit_id);
+
+        if let Some(circuit) =
+            removed.map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
         {
             debug!("Purged circuit {}", circuit.circuit_id());
+            #[cfg(feature = "circuit-purge-preview")]
+            self.retain_purge_report(report);
             Ok(())
         } else {
             Err(ServiceError::UnableToHandleMessage(Box::new(
@@ -1255,6 +1693,9 @@ impl AdminServiceShared {
                     format!("Unable to update circuit {}", circuit_id),
                 )))
             })?;
+        #[cfg(feature = "challenge-trust-policy")]
+        self.sync_trust_policy(circuit_id)
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
         gauge!(
             "splinter.admin.circuits.active",
@@ -1339,6 +1780,9 @@ impl AdminServiceShared {
                 }
             }
 
+            #[cfg(feature = "admin-service-metrics")]
+            self.proposal_metrics.removed(circuit_id);
+
             // Remove the proposal itself
             self.remove_proposal(circuit_id)
                 .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
@@ -1562,6 +2006,24 @@ impl AdminServiceShared {
     }
 
     pub fn submit(&mut self, payload: CircuitManagementPayload) -> Result<(), ServiceError> {
+        #[cfg(feature = "tracing")]
+        let mut span =
+            Span::new("admin.submit").with_attribute("circuit_id", payload_circuit_id(&payload));
+        #[cfg(feature = "tracing")]
+        let _guard = span.enter();
+
+        let result = self.submit_payload(payload);
+
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            span.set_error(&err.to_string());
+        }
+
+        result
+    }
+
+    /// Validates a payload submitted by a local client and proposes it to the circuit members.
+    fn submit_payload(&mut self, payload: CircuitManagementPayload) -> Result<(), ServiceError> {
         debug!("Payload submitted: {:?}", payload);
 
         let header = Message::parse_from_bytes(payload.get_header())?;
@@ -1569,6 +2031,27 @@ impl AdminServiceShared {
             .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
         self.verify_signature(&payload)?;
 
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if matches!(
+            header.get_action(),
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST
+                | CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST
+        ) {
+            let admin_store = &self.admin_store;
+            self.proposal_limiter
+                .check(
+                    &to_hex(header.get_requester()),
+                    || admin_store.count_proposals(&[]),
+                    Instant::now(),
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
+                .map_err(|throttled| {
+                    ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::Throttled(
+                        throttled,
+                    )))
+                })?;
+        }
+
         match header.get_action() {
             CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
                 let signer_public_key = header.get_requester();
@@ -1584,6 +2067,10 @@ impl AdminServiceShared {
                 self.propose_circuit(payload, "local".to_string())
             }
             CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+                #[cfg(feature = "admin-service-vote-delegation")]
+                let payload = self
+                    .attach_vote_delegation(payload, &header)
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
                 let proposal_vote = payload.get_circuit_proposal_vote();
 
                 // validate vote proposal
@@ -1608,12 +2095,20 @@ impl AdminServiceShared {
                     })?;
 
                 let signer_public_key = header.get_requester();
-                self.validate_circuit_vote(
-                    proposal_vote,
+                self.voter_public_key(
+                    &payload,
                     signer_public_key,
-                    &circuit_proposal,
                     header.get_requester_node_id(),
+                    &circuit_proposal,
                 )
+                .and_then(|voter_public_key| {
+                    self.validate_circuit_vote(
+                        proposal_vote,
+                        &voter_public_key,
+                        &circuit_proposal,
+                        header.get_requester_node_id(),
+                    )
+                })
                 .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
                 self.propose_vote(payload, "local".to_string())
@@ -1648,24 +2143,47 @@ impl AdminServiceShared {
             CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
                 let signer_public_key = header.get_requester();
                 let requester_node_id = header.get_requester_node_id();
-                let circuit_id = payload.get_circuit_purge_request().get_circuit_id();
+                let purge_request = payload.get_circuit_purge_request();
+                let circuit_id = purge_request.get_circuit_id();
                 debug!("received purge request for circuit {}", circuit_id);
 
                 self.validate_purge_request(circuit_id, signer_public_key, requester_node_id)
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
-                self.purge_circuit(circuit_id)
+                #[cfg(feature = "circuit-purge-keep-state")]
+                let keep_state = purge_request.get_keep_state();
+                #[cfg(not(feature = "circuit-purge-keep-state"))]
+                let keep_state = false;
+
+                self.purge_circuit(circuit_id, keep_state)
             }
             CircuitManagementPayload_Action::CIRCUIT_ABANDON => {
                 let signer_public_key = header.get_requester();
                 let requester_node_id = header.get_requester_node_id();
-                let circuit_id = payload.get_circuit_abandon().get_circuit_id();
+                let abandon = payload.get_circuit_abandon();
+                let circuit_id = abandon.get_circuit_id();
                 debug!("received abandon request for circuit {}", circuit_id);
 
                 self.validate_abandon_circuit(circuit_id, signer_public_key, requester_node_id)
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
-                self.abandon_circuit(circuit_id)
+                self.abandon_circuit(circuit_id)?;
+
+                #[cfg(feature = "circuit-abandon-purge")]
+                if abandon.get_purge() {
+                    debug!("purging abandoned circuit {}", circuit_id);
+                    // The abandon has already been applied, so it is left in place if the purge
+                    // fails; the purge may be retried with a separate purge request
+                    if let Err(err) = self.purge_circuit(circuit_id, abandon.get_keep_state()) {
+                        warn!(
+                            "Circuit {} was abandoned, but could not be purged: {}",
+                            circuit_id, err
+                        );
+                        return Err(err);
+                    }
+                }
+
+                Ok(())
             }
             CircuitManagementPayload_Action::PROPOSAL_REMOVE_REQUEST => {
                 let signer_public_key = header.get_requester();
@@ -1692,6 +2210,112 @@ impl AdminServiceShared {
         }
     }
 
+    /// Validates a circuit create request as `submit` would, without proposing it.
+    ///
+    /// In addition to the checks made on submission, this verifies that the members' public keys
+    /// are valid, that the services to be run on this node have a supported type, and that the
+    /// circuit's schema version is supported by the admin protocol agreed on with each member.
+    /// Members this node has not yet agreed on a protocol with are reported as warnings.
+    #[cfg(feature = "admin-service-proposal-validation")]
+    pub fn validate_payload(
+        &self,
+        payload: &CircuitManagementPayload,
+    ) -> Result<PayloadValidation, ServiceError> {
+        let header: CircuitManagementPayload_Header =
+            Message::parse_from_bytes(payload.get_header())?;
+
+        if header.get_action() != CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST {
+            return Err(ServiceError::UnableToHandleMessage(Box::new(
+                AdminSharedError::ValidationFailed(format!(
+                    "Only circuit create requests can be validated, not {:?}",
+                    header.get_action()
+                )),
+            )));
+        }
+
+        let mut validation = PayloadValidation::new();
+
+        if let Err(err) = self.validate_circuit_management_payload(payload, &header) {
+            validation.add_error(err.to_string());
+            return Ok(validation);
+        }
+
+        match self.verify_signature(payload) {
+            Ok(true) => (),
+            Ok(false) => validation.add_error("CircuitManagementPayload signature is invalid"),
+            Err(err) => validation.add_error(format!("Unable to verify signature: {}", err)),
+        }
+
+        let circuit = payload.get_circuit_create_request().get_circuit();
+
+        // Use the lowest protocol version agreed on with the members, as the proposal would
+        let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+        match (circuit.get_node_token(&self.node_id), circuit.list_nodes()) {
+            (Ok(Some(local_required_auth)), Ok(members)) => {
+                for member in members
+                    .iter()
+                    .filter(|member| member.node_id != self.node_id)
+                {
+                    match self.service_protocols.get(&PeerTokenPair::new(
+                        member.token.clone(),
+                        local_required_auth.clone(),
+                    )) {
+                        Some(protocol_version) if *protocol_version < protocol => {
+                            protocol = *protocol_version
+                        }
+                        Some(_) => (),
+                        None => validation.add_warning(format!(
+                            "The admin protocol version of member {} is not yet known, so                              schema version compatibility with it could not be checked",
+                            member.node_id
+                        )),
+                    }
+                }
+            }
+            // The local node is not a member, or the members are invalid; this is reported by
+            // the circuit validation below
+            _ => (),
+        }
+
+        if let Err(err) = self.validate_create_circuit(
+            circuit,
+            header.get_requester(),
+            header.get_requester_node_id(),
+            protocol,
+        ) {
+            validation.add_error(err.to_string());
+        }
+
+        for member in circuit.get_members() {
+            if !member.get_public_key().is_empty() {
+                if let Err(err) = self.validate_key(member.get_public_key()) {
+                    validation.add_error(format!("Member {}: {}", member.get_node_id(), err));
+                }
+            }
+        }
+
+        for service in circuit
+            .get_roster()
+            .iter()
+            .filter(|service| service.get_allowed_nodes().contains(&self.node_id))
+        {
+            let support = self
+                .lifecycle_dispatch
+                .iter()
+                .filter_map(|dispatch| dispatch.supports_service_type(service.get_service_type()))
+                .collect::<Vec<_>>();
+            if !support.is_empty() && !support.contains(&true) {
+                validation.add_error(format!(
+                    "Service {} has type {}, which is not supported by node {}",
+                    service.get_service_id(),
+                    service.get_service_type(),
+                    self.node_id
+                ));
+            }
+        }
+
+        Ok(validation)
+    }
+
     /// Handle a new circuit proposal
     ///
     /// This operation will accept a new circuit proposal.  If there is no peer connection, a
@@ -1702,6 +2326,13 @@ impl AdminServiceShared {
         payload: CircuitManagementPayload,
         message_sender: String,
     ) -> Result<(), ServiceError> {
+        #[cfg(feature = "tracing")]
+        let span = Span::new("admin.handle_proposed_circuit")
+            .with_attribute("circuit_id", payload_circuit_id(&payload))
+            .with_attribute("requester_node_id", &message_sender);
+        #[cfg(feature = "tracing")]
+        let _guard = span.enter();
+
         let mut missing_protocol_ids = vec![];
         let mut pending_peers = vec![];
         let mut added_peers: Vec<PeerTokenPair> = vec![];
@@ -1901,7 +2532,7 @@ impl AdminServiceShared {
                 circuit_management_type.to_string(),
                 *since_event_id,
             )
-            .map_err(|err| AdminSharedError::UnableToAddSubscriber(err.to_string()))?;
+            .map_err(events_error)?;
         Ok(Events {
             inner: Box::new(events),
         })
@@ -1916,14 +2547,14 @@ impl AdminServiceShared {
         let events = if circuit_management_type == "*" {
             self.event_store
                 .list_events_since(*since_event_id)
-                .map_err(|err| AdminSharedError::UnableToAddSubscriber(err.to_string()))?
+                .map_err(events_error)?
         } else {
             self.event_store
                 .list_events_by_management_type_since(
                     circuit_management_type.to_string(),
                     *since_event_id,
                 )
-                .map_err(|err| AdminSharedError::UnableToAddSubscriber(err.to_string()))?
+                .map_err(events_error)?
         };
         Ok(Events {
             inner: Box::new(events),
@@ -1996,6 +2627,12 @@ impl AdminServiceShared {
             // add payloads that are not waiting on peer connection
             unpeered_payloads.extend(peering);
             self.unpeered_payloads = unpeered_payloads;
+
+            #[cfg(feature = "admin-service-partition-recovery")]
+            {
+                self.pending_reconciliations.remove(&peer_id);
+                self.partitioned_peers.insert(peer_id, peer_node_pair);
+            }
         }
     }
 
@@ -2022,6 +2659,20 @@ impl AdminServiceShared {
             return Ok(());
         }
 
+        // If the peer was disconnected, restore its service information so the protocol can be
+        // agreed on again, after which the state shared with the peer is re-validated
+        #[cfg(feature = "admin-service-partition-recovery")]
+        if let Some(peer_node_pair) = self.partitioned_peers.remove(peer_id) {
+            info!(
+                "Peer {} has reconnected; shared circuits will be re-validated",
+                peer_id
+            );
+            self.token_to_peer
+                .entry(peer_id.clone())
+                .or_insert(peer_node_pair);
+            self.pending_reconciliations.insert(peer_id.clone());
+        }
+
         let peer_node_pair = match self.token_to_peer.get(peer_id) {
             Some(peer_node_pair) => peer_node_pair,
             None => {
@@ -2116,27 +2767,322 @@ impl AdminServiceShared {
             }
         };
 
-        self.update_pending_for_protocol_agreement(service_id, peer_token, protocol)
+        // A peer that reconnected after a partition is asked for its view of the shared circuits
+        // once a protocol has been agreed on
+        #[cfg(feature = "admin-service-partition-recovery")]
+        let reconcile_peer = if self.pending_reconciliations.remove(&peer_token) && protocol != 0 {
+            Some(peer_token.clone())
+        } else {
+            None
+        };
+
+        self.update_pending_for_protocol_agreement(service_id, peer_token, protocol)?;
+
+        #[cfg(feature = "admin-service-partition-recovery")]
+        if let Some(peer_token) = reconcile_peer {
+            self.request_circuit_sync(&peer_token)?;
+        }
+
+        Ok(())
     }
 
-    fn update_pending_for_protocol_agreement(
-        &mut self,
-        service_id: String,
-        token: PeerTokenPair,
-        protocol: u32,
-    ) -> Result<(), AdminSharedError> {
-        // Update any unpeered payloads that this service might be a member of
-        let mut unpeered_payloads = std::mem::take(&mut self.unpeered_payloads);
-        for pending_protocol_payload in unpeered_payloads.iter_mut() {
-            match protocol {
-                0 => {
-                    if pending_protocol_payload
-                        .missing_protocol_ids
-                        .iter()
-                        .any(|missing_protocol_id| missing_protocol_id.admin_service == service_id)
-                    {
-                        warn!(
-                            "Dropping circuit request including service {}, \
+    /// Request a reconnected peer's view of the circuits and proposals this node shares with it.
+    /// The peer's response is handled by `on_circuit_sync_response`.
+    #[cfg(feature = "admin-service-partition-recovery")]
+    fn request_circuit_sync(&mut self, peer_id: &PeerTokenPair) -> Result<(), AdminSharedError> {
+        let local_peer_token = match self.token_to_peer.get(peer_id) {
+            Some(peer_node_pair) => peer_node_pair.local_peer_token.clone(),
+            None => {
+                warn!(
+                    "Unable to re-validate shared circuits; missing service information for peer \
+                     token: {}",
+                    peer_id
+                );
+                return Ok(());
+            }
+        };
+
+        let mut msg = AdminMessage::new();
+        msg.set_message_type(AdminMessage_Type::CIRCUIT_SYNC_REQUEST);
+        msg.set_circuit_sync_request(CircuitSyncRequest::new());
+
+        debug!("Sending circuit sync request to {}", peer_id);
+        self.send_to_peer_admin_service(peer_id, local_peer_token, msg)
+    }
+
+    /// Respond to a peer's request for the circuits and proposals this node shares with it.
+    #[cfg(feature = "admin-service-partition-recovery")]
+    pub fn on_circuit_sync_request(&mut self, service_id: &str) -> Result<(), AdminSharedError> {
+        let peer_token =
+            get_peer_token_from_service_id(service_id, &self.node_id).map_err(|err| {
+                AdminSharedError::ServiceProtocolError(format!(
+                    "Unable to verify peer token for service id: {}",
+                    err
+                ))
+            })?;
+        let peer_node_id = match self.token_to_peer.get(&peer_token) {
+            Some(peer_node_pair) => peer_node_pair.peer_node.node_id.clone(),
+            None => {
+                warn!(
+                    "Ignoring circuit sync request; missing service information for peer token: \
+                     {}",
+                    peer_token
+                );
+                return Ok(());
+            }
+        };
+        let members = vec![self.node_id.clone(), peer_node_id];
+
+        let mut response = CircuitSyncResponse::new();
+        response.set_circuit_ids(RepeatedField::from_vec(
+            self.admin_store
+                .list_circuits(&[
+                    CircuitPredicate::MembersInclude(members.clone()),
+                    CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
+                ])?
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect(),
+        ));
+        response.set_proposals(RepeatedField::from_vec(
+            self.admin_store
+                .list_proposals(&[CircuitPredicate::MembersInclude(members)])?
+                .map(StoreProposal::into_proto)
+                .collect(),
+        ));
+
+        let mut msg = AdminMessage::new();
+        msg.set_message_type(AdminMessage_Type::CIRCUIT_SYNC_RESPONSE);
+        msg.set_circuit_sync_response(response);
+
+        // The response is sent from the local id the peer used to reach this node
+        let local_peer_token = peer_token.local_id().clone();
+        self.send_to_peer_admin_service(&peer_token, local_peer_token, msg)
+    }
+
+    /// Re-validate the circuits and proposals this node shares with a reconnected peer against
+    /// the peer's view of them.
+    ///
+    /// Differences between the active circuits are logged, as they can only be resolved by the
+    /// circuit's members. Proposals that were committed by the peer while it was partitioned from
+    /// this node are added, along with the peer's own votes on them; each peer is only trusted to
+    /// report its own votes, the votes of other members are recovered when they reconnect. The
+    /// `ProposalSubmitted` and `ProposalVote` events that were missed are emitted for the
+    /// recovered proposals and votes, so that event subscribers can reconcile their own state.
+    #[cfg(feature = "admin-service-partition-recovery")]
+    pub fn on_circuit_sync_response(
+        &mut self,
+        service_id: &str,
+        mut response: CircuitSyncResponse,
+    ) -> Result<(), AdminSharedError> {
+        let peer_token =
+            get_peer_token_from_service_id(service_id, &self.node_id).map_err(|err| {
+                AdminSharedError::ServiceProtocolError(format!(
+                    "Unable to verify peer token for service id: {}",
+                    err
+                ))
+            })?;
+        let peer_node_id = match self.token_to_peer.get(&peer_token) {
+            Some(peer_node_pair) => peer_node_pair.peer_node.node_id.clone(),
+            None => {
+                warn!(
+                    "Ignoring circuit sync response; missing service information for peer \
+                     token: {}",
+                    peer_token
+                );
+                return Ok(());
+            }
+        };
+
+        let local_circuits = self
+            .admin_store
+            .list_circuits(&[
+                CircuitPredicate::MembersInclude(vec![self.node_id.clone(), peer_node_id.clone()]),
+                CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
+            ])?
+            .map(|circuit| circuit.circuit_id().to_string())
+            .collect::<HashSet<_>>();
+        let peer_circuits = response
+            .take_circuit_ids()
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        for circuit_id in peer_circuits.difference(&local_circuits) {
+            warn!(
+                "Circuit {} is active on peer {} but not on this node",
+                circuit_id, peer_node_id
+            );
+        }
+        for circuit_id in local_circuits.difference(&peer_circuits) {
+            warn!(
+                "Circuit {} is active on this node but not on peer {}",
+                circuit_id, peer_node_id
+            );
+        }
+
+        let mut recovered_proposals = 0;
+        let mut recovered_votes = 0;
+        for proto in response.take_proposals().into_iter() {
+            let peer_proposal = StoreProposal::from_proto(proto).map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received invalid proposal from peer {}: {}",
+                    peer_node_id, err
+                ))
+            })?;
+            let circuit_id = peer_proposal.circuit_id().to_string();
+
+            let members = peer_proposal.circuit().members();
+            if !members.iter().any(|node| node.node_id() == self.node_id)
+                || !members.iter().any(|node| node.node_id() == peer_node_id)
+            {
+                warn!(
+                    "Ignoring proposal for circuit {} from peer {}; both nodes must be members",
+                    circuit_id, peer_node_id
+                );
+                continue;
+            }
+
+            let peer_votes = peer_proposal
+                .votes()
+                .iter()
+                .filter(|vote| vote.voter_node_id() == peer_node_id)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let (proposal, new_votes) = match self.get_proposal(&circuit_id)? {
+                Some(local_proposal) => {
+                    if local_proposal.circuit_hash() != peer_proposal.circuit_hash() {
+                        warn!(
+                            "Proposal for circuit {} differs from the proposal on peer {}",
+                            circuit_id, peer_node_id
+                        );
+                        continue;
+                    }
+
+                    let new_votes = peer_votes
+                        .into_iter()
+                        .filter(|vote| {
+                            !local_proposal
+                                .votes()
+                                .iter()
+                                .any(|local| local.voter_node_id() == vote.voter_node_id())
+                        })
+                        .collect::<Vec<_>>();
+                    if new_votes.is_empty() {
+                        continue;
+                    }
+
+                    let mut votes = local_proposal.votes().to_vec();
+                    votes.extend(new_votes.iter().cloned());
+                    let proposal = local_proposal
+                        .builder()
+                        .with_votes(&votes)
+                        .build()
+                        .map_err(|err| AdminSharedError::SplinterStateError(err.to_string()))?;
+                    self.update_proposal(proposal.clone())?;
+
+                    (proposal, new_votes)
+                }
+                None => {
+                    // A proposal to create a circuit that is already active is stale
+                    if peer_proposal.proposal_type() == &ProposalType::Create
+                        && local_circuits.contains(&circuit_id)
+                    {
+                        continue;
+                    }
+
+                    let proposal = peer_proposal
+                        .builder()
+                        .with_votes(&peer_votes)
+                        .build()
+                        .map_err(|err| AdminSharedError::SplinterStateError(err.to_string()))?;
+                    self.add_proposal(proposal.clone())?;
+                    recovered_proposals += 1;
+
+                    info!(
+                        "Recovered proposal for circuit {} from peer {}",
+                        circuit_id, peer_node_id
+                    );
+                    let event = messages::AdminServiceEvent::ProposalSubmitted(
+                        messages::CircuitProposal::from_proto(proposal.clone().into_proto())
+                            .map_err(AdminSharedError::InvalidMessageFormat)?,
+                    );
+                    self.send_event(proposal.circuit().circuit_management_type(), event);
+
+                    (proposal, peer_votes)
+                }
+            };
+
+            for vote in new_votes {
+                recovered_votes += 1;
+                let event = messages::AdminServiceEvent::ProposalVote((
+                    messages::CircuitProposal::from_proto(proposal.clone().into_proto())
+                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                    vote.public_key().clone().into_bytes(),
+                ));
+                self.send_event(proposal.circuit().circuit_management_type(), event);
+            }
+        }
+
+        info!(
+            "Re-validated shared circuits with peer {}: recovered {} proposals and {} votes",
+            peer_node_id, recovered_proposals, recovered_votes
+        );
+
+        Ok(())
+    }
+
+    /// Send the given message to the admin service of the given peer, using the local id that
+    /// the peer knows this node by as the sender.
+    #[cfg(feature = "admin-service-partition-recovery")]
+    fn send_to_peer_admin_service(
+        &mut self,
+        peer_id: &PeerTokenPair,
+        local_peer_token: PeerAuthorizationToken,
+        msg: AdminMessage,
+    ) -> Result<(), AdminSharedError> {
+        let envelope_bytes = msg.write_to_bytes().map_err(|err| {
+            AdminSharedError::ServiceProtocolError(format!(
+                "Unable to serialize admin message: {}",
+                err
+            ))
+        })?;
+
+        let network_sender = self.network_sender.as_mut().ok_or_else(|| {
+            AdminSharedError::ServiceProtocolError(format!(
+                "AdminService is not started, can't send message to {}",
+                peer_id
+            ))
+        })?;
+
+        network_sender
+            .send_with_sender(
+                &admin_service_id(&peer_id.id_as_string()),
+                &envelope_bytes,
+                &admin_service_id(
+                    &PeerTokenPair::new(local_peer_token, peer_id.peer_id().clone()).id_as_string(),
+                ),
+            )
+            .map_err(AdminSharedError::from)
+    }
+
+    fn update_pending_for_protocol_agreement(
+        &mut self,
+        service_id: String,
+        token: PeerTokenPair,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        // Update any unpeered payloads that this service might be a member of
+        let mut unpeered_payloads = std::mem::take(&mut self.unpeered_payloads);
+        for pending_protocol_payload in unpeered_payloads.iter_mut() {
+            match protocol {
+                0 => {
+                    if pending_protocol_payload
+                        .missing_protocol_ids
+                        .iter()
+                        .any(|missing_protocol_id| missing_protocol_id.admin_service == service_id)
+                    {
+                        warn!(
+                            "Dropping circuit request including service {}, \
                              due to protocol mismatch",
                             service_id
                         );
@@ -2291,6 +3237,22 @@ impl AdminServiceShared {
     ) -> Result<Option<StoreCircuit>, AdminSharedError> {
         let circuit = self.admin_store.get_circuit(circuit_id)?;
         self.admin_store.remove_circuit(circuit_id)?;
+        #[cfg(feature = "challenge-trust-policy")]
+        self.sync_trust_policy(circuit_id)?;
+        Ok(circuit)
+    }
+
+    /// Use the internal `admin_store`'s `tombstone_circuit` method
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn tombstone_circuit(
+        &mut self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<Option<StoreCircuit>, AdminSharedError> {
+        let circuit = self.admin_store.get_circuit(circuit_id)?;
+        self.admin_store.tombstone_circuit(circuit_id, reason)?;
+        #[cfg(feature = "challenge-trust-policy")]
+        self.sync_trust_policy(circuit_id)?;
         Ok(circuit)
     }
 
@@ -2394,6 +3356,10 @@ impl AdminServiceShared {
 
     /// If all members of an uninitialized circuit are ready, initialize services. Also send
     /// CircuitReady notification to application authorization handler.
+    ///
+    /// The CircuitReady event is only sent once all local services have been successfully
+    /// initialized; if any service fails to initialize, the error is returned and no event is
+    /// sent.
     fn initialize_services_if_members_ready(
         &mut self,
         circuit_id: &str,
@@ -2423,12 +3389,17 @@ impl AdminServiceShared {
                 .expect("Uninitialized circuit not set")
                 .circuit
                 .expect("Uninitialized circuit's circuit proposal not set");
+            #[cfg(feature = "circuit-max-message-size")]
+            self.negotiate_max_message_size(circuit_id)?;
             self.initialize_services(circuit_proposal.get_circuit_proposal())?;
 
             let mgmt_type = circuit_proposal
                 .get_circuit_proposal()
                 .circuit_management_type
                 .clone();
+            #[cfg(feature = "admin-service-metrics")]
+            self.proposal_metrics.circuit_ready(circuit_id, &mgmt_type);
+
             let event = messages::AdminServiceEvent::CircuitReady(
                 messages::CircuitProposal::from_proto(circuit_proposal)?,
             );
@@ -2438,6 +3409,108 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Lowers the maximum message size of a newly created circuit to the smallest limit requested
+    /// by the proposal or configured by its members. The stored circuit and the routing table are
+    /// updated if the limit changed.
+    #[cfg(feature = "circuit-max-message-size")]
+    fn negotiate_max_message_size(&mut self, circuit_id: &str) -> Result<(), AdminSharedError> {
+        let member_limits = self
+            .member_max_message_sizes
+            .remove(circuit_id)
+            .unwrap_or_default();
+
+        let circuit = match self.admin_store.get_circuit(circuit_id)? {
+            Some(circuit) => circuit,
+            None => return Ok(()),
+        };
+
+        let max_message_size = match member_limits
+            .values()
+            .copied()
+            .chain(self.max_message_size)
+            .chain(circuit.max_message_size())
+            .min()
+        {
+            Some(max_message_size) if Some(max_message_size) != circuit.max_message_size() => {
+                max_message_size
+            }
+            _ => return Ok(()),
+        };
+
+        debug!(
+            "Limiting messages on circuit {} to {} bytes",
+            circuit_id, max_message_size
+        );
+
+        let mut builder = StoreCircuitBuilder::new()
+            .with_circuit_id(circuit.circuit_id())
+            .with_roster(circuit.roster())
+            .with_members(circuit.members())
+            .with_authorization_type(circuit.authorization_type())
+            .with_persistence(circuit.persistence())
+            .with_durability(circuit.durability())
+            .with_routes(circuit.routes())
+            .with_circuit_management_type(circuit.circuit_management_type())
+            .with_circuit_version(circuit.circuit_version())
+            .with_circuit_status(circuit.circuit_status())
+            .with_max_message_size(max_message_size);
+        if let Some(display_name) = circuit.display_name() {
+            builder = builder.with_display_name(display_name);
+        }
+        let circuit = builder.build().map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to build circuit {} with negotiated max message size: {}",
+                circuit_id, err
+            ))
+        })?;
+
+        self.admin_store.update_circuit(circuit.clone())?;
+
+        let routing_circuit = routing::Circuit::new(
+            circuit.circuit_id().to_string(),
+            circuit
+                .roster()
+                .iter()
+                .map(|service| {
+                    routing::Service::new(
+                        service.service_id().to_string(),
+                        service.service_type().to_string(),
+                        service.node_id().to_string(),
+                        service.arguments().to_vec(),
+                    )
+                })
+                .collect(),
+            circuit
+                .members()
+                .iter()
+                .map(|node| node.node_id().to_string())
+                .collect(),
+            circuit.authorization_type().into(),
+        )
+        .with_max_message_size(circuit.max_message_size());
+
+        let routing_members = circuit
+            .members()
+            .iter()
+            .map(|node| {
+                routing::CircuitNode::new(
+                    node.node_id().to_string(),
+                    node.endpoints().to_vec(),
+                    node.public_key().clone(),
+                )
+            })
+            .collect::<Vec<routing::CircuitNode>>();
+
+        self.routing_table_writer
+            .add_circuit(circuit_id.to_string(), routing_circuit, routing_members)
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to update circuit in routing table: {}",
+                    circuit_id
+                ))
+            })
+    }
+
     fn validate_create_circuit(
         &self,
         circuit: &Circuit,
@@ -2468,6 +3541,14 @@ impl AdminServiceShared {
                     return Err(AdminSharedError::ValidationFailed(
                         "Proposed circuit cannot have a circuit status on protocol 1".to_string(),
                     ));
+                } else if !circuit.get_attachments().is_empty() {
+                    return Err(AdminSharedError::ValidationFailed(
+                        "Proposed circuit cannot have attachments on protocol 1".to_string(),
+                    ));
+                } else if circuit.get_max_message_size() != 0 {
+                    return Err(AdminSharedError::ValidationFailed(
+                        "Proposed circuit cannot have a max message size on protocol 1".to_string(),
+                    ));
                 }
                 // check that the circuit includes supported versions
                 match circuit.get_circuit_version() {
@@ -2607,6 +3688,10 @@ impl AdminServiceShared {
 
         let mut members: Vec<String> = Vec::new();
         let mut all_endpoints: Vec<String> = Vec::new();
+        #[cfg(feature = "outbound-only")]
+        let mut outbound_only_member: Option<String> = None;
+        let mut member_keys: Vec<&[u8]> = Vec::new();
+        let mut all_authorized_keys: Vec<&[u8]> = Vec::new();
         for member in circuit.get_members() {
             let node_id = member.get_node_id().to_string();
             if node_id.is_empty() {
@@ -2623,9 +3708,21 @@ impl AdminServiceShared {
 
             let mut endpoints = member.get_endpoints().to_vec();
             if endpoints.is_empty() {
+                #[cfg(not(feature = "outbound-only"))]
                 return Err(AdminSharedError::ValidationFailed(
                     "Member endpoints cannot be empty".to_string(),
                 ));
+                // A member without endpoints is outbound-only and connects to every other member
+                // itself, so no two such members can be part of the same circuit
+                #[cfg(feature = "outbound-only")]
+                if let Some(other) = outbound_only_member.replace(member.get_node_id().into()) {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Members {} and {} both have no endpoints; outbound-only members \
+                         cannot connect to each other",
+                        other,
+                        member.get_node_id()
+                    )));
+                }
             } else if endpoints.iter().any(|endpoint| endpoint.is_empty()) {
                 return Err(AdminSharedError::ValidationFailed(
                     "Member cannot have an empty endpoint".to_string(),
@@ -2650,6 +3747,50 @@ impl AdminServiceShared {
                         .to_string(),
                 ));
             }
+
+            if !member.get_authorized_public_keys().is_empty()
+                && circuit.get_authorization_type()
+                    != Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION
+            {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Members may only have authorized public keys if authorization type is \
+                     challenge"
+                        .to_string(),
+                ));
+            }
+
+            if !member.get_public_key().is_empty() {
+                member_keys.push(member.get_public_key());
+            }
+
+            for authorized_key in member.get_authorized_public_keys() {
+                self.validate_key(authorized_key)?;
+                if authorized_key.as_slice() == member.get_public_key() {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Authorized public key {} of member {} duplicates its public key",
+                        to_hex(authorized_key),
+                        node_id
+                    )));
+                }
+                if all_authorized_keys.contains(&authorized_key.as_slice()) {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Authorized public key {} must be unique in the circuit",
+                        to_hex(authorized_key)
+                    )));
+                }
+                all_authorized_keys.push(authorized_key);
+            }
+        }
+
+        // an authorized key may not stand in for another member's public key
+        if let Some(authorized_key) = all_authorized_keys
+            .iter()
+            .find(|authorized_key| member_keys.contains(authorized_key))
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Authorized public key {} is the public key of a circuit member",
+                to_hex(authorized_key)
+            )));
         }
 
         if members.is_empty() {
@@ -2723,6 +3864,46 @@ impl AdminServiceShared {
             ));
         }
 
+        self.validate_attachments(circuit)?;
+
+        Ok(())
+    }
+
+    /// Validates that every attachment has a unique name, a URI and a well-formed sha256 hash,
+    /// so that a vote on the proposal commits to an identifiable document.
+    fn validate_attachments(&self, circuit: &Circuit) -> Result<(), AdminSharedError> {
+        let mut names = HashSet::new();
+        for attachment in circuit.get_attachments() {
+            let name = attachment.get_name();
+            if name.is_empty() {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Attachment name cannot be empty".to_string(),
+                ));
+            }
+
+            if !names.insert(name) {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Every attachment must have a unique name: {}",
+                    name
+                )));
+            }
+
+            let sha256 = attachment.get_sha256();
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Attachment {} has an invalid sha256 hash: must be 64 hex characters",
+                    name
+                )));
+            }
+
+            if attachment.get_uri().is_empty() {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Attachment {} must have a URI",
+                    name
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -2734,9 +3915,14 @@ impl AdminServiceShared {
                 .map(|arg| (arg.get_key().into(), arg.get_value().into()))
                 .collect();
 
-            validator
-                .validate(&args)
-                .map_err(|err| AdminSharedError::ValidationFailed(err.to_string()))
+            validator.validate(&args).map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Invalid arguments for service {} of type {}: {}",
+                    service.get_service_id(),
+                    service.get_service_type(),
+                    err
+                ))
+            })
         } else {
             Ok(())
         }
@@ -2816,6 +4002,104 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Returns the public key whose voting rights are used by a vote signed with the given key.
+    ///
+    /// This is the signer's key, unless the payload carries a vote delegation. The delegation
+    /// must allow the signer to vote for the node on proposals of the circuit's management type
+    /// and must be signed by the delegator key; the vote is then validated as the delegator's.
+    #[cfg_attr(
+        not(feature = "admin-service-vote-delegation"),
+        allow(unused_variables)
+    )]
+    fn voter_public_key(
+        &self,
+        payload: &CircuitManagementPayload,
+        signer_public_key: &[u8],
+        node_id: &str,
+        circuit_proposal: &StoreProposal,
+    ) -> Result<Vec<u8>, AdminSharedError> {
+        #[cfg(feature = "admin-service-vote-delegation")]
+        if payload.has_vote_delegation() {
+            let delegation = VoteDelegation::try_from(payload.get_vote_delegation().clone())
+                .map_err(|err| {
+                    AdminSharedError::ValidationFailed(format!("Invalid vote delegation: {}", err))
+                })?;
+
+            let circuit_management_type = circuit_proposal.circuit().circuit_management_type();
+            if !delegation.permits(signer_public_key, node_id, circuit_management_type) {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Vote delegation does not allow {} to vote for node {} on {} proposals",
+                    to_hex(signer_public_key),
+                    node_id,
+                    circuit_management_type
+                )));
+            }
+
+            let verified = delegation
+                .verify_signature(&*self.signature_verifier)
+                .map_err(|err| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Unable to verify vote delegation signature: {}",
+                        err
+                    ))
+                })?;
+            if !verified {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Vote delegation was not signed by the delegator key".to_string(),
+                ));
+            }
+
+            return Ok(delegation.delegator_public_key().as_slice().to_vec());
+        }
+
+        Ok(signer_public_key.to_vec())
+    }
+
+    /// Attaches a stored vote delegation to a vote whose signer is not registered for the voting
+    /// node, if one allows the signer to vote for the node on the proposal.
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn attach_vote_delegation(
+        &self,
+        mut payload: CircuitManagementPayload,
+        header: &CircuitManagementPayload_Header,
+    ) -> Result<CircuitManagementPayload, AdminSharedError> {
+        let store = match &self.vote_delegation_store {
+            Some(store) if !payload.has_vote_delegation() => store,
+            _ => return Ok(payload),
+        };
+
+        let signer_public_key = header.get_requester();
+        let node_id = header.get_requester_node_id();
+        if self.key_verifier.is_permitted(node_id, signer_public_key)? {
+            return Ok(payload);
+        }
+
+        let circuit_management_type =
+            match self.get_proposal(payload.get_circuit_proposal_vote().get_circuit_id())? {
+                Some(proposal) => proposal.circuit().circuit_management_type().to_string(),
+                None => return Ok(payload),
+            };
+
+        let delegation = store
+            .list_delegations(Some(&public_key::PublicKey::from_bytes(
+                signer_public_key.to_vec(),
+            )))?
+            .find(|delegation| {
+                delegation.permits(signer_public_key, node_id, &circuit_management_type)
+            });
+
+        if let Some(delegation) = delegation {
+            debug!(
+                "Attaching vote delegation from {} to vote by {}",
+                to_hex(delegation.delegator_public_key().as_slice()),
+                to_hex(signer_public_key)
+            );
+            payload.set_vote_delegation(delegation.into());
+        }
+
+        Ok(payload)
+    }
+
     /// Validates a `CircuitDisbandRequest` using the following:
     ///
     /// - Validate the protocol version used by the submitter node. Currently, disbanding is only
@@ -2985,6 +4269,14 @@ impl AdminServiceShared {
             )));
         }
 
+        #[cfg(feature = "circuit-tombstones")]
+        if stored_circuit.tombstone().is_some() {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to purge a circuit that has already been purged: {}",
+                circuit_id
+            )));
+        }
+
         Ok(())
     }
 
@@ -3224,6 +4516,11 @@ impl AdminServiceShared {
                     .public_key()
                     .clone()
                     .map(|public_key| public_key.into_bytes()),
+                authorized_public_keys: circuit_node
+                    .authorized_public_keys()
+                    .iter()
+                    .map(|public_key| public_key.as_slice().to_vec())
+                    .collect(),
             })
             .collect::<Vec<messages::SplinterNode>>();
         let mut create_circuit_builder = messages::CreateCircuitBuilder::new()
@@ -3262,6 +4559,10 @@ impl AdminServiceShared {
             create_circuit_builder = create_circuit_builder.with_display_name(display_name);
         }
 
+        if let Some(max_message_size) = store_circuit.max_message_size() {
+            create_circuit_builder = create_circuit_builder.with_max_message_size(max_message_size);
+        }
+
         let proposed_circuit: Circuit = create_circuit_builder
             .build()
             .map_err(|err| {
@@ -3341,6 +4642,10 @@ impl AdminServiceShared {
         }
         circuit.set_circuit_version(store_circuit.circuit_version());
         circuit.set_circuit_status(Circuit_CircuitStatus::from(store_circuit.circuit_status()));
+        let max_message_size = store_circuit.max_message_size();
+        if let Some(max_message_size) = max_message_size {
+            circuit.set_max_message_size(max_message_size);
+        }
 
         // Creating the `Abandoned` StoreCircuit
         let mut store_circuit = StoreCircuitBuilder::new()
@@ -3357,6 +4662,9 @@ impl AdminServiceShared {
         if let Some(display_name) = store_circuit.display_name() {
             store_circuit = store_circuit.with_display_name(&display_name);
         }
+        if let Some(max_message_size) = max_message_size {
+            store_circuit = store_circuit.with_max_message_size(max_message_size);
+        }
 
         Ok((
             circuit,
@@ -3406,6 +4714,17 @@ impl AdminServiceShared {
             }
         }
 
+        // All local services have been added, so notify the dispatches' ready hooks
+        #[cfg(feature = "circuit-ready-hooks")]
+        for dispatch in &self.lifecycle_dispatch {
+            if let Err(err) = dispatch.circuit_ready(&circuit.circuit_id) {
+                error!(
+                    "Unable to notify circuit ready hooks for circuit {}: {}",
+                    circuit.circuit_id, err
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -3443,11 +4762,16 @@ impl AdminServiceShared {
 
     /// Purges all services that this node was running on the disbanded circuit using the service
     /// lifecycle dispatch. Destroying a service will also remove the service's state LMDB files.
+    /// Purges the state of the local services in the given list.
+    ///
+    /// Failures are logged rather than returned as an error, so that every service gets a chance
+    /// to be purged. Returns the IDs of the services that failed, along with the error messages.
     pub fn purge_services(
         &mut self,
         circuit_id: &str,
         services: &[StoreService],
-    ) -> Result<(), AdminSharedError> {
+    ) -> Result<HashMap<String, String>, AdminSharedError> {
+        let mut failures = HashMap::new();
         for service in services {
             if service.node_id() != self.node_id() {
                 continue;
@@ -3464,11 +4788,78 @@ impl AdminServiceShared {
                         service.service_type(),
                         err
                     );
+                    failures.insert(service.service_id().to_string(), err.to_string());
                 }
             }
         }
 
-        Ok(())
+        Ok(failures)
+    }
+
+    /// Returns the purge report for the given circuit.
+    ///
+    /// If the circuit still exists, the report previews the local services that would be purged.
+    /// If the circuit was recently purged, the report contains the outcome for each service.
+    /// Otherwise, `None` is returned.
+    #[cfg(feature = "circuit-purge-preview")]
+    pub fn purge_report(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<CircuitPurgeReport>, AdminSharedError> {
+        if let Some(circuit) = self.admin_store.get_circuit(circuit_id)? {
+            return Ok(Some(self.preview_purge(circuit_id, circuit.roster())));
+        }
+
+        Ok(self
+            .purge_reports
+            .iter()
+            .rev()
+            .find(|report| report.circuit_id() == circuit_id)
+            .cloned())
+    }
+
+    /// Builds a report of the local services in the given list, along with the amount of state
+    /// each holds. Sizes that cannot be determined are logged and reported as unknown.
+    #[cfg(feature = "circuit-purge-preview")]
+    fn preview_purge(&self, circuit_id: &str, services: &[StoreService]) -> CircuitPurgeReport {
+        let services = services
+            .iter()
+            .filter(|service| service.node_id() == self.node_id())
+            .map(|service| {
+                let state_size = self.lifecycle_dispatch.iter().find_map(|dispatch| {
+                    dispatch
+                        .service_state_size(
+                            circuit_id,
+                            service.service_id(),
+                            service.service_type(),
+                        )
+                        .unwrap_or_else(|err| {
+                            warn!(
+                                "Unable to get state size of service {}::{} ({}): {}",
+                                circuit_id,
+                                service.service_id(),
+                                service.service_type(),
+                                err
+                            );
+                            None
+                        })
+                });
+                ServicePurgeReport::new(service.service_id(), service.service_type(), state_size)
+            })
+            .collect();
+
+        CircuitPurgeReport::new(circuit_id, services)
+    }
+
+    /// Keeps the report of a purged circuit, dropping the oldest report if the limit is reached.
+    #[cfg(feature = "circuit-purge-preview")]
+    fn retain_purge_report(&mut self, report: CircuitPurgeReport) {
+        self.purge_reports
+            .retain(|existing| existing.circuit_id() != report.circuit_id());
+        if self.purge_reports.len() >= MAX_RETAINED_PURGE_REPORTS {
+            self.purge_reports.pop_front();
+        }
+        self.purge_reports.push_back(report);
     }
 
     pub fn shutdown_all_services(&self) {
@@ -3589,6 +4980,62 @@ impl AdminServiceShared {
     }
 }
 
+/// Returns the ID of the circuit a payload applies to, or an empty string for an unknown action.
+#[cfg(feature = "tracing")]
+fn payload_circuit_id(payload: &CircuitManagementPayload) -> &str {
+    if payload.has_circuit_create_request() {
+        payload
+            .get_circuit_create_request()
+            .get_circuit()
+            .get_circuit_id()
+    } else if payload.has_circuit_proposal_vote() {
+        payload.get_circuit_proposal_vote().get_circuit_id()
+    } else if payload.has_circuit_disband_request() {
+        payload.get_circuit_disband_request().get_circuit_id()
+    } else if payload.has_circuit_purge_request() {
+        payload.get_circuit_purge_request().get_circuit_id()
+    } else if payload.has_circuit_abandon() {
+        payload.get_circuit_abandon().get_circuit_id()
+    } else if payload.has_proposal_remove_request() {
+        payload.get_proposal_remove_request().get_circuit_id()
+    } else {
+        ""
+    }
+}
+
+/// Converts a store error from an event query, keeping truncation errors distinct so that
+/// callers can report them to subscribers.
+fn events_error(err: AdminServiceStoreError) -> AdminSharedError {
+    match err {
+        #[cfg(feature = "admin-service-event-retention")]
+        AdminServiceStoreError::EventsTruncated {
+            since,
+            truncated_to,
+        } => AdminSharedError::EventsTruncated {
+            since,
+            truncated_to,
+        },
+        err => AdminSharedError::UnableToAddSubscriber(err.to_string()),
+    }
+}
+
+/// Pairs each member's public key with its authorized public keys.
+#[cfg(feature = "challenge-trust-policy")]
+fn member_authorized_keys(
+    circuit: &StoreCircuit,
+) -> Vec<(public_key::PublicKey, Vec<public_key::PublicKey>)> {
+    circuit
+        .members()
+        .iter()
+        .filter_map(|member| {
+            member
+                .public_key()
+                .clone()
+                .map(|public_key| (public_key, member.authorized_public_keys().to_vec()))
+        })
+        .collect()
+}
+
 // This should never return an error since we received a message from this service id
 pub fn get_peer_token_from_service_id(
     service_id: &str,
@@ -3703,7 +5150,8 @@ mod tests {
     use crate::protocol::network::NetworkMessage;
     use crate::protos::admin;
     use crate::protos::admin::{
-        CircuitProposalVote_Vote, CircuitProposal_VoteRecord, SplinterNode, SplinterService,
+        CircuitAttachment, CircuitProposalVote_Vote, CircuitProposal_VoteRecord, SplinterNode,
+        SplinterService,
     };
     use crate::protos::network;
     use crate::protos::prelude::*;
@@ -4067,6 +5515,98 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that attachments must have unique names, a URI, and a valid sha256 hash, and that
+    // attachments cannot be used on protocol 1
+    fn test_validate_circuit_attachments() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let attachment = |name: &str, sha256: &str, uri: &str| {
+            let mut attachment = CircuitAttachment::new();
+            attachment.set_name(name.to_string());
+            attachment.set_sha256(sha256.to_string());
+            attachment.set_uri(uri.to_string());
+            attachment
+        };
+        let hash = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+        let mut circuit = setup_test_circuit();
+        circuit.set_attachments(RepeatedField::from_vec(vec![
+            attachment("agreement", hash, "https://example.com/agreement.pdf"),
+            attachment("appendix", hash, "https://example.com/appendix.pdf"),
+        ]));
+        assert!(admin_shared
+            .validate_create_circuit(&circuit, PUB_KEY, "node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .is_ok());
+
+        let mut v1_circuit = circuit.clone();
+        v1_circuit.clear_display_name();
+        v1_circuit.set_circuit_version(0);
+        v1_circuit.set_circuit_status(Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS);
+        assert!(admin_shared
+            .validate_create_circuit(&v1_circuit, PUB_KEY, "node_a", 1)
+            .is_err());
+
+        let invalid_attachments = vec![
+            vec![attachment("", hash, "https://example.com/agreement.pdf")],
+            vec![
+                attachment("agreement", hash, "https://example.com/agreement.pdf"),
+                attachment("agreement", hash, "https://example.com/appendix.pdf"),
+            ],
+            vec![attachment(
+                "agreement",
+                "not-a-hash",
+                "https://example.com/agreement.pdf",
+            )],
+            vec![attachment(
+                "agreement",
+                &hash[..63],
+                "https://example.com/agreement.pdf",
+            )],
+            vec![attachment("agreement", hash, "")],
+        ];
+        for attachments in invalid_attachments {
+            circuit.set_attachments(RepeatedField::from_vec(attachments));
+            assert!(
+                admin_shared
+                    .validate_create_circuit(
+                        &circuit,
+                        PUB_KEY,
+                        "node_a",
+                        ADMIN_SERVICE_PROTOCOL_VERSION
+                    )
+                    .is_err(),
+                "Should have been invalid: {:?}",
+                circuit.get_attachments()
+            );
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that a circuit proposed with a key that is not permitted for the requesting node is
     // invalid
@@ -4727,6 +6267,7 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(not(feature = "outbound-only"))]
     #[test]
     // test that if a circuit has a member with no endpoints an error is returned
     fn test_validate_circuit_no_endpoints() {
@@ -4777,6 +6318,63 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "outbound-only")]
+    #[test]
+    // test that a circuit may have a single outbound-only member, which has no endpoints, but an
+    // error is returned if more than one member has no endpoints
+    fn test_validate_circuit_outbound_only_member() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+        circuit.mut_members()[0].set_endpoints(vec![].into());
+
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!(
+                "Should have been valid with one outbound-only member: {}",
+                err
+            );
+        }
+
+        circuit.mut_members()[1].set_endpoints(vec![].into());
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because two members have no endpoints");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that if a circuit has a member with an empty endpoint an error is returned
     fn test_validate_circuit_empty_endpoint() {
@@ -5088,8 +6686,93 @@ mod tests {
     }
 
     #[test]
-    // test that if a circuit has challenge auth set while circuit version 1 an error is returned
-    fn test_validate_circuit_challenge_auth_not_supported() {
+    // test that if a circuit has challenge auth set while circuit version 1 an error is returned
+    fn test_validate_circuit_challenge_auth_not_supported() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_v1_test_circuit();
+
+        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because cannot have challenge auth if version 1");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has challenge auth set and nodes do not have public keys, the circuit
+    // is invalid
+    fn test_validate_circuit_challenge_auth_no_public_keys() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because nodes do not have public keys set");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a member's authorized public keys must be distinct from its own key and from
+    // the keys of every other member of a challenge authorization circuit
+    fn test_validate_circuit_challenge_auth_authorized_keys() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5114,28 +6797,70 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_v1_test_circuit();
-
+        let mut circuit = setup_test_circuit();
         circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
+        circuit.mut_members()[0].set_public_key(vec![1; 33]);
+        circuit.mut_members()[1].set_public_key(vec![2; 33]);
+
+        circuit.mut_members()[0].set_authorized_public_keys(vec![vec![3; 33]].into());
+        assert!(admin_shared
+            .validate_create_circuit(&circuit, PUB_KEY, "node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .is_ok());
+
+        circuit.mut_members()[0].set_authorized_public_keys(vec![vec![1; 33]].into());
+        assert!(
+            admin_shared
+                .validate_create_circuit(
+                    &circuit,
+                    PUB_KEY,
+                    "node_a",
+                    ADMIN_SERVICE_PROTOCOL_VERSION
+                )
+                .is_err(),
+            "Should have been invalid because the authorized key is the member's own key"
+        );
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because cannot have challenge auth if version 1");
-        }
+        circuit.mut_members()[0].set_authorized_public_keys(vec![vec![2; 33]].into());
+        assert!(
+            admin_shared
+                .validate_create_circuit(
+                    &circuit,
+                    PUB_KEY,
+                    "node_a",
+                    ADMIN_SERVICE_PROTOCOL_VERSION
+                )
+                .is_err(),
+            "Should have been invalid because the authorized key belongs to another member"
+        );
+
+        circuit.mut_members()[0].set_authorized_public_keys(vec![vec![3; 33]].into());
+        circuit.mut_members()[1].set_authorized_public_keys(vec![vec![3; 33]].into());
+        assert!(
+            admin_shared
+                .validate_create_circuit(
+                    &circuit,
+                    PUB_KEY,
+                    "node_a",
+                    ADMIN_SERVICE_PROTOCOL_VERSION
+                )
+                .is_err(),
+            "Should have been invalid because the authorized key is not unique"
+        );
         shutdown(mesh, cm, pm);
     }
 
+    /// Test that proposals and votes a reconnected peer committed while partitioned from the
+    /// local node are recovered from its circuit sync response.
+    ///
+    /// 1. Handle a response with a proposal the local node does not have, and verify the proposal
+    ///    is added without any votes
+    /// 2. Handle a response with the same proposal, voted on by the peer and another node, and
+    ///    verify only the peer's own vote is added
+    #[cfg(feature = "admin-service-partition-recovery")]
     #[test]
-    // test that if a circuit has challenge auth set and nodes do not have public keys, the circuit
-    // is invalid
-    fn test_validate_circuit_challenge_auth_no_public_keys() {
+    fn test_circuit_sync_response_recovers_proposals() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
-
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
@@ -5144,7 +6869,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5157,17 +6882,61 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
+        admin_shared.token_to_peer.insert(
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("node_b"),
+                PeerAuthorizationToken::from_peer_id("node_a"),
+            ),
+            PeerNodePair {
+                peer_node: PeerNode {
+                    token: PeerAuthorizationToken::from_peer_id("node_b"),
+                    node_id: "node_b".into(),
+                    endpoints: vec!["test://endpoint_b:0".into()],
+                    admin_service: "admin::node_b".into(),
+                },
+                local_peer_token: PeerAuthorizationToken::from_peer_id("node_a"),
+            },
+        );
+
+        let circuit = setup_test_circuit();
+        let proposal = setup_test_proposal(&circuit);
+
+        let mut response = CircuitSyncResponse::new();
+        response.set_proposals(RepeatedField::from_vec(vec![proposal.clone()]));
+        admin_shared
+            .on_circuit_sync_response("admin::node_b", response)
+            .expect("Unable to handle circuit sync response");
+
+        let recovered = admin_shared
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .expect("Proposal was not recovered");
+        assert!(recovered.votes().is_empty());
+
+        let mut voted_proposal = proposal;
+        let mut votes = vec![];
+        for (node_id, public_key) in [("node_b", b"test_signer_b"), ("node_c", b"test_signer_c")] {
+            let mut vote = CircuitProposal_VoteRecord::new();
+            vote.set_public_key(public_key.to_vec());
+            vote.set_vote(CircuitProposalVote_Vote::ACCEPT);
+            vote.set_voter_node_id(node_id.into());
+            votes.push(vote);
+        }
+        voted_proposal.set_votes(RepeatedField::from_vec(votes));
+
+        let mut response = CircuitSyncResponse::new();
+        response.set_proposals(RepeatedField::from_vec(vec![voted_proposal]));
+        admin_shared
+            .on_circuit_sync_response("admin::node_b", response)
+            .expect("Unable to handle circuit sync response");
+
+        let recovered = admin_shared
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .expect("Proposal was removed");
+        assert_eq!(recovered.votes().len(), 1);
+        assert_eq!(recovered.votes()[0].voter_node_id(), "node_b");
 
-        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because nodes do not have public keys set");
-        }
         shutdown(mesh, cm, pm);
     }
 
@@ -5391,6 +7160,97 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    #[cfg(feature = "admin-service-vote-delegation")]
+    // test that a vote carrying a vote delegation is validated as the delegator's vote, and only
+    // when the delegation covers the signer, the voting node and the circuit management type
+    fn test_voter_public_key_with_delegation() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let context = Secp256k1Context::new();
+        let signature_verifier = context.new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+        let proposal = StoreProposal::from_proto(setup_test_proposal(&circuit))
+            .expect("Unable to get proposal");
+
+        let delegator = context.new_signer(context.new_random_private_key());
+        let delegate = context
+            .get_public_key(&context.new_random_private_key())
+            .expect("Unable to get public key")
+            .into_bytes();
+
+        // without a delegation the signer votes for itself
+        let mut payload = admin::CircuitManagementPayload::new();
+        assert_eq!(
+            admin_shared
+                .voter_public_key(&payload, &delegate, "node_a", &proposal)
+                .expect("Unable to get voter key"),
+            delegate
+        );
+
+        payload.set_vote_delegation(
+            crate::admin::delegation::tests::signed_delegation(
+                &*delegator,
+                &delegate,
+                "node_a",
+                &["test_circuit"],
+            )
+            .into(),
+        );
+        assert_eq!(
+            admin_shared
+                .voter_public_key(&payload, &delegate, "node_a", &proposal)
+                .expect("Unable to get voter key"),
+            delegator
+                .public_key()
+                .expect("Unable to get public key")
+                .into_bytes()
+        );
+        // the delegation does not cover other nodes or signers
+        assert!(admin_shared
+            .voter_public_key(&payload, &delegate, "node_b", &proposal)
+            .is_err());
+        assert!(admin_shared
+            .voter_public_key(&payload, PUB_KEY, "node_a", &proposal)
+            .is_err());
+
+        // the delegation does not cover other circuit management types
+        payload.set_vote_delegation(
+            crate::admin::delegation::tests::signed_delegation(
+                &*delegator,
+                &delegate,
+                "node_a",
+                &["other"],
+            )
+            .into(),
+        );
+        assert!(admin_shared
+            .voter_public_key(&payload, &delegate, "node_a", &proposal)
+            .is_err());
+
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that the validate_circuit_management_payload method returns an error in case the
     // signature is empty.
@@ -5634,6 +7494,79 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that validating a circuit create request reports all problems found with it
+    ///
+    /// 1. Set up `AdminServiceShared` with an orchestrator that does not support any service
+    ///    types
+    /// 2. Validate a signed create request for the test circuit
+    /// 3. Verify that the local service's type is reported as unsupported, and that the unknown
+    ///    protocol version of node_b is reported as a warning
+    /// 4. Verify that a disband request cannot be validated
+    #[cfg(feature = "admin-service-proposal-validation")]
+    #[test]
+    fn test_validate_payload() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(setup_test_circuit());
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(
+            signer
+                .public_key()
+                .expect("Unable to get public key")
+                .into_bytes(),
+        );
+        header.set_requester_node_id("node_a".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
+
+        let validation = shared
+            .validate_payload(&payload)
+            .expect("Unable to validate payload");
+        assert!(!validation.is_valid());
+        assert_eq!(validation.errors().len(), 1);
+        assert!(validation.errors()[0].contains("type_a"));
+        assert_eq!(validation.warnings().len(), 1);
+        assert!(validation.warnings()[0].contains("node_b"));
+
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST);
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        assert!(shared.validate_payload(&payload).is_err());
+
+        shutdown(mesh, cm, pm);
+    }
+
     /// Tests that a circuit being disbanded is validated correctly
     ///
     /// 1. Set up `AdminServiceShared`
@@ -6330,6 +8263,80 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that the purge report previews the local services of a circuit before it is purged,
+    /// and records the per-service results once it has been purged.
+    ///
+    /// 1. Set up `AdminServiceShared` and add a disbanded circuit to the admin store
+    /// 2. Call `purge_report` and validate only the local service is reported as pending
+    /// 3. Purge the circuit
+    /// 4. Call `purge_report` and validate the local service is reported as purged
+    #[cfg(feature = "circuit-purge-preview")]
+    #[test]
+    fn test_purge_report() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let preview = admin_shared
+            .purge_report("01234-ABCDE")
+            .expect("unable to get purge preview")
+            .expect("purge preview not found");
+        assert!(!preview.is_complete());
+        assert_eq!(preview.services().len(), 1);
+        assert_eq!(preview.services()[0].service_id(), "0123");
+        assert_eq!(preview.services()[0].state_size(), None);
+        assert_eq!(preview.services()[0].status(), &ServicePurgeStatus::Pending);
+
+        admin_shared
+            .purge_circuit("01234-ABCDE", false)
+            .expect("unable to purge circuit");
+
+        let report = admin_shared
+            .purge_report("01234-ABCDE")
+            .expect("unable to get purge report")
+            .expect("purge report not found");
+        assert!(report.is_complete());
+        assert_eq!(report.services()[0].status(), &ServicePurgeStatus::Purged);
+
+        assert_eq!(
+            admin_shared
+                .purge_report("unknown")
+                .expect("unable to get purge report"),
+            None
+        );
+
+        shutdown(mesh, cm, pm);
+    }
+
     /// Tests that a circuit purge request is invalid if the circuit to be purged is still `Active`
     /// in the admin store. The `CircuitPurgeRequest` is only valid for circuits that have already
     /// been disbanded, a `circuit_status` of `Disbanded`.
@@ -6781,6 +8788,84 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit is purged after it is abandoned if the `CircuitAbandon` request sets
+    /// `purge`.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add an `Active` circuit to the admin store
+    /// 3. Create a `CircuitAbandon` message with `purge` set
+    /// 4. Submit the `CircuitAbandon` request to the node's admin service
+    /// 5. Validate the circuit has been removed from the admin store
+    #[cfg(feature = "circuit-abandon-purge")]
+    #[test]
+    fn test_abandon_circuit_with_purge() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let pub_key = context
+            .get_public_key(&private_key)
+            .expect("Unable to get corresponding public key");
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        // Add the circuit to be abandoned
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+        // Make `CircuitAbandon` and corresponding payload
+        let mut abandon = admin::CircuitAbandon::new();
+        abandon.set_circuit_id("01234-ABCDE".to_string());
+        abandon.set_purge(true);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_ABANDON);
+        header.set_requester(pub_key.into_bytes());
+        header.set_requester_node_id("node_a".to_string());
+
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_abandon(abandon);
+
+        // Submit `CircuitAbandon` payload
+        if let Err(err) = admin_shared.submit(payload) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        assert!(admin_shared
+            .admin_store
+            .get_circuit(&"01234-ABCDE".to_string())
+            .expect("Unable to get circuit")
+            .is_none());
+
+        shutdown(mesh, cm, pm);
+    }
+
     /// Tests that a request to remove a circuit proposal is validated correctly
     ///
     /// 1. Set up `AdminServiceShared`
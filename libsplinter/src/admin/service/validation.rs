@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The results of validating a circuit management payload without submitting it.
+
+/// The outcome of validating a circuit management payload without submitting it.
+///
+/// Errors are problems that would cause the payload to be rejected by this node. Warnings are
+/// checks that could not be completed, such as those that depend on members this node has not yet
+/// peered with, and so may still cause the proposal to fail once it is submitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PayloadValidation {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl PayloadValidation {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add_error<S: Into<String>>(&mut self, error: S) {
+        self.errors.push(error.into());
+    }
+
+    pub(crate) fn add_warning<S: Into<String>>(&mut self, warning: S) {
+        self.warnings.push(warning.into());
+    }
+
+    /// Returns true if no errors were found.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the problems that would cause the payload to be rejected.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Returns the checks that could not be completed.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
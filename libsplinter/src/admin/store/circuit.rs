@@ -15,6 +15,8 @@
 //! Structs for building circuits
 
 use std::convert::TryFrom;
+#[cfg(feature = "circuit-tombstones")]
+use std::time::SystemTime;
 
 use crate::admin::messages::{self, is_valid_circuit_id};
 use crate::circuit::routing;
@@ -39,9 +41,20 @@ pub struct Circuit {
     display_name: Option<String>,
     circuit_version: i32,
     circuit_status: CircuitStatus,
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    tombstone: Option<CircuitTombstone>,
 }
 
 impl Circuit {
+    /// Returns a copy of the circuit with the given schema version
+    #[cfg(feature = "circuit-schema-migration")]
+    pub(super) fn with_circuit_version(&self, circuit_version: i32) -> Circuit {
+        let mut circuit = self.clone();
+        circuit.circuit_version = circuit_version;
+        circuit
+    }
+
     /// Returns the ID of the circuit
     pub fn circuit_id(&self) -> &str {
         &self.id
@@ -96,6 +109,51 @@ impl Circuit {
     pub fn circuit_status(&self) -> &CircuitStatus {
         &self.circuit_status
     }
+
+    /// Returns the maximum message payload size for the circuit, if any
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
+
+    /// Returns the tombstone of the circuit, if it was purged while tombstones were enabled
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn tombstone(&self) -> Option<&CircuitTombstone> {
+        self.tombstone.as_ref()
+    }
+}
+
+/// Records that a circuit was purged, in place of removing it from the store
+#[cfg(feature = "circuit-tombstones")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitTombstone {
+    deleted_at: SystemTime,
+    reason: String,
+}
+
+#[cfg(feature = "circuit-tombstones")]
+impl CircuitTombstone {
+    /// Creates a new tombstone
+    ///
+    /// # Arguments
+    ///
+    ///  * `deleted_at` - When the circuit was purged
+    ///  * `reason` - Why the circuit was purged
+    pub fn new(deleted_at: SystemTime, reason: &str) -> Self {
+        Self {
+            deleted_at,
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns when the circuit was purged
+    pub fn deleted_at(&self) -> SystemTime {
+        self.deleted_at
+    }
+
+    /// Returns why the circuit was purged
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
 }
 
 impl TryFrom<&admin::Circuit> for Circuit {
@@ -146,6 +204,9 @@ impl TryFrom<&admin::Circuit> for Circuit {
         if !proto.get_display_name().is_empty() {
             builder = builder.with_display_name(proto.get_display_name());
         }
+        if proto.get_max_message_size() != 0 {
+            builder = builder.with_max_message_size(proto.get_max_message_size());
+        }
 
         builder.build()
     }
@@ -390,6 +451,9 @@ pub struct CircuitBuilder {
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    tombstone: Option<CircuitTombstone>,
 }
 
 impl CircuitBuilder {
@@ -453,6 +517,17 @@ impl CircuitBuilder {
         self.circuit_status.clone()
     }
 
+    /// Returns the maximum message payload size in the builder
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
+
+    /// Returns the tombstone in the builder
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn tombstone(&self) -> Option<CircuitTombstone> {
+        self.tombstone.clone()
+    }
+
     /// Sets the circuit ID
     ///
     /// # Arguments
@@ -568,6 +643,28 @@ impl CircuitBuilder {
         self
     }
 
+    /// Sets the maximum message payload size for the circuit
+    ///
+    /// # Arguments
+    ///
+    ///  * `max_message_size` - The largest service message payload, in bytes, that may be sent
+    ///    on the circuit
+    pub fn with_max_message_size(mut self, max_message_size: u64) -> CircuitBuilder {
+        self.max_message_size = Some(max_message_size);
+        self
+    }
+
+    /// Sets the tombstone for the circuit
+    ///
+    /// # Arguments
+    ///
+    ///  * `tombstone` - The record of when and why the circuit was purged
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn with_tombstone(mut self, tombstone: CircuitTombstone) -> CircuitBuilder {
+        self.tombstone = Some(tombstone);
+        self
+    }
+
     /// Builds a `Circuit`
     ///
     /// Returns an error if the circuit ID, roster, members or circuit management
@@ -617,6 +714,12 @@ impl CircuitBuilder {
 
         let circuit_status = self.circuit_status.unwrap_or_default();
 
+        if self.max_message_size == Some(0) {
+            return Err(InvalidStateError::with_message(
+                "max_message_size must be greater than 0".to_string(),
+            ));
+        }
+
         let circuit = Circuit {
             id: circuit_id,
             roster,
@@ -629,6 +732,9 @@ impl CircuitBuilder {
             display_name,
             circuit_version,
             circuit_status,
+            max_message_size: self.max_message_size,
+            #[cfg(feature = "circuit-tombstones")]
+            tombstone: self.tombstone,
         };
 
         Ok(circuit)
@@ -649,6 +755,9 @@ impl From<ProposedCircuit> for Circuit {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone(),
+            max_message_size: circuit.max_message_size(),
+            #[cfg(feature = "circuit-tombstones")]
+            tombstone: None,
         }
     }
 }
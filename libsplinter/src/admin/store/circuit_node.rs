@@ -24,6 +24,7 @@ pub struct CircuitNode {
     id: String,
     endpoints: Vec<String>,
     public_key: Option<PublicKey>,
+    authorized_public_keys: Vec<PublicKey>,
 }
 
 impl CircuitNode {
@@ -41,6 +42,11 @@ impl CircuitNode {
     pub fn public_key(&self) -> &Option<PublicKey> {
         &self.public_key
     }
+
+    /// Returns the additional public keys the node may use for challenge authorization
+    pub fn authorized_public_keys(&self) -> &[PublicKey] {
+        &self.authorized_public_keys
+    }
 }
 
 impl From<&ProposedNode> for CircuitNode {
@@ -49,6 +55,7 @@ impl From<&ProposedNode> for CircuitNode {
             id: proposed_node.node_id().into(),
             endpoints: proposed_node.endpoints().to_vec(),
             public_key: proposed_node.public_key().clone(),
+            authorized_public_keys: proposed_node.authorized_public_keys().to_vec(),
         }
     }
 }
@@ -59,6 +66,7 @@ impl From<ProposedNode> for CircuitNode {
             id: node.node_id().into(),
             endpoints: node.endpoints().to_vec(),
             public_key: node.public_key().clone(),
+            authorized_public_keys: node.authorized_public_keys().to_vec(),
         }
     }
 }
@@ -69,6 +77,7 @@ pub struct CircuitNodeBuilder {
     node_id: Option<String>,
     endpoints: Option<Vec<String>>,
     public_key: Option<PublicKey>,
+    authorized_public_keys: Vec<PublicKey>,
 }
 
 impl CircuitNodeBuilder {
@@ -92,6 +101,11 @@ impl CircuitNodeBuilder {
         self.public_key.clone()
     }
 
+    /// Returns the additional public keys for the node
+    pub fn authorized_public_keys(&self) -> Vec<PublicKey> {
+        self.authorized_public_keys.clone()
+    }
+
     /// Sets the node ID
     ///
     /// # Arguments
@@ -122,6 +136,16 @@ impl CircuitNodeBuilder {
         self
     }
 
+    /// Sets the additional public keys
+    ///
+    /// # Arguments
+    ///
+    ///  * `public_keys` - Other public keys the node may use for challenge authorization
+    pub fn with_authorized_public_keys(mut self, public_keys: &[PublicKey]) -> CircuitNodeBuilder {
+        self.authorized_public_keys = public_keys.to_vec();
+        self
+    }
+
     /// Builds the `CircuitNode`
     ///
     /// Returns an error if the node ID or endpoints are not set
@@ -142,6 +166,7 @@ impl CircuitNodeBuilder {
             id: node_id,
             endpoints,
             public_key: self.public_key,
+            authorized_public_keys: self.authorized_public_keys,
         };
 
         Ok(node)
@@ -359,6 +359,7 @@ pub struct VoteRecord {
     public_key: PublicKey,
     vote: Vote,
     voter_node_id: String,
+    comment: Option<String>,
 }
 
 impl VoteRecord {
@@ -377,6 +378,11 @@ impl VoteRecord {
         &self.voter_node_id
     }
 
+    /// Returns the voter's explanation of the vote, if one was given
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
     fn from_proto(mut proto: admin::CircuitProposal_VoteRecord) -> Result<Self, InvalidStateError> {
         let vote = match proto.get_vote() {
             admin::CircuitProposalVote_Vote::ACCEPT => Vote::Accept,
@@ -388,10 +394,16 @@ impl VoteRecord {
             }
         };
 
+        let comment = match proto.take_comment() {
+            comment if comment.is_empty() => None,
+            comment => Some(comment),
+        };
+
         Ok(Self {
             public_key: PublicKey::from_bytes(proto.take_public_key()),
             vote,
             voter_node_id: proto.take_voter_node_id(),
+            comment,
         })
     }
 
@@ -405,6 +417,9 @@ impl VoteRecord {
         vote_record.set_vote(vote);
         vote_record.set_public_key(self.public_key.into_bytes());
         vote_record.set_voter_node_id(self.voter_node_id);
+        if let Some(comment) = self.comment {
+            vote_record.set_comment(comment);
+        }
 
         vote_record
     }
@@ -415,6 +430,7 @@ pub struct VoteRecordBuilder {
     public_key: Option<PublicKey>,
     vote: Option<Vote>,
     voter_node_id: Option<String>,
+    comment: Option<String>,
 }
 
 impl VoteRecordBuilder {
@@ -437,6 +453,11 @@ impl VoteRecordBuilder {
         self.voter_node_id.clone()
     }
 
+    /// Returns the voter's explanation of the vote
+    pub fn comment(&self) -> Option<String> {
+        self.comment.clone()
+    }
+
     pub fn with_public_key(mut self, public_key: &PublicKey) -> VoteRecordBuilder {
         self.public_key = Some(public_key.clone());
         self
@@ -452,6 +473,12 @@ impl VoteRecordBuilder {
         self
     }
 
+    /// Sets the voter's explanation of the vote
+    pub fn with_comment(mut self, comment: &str) -> VoteRecordBuilder {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
     pub fn build(self) -> Result<VoteRecord, InvalidStateError> {
         let public_key = self.public_key.ok_or_else(|| {
             InvalidStateError::with_message(
@@ -471,6 +498,7 @@ impl VoteRecordBuilder {
             public_key,
             vote,
             voter_node_id,
+            comment: self.comment,
         })
     }
 }
@@ -538,6 +566,7 @@ impl From<&messages::VoteRecord> for VoteRecord {
             public_key: PublicKey::from_bytes(admin_vote_record.public_key.to_vec()),
             vote: Vote::from(&admin_vote_record.vote),
             voter_node_id: admin_vote_record.voter_node_id.to_string(),
+            comment: admin_vote_record.comment.clone(),
         }
     }
 }
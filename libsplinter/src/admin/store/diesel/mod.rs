@@ -26,6 +26,14 @@ mod operations;
 mod schema;
 
 use std::sync::{Arc, RwLock};
+#[cfg(all(
+    any(
+        feature = "admin-service-event-retention",
+        feature = "circuit-tombstones"
+    ),
+    any(feature = "postgres", feature = "sqlite")
+))]
+use std::time::SystemTime;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
@@ -70,10 +78,25 @@ use operations::list_nodes::AdminServiceStoreListNodesOperation as _;
 use operations::list_proposals::AdminServiceStoreListProposalsOperation as _;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use operations::list_services::AdminServiceStoreListServicesOperation as _;
+#[cfg(all(
+    feature = "admin-service-event-retention",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use operations::prune_events::AdminServiceStorePruneEventsOperation as _;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use operations::remove_circuit::AdminServiceStoreRemoveCircuitOperation as _;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use operations::remove_proposal::AdminServiceStoreRemoveProposalOperation as _;
+#[cfg(all(
+    feature = "circuit-tombstones",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use operations::remove_tombstones::AdminServiceStoreRemoveTombstonesOperation as _;
+#[cfg(all(
+    feature = "circuit-tombstones",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use operations::tombstone_circuit::AdminServiceStoreTombstoneCircuitOperation as _;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use operations::update_circuit::AdminServiceStoreUpdateCircuitOperation as _;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
@@ -197,6 +220,26 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_circuit(circuit_id))
     }
 
+    #[cfg(feature = "circuit-tombstones")]
+    fn tombstone_circuit(
+        &self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).tombstone_circuit(circuit_id, reason)
+        })
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    fn remove_tombstones(
+        &self,
+        before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_tombstones(before))
+    }
+
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
         self.connection_pool
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuit(circuit_id))
@@ -276,6 +319,17 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
         })
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).prune_events(before, max_count)
+        })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -343,6 +397,26 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_circuit(circuit_id))
     }
 
+    #[cfg(feature = "circuit-tombstones")]
+    fn tombstone_circuit(
+        &self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).tombstone_circuit(circuit_id, reason)
+        })
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    fn remove_tombstones(
+        &self,
+        before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_tombstones(before))
+    }
+
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
         self.connection_pool
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuit(circuit_id))
@@ -422,6 +496,17 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
         })
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).prune_events(before, max_count)
+        })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -433,8 +518,9 @@ pub mod tests {
 
     use crate::admin::store::{
         CircuitBuilder, CircuitNodeBuilder, CircuitProposal, CircuitProposalBuilder, CircuitStatus,
-        ProposalType, ProposedCircuitBuilder, ProposedNodeBuilder, ProposedServiceBuilder,
-        ServiceBuilder, Vote, VoteRecordBuilder,
+        ProposalType, ProposedAttachment, ProposedAttachmentBuilder, ProposedCircuit,
+        ProposedCircuitBuilder, ProposedNodeBuilder, ProposedServiceBuilder, ServiceBuilder, Vote,
+        VoteRecordBuilder,
     };
 
     use crate::admin::store::{AdminServiceEventBuilder, EventType};
@@ -481,6 +567,71 @@ pub mod tests {
         assert_eq!(proposal, fetched_proposal);
     }
 
+    /// Verify that the attachments of a proposal are stored and returned in order
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a proposal with two attachments
+    /// 4. Add proposal to store
+    /// 5. Validate the fetched and listed proposals contain the attachments in the same order
+    #[test]
+    fn test_add_get_proposal_with_attachments() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        let attachments = vec![
+            ProposedAttachmentBuilder::new()
+                .with_name("service-agreement")
+                .with_sha256("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")
+                .with_uri("https://example.com/docs/service-agreement.pdf")
+                .build()
+                .expect("Unable to build attachment"),
+            ProposedAttachmentBuilder::new()
+                .with_name("appendix")
+                .with_sha256("2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae")
+                .with_uri("https://example.com/docs/appendix.pdf")
+                .build()
+                .expect("Unable to build attachment"),
+        ];
+        let mut circuit_proto = proposal.circuit().clone().into_proto();
+        circuit_proto.set_attachments(protobuf::RepeatedField::from_vec(
+            attachments
+                .iter()
+                .cloned()
+                .map(ProposedAttachment::into_proto)
+                .collect(),
+        ));
+        let proposal = proposal
+            .builder()
+            .with_circuit(
+                &ProposedCircuit::from_proto(circuit_proto).expect("Unable to build circuit"),
+            )
+            .build()
+            .expect("Unable to build proposal");
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+        assert_eq!(
+            fetched_proposal.circuit().attachments(),
+            attachments.as_slice()
+        );
+        assert_eq!(proposal, fetched_proposal);
+
+        let listed_proposals = store
+            .list_proposals(&[])
+            .expect("Unable to list proposals")
+            .collect::<Vec<_>>();
+        assert_eq!(listed_proposals, vec![proposal]);
+    }
+
     /// Verify that list_proposals works correctly
     ///
     /// 1. Run sqlite migrations
@@ -556,6 +707,159 @@ pub mod tests {
         assert_eq!(proposals.len(), 2);
     }
 
+    /// Verify that list_proposals and list_circuits filter by requester correctly
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal, a circuit and the circuit ready event created from the proposal
+    /// 4. List proposals and circuits with the requester of the proposal, validate the proposal
+    ///    and circuit are returned
+    /// 5. List proposals and circuits with a different requester, validate nothing is returned
+    #[cfg(feature = "admin-service-requester-filter")]
+    #[test]
+    fn test_list_with_requester_predicate() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        store
+            .add_circuit(circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_event(create_circuit_ready_messages_event("gameroom"))
+            .expect("Unable to add event");
+
+        let requester = proposal.requester().clone();
+        let mut proposals = store
+            .list_proposals(&[CircuitPredicate::RequesterEq(requester.clone())])
+            .expect("Unable to list proposals with requester predicate");
+        assert_eq!(proposals.next(), Some(proposal));
+        assert_eq!(proposals.next(), None);
+
+        let mut circuits = store
+            .list_circuits(&[CircuitPredicate::RequesterEq(requester.clone())])
+            .expect("Unable to list circuits with requester predicate");
+        assert_eq!(circuits.next(), Some(circuit));
+        assert_eq!(circuits.next(), None);
+        assert_eq!(
+            store
+                .count_circuits(&[CircuitPredicate::RequesterEq(requester)])
+                .expect("Unable to count circuits with requester predicate"),
+            1
+        );
+
+        let other_requester = PublicKey::from_bytes(
+            parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                .unwrap(),
+        );
+        assert_eq!(
+            store
+                .list_proposals(&[CircuitPredicate::RequesterEq(other_requester.clone())])
+                .expect("Unable to list proposals with requester predicate")
+                .len(),
+            0
+        );
+        assert_eq!(
+            store
+                .list_circuits(&[CircuitPredicate::RequesterEq(other_requester.clone())])
+                .expect("Unable to list circuits with requester predicate")
+                .len(),
+            0
+        );
+        assert_eq!(
+            store
+                .count_proposals(&[CircuitPredicate::RequesterEq(other_requester)])
+                .expect("Unable to count proposals with requester predicate"),
+            0
+        );
+    }
+
+    /// Verify that list and count operations filter by service type correctly
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal and a circuit, both with scabbard services
+    /// 4. List and count proposals and circuits with the scabbard service type, validate the
+    ///    proposal and circuit are returned
+    /// 5. List and count proposals and circuits with a different service type, validate nothing
+    ///    is returned
+    #[cfg(feature = "admin-service-type-filter")]
+    #[test]
+    fn test_list_with_service_type_predicate() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        store
+            .add_circuit(circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+
+        let scabbard = || CircuitPredicate::ServiceTypeEq("scabbard".to_string());
+        let mut proposals = store
+            .list_proposals(&[scabbard()])
+            .expect("Unable to list proposals with service type predicate");
+        assert_eq!(proposals.next(), Some(proposal));
+        assert_eq!(proposals.next(), None);
+
+        let mut circuits = store
+            .list_circuits(&[scabbard()])
+            .expect("Unable to list circuits with service type predicate");
+        assert_eq!(circuits.next(), Some(circuit));
+        assert_eq!(circuits.next(), None);
+        assert_eq!(
+            store
+                .count_circuits(&[scabbard()])
+                .expect("Unable to count circuits with service type predicate"),
+            1
+        );
+        assert_eq!(
+            store
+                .count_proposals(&[scabbard()])
+                .expect("Unable to count proposals with service type predicate"),
+            1
+        );
+
+        let echo = || CircuitPredicate::ServiceTypeEq("echo".to_string());
+        assert_eq!(
+            store
+                .list_proposals(&[echo()])
+                .expect("Unable to list proposals with service type predicate")
+                .len(),
+            0
+        );
+        assert_eq!(
+            store
+                .list_circuits(&[echo()])
+                .expect("Unable to list circuits with service type predicate")
+                .len(),
+            0
+        );
+        assert_eq!(
+            store
+                .count_circuits(&[echo()])
+                .expect("Unable to count circuits with service type predicate"),
+            0
+        );
+        assert_eq!(
+            store
+                .count_proposals(&[echo()])
+                .expect("Unable to count proposals with service type predicate"),
+            0
+        );
+    }
+
     /// Verify that count_proposals works correctly
     ///
     /// 1. Run sqlite migrations
@@ -816,6 +1120,58 @@ pub mod tests {
         )
     }
 
+    /// Verify that a circuit's maximum message size is stored, updated and returned
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a circuit with a maximum message size to the store
+    /// 4. Validate the fetched and listed circuits contain the maximum message size
+    /// 5. Update the circuit with a smaller maximum message size
+    /// 6. Validate the fetched circuit contains the updated maximum message size
+    #[test]
+    fn test_add_update_circuit_max_message_size() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active)
+            .with_max_message_size(1_048_576)
+            .build()
+            .expect("Unable to build circuit");
+
+        store
+            .add_circuit(circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+        assert_eq!(fetched_circuit.max_message_size(), Some(1_048_576));
+        assert_eq!(circuit, fetched_circuit);
+
+        let listed_circuits = store
+            .list_circuits(&[])
+            .expect("Unable to list circuits")
+            .collect::<Vec<_>>();
+        assert_eq!(listed_circuits, vec![circuit]);
+
+        let updated_circuit = create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active)
+            .with_max_message_size(65_536)
+            .build()
+            .expect("Unable to build circuit");
+
+        store
+            .update_circuit(updated_circuit.clone())
+            .expect("Unable to update circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+        assert_eq!(updated_circuit, fetched_circuit);
+    }
+
     /// Verify that list_circuits works correctly
     ///
     /// 1. Run sqlite migrations
@@ -1095,6 +1451,95 @@ pub mod tests {
         assert_eq!(None, fetched_circuit);
     }
 
+    /// Verify that a tombstoned circuit is kept in the store until its tombstone is removed
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two circuits to the store and tombstone one of them
+    /// 4. Validate the tombstoned circuit is returned by `get_circuit` with its tombstone, and
+    ///    that tombstoning it again fails
+    /// 5. Validate the tombstoned circuit is only listed and counted with the
+    ///    `IncludeTombstones` predicate
+    /// 6. Validate removing tombstones from before the circuit was tombstoned removes nothing
+    /// 7. Remove all tombstones and validate the circuit was removed, while the other was kept
+    #[cfg(feature = "circuit-tombstones")]
+    #[test]
+    fn test_tombstone_circuits() {
+        use std::time::{Duration, SystemTime};
+
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        let extra_circuit = create_extra_circuit("WBKLF-CCCCC");
+        store
+            .add_circuit(circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(extra_circuit.clone(), create_extra_nodes())
+            .expect("Unable to add circuit");
+
+        store
+            .tombstone_circuit("WBKLF-BBBBB", "purged")
+            .expect("Unable to tombstone circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+        let tombstone = fetched_circuit
+            .tombstone()
+            .expect("Circuit was not tombstoned");
+        assert_eq!(tombstone.reason(), "purged");
+        assert!(store.tombstone_circuit("WBKLF-BBBBB", "purged").is_err());
+
+        let circuits = store
+            .list_circuits(&[])
+            .expect("Unable to list circuits")
+            .map(|circuit| circuit.circuit_id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec!["WBKLF-CCCCC".to_string()]);
+        assert_eq!(store.count_circuits(&[]).expect("Unable to count"), 1);
+
+        let circuits = store
+            .list_circuits(&[CircuitPredicate::IncludeTombstones])
+            .expect("Unable to list circuits")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits.len(), 2);
+        assert!(circuits
+            .iter()
+            .any(|circuit| circuit.circuit_id() == "WBKLF-BBBBB" && circuit.tombstone().is_some()));
+        assert_eq!(
+            store
+                .count_circuits(&[CircuitPredicate::IncludeTombstones])
+                .expect("Unable to count"),
+            2
+        );
+
+        let removed = store
+            .remove_tombstones(Some(SystemTime::now() - Duration::from_secs(60)))
+            .expect("Unable to remove tombstones");
+        assert_eq!(removed, 0);
+
+        let removed = store
+            .remove_tombstones(None)
+            .expect("Unable to remove tombstones");
+        assert_eq!(removed, 1);
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to get circuit"),
+            None
+        );
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-CCCCC")
+                .expect("Unable to get circuit"),
+            Some(extra_circuit)
+        );
+    }
+
     /// Verify that a service can be fetched from the store
     ///
     /// 1. Run sqlite migrations
@@ -1423,6 +1868,98 @@ pub mod tests {
         );
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    #[test]
+    /// Verify that events are pruned by count and by age, and that listing events since a pruned
+    /// position returns an `EventsTruncated` error.
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore and add three events
+    /// 3. Prune, keeping only the newest two events, and validate one event was removed
+    /// 4. Validate `list_events_since(0)` returns events 2 and 3, and that listing since event 1,
+    ///    the last event removed, succeeds
+    /// 5. Prune events recorded before a minute from now, removing the remaining events
+    /// 6. Validate listing events since 1 returns `EventsTruncated`, while listing since 3 does not
+    /// 7. Validate a new event is assigned the ID 4
+    fn test_prune_events() {
+        use std::time::{Duration, SystemTime};
+
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        store
+            .add_event(create_proposal_submitted_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_circuit_ready_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_proposal_vote_messages_event("test"))
+            .expect("Unable to add event");
+
+        assert_eq!(
+            store
+                .prune_events(None, Some(2))
+                .expect("Unable to prune events"),
+            1
+        );
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events_since(0)
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                create_circuit_ready_event(2, "test"),
+                create_proposal_vote_event(3, "test")
+            ],
+        );
+        assert_eq!(
+            store
+                .list_events_since(1)
+                .expect("Unable to get events from store")
+                .len(),
+            2
+        );
+
+        assert_eq!(
+            store
+                .prune_events(Some(SystemTime::now() + Duration::from_secs(60)), None)
+                .expect("Unable to prune events"),
+            2
+        );
+
+        match store.list_events_since(1) {
+            Err(AdminServiceStoreError::EventsTruncated {
+                since,
+                truncated_to,
+            }) => {
+                assert_eq!(since, 1);
+                assert_eq!(truncated_to, 3);
+            }
+            Err(err) => panic!("Unexpected error: {}", err),
+            Ok(_) => panic!("Listing events since a pruned event should fail"),
+        }
+        match store.list_events_by_management_type_since("test".into(), 2) {
+            Err(AdminServiceStoreError::EventsTruncated { .. }) => (),
+            Err(err) => panic!("Unexpected error: {}", err),
+            Ok(_) => panic!("Listing events since a pruned event should fail"),
+        }
+        assert_eq!(
+            store
+                .list_events_since(3)
+                .expect("Unable to get events from store")
+                .len(),
+            0
+        );
+
+        let event = store
+            .add_event(create_circuit_ready_messages_event("test"))
+            .expect("Unable to add event");
+        assert_eq!(*event.event_id(), 4);
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection ensures that the same DB is used for all operations.
@@ -1579,6 +2116,12 @@ pub mod tests {
     }
 
     fn create_circuit(circuit_id: &str, status: CircuitStatus) -> Circuit {
+        create_circuit_builder(circuit_id, status)
+            .build()
+            .expect("Unable to build circuit")
+    }
+
+    fn create_circuit_builder(circuit_id: &str, status: CircuitStatus) -> CircuitBuilder {
         let nodes = create_nodes();
 
         CircuitBuilder::default()
@@ -1612,8 +2155,6 @@ pub mod tests {
             .with_display_name("test_display")
             .with_circuit_version(3)
             .with_circuit_status(&status)
-            .build()
-            .expect("Unable to build circuit")
     }
 
     fn create_circuit_from_proposal(circuit_id: &str, status: CircuitStatus) -> Circuit {
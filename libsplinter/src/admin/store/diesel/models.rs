@@ -18,6 +18,9 @@
 
 use std::convert::TryFrom;
 use std::io::Write;
+#[cfg(feature = "circuit-tombstones")]
+use std::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use diesel::{
     backend::Backend,
@@ -28,23 +31,30 @@ use diesel::{
 };
 
 use crate::admin::service::messages::{self, CreateCircuit};
+#[cfg(feature = "circuit-tombstones")]
+use crate::admin::store::diesel::schema::circuit_tombstone;
 use crate::admin::store::diesel::schema::{
     admin_event_circuit_proposal, admin_event_proposed_circuit, admin_event_proposed_node,
     admin_event_proposed_node_endpoint, admin_event_proposed_service,
     admin_event_proposed_service_argument, admin_event_vote_record, admin_service_event,
 };
 use crate::admin::store::diesel::schema::{
-    circuit, circuit_member, circuit_proposal, node_endpoint, proposed_circuit, proposed_node,
+    circuit, circuit_member, circuit_member_authorized_key, circuit_proposal, node_endpoint,
+    proposed_circuit, proposed_circuit_attachment, proposed_node, proposed_node_authorized_key,
     proposed_node_endpoint, proposed_service, proposed_service_argument, service, service_argument,
     vote_record,
 };
 use crate::admin::store::error::AdminServiceStoreError;
+#[cfg(feature = "circuit-tombstones")]
+use crate::admin::store::CircuitTombstone;
 use crate::admin::store::{AdminServiceEvent, AdminServiceEventBuilder, EventType};
 use crate::admin::store::{
     AuthorizationType, CircuitStatus, DurabilityType, PersistenceType, ProposalType, RouteType,
     Vote, VoteRecord, VoteRecordBuilder,
 };
-use crate::admin::store::{Circuit, CircuitProposal, ProposedCircuit};
+use crate::admin::store::{
+    Circuit, CircuitProposal, ProposedAttachment, ProposedAttachmentBuilder, ProposedCircuit,
+};
 use crate::error::{InternalError, InvalidStateError};
 use crate::public_key::PublicKey;
 
@@ -93,6 +103,7 @@ pub struct ProposedCircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub max_message_size: Option<i64>,
 }
 
 impl From<&ProposedCircuit> for ProposedCircuitModel {
@@ -109,6 +120,9 @@ impl From<&ProposedCircuit> for ProposedCircuitModel {
             display_name: proposed_circuit.display_name().clone(),
             circuit_version: proposed_circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(proposed_circuit.circuit_status()),
+            max_message_size: proposed_circuit
+                .max_message_size()
+                .map(max_message_size_to_model),
         }
     }
 }
@@ -126,6 +140,7 @@ pub struct VoteRecordModel {
     pub vote: String,
     pub voter_node_id: String,
     pub position: i32,
+    pub comment: Option<String>,
 }
 
 impl TryFrom<&CircuitProposal> for Vec<VoteRecordModel> {
@@ -147,6 +162,7 @@ impl TryFrom<&CircuitProposal> for Vec<VoteRecordModel> {
                             "Unable to convert index into i32".to_string(),
                         ))
                     })?,
+                    comment: vote.comment().map(String::from),
                 })
             })
             .collect::<Result<Vec<VoteRecordModel>, AdminServiceStoreError>>()
@@ -156,10 +172,16 @@ impl TryFrom<&CircuitProposal> for Vec<VoteRecordModel> {
 impl TryFrom<&VoteRecordModel> for VoteRecord {
     type Error = AdminServiceStoreError;
     fn try_from(vote: &VoteRecordModel) -> Result<Self, Self::Error> {
-        VoteRecordBuilder::new()
+        let mut builder = VoteRecordBuilder::new()
             .with_public_key(&PublicKey::from_bytes(vote.public_key.to_vec()))
             .with_vote(&Vote::try_from(vote.vote.clone())?)
-            .with_voter_node_id(&vote.voter_node_id)
+            .with_voter_node_id(&vote.voter_node_id);
+
+        if let Some(comment) = &vote.comment {
+            builder = builder.with_comment(comment);
+        }
+
+        builder
             .build()
             .map_err(AdminServiceStoreError::InvalidStateError)
     }
@@ -249,6 +271,102 @@ impl TryFrom<&ProposedCircuit> for Vec<ProposedNodeEndpointModel> {
     }
 }
 
+/// Database model representation of the authorized public keys associated with a `ProposedNode`
+#[derive(
+    Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
+)]
+#[table_name = "proposed_node_authorized_key"]
+#[belongs_to(ProposedCircuitModel, foreign_key = "circuit_id")]
+#[primary_key(circuit_id, node_id, public_key)]
+pub struct ProposedNodeAuthorizedKeyModel {
+    pub circuit_id: String,
+    pub node_id: String,
+    pub public_key: Vec<u8>,
+    pub position: i32,
+}
+
+impl TryFrom<&ProposedCircuit> for Vec<ProposedNodeAuthorizedKeyModel> {
+    type Error = AdminServiceStoreError;
+
+    fn try_from(proposed_circuit: &ProposedCircuit) -> Result<Self, Self::Error> {
+        let mut key_models = Vec::new();
+        for node in proposed_circuit.members() {
+            key_models.extend(
+                node.authorized_public_keys()
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, public_key)| {
+                        Ok(ProposedNodeAuthorizedKeyModel {
+                            circuit_id: proposed_circuit.circuit_id().into(),
+                            node_id: node.node_id().into(),
+                            public_key: public_key.as_slice().to_vec(),
+                            position: i32::try_from(idx).map_err(|_| {
+                                AdminServiceStoreError::InternalError(InternalError::with_message(
+                                    "Unable to convert index into i32".to_string(),
+                                ))
+                            })?,
+                        })
+                    })
+                    .collect::<Result<Vec<ProposedNodeAuthorizedKeyModel>, AdminServiceStoreError>>(
+                    )?,
+            );
+        }
+        Ok(key_models)
+    }
+}
+
+/// Database model representation of the attachments associated with a `ProposedCircuit`
+#[derive(
+    Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
+)]
+#[table_name = "proposed_circuit_attachment"]
+#[belongs_to(ProposedCircuitModel, foreign_key = "circuit_id")]
+#[primary_key(circuit_id, name)]
+pub struct ProposedCircuitAttachmentModel {
+    pub circuit_id: String,
+    pub name: String,
+    pub sha256: String,
+    pub uri: String,
+    pub position: i32,
+}
+
+impl TryFrom<&ProposedCircuit> for Vec<ProposedCircuitAttachmentModel> {
+    type Error = AdminServiceStoreError;
+
+    fn try_from(proposed_circuit: &ProposedCircuit) -> Result<Self, Self::Error> {
+        proposed_circuit
+            .attachments()
+            .iter()
+            .enumerate()
+            .map(|(idx, attachment)| {
+                Ok(ProposedCircuitAttachmentModel {
+                    circuit_id: proposed_circuit.circuit_id().into(),
+                    name: attachment.name().into(),
+                    sha256: attachment.sha256().into(),
+                    uri: attachment.uri().into(),
+                    position: i32::try_from(idx).map_err(|_| {
+                        AdminServiceStoreError::InternalError(InternalError::with_message(
+                            "Unable to convert index into i32".to_string(),
+                        ))
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<ProposedCircuitAttachmentModel>, AdminServiceStoreError>>()
+    }
+}
+
+impl TryFrom<&ProposedCircuitAttachmentModel> for ProposedAttachment {
+    type Error = AdminServiceStoreError;
+    fn try_from(attachment: &ProposedCircuitAttachmentModel) -> Result<Self, Self::Error> {
+        ProposedAttachmentBuilder::new()
+            .with_name(&attachment.name)
+            .with_sha256(&attachment.sha256)
+            .with_uri(&attachment.uri)
+            .build()
+            .map_err(AdminServiceStoreError::InvalidStateError)
+    }
+}
+
 /// Database model representation of a `ProposedService`
 #[derive(
     Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
@@ -438,6 +556,7 @@ pub struct CircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub max_message_size: Option<i64>,
 }
 
 impl From<&Circuit> for CircuitModel {
@@ -452,10 +571,48 @@ impl From<&Circuit> for CircuitModel {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(circuit.circuit_status()),
+            max_message_size: circuit.max_message_size().map(max_message_size_to_model),
         }
     }
 }
 
+/// Converts a maximum message size into its database representation, saturating at the largest
+/// value the `BIGINT` column can hold
+fn max_message_size_to_model(max_message_size: u64) -> i64 {
+    i64::try_from(max_message_size).unwrap_or(i64::MAX)
+}
+
+/// Converts the database representation of a maximum message size, ignoring values that are not
+/// positive
+pub fn max_message_size_from_model(max_message_size: Option<i64>) -> Option<u64> {
+    max_message_size
+        .and_then(|max_message_size| u64::try_from(max_message_size).ok())
+        .filter(|max_message_size| *max_message_size > 0)
+}
+
+/// Database model representation of the tombstone of a `Circuit`
+#[cfg(feature = "circuit-tombstones")]
+#[derive(
+    Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
+)]
+#[table_name = "circuit_tombstone"]
+#[belongs_to(CircuitModel, foreign_key = "circuit_id")]
+#[primary_key(circuit_id)]
+pub struct CircuitTombstoneModel {
+    pub circuit_id: String,
+    pub deleted_at: i64,
+    pub reason: String,
+}
+
+#[cfg(feature = "circuit-tombstones")]
+impl From<CircuitTombstoneModel> for CircuitTombstone {
+    fn from(model: CircuitTombstoneModel) -> Self {
+        let deleted_at =
+            UNIX_EPOCH + Duration::from_secs(u64::try_from(model.deleted_at).unwrap_or(0));
+        CircuitTombstone::new(deleted_at, &model.reason)
+    }
+}
+
 /// Database model representation of the `members` of a `Circuit`
 #[derive(
     Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
@@ -497,6 +654,50 @@ impl TryFrom<&Circuit> for Vec<CircuitMemberModel> {
     }
 }
 
+/// Database model representation of the authorized public keys associated with a `Circuit` member
+#[derive(
+    Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
+)]
+#[table_name = "circuit_member_authorized_key"]
+#[belongs_to(CircuitModel, foreign_key = "circuit_id")]
+#[primary_key(circuit_id, node_id, public_key)]
+pub struct CircuitMemberAuthorizedKeyModel {
+    pub circuit_id: String,
+    pub node_id: String,
+    pub public_key: Vec<u8>,
+    pub position: i32,
+}
+
+impl TryFrom<&Circuit> for Vec<CircuitMemberAuthorizedKeyModel> {
+    type Error = AdminServiceStoreError;
+
+    fn try_from(circuit: &Circuit) -> Result<Self, Self::Error> {
+        let mut key_models = Vec::new();
+        for node in circuit.members() {
+            key_models.extend(
+                node.authorized_public_keys()
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, public_key)| {
+                        Ok(CircuitMemberAuthorizedKeyModel {
+                            circuit_id: circuit.circuit_id().into(),
+                            node_id: node.node_id().into(),
+                            public_key: public_key.as_slice().to_vec(),
+                            position: i32::try_from(idx).map_err(|_| {
+                                AdminServiceStoreError::InternalError(InternalError::with_message(
+                                    "Unable to convert index into i32".to_string(),
+                                ))
+                            })?,
+                        })
+                    })
+                    .collect::<Result<Vec<CircuitMemberAuthorizedKeyModel>, AdminServiceStoreError>>(
+                    )?,
+            );
+        }
+        Ok(key_models)
+    }
+}
+
 /// Database model representation of the endpoint values associated with a `Circuit` member `node_id`
 #[derive(
     Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
@@ -518,6 +719,9 @@ pub struct AdminServiceEventModel {
     pub id: i64,
     pub event_type: String,
     pub data: Option<Vec<u8>>,
+    pub created_at: i64,
+    pub rejecting_node_id: Option<String>,
+    pub rejection_reason: Option<String>,
 }
 
 #[derive(AsChangeset, Insertable, PartialEq, Eq, Debug)]
@@ -525,6 +729,9 @@ pub struct AdminServiceEventModel {
 pub struct NewAdminServiceEventModel<'a> {
     pub event_type: &'a str,
     pub data: Option<&'a [u8]>,
+    pub created_at: i64,
+    pub rejecting_node_id: Option<&'a str>,
+    pub rejection_reason: Option<&'a str>,
 }
 
 /// Database model representation of a `CircuitProposal` from an `AdminServiceEvent`
@@ -616,6 +823,7 @@ pub struct AdminEventVoteRecordModel {
     pub vote: String,
     pub voter_node_id: String,
     pub position: i32,
+    pub comment: Option<String>,
 }
 
 impl AdminEventVoteRecordModel {
@@ -640,6 +848,7 @@ impl AdminEventVoteRecordModel {
                             "Unable to convert index into i32".to_string(),
                         ))
                     })?,
+                    comment: vote.comment.clone(),
                 })
             })
             .collect()
@@ -651,7 +860,7 @@ impl TryFrom<&AdminEventVoteRecordModel> for VoteRecord {
     fn try_from(
         admin_event_vote_record_model: &AdminEventVoteRecordModel,
     ) -> Result<Self, Self::Error> {
-        VoteRecordBuilder::new()
+        let mut builder = VoteRecordBuilder::new()
             .with_public_key(&PublicKey::from_bytes(
                 admin_event_vote_record_model.public_key.to_vec(),
             ))
@@ -660,8 +869,13 @@ impl TryFrom<&AdminEventVoteRecordModel> for VoteRecord {
                     InvalidStateError::with_message("Unable to convert string to Vote".into())
                 })?,
             )
-            .with_voter_node_id(&admin_event_vote_record_model.voter_node_id)
-            .build()
+            .with_voter_node_id(&admin_event_vote_record_model.voter_node_id);
+
+        if let Some(comment) = &admin_event_vote_record_model.comment {
+            builder = builder.with_comment(comment);
+        }
+
+        builder.build()
     }
 }
 
@@ -863,35 +1077,49 @@ impl AdminEventProposedServiceArgumentModel {
 
 impl<'a> From<&'a messages::AdminServiceEvent> for NewAdminServiceEventModel<'a> {
     fn from(event: &'a messages::AdminServiceEvent) -> Self {
-        match event {
-            messages::AdminServiceEvent::ProposalSubmitted(_) => NewAdminServiceEventModel {
-                event_type: "ProposalSubmitted",
-                data: None,
-            },
-            messages::AdminServiceEvent::ProposalVote((_, data)) => NewAdminServiceEventModel {
-                event_type: "ProposalVote",
-                data: Some(data),
-            },
-            messages::AdminServiceEvent::ProposalAccepted((_, data)) => NewAdminServiceEventModel {
-                event_type: "ProposalAccepted",
-                data: Some(data),
-            },
-            messages::AdminServiceEvent::ProposalRejected((_, data)) => NewAdminServiceEventModel {
-                event_type: "ProposalRejected",
-                data: Some(data),
-            },
-            messages::AdminServiceEvent::CircuitReady(_) => NewAdminServiceEventModel {
-                event_type: "CircuitReady",
-                data: None,
-            },
-            messages::AdminServiceEvent::CircuitDisbanded(_) => NewAdminServiceEventModel {
-                event_type: "CircuitDisbanded",
-                data: None,
-            },
+        #[cfg_attr(not(feature = "admin-service-rejection-reasons"), allow(unused_mut))]
+        let mut rejecting_node_id = None;
+        #[cfg_attr(not(feature = "admin-service-rejection-reasons"), allow(unused_mut))]
+        let mut rejection_reason = None;
+        let (event_type, data) = match event {
+            messages::AdminServiceEvent::ProposalSubmitted(_) => ("ProposalSubmitted", None),
+            messages::AdminServiceEvent::ProposalVote((_, data)) => {
+                ("ProposalVote", Some(data.as_slice()))
+            }
+            messages::AdminServiceEvent::ProposalAccepted((_, data)) => {
+                ("ProposalAccepted", Some(data.as_slice()))
+            }
+            messages::AdminServiceEvent::ProposalRejected((_, data)) => {
+                ("ProposalRejected", Some(data.as_slice()))
+            }
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            messages::AdminServiceEvent::ProposalInvalid((_, data, failure)) => {
+                rejecting_node_id = Some(failure.node_id.as_str());
+                rejection_reason = Some(failure.reason.as_str());
+                ("ProposalInvalid", Some(data.as_slice()))
+            }
+            messages::AdminServiceEvent::CircuitReady(_) => ("CircuitReady", None),
+            messages::AdminServiceEvent::CircuitDisbanded(_) => ("CircuitDisbanded", None),
+        };
+
+        NewAdminServiceEventModel {
+            event_type,
+            data,
+            created_at: to_db_timestamp(SystemTime::now()),
+            rejecting_node_id,
+            rejection_reason,
         }
     }
 }
 
+/// Converts a time to the number of seconds since the unix epoch, as stored in the
+/// `created_at` column of the `admin_service_event` table.
+pub fn to_db_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or(0)
+}
+
 impl TryFrom<(AdminServiceEventModel, CircuitProposal)> for AdminServiceEvent {
     type Error = AdminServiceStoreError;
 
@@ -923,6 +1151,17 @@ impl TryFrom<(AdminServiceEventModel, CircuitProposal)> for AdminServiceEvent {
                 .with_proposal(&proposal)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            ("ProposalInvalid", Some(requester)) => AdminServiceEventBuilder::new()
+                .with_event_id(event_model.id)
+                .with_event_type(&EventType::ProposalInvalid {
+                    requester,
+                    node_id: event_model.rejecting_node_id.unwrap_or_default(),
+                    reason: event_model.rejection_reason.unwrap_or_default(),
+                })
+                .with_proposal(&proposal)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError),
             ("CircuitReady", None) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::CircuitReady)
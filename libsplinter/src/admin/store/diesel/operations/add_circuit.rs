@@ -26,9 +26,13 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitMemberModel, CircuitModel, NodeEndpointModel, ServiceArgumentModel, ServiceModel,
+            CircuitMemberAuthorizedKeyModel, CircuitMemberModel, CircuitModel, NodeEndpointModel,
+            ServiceArgumentModel, ServiceModel,
+        },
+        schema::{
+            circuit, circuit_member, circuit_member_authorized_key, node_endpoint, service,
+            service_argument,
         },
-        schema::{circuit, circuit_member, node_endpoint, service, service_argument},
     },
     error::AdminServiceStoreError,
     Circuit, CircuitNode,
@@ -94,6 +98,25 @@ impl<'a> AdminServiceStoreAddCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_members)
                 .execute(self.conn)?;
+            // Create a list of the authorized public keys of the circuit members
+            let mut member_keys: Vec<CircuitMemberAuthorizedKeyModel> = Vec::new();
+            for node in nodes.iter() {
+                for (idx, public_key) in node.authorized_public_keys().iter().enumerate() {
+                    member_keys.push(CircuitMemberAuthorizedKeyModel {
+                        circuit_id: circuit.circuit_id().into(),
+                        node_id: node.node_id().into(),
+                        public_key: public_key.as_slice().to_vec(),
+                        position: i32::try_from(idx).map_err(|_| {
+                            AdminServiceStoreError::InternalError(InternalError::with_message(
+                                "Unable to convert index into i32".to_string(),
+                            ))
+                        })?,
+                    });
+                }
+            }
+            insert_into(circuit_member_authorized_key::table)
+                .values(member_keys)
+                .execute(self.conn)?;
             // Iterate over the list of `CircuitNodes` to extract the `node_id` and `endpoints`, to
             // convert them into the `NodeEndpointModel`. Then, verify the `node_id` does not
             // already have associated `node_endpoint` entries before inserting the list of
@@ -193,6 +216,25 @@ impl<'a> AdminServiceStoreAddCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_members)
                 .execute(self.conn)?;
+            // Create a list of the authorized public keys of the circuit members
+            let mut member_keys: Vec<CircuitMemberAuthorizedKeyModel> = Vec::new();
+            for node in nodes.iter() {
+                for (idx, public_key) in node.authorized_public_keys().iter().enumerate() {
+                    member_keys.push(CircuitMemberAuthorizedKeyModel {
+                        circuit_id: circuit.circuit_id().into(),
+                        node_id: node.node_id().into(),
+                        public_key: public_key.as_slice().to_vec(),
+                        position: i32::try_from(idx).map_err(|_| {
+                            AdminServiceStoreError::InternalError(InternalError::with_message(
+                                "Unable to convert index into i32".to_string(),
+                            ))
+                        })?,
+                    });
+                }
+            }
+            insert_into(circuit_member_authorized_key::table)
+                .values(member_keys)
+                .execute(self.conn)?;
             // Iterate over the list of `CircuitNodes` to extract the `node_id` and `endpoints`, to
             // convert them into the `NodeEndpointModel`. Then, verify the `node_id` does not
             // already have associated `node_endpoint` entries before inserting the list of
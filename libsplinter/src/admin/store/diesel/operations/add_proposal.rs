@@ -22,12 +22,14 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
-            ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
+            CircuitProposalModel, ProposedCircuitAttachmentModel, ProposedCircuitModel,
+            ProposedNodeAuthorizedKeyModel, ProposedNodeEndpointModel, ProposedNodeModel,
+            ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
-            circuit_proposal, proposed_circuit, proposed_node, proposed_node_endpoint,
-            proposed_service, proposed_service_argument, vote_record,
+            circuit_proposal, proposed_circuit, proposed_circuit_attachment, proposed_node,
+            proposed_node_authorized_key, proposed_node_endpoint, proposed_service,
+            proposed_service_argument, vote_record,
         },
     },
     error::AdminServiceStoreError,
@@ -80,6 +82,18 @@ impl<'a> AdminServiceStoreAddProposalOperation
             insert_into(proposed_node_endpoint::table)
                 .values(proposed_member_endpoints)
                 .execute(self.conn)?;
+            // Insert the authorized public keys of the proposed `members` of a `ProposedCircuit`
+            let proposed_member_keys: Vec<ProposedNodeAuthorizedKeyModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_node_authorized_key::table)
+                .values(proposed_member_keys)
+                .execute(self.conn)?;
+            // Insert the `attachments` of a `ProposedCircuit`
+            let proposed_attachments: Vec<ProposedCircuitAttachmentModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_circuit_attachment::table)
+                .values(proposed_attachments)
+                .execute(self.conn)?;
             // Insert `roster`, list of `Services` of a `ProposedCircuit`
             let proposed_services: Vec<ProposedServiceModel> = Vec::try_from(proposal.circuit())?;
             insert_into(proposed_service::table)
@@ -143,6 +157,18 @@ impl<'a> AdminServiceStoreAddProposalOperation
             insert_into(proposed_node_endpoint::table)
                 .values(proposed_member_endpoints)
                 .execute(self.conn)?;
+            // Insert the authorized public keys of the proposed `members` of a `ProposedCircuit`
+            let proposed_member_keys: Vec<ProposedNodeAuthorizedKeyModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_node_authorized_key::table)
+                .values(proposed_member_keys)
+                .execute(self.conn)?;
+            // Insert the `attachments` of a `ProposedCircuit`
+            let proposed_attachments: Vec<ProposedCircuitAttachmentModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_circuit_attachment::table)
+                .values(proposed_attachments)
+                .execute(self.conn)?;
             // Insert `roster`, list of `Services` of a `ProposedCircuit`
             let proposed_services: Vec<ProposedServiceModel> = Vec::try_from(proposal.circuit())?;
             insert_into(proposed_service::table)
@@ -0,0 +1,56 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "check events retained" operation for the `DieselAdminServiceStore`.
+
+use diesel::prelude::*;
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{
+    diesel::schema::admin_service_event_truncation, error::AdminServiceStoreError,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreCheckEventsRetainedOperation {
+    /// Returns an `EventsTruncated` error if events since `start` have been removed by
+    /// `prune_events`. A `start` of 0 requests every event still in the store, so it is never
+    /// rejected.
+    fn check_events_retained(&self, start: i64) -> Result<(), AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreCheckEventsRetainedOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn check_events_retained(&self, start: i64) -> Result<(), AdminServiceStoreError> {
+        if start <= 0 {
+            return Ok(());
+        }
+
+        let truncated_to = admin_service_event_truncation::table
+            .select(admin_service_event_truncation::truncated_to)
+            .first::<i64>(self.conn)
+            .optional()?;
+
+        match truncated_to {
+            Some(truncated_to) if start < truncated_to => {
+                Err(AdminServiceStoreError::EventsTruncated {
+                    since: start,
+                    truncated_to,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
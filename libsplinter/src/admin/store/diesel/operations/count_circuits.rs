@@ -21,6 +21,12 @@ use diesel::{
     prelude::*,
 };
 
+#[cfg(feature = "admin-service-requester-filter")]
+use crate::admin::store::diesel::schema::admin_event_circuit_proposal;
+#[cfg(feature = "circuit-tombstones")]
+use crate::admin::store::diesel::schema::circuit_tombstone;
+#[cfg(feature = "admin-service-type-filter")]
+use crate::admin::store::diesel::schema::service;
 use crate::admin::store::{
     diesel::{
         models::CircuitStatusModel,
@@ -44,6 +50,7 @@ impl<'a, C> AdminServiceStoreCountCircuitsOperation for AdminServiceStoreOperati
 where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
     fn count_circuits(
         &self,
@@ -66,6 +73,24 @@ where
             })
             .flatten()
             .collect();
+        // Collects the requesters included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-requester-filter")]
+        let requesters: Vec<Vec<u8>> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::RequesterEq(requester) => Some(requester.as_slice().to_vec()),
+                _ => None,
+            })
+            .collect();
+        // Collects the service types included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-type-filter")]
+        let service_types: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::ServiceTypeEq(service_type) => Some(service_type.to_string()),
+                _ => None,
+            })
+            .collect();
         let statuses: Vec<CircuitStatusModel> = predicates
             .iter()
             .filter_map(|pred| match pred {
@@ -73,6 +98,10 @@ where
                 _ => None,
             })
             .collect();
+        #[cfg(feature = "circuit-tombstones")]
+        let include_tombstones = predicates
+            .iter()
+            .any(|pred| matches!(pred, CircuitPredicate::IncludeTombstones));
         self.conn.transaction::<u32, _, _>(|| {
             // Collects circuits which match the circuit predicates
             let mut query = circuit::table.into_boxed().select(circuit::all_columns);
@@ -93,6 +122,31 @@ where
                 ));
             }
 
+            #[cfg(feature = "admin-service-requester-filter")]
+            if !requesters.is_empty() {
+                // Circuits do not store their requester, so select the circuits that were created
+                // from a proposal submitted by any of the requesters in the circuit predicates
+                let requested_circuit_ids: Vec<String> = admin_event_circuit_proposal::table
+                    .filter(admin_event_circuit_proposal::requester.eq_any(requesters))
+                    .select(admin_event_circuit_proposal::circuit_id)
+                    .distinct()
+                    .load::<String>(self.conn)?;
+                query = query.filter(circuit::circuit_id.eq_any(requested_circuit_ids));
+            }
+
+            #[cfg(feature = "admin-service-type-filter")]
+            if !service_types.is_empty() {
+                query = query.filter(exists(
+                    // Selects all `service` entries where the `service_type` is equal to any of the
+                    // service types in the circuit predicates
+                    service::table.filter(
+                        service::circuit_id
+                            .eq(circuit::circuit_id)
+                            .and(service::service_type.eq_any(service_types)),
+                    ),
+                ));
+            }
+
             if statuses.is_empty() {
                 // By default, only display active circuits
                 query = query.filter(circuit::circuit_status.eq(CircuitStatusModel::Active));
@@ -103,6 +157,14 @@ where
                 );
             }
 
+            #[cfg(feature = "circuit-tombstones")]
+            if !include_tombstones {
+                query = query.filter(diesel::dsl::not(exists(
+                    circuit_tombstone::table
+                        .filter(circuit_tombstone::circuit_id.eq(circuit::circuit_id)),
+                )));
+            }
+
             let count = query.select(count_star()).first::<i64>(self.conn)?;
 
             u32::try_from(count).map_err(|_| {
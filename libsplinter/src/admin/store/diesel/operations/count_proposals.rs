@@ -21,8 +21,10 @@ use diesel::{
     prelude::*,
 };
 
+#[cfg(feature = "admin-service-type-filter")]
+use crate::admin::store::diesel::schema::proposed_service;
 use crate::admin::store::{
-    diesel::schema::{proposed_circuit, proposed_node},
+    diesel::schema::{circuit_proposal, proposed_circuit, proposed_node},
     error::AdminServiceStoreError,
     CircuitPredicate,
 };
@@ -63,6 +65,24 @@ where
             })
             .flatten()
             .collect();
+        // Collects the requesters included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-requester-filter")]
+        let requesters: Vec<Vec<u8>> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::RequesterEq(requester) => Some(requester.as_slice().to_vec()),
+                _ => None,
+            })
+            .collect();
+        // Collects the service types included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-type-filter")]
+        let service_types: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::ServiceTypeEq(service_type) => Some(service_type.to_string()),
+                _ => None,
+            })
+            .collect();
 
         self.conn.transaction::<u32, _, _>(|| {
             let mut query = proposed_circuit::table
@@ -81,6 +101,32 @@ where
                 ))
             }
 
+            #[cfg(feature = "admin-service-requester-filter")]
+            if !requesters.is_empty() {
+                query = query.filter(exists(
+                    // Selects all `circuit_proposal` entries where the `requester` is equal to
+                    // any of the requesters in the circuit predicates
+                    circuit_proposal::table.filter(
+                        circuit_proposal::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(circuit_proposal::requester.eq_any(requesters)),
+                    ),
+                ));
+            }
+
+            #[cfg(feature = "admin-service-type-filter")]
+            if !service_types.is_empty() {
+                query = query.filter(exists(
+                    // Selects all `proposed_service` entries where the `service_type` is equal to any of
+                    // the service types in the circuit predicates
+                    proposed_service::table.filter(
+                        proposed_service::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(proposed_service::service_type.eq_any(service_types)),
+                    ),
+                ));
+            }
+
             // Selects proposed circuits that match the management types
             if !management_types.is_empty() {
                 query = query
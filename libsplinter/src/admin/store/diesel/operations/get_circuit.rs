@@ -20,10 +20,15 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use super::{list_services::AdminServiceStoreListServicesOperation, AdminServiceStoreOperations};
+#[cfg(feature = "circuit-tombstones")]
+use crate::admin::store::diesel::{models::CircuitTombstoneModel, schema::circuit_tombstone};
 use crate::admin::store::{
     diesel::{
-        models::{CircuitMemberModel, CircuitModel, NodeEndpointModel},
-        schema::{circuit, circuit_member, node_endpoint},
+        models::{
+            max_message_size_from_model, CircuitMemberAuthorizedKeyModel, CircuitMemberModel,
+            CircuitModel, NodeEndpointModel,
+        },
+        schema::{circuit, circuit_member, circuit_member_authorized_key, node_endpoint},
     },
     error::AdminServiceStoreError,
     AuthorizationType, Circuit, CircuitBuilder, CircuitNode, CircuitNodeBuilder, CircuitStatus,
@@ -44,6 +49,7 @@ where
     i32: diesel::deserialize::FromSql<Integer, C::Backend>,
     i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
     CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    CircuitMemberAuthorizedKeyModel: diesel::Queryable<(Text, Text, Binary, Integer), C::Backend>,
 {
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
         self.conn.transaction::<Option<Circuit>, _, _>(|| {
@@ -93,6 +99,19 @@ where
                 nodes.into_iter().map(|(_, node)| node).collect();
             nodes_vec.sort_by_key(|node| node.position);
 
+            // Collect the authorized public keys of the members, in order
+            let mut authorized_keys: HashMap<String, Vec<PublicKey>> = HashMap::new();
+            for key in circuit_member_authorized_key::table
+                .filter(circuit_member_authorized_key::circuit_id.eq(circuit_id.to_string()))
+                .order(circuit_member_authorized_key::position)
+                .load::<CircuitMemberAuthorizedKeyModel>(self.conn)?
+            {
+                authorized_keys
+                    .entry(key.node_id)
+                    .or_insert_with(Vec::new)
+                    .push(PublicKey::from_bytes(key.public_key));
+            }
+
             // Collecting services associated with the `Circuit` using the `list_services` method,
             // which provides a list of the `Services` with the matching `circuit_id`.
             let services: Vec<Service> = self.list_services(circuit_id)?.collect();
@@ -110,6 +129,10 @@ where
                             builder.with_public_key(&PublicKey::from_bytes(public_key.to_vec()));
                     }
 
+                    if let Some(keys) = authorized_keys.get(&member.node_id) {
+                        builder = builder.with_authorized_public_keys(keys);
+                    }
+
                     builder.build()
                 })
                 .collect::<Result<Vec<CircuitNode>, InvalidStateError>>()
@@ -132,6 +155,19 @@ where
                 builder = builder.with_display_name(&display_name);
             }
 
+            if let Some(max_message_size) = max_message_size_from_model(circuit.max_message_size) {
+                builder = builder.with_max_message_size(max_message_size);
+            }
+
+            #[cfg(feature = "circuit-tombstones")]
+            if let Some(tombstone) = circuit_tombstone::table
+                .find(circuit_id)
+                .first::<CircuitTombstoneModel>(self.conn)
+                .optional()?
+            {
+                builder = builder.with_tombstone(tombstone.into());
+            }
+
             Ok(Some(
                 builder
                     .build()
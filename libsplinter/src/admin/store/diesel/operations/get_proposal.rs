@@ -16,7 +16,7 @@
 
 use diesel::{
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -25,18 +25,20 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
+            max_message_size_from_model, CircuitProposalModel, ProposedCircuitAttachmentModel,
+            ProposedCircuitModel, ProposedNodeAuthorizedKeyModel, ProposedNodeEndpointModel,
             ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
-            circuit_proposal, proposed_circuit, proposed_node, proposed_node_endpoint,
-            proposed_service, proposed_service_argument, vote_record,
+            circuit_proposal, proposed_circuit, proposed_circuit_attachment, proposed_node,
+            proposed_node_authorized_key, proposed_node_endpoint, proposed_service,
+            proposed_service_argument, vote_record,
         },
     },
     error::AdminServiceStoreError,
     AuthorizationType, CircuitProposal, CircuitProposalBuilder, CircuitStatus, DurabilityType,
-    PersistenceType, ProposalType, ProposedCircuitBuilder, ProposedNode, ProposedNodeBuilder,
-    ProposedService, ProposedServiceBuilder, RouteType, VoteRecord,
+    PersistenceType, ProposalType, ProposedAttachment, ProposedCircuitBuilder, ProposedNode,
+    ProposedNodeBuilder, ProposedService, ProposedServiceBuilder, RouteType, VoteRecord,
 };
 use crate::public_key::PublicKey;
 
@@ -67,11 +69,16 @@ where
             Nullable<Text>,
             Integer,
             SmallInt,
+            Nullable<BigInt>,
         ),
         C::Backend,
     >,
-    VoteRecordModel: diesel::Queryable<(Text, Binary, Text, Text, Integer), C::Backend>,
+    VoteRecordModel:
+        diesel::Queryable<(Text, Binary, Text, Text, Integer, Nullable<Text>), C::Backend>,
     ProposedNodeModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    ProposedNodeAuthorizedKeyModel: diesel::Queryable<(Text, Text, Binary, Integer), C::Backend>,
+    ProposedCircuitAttachmentModel:
+        diesel::Queryable<(Text, Text, Text, Text, Integer), C::Backend>,
 {
     fn get_proposal(
         &self,
@@ -126,6 +133,19 @@ where
                 }
             }
 
+            // Collect the authorized public keys of the proposed nodes, in order
+            let mut authorized_keys: HashMap<String, Vec<PublicKey>> = HashMap::new();
+            for key in proposed_node_authorized_key::table
+                .filter(proposed_node_authorized_key::circuit_id.eq(&proposal.circuit_id))
+                .order(proposed_node_authorized_key::position)
+                .load::<ProposedNodeAuthorizedKeyModel>(self.conn)?
+            {
+                authorized_keys
+                    .entry(key.node_id)
+                    .or_insert_with(Vec::new)
+                    .push(PublicKey::from_bytes(key.public_key));
+            }
+
             let mut nodes_vec: Vec<ProposedNodeModel> =
                 nodes.into_iter().map(|(_, node)| node).collect();
             nodes_vec.sort_by_key(|node| node.position);
@@ -148,6 +168,10 @@ where
                             builder.with_public_key(&PublicKey::from_bytes(public_key.to_vec()))
                     }
 
+                    if let Some(keys) = authorized_keys.get(&node.node_id) {
+                        builder = builder.with_authorized_public_keys(keys);
+                    }
+
                     builder
                         .build()
                         .map_err(AdminServiceStoreError::InvalidStateError)
@@ -235,6 +259,16 @@ where
                 .into_iter()
                 .filter_map(|vote| VoteRecord::try_from(&vote).ok())
                 .collect();
+
+            // Retrieve all associated attachments, in order
+            let attachments = proposed_circuit_attachment::table
+                .filter(proposed_circuit_attachment::circuit_id.eq(&proposal.circuit_id))
+                .order(proposed_circuit_attachment::position)
+                .load::<ProposedCircuitAttachmentModel>(self.conn)?
+                .iter()
+                .map(ProposedAttachment::try_from)
+                .collect::<Result<Vec<ProposedAttachment>, AdminServiceStoreError>>()?;
+
             let mut builder = ProposedCircuitBuilder::new()
                 .with_circuit_id(&proposal.circuit_id)
                 .with_roster(&built_proposed_services)
@@ -247,7 +281,8 @@ where
                 .with_routes(&RouteType::try_from(proposed_circuit.routes)?)
                 .with_circuit_management_type(&proposed_circuit.circuit_management_type)
                 .with_circuit_version(proposed_circuit.circuit_version)
-                .with_circuit_status(&CircuitStatus::from(&proposed_circuit.circuit_status));
+                .with_circuit_status(&CircuitStatus::from(&proposed_circuit.circuit_status))
+                .with_attachments(&attachments);
 
             if let Some(application_metadata) = &proposed_circuit.application_metadata {
                 builder = builder.with_application_metadata(application_metadata);
@@ -261,6 +296,12 @@ where
                 builder = builder.with_display_name(display_name)
             }
 
+            if let Some(max_message_size) =
+                max_message_size_from_model(proposed_circuit.max_message_size)
+            {
+                builder = builder.with_max_message_size(max_message_size)
+            }
+
             let native_proposed_circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
@@ -20,13 +20,21 @@ use std::convert::TryFrom;
 use diesel::sql_types::{Binary, Integer, Nullable, Text};
 use diesel::{dsl::exists, prelude::*};
 
+#[cfg(feature = "admin-service-requester-filter")]
+use crate::admin::store::diesel::schema::admin_event_circuit_proposal;
+#[cfg(feature = "circuit-tombstones")]
+use crate::admin::store::diesel::{models::CircuitTombstoneModel, schema::circuit_tombstone};
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitMemberModel, CircuitModel, CircuitStatusModel, NodeEndpointModel,
-            ServiceArgumentModel, ServiceModel,
+            max_message_size_from_model, CircuitMemberAuthorizedKeyModel, CircuitMemberModel,
+            CircuitModel, CircuitStatusModel, NodeEndpointModel, ServiceArgumentModel,
+            ServiceModel,
+        },
+        schema::{
+            circuit, circuit_member, circuit_member_authorized_key, node_endpoint, service,
+            service_argument,
         },
-        schema::{circuit, circuit_member, node_endpoint, service, service_argument},
     },
     error::AdminServiceStoreError,
     AuthorizationType, Circuit, CircuitBuilder, CircuitNode, CircuitNodeBuilder, CircuitPredicate,
@@ -52,6 +60,7 @@ where
     i32: diesel::deserialize::FromSql<Integer, C::Backend>,
     i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
     CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    CircuitMemberAuthorizedKeyModel: diesel::Queryable<(Text, Text, Binary, Integer), C::Backend>,
 {
     fn list_circuits(
         &self,
@@ -74,6 +83,24 @@ where
             })
             .flatten()
             .collect();
+        // Collects the requesters included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-requester-filter")]
+        let requesters: Vec<Vec<u8>> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::RequesterEq(requester) => Some(requester.as_slice().to_vec()),
+                _ => None,
+            })
+            .collect();
+        // Collects the service types included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-type-filter")]
+        let service_types: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::ServiceTypeEq(service_type) => Some(service_type.to_string()),
+                _ => None,
+            })
+            .collect();
         let statuses: Vec<CircuitStatusModel> = predicates
             .iter()
             .filter_map(|pred| match pred {
@@ -81,6 +108,10 @@ where
                 _ => None,
             })
             .collect();
+        #[cfg(feature = "circuit-tombstones")]
+        let include_tombstones = predicates
+            .iter()
+            .any(|pred| matches!(pred, CircuitPredicate::IncludeTombstones));
         self.conn
             .transaction::<Box<dyn ExactSizeIterator<Item = Circuit>>, _, _>(|| {
                 // Collects circuits which match the circuit predicates
@@ -102,6 +133,32 @@ where
                     ));
                 }
 
+                #[cfg(feature = "admin-service-requester-filter")]
+                if !requesters.is_empty() {
+                    // Circuits do not store their requester, so select the circuits that were
+                    // created from a proposal submitted by any of the requesters in the circuit
+                    // predicates
+                    let requested_circuit_ids: Vec<String> = admin_event_circuit_proposal::table
+                        .filter(admin_event_circuit_proposal::requester.eq_any(requesters))
+                        .select(admin_event_circuit_proposal::circuit_id)
+                        .distinct()
+                        .load::<String>(self.conn)?;
+                    query = query.filter(circuit::circuit_id.eq_any(requested_circuit_ids));
+                }
+
+                #[cfg(feature = "admin-service-type-filter")]
+                if !service_types.is_empty() {
+                    query = query.filter(exists(
+                        // Selects all `service` entries where the `service_type` is equal to any of the
+                        // service types in the circuit predicates
+                        service::table.filter(
+                            service::circuit_id
+                                .eq(circuit::circuit_id)
+                                .and(service::service_type.eq_any(service_types)),
+                        ),
+                    ));
+                }
+
                 if statuses.is_empty() {
                     // By default, only display active circuits
                     query = query.filter(circuit::circuit_status.eq(CircuitStatusModel::Active));
@@ -112,6 +169,14 @@ where
                     );
                 }
 
+                #[cfg(feature = "circuit-tombstones")]
+                if !include_tombstones {
+                    query = query.filter(diesel::dsl::not(exists(
+                        circuit_tombstone::table
+                            .filter(circuit_tombstone::circuit_id.eq(circuit::circuit_id)),
+                    )));
+                }
+
                 let circuits: Vec<CircuitModel> = query
                     .order(circuit::circuit_id.desc())
                     .load::<CircuitModel>(self.conn)?;
@@ -149,6 +214,33 @@ where
                     }
                 }
 
+                // Collect the authorized public keys of the members, keyed by
+                // (`circuit_id`, `node_id`)
+                let mut authorized_keys: HashMap<(String, String), Vec<PublicKey>> = HashMap::new();
+                for key in circuit_member_authorized_key::table
+                    .filter(circuit_member_authorized_key::circuit_id.eq_any(&circuit_ids))
+                    .order(circuit_member_authorized_key::position)
+                    .load::<CircuitMemberAuthorizedKeyModel>(self.conn)?
+                {
+                    authorized_keys
+                        .entry((key.circuit_id, key.node_id))
+                        .or_insert_with(Vec::new)
+                        .push(PublicKey::from_bytes(key.public_key));
+                }
+
+                // Collect the tombstones of the circuits, keyed by `circuit_id`
+                #[cfg(feature = "circuit-tombstones")]
+                let mut tombstones: HashMap<String, CircuitTombstoneModel> = if include_tombstones {
+                    circuit_tombstone::table
+                        .filter(circuit_tombstone::circuit_id.eq_any(&circuit_ids))
+                        .load::<CircuitTombstoneModel>(self.conn)?
+                        .into_iter()
+                        .map(|tombstone| (tombstone.circuit_id.to_string(), tombstone))
+                        .collect()
+                } else {
+                    HashMap::new()
+                };
+
                 // Create HashMap of (`circuit_id`, ` service_id`) to a `ServiceModel`
                 let mut services: HashMap<(String, String), ServiceModel> = HashMap::new();
                 // Create HashMap of (`circuit_id`, `service_id`) to the associated argument values
@@ -258,6 +350,11 @@ where
                     if let Some(display_name) = &model.display_name {
                         circuit_builder = circuit_builder.with_display_name(display_name);
                     }
+                    if let Some(max_message_size) =
+                        max_message_size_from_model(model.max_message_size)
+                    {
+                        circuit_builder = circuit_builder.with_max_message_size(max_message_size);
+                    }
                     if let Some(members) = circuit_members.get_mut(&model.circuit_id) {
                         members.sort_by_key(|node| node.position);
 
@@ -277,6 +374,13 @@ where
                                     ));
                                 }
 
+                                if let Some(keys) = authorized_keys.get(&(
+                                    member.circuit_id.to_string(),
+                                    member.node_id.to_string(),
+                                )) {
+                                    builder = builder.with_authorized_public_keys(keys);
+                                }
+
                                 builder.build()
                             })
                             .collect::<Result<Vec<CircuitNode>, InvalidStateError>>()
@@ -287,6 +391,10 @@ where
                     if let Some(services) = built_services.get(&model.circuit_id) {
                         circuit_builder = circuit_builder.with_roster(services);
                     }
+                    #[cfg(feature = "circuit-tombstones")]
+                    if let Some(tombstone) = tombstones.remove(&model.circuit_id) {
+                        circuit_builder = circuit_builder.with_tombstone(tombstone.into());
+                    }
 
                     ret_circuits.push(
                         circuit_builder
@@ -16,6 +16,8 @@
 
 use diesel::{prelude::*, types::HasSqlType};
 
+#[cfg(feature = "admin-service-event-retention")]
+use super::check_events_retained::AdminServiceStoreCheckEventsRetainedOperation;
 use super::{list_events::AdminServiceStoreListEventsOperation, AdminServiceStoreOperations};
 
 use crate::admin::store::{
@@ -48,6 +50,9 @@ where
         start: i64,
     ) -> Result<EventIter, AdminServiceStoreError> {
         self.conn.transaction::<EventIter, _, _>(|| {
+            #[cfg(feature = "admin-service-event-retention")]
+            self.check_events_retained(start)?;
+
             let event_ids: Vec<i64> = admin_event_proposed_circuit::table
                 .filter(admin_event_proposed_circuit::event_id.gt(start))
                 .filter(admin_event_proposed_circuit::circuit_management_type.eq(management_type))
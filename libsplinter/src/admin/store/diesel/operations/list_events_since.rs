@@ -16,6 +16,8 @@
 
 use diesel::{prelude::*, types::HasSqlType};
 
+#[cfg(feature = "admin-service-event-retention")]
+use super::check_events_retained::AdminServiceStoreCheckEventsRetainedOperation;
 use super::{list_events::AdminServiceStoreListEventsOperation, AdminServiceStoreOperations};
 
 use crate::admin::store::{diesel::schema::admin_service_event, AdminServiceStoreError, EventIter};
@@ -36,6 +38,9 @@ where
 {
     fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
         self.conn.transaction::<EventIter, _, _>(|| {
+            #[cfg(feature = "admin-service-event-retention")]
+            self.check_events_retained(start)?;
+
             let event_ids: Vec<i64> = admin_service_event::table
                 .filter(admin_service_event::id.gt(start))
                 .select(admin_service_event::id)
@@ -20,24 +20,27 @@ use std::convert::TryFrom;
 use diesel::{
     dsl::exists,
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
+            max_message_size_from_model, CircuitProposalModel, ProposedCircuitAttachmentModel,
+            ProposedCircuitModel, ProposedNodeAuthorizedKeyModel, ProposedNodeEndpointModel,
             ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
-            circuit_proposal, proposed_circuit, proposed_node, proposed_node_endpoint,
-            proposed_service, proposed_service_argument, vote_record,
+            circuit_proposal, proposed_circuit, proposed_circuit_attachment, proposed_node,
+            proposed_node_authorized_key, proposed_node_endpoint, proposed_service,
+            proposed_service_argument, vote_record,
         },
     },
     error::AdminServiceStoreError,
     AuthorizationType, CircuitPredicate, CircuitProposal, CircuitProposalBuilder, CircuitStatus,
-    DurabilityType, PersistenceType, ProposalType, ProposedCircuitBuilder, ProposedNode,
-    ProposedNodeBuilder, ProposedService, ProposedServiceBuilder, RouteType, VoteRecord,
+    DurabilityType, PersistenceType, ProposalType, ProposedAttachment, ProposedCircuitBuilder,
+    ProposedNode, ProposedNodeBuilder, ProposedService, ProposedServiceBuilder, RouteType,
+    VoteRecord,
 };
 use crate::error::InvalidStateError;
 use crate::public_key::PublicKey;
@@ -71,11 +74,16 @@ where
             Nullable<Text>,
             Integer,
             SmallInt,
+            Nullable<BigInt>,
         ),
         C::Backend,
     >,
-    VoteRecordModel: diesel::Queryable<(Text, Binary, Text, Text, Integer), C::Backend>,
+    VoteRecordModel:
+        diesel::Queryable<(Text, Binary, Text, Text, Integer, Nullable<Text>), C::Backend>,
     ProposedNodeModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    ProposedNodeAuthorizedKeyModel: diesel::Queryable<(Text, Text, Binary, Integer), C::Backend>,
+    ProposedCircuitAttachmentModel:
+        diesel::Queryable<(Text, Text, Text, Text, Integer), C::Backend>,
 {
     fn list_proposals(
         &self,
@@ -98,6 +106,24 @@ where
             })
             .flatten()
             .collect();
+        // Collects the requesters included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-requester-filter")]
+        let requesters: Vec<Vec<u8>> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::RequesterEq(requester) => Some(requester.as_slice().to_vec()),
+                _ => None,
+            })
+            .collect();
+        // Collects the service types included in the list of `CircuitPredicates`
+        #[cfg(feature = "admin-service-type-filter")]
+        let service_types: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::ServiceTypeEq(service_type) => Some(service_type.to_string()),
+                _ => None,
+            })
+            .collect();
 
         self.conn
             .transaction::<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, _, _>(|| {
@@ -117,6 +143,32 @@ where
                     ))
                 }
 
+                #[cfg(feature = "admin-service-requester-filter")]
+                if !requesters.is_empty() {
+                    query = query.filter(exists(
+                        // Selects all `circuit_proposal` entries where the `requester` is equal to
+                        // any of the requesters in the circuit predicates
+                        circuit_proposal::table.filter(
+                            circuit_proposal::circuit_id
+                                .eq(proposed_circuit::circuit_id)
+                                .and(circuit_proposal::requester.eq_any(requesters)),
+                        ),
+                    ));
+                }
+
+                #[cfg(feature = "admin-service-type-filter")]
+                if !service_types.is_empty() {
+                    query = query.filter(exists(
+                        // Selects all `proposed_service` entries where the `service_type` is equal to any of
+                        // the service types in the circuit predicates
+                        proposed_service::table.filter(
+                            proposed_service::circuit_id
+                                .eq(proposed_circuit::circuit_id)
+                                .and(proposed_service::service_type.eq_any(service_types)),
+                        ),
+                    ));
+                }
+
                 // Selects proposed circuits that match the management types
                 if !management_types.is_empty() {
                     query = query
@@ -202,6 +254,13 @@ where
                                 proposed_circuit_builder.with_display_name(display_name);
                         }
 
+                        if let Some(max_message_size) =
+                            max_message_size_from_model(proposed_circuit.max_message_size)
+                        {
+                            proposed_circuit_builder =
+                                proposed_circuit_builder.with_max_message_size(max_message_size);
+                        }
+
                         Ok((
                             proposed_circuit.circuit_id.to_string(),
                             (proposal_builder, proposed_circuit_builder),
@@ -288,6 +347,18 @@ where
                     }
                 }
 
+                // Collect the authorized public keys of the proposed nodes, in order
+                let mut authorized_keys: HashMap<(String, String), Vec<PublicKey>> = HashMap::new();
+                for key in proposed_node_authorized_key::table
+                    .order(proposed_node_authorized_key::position)
+                    .load::<ProposedNodeAuthorizedKeyModel>(self.conn)?
+                {
+                    authorized_keys
+                        .entry((key.circuit_id, key.node_id))
+                        .or_insert_with(Vec::new)
+                        .push(PublicKey::from_bytes(key.public_key));
+                }
+
                 // Collect `ProposedNodes` and proposed node endpoints
                 let mut proposed_nodes: HashMap<(String, String), IndexedNodeBuilder> =
                     HashMap::new();
@@ -316,6 +387,12 @@ where
                                 .with_public_key(&PublicKey::from_bytes(public_key.to_vec()))
                         }
 
+                        if let Some(keys) = authorized_keys
+                            .get(&(node.circuit_id.to_string(), node.node_id.to_string()))
+                        {
+                            proposed_node = proposed_node.with_authorized_public_keys(keys);
+                        }
+
                         proposed_nodes.insert(
                             (node.circuit_id, node.node_id),
                             IndexedNodeBuilder {
@@ -388,6 +465,18 @@ where
                     }
                 }
 
+                // Collect attachments to apply to the `ProposedCircuit`, in order
+                let mut attachments: HashMap<String, Vec<ProposedAttachment>> = HashMap::new();
+                for attachment in proposed_circuit_attachment::table
+                    .order(proposed_circuit_attachment::position)
+                    .load::<ProposedCircuitAttachmentModel>(self.conn)?
+                {
+                    attachments
+                        .entry(attachment.circuit_id.to_string())
+                        .or_insert_with(Vec::new)
+                        .push(ProposedAttachment::try_from(&attachment)?);
+                }
+
                 let mut proposals: Vec<CircuitProposal> = Vec::new();
                 for (circuit_id, (mut proposal_builder, mut proposed_circuit_builder)) in
                     proposal_builders
@@ -401,6 +490,10 @@ where
                     if let Some(votes) = vote_records.get(&circuit_id) {
                         proposal_builder = proposal_builder.with_votes(votes);
                     }
+                    if let Some(attachments) = attachments.get(&circuit_id) {
+                        proposed_circuit_builder =
+                            proposed_circuit_builder.with_attachments(attachments);
+                    }
                     proposals.push(
                         proposal_builder
                             .with_circuit(
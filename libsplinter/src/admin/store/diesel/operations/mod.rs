@@ -20,6 +20,11 @@ pub(super) mod add_circuit;
 pub(super) mod add_event;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub(super) mod add_proposal;
+#[cfg(all(
+    feature = "admin-service-event-retention",
+    any(feature = "sqlite", feature = "postgres")
+))]
+pub(super) mod check_events_retained;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub(super) mod count_circuits;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
@@ -46,10 +51,25 @@ pub(super) mod list_nodes;
 pub(super) mod list_proposals;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub(super) mod list_services;
+#[cfg(all(
+    feature = "admin-service-event-retention",
+    any(feature = "sqlite", feature = "postgres")
+))]
+pub(super) mod prune_events;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub(super) mod remove_circuit;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub(super) mod remove_proposal;
+#[cfg(all(
+    feature = "circuit-tombstones",
+    any(feature = "sqlite", feature = "postgres")
+))]
+pub(super) mod remove_tombstones;
+#[cfg(all(
+    feature = "circuit-tombstones",
+    any(feature = "sqlite", feature = "postgres")
+))]
+pub(super) mod tombstone_circuit;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub(super) mod update_circuit;
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
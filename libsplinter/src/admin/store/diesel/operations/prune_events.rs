@@ -0,0 +1,171 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "prune events" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+use std::time::SystemTime;
+
+use diesel::{
+    dsl::{delete, insert_into, max, update},
+    prelude::*,
+};
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{
+    diesel::{
+        models::to_db_timestamp,
+        schema::{admin_service_event, admin_service_event_truncation},
+    },
+    error::AdminServiceStoreError,
+};
+
+/// The ID of the single row of the `admin_service_event_truncation` table.
+const TRUNCATION_ID: i16 = 0;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStorePruneEventsOperation {
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStorePruneEventsOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.conn.transaction::<usize, _, _>(|| {
+            // Find the newest event to be removed; as event IDs increase over time, every event
+            // up to and including it is removed
+            let mut cutoff = None;
+            if let Some(before) = before {
+                cutoff = admin_service_event::table
+                    .filter(admin_service_event::created_at.lt(to_db_timestamp(before)))
+                    .select(max(admin_service_event::id))
+                    .first::<Option<i64>>(self.conn)?;
+            }
+            if let Some(max_count) = max_count {
+                let count_cutoff = admin_service_event::table
+                    .select(admin_service_event::id)
+                    .order(admin_service_event::id.desc())
+                    .offset(i64::try_from(max_count).unwrap_or(i64::MAX))
+                    .first::<i64>(self.conn)
+                    .optional()?;
+                cutoff = cutoff.max(count_cutoff);
+            }
+            let cutoff = match cutoff {
+                Some(cutoff) => cutoff,
+                None => return Ok(0),
+            };
+
+            // The `admin_event_*` tables are removed by cascade delete
+            let removed =
+                delete(admin_service_event::table.filter(admin_service_event::id.le(cutoff)))
+                    .execute(self.conn)?;
+
+            // Record the removed events, so that requests for events since a removed position
+            // can be rejected
+            if admin_service_event_truncation::table
+                .find(TRUNCATION_ID)
+                .select(admin_service_event_truncation::truncated_to)
+                .first::<i64>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                update(admin_service_event_truncation::table.find(TRUNCATION_ID))
+                    .set(admin_service_event_truncation::truncated_to.eq(cutoff))
+                    .execute(self.conn)?;
+            } else {
+                insert_into(admin_service_event_truncation::table)
+                    .values((
+                        admin_service_event_truncation::id.eq(TRUNCATION_ID),
+                        admin_service_event_truncation::truncated_to.eq(cutoff),
+                    ))
+                    .execute(self.conn)?;
+            }
+
+            Ok(removed)
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStorePruneEventsOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.conn.transaction::<usize, _, _>(|| {
+            // Find the newest event to be removed; as event IDs increase over time, every event
+            // up to and including it is removed
+            let mut cutoff = None;
+            if let Some(before) = before {
+                cutoff = admin_service_event::table
+                    .filter(admin_service_event::created_at.lt(to_db_timestamp(before)))
+                    .select(max(admin_service_event::id))
+                    .first::<Option<i64>>(self.conn)?;
+            }
+            if let Some(max_count) = max_count {
+                let count_cutoff = admin_service_event::table
+                    .select(admin_service_event::id)
+                    .order(admin_service_event::id.desc())
+                    .offset(i64::try_from(max_count).unwrap_or(i64::MAX))
+                    .first::<i64>(self.conn)
+                    .optional()?;
+                cutoff = cutoff.max(count_cutoff);
+            }
+            let cutoff = match cutoff {
+                Some(cutoff) => cutoff,
+                None => return Ok(0),
+            };
+
+            // The `admin_event_*` tables are removed by cascade delete
+            let removed =
+                delete(admin_service_event::table.filter(admin_service_event::id.le(cutoff)))
+                    .execute(self.conn)?;
+
+            // Record the removed events, so that requests for events since a removed position
+            // can be rejected
+            if admin_service_event_truncation::table
+                .find(TRUNCATION_ID)
+                .select(admin_service_event_truncation::truncated_to)
+                .first::<i64>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                update(admin_service_event_truncation::table.find(TRUNCATION_ID))
+                    .set(admin_service_event_truncation::truncated_to.eq(cutoff))
+                    .execute(self.conn)?;
+            } else {
+                insert_into(admin_service_event_truncation::table)
+                    .values((
+                        admin_service_event_truncation::id.eq(TRUNCATION_ID),
+                        admin_service_event_truncation::truncated_to.eq(cutoff),
+                    ))
+                    .execute(self.conn)?;
+            }
+
+            Ok(removed)
+        })
+    }
+}
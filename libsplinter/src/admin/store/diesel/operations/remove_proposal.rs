@@ -17,12 +17,15 @@
 use diesel::{
     dsl::delete,
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 
 use crate::admin::store::{
     diesel::{
-        models::{CircuitProposalModel, ProposedCircuitModel, ProposedNodeModel, VoteRecordModel},
+        models::{
+            CircuitProposalModel, ProposedCircuitAttachmentModel, ProposedCircuitModel,
+            ProposedNodeAuthorizedKeyModel, ProposedNodeModel, VoteRecordModel,
+        },
         schema::circuit_proposal,
     },
     error::AdminServiceStoreError,
@@ -54,11 +57,16 @@ where
             Nullable<Text>,
             Integer,
             SmallInt,
+            Nullable<BigInt>,
         ),
         C::Backend,
     >,
-    VoteRecordModel: diesel::Queryable<(Text, Binary, Text, Text, Integer), C::Backend>,
+    VoteRecordModel:
+        diesel::Queryable<(Text, Binary, Text, Text, Integer, Nullable<Text>), C::Backend>,
     ProposedNodeModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    ProposedNodeAuthorizedKeyModel: diesel::Queryable<(Text, Text, Binary, Integer), C::Backend>,
+    ProposedCircuitAttachmentModel:
+        diesel::Queryable<(Text, Text, Text, Text, Integer), C::Backend>,
 {
     fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
@@ -0,0 +1,90 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove tombstones" operation for the `DieselAdminServiceStore`.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::delete, prelude::*};
+
+use super::{remove_circuit::AdminServiceStoreRemoveCircuitOperation, AdminServiceStoreOperations};
+use crate::admin::store::{
+    diesel::{models::to_db_timestamp, schema::circuit_tombstone},
+    error::AdminServiceStoreError,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreRemoveTombstonesOperation {
+    fn remove_tombstones(
+        &self,
+        before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreRemoveTombstonesOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_tombstones(
+        &self,
+        before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.conn.transaction::<usize, _, _>(|| {
+            let mut query = circuit_tombstone::table
+                .into_boxed()
+                .select(circuit_tombstone::circuit_id);
+            if let Some(before) = before {
+                query = query.filter(circuit_tombstone::deleted_at.lt(to_db_timestamp(before)));
+            }
+            let circuit_ids = query.load::<String>(self.conn)?;
+
+            // Removing the circuit also removes any endpoints of its members that are no longer
+            // in use
+            for circuit_id in &circuit_ids {
+                delete(circuit_tombstone::table.find(circuit_id)).execute(self.conn)?;
+                self.remove_circuit(circuit_id)?;
+            }
+
+            Ok(circuit_ids.len())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreRemoveTombstonesOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_tombstones(
+        &self,
+        before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        self.conn.transaction::<usize, _, _>(|| {
+            let mut query = circuit_tombstone::table
+                .into_boxed()
+                .select(circuit_tombstone::circuit_id);
+            if let Some(before) = before {
+                query = query.filter(circuit_tombstone::deleted_at.lt(to_db_timestamp(before)));
+            }
+            let circuit_ids = query.load::<String>(self.conn)?;
+
+            // Removing the circuit also removes any endpoints of its members that are no longer
+            // in use
+            for circuit_id in &circuit_ids {
+                delete(circuit_tombstone::table.find(circuit_id)).execute(self.conn)?;
+                self.remove_circuit(circuit_id)?;
+            }
+
+            Ok(circuit_ids.len())
+        })
+    }
+}
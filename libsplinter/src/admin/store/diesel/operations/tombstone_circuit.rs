@@ -0,0 +1,127 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "tombstone circuit" operation for the `DieselAdminServiceStore`.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{
+    diesel::{
+        models::{to_db_timestamp, CircuitModel, CircuitTombstoneModel},
+        schema::{circuit, circuit_tombstone},
+    },
+    error::AdminServiceStoreError,
+};
+use crate::error::{ConstraintViolationError, ConstraintViolationType, InvalidStateError};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreTombstoneCircuitOperation {
+    fn tombstone_circuit(
+        &self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<(), AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreTombstoneCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn tombstone_circuit(
+        &self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the `circuit` entry to be tombstoned exists
+            circuit::table
+                .filter(circuit::circuit_id.eq(circuit_id))
+                .first::<CircuitModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
+                        String::from("Circuit does not exist in AdminServiceStore"),
+                    ))
+                })?;
+
+            if circuit_tombstone::table
+                .find(circuit_id)
+                .first::<CircuitTombstoneModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+                ));
+            }
+
+            insert_into(circuit_tombstone::table)
+                .values(CircuitTombstoneModel {
+                    circuit_id: circuit_id.into(),
+                    deleted_at: to_db_timestamp(SystemTime::now()),
+                    reason: reason.into(),
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreTombstoneCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn tombstone_circuit(
+        &self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the `circuit` entry to be tombstoned exists
+            circuit::table
+                .filter(circuit::circuit_id.eq(circuit_id))
+                .first::<CircuitModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
+                        String::from("Circuit does not exist in AdminServiceStore"),
+                    ))
+                })?;
+
+            if circuit_tombstone::table
+                .find(circuit_id)
+                .first::<CircuitTombstoneModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+                ));
+            }
+
+            insert_into(circuit_tombstone::table)
+                .values(CircuitTombstoneModel {
+                    circuit_id: circuit_id.into(),
+                    deleted_at: to_db_timestamp(SystemTime::now()),
+                    reason: reason.into(),
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -23,8 +23,13 @@ use diesel::{
 use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
-        models::{CircuitMemberModel, CircuitModel, ServiceArgumentModel, ServiceModel},
-        schema::{circuit, circuit_member, service, service_argument},
+        models::{
+            CircuitMemberAuthorizedKeyModel, CircuitMemberModel, CircuitModel,
+            ServiceArgumentModel, ServiceModel,
+        },
+        schema::{
+            circuit, circuit_member, circuit_member_authorized_key, service, service_argument,
+        },
     },
     error::AdminServiceStoreError,
     Circuit,
@@ -61,7 +66,9 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::circuit_version.eq(circuit_model.circuit_version),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::max_message_size.eq(circuit_model.max_message_size),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -76,6 +83,11 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                 circuit_member::table.filter(circuit_member::circuit_id.eq(circuit.circuit_id())),
             )
             .execute(self.conn)?;
+            delete(
+                circuit_member_authorized_key::table
+                    .filter(circuit_member_authorized_key::circuit_id.eq(circuit.circuit_id())),
+            )
+            .execute(self.conn)?;
             // Insert new data associate with the `Circuit`
             let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
             insert_into(service::table)
@@ -89,6 +101,10 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
+            let member_keys: Vec<CircuitMemberAuthorizedKeyModel> = Vec::try_from(&circuit)?;
+            insert_into(circuit_member_authorized_key::table)
+                .values(member_keys)
+                .execute(self.conn)?;
             Ok(())
         })
     }
@@ -120,7 +136,9 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::circuit_version.eq(circuit_model.circuit_version),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::max_message_size.eq(circuit_model.max_message_size),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -135,6 +153,11 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                 circuit_member::table.filter(circuit_member::circuit_id.eq(circuit.circuit_id())),
             )
             .execute(self.conn)?;
+            delete(
+                circuit_member_authorized_key::table
+                    .filter(circuit_member_authorized_key::circuit_id.eq(circuit.circuit_id())),
+            )
+            .execute(self.conn)?;
             // Insert new data associate with the `Circuit`
             let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
             insert_into(service::table)
@@ -148,6 +171,10 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
+            let member_keys: Vec<CircuitMemberAuthorizedKeyModel> = Vec::try_from(&circuit)?;
+            insert_into(circuit_member_authorized_key::table)
+                .values(member_keys)
+                .execute(self.conn)?;
             Ok(())
         })
     }
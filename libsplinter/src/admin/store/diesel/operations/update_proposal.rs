@@ -25,12 +25,14 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
-            ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
+            CircuitProposalModel, ProposedCircuitAttachmentModel, ProposedCircuitModel,
+            ProposedNodeAuthorizedKeyModel, ProposedNodeEndpointModel, ProposedNodeModel,
+            ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
-            circuit_proposal, proposed_circuit, proposed_node, proposed_node_endpoint,
-            proposed_service, proposed_service_argument, vote_record,
+            circuit_proposal, proposed_circuit, proposed_circuit_attachment, proposed_node,
+            proposed_node_authorized_key, proposed_node_endpoint, proposed_service,
+            proposed_service_argument, vote_record,
         },
     },
     error::AdminServiceStoreError,
@@ -83,6 +85,7 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     proposed_circuit::application_metadata
                         .eq(proposed_circuit_model.application_metadata),
                     proposed_circuit::comments.eq(proposed_circuit_model.comments),
+                    proposed_circuit::max_message_size.eq(proposed_circuit_model.max_message_size),
                 ))
                 .execute(self.conn)?;
 
@@ -96,6 +99,16 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     .filter(proposed_node_endpoint::circuit_id.eq(proposal.circuit_id())),
             )
             .execute(self.conn)?;
+            delete(
+                proposed_node_authorized_key::table
+                    .filter(proposed_node_authorized_key::circuit_id.eq(proposal.circuit_id())),
+            )
+            .execute(self.conn)?;
+            delete(
+                proposed_circuit_attachment::table
+                    .filter(proposed_circuit_attachment::circuit_id.eq(proposal.circuit_id())),
+            )
+            .execute(self.conn)?;
             delete(
                 proposed_service::table
                     .filter(proposed_service::circuit_id.eq(proposal.circuit_id())),
@@ -121,6 +134,18 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
             insert_into(proposed_node_endpoint::table)
                 .values(proposed_member_endpoints)
                 .execute(self.conn)?;
+            // Insert the authorized public keys of the proposed `members` of a `ProposedCircuit`
+            let proposed_member_keys: Vec<ProposedNodeAuthorizedKeyModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_node_authorized_key::table)
+                .values(proposed_member_keys)
+                .execute(self.conn)?;
+            // Insert the `attachments` of a `ProposedCircuit`
+            let proposed_attachments: Vec<ProposedCircuitAttachmentModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_circuit_attachment::table)
+                .values(proposed_attachments)
+                .execute(self.conn)?;
             // Insert `roster`, list of `Services` of a `ProposedCircuit`
             let proposed_service: Vec<ProposedServiceModel> = Vec::try_from(proposal.circuit())?;
             insert_into(proposed_service::table)
@@ -184,6 +209,7 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     proposed_circuit::application_metadata
                         .eq(proposed_circuit_model.application_metadata),
                     proposed_circuit::comments.eq(proposed_circuit_model.comments),
+                    proposed_circuit::max_message_size.eq(proposed_circuit_model.max_message_size),
                 ))
                 .execute(self.conn)?;
 
@@ -197,6 +223,16 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     .filter(proposed_node_endpoint::circuit_id.eq(proposal.circuit_id())),
             )
             .execute(self.conn)?;
+            delete(
+                proposed_node_authorized_key::table
+                    .filter(proposed_node_authorized_key::circuit_id.eq(proposal.circuit_id())),
+            )
+            .execute(self.conn)?;
+            delete(
+                proposed_circuit_attachment::table
+                    .filter(proposed_circuit_attachment::circuit_id.eq(proposal.circuit_id())),
+            )
+            .execute(self.conn)?;
             delete(
                 proposed_service::table
                     .filter(proposed_service::circuit_id.eq(proposal.circuit_id())),
@@ -223,6 +259,18 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
             insert_into(proposed_node_endpoint::table)
                 .values(proposed_member_endpoints)
                 .execute(self.conn)?;
+            // Insert the authorized public keys of the proposed `members` of a `ProposedCircuit`
+            let proposed_member_keys: Vec<ProposedNodeAuthorizedKeyModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_node_authorized_key::table)
+                .values(proposed_member_keys)
+                .execute(self.conn)?;
+            // Insert the `attachments` of a `ProposedCircuit`
+            let proposed_attachments: Vec<ProposedCircuitAttachmentModel> =
+                Vec::try_from(proposal.circuit())?;
+            insert_into(proposed_circuit_attachment::table)
+                .values(proposed_attachments)
+                .execute(self.conn)?;
             // Insert `roster`, list of `Services` of a `ProposedCircuit`
             let proposed_service: Vec<ProposedServiceModel> = Vec::try_from(proposal.circuit())?;
             insert_into(proposed_service::table)
@@ -74,6 +74,10 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 builder = builder.with_display_name(display_name);
             }
 
+            if let Some(max_message_size) = proposed_circuit.max_message_size() {
+                builder = builder.with_max_message_size(max_message_size);
+            }
+
             let circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
@@ -136,6 +140,10 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 builder = builder.with_display_name(display_name);
             }
 
+            if let Some(max_message_size) = proposed_circuit.max_message_size() {
+                builder = builder.with_max_message_size(max_message_size);
+            }
+
             let circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
@@ -35,6 +35,7 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        max_message_size -> Nullable<BigInt>,
     }
 }
 
@@ -45,6 +46,7 @@ table! {
         vote -> Text,
         voter_node_id -> Text,
         position -> Integer,
+        comment -> Nullable<Text>,
     }
 }
 
@@ -66,6 +68,25 @@ table! {
     }
 }
 
+table! {
+    proposed_node_authorized_key (circuit_id, node_id, public_key) {
+        circuit_id -> Text,
+        node_id -> Text,
+        public_key -> Binary,
+        position -> Integer,
+    }
+}
+
+table! {
+    proposed_circuit_attachment (circuit_id, name) {
+        circuit_id -> Text,
+        name -> Text,
+        sha256 -> Text,
+        uri -> Text,
+        position -> Integer,
+    }
+}
+
 table! {
     proposed_service (circuit_id, service_id) {
         circuit_id -> Text,
@@ -117,6 +138,7 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        max_message_size -> Nullable<BigInt>,
     }
 }
 
@@ -129,6 +151,15 @@ table! {
     }
 }
 
+table! {
+    circuit_member_authorized_key (circuit_id, node_id, public_key) {
+        circuit_id -> Text,
+        node_id -> Text,
+        public_key -> Binary,
+        position -> Integer,
+    }
+}
+
 table! {
     node_endpoint (node_id, endpoint) {
         node_id -> Text,
@@ -136,10 +167,20 @@ table! {
     }
 }
 
+table! {
+    circuit_tombstone (circuit_id) {
+        circuit_id -> Text,
+        deleted_at -> Int8,
+        reason -> Text,
+    }
+}
+
 allow_tables_to_appear_in_same_query!(
     proposed_circuit,
     proposed_node,
     proposed_node_endpoint,
+    proposed_node_authorized_key,
+    proposed_circuit_attachment,
     proposed_service,
     proposed_service_argument,
     vote_record,
@@ -151,7 +192,9 @@ allow_tables_to_appear_in_same_query!(
     service_argument,
     circuit,
     circuit_member,
-    node_endpoint
+    circuit_member_authorized_key,
+    node_endpoint,
+    circuit_tombstone
 );
 
 table! {
@@ -159,6 +202,16 @@ table! {
         id -> Int8,
         event_type -> Text,
         data -> Nullable<Binary>,
+        created_at -> Int8,
+        rejecting_node_id -> Nullable<Text>,
+        rejection_reason -> Nullable<Text>,
+    }
+}
+
+table! {
+    admin_service_event_truncation (id) {
+        id -> Int2,
+        truncated_to -> Int8,
     }
 }
 
@@ -197,6 +250,7 @@ table! {
         vote -> Text,
         voter_node_id -> Text,
         position -> Integer,
+        comment -> Nullable<Text>,
     }
 }
 
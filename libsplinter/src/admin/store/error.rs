@@ -34,6 +34,10 @@ pub enum AdminServiceStoreError {
     /// Represents when an operation cannot be completed because the state of the underlying
     /// struct is inconsistent.
     InvalidStateError(InvalidStateError),
+    /// Represents when events are requested since a position whose following events have been
+    /// removed by the event retention policy.
+    #[cfg(feature = "admin-service-event-retention")]
+    EventsTruncated { since: i64, truncated_to: i64 },
 }
 
 impl Error for AdminServiceStoreError {
@@ -43,6 +47,8 @@ impl Error for AdminServiceStoreError {
             AdminServiceStoreError::ConstraintViolationError(err) => Some(err),
             AdminServiceStoreError::ResourceTemporarilyUnavailableError(err) => Some(err),
             AdminServiceStoreError::InvalidStateError(err) => Some(err),
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminServiceStoreError::EventsTruncated { .. } => None,
         }
     }
 }
@@ -56,6 +62,15 @@ impl fmt::Display for AdminServiceStoreError {
                 write!(f, "{}", err)
             }
             AdminServiceStoreError::InvalidStateError(err) => write!(f, "{}", err),
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminServiceStoreError::EventsTruncated {
+                since,
+                truncated_to,
+            } => write!(
+                f,
+                "Events since {} are no longer available; events up to {} have been removed",
+                since, truncated_to
+            ),
         }
     }
 }
@@ -35,9 +35,22 @@ pub struct AdminServiceEvent {
 /// Native representation of the `AdminServiceEvent` enum variants
 pub enum EventType {
     ProposalSubmitted,
-    ProposalVote { requester: PublicKey },
-    ProposalAccepted { requester: PublicKey },
-    ProposalRejected { requester: PublicKey },
+    ProposalVote {
+        requester: PublicKey,
+    },
+    ProposalAccepted {
+        requester: PublicKey,
+    },
+    ProposalRejected {
+        requester: PublicKey,
+    },
+    /// A member node failed to validate the proposed change
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    ProposalInvalid {
+        requester: PublicKey,
+        node_id: String,
+        reason: String,
+    },
     CircuitReady,
     CircuitDisbanded,
 }
@@ -166,6 +179,18 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                 },
                 proposal,
             }),
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            messages::AdminServiceEvent::ProposalInvalid((_, data, failure)) => {
+                Ok(AdminServiceEvent {
+                    event_id,
+                    event_type: EventType::ProposalInvalid {
+                        requester: data.to_vec(),
+                        node_id: failure.node_id.to_string(),
+                        reason: failure.reason.to_string(),
+                    },
+                    proposal,
+                })
+            }
             messages::AdminServiceEvent::CircuitReady(_) => Ok(AdminServiceEvent {
                 event_id,
                 event_type: EventType::CircuitReady,
@@ -0,0 +1,347 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! An in-memory implementation of the [`AdminServiceEventStore`].
+//!
+//! The public interface includes the struct [`MemoryAdminServiceEventStore`]. Events are only
+//! held for the lifetime of the store, making it suitable for tests and ephemeral deployments
+//! that do not have a database available.
+//!
+//! [`AdminServiceEventStore`]: ../trait.AdminServiceEventStore.html
+//! [`MemoryAdminServiceEventStore`]: struct.MemoryAdminServiceEventStore.html
+
+use std::convert::TryFrom;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use crate::admin::service::messages;
+use crate::error::InternalError;
+
+use super::error::AdminServiceStoreError;
+use super::{AdminServiceEvent, AdminServiceEventStore, EventIter};
+
+/// An in-memory implementation of the `AdminServiceEventStore`
+///
+/// Event IDs are assigned sequentially, starting at 1. Clones of the store share the same
+/// underlying events.
+#[derive(Clone, Default)]
+pub struct MemoryAdminServiceEventStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// The stored events, along with the time each was recorded
+    events: Vec<(SystemTime, AdminServiceEvent)>,
+    /// The ID of the newest event that has been assigned, including events that were pruned
+    last_event_id: i64,
+    /// The ID of the newest event removed by `prune_events`
+    #[cfg(feature = "admin-service-event-retention")]
+    truncated_to: i64,
+}
+
+impl MemoryAdminServiceEventStore {
+    /// Creates a new, empty `MemoryAdminServiceEventStore`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_inner(&self) -> Result<MutexGuard<Inner>, AdminServiceStoreError> {
+        self.inner.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "Admin service event store lock was poisoned".to_string(),
+            ))
+        })
+    }
+
+    fn list_events_matching<F>(
+        &self,
+        start: i64,
+        predicate: F,
+    ) -> Result<EventIter, AdminServiceStoreError>
+    where
+        F: Fn(&AdminServiceEvent) -> bool,
+    {
+        let inner = self.lock_inner()?;
+
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            if start > 0 && start < inner.truncated_to {
+                return Err(AdminServiceStoreError::EventsTruncated {
+                    since: start,
+                    truncated_to: inner.truncated_to,
+                });
+            }
+        }
+
+        let matching = inner
+            .events
+            .iter()
+            .map(|(_, event)| event)
+            .filter(|event| *event.event_id() > start && predicate(event))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(matching.into_iter()))
+    }
+}
+
+impl AdminServiceEventStore for MemoryAdminServiceEventStore {
+    fn add_event(
+        &self,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        let mut inner = self.lock_inner()?;
+
+        let event_id = inner.last_event_id + 1;
+        let admin_event = AdminServiceEvent::try_from((event_id, &event))
+            .map_err(AdminServiceStoreError::InvalidStateError)?;
+
+        inner.last_event_id = event_id;
+        inner.events.push((SystemTime::now(), admin_event.clone()));
+
+        Ok(admin_event)
+    }
+
+    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
+        self.list_events_matching(start, |_| true)
+    }
+
+    fn list_events_by_management_type_since(
+        &self,
+        management_type: String,
+        start: i64,
+    ) -> Result<EventIter, AdminServiceStoreError> {
+        self.list_events_matching(start, |event| {
+            event.proposal().circuit().circuit_management_type() == management_type
+        })
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        let mut inner = self.lock_inner()?;
+
+        let expired = match before {
+            Some(before) => inner
+                .events
+                .iter()
+                .take_while(|(recorded_at, _)| *recorded_at < before)
+                .count(),
+            None => 0,
+        };
+        let excess = match max_count {
+            Some(max_count) => inner
+                .events
+                .len()
+                .saturating_sub(usize::try_from(max_count).unwrap_or(usize::MAX)),
+            None => 0,
+        };
+
+        let removed = expired.max(excess);
+        let newest_removed = inner
+            .events
+            .drain(..removed)
+            .last()
+            .map(|(_, event)| *event.event_id());
+        if let Some(newest_removed) = newest_removed {
+            inner.truncated_to = newest_removed;
+        }
+
+        Ok(removed)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn AdminServiceEventStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::store::{
+        CircuitProposal, CircuitProposalBuilder, EventType, ProposalType, ProposedCircuitBuilder,
+        ProposedNodeBuilder, ProposedServiceBuilder,
+    };
+    use crate::hex::parse_hex;
+    use crate::public_key::PublicKey;
+
+    /// Verify that events added to the store are assigned sequential IDs and can be listed by
+    /// index and by circuit management type.
+    ///
+    /// 1. Add a proposal submitted event with management type "test"
+    /// 2. Add a circuit ready event with management type "other"
+    /// 3. Add a circuit ready event with management type "test"
+    /// 4. Validate that the event IDs are 1, 2 and 3
+    /// 5. Validate `list_events_since(1)` returns the last two events
+    /// 6. Validate `list_events_by_management_type_since("test", 0)` returns events 1 and 3
+    #[test]
+    fn test_add_and_list_events() {
+        let store = MemoryAdminServiceEventStore::new();
+
+        let first = store
+            .add_event(messages::AdminServiceEvent::ProposalSubmitted(
+                messages::CircuitProposal::from(create_proposal("test")),
+            ))
+            .expect("Unable to add event");
+        let second = store
+            .add_event(messages::AdminServiceEvent::CircuitReady(
+                messages::CircuitProposal::from(create_proposal("other")),
+            ))
+            .expect("Unable to add event");
+        let third = store
+            .add_event(messages::AdminServiceEvent::CircuitReady(
+                messages::CircuitProposal::from(create_proposal("test")),
+            ))
+            .expect("Unable to add event");
+
+        assert_eq!(*first.event_id(), 1);
+        assert_eq!(first.event_type(), &EventType::ProposalSubmitted);
+        assert_eq!(*second.event_id(), 2);
+        assert_eq!(*third.event_id(), 3);
+
+        let since: Vec<AdminServiceEvent> = store
+            .list_events_since(1)
+            .expect("Unable to list events")
+            .collect();
+        assert_eq!(since, vec![second.clone(), third.clone()]);
+
+        let by_type: Vec<AdminServiceEvent> = store
+            .list_events_by_management_type_since("test".to_string(), 0)
+            .expect("Unable to list events")
+            .collect();
+        assert_eq!(by_type, vec![first, third]);
+    }
+
+    /// Verify that a boxed clone of the store shares its events with the original.
+    #[test]
+    fn test_clone_shares_events() {
+        let store = MemoryAdminServiceEventStore::new();
+        let boxed = store.clone_boxed();
+
+        boxed
+            .add_event(messages::AdminServiceEvent::ProposalSubmitted(
+                messages::CircuitProposal::from(create_proposal("test")),
+            ))
+            .expect("Unable to add event");
+
+        assert_eq!(
+            store
+                .list_events_since(0)
+                .expect("Unable to list events")
+                .len(),
+            1
+        );
+    }
+
+    /// Verify that pruning removes the oldest events beyond the count limit, that listing events
+    /// since a removed position fails, and that event IDs are not reused after pruning.
+    ///
+    /// 1. Add three events
+    /// 2. Prune, keeping the newest event, and validate two events were removed
+    /// 3. Validate listing since event 1 returns `EventsTruncated`, while listing since 0 and 2
+    ///    returns the remaining event
+    /// 4. Prune all events recorded before a second from now and validate a new event is assigned the ID 4
+    #[cfg(feature = "admin-service-event-retention")]
+    #[test]
+    fn test_prune_events() {
+        use std::time::Duration;
+
+        let store = MemoryAdminServiceEventStore::new();
+        for _ in 0..3 {
+            store
+                .add_event(messages::AdminServiceEvent::CircuitReady(
+                    messages::CircuitProposal::from(create_proposal("test")),
+                ))
+                .expect("Unable to add event");
+        }
+
+        assert_eq!(
+            store
+                .prune_events(None, Some(1))
+                .expect("Unable to prune events"),
+            2
+        );
+
+        match store.list_events_since(1) {
+            Err(AdminServiceStoreError::EventsTruncated {
+                since,
+                truncated_to,
+            }) => {
+                assert_eq!(since, 1);
+                assert_eq!(truncated_to, 2);
+            }
+            Err(err) => panic!("Unexpected error: {}", err),
+            Ok(_) => panic!("Listing events since a pruned event should fail"),
+        }
+        for start in &[0, 2] {
+            let events: Vec<AdminServiceEvent> = store
+                .list_events_since(*start)
+                .expect("Unable to list events")
+                .collect();
+            assert_eq!(events.len(), 1);
+            assert_eq!(*events[0].event_id(), 3);
+        }
+
+        assert_eq!(
+            store
+                .prune_events(Some(SystemTime::now() + Duration::from_secs(1)), None)
+                .expect("Unable to prune events"),
+            1
+        );
+        let event = store
+            .add_event(messages::AdminServiceEvent::CircuitReady(
+                messages::CircuitProposal::from(create_proposal("test")),
+            ))
+            .expect("Unable to add event");
+        assert_eq!(*event.event_id(), 4);
+    }
+
+    fn create_proposal(management_type: &str) -> CircuitProposal {
+        CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_circuit_hash("7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-BBBBB")
+                    .with_roster(&[ProposedServiceBuilder::default()
+                        .with_service_id("a000")
+                        .with_service_type("scabbard")
+                        .with_node_id("acme-node-000")
+                        .with_arguments(&[("peer_services".into(), "[]".into())])
+                        .build()
+                        .expect("Unable to build service")])
+                    .with_members(&[ProposedNodeBuilder::default()
+                        .with_node_id("acme-node-000".into())
+                        .with_endpoints(&["tcps://splinterd-node-acme:8044".into()])
+                        .build()
+                        .expect("Unable to build node")])
+                    .with_circuit_version(1)
+                    .with_circuit_management_type(management_type)
+                    .build()
+                    .expect("Unable to build circuit"),
+            )
+            .with_requester(&PublicKey::from_bytes(
+                parse_hex("0283a14e0a17cb7f665311e9b5560f4cde2b502f17e2d03223e15d90d9318d7482")
+                    .expect("Unable to parse requester"),
+            ))
+            .with_requester_node_id("acme-node-000")
+            .build()
+            .expect("Unable to build proposal")
+    }
+}
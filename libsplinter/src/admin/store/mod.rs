@@ -22,7 +22,13 @@
 //!   supports SQLite databases (with the `sqlite` feature) and PostgreSQL databases (with the
 //!   `postgres` feature).
 //!
+//! Admin service events are stored through the [`AdminServiceEventStore`] trait. Any boxed
+//! `AdminServiceStore` may be used as an event store; [`MemoryAdminServiceEventStore`] provides
+//! an implementation that keeps events in memory, for use in tests and ephemeral deployments.
+//!
 //! [`AdminServiceStore`]: trait.AdminServiceStore.html
+//! [`AdminServiceEventStore`]: trait.AdminServiceEventStore.html
+//! [`MemoryAdminServiceEventStore`]: memory/struct.MemoryAdminServiceEventStore.html
 //! [`YamlAdminServiceStore`]: yaml/struct.YamlAdminServiceStore.html
 //! [`DieselAdminServiceStore`]: diesel/struct.DieselAdminServiceStore.html
 //! [`Diesel`]: https://crates.io/crates/diesel
@@ -34,17 +40,32 @@ mod circuit_proposal;
 pub mod diesel;
 pub mod error;
 mod event;
+pub mod memory;
+mod proposed_attachment;
 mod proposed_circuit;
 mod proposed_node;
 mod proposed_service;
+#[cfg(feature = "admin-service-event-retention")]
+mod retention;
+#[cfg(feature = "circuit-schema-migration")]
+mod schema_migration;
 mod service;
 pub mod yaml;
 
 use std::cmp::Ordering;
 use std::fmt;
+#[cfg(any(
+    feature = "admin-service-event-retention",
+    feature = "circuit-tombstones"
+))]
+use std::time::SystemTime;
 
 use crate::admin::service::messages;
+#[cfg(feature = "admin-service-requester-filter")]
+use crate::public_key::PublicKey;
 
+#[cfg(feature = "circuit-tombstones")]
+pub use self::circuit::CircuitTombstone;
 pub use self::circuit::{
     AuthorizationType, Circuit, CircuitBuilder, CircuitStatus, DurabilityType, PersistenceType,
     RouteType,
@@ -55,9 +76,17 @@ pub use self::circuit_proposal::{
 };
 use self::error::AdminServiceStoreError;
 pub use self::event::{AdminServiceEvent, AdminServiceEventBuilder, EventType};
+pub use self::proposed_attachment::{ProposedAttachment, ProposedAttachmentBuilder};
 pub use self::proposed_circuit::{ProposedCircuit, ProposedCircuitBuilder};
 pub use self::proposed_node::{ProposedNode, ProposedNodeBuilder};
 pub use self::proposed_service::{ProposedService, ProposedServiceBuilder};
+#[cfg(feature = "admin-service-event-retention")]
+pub use self::retention::{EventPruner, EventRetention};
+#[cfg(feature = "circuit-schema-migration")]
+pub use self::schema_migration::{
+    migrate_circuit_schemas, report_circuit_schemas, CircuitFieldChange, CircuitSchemaMigration,
+    CircuitSchemaReport, MigrationEligibility,
+};
 pub use self::service::{Service, ServiceBuilder};
 
 pub const UNSET_CIRCUIT_VERSION: i32 = 1;
@@ -130,6 +159,19 @@ pub enum CircuitPredicate {
     ManagementTypeEq(String),
     MembersInclude(Vec<String>),
     CircuitStatus(CircuitStatus),
+    /// Matches circuits and proposals that were requested by the given public key.
+    ///
+    /// Circuits do not record their requester, so stores resolve this predicate for circuits
+    /// using the proposal that created them. Applied directly to a `Circuit`, it never matches.
+    #[cfg(feature = "admin-service-requester-filter")]
+    RequesterEq(PublicKey),
+    /// Matches circuits and proposals that include at least one service of the given type.
+    #[cfg(feature = "admin-service-type-filter")]
+    ServiceTypeEq(String),
+    /// Includes circuits that have been tombstoned, which are otherwise omitted when listing or
+    /// counting circuits. This predicate does not filter circuits or proposals on its own.
+    #[cfg(feature = "circuit-tombstones")]
+    IncludeTombstones,
 }
 
 impl CircuitPredicate {
@@ -152,6 +194,15 @@ impl CircuitPredicate {
                 true
             }
             CircuitPredicate::CircuitStatus(status) => circuit.circuit_status() == status,
+            #[cfg(feature = "admin-service-requester-filter")]
+            CircuitPredicate::RequesterEq(_) => false,
+            #[cfg(feature = "admin-service-type-filter")]
+            CircuitPredicate::ServiceTypeEq(service_type) => circuit
+                .roster()
+                .iter()
+                .any(|service| service.service_type() == service_type),
+            #[cfg(feature = "circuit-tombstones")]
+            CircuitPredicate::IncludeTombstones => true,
         }
     }
 
@@ -177,6 +228,16 @@ impl CircuitPredicate {
             CircuitPredicate::CircuitStatus(status) => {
                 proposal.circuit().circuit_status() == status
             }
+            #[cfg(feature = "admin-service-requester-filter")]
+            CircuitPredicate::RequesterEq(requester) => proposal.requester() == requester,
+            #[cfg(feature = "admin-service-type-filter")]
+            CircuitPredicate::ServiceTypeEq(service_type) => proposal
+                .circuit()
+                .roster()
+                .iter()
+                .any(|service| service.service_type() == service_type),
+            #[cfg(feature = "circuit-tombstones")]
+            CircuitPredicate::IncludeTombstones => true,
         }
     }
 }
@@ -275,6 +336,38 @@ pub trait AdminServiceStore: Send + Sync {
     ///  Returns an error if a `Circuit` with the specified ID does not exist
     fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
 
+    /// Marks a circuit as deleted, in place of removing it from the store
+    ///
+    /// A tombstoned circuit is still returned by `get_circuit`, along with its tombstone, but is
+    /// omitted when listing or counting circuits unless the `IncludeTombstones` predicate is
+    /// provided. Its ID remains in use until the tombstone is removed.
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The unique ID of the circuit to be tombstoned
+    ///  * `reason` - Why the circuit was deleted
+    ///
+    ///  Returns an error if a `Circuit` with the specified ID does not exist, or if it has already
+    ///  been tombstoned
+    #[cfg(feature = "circuit-tombstones")]
+    fn tombstone_circuit(
+        &self,
+        circuit_id: &str,
+        reason: &str,
+    ) -> Result<(), AdminServiceStoreError>;
+
+    /// Removes tombstoned circuits from the store, along with their tombstones. Returns the
+    /// number of circuits removed.
+    ///
+    /// # Arguments
+    ///
+    ///  * `before` - If provided, only circuits tombstoned before this time are removed
+    #[cfg(feature = "circuit-tombstones")]
+    fn remove_tombstones(
+        &self,
+        before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError>;
+
     /// Fetches a circuit from the store
     ///
     /// # Arguments
@@ -373,6 +466,24 @@ pub trait AdminServiceStore: Send + Sync {
         start: i64,
     ) -> Result<EventIter, AdminServiceStoreError>;
 
+    /// Removes events recorded before `before`, along with all but the newest `max_count`
+    /// events. Returns the number of events removed.
+    ///
+    /// Once an event has been removed, listing events since any earlier position returns an
+    /// `EventsTruncated` error, as the events the caller has not yet seen are no longer
+    /// available.
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - events recorded before this time are removed
+    /// * `max_count` - the number of the newest events to keep
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError>;
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore>;
 }
 
@@ -381,3 +492,98 @@ impl Clone for Box<dyn AdminServiceStore> {
         self.clone_boxed()
     }
 }
+
+/// Interface for recording and reading back admin service events
+pub trait AdminServiceEventStore: Send + Sync {
+    /// Add an event to the store.  Returns the recorded event index and a copy of the event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - the `AdminServiceEvent` to be added to the store
+    fn add_event(
+        &self,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError>;
+
+    /// List `AdminServiceEvent`s that have been added to the store since the provided index.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - index used to filter events
+    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError>;
+
+    /// List `AdminServiceEvent`s, with a corresponding `CircuitProposal` that has the specified
+    /// `circuit_management_type`, that have been added to the store since the provided index.
+    ///
+    /// # Arguments
+    ///
+    /// * `management_type` - management type used to filter `CircuitProposal`s
+    /// * `start` - index used to filter events
+    fn list_events_by_management_type_since(
+        &self,
+        management_type: String,
+        start: i64,
+    ) -> Result<EventIter, AdminServiceStoreError>;
+
+    /// Removes events recorded before `before`, along with all but the newest `max_count`
+    /// events. Returns the number of events removed.
+    ///
+    /// Once an event has been removed, listing events since any earlier position returns an
+    /// `EventsTruncated` error, as the events the caller has not yet seen are no longer
+    /// available.
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - events recorded before this time are removed
+    /// * `max_count` - the number of the newest events to keep
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError>;
+
+    fn clone_boxed(&self) -> Box<dyn AdminServiceEventStore>;
+}
+
+impl Clone for Box<dyn AdminServiceEventStore> {
+    fn clone(&self) -> Self {
+        self.clone_boxed()
+    }
+}
+
+/// Allows an `AdminServiceStore` to be used as the event store, as is done when the admin
+/// service's circuit state and events share a database.
+impl AdminServiceEventStore for Box<dyn AdminServiceStore> {
+    fn add_event(
+        &self,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        AdminServiceStore::add_event(&**self, event)
+    }
+
+    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
+        AdminServiceStore::list_events_since(&**self, start)
+    }
+
+    fn list_events_by_management_type_since(
+        &self,
+        management_type: String,
+        start: i64,
+    ) -> Result<EventIter, AdminServiceStoreError> {
+        AdminServiceStore::list_events_by_management_type_since(&**self, management_type, start)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        before: Option<SystemTime>,
+        max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        AdminServiceStore::prune_events(&**self, before, max_count)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn AdminServiceEventStore> {
+        Box::new(AdminServiceStore::clone_boxed(&**self))
+    }
+}
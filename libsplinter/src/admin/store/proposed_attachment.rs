@@ -0,0 +1,152 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Structs for building proposed circuit attachments
+
+use crate::admin::messages;
+use crate::error::InvalidStateError;
+use crate::protos::admin;
+
+/// Native representation of a document attached to a proposed circuit
+///
+/// Only the sha256 hash of the document is recorded; the document itself is exchanged outside
+/// of Splinter and may be retrieved from the attachment's URI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposedAttachment {
+    name: String,
+    sha256: String,
+    uri: String,
+}
+
+impl ProposedAttachment {
+    /// Returns the name of the attachment
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the hex-encoded sha256 hash of the attached document
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// Returns the URI of the attached document
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn into_proto(self) -> admin::CircuitAttachment {
+        let mut proto = admin::CircuitAttachment::new();
+        proto.set_name(self.name);
+        proto.set_sha256(self.sha256);
+        proto.set_uri(self.uri);
+
+        proto
+    }
+
+    pub fn from_proto(mut proto: admin::CircuitAttachment) -> Self {
+        Self {
+            name: proto.take_name(),
+            sha256: proto.take_sha256(),
+            uri: proto.take_uri(),
+        }
+    }
+}
+
+/// Builder for creating a `ProposedAttachment`
+#[derive(Default, Clone)]
+pub struct ProposedAttachmentBuilder {
+    name: Option<String>,
+    sha256: Option<String>,
+    uri: Option<String>,
+}
+
+impl ProposedAttachmentBuilder {
+    /// Creates a `ProposedAttachmentBuilder`
+    pub fn new() -> Self {
+        ProposedAttachmentBuilder::default()
+    }
+
+    /// Returns the name in the builder
+    pub fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    /// Returns the sha256 hash in the builder
+    pub fn sha256(&self) -> Option<String> {
+        self.sha256.clone()
+    }
+
+    /// Returns the URI in the builder
+    pub fn uri(&self) -> Option<String> {
+        self.uri.clone()
+    }
+
+    /// Sets the name
+    ///
+    /// # Arguments
+    ///
+    ///  * `name` - The name of the attachment, unique within the proposal
+    pub fn with_name(mut self, name: &str) -> ProposedAttachmentBuilder {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the sha256 hash
+    ///
+    /// # Arguments
+    ///
+    ///  * `sha256` - The hex-encoded sha256 hash of the attached document
+    pub fn with_sha256(mut self, sha256: &str) -> ProposedAttachmentBuilder {
+        self.sha256 = Some(sha256.into());
+        self
+    }
+
+    /// Sets the URI
+    ///
+    /// # Arguments
+    ///
+    ///  * `uri` - Where the attached document may be retrieved from
+    pub fn with_uri(mut self, uri: &str) -> ProposedAttachmentBuilder {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Builds the `ProposedAttachment`
+    ///
+    /// Returns an error if the name, sha256 hash, or URI are not set
+    pub fn build(self) -> Result<ProposedAttachment, InvalidStateError> {
+        let name = self.name.ok_or_else(|| {
+            InvalidStateError::with_message("unable to build, missing field: `name`".to_string())
+        })?;
+
+        let sha256 = self.sha256.ok_or_else(|| {
+            InvalidStateError::with_message("unable to build, missing field: `sha256`".to_string())
+        })?;
+
+        let uri = self.uri.ok_or_else(|| {
+            InvalidStateError::with_message("unable to build, missing field: `uri`".to_string())
+        })?;
+
+        Ok(ProposedAttachment { name, sha256, uri })
+    }
+}
+
+impl From<&messages::CircuitAttachment> for ProposedAttachment {
+    fn from(attachment: &messages::CircuitAttachment) -> ProposedAttachment {
+        ProposedAttachment {
+            name: attachment.name.to_string(),
+            sha256: attachment.sha256.to_string(),
+            uri: attachment.uri.to_string(),
+        }
+    }
+}
@@ -21,8 +21,8 @@ use crate::error::InvalidStateError;
 use crate::protos::admin;
 
 use super::{
-    AuthorizationType, CircuitStatus, DurabilityType, PersistenceType, ProposedNode,
-    ProposedService, RouteType, UNSET_CIRCUIT_VERSION,
+    AuthorizationType, CircuitStatus, DurabilityType, PersistenceType, ProposedAttachment,
+    ProposedNode, ProposedService, RouteType, UNSET_CIRCUIT_VERSION,
 };
 
 /// Native representation of a circuit that is being proposed in a proposal
@@ -41,6 +41,8 @@ pub struct ProposedCircuit {
     display_name: Option<String>,
     circuit_version: i32,
     circuit_status: CircuitStatus,
+    attachments: Vec<ProposedAttachment>,
+    max_message_size: Option<u64>,
 }
 
 impl ProposedCircuit {
@@ -108,6 +110,16 @@ impl ProposedCircuit {
         &self.circuit_status
     }
 
+    /// Returns the documents attached to the proposed circuit
+    pub fn attachments(&self) -> &[ProposedAttachment] {
+        &self.attachments
+    }
+
+    /// Returns the maximum message payload size requested for the circuit, if any
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
+
     pub fn from_proto(mut proto: admin::Circuit) -> Result<Self, InvalidStateError> {
         let authorization_type = match proto.get_authorization_type() {
             admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION => AuthorizationType::Trust,
@@ -183,6 +195,11 @@ impl ProposedCircuit {
             proto.get_circuit_version()
         };
 
+        let max_message_size = match proto.get_max_message_size() {
+            0 => None,
+            max_message_size => Some(max_message_size),
+        };
+
         Ok(Self {
             circuit_id: proto.take_circuit_id(),
             roster: proto
@@ -205,6 +222,12 @@ impl ProposedCircuit {
             display_name,
             circuit_version,
             circuit_status,
+            attachments: proto
+                .take_attachments()
+                .into_iter()
+                .map(ProposedAttachment::from_proto)
+                .collect(),
+            max_message_size,
         })
     }
 
@@ -239,6 +262,17 @@ impl ProposedCircuit {
             circuit.set_display_name(display_name);
         }
 
+        circuit.set_attachments(protobuf::RepeatedField::from_vec(
+            self.attachments
+                .into_iter()
+                .map(ProposedAttachment::into_proto)
+                .collect(),
+        ));
+
+        if let Some(max_message_size) = self.max_message_size {
+            circuit.set_max_message_size(max_message_size);
+        }
+
         if self.circuit_version != UNSET_CIRCUIT_VERSION {
             circuit.set_circuit_version(self.circuit_version);
         }
@@ -306,6 +340,8 @@ pub struct ProposedCircuitBuilder {
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
+    attachments: Vec<ProposedAttachment>,
+    max_message_size: Option<u64>,
 }
 
 impl ProposedCircuitBuilder {
@@ -379,6 +415,16 @@ impl ProposedCircuitBuilder {
         self.circuit_status.clone()
     }
 
+    /// Returns the attachments of the circuit proposal in the builder
+    pub fn attachments(&self) -> Vec<ProposedAttachment> {
+        self.attachments.clone()
+    }
+
+    /// Returns the maximum message payload size in the builder
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
+
     /// Sets the circuit ID
     ///
     /// # Arguments
@@ -517,6 +563,30 @@ impl ProposedCircuitBuilder {
         self
     }
 
+    /// Sets the attachments
+    ///
+    /// # Arguments
+    ///
+    ///  * `attachments` - The documents attached to the proposed circuit
+    pub fn with_attachments(
+        mut self,
+        attachments: &[ProposedAttachment],
+    ) -> ProposedCircuitBuilder {
+        self.attachments = attachments.to_vec();
+        self
+    }
+
+    /// Sets the maximum message payload size
+    ///
+    /// # Arguments
+    ///
+    ///  * `max_message_size` - The largest service message payload, in bytes, that may be sent
+    ///    on the circuit
+    pub fn with_max_message_size(mut self, max_message_size: u64) -> ProposedCircuitBuilder {
+        self.max_message_size = Some(max_message_size);
+        self
+    }
+
     /// Builds a `ProposedCircuit`
     ///
     /// Returns an error if the circuit ID, roster, members or circuit management
@@ -570,6 +640,12 @@ impl ProposedCircuitBuilder {
 
         let circuit_status = self.circuit_status.unwrap_or_default();
 
+        if self.max_message_size == Some(0) {
+            return Err(InvalidStateError::with_message(
+                "max_message_size must be greater than 0".to_string(),
+            ));
+        }
+
         let create_circuit_message = ProposedCircuit {
             circuit_id,
             roster,
@@ -584,6 +660,8 @@ impl ProposedCircuitBuilder {
             display_name,
             circuit_version,
             circuit_status,
+            attachments: self.attachments,
+            max_message_size: self.max_message_size,
         };
 
         Ok(create_circuit_message)
@@ -629,6 +707,19 @@ impl TryFrom<&messages::CreateCircuit> for ProposedCircuit {
         if let Some(display_name) = &create_circuit.display_name {
             circuit_builder = circuit_builder.with_display_name(display_name);
         }
+        // Add the `attachments` if present
+        if !create_circuit.attachments.is_empty() {
+            let attachments = create_circuit
+                .attachments
+                .iter()
+                .map(ProposedAttachment::from)
+                .collect::<Vec<ProposedAttachment>>();
+            circuit_builder = circuit_builder.with_attachments(&attachments);
+        }
+        // Add the `max_message_size` if present
+        if let Some(max_message_size) = create_circuit.max_message_size {
+            circuit_builder = circuit_builder.with_max_message_size(max_message_size);
+        }
         circuit_builder.build()
     }
 }
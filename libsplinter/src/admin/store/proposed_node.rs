@@ -25,6 +25,7 @@ pub struct ProposedNode {
     node_id: String,
     endpoints: Vec<String>,
     public_key: Option<PublicKey>,
+    authorized_public_keys: Vec<PublicKey>,
 }
 
 impl ProposedNode {
@@ -43,6 +44,11 @@ impl ProposedNode {
         &self.public_key
     }
 
+    /// Returns the additional public keys the proposed node may use for challenge authorization
+    pub fn authorized_public_keys(&self) -> &[PublicKey] {
+        &self.authorized_public_keys
+    }
+
     pub fn into_proto(self) -> admin::SplinterNode {
         let mut proto = admin::SplinterNode::new();
 
@@ -53,6 +59,13 @@ impl ProposedNode {
             proto.set_public_key(public_key.into_bytes());
         }
 
+        proto.set_authorized_public_keys(
+            self.authorized_public_keys
+                .into_iter()
+                .map(PublicKey::into_bytes)
+                .collect(),
+        );
+
         proto
     }
 
@@ -70,6 +83,11 @@ impl ProposedNode {
             node_id: proto.take_node_id(),
             endpoints: proto.take_endpoints().into(),
             public_key,
+            authorized_public_keys: proto
+                .take_authorized_public_keys()
+                .into_iter()
+                .map(PublicKey::from_bytes)
+                .collect(),
         }
     }
 }
@@ -80,6 +98,7 @@ pub struct ProposedNodeBuilder {
     node_id: Option<String>,
     endpoints: Option<Vec<String>>,
     public_key: Option<PublicKey>,
+    authorized_public_keys: Vec<PublicKey>,
 }
 
 impl ProposedNodeBuilder {
@@ -103,6 +122,11 @@ impl ProposedNodeBuilder {
         self.public_key.clone()
     }
 
+    /// Returns the additional public keys for the node
+    pub fn authorized_public_keys(&self) -> Vec<PublicKey> {
+        self.authorized_public_keys.clone()
+    }
+
     /// Sets the node ID
     ///
     /// # Arguments
@@ -133,6 +157,16 @@ impl ProposedNodeBuilder {
         self
     }
 
+    /// Sets the additional public keys
+    ///
+    /// # Arguments
+    ///
+    ///  * `public_keys` - Other public keys the node may use for challenge authorization
+    pub fn with_authorized_public_keys(mut self, public_keys: &[PublicKey]) -> ProposedNodeBuilder {
+        self.authorized_public_keys = public_keys.to_vec();
+        self
+    }
+
     /// Builds the `ProposedNode`
     ///
     /// Returns an error if the node ID or endpoints are not set
@@ -151,6 +185,7 @@ impl ProposedNodeBuilder {
             node_id,
             endpoints,
             public_key: self.public_key,
+            authorized_public_keys: self.authorized_public_keys,
         };
 
         Ok(node)
@@ -163,6 +198,12 @@ impl From<&messages::SplinterNode> for ProposedNode {
             node_id: admin_node.node_id.to_string(),
             endpoints: admin_node.endpoints.to_vec(),
             public_key: admin_node.public_key.clone().map(PublicKey::from_bytes),
+            authorized_public_keys: admin_node
+                .authorized_public_keys
+                .iter()
+                .cloned()
+                .map(PublicKey::from_bytes)
+                .collect(),
         }
     }
 }
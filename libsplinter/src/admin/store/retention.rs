@@ -0,0 +1,260 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Removes admin service events once they fall outside of a retention policy.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::AdminServiceEventStore;
+
+// How often events outside of the retention policy are pruned from the store
+const PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// How long, and how many, admin service events are kept in the event store.
+///
+/// Events are removed once they are older than the maximum age or are not among the newest
+/// maximum count of events, whichever removes more. A retention with neither limit keeps every
+/// event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventRetention {
+    max_age: Option<Duration>,
+    max_count: Option<u64>,
+}
+
+impl EventRetention {
+    /// Creates a retention that keeps every event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes events once they are older than `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Keeps only the newest `max_count` events.
+    pub fn with_max_count(mut self, max_count: u64) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    pub fn max_count(&self) -> Option<u64> {
+        self.max_count
+    }
+
+    /// Returns `true` if the retention keeps every event.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_age.is_none() && self.max_count.is_none()
+    }
+
+    /// Removes the events outside of this retention from the given store, returning the number
+    /// of events removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store to prune
+    pub fn prune(&self, store: &dyn AdminServiceEventStore) -> Result<usize, InternalError> {
+        if self.is_unlimited() {
+            return Ok(0);
+        }
+
+        let before = self
+            .max_age
+            .and_then(|max_age| SystemTime::now().checked_sub(max_age));
+        store
+            .prune_events(before, self.max_count)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+/// Periodically removes the events outside of an [`EventRetention`] from an admin service event
+/// store.
+pub struct EventPruner {
+    sender: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl EventPruner {
+    /// Starts a new event pruner. Events are pruned as soon as the pruner starts, so that events
+    /// which expired while the node was down are removed, and then periodically.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store from which events are removed
+    /// * `retention` - The events to keep
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the pruner's thread cannot be started.
+    pub fn start(
+        store: Box<dyn AdminServiceEventStore>,
+        retention: EventRetention,
+    ) -> Result<Self, InternalError> {
+        let (sender, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("AdminEventPruner".into())
+            .spawn(move || loop {
+                match retention.prune(&*store) {
+                    Ok(0) => (),
+                    Ok(count) => debug!("Pruned {} admin service events", count),
+                    Err(err) => error!("Unable to prune admin service events: {}", err),
+                }
+
+                match receiver.recv_timeout(PRUNE_INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            sender,
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for EventPruner {
+    fn signal_shutdown(&mut self) {
+        if self.sender.send(()).is_err() {
+            warn!("Admin service event pruner is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "Admin service event pruner thread did not shutdown correctly: {:?}",
+                err
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::service::messages;
+    use crate::admin::store::memory::MemoryAdminServiceEventStore;
+    use crate::admin::store::{
+        CircuitProposal, CircuitProposalBuilder, ProposalType, ProposedCircuitBuilder,
+        ProposedNodeBuilder, ProposedServiceBuilder,
+    };
+    use crate::hex::parse_hex;
+    use crate::public_key::PublicKey;
+
+    /// Verify that the pruner removes events beyond the retention's count as soon as it starts.
+    #[test]
+    fn test_pruner() {
+        let store = MemoryAdminServiceEventStore::new();
+        for _ in 0..3 {
+            store
+                .add_event(messages::AdminServiceEvent::CircuitReady(
+                    messages::CircuitProposal::from(create_proposal()),
+                ))
+                .expect("Unable to add event");
+        }
+
+        let mut pruner = EventPruner::start(
+            Box::new(store.clone()),
+            EventRetention::new().with_max_count(1),
+        )
+        .expect("Unable to start pruner");
+        pruner.signal_shutdown();
+        pruner
+            .wait_for_shutdown()
+            .expect("Unable to shutdown pruner");
+
+        assert_eq!(
+            store
+                .list_events_since(0)
+                .expect("Unable to list events")
+                .map(|event| *event.event_id())
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+
+    /// Verify that an unlimited retention does not remove any events.
+    #[test]
+    fn test_unlimited_retention() {
+        let store = MemoryAdminServiceEventStore::new();
+        store
+            .add_event(messages::AdminServiceEvent::CircuitReady(
+                messages::CircuitProposal::from(create_proposal()),
+            ))
+            .expect("Unable to add event");
+
+        assert!(EventRetention::new().is_unlimited());
+        assert_eq!(
+            EventRetention::new()
+                .prune(&store)
+                .expect("Unable to prune events"),
+            0
+        );
+        assert_eq!(
+            store
+                .list_events_since(0)
+                .expect("Unable to list events")
+                .len(),
+            1
+        );
+    }
+
+    fn create_proposal() -> CircuitProposal {
+        CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_circuit_hash("7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-BBBBB")
+                    .with_roster(&[ProposedServiceBuilder::default()
+                        .with_service_id("a000")
+                        .with_service_type("scabbard")
+                        .with_node_id("acme-node-000")
+                        .with_arguments(&[("peer_services".into(), "[]".into())])
+                        .build()
+                        .expect("Unable to build service")])
+                    .with_members(&[ProposedNodeBuilder::default()
+                        .with_node_id("acme-node-000".into())
+                        .with_endpoints(&["tcps://splinterd-node-acme:8044".into()])
+                        .build()
+                        .expect("Unable to build node")])
+                    .with_circuit_version(1)
+                    .with_circuit_management_type("test")
+                    .build()
+                    .expect("Unable to build circuit"),
+            )
+            .with_requester(&PublicKey::from_bytes(
+                parse_hex("0283a14e0a17cb7f665311e9b5560f4cde2b502f17e2d03223e15d90d9318d7482")
+                    .expect("Unable to parse requester"),
+            ))
+            .with_requester_node_id("acme-node-000")
+            .build()
+            .expect("Unable to build proposal")
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reporting on the schema versions of stored circuits, and migrating circuits stored with an
+//! older schema version to the current one.
+//!
+//! Circuits created with schema version 1 cannot be disbanded, abandoned or purged. Where the
+//! definition of such a circuit has the same meaning under the current schema, it can be
+//! migrated in place. Only the local store is changed, so the migration should be run on every
+//! member node of a circuit.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::admin::CIRCUIT_PROTOCOL_VERSION;
+
+use super::error::AdminServiceStoreError;
+use super::{AdminServiceStore, AuthorizationType, Circuit, CircuitStatus};
+
+/// The schema versions of the circuits in a store, along with the migrations planned or made
+/// for the circuits that are not at the current version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitSchemaReport {
+    versions: BTreeMap<i32, usize>,
+    circuits: Vec<CircuitSchemaMigration>,
+    dry_run: bool,
+}
+
+impl CircuitSchemaReport {
+    /// Returns the number of circuits stored with each schema version.
+    pub fn versions(&self) -> &BTreeMap<i32, usize> {
+        &self.versions
+    }
+
+    /// Returns the migrations of the circuits that are not at the current schema version.
+    pub fn circuits(&self) -> &[CircuitSchemaMigration] {
+        &self.circuits
+    }
+
+    /// Returns `true` if the report only describes the changes, without having made them.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+/// The migration of a single circuit to the current schema version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitSchemaMigration {
+    circuit_id: String,
+    from_version: i32,
+    to_version: i32,
+    eligibility: MigrationEligibility,
+    changes: Vec<CircuitFieldChange>,
+    migrated: bool,
+}
+
+impl CircuitSchemaMigration {
+    /// Returns the ID of the circuit.
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the schema version the circuit is stored with.
+    pub fn from_version(&self) -> i32 {
+        self.from_version
+    }
+
+    /// Returns the schema version the circuit would be migrated to.
+    pub fn to_version(&self) -> i32 {
+        self.to_version
+    }
+
+    /// Returns whether the circuit can be migrated.
+    pub fn eligibility(&self) -> &MigrationEligibility {
+        &self.eligibility
+    }
+
+    /// Returns the changes that migrating the circuit makes to its stored definition.
+    pub fn changes(&self) -> &[CircuitFieldChange] {
+        &self.changes
+    }
+
+    /// Returns `true` if the circuit's stored definition was migrated.
+    pub fn is_migrated(&self) -> bool {
+        self.migrated
+    }
+}
+
+/// Whether a circuit's stored definition can be migrated to the current schema version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MigrationEligibility {
+    Eligible,
+    /// The circuit cannot be migrated without changing its meaning; contains the reason.
+    Ineligible(String),
+}
+
+/// A change to one field of a stored circuit definition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitFieldChange {
+    field: &'static str,
+    from: String,
+    to: String,
+}
+
+impl CircuitFieldChange {
+    /// Returns the name of the changed field.
+    pub fn field(&self) -> &str {
+        self.field
+    }
+
+    /// Returns the value of the field before the change.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Returns the value of the field after the change.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+}
+
+impl fmt::Display for CircuitFieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.from, self.to)
+    }
+}
+
+/// Reports the schema versions of the circuits in the store, along with the changes that
+/// migrating the older circuits would make. The store is not modified.
+pub fn report_circuit_schemas(
+    store: &dyn AdminServiceStore,
+) -> Result<CircuitSchemaReport, AdminServiceStoreError> {
+    migrate(store, true)
+}
+
+/// Migrates the eligible circuits in the store to the current schema version. If `dry_run` is
+/// `true`, the store is not modified and the report only describes the changes.
+pub fn migrate_circuit_schemas(
+    store: &dyn AdminServiceStore,
+    dry_run: bool,
+) -> Result<CircuitSchemaReport, AdminServiceStoreError> {
+    migrate(store, dry_run)
+}
+
+fn migrate(
+    store: &dyn AdminServiceStore,
+    dry_run: bool,
+) -> Result<CircuitSchemaReport, AdminServiceStoreError> {
+    let mut versions = BTreeMap::new();
+    let mut circuits = vec![];
+
+    for circuit in store.list_circuits(&[])? {
+        *versions.entry(circuit.circuit_version()).or_insert(0) += 1;
+
+        if circuit.circuit_version() >= CIRCUIT_PROTOCOL_VERSION {
+            continue;
+        }
+
+        let eligibility = check_eligibility(store, &circuit)?;
+        let migrated_circuit = circuit.with_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+        let changes = diff(&circuit, &migrated_circuit);
+
+        let migrated = if eligibility == MigrationEligibility::Eligible && !dry_run {
+            store.update_circuit(migrated_circuit)?;
+            info!(
+                "Migrated circuit {} from schema version {} to {}",
+                circuit.circuit_id(),
+                circuit.circuit_version(),
+                CIRCUIT_PROTOCOL_VERSION
+            );
+            true
+        } else {
+            false
+        };
+
+        circuits.push(CircuitSchemaMigration {
+            circuit_id: circuit.circuit_id().into(),
+            from_version: circuit.circuit_version(),
+            to_version: CIRCUIT_PROTOCOL_VERSION,
+            eligibility,
+            changes,
+            migrated,
+        });
+    }
+
+    Ok(CircuitSchemaReport {
+        versions,
+        circuits,
+        dry_run,
+    })
+}
+
+/// Checks that the circuit's definition means the same under the current schema version.
+fn check_eligibility(
+    store: &dyn AdminServiceStore,
+    circuit: &Circuit,
+) -> Result<MigrationEligibility, AdminServiceStoreError> {
+    if store.get_proposal(circuit.circuit_id())?.is_some() {
+        return Ok(MigrationEligibility::Ineligible(
+            "the circuit has a pending proposal".into(),
+        ));
+    }
+
+    if circuit.circuit_status() != &CircuitStatus::Active {
+        return Ok(MigrationEligibility::Ineligible(
+            "the circuit is not active".into(),
+        ));
+    }
+
+    if circuit.authorization_type() == &AuthorizationType::Challenge
+        && circuit
+            .members()
+            .iter()
+            .any(|member| member.public_key().is_none())
+    {
+        return Ok(MigrationEligibility::Ineligible(
+            "challenge authorization requires a public key for every member".into(),
+        ));
+    }
+
+    Ok(MigrationEligibility::Eligible)
+}
+
+fn diff(before: &Circuit, after: &Circuit) -> Vec<CircuitFieldChange> {
+    let mut changes = vec![];
+    if before.circuit_version() != after.circuit_version() {
+        changes.push(CircuitFieldChange {
+            field: "circuit_version",
+            from: before.circuit_version().to_string(),
+            to: after.circuit_version().to_string(),
+        });
+    }
+    if before.circuit_status() != after.circuit_status() {
+        changes.push(CircuitFieldChange {
+            field: "circuit_status",
+            from: format!("{:?}", before.circuit_status()),
+            to: format!("{:?}", after.circuit_status()),
+        });
+    }
+    changes
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::admin::store::diesel::DieselAdminServiceStore;
+    use crate::admin::store::{CircuitBuilder, CircuitNodeBuilder, ServiceBuilder};
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a dry run reports the changes to a version 1 circuit without making them, and
+    /// that a migration makes them.
+    ///
+    /// 1. Add a version 1 circuit and a version 2 circuit to the store
+    /// 2. Run a dry run and verify the version counts and that the version 1 circuit is eligible,
+    ///    with a change to its version, but was not migrated
+    /// 3. Run the migration and verify the version 1 circuit was migrated
+    /// 4. Report again and verify that all circuits are at the current version
+    #[test]
+    fn test_migrate_circuit_schemas() {
+        let store = create_store();
+        let circuit = build_circuit("WBKLF-BBBBB", 1);
+        let members = circuit.members().to_vec();
+        store
+            .add_circuit(circuit, members)
+            .expect("Unable to add circuit");
+        let circuit = build_circuit("WBKLF-CCCCC", 2);
+        let members = circuit.members().to_vec();
+        store
+            .add_circuit(circuit, members)
+            .expect("Unable to add circuit");
+
+        let report = migrate_circuit_schemas(&store, true).expect("Unable to run dry run");
+        assert!(report.is_dry_run());
+        assert_eq!(report.versions().get(&1), Some(&1));
+        assert_eq!(report.versions().get(&2), Some(&1));
+        assert_eq!(report.circuits().len(), 1);
+        let migration = &report.circuits()[0];
+        assert_eq!(migration.circuit_id(), "WBKLF-BBBBB");
+        assert_eq!(migration.eligibility(), &MigrationEligibility::Eligible);
+        assert_eq!(
+            migration
+                .changes()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["circuit_version: 1 -> 2".to_string()]
+        );
+        assert!(!migration.is_migrated());
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to get circuit")
+                .expect("Circuit not found")
+                .circuit_version(),
+            1
+        );
+
+        let report = migrate_circuit_schemas(&store, false).expect("Unable to migrate");
+        assert!(report.circuits()[0].is_migrated());
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to get circuit")
+                .expect("Circuit not found")
+                .circuit_version(),
+            CIRCUIT_PROTOCOL_VERSION
+        );
+
+        let report = report_circuit_schemas(&store).expect("Unable to report");
+        assert!(report.circuits().is_empty());
+        assert_eq!(report.versions().get(&2), Some(&2));
+    }
+
+    fn build_circuit(circuit_id: &str, circuit_version: i32) -> Circuit {
+        CircuitBuilder::new()
+            .with_circuit_id(circuit_id)
+            .with_roster(&[ServiceBuilder::new()
+                .with_service_id("a000")
+                .with_service_type("scabbard")
+                .with_node_id("acme-node-000")
+                .build()
+                .expect("Unable to build service")])
+            .with_members(&[CircuitNodeBuilder::new()
+                .with_node_id("acme-node-000")
+                .with_endpoints(&["tcps://splinterd-node-acme:8044".into()])
+                .build()
+                .expect("Unable to build node")])
+            .with_circuit_management_type("test")
+            .with_circuit_version(circuit_version)
+            .build()
+            .expect("Unable to build circuit")
+    }
+
+    fn create_store() -> DieselAdminServiceStore<SqliteConnection> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+        DieselAdminServiceStore::new(pool)
+    }
+}
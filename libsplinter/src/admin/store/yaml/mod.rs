@@ -25,6 +25,11 @@ use std::fs::{rename, File};
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+#[cfg(any(
+    feature = "admin-service-event-retention",
+    feature = "circuit-tombstones"
+))]
+use std::time::SystemTime;
 
 use super::{AdminServiceEvent, EventIter};
 use super::{
@@ -836,6 +841,28 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    #[cfg(feature = "circuit-tombstones")]
+    fn tombstone_circuit(
+        &self,
+        _circuit_id: &str,
+        _reason: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        Err(AdminServiceStoreError::InvalidStateError(
+            InvalidStateError::with_message(
+                "Circuit tombstones are not supported by the YAML admin service store".to_string(),
+            ),
+        ))
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    fn remove_tombstones(
+        &self,
+        _before: Option<SystemTime>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        // Circuits can never be tombstoned in this store
+        Ok(0)
+    }
+
     /// Fetches a circuit from the underlying storage
     ///
     /// # Arguments
@@ -1115,6 +1142,15 @@ impl AdminServiceStore for YamlAdminServiceStore {
         unimplemented!()
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    fn prune_events(
+        &self,
+        _before: Option<SystemTime>,
+        _max_count: Option<u64>,
+    ) -> Result<usize, AdminServiceStoreError> {
+        unimplemented!()
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -1139,6 +1175,8 @@ struct YamlCircuit {
     circuit_version: i32,
     #[serde(default = "default_circuit_status")]
     circuit_status: YamlCircuitStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_message_size: Option<u64>,
 }
 
 impl TryFrom<YamlCircuit> for Circuit {
@@ -1179,6 +1217,10 @@ impl TryFrom<YamlCircuit> for Circuit {
             builder = builder.with_display_name(display_name);
         }
 
+        if let Some(max_message_size) = circuit.max_message_size {
+            builder = builder.with_max_message_size(max_message_size);
+        }
+
         builder.build()
     }
 }
@@ -1205,6 +1247,7 @@ impl From<Circuit> for YamlCircuit {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone().into(),
+            max_message_size: circuit.max_message_size(),
         }
     }
 }
@@ -1441,13 +1484,15 @@ pub struct YamlVoteRecord {
     public_key: String,
     vote: YamlVote,
     voter_node_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
 }
 
 impl TryFrom<YamlVoteRecord> for VoteRecord {
     type Error = InvalidStateError;
 
     fn try_from(vote: YamlVoteRecord) -> Result<Self, Self::Error> {
-        VoteRecordBuilder::new()
+        let mut builder = VoteRecordBuilder::new()
             .with_public_key(&PublicKey::from_bytes(
                 parse_hex(&vote.public_key).map_err(|_| {
                     InvalidStateError::with_message(
@@ -1456,8 +1501,13 @@ impl TryFrom<YamlVoteRecord> for VoteRecord {
                 })?,
             ))
             .with_vote(&Vote::from(vote.vote))
-            .with_voter_node_id(&vote.voter_node_id)
-            .build()
+            .with_voter_node_id(&vote.voter_node_id);
+
+        if let Some(comment) = &vote.comment {
+            builder = builder.with_comment(comment);
+        }
+
+        builder.build()
     }
 }
 
@@ -1467,6 +1517,7 @@ impl From<VoteRecord> for YamlVoteRecord {
             public_key: to_hex(vote.public_key().as_slice()),
             vote: vote.vote().clone().into(),
             voter_node_id: vote.voter_node_id().into(),
+            comment: vote.comment().map(String::from),
         }
     }
 }
@@ -1491,6 +1542,8 @@ struct YamlProposedCircuit {
     circuit_version: i32,
     #[serde(default = "default_circuit_status")]
     circuit_status: YamlCircuitStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_message_size: Option<u64>,
 }
 
 impl TryFrom<YamlProposedCircuit> for ProposedCircuit {
@@ -1539,6 +1592,10 @@ impl TryFrom<YamlProposedCircuit> for ProposedCircuit {
             builder = builder.with_display_name(display_name);
         }
 
+        if let Some(max_message_size) = circuit.max_message_size {
+            builder = builder.with_max_message_size(max_message_size);
+        }
+
         builder.build()
     }
 }
@@ -1574,6 +1631,7 @@ impl From<ProposedCircuit> for YamlProposedCircuit {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone().into(),
+            max_message_size: circuit.max_message_size(),
         }
     }
 }
@@ -19,6 +19,7 @@ use std::convert::From;
 use reqwest::{blocking::Client, StatusCode};
 
 use crate::error::InternalError;
+use crate::http_client::build_client;
 use crate::rest_api::SPLINTER_PROTOCOL_VERSION;
 
 use super::{Authorization, BiomeClient, Credentials, Key, NewKey, Profile, UpdateUser};
@@ -33,11 +34,17 @@ struct ServerError {
 pub struct ReqwestBiomeClient {
     url: String,
     auth: Option<String>,
+    // Shared by every request, so that connections are reused
+    client: Client,
 }
 
 impl ReqwestBiomeClient {
     pub fn new(url: String) -> Self {
-        ReqwestBiomeClient { url, auth: None }
+        ReqwestBiomeClient {
+            url,
+            auth: None,
+            client: build_client(),
+        }
     }
 
     pub fn add_auth(&mut self, auth: String) {
@@ -57,7 +64,8 @@ impl ReqwestBiomeClient {
 impl BiomeClient for ReqwestBiomeClient {
     /// Register a user with Biome.
     fn register(&self, username: &str, password: &str) -> Result<Credentials, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/biome/register", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .json(&json!({
@@ -110,7 +118,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Login a user with Biome.
     fn login(&self, username: &str, password: &str) -> Result<Authorization, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/biome/login", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .json(&json!({
@@ -162,7 +171,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Logout a user with Biome, removes the user's Splinter access token.
     fn logout(&self) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .patch(&format!("{}/biome/logout", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -205,7 +215,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Returns a new access token for the Biome user, based on the supplied refresh token
     fn get_new_access_token(&self, refresh_token: &str) -> Result<String, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/biome/token", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?)
@@ -256,7 +267,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Verify the credentials of a Biome user.
     fn verify(&self, username: &str, password: &str) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/biome/verify", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?)
@@ -300,7 +312,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// List all Biome users.
     fn list_users(&self) -> Result<Box<dyn Iterator<Item = Credentials>>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/biome/users?limit={}", self.url, PAGING_LIMIT))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -361,7 +374,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Get a Biome user.
     fn get_user(&self, user_id: &str) -> Result<Option<Credentials>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/biome/users/{}", self.url, user_id))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -417,7 +431,8 @@ impl BiomeClient for ReqwestBiomeClient {
         user_id: &str,
         updated_user: UpdateUser,
     ) -> Result<Box<dyn Iterator<Item = Key>>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .put(&format!("{}/biome/users/{}", self.url, user_id))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?)
@@ -472,7 +487,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Remove a Biome user.
     fn delete_user(&self, user_id: &str) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .delete(&format!("{}/biome/users/{}", self.url, user_id))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -515,7 +531,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// List all Biome user profiles.
     fn list_profiles(&self) -> Result<Box<dyn Iterator<Item = Profile>>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!(
                 "{}/biome/profiles?limit={}",
                 self.url, PAGING_LIMIT
@@ -579,7 +596,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Get a Biome user's profile.
     fn get_profile(&self, user_id: &str) -> Result<Option<Profile>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/biome/profiles/{}", self.url, user_id))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -628,7 +646,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// List the keys associated with the authorized Biome user.
     fn list_user_keys(&self) -> Result<Box<dyn Iterator<Item = Key>>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/biome/keys?limit={}", self.url, PAGING_LIMIT))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -682,7 +701,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Update a Biome user's key pair display name.
     fn update_key(&self, public_key: &str, new_display_name: &str) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .patch(&format!("{}/biome/keys", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?)
@@ -729,7 +749,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Add a key pair for a Biome user.
     fn add_key(&self, user_id: &str, new_key: NewKey) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/biome/keys", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?)
@@ -774,7 +795,8 @@ impl BiomeClient for ReqwestBiomeClient {
     /// Replace a Biome user's keys
     fn replace_keys(&self, keys: Vec<NewKey>) -> Result<(), InternalError> {
         let keys: Vec<ClientNewKey> = keys.into_iter().map(ClientNewKey::from).collect();
-        let request = Client::new()
+        let request = self
+            .client
             .put(&format!("{}/biome/keys", self.url))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?)
@@ -818,7 +840,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Get a Biome user's key pair.
     fn get_key(&self, public_key: &str) -> Result<Option<Key>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/biome/keys/{}", self.url, public_key))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
@@ -871,7 +894,8 @@ impl BiomeClient for ReqwestBiomeClient {
 
     /// Delete one of a Biome user's key pairs.
     fn delete_key(&self, public_key: &str) -> Result<Option<Key>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .delete(&format!("{}/biome/keys/{}", self.url, public_key))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth()?);
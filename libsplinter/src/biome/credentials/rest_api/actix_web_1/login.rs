@@ -97,6 +97,16 @@ pub fn make_login_route(
                         }
                     };
 
+                    #[cfg(feature = "biome-user-deactivation")]
+                    if !credentials.active {
+                        return HttpResponse::Forbidden()
+                            .json(ErrorResponse::forbidden(&format!(
+                                "User has been deactivated: {}",
+                                username_password.username
+                            )))
+                            .into_future();
+                    }
+
                     match credentials.verify_password(&username_password.hashed_password) {
                         Ok(is_valid) => {
                             if is_valid {
@@ -232,6 +242,16 @@ pub fn make_login_route(
                     }
                 };
 
+                #[cfg(feature = "biome-user-deactivation")]
+                if !credentials.active {
+                    return HttpResponse::Forbidden()
+                        .json(ErrorResponse::forbidden(&format!(
+                            "User has been deactivated: {}",
+                            username_password.username
+                        )))
+                        .into_future();
+                }
+
                 match credentials.verify_password(&username_password.hashed_password) {
                     Ok(is_valid) => {
                         if is_valid {
@@ -19,6 +19,8 @@ mod logout;
 mod register;
 mod token;
 mod user;
+#[cfg(feature = "biome-user-deactivation")]
+mod user_status;
 mod verify;
 
 use std::sync::Arc;
@@ -49,6 +51,8 @@ pub use config::{BiomeCredentialsRestConfig, BiomeCredentialsRestConfigBuilder};
 /// * `PUT /biome/users/{id}` - Update user with specified ID
 /// * `GET /biome/users/{id}` - Retrieve user with specified ID
 /// * `DELETE /biome/users/{id}` - Remove user with specified ID
+/// * `POST /biome/users/{id}/deactivate` - Deactivate user with specified ID
+/// * `POST /biome/users/{id}/reactivate` - Reactivate user with specified ID
 pub struct BiomeCredentialsRestResourceProvider {
     #[cfg(feature = "biome-key-management")]
     key_store: Arc<dyn KeyStore>,
@@ -62,10 +66,19 @@ pub struct BiomeCredentialsRestResourceProvider {
 impl BiomeCredentialsRestResourceProvider {
     /// Creates a new Biome user identity provider for the Splinter REST API
     pub fn get_identity_provider(&self) -> BiomeUserIdentityProvider {
-        BiomeUserIdentityProvider::new(
+        let identity_provider = BiomeUserIdentityProvider::new(
             self.token_secret_manager.clone(),
             default_validation(&self.credentials_config.issuer()),
-        )
+        );
+
+        #[cfg(feature = "biome-user-deactivation")]
+        {
+            identity_provider.with_credentials_store(self.credentials_store.clone())
+        }
+        #[cfg(not(feature = "biome-user-deactivation"))]
+        {
+            identity_provider
+        }
     }
 }
 
@@ -112,6 +125,13 @@ impl RestResourceProvider for BiomeCredentialsRestResourceProvider {
                 self.credentials_store.clone(),
                 self.key_store.clone(),
             ),
+            #[cfg(feature = "biome-user-deactivation")]
+            user_status::make_deactivate_route(
+                self.credentials_store.clone(),
+                self.refresh_token_store.clone(),
+            ),
+            #[cfg(feature = "biome-user-deactivation")]
+            user_status::make_reactivate_route(self.credentials_store.clone()),
         ]
     }
 }
@@ -826,4 +846,92 @@ mod tests {
             token_response.json::<PostToken>().unwrap();
         });
     }
+
+    /// Test deactivating and reactivating a user via POST /biome/users/{id}/deactivate and
+    /// POST /biome/users/{id}/reactivate
+    ///
+    /// Procedure
+    ///
+    /// 1) Create two users and log in as both of them
+    /// 2) Deactivate the second user via POST /biome/users/{id}/deactivate as the first user
+    /// 3) Verify that the second user can no longer log in, and that its access token is rejected
+    /// 4) Verify that GET /biome/users?active=false returns only the deactivated user
+    /// 5) Reactivate the second user via POST /biome/users/{id}/reactivate
+    /// 6) Verify that the second user can log in again
+    /// 7) Verify that deactivating a non-existent user returns a status code of 404
+    #[test]
+    #[cfg(feature = "biome-user-deactivation")]
+    fn test_deactivate_user() {
+        #[derive(Deserialize)]
+        struct ListedUser {
+            pub user_id: String,
+            pub active: bool,
+        }
+
+        run_test(|url, client| {
+            let admin =
+                create_and_authorize_user(url, &client, "test_deactivate_admin", "Admin2193!");
+            let user =
+                create_and_authorize_user(url, &client, "test_deactivate_user", "Admin2193!");
+
+            let deactivate_response = client
+                .post(&format!("{}/biome/users/{}/deactivate", url, user.user_id))
+                .header("Authorization", format!("Bearer {}", admin.token))
+                .send()
+                .unwrap();
+            assert_eq!(deactivate_response.status().as_u16(), 200);
+
+            let login_response = client
+                .post(&format!("{}/biome/login", url))
+                .json(&UsernamePassword {
+                    username: "test_deactivate_user".to_string(),
+                    hashed_password: "Admin2193!".to_string(),
+                })
+                .send()
+                .unwrap();
+            assert_eq!(login_response.status().as_u16(), 403);
+
+            let users_response = client
+                .get(&format!("{}/biome/users", url))
+                .header("Authorization", format!("Bearer {}", user.token))
+                .send()
+                .unwrap();
+            assert_eq!(users_response.status().as_u16(), 401);
+
+            let users_response = client
+                .get(&format!("{}/biome/users?active=false", url))
+                .header("Authorization", format!("Bearer {}", admin.token))
+                .send()
+                .unwrap();
+            assert_eq!(users_response.status().as_u16(), 200);
+            let users = users_response.json::<Vec<ListedUser>>().unwrap();
+            assert_eq!(users.len(), 1);
+            assert_eq!(users[0].user_id, user.user_id);
+            assert!(!users[0].active);
+
+            let reactivate_response = client
+                .post(&format!("{}/biome/users/{}/reactivate", url, user.user_id))
+                .header("Authorization", format!("Bearer {}", admin.token))
+                .send()
+                .unwrap();
+            assert_eq!(reactivate_response.status().as_u16(), 200);
+
+            let login_response = client
+                .post(&format!("{}/biome/login", url))
+                .json(&UsernamePassword {
+                    username: "test_deactivate_user".to_string(),
+                    hashed_password: "Admin2193!".to_string(),
+                })
+                .send()
+                .unwrap();
+            assert_eq!(login_response.status().as_u16(), 200);
+
+            let not_found_response = client
+                .post(&format!("{}/biome/users/not_a_user/deactivate", url))
+                .header("Authorization", format!("Bearer {}", admin.token))
+                .send()
+                .unwrap();
+            assert_eq!(not_found_response.status().as_u16(), 404);
+        });
+    }
 }
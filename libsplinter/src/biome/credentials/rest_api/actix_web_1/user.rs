@@ -14,6 +14,8 @@
 
 use std::sync::Arc;
 
+#[cfg(feature = "biome-user-deactivation")]
+use actix_web::web;
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
 
@@ -30,6 +32,8 @@ use crate::biome::key_management::{
     Key,
 };
 
+#[cfg(feature = "biome-user-deactivation")]
+use crate::biome::credentials::rest_api::resources::user::UserStatusQuery;
 #[cfg(feature = "biome-key-management")]
 use crate::biome::credentials::rest_api::resources::{
     key_management::ResponseKey, user::ModifyUser,
@@ -49,36 +53,55 @@ pub fn make_list_route(credentials_store: Arc<dyn CredentialsStore>) -> Resource
     );
     #[cfg(feature = "authorization")]
     {
-        resource.add_method(Method::Get, BIOME_USER_READ_PERMISSION, move |_, _| {
-            let credentials_store = credentials_store.clone();
-            Box::new(match credentials_store.list_usernames() {
-                Ok(users) => HttpResponse::Ok().json(users).into_future(),
-                Err(err) => {
-                    debug!("Failed to get users from the database {}", err);
-                    HttpResponse::InternalServerError()
-                        .json(ErrorResponse::internal_error())
-                        .into_future()
-                }
-            })
-        })
+        resource.add_method(
+            Method::Get,
+            BIOME_USER_READ_PERMISSION,
+            add_list_users_method(credentials_store),
+        )
     }
     #[cfg(not(feature = "authorization"))]
     {
-        resource.add_method(Method::Get, move |_, _| {
-            let credentials_store = credentials_store.clone();
-            Box::new(match credentials_store.list_usernames() {
-                Ok(users) => HttpResponse::Ok().json(users).into_future(),
-                Err(err) => {
-                    debug!("Failed to get users from the database {}", err);
-                    HttpResponse::InternalServerError()
-                        .json(ErrorResponse::internal_error())
-                        .into_future()
-                }
-            })
-        })
+        resource.add_method(Method::Get, add_list_users_method(credentials_store))
     }
 }
 
+/// Defines a REST endpoint to list users from the database, optionally filtered by whether or not
+/// the users are active
+fn add_list_users_method(credentials_store: Arc<dyn CredentialsStore>) -> HandlerFunction {
+    Box::new(move |_request, _| {
+        #[cfg(feature = "biome-user-deactivation")]
+        let active_filter = match web::Query::<UserStatusQuery>::from_query(_request.query_string())
+        {
+            Ok(web::Query(query)) => query.active,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid query"))
+                        .into_future(),
+                )
+            }
+        };
+
+        Box::new(match credentials_store.list_usernames() {
+            Ok(users) => {
+                #[cfg(feature = "biome-user-deactivation")]
+                let users = users
+                    .into_iter()
+                    .filter(|user| active_filter.map_or(true, |active| user.active == active))
+                    .collect::<Vec<_>>();
+
+                HttpResponse::Ok().json(users).into_future()
+            }
+            Err(err) => {
+                debug!("Failed to get users from the database {}", err);
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future()
+            }
+        })
+    })
+}
+
 #[cfg(feature = "biome-key-management")]
 /// Defines the `/biome/users/{id}` REST resource for managing users
 pub fn make_user_routes(
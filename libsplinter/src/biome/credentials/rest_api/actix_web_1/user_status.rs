@@ -0,0 +1,135 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use futures::IntoFuture;
+
+#[cfg(feature = "authorization")]
+use crate::biome::credentials::rest_api::BIOME_USER_WRITE_PERMISSION;
+use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
+use crate::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+use crate::rest_api::{
+    actix_web_1::{HandlerFunction, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+const BIOME_USER_STATUS_PROTOCOL_MIN: u32 = 1;
+
+/// Defines the `/biome/users/{id}/deactivate` REST resource for deactivating a user
+///
+/// The user's credentials and keys are kept, but the user may no longer log in, and any refresh
+/// token the user holds is revoked.
+pub fn make_deactivate_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+) -> Resource {
+    make_user_status_route(
+        "/biome/users/{id}/deactivate",
+        set_user_active_method(credentials_store, Some(refresh_token_store), false),
+    )
+}
+
+/// Defines the `/biome/users/{id}/reactivate` REST resource for reactivating a user
+pub fn make_reactivate_route(credentials_store: Arc<dyn CredentialsStore>) -> Resource {
+    make_user_status_route(
+        "/biome/users/{id}/reactivate",
+        set_user_active_method(credentials_store, None, true),
+    )
+}
+
+fn make_user_status_route(route: &str, handler: HandlerFunction) -> Resource {
+    let resource = Resource::build(route).add_request_guard(ProtocolVersionRangeGuard::new(
+        BIOME_USER_STATUS_PROTOCOL_MIN,
+        SPLINTER_PROTOCOL_VERSION,
+    ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, BIOME_USER_WRITE_PERMISSION, handler)
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, handler)
+    }
+}
+
+fn set_user_active_method(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Option<Arc<dyn RefreshTokenStore>>,
+    active: bool,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match request.match_info().get("id") {
+            Some(user_id) => user_id.to_string(),
+            None => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Failed to process request: no user id",
+                        ))
+                        .into_future(),
+                )
+            }
+        };
+
+        if let Err(err) = credentials_store.set_user_active(&user_id, active) {
+            return Box::new(match err {
+                CredentialsStoreError::NotFoundError(msg) => {
+                    debug!("User not found: {}", msg);
+                    HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(&format!(
+                            "User ID not found: {}",
+                            user_id
+                        )))
+                        .into_future()
+                }
+                _ => {
+                    error!("Failed to update user status in database {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            });
+        }
+
+        if let Some(refresh_token_store) = &refresh_token_store {
+            match refresh_token_store.remove_token(&user_id) {
+                Ok(()) | Err(RefreshTokenError::NotFoundError(_)) => (),
+                Err(err) => {
+                    error!(
+                        "Failed to revoke refresh token of user {}: {}",
+                        user_id, err
+                    );
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            }
+        }
+
+        let message = if active {
+            "User reactivated successfully"
+        } else {
+            "User deactivated successfully"
+        };
+        Box::new(
+            HttpResponse::Ok()
+                .json(json!({ "message": message }))
+                .into_future(),
+        )
+    })
+}
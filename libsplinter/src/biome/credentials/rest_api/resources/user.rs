@@ -23,3 +23,9 @@ pub(crate) struct ModifyUser {
     #[cfg(feature = "biome-key-management")]
     pub new_key_pairs: Vec<NewKey>,
 }
+
+#[cfg(feature = "biome-user-deactivation")]
+#[derive(Deserialize)]
+pub(crate) struct UserStatusQuery {
+    pub active: Option<bool>,
+}
@@ -33,6 +33,8 @@ use operations::fetch_credential_by_username::CredentialsStoreFetchCredentialByU
 use operations::fetch_username::CredentialsStoreFetchUsernameOperation as _;
 use operations::list_usernames::CredentialsStoreListUsernamesOperation as _;
 use operations::remove_credentials::CredentialsStoreRemoveCredentialsOperation as _;
+#[cfg(feature = "biome-user-deactivation")]
+use operations::set_user_active::CredentialsStoreSetUserActiveOperation as _;
 use operations::update_credentials::CredentialsStoreUpdateCredentialsOperation as _;
 use operations::CredentialsStoreOperations;
 
@@ -128,6 +130,13 @@ impl CredentialsStore for DieselCredentialsStore<diesel::pg::PgConnection> {
         self.connection_pool
             .execute_read(|conn| CredentialsStoreOperations::new(conn).list_usernames())
     }
+
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            CredentialsStoreOperations::new(conn).set_user_active(user_id, active)
+        })
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -188,6 +197,13 @@ impl CredentialsStore for DieselCredentialsStore<diesel::sqlite::SqliteConnectio
         self.connection_pool
             .execute_read(|conn| CredentialsStoreOperations::new(conn).list_usernames())
     }
+
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            CredentialsStoreOperations::new(conn).set_user_active(user_id, active)
+        })
+    }
 }
 
 impl From<CredentialsModel> for UsernameId {
@@ -195,6 +211,7 @@ impl From<CredentialsModel> for UsernameId {
         Self {
             user_id: user_credentials.user_id,
             username: user_credentials.username,
+            active: user_credentials.active,
         }
     }
 }
@@ -205,6 +222,7 @@ impl From<CredentialsModel> for Credentials {
             user_id: user_credentials.user_id,
             username: user_credentials.username,
             password: user_credentials.password,
+            active: user_credentials.active,
         }
     }
 }
@@ -419,6 +437,7 @@ pub mod tests {
             UsernameId {
                 username: "user1".into(),
                 user_id: "id1".into(),
+                active: true,
             },
         );
         assert_eq!(
@@ -428,6 +447,7 @@ pub mod tests {
             UsernameId {
                 username: "user2".into(),
                 user_id: "id2".into(),
+                active: true,
             },
         );
         assert_eq!(
@@ -437,6 +457,7 @@ pub mod tests {
             UsernameId {
                 username: "user3".into(),
                 user_id: "id3".into(),
+                active: true,
             },
         );
 
@@ -491,14 +512,17 @@ pub mod tests {
         assert!(usernames.contains(&UsernameId {
             username: "user1".into(),
             user_id: "id1".into(),
+            active: true,
         }));
         assert!(usernames.contains(&UsernameId {
             username: "user2".into(),
             user_id: "id2".into(),
+            active: true,
         }));
         assert!(usernames.contains(&UsernameId {
             username: "user3".into(),
             user_id: "id3".into(),
+            active: true,
         }));
     }
 
@@ -613,9 +637,79 @@ pub mod tests {
         assert!(!usernames.contains(&UsernameId {
             username: "user3".into(),
             user_id: "id3".into(),
+            active: true,
         }));
     }
 
+    /// Verify that a SQLite-backed `DieselCredentialsStore` correctly supports deactivating and
+    /// reactivating users.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselCredentialsStore`.
+    /// 3. Add some credentials and verify that they are active.
+    /// 4. Deactivate the user and verify that the credentials are kept but inactive.
+    /// 5. Reactivate the user and verify that the credentials are active again.
+    /// 6. Verify that `set_user_active` returns a `CredentialsStoreError::NotFoundError` for
+    ///    non-existent credentials.
+    #[cfg(feature = "biome-user-deactivation")]
+    #[test]
+    fn sqlite_set_user_active() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselCredentialsStore::new(pool);
+
+        let cred = CredentialsBuilder::default()
+            .with_user_id("id1")
+            .with_username("user1")
+            .with_password("pwd1")
+            .with_password_encryption_cost(PasswordEncryptionCost::Low)
+            .build()
+            .expect("Failed to build cred");
+        store.add_credentials(cred).expect("Failed to add cred");
+        assert!(
+            store
+                .fetch_credential_by_user_id("id1")
+                .expect("Failed to fetch id1")
+                .active
+        );
+
+        store
+            .set_user_active("id1", false)
+            .expect("Failed to deactivate user");
+        assert!(
+            !store
+                .fetch_credential_by_username("user1")
+                .expect("Failed to fetch user1")
+                .active
+        );
+        assert_eq!(
+            store.list_usernames().expect("Failed to list usernames"),
+            vec![UsernameId {
+                username: "user1".into(),
+                user_id: "id1".into(),
+                active: false,
+            }]
+        );
+
+        store
+            .set_user_active("id1", true)
+            .expect("Failed to reactivate user");
+        assert!(
+            store
+                .fetch_credential_by_user_id("id1")
+                .expect("Failed to fetch id1")
+                .active
+        );
+
+        match store.set_user_active("id2", false) {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
@@ -21,6 +21,7 @@ pub struct CredentialsModel {
     pub user_id: String,
     pub username: String,
     pub password: String,
+    pub active: bool,
 }
 
 #[derive(Insertable, PartialEq, Eq, Debug)]
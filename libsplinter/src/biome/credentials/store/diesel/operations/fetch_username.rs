@@ -16,7 +16,7 @@ use super::CredentialsStoreOperations;
 use crate::biome::credentials::store::diesel::schema::user_credentials;
 use crate::biome::credentials::store::error::CredentialsStoreError;
 use crate::biome::credentials::store::{CredentialsModel, UsernameId};
-use diesel::{prelude::*, result::Error::NotFound};
+use diesel::{prelude::*, result::Error::NotFound, types::HasSqlType};
 
 pub(in crate::biome::credentials) trait CredentialsStoreFetchUsernameOperation {
     fn fetch_username_by_id(&self, user_id: &str) -> Result<UsernameId, CredentialsStoreError>;
@@ -27,6 +27,8 @@ where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    C::Backend: HasSqlType<diesel::sql_types::Bool>,
 {
     fn fetch_username_by_id(&self, user_id: &str) -> Result<UsernameId, CredentialsStoreError> {
         let username = user_credentials::table
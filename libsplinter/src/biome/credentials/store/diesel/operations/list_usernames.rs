@@ -17,7 +17,7 @@ use crate::biome::credentials::store::diesel::{
     schema::user_credentials, CredentialsStoreError, UsernameId,
 };
 use crate::biome::credentials::store::CredentialsModel;
-use diesel::prelude::*;
+use diesel::{prelude::*, types::HasSqlType};
 
 pub(in crate::biome::credentials) trait CredentialsStoreListUsernamesOperation {
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError>;
@@ -28,6 +28,8 @@ where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    C::Backend: HasSqlType<diesel::sql_types::Bool>,
 {
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError> {
         let usernames = user_credentials::table
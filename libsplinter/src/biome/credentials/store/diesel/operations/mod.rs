@@ -20,6 +20,8 @@ pub(super) mod fetch_credential_by_username;
 pub(super) mod fetch_username;
 pub(super) mod list_usernames;
 pub(super) mod remove_credentials;
+#[cfg(feature = "biome-user-deactivation")]
+pub(super) mod set_user_active;
 pub(super) mod update_credentials;
 
 pub(super) struct CredentialsStoreOperations<'a, C> {
@@ -15,7 +15,7 @@
 use super::CredentialsStoreOperations;
 use crate::biome::credentials::store::diesel::{schema::user_credentials, CredentialsStoreError};
 use crate::biome::credentials::store::CredentialsModel;
-use diesel::{dsl::delete, prelude::*, result::Error::NotFound};
+use diesel::{dsl::delete, prelude::*, result::Error::NotFound, types::HasSqlType};
 
 pub(in crate::biome::credentials) trait CredentialsStoreRemoveCredentialsOperation {
     fn remove_credentials(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
@@ -26,6 +26,8 @@ where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    C::Backend: HasSqlType<diesel::sql_types::Bool>,
 {
     fn remove_credentials(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
         let credentials = user_credentials::table
@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{schema::user_credentials, CredentialsStoreError};
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::biome::credentials) trait CredentialsStoreSetUserActiveOperation {
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CredentialsStoreSetUserActiveOperation
+    for CredentialsStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError> {
+        let updated = update(user_credentials::table.filter(user_credentials::user_id.eq(user_id)))
+            .set(user_credentials::active.eq(active))
+            .execute(self.conn)
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to update user status".to_string(),
+                source: Box::new(err),
+            })?;
+        check_user_updated(updated, user_id)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CredentialsStoreSetUserActiveOperation
+    for CredentialsStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError> {
+        let updated = update(user_credentials::table.filter(user_credentials::user_id.eq(user_id)))
+            .set(user_credentials::active.eq(active))
+            .execute(self.conn)
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to update user status".to_string(),
+                source: Box::new(err),
+            })?;
+        check_user_updated(updated, user_id)
+    }
+}
+
+fn check_user_updated(updated: usize, user_id: &str) -> Result<(), CredentialsStoreError> {
+    if updated == 0 {
+        Err(CredentialsStoreError::NotFoundError(format!(
+            "Credentials not found for user id: {}",
+            user_id
+        )))
+    } else {
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ use crate::biome::credentials::store::error::CredentialsStoreError;
 use crate::biome::credentials::store::{
     CredentialsBuilder, CredentialsModel, PasswordEncryptionCost,
 };
-use diesel::{dsl::update, prelude::*, result::Error::NotFound};
+use diesel::{dsl::update, prelude::*, result::Error::NotFound, types::HasSqlType};
 
 pub(in crate::biome::credentials) trait CredentialsStoreUpdateCredentialsOperation {
     fn update_credentials(
@@ -35,6 +35,8 @@ where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    C::Backend: HasSqlType<diesel::sql_types::Bool>,
 {
     fn update_credentials(
         &self,
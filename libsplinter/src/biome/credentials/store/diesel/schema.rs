@@ -18,5 +18,6 @@ table! {
         user_id -> Text,
         username -> Text,
         password -> Text,
+        active -> Bool,
     }
 }
@@ -60,8 +60,8 @@ impl CredentialsStore for MemoryCredentialsStore {
                 context: "Cannot access credentials: mutex lock poisoned".to_string(),
                 source: None,
             })?;
-        if inner.contains_key(user_id) {
-            let new_credentials = CredentialsBuilder::default()
+        if let Some(active) = inner.get(user_id).map(|credentials| credentials.active) {
+            let mut new_credentials = CredentialsBuilder::default()
                 .with_user_id(user_id)
                 .with_username(updated_username)
                 .with_password(updated_password)
@@ -71,6 +71,7 @@ impl CredentialsStore for MemoryCredentialsStore {
                     context: "Failed to build updated credentials".to_string(),
                     source: err.into(),
                 })?;
+            new_credentials.active = active;
             inner.insert(user_id.into(), new_credentials);
             Ok(())
         } else {
@@ -155,6 +156,7 @@ impl CredentialsStore for MemoryCredentialsStore {
                 return Ok(UsernameId {
                     username: v.username.clone(),
                     user_id: v.user_id.clone(),
+                    active: v.active,
                 });
             }
         }
@@ -177,7 +179,29 @@ impl CredentialsStore for MemoryCredentialsStore {
             .map(|(_, v)| UsernameId {
                 username: v.username.clone(),
                 user_id: v.user_id.clone(),
+                active: v.active,
             })
             .collect())
     }
+
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access credentials: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        match inner.get_mut(user_id) {
+            Some(credentials) => {
+                credentials.active = active;
+                Ok(())
+            }
+            None => Err(CredentialsStoreError::NotFoundError(format!(
+                "User with user id {} not found",
+                user_id
+            ))),
+        }
+    }
 }
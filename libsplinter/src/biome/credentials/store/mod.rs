@@ -38,6 +38,7 @@ pub struct Credentials {
     pub user_id: String,
     pub username: String,
     pub password: String,
+    pub active: bool,
 }
 
 impl Credentials {
@@ -57,6 +58,7 @@ impl Credentials {
 pub struct UsernameId {
     pub username: String,
     pub user_id: String,
+    pub active: bool,
 }
 
 /// Builder for Credential. It hashes the password upon build.
@@ -137,6 +139,7 @@ impl CredentialsBuilder {
             user_id,
             username,
             password: hashed_password,
+            active: true,
         })
     }
 }
@@ -238,6 +241,21 @@ pub trait CredentialsStore: Send + Sync {
     ///
     /// Returns a CredentialsStoreError if implementation cannot fetch the user IDs
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError>;
+
+    /// Marks a user as active or inactive. The user's credentials are kept, but an inactive user
+    /// may not log in.
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user the credential belongs to
+    ///  * `active` - Whether or not the user is active
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if implementation cannot update the credential or if the
+    /// credential cannot be found
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError>;
 }
 
 impl<CS> CredentialsStore for Box<CS>
@@ -288,6 +306,11 @@ where
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError> {
         (**self).list_usernames()
     }
+
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(&self, user_id: &str, active: bool) -> Result<(), CredentialsStoreError> {
+        (**self).set_user_active(user_id, active)
+    }
 }
 
 #[cfg(feature = "diesel")]
@@ -0,0 +1,38 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by a `BiomeNotificationSubscriber`.
+#[derive(Debug)]
+pub enum BiomeNotificationSubscriberError {
+    /// The subscriber was unable to handle the notification, but remains subscribed.
+    UnableToHandleNotification(String),
+    /// The subscriber no longer wishes to receive notifications.
+    Unsubscribe,
+}
+
+impl Error for BiomeNotificationSubscriberError {}
+
+impl fmt::Display for BiomeNotificationSubscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BiomeNotificationSubscriberError::UnableToHandleNotification(msg) => {
+                write!(f, "Unable to handle notification: {}", msg)
+            }
+            BiomeNotificationSubscriberError::Unsubscribe => f.write_str("Unsubscribe"),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `KeyStore` that publishes notifications when keys are added or removed.
+
+#[cfg(feature = "biome-credentials")]
+use crate::biome::credentials::store::PasswordEncryptionCost;
+use crate::biome::key_management::{
+    store::{KeyStore, KeyStoreError},
+    Key,
+};
+
+use super::{BiomeNotification, BiomeNotifier};
+
+/// Wraps a `KeyStore`, publishing a `KeyAdded` or `KeyRemoved` notification for each key that is
+/// added to or removed from a user by a successful operation.
+pub struct NotifyingKeyStore {
+    inner: Box<dyn KeyStore>,
+    notifier: BiomeNotifier,
+}
+
+impl NotifyingKeyStore {
+    /// Creates a new store that delegates to `inner` and publishes to `notifier`.
+    pub fn new(inner: Box<dyn KeyStore>, notifier: BiomeNotifier) -> Self {
+        Self { inner, notifier }
+    }
+
+    fn notify_added(&self, key: &Key) {
+        self.notifier.notify(BiomeNotification::KeyAdded {
+            user_id: key.user_id.clone(),
+            public_key: key.public_key.clone(),
+        });
+    }
+
+    fn notify_removed(&self, key: &Key) {
+        self.notifier.notify(BiomeNotification::KeyRemoved {
+            user_id: key.user_id.clone(),
+            public_key: key.public_key.clone(),
+        });
+    }
+
+    // Publishes the difference between a user's keys before and after an operation that replaced
+    // them as a whole.
+    fn notify_replaced(&self, previous: &[Key], current: &[Key]) {
+        previous
+            .iter()
+            .filter(|key| !current.iter().any(|k| k.public_key == key.public_key))
+            .for_each(|key| self.notify_removed(key));
+        current
+            .iter()
+            .filter(|key| !previous.iter().any(|k| k.public_key == key.public_key))
+            .for_each(|key| self.notify_added(key));
+    }
+}
+
+impl KeyStore for NotifyingKeyStore {
+    fn add_key(&self, key: Key) -> Result<(), KeyStoreError> {
+        let added = key.clone();
+        self.inner.add_key(key)?;
+        self.notify_added(&added);
+        Ok(())
+    }
+
+    fn update_key(
+        &self,
+        public_key: &str,
+        user_id: &str,
+        new_display_name: &str,
+    ) -> Result<(), KeyStoreError> {
+        self.inner.update_key(public_key, user_id, new_display_name)
+    }
+
+    fn replace_keys(&self, user_id: &str, keys: &[Key]) -> Result<(), KeyStoreError> {
+        let previous = self.inner.list_keys(Some(user_id))?;
+        self.inner.replace_keys(user_id, keys)?;
+        self.notify_replaced(&previous, keys);
+        Ok(())
+    }
+
+    fn remove_key(&self, public_key: &str, user_id: &str) -> Result<Key, KeyStoreError> {
+        let key = self.inner.remove_key(public_key, user_id)?;
+        self.notify_removed(&key);
+        Ok(key)
+    }
+
+    fn fetch_key(&self, public_key: &str, user_id: &str) -> Result<Key, KeyStoreError> {
+        self.inner.fetch_key(public_key, user_id)
+    }
+
+    fn list_keys(&self, user_id: Option<&str>) -> Result<Vec<Key>, KeyStoreError> {
+        self.inner.list_keys(user_id)
+    }
+
+    #[cfg(feature = "biome-credentials")]
+    fn update_keys_and_password(
+        &self,
+        user_id: &str,
+        updated_password: &str,
+        password_encryption_cost: PasswordEncryptionCost,
+        keys: &[Key],
+    ) -> Result<(), KeyStoreError> {
+        let previous = self.inner.list_keys(Some(user_id))?;
+        self.inner.update_keys_and_password(
+            user_id,
+            updated_password,
+            password_encryption_cost,
+            keys,
+        )?;
+        let current = self.inner.list_keys(Some(user_id))?;
+        self.notify_replaced(&previous, &current);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    #[cfg(feature = "biome-credentials")]
+    use crate::biome::MemoryCredentialsStore;
+    use crate::biome::MemoryKeyStore;
+
+    use super::super::{BiomeNotificationSubscriber, BiomeNotificationSubscriberError};
+
+    #[derive(Clone, Default)]
+    struct RecordingSubscriber {
+        notifications: Arc<Mutex<Vec<BiomeNotification>>>,
+    }
+
+    impl RecordingSubscriber {
+        fn take(&self) -> Vec<BiomeNotification> {
+            std::mem::take(&mut *self.notifications.lock().expect("lock poisoned"))
+        }
+    }
+
+    impl BiomeNotificationSubscriber for RecordingSubscriber {
+        fn handle_notification(
+            &self,
+            notification: &BiomeNotification,
+        ) -> Result<(), BiomeNotificationSubscriberError> {
+            self.notifications
+                .lock()
+                .expect("lock poisoned")
+                .push(notification.clone());
+            Ok(())
+        }
+    }
+
+    fn new_store() -> (NotifyingKeyStore, RecordingSubscriber) {
+        #[cfg(feature = "biome-credentials")]
+        let inner = MemoryKeyStore::new(MemoryCredentialsStore::new());
+        #[cfg(not(feature = "biome-credentials"))]
+        let inner = MemoryKeyStore::new();
+
+        let notifier = BiomeNotifier::new();
+        let subscriber = RecordingSubscriber::default();
+        notifier
+            .add_subscriber(Box::new(subscriber.clone()))
+            .expect("Unable to add subscriber");
+
+        (
+            NotifyingKeyStore::new(Box::new(inner), notifier),
+            subscriber,
+        )
+    }
+
+    fn key(public_key: &str) -> Key {
+        Key::new(public_key, "encrypted", "alice", public_key)
+    }
+
+    fn added(public_key: &str) -> BiomeNotification {
+        BiomeNotification::KeyAdded {
+            user_id: "alice".into(),
+            public_key: public_key.into(),
+        }
+    }
+
+    fn removed(public_key: &str) -> BiomeNotification {
+        BiomeNotification::KeyRemoved {
+            user_id: "alice".into(),
+            public_key: public_key.into(),
+        }
+    }
+
+    /// Verifies that adding and removing keys publishes notifications, while display name
+    /// updates and failed operations do not.
+    #[test]
+    fn add_and_remove_keys() {
+        let (store, subscriber) = new_store();
+
+        store.add_key(key("0102")).expect("Unable to add key");
+        assert_eq!(subscriber.take(), vec![added("0102")]);
+
+        store
+            .update_key("0102", "alice", "renamed")
+            .expect("Unable to update key");
+        assert!(subscriber.take().is_empty());
+
+        store
+            .remove_key("0102", "alice")
+            .expect("Unable to remove key");
+        assert_eq!(subscriber.take(), vec![removed("0102")]);
+
+        assert!(store.remove_key("0102", "alice").is_err());
+        assert!(subscriber.take().is_empty());
+    }
+
+    /// Verifies that replacing a user's keys publishes only the keys that were added or removed.
+    #[test]
+    fn replace_keys() {
+        let (store, subscriber) = new_store();
+
+        store.add_key(key("0102")).expect("Unable to add key");
+        store.add_key(key("0304")).expect("Unable to add key");
+        subscriber.take();
+
+        store
+            .replace_keys("alice", &[key("0304"), key("0506")])
+            .expect("Unable to replace keys");
+        assert_eq!(subscriber.take(), vec![removed("0102"), added("0506")]);
+    }
+}
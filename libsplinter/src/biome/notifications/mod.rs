@@ -0,0 +1,266 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notifications of changes to the keys and role assignments of Biome identities.
+//!
+//! A `BiomeNotifier` fans out a `BiomeNotification` to every registered
+//! `BiomeNotificationSubscriber`. The notifications are produced by wrapping the stores that hold
+//! the data: `NotifyingKeyStore` publishes key additions and removals, and
+//! `NotifyingRoleBasedAuthorizationStore` publishes changes to role assignments. Subscribers are
+//! responsible for ignoring the notifications that do not concern them, typically by using
+//! `BiomeNotification::concerns`.
+
+mod error;
+mod key_store;
+#[cfg(feature = "authorization-handler-rbac")]
+mod rbac_store;
+
+use std::sync::{Arc, Mutex};
+
+use crate::error::InternalError;
+
+pub use error::BiomeNotificationSubscriberError;
+pub use key_store::NotifyingKeyStore;
+#[cfg(feature = "authorization-handler-rbac")]
+pub use rbac_store::NotifyingRoleBasedAuthorizationStore;
+
+/// The identity that a notification is about.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum NotificationSubject {
+    /// A public key-based identity.
+    Key(String),
+    /// A Biome user ID-based identity.
+    User(String),
+}
+
+/// A change to the keys or role assignments of an identity.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum BiomeNotification {
+    /// A key was added for a Biome user.
+    KeyAdded { user_id: String, public_key: String },
+    /// A key was removed from a Biome user.
+    KeyRemoved { user_id: String, public_key: String },
+    /// The roles assigned to an identity were changed; `roles` is empty if the assignment was
+    /// removed.
+    AssignmentChanged {
+        identity: NotificationSubject,
+        roles: Vec<String>,
+    },
+}
+
+impl BiomeNotification {
+    /// Returns whether or not the notification is relevant to the given identity.
+    ///
+    /// Key notifications concern both the user that owns the key and the key itself, so that a
+    /// session authenticated by a removed key learns of the removal.
+    pub fn concerns(&self, subject: &NotificationSubject) -> bool {
+        match self {
+            BiomeNotification::KeyAdded {
+                user_id,
+                public_key,
+            }
+            | BiomeNotification::KeyRemoved {
+                user_id,
+                public_key,
+            } => match subject {
+                NotificationSubject::User(id) => id == user_id,
+                NotificationSubject::Key(key) => key == public_key,
+            },
+            BiomeNotification::AssignmentChanged { identity, .. } => identity == subject,
+        }
+    }
+}
+
+/// Receives the notifications published by a `BiomeNotifier`.
+pub trait BiomeNotificationSubscriber: Send {
+    /// Handles a single notification.
+    ///
+    /// # Errors
+    ///
+    /// Returning `BiomeNotificationSubscriberError::Unsubscribe` removes the subscriber from the
+    /// notifier; any other error is logged and the subscriber is kept.
+    fn handle_notification(
+        &self,
+        notification: &BiomeNotification,
+    ) -> Result<(), BiomeNotificationSubscriberError>;
+}
+
+/// Publishes Biome notifications to a set of subscribers.
+///
+/// The notifier is cheaply cloneable; all clones share the same subscribers.
+#[derive(Clone, Default)]
+pub struct BiomeNotifier {
+    subscribers: Arc<Mutex<Vec<Box<dyn BiomeNotificationSubscriber>>>>,
+}
+
+impl BiomeNotifier {
+    /// Creates a new notifier with no subscribers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a subscriber that will receive all future notifications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the subscriber list lock is poisoned.
+    pub fn add_subscriber(
+        &self,
+        subscriber: Box<dyn BiomeNotificationSubscriber>,
+    ) -> Result<(), InternalError> {
+        self.subscribers
+            .lock()
+            .map_err(|_| {
+                InternalError::with_message("Biome notifier lock was poisoned".to_string())
+            })?
+            .push(subscriber);
+        Ok(())
+    }
+
+    /// Sends the notification to every subscriber, dropping those that unsubscribe.
+    ///
+    /// Notifications are delivered on a best-effort basis: a failure to notify never fails the
+    /// store operation that produced the notification.
+    pub fn notify(&self, notification: BiomeNotification) {
+        let mut subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(_) => {
+                error!(
+                    "Unable to send Biome notification {:?}: notifier lock was poisoned",
+                    notification
+                );
+                return;
+            }
+        };
+
+        subscribers.retain(
+            |subscriber| match subscriber.handle_notification(&notification) {
+                Ok(()) => true,
+                Err(BiomeNotificationSubscriberError::Unsubscribe) => false,
+                Err(err) => {
+                    warn!("Unable to send Biome notification: {}", err);
+                    true
+                }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, Sender};
+
+    struct ChannelSubscriber {
+        subject: NotificationSubject,
+        sender: Mutex<Sender<BiomeNotification>>,
+    }
+
+    impl BiomeNotificationSubscriber for ChannelSubscriber {
+        fn handle_notification(
+            &self,
+            notification: &BiomeNotification,
+        ) -> Result<(), BiomeNotificationSubscriberError> {
+            if !notification.concerns(&self.subject) {
+                return Ok(());
+            }
+            self.sender
+                .lock()
+                .expect("sender lock poisoned")
+                .send(notification.clone())
+                .map_err(|_| BiomeNotificationSubscriberError::Unsubscribe)
+        }
+    }
+
+    /// Verifies that key notifications concern the owning user and the key, and assignment
+    /// notifications concern only the assigned identity.
+    #[test]
+    fn notification_concerns() {
+        let key_added = BiomeNotification::KeyAdded {
+            user_id: "alice".into(),
+            public_key: "0102".into(),
+        };
+        assert!(key_added.concerns(&NotificationSubject::User("alice".into())));
+        assert!(key_added.concerns(&NotificationSubject::Key("0102".into())));
+        assert!(!key_added.concerns(&NotificationSubject::User("bob".into())));
+        assert!(!key_added.concerns(&NotificationSubject::Key("0304".into())));
+
+        let assignment = BiomeNotification::AssignmentChanged {
+            identity: NotificationSubject::User("alice".into()),
+            roles: vec!["admin".into()],
+        };
+        assert!(assignment.concerns(&NotificationSubject::User("alice".into())));
+        assert!(!assignment.concerns(&NotificationSubject::Key("alice".into())));
+    }
+
+    /// Verifies that the notifier delivers notifications to its subscribers and drops the
+    /// subscribers that unsubscribe.
+    #[test]
+    fn notifier_drops_unsubscribed() {
+        let notifier = BiomeNotifier::new();
+
+        let (sender, receiver) = channel();
+        notifier
+            .add_subscriber(Box::new(ChannelSubscriber {
+                subject: NotificationSubject::User("alice".into()),
+                sender: Mutex::new(sender),
+            }))
+            .expect("Unable to add subscriber");
+
+        let (closed_sender, closed_receiver) = channel();
+        drop(closed_receiver);
+        notifier
+            .add_subscriber(Box::new(ChannelSubscriber {
+                subject: NotificationSubject::User("alice".into()),
+                sender: Mutex::new(closed_sender),
+            }))
+            .expect("Unable to add subscriber");
+
+        let notification = BiomeNotification::KeyRemoved {
+            user_id: "alice".into(),
+            public_key: "0102".into(),
+        };
+        notifier.notify(notification.clone());
+
+        assert_eq!(receiver.try_recv().ok(), Some(notification));
+        assert_eq!(
+            notifier
+                .subscribers
+                .lock()
+                .expect("subscriber lock poisoned")
+                .len(),
+            1
+        );
+    }
+
+    /// Verifies the JSON representation of notifications.
+    #[test]
+    fn notification_json() {
+        let notification = BiomeNotification::AssignmentChanged {
+            identity: NotificationSubject::Key("0102".into()),
+            roles: vec![],
+        };
+        assert_eq!(
+            serde_json::to_value(&notification).expect("Unable to serialize"),
+            serde_json::json!({
+                "event_type": "assignment_changed",
+                "identity": {"type": "key", "id": "0102"},
+                "roles": [],
+            })
+        );
+    }
+}
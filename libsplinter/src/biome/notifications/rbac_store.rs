@@ -0,0 +1,130 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `RoleBasedAuthorizationStore` that publishes notifications when role assignments change.
+
+use crate::rbac::store::{
+    Assignment, Identity, Role, RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError,
+};
+
+use super::{BiomeNotification, BiomeNotifier, NotificationSubject};
+
+impl From<&Identity> for NotificationSubject {
+    fn from(identity: &Identity) -> Self {
+        match identity {
+            Identity::Key(key) => NotificationSubject::Key(key.clone()),
+            Identity::User(user_id) => NotificationSubject::User(user_id.clone()),
+        }
+    }
+}
+
+/// Wraps a `RoleBasedAuthorizationStore`, publishing an `AssignmentChanged` notification for each
+/// assignment that is added, updated or removed by a successful operation.
+#[derive(Clone)]
+pub struct NotifyingRoleBasedAuthorizationStore {
+    inner: Box<dyn RoleBasedAuthorizationStore>,
+    notifier: BiomeNotifier,
+}
+
+impl NotifyingRoleBasedAuthorizationStore {
+    /// Creates a new store that delegates to `inner` and publishes to `notifier`.
+    pub fn new(inner: Box<dyn RoleBasedAuthorizationStore>, notifier: BiomeNotifier) -> Self {
+        Self { inner, notifier }
+    }
+
+    fn notify(&self, identity: &Identity, roles: Vec<String>) {
+        self.notifier.notify(BiomeNotification::AssignmentChanged {
+            identity: identity.into(),
+            roles,
+        });
+    }
+}
+
+impl RoleBasedAuthorizationStore for NotifyingRoleBasedAuthorizationStore {
+    fn get_role(&self, id: &str) -> Result<Option<Role>, RoleBasedAuthorizationStoreError> {
+        self.inner.get_role(id)
+    }
+
+    fn list_roles(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.inner.list_roles()
+    }
+
+    fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.inner.add_role(role)
+    }
+
+    fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.inner.update_role(role)
+    }
+
+    fn remove_role(&self, role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.inner.remove_role(role_id)
+    }
+
+    fn get_assignment(
+        &self,
+        identity: &Identity,
+    ) -> Result<Option<Assignment>, RoleBasedAuthorizationStoreError> {
+        self.inner.get_assignment(identity)
+    }
+
+    fn get_assigned_roles(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.inner.get_assigned_roles(identity)
+    }
+
+    fn list_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
+    {
+        self.inner.list_assignments()
+    }
+
+    fn add_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (identity, roles) = assignment.clone().into_parts();
+        self.inner.add_assignment(assignment)?;
+        self.notify(&identity, roles);
+        Ok(())
+    }
+
+    fn update_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (identity, roles) = assignment.clone().into_parts();
+        self.inner.update_assignment(assignment)?;
+        self.notify(&identity, roles);
+        Ok(())
+    }
+
+    fn remove_assignment(
+        &self,
+        identity: &Identity,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.inner.remove_assignment(identity)?;
+        self.notify(identity, vec![]);
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
+        Box::new(self.clone())
+    }
+}
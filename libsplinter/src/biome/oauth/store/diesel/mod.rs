@@ -29,6 +29,8 @@ use super::{
     OAuthUserSessionStoreError,
 };
 
+#[cfg(feature = "biome-user-deactivation")]
+use operations::set_user_active::OAuthUserSessionStoreSetUserActive as _;
 use operations::{
     add_session::OAuthUserSessionStoreAddSession as _,
     get_session::OAuthUserSessionStoreGetSession as _, get_user::OAuthUserSessionStoreGetUser as _,
@@ -116,6 +118,17 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::sqlite::Sqlit
         })
     }
 
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            OAuthUserSessionStoreOperations::new(connection).set_user_active(user_id, active)
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn OAuthUserSessionStore> {
         Box::new(Self {
             connection_pool: self.connection_pool.clone(),
@@ -173,6 +186,17 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::pg::PgConnect
         })
     }
 
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            OAuthUserSessionStoreOperations::new(connection).set_user_active(user_id, active)
+        })
+    }
+
     fn clone_box(&self) -> Box<dyn OAuthUserSessionStore> {
         Box::new(Self {
             connection_pool: self.connection_pool.clone(),
@@ -606,6 +630,70 @@ pub mod tests {
         assert_eq!(users.len(), 2);
     }
 
+    /// Verify that a SQLite-backed `DieselOAuthUserSessionStore` correctly supports deactivating
+    /// and reactivating users.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create a `DieselOAuthUserSessionStore`.
+    /// 3. Add an OAuth user session and verify that the user is active.
+    /// 4. Deactivate the user and verify that both the user and the session's user are inactive.
+    /// 5. Reactivate the user and verify that the user is active again.
+    /// 6. Verify that deactivating a non-existent user results in an InvalidState error.
+    #[cfg(feature = "biome-user-deactivation")]
+    #[test]
+    fn sqlite_set_user_active() {
+        let pool = create_connection_pool_and_migrate();
+
+        let oauth_user_session_store = DieselOAuthUserSessionStore::new(pool);
+
+        let splinter_access_token = "splinter_access_token";
+        let subject = "subject";
+        let session = InsertableOAuthUserSessionBuilder::new()
+            .with_splinter_access_token(splinter_access_token.into())
+            .with_subject(subject.into())
+            .with_oauth_access_token("oauth_access_token".into())
+            .build()
+            .expect("Unable to build session");
+        oauth_user_session_store
+            .add_session(session)
+            .expect("Unable to add session");
+
+        let user = oauth_user_session_store
+            .get_user(subject)
+            .expect("Unable to get user")
+            .expect("User not found");
+        assert!(user.is_active());
+
+        oauth_user_session_store
+            .set_user_active(user.user_id(), false)
+            .expect("Unable to deactivate user");
+        assert!(!oauth_user_session_store
+            .get_user(subject)
+            .expect("Unable to get user")
+            .expect("User not found")
+            .is_active());
+        assert!(!oauth_user_session_store
+            .get_session(splinter_access_token)
+            .expect("Unable to get session")
+            .expect("Session not found")
+            .user()
+            .is_active());
+
+        oauth_user_session_store
+            .set_user_active(user.user_id(), true)
+            .expect("Unable to reactivate user");
+        assert!(oauth_user_session_store
+            .get_user(subject)
+            .expect("Unable to get user")
+            .expect("User not found")
+            .is_active());
+
+        assert!(matches!(
+            oauth_user_session_store.set_user_active("NonExistentUser", false),
+            Err(OAuthUserSessionStoreError::InvalidState(_)),
+        ));
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
@@ -22,6 +22,7 @@ use super::schema::{oauth_user_sessions, oauth_users};
 pub struct OAuthUserModel {
     pub subject: String,
     pub user_id: String,
+    pub active: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Associations, Identifiable, Queryable)]
@@ -47,15 +48,31 @@ pub struct InsertableOAuthUserSessionModel {
 
 impl From<OAuthUser> for OAuthUserModel {
     fn from(user: OAuthUser) -> Self {
-        let OAuthUser { subject, user_id } = user;
-        OAuthUserModel { subject, user_id }
+        let OAuthUser {
+            subject,
+            user_id,
+            active,
+        } = user;
+        OAuthUserModel {
+            subject,
+            user_id,
+            active,
+        }
     }
 }
 
 impl From<OAuthUserModel> for OAuthUser {
     fn from(user: OAuthUserModel) -> Self {
-        let OAuthUserModel { subject, user_id } = user;
-        OAuthUser { subject, user_id }
+        let OAuthUserModel {
+            subject,
+            user_id,
+            active,
+        } = user;
+        OAuthUser {
+            subject,
+            user_id,
+            active,
+        }
     }
 }
 
@@ -15,7 +15,7 @@
 use std::convert::TryFrom;
 use std::time::{Duration, UNIX_EPOCH};
 
-use diesel::prelude::*;
+use diesel::{prelude::*, types::HasSqlType};
 
 use crate::biome::oauth::store::{
     diesel::{
@@ -40,6 +40,8 @@ where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    C::Backend: HasSqlType<diesel::sql_types::Bool>,
 {
     fn get_session(
         &self,
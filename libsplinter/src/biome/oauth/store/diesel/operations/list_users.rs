@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use diesel::prelude::*;
+use diesel::{prelude::*, types::HasSqlType};
 
 use crate::biome::oauth::store::{
     diesel::{models::OAuthUserModel, schema::oauth_users},
@@ -29,6 +29,8 @@ impl<'a, C> OAuthUserSessionStoreListUsers for OAuthUserSessionStoreOperations<'
 where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    C::Backend: HasSqlType<diesel::sql_types::Bool>,
 {
     fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
         Ok(OAuthUserIter::new(
@@ -20,6 +20,8 @@ pub(super) mod get_session;
 pub(super) mod get_user;
 pub(super) mod list_users;
 pub(super) mod remove_session;
+#[cfg(feature = "biome-user-deactivation")]
+pub(super) mod set_user_active;
 pub(super) mod update_session;
 
 pub(super) struct OAuthUserSessionStoreOperations<'a, C> {
@@ -0,0 +1,75 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::update, prelude::*};
+
+use crate::biome::oauth::store::{diesel::schema::oauth_users, OAuthUserSessionStoreError};
+use crate::error::InvalidStateError;
+
+use super::OAuthUserSessionStoreOperations;
+
+pub trait OAuthUserSessionStoreSetUserActive {
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> OAuthUserSessionStoreSetUserActive
+    for OAuthUserSessionStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError> {
+        let updated = update(oauth_users::table.filter(oauth_users::user_id.eq(user_id)))
+            .set(oauth_users::active.eq(active))
+            .execute(self.conn)?;
+
+        check_user_updated(updated, user_id)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> OAuthUserSessionStoreSetUserActive
+    for OAuthUserSessionStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError> {
+        let updated = update(oauth_users::table.filter(oauth_users::user_id.eq(user_id)))
+            .set(oauth_users::active.eq(active))
+            .execute(self.conn)?;
+
+        check_user_updated(updated, user_id)
+    }
+}
+
+fn check_user_updated(updated: usize, user_id: &str) -> Result<(), OAuthUserSessionStoreError> {
+    if updated == 0 {
+        Err(OAuthUserSessionStoreError::InvalidState(
+            InvalidStateError::with_message(format!(
+                "An OAuth user with the user ID {} does not exist",
+                user_id
+            )),
+        ))
+    } else {
+        Ok(())
+    }
+}
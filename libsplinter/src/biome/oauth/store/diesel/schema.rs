@@ -16,6 +16,7 @@ table! {
     oauth_users (subject) {
         subject -> Text,
         user_id -> Text,
+        active -> Bool,
     }
 }
 
@@ -198,6 +198,31 @@ impl OAuthUserSessionStore for MemoryOAuthUserSessionStore {
         Ok(OAuthUserIter::new(users))
     }
 
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError> {
+        let mut internal = self.internal.lock().map_err(|_| {
+            OAuthUserSessionStoreError::Internal(InternalError::with_message(
+                "Cannot access OAuth user session store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        internal
+            .users
+            .values_mut()
+            .find(|user| user.user_id == user_id)
+            .map(|user| user.active = active)
+            .ok_or_else(|| {
+                OAuthUserSessionStoreError::InvalidState(InvalidStateError::with_message(format!(
+                    "An OAuth user with the user ID {} does not exist",
+                    user_id
+                )))
+            })
+    }
+
     fn clone_box(&self) -> Box<dyn OAuthUserSessionStore> {
         Box::new(self.clone())
     }
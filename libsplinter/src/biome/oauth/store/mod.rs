@@ -43,6 +43,7 @@ const UUID_NAMESPACE: Uuid = Uuid::from_u128(18764314186717360267674088713283300
 pub struct OAuthUser {
     subject: String,
     user_id: String,
+    active: bool,
 }
 
 impl OAuthUser {
@@ -54,6 +55,7 @@ impl OAuthUser {
         Self {
             subject,
             user_id: Uuid::new_v5(&UUID_NAMESPACE, Uuid::new_v4().as_bytes()).to_string(),
+            active: true,
         }
     }
 
@@ -62,7 +64,11 @@ impl OAuthUser {
     /// This constructor should only be used by implementations of the [OAuthUserSessionStore] for
     /// returning an existing user.
     pub fn new_with_id(subject: String, user_id: String) -> Self {
-        Self { subject, user_id }
+        Self {
+            subject,
+            user_id,
+            active: true,
+        }
     }
 
     /// Returns the user's subject identifier
@@ -74,6 +80,12 @@ impl OAuthUser {
     pub fn user_id(&self) -> &str {
         &self.user_id
     }
+
+    /// Returns whether or not the user is active; inactive users may not log in or be
+    /// authenticated
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
 }
 
 /// Data for an OAuth user's session that's in an [OAuthUserSessionStore]
@@ -393,6 +405,21 @@ pub trait OAuthUserSessionStore: Send + Sync {
     /// Returns the list of OAuth users, including the Biome user ID if it exists
     fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError>;
 
+    /// Marks the OAuth user with the given Biome user ID as active or inactive
+    ///
+    /// The user's record and sessions are kept; an inactive user's sessions are rejected until
+    /// the user is reactivated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidState` error if there is no OAuth user with the given `user_id`
+    #[cfg(feature = "biome-user-deactivation")]
+    fn set_user_active(
+        &self,
+        user_id: &str,
+        active: bool,
+    ) -> Result<(), OAuthUserSessionStoreError>;
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn OAuthUserSessionStore>;
 }
@@ -0,0 +1,210 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use openssl::sha::sha256;
+
+use crate::error::InternalError;
+use crate::hex::to_hex;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::{AccessLogCircuits, AccessLogStore, AccessRecord};
+
+// How often records older than the retention period are purged from the store
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+enum AccessLogMessage {
+    Record(AccessRecord),
+    Shutdown,
+}
+
+/// Records the payloads sent on circuits with access logging enabled.
+///
+/// Recorders are cheap to clone; each is connected to the [`AccessLogger`] that created it.
+#[derive(Clone)]
+pub struct AccessLogRecorder {
+    circuits: AccessLogCircuits,
+    sender: Sender<AccessLogMessage>,
+}
+
+impl AccessLogRecorder {
+    /// Records a payload if access logging is enabled for its circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit the payload was sent on
+    /// * `service_id` - The service that sent the payload
+    /// * `identity` - The identity of the peer that delivered the payload
+    /// * `payload` - The payload; only its SHA-256 hash is recorded
+    pub fn record(&self, circuit_id: &str, service_id: &str, identity: &str, payload: &[u8]) {
+        if !self.circuits.includes(circuit_id) {
+            return;
+        }
+
+        let record = AccessRecord::new(circuit_id, service_id, identity, &to_hex(&sha256(payload)));
+        if self.sender.send(AccessLogMessage::Record(record)).is_err() {
+            warn!("Circuit access logger is no longer running; payload was not recorded");
+        }
+    }
+}
+
+/// Writes the records received from its [`AccessLogRecorder`]s to an [`AccessLogStore`] and
+/// removes records once they are older than the retention period.
+pub struct AccessLogger {
+    circuits: AccessLogCircuits,
+    sender: Sender<AccessLogMessage>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl AccessLogger {
+    /// Starts a new access logger.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store to which access records are written
+    /// * `circuits` - The circuits whose payloads are recorded
+    /// * `retention` - How long records are kept; if `None`, records are never removed
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the logger's thread cannot be started.
+    pub fn start(
+        store: Box<dyn AccessLogStore>,
+        circuits: AccessLogCircuits,
+        retention: Option<Duration>,
+    ) -> Result<Self, InternalError> {
+        let (sender, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("CircuitAccessLogger".into())
+            .spawn(move || run(receiver, store, retention))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            circuits,
+            sender,
+            join_handle,
+        })
+    }
+
+    /// Returns a recorder that sends records to this logger.
+    pub fn recorder(&self) -> AccessLogRecorder {
+        AccessLogRecorder {
+            circuits: self.circuits.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+fn run(
+    receiver: Receiver<AccessLogMessage>,
+    store: Box<dyn AccessLogStore>,
+    retention: Option<Duration>,
+) {
+    // Purge as soon as the logger starts, so that records which expired while the node was down
+    // are removed
+    let mut next_purge = Instant::now();
+    loop {
+        if let Some(retention) = retention {
+            if Instant::now() >= next_purge {
+                purge(&*store, retention);
+                next_purge = Instant::now() + PURGE_INTERVAL;
+            }
+        }
+
+        let message = match retention {
+            Some(_) => receiver.recv_timeout(next_purge.saturating_duration_since(Instant::now())),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        let record = match message {
+            Ok(AccessLogMessage::Record(record)) => record,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Ok(AccessLogMessage::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        if let Err(err) = store.add_record(record) {
+            error!("Unable to write circuit access record: {}", err);
+        }
+    }
+}
+
+fn purge(store: &dyn AccessLogStore, retention: Duration) {
+    let before = match SystemTime::now().checked_sub(retention) {
+        Some(before) => before,
+        None => return,
+    };
+    match store.purge_records(before) {
+        Ok(0) => (),
+        Ok(count) => debug!("Purged {} expired circuit access records", count),
+        Err(err) => error!("Unable to purge expired circuit access records: {}", err),
+    }
+}
+
+impl ShutdownHandle for AccessLogger {
+    fn signal_shutdown(&mut self) {
+        if self.sender.send(AccessLogMessage::Shutdown).is_err() {
+            warn!("Circuit access logger is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "Circuit access logger thread did not shutdown correctly: {:?}",
+                err
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::circuit::access_log::store::memory::MemoryAccessLogStore;
+    use crate::circuit::access_log::AccessRecordFilter;
+
+    /// Verify that only payloads on circuits with access logging enabled are written to the
+    /// store, and that the payload is recorded by its hash.
+    #[test]
+    fn test_recorder() {
+        let store = MemoryAccessLogStore::new();
+        let mut logger = AccessLogger::start(
+            Box::new(store.clone()),
+            AccessLogCircuits::from(&["abcde-01234"][..]),
+            None,
+        )
+        .expect("Unable to start logger");
+
+        let recorder = logger.recorder();
+        recorder.record("abcde-01234", "a000", "node-a", b"payload");
+        recorder.record("fghij-56789", "a000", "node-a", b"payload");
+
+        logger.signal_shutdown();
+        logger
+            .wait_for_shutdown()
+            .expect("Unable to shutdown logger");
+
+        let records = store
+            .list_records(&AccessRecordFilter::default())
+            .expect("Unable to list records")
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].circuit_id(), "abcde-01234");
+        assert_eq!(records[0].payload_hash(), to_hex(&sha256(b"payload")));
+    }
+}
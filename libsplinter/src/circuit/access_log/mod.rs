@@ -0,0 +1,284 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A per-circuit record of the service payloads routed by this node.
+//!
+//! When access logging is enabled for a circuit, every direct message sent on that circuit is
+//! recorded as an [`AccessRecord`]: the identity of the peer that delivered it, the sending
+//! service, a SHA-256 hash of the payload and the time it was received. The
+//! [`CircuitDirectMessageHandler`](crate::circuit::handlers::CircuitDirectMessageHandler) hands
+//! records to an [`AccessLogRecorder`]; the [`AccessLogger`] writes them to an
+//! [`AccessLogStore`] and purges records that are older than the configured retention period.
+
+mod logger;
+pub mod store;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::time::SystemTime;
+
+pub use logger::{AccessLogRecorder, AccessLogger};
+pub use store::{AccessLogStore, AccessLogStoreError};
+
+/// The circuits for which access logging is enabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessLogCircuits {
+    /// Record the payloads of every circuit.
+    All,
+    /// Record only the payloads of the given circuits.
+    Only(HashSet<String>),
+}
+
+impl AccessLogCircuits {
+    /// Returns true if payloads on the given circuit should be recorded.
+    pub fn includes(&self, circuit_id: &str) -> bool {
+        match self {
+            AccessLogCircuits::All => true,
+            AccessLogCircuits::Only(circuits) => circuits.contains(circuit_id),
+        }
+    }
+}
+
+impl<S: AsRef<str>> From<&[S]> for AccessLogCircuits {
+    /// Creates the set of circuits from a list of circuit IDs; the special value `*` selects
+    /// every circuit.
+    fn from(circuits: &[S]) -> Self {
+        if circuits.iter().any(|circuit| circuit.as_ref() == "*") {
+            AccessLogCircuits::All
+        } else {
+            AccessLogCircuits::Only(
+                circuits
+                    .iter()
+                    .map(|circuit| circuit.as_ref().to_string())
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// A single entry in a circuit's access log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessRecord {
+    timestamp: SystemTime,
+    circuit_id: String,
+    service_id: String,
+    identity: String,
+    payload_hash: String,
+}
+
+impl AccessRecord {
+    /// Creates a new record with the current time as its timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit the payload was sent on
+    /// * `service_id` - The service that sent the payload
+    /// * `identity` - The identity of the peer that delivered the payload
+    /// * `payload_hash` - The hex-encoded SHA-256 hash of the payload
+    pub fn new(circuit_id: &str, service_id: &str, identity: &str, payload_hash: &str) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            circuit_id: circuit_id.to_string(),
+            service_id: service_id.to_string(),
+            identity: identity.to_string(),
+            payload_hash: payload_hash.to_string(),
+        }
+    }
+
+    /// Sets the time at which the payload was received.
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// The time at which the payload was received.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// The circuit the payload was sent on.
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// The service that sent the payload.
+    pub fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    /// The identity of the peer that delivered the payload.
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// The hex-encoded SHA-256 hash of the payload.
+    pub fn payload_hash(&self) -> &str {
+        &self.payload_hash
+    }
+}
+
+impl fmt::Display for AccessRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "circuit_id={} service_id={} identity={} payload_hash={}",
+            self.circuit_id, self.service_id, self.identity, self.payload_hash
+        )
+    }
+}
+
+/// Criteria for selecting records from an [`AccessLogStore`].
+///
+/// An empty filter matches every record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessRecordFilter {
+    circuit_id: Option<String>,
+    service_id: Option<String>,
+    identity: Option<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+}
+
+impl AccessRecordFilter {
+    /// Only match records for the given circuit.
+    pub fn with_circuit_id(mut self, circuit_id: &str) -> Self {
+        self.circuit_id = Some(circuit_id.to_string());
+        self
+    }
+
+    /// Only match records sent by the given service.
+    pub fn with_service_id(mut self, service_id: &str) -> Self {
+        self.service_id = Some(service_id.to_string());
+        self
+    }
+
+    /// Only match records delivered by the given peer identity.
+    pub fn with_identity(mut self, identity: &str) -> Self {
+        self.identity = Some(identity.to_string());
+        self
+    }
+
+    /// Only match records at or after the given time.
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only match records at or before the given time.
+    pub fn with_until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn circuit_id(&self) -> Option<&str> {
+        self.circuit_id.as_deref()
+    }
+
+    pub fn service_id(&self) -> Option<&str> {
+        self.service_id.as_deref()
+    }
+
+    pub fn identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+
+    pub fn since(&self) -> Option<SystemTime> {
+        self.since
+    }
+
+    pub fn until(&self) -> Option<SystemTime> {
+        self.until
+    }
+
+    /// Returns true if the given record satisfies all of the filter's criteria.
+    pub fn matches(&self, record: &AccessRecord) -> bool {
+        if let Some(circuit_id) = &self.circuit_id {
+            if record.circuit_id() != circuit_id {
+                return false;
+            }
+        }
+        if let Some(service_id) = &self.service_id {
+            if record.service_id() != service_id {
+                return false;
+            }
+        }
+        if let Some(identity) = &self.identity {
+            if record.identity() != identity {
+                return false;
+            }
+        }
+        if let Some(since) = self.since {
+            if record.timestamp() < since {
+                return false;
+            }
+        }
+        if let Some(until) = self.until {
+            if record.timestamp() > until {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Verify that a list containing `*` selects every circuit and any other list selects only
+    /// the listed circuits.
+    #[test]
+    fn test_access_log_circuits() {
+        let all = AccessLogCircuits::from(&["abcde-01234", "*"][..]);
+        assert_eq!(all, AccessLogCircuits::All);
+        assert!(all.includes("fghij-56789"));
+
+        let only = AccessLogCircuits::from(&["abcde-01234"][..]);
+        assert!(only.includes("abcde-01234"));
+        assert!(!only.includes("fghij-56789"));
+    }
+
+    /// Verify that each criteria of an `AccessRecordFilter` is applied.
+    #[test]
+    fn test_filter_matches() {
+        let now = SystemTime::now();
+        let record =
+            AccessRecord::new("abcde-01234", "a000", "node-a", "0a1b2c").with_timestamp(now);
+
+        assert!(AccessRecordFilter::default().matches(&record));
+        assert!(AccessRecordFilter::default()
+            .with_circuit_id("abcde-01234")
+            .with_service_id("a000")
+            .with_identity("node-a")
+            .matches(&record));
+        assert!(!AccessRecordFilter::default()
+            .with_circuit_id("fghij-56789")
+            .matches(&record));
+        assert!(!AccessRecordFilter::default()
+            .with_service_id("b000")
+            .matches(&record));
+        assert!(!AccessRecordFilter::default()
+            .with_identity("node-b")
+            .matches(&record));
+        assert!(AccessRecordFilter::default()
+            .with_since(now - Duration::from_secs(1))
+            .with_until(now + Duration::from_secs(1))
+            .matches(&record));
+        assert!(!AccessRecordFilter::default()
+            .with_since(now + Duration::from_secs(1))
+            .matches(&record));
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A database-backed [AccessLogStore](super::AccessLogStore), powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::circuit::access_log::{AccessRecord, AccessRecordFilter};
+use crate::store::pool::ConnectionPool;
+
+use super::{AccessLogStore, AccessLogStoreError};
+
+use operations::{
+    add_record::AccessLogStoreAddRecordOperation, list_records::AccessLogStoreListRecordsOperation,
+    purge_records::AccessLogStorePurgeRecordsOperation, AccessLogStoreOperations,
+};
+
+/// Database backed [AccessLogStore](super::AccessLogStore) implementation.
+pub struct DieselAccessLogStore<C: diesel::Connection + 'static> {
+    pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselAccessLogStore<C> {
+    /// Creates a new `DieselAccessLogStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: connection pool for the database
+    pub fn new(pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselAccessLogStore { pool: pool.into() }
+    }
+
+    /// Create a new `DieselAccessLogStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(pool: Arc<RwLock<Pool<ConnectionManager<C>>>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselAccessLogStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl AccessLogStore for DieselAccessLogStore<diesel::pg::PgConnection> {
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError> {
+        self.pool
+            .execute_write(|conn| AccessLogStoreOperations::new(conn).add_record(record))
+    }
+
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AccessRecord>>, AccessLogStoreError> {
+        self.pool
+            .execute_read(|conn| AccessLogStoreOperations::new(conn).list_records(filter))
+            .map(|records| Box::new(records.into_iter()) as Box<_>)
+    }
+
+    fn purge_records(&self, before: SystemTime) -> Result<usize, AccessLogStoreError> {
+        self.pool
+            .execute_write(|conn| AccessLogStoreOperations::new(conn).purge_records(before))
+    }
+
+    fn clone_box(&self) -> Box<dyn AccessLogStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl AccessLogStore for DieselAccessLogStore<diesel::sqlite::SqliteConnection> {
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError> {
+        self.pool
+            .execute_write(|conn| AccessLogStoreOperations::new(conn).add_record(record))
+    }
+
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AccessRecord>>, AccessLogStoreError> {
+        self.pool
+            .execute_read(|conn| AccessLogStoreOperations::new(conn).list_records(filter))
+            .map(|records| Box::new(records.into_iter()) as Box<_>)
+    }
+
+    fn purge_records(&self, before: SystemTime) -> Result<usize, AccessLogStoreError> {
+        self.pool
+            .execute_write(|conn| AccessLogStoreOperations::new(conn).purge_records(before))
+    }
+
+    fn clone_box(&self) -> Box<dyn AccessLogStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that records can be added to a SQLite-backed store, listed using a filter and
+    /// purged.
+    #[test]
+    fn sqlite_add_list_and_purge_records() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselAccessLogStore::new(pool);
+
+        let now = SystemTime::now();
+        store
+            .add_record(
+                AccessRecord::new("abcde-01234", "a000", "node-a", "01")
+                    .with_timestamp(now - Duration::from_secs(120)),
+            )
+            .expect("Unable to add record");
+        store
+            .add_record(
+                AccessRecord::new("abcde-01234", "b000", "node-b", "02").with_timestamp(now),
+            )
+            .expect("Unable to add record");
+        store
+            .add_record(
+                AccessRecord::new("fghij-56789", "a000", "node-a", "03").with_timestamp(now),
+            )
+            .expect("Unable to add record");
+
+        let all = store
+            .list_records(&AccessRecordFilter::default())
+            .expect("Unable to list records");
+        assert_eq!(all.len(), 3);
+
+        let mut by_service = store
+            .list_records(
+                &AccessRecordFilter::default()
+                    .with_circuit_id("abcde-01234")
+                    .with_service_id("b000"),
+            )
+            .expect("Unable to list records");
+        assert_eq!(by_service.len(), 1);
+        let record = by_service.next().expect("Missing record");
+        assert_eq!(record.identity(), "node-b");
+        assert_eq!(record.payload_hash(), "02");
+
+        assert_eq!(
+            store
+                .purge_records(now - Duration::from_secs(60))
+                .expect("Unable to purge records"),
+            1
+        );
+        let by_identity = store
+            .list_records(&AccessRecordFilter::default().with_identity("node-a"))
+            .expect("Unable to list records");
+        assert_eq!(by_identity.len(), 1);
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
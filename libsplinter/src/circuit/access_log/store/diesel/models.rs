@@ -0,0 +1,93 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::circuit::access_log::{AccessLogStoreError, AccessRecord};
+use crate::error::InternalError;
+
+use super::schema::circuit_access_record;
+
+#[derive(Queryable, PartialEq, Eq, Debug)]
+pub struct AccessRecordModel {
+    pub id: i64,
+    pub timestamp: i64,
+    pub circuit_id: String,
+    pub service_id: String,
+    pub identity: String,
+    pub payload_hash: String,
+}
+
+#[derive(Insertable, PartialEq, Eq, Debug)]
+#[table_name = "circuit_access_record"]
+pub struct NewAccessRecordModel {
+    pub timestamp: i64,
+    pub circuit_id: String,
+    pub service_id: String,
+    pub identity: String,
+    pub payload_hash: String,
+}
+
+/// Converts a `SystemTime` into seconds since the Unix epoch, as stored in the database.
+pub fn to_db_timestamp(time: SystemTime) -> Result<i64, AccessLogStoreError> {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| AccessLogStoreError::Internal(InternalError::from_source(Box::new(err))))?
+        .as_secs();
+    i64::try_from(secs)
+        .map_err(|err| AccessLogStoreError::Internal(InternalError::from_source(Box::new(err))))
+}
+
+impl TryFrom<AccessRecord> for NewAccessRecordModel {
+    type Error = AccessLogStoreError;
+
+    fn try_from(record: AccessRecord) -> Result<Self, Self::Error> {
+        Ok(NewAccessRecordModel {
+            timestamp: to_db_timestamp(record.timestamp())?,
+            circuit_id: record.circuit_id().to_string(),
+            service_id: record.service_id().to_string(),
+            identity: record.identity().to_string(),
+            payload_hash: record.payload_hash().to_string(),
+        })
+    }
+}
+
+impl TryFrom<AccessRecordModel> for AccessRecord {
+    type Error = AccessLogStoreError;
+
+    fn try_from(model: AccessRecordModel) -> Result<Self, Self::Error> {
+        let secs = u64::try_from(model.timestamp).map_err(|err| {
+            AccessLogStoreError::Internal(InternalError::from_source_with_message(
+                Box::new(err),
+                "'timestamp' could not be converted from i64 to u64".to_string(),
+            ))
+        })?;
+        let timestamp = UNIX_EPOCH
+            .checked_add(Duration::from_secs(secs))
+            .ok_or_else(|| {
+                AccessLogStoreError::Internal(InternalError::with_message(
+                    "'timestamp' could not be represented as a `SystemTime`".to_string(),
+                ))
+            })?;
+
+        Ok(AccessRecord::new(
+            &model.circuit_id,
+            &model.service_id,
+            &model.identity,
+            &model.payload_hash,
+        )
+        .with_timestamp(timestamp))
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::circuit::access_log::{
+    store::diesel::{models::NewAccessRecordModel, schema::circuit_access_record},
+    AccessLogStoreError, AccessRecord,
+};
+
+use super::AccessLogStoreOperations;
+
+pub(in crate::circuit::access_log::store::diesel) trait AccessLogStoreAddRecordOperation {
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AccessLogStoreAddRecordOperation
+    for AccessLogStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError> {
+        insert_into(circuit_access_record::table)
+            .values(NewAccessRecordModel::try_from(record)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(AccessLogStoreError::from)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AccessLogStoreAddRecordOperation
+    for AccessLogStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError> {
+        insert_into(circuit_access_record::table)
+            .values(NewAccessRecordModel::try_from(record)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(AccessLogStoreError::from)
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::circuit::access_log::{
+    store::diesel::{
+        models::{to_db_timestamp, AccessRecordModel},
+        schema::circuit_access_record,
+    },
+    AccessLogStoreError, AccessRecord, AccessRecordFilter,
+};
+
+use super::AccessLogStoreOperations;
+
+pub(in crate::circuit::access_log::store::diesel) trait AccessLogStoreListRecordsOperation {
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Vec<AccessRecord>, AccessLogStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AccessLogStoreListRecordsOperation
+    for AccessLogStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Vec<AccessRecord>, AccessLogStoreError> {
+        let mut query = circuit_access_record::table.into_boxed::<diesel::pg::Pg>();
+
+        if let Some(circuit_id) = filter.circuit_id() {
+            query = query.filter(circuit_access_record::circuit_id.eq(circuit_id.to_string()));
+        }
+        if let Some(service_id) = filter.service_id() {
+            query = query.filter(circuit_access_record::service_id.eq(service_id.to_string()));
+        }
+        if let Some(identity) = filter.identity() {
+            query = query.filter(circuit_access_record::identity.eq(identity.to_string()));
+        }
+        if let Some(since) = filter.since() {
+            query = query.filter(circuit_access_record::timestamp.ge(to_db_timestamp(since)?));
+        }
+        if let Some(until) = filter.until() {
+            query = query.filter(circuit_access_record::timestamp.le(to_db_timestamp(until)?));
+        }
+
+        query
+            .order(circuit_access_record::id.asc())
+            .load::<AccessRecordModel>(self.conn)?
+            .into_iter()
+            .map(AccessRecord::try_from)
+            .collect()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AccessLogStoreListRecordsOperation
+    for AccessLogStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Vec<AccessRecord>, AccessLogStoreError> {
+        let mut query = circuit_access_record::table.into_boxed::<diesel::sqlite::Sqlite>();
+
+        if let Some(circuit_id) = filter.circuit_id() {
+            query = query.filter(circuit_access_record::circuit_id.eq(circuit_id.to_string()));
+        }
+        if let Some(service_id) = filter.service_id() {
+            query = query.filter(circuit_access_record::service_id.eq(service_id.to_string()));
+        }
+        if let Some(identity) = filter.identity() {
+            query = query.filter(circuit_access_record::identity.eq(identity.to_string()));
+        }
+        if let Some(since) = filter.since() {
+            query = query.filter(circuit_access_record::timestamp.ge(to_db_timestamp(since)?));
+        }
+        if let Some(until) = filter.until() {
+            query = query.filter(circuit_access_record::timestamp.le(to_db_timestamp(until)?));
+        }
+
+        query
+            .order(circuit_access_record::id.asc())
+            .load::<AccessRecordModel>(self.conn)?
+            .into_iter()
+            .map(AccessRecord::try_from)
+            .collect()
+    }
+}
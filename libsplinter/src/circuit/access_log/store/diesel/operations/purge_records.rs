@@ -0,0 +1,43 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::circuit::access_log::{
+    store::diesel::{models::to_db_timestamp, schema::circuit_access_record},
+    AccessLogStoreError,
+};
+
+use super::AccessLogStoreOperations;
+
+pub(in crate::circuit::access_log::store::diesel) trait AccessLogStorePurgeRecordsOperation {
+    fn purge_records(&self, before: SystemTime) -> Result<usize, AccessLogStoreError>;
+}
+
+impl<'a, C> AccessLogStorePurgeRecordsOperation for AccessLogStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn purge_records(&self, before: SystemTime) -> Result<usize, AccessLogStoreError> {
+        delete(
+            circuit_access_record::table
+                .filter(circuit_access_record::timestamp.lt(to_db_timestamp(before)?)),
+        )
+        .execute(self.conn)
+        .map_err(AccessLogStoreError::from)
+    }
+}
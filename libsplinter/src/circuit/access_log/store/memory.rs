@@ -0,0 +1,168 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-memory implementation of [AccessLogStore](super::AccessLogStore).
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::circuit::access_log::{AccessRecord, AccessRecordFilter};
+use crate::error::InternalError;
+
+use super::{AccessLogStore, AccessLogStoreError};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// An [AccessLogStore](super::AccessLogStore) that keeps records in memory.
+///
+/// The store holds at most `capacity` records; once full, the oldest record is dropped for each
+/// new record added.
+#[derive(Clone)]
+pub struct MemoryAccessLogStore {
+    records: Arc<Mutex<VecDeque<AccessRecord>>>,
+    capacity: usize,
+}
+
+impl MemoryAccessLogStore {
+    /// Constructs a new store that holds up to 10,000 records.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Constructs a new store that holds up to `capacity` records.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::new())),
+            capacity,
+        }
+    }
+
+    fn lock_records(
+        &self,
+    ) -> Result<std::sync::MutexGuard<VecDeque<AccessRecord>>, AccessLogStoreError> {
+        self.records.lock().map_err(|_| {
+            AccessLogStoreError::Internal(InternalError::with_message(
+                "Cannot access access log store: mutex lock poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl Default for MemoryAccessLogStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessLogStore for MemoryAccessLogStore {
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError> {
+        let mut records = self.lock_records()?;
+
+        if self.capacity == 0 {
+            return Ok(());
+        }
+        while records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+
+        Ok(())
+    }
+
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AccessRecord>>, AccessLogStoreError> {
+        let records = self.lock_records()?;
+
+        Ok(Box::new(
+            records
+                .iter()
+                .filter(|record| filter.matches(record))
+                .cloned()
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn purge_records(&self, before: SystemTime) -> Result<usize, AccessLogStoreError> {
+        let mut records = self.lock_records()?;
+
+        let count = records.len();
+        records.retain(|record| record.timestamp() >= before);
+
+        Ok(count - records.len())
+    }
+
+    fn clone_box(&self) -> Box<dyn AccessLogStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Verify that the memory store drops its oldest records once it reaches capacity.
+    #[test]
+    fn test_capacity() {
+        let store = MemoryAccessLogStore::with_capacity(2);
+        for hash in &["01", "02", "03"] {
+            store
+                .add_record(AccessRecord::new("abcde-01234", "a000", "node-a", hash))
+                .expect("Unable to add record");
+        }
+
+        let hashes = store
+            .list_records(&AccessRecordFilter::default())
+            .expect("Unable to list records")
+            .map(|record| record.payload_hash().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, vec!["02".to_string(), "03".to_string()]);
+    }
+
+    /// Verify that purging removes only the records older than the given time.
+    #[test]
+    fn test_purge_records() {
+        let now = SystemTime::now();
+        let store = MemoryAccessLogStore::new();
+        store
+            .add_record(
+                AccessRecord::new("abcde-01234", "a000", "node-a", "01")
+                    .with_timestamp(now - Duration::from_secs(60)),
+            )
+            .expect("Unable to add record");
+        store
+            .add_record(
+                AccessRecord::new("abcde-01234", "a000", "node-a", "02").with_timestamp(now),
+            )
+            .expect("Unable to add record");
+
+        assert_eq!(
+            store
+                .purge_records(now - Duration::from_secs(30))
+                .expect("Unable to purge records"),
+            1
+        );
+        let hashes = store
+            .list_records(&AccessRecordFilter::default())
+            .expect("Unable to list records")
+            .map(|record| record.payload_hash().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, vec!["02".to_string()]);
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for circuit access records.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+pub mod memory;
+
+use std::time::SystemTime;
+
+pub use error::AccessLogStoreError;
+
+use super::{AccessRecord, AccessRecordFilter};
+
+/// Trait for persisting and querying circuit access records.
+pub trait AccessLogStore: Send + Sync {
+    /// Adds a record to the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - the record to add
+    fn add_record(&self, record: AccessRecord) -> Result<(), AccessLogStoreError>;
+
+    /// Lists the records that match the given filter, ordered from oldest to newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - the criteria records must match
+    fn list_records(
+        &self,
+        filter: &AccessRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AccessRecord>>, AccessLogStoreError>;
+
+    /// Removes every record older than the given time, returning the number of records removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - records with a timestamp before this time are removed
+    fn purge_records(&self, before: SystemTime) -> Result<usize, AccessLogStoreError>;
+
+    fn clone_box(&self) -> Box<dyn AccessLogStore>;
+}
+
+impl Clone for Box<dyn AccessLogStore> {
+    fn clone(&self) -> Box<dyn AccessLogStore> {
+        self.clone_box()
+    }
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "circuit-access-log")]
+use crate::circuit::access_log::AccessLogRecorder;
 use crate::circuit::handlers::create_message;
 use crate::circuit::routing::{RoutingTableReader, ServiceId as RoutingServiceId};
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
@@ -36,6 +38,8 @@ pub struct CircuitDirectMessageHandler {
     routing_table: Box<dyn RoutingTableReader>,
     #[cfg(feature = "service-message-handler-dispatch")]
     service_dispatcher: ServiceDispatcher,
+    #[cfg(feature = "circuit-access-log")]
+    access_log: Option<AccessLogRecorder>,
 }
 
 impl Handler for CircuitDirectMessageHandler {
@@ -76,6 +80,25 @@ impl Handler for CircuitDirectMessageHandler {
         let recipient = msg.get_recipient();
         let recipient_id = RoutingServiceId::new(circuit_name.to_string(), recipient.to_string());
 
+        #[cfg(feature = "circuit-max-message-size")]
+        if let Some(error_bytes) = self.check_message_size(&msg)? {
+            return sender
+                .send(context.source_peer_id().clone(), error_bytes)
+                .map_err(|(recipient, payload)| {
+                    DispatchError::NetworkSendError((recipient.into(), payload))
+                });
+        }
+
+        #[cfg(feature = "circuit-access-log")]
+        if let Some(access_log) = &self.access_log {
+            access_log.record(
+                circuit_name,
+                msg_sender,
+                &context.source_peer_id().peer_id().id_as_string(),
+                msg.get_payload(),
+            );
+        }
+
         #[cfg(feature = "service-message-handler-dispatch")]
         {
             let to_service = FullyQualifiedServiceId::new(
@@ -267,8 +290,58 @@ impl CircuitDirectMessageHandler {
             routing_table,
             #[cfg(feature = "service-message-handler-dispatch")]
             service_dispatcher,
+            #[cfg(feature = "circuit-access-log")]
+            access_log: None,
         }
     }
+
+    /// Records the payloads of direct messages on circuits with access logging enabled.
+    #[cfg(feature = "circuit-access-log")]
+    pub fn with_access_log(mut self, access_log: AccessLogRecorder) -> Self {
+        self.access_log = Some(access_log);
+        self
+    }
+
+    /// Returns the bytes of a circuit error to send back to the sender if the message payload is
+    /// larger than its circuit allows.
+    #[cfg(feature = "circuit-max-message-size")]
+    fn check_message_size(
+        &self,
+        msg: &CircuitDirectMessage,
+    ) -> Result<Option<Vec<u8>>, DispatchError> {
+        let max_message_size = match self
+            .routing_table
+            .get_circuit(msg.get_circuit())
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+            .and_then(|circuit| circuit.max_message_size())
+        {
+            Some(max_message_size) => max_message_size,
+            None => return Ok(None),
+        };
+
+        let payload_size = msg.get_payload().len() as u64;
+        if payload_size <= max_message_size {
+            return Ok(None);
+        }
+
+        let mut error_message = CircuitError::new();
+        error_message.set_correlation_id(msg.get_correlation_id().to_string());
+        error_message.set_service_id(msg.get_sender().into());
+        error_message.set_circuit_name(msg.get_circuit().into());
+        error_message.set_error(CircuitError_Error::ERROR_MESSAGE_TOO_LARGE);
+        error_message.set_error_message(format!(
+            "Message payload of {} bytes exceeds the maximum message size of circuit {} ({} bytes)",
+            payload_size,
+            msg.get_circuit(),
+            max_message_size
+        ));
+
+        let msg_bytes = error_message.write_to_bytes()?;
+        Ok(Some(create_message(
+            msg_bytes,
+            CircuitMessageType::CIRCUIT_ERROR_MESSAGE,
+        )?))
+    }
 }
 
 #[cfg(test)]
@@ -401,6 +474,109 @@ mod tests {
         )
     }
 
+    // Test that a direct message on a circuit with access logging enabled is recorded with the
+    // sending service and the peer that delivered it
+    #[cfg(feature = "circuit-access-log")]
+    #[test]
+    fn test_circuit_direct_message_handler_access_log() {
+        use crate::circuit::access_log::{
+            store::memory::MemoryAccessLogStore, AccessLogCircuits, AccessLogStore, AccessLogger,
+            AccessRecordFilter,
+        };
+        use crate::threading::lifecycle::ShutdownHandle;
+
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+
+        let mut service_abc = Service::new(
+            "b0001".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let service_def = Service::new(
+            "a0001".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+        service_abc.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("abc_network"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        ));
+
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![service_abc, service_def],
+            vec!["123".into(), "345".into()],
+            AuthorizationType::Trust,
+        );
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+
+        let store = MemoryAccessLogStore::new();
+        let mut logger = AccessLogger::start(
+            Box::new(store.clone()),
+            AccessLogCircuits::from(&["Alpha-00000"][..]),
+            None,
+        )
+        .expect("Unable to start access logger");
+
+        let handler = CircuitDirectMessageHandler::new(
+            "123".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        )
+        .with_access_log(logger.recorder());
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("1234".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("def"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        logger.signal_shutdown();
+        logger
+            .wait_for_shutdown()
+            .expect("Unable to shutdown access logger");
+
+        let records = store
+            .list_records(&AccessRecordFilter::default().with_circuit_id("Alpha-00000"))
+            .expect("Unable to list records")
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].service_id(), "a0001");
+        assert_eq!(records[0].identity(), "def");
+    }
+
     // Test that a direct message will be properly sent to the node the recipient service is
     // connected to
     #[test]
@@ -599,6 +775,123 @@ mod tests {
         )
     }
 
+    // Test that an error message is returned if the payload is larger than the maximum message
+    // size of the circuit, and that a payload within the limit is delivered
+    #[cfg(feature = "circuit-max-message-size")]
+    #[test]
+    fn test_circuit_direct_message_handler_message_too_large() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+
+        let mut service_abc = Service::new(
+            "b0001".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let service_def = Service::new(
+            "a0001".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+        service_abc.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("abc_network"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        ));
+
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![service_abc, service_def],
+            vec!["123".into(), "345".into()],
+            AuthorizationType::Trust,
+        )
+        .with_max_message_size(Some(4));
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+
+        let handler = CircuitDirectMessageHandler::new(
+            "123".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        let source: PeerId = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("def"),
+            PeerAuthorizationToken::from_peer_id("345"),
+        )
+        .into();
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"too large".to_vec());
+        direct_message.set_correlation_id("1234".into());
+
+        dispatcher
+            .dispatch(
+                source.clone(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("def"),
+                PeerAuthorizationToken::from_peer_id("345"),
+            ),
+            CircuitMessageType::CIRCUIT_ERROR_MESSAGE,
+            |msg: CircuitError| {
+                assert_eq!(msg.get_service_id(), "a0001");
+                assert_eq!(msg.get_error(), CircuitError_Error::ERROR_MESSAGE_TOO_LARGE);
+                assert_eq!(msg.get_correlation_id(), "1234");
+            },
+        );
+
+        direct_message.set_payload(b"test".to_vec());
+        dispatcher
+            .dispatch(
+                source,
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("abc_network"),
+                PeerAuthorizationToken::from_peer_id("123"),
+            ),
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_payload().to_vec(), b"test".to_vec());
+            },
+        )
+    }
+
     // Test that an error message is returned if the recipient is not in the circuit roster
     #[test]
     fn test_circuit_direct_message_handler_recipient_not_in_circuit_roster() {
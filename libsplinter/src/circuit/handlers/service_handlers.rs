@@ -16,6 +16,8 @@ use crate::circuit::handlers::create_message;
 use crate::circuit::routing::{RoutingTableReader, RoutingTableWriter, Service, ServiceId};
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::peer::PeerTokenPair;
+#[cfg(feature = "service-protocol-version")]
+use crate::protocol::service_version::ProtocolVersionRange;
 use crate::protos::circuit::{
     CircuitMessageType, ServiceConnectRequest, ServiceConnectResponse,
     ServiceConnectResponse_Status, ServiceDisconnectRequest, ServiceDisconnectResponse,
@@ -59,6 +61,17 @@ impl Handler for ServiceConnectRequestHandler {
         response.set_circuit(circuit_name.into());
         response.set_service_id(service_id.into());
 
+        #[cfg(feature = "service-protocol-version")]
+        match negotiate_protocol_version(&msg) {
+            Ok(version) => response.set_protocol_version(version),
+            Err(err) => {
+                response
+                    .set_status(ServiceConnectResponse_Status::ERROR_UNSUPPORTED_PROTOCOL_VERSION);
+                response.set_error_message(format!("Unable to connect {}: {}", unique_id, err));
+                return send_connect_response(response, context, sender);
+            }
+        }
+
         // hold on to the write lock for the entirety of the function
         let circuit_result = self
             .routing_table_reader
@@ -131,20 +144,46 @@ impl Handler for ServiceConnectRequestHandler {
             response.set_error_message(format!("Circuit does not exist: {}", msg.get_circuit()))
         }
 
-        // Return response
-        let response_bytes = response.write_to_bytes()?;
-        let network_msg_bytes =
-            create_message(response_bytes, CircuitMessageType::SERVICE_CONNECT_RESPONSE)?;
-
-        let recipient = context.source_peer_id().clone();
+        send_connect_response(response, context, sender)
+    }
+}
 
-        sender
-            .send(recipient, network_msg_bytes)
-            .map_err(|(recipient, payload)| {
-                DispatchError::NetworkSendError((recipient.into(), payload))
-            })?;
-        Ok(())
+/// Negotiates the service protocol version requested by a connecting service against the versions
+/// supported by this node.
+///
+/// Services that do not version their messages send a maximum of 0, and are connected without a
+/// version.
+#[cfg(feature = "service-protocol-version")]
+fn negotiate_protocol_version(msg: &ServiceConnectRequest) -> Result<u32, String> {
+    if msg.get_protocol_max() == 0 {
+        return Ok(0);
     }
+
+    let requested = ProtocolVersionRange::new(msg.get_protocol_min(), msg.get_protocol_max())
+        .map_err(|err| format!("invalid service protocol versions: {}", err))?;
+
+    ProtocolVersionRange::node_supported()
+        .negotiate(&requested)
+        .map_err(|err| err.to_string())
+}
+
+fn send_connect_response(
+    response: ServiceConnectResponse,
+    context: &MessageContext<PeerId, CircuitMessageType>,
+    sender: &dyn MessageSender<PeerId>,
+) -> Result<(), DispatchError> {
+    let response_bytes = response.write_to_bytes()?;
+    let network_msg_bytes =
+        create_message(response_bytes, CircuitMessageType::SERVICE_CONNECT_RESPONSE)?;
+
+    let recipient = context.source_peer_id().clone();
+
+    sender
+        .send(recipient, network_msg_bytes)
+        .map_err(|(recipient, payload)| {
+            DispatchError::NetworkSendError((recipient.into(), payload))
+        })?;
+    Ok(())
 }
 
 impl ServiceConnectRequestHandler {
@@ -461,6 +500,73 @@ mod tests {
         )
     }
 
+    #[cfg(feature = "service-protocol-version")]
+    #[test]
+    // Test that if the service requests service protocol versions that the node does not support,
+    // a ServiceConnectResponse is returned with an ERROR_UNSUPPORTED_PROTOCOL_VERSION and the
+    // service is not connected
+    fn test_service_connect_request_handler_unsupported_protocol_version() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let (circuit, nodes) = build_circuit();
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        writer
+            .add_circuit(circuit.circuit_id().to_string(), circuit, nodes)
+            .expect("Unable to add circuit");
+        let handler = ServiceConnectRequestHandler::new("123".to_string(), reader.clone(), writer);
+
+        dispatcher.set_handler(Box::new(handler));
+        let mut connect_request = ServiceConnectRequest::new();
+        connect_request.set_circuit("alpha".into());
+        connect_request.set_service_id("abc".into());
+        connect_request.set_protocol_min(crate::protocol::SERVICE_PROTOCOL_VERSION + 1);
+        connect_request.set_protocol_max(crate::protocol::SERVICE_PROTOCOL_VERSION + 2);
+        let connect_bytes = connect_request.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("abc"),
+                    PeerAuthorizationToken::from_peer_id("123"),
+                )
+                .into(),
+                &CircuitMessageType::SERVICE_CONNECT_REQUEST,
+                connect_bytes.clone(),
+            )
+            .unwrap();
+
+        let id = ServiceId::new("alpha".into(), "abc".into());
+        assert!(reader
+            .get_service(&id)
+            .unwrap()
+            .expect("Service not in routing table")
+            .local_peer_id()
+            .is_none());
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("abc"),
+                PeerAuthorizationToken::from_peer_id("123"),
+            ),
+            CircuitMessageType::SERVICE_CONNECT_RESPONSE,
+            |msg: ServiceConnectResponse| {
+                assert_eq!(
+                    msg.get_status(),
+                    ServiceConnectResponse_Status::ERROR_UNSUPPORTED_PROTOCOL_VERSION
+                );
+                assert_eq!(msg.get_protocol_version(), 0);
+            },
+        )
+    }
+
     #[test]
     // Test that if the service is in a circuit and already connected, a ServiceConnectResponse is
     // returned with an ERROR_SERVICE_ALREADY_REGISTERED
@@ -14,6 +14,8 @@
 
 //! Circuit routing and message handling.
 
+#[cfg(feature = "circuit-access-log")]
+pub mod access_log;
 pub mod handlers;
 pub mod routing;
 #[cfg(feature = "circuit-template")]
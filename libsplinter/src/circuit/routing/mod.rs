@@ -190,6 +190,7 @@ pub struct Circuit {
     roster: Vec<Service>,
     members: Vec<String>,
     authorization_type: AuthorizationType,
+    max_message_size: Option<u64>,
 }
 
 impl Circuit {
@@ -212,9 +213,17 @@ impl Circuit {
             roster,
             members,
             authorization_type,
+            max_message_size: None,
         }
     }
 
+    /// Sets the maximum size, in bytes, of a message payload that may be sent between services on
+    /// the circuit. `None` means that the circuit does not limit message size.
+    pub fn with_max_message_size(mut self, max_message_size: Option<u64>) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
     /// Returns the ID of the circuit
     pub fn circuit_id(&self) -> &str {
         &self.circuit_id
@@ -234,6 +243,11 @@ impl Circuit {
     pub fn authorization_type(&self) -> &AuthorizationType {
         &self.authorization_type
     }
+
+    /// Returns the maximum message payload size for the circuit, if one is set
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
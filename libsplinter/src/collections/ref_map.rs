@@ -77,6 +77,14 @@ impl<K: Hash + Eq> RefMap<K> {
             Ok(None)
         }
     }
+
+    /// Returns an iterator over each ID and its reference count
+    #[cfg(feature = "peer-ref-diagnostics")]
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.references
+            .iter()
+            .map(|(ref_id, ref_count)| (ref_id, *ref_count))
+    }
 }
 
 #[cfg(test)]
@@ -69,6 +69,92 @@ enum State {
     EvaluatingProposal(TwoPhaseProposal),
 }
 
+/// How a coordinator re-sends a proposal's verification request to the verifiers that have not
+/// responded to it.
+///
+/// After sending a verification request, the coordinator waits for the verification timeout for
+/// every verifier to respond. It then re-sends the request to the verifiers that have not
+/// responded, up to the configured number of retries, multiplying its wait by the backoff after
+/// each attempt. Once the retries are exhausted, the proposal is rejected. The coordinator
+/// timeout still bounds the whole round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerificationRetry {
+    timeout: Duration,
+    retries: u32,
+    backoff: u32,
+}
+
+impl VerificationRetry {
+    /// Creates a policy that waits `timeout` for verification responses and rejects the proposal
+    /// if they have not all arrived, without retrying.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            retries: 0,
+            backoff: 1,
+        }
+    }
+
+    /// Sets how many times the verification request is re-sent before the proposal is rejected.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the factor by which the wait is multiplied after each retry. A backoff of 1 (or 0)
+    /// waits the same time before every retry.
+    pub fn with_backoff(mut self, backoff: u32) -> Self {
+        self.backoff = backoff.max(1);
+        self
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    pub fn backoff(&self) -> u32 {
+        self.backoff
+    }
+
+    /// Applies the overrides requested in a proposal's consensus data to the given policy. A
+    /// proposal that requests a verification timeout gets a policy even if the engine has none.
+    fn with_overrides(
+        policy: Option<Self>,
+        required_verifiers: &RequiredVerifiers,
+    ) -> Option<Self> {
+        let policy = match (policy, required_verifiers.verification_timeout_millis) {
+            (policy, 0) => policy,
+            (Some(policy), millis) => Some(Self {
+                timeout: Duration::from_millis(millis),
+                ..policy
+            }),
+            (None, millis) => Some(Self::new(Duration::from_millis(millis))),
+        }?;
+
+        let policy = match required_verifiers.verification_retries {
+            0 => policy,
+            retries => policy.with_retries(retries),
+        };
+        Some(match required_verifiers.verification_backoff {
+            0 => policy,
+            backoff => policy.with_backoff(backoff),
+        })
+    }
+
+    /// The time to wait for responses after the given attempt, where attempt 0 is the original
+    /// request.
+    fn wait_after(&self, attempt: u32) -> Duration {
+        self.backoff
+            .checked_pow(attempt)
+            .and_then(|factor| self.timeout.checked_mul(factor))
+            .unwrap_or(Duration::MAX)
+    }
+}
+
 /// Contains information about a proposal that two phase consensus needs to keep track of
 #[derive(Debug)]
 struct TwoPhaseProposal {
@@ -76,6 +162,12 @@ struct TwoPhaseProposal {
     coordinator_id: PeerId,
     peers_verified: HashSet<PeerId>,
     required_verifiers: HashSet<PeerId>,
+    coordinator_timeout: Option<Duration>,
+    verification_retry: Option<VerificationRetry>,
+    // How many times the coordinator has re-sent the verification request
+    verification_attempts: u32,
+    // The response this node sent to the coordinator, if it is a participant that has responded
+    verification_response: Option<TwoPhaseMessage_ProposalVerificationResponse>,
 }
 
 impl TwoPhaseProposal {
@@ -83,12 +175,18 @@ impl TwoPhaseProposal {
         proposal_id: ProposalId,
         coordinator_id: PeerId,
         required_verifiers: HashSet<PeerId>,
+        coordinator_timeout: Option<Duration>,
+        verification_retry: Option<VerificationRetry>,
     ) -> Self {
         TwoPhaseProposal {
             proposal_id,
             coordinator_id,
             peers_verified: HashSet::new(),
             required_verifiers,
+            coordinator_timeout,
+            verification_retry,
+            verification_attempts: 0,
+            verification_response: None,
         }
     }
 
@@ -111,6 +209,15 @@ impl TwoPhaseProposal {
     fn add_verified_peer(&mut self, id: PeerId) {
         self.peers_verified.insert(id);
     }
+
+    /// The coordinator timeout requested for this proposal, if any
+    fn coordinator_timeout(&self) -> Option<Duration> {
+        self.coordinator_timeout
+    }
+
+    fn verification_retry(&self) -> Option<VerificationRetry> {
+        self.verification_retry
+    }
 }
 
 pub struct TwoPhaseEngine {
@@ -118,6 +225,10 @@ pub struct TwoPhaseEngine {
     peers: HashSet<PeerId>,
     state: State,
     coordinator_timeout: Timeout,
+    default_coordinator_timeout: Duration,
+    max_coordinator_timeout: Option<Duration>,
+    verification_timeout: Timeout,
+    verification_retry: Option<VerificationRetry>,
     proposal_backlog: VecDeque<TwoPhaseProposal>,
     verification_request_backlog: VecDeque<ProposalId>,
 }
@@ -129,11 +240,32 @@ impl TwoPhaseEngine {
             peers: HashSet::new(),
             state: State::Idle,
             coordinator_timeout: Timeout::new(coordinator_timeout_duration),
+            default_coordinator_timeout: coordinator_timeout_duration,
+            max_coordinator_timeout: None,
+            verification_timeout: Timeout::new(Duration::default()),
+            verification_retry: None,
             proposal_backlog: VecDeque::new(),
             verification_request_backlog: VecDeque::new(),
         }
     }
 
+    /// Set the maximum coordinator timeout that a proposal may request in its consensus data.
+    /// Longer requested timeouts are reduced to this value. If not set, requested timeouts are not
+    /// bounded.
+    pub fn with_max_coordinator_timeout(mut self, max_coordinator_timeout: Duration) -> Self {
+        self.max_coordinator_timeout = Some(max_coordinator_timeout);
+        self
+    }
+
+    /// Set how the coordinator re-sends verification requests that have not been answered. A
+    /// proposal may override the policy in its consensus data. If not set, and not requested by
+    /// the proposal, verification requests are sent once and the coordinator waits for the
+    /// coordinator timeout.
+    pub fn with_verification_retry(mut self, verification_retry: VerificationRetry) -> Self {
+        self.verification_retry = Some(verification_retry);
+        self
+    }
+
     fn handle_consensus_msg(
         &mut self,
         consensus_msg: ConsensusMessage,
@@ -157,6 +289,29 @@ impl TwoPhaseEngine {
                         );
                         self.verification_request_backlog.push_back(proposal_id);
                     }
+                    // The coordinator re-sends its request when it has not received this node's
+                    // response
+                    State::EvaluatingProposal(ref tpc_proposal) => {
+                        match tpc_proposal.verification_response {
+                            Some(response) => {
+                                debug!(
+                                    "Re-sending verification response for proposal {}",
+                                    proposal_id
+                                );
+                                send_verification_response(
+                                    network_sender,
+                                    tpc_proposal.coordinator_id(),
+                                    &proposal_id,
+                                    response,
+                                )?;
+                            }
+                            None => debug!(
+                                "Proposal {} is already being checked; ignoring repeated \
+                                 verification request",
+                                proposal_id
+                            ),
+                        }
+                    }
                     _ => {
                         // Try to find the proposal in the backlog
                         match self
@@ -309,25 +464,28 @@ impl TwoPhaseEngine {
                         request.set_proposal_id(proposal_id.into());
 
                         network_sender.broadcast(request.write_to_bytes()?)?;
+
+                        if let Some(retry) = tpc_proposal.verification_retry() {
+                            self.verification_timeout
+                                .start_with_duration(retry.wait_after(0));
+                        }
                     } else {
                         debug!("Sending verified response for proposal {}", proposal_id);
 
-                        let mut response = TwoPhaseMessage::new();
-                        response
-                            .set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-                        response.set_proposal_id(proposal_id.into());
-                        response.set_proposal_verification_response(
-                            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
-                        );
-
-                        network_sender
-                            .send_to(tpc_proposal.coordinator_id(), response.write_to_bytes()?)?;
+                        let response = TwoPhaseMessage_ProposalVerificationResponse::VERIFIED;
+                        send_verification_response(
+                            network_sender,
+                            tpc_proposal.coordinator_id(),
+                            &proposal_id,
+                            response,
+                        )?;
+                        tpc_proposal.verification_response = Some(response);
                     }
                 }
                 _ => warn!("Got valid message for unknown proposal: {}", proposal_id),
             },
-            ProposalUpdate::ProposalInvalid(proposal_id) => match self.state {
-                State::EvaluatingProposal(ref tpc_proposal)
+            ProposalUpdate::ProposalInvalid(proposal_id) => match &mut self.state {
+                State::EvaluatingProposal(tpc_proposal)
                     if tpc_proposal.proposal_id() == &proposal_id =>
                 {
                     debug!("Proposal invalid: {}", proposal_id);
@@ -343,16 +501,14 @@ impl TwoPhaseEngine {
                     } else {
                         debug!("Sending failed response for proposal {}", proposal_id);
 
-                        let mut response = TwoPhaseMessage::new();
-                        response
-                            .set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-                        response.set_proposal_id(proposal_id.into());
-                        response.set_proposal_verification_response(
-                            TwoPhaseMessage_ProposalVerificationResponse::FAILED,
-                        );
-
-                        network_sender
-                            .send_to(tpc_proposal.coordinator_id(), response.write_to_bytes()?)?;
+                        let response = TwoPhaseMessage_ProposalVerificationResponse::FAILED;
+                        send_verification_response(
+                            network_sender,
+                            tpc_proposal.coordinator_id(),
+                            &proposal_id,
+                            response,
+                        )?;
+                        tpc_proposal.verification_response = Some(response);
                     }
                 }
                 _ => warn!("Got invalid message for unknown proposal: {}", proposal_id),
@@ -394,8 +550,11 @@ impl TwoPhaseEngine {
         debug!("Checking proposal {}", tpc_proposal.proposal_id());
         match proposal_manager.check_proposal(tpc_proposal.proposal_id()) {
             Ok(_) => {
+                let timeout = tpc_proposal
+                    .coordinator_timeout()
+                    .unwrap_or(self.default_coordinator_timeout);
                 self.state = State::EvaluatingProposal(tpc_proposal);
-                self.coordinator_timeout.start();
+                self.coordinator_timeout.start_with_duration(timeout);
             }
             Err(err) => {
                 debug!(
@@ -439,6 +598,7 @@ impl TwoPhaseEngine {
 
         self.state = State::Idle;
         self.coordinator_timeout.stop();
+        self.verification_timeout.stop();
 
         let mut result = TwoPhaseMessage::new();
         result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
@@ -470,19 +630,40 @@ impl TwoPhaseEngine {
 
         // Determine which peers must verify the proposal for it to be committed. If the proposal
         // manager provides a list in the consensus data field, those peers are used; otherwise,
-        // the list will be all peers.
-        let verifiers = if !proposal.consensus_data.is_empty() {
-            let required_verifiers: RequiredVerifiers =
-                Message::parse_from_bytes(&proposal.consensus_data)?;
-            required_verifiers
-                .verifiers
-                .into_iter()
-                .map(PeerId::from)
-                .collect::<HashSet<PeerId>>()
-        } else {
-            let mut verifiers = self.peers.clone();
-            verifiers.insert(self.id.clone());
-            verifiers
+        // the list will be all peers. The consensus data may also request a coordinator timeout
+        // and verification retry policy for the proposal.
+        let (verifiers, requested_timeout, verification_retry) =
+            if !proposal.consensus_data.is_empty() {
+                let required_verifiers: RequiredVerifiers =
+                    Message::parse_from_bytes(&proposal.consensus_data)?;
+                let requested_timeout = match required_verifiers.coordinator_timeout_millis {
+                    0 => None,
+                    millis => Some(Duration::from_millis(millis)),
+                };
+                let verification_retry =
+                    VerificationRetry::with_overrides(self.verification_retry, &required_verifiers);
+                let verifiers = required_verifiers
+                    .verifiers
+                    .into_iter()
+                    .map(PeerId::from)
+                    .collect::<HashSet<PeerId>>();
+                (verifiers, requested_timeout, verification_retry)
+            } else {
+                let mut verifiers = self.peers.clone();
+                verifiers.insert(self.id.clone());
+                (verifiers, None, self.verification_retry)
+            };
+
+        let coordinator_timeout = match (requested_timeout, self.max_coordinator_timeout) {
+            (Some(requested), Some(max)) if requested > max => {
+                warn!(
+                    "Proposal {} requested a coordinator timeout of {:?}, which exceeds the \
+                     maximum; using {:?}",
+                    proposal.id, requested, max
+                );
+                Some(max)
+            }
+            (requested, _) => requested,
         };
 
         // Determines which verifier is the coordinator; the coordinator is the verifier with the
@@ -500,7 +681,13 @@ impl TwoPhaseEngine {
             }
         };
 
-        let tpc_proposal = TwoPhaseProposal::new(proposal.id, coordinator, verifiers);
+        let tpc_proposal = TwoPhaseProposal::new(
+            proposal.id,
+            coordinator,
+            verifiers,
+            coordinator_timeout,
+            verification_retry,
+        );
 
         if let State::EvaluatingProposal(ref current_proposal) = self.state {
             if tpc_proposal.proposal_id() == current_proposal.proposal_id() {
@@ -557,6 +744,81 @@ impl TwoPhaseEngine {
         Ok(())
     }
 
+    /// If this node is coordinating a proposal and the verifiers have not all responded to its
+    /// verification request in time, re-send the request to those that have not responded, or
+    /// reject the proposal if the retries are exhausted.
+    fn retry_verification_if_timed_out(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let (proposal_id, retry) = match &mut self.state {
+            State::EvaluatingProposal(tpc_proposal)
+                if tpc_proposal.coordinator_id() == &self.id
+                    && self.verification_timeout.check_expired() =>
+            {
+                let policy = match tpc_proposal.verification_retry() {
+                    Some(policy) => policy,
+                    None => return Ok(()),
+                };
+
+                if tpc_proposal.verification_attempts >= policy.retries() {
+                    (tpc_proposal.proposal_id().clone(), None)
+                } else {
+                    tpc_proposal.verification_attempts += 1;
+                    let unverified = tpc_proposal
+                        .required_verifiers()
+                        .difference(tpc_proposal.peers_verified())
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (
+                        tpc_proposal.proposal_id().clone(),
+                        Some((
+                            unverified,
+                            policy.wait_after(tpc_proposal.verification_attempts),
+                        )),
+                    )
+                }
+            }
+            _ => return Ok(()),
+        };
+
+        match retry {
+            Some((unverified, wait)) => {
+                debug!(
+                    "Re-sending verification request for proposal {} to {} verifier(s)",
+                    proposal_id,
+                    unverified.len()
+                );
+
+                let mut request = TwoPhaseMessage::new();
+                request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+                request.set_proposal_id(proposal_id.into());
+                let request_bytes = request.write_to_bytes()?;
+
+                for peer_id in unverified {
+                    network_sender.send_to(&peer_id, request_bytes.clone())?;
+                }
+
+                self.verification_timeout.start_with_duration(wait);
+            }
+            None => {
+                warn!(
+                    "Verifiers did not respond to verification request; rejecting: {}",
+                    proposal_id
+                );
+                self.complete_coordination(
+                    proposal_id,
+                    TwoPhaseMessage_ProposalResult::REJECT,
+                    network_sender,
+                    proposal_manager,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// If not doing anything, see if there are any backlogged verification requests that this node
     /// has received a proposal for, and evaluate that proposal.
     fn handle_backlogged_verification_request(
@@ -659,6 +921,12 @@ impl ConsensusEngine for TwoPhaseEngine {
                 error!("Failed to abort timed-out proposal: {}", err);
             }
 
+            if let Err(err) =
+                self.retry_verification_if_timed_out(&*network_sender, &*proposal_manager)
+            {
+                error!("Failed to retry verification request: {}", err);
+            }
+
             if let Err(err) = self.handle_backlogged_verification_request(&*proposal_manager) {
                 error!("Failed to handle backlogged verification request: {}", err);
             }
@@ -708,6 +976,24 @@ impl ConsensusEngine for TwoPhaseEngine {
     }
 }
 
+/// Sends this node's response to a proposal's verification request to the proposal's
+/// coordinator.
+fn send_verification_response(
+    network_sender: &dyn ConsensusNetworkSender,
+    coordinator_id: &PeerId,
+    proposal_id: &ProposalId,
+    response: TwoPhaseMessage_ProposalVerificationResponse,
+) -> Result<(), ConsensusEngineError> {
+    let mut message = TwoPhaseMessage::new();
+    message.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+    message.set_proposal_id(proposal_id.clone().into());
+    message.set_proposal_verification_response(response);
+
+    network_sender.send_to(coordinator_id, message.write_to_bytes()?)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -1326,4 +1612,176 @@ pub mod tests {
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
     }
+
+    /// Test that the coordinator timeout requested in a proposal's consensus data is used instead
+    /// of the engine's default, and that it is bounded by the engine's maximum.
+    ///
+    /// 1. Start the engine with a long default timeout and a very short maximum timeout
+    /// 2. Create a proposal that requests a long coordinator timeout
+    /// 3. Verify that the proposal is rejected, since the requested timeout was reduced to the
+    ///    maximum
+    #[test]
+    fn test_proposal_coordinator_timeout() {
+        let (update_tx, update_rx) = channel();
+        let (_consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let mut manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+
+        let mut required_verifiers = RequiredVerifiers::new();
+        required_verifiers.set_verifiers(RepeatedField::from_vec(vec![
+            vec![0].into(),
+            vec![1].into(),
+            vec![2].into(),
+        ]));
+        required_verifiers.set_coordinator_timeout_millis(60_000);
+        let data = required_verifiers.write_to_bytes().unwrap();
+        manager.set_consensus_data(Some(data));
+
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_secs(60))
+            .with_max_coordinator_timeout(Duration::from_millis(10));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Verify the proposal is rejected due to the timeout
+        loop {
+            if let Some(id) = manager.rejected_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that the coordinator re-sends its verification request to the verifiers that have
+    /// not responded, and rejects the proposal once the retries requested by the proposal are
+    /// exhausted.
+    ///
+    /// 1. Start an engine with a long coordinator timeout and a short verification timeout
+    /// 2. Create a proposal that requests two retries
+    /// 3. Respond to the verification request from one of the two peers
+    /// 4. Verify the request is re-sent twice, only to the peer that did not respond
+    /// 5. Verify the proposal is rejected
+    #[test]
+    fn test_verification_retry() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let mut manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+
+        let mut required_verifiers = RequiredVerifiers::new();
+        required_verifiers.set_verifiers(RepeatedField::from_vec(vec![
+            vec![0].into(),
+            vec![1].into(),
+            vec![2].into(),
+        ]));
+        required_verifiers.set_verification_retries(2);
+        let data = required_verifiers.write_to_bytes().unwrap();
+        manager.set_consensus_data(Some(data));
+
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_secs(60))
+            .with_verification_retry(VerificationRetry::new(Duration::from_millis(50)));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Wait for the verification request, then respond from peer 1 only
+        while network.broadcast_messages().is_empty() {}
+
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![1]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+        );
+        consensus_msg_tx
+            .send(ConsensusMessage::new(
+                response.write_to_bytes().unwrap(),
+                vec![1].into(),
+            ))
+            .expect("failed to send response");
+
+        // Verify the proposal is rejected after the retries are exhausted
+        loop {
+            if let Some(id) = manager.rejected_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        // Verify the request was re-sent twice, and only to peer 2
+        let sent_messages = network.sent_messages().clone();
+        assert_eq!(sent_messages.len(), 2);
+        for (msg, peer_id) in sent_messages {
+            let msg: TwoPhaseMessage =
+                Message::parse_from_bytes(&msg).expect("failed to parse message");
+            assert_eq!(
+                msg.get_message_type(),
+                TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+            );
+            assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+            assert_eq!(peer_id, PeerId::from(vec![2]));
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    #[test]
+    fn test_verification_retry_backoff() {
+        let retry = VerificationRetry::new(Duration::from_secs(1))
+            .with_retries(3)
+            .with_backoff(2);
+
+        assert_eq!(retry.wait_after(0), Duration::from_secs(1));
+        assert_eq!(retry.wait_after(1), Duration::from_secs(2));
+        assert_eq!(retry.wait_after(3), Duration::from_secs(8));
+        assert_eq!(retry.wait_after(u32::MAX), Duration::MAX);
+        assert_eq!(
+            VerificationRetry::new(Duration::from_secs(1))
+                .with_backoff(0)
+                .backoff(),
+            1
+        );
+    }
 }
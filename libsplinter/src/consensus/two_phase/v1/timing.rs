@@ -56,6 +56,12 @@ impl Timeout {
         self.start = Instant::now();
     }
 
+    /// Start the timer with a new duration, which is used until the duration is changed again
+    pub fn start_with_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+        self.start();
+    }
+
     pub fn stop(&mut self) {
         self.state = TimeoutState::Inactive;
     }
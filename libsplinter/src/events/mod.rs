@@ -20,4 +20,6 @@ mod ws;
 
 pub use error::{ParseError, ReactorError, WebSocketError};
 pub use reactor::{Igniter, Reactor, ReactorShutdownSignaler};
+#[cfg(feature = "events-reconnect-backoff")]
+pub use ws::ReconnectBackoff;
 pub use ws::{ParseBytes, WebSocketClient, WsResponse};
@@ -17,8 +17,8 @@ use std::sync::{
     Arc,
 };
 
-use crossbeam_channel::Sender;
-use futures::Future;
+use futures::{sync::mpsc::UnboundedSender, Future};
+use hyper::{client::HttpConnector, Client};
 
 use crate::events::ws::{Context, Listen, ParseBytes, WebSocketClient};
 use crate::events::{ReactorError, WebSocketError};
@@ -28,8 +28,9 @@ use super::ReactorMessage;
 /// The Igniter is a channel that allows for communication with a Reactor runtime
 #[derive(Clone)]
 pub struct Igniter {
-    pub(super) sender: Sender<ReactorMessage>,
+    pub(super) sender: UnboundedSender<ReactorMessage>,
     pub(super) reactor_running: Arc<AtomicBool>,
+    pub(super) client: Client<HttpConnector>,
 }
 
 impl Igniter {
@@ -39,7 +40,7 @@ impl Igniter {
     ) -> Result<(), WebSocketError> {
         let context = Context::new(self.clone(), ws.clone());
         self.sender
-            .send(ReactorMessage::StartWs(ws.listen(context)?))
+            .unbounded_send(ReactorMessage::StartWs(ws.listen(context)?))
             .map_err(|err| {
                 WebSocketError::ListenError(format!("Failed to start ws {}: {}", ws.url(), err))
             })
@@ -50,7 +51,7 @@ impl Igniter {
         req: Box<dyn Future<Item = (), Error = ()> + Send + 'static>,
     ) -> Result<(), ReactorError> {
         self.sender
-            .send(ReactorMessage::HttpRequest(req))
+            .unbounded_send(ReactorMessage::HttpRequest(req))
             .map_err(|err| {
                 ReactorError::RequestSendError(format!("Failed to send request to reactor {}", err))
             })
@@ -58,11 +59,16 @@ impl Igniter {
 
     pub fn start_ws_with_listen(&self, listen: Listen) -> Result<(), WebSocketError> {
         self.sender
-            .send(ReactorMessage::StartWs(listen))
+            .unbounded_send(ReactorMessage::StartWs(listen))
             .map_err(|err| WebSocketError::ListenError(format!("Failed to start ws {}", err)))
     }
 
     pub fn is_reactor_running(&self) -> bool {
         self.reactor_running.load(Ordering::SeqCst)
     }
+
+    /// Returns the HTTP client shared by all websockets started on the reactor.
+    pub(crate) fn http_client(&self) -> Client<HttpConnector> {
+        self.client.clone()
+    }
 }
@@ -21,13 +21,16 @@ use std::sync::{
     Arc,
 };
 use std::thread;
-use std::time::Duration;
 
-use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
-use futures::Future;
+use futures::{
+    future,
+    sync::mpsc::{unbounded, UnboundedSender},
+    Future, Stream,
+};
+use hyper::{client::HttpConnector, Client};
 use tokio::runtime::Runtime;
 
-use crate::events::ws::ShutdownHandle;
+use crate::events::ws::{Listen, ShutdownHandle};
 use crate::events::{ReactorError, WebSocketError};
 
 pub use igniter::Igniter;
@@ -39,15 +42,20 @@ pub use reactor_shutdown_signaler::ReactorShutdownSignaler;
 /// Reactor creates a runtime environment for http related futures
 /// on start up. Reactors create `Igniter` object that are used to
 /// send futures to the runtime.
+///
+/// Messages sent by igniters are handled by the reactor's tokio runtime as they arrive. Each
+/// websocket runs as its own task, so an error on one connection is logged against its URL and
+/// does not affect the others.
 pub struct Reactor {
-    sender: Sender<ReactorMessage>,
+    sender: UnboundedSender<ReactorMessage>,
     thread_handle: thread::JoinHandle<()>,
     running: Arc<AtomicBool>,
+    client: Client<HttpConnector>,
 }
 
 impl Reactor {
     pub fn new() -> Self {
-        let (sender, receiver) = bounded::<ReactorMessage>(10);
+        let (sender, receiver) = unbounded::<ReactorMessage>();
         let running = Arc::new(AtomicBool::new(true));
         let reactor_running = running.clone();
 
@@ -62,52 +70,35 @@ impl Reactor {
                     }
                 };
 
-                let mut connections = Vec::new();
-                let shutdown_errors = loop {
-                    match receiver.recv_timeout(Duration::from_millis(500)) {
-                        Ok(ReactorMessage::StartWs(listen)) => {
-                            let (future, handle) = listen.into_shutdown_handle();
-                            runtime.spawn(futures::lazy(|| future.map_err(|_| ())));
-                            connections.push(handle);
-                        }
-                        Ok(ReactorMessage::HttpRequest(req)) => {
-                            runtime.spawn(req);
-                        }
-                        Ok(ReactorMessage::Stop) => {
-                            debug!("Shutting down event reactor");
-                            reactor_running.store(false, Ordering::SeqCst);
-
-                            break connections
-                                .into_iter()
-                                .map(|connection| connection.shutdown())
-                                .filter_map(|res| if let Err(err) = res { Some(err) } else { None })
-                                .collect::<Vec<WebSocketError>>();
-                        }
-                        Err(RecvTimeoutError::Timeout) => {
-                            continue;
-                        }
-                        Err(RecvTimeoutError::Disconnected) => {
-                            debug!(
-                                "Event reactor sender disconnected; terminating web socket loop..."
-                            );
-                            break vec![];
-                        }
-                    }
-
-                    let (live_connections, closed_connections): (
-                        Vec<ShutdownHandle>,
-                        Vec<ShutdownHandle>,
-                    ) = connections.into_iter().partition(|conn| conn.running());
-                    for conn in closed_connections {
-                        match conn.shutdown() {
-                            Ok(()) => info!("A ws connection closed"),
-                            Err(err) => {
-                                error!("A ws connection closed unexpectedly with error {}", err)
+                // Handle messages until a stop message is received or all of the igniters and
+                // signalers have been dropped
+                let connections = runtime.block_on(
+                    receiver
+                        .take_while(|message| future::ok(!matches!(message, ReactorMessage::Stop)))
+                        .fold(Vec::new(), |mut connections, message| {
+                            match message {
+                                ReactorMessage::StartWs(listen) => {
+                                    connections.retain(ShutdownHandle::running);
+                                    connections.push(spawn_listener(listen));
+                                }
+                                ReactorMessage::HttpRequest(req) => {
+                                    tokio::spawn(req);
+                                }
+                                ReactorMessage::Stop => (),
                             }
-                        }
-                    }
-                    connections = live_connections;
-                };
+                            future::ok::<_, ()>(connections)
+                        }),
+                );
+
+                debug!("Shutting down event reactor");
+                reactor_running.store(false, Ordering::SeqCst);
+
+                let shutdown_errors = connections
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|connection| connection.shutdown())
+                    .filter_map(|res| if let Err(err) = res { Some(err) } else { None })
+                    .collect::<Vec<WebSocketError>>();
 
                 if let Err(err) = runtime
                     .shutdown_on_idle()
@@ -134,6 +125,7 @@ impl Reactor {
             sender,
             thread_handle,
             running,
+            client: Client::new(),
         }
     }
 
@@ -141,6 +133,7 @@ impl Reactor {
         Igniter {
             sender: self.sender.clone(),
             reactor_running: self.running.clone(),
+            client: self.client.clone(),
         }
     }
 
@@ -171,6 +164,25 @@ impl Reactor {
     }
 }
 
+/// Spawns the websocket future of the given `Listen` on the current runtime, logging how the
+/// connection ended, and returns the handle used to shut it down.
+fn spawn_listener(listen: Listen) -> ShutdownHandle {
+    let (future, handle) = listen.into_shutdown_handle();
+    let url = handle.url().to_string();
+    tokio::spawn(future.then(move |res| {
+        match res {
+            Ok(()) => info!("Websocket connection to {} closed", url),
+            Err(err) => error!(
+                "Websocket connection to {} closed unexpectedly: {}",
+                url, err
+            ),
+        }
+        Ok(())
+    }));
+
+    handle
+}
+
 impl std::default::Default for Reactor {
     fn default() -> Self {
         Self::new()
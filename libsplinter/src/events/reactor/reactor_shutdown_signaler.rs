@@ -12,20 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crossbeam_channel::Sender;
+use futures::sync::mpsc::UnboundedSender;
 
 use crate::events::ReactorError;
 
 use super::ReactorMessage;
 
 pub struct ReactorShutdownSignaler {
-    pub(super) sender: Sender<ReactorMessage>,
+    pub(super) sender: UnboundedSender<ReactorMessage>,
 }
 
 impl ReactorShutdownSignaler {
     pub fn signal_shutdown(&self) -> Result<(), ReactorError> {
-        self.sender.send(ReactorMessage::Stop).map_err(|_| {
-            ReactorError::ReactorShutdownError("Failed to send shutdown message".to_string())
-        })
+        self.sender
+            .unbounded_send(ReactorMessage::Stop)
+            .map_err(|_| {
+                ReactorError::ReactorShutdownError("Failed to send shutdown message".to_string())
+            })
     }
 }
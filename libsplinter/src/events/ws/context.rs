@@ -13,10 +13,16 @@
 // limitations under the License.
 
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant};
+
+use futures::{Future, Stream};
+use tokio::timer::Interval;
 
 use crate::events::{Igniter, ParseBytes, WebSocketClient, WebSocketError};
 
+// How often a pending reconnect checks whether the reactor is shutting down
+const RECONNECT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Websocket context object. It contains an Igniter pointing
 /// to the Reactor on which the websocket future is running and
 /// a copy of the WebSocketClient object.
@@ -25,18 +31,17 @@ pub struct Context<T: ParseBytes<T> + 'static> {
     igniter: Igniter,
     ws: WebSocketClient<T>,
     reconnect_count: u64,
-    last_reconnect: SystemTime,
     wait: Duration,
 }
 
 impl<T: ParseBytes<T> + 'static> Context<T> {
     pub fn new(igniter: Igniter, ws: WebSocketClient<T>) -> Self {
+        let wait = ws.backoff().initial();
         Self {
             igniter,
             ws,
             reconnect_count: 0,
-            last_reconnect: SystemTime::now(),
-            wait: Duration::from_secs(1),
+            wait,
         }
     }
 
@@ -95,38 +100,48 @@ impl<T: ParseBytes<T> + 'static> Context<T> {
         }
     }
 
+    /// Schedules the next reconnect attempt on the reactor once the current wait has passed. The
+    /// attempt is abandoned if the reactor shuts down in the meantime.
     fn reconnect(&mut self) -> Result<(), WebSocketError> {
-        // loop until wait time has passed or reactor received shutdown signal
-        debug!("Reconnecting in {:?}", self.wait);
-        loop {
-            // time elapsed since last reconnect attempt
-            let elapsed = SystemTime::now()
-                .duration_since(self.last_reconnect)
-                .unwrap_or_else(|_| Duration::from_secs(0));
-
-            if elapsed >= self.wait {
-                break;
-            }
-
-            if !self.igniter.is_reactor_running() {
-                return Ok(());
-            }
+        if !self.igniter.is_reactor_running() {
+            return Ok(());
         }
 
-        self.reconnect_count += 1;
-        self.last_reconnect = SystemTime::now();
-
-        let new_wait = self.wait.as_secs_f64() * 2.0;
+        debug!("Reconnecting to {} in {:?}", self.ws.url(), self.wait);
+        let deadline = Instant::now() + self.wait;
 
-        self.wait = Duration::from_secs_f64(new_wait);
-
-        debug!(
-            "Attempting to reconnect. Attempt number {} out of {}",
-            self.reconnect_count,
-            self.ws.reconnect_limit()
-        );
-
-        self.start_ws()
+        self.reconnect_count += 1;
+        self.wait = self.ws.backoff().next_wait(self.wait);
+
+        let context = self.clone();
+        let igniter = self.igniter.clone();
+        let reconnect = Interval::new_interval(RECONNECT_POLL_INTERVAL)
+            .take_while(move |_| Ok(igniter.is_reactor_running() && Instant::now() < deadline))
+            .for_each(|_| Ok(()))
+            .then(move |res| -> Result<(), ()> {
+                if let Err(err) = res {
+                    error!("Reconnect timer for {} failed: {}", context.ws.url(), err);
+                } else if context.igniter.is_reactor_running() {
+                    debug!(
+                        "Attempting to reconnect to {}. Attempt number {} out of {}",
+                        context.ws.url(),
+                        context.reconnect_count,
+                        context.ws.reconnect_limit()
+                    );
+                    if let Err(err) = context.start_ws() {
+                        error!("Unable to reconnect to {}: {}", context.ws.url(), err);
+                    }
+                }
+                Ok(())
+            });
+
+        self.igniter.send(Box::new(reconnect)).map_err(|err| {
+            WebSocketError::ReconnectError(format!(
+                "Unable to schedule reconnect to {}: {}",
+                self.ws.url(),
+                err
+            ))
+        })
     }
 
     fn reset_reconnect_count(&mut self) {
@@ -134,6 +149,6 @@ impl<T: ParseBytes<T> + 'static> Context<T> {
     }
 
     fn reset_wait(&mut self) {
-        self.wait = Duration::from_secs(1)
+        self.wait = self.ws.backoff().initial()
     }
 }
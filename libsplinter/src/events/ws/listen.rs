@@ -24,6 +24,7 @@ use super::ShutdownHandle;
 /// Wrapper around future created by `WebSocketClient`. In order for
 /// the future to run it must be passed to `Igniter::start_ws`
 pub struct Listen {
+    pub(super) url: String,
     pub(super) future: Box<dyn Future<Item = (), Error = WebSocketError> + Send + 'static>,
     pub(super) sender: Sender<WebSocketClientCmd>,
     pub(super) running: Arc<AtomicBool>,
@@ -39,6 +40,7 @@ impl Listen {
         (
             self.future,
             ShutdownHandle {
+                url: self.url,
                 sender: self.sender,
                 running: self.running,
             },
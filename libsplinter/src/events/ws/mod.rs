@@ -56,6 +56,7 @@ mod connection_status;
 mod context;
 mod listen;
 mod parse_bytes;
+mod reconnect_backoff;
 mod shutdown_handle;
 mod web_socket_client;
 mod web_socket_client_cmd;
@@ -78,6 +79,7 @@ use crate::events::WebSocketError;
 pub use context::Context;
 pub use listen::Listen;
 pub use parse_bytes::ParseBytes;
+pub use reconnect_backoff::ReconnectBackoff;
 pub use shutdown_handle::ShutdownHandle;
 pub use web_socket_client::WebSocketClient;
 pub use ws_respoonse::WsResponse;
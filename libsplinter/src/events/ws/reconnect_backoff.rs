@@ -0,0 +1,154 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+#[cfg(feature = "events-reconnect-backoff")]
+use crate::error::InvalidArgumentError;
+
+const DEFAULT_INITIAL_WAIT: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(300);
+const DEFAULT_MULTIPLIER: f64 = 2.0;
+
+/// The delay between a websocket's reconnect attempts.
+///
+/// The first reconnect attempt waits for the initial delay; each following attempt waits
+/// `multiplier` times longer than the previous one, up to the maximum delay. The delay is reset
+/// once a connection is established.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconnectBackoff {
+    initial: Duration,
+    max: Duration,
+    multiplier: f64,
+}
+
+impl ReconnectBackoff {
+    /// Creates a new `ReconnectBackoff`.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The delay before the first reconnect attempt
+    /// * `max` - The longest delay between two reconnect attempts
+    /// * `multiplier` - The factor the delay grows by after each attempt; must be at least 1.0
+    ///
+    /// Returns an error if `initial` is zero or greater than `max`, or if `multiplier` is less
+    /// than 1.0.
+    #[cfg(feature = "events-reconnect-backoff")]
+    pub fn new(
+        initial: Duration,
+        max: Duration,
+        multiplier: f64,
+    ) -> Result<Self, InvalidArgumentError> {
+        if initial == Duration::from_secs(0) {
+            return Err(InvalidArgumentError::new(
+                "initial".to_string(),
+                "must be greater than zero".to_string(),
+            ));
+        }
+        if initial > max {
+            return Err(InvalidArgumentError::new(
+                "max".to_string(),
+                "must not be less than the initial delay".to_string(),
+            ));
+        }
+        if multiplier.is_nan() || multiplier < 1.0 {
+            return Err(InvalidArgumentError::new(
+                "multiplier".to_string(),
+                "must be at least 1.0".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            initial,
+            max,
+            multiplier,
+        })
+    }
+
+    /// Returns the delay before the first reconnect attempt.
+    pub fn initial(&self) -> Duration {
+        self.initial
+    }
+
+    /// Returns the longest delay between two reconnect attempts.
+    #[cfg(feature = "events-reconnect-backoff")]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the factor the delay grows by after each attempt.
+    #[cfg(feature = "events-reconnect-backoff")]
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Returns the delay to use for the attempt following one that waited for `wait`.
+    pub(super) fn next_wait(&self, wait: Duration) -> Duration {
+        let next = Duration::from_secs_f64(wait.as_secs_f64() * self.multiplier);
+        std::cmp::min(next, self.max)
+    }
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self {
+            initial: DEFAULT_INITIAL_WAIT,
+            max: DEFAULT_MAX_WAIT,
+            multiplier: DEFAULT_MULTIPLIER,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "events-reconnect-backoff"))]
+mod tests {
+    use super::*;
+
+    /// Verify that the delay grows by the multiplier and is capped at the maximum delay.
+    #[test]
+    fn test_next_wait() {
+        let backoff =
+            ReconnectBackoff::new(Duration::from_secs(1), Duration::from_secs(10), 3.0).unwrap();
+
+        let mut wait = backoff.initial();
+        let mut waits = vec![];
+        for _ in 0..4 {
+            waits.push(wait);
+            wait = backoff.next_wait(wait);
+        }
+
+        assert_eq!(
+            waits,
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(3),
+                Duration::from_secs(9),
+                Duration::from_secs(10),
+            ]
+        );
+    }
+
+    /// Verify that invalid backoff settings are rejected.
+    #[test]
+    fn test_new_invalid() {
+        assert!(
+            ReconnectBackoff::new(Duration::from_secs(0), Duration::from_secs(1), 2.0).is_err()
+        );
+        assert!(
+            ReconnectBackoff::new(Duration::from_secs(2), Duration::from_secs(1), 2.0).is_err()
+        );
+        assert!(
+            ReconnectBackoff::new(Duration::from_secs(1), Duration::from_secs(1), 0.5).is_err()
+        );
+    }
+}
@@ -25,6 +25,7 @@ use super::web_socket_client_cmd::WebSocketClientCmd;
 
 #[derive(Clone)]
 pub struct ShutdownHandle {
+    pub(super) url: String,
     pub(super) sender: Sender<WebSocketClientCmd>,
     pub(super) running: Arc<AtomicBool>,
 }
@@ -42,4 +43,9 @@ impl ShutdownHandle {
     pub fn running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
+
+    /// Returns the URL of the websocket.
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
 }
@@ -25,7 +25,7 @@ use futures::{
     sync::mpsc::channel,
     Future,
 };
-use hyper::{self, header, Body, Client, Request, StatusCode};
+use hyper::{self, header, Body, Request, StatusCode};
 use tokio::codec::Decoder;
 use tokio::prelude::*;
 
@@ -33,7 +33,7 @@ use crate::events::{ParseBytes, WebSocketError, WsResponse};
 
 use super::{
     connection_status::ConnectionStatus, do_shutdown, handle_response,
-    web_socket_client_cmd::WebSocketClientCmd, Context, Listen,
+    web_socket_client_cmd::WebSocketClientCmd, Context, Listen, ReconnectBackoff,
 };
 
 const MAX_FRAME_SIZE: usize = 10_000_000;
@@ -56,6 +56,7 @@ pub struct WebSocketClient<T: ParseBytes<T> + 'static = Vec<u8>> {
     on_reconnect: Option<Arc<OnReconnectHandle<T>>>,
     reconnect: bool,
     reconnect_limit: u64,
+    reconnect_backoff: ReconnectBackoff,
     timeout: u64,
     additional_headers: HashMap<String, String>,
 }
@@ -71,6 +72,7 @@ impl<T: ParseBytes<T> + 'static> Clone for WebSocketClient<T> {
             on_reconnect: self.on_reconnect.clone(),
             reconnect: self.reconnect,
             reconnect_limit: self.reconnect_limit,
+            reconnect_backoff: self.reconnect_backoff.clone(),
             timeout: self.timeout,
             additional_headers: self.additional_headers.clone(),
         }
@@ -91,6 +93,7 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
             on_reconnect: None,
             reconnect: DEFAULT_RECONNECT,
             reconnect_limit: DEFAULT_RECONNECT_LIMIT,
+            reconnect_backoff: ReconnectBackoff::default(),
             timeout: DEFAULT_TIMEOUT,
             additional_headers: HashMap::new(),
         }
@@ -112,6 +115,13 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         self.reconnect_limit = reconnect_limit
     }
 
+    /// Sets the delay between reconnect attempts; defaults to one second, doubling after each
+    /// attempt up to five minutes.
+    #[cfg(feature = "events-reconnect-backoff")]
+    pub fn set_reconnect_backoff(&mut self, reconnect_backoff: ReconnectBackoff) {
+        self.reconnect_backoff = reconnect_backoff
+    }
+
     pub fn set_timeout(&mut self, timeout: u64) {
         self.timeout = timeout
     }
@@ -140,6 +150,15 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         self.timeout
     }
 
+    #[cfg(feature = "events-reconnect-backoff")]
+    pub fn reconnect_backoff(&self) -> &ReconnectBackoff {
+        &self.reconnect_backoff
+    }
+
+    pub(super) fn backoff(&self) -> &ReconnectBackoff {
+        &self.reconnect_backoff
+    }
+
     /// Adds optional `on_open` closure. This closer is called after a connection is initially
     /// established with the server, and is used for printing debug information and sending initial
     /// messages to server if necessary.
@@ -232,8 +251,12 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
             .body(Body::empty())
             .map_err(|err| WebSocketError::RequestBuilderError(format!("{:?}", err)))?;
 
+        // All of the reactor's websockets share a single HTTP client, and with it the client's
+        // connection pool and DNS resolver threads
         let future = Box::new(
-            Client::new()
+            context
+                .igniter()
+                .http_client()
                 .request(request)
                 .and_then(move |res| {
                     if res.status() != StatusCode::SWITCHING_PROTOCOLS {
@@ -419,6 +442,7 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         );
 
         Ok(Listen {
+            url: self.url.clone(),
             future,
             sender: cmd_sender,
             running,
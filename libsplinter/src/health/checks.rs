@@ -0,0 +1,375 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The health checks run by a splinter node.
+
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::admin::store::{AdminServiceStore, CircuitPredicate, CircuitStatus};
+use crate::peer::{PeerAuthorizationToken, PeerManagerConnector};
+
+use super::{CheckOutcome, HealthCheck};
+
+// How long the REST API check waits to connect and for a response
+const REST_API_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The percentage of a volume in use at which it is considered full
+const FULL_PERCENT: u8 = 99;
+
+/// Checks that the node's stores can be reached, by counting the circuits in the admin service
+/// store.
+pub struct StoreCheck {
+    store: Box<dyn AdminServiceStore>,
+}
+
+impl StoreCheck {
+    pub fn new(store: Box<dyn AdminServiceStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl HealthCheck for StoreCheck {
+    fn name(&self) -> &str {
+        "store"
+    }
+
+    fn check(&self) -> CheckOutcome {
+        match self.store.count_circuits(&[]) {
+            Ok(count) => CheckOutcome::pass(format!("{} circuit(s) in the admin store", count)),
+            Err(err) => CheckOutcome::fail(format!("Unable to reach the admin store: {}", err)),
+        }
+    }
+}
+
+/// Checks that the peer manager is responding, and that the node is connected to the other
+/// members of its active circuits.
+///
+/// Disconnected members only produce a warning, as the node is still able to serve requests
+/// while its peers reconnect.
+pub struct PeerCheck {
+    node_id: String,
+    connector: PeerManagerConnector,
+    store: Box<dyn AdminServiceStore>,
+}
+
+impl PeerCheck {
+    /// Creates a new peer check.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the local node, which is excluded from the circuit members
+    /// * `connector` - The connector of the node's peer manager
+    /// * `store` - The store from which the active circuits are read
+    pub fn new(
+        node_id: String,
+        connector: PeerManagerConnector,
+        store: Box<dyn AdminServiceStore>,
+    ) -> Self {
+        Self {
+            node_id,
+            connector,
+            store,
+        }
+    }
+}
+
+impl HealthCheck for PeerCheck {
+    fn name(&self) -> &str {
+        "peers"
+    }
+
+    fn check(&self) -> CheckOutcome {
+        let peers = match self.connector.list_peers() {
+            Ok(peers) => peers,
+            Err(err) => {
+                return CheckOutcome::fail(format!("Unable to reach the peer manager: {}", err))
+            }
+        };
+
+        let circuits = match self
+            .store
+            .list_circuits(&[CircuitPredicate::CircuitStatus(CircuitStatus::Active)])
+        {
+            Ok(circuits) => circuits,
+            Err(err) => {
+                return CheckOutcome::warn(format!(
+                    "{} peer(s) connected, but unable to list circuits: {}",
+                    peers.len(),
+                    err
+                ))
+            }
+        };
+
+        let mut checked = HashSet::new();
+        let mut disconnected = Vec::new();
+        for circuit in circuits {
+            for member in circuit.members() {
+                if member.node_id() == self.node_id || !checked.insert(member.node_id().to_string())
+                {
+                    continue;
+                }
+
+                let connected = peers.iter().any(|peer| match peer {
+                    PeerAuthorizationToken::Trust { .. } => peer.has_peer_id(member.node_id()),
+                    PeerAuthorizationToken::Challenge { .. } => {
+                        member.public_key().as_ref() == peer.public_key()
+                    }
+                });
+                if !connected {
+                    disconnected.push(member.node_id().to_string());
+                }
+            }
+        }
+
+        if disconnected.is_empty() {
+            CheckOutcome::pass(format!(
+                "{} peer(s) connected; all {} circuit member(s) are connected",
+                peers.len(),
+                checked.len()
+            ))
+        } else {
+            disconnected.sort();
+            CheckOutcome::warn(format!(
+                "{} of {} circuit member(s) are not connected: {}",
+                disconnected.len(),
+                checked.len(),
+                disconnected.join(", ")
+            ))
+        }
+    }
+}
+
+/// Checks that the node's REST API is accepting connections and, unless it is served over TLS,
+/// responding to requests.
+pub struct RestApiCheck {
+    address: String,
+    tls: bool,
+}
+
+impl RestApiCheck {
+    /// Creates a new REST API check.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The `host:port` the REST API is bound to; unspecified hosts, such as
+    ///   `0.0.0.0`, are checked on the loopback address
+    /// * `tls` - Whether the REST API is served over TLS, in which case only the connection is
+    ///   checked
+    pub fn new(address: String, tls: bool) -> Self {
+        Self { address, tls }
+    }
+
+    fn resolve(&self) -> Result<SocketAddr, String> {
+        let mut address = self
+            .address
+            .to_socket_addrs()
+            .map_err(|err| format!("Unable to resolve {}: {}", self.address, err))?
+            .next()
+            .ok_or_else(|| format!("{} did not resolve to any address", self.address))?;
+
+        if address.ip().is_unspecified() {
+            address.set_ip(match address.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            });
+        }
+
+        Ok(address)
+    }
+
+    fn request(&self, address: &SocketAddr) -> Result<(), String> {
+        let mut stream = TcpStream::connect_timeout(address, REST_API_TIMEOUT)
+            .map_err(|err| format!("Unable to connect to {}: {}", address, err))?;
+
+        if self.tls {
+            return Ok(());
+        }
+
+        stream
+            .set_read_timeout(Some(REST_API_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(REST_API_TIMEOUT)))
+            .and_then(|_| {
+                write!(
+                    stream,
+                    "GET /health/live HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                    address
+                )
+            })
+            .map_err(|err| format!("Unable to send request to {}: {}", address, err))?;
+
+        let mut response = [0; 5];
+        stream
+            .read_exact(&mut response)
+            .map_err(|err| format!("No response from {}: {}", address, err))?;
+        if &response != b"HTTP/" {
+            return Err(format!("{} did not respond with HTTP", address));
+        }
+
+        Ok(())
+    }
+}
+
+impl HealthCheck for RestApiCheck {
+    fn name(&self) -> &str {
+        "rest_api"
+    }
+
+    fn check(&self) -> CheckOutcome {
+        match self.resolve().and_then(|address| self.request(&address)) {
+            Ok(()) if self.tls => {
+                CheckOutcome::pass(format!("Accepting connections on {}", self.address))
+            }
+            Ok(()) => CheckOutcome::pass(format!("Responding on {}", self.address)),
+            Err(err) => CheckOutcome::fail(err),
+        }
+    }
+}
+
+/// Checks the free space of the volume containing a directory, such as the node's state
+/// directory.
+///
+/// The check warns once the percentage of the volume in use reaches a threshold, and fails once
+/// the volume is full.
+pub struct DiskSpaceCheck {
+    path: PathBuf,
+    threshold_percent: u8,
+}
+
+impl DiskSpaceCheck {
+    /// Creates a new disk space check.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A directory on the volume to check
+    /// * `threshold_percent` - The percentage of the volume that may be in use before the check
+    ///   warns
+    pub fn new<P: Into<PathBuf>>(path: P, threshold_percent: u8) -> Self {
+        Self {
+            path: path.into(),
+            threshold_percent,
+        }
+    }
+}
+
+impl HealthCheck for DiskSpaceCheck {
+    fn name(&self) -> &str {
+        "disk_space"
+    }
+
+    fn check(&self) -> CheckOutcome {
+        let (size, available) = match volume_stats(&self.path) {
+            Ok(stats) => stats,
+            Err(err) => return CheckOutcome::warn(err),
+        };
+
+        let used_percent = used_percent(size, available);
+        let details = format!(
+            "{}% of the volume containing {} is in use ({} bytes available)",
+            used_percent,
+            self.path.display(),
+            available
+        );
+        if used_percent >= FULL_PERCENT {
+            CheckOutcome::fail(details)
+        } else if used_percent >= self.threshold_percent {
+            CheckOutcome::warn(details)
+        } else {
+            CheckOutcome::pass(details)
+        }
+    }
+}
+
+fn used_percent(size: u64, available: u64) -> u8 {
+    if size == 0 {
+        return 0;
+    }
+    let used = size.saturating_sub(available);
+    ((used as u128 * 100) / size as u128) as u8
+}
+
+/// Returns the total and available bytes of the volume containing `path`.
+#[cfg(unix)]
+fn volume_stats(path: &std::path::Path) -> Result<(u64, u64), String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| format!("Invalid path: {}", path.display()))?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return Err(format!(
+            "Unable to read the volume containing {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    let block_size = stats.f_frsize as u64;
+    Ok((
+        stats.f_blocks as u64 * block_size,
+        stats.f_bavail as u64 * block_size,
+    ))
+}
+
+#[cfg(not(unix))]
+fn volume_stats(_path: &std::path::Path) -> Result<(u64, u64), String> {
+    Err("Disk space checks are not supported on this platform".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+    use std::thread;
+
+    use crate::health::CheckStatus;
+
+    /// Verify that the used percentage of a volume is rounded down, and that an empty volume is
+    /// unused.
+    #[test]
+    fn test_used_percent() {
+        assert_eq!(used_percent(0, 0), 0);
+        assert_eq!(used_percent(1000, 1000), 0);
+        assert_eq!(used_percent(1000, 15), 98);
+        assert_eq!(used_percent(1000, 0), 100);
+    }
+
+    /// Verify that the REST API check passes against a server that responds with HTTP, and fails
+    /// once nothing is listening.
+    #[test]
+    fn test_rest_api_check() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let address = listener.local_addr().expect("Unable to get address");
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("Unable to accept connection");
+            let mut request = [0; 16];
+            let _ = stream.read(&mut request);
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .expect("Unable to write response");
+        });
+
+        let check = RestApiCheck::new(format!("0.0.0.0:{}", address.port()), false);
+        assert_eq!(check.check().status(), CheckStatus::Pass);
+        server.join().expect("Server thread panicked");
+
+        assert_eq!(check.check().status(), CheckStatus::Fail);
+    }
+}
@@ -0,0 +1,434 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Periodic checks of a node's health.
+//!
+//! A [`HealthMonitor`] runs a set of [`HealthCheck`]s on an interval and keeps the results of the
+//! most recent run as a [`HealthReport`]. The report is read through a [`HealthReader`], which
+//! also determines whether the node is live (the checks are still being run) and ready (no check
+//! is failing), as required by liveness and readiness probes.
+
+mod checks;
+
+use std::fmt;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+pub use checks::{DiskSpaceCheck, PeerCheck, RestApiCheck, StoreCheck};
+
+/// The default interval between runs of a monitor's checks.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The default percentage of a volume that may be in use before the disk space check warns.
+pub const DEFAULT_DISK_SPACE_THRESHOLD: u8 = 90;
+
+// How many check intervals may pass without a completed run before the node is no longer live
+const STALE_INTERVALS: u32 = 3;
+
+/// The status of a health check, ordered from best to worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    /// The check found no problems
+    Pass,
+    /// The check found a problem that does not prevent the node from serving requests
+    Warn,
+    /// The check found a problem that prevents the node from serving requests
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => f.write_str("pass"),
+            CheckStatus::Warn => f.write_str("warn"),
+            CheckStatus::Fail => f.write_str("fail"),
+        }
+    }
+}
+
+/// What a health check found, with a human-readable description of the details.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckOutcome {
+    status: CheckStatus,
+    details: String,
+}
+
+impl CheckOutcome {
+    pub fn pass<S: Into<String>>(details: S) -> Self {
+        Self {
+            status: CheckStatus::Pass,
+            details: details.into(),
+        }
+    }
+
+    pub fn warn<S: Into<String>>(details: S) -> Self {
+        Self {
+            status: CheckStatus::Warn,
+            details: details.into(),
+        }
+    }
+
+    pub fn fail<S: Into<String>>(details: S) -> Self {
+        Self {
+            status: CheckStatus::Fail,
+            details: details.into(),
+        }
+    }
+
+    pub fn status(&self) -> CheckStatus {
+        self.status
+    }
+
+    pub fn details(&self) -> &str {
+        &self.details
+    }
+}
+
+/// A check of one aspect of a node's health.
+pub trait HealthCheck: Send {
+    /// Returns the name of the check, which is unique among the checks of a monitor.
+    fn name(&self) -> &str;
+
+    /// Runs the check.
+    fn check(&self) -> CheckOutcome;
+}
+
+/// The result of running a single health check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    name: String,
+    outcome: CheckOutcome,
+    duration: Duration,
+}
+
+impl CheckResult {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn status(&self) -> CheckStatus {
+        self.outcome.status
+    }
+
+    pub fn details(&self) -> &str {
+        &self.outcome.details
+    }
+
+    /// Returns how long the check took to run.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// The results of one run of every check of a [`HealthMonitor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthReport {
+    checks: Vec<CheckResult>,
+    completed_at: SystemTime,
+}
+
+impl HealthReport {
+    pub fn checks(&self) -> &[CheckResult] {
+        &self.checks
+    }
+
+    pub fn completed_at(&self) -> SystemTime {
+        self.completed_at
+    }
+
+    /// Returns the worst status of the report's checks; a report without any checks passes.
+    pub fn status(&self) -> CheckStatus {
+        self.checks
+            .iter()
+            .map(CheckResult::status)
+            .max()
+            .unwrap_or(CheckStatus::Pass)
+    }
+}
+
+struct MonitorState {
+    report: Option<HealthReport>,
+    // When the monitor started, or last completed a run of its checks
+    last_progress: Instant,
+}
+
+/// Reads the most recent report of a [`HealthMonitor`].
+#[derive(Clone)]
+pub struct HealthReader {
+    state: Arc<RwLock<MonitorState>>,
+    stale_after: Duration,
+}
+
+impl HealthReader {
+    /// Returns the most recent report, or `None` if the checks have not completed yet.
+    pub fn report(&self) -> Option<HealthReport> {
+        match self.state.read() {
+            Ok(state) => state.report.clone(),
+            Err(_) => {
+                error!("Health monitor state lock was poisoned");
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if the monitor is still completing runs of its checks.
+    ///
+    /// A check that never returns, such as one waiting on a deadlocked component, stops the
+    /// monitor from completing runs; the node is no longer live once several check intervals have
+    /// passed without a completed run.
+    pub fn is_live(&self) -> bool {
+        match self.state.read() {
+            Ok(state) => state.last_progress.elapsed() <= self.stale_after,
+            Err(_) => false,
+        }
+    }
+
+    /// Returns `true` if the node is live and none of the most recent checks failed.
+    ///
+    /// The node is not ready until the checks have completed once.
+    pub fn is_ready(&self) -> bool {
+        self.is_live()
+            && self
+                .report()
+                .map(|report| report.status() != CheckStatus::Fail)
+                .unwrap_or(false)
+    }
+}
+
+/// Periodically runs a set of health checks, keeping the results of the most recent run.
+pub struct HealthMonitor {
+    reader: HealthReader,
+    interval: Duration,
+    pending: Option<(Vec<Box<dyn HealthCheck>>, Receiver<()>)>,
+    sender: Sender<()>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl HealthMonitor {
+    /// Creates a new health monitor. The checks are not run until the monitor is started, so
+    /// that readers can be handed out before the components being checked are running.
+    ///
+    /// # Arguments
+    ///
+    /// * `checks` - The checks to run
+    /// * `interval` - How long to wait between runs of the checks
+    pub fn new(checks: Vec<Box<dyn HealthCheck>>, interval: Duration) -> Self {
+        let reader = HealthReader {
+            state: Arc::new(RwLock::new(MonitorState {
+                report: None,
+                last_progress: Instant::now(),
+            })),
+            stale_after: interval * STALE_INTERVALS,
+        };
+        let (sender, receiver) = channel();
+
+        Self {
+            reader,
+            interval,
+            pending: Some((checks, receiver)),
+            sender,
+            join_handle: None,
+        }
+    }
+
+    /// Returns a reader for the monitor's most recent report.
+    pub fn reader(&self) -> HealthReader {
+        self.reader.clone()
+    }
+
+    /// Starts the monitor. The checks are run immediately, and then once per interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the monitor was already started, or if its thread cannot
+    /// be started.
+    pub fn start(&mut self) -> Result<(), InternalError> {
+        let (checks, receiver) = self.pending.take().ok_or_else(|| {
+            InternalError::with_message("Health monitor was already started".into())
+        })?;
+        let state = self.reader.state.clone();
+        let interval = self.interval;
+
+        if let Ok(mut state) = state.write() {
+            state.last_progress = Instant::now();
+        }
+
+        let join_handle = thread::Builder::new()
+            .name("HealthMonitor".into())
+            .spawn(move || loop {
+                let report = run_checks(&checks);
+                for result in report.checks() {
+                    if result.status() != CheckStatus::Pass {
+                        warn!(
+                            "Health check {} reported {}: {}",
+                            result.name(),
+                            result.status(),
+                            result.details()
+                        );
+                    }
+                }
+
+                match state.write() {
+                    Ok(mut state) => {
+                        state.report = Some(report);
+                        state.last_progress = Instant::now();
+                    }
+                    Err(_) => {
+                        error!("Health monitor state lock was poisoned");
+                        break;
+                    }
+                }
+
+                match receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        self.join_handle = Some(join_handle);
+
+        Ok(())
+    }
+}
+
+impl ShutdownHandle for HealthMonitor {
+    fn signal_shutdown(&mut self) {
+        if self.join_handle.is_some() && self.sender.send(()).is_err() {
+            warn!("Health monitor is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        match self.join_handle {
+            Some(join_handle) => join_handle.join().map_err(|err| {
+                InternalError::with_message(format!(
+                    "Health monitor thread did not shutdown correctly: {:?}",
+                    err
+                ))
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn run_checks(checks: &[Box<dyn HealthCheck>]) -> HealthReport {
+    let checks = checks
+        .iter()
+        .map(|check| {
+            let start = Instant::now();
+            let outcome = check.check();
+            CheckResult {
+                name: check.name().to_string(),
+                outcome,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+
+    HealthReport {
+        checks,
+        completed_at: SystemTime::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticCheck(&'static str, CheckOutcome);
+
+    impl HealthCheck for StaticCheck {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn check(&self) -> CheckOutcome {
+            self.1.clone()
+        }
+    }
+
+    /// Verify that a report's status is the worst status of its checks.
+    #[test]
+    fn test_report_status() {
+        let report = run_checks(&[]);
+        assert_eq!(report.status(), CheckStatus::Pass);
+
+        let report = run_checks(&[
+            Box::new(StaticCheck("a", CheckOutcome::pass("ok"))),
+            Box::new(StaticCheck("b", CheckOutcome::warn("slow"))),
+        ]);
+        assert_eq!(report.status(), CheckStatus::Warn);
+
+        let report = run_checks(&[
+            Box::new(StaticCheck("a", CheckOutcome::fail("down"))),
+            Box::new(StaticCheck("b", CheckOutcome::warn("slow"))),
+        ]);
+        assert_eq!(report.status(), CheckStatus::Fail);
+        assert_eq!(report.checks()[0].name(), "a");
+        assert_eq!(report.checks()[0].details(), "down");
+    }
+
+    /// Verify that the node is not ready until the monitor has started and run its checks, and
+    /// that it is only ready while no check is failing.
+    #[test]
+    fn test_monitor_readiness() {
+        let mut monitor = HealthMonitor::new(
+            vec![Box::new(StaticCheck("a", CheckOutcome::warn("slow")))],
+            Duration::from_secs(60),
+        );
+        let reader = monitor.reader();
+        assert!(reader.is_live());
+        assert!(!reader.is_ready());
+        monitor.start().expect("Unable to start monitor");
+        wait_for_report(&reader);
+        assert!(reader.is_live());
+        assert!(reader.is_ready());
+        monitor.signal_shutdown();
+        monitor
+            .wait_for_shutdown()
+            .expect("Unable to shutdown monitor");
+
+        let mut monitor = HealthMonitor::new(
+            vec![
+                Box::new(StaticCheck("a", CheckOutcome::pass("ok"))),
+                Box::new(StaticCheck("b", CheckOutcome::fail("down"))),
+            ],
+            Duration::from_secs(60),
+        );
+        let reader = monitor.reader();
+        monitor.start().expect("Unable to start monitor");
+        wait_for_report(&reader);
+        assert!(reader.is_live());
+        assert!(!reader.is_ready());
+        monitor.signal_shutdown();
+        monitor
+            .wait_for_shutdown()
+            .expect("Unable to shutdown monitor");
+    }
+
+    fn wait_for_report(reader: &HealthReader) {
+        let start = Instant::now();
+        while reader.report().is_none() {
+            if start.elapsed() > Duration::from_secs(5) {
+                panic!("Monitor did not complete its checks");
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
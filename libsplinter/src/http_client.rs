@@ -0,0 +1,39 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Construction of the blocking `reqwest` client used by the REST API clients.
+
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+
+/// How long an idle connection is kept open for reuse by a later request
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// The interval between TCP keep-alive probes on open connections
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+/// Builds a blocking HTTP client that keeps idle connections open, so that consecutive requests
+/// to the same server reuse a connection. HTTPS connections use HTTP/2 when the server supports
+/// it.
+///
+/// # Panics
+///
+/// Panics if the TLS backend cannot be initialized, as `Client::new` does.
+pub(crate) fn build_client() -> Client {
+    Client::builder()
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .build()
+        .expect("Unable to initialize the TLS backend of the HTTP client")
+}
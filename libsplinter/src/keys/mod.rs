@@ -18,6 +18,8 @@
 //! role-based access system.  The underlying implementation determines how those values are set
 //! and modified.
 
+#[cfg(feature = "key-pem")]
+pub mod encoding;
 mod error;
 pub mod insecure;
 
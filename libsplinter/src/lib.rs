@@ -32,6 +32,8 @@ extern crate serde_json;
         feature = "biome-credentials",
         feature = "biome-key-management",
         feature = "biome-profile",
+        feature = "circuit-access-log",
+        feature = "network-audit",
         feature = "node-id-store",
         feature = "oauth",
         feature = "registry",
@@ -52,34 +54,25 @@ pub mod tap;
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! rwlock_read_unwrap {
+macro_rules! rwlock_read_recover {
     ($lock:expr) => {
-        match $lock.read() {
-            Ok(d) => d,
-            Err(e) => panic!("RwLock error: {:?}", e),
-        }
+        $crate::threading::lock::read_or_recover(&$lock, stringify!($lock))
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! rwlock_write_unwrap {
+macro_rules! rwlock_write_recover {
     ($lock:expr) => {
-        match $lock.write() {
-            Ok(d) => d,
-            Err(e) => panic!("RwLock error: {:?}", e),
-        }
+        $crate::threading::lock::write_or_recover(&$lock, stringify!($lock))
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! mutex_lock_unwrap {
+macro_rules! mutex_lock_recover {
     ($lock:expr) => {
-        match $lock.lock() {
-            Ok(guard) => guard,
-            Err(e) => panic!("Mutex error: {:?}", e),
-        }
+        $crate::threading::lock::lock_or_recover(&$lock, stringify!($lock))
     };
 }
 
@@ -95,7 +88,15 @@ pub mod consensus;
 pub mod error;
 #[cfg(feature = "events")]
 pub mod events;
+#[cfg(feature = "health-service")]
+pub mod health;
 mod hex;
+#[cfg(any(
+    feature = "biome-client-reqwest",
+    feature = "client-reqwest",
+    feature = "registry-client-reqwest"
+))]
+mod http_client;
 pub mod keys;
 pub mod mesh;
 pub mod migrations;
@@ -124,4 +125,6 @@ pub mod service;
 #[cfg(feature = "store")]
 pub mod store;
 pub mod threading;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 pub mod transport;
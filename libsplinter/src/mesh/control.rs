@@ -32,10 +32,16 @@ impl Control {
         Control { tx }
     }
 
-    pub fn add(&self, connection: Box<dyn Connection>) -> Result<Outgoing, AddError> {
+    /// Add the connection to the backend. The label identifies the connection in metrics.
+    pub fn add(
+        &self,
+        connection: Box<dyn Connection>,
+        label: String,
+    ) -> Result<Outgoing, AddError> {
         let (response_tx, response_rx) = crossbeam_channel::bounded(1);
         self.tx.send(ControlRequest::Add(AddRequest {
             connection,
+            label,
             response_tx,
         }))?;
         match response_rx.recv() {
@@ -71,6 +77,7 @@ pub(super) enum ControlRequest {
 
 pub(super) struct AddRequest {
     pub connection: Box<dyn Connection>,
+    pub label: String,
     pub response_tx: crossbeam_channel::Sender<AddResponse>,
 }
 
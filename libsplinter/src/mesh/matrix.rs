@@ -17,10 +17,11 @@ use crate::transport::matrix::{
     ConnectionMatrixAddError, ConnectionMatrixEnvelope, ConnectionMatrixLifeCycle,
     ConnectionMatrixReceiver, ConnectionMatrixRecvError, ConnectionMatrixRecvTimeoutError,
     ConnectionMatrixRemoveError, ConnectionMatrixSendError, ConnectionMatrixSender,
+    ConnectionMatrixTrySendError,
 };
 use crate::transport::Connection;
 
-use super::{Mesh, RecvError, RecvTimeoutError};
+use super::{Mesh, RecvError, RecvTimeoutError, SendError};
 
 #[derive(Clone)]
 /// Mesh specific implementation of ConnectionMatrixLifeCycle
@@ -80,6 +81,39 @@ impl ConnectionMatrixSender for MeshMatrixSender {
             )
         })
     }
+
+    fn try_send(&self, id: String, message: Vec<u8>) -> Result<(), ConnectionMatrixTrySendError> {
+        let envelope = ConnectionMatrixEnvelope::new(id, message);
+        self.mesh
+            .try_send(envelope)
+            .map_err(ConnectionMatrixTrySendError::from)
+    }
+
+    fn send_timeout(
+        &self,
+        id: String,
+        message: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<(), ConnectionMatrixTrySendError> {
+        let envelope = ConnectionMatrixEnvelope::new(id, message);
+        self.mesh
+            .send_timeout(envelope, timeout)
+            .map_err(ConnectionMatrixTrySendError::from)
+    }
+}
+
+impl From<SendError> for ConnectionMatrixTrySendError {
+    fn from(err: SendError) -> Self {
+        match err {
+            SendError::Full(envelope) => {
+                ConnectionMatrixTrySendError::Full(envelope.take_payload())
+            }
+            err => ConnectionMatrixTrySendError::Send(ConnectionMatrixSendError::new(
+                "Unable to send message to connection".to_string(),
+                Some(Box::new(err)),
+            )),
+        }
+    }
 }
 
 #[derive(Clone)]
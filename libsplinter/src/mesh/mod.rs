@@ -48,6 +48,18 @@
 //!    be a more efficient implementation.
 //! 3. Backpressure should be built in. This means all queues should be bounded so that a
 //!    backpressure error can be returned when the queue is full.
+//!
+//! When the `tap` feature is enabled, the mesh reports the following metrics. All but
+//! `splinter.mesh.incoming_queue_depth` are labeled with the `connection` they apply to.
+//!
+//! - `splinter.mesh.outbound_queue_depth`: messages waiting in a connection's outgoing queue
+//! - `splinter.mesh.incoming_queue_depth`: messages waiting to be received from the mesh
+//! - `splinter.mesh.sent_messages`, `splinter.mesh.sent_bytes`: data written to a connection
+//! - `splinter.mesh.received_messages`, `splinter.mesh.received_bytes`: data read from a
+//!   connection
+//! - `splinter.mesh.blocked_sends`: sends delayed because the connection was not writable
+//! - `splinter.mesh.dropped_messages`: messages rejected or dropped because a queue was full,
+//!   labeled with the `queue` (`outgoing` or `incoming`)
 
 mod control;
 mod incoming;
@@ -55,12 +67,15 @@ mod matrix;
 mod outgoing;
 mod pool;
 mod reactor;
+#[cfg(feature = "mesh-bandwidth-throttle")]
+mod throttle;
 
 use std::collections::HashMap;
 use std::error::Error;
 use std::io;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::collections::BiHashMap;
 use crate::error::InternalError;
@@ -70,10 +85,16 @@ use crate::mesh::incoming::Incoming;
 pub use crate::mesh::matrix::{MeshLifeCycle, MeshMatrixReceiver, MeshMatrixSender};
 use crate::mesh::outgoing::Outgoing;
 use crate::mesh::reactor::Reactor;
+#[cfg(feature = "mesh-bandwidth-throttle")]
+pub use crate::mesh::throttle::{BandwidthLimits, BandwidthThrottle, ThrottleStats};
 use crate::threading::lifecycle::ShutdownHandle;
+use crate::threading::lock::{read_or_recover, write_or_recover};
 pub use crate::transport::matrix::ConnectionMatrixEnvelope as Envelope;
 use crate::transport::Connection;
 
+// How long to wait between attempts when a send is waiting for room in a full queue
+const SEND_RETRY_INTERVAL: Duration = Duration::from_millis(1);
+
 /// Wrapper around payload to include connection id
 #[derive(Debug, PartialEq)]
 pub(in crate::mesh) enum InternalEnvelope {
@@ -101,6 +122,8 @@ pub struct Mesh {
     state: Arc<RwLock<MeshState>>,
     incoming: Incoming,
     ctrl: Control,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    throttle: BandwidthThrottle,
 }
 
 impl Mesh {
@@ -112,6 +135,41 @@ impl Mesh {
             state: Arc::new(RwLock::new(MeshState::new())),
             incoming,
             ctrl,
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            throttle: BandwidthThrottle::default(),
+        }
+    }
+
+    /// Returns the outbound bandwidth throttle shared by this mesh and all of its clones.
+    ///
+    /// The throttle is unlimited by default; its limits may be changed at any time.
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_throttle(&self) -> BandwidthThrottle {
+        self.throttle.clone()
+    }
+
+    /// Blocks until the bandwidth limits allow `len` bytes to be sent on the given connection.
+    ///
+    /// Returns `false`, without reserving any bandwidth, if the bandwidth would not be available
+    /// before the deadline.
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    fn wait_for_bandwidth(&self, id: &str, len: usize, deadline: Option<Instant>) -> bool {
+        let mut delay = match self.throttle.reserve(id, len) {
+            Some(delay) => delay,
+            None => return true,
+        };
+        self.throttle.record_throttled(id, len);
+        loop {
+            if let Some(deadline) = deadline {
+                if Instant::now() + delay > deadline {
+                    return false;
+                }
+            }
+            thread::sleep(delay);
+            match self.throttle.reserve(id, len) {
+                Some(next_delay) => delay = next_delay,
+                None => return true,
+            }
         }
     }
 
@@ -122,8 +180,8 @@ impl Mesh {
         connection: Box<dyn Connection>,
         unique_id: String,
     ) -> Result<usize, AddError> {
-        let mut state = self.state.write().map_err(|_| AddError::PoisonedLock)?;
-        let outgoing = self.ctrl.add(connection)?;
+        let mut state = write_or_recover(&self.state, "mesh state");
+        let outgoing = self.ctrl.add(connection, unique_id.clone())?;
         let mesh_id = outgoing.id();
 
         state.outgoings.insert(mesh_id, outgoing);
@@ -134,7 +192,7 @@ impl Mesh {
 
     /// Remove an existing connection from the mesh and return it.
     pub fn remove(&self, unique_id: &str) -> Result<Box<dyn Connection>, RemoveError> {
-        let mut state = self.state.write().map_err(|_| RemoveError::PoisonedLock)?;
+        let mut state = write_or_recover(&self.state, "mesh state");
         if let Some((_, mesh_id)) = state.unique_ids.remove_by_key(unique_id) {
             let connection = self.ctrl.remove(mesh_id)?;
             // The outgoing channel needs to be removed after the control request completes, or else
@@ -142,6 +200,8 @@ impl Mesh {
             // automatically, causing the control request to fail with NotFound.
 
             state.outgoings.remove(&mesh_id);
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            self.throttle.remove_connection(unique_id);
             Ok(connection)
         } else {
             Err(RemoveError::NotFound)
@@ -150,26 +210,105 @@ impl Mesh {
 
     /// Send the envelope on the mesh.
     ///
-    /// This is a convenience function and is equivalent to
-    /// `mesh.outgoing(envelope.id()).send(Vec::from(envelope))`.
+    /// This is equivalent to `try_send`, except that when the mesh's bandwidth limits have been
+    /// reached it blocks until bandwidth is available instead of returning `SendError::Full`.
     pub fn send(&self, envelope: Envelope) -> Result<(), SendError> {
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        {
+            let id = envelope.id().to_string();
+            let outgoing = self.outgoing(&id)?;
+            self.wait_for_bandwidth(&id, envelope.payload().len(), None);
+            queue_outgoing(&outgoing, id, Vec::from(envelope))
+        }
+        #[cfg(not(feature = "mesh-bandwidth-throttle"))]
+        self.try_send(envelope)
+    }
+
+    /// Attempt to send the envelope on the mesh without blocking.
+    ///
+    /// Returns `SendError::Full`, containing the envelope, if the connection's outgoing queue is
+    /// at capacity or if sending the envelope would exceed the mesh's bandwidth limits.
+    pub fn try_send(&self, envelope: Envelope) -> Result<(), SendError> {
         let id = envelope.id().to_string();
-        let outgoing = {
-            let state = self.state.read().map_err(|_| SendError::PoisonedLock)?;
-            let mesh_id = state
-                .unique_ids
-                .get_by_key(&id)
-                .ok_or(SendError::NotFound)?;
-            state
-                .outgoings
-                .get(mesh_id)
-                .cloned()
-                .ok_or(SendError::NotFound)?
-        };
+        let outgoing = self.outgoing(&id)?;
+
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        if self
+            .throttle
+            .reserve(&id, envelope.payload().len())
+            .is_some()
+        {
+            self.throttle
+                .record_throttled(&id, envelope.payload().len());
+            return Err(SendError::Full(envelope));
+        }
+
+        queue_outgoing(&outgoing, id, Vec::from(envelope))
+    }
+
+    /// Send the envelope on the mesh, waiting up to `timeout` for room in the connection's
+    /// outgoing queue.
+    ///
+    /// Returns `SendError::Full`, containing the envelope, if the queue is still at capacity when
+    /// the timeout expires. If the mesh's bandwidth limits have been reached, the send waits for
+    /// bandwidth to become available, returning `SendError::Full` if that would exceed the
+    /// timeout.
+    pub fn send_timeout(&self, envelope: Envelope, timeout: Duration) -> Result<(), SendError> {
+        let deadline = Instant::now() + timeout;
+        let id = envelope.id().to_string();
+        let outgoing = self.outgoing(&id)?;
+
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        if !self.wait_for_bandwidth(&id, envelope.payload().len(), Some(deadline)) {
+            return Err(SendError::Full(envelope));
+        }
+
+        let mut payload = Vec::from(envelope);
+        loop {
+            match outgoing.send(payload) {
+                Ok(()) => {
+                    gauge!(
+                        "splinter.mesh.outbound_queue_depth",
+                        outgoing.queue_depth() as f64,
+                        "connection" => id
+                    );
+                    return Ok(());
+                }
+                Err(outgoing::SendError::Full(returned)) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        counter!(
+                            "splinter.mesh.dropped_messages",
+                            1,
+                            "connection" => id.clone(),
+                            "queue" => "outgoing"
+                        );
+                        return Err(SendError::Full(Envelope::new(id, returned)));
+                    }
+                    payload = returned;
+                    thread::sleep(SEND_RETRY_INTERVAL.min(deadline - now));
+                }
+                Err(err) => return Err(SendError::from_outgoing_send_error(err, id)),
+            }
+        }
+    }
 
-        outgoing
-            .send(Vec::from(envelope))
-            .map_err(|err| SendError::from_outgoing_send_error(err, id))
+    /// Returns the number of messages waiting in the outgoing queue of the given connection.
+    pub fn queue_depth(&self, unique_id: &str) -> Result<usize, SendError> {
+        Ok(self.outgoing(unique_id)?.queue_depth())
+    }
+
+    fn outgoing(&self, unique_id: &str) -> Result<Outgoing, SendError> {
+        let state = read_or_recover(&self.state, "mesh state");
+        let mesh_id = state
+            .unique_ids
+            .get_by_key(unique_id)
+            .ok_or(SendError::NotFound)?;
+        state
+            .outgoings
+            .get(mesh_id)
+            .cloned()
+            .ok_or(SendError::NotFound)
     }
 
     /// Receive a new envelope from the mesh.
@@ -181,10 +320,7 @@ impl Mesh {
                 id: connection_id,
                 payload,
             } => {
-                let id = self
-                    .state
-                    .read()
-                    .map_err(|_| RecvError::PoisonedLock)?
+                let id = read_or_recover(&self.state, "mesh state")
                     .unique_ids
                     .get_by_value(&connection_id)
                     .cloned()
@@ -207,10 +343,7 @@ impl Mesh {
                 id: connection_id,
                 payload,
             } => {
-                let id = self
-                    .state
-                    .read()
-                    .map_err(|_| RecvTimeoutError::PoisonedLock)?
+                let id = read_or_recover(&self.state, "mesh state")
                     .unique_ids
                     .get_by_value(&connection_id)
                     .cloned()
@@ -270,6 +403,32 @@ impl ShutdownHandle for Mesh {
     }
 }
 
+/// Queue the payload on the connection's outgoing queue, recording the resulting queue depth or,
+/// if the queue is full, the rejected message.
+fn queue_outgoing(outgoing: &Outgoing, id: String, payload: Vec<u8>) -> Result<(), SendError> {
+    match outgoing.send(payload) {
+        Ok(()) => {
+            gauge!(
+                "splinter.mesh.outbound_queue_depth",
+                outgoing.queue_depth() as f64,
+                "connection" => id
+            );
+            Ok(())
+        }
+        Err(err) => {
+            if let outgoing::SendError::Full(_) = err {
+                counter!(
+                    "splinter.mesh.dropped_messages",
+                    1,
+                    "connection" => id.clone(),
+                    "queue" => "outgoing"
+                );
+            }
+            Err(SendError::from_outgoing_send_error(err, id))
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum SendError {
     NotFound,
@@ -580,6 +739,48 @@ mod tests {
         test_add_remove_connections(tls, "127.0.0.1:0");
     }
 
+    #[test]
+    // Test that send_timeout delivers a message, and that the queue depth is only reported for
+    // known connections.
+    //
+    // 1. Add both ends of a TCP connection to a Mesh
+    // 2. Send a message with send_timeout and verify that it is received
+    // 3. Verify that the client's queue has been drained
+    // 4. Verify that the queue depth of an unknown connection returns NotFound
+    fn test_send_timeout_and_queue_depth() {
+        let mut transport = TcpTransport::default();
+        let mut listener = assert_ok(transport.listen("127.0.0.1:0"));
+        let endpoint = listener.endpoint();
+
+        let mut mesh = Mesh::new(1, 1);
+
+        let handle = thread::spawn(move || assert_ok(transport.connect(&endpoint)));
+        let server = assert_ok(listener.accept());
+        let client = handle.join().unwrap();
+
+        assert_ok(mesh.add(client, "client".to_string()));
+        assert_ok(mesh.add(server, "server".to_string()));
+
+        assert_ok(mesh.send_timeout(
+            Envelope::new("client".to_string(), b"hello".to_vec()),
+            Duration::from_secs(1),
+        ));
+
+        let envelope = assert_ok(mesh.recv());
+        assert_eq!(b"hello", envelope.payload());
+        assert_eq!("server", envelope.id());
+
+        assert_eq!(0, assert_ok(mesh.queue_depth("client")));
+
+        match mesh.queue_depth("unknown") {
+            Err(SendError::NotFound) => (),
+            res => panic!("Expected Err(SendError::NotFound), got {:?}", res),
+        }
+
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().unwrap();
+    }
+
     #[test]
     // Test that mesh can be shutdown after sending and receiving a message.
     //
@@ -15,6 +15,8 @@
 use mio_extras::channel::{SyncSender, TrySendError};
 
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use super::InternalEnvelope;
 
@@ -23,19 +25,39 @@ use super::InternalEnvelope;
 pub struct Outgoing {
     id: usize,
     tx: SyncSender<InternalEnvelope>,
+    /// The number of messages in the queue that have not yet been taken by the reactor
+    queued: Arc<AtomicUsize>,
 }
 
 impl Outgoing {
-    pub(super) fn new(id: usize, tx: SyncSender<InternalEnvelope>) -> Self {
-        Outgoing { id, tx }
+    pub(super) fn new(
+        id: usize,
+        tx: SyncSender<InternalEnvelope>,
+        queued: Arc<AtomicUsize>,
+    ) -> Self {
+        Outgoing { id, tx, queued }
     }
 
+    /// Queue the payload to be sent without blocking, returning `SendError::Full` if the queue
+    /// is at capacity.
     pub fn send(&self, payload: Vec<u8>) -> Result<(), SendError> {
-        self.tx.try_send(InternalEnvelope::Message {
-            id: self.id,
-            payload,
-        })?;
-        Ok(())
+        // Increment before sending so the reactor never decrements a message that has not been
+        // counted
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        self.tx
+            .try_send(InternalEnvelope::Message {
+                id: self.id,
+                payload,
+            })
+            .map_err(|err| {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+                SendError::from(err)
+            })
+    }
+
+    /// Returns the number of messages waiting in the queue.
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
     }
 
     pub fn id(&self) -> usize {
@@ -20,7 +20,9 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::TryRecvError;
+use std::sync::Arc;
 
 use crate::transport::{Connection, RecvError, SendError};
 
@@ -59,11 +61,13 @@ impl Pool {
     }
 
     /// Add a new connection to the reactor, returning unique ids for the actual connection and the
-    /// outgoing queue
+    /// outgoing queue. The label identifies the connection in metrics.
     pub fn add(
         &mut self,
         connection: Box<dyn Connection>,
         outgoing: mio_channel::Receiver<InternalEnvelope>,
+        queued: Arc<AtomicUsize>,
+        label: String,
     ) -> Result<usize, io::Error> {
         let connection_token = self.next_token();
         let outgoing_token = self.next_token();
@@ -87,7 +91,15 @@ impl Pool {
         self.tokens.insert(outgoing_token, id);
         self.entries.insert(
             id,
-            Entry::new(id, connection, connection_token, outgoing, outgoing_token),
+            Entry::new(
+                id,
+                connection,
+                connection_token,
+                outgoing,
+                outgoing_token,
+                queued,
+                label,
+            ),
         );
 
         Ok(id)
@@ -205,16 +217,18 @@ struct Entry {
     connection_token: Token,
     outgoing: mio_channel::Receiver<InternalEnvelope>,
     outgoing_token: Token,
+    queued: Arc<AtomicUsize>,
     cached: RefCell<Option<Vec<u8>>>,
     write_evented_guard: RefCell<bool>,
+    label: String,
 }
 
 impl fmt::Debug for Entry {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Entry {{ id: {:?}, connection: {:?}, outgoing: {:?}, cached: {:?} }}",
-            self.id, self.connection_token, self.outgoing_token, self.cached,
+            "Entry {{ id: {:?}, label: {:?}, connection: {:?}, outgoing: {:?}, cached: {:?} }}",
+            self.id, self.label, self.connection_token, self.outgoing_token, self.cached,
         )
     }
 }
@@ -226,6 +240,8 @@ impl Entry {
         connection_token: Token,
         outgoing: mio_channel::Receiver<InternalEnvelope>,
         outgoing_token: Token,
+        queued: Arc<AtomicUsize>,
+        label: String,
     ) -> Self {
         Entry {
             id,
@@ -233,8 +249,10 @@ impl Entry {
             connection_token,
             outgoing,
             outgoing_token,
+            queued,
             cached: RefCell::new(None),
             write_evented_guard: RefCell::new(false),
+            label,
         }
     }
 
@@ -288,6 +306,12 @@ impl Entry {
 
         match envelope {
             InternalEnvelope::Message { payload, .. } => {
+                let depth = self.queued.fetch_sub(1, Ordering::SeqCst).saturating_sub(1);
+                gauge!(
+                    "splinter.mesh.outbound_queue_depth",
+                    depth as f64,
+                    "connection" => self.label.clone()
+                );
                 self.try_send_connection_or_cache(payload, poll)
             }
             // won't be sent outgoing
@@ -330,6 +354,13 @@ impl Entry {
 
         match connection.send(&payload) {
             Ok(()) => {
+                counter!("splinter.mesh.sent_messages", 1, "connection" => self.label.clone());
+                counter!(
+                    "splinter.mesh.sent_bytes",
+                    payload.len() as u64,
+                    "connection" => self.label.clone()
+                );
+
                 // Return to readable only.
                 if self.write_evented_guard.replace(false) {
                     poll.reregister(
@@ -343,6 +374,8 @@ impl Entry {
                 Ok(())
             }
             Err(SendError::WouldBlock) => {
+                // The connection's socket is backed up; hold the message until it is writable
+                counter!("splinter.mesh.blocked_sends", 1, "connection" => self.label.clone());
                 self.cached.replace(Some(payload));
                 if !*self.write_evented_guard.borrow() {
                     poll.reregister(
@@ -378,18 +411,40 @@ impl Entry {
             };
             match connection.recv() {
                 Ok(payload) => {
+                    counter!(
+                        "splinter.mesh.received_messages",
+                        1,
+                        "connection" => self.label.clone()
+                    );
+                    counter!(
+                        "splinter.mesh.received_bytes",
+                        payload.len() as u64,
+                        "connection" => self.label.clone()
+                    );
                     match incoming_tx.try_send(InternalEnvelope::Message {
                         id: self.id,
                         payload,
                     }) {
                         Err(TrySendError::Full(_)) => {
                             warn!("Dropped message due to full incoming queue");
+                            counter!(
+                                "splinter.mesh.dropped_messages",
+                                1,
+                                "connection" => self.label.clone(),
+                                "queue" => "incoming"
+                            );
                             Ok(())
                         }
                         Err(TrySendError::Disconnected(_)) => {
                             Err(TryEventError::IncomingDisconnected)
                         }
-                        Ok(()) => Ok(()),
+                        Ok(()) => {
+                            gauge!(
+                                "splinter.mesh.incoming_queue_depth",
+                                incoming_tx.len() as f64
+                            );
+                            Ok(())
+                        }
                     }
                 }
                 Err(RecvError::WouldBlock) => Ok(()),
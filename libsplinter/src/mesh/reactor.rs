@@ -15,7 +15,7 @@
 use mio::{Event, Events, Token};
 use mio_extras::channel as mio_channel;
 
-use std::sync::{mpsc::TryRecvError, Arc, Barrier};
+use std::sync::{atomic::AtomicUsize, mpsc::TryRecvError, Arc, Barrier};
 use std::thread;
 
 use crate::mesh::{
@@ -136,10 +136,10 @@ impl Reactor {
             match self.ctrl_rx.try_recv() {
                 Ok(ControlRequest::Add(AddRequest {
                     connection,
+                    label,
                     response_tx,
-                    ..
                 })) => {
-                    if let Err(err) = response_tx.send(self.add_connection(connection)) {
+                    if let Err(err) = response_tx.send(self.add_connection(connection, label)) {
                         error!("Failed to send back AddResponse: {:?}", err);
                     }
                 }
@@ -162,11 +162,12 @@ impl Reactor {
         }
     }
 
-    fn add_connection(&mut self, connection: Box<dyn Connection>) -> AddResponse {
+    fn add_connection(&mut self, connection: Box<dyn Connection>, label: String) -> AddResponse {
         let (tx, rx) = mio_channel::sync_channel(self.outgoing_capacity);
+        let queued = Arc::new(AtomicUsize::new(0));
 
-        match self.pool.add(connection, rx) {
-            Ok(id) => Ok(Outgoing::new(id, tx)),
+        match self.pool.add(connection, rx, queued.clone(), label) {
+            Ok(id) => Ok(Outgoing::new(id, tx, queued)),
             Err(err) => Err(AddError::Io(err)),
         }
     }
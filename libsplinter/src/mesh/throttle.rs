@@ -0,0 +1,289 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Outbound bandwidth throttling for the mesh.
+//!
+//! The [`BandwidthThrottle`] limits the rate at which bytes are sent, both across all connections
+//! (the global limit) and on each individual connection (the connection limit). Each limit is
+//! enforced with a token bucket that is refilled continuously at the configured rate, in bytes
+//! per second, and holds at most one second's worth of tokens.
+//!
+//! A message may be sent as long as each applicable bucket is not in deficit; sending it may put
+//! the bucket into deficit, which must be paid back before the next message is sent. This allows
+//! messages that are larger than the bucket to be sent while still enforcing the average rate.
+//!
+//! [`BandwidthThrottle`]: struct.BandwidthThrottle.html
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A token bucket, measured in bytes.
+struct TokenBucket {
+    rate: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            tokens: rate as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.rate as f64).min(self.rate as f64);
+        self.last_refill = now;
+    }
+
+    /// Returns how long to wait until the bucket is no longer in deficit.
+    fn delay(&self) -> Duration {
+        if self.tokens >= 0.0 || self.rate == 0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate as f64)
+        }
+    }
+
+    fn take(&mut self, bytes: usize) {
+        self.tokens -= bytes as f64;
+    }
+}
+
+/// The bandwidth limits applied by a `BandwidthThrottle`, in bytes per second.
+///
+/// A limit of `None` means the corresponding traffic is not limited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BandwidthLimits {
+    /// The limit applied to the combined outbound traffic of all connections
+    pub global: Option<u64>,
+    /// The limit applied to the outbound traffic of each connection
+    pub per_connection: Option<u64>,
+}
+
+/// Counts of the messages that were delayed or rejected because a bandwidth limit was reached.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThrottleStats {
+    /// The total number of bytes in messages that were throttled
+    pub throttled_bytes: u64,
+    /// The total number of messages that were throttled
+    pub throttled_messages: u64,
+    /// The number of throttled bytes for each connection, keyed by connection ID
+    pub throttled_bytes_by_connection: HashMap<String, u64>,
+}
+
+struct Inner {
+    limits: BandwidthLimits,
+    global: Option<TokenBucket>,
+    connections: HashMap<String, TokenBucket>,
+    stats: ThrottleStats,
+}
+
+/// A shared, adjustable outbound bandwidth throttle.
+///
+/// Clones of a `BandwidthThrottle` share the same limits, buckets, and statistics, so limits may
+/// be adjusted at runtime (for example, from the REST API) through any clone.
+#[derive(Clone)]
+pub struct BandwidthThrottle {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for BandwidthThrottle {
+    fn default() -> Self {
+        Self::new(BandwidthLimits::default())
+    }
+}
+
+impl BandwidthThrottle {
+    /// Creates a new throttle with the given limits.
+    pub fn new(limits: BandwidthLimits) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                limits,
+                global: limits.global.map(TokenBucket::new),
+                connections: HashMap::new(),
+                stats: ThrottleStats::default(),
+            })),
+        }
+    }
+
+    /// Returns the current limits.
+    pub fn limits(&self) -> BandwidthLimits {
+        self.lock().limits
+    }
+
+    /// Replaces the current limits. Buckets are reset so that the new limits apply immediately.
+    pub fn set_limits(&self, limits: BandwidthLimits) {
+        let mut inner = self.lock();
+        inner.limits = limits;
+        inner.global = limits.global.map(TokenBucket::new);
+        inner.connections.clear();
+    }
+
+    /// Returns the throttling statistics collected since the throttle was created.
+    pub fn stats(&self) -> ThrottleStats {
+        self.lock().stats.clone()
+    }
+
+    /// Attempts to reserve bandwidth for sending `bytes` bytes on the given connection.
+    ///
+    /// Returns `None` if the message may be sent immediately; the bytes are deducted from the
+    /// applicable buckets. Otherwise, returns how long the caller should wait before trying
+    /// again and leaves the buckets unchanged.
+    pub fn reserve(&self, connection_id: &str, bytes: usize) -> Option<Duration> {
+        let mut inner = self.lock();
+        let now = Instant::now();
+
+        let per_connection = inner.limits.per_connection;
+        let Inner {
+            global,
+            connections,
+            ..
+        } = &mut *inner;
+
+        let mut delay = Duration::from_secs(0);
+        if let Some(bucket) = global.as_mut() {
+            bucket.refill(now);
+            delay = delay.max(bucket.delay());
+        }
+        let connection_bucket = per_connection.map(|rate| {
+            connections
+                .entry(connection_id.to_string())
+                .or_insert_with(|| TokenBucket::new(rate))
+        });
+        if let Some(bucket) = connection_bucket {
+            bucket.refill(now);
+            delay = delay.max(bucket.delay());
+            if delay == Duration::from_secs(0) {
+                bucket.take(bytes);
+            }
+        }
+
+        if delay > Duration::from_secs(0) {
+            return Some(delay);
+        }
+
+        if let Some(bucket) = global.as_mut() {
+            bucket.take(bytes);
+        }
+
+        None
+    }
+
+    /// Records that a message of `bytes` bytes on the given connection was throttled.
+    pub fn record_throttled(&self, connection_id: &str, bytes: usize) {
+        let mut inner = self.lock();
+        inner.stats.throttled_bytes += bytes as u64;
+        inner.stats.throttled_messages += 1;
+        *inner
+            .stats
+            .throttled_bytes_by_connection
+            .entry(connection_id.to_string())
+            .or_insert(0) += bytes as u64;
+
+        counter!("splinter.mesh.throttled_bytes", bytes as u64);
+        counter!("splinter.mesh.throttled_messages", 1);
+    }
+
+    /// Discards the bucket for a connection that has been removed.
+    pub fn remove_connection(&self, connection_id: &str) {
+        self.lock().connections.remove(connection_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<Inner> {
+        // The inner state is always left consistent, so a poisoned lock can be recovered
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that an unlimited throttle never delays sends.
+    #[test]
+    fn unlimited() {
+        let throttle = BandwidthThrottle::default();
+
+        for _ in 0..100 {
+            assert_eq!(throttle.reserve("conn", 1_000_000), None);
+        }
+    }
+
+    /// Verifies that the global limit applies across connections, that a message larger than
+    /// the bucket is allowed once, and that the next send is delayed until the deficit is paid.
+    #[test]
+    fn global_limit() {
+        let throttle = BandwidthThrottle::new(BandwidthLimits {
+            global: Some(1000),
+            per_connection: None,
+        });
+
+        assert_eq!(throttle.reserve("conn1", 1500), None);
+        let delay = throttle
+            .reserve("conn2", 10)
+            .expect("Send should have been delayed");
+        assert!(delay > Duration::from_millis(400));
+        assert!(delay <= Duration::from_millis(500));
+    }
+
+    /// Verifies that the per-connection limit only applies to the connection that exceeded it.
+    #[test]
+    fn per_connection_limit() {
+        let throttle = BandwidthThrottle::new(BandwidthLimits {
+            global: None,
+            per_connection: Some(1000),
+        });
+
+        assert_eq!(throttle.reserve("conn1", 1001), None);
+        assert!(throttle.reserve("conn1", 10).is_some());
+        assert_eq!(throttle.reserve("conn2", 10), None);
+
+        // Removing a connection discards its deficit
+        throttle.remove_connection("conn1");
+        assert_eq!(throttle.reserve("conn1", 10), None);
+    }
+
+    /// Verifies that changing the limits takes effect immediately and that throttled messages
+    /// are counted.
+    #[test]
+    fn set_limits_and_stats() {
+        let throttle = BandwidthThrottle::default();
+        let limits = BandwidthLimits {
+            global: Some(100),
+            per_connection: Some(50),
+        };
+        throttle.set_limits(limits);
+        assert_eq!(throttle.limits(), limits);
+
+        assert_eq!(throttle.reserve("conn1", 60), None);
+        assert!(throttle.reserve("conn1", 60).is_some());
+        throttle.record_throttled("conn1", 60);
+
+        let stats = throttle.stats();
+        assert_eq!(stats.throttled_bytes, 60);
+        assert_eq!(stats.throttled_messages, 1);
+        assert_eq!(stats.throttled_bytes_by_connection.get("conn1"), Some(&60));
+
+        throttle.set_limits(BandwidthLimits::default());
+        assert_eq!(throttle.reserve("conn1", 60), None);
+    }
+}
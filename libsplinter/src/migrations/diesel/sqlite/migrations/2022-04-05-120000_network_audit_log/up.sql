@@ -0,0 +1,29 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS network_audit_record (
+    id                        INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp                 BIGINT NOT NULL,
+    endpoint                  TEXT NOT NULL,
+    connection_id             TEXT NOT NULL,
+    identity                  TEXT,
+    outcome                   TEXT NOT NULL
+    CHECK ( outcome IN ('outbound_connected', 'inbound_connected', 'authorization_failed',
+                        'connection_failed', 'reconnection_failed', 'disconnected') ),
+    reason                    TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_network_audit_record_timestamp
+    ON network_audit_record (timestamp);
@@ -0,0 +1,32 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS proposed_node_authorized_key (
+    circuit_id             TEXT NOT NULL,
+    node_id                TEXT NOT NULL,
+    public_key             BYTEA NOT NULL,
+    position               INTEGER NOT NULL,
+    PRIMARY KEY (circuit_id, node_id, public_key),
+    FOREIGN KEY (circuit_id) REFERENCES proposed_circuit(circuit_id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS circuit_member_authorized_key (
+    circuit_id             TEXT NOT NULL,
+    node_id                TEXT NOT NULL,
+    public_key             BYTEA NOT NULL,
+    position               INTEGER NOT NULL,
+    PRIMARY KEY (circuit_id, node_id, public_key),
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+);
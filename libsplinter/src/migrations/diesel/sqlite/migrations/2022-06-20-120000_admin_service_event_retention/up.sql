@@ -0,0 +1,26 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Existing events are treated as having been recorded when the migration ran
+ALTER TABLE admin_service_event
+ADD COLUMN created_at BIGINT NOT NULL DEFAULT 0;
+
+UPDATE admin_service_event SET created_at = CAST(strftime('%s', 'now') AS BIGINT);
+
+-- A single row recording the ID of the newest event removed by the retention policy
+CREATE TABLE IF NOT EXISTS admin_service_event_truncation (
+    id                       SMALLINT PRIMARY KEY,
+    truncated_to             BIGINT NOT NULL
+);
@@ -0,0 +1,21 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Users are soft-deleted by marking them inactive, so their records are kept
+ALTER TABLE user_credentials
+ADD COLUMN active BOOLEAN NOT NULL DEFAULT 1;
+
+ALTER TABLE oauth_users
+ADD COLUMN active BOOLEAN NOT NULL DEFAULT 1;
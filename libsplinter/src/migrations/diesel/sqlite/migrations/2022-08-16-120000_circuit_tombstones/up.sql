@@ -0,0 +1,25 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+
+
+-- Circuits that were purged while tombstones are enabled. The circuit's rows are kept until
+-- the tombstone is removed, which also removes the circuit.
+CREATE TABLE IF NOT EXISTS circuit_tombstone (
+    circuit_id             TEXT PRIMARY KEY,
+    deleted_at             BIGINT NOT NULL,
+    reason                 TEXT NOT NULL,
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+);
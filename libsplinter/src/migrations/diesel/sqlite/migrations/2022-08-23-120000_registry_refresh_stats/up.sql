@@ -0,0 +1,26 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+
+
+CREATE TABLE IF NOT EXISTS registry_refresh_stats (
+    url                 TEXT PRIMARY KEY,
+    last_attempt        BIGINT NOT NULL,
+    last_success        BIGINT,
+    last_duration_ms    BIGINT NOT NULL,
+    success_count       BIGINT NOT NULL,
+    failure_count       BIGINT NOT NULL,
+    last_error          TEXT
+);
@@ -0,0 +1,123 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+
+use crate::error::InternalError;
+use crate::network::connection_manager::{ConnectionManagerNotification, Connector, SubscriberId};
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::{AuditRecord, AuditStore};
+
+/// The log target used when audit records are also written to the log.
+///
+/// A logger for this target may be configured to route audit records to a dedicated appender,
+/// such as a file or syslog.
+pub const AUDIT_LOG_TARGET: &str = "splinter::audit";
+
+enum AuditMessage {
+    Record(AuditRecord),
+    Shutdown,
+}
+
+impl From<ConnectionManagerNotification> for AuditMessage {
+    fn from(notification: ConnectionManagerNotification) -> Self {
+        AuditMessage::Record(AuditRecord::from(notification))
+    }
+}
+
+/// Subscribes to connection manager notifications and writes an [`AuditRecord`] for each to an
+/// [`AuditStore`].
+pub struct AuditLogger {
+    connector: Connector,
+    subscriber_id: SubscriberId,
+    sender: Sender<AuditMessage>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl AuditLogger {
+    /// Starts a new audit logger.
+    ///
+    /// # Arguments
+    ///
+    /// * `connector` - The connection manager connector to receive notifications from
+    /// * `store` - The store to which audit records are written
+    /// * `log_records` - If true, each record is also logged at `info` level to the
+    ///   [`AUDIT_LOG_TARGET`] target
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the logger cannot subscribe to the connection manager or
+    /// its thread cannot be started.
+    pub fn start(
+        connector: Connector,
+        store: Box<dyn AuditStore>,
+        log_records: bool,
+    ) -> Result<Self, InternalError> {
+        let (sender, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("AuditLogger".into())
+            .spawn(move || loop {
+                let record = match receiver.recv() {
+                    Ok(AuditMessage::Record(record)) => record,
+                    Ok(AuditMessage::Shutdown) | Err(_) => break,
+                };
+
+                if log_records {
+                    info!(target: AUDIT_LOG_TARGET, "{}", record);
+                }
+
+                if let Err(err) = store.add_record(record) {
+                    error!("Unable to write connection audit record: {}", err);
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let subscriber_id = connector
+            .subscribe(sender.clone())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            connector,
+            subscriber_id,
+            sender,
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for AuditLogger {
+    fn signal_shutdown(&mut self) {
+        if let Err(err) = self.connector.unsubscribe(self.subscriber_id) {
+            debug!(
+                "Unable to unsubscribe audit logger from connection manager: {}",
+                err
+            );
+        }
+        if self.sender.send(AuditMessage::Shutdown).is_err() {
+            warn!("Audit logger is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "Audit logger thread did not shutdown correctly: {:?}",
+                err
+            ))
+        })
+    }
+}
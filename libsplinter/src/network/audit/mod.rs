@@ -0,0 +1,389 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An audit trail of peer connection events.
+//!
+//! Every connection attempt, authorization outcome and disconnect reported by the
+//! [`ConnectionManager`](crate::network::connection_manager::ConnectionManager) is converted into
+//! an [`AuditRecord`] and written to an [`AuditStore`] by the [`AuditLogger`].
+
+mod logger;
+pub mod store;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::error::InvalidArgumentError;
+use crate::network::connection_manager::{ConnectionManagerError, ConnectionManagerNotification};
+use crate::peer::PeerAuthorizationToken;
+
+pub use logger::{AuditLogger, AUDIT_LOG_TARGET};
+pub use store::{AuditStore, AuditStoreError};
+
+/// The outcome of a connection event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditOutcome {
+    /// An outbound connection was established and authorized.
+    OutboundConnected,
+    /// An inbound connection was accepted and authorized.
+    InboundConnected,
+    /// A connection failed authorization.
+    AuthorizationFailed,
+    /// A connection could not be established.
+    ConnectionFailed,
+    /// An attempt to reconnect to a peer failed.
+    ReconnectionFailed,
+    /// An established connection was disconnected.
+    Disconnected,
+}
+
+impl AuditOutcome {
+    /// Returns the string representation used in storage and in the REST API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOutcome::OutboundConnected => "outbound_connected",
+            AuditOutcome::InboundConnected => "inbound_connected",
+            AuditOutcome::AuthorizationFailed => "authorization_failed",
+            AuditOutcome::ConnectionFailed => "connection_failed",
+            AuditOutcome::ReconnectionFailed => "reconnection_failed",
+            AuditOutcome::Disconnected => "disconnected",
+        }
+    }
+}
+
+impl fmt::Display for AuditOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditOutcome {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "outbound_connected" => Ok(AuditOutcome::OutboundConnected),
+            "inbound_connected" => Ok(AuditOutcome::InboundConnected),
+            "authorization_failed" => Ok(AuditOutcome::AuthorizationFailed),
+            "connection_failed" => Ok(AuditOutcome::ConnectionFailed),
+            "reconnection_failed" => Ok(AuditOutcome::ReconnectionFailed),
+            "disconnected" => Ok(AuditOutcome::Disconnected),
+            _ => Err(InvalidArgumentError::new(
+                "outcome".to_string(),
+                format!("unknown audit outcome: {}", s),
+            )),
+        }
+    }
+}
+
+/// A single entry in the connection audit log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditRecord {
+    timestamp: SystemTime,
+    endpoint: String,
+    connection_id: String,
+    identity: Option<String>,
+    outcome: AuditOutcome,
+    reason: Option<String>,
+}
+
+impl AuditRecord {
+    /// Creates a new record with the current time as its timestamp.
+    pub fn new(endpoint: &str, connection_id: &str, outcome: AuditOutcome) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            endpoint: endpoint.to_string(),
+            connection_id: connection_id.to_string(),
+            identity: None,
+            outcome,
+            reason: None,
+        }
+    }
+
+    /// Sets the time at which the event occurred.
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the identity of the remote peer.
+    pub fn with_identity(mut self, identity: &str) -> Self {
+        self.identity = Some(identity.to_string());
+        self
+    }
+
+    /// Sets a human-readable reason for the outcome.
+    pub fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+
+    /// The time at which the event occurred.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// The remote endpoint of the connection.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The connection ID assigned by the connection manager.
+    pub fn connection_id(&self) -> &str {
+        &self.connection_id
+    }
+
+    /// The identity of the remote peer, if it was known at the time of the event.
+    pub fn identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+
+    /// The outcome of the event.
+    pub fn outcome(&self) -> AuditOutcome {
+        self.outcome
+    }
+
+    /// The reason for the outcome, if any.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
+
+impl From<ConnectionManagerNotification> for AuditRecord {
+    fn from(notification: ConnectionManagerNotification) -> Self {
+        match notification {
+            ConnectionManagerNotification::Connected {
+                endpoint,
+                connection_id,
+                identity,
+                ..
+            } => AuditRecord::new(&endpoint, &connection_id, AuditOutcome::OutboundConnected)
+                .with_identity(&PeerAuthorizationToken::from(identity).id_as_string()),
+            ConnectionManagerNotification::InboundConnection {
+                endpoint,
+                connection_id,
+                identity,
+                ..
+            } => AuditRecord::new(&endpoint, &connection_id, AuditOutcome::InboundConnected)
+                .with_identity(&PeerAuthorizationToken::from(identity).id_as_string()),
+            ConnectionManagerNotification::FatalConnectionError {
+                endpoint,
+                connection_id,
+                error,
+            } => {
+                let outcome = match error {
+                    ConnectionManagerError::Unauthorized(_) => AuditOutcome::AuthorizationFailed,
+                    _ => AuditOutcome::ConnectionFailed,
+                };
+                AuditRecord::new(&endpoint, &connection_id, outcome).with_reason(&error.to_string())
+            }
+            ConnectionManagerNotification::NonFatalConnectionError {
+                endpoint,
+                attempts,
+                identity,
+                connection_id,
+            } => AuditRecord::new(&endpoint, &connection_id, AuditOutcome::ReconnectionFailed)
+                .with_identity(&PeerAuthorizationToken::from(identity).id_as_string())
+                .with_reason(&format!("reconnection attempt {} failed", attempts)),
+            ConnectionManagerNotification::Disconnected {
+                endpoint,
+                identity,
+                connection_id,
+            } => AuditRecord::new(&endpoint, &connection_id, AuditOutcome::Disconnected)
+                .with_identity(&PeerAuthorizationToken::from(identity).id_as_string())
+                .with_reason("connection lost"),
+        }
+    }
+}
+
+impl fmt::Display for AuditRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "outcome={} endpoint={} connection_id={} identity={}",
+            self.outcome,
+            self.endpoint,
+            self.connection_id,
+            self.identity.as_deref().unwrap_or("-")
+        )?;
+        if let Some(reason) = &self.reason {
+            write!(f, " reason=\"{}\"", reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Criteria for selecting records from an [`AuditStore`].
+///
+/// An empty filter matches every record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditRecordFilter {
+    peer: Option<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    outcome: Option<AuditOutcome>,
+}
+
+impl AuditRecordFilter {
+    /// Only match records whose endpoint or identity equals `peer`.
+    pub fn with_peer(mut self, peer: &str) -> Self {
+        self.peer = Some(peer.to_string());
+        self
+    }
+
+    /// Only match records at or after the given time.
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only match records at or before the given time.
+    pub fn with_until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Only match records with the given outcome.
+    pub fn with_outcome(mut self, outcome: AuditOutcome) -> Self {
+        self.outcome = Some(outcome);
+        self
+    }
+
+    pub fn peer(&self) -> Option<&str> {
+        self.peer.as_deref()
+    }
+
+    pub fn since(&self) -> Option<SystemTime> {
+        self.since
+    }
+
+    pub fn until(&self) -> Option<SystemTime> {
+        self.until
+    }
+
+    pub fn outcome(&self) -> Option<AuditOutcome> {
+        self.outcome
+    }
+
+    /// Returns true if the given record satisfies all of the filter's criteria.
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        if let Some(peer) = &self.peer {
+            if record.endpoint() != peer && record.identity() != Some(peer.as_str()) {
+                return false;
+            }
+        }
+        if let Some(since) = self.since {
+            if record.timestamp() < since {
+                return false;
+            }
+        }
+        if let Some(until) = self.until {
+            if record.timestamp() > until {
+                return false;
+            }
+        }
+        if let Some(outcome) = self.outcome {
+            if record.outcome() != outcome {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use crate::network::auth::ConnectionAuthorizationType;
+
+    /// Verify that connection manager notifications are converted into the expected audit
+    /// outcomes.
+    #[test]
+    fn test_record_from_notification() {
+        let record = AuditRecord::from(ConnectionManagerNotification::InboundConnection {
+            endpoint: "tcp://127.0.0.1:8044".into(),
+            connection_id: "conn-1".into(),
+            identity: ConnectionAuthorizationType::Trust {
+                identity: "node-a".into(),
+            },
+            local_identity: ConnectionAuthorizationType::Trust {
+                identity: "node-b".into(),
+            },
+        });
+        assert_eq!(record.outcome(), AuditOutcome::InboundConnected);
+        assert_eq!(record.identity(), Some("node-a"));
+
+        let record = AuditRecord::from(ConnectionManagerNotification::FatalConnectionError {
+            endpoint: "tcp://127.0.0.1:8044".into(),
+            connection_id: "conn-2".into(),
+            error: ConnectionManagerError::Unauthorized("conn-2".into()),
+        });
+        assert_eq!(record.outcome(), AuditOutcome::AuthorizationFailed);
+        assert_eq!(record.identity(), None);
+        assert_eq!(
+            record.reason(),
+            Some("Connection conn-2 failed authorization")
+        );
+    }
+
+    /// Verify that each criteria of an `AuditRecordFilter` is applied.
+    #[test]
+    fn test_filter_matches() {
+        let now = SystemTime::now();
+        let record = AuditRecord::new("tcp://peer:8044", "conn-1", AuditOutcome::Disconnected)
+            .with_identity("node-a")
+            .with_timestamp(now);
+
+        assert!(AuditRecordFilter::default().matches(&record));
+        assert!(AuditRecordFilter::default()
+            .with_peer("node-a")
+            .matches(&record));
+        assert!(AuditRecordFilter::default()
+            .with_peer("tcp://peer:8044")
+            .matches(&record));
+        assert!(!AuditRecordFilter::default()
+            .with_peer("node-b")
+            .matches(&record));
+        assert!(AuditRecordFilter::default()
+            .with_since(now - Duration::from_secs(1))
+            .with_until(now + Duration::from_secs(1))
+            .matches(&record));
+        assert!(!AuditRecordFilter::default()
+            .with_since(now + Duration::from_secs(1))
+            .matches(&record));
+        assert!(!AuditRecordFilter::default()
+            .with_outcome(AuditOutcome::InboundConnected)
+            .matches(&record));
+    }
+
+    /// Verify that outcomes round-trip through their string representation.
+    #[test]
+    fn test_outcome_from_str() {
+        for outcome in &[
+            AuditOutcome::OutboundConnected,
+            AuditOutcome::InboundConnected,
+            AuditOutcome::AuthorizationFailed,
+            AuditOutcome::ConnectionFailed,
+            AuditOutcome::ReconnectionFailed,
+            AuditOutcome::Disconnected,
+        ] {
+            assert_eq!(&outcome.as_str().parse::<AuditOutcome>().unwrap(), outcome);
+        }
+        assert!("bogus".parse::<AuditOutcome>().is_err());
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A database-backed [AuditStore](super::AuditStore), powered by [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::network::audit::{AuditRecord, AuditRecordFilter};
+use crate::store::pool::ConnectionPool;
+
+use super::{AuditStore, AuditStoreError};
+
+use operations::{
+    add_record::AuditStoreAddRecordOperation, list_records::AuditStoreListRecordsOperation,
+    AuditStoreOperations,
+};
+
+/// Database backed [AuditStore](super::AuditStore) implementation.
+pub struct DieselAuditStore<C: diesel::Connection + 'static> {
+    pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselAuditStore<C> {
+    /// Creates a new `DieselAuditStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: connection pool for the database
+    pub fn new(pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselAuditStore { pool: pool.into() }
+    }
+
+    /// Create a new `DieselAuditStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(pool: Arc<RwLock<Pool<ConnectionManager<C>>>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselAuditStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl AuditStore for DieselAuditStore<diesel::pg::PgConnection> {
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError> {
+        self.pool
+            .execute_write(|conn| AuditStoreOperations::new(conn).add_record(record))
+    }
+
+    fn list_records(
+        &self,
+        filter: &AuditRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditRecord>>, AuditStoreError> {
+        self.pool
+            .execute_read(|conn| AuditStoreOperations::new(conn).list_records(filter))
+            .map(|records| Box::new(records.into_iter()) as Box<_>)
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl AuditStore for DieselAuditStore<diesel::sqlite::SqliteConnection> {
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError> {
+        self.pool
+            .execute_write(|conn| AuditStoreOperations::new(conn).add_record(record))
+    }
+
+    fn list_records(
+        &self,
+        filter: &AuditRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditRecord>>, AuditStoreError> {
+        self.pool
+            .execute_read(|conn| AuditStoreOperations::new(conn).list_records(filter))
+            .map(|records| Box::new(records.into_iter()) as Box<_>)
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::network::audit::AuditOutcome;
+
+    /// Verify that records can be added to a SQLite-backed store and then listed using a filter.
+    #[test]
+    fn sqlite_add_and_list_records() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselAuditStore::new(pool);
+
+        store
+            .add_record(
+                AuditRecord::new(
+                    "tcp://peer-a:8044",
+                    "conn-1",
+                    AuditOutcome::InboundConnected,
+                )
+                .with_identity("node-a"),
+            )
+            .expect("Unable to add record");
+        store
+            .add_record(
+                AuditRecord::new(
+                    "tcp://peer-b:8044",
+                    "conn-2",
+                    AuditOutcome::AuthorizationFailed,
+                )
+                .with_reason("Connection conn-2 failed authorization"),
+            )
+            .expect("Unable to add record");
+
+        let all = store
+            .list_records(&AuditRecordFilter::default())
+            .expect("Unable to list records");
+        assert_eq!(all.len(), 2);
+
+        let mut failed = store
+            .list_records(
+                &AuditRecordFilter::default().with_outcome(AuditOutcome::AuthorizationFailed),
+            )
+            .expect("Unable to list records");
+        assert_eq!(failed.len(), 1);
+        let record = failed.next().expect("Missing record");
+        assert_eq!(record.endpoint(), "tcp://peer-b:8044");
+        assert_eq!(
+            record.reason(),
+            Some("Connection conn-2 failed authorization")
+        );
+
+        let by_peer = store
+            .list_records(&AuditRecordFilter::default().with_peer("node-a"))
+            .expect("Unable to list records");
+        assert_eq!(by_peer.len(), 1);
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::InternalError;
+use crate::network::audit::{AuditRecord, AuditStoreError};
+
+use super::schema::network_audit_record;
+
+#[derive(Queryable, PartialEq, Eq, Debug)]
+pub struct AuditRecordModel {
+    pub id: i64,
+    pub timestamp: i64,
+    pub endpoint: String,
+    pub connection_id: String,
+    pub identity: Option<String>,
+    pub outcome: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Eq, Debug)]
+#[table_name = "network_audit_record"]
+pub struct NewAuditRecordModel {
+    pub timestamp: i64,
+    pub endpoint: String,
+    pub connection_id: String,
+    pub identity: Option<String>,
+    pub outcome: String,
+    pub reason: Option<String>,
+}
+
+/// Converts a `SystemTime` into seconds since the Unix epoch, as stored in the database.
+pub fn to_db_timestamp(time: SystemTime) -> Result<i64, AuditStoreError> {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| AuditStoreError::Internal(InternalError::from_source(Box::new(err))))?
+        .as_secs();
+    i64::try_from(secs)
+        .map_err(|err| AuditStoreError::Internal(InternalError::from_source(Box::new(err))))
+}
+
+impl TryFrom<AuditRecord> for NewAuditRecordModel {
+    type Error = AuditStoreError;
+
+    fn try_from(record: AuditRecord) -> Result<Self, Self::Error> {
+        Ok(NewAuditRecordModel {
+            timestamp: to_db_timestamp(record.timestamp())?,
+            endpoint: record.endpoint().to_string(),
+            connection_id: record.connection_id().to_string(),
+            identity: record.identity().map(String::from),
+            outcome: record.outcome().as_str().to_string(),
+            reason: record.reason().map(String::from),
+        })
+    }
+}
+
+impl TryFrom<AuditRecordModel> for AuditRecord {
+    type Error = AuditStoreError;
+
+    fn try_from(model: AuditRecordModel) -> Result<Self, Self::Error> {
+        let secs = u64::try_from(model.timestamp).map_err(|err| {
+            AuditStoreError::Internal(InternalError::from_source_with_message(
+                Box::new(err),
+                "'timestamp' could not be converted from i64 to u64".to_string(),
+            ))
+        })?;
+        let timestamp = UNIX_EPOCH
+            .checked_add(Duration::from_secs(secs))
+            .ok_or_else(|| {
+                AuditStoreError::Internal(InternalError::with_message(
+                    "'timestamp' could not be represented as a `SystemTime`".to_string(),
+                ))
+            })?;
+        let outcome = model
+            .outcome
+            .parse()
+            .map_err(|err| AuditStoreError::Internal(InternalError::from_source(Box::new(err))))?;
+
+        let mut record = AuditRecord::new(&model.endpoint, &model.connection_id, outcome)
+            .with_timestamp(timestamp);
+        if let Some(identity) = model.identity {
+            record = record.with_identity(&identity);
+        }
+        if let Some(reason) = model.reason {
+            record = record.with_reason(&reason);
+        }
+
+        Ok(record)
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::network::audit::{
+    store::diesel::{models::NewAuditRecordModel, schema::network_audit_record},
+    AuditRecord, AuditStoreError,
+};
+
+use super::AuditStoreOperations;
+
+pub(in crate::network::audit::store::diesel) trait AuditStoreAddRecordOperation {
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditStoreAddRecordOperation for AuditStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError> {
+        insert_into(network_audit_record::table)
+            .values(NewAuditRecordModel::try_from(record)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(AuditStoreError::from)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditStoreAddRecordOperation
+    for AuditStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError> {
+        insert_into(network_audit_record::table)
+            .values(NewAuditRecordModel::try_from(record)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(AuditStoreError::from)
+    }
+}
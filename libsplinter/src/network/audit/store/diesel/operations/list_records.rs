@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::network::audit::{
+    store::diesel::{
+        models::{to_db_timestamp, AuditRecordModel},
+        schema::network_audit_record,
+    },
+    AuditRecord, AuditRecordFilter, AuditStoreError,
+};
+
+use super::AuditStoreOperations;
+
+pub(in crate::network::audit::store::diesel) trait AuditStoreListRecordsOperation {
+    fn list_records(&self, filter: &AuditRecordFilter)
+        -> Result<Vec<AuditRecord>, AuditStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditStoreListRecordsOperation for AuditStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_records(
+        &self,
+        filter: &AuditRecordFilter,
+    ) -> Result<Vec<AuditRecord>, AuditStoreError> {
+        let mut query = network_audit_record::table.into_boxed::<diesel::pg::Pg>();
+
+        if let Some(peer) = filter.peer() {
+            query = query.filter(
+                network_audit_record::endpoint
+                    .eq(peer.to_string())
+                    .or(network_audit_record::identity.eq(peer.to_string())),
+            );
+        }
+        if let Some(since) = filter.since() {
+            query = query.filter(network_audit_record::timestamp.ge(to_db_timestamp(since)?));
+        }
+        if let Some(until) = filter.until() {
+            query = query.filter(network_audit_record::timestamp.le(to_db_timestamp(until)?));
+        }
+        if let Some(outcome) = filter.outcome() {
+            query = query.filter(network_audit_record::outcome.eq(outcome.as_str().to_string()));
+        }
+
+        query
+            .order(network_audit_record::id.asc())
+            .load::<AuditRecordModel>(self.conn)?
+            .into_iter()
+            .map(AuditRecord::try_from)
+            .collect()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditStoreListRecordsOperation
+    for AuditStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_records(
+        &self,
+        filter: &AuditRecordFilter,
+    ) -> Result<Vec<AuditRecord>, AuditStoreError> {
+        let mut query = network_audit_record::table.into_boxed::<diesel::sqlite::Sqlite>();
+
+        if let Some(peer) = filter.peer() {
+            query = query.filter(
+                network_audit_record::endpoint
+                    .eq(peer.to_string())
+                    .or(network_audit_record::identity.eq(peer.to_string())),
+            );
+        }
+        if let Some(since) = filter.since() {
+            query = query.filter(network_audit_record::timestamp.ge(to_db_timestamp(since)?));
+        }
+        if let Some(until) = filter.until() {
+            query = query.filter(network_audit_record::timestamp.le(to_db_timestamp(until)?));
+        }
+        if let Some(outcome) = filter.outcome() {
+            query = query.filter(network_audit_record::outcome.eq(outcome.as_str().to_string()));
+        }
+
+        query
+            .order(network_audit_record::id.asc())
+            .load::<AuditRecordModel>(self.conn)?
+            .into_iter()
+            .map(AuditRecord::try_from)
+            .collect()
+    }
+}
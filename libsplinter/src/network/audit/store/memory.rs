@@ -0,0 +1,129 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-memory implementation of [AuditStore](super::AuditStore).
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::error::InternalError;
+use crate::network::audit::{AuditRecord, AuditRecordFilter};
+
+use super::{AuditStore, AuditStoreError};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// An [AuditStore](super::AuditStore) that keeps records in memory.
+///
+/// The store holds at most `capacity` records; once full, the oldest record is dropped for each
+/// new record added.
+#[derive(Clone)]
+pub struct MemoryAuditStore {
+    records: Arc<Mutex<VecDeque<AuditRecord>>>,
+    capacity: usize,
+}
+
+impl MemoryAuditStore {
+    /// Constructs a new store that holds up to 10,000 records.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Constructs a new store that holds up to `capacity` records.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(VecDeque::new())),
+            capacity,
+        }
+    }
+}
+
+impl Default for MemoryAuditStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuditStore for MemoryAuditStore {
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError> {
+        let mut records = self.records.lock().map_err(|_| {
+            AuditStoreError::Internal(InternalError::with_message(
+                "Cannot access audit store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        if self.capacity == 0 {
+            return Ok(());
+        }
+        while records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+
+        Ok(())
+    }
+
+    fn list_records(
+        &self,
+        filter: &AuditRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditRecord>>, AuditStoreError> {
+        let records = self.records.lock().map_err(|_| {
+            AuditStoreError::Internal(InternalError::with_message(
+                "Cannot access audit store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        Ok(Box::new(
+            records
+                .iter()
+                .filter(|record| filter.matches(record))
+                .cloned()
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::network::audit::AuditOutcome;
+
+    /// Verify that the memory store drops its oldest records once it reaches capacity.
+    #[test]
+    fn test_capacity() {
+        let store = MemoryAuditStore::with_capacity(2);
+        for id in &["conn-1", "conn-2", "conn-3"] {
+            store
+                .add_record(AuditRecord::new(
+                    "tcp://peer:8044",
+                    id,
+                    AuditOutcome::InboundConnected,
+                ))
+                .expect("Unable to add record");
+        }
+
+        let ids = store
+            .list_records(&AuditRecordFilter::default())
+            .expect("Unable to list records")
+            .map(|record| record.connection_id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["conn-2".to_string(), "conn-3".to_string()]);
+    }
+}
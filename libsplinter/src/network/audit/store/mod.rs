@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for connection audit records.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+pub mod memory;
+
+pub use error::AuditStoreError;
+
+use super::{AuditRecord, AuditRecordFilter};
+
+/// Trait for persisting and querying connection audit records.
+pub trait AuditStore: Send + Sync {
+    /// Adds a record to the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - the record to add
+    fn add_record(&self, record: AuditRecord) -> Result<(), AuditStoreError>;
+
+    /// Lists the records that match the given filter, ordered from oldest to newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - the criteria records must match
+    fn list_records(
+        &self,
+        filter: &AuditRecordFilter,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditRecord>>, AuditStoreError>;
+
+    fn clone_box(&self) -> Box<dyn AuditStore>;
+}
+
+impl Clone for Box<dyn AuditStore> {
+    fn clone(&self) -> Box<dyn AuditStore> {
+        self.clone_box()
+    }
+}
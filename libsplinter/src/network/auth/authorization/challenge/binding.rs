@@ -0,0 +1,282 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Binding of challenge authorization to the endpoint a connection was made to.
+//!
+//! When a node dials a peer, it may sign the challenge nonce together with the endpoint it
+//! dialed, such as the DNS name used for SNI when peering through a proxy. The accepting node
+//! checks that the endpoint is one of its advertised endpoints, which prevents a signature from
+//! being replayed against a different endpoint.
+
+use std::fmt;
+use std::str::FromStr;
+
+use cylinder::{PublicKey, Signature, Verifier};
+
+use crate::error::InvalidArgumentError;
+use crate::protocol::authorization::EndpointSignature;
+
+/// How the accepting side of a challenge authorization treats endpoint bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointBindingMode {
+    /// Endpoint bindings are neither sent nor checked.
+    Off,
+    /// Endpoint bindings are sent; missing or invalid bindings are logged but accepted.
+    Warn,
+    /// Endpoint bindings are sent; missing or invalid bindings fail authorization.
+    Enforce,
+}
+
+impl Default for EndpointBindingMode {
+    fn default() -> Self {
+        EndpointBindingMode::Off
+    }
+}
+
+impl FromStr for EndpointBindingMode {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(EndpointBindingMode::Off),
+            "warn" => Ok(EndpointBindingMode::Warn),
+            "enforce" => Ok(EndpointBindingMode::Enforce),
+            _ => Err(InvalidArgumentError::new(
+                "endpoint_binding".to_string(),
+                format!(
+                    "\"{}\" is not a valid mode; expected one of off, warn, or enforce",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for EndpointBindingMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EndpointBindingMode::Off => f.write_str("off"),
+            EndpointBindingMode::Warn => f.write_str("warn"),
+            EndpointBindingMode::Enforce => f.write_str("enforce"),
+        }
+    }
+}
+
+/// The endpoint binding configuration for a node.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EndpointBindingPolicy {
+    mode: EndpointBindingMode,
+    advertised_endpoints: Vec<String>,
+}
+
+impl EndpointBindingPolicy {
+    pub fn new(mode: EndpointBindingMode, advertised_endpoints: Vec<String>) -> Self {
+        Self {
+            mode,
+            advertised_endpoints: advertised_endpoints
+                .iter()
+                .map(|endpoint| normalize_endpoint(endpoint))
+                .collect(),
+        }
+    }
+
+    /// Returns true if endpoint bindings should be sent when dialing a peer.
+    pub fn is_enabled(&self) -> bool {
+        self.mode != EndpointBindingMode::Off
+    }
+
+    /// Checks the endpoint binding submitted with a verified challenge signature.
+    ///
+    /// Returns an error message if authorization should fail. In warn mode, problems are logged
+    /// and `Ok` is returned.
+    pub fn check(
+        &self,
+        nonce: &[u8],
+        public_key: &PublicKey,
+        endpoint_signature: Option<&EndpointSignature>,
+        verifier: &dyn Verifier,
+    ) -> Result<(), String> {
+        if self.mode == EndpointBindingMode::Off {
+            return Ok(());
+        }
+
+        let problem = match endpoint_signature {
+            None => Some("no endpoint binding was submitted".to_string()),
+            Some(endpoint_signature)
+                if !self
+                    .advertised_endpoints
+                    .contains(&normalize_endpoint(&endpoint_signature.endpoint)) =>
+            {
+                Some(format!(
+                    "endpoint binding is for {}, which is not advertised by this node",
+                    endpoint_signature.endpoint
+                ))
+            }
+            Some(endpoint_signature) => {
+                match verifier.verify(
+                    &endpoint_binding_payload(nonce, &endpoint_signature.endpoint),
+                    &Signature::new(endpoint_signature.signature.clone()),
+                    public_key,
+                ) {
+                    Ok(true) => None,
+                    Ok(false) => Some("endpoint binding signature was not valid".to_string()),
+                    Err(err) => Some(format!(
+                        "unable to verify endpoint binding signature: {}",
+                        err
+                    )),
+                }
+            }
+        };
+
+        match (problem, self.mode) {
+            (Some(problem), EndpointBindingMode::Enforce) => {
+                Err(format!("Challenge endpoint binding failed: {}", problem))
+            }
+            (Some(problem), _) => {
+                warn!("Accepting challenge authorization, but {}", problem);
+                Ok(())
+            }
+            (None, _) => Ok(()),
+        }
+    }
+}
+
+/// Returns the bytes that are signed to bind a challenge nonce to an endpoint.
+pub(crate) fn endpoint_binding_payload(nonce: &[u8], endpoint: &str) -> Vec<u8> {
+    [nonce, endpoint.as_bytes()].concat()
+}
+
+/// Normalizes an endpoint for comparison by removing the protocol prefix and lowercasing the
+/// remaining address, since DNS names are case-insensitive.
+fn normalize_endpoint(endpoint: &str) -> String {
+    endpoint
+        .split_once("://")
+        .map(|(_, address)| address)
+        .unwrap_or(endpoint)
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+
+    const NONCE: &[u8] = b"test-nonce";
+
+    fn signed(signer: &dyn Signer, endpoint: &str) -> EndpointSignature {
+        EndpointSignature {
+            endpoint: endpoint.to_string(),
+            signature: signer
+                .sign(&endpoint_binding_payload(NONCE, endpoint))
+                .expect("Unable to sign")
+                .take_bytes(),
+        }
+    }
+
+    /// Verify that an enforcing policy accepts a valid binding to an advertised endpoint, matching
+    /// regardless of protocol prefix and case.
+    #[test]
+    fn test_enforce_accepts_advertised_endpoint() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = signer.public_key().expect("Unable to get public key");
+        let verifier = context.new_verifier();
+
+        let policy = EndpointBindingPolicy::new(
+            EndpointBindingMode::Enforce,
+            vec!["tcps://node-a.example.com:8044".to_string()],
+        );
+
+        let endpoint_signature = signed(&*signer, "tcps://Node-A.example.com:8044");
+        assert!(policy
+            .check(NONCE, &public_key, Some(&endpoint_signature), &*verifier)
+            .is_ok());
+
+        let endpoint_signature = signed(&*signer, "node-a.example.com:8044");
+        assert!(policy
+            .check(NONCE, &public_key, Some(&endpoint_signature), &*verifier)
+            .is_ok());
+    }
+
+    /// Verify that an enforcing policy rejects missing bindings, bindings to other endpoints, and
+    /// bindings signed over a different nonce.
+    #[test]
+    fn test_enforce_rejects_invalid_bindings() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = signer.public_key().expect("Unable to get public key");
+        let verifier = context.new_verifier();
+
+        let policy = EndpointBindingPolicy::new(
+            EndpointBindingMode::Enforce,
+            vec!["tcps://node-a.example.com:8044".to_string()],
+        );
+
+        assert!(policy.check(NONCE, &public_key, None, &*verifier).is_err());
+
+        let endpoint_signature = signed(&*signer, "tcps://node-b.example.com:8044");
+        assert!(policy
+            .check(NONCE, &public_key, Some(&endpoint_signature), &*verifier)
+            .is_err());
+
+        let endpoint_signature = signed(&*signer, "tcps://node-a.example.com:8044");
+        assert!(policy
+            .check(
+                b"other-nonce",
+                &public_key,
+                Some(&endpoint_signature),
+                &*verifier
+            )
+            .is_err());
+    }
+
+    /// Verify that warn and off policies accept missing or invalid bindings.
+    #[test]
+    fn test_warn_and_off_accept_invalid_bindings() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = signer.public_key().expect("Unable to get public key");
+        let verifier = context.new_verifier();
+        let endpoint_signature = signed(&*signer, "tcps://node-b.example.com:8044");
+
+        for mode in [EndpointBindingMode::Warn, EndpointBindingMode::Off] {
+            let policy = EndpointBindingPolicy::new(
+                mode,
+                vec!["tcps://node-a.example.com:8044".to_string()],
+            );
+            assert!(policy.check(NONCE, &public_key, None, &*verifier).is_ok());
+            assert!(policy
+                .check(NONCE, &public_key, Some(&endpoint_signature), &*verifier)
+                .is_ok());
+        }
+    }
+
+    /// Verify that modes are parsed case-insensitively and invalid modes are rejected.
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!(
+            "Enforce".parse::<EndpointBindingMode>().unwrap(),
+            EndpointBindingMode::Enforce
+        );
+        assert_eq!(
+            "warn".parse::<EndpointBindingMode>().unwrap(),
+            EndpointBindingMode::Warn
+        );
+        assert_eq!(
+            "off".parse::<EndpointBindingMode>().unwrap(),
+            EndpointBindingMode::Off
+        );
+        assert!("strict".parse::<EndpointBindingMode>().is_err());
+    }
+}
@@ -17,6 +17,12 @@
 use cylinder::{Signature, Signer, Verifier};
 
 use crate::error::InternalError;
+#[cfg(feature = "challenge-endpoint-binding")]
+use crate::network::auth::authorization::challenge::binding::{
+    endpoint_binding_payload, EndpointBindingPolicy,
+};
+#[cfg(feature = "challenge-trust-policy")]
+use crate::network::auth::authorization::challenge::ChallengeTrustPolicy;
 use crate::network::auth::state_machine::challenge_v1::{
     ChallengeAuthorizationAcceptingAction, ChallengeAuthorizationAcceptingState,
     ChallengeAuthorizationInitiatingAction, ChallengeAuthorizationInitiatingState,
@@ -29,6 +35,8 @@ use crate::network::dispatch::{
     ConnectionId, DispatchError, Handler, MessageContext, MessageSender, RawBytes,
 };
 use crate::protocol::authorization::AuthComplete;
+#[cfg(feature = "challenge-endpoint-binding")]
+use crate::protocol::authorization::EndpointSignature;
 use crate::protocol::authorization::{
     AuthChallengeNonceResponse, AuthChallengeSubmitRequest, AuthChallengeSubmitResponse,
     AuthorizationError, SubmitRequest,
@@ -141,6 +149,8 @@ impl Handler for AuthChallengeNonceRequestHandler {
 pub struct AuthChallengeNonceResponseHandler {
     auth_manager: AuthorizationManagerStateMachine,
     signers: Vec<Box<dyn Signer>>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    bound_endpoint: Option<String>,
 }
 
 impl AuthChallengeNonceResponseHandler {
@@ -151,8 +161,17 @@ impl AuthChallengeNonceResponseHandler {
         Self {
             auth_manager,
             signers,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            bound_endpoint: None,
         }
     }
+
+    /// Sets the endpoint the nonce signatures will also be bound to.
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn with_bound_endpoint(mut self, bound_endpoint: Option<String>) -> Self {
+        self.bound_endpoint = bound_endpoint;
+        self
+    }
 }
 
 impl Handler for AuthChallengeNonceResponseHandler {
@@ -198,9 +217,32 @@ impl Handler for AuthChallengeNonceResponseHandler {
                     ))
                 })?;
 
+                #[cfg(feature = "challenge-endpoint-binding")]
+                let endpoint_signature = self
+                    .bound_endpoint
+                    .as_ref()
+                    .map(|endpoint| {
+                        signer
+                            .sign(&endpoint_binding_payload(&nonce_request.nonce, endpoint))
+                            .map(|signature| EndpointSignature {
+                                endpoint: endpoint.to_string(),
+                                signature: signature.take_bytes(),
+                            })
+                            .map_err(|err| {
+                                DispatchError::HandleError(format!(
+                                    "Unable to sign endpoint binding: {}",
+                                    err
+                                ))
+                            })
+                    })
+                    .transpose()?;
+                #[cfg(not(feature = "challenge-endpoint-binding"))]
+                let endpoint_signature = None;
+
                 Ok(SubmitRequest {
                     public_key: public_key.into(),
                     signature,
+                    endpoint_signature,
                 })
             })
             .collect::<Result<Vec<SubmitRequest>, DispatchError>>()?;
@@ -273,6 +315,10 @@ pub struct AuthChallengeSubmitRequestHandler {
     verifier: Box<dyn Verifier>,
     nonce: Vec<u8>,
     expected_public_key: Option<public_key::PublicKey>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    endpoint_binding: EndpointBindingPolicy,
+    #[cfg(feature = "challenge-trust-policy")]
+    trust_policy: ChallengeTrustPolicy,
 }
 
 impl AuthChallengeSubmitRequestHandler {
@@ -287,8 +333,27 @@ impl AuthChallengeSubmitRequestHandler {
             verifier,
             nonce,
             expected_public_key,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            endpoint_binding: EndpointBindingPolicy::default(),
+            #[cfg(feature = "challenge-trust-policy")]
+            trust_policy: ChallengeTrustPolicy::default(),
         }
     }
+
+    /// Sets the policy used to check the endpoint bindings of submitted signatures.
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub(crate) fn with_endpoint_binding(mut self, endpoint_binding: EndpointBindingPolicy) -> Self {
+        self.endpoint_binding = endpoint_binding;
+        self
+    }
+
+    /// Sets the trust policy used to accept an authorized public key in place of the expected
+    /// public key.
+    #[cfg(feature = "challenge-trust-policy")]
+    pub(crate) fn with_trust_policy(mut self, trust_policy: ChallengeTrustPolicy) -> Self {
+        self.trust_policy = trust_policy;
+        self
+    }
 }
 
 impl Handler for AuthChallengeSubmitRequestHandler {
@@ -336,11 +401,43 @@ impl Handler for AuthChallengeSubmitRequestHandler {
 
                 return Ok(());
             }
+
+            #[cfg(feature = "challenge-endpoint-binding")]
+            {
+                if let Err(msg) = self.endpoint_binding.check(
+                    &self.nonce,
+                    &request.public_key.clone().into(),
+                    request.endpoint_signature.as_ref(),
+                    &*self.verifier,
+                ) {
+                    send_authorization_error(
+                        &self.auth_manager,
+                        context.source_id(),
+                        context.source_connection_id(),
+                        sender,
+                        &msg,
+                    )?;
+
+                    return Ok(());
+                }
+            }
+
             public_keys.push(request.public_key);
         }
 
+        #[cfg(not(feature = "challenge-trust-policy"))]
+        let required_key_submitted =
+            |public_key: &public_key::PublicKey| public_keys.contains(public_key);
+        // an authorized public key stands in for the expected key, which remains the identity
+        #[cfg(feature = "challenge-trust-policy")]
+        let required_key_submitted = |public_key: &public_key::PublicKey| {
+            public_keys
+                .iter()
+                .any(|submitted| self.trust_policy.is_authorized(public_key, submitted))
+        };
+
         let identity = if let Some(public_key) = &self.expected_public_key {
-            if public_keys.contains(public_key) {
+            if required_key_submitted(public_key) {
                 public_key.clone()
             } else {
                 send_authorization_error(
@@ -1002,6 +1099,7 @@ mod tests {
                         .sign(&nonce)
                         .expect("Unable to sign nonce")
                         .take_bytes(),
+                    endpoint_signature: None,
                 }],
             }),
         )
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "challenge-endpoint-binding")]
+mod binding;
 mod handlers;
+#[cfg(feature = "challenge-trust-policy")]
+mod trust_policy;
 
 use cylinder::{Signer, Verifier};
 
@@ -27,6 +31,13 @@ use self::handlers::{
 
 use super::{AuthDispatchHandler, Authorization};
 
+#[cfg(feature = "challenge-endpoint-binding")]
+pub use self::binding::EndpointBindingMode;
+#[cfg(feature = "challenge-endpoint-binding")]
+pub(crate) use self::binding::EndpointBindingPolicy;
+#[cfg(feature = "challenge-trust-policy")]
+pub use self::trust_policy::ChallengeTrustPolicy;
+
 pub struct ChallengeAuthorization {
     signers: Vec<Box<dyn Signer>>,
     nonce: Vec<u8>,
@@ -34,6 +45,12 @@ pub struct ChallengeAuthorization {
     expected_authorization: Option<ConnectionAuthorizationType>,
     local_authorization: Option<ConnectionAuthorizationType>,
     auth_manager: AuthorizationManagerStateMachine,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    endpoint_binding: EndpointBindingPolicy,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    dialed_endpoint: Option<String>,
+    #[cfg(feature = "challenge-trust-policy")]
+    trust_policy: ChallengeTrustPolicy,
 }
 
 impl ChallengeAuthorization {
@@ -52,8 +69,38 @@ impl ChallengeAuthorization {
             expected_authorization,
             local_authorization,
             auth_manager,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            endpoint_binding: EndpointBindingPolicy::default(),
+            #[cfg(feature = "challenge-endpoint-binding")]
+            dialed_endpoint: None,
+            #[cfg(feature = "challenge-trust-policy")]
+            trust_policy: ChallengeTrustPolicy::default(),
         }
     }
+
+    /// Sets the endpoint binding policy and, for outbound connections, the endpoint that was
+    /// dialed.
+    ///
+    /// If the policy is enabled and a dialed endpoint is provided, the challenge signature will
+    /// also be bound to that endpoint.
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub(crate) fn with_endpoint_binding(
+        mut self,
+        endpoint_binding: EndpointBindingPolicy,
+        dialed_endpoint: Option<String>,
+    ) -> Self {
+        self.endpoint_binding = endpoint_binding;
+        self.dialed_endpoint = dialed_endpoint;
+        self
+    }
+
+    /// Sets the trust policy used to accept, and sign with, authorized public keys in place of
+    /// a circuit member's public key.
+    #[cfg(feature = "challenge-trust-policy")]
+    pub(crate) fn with_trust_policy(mut self, trust_policy: ChallengeTrustPolicy) -> Self {
+        self.trust_policy = trust_policy;
+        self
+    }
 }
 
 impl Authorization for ChallengeAuthorization {
@@ -75,6 +122,20 @@ impl Authorization for ChallengeAuthorization {
                         Err(_) => false,
                     });
 
+                // fall back to a signer holding one of the key's authorized public keys
+                #[cfg(feature = "challenge-trust-policy")]
+                let signer = signer.or_else(|| {
+                    let authorized_keys = self.trust_policy.authorized_keys(public_key);
+                    self.signers
+                        .iter()
+                        .find(|signer| match signer.public_key() {
+                            Ok(signer_public_key) => authorized_keys
+                                .iter()
+                                .any(|key| key.as_slice() == signer_public_key.as_slice()),
+                            Err(_) => false,
+                        })
+                });
+
                 match signer {
                     Some(signer) => vec![signer.clone()],
                     None => {
@@ -89,10 +150,19 @@ impl Authorization for ChallengeAuthorization {
             _ => self.signers.clone(),
         };
 
-        handlers.push(Box::new(AuthChallengeNonceResponseHandler::new(
-            self.auth_manager.clone(),
-            signers_to_use,
-        )));
+        #[cfg(not(feature = "challenge-endpoint-binding"))]
+        let nonce_response_handler =
+            AuthChallengeNonceResponseHandler::new(self.auth_manager.clone(), signers_to_use);
+        #[cfg(feature = "challenge-endpoint-binding")]
+        let nonce_response_handler =
+            AuthChallengeNonceResponseHandler::new(self.auth_manager.clone(), signers_to_use)
+                .with_bound_endpoint(
+                    self.dialed_endpoint
+                        .clone()
+                        .filter(|_| self.endpoint_binding.is_enabled()),
+                );
+
+        handlers.push(Box::new(nonce_response_handler));
 
         let expected_public_key = match &self.expected_authorization {
             Some(ConnectionAuthorizationType::Challenge { public_key }) => Some(public_key.clone()),
@@ -103,12 +173,20 @@ impl Authorization for ChallengeAuthorization {
             InvalidStateError::with_message("No verifier to add to handler".to_string())
         })?;
 
-        handlers.push(Box::new(AuthChallengeSubmitRequestHandler::new(
+        let submit_request_handler = AuthChallengeSubmitRequestHandler::new(
             self.auth_manager.clone(),
             verifier,
             self.nonce.clone(),
             expected_public_key,
-        )));
+        );
+        #[cfg(feature = "challenge-endpoint-binding")]
+        let submit_request_handler =
+            submit_request_handler.with_endpoint_binding(self.endpoint_binding.clone());
+        #[cfg(feature = "challenge-trust-policy")]
+        let submit_request_handler =
+            submit_request_handler.with_trust_policy(self.trust_policy.clone());
+
+        handlers.push(Box::new(submit_request_handler));
 
         handlers.push(Box::new(AuthChallengeSubmitResponseHandler::new(
             self.auth_manager.clone(),
@@ -0,0 +1,144 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Circuit-defined trust policies for challenge authorization.
+//!
+//! A circuit member may list authorized public keys in addition to its own public key, allowing
+//! the member's key to be rotated or shared among multiple administrators without disbanding
+//! the circuit. The policy collects these keys for every circuit the local node knows about, so
+//! the authorization handshake can accept a signature from an authorized key in place of the
+//! member's public key.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::public_key::PublicKey;
+use crate::threading::lock::{read_or_recover, write_or_recover};
+
+/// Maps a member's public key to its additional authorized public keys.
+type MemberKeys = HashMap<PublicKey, Vec<PublicKey>>;
+
+/// The authorized public keys of circuit members, shared between the admin service, which
+/// maintains them, and the authorization manager, which checks them.
+#[derive(Clone, Default)]
+pub struct ChallengeTrustPolicy {
+    circuits: Arc<RwLock<HashMap<String, MemberKeys>>>,
+}
+
+impl ChallengeTrustPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the authorized public keys of the members of a circuit, replacing any keys
+    /// previously set for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit (or circuit proposal) defining the keys
+    /// * `members` - Pairs of a member's public key and its additional authorized public keys
+    pub fn set_circuit_keys(
+        &self,
+        circuit_id: &str,
+        members: impl IntoIterator<Item = (PublicKey, Vec<PublicKey>)>,
+    ) {
+        let members: MemberKeys = members
+            .into_iter()
+            .filter(|(_, authorized_keys)| !authorized_keys.is_empty())
+            .collect();
+
+        let mut circuits = write_or_recover(&self.circuits, "challenge trust policy");
+        if members.is_empty() {
+            circuits.remove(circuit_id);
+        } else {
+            circuits.insert(circuit_id.to_string(), members);
+        }
+    }
+
+    /// Removes the authorized public keys defined by a circuit.
+    pub fn remove_circuit(&self, circuit_id: &str) {
+        write_or_recover(&self.circuits, "challenge trust policy").remove(circuit_id);
+    }
+
+    /// Returns the public keys that may authorize in place of the given member public key,
+    /// across all circuits.
+    pub fn authorized_keys(&self, public_key: &PublicKey) -> Vec<PublicKey> {
+        let mut authorized_keys = vec![];
+        for members in read_or_recover(&self.circuits, "challenge trust policy").values() {
+            for key in members.get(public_key).into_iter().flatten() {
+                if !authorized_keys.contains(key) {
+                    authorized_keys.push(key.clone());
+                }
+            }
+        }
+        authorized_keys
+    }
+
+    /// Returns true if `candidate` is the member public key itself, or one of its authorized
+    /// public keys.
+    pub fn is_authorized(&self, public_key: &PublicKey, candidate: &PublicKey) -> bool {
+        public_key == candidate
+            || read_or_recover(&self.circuits, "challenge trust policy")
+                .values()
+                .any(|members| {
+                    members
+                        .get(public_key)
+                        .map(|keys| keys.contains(candidate))
+                        .unwrap_or(false)
+                })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> PublicKey {
+        PublicKey::from_bytes(vec![byte; 33])
+    }
+
+    /// Verify that authorized keys are accepted for a member only while the circuit defining
+    /// them is part of the policy.
+    ///
+    /// 1. Set keys for two circuits, one authorizing key 2 and the other key 3 for key 1
+    /// 2. Verify keys 1, 2 and 3 are authorized for key 1, and key 1 is not authorized for key 2
+    /// 3. Remove the first circuit and verify key 2 is no longer authorized
+    /// 4. Set the second circuit's keys to an empty list and verify key 3 is no longer authorized
+    #[test]
+    fn test_authorized_keys_by_circuit() {
+        let policy = ChallengeTrustPolicy::new();
+        policy.set_circuit_keys("abcde-01234", vec![(key(1), vec![key(2)])]);
+        policy.set_circuit_keys(
+            "fghij-56789",
+            vec![(key(1), vec![key(3)]), (key(4), vec![])],
+        );
+
+        assert!(policy.is_authorized(&key(1), &key(1)));
+        assert!(policy.is_authorized(&key(1), &key(2)));
+        assert!(policy.is_authorized(&key(1), &key(3)));
+        assert!(!policy.is_authorized(&key(2), &key(1)));
+        assert!(!policy.is_authorized(&key(4), &key(3)));
+
+        let mut authorized_keys = policy.authorized_keys(&key(1));
+        authorized_keys.sort_by(|a, b| a.as_slice().cmp(b.as_slice()));
+        assert_eq!(authorized_keys, vec![key(2), key(3)]);
+
+        policy.remove_circuit("abcde-01234");
+        assert!(!policy.is_authorized(&key(1), &key(2)));
+        assert!(policy.is_authorized(&key(1), &key(3)));
+
+        policy.set_circuit_keys("fghij-56789", vec![(key(1), vec![])]);
+        assert!(!policy.is_authorized(&key(1), &key(3)));
+        assert!(policy.authorized_keys(&key(1)).is_empty());
+    }
+}
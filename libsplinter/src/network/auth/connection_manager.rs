@@ -38,6 +38,27 @@ impl Authorizer for AuthorizationConnector {
         )
         .map_err(AuthorizerError::from)
     }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    fn authorize_outbound_connection(
+        &self,
+        endpoint: &str,
+        connection_id: String,
+        connection: Box<dyn Connection>,
+        callback: AuthorizerCallback,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+    ) -> Result<(), AuthorizerError> {
+        self.add_outbound_connection(
+            endpoint,
+            connection_id,
+            connection,
+            expected_authorization,
+            local_authorization,
+            Box::new(move |state| (*callback)(state.into())),
+        )
+        .map_err(AuthorizerError::from)
+    }
 }
 
 impl From<ConnectionAuthorizationState> for AuthorizationResult {
@@ -20,6 +20,8 @@ mod state_machine;
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::{mpsc, Arc, Mutex};
+#[cfg(feature = "auth-handshake-limits")]
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "challenge-authorization")]
 use cylinder::{Signer, VerifierFactory};
@@ -36,6 +38,7 @@ use crate::protocol::{PEER_AUTHORIZATION_PROTOCOL_MIN, PEER_AUTHORIZATION_PROTOC
 use crate::protos::network;
 use crate::protos::prelude::*;
 use crate::public_key::PublicKey;
+use crate::threading::lock::lock_or_recover;
 use crate::threading::pool::{
     JobExecutor, ShutdownSignaler as ThreadPoolShutdownSignaller, ThreadPool, ThreadPoolBuilder,
 };
@@ -43,6 +46,12 @@ use crate::transport::{Connection, RecvError};
 
 #[cfg(feature = "challenge-authorization")]
 use self::authorization::challenge::ChallengeAuthorization;
+#[cfg(feature = "challenge-trust-policy")]
+pub use self::authorization::challenge::ChallengeTrustPolicy;
+#[cfg(feature = "challenge-endpoint-binding")]
+pub use self::authorization::challenge::EndpointBindingMode;
+#[cfg(feature = "challenge-endpoint-binding")]
+use self::authorization::challenge::EndpointBindingPolicy;
 #[cfg(feature = "trust-authorization")]
 use self::authorization::trust::TrustAuthorization;
 use self::authorization::trust_v0::TrustV0Authorization;
@@ -94,6 +103,12 @@ pub struct AuthorizationManager {
     shared: Arc<Mutex<ManagedAuthorizations>>,
     #[cfg(feature = "challenge-authorization")]
     verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
+    #[cfg(feature = "auth-handshake-limits")]
+    handshake_timeout: Option<Duration>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    endpoint_binding: EndpointBindingPolicy,
+    #[cfg(feature = "challenge-trust-policy")]
+    trust_policy: ChallengeTrustPolicy,
 }
 
 impl AuthorizationManager {
@@ -121,9 +136,53 @@ impl AuthorizationManager {
             shared,
             #[cfg(feature = "challenge-authorization")]
             verifier_factory,
+            #[cfg(feature = "auth-handshake-limits")]
+            handshake_timeout: None,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            endpoint_binding: EndpointBindingPolicy::default(),
+            #[cfg(feature = "challenge-trust-policy")]
+            trust_policy: ChallengeTrustPolicy::default(),
         })
     }
 
+    /// Sets the maximum amount of time a connection may take to complete authorization.
+    ///
+    /// Connections that have not completed authorization before the timeout are treated as
+    /// unauthorized. The timeout is checked each time the connection is polled for a message; for
+    /// transports that block while receiving, it is only checked when a message arrives.
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how challenge authorization binds signatures to the endpoint that was dialed.
+    ///
+    /// When enabled, outbound connections sign the challenge nonce together with the endpoint
+    /// they dialed, and inbound connections check that the bound endpoint is one of the given
+    /// advertised endpoints. In `Warn` mode, missing or invalid bindings are logged; in `Enforce`
+    /// mode, they fail authorization.
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn with_endpoint_binding(
+        mut self,
+        mode: EndpointBindingMode,
+        advertised_endpoints: Vec<String>,
+    ) -> Self {
+        self.endpoint_binding = EndpointBindingPolicy::new(mode, advertised_endpoints);
+        self
+    }
+
+    /// Sets the trust policy holding the authorized public keys of circuit members.
+    ///
+    /// A peer that submits one of a member's authorized public keys is accepted as that member,
+    /// and the local node may sign with one of its own authorized public keys when the key it
+    /// is required to use is not available. The policy is kept up to date by the admin service.
+    #[cfg(feature = "challenge-trust-policy")]
+    pub fn with_trust_policy(mut self, trust_policy: ChallengeTrustPolicy) -> Self {
+        self.trust_policy = trust_policy;
+        self
+    }
+
     pub fn shutdown_signaler(&self) -> ShutdownSignaler {
         ShutdownSignaler {
             thread_pool_signaler: self.thread_pool.shutdown_signaler(),
@@ -143,6 +202,12 @@ impl AuthorizationManager {
             executor: self.thread_pool.executor(),
             #[cfg(feature = "challenge-authorization")]
             verifier_factory: self.verifier_factory.clone(),
+            #[cfg(feature = "auth-handshake-limits")]
+            handshake_timeout: self.handshake_timeout,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            endpoint_binding: self.endpoint_binding.clone(),
+            #[cfg(feature = "challenge-trust-policy")]
+            trust_policy: self.trust_policy.clone(),
         }
     }
 }
@@ -168,6 +233,12 @@ pub struct AuthorizationConnector {
     executor: JobExecutor,
     #[cfg(feature = "challenge-authorization")]
     verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
+    #[cfg(feature = "auth-handshake-limits")]
+    handshake_timeout: Option<Duration>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    endpoint_binding: EndpointBindingPolicy,
+    #[cfg(feature = "challenge-trust-policy")]
+    trust_policy: ChallengeTrustPolicy,
 }
 
 impl AuthorizationConnector {
@@ -178,6 +249,50 @@ impl AuthorizationConnector {
         expected_authorization: Option<ConnectionAuthorizationType>,
         local_authorization: Option<ConnectionAuthorizationType>,
         on_complete_callback: Callback,
+    ) -> Result<(), AuthorizationManagerError> {
+        self.start_authorization(
+            connection_id,
+            connection,
+            expected_authorization,
+            local_authorization,
+            on_complete_callback,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            None,
+        )
+    }
+
+    /// Adds an outbound connection that was made by dialing the given endpoint.
+    ///
+    /// The endpoint should be provided as it was configured, before any name resolution, so that
+    /// challenge signatures can be bound to it.
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn add_outbound_connection(
+        &self,
+        endpoint: &str,
+        connection_id: String,
+        connection: Box<dyn Connection>,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+        on_complete_callback: Callback,
+    ) -> Result<(), AuthorizationManagerError> {
+        self.start_authorization(
+            connection_id,
+            connection,
+            expected_authorization,
+            local_authorization,
+            on_complete_callback,
+            Some(endpoint.to_string()),
+        )
+    }
+
+    fn start_authorization(
+        &self,
+        connection_id: String,
+        connection: Box<dyn Connection>,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+        on_complete_callback: Callback,
+        #[cfg(feature = "challenge-endpoint-binding")] dialed_endpoint: Option<String>,
     ) -> Result<(), AuthorizationManagerError> {
         let mut connection = connection;
 
@@ -197,13 +312,8 @@ impl AuthorizationConnector {
 
         #[cfg(feature = "challenge-authorization")]
         {
-            let verifier = self
-                .verifier_factory
-                .lock()
-                .map_err(|_| {
-                    AuthorizationManagerError("VerifierFactory lock poisoned".to_string())
-                })?
-                .new_verifier();
+            let verifier =
+                lock_or_recover(&self.verifier_factory, "verifier factory").new_verifier();
             let nonce: Vec<u8> = (0..70).map(|_| rand::random::<u8>()).collect();
             let challenge_authorization = ChallengeAuthorization::new(
                 self.signers.clone(),
@@ -213,6 +323,12 @@ impl AuthorizationConnector {
                 local_authorization,
                 state_machine.clone(),
             );
+            #[cfg(feature = "challenge-endpoint-binding")]
+            let challenge_authorization = challenge_authorization
+                .with_endpoint_binding(self.endpoint_binding.clone(), dialed_endpoint);
+            #[cfg(feature = "challenge-trust-policy")]
+            let challenge_authorization =
+                challenge_authorization.with_trust_policy(self.trust_policy.clone());
 
             dispatcher_builder =
                 dispatcher_builder.add_authorization(Box::new(challenge_authorization));
@@ -242,6 +358,11 @@ impl AuthorizationConnector {
                 ))
             })?;
 
+        #[cfg(feature = "auth-handshake-limits")]
+        let deadline = self
+            .handshake_timeout
+            .map(|timeout| Instant::now() + timeout);
+
         self.executor.execute(move || {
             #[cfg(not(any(feature = "trust-authorization", feature = "challenge-authorization")))]
             {
@@ -299,6 +420,18 @@ impl AuthorizationConnector {
             }
 
             let authed_identities = 'main: loop {
+                #[cfg(feature = "auth-handshake-limits")]
+                {
+                    if deadline.map(|deadline| Instant::now() >= deadline).unwrap_or(false) {
+                        warn!(
+                            "Authorization of connection {} timed out; aborting auth",
+                            &connection_id
+                        );
+                        counter!("splinter.network.authorization.timeouts", 1);
+                        break 'main None;
+                    }
+                }
+
                 match connection.recv() {
                     Ok(bytes) => {
                         let mut msg: network::NetworkMessage =
@@ -350,13 +483,7 @@ impl AuthorizationConnector {
                     }
                 }
 
-                let mut shared = match connection_shared.lock() {
-                    Ok(shared) => shared,
-                    Err(_) => {
-                        error!("connection authorization lock poisoned; aborting auth");
-                        break 'main None;
-                    }
-                };
+                let mut shared = lock_or_recover(&connection_shared, "connection authorization");
 
                 if let Some(true) = shared.is_complete(&connection_id) {
                     break 'main shared.take_connection_identity(&connection_id);
@@ -22,6 +22,7 @@ use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "challenge-authorization")]
 use crate::public_key::PublicKey;
+use crate::threading::lock::lock_or_recover;
 
 #[cfg(feature = "challenge-authorization")]
 use self::challenge_v1::{
@@ -272,9 +273,7 @@ impl AuthorizationManagerStateMachine {
         connection_id: &str,
         action: AuthorizationInitiatingAction,
     ) -> Result<AuthorizationInitiatingState, AuthorizationActionError> {
-        let mut shared = self.shared.lock().map_err(|_| {
-            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
-        })?;
+        let mut shared = lock_or_recover(&self.shared, "authorization pool");
 
         let mut cur_state =
             shared
@@ -390,9 +389,7 @@ impl AuthorizationManagerStateMachine {
         connection_id: &str,
         action: AuthorizationAcceptingAction,
     ) -> Result<AuthorizationAcceptingState, AuthorizationActionError> {
-        let mut shared = self.shared.lock().map_err(|_| {
-            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
-        })?;
+        let mut shared = lock_or_recover(&self.shared, "authorization pool");
 
         let mut cur_state =
             shared
@@ -526,9 +523,7 @@ impl AuthorizationManagerStateMachine {
         &self,
         connection_id: &str,
     ) -> Result<(), AuthorizationActionError> {
-        let mut shared = self.shared.lock().map_err(|_| {
-            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
-        })?;
+        let mut shared = lock_or_recover(&self.shared, "authorization pool");
 
         let mut cur_state =
             shared
@@ -554,9 +549,7 @@ impl AuthorizationManagerStateMachine {
         connection_id: &str,
         identity: Identity,
     ) -> Result<(), AuthorizationActionError> {
-        let mut shared = self.shared.lock().map_err(|_| {
-            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
-        })?;
+        let mut shared = lock_or_recover(&self.shared, "authorization pool");
 
         let mut cur_state =
             shared
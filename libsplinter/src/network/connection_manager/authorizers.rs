@@ -147,6 +147,36 @@ impl Authorizer for Authorizers {
             connection.remote_endpoint()
         )))
     }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    fn authorize_outbound_connection(
+        &self,
+        endpoint: &str,
+        connection_id: String,
+        connection: Box<dyn Connection>,
+        on_complete: AuthorizerCallback,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+    ) -> Result<(), AuthorizerError> {
+        for (match_prefix, authorizer) in &self.authorizers {
+            if connection.remote_endpoint().starts_with(match_prefix) {
+                return authorizer.authorize_outbound_connection(
+                    endpoint,
+                    connection_id,
+                    connection,
+                    on_complete,
+                    expected_authorization,
+                    local_authorization,
+                );
+            }
+        }
+
+        Err(AuthorizerError(format!(
+            "no authorizer found for {} ({})",
+            connection_id,
+            connection.remote_endpoint()
+        )))
+    }
 }
 
 #[cfg(test)]
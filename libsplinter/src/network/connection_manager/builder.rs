@@ -23,6 +23,8 @@ use crate::transport::matrix::{ConnectionMatrixLifeCycle, ConnectionMatrixSender
 use crate::transport::Transport;
 
 use super::error::ConnectionManagerError;
+#[cfg(feature = "auth-handshake-limits")]
+use super::InboundConnectionLimits;
 use super::{
     AuthResult, Authorizer, CmMessage, CmRequest, ConnectionManager, ConnectionManagerNotification,
     ConnectionManagerState, ConnectionMetadataExt, OutboundConnection, SubscriberMap,
@@ -38,6 +40,8 @@ pub struct ConnectionManagerBuilder<T, U> {
     transport: Option<Box<dyn Transport + Send>>,
     heartbeat_interval: u64,
     maximum_retry_frequency: u64,
+    #[cfg(feature = "auth-handshake-limits")]
+    inbound_limits: InboundConnectionLimits,
 }
 
 impl<T, U> Default for ConnectionManagerBuilder<T, U> {
@@ -49,6 +53,8 @@ impl<T, U> Default for ConnectionManagerBuilder<T, U> {
             transport: None,
             heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
             maximum_retry_frequency: DEFAULT_MAXIMUM_RETRY_FREQUENCY,
+            #[cfg(feature = "auth-handshake-limits")]
+            inbound_limits: InboundConnectionLimits::default(),
         }
     }
 }
@@ -119,6 +125,16 @@ where
         self
     }
 
+    /// Set the optional limits on inbound connections that have not completed authorization.
+    ///
+    /// Inbound connections that would exceed a limit are disconnected before authorization
+    /// starts. By default, no limits are applied.
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn with_inbound_limits(mut self, limits: InboundConnectionLimits) -> Self {
+        self.inbound_limits = limits;
+        self
+    }
+
     /// Create a started connection manager instance.
     ///
     /// This function creates and starts a `ConnectionManager` instance, which includes a
@@ -132,6 +148,8 @@ where
         let (sender, recv) = channel();
         let heartbeat = self.heartbeat_interval;
         let retry_frequency = self.maximum_retry_frequency;
+        #[cfg(feature = "auth-handshake-limits")]
+        let inbound_limits = self.inbound_limits.clone();
 
         let authorizer = self
             .authorizer
@@ -160,6 +178,8 @@ where
                     transport,
                     retry_frequency,
                 );
+                #[cfg(feature = "auth-handshake-limits")]
+                state.set_inbound_limits(inbound_limits);
                 let mut subscribers = SubscriberMap::new();
                 loop {
                     match recv.recv() {
@@ -0,0 +1,212 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Limits on inbound connections that have not yet completed authorization.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// The window over which inbound connection attempts from a single IP address are counted
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits applied to inbound connections before they are authorized.
+///
+/// Connections that would exceed a limit are disconnected before the authorization handshake is
+/// started.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InboundConnectionLimits {
+    /// The maximum number of inbound connections that may be waiting on authorization at once.
+    pub max_unauthorized_connections: Option<usize>,
+    /// The maximum number of inbound connections accepted from a single IP address per minute.
+    pub max_connections_per_ip_per_minute: Option<u32>,
+}
+
+/// The reason an inbound connection was rejected.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum LimitExceeded {
+    /// Too many inbound connections are waiting on authorization.
+    Unauthorized { limit: usize },
+    /// Too many connections have been attempted from the connection's IP address.
+    RateLimit { host: String, limit: u32 },
+}
+
+impl LimitExceeded {
+    /// Returns a short label for the limit, suitable for use in metrics.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            LimitExceeded::Unauthorized { .. } => "unauthorized_limit",
+            LimitExceeded::RateLimit { .. } => "rate_limit",
+        }
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitExceeded::Unauthorized { limit } => {
+                write!(f, "maximum of {} unauthorized connections reached", limit)
+            }
+            LimitExceeded::RateLimit { host, limit } => write!(
+                f,
+                "{} exceeded the limit of {} connections per minute",
+                host, limit
+            ),
+        }
+    }
+}
+
+/// Tracks inbound connections that are being authorized and recent connection attempts, in order
+/// to enforce `InboundConnectionLimits`.
+#[derive(Default)]
+pub(super) struct InboundLimiter {
+    limits: InboundConnectionLimits,
+    unauthorized: usize,
+    attempts: HashMap<String, VecDeque<Instant>>,
+}
+
+impl InboundLimiter {
+    pub fn new(limits: InboundConnectionLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    /// Checks whether a new inbound connection from the given endpoint may be authorized.
+    ///
+    /// If the connection is admitted, it is counted as unauthorized until `release` is called.
+    pub fn try_admit(&mut self, endpoint: &str, now: Instant) -> Result<(), LimitExceeded> {
+        if let Some(limit) = self.limits.max_connections_per_ip_per_minute {
+            // Drop attempts that have aged out of the window, so hosts that have stopped
+            // connecting are not tracked forever
+            self.attempts.retain(|_, attempts| {
+                while let Some(oldest) = attempts.front() {
+                    if now.duration_since(*oldest) < RATE_LIMIT_WINDOW {
+                        break;
+                    }
+                    attempts.pop_front();
+                }
+                !attempts.is_empty()
+            });
+
+            let host = endpoint_host(endpoint);
+            let attempts = self.attempts.entry(host.to_string()).or_default();
+            if attempts.len() >= limit as usize {
+                return Err(LimitExceeded::RateLimit {
+                    host: host.to_string(),
+                    limit,
+                });
+            }
+            attempts.push_back(now);
+        }
+
+        if let Some(limit) = self.limits.max_unauthorized_connections {
+            if self.unauthorized >= limit {
+                return Err(LimitExceeded::Unauthorized { limit });
+            }
+        }
+
+        self.unauthorized += 1;
+
+        Ok(())
+    }
+
+    /// Marks an admitted connection as no longer waiting on authorization.
+    pub fn release(&mut self) {
+        self.unauthorized = self.unauthorized.saturating_sub(1);
+    }
+
+    /// Returns the number of admitted connections that are waiting on authorization.
+    pub fn unauthorized(&self) -> usize {
+        self.unauthorized
+    }
+}
+
+/// Returns the host portion of an endpoint, e.g. `10.0.0.1` for `tcps://10.0.0.1:8044` or `::1`
+/// for `tcp://[::1]:8044`.
+fn endpoint_host(endpoint: &str) -> &str {
+    let address = endpoint
+        .split_once("://")
+        .map(|(_, address)| address)
+        .unwrap_or(endpoint);
+
+    let host = match address.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => address,
+    };
+
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify the host is extracted from endpoints with and without a protocol prefix and port,
+    /// including IPv6 addresses.
+    #[test]
+    fn test_endpoint_host() {
+        assert_eq!(endpoint_host("tcps://10.0.0.1:8044"), "10.0.0.1");
+        assert_eq!(endpoint_host("tcp://[::1]:8044"), "::1");
+        assert_eq!(endpoint_host("10.0.0.1:8044"), "10.0.0.1");
+        assert_eq!(endpoint_host("inproc://admin-service"), "admin-service");
+    }
+
+    /// Verify that no more than the maximum number of unauthorized connections are admitted, and
+    /// that releasing a connection allows another to be admitted.
+    #[test]
+    fn test_max_unauthorized_connections() {
+        let mut limiter = InboundLimiter::new(InboundConnectionLimits {
+            max_unauthorized_connections: Some(2),
+            max_connections_per_ip_per_minute: None,
+        });
+        let now = Instant::now();
+
+        assert!(limiter.try_admit("tcp://10.0.0.1:1000", now).is_ok());
+        assert!(limiter.try_admit("tcp://10.0.0.2:1000", now).is_ok());
+        assert_eq!(
+            limiter.try_admit("tcp://10.0.0.3:1000", now),
+            Err(LimitExceeded::Unauthorized { limit: 2 })
+        );
+
+        limiter.release();
+        assert_eq!(limiter.unauthorized(), 1);
+        assert!(limiter.try_admit("tcp://10.0.0.3:1000", now).is_ok());
+    }
+
+    /// Verify that connections from a single host are limited per minute, that other hosts are
+    /// unaffected, and that the host may connect again once its attempts leave the window.
+    #[test]
+    fn test_rate_limit_per_ip() {
+        let mut limiter = InboundLimiter::new(InboundConnectionLimits {
+            max_unauthorized_connections: None,
+            max_connections_per_ip_per_minute: Some(2),
+        });
+        let now = Instant::now();
+
+        assert!(limiter.try_admit("tcp://10.0.0.1:1000", now).is_ok());
+        assert!(limiter.try_admit("tcp://10.0.0.1:1001", now).is_ok());
+        assert_eq!(
+            limiter.try_admit("tcp://10.0.0.1:1002", now),
+            Err(LimitExceeded::RateLimit {
+                host: "10.0.0.1".into(),
+                limit: 2
+            })
+        );
+        assert!(limiter.try_admit("tcp://10.0.0.2:1000", now).is_ok());
+
+        let later = now + RATE_LIMIT_WINDOW;
+        assert!(limiter.try_admit("tcp://10.0.0.1:1003", later).is_ok());
+    }
+}
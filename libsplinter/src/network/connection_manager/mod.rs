@@ -15,6 +15,8 @@
 pub mod authorizers;
 mod builder;
 mod error;
+#[cfg(feature = "auth-handshake-limits")]
+mod limits;
 mod notification;
 
 use std::cmp::min;
@@ -27,6 +29,8 @@ use uuid::Uuid;
 
 pub use builder::ConnectionManagerBuilder;
 pub use error::{AuthorizerError, ConnectionManagerError};
+#[cfg(feature = "auth-handshake-limits")]
+pub use limits::InboundConnectionLimits;
 pub use notification::ConnectionManagerNotification;
 
 use crate::error::InternalError;
@@ -36,6 +40,9 @@ use crate::threading::pacemaker;
 use crate::transport::matrix::{ConnectionMatrixLifeCycle, ConnectionMatrixSender};
 use crate::transport::{ConnectError, Connection, Transport};
 
+#[cfg(feature = "auth-handshake-limits")]
+use self::limits::InboundLimiter;
+
 const INITIAL_RETRY_FREQUENCY: u64 = 10;
 
 pub type AuthorizerCallback =
@@ -50,6 +57,29 @@ pub trait Authorizer {
         expected_authorization: Option<ConnectionAuthorizationType>,
         local_authorization: Option<ConnectionAuthorizationType>,
     ) -> Result<(), AuthorizerError>;
+
+    /// Authorize an outbound connection that was made by dialing the given endpoint.
+    ///
+    /// Authorizers may use the endpoint, as it was dialed, to bind their authorization to it. By
+    /// default, this delegates to `authorize_connection`.
+    #[cfg(feature = "challenge-endpoint-binding")]
+    fn authorize_outbound_connection(
+        &self,
+        _endpoint: &str,
+        connection_id: String,
+        connection: Box<dyn Connection>,
+        on_complete: AuthorizerCallback,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+    ) -> Result<(), AuthorizerError> {
+        self.authorize_connection(
+            connection_id,
+            connection,
+            on_complete,
+            expected_authorization,
+            local_authorization,
+        )
+    }
 }
 
 pub enum AuthorizationResult {
@@ -511,6 +541,8 @@ where
     matrix_sender: U,
     transport: Box<dyn Transport>,
     maximum_retry_frequency: u64,
+    #[cfg(feature = "auth-handshake-limits")]
+    inbound_limiter: InboundLimiter,
 }
 
 impl<T, U> ConnectionManagerState<T, U>
@@ -530,9 +562,17 @@ where
             transport,
             connections: HashMap::new(),
             maximum_retry_frequency,
+            #[cfg(feature = "auth-handshake-limits")]
+            inbound_limiter: InboundLimiter::default(),
         }
     }
 
+    /// Sets the limits applied to inbound connections before they are authorized.
+    #[cfg(feature = "auth-handshake-limits")]
+    fn set_inbound_limits(&mut self, limits: InboundConnectionLimits) {
+        self.inbound_limiter = InboundLimiter::new(limits);
+    }
+
     /// Adds a new connection as an inbound connection.
     fn add_inbound_connection(
         &mut self,
@@ -544,6 +584,40 @@ where
         let endpoint = connection.remote_endpoint();
         let id = Uuid::new_v4().to_string();
 
+        #[cfg(feature = "auth-handshake-limits")]
+        {
+            if let Err(exceeded) = self.inbound_limiter.try_admit(&endpoint, Instant::now()) {
+                warn!(
+                    "Rejecting inbound connection from {}: {}",
+                    endpoint, exceeded
+                );
+                counter!(
+                    "splinter.connection_manager.inbound_rejected",
+                    1,
+                    "reason" => exceeded.reason()
+                );
+
+                let mut connection = connection;
+                if let Err(err) = connection.disconnect() {
+                    debug!("Unable to disconnect rejected connection: {}", err);
+                }
+
+                if reply_sender
+                    .send(Err(ConnectionManagerError::connection_creation_error(
+                        &format!("Inbound connection rejected: {}", exceeded),
+                    )))
+                    .is_err()
+                {
+                    warn!("connector dropped before receiving result of add connection");
+                }
+                return;
+            }
+            gauge!(
+                "splinter.connection_manager.unauthorized_inbound",
+                self.inbound_limiter.unauthorized() as f64
+            );
+        }
+
         // add the connection to the authorization pool.
         let auth_endpoint = endpoint;
         if let Err(err) = authorizer.authorize_connection(
@@ -560,6 +634,9 @@ where
             None,
             None,
         ) {
+            #[cfg(feature = "auth-handshake-limits")]
+            self.inbound_limiter.release();
+
             if reply_sender
                 .send(Err(ConnectionManagerError::connection_creation_error(
                     &err.to_string(),
@@ -630,20 +707,32 @@ where
             Ok(connection) => {
                 // add the connection to the authorization pool.
                 let auth_endpoint = outbound.endpoint.to_string();
-                if let Err(err) = authorizer.authorize_connection(
+                let on_complete: AuthorizerCallback = Box::new(move |auth_result| {
+                    internal_sender
+                        .send(CmMessage::AuthResult(AuthResult::Outbound {
+                            endpoint: auth_endpoint.clone(),
+                            auth_result,
+                        }))
+                        .map_err(Box::from)
+                });
+                #[cfg(feature = "challenge-endpoint-binding")]
+                let auth_result = authorizer.authorize_outbound_connection(
+                    &outbound.endpoint,
+                    outbound.connection_id,
+                    connection,
+                    on_complete,
+                    outbound.expected_authorization.clone(),
+                    outbound.local_authorization.clone(),
+                );
+                #[cfg(not(feature = "challenge-endpoint-binding"))]
+                let auth_result = authorizer.authorize_connection(
                     outbound.connection_id,
                     connection,
-                    Box::new(move |auth_result| {
-                        internal_sender
-                            .send(CmMessage::AuthResult(AuthResult::Outbound {
-                                endpoint: auth_endpoint.clone(),
-                                auth_result,
-                            }))
-                            .map_err(Box::from)
-                    }),
+                    on_complete,
                     outbound.expected_authorization.clone(),
                     outbound.local_authorization.clone(),
-                ) {
+                );
+                if let Err(err) = auth_result {
                     if reply_sender
                         .send(Err(ConnectionManagerError::connection_creation_error(
                             &err.to_string(),
@@ -772,6 +861,15 @@ where
         auth_result: AuthorizationResult,
         subscribers: &mut SubscriberMap,
     ) {
+        #[cfg(feature = "auth-handshake-limits")]
+        {
+            self.inbound_limiter.release();
+            gauge!(
+                "splinter.connection_manager.unauthorized_inbound",
+                self.inbound_limiter.unauthorized() as f64
+            );
+        }
+
         match auth_result {
             AuthorizationResult::Authorized {
                 connection_id,
@@ -906,20 +1004,32 @@ where
                 })?;
 
             let auth_endpoint = endpoint.to_string();
-            if let Err(err) = authorizer.authorize_connection(
+            let on_complete: AuthorizerCallback = Box::new(move |auth_result| {
+                internal_sender
+                    .send(CmMessage::AuthResult(AuthResult::Outbound {
+                        endpoint: auth_endpoint.clone(),
+                        auth_result,
+                    }))
+                    .map_err(Box::from)
+            });
+            #[cfg(feature = "challenge-endpoint-binding")]
+            let auth_result = authorizer.authorize_outbound_connection(
+                endpoint,
                 meta.connection_id().into(),
                 connection,
-                Box::new(move |auth_result| {
-                    internal_sender
-                        .send(CmMessage::AuthResult(AuthResult::Outbound {
-                            endpoint: auth_endpoint.clone(),
-                            auth_result,
-                        }))
-                        .map_err(Box::from)
-                }),
+                on_complete,
+                meta.extended_metadata.expected_authorization(),
+                Some(meta.extended_metadata.local_authorization()),
+            );
+            #[cfg(not(feature = "challenge-endpoint-binding"))]
+            let auth_result = authorizer.authorize_connection(
+                meta.connection_id().into(),
+                connection,
+                on_complete,
                 meta.extended_metadata.expected_authorization(),
                 Some(meta.extended_metadata.local_authorization()),
-            ) {
+            );
+            if let Err(err) = auth_result {
                 error!(
                     "Error authorizing {} ({}): {}",
                     endpoint,
@@ -13,11 +13,12 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::Hash;
-use std::sync::mpsc::{channel, Receiver, RecvError, Sender};
+use std::sync::mpsc::{channel, Receiver, RecvError, Sender, TryRecvError};
 
 use super::{Dispatcher, PeerId};
 
@@ -41,6 +42,24 @@ where
     Shutdown,
 }
 
+/// The default number of control messages dispatched for each data message, when both kinds of
+/// messages are waiting.
+const DEFAULT_CONTROL_WEIGHT: usize = 8;
+
+/// The priority lane a message is queued in before it is dispatched.
+///
+/// Control messages (for example, admin and consensus messages) are dispatched ahead of data
+/// messages, so they are not blocked behind bulk service traffic. Data messages are still
+/// dispatched regularly, according to the loop's control weight, so they are never starved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "dispatch-priority-lanes"), allow(dead_code))]
+pub enum MessagePriority {
+    Control,
+    Data,
+}
+
+type PriorityClassifier<MT> = Box<dyn Fn(&MT) -> MessagePriority + Send>;
+
 /// Errors that may occur during the operation of the Dispatch Loop.
 #[derive(Debug)]
 pub struct DispatchLoopError(String);
@@ -65,6 +84,8 @@ where
         DispatchMessageReceiver<MT, Source>,
     )>,
     thread_name: Option<String>,
+    priority_classifier: Option<PriorityClassifier<MT>>,
+    control_weight: Option<usize>,
 }
 
 impl<MT, Source> DispatchLoopBuilder<MT, Source>
@@ -77,6 +98,8 @@ where
             dispatcher: None,
             channel: None,
             thread_name: None,
+            priority_classifier: None,
+            control_weight: None,
         }
     }
 
@@ -101,6 +124,26 @@ where
         self
     }
 
+    /// Set the function used to determine the priority lane of each message, based on its
+    /// message type. If not set, all messages are data messages and are dispatched in the order
+    /// they are received.
+    #[cfg(feature = "dispatch-priority-lanes")]
+    pub fn with_priority_classifier<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&MT) -> MessagePriority + Send + 'static,
+    {
+        self.priority_classifier = Some(Box::new(classifier));
+        self
+    }
+
+    /// Set the number of control messages that are dispatched for each data message when both
+    /// kinds of messages are waiting; the default is 8. A weight of 0 is treated as 1.
+    #[cfg(feature = "dispatch-priority-lanes")]
+    pub fn with_control_weight(mut self, control_weight: usize) -> Self {
+        self.control_weight = Some(control_weight);
+        self
+    }
+
     pub fn build(mut self) -> Result<DispatchLoop<MT, Source>, String> {
         let (tx, rx) = self.channel.take().unwrap_or_else(dispatch_channel);
 
@@ -113,11 +156,49 @@ where
             .thread_name
             .unwrap_or_else(|| format!("DispatchLoop({})", std::any::type_name::<MT>()));
 
+        let mut lanes = PriorityLanes::new(
+            self.priority_classifier.take(),
+            self.control_weight.unwrap_or(DEFAULT_CONTROL_WEIGHT),
+        );
+
+        // underscored since it is only used by the metrics macro, which is a no-op when the tap
+        // feature is disabled
+        let _loop_name = thread_name.clone();
+
         let join_handle = std::thread::Builder::new()
             .name(thread_name)
             .spawn(move || loop {
-                match rx.receiver.recv() {
-                    Ok(DispatchMessage::Message {
+                // Wait for a message if none are queued; otherwise, queue any messages that have
+                // arrived since the last dispatch, so they are prioritized against the backlog.
+                if lanes.is_empty() {
+                    match rx.receiver.recv() {
+                        Ok(msg) => lanes.push(msg),
+                        Err(RecvError) => {
+                            error!("Received error from receiver");
+                            break;
+                        }
+                    }
+                }
+                while !lanes.is_shutdown() {
+                    match rx.receiver.try_recv() {
+                        Ok(msg) => lanes.push(msg),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            error!("Received error from receiver");
+                            break;
+                        }
+                    }
+                }
+
+                let msg = lanes.pop();
+                gauge!(
+                    "splinter.dispatch.queue_depth",
+                    lanes.len() as f64,
+                    "dispatch_loop" => _loop_name.clone()
+                );
+
+                match msg {
+                    Some(DispatchMessage::Message {
                         message_type,
                         message_bytes,
                         source_id,
@@ -132,7 +213,7 @@ where
                             warn!("Unable to dispatch message: {:?}", err);
                         }
                     }
-                    Ok(DispatchMessage::Message {
+                    Some(DispatchMessage::Message {
                         message_type,
                         message_bytes,
                         source_id,
@@ -144,14 +225,11 @@ where
                             warn!("Unable to dispatch message: {:?}", err);
                         }
                     }
-                    Ok(DispatchMessage::Shutdown) => {
+                    Some(DispatchMessage::Shutdown) => {
                         debug!("Received shutdown signal");
                         break;
                     }
-                    Err(RecvError) => {
-                        error!("Received error from receiver");
-                        break;
-                    }
+                    None => (),
                 }
             });
 
@@ -165,6 +243,90 @@ where
     }
 }
 
+/// Queues of messages waiting to be dispatched, one per priority.
+///
+/// Messages within a lane are dispatched in the order they were received. A shutdown signal is
+/// returned once all messages received before it have been dispatched.
+struct PriorityLanes<MT, Source>
+where
+    MT: Any + Hash + Eq + Debug + Clone,
+{
+    classifier: Option<PriorityClassifier<MT>>,
+    control_weight: usize,
+    control: VecDeque<DispatchMessage<MT, Source>>,
+    data: VecDeque<DispatchMessage<MT, Source>>,
+    shutdown: bool,
+    // The number of control messages dispatched since the last data message
+    control_streak: usize,
+}
+
+impl<MT, Source> PriorityLanes<MT, Source>
+where
+    MT: Any + Hash + Eq + Debug + Clone,
+{
+    fn new(classifier: Option<PriorityClassifier<MT>>, control_weight: usize) -> Self {
+        Self {
+            classifier,
+            control_weight: std::cmp::max(control_weight, 1),
+            control: VecDeque::new(),
+            data: VecDeque::new(),
+            shutdown: false,
+            control_streak: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.control.is_empty() && self.data.is_empty() && !self.shutdown
+    }
+
+    fn is_shutdown(&self) -> bool {
+        self.shutdown
+    }
+
+    /// Returns the number of messages waiting to be dispatched.
+    #[cfg(feature = "tap")]
+    fn len(&self) -> usize {
+        self.control.len() + self.data.len()
+    }
+
+    fn push(&mut self, msg: DispatchMessage<MT, Source>) {
+        match &msg {
+            DispatchMessage::Message { message_type, .. } => {
+                match self
+                    .classifier
+                    .as_ref()
+                    .map(|classify| classify(message_type))
+                {
+                    Some(MessagePriority::Control) => self.control.push_back(msg),
+                    Some(MessagePriority::Data) | None => self.data.push_back(msg),
+                }
+            }
+            DispatchMessage::Shutdown => self.shutdown = true,
+        }
+    }
+
+    fn pop(&mut self) -> Option<DispatchMessage<MT, Source>> {
+        let take_data = !self.data.is_empty()
+            && (self.control.is_empty() || self.control_streak >= self.control_weight);
+
+        let msg = if take_data {
+            self.control_streak = 0;
+            self.data.pop_front()
+        } else {
+            self.control_streak = self.control_streak.saturating_add(1);
+            self.control.pop_front()
+        };
+
+        match msg {
+            None if self.shutdown => {
+                self.shutdown = false;
+                Some(DispatchMessage::Shutdown)
+            }
+            msg => msg,
+        }
+    }
+}
+
 /// The Dispatch Loop
 ///
 /// The dispatch loop processes messages that are pulled from a `Receiver<DispatchMessage>` and
@@ -303,3 +465,80 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(message_type: u8) -> DispatchMessage<u8, String> {
+        DispatchMessage::Message {
+            message_type,
+            message_bytes: vec![],
+            source_id: "peer".to_string(),
+            parent_context: None,
+        }
+    }
+
+    fn pop_type(lanes: &mut PriorityLanes<u8, String>) -> Option<u8> {
+        match lanes.pop() {
+            Some(DispatchMessage::Message { message_type, .. }) => Some(message_type),
+            Some(DispatchMessage::Shutdown) => panic!("Unexpected shutdown"),
+            None => None,
+        }
+    }
+
+    /// Verify that without a classifier, messages are returned in the order they were pushed.
+    #[test]
+    fn lanes_without_classifier() {
+        let mut lanes = PriorityLanes::new(None, DEFAULT_CONTROL_WEIGHT);
+        for message_type in 0..4 {
+            lanes.push(message(message_type));
+        }
+
+        assert_eq!(
+            (0..4).map(|_| pop_type(&mut lanes)).collect::<Vec<_>>(),
+            vec![Some(0), Some(1), Some(2), Some(3)]
+        );
+        assert_eq!(pop_type(&mut lanes), None);
+    }
+
+    /// Verify that control messages are returned ahead of data messages, but that a data message
+    /// is returned after `control_weight` control messages.
+    ///
+    /// Even message types are control messages, odd message types are data messages.
+    #[test]
+    fn lanes_weighted_dequeue() {
+        let classifier: PriorityClassifier<u8> = Box::new(|message_type| {
+            if message_type % 2 == 0 {
+                MessagePriority::Control
+            } else {
+                MessagePriority::Data
+            }
+        });
+        let mut lanes = PriorityLanes::new(Some(classifier), 2);
+
+        for message_type in &[1, 3, 0, 2, 4, 6] {
+            lanes.push(message(*message_type));
+        }
+
+        assert_eq!(
+            (0..6).map(|_| pop_type(&mut lanes)).collect::<Vec<_>>(),
+            vec![Some(0), Some(2), Some(1), Some(4), Some(6), Some(3)]
+        );
+        assert_eq!(pop_type(&mut lanes), None);
+    }
+
+    /// Verify that a shutdown signal is returned only after the queued messages.
+    #[test]
+    fn lanes_shutdown_after_messages() {
+        let mut lanes = PriorityLanes::new(None, DEFAULT_CONTROL_WEIGHT);
+        lanes.push(message(0));
+        lanes.push(DispatchMessage::Shutdown);
+
+        assert!(!lanes.is_empty());
+        assert!(lanes.is_shutdown());
+        assert_eq!(pop_type(&mut lanes), Some(0));
+        assert!(matches!(lanes.pop(), Some(DispatchMessage::Shutdown)));
+        assert!(lanes.pop().is_none());
+    }
+}
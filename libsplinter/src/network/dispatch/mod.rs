@@ -26,6 +26,8 @@ use std::fmt::Debug;
 use std::hash::Hash;
 
 pub use context::MessageContext;
+#[cfg(feature = "dispatch-priority-lanes")]
+pub use r#loop::MessagePriority;
 pub use r#loop::{
     dispatch_channel, DispatchLoop, DispatchLoopBuilder, DispatchLoopError,
     DispatchMessageReceiver, DispatchMessageSender,
@@ -33,6 +35,8 @@ pub use r#loop::{
 
 use crate::error::InternalError;
 use crate::peer::PeerTokenPair;
+#[cfg(feature = "tracing")]
+use crate::tracing::Span;
 
 /// A wrapper for a PeerId.
 ///
@@ -356,7 +360,14 @@ where
     }
 
     fn execute(&self, ctx: MessageContext<Source, MT>) -> Result<(), DispatchError> {
-        self.handlers
+        #[cfg(feature = "tracing")]
+        let mut span = Span::new("dispatch")
+            .with_attribute("message_type", &format!("{:?}", ctx.message_type()));
+        #[cfg(feature = "tracing")]
+        let _guard = span.enter();
+
+        let result = self
+            .handlers
             .get(ctx.message_type())
             .ok_or_else(|| {
                 DispatchError::UnknownMessageType(format!(
@@ -364,7 +375,14 @@ where
                     ctx.message_type(),
                 ))
             })
-            .and_then(|handler| handler.handle(ctx.message_bytes(), &ctx, &*self.network_sender))
+            .and_then(|handler| handler.handle(ctx.message_bytes(), &ctx, &*self.network_sender));
+
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            span.set_error(&err.to_string());
+        }
+
+        result
     }
 }
 
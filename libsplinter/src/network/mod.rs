@@ -14,9 +14,13 @@
 
 //! Network connection management and message dispatching framework.
 
+#[cfg(feature = "network-audit")]
+pub mod audit;
 pub mod auth;
 pub mod connection_manager;
 pub mod dispatch;
 pub mod handlers;
+#[cfg(feature = "peer-ping")]
+pub mod ping;
 #[cfg(feature = "runtime-service")]
 pub(crate) mod reply;
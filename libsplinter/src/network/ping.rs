@@ -0,0 +1,359 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Measures the round-trip time to a peer using application-level ping messages.
+//!
+//! A `PeerPinger` sends a `NetworkPing` to a connected peer and waits for the `NetworkPong` that
+//! carries the same nonce. The peer's `NetworkPingHandler` replies to the ping, and the local
+//! `NetworkPongHandler` hands the reply back to the waiting `PeerPinger`.
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
+use crate::peer::interconnect::NetworkMessageSender;
+use crate::peer::{PeerManagerConnector, PeerTokenPair};
+use crate::protocol::network::{NetworkMessage, NetworkPing, NetworkPong};
+use crate::protos::network;
+use crate::protos::prelude::*;
+
+/// The pings awaiting a pong, by nonce, along with the peer each ping was sent to.
+type PendingPings = Arc<Mutex<HashMap<u64, (PeerTokenPair, Sender<Instant>)>>>;
+
+/// Errors that could be raised when pinging a peer
+#[derive(Debug, PartialEq, Eq)]
+pub enum PeerPingError {
+    /// The peer is not connected
+    NotConnected(String),
+    /// The peer did not reply before the timeout expired
+    Timeout(String),
+    /// Internal error
+    Internal(String),
+}
+
+impl error::Error for PeerPingError {}
+
+impl fmt::Display for PeerPingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeerPingError::NotConnected(peer_id) => write!(f, "Peer {} is not connected", peer_id),
+            PeerPingError::Timeout(peer_id) => {
+                write!(f, "Peer {} did not reply to the ping in time", peer_id)
+            }
+            PeerPingError::Internal(msg) => write!(f, "Received internal error: {}", msg),
+        }
+    }
+}
+
+/// Sends pings to connected peers and measures the time until each peer replies.
+#[derive(Clone)]
+pub struct PeerPinger {
+    connector: PeerManagerConnector,
+    network_sender: NetworkMessageSender,
+    pending: PendingPings,
+    next_nonce: Arc<AtomicU64>,
+}
+
+impl PeerPinger {
+    /// Creates a new `PeerPinger`.
+    ///
+    /// # Arguments
+    ///
+    /// * `connector` - Used to look up the connected peers
+    /// * `network_sender` - Used to send the pings
+    pub fn new(connector: PeerManagerConnector, network_sender: NetworkMessageSender) -> Self {
+        Self {
+            connector,
+            network_sender,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            next_nonce: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns the handler that delivers pongs to this pinger. It must be added to the network
+    /// dispatcher for pings to receive replies.
+    pub fn pong_handler(&self) -> NetworkPongHandler {
+        NetworkPongHandler {
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Pings a connected peer and returns the round-trip time.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer's ID, as returned by `PeerAuthorizationToken::id_as_string`
+    /// * `timeout` - How long to wait for the peer to reply
+    pub fn ping(&self, peer_id: &str, timeout: Duration) -> Result<Duration, PeerPingError> {
+        let recipient = self
+            .connector
+            .connection_ids()
+            .map_err(|err| PeerPingError::Internal(err.to_string()))?
+            .keys()
+            .find(|peer| peer.peer_id().id_as_string() == peer_id)
+            .cloned()
+            .ok_or_else(|| PeerPingError::NotConnected(peer_id.to_string()))?;
+
+        let nonce = self.next_nonce.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = channel();
+        self.pending
+            .lock()
+            .map_err(|_| PeerPingError::Internal("Pending pings lock was poisoned".into()))?
+            .insert(nonce, (recipient.clone(), sender));
+
+        let ping_bytes = IntoBytes::<network::NetworkMessage>::into_bytes(
+            NetworkMessage::NetworkPing(NetworkPing { nonce }),
+        )
+        .map_err(|err| PeerPingError::Internal(format!("Unable to serialize ping: {}", err)))?;
+
+        let sent_at = Instant::now();
+        let result = match self.network_sender.send(recipient, ping_bytes) {
+            Ok(()) => match receiver.recv_timeout(timeout) {
+                Ok(received_at) => Ok(received_at.saturating_duration_since(sent_at)),
+                Err(RecvTimeoutError::Timeout) => Err(PeerPingError::Timeout(peer_id.to_string())),
+                Err(RecvTimeoutError::Disconnected) => {
+                    Err(PeerPingError::Internal("Pong sender was dropped".into()))
+                }
+            },
+            Err(_) => Err(PeerPingError::Internal(format!(
+                "Unable to send ping to peer {}",
+                peer_id
+            ))),
+        };
+
+        // The ping is no longer pending, whether or not the peer replied
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&nonce);
+        }
+
+        result
+    }
+}
+
+/// Replies to `NetworkPing` messages with a `NetworkPong` carrying the same nonce.
+#[derive(Default)]
+pub struct NetworkPingHandler;
+
+impl Handler for NetworkPingHandler {
+    type Source = PeerId;
+    type MessageType = network::NetworkMessageType;
+    type Message = network::NetworkPing;
+
+    fn match_type(&self) -> Self::MessageType {
+        network::NetworkMessageType::NETWORK_PING
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        let ping = NetworkPing::from_proto(msg)?;
+        trace!(
+            "Received ping {} from {}",
+            ping.nonce,
+            context.source_peer_id()
+        );
+
+        let pong_bytes = IntoBytes::<network::NetworkMessage>::into_bytes(
+            NetworkMessage::NetworkPong(NetworkPong { nonce: ping.nonce }),
+        )
+        .map_err(|err| {
+            DispatchError::SerializationError(format!("cannot get bytes of NetworkPong: {}", err))
+        })?;
+
+        sender
+            .send(context.source_peer_id().clone(), pong_bytes)
+            .map_err(|(recipient, payload)| {
+                DispatchError::NetworkSendError((recipient.into(), payload))
+            })?;
+        Ok(())
+    }
+}
+
+/// Delivers `NetworkPong` messages to the `PeerPinger` that sent the matching ping.
+pub struct NetworkPongHandler {
+    pending: PendingPings,
+}
+
+impl Handler for NetworkPongHandler {
+    type Source = PeerId;
+    type MessageType = network::NetworkMessageType;
+    type Message = network::NetworkPong;
+
+    fn match_type(&self) -> Self::MessageType {
+        network::NetworkMessageType::NETWORK_PONG
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        _sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        let received_at = Instant::now();
+        let pong = NetworkPong::from_proto(msg)?;
+
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|_| DispatchError::HandleError("Pending pings lock was poisoned".into()))?;
+        match pending.get(&pong.nonce) {
+            // Only the peer that was pinged may answer the ping
+            Some((peer, _)) if peer == &**context.source_peer_id() => {
+                if let Some((_, sender)) = pending.remove(&pong.nonce) {
+                    // The pinger may have stopped waiting, in which case the pong is dropped
+                    let _ = sender.send(received_at);
+                }
+            }
+            _ => debug!(
+                "Ignoring pong {} from {}, which does not match a pending ping",
+                pong.nonce,
+                context.source_peer_id()
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use protobuf::Message;
+
+    use std::collections::VecDeque;
+
+    use crate::network::dispatch::Dispatcher;
+    use crate::peer::PeerAuthorizationToken;
+    use crate::protos::network::NetworkMessageType;
+
+    /// Verifies that the ping handler replies to the sender of a ping with a pong carrying the
+    /// same nonce.
+    #[test]
+    fn test_ping_handler_replies_with_pong() {
+        let network_sender = MockSender::new();
+        let mut dispatcher: Dispatcher<NetworkMessageType> =
+            Dispatcher::new(Box::new(network_sender.clone()));
+        dispatcher.set_handler(Box::new(NetworkPingHandler::default()));
+
+        let mut ping = network::NetworkPing::new();
+        ping.set_nonce(7);
+
+        dispatcher
+            .dispatch(
+                peer("OTHER_PEER").into(),
+                &NetworkMessageType::NETWORK_PING,
+                ping.write_to_bytes().expect("Unable to serialize ping"),
+            )
+            .expect("Unable to dispatch ping");
+
+        let (recipient, message_bytes) = network_sender
+            .next_outbound()
+            .expect("Unable to get expected message");
+        assert_eq!(*recipient, peer("OTHER_PEER"));
+
+        let network_msg: network::NetworkMessage =
+            Message::parse_from_bytes(&message_bytes).expect("Unable to parse message");
+        assert_eq!(
+            network_msg.get_message_type(),
+            NetworkMessageType::NETWORK_PONG
+        );
+        let pong: network::NetworkPong =
+            Message::parse_from_bytes(network_msg.get_payload()).expect("Unable to parse pong");
+        assert_eq!(pong.get_nonce(), 7);
+    }
+
+    /// Verifies that the pong handler delivers a pong to the pending ping with the same nonce,
+    /// and ignores a pong from a peer other than the one that was pinged.
+    #[test]
+    fn test_pong_handler_delivers_pong() {
+        let pending: PendingPings = Arc::new(Mutex::new(HashMap::new()));
+        let (sender, receiver) = channel();
+        pending
+            .lock()
+            .expect("lock was poisoned")
+            .insert(3, (peer("OTHER_PEER"), sender));
+
+        let mut dispatcher: Dispatcher<NetworkMessageType> =
+            Dispatcher::new(Box::new(MockSender::new()));
+        dispatcher.set_handler(Box::new(NetworkPongHandler {
+            pending: pending.clone(),
+        }));
+
+        let mut pong = network::NetworkPong::new();
+        pong.set_nonce(3);
+        let pong_bytes = pong.write_to_bytes().expect("Unable to serialize pong");
+
+        dispatcher
+            .dispatch(
+                peer("THIRD_PEER").into(),
+                &NetworkMessageType::NETWORK_PONG,
+                pong_bytes.clone(),
+            )
+            .expect("Unable to dispatch pong");
+        assert!(receiver.try_recv().is_err());
+
+        dispatcher
+            .dispatch(
+                peer("OTHER_PEER").into(),
+                &NetworkMessageType::NETWORK_PONG,
+                pong_bytes,
+            )
+            .expect("Unable to dispatch pong");
+        assert!(receiver.try_recv().is_ok());
+        assert!(pending.lock().expect("lock was poisoned").is_empty());
+    }
+
+    fn peer(peer_id: &str) -> PeerTokenPair {
+        PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id(peer_id),
+            PeerAuthorizationToken::from_peer_id("TestPeer"),
+        )
+    }
+
+    #[derive(Clone)]
+    struct MockSender {
+        outbound: Arc<Mutex<VecDeque<(PeerId, Vec<u8>)>>>,
+    }
+
+    impl MockSender {
+        fn new() -> Self {
+            Self {
+                outbound: Arc::new(Mutex::new(VecDeque::new())),
+            }
+        }
+
+        fn next_outbound(&self) -> Option<(PeerId, Vec<u8>)> {
+            self.outbound.lock().expect("lock was poisoned").pop_front()
+        }
+    }
+
+    impl MessageSender<PeerId> for MockSender {
+        fn send(&self, id: PeerId, message: Vec<u8>) -> Result<(), (PeerId, Vec<u8>)> {
+            self.outbound
+                .lock()
+                .expect("lock was poisoned")
+                .push_back((id, message));
+
+            Ok(())
+        }
+    }
+}
@@ -18,6 +18,7 @@ use std::sync::{mpsc::channel, Arc, Mutex};
 
 use crate::channel::{Receiver, RecvError, Sender};
 use crate::network::dispatch::FromMessageBytes;
+use crate::threading::lock::lock_or_recover;
 
 pub type MessageResult<MessageType> = Result<(MessageType, Vec<u8>), RecvError>;
 
@@ -51,7 +52,8 @@ where
     ) -> Result<(), RouteError> {
         match message_result {
             Ok((message_type, message)) => {
-                let mut expected_replies = self.expected_replies.lock().expect("Lock was poisoned");
+                let mut expected_replies =
+                    lock_or_recover(&self.expected_replies, "expected replies");
                 if let Some(sender) = expected_replies.remove(correlation_id) {
                     sender
                         .send(Ok((message_type, message)))
@@ -63,7 +65,8 @@ where
                 }
             }
             Err(RecvError { error }) => {
-                let mut expected_replies = self.expected_replies.lock().expect("Lock was poisoned");
+                let mut expected_replies =
+                    lock_or_recover(&self.expected_replies, "expected replies");
                 for (_, sender) in expected_replies.iter_mut() {
                     sender
                         .send(Err(RecvError {
@@ -78,7 +81,7 @@ where
 
     pub fn expect_reply(&self, correlation_id: String) -> MessageFuture<MessageType> {
         let (expect_tx, expect_rx) = channel();
-        let mut expected_replies = self.expected_replies.lock().unwrap();
+        let mut expected_replies = lock_or_recover(&self.expected_replies, "expected replies");
         expected_replies.insert(correlation_id, Box::new(expect_tx));
 
         MessageFuture::new(Box::new(expect_rx))
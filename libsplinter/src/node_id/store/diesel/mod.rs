@@ -18,6 +18,8 @@ mod models;
 mod operations;
 mod schema;
 
+#[cfg(feature = "node-metadata")]
+use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
 
 use diesel::r2d2::{ConnectionManager, Pool};
@@ -31,6 +33,10 @@ use models::NodeID;
 use operations::{
     get_node_id::NodeIdGetOperation, set_node_id::NodeIdSetOperation, NodeIdOperations,
 };
+#[cfg(feature = "node-metadata")]
+use operations::{
+    get_node_metadata::NodeMetadataGetOperation, set_node_metadata::NodeMetadataSetOperation,
+};
 
 /// Database backed [NodeIdStore] implementation.
 pub struct DieselNodeIdStore<Conn: diesel::Connection + 'static> {
@@ -74,6 +80,30 @@ impl NodeIdStore for DieselNodeIdStore<diesel::pg::PgConnection> {
         self.pool
             .execute_write(|conn| NodeIdOperations::new(conn).set_node_id(new_id))
     }
+
+    #[cfg(feature = "node-metadata")]
+    fn get_display_name(&self) -> Result<Option<String>, NodeIdStoreError> {
+        self.pool
+            .execute_read(|conn| NodeIdOperations::new(conn).get_display_name())
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn set_display_name(&self, display_name: String) -> Result<(), NodeIdStoreError> {
+        self.pool
+            .execute_write(|conn| NodeIdOperations::new(conn).set_display_name(display_name))
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn get_metadata(&self) -> Result<BTreeMap<String, String>, NodeIdStoreError> {
+        self.pool
+            .execute_read(|conn| NodeIdOperations::new(conn).get_metadata())
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn set_metadata(&self, metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError> {
+        self.pool
+            .execute_write(|conn| NodeIdOperations::new(conn).set_metadata(metadata))
+    }
 }
 #[cfg(feature = "sqlite")]
 impl NodeIdStore for DieselNodeIdStore<diesel::sqlite::SqliteConnection> {
@@ -85,4 +115,94 @@ impl NodeIdStore for DieselNodeIdStore<diesel::sqlite::SqliteConnection> {
         self.pool
             .execute_write(|conn| NodeIdOperations::new(conn).set_node_id(new_id))
     }
+
+    #[cfg(feature = "node-metadata")]
+    fn get_display_name(&self) -> Result<Option<String>, NodeIdStoreError> {
+        self.pool
+            .execute_read(|conn| NodeIdOperations::new(conn).get_display_name())
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn set_display_name(&self, display_name: String) -> Result<(), NodeIdStoreError> {
+        self.pool
+            .execute_write(|conn| NodeIdOperations::new(conn).set_display_name(display_name))
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn get_metadata(&self) -> Result<BTreeMap<String, String>, NodeIdStoreError> {
+        self.pool
+            .execute_read(|conn| NodeIdOperations::new(conn).get_metadata())
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn set_metadata(&self, metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError> {
+        self.pool
+            .execute_write(|conn| NodeIdOperations::new(conn).set_metadata(metadata))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite", feature = "node-metadata"))]
+mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that the display name and metadata can be set and replaced in a SQLite-backed store.
+    #[test]
+    fn sqlite_display_name_and_metadata() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselNodeIdStore::new(pool);
+
+        assert_eq!(store.get_display_name().expect("Failed to get name"), None);
+        assert!(store
+            .get_metadata()
+            .expect("Failed to get metadata")
+            .is_empty());
+
+        store
+            .set_display_name("Acme Node".into())
+            .expect("Failed to set name");
+        store
+            .set_display_name("Acme Node 2".into())
+            .expect("Failed to replace name");
+        assert_eq!(
+            store.get_display_name().expect("Failed to get name"),
+            Some("Acme Node 2".to_string())
+        );
+
+        let mut metadata = BTreeMap::new();
+        metadata.insert("region".to_string(), "us-east".to_string());
+        metadata.insert("company".to_string(), "Acme".to_string());
+        store
+            .set_metadata(metadata.clone())
+            .expect("Failed to set metadata");
+        assert_eq!(
+            store.get_metadata().expect("Failed to get metadata"),
+            metadata
+        );
+
+        metadata.remove("company");
+        store
+            .set_metadata(metadata.clone())
+            .expect("Failed to replace metadata");
+        assert_eq!(
+            store.get_metadata().expect("Failed to get metadata"),
+            metadata
+        );
+    }
+
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
 }
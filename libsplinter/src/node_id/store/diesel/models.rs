@@ -15,9 +15,26 @@
 use diesel::{Insertable, Queryable};
 
 use super::schema::node_id;
+#[cfg(feature = "node-metadata")]
+use super::schema::{node_display_name, node_metadata};
 
 #[derive(Queryable, Insertable)]
 #[table_name = "node_id"]
 pub struct NodeID {
     pub id: String,
 }
+
+#[cfg(feature = "node-metadata")]
+#[derive(Queryable, Insertable)]
+#[table_name = "node_display_name"]
+pub struct NodeDisplayNameModel {
+    pub display_name: String,
+}
+
+#[cfg(feature = "node-metadata")]
+#[derive(Queryable, Insertable)]
+#[table_name = "node_metadata"]
+pub struct NodeMetadataModel {
+    pub key: String,
+    pub value: String,
+}
@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+
+use crate::node_id::store::diesel::models::{NodeDisplayNameModel, NodeMetadataModel};
+use crate::node_id::store::NodeIdStoreError;
+
+use super::NodeIdOperations;
+
+pub trait NodeMetadataGetOperation {
+    fn get_display_name(&self) -> Result<Option<String>, NodeIdStoreError>;
+
+    fn get_metadata(&self) -> Result<BTreeMap<String, String>, NodeIdStoreError>;
+}
+
+impl<'a, C> NodeMetadataGetOperation for NodeIdOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn get_display_name(&self) -> Result<Option<String>, NodeIdStoreError> {
+        use crate::node_id::store::diesel::schema::node_display_name::dsl::*;
+        match node_display_name.first::<NodeDisplayNameModel>(self.connection) {
+            Ok(model) => Ok(Some(model.display_name)),
+            Err(diesel::result::Error::NotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn get_metadata(&self) -> Result<BTreeMap<String, String>, NodeIdStoreError> {
+        use crate::node_id::store::diesel::schema::node_metadata::dsl::*;
+        Ok(node_metadata
+            .load::<NodeMetadataModel>(self.connection)?
+            .into_iter()
+            .map(|model| (model.key, model.value))
+            .collect())
+    }
+}
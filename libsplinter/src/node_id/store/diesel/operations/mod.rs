@@ -16,7 +16,11 @@
 //! [NodeIdStore](super::NodeIdStore) implementors.
 
 pub(super) mod get_node_id;
+#[cfg(feature = "node-metadata")]
+pub(super) mod get_node_metadata;
 pub(super) mod set_node_id;
+#[cfg(feature = "node-metadata")]
+pub(super) mod set_node_metadata;
 
 pub struct NodeIdOperations<'a, C> {
     connection: &'a C,
@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::BTreeMap;
+
+use diesel::insert_into;
+use diesel::prelude::*;
+
+use crate::node_id::store::diesel::models::{NodeDisplayNameModel, NodeMetadataModel};
+use crate::node_id::store::NodeIdStoreError;
+
+use super::NodeIdOperations;
+
+pub trait NodeMetadataSetOperation {
+    fn set_display_name(&self, new_display_name: String) -> Result<(), NodeIdStoreError>;
+
+    fn set_metadata(&self, metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> NodeMetadataSetOperation for NodeIdOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn set_display_name(&self, new_display_name: String) -> Result<(), NodeIdStoreError> {
+        use super::super::schema::node_display_name::dsl::*;
+        self.connection.transaction(|| {
+            // The table holds a single row, so any previous display name is replaced
+            diesel::delete(node_display_name).execute(self.connection)?;
+            insert_into(node_display_name)
+                .values(NodeDisplayNameModel {
+                    display_name: new_display_name,
+                })
+                .execute(self.connection)
+                .map(|_| ())
+                .map_err(|e| e.into())
+        })
+    }
+
+    fn set_metadata(&self, metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError> {
+        use super::super::schema::node_metadata::dsl::*;
+        self.connection.transaction(|| {
+            diesel::delete(node_metadata).execute(self.connection)?;
+            let models = metadata
+                .into_iter()
+                .map(|(k, v)| NodeMetadataModel { key: k, value: v })
+                .collect::<Vec<_>>();
+            if models.is_empty() {
+                return Ok(());
+            }
+            insert_into(node_metadata)
+                .values(models)
+                .execute(self.connection)
+                .map(|_| ())
+                .map_err(|e| e.into())
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> NodeMetadataSetOperation for NodeIdOperations<'a, diesel::pg::PgConnection> {
+    fn set_display_name(&self, new_display_name: String) -> Result<(), NodeIdStoreError> {
+        use super::super::schema::node_display_name::dsl::*;
+        self.connection.transaction(|| {
+            // The table holds a single row, so any previous display name is replaced
+            diesel::delete(node_display_name).execute(self.connection)?;
+            insert_into(node_display_name)
+                .values(NodeDisplayNameModel {
+                    display_name: new_display_name,
+                })
+                .execute(self.connection)
+                .map(|_| ())
+                .map_err(|e| e.into())
+        })
+    }
+
+    fn set_metadata(&self, metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError> {
+        use super::super::schema::node_metadata::dsl::*;
+        self.connection.transaction(|| {
+            diesel::delete(node_metadata).execute(self.connection)?;
+            let models = metadata
+                .into_iter()
+                .map(|(k, v)| NodeMetadataModel { key: k, value: v })
+                .collect::<Vec<_>>();
+            if models.is_empty() {
+                return Ok(());
+            }
+            insert_into(node_metadata)
+                .values(models)
+                .execute(self.connection)
+                .map(|_| ())
+                .map_err(|e| e.into())
+        })
+    }
+}
@@ -17,3 +17,16 @@ table! {
         id -> Text,
     }
 }
+
+table! {
+    node_display_name (display_name) {
+        display_name -> Text,
+    }
+}
+
+table! {
+    node_metadata (key) {
+        key -> Text,
+        value -> Text,
+    }
+}
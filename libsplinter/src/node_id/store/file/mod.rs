@@ -14,12 +14,24 @@
 
 //! A NodeIdStore backed by a file.
 
+#[cfg(feature = "node-metadata")]
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
+#[cfg(feature = "node-metadata")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "node-metadata")]
+use crate::error::InternalError;
+
 use super::NodeIdStore;
 use super::NodeIdStoreError;
 
+/// The name of the file, next to the node_id file, that holds the display name and metadata.
+#[cfg(feature = "node-metadata")]
+const NODE_INFO_FILENAME: &str = "node_info.json";
+
 /// A [NodeIdStore] backed by a file.
 /// The 0.4 node_id file is soft-deprecated, this exists to help migrate the node_id.
 pub struct FileNodeIdStore {
@@ -30,6 +42,37 @@ impl FileNodeIdStore {
     pub fn new(filename: PathBuf) -> Self {
         Self { filename }
     }
+
+    #[cfg(feature = "node-metadata")]
+    fn info_filename(&self) -> PathBuf {
+        self.filename.with_file_name(NODE_INFO_FILENAME)
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn read_info(&self) -> Result<NodeInfo, NodeIdStoreError> {
+        match fs::read(self.info_filename()) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|err| InternalError::from_source(Box::new(err)).into()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(NodeInfo::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn write_info(&self, info: &NodeInfo) -> Result<(), NodeIdStoreError> {
+        let bytes = serde_json::to_vec_pretty(info)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        fs::write(self.info_filename(), bytes).map_err(|e| e.into())
+    }
+}
+
+#[cfg(feature = "node-metadata")]
+#[derive(Default, Serialize, Deserialize)]
+struct NodeInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
 }
 
 impl NodeIdStore for FileNodeIdStore {
@@ -49,4 +92,68 @@ impl NodeIdStore for FileNodeIdStore {
     fn set_node_id(&self, node_id: String) -> Result<(), NodeIdStoreError> {
         fs::write(&self.filename, node_id).map_err(|e| e.into())
     }
+
+    #[cfg(feature = "node-metadata")]
+    fn get_display_name(&self) -> Result<Option<String>, NodeIdStoreError> {
+        self.read_info().map(|info| info.display_name)
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn set_display_name(&self, display_name: String) -> Result<(), NodeIdStoreError> {
+        let mut info = self.read_info()?;
+        info.display_name = Some(display_name);
+        self.write_info(&info)
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn get_metadata(&self) -> Result<BTreeMap<String, String>, NodeIdStoreError> {
+        self.read_info().map(|info| info.metadata)
+    }
+
+    #[cfg(feature = "node-metadata")]
+    fn set_metadata(&self, metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError> {
+        let mut info = self.read_info()?;
+        info.metadata = metadata;
+        self.write_info(&info)
+    }
+}
+
+#[cfg(all(test, feature = "node-metadata"))]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    /// Verifies that the display name and metadata are stored next to the node_id file, and that
+    /// setting one leaves the other unchanged.
+    #[test]
+    fn display_name_and_metadata() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = FileNodeIdStore::new(temp_dir.path().join("node_id"));
+
+        assert_eq!(store.get_display_name().expect("Failed to get name"), None);
+        assert!(store
+            .get_metadata()
+            .expect("Failed to get metadata")
+            .is_empty());
+
+        store
+            .set_display_name("Acme Node".into())
+            .expect("Failed to set name");
+        let mut metadata = BTreeMap::new();
+        metadata.insert("region".to_string(), "us-east".to_string());
+        store
+            .set_metadata(metadata.clone())
+            .expect("Failed to set metadata");
+
+        assert!(temp_dir.path().join(NODE_INFO_FILENAME).exists());
+        assert_eq!(
+            store.get_display_name().expect("Failed to get name"),
+            Some("Acme Node".to_string())
+        );
+        assert_eq!(
+            store.get_metadata().expect("Failed to get metadata"),
+            metadata
+        );
+    }
 }
@@ -17,10 +17,16 @@ pub mod diesel;
 pub mod error;
 pub mod file;
 
+#[cfg(feature = "node-metadata")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "node-metadata")]
+use crate::error::InternalError;
+
 use error::NodeIdStoreError;
 
 /// Trait for interacting with the instances node_id.
-pub trait NodeIdStore {
+pub trait NodeIdStore: Send + Sync {
     /// Gets node_id for the instance
     fn get_node_id(&self) -> Result<Option<String>, NodeIdStoreError>;
 
@@ -30,4 +36,46 @@ pub trait NodeIdStore {
     ///
     /// * `node_id` - the desired node_id
     fn set_node_id(&self, node_id: String) -> Result<(), NodeIdStoreError>;
+
+    /// Gets the display name for the instance, if one has been set
+    ///
+    /// Stores that do not support display names report that none has been set.
+    #[cfg(feature = "node-metadata")]
+    fn get_display_name(&self) -> Result<Option<String>, NodeIdStoreError> {
+        Ok(None)
+    }
+
+    /// Sets the display name for the instance
+    ///
+    /// # Arguments
+    ///
+    /// * `display_name` - the desired display name
+    #[cfg(feature = "node-metadata")]
+    fn set_display_name(&self, _display_name: String) -> Result<(), NodeIdStoreError> {
+        Err(NodeIdStoreError::InternalError(
+            InternalError::with_message(
+                "Setting the display name is not supported by this store".into(),
+            ),
+        ))
+    }
+
+    /// Gets the metadata for the instance
+    ///
+    /// Stores that do not support metadata report that none has been set.
+    #[cfg(feature = "node-metadata")]
+    fn get_metadata(&self) -> Result<BTreeMap<String, String>, NodeIdStoreError> {
+        Ok(BTreeMap::new())
+    }
+
+    /// Replaces the metadata for the instance
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - the complete set of metadata for the instance
+    #[cfg(feature = "node-metadata")]
+    fn set_metadata(&self, _metadata: BTreeMap<String, String>) -> Result<(), NodeIdStoreError> {
+        Err(NodeIdStoreError::InternalError(
+            InternalError::with_message("Setting metadata is not supported by this store".into()),
+        ))
+    }
 }
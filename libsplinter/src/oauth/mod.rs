@@ -211,6 +211,13 @@ impl OAuthClient {
     pub fn get_subject(&self, access_token: &str) -> Result<Option<String>, InternalError> {
         self.subject_provider.get_subject(access_token)
     }
+
+    /// Attempts to get the profile details of the account that the given access token is for
+    /// from the OAuth server. This method will return `Ok(None)` if the access token could not be
+    /// resolved to a profile.
+    pub fn get_profile(&self, access_token: &str) -> Result<Option<Profile>, InternalError> {
+        self.profile_provider.get_profile(access_token)
+    }
 }
 
 fn new_basic_client(
@@ -17,6 +17,10 @@
 mod github;
 mod openid;
 
+#[cfg(feature = "biome-profile")]
+use crate::biome::{
+    profile::store::ProfileBuilder, profile::store::UserProfileStoreError, UserProfileStore,
+};
 use crate::error::InternalError;
 use crate::oauth::Profile;
 
@@ -38,3 +42,33 @@ impl Clone for Box<dyn ProfileProvider> {
         self.clone_box()
     }
 }
+
+/// Saves the given OAuth profile details as the Biome user profile of the user with the given
+/// ID, adding the profile if the user does not have one yet.
+#[cfg(feature = "biome-profile")]
+pub(crate) fn save_profile(
+    user_profile_store: &dyn UserProfileStore,
+    user_id: &str,
+    profile: &Profile,
+) -> Result<(), InternalError> {
+    let profile = ProfileBuilder::new()
+        .with_user_id(user_id.into())
+        .with_subject(profile.subject.clone())
+        .with_name(profile.name.clone())
+        .with_given_name(profile.given_name.clone())
+        .with_family_name(profile.family_name.clone())
+        .with_email(profile.email.clone())
+        .with_picture(profile.picture.clone())
+        .build()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    match user_profile_store.get_profile(user_id) {
+        Ok(_) => user_profile_store
+            .update_profile(profile)
+            .map_err(|err| InternalError::from_source(Box::new(err))),
+        Err(UserProfileStoreError::InvalidArgument(_)) => user_profile_store
+            .add_profile(profile)
+            .map_err(|err| InternalError::from_source(Box::new(err))),
+        Err(err) => Err(InternalError::from_source(Box::new(err))),
+    }
+}
@@ -23,13 +23,11 @@ use rand::{thread_rng, Rng};
 use crate::biome::oauth::store::{InsertableOAuthUserSessionBuilder, OAuthUserSessionStore};
 
 #[cfg(feature = "biome-profile")]
-use crate::biome::{
-    profile::store::ProfileBuilder, profile::store::UserProfileStoreError, UserProfileStore,
-};
+use crate::biome::UserProfileStore;
 #[cfg(feature = "biome-profile")]
 use crate::error::InternalError;
 #[cfg(feature = "biome-profile")]
-use crate::oauth::Profile as OauthProfile;
+use crate::oauth::{profile::save_profile, Profile as OauthProfile};
 use crate::oauth::{
     rest_api::resources::callback::{generate_redirect_query, CallbackQuery},
     OAuthClient,
@@ -64,6 +62,29 @@ pub fn make_callback_route(
                                 .exchange_authorization_code(query.code.clone(), &query.state)
                             {
                                 Ok(Some((user_info, redirect_url))) => {
+                                    // Deactivated users may not start new sessions
+                                    #[cfg(feature = "biome-user-deactivation")]
+                                    match oauth_user_session_store.get_user(user_info.subject()) {
+                                        Ok(Some(user)) if !user.is_active() => {
+                                            return Box::new(
+                                                HttpResponse::Forbidden()
+                                                    .json(ErrorResponse::forbidden(
+                                                        "User has been deactivated",
+                                                    ))
+                                                    .into_future(),
+                                            );
+                                        }
+                                        Ok(_) => (),
+                                        Err(err) => {
+                                            error!("Unable to get user: {}", err);
+                                            return Box::new(
+                                                HttpResponse::InternalServerError()
+                                                    .json(ErrorResponse::internal_error())
+                                                    .into_future(),
+                                            );
+                                        }
+                                    }
+
                                     // Generate a Splinter access token for the new session
                                     let splinter_access_token = new_splinter_access_token();
 
@@ -176,6 +197,29 @@ pub fn make_callback_route(
                     Ok(query) => {
                         match client.exchange_authorization_code(query.code.clone(), &query.state) {
                             Ok(Some((user_info, redirect_url))) => {
+                                // Deactivated users may not start new sessions
+                                #[cfg(feature = "biome-user-deactivation")]
+                                match oauth_user_session_store.get_user(user_info.subject()) {
+                                    Ok(Some(user)) if !user.is_active() => {
+                                        return Box::new(
+                                            HttpResponse::Forbidden()
+                                                .json(ErrorResponse::forbidden(
+                                                    "User has been deactivated",
+                                                ))
+                                                .into_future(),
+                                        );
+                                    }
+                                    Ok(_) => (),
+                                    Err(err) => {
+                                        error!("Unable to get user: {}", err);
+                                        return Box::new(
+                                            HttpResponse::InternalServerError()
+                                                .json(ErrorResponse::internal_error())
+                                                .into_future(),
+                                        );
+                                    }
+                                }
+
                                 // Generate a Splinter access token for the new session
                                 let splinter_access_token = new_splinter_access_token();
 
@@ -301,26 +345,7 @@ fn save_user_profile(
         .get_user(&subject)
         .map_err(|err| InternalError::from_source(Box::new(err)))?
     {
-        let profile = ProfileBuilder::new()
-            .with_user_id(user.user_id().into())
-            .with_subject(profile.subject.clone())
-            .with_name(profile.name.clone())
-            .with_given_name(profile.given_name.clone())
-            .with_family_name(profile.family_name.clone())
-            .with_email(profile.email.clone())
-            .with_picture(profile.picture.clone())
-            .build()
-            .map_err(|err| InternalError::from_source(Box::new(err)))?;
-
-        match user_profile_store.get_profile(user.user_id()) {
-            Ok(_) => user_profile_store
-                .update_profile(profile)
-                .map_err(|err| InternalError::from_source(Box::new(err))),
-            Err(UserProfileStoreError::InvalidArgument(_)) => user_profile_store
-                .add_profile(profile)
-                .map_err(|err| InternalError::from_source(Box::new(err))),
-            Err(err) => Err(InternalError::from_source(Box::new(err))),
-        }
+        save_profile(&*user_profile_store, user.user_id(), profile)
     } else {
         Err(InternalError::with_message(
             "Unable to retrieve user".to_string(),
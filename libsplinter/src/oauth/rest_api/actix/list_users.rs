@@ -14,6 +14,8 @@
 
 use actix_web::{web, HttpResponse};
 
+#[cfg(feature = "biome-user-deactivation")]
+use crate::biome::oauth::store::OAuthUserIter;
 use crate::biome::oauth::store::OAuthUserSessionStore;
 use crate::oauth::rest_api::{
     resources::list_users::{ListOAuthUserResponse, OAuthUserResponse, PagingQuery},
@@ -49,8 +51,23 @@ pub fn make_oauth_list_users_resource(
                     }
                 };
             let link = format!("{}?", req.uri().path());
+            #[cfg(feature = "biome-user-deactivation")]
+            let link = match paging_query.active {
+                Some(active) => format!("{}?active={}&", req.uri().path(), active),
+                None => link,
+            };
             Box::new(match oauth_user_session_store.list_users() {
                 Ok(users) => {
+                    #[cfg(feature = "biome-user-deactivation")]
+                    let users = OAuthUserIter::new(
+                        users
+                            .filter(|user| {
+                                paging_query
+                                    .active
+                                    .map_or(true, |active| user.is_active() == active)
+                            })
+                            .collect(),
+                    );
                     let total = users.len();
                     let oauth_users = users
                         .skip(paging_query.offset)
@@ -92,9 +109,24 @@ pub fn make_oauth_list_users_resource(
                     }
                 };
             let link = format!("{}?", req.uri().path());
+            #[cfg(feature = "biome-user-deactivation")]
+            let link = match paging_query.active {
+                Some(active) => format!("{}?active={}&", req.uri().path(), active),
+                None => link,
+            };
 
             Box::new(match oauth_user_session_store.list_users() {
                 Ok(users) => {
+                    #[cfg(feature = "biome-user-deactivation")]
+                    let users = OAuthUserIter::new(
+                        users
+                            .filter(|user| {
+                                paging_query
+                                    .active
+                                    .map_or(true, |active| user.is_active() == active)
+                            })
+                            .collect(),
+                    );
                     let total = users.total();
                     let oauth_users = users
                         .skip(paging_query.offset)
@@ -16,3 +16,5 @@ pub(super) mod callback;
 pub(super) mod list_users;
 pub(super) mod login;
 pub(super) mod logout;
+#[cfg(feature = "biome-user-deactivation")]
+pub(super) mod user_status;
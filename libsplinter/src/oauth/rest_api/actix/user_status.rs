@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `POST /oauth/users/{id}/deactivate` and `POST /oauth/users/{id}/reactivate` endpoints for
+//! changing whether or not an OAuth user may log in.
+
+use actix_web::HttpResponse;
+use futures::future::IntoFuture;
+
+use crate::biome::oauth::store::{OAuthUserSessionStore, OAuthUserSessionStoreError};
+#[cfg(feature = "authorization")]
+use crate::oauth::rest_api::OAUTH_USER_WRITE_PERMISSION;
+use crate::rest_api::{
+    actix_web_1::{HandlerFunction, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+const OAUTH_USER_STATUS_PROTOCOL_MIN: u32 = 1;
+
+/// Creates the `/oauth/users/{id}/deactivate` resource. The user's record and sessions are kept,
+/// but the user may no longer log in or use existing sessions.
+pub fn make_deactivate_route(oauth_user_session_store: Box<dyn OAuthUserSessionStore>) -> Resource {
+    make_user_status_route(
+        "/oauth/users/{id}/deactivate",
+        set_user_active_method(oauth_user_session_store, false),
+    )
+}
+
+/// Creates the `/oauth/users/{id}/reactivate` resource
+pub fn make_reactivate_route(oauth_user_session_store: Box<dyn OAuthUserSessionStore>) -> Resource {
+    make_user_status_route(
+        "/oauth/users/{id}/reactivate",
+        set_user_active_method(oauth_user_session_store, true),
+    )
+}
+
+fn make_user_status_route(route: &str, handler: HandlerFunction) -> Resource {
+    let resource = Resource::build(route).add_request_guard(ProtocolVersionRangeGuard::new(
+        OAUTH_USER_STATUS_PROTOCOL_MIN,
+        SPLINTER_PROTOCOL_VERSION,
+    ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, OAUTH_USER_WRITE_PERMISSION, handler)
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, handler)
+    }
+}
+
+fn set_user_active_method(
+    oauth_user_session_store: Box<dyn OAuthUserSessionStore>,
+    active: bool,
+) -> HandlerFunction {
+    Box::new(move |req, _| {
+        let user_id = match req.match_info().get("id") {
+            Some(user_id) => user_id.to_string(),
+            None => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Failed to process request: no user id",
+                        ))
+                        .into_future(),
+                )
+            }
+        };
+
+        Box::new(
+            match oauth_user_session_store.set_user_active(&user_id, active) {
+                Ok(()) => {
+                    let message = if active {
+                        "User reactivated successfully"
+                    } else {
+                        "User deactivated successfully"
+                    };
+                    HttpResponse::Ok().json(json!({ "message": message }))
+                }
+                Err(OAuthUserSessionStoreError::InvalidState(_)) => HttpResponse::NotFound().json(
+                    ErrorResponse::not_found(&format!("User ID not found: {}", user_id)),
+                ),
+                Err(err) => {
+                    error!("Unable to update user status: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            }
+            .into_future(),
+        )
+    })
+}
@@ -29,3 +29,10 @@ const OAUTH_USER_READ_PERMISSION: Permission = Permission::Check {
     permission_display_name: "OAuth Users read",
     permission_description: "Allows the client to read OAuth users",
 };
+
+#[cfg(all(feature = "authorization", feature = "biome-user-deactivation"))]
+const OAUTH_USER_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "oauth.users.write",
+    permission_display_name: "OAuth Users write",
+    permission_description: "Allows the client to deactivate and reactivate OAuth users",
+};
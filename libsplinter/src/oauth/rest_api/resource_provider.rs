@@ -56,6 +56,8 @@ impl OAuthResourceProvider {
 /// * `GET /oauth/callback` - Receive the authorization code from the provider
 /// * `GET /oauth/logout` - Remove the user's access and refresh tokens
 /// * `GET` /oauth/users` - Get a list of the OAuth users
+/// * `POST /oauth/users/{id}/deactivate` - Deactivate the OAuth user with the specified ID
+/// * `POST /oauth/users/{id}/reactivate` - Reactivate the OAuth user with the specified ID
 impl RestResourceProvider for OAuthResourceProvider {
     fn resources(&self) -> Vec<Resource> {
         vec![
@@ -70,6 +72,10 @@ impl RestResourceProvider for OAuthResourceProvider {
             actix::list_users::make_oauth_list_users_resource(
                 self.oauth_user_session_store.clone(),
             ),
+            #[cfg(feature = "biome-user-deactivation")]
+            actix::user_status::make_deactivate_route(self.oauth_user_session_store.clone()),
+            #[cfg(feature = "biome-user-deactivation")]
+            actix::user_status::make_reactivate_route(self.oauth_user_session_store.clone()),
         ]
     }
 }
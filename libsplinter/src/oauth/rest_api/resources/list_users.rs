@@ -27,6 +27,7 @@ pub(crate) struct ListOAuthUserResponse<'a> {
 pub(crate) struct OAuthUserResponse<'a> {
     pub subject: &'a str,
     pub user_id: &'a str,
+    pub active: bool,
 }
 
 impl<'a> From<&'a OAuthUser> for OAuthUserResponse<'a> {
@@ -34,6 +35,7 @@ impl<'a> From<&'a OAuthUser> for OAuthUserResponse<'a> {
         Self {
             subject: user.subject(),
             user_id: user.user_id(),
+            active: user.is_active(),
         }
     }
 }
@@ -44,6 +46,8 @@ pub struct PagingQuery {
     pub limit: usize,
     #[serde(default = "default_offset")]
     pub offset: usize,
+    #[cfg(feature = "biome-user-deactivation")]
+    pub active: Option<bool>,
 }
 
 fn default_limit() -> usize {
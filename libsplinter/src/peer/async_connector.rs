@@ -0,0 +1,344 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! An async interface for communicating with the PeerManager.
+
+use std::thread;
+
+use tokio_1::sync::{mpsc, oneshot};
+
+use crate::collections::BiHashMap;
+
+use super::connector::PeerManagerConnector;
+use super::error::{
+    PeerConnectionIdError, PeerListError, PeerManagerError, PeerRefAddError, PeerUnknownAddError,
+};
+use super::{EndpointPeerRef, PeerAuthorizationToken, PeerRef, PeerTokenPair};
+
+/// Requests forwarded from the `AsyncPeerManagerConnector` to its bridge thread.
+enum AsyncPeerManagerRequest {
+    AddPeer {
+        peer_id: PeerAuthorizationToken,
+        endpoints: Vec<String>,
+        required_local_auth: PeerAuthorizationToken,
+        sender: oneshot::Sender<Result<PeerRef, PeerRefAddError>>,
+    },
+    AddUnidentified {
+        endpoint: String,
+        local_authorization: PeerAuthorizationToken,
+        sender: oneshot::Sender<Result<EndpointPeerRef, PeerUnknownAddError>>,
+    },
+    RemovePeer {
+        peer_ref: PeerRef,
+        sender: oneshot::Sender<()>,
+    },
+    RemoveUnidentified {
+        peer_ref: EndpointPeerRef,
+        sender: oneshot::Sender<()>,
+    },
+    ListPeers {
+        sender: oneshot::Sender<Result<Vec<PeerAuthorizationToken>, PeerListError>>,
+    },
+    ListUnreferencedPeers {
+        sender: oneshot::Sender<Result<Vec<PeerTokenPair>, PeerListError>>,
+    },
+    ConnectionIds {
+        sender: oneshot::Sender<Result<BiHashMap<PeerTokenPair, String>, PeerConnectionIdError>>,
+    },
+}
+
+/// The `AsyncPeerManagerConnector` makes requests to the `PeerManager` without blocking the
+/// calling task.
+///
+/// Requests are passed over a tokio channel to a dedicated bridge thread, which makes the
+/// blocking call using a `PeerManagerConnector` and returns the result over a oneshot channel.
+/// The bridge thread exits once all clones of the connector have been dropped.
+#[derive(Clone, Debug)]
+pub struct AsyncPeerManagerConnector {
+    sender: mpsc::UnboundedSender<AsyncPeerManagerRequest>,
+}
+
+impl AsyncPeerManagerConnector {
+    /// Creates a new `AsyncPeerManagerConnector` that forwards requests to the `PeerManager`
+    /// using the given connector.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PeerManagerError` if the bridge thread cannot be started.
+    pub fn new(connector: PeerManagerConnector) -> Result<Self, PeerManagerError> {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        thread::Builder::new()
+            .name("Peer Manager Async Bridge".into())
+            .spawn(move || {
+                while let Some(request) = receiver.blocking_recv() {
+                    handle_request(&connector, request);
+                }
+                debug!("Exiting Peer Manager async bridge");
+            })
+            .map_err(|err| {
+                PeerManagerError::StartUpError(format!(
+                    "Unable to start peer manager async bridge thread: {}",
+                    err
+                ))
+            })?;
+
+        Ok(Self { sender })
+    }
+
+    /// Requests that a peer is added to the `PeerManager`. If a peer already exists, the peer's
+    /// reference count will be incremented
+    ///
+    /// Returns a `PeerRef` that should be passed to [`remove_peer_ref`] when it is no longer
+    /// needed; dropping it directly sends a blocking removal request to the `PeerManager`.
+    ///
+    /// See [`PeerManagerConnector::add_peer_ref`] for a description of the arguments.
+    ///
+    /// [`remove_peer_ref`]: #method.remove_peer_ref
+    /// [`PeerManagerConnector::add_peer_ref`]: struct.PeerManagerConnector.html#method.add_peer_ref
+    pub async fn add_peer_ref(
+        &self,
+        peer_id: PeerAuthorizationToken,
+        endpoints: Vec<String>,
+        required_local_auth: PeerAuthorizationToken,
+    ) -> Result<PeerRef, PeerRefAddError> {
+        let (sender, recv) = oneshot::channel();
+
+        self.sender
+            .send(AsyncPeerManagerRequest::AddPeer {
+                peer_id,
+                endpoints,
+                required_local_auth,
+                sender,
+            })
+            .map_err(|_| {
+                PeerRefAddError::InternalError(
+                    "Unable to send message to PeerManager, bridge thread stopped".to_string(),
+                )
+            })?;
+
+        recv.await
+            .map_err(|err| PeerRefAddError::ReceiveError(err.to_string()))?
+    }
+
+    /// Requests that a peer is added to the `PeerManager`. This function should be used when the
+    /// peer ID is unknown.
+    ///
+    /// Returns an `EndpointPeerRef` that should be passed to [`remove_unidentified_peer_ref`]
+    /// when it is no longer needed.
+    ///
+    /// [`remove_unidentified_peer_ref`]: #method.remove_unidentified_peer_ref
+    pub async fn add_unidentified_peer(
+        &self,
+        endpoint: String,
+        local_authorization: PeerAuthorizationToken,
+    ) -> Result<EndpointPeerRef, PeerUnknownAddError> {
+        let (sender, recv) = oneshot::channel();
+
+        self.sender
+            .send(AsyncPeerManagerRequest::AddUnidentified {
+                endpoint,
+                local_authorization,
+                sender,
+            })
+            .map_err(|_| {
+                PeerUnknownAddError::InternalError(
+                    "Unable to send message to PeerManager, bridge thread stopped".to_string(),
+                )
+            })?;
+
+        recv.await
+            .map_err(|err| PeerUnknownAddError::ReceiveError(err.to_string()))?
+    }
+
+    /// Releases a `PeerRef`, removing the peer if no more references exist.
+    ///
+    /// The reference is dropped on the bridge thread, so that the removal request does not block
+    /// the calling task.
+    pub async fn remove_peer_ref(&self, peer_ref: PeerRef) {
+        let (sender, recv) = oneshot::channel();
+
+        // If the bridge thread has stopped, the reference is returned in the error and dropped
+        // here instead.
+        if self
+            .sender
+            .send(AsyncPeerManagerRequest::RemovePeer { peer_ref, sender })
+            .is_ok()
+        {
+            let _ = recv.await;
+        }
+    }
+
+    /// Releases an `EndpointPeerRef`, removing the peer if no more references exist.
+    pub async fn remove_unidentified_peer_ref(&self, peer_ref: EndpointPeerRef) {
+        let (sender, recv) = oneshot::channel();
+
+        if self
+            .sender
+            .send(AsyncPeerManagerRequest::RemoveUnidentified { peer_ref, sender })
+            .is_ok()
+        {
+            let _ = recv.await;
+        }
+    }
+
+    /// Requests the list of currently connected peers.
+    ///
+    /// Returns the list of peer IDs.
+    pub async fn list_peers(&self) -> Result<Vec<PeerAuthorizationToken>, PeerListError> {
+        let (sender, recv) = oneshot::channel();
+
+        self.sender
+            .send(AsyncPeerManagerRequest::ListPeers { sender })
+            .map_err(|_| {
+                PeerListError::Internal(
+                    "Unable to send message to PeerManager, bridge thread stopped".to_string(),
+                )
+            })?;
+
+        recv.await
+            .map_err(|err| PeerListError::Receive(err.to_string()))?
+    }
+
+    /// Requests the list of unreferenced peers.
+    pub async fn list_unreferenced_peers(&self) -> Result<Vec<PeerTokenPair>, PeerListError> {
+        let (sender, recv) = oneshot::channel();
+
+        self.sender
+            .send(AsyncPeerManagerRequest::ListUnreferencedPeers { sender })
+            .map_err(|_| {
+                PeerListError::Internal(
+                    "Unable to send message to PeerManager, bridge thread stopped".to_string(),
+                )
+            })?;
+
+        recv.await
+            .map_err(|err| PeerListError::Receive(err.to_string()))?
+    }
+
+    /// Requests the map of currently connected peers to connection IDs
+    pub async fn connection_ids(
+        &self,
+    ) -> Result<BiHashMap<PeerTokenPair, String>, PeerConnectionIdError> {
+        let (sender, recv) = oneshot::channel();
+
+        self.sender
+            .send(AsyncPeerManagerRequest::ConnectionIds { sender })
+            .map_err(|_| {
+                PeerConnectionIdError::InternalError(
+                    "Unable to send message to PeerManager, bridge thread stopped".to_string(),
+                )
+            })?;
+
+        recv.await
+            .map_err(|err| PeerConnectionIdError::ReceiveError(err.to_string()))?
+    }
+}
+
+fn handle_request(connector: &PeerManagerConnector, request: AsyncPeerManagerRequest) {
+    // The requester may have stopped waiting, so failures to send the reply are ignored.
+    match request {
+        AsyncPeerManagerRequest::AddPeer {
+            peer_id,
+            endpoints,
+            required_local_auth,
+            sender,
+        } => {
+            let _ = sender.send(connector.add_peer_ref(peer_id, endpoints, required_local_auth));
+        }
+        AsyncPeerManagerRequest::AddUnidentified {
+            endpoint,
+            local_authorization,
+            sender,
+        } => {
+            let _ = sender.send(connector.add_unidentified_peer(endpoint, local_authorization));
+        }
+        AsyncPeerManagerRequest::RemovePeer { peer_ref, sender } => {
+            drop(peer_ref);
+            let _ = sender.send(());
+        }
+        AsyncPeerManagerRequest::RemoveUnidentified { peer_ref, sender } => {
+            drop(peer_ref);
+            let _ = sender.send(());
+        }
+        AsyncPeerManagerRequest::ListPeers { sender } => {
+            let _ = sender.send(connector.list_peers());
+        }
+        AsyncPeerManagerRequest::ListUnreferencedPeers { sender } => {
+            let _ = sender.send(connector.list_unreferenced_peers());
+        }
+        AsyncPeerManagerRequest::ConnectionIds { sender } => {
+            let _ = sender.send(connector.connection_ids());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    use tokio_1::runtime::Builder;
+
+    use crate::peer::{PeerManagerMessage, PeerManagerRequest};
+
+    /// Test that the async connector forwards list requests to the `PeerManager` and returns the
+    /// response.
+    ///
+    /// 1. Create a connector backed by a channel, answered by a thread acting as the PeerManager
+    /// 2. Request the list of peers and verify the expected peers are returned
+    /// 3. Drop the PeerManager side of the channel and verify the next request fails
+    #[test]
+    fn test_async_list_peers() {
+        let (sender, receiver) = channel();
+        let connector = PeerManagerConnector::new(sender);
+
+        let peer_manager = thread::spawn(move || {
+            if let Ok(PeerManagerMessage::Request(PeerManagerRequest::ListPeers { sender })) =
+                receiver.recv()
+            {
+                sender
+                    .send(Ok(vec![
+                        PeerAuthorizationToken::from_peer_id("peer_1"),
+                        PeerAuthorizationToken::from_peer_id("peer_2"),
+                    ]))
+                    .expect("Unable to send list response");
+            } else {
+                panic!("Did not receive list peers request");
+            }
+        });
+
+        let async_connector =
+            AsyncPeerManagerConnector::new(connector).expect("Unable to create async connector");
+
+        let runtime = Builder::new_current_thread()
+            .build()
+            .expect("Unable to create runtime");
+
+        let peers = runtime
+            .block_on(async_connector.list_peers())
+            .expect("Unable to list peers");
+        assert_eq!(
+            peers,
+            vec![
+                PeerAuthorizationToken::from_peer_id("peer_1"),
+                PeerAuthorizationToken::from_peer_id("peer_2"),
+            ]
+        );
+
+        peer_manager.join().expect("PeerManager thread panicked");
+
+        assert!(runtime.block_on(async_connector.list_peers()).is_err());
+    }
+}
@@ -17,6 +17,8 @@
 //! The public interface includes the structs [`PeerManagerBuilder`]
 
 use crate::network::connection_manager::Connector;
+#[cfg(feature = "transport-ipv6")]
+use crate::transport::address::AddressFamily;
 
 use super::error::PeerManagerError;
 use super::PeerManager;
@@ -43,6 +45,10 @@ pub struct PeerManagerBuilder {
     endpoint_retry_frequency: Option<u64>,
     identity: Option<String>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "transport-ipv6")]
+    preferred_address_family: Option<AddressFamily>,
+    #[cfg(feature = "outbound-only")]
+    outbound_only: bool,
 }
 
 /// Constructs new `PeerManager` instances.
@@ -128,6 +134,29 @@ impl PeerManagerBuilder {
         self
     }
 
+    /// Set the address family whose endpoints the resulting `PeerManager` tries first.
+    ///
+    /// When a peer has several endpoints, those using an IP address of this family are tried
+    /// before those using host names, which are tried before those of the other family. By
+    /// default, endpoints are tried in the order they were given.
+    #[cfg(feature = "transport-ipv6")]
+    pub fn with_preferred_address_family(mut self, family: AddressFamily) -> Self {
+        self.preferred_address_family = Some(family);
+        self
+    }
+
+    /// Set whether the node the resulting `PeerManager` belongs to is outbound-only.
+    ///
+    /// An outbound-only node has no network listeners, so it must connect to each of its peers
+    /// itself. A node that is not outbound-only will accept a peer without endpoints and wait for
+    /// that peer to connect; an outbound-only node will refuse such a peer, as neither node would
+    /// be able to establish the connection. Defaults to `false`.
+    #[cfg(feature = "outbound-only")]
+    pub fn with_outbound_only(mut self, outbound_only: bool) -> Self {
+        self.outbound_only = outbound_only;
+        self
+    }
+
     /// Starts the `PeerManager`
     ///
     /// Starts up a thread that will handle incoming requests to add, remove and get peers. Also
@@ -163,6 +192,10 @@ impl PeerManagerBuilder {
             retry_frequency,
             max_retry_frequency,
             endpoint_retry_frequency,
+            #[cfg(feature = "transport-ipv6")]
+            self.preferred_address_family,
+            #[cfg(feature = "outbound-only")]
+            self.outbound_only,
         )
     }
 }
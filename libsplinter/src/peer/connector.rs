@@ -14,10 +14,22 @@
 
 //! Data structures for communicating with the PeerManager.
 
+#[cfg(feature = "peer-ref-diagnostics")]
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{channel, Sender};
+#[cfg(feature = "peer-ref-diagnostics")]
+use std::sync::Arc;
+#[cfg(feature = "peer-disconnect")]
+use std::time::Duration;
 
 use crate::collections::BiHashMap;
 
+#[cfg(feature = "peer-ref-diagnostics")]
+use super::diagnostics::{capture_caller, RefCountReport};
+#[cfg(feature = "peer-disconnect")]
+use super::error::PeerDisconnectError;
+#[cfg(feature = "peer-endpoint-pin")]
+use super::error::PeerEndpointPinError;
 use super::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
@@ -187,6 +199,178 @@ impl PeerManagerConnector {
             .map_err(|err| PeerListError::Receive(format!("{:?}", err)))?
     }
 
+    /// Requests that all connections to a peer are closed, regardless of how many references to
+    /// the peer exist. Referenced peers will be reconnected, unless a ban is given, in which case
+    /// the peer will not be connected to (or accepted from) until the ban has expired.
+    ///
+    /// Returns the number of connections that were closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer's ID, as returned by `PeerAuthorizationToken::id_as_string`
+    /// * `ban` - How long to prevent the peer from reconnecting, if at all
+    #[cfg(feature = "peer-disconnect")]
+    pub fn disconnect_peer(
+        &self,
+        peer_id: &str,
+        ban: Option<Duration>,
+    ) -> Result<usize, PeerDisconnectError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::DisconnectPeer {
+            peer_id: peer_id.to_string(),
+            ban,
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerDisconnectError::Internal(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerDisconnectError::Receive(format!("{:?}", err)))?
+    }
+
+    /// Pins a peer to the given endpoint. Until the peer is unpinned, the endpoint is used in
+    /// place of the peer's own endpoints whenever the peer is connected to, including after the
+    /// peer disconnects or is removed and added again. A peer that is connected through another
+    /// endpoint is reconnected through the pinned endpoint.
+    ///
+    /// Connections that the peer makes to this node are still accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer's ID, as returned by `PeerAuthorizationToken::id_as_string`
+    /// * `endpoint` - The endpoint to connect to the peer through
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn pin_endpoint(&self, peer_id: &str, endpoint: &str) -> Result<(), PeerEndpointPinError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::PinEndpoint {
+            peer_id: peer_id.to_string(),
+            endpoint: endpoint.to_string(),
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerEndpointPinError::Internal(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerEndpointPinError::Receive(format!("{:?}", err)))?
+    }
+
+    /// Removes a peer's pinned endpoint, so its own endpoints are used the next time it is
+    /// connected to. The peer's current connection is left open.
+    ///
+    /// Returns the endpoint the peer was pinned to, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer's ID, as returned by `PeerAuthorizationToken::id_as_string`
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn unpin_endpoint(&self, peer_id: &str) -> Result<Option<String>, PeerEndpointPinError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::UnpinEndpoint {
+            peer_id: peer_id.to_string(),
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerEndpointPinError::Internal(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerEndpointPinError::Receive(format!("{:?}", err)))?
+    }
+
+    /// Returns the endpoint a peer is pinned to, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer's ID, as returned by `PeerAuthorizationToken::id_as_string`
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn pinned_endpoint(&self, peer_id: &str) -> Result<Option<String>, PeerEndpointPinError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::GetPinnedEndpoint {
+            peer_id: peer_id.to_string(),
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerEndpointPinError::Internal(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerEndpointPinError::Receive(format!("{:?}", err)))?
+    }
+
+    /// Returns the reference count settings of the `PeerManager` and the reference count of every
+    /// referenced peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PeerManagerError` if the `PeerManager` has stopped running.
+    #[cfg(feature = "peer-ref-diagnostics")]
+    pub fn ref_counts(&self) -> Result<RefCountReport, PeerManagerError> {
+        self.set_ref_count_mode(None, None)
+    }
+
+    /// Changes how the `PeerManager` handles reference counts, without restarting it.
+    ///
+    /// Returns the resulting settings and the reference count of every referenced peer.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether removing a reference that does not exist should panic; `None` leaves
+    ///   the setting unchanged
+    /// * `diagnostics` - Whether the call stacks that remove references should be captured, so
+    ///   that removing a reference that does not exist logs both the offending call stack and the
+    ///   one that released the peer; `None` leaves the setting unchanged
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PeerManagerError` if the `PeerManager` has stopped running.
+    #[cfg(feature = "peer-ref-diagnostics")]
+    pub fn set_ref_count_mode(
+        &self,
+        strict: Option<bool>,
+        diagnostics: Option<bool>,
+    ) -> Result<RefCountReport, PeerManagerError> {
+        let (sender, recv) = channel();
+        self.sender
+            .send(PeerManagerMessage::RefCounts {
+                strict,
+                diagnostics,
+                sender,
+            })
+            .map_err(|_| {
+                PeerManagerError::SendMessageError("The peer manager is no longer running".into())
+            })?;
+
+        recv.recv().map_err(|_| {
+            PeerManagerError::SendMessageError("The peer manager is no longer running".into())
+        })
+    }
+
     /// Requests the map of currently connected peers to connection IDs
     ///
     /// Returns a map of peer IDs to connection IDs
@@ -338,6 +522,9 @@ impl PeerLookupProvider for PeerManagerConnector {
 #[derive(Clone, Debug)]
 pub(crate) struct PeerRemover {
     pub sender: Sender<PeerManagerMessage>,
+    /// Set while reference count diagnostics are enabled
+    #[cfg(feature = "peer-ref-diagnostics")]
+    pub diagnostics: Arc<AtomicBool>,
 }
 
 impl PeerRemover {
@@ -353,6 +540,8 @@ impl PeerRemover {
         let message = PeerManagerMessage::Request(PeerManagerRequest::RemovePeer {
             peer_id: peer_id.clone(),
             sender,
+            #[cfg(feature = "peer-ref-diagnostics")]
+            caller: capture_caller(&self.diagnostics),
         });
 
         match self.sender.send(message) {
@@ -386,6 +575,8 @@ impl PeerRemover {
             endpoint: endpoint.to_string(),
             connection_id: connection_id.to_string(),
             sender,
+            #[cfg(feature = "peer-ref-diagnostics")]
+            caller: capture_caller(&self.diagnostics),
         });
 
         match self.sender.send(message) {
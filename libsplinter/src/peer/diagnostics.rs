@@ -0,0 +1,146 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reference count diagnostics for the `PeerManager`.
+//!
+//! When diagnostics are enabled, each `PeerRef` captures the call stack that dropped it. The
+//! `PeerManager` keeps the call stack that released the last reference to each peer, so that a
+//! later attempt to remove a reference that no longer exists can be logged along with both the
+//! offending call stack and the one that removed the reference first.
+
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::PeerTokenPair;
+
+/// The current reference count settings of a `PeerManager`, and the reference count of every
+/// peer it holds references to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefCountReport {
+    /// Whether removing a reference that does not exist panics
+    pub strict: bool,
+    /// Whether the call stacks that remove references are captured and logged
+    pub diagnostics: bool,
+    /// The reference count of each referenced peer, ordered by peer ID
+    pub peers: Vec<PeerRefCount>,
+}
+
+/// The number of references held to a single peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerRefCount {
+    pub peer_id: PeerTokenPair,
+    pub ref_count: u64,
+}
+
+/// Tracks the call stacks that released peers while diagnostics are enabled.
+pub(crate) struct RefDiagnostics {
+    enabled: Arc<AtomicBool>,
+    released_by: HashMap<PeerTokenPair, String>,
+}
+
+impl RefDiagnostics {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            released_by: HashMap::new(),
+        }
+    }
+
+    /// Returns the flag shared with `PeerRemover`s, which capture the call stack of each removal
+    /// while it is set.
+    pub fn enabled_flag(&self) -> Arc<AtomicBool> {
+        self.enabled.clone()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Enables or disables diagnostics. Disabling them drops the recorded call stacks.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            self.released_by.clear();
+        }
+    }
+
+    /// Records the call stack that released the last reference to the given peer.
+    pub fn record_release(&mut self, peer_id: &PeerTokenPair, caller: Option<String>) {
+        if let (true, Some(caller)) = (self.is_enabled(), caller) {
+            self.released_by.insert(peer_id.clone(), caller);
+        }
+    }
+
+    /// Logs an attempt to remove a reference to a peer that is not referenced.
+    pub fn log_invalid_remove(&self, peer_id: &PeerTokenPair, caller: Option<&str>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        error!(
+            "Attempted to remove a reference to peer {} that does not exist\n\
+             Removed by:\n{}\nLast reference released by:\n{}",
+            peer_id,
+            caller.unwrap_or("<call stack not captured>"),
+            self.released_by
+                .get(peer_id)
+                .map(String::as_str)
+                .unwrap_or("<no release recorded>"),
+        );
+    }
+}
+
+/// Captures the current call stack, if diagnostics are enabled.
+pub(crate) fn capture_caller(enabled: &AtomicBool) -> Option<String> {
+    if enabled.load(Ordering::SeqCst) {
+        Some(Backtrace::force_capture().to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::peer::PeerAuthorizationToken;
+
+    /// Test that release call stacks are only recorded while diagnostics are enabled, and are
+    /// dropped when diagnostics are disabled.
+    #[test]
+    fn test_record_release() {
+        let peer_id = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("peer_1"),
+            PeerAuthorizationToken::from_peer_id("local"),
+        );
+
+        let mut diagnostics = RefDiagnostics::new(false);
+        assert!(capture_caller(&diagnostics.enabled_flag()).is_none());
+        diagnostics.record_release(&peer_id, Some("stack".into()));
+        assert!(diagnostics.released_by.is_empty());
+
+        diagnostics.set_enabled(true);
+        assert!(capture_caller(&diagnostics.enabled_flag()).is_some());
+        diagnostics.record_release(&peer_id, Some("stack".into()));
+        assert_eq!(
+            diagnostics.released_by.get(&peer_id).map(String::as_str),
+            Some("stack")
+        );
+
+        diagnostics.set_enabled(false);
+        assert!(diagnostics.released_by.is_empty());
+    }
+}
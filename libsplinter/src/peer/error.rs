@@ -173,6 +173,56 @@ impl fmt::Display for PeerConnectionIdError {
     }
 }
 
+/// Errors that could be raised when requesting a peer be disconnected
+#[cfg(feature = "peer-disconnect")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum PeerDisconnectError {
+    /// Internal `PeerManager` error
+    Internal(String),
+    /// Unable to receive response
+    Receive(String),
+}
+
+#[cfg(feature = "peer-disconnect")]
+impl error::Error for PeerDisconnectError {}
+
+#[cfg(feature = "peer-disconnect")]
+impl fmt::Display for PeerDisconnectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeerDisconnectError::Internal(msg) => write!(f, "Received internal error: {}", msg),
+            PeerDisconnectError::Receive(msg) => {
+                write!(f, "Unable to receive response from PeerManager: {}", msg)
+            }
+        }
+    }
+}
+
+/// Errors that could be raised when pinning or unpinning a peer's endpoint
+#[cfg(feature = "peer-endpoint-pin")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum PeerEndpointPinError {
+    /// Internal `PeerManager` error
+    Internal(String),
+    /// Unable to receive response
+    Receive(String),
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+impl error::Error for PeerEndpointPinError {}
+
+#[cfg(feature = "peer-endpoint-pin")]
+impl fmt::Display for PeerEndpointPinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeerEndpointPinError::Internal(msg) => write!(f, "Received internal error: {}", msg),
+            PeerEndpointPinError::Receive(msg) => {
+                write!(f, "Unable to receive response from PeerManager: {}", msg)
+            }
+        }
+    }
+}
+
 /// Errors raised by trying to update a peer
 #[derive(Debug)]
 pub struct PeerUpdateError(pub String);
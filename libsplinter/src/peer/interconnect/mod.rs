@@ -27,7 +27,7 @@ mod pending;
 use std::collections::HashMap;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use protobuf::Message;
 
@@ -38,6 +38,7 @@ use crate::threading::lifecycle::ShutdownHandle;
 use crate::threading::pacemaker;
 use crate::transport::matrix::{
     ConnectionMatrixReceiver, ConnectionMatrixRecvError, ConnectionMatrixSender,
+    ConnectionMatrixTrySendError,
 };
 
 use super::connector::{PeerLookup, PeerLookupProvider};
@@ -51,6 +52,10 @@ use self::pending::{
 
 const DEFAULT_INITIAL_ATTEMPTS: usize = 3; // 3 attempts
 
+// How long to wait for room in a peer's outgoing queue before handing the message off to be
+// retried, so that one slow peer does not hold up messages to the others
+const SEND_TIMEOUT: Duration = Duration::from_millis(10);
+
 /// Message to send to the network message sender with the recipient and payload
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) enum SendRequest {
@@ -500,25 +505,39 @@ where
         let mut pending = None;
         // if peer exists, send message over the network
         if let Some(connection_id) = connection_id {
-            // If connection is missing, check with peer manager to see if connection id has
-            // changed and try to resend message. Otherwise remove cached connection_id.
-            if message_sender
-                .send(connection_id.to_string(), payload.to_vec())
-                .is_err()
-            {
-                if let Some(new_connection_id) =
-                    peer_connector.connection_id(&recipient).map_err(|err| {
-                        format!("Unable to get connection ID for {}: {}", recipient, err)
-                    })?
-                {
-                    // if connection_id has changed replace it and try to send again
-                    if new_connection_id != connection_id {
-                        peer_id_to_connection_id
-                            .insert(recipient.clone(), new_connection_id.clone());
-                        if message_sender
-                            .send(new_connection_id, payload.to_vec())
-                            .is_err()
-                        {
+            // If the peer's queue is full, retry the message later; the peer is still connected,
+            // so the cached connection_id is kept. If connection is missing, check with peer
+            // manager to see if connection id has changed and try to resend message. Otherwise
+            // remove cached connection_id.
+            match message_sender.send_timeout(
+                connection_id.to_string(),
+                payload.to_vec(),
+                SEND_TIMEOUT,
+            ) {
+                Ok(()) => (),
+                Err(ConnectionMatrixTrySendError::Full(_)) => {
+                    debug!("Outgoing queue for peer {} is full", recipient);
+                    pending = Some((recipient, payload));
+                }
+                Err(ConnectionMatrixTrySendError::Send(_)) => {
+                    if let Some(new_connection_id) =
+                        peer_connector.connection_id(&recipient).map_err(|err| {
+                            format!("Unable to get connection ID for {}: {}", recipient, err)
+                        })?
+                    {
+                        // if connection_id has changed replace it and try to send again
+                        if new_connection_id != connection_id {
+                            peer_id_to_connection_id
+                                .insert(recipient.clone(), new_connection_id.clone());
+                            if message_sender
+                                .send_timeout(new_connection_id, payload.to_vec(), SEND_TIMEOUT)
+                                .is_err()
+                            {
+                                pending = Some((recipient, payload));
+                            }
+                        } else {
+                            // remove cached connection id, peer has gone away
+                            peer_id_to_connection_id.remove(&recipient);
                             pending = Some((recipient, payload));
                         }
                     } else {
@@ -526,10 +545,6 @@ where
                         peer_id_to_connection_id.remove(&recipient);
                         pending = Some((recipient, payload));
                     }
-                } else {
-                    // remove cached connection id, peer has gone away
-                    peer_id_to_connection_id.remove(&recipient);
-                    pending = Some((recipient, payload));
                 }
             }
         } else {
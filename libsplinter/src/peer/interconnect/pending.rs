@@ -14,20 +14,25 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::mpsc::Receiver;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use protobuf::Message;
 
 use crate::network::dispatch::DispatchMessageSender;
 use crate::peer::connector::PeerLookup;
 use crate::protos::network::{NetworkMessage, NetworkMessageType};
-use crate::transport::matrix::{ConnectionMatrixEnvelope, ConnectionMatrixSender};
+use crate::transport::matrix::{
+    ConnectionMatrixEnvelope, ConnectionMatrixSender, ConnectionMatrixTrySendError,
+};
 
 use super::PeerTokenPair;
 
 const DEFAULT_PENDING_QUEUE_SIZE: usize = 100;
 pub const DEFAULT_TIME_BETWEEN_ATTEMPTS: u64 = 10; // 10 seconds
 
+// How long a retried message may wait for room in a peer's outgoing queue
+const PENDING_SEND_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// Internal struct for keeping track of an pending message whose peer was not known at time of
 /// receipt.
 pub struct PendingIncomingMsg {
@@ -200,36 +205,46 @@ where
             // if peer exists, send message over the network
             if let Some(connection_id) = connection_id {
                 // If connection is missing, check with peer manager to see if connection id has
-                // changed and try to resend message. Otherwise remove cached connection_id.
-                if message_sender
-                    .send(connection_id.to_string(), pending.payload.to_vec())
-                    .is_err()
-                {
-                    if let Some(new_connection_id) = peer_connector
-                        .connection_id(&pending.recipient)
-                        .map_err(|err| {
-                            format!(
-                                "Unable to get connection ID for {}: {}",
-                                &pending.recipient, err
-                            )
-                        })?
-                    {
-                        // if connection_id has changed replace it and try to send again
-                        if new_connection_id != connection_id {
-                            peer_id_to_connection_id
-                                .insert(pending.recipient.clone(), new_connection_id.clone());
-                            if message_sender
-                                .send(new_connection_id, pending.payload.to_vec())
-                                .is_ok()
-                            {
-                                // if send was successfully move on to next pending message
-                                continue;
+                // changed and try to resend message. Otherwise remove cached connection_id. A full
+                // queue is retried later without a lookup, since the peer is still connected.
+                match message_sender.send_timeout(
+                    connection_id.to_string(),
+                    pending.payload.to_vec(),
+                    PENDING_SEND_TIMEOUT,
+                ) {
+                    Ok(()) => {
+                        // if send was successfully move on to next pending message
+                        continue;
+                    }
+                    Err(ConnectionMatrixTrySendError::Full(_)) => (),
+                    Err(ConnectionMatrixTrySendError::Send(_)) => {
+                        if let Some(new_connection_id) = peer_connector
+                            .connection_id(&pending.recipient)
+                            .map_err(|err| {
+                                format!(
+                                    "Unable to get connection ID for {}: {}",
+                                    &pending.recipient, err
+                                )
+                            })?
+                        {
+                            // if connection_id has changed replace it and try to send again
+                            if new_connection_id != connection_id {
+                                peer_id_to_connection_id
+                                    .insert(pending.recipient.clone(), new_connection_id.clone());
+                                if message_sender
+                                    .send_timeout(
+                                        new_connection_id,
+                                        pending.payload.to_vec(),
+                                        PENDING_SEND_TIMEOUT,
+                                    )
+                                    .is_ok()
+                                {
+                                    // if send was successfully move on to next pending message
+                                    continue;
+                                }
                             }
                         }
                     }
-                } else {
-                    // if send was successfully move on to next pending message
-                    continue;
                 }
             }
 
@@ -22,8 +22,12 @@
 //! [`PeerInterconnect`]: interconnect/struct.PeerInterconnect.html
 //! [`PeerManagerNotification`]: notification/enum.PeerManagerNotification.html
 
+#[cfg(feature = "async")]
+mod async_connector;
 mod builder;
 mod connector;
+#[cfg(feature = "peer-ref-diagnostics")]
+mod diagnostics;
 mod error;
 pub mod interconnect;
 mod notification;
@@ -36,6 +40,8 @@ use std::cmp::min;
 use std::io::ErrorKind;
 use std::sync::mpsc::{channel, Sender};
 use std::thread;
+#[cfg(feature = "peer-disconnect")]
+use std::time::Duration;
 use std::time::Instant;
 
 use uuid::Uuid;
@@ -46,10 +52,22 @@ use crate::network::connection_manager::ConnectionManagerNotification;
 use crate::network::connection_manager::{ConnectionManagerError, Connector};
 use crate::threading::lifecycle::ShutdownHandle;
 use crate::threading::pacemaker;
+#[cfg(feature = "transport-ipv6")]
+use crate::transport::address::{order_endpoints, AddressFamily};
 
+#[cfg(feature = "async")]
+pub use self::async_connector::AsyncPeerManagerConnector;
 pub use self::builder::PeerManagerBuilder;
 use self::connector::PeerRemover;
 pub use self::connector::{PeerLookup, PeerManagerConnector};
+#[cfg(feature = "peer-ref-diagnostics")]
+use self::diagnostics::RefDiagnostics;
+#[cfg(feature = "peer-ref-diagnostics")]
+pub use self::diagnostics::{PeerRefCount, RefCountReport};
+#[cfg(feature = "peer-disconnect")]
+use self::error::PeerDisconnectError;
+#[cfg(feature = "peer-endpoint-pin")]
+use self::error::PeerEndpointPinError;
 use self::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
@@ -73,6 +91,14 @@ pub(crate) enum PeerManagerMessage {
     InternalNotification(ConnectionManagerNotification),
     /// Notifies the `PeerManager` it should retry connecting to pending peers
     RetryPending,
+    /// Optionally changes the reference count settings, then reports them along with the
+    /// current reference counts
+    #[cfg(feature = "peer-ref-diagnostics")]
+    RefCounts {
+        strict: Option<bool>,
+        diagnostics: Option<bool>,
+        sender: Sender<RefCountReport>,
+    },
 }
 
 /// Converts `ConnectionManagerNotification` into `PeerManagerMessage::InternalNotification`
@@ -98,11 +124,17 @@ pub(crate) enum PeerManagerRequest {
     RemovePeer {
         peer_id: PeerTokenPair,
         sender: Sender<Result<(), PeerRefRemoveError>>,
+        /// The call stack that removed the reference, if diagnostics are enabled
+        #[cfg(feature = "peer-ref-diagnostics")]
+        caller: Option<String>,
     },
     RemovePeerByEndpoint {
         endpoint: String,
         connection_id: String,
         sender: Sender<Result<(), PeerRefRemoveError>>,
+        /// The call stack that removed the reference, if diagnostics are enabled
+        #[cfg(feature = "peer-ref-diagnostics")]
+        caller: Option<String>,
     },
     ListPeers {
         sender: Sender<Result<Vec<PeerAuthorizationToken>, PeerListError>>,
@@ -129,6 +161,28 @@ pub(crate) enum PeerManagerRequest {
         subscriber_id: SubscriberId,
         sender: Sender<Result<(), PeerManagerError>>,
     },
+    #[cfg(feature = "peer-disconnect")]
+    DisconnectPeer {
+        peer_id: String,
+        ban: Option<Duration>,
+        sender: Sender<Result<usize, PeerDisconnectError>>,
+    },
+    #[cfg(feature = "peer-endpoint-pin")]
+    PinEndpoint {
+        peer_id: String,
+        endpoint: String,
+        sender: Sender<Result<(), PeerEndpointPinError>>,
+    },
+    #[cfg(feature = "peer-endpoint-pin")]
+    UnpinEndpoint {
+        peer_id: String,
+        sender: Sender<Result<Option<String>, PeerEndpointPinError>>,
+    },
+    #[cfg(feature = "peer-endpoint-pin")]
+    GetPinnedEndpoint {
+        peer_id: String,
+        sender: Sender<Result<Option<String>, PeerEndpointPinError>>,
+    },
 }
 
 /// The `PeerManager` is in charge of keeping track of peers and their reference counts, as well as
@@ -219,6 +273,8 @@ impl PeerManager {
         retry_frequency: u64,
         max_retry_frequency: u64,
         endpoint_retry_frequency: u64,
+        #[cfg(feature = "transport-ipv6")] preferred_address_family: Option<AddressFamily>,
+        #[cfg(feature = "outbound-only")] outbound_only: bool,
     ) -> Result<PeerManager, PeerManagerError> {
         debug!(
             "Starting peer manager with identity={}, retry_interval={}s, max_retry_attempts={} \
@@ -235,8 +291,13 @@ impl PeerManager {
 
         let (sender, recv) = channel();
 
+        #[cfg(feature = "peer-ref-diagnostics")]
+        let mut ref_diagnostics = RefDiagnostics::new(false);
+
         let peer_remover = PeerRemover {
             sender: sender.clone(),
+            #[cfg(feature = "peer-ref-diagnostics")]
+            diagnostics: ref_diagnostics.enabled_flag(),
         };
 
         let subscriber_id = connector.subscribe(sender.clone()).map_err(|err| {
@@ -269,6 +330,8 @@ impl PeerManager {
                 let mut unreferenced_peers = UnreferencedPeerState::new(endpoint_retry_frequency);
                 let mut ref_map = RefMap::new();
                 let mut subscribers = SubscriberMap::new();
+                #[cfg(feature = "peer-ref-diagnostics")]
+                let mut strict_ref_counts = strict_ref_counts;
                 loop {
                     match recv.recv() {
                         Ok(PeerManagerMessage::Shutdown) => break,
@@ -282,8 +345,47 @@ impl PeerManager {
                                 &mut ref_map,
                                 &mut subscribers,
                                 strict_ref_counts,
+                                #[cfg(feature = "peer-ref-diagnostics")]
+                                &mut ref_diagnostics,
+                                #[cfg(feature = "transport-ipv6")]
+                                preferred_address_family,
+                                #[cfg(feature = "outbound-only")]
+                                outbound_only,
                             );
                         }
+                        #[cfg(feature = "peer-ref-diagnostics")]
+                        Ok(PeerManagerMessage::RefCounts {
+                            strict,
+                            diagnostics,
+                            sender,
+                        }) => {
+                            if let Some(strict) = strict {
+                                info!("Setting strict reference counts to {}", strict);
+                                strict_ref_counts = strict;
+                            }
+                            if let Some(diagnostics) = diagnostics {
+                                info!("Setting reference count diagnostics to {}", diagnostics);
+                                ref_diagnostics.set_enabled(diagnostics);
+                            }
+
+                            let mut peers = ref_map
+                                .iter()
+                                .map(|(peer_id, ref_count)| PeerRefCount {
+                                    peer_id: peer_id.clone(),
+                                    ref_count,
+                                })
+                                .collect::<Vec<_>>();
+                            peers.sort_by_key(|peer| peer.peer_id.to_string());
+
+                            let report = RefCountReport {
+                                strict: strict_ref_counts,
+                                diagnostics: ref_diagnostics.is_enabled(),
+                                peers,
+                            };
+                            if sender.send(report).is_err() {
+                                warn!("Connector dropped before receiving reference counts");
+                            }
+                        }
                         Ok(PeerManagerMessage::Subscribe(sender)) => {
                             // drop subscriber id because it will not be sent back
                             subscribers.add_subscriber(Box::new(move |notification| {
@@ -374,6 +476,9 @@ fn handle_request(
     ref_map: &mut RefMap<PeerTokenPair>,
     subscribers: &mut SubscriberMap,
     strict_ref_counts: bool,
+    #[cfg(feature = "peer-ref-diagnostics")] ref_diagnostics: &mut RefDiagnostics,
+    #[cfg(feature = "transport-ipv6")] preferred_address_family: Option<AddressFamily>,
+    #[cfg(feature = "outbound-only")] outbound_only: bool,
 ) {
     match request {
         PeerManagerRequest::AddPeer {
@@ -382,6 +487,16 @@ fn handle_request(
             required_local_auth,
             sender,
         } => {
+            #[cfg(feature = "transport-ipv6")]
+            let endpoints = match preferred_address_family {
+                Some(family) => {
+                    let mut endpoints = endpoints;
+                    order_endpoints(&mut endpoints, family);
+                    endpoints
+                }
+                None => endpoints,
+            };
+
             if sender
                 .send(add_peer(
                     peer_id,
@@ -393,6 +508,8 @@ fn handle_request(
                     ref_map,
                     subscribers,
                     required_local_auth,
+                    #[cfg(feature = "outbound-only")]
+                    outbound_only,
                 ))
                 .is_err()
             {
@@ -419,7 +536,12 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of adding unidentified peer");
             }
         }
-        PeerManagerRequest::RemovePeer { peer_id, sender } => {
+        PeerManagerRequest::RemovePeer {
+            peer_id,
+            sender,
+            #[cfg(feature = "peer-ref-diagnostics")]
+            caller,
+        } => {
             if sender
                 .send(remove_peer(
                     peer_id,
@@ -428,6 +550,10 @@ fn handle_request(
                     peers,
                     ref_map,
                     strict_ref_counts,
+                    #[cfg(feature = "peer-ref-diagnostics")]
+                    caller,
+                    #[cfg(feature = "peer-ref-diagnostics")]
+                    ref_diagnostics,
                 ))
                 .is_err()
             {
@@ -438,6 +564,8 @@ fn handle_request(
             endpoint,
             connection_id,
             sender,
+            #[cfg(feature = "peer-ref-diagnostics")]
+            caller,
         } => {
             if sender
                 .send(remove_peer_by_endpoint(
@@ -447,6 +575,10 @@ fn handle_request(
                     peers,
                     ref_map,
                     strict_ref_counts,
+                    #[cfg(feature = "peer-ref-diagnostics")]
+                    caller,
+                    #[cfg(feature = "peer-ref-diagnostics")]
+                    ref_diagnostics,
                 ))
                 .is_err()
             {
@@ -521,9 +653,199 @@ fn handle_request(
                 warn!("connector dropped before receiving result of remove connection");
             }
         }
+        #[cfg(feature = "peer-disconnect")]
+        PeerManagerRequest::DisconnectPeer {
+            peer_id,
+            ban,
+            sender,
+        } => {
+            if sender
+                .send(Ok(disconnect_peer(
+                    peer_id,
+                    ban,
+                    connector,
+                    unreferenced_peers,
+                    peers,
+                    subscribers,
+                )))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of disconnecting peer");
+            }
+        }
+        #[cfg(feature = "peer-endpoint-pin")]
+        PeerManagerRequest::PinEndpoint {
+            peer_id,
+            endpoint,
+            sender,
+        } => {
+            pin_endpoint(peer_id, endpoint, connector, peers, subscribers);
+            if sender.send(Ok(())).is_err() {
+                warn!("Connector dropped before receiving result of pinning endpoint");
+            }
+        }
+        #[cfg(feature = "peer-endpoint-pin")]
+        PeerManagerRequest::UnpinEndpoint { peer_id, sender } => {
+            let endpoint = peers.unpin_endpoint(&peer_id);
+            if let Some(endpoint) = &endpoint {
+                info!("Unpinned peer {} from endpoint {}", peer_id, endpoint);
+            }
+            if sender.send(Ok(endpoint)).is_err() {
+                warn!("Connector dropped before receiving result of unpinning endpoint");
+            }
+        }
+        #[cfg(feature = "peer-endpoint-pin")]
+        PeerManagerRequest::GetPinnedEndpoint { peer_id, sender } => {
+            let endpoint = peers.pinned_endpoint(&peer_id).map(ToString::to_string);
+            if sender.send(Ok(endpoint)).is_err() {
+                warn!("Connector dropped before receiving result of getting pinned endpoint");
+            }
+        }
     };
 }
 
+/// Pins the peer with the given ID to the given endpoint. Peers that are connected, or being
+/// connected to, through another endpoint are disconnected and reconnected through the pinned
+/// endpoint.
+#[cfg(feature = "peer-endpoint-pin")]
+fn pin_endpoint(
+    peer_id: String,
+    endpoint: String,
+    connector: Connector,
+    peers: &mut PeerMap,
+    subscribers: &mut SubscriberMap,
+) {
+    info!("Pinning peer {} to endpoint {}", peer_id, endpoint);
+    peers.pin_endpoint(peer_id.clone(), endpoint.clone());
+
+    for mut peer_metadata in peers.get_by_id_string(&peer_id) {
+        if peer_metadata.active_endpoint == endpoint {
+            continue;
+        }
+
+        if peer_metadata.status != PeerStatus::Pending {
+            info!(
+                "Reconnecting peer {} through pinned endpoint {}",
+                peer_metadata.id, endpoint
+            );
+            if let Err(err) = connector
+                .remove_connection(&peer_metadata.active_endpoint, &peer_metadata.connection_id)
+            {
+                error!(
+                    "Unable to close connection to {}: {}",
+                    peer_metadata.id, err
+                );
+            }
+
+            subscribers.broadcast(PeerManagerNotification::Disconnected {
+                peer: PeerTokenPair::new(
+                    peer_metadata.id.clone(),
+                    peer_metadata.required_local_auth.clone(),
+                ),
+            });
+        }
+
+        if let Err(err) = connector.request_connection(
+            &endpoint,
+            &peer_metadata.connection_id,
+            Some(peer_metadata.id.clone().into()),
+            Some(peer_metadata.required_local_auth.clone().into()),
+        ) {
+            log_connect_request_err(err, &peer_metadata.id, &endpoint);
+        }
+
+        peer_metadata.active_endpoint = endpoint.clone();
+        peer_metadata.status = PeerStatus::Pending;
+        peer_metadata.last_connection_attempt = Instant::now();
+        if let Err(err) = peers.update_peer(peer_metadata) {
+            error!("Unable to update peer: {}", err);
+        }
+    }
+}
+
+/// Closes every connection to the peer with the given ID, optionally preventing it from
+/// reconnecting for the given duration. Referenced peers are set back to pending, so they are
+/// reconnected by `retry_pending` once any ban has expired; unreferenced peers are dropped.
+///
+/// Returns the number of connections that were closed.
+#[cfg(feature = "peer-disconnect")]
+fn disconnect_peer(
+    peer_id: String,
+    ban: Option<Duration>,
+    connector: Connector,
+    unreferenced_peers: &mut UnreferencedPeerState,
+    peers: &mut PeerMap,
+    subscribers: &mut SubscriberMap,
+) -> usize {
+    if let Some(ban) = ban {
+        info!("Banning peer {} for {}s", peer_id, ban.as_secs());
+        peers.ban(peer_id.clone(), Instant::now() + ban);
+    }
+
+    let mut closed = 0;
+
+    for mut peer_metadata in peers.get_by_id_string(&peer_id) {
+        if peer_metadata.status == PeerStatus::Pending {
+            continue;
+        }
+
+        info!(
+            "Disconnecting peer {} ({})",
+            peer_metadata.id, peer_metadata.connection_id
+        );
+        match connector
+            .remove_connection(&peer_metadata.active_endpoint, &peer_metadata.connection_id)
+        {
+            Ok(Some(_)) => closed += 1,
+            Ok(None) => (),
+            Err(err) => error!(
+                "Unable to close connection to {}: {}",
+                peer_metadata.id, err
+            ),
+        }
+
+        let notification = PeerManagerNotification::Disconnected {
+            peer: PeerTokenPair::new(
+                peer_metadata.id.clone(),
+                peer_metadata.required_local_auth.clone(),
+            ),
+        };
+
+        peer_metadata.status = PeerStatus::Pending;
+        peer_metadata.last_connection_attempt = Instant::now();
+        if let Err(err) = peers.update_peer(peer_metadata) {
+            error!("Unable to update peer: {}", err);
+        }
+
+        subscribers.broadcast(notification);
+    }
+
+    let unreferenced_ids: Vec<PeerTokenPair> = unreferenced_peers
+        .peers
+        .keys()
+        .filter(|id| id.peer_id().id_as_string() == peer_id)
+        .cloned()
+        .collect();
+    for id in unreferenced_ids {
+        if let Some(unreferenced_peer) = unreferenced_peers.peers.remove(&id) {
+            info!(
+                "Disconnecting unreferenced peer {} ({})",
+                id, unreferenced_peer.connection_id
+            );
+            match connector.remove_connection(
+                &unreferenced_peer.endpoint,
+                &unreferenced_peer.connection_id,
+            ) {
+                Ok(Some(_)) => closed += 1,
+                Ok(None) => (),
+                Err(err) => error!("Unable to close connection to {}: {}", id, err),
+            }
+        }
+    }
+
+    closed
+}
+
 // Allow clippy errors for too_many_arguments. The arguments are required
 // to avoid needing a lock in the PeerManager.
 #[allow(clippy::too_many_arguments)]
@@ -537,6 +859,7 @@ fn add_peer(
     ref_map: &mut RefMap<PeerTokenPair>,
     subscribers: &mut SubscriberMap,
     required_local_auth: PeerAuthorizationToken,
+    #[cfg(feature = "outbound-only")] outbound_only: bool,
 ) -> Result<PeerRef, PeerRefAddError> {
     let peer_token_pair = PeerTokenPair::new(peer_id.clone(), required_local_auth.clone());
 
@@ -647,8 +970,33 @@ fn add_peer(
     info!("Attempting to peer with {}", peer_id);
     let connection_id = format!("{}", Uuid::new_v4());
 
-    let mut active_endpoint = match endpoints.get(0) {
+    // A pinned endpoint is used in place of the peer's own endpoints
+    #[cfg(feature = "peer-endpoint-pin")]
+    let connection_endpoints = match peers.pinned_endpoint(&peer_id.id_as_string()) {
+        Some(endpoint) => vec![endpoint.to_string()],
+        None => endpoints.clone(),
+    };
+    #[cfg(not(feature = "peer-endpoint-pin"))]
+    let connection_endpoints = endpoints.clone();
+
+    let mut active_endpoint = match connection_endpoints.get(0) {
         Some(endpoint) => endpoint.to_string(),
+        // A peer without endpoints is outbound-only; wait for it to connect, unless this node
+        // cannot accept its connection either
+        #[cfg(feature = "outbound-only")]
+        None if !outbound_only => {
+            info!("Waiting for outbound-only peer {} to connect", peer_id);
+            peers.insert(
+                peer_id,
+                connection_id,
+                vec![],
+                String::new(),
+                PeerStatus::Pending,
+                required_local_auth,
+                vec![],
+            );
+            return Ok(PeerRef::new(peer_token_pair, peer_remover.clone()));
+        }
         None => {
             // remove ref we just added
             if let Err(err) = ref_map.remove_ref(&peer_token_pair) {
@@ -664,7 +1012,7 @@ fn add_peer(
         }
     };
 
-    for endpoint in endpoints.iter() {
+    for endpoint in connection_endpoints.iter() {
         match connector.request_connection(
             endpoint,
             &connection_id,
@@ -747,6 +1095,9 @@ fn add_unidentified(
     EndpointPeerRef::new(endpoint, connection_id, peer_remover.clone())
 }
 
+// Allow clippy errors for too_many_arguments. The arguments are required
+// to avoid needing a lock in the PeerManager.
+#[allow(clippy::too_many_arguments)]
 fn remove_peer(
     peer_id: PeerTokenPair,
     connector: Connector,
@@ -754,6 +1105,8 @@ fn remove_peer(
     peers: &mut PeerMap,
     ref_map: &mut RefMap<PeerTokenPair>,
     strict_ref_counts: bool,
+    #[cfg(feature = "peer-ref-diagnostics")] caller: Option<String>,
+    #[cfg(feature = "peer-ref-diagnostics")] ref_diagnostics: &mut RefDiagnostics,
 ) -> Result<(), PeerRefRemoveError> {
     debug!("Removing peer: {}", peer_id);
 
@@ -764,6 +1117,9 @@ fn remove_peer(
     let removed_peer = match ref_map.remove_ref(&peer_id) {
         Ok(removed_peer) => removed_peer,
         Err(err) => {
+            #[cfg(feature = "peer-ref-diagnostics")]
+            ref_diagnostics.log_invalid_remove(&peer_id, caller.as_deref());
+
             if strict_ref_counts {
                 panic!(
                     "Trying to remove a reference that does not exist: {}",
@@ -779,6 +1135,9 @@ fn remove_peer(
     };
 
     if removed_peer.is_some() {
+        #[cfg(feature = "peer-ref-diagnostics")]
+        ref_diagnostics.record_release(&peer_id, caller);
+
         let peer_metadata = peers.remove(&peer_id).ok_or_else(|| {
             PeerRefRemoveError::Remove(format!(
                 "Peer {} has already been removed from the peer map",
@@ -812,6 +1171,9 @@ fn remove_peer(
     }
 }
 
+// Allow clippy errors for too_many_arguments. The arguments are required
+// to avoid needing a lock in the PeerManager.
+#[allow(clippy::too_many_arguments)]
 fn remove_peer_by_endpoint(
     endpoint: String,
     connection_id: String,
@@ -819,6 +1181,8 @@ fn remove_peer_by_endpoint(
     peers: &mut PeerMap,
     ref_map: &mut RefMap<PeerTokenPair>,
     strict_ref_counts: bool,
+    #[cfg(feature = "peer-ref-diagnostics")] caller: Option<String>,
+    #[cfg(feature = "peer-ref-diagnostics")] ref_diagnostics: &mut RefDiagnostics,
 ) -> Result<(), PeerRefRemoveError> {
     let peer_metadata = match peers.get_by_connection_id(&connection_id) {
         Some(peer_metadata) => peer_metadata,
@@ -843,6 +1207,9 @@ fn remove_peer_by_endpoint(
     let removed_peer = match ref_map.remove_ref(&peer_token_pair) {
         Ok(removed_peer) => removed_peer,
         Err(err) => {
+            #[cfg(feature = "peer-ref-diagnostics")]
+            ref_diagnostics.log_invalid_remove(&peer_token_pair, caller.as_deref());
+
             if strict_ref_counts {
                 panic!(
                     "Trying to remove a reference that does not exist: {}",
@@ -857,6 +1224,9 @@ fn remove_peer_by_endpoint(
         }
     };
     if let Some(removed_peer) = removed_peer {
+        #[cfg(feature = "peer-ref-diagnostics")]
+        ref_diagnostics.record_release(&removed_peer, caller);
+
         let peer_metadata = peers.remove(&removed_peer).ok_or_else(|| {
             PeerRefRemoveError::Remove(format!(
                 "Peer with endpoint {} has already been removed from the peer map",
@@ -945,7 +1315,11 @@ fn handle_notifications(
                         "Attempting to find available endpoint for {}",
                         peer_metadata.id
                     );
-                    for endpoint in peer_metadata.endpoints.iter() {
+                    #[cfg(feature = "peer-endpoint-pin")]
+                    let endpoints = peers.connection_endpoints(&peer_metadata);
+                    #[cfg(not(feature = "peer-endpoint-pin"))]
+                    let endpoints = peer_metadata.endpoints.clone();
+                    for endpoint in endpoints.iter() {
                         // do not retry the connection that is currently failing
                         if endpoint == &peer_metadata.active_endpoint {
                             continue;
@@ -1046,7 +1420,11 @@ fn handle_disconnection(
             ),
         };
         info!("Peer {} is currently disconnected", peer_metadata.id);
-        if peer_metadata.endpoints.contains(&endpoint) {
+        #[cfg(feature = "peer-endpoint-pin")]
+        let endpoints = peers.connection_endpoints(&peer_metadata);
+        #[cfg(not(feature = "peer-endpoint-pin"))]
+        let endpoints = peer_metadata.endpoints.clone();
+        if endpoints.contains(&endpoint) {
             // allow peer manager to retry connection to that endpoint until the retry max is
             // reached
 
@@ -1065,8 +1443,13 @@ fn handle_disconnection(
                 error!("Unable to clean up old connection: {}", err);
             }
 
+            #[cfg(feature = "outbound-only")]
+            if peer_metadata.endpoints.is_empty() {
+                info!("Waiting for outbound-only peer {} to reconnect", identity);
+            }
+
             info!("Attempting to find available endpoint for {}", identity);
-            for endpoint in peer_metadata.endpoints.iter() {
+            for endpoint in endpoints.iter() {
                 match connector.request_connection(
                     endpoint,
                     &peer_metadata.connection_id,
@@ -1120,6 +1503,20 @@ fn handle_inbound_connection(
         identity, endpoint
     );
 
+    #[cfg(feature = "peer-disconnect")]
+    {
+        if peers.is_banned(&identity) {
+            info!(
+                "Rejecting connection from banned peer {} ({})",
+                identity, connection_id
+            );
+            if let Err(err) = connector.remove_connection(&endpoint, &connection_id) {
+                error!("Unable to clean up connection: {}", err);
+            }
+            return;
+        }
+    }
+
     let peer_token_pair = PeerTokenPair::new(identity.clone(), local_authorization.clone());
     // If we got an inbound counnection for an existing peer, replace old connection with
     // this new one unless we are already connected.
@@ -1255,6 +1652,20 @@ fn handle_connected(
     ref_map: &mut RefMap<PeerTokenPair>,
     retry_frequency: u64,
 ) {
+    #[cfg(feature = "peer-disconnect")]
+    {
+        if peers.is_banned(&identity) {
+            info!(
+                "Closing connection to banned peer {} ({})",
+                identity, connection_id
+            );
+            if let Err(err) = connector.remove_connection(&endpoint, &connection_id) {
+                error!("Unable to clean up connection: {}", err);
+            }
+            return;
+        }
+    }
+
     let peer_token_pair = PeerTokenPair::new(identity.clone(), local_authorization.clone());
     if let Some(mut peer_metadata) = peers.get_by_peer_id(&peer_token_pair).cloned() {
         match peer_metadata.status {
@@ -1490,8 +1901,24 @@ fn retry_pending(
     unreferenced_peers: &mut UnreferencedPeerState,
     max_retry_frequency: u64,
 ) {
+    #[cfg(feature = "peer-disconnect")]
+    peers.remove_expired_bans();
+
     let mut to_retry = Vec::new();
     for (_, peer) in peers.get_pending() {
+        #[cfg(feature = "peer-disconnect")]
+        {
+            if peers.is_banned(&peer.id) {
+                continue;
+            }
+        }
+        // Outbound-only peers cannot be connected to; they will reconnect on their own
+        #[cfg(feature = "outbound-only")]
+        {
+            if peer.endpoints.is_empty() {
+                continue;
+            }
+        }
         if peer.last_connection_attempt.elapsed().as_secs() > peer.retry_frequency {
             to_retry.push(peer.clone());
         }
@@ -1499,7 +1926,11 @@ fn retry_pending(
 
     for mut peer_metadata in to_retry {
         debug!("Attempting to peer with pending peer {}", peer_metadata.id);
-        for endpoint in peer_metadata.endpoints.iter() {
+        #[cfg(feature = "peer-endpoint-pin")]
+        let endpoints = peers.connection_endpoints(&peer_metadata);
+        #[cfg(not(feature = "peer-endpoint-pin"))]
+        let endpoints = peer_metadata.endpoints.clone();
+        for endpoint in endpoints.iter() {
             match connector.request_connection(
                 endpoint,
                 &peer_metadata.connection_id,
@@ -1648,6 +2079,8 @@ pub mod tests {
 
     use std::collections::VecDeque;
     use std::sync::mpsc;
+    #[cfg(feature = "peer-ref-diagnostics")]
+    use std::sync::{atomic::AtomicBool, Arc};
     use std::time::Duration;
 
     use protobuf::Message;
@@ -1744,6 +2177,176 @@ pub mod tests {
         mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that the reference count settings can be changed while the peer manager is running,
+    // and that removing a reference that does not exist returns an error once strict reference
+    // counts are disabled
+    //
+    // 1. start a peer manager with strict reference counts and add test_peer twice
+    // 2. verify that the reference count report contains test_peer with a count of 2
+    // 3. disable strict reference counts and enable diagnostics; verify the report
+    // 4. drop both peer refs and verify that test_peer is no longer reported
+    // 5. remove test_peer again and verify that an error is returned instead of a panic
+    #[cfg(feature = "peer-ref-diagnostics")]
+    #[test]
+    fn test_peer_manager_ref_count_mode() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(cm.connector())
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+
+        let peer_id = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            PeerAuthorizationToken::from_peer_id("my_id"),
+        );
+        let peer_refs = (0..2)
+            .map(|_| {
+                peer_connector
+                    .add_peer_ref(
+                        PeerAuthorizationToken::from_peer_id("test_peer"),
+                        vec!["inproc://test".to_string()],
+                        PeerAuthorizationToken::from_peer_id("my_id"),
+                    )
+                    .expect("Unable to add peer")
+            })
+            .collect::<Vec<_>>();
+
+        let report = peer_connector
+            .ref_counts()
+            .expect("Unable to get reference counts");
+        assert!(report.strict);
+        assert!(!report.diagnostics);
+        assert_eq!(
+            report.peers,
+            vec![PeerRefCount {
+                peer_id: peer_id.clone(),
+                ref_count: 2,
+            }]
+        );
+
+        let report = peer_connector
+            .set_ref_count_mode(Some(false), Some(true))
+            .expect("Unable to set reference count mode");
+        assert!(!report.strict);
+        assert!(report.diagnostics);
+
+        drop(peer_refs);
+        let report = peer_connector
+            .ref_counts()
+            .expect("Unable to get reference counts");
+        assert!(report.peers.is_empty());
+
+        let peer_remover = PeerRemover {
+            sender: peer_manager.sender.clone(),
+            diagnostics: Arc::new(AtomicBool::new(true)),
+        };
+        assert!(peer_remover.remove_peer_ref(&peer_id).is_err());
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
+    // Test that a peer without endpoints is only accepted by a node that is not outbound-only
+    //
+    // 1. start a peer manager that accepts inbound connections and add test_peer without any
+    //    endpoints; verify that the peer is added
+    // 2. start an outbound-only peer manager and add test_peer without any endpoints; verify that
+    //    an error is returned, since neither node could connect to the other
+    #[cfg(feature = "outbound-only")]
+    #[test]
+    fn test_peer_manager_add_outbound_only_peer() {
+        let transport = Box::new(InprocTransport::default());
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(cm.connector())
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let peer_ref = peer_connector
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec![],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add outbound-only peer");
+        assert_eq!(
+            peer_ref.peer_id(),
+            &PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+        );
+        drop(peer_ref);
+
+        let mut outbound_only_peer_manager = PeerManager::builder()
+            .with_connector(cm.connector())
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .with_outbound_only(true)
+            .start()
+            .expect("Cannot start outbound-only peer_manager");
+        assert!(outbound_only_peer_manager
+            .connector()
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec![],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .is_err());
+
+        outbound_only_peer_manager.signal_shutdown();
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        outbound_only_peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown outbound-only peer manager");
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     // Test that a call to add_peer_ref, where the peer being added is a different trust peer id
     // with an endpoint that already belongs to another trust peer id is rejected.
     //
@@ -2401,6 +3004,186 @@ pub mod tests {
         mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that a peer can be forcibly disconnected and banned while still referenced
+    //
+    // 1. add test_peer and verify that a Connected notification is received
+    // 2. disconnect test_peer with a ban and verify that one connection was closed
+    // 3. verify that a Disconnected notification is received
+    // 4. verify that test_peer is still in the peer list, as it is still referenced
+    // 5. disconnect test_peer again and verify that no connections were closed
+    #[cfg(feature = "peer-disconnect")]
+    #[test]
+    fn test_peer_manager_disconnect_peer() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+        let _peer_ref = peer_connector
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://test".to_string()],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add peer");
+
+        let peer = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            PeerAuthorizationToken::from_peer_id("my_id"),
+        );
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(notification == PeerManagerNotification::Connected { peer: peer.clone() });
+
+        assert_eq!(
+            peer_connector.disconnect_peer("test_peer", Some(Duration::from_secs(60))),
+            Ok(1)
+        );
+
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(notification == PeerManagerNotification::Disconnected { peer });
+
+        let peer_list = peer_connector
+            .list_peers()
+            .expect("Unable to get peer list");
+        assert_eq!(
+            peer_list,
+            vec![PeerAuthorizationToken::from_peer_id("test_peer")]
+        );
+
+        assert_eq!(peer_connector.disconnect_peer("test_peer", None), Ok(0));
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
+    // Test that a pinned endpoint is used in place of a peer's own endpoints
+    //
+    // 1. pin test_peer to inproc://test and verify the pin is returned
+    // 2. add test_peer with only an unreachable endpoint
+    // 3. verify that a Connected notification is received, as the pinned endpoint was used
+    // 4. unpin test_peer and verify the pinned endpoint is returned
+    #[cfg(feature = "peer-endpoint-pin")]
+    #[test]
+    fn test_peer_manager_pin_endpoint() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+
+        peer_connector
+            .pin_endpoint("test_peer", "inproc://test")
+            .expect("Unable to pin endpoint");
+        assert_eq!(
+            peer_connector.pinned_endpoint("test_peer"),
+            Ok(Some("inproc://test".to_string()))
+        );
+
+        let _peer_ref = peer_connector
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://unreachable".to_string()],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add peer");
+
+        let peer = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            PeerAuthorizationToken::from_peer_id("my_id"),
+        );
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(notification == PeerManagerNotification::Connected { peer });
+
+        assert_eq!(
+            peer_connector.unpin_endpoint("test_peer"),
+            Ok(Some("inproc://test".to_string()))
+        );
+        assert_eq!(peer_connector.pinned_endpoint("test_peer"), Ok(None));
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     // Test that when a EndpointPeerRef is dropped, a remove peer request is properly sent and the
     // peer is removed
     //
@@ -65,6 +65,14 @@ pub struct PeerMap {
     initial_retry_frequency: u64,
     // If a peer's connection id changes store it off incase it is cached.
     removed_connection_ids: HashMap<String, PeerTokenPair>,
+    // Peer IDs (as returned by `PeerAuthorizationToken::id_as_string`) that may not reconnect
+    // until the given time
+    #[cfg(feature = "peer-disconnect")]
+    banned: HashMap<String, Instant>,
+    // Peer IDs (as returned by `PeerAuthorizationToken::id_as_string`) that must be connected to
+    // through the given endpoint
+    #[cfg(feature = "peer-endpoint-pin")]
+    pinned_endpoints: HashMap<String, String>,
 }
 
 impl PeerMap {
@@ -82,6 +90,10 @@ impl PeerMap {
             endpoints: HashMap::new(),
             initial_retry_frequency,
             removed_connection_ids: HashMap::new(),
+            #[cfg(feature = "peer-disconnect")]
+            banned: HashMap::new(),
+            #[cfg(feature = "peer-endpoint-pin")]
+            pinned_endpoints: HashMap::new(),
         }
     }
 
@@ -275,6 +287,90 @@ impl PeerMap {
     pub fn contains_endpoint(&self, endpoint: &str) -> bool {
         self.endpoints.contains_key(endpoint)
     }
+
+    /// Returns the metadata of every peer with the given ID, regardless of the local
+    /// authorization used with the peer
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer ID, as returned by `PeerAuthorizationToken::id_as_string`
+    #[cfg(any(feature = "peer-disconnect", feature = "peer-endpoint-pin"))]
+    pub fn get_by_id_string(&self, peer_id: &str) -> Vec<PeerMetadata> {
+        self.peers
+            .values()
+            .filter(|metadata| metadata.id.id_as_string() == peer_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Prevents the peer from connecting until the given time
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer ID, as returned by `PeerAuthorizationToken::id_as_string`
+    /// * `until` - The time at which the ban expires
+    #[cfg(feature = "peer-disconnect")]
+    pub fn ban(&mut self, peer_id: String, until: Instant) {
+        self.banned.insert(peer_id, until);
+    }
+
+    /// Returns true if the peer is currently banned
+    #[cfg(feature = "peer-disconnect")]
+    pub fn is_banned(&self, peer_id: &PeerAuthorizationToken) -> bool {
+        self.banned
+            .get(&peer_id.id_as_string())
+            .map(|until| *until > Instant::now())
+            .unwrap_or(false)
+    }
+
+    /// Forgets bans that have expired
+    #[cfg(feature = "peer-disconnect")]
+    pub fn remove_expired_bans(&mut self) {
+        let now = Instant::now();
+        self.banned.retain(|_, until| *until > now);
+    }
+
+    /// Pins the peer to the given endpoint, which is used in place of the peer's own endpoints
+    /// whenever it is connected to
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer ID, as returned by `PeerAuthorizationToken::id_as_string`
+    /// * `endpoint` - The endpoint to connect to the peer through
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn pin_endpoint(&mut self, peer_id: String, endpoint: String) {
+        self.pinned_endpoints.insert(peer_id, endpoint);
+    }
+
+    /// Removes the peer's pinned endpoint, returning it if there was one
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer ID, as returned by `PeerAuthorizationToken::id_as_string`
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn unpin_endpoint(&mut self, peer_id: &str) -> Option<String> {
+        self.pinned_endpoints.remove(peer_id)
+    }
+
+    /// Returns the endpoint the peer is pinned to, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer ID, as returned by `PeerAuthorizationToken::id_as_string`
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn pinned_endpoint(&self, peer_id: &str) -> Option<&str> {
+        self.pinned_endpoints.get(peer_id).map(String::as_str)
+    }
+
+    /// Returns the endpoints to try, in order, when connecting to the peer: its pinned endpoint,
+    /// if it has one, otherwise its own endpoints
+    #[cfg(feature = "peer-endpoint-pin")]
+    pub fn connection_endpoints(&self, peer_metadata: &PeerMetadata) -> Vec<String> {
+        match self.pinned_endpoint(&peer_metadata.id.id_as_string()) {
+            Some(endpoint) => vec![endpoint.to_string()],
+            None => peer_metadata.endpoints.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -654,4 +750,83 @@ pub mod tests {
             PeerStatus::Disconnected { retry_attempts: 5 }
         );
     }
+
+    // Test that bans only apply to the banned peer until they expire
+    //  1. Ban a peer until a future time and verify it is banned, and another peer is not
+    //  2. Ban a peer until an elapsed time and verify it is not banned
+    //  3. Remove expired bans and verify only the active ban remains
+    #[cfg(feature = "peer-disconnect")]
+    #[test]
+    fn test_ban() {
+        use std::time::Duration;
+
+        let mut peer_map = PeerMap::new(10);
+        let banned_peer = PeerAuthorizationToken::from_peer_id("banned_peer");
+        let expired_peer = PeerAuthorizationToken::from_peer_id("expired_peer");
+        let other_peer = PeerAuthorizationToken::from_peer_id("other_peer");
+
+        peer_map.ban(
+            banned_peer.id_as_string(),
+            Instant::now() + Duration::from_secs(60),
+        );
+        assert!(peer_map.is_banned(&banned_peer));
+        assert!(!peer_map.is_banned(&other_peer));
+
+        peer_map.ban(expired_peer.id_as_string(), Instant::now());
+        assert!(!peer_map.is_banned(&expired_peer));
+
+        peer_map.remove_expired_bans();
+        assert_eq!(peer_map.banned.len(), 1);
+        assert!(peer_map.is_banned(&banned_peer));
+    }
+
+    // Test that a pinned endpoint replaces a peer's endpoints until it is unpinned
+    //  1. Insert a peer and verify its own endpoints are used
+    //  2. Pin the peer to another endpoint and verify only the pinned endpoint is used
+    //  3. Unpin the peer and verify the pinned endpoint is returned and its own endpoints are
+    //     used again
+    #[cfg(feature = "peer-endpoint-pin")]
+    #[test]
+    fn test_pin_endpoint() {
+        let mut peer_map = PeerMap::new(10);
+        peer_map.insert(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            "connection_id".to_string(),
+            vec!["tcp://one:8080".to_string(), "tcp://two:8080".to_string()],
+            "tcp://one:8080".to_string(),
+            PeerStatus::Connected,
+            PeerAuthorizationToken::from_peer_id("my_id"),
+            vec![],
+        );
+        let peer_metadata = peer_map
+            .get_by_id_string("test_peer")
+            .pop()
+            .expect("Missing peer metadata");
+
+        assert_eq!(peer_map.pinned_endpoint("test_peer"), None);
+        assert_eq!(
+            peer_map.connection_endpoints(&peer_metadata),
+            vec!["tcp://one:8080".to_string(), "tcp://two:8080".to_string()]
+        );
+
+        peer_map.pin_endpoint("test_peer".to_string(), "tcp://vpn:8080".to_string());
+        assert_eq!(
+            peer_map.pinned_endpoint("test_peer"),
+            Some("tcp://vpn:8080")
+        );
+        assert_eq!(
+            peer_map.connection_endpoints(&peer_metadata),
+            vec!["tcp://vpn:8080".to_string()]
+        );
+
+        assert_eq!(
+            peer_map.unpin_endpoint("test_peer"),
+            Some("tcp://vpn:8080".to_string())
+        );
+        assert_eq!(peer_map.unpin_endpoint("test_peer"), None);
+        assert_eq!(
+            peer_map.connection_endpoints(&peer_metadata),
+            vec!["tcp://one:8080".to_string(), "tcp://two:8080".to_string()]
+        );
+    }
 }
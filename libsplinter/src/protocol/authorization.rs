@@ -285,6 +285,16 @@ pub struct AuthChallengeNonceResponse {
 pub struct SubmitRequest {
     pub public_key: PublicKey,
     pub signature: Vec<u8>,
+    pub endpoint_signature: Option<EndpointSignature>,
+}
+
+/// A signature binding a challenge to the endpoint the connecting node dialed
+///
+/// The signature is created by signing the nonce followed by the bytes of the endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointSignature {
+    pub endpoint: String,
+    pub signature: Vec<u8>,
 }
 
 /// A challenge submit request
@@ -447,9 +457,22 @@ impl FromProto<authorization::AuthChallengeSubmitRequest> for AuthChallengeSubmi
             submit_requests: source
                 .take_submit_requests()
                 .into_iter()
-                .map(|mut submit_request| SubmitRequest {
-                    public_key: PublicKey::from_bytes(submit_request.take_public_key()),
-                    signature: submit_request.take_signature(),
+                .map(|mut submit_request| {
+                    let endpoint = submit_request.take_endpoint();
+                    let endpoint_signature = if endpoint.is_empty() {
+                        None
+                    } else {
+                        Some(EndpointSignature {
+                            endpoint,
+                            signature: submit_request.take_endpoint_signature(),
+                        })
+                    };
+
+                    SubmitRequest {
+                        public_key: PublicKey::from_bytes(submit_request.take_public_key()),
+                        signature: submit_request.take_signature(),
+                        endpoint_signature,
+                    }
                 })
                 .collect(),
         })
@@ -466,6 +489,11 @@ impl FromNative<AuthChallengeSubmitRequest> for authorization::AuthChallengeSubm
                 let mut proto_submit_request = authorization::SubmitRequest::new();
                 proto_submit_request.set_public_key(submit_request.public_key.clone().into_bytes());
                 proto_submit_request.set_signature(submit_request.signature.to_vec());
+                if let Some(endpoint_signature) = &submit_request.endpoint_signature {
+                    proto_submit_request.set_endpoint(endpoint_signature.endpoint.clone());
+                    proto_submit_request
+                        .set_endpoint_signature(endpoint_signature.signature.clone());
+                }
                 proto_submit_request
             })
             .collect();
@@ -14,10 +14,15 @@
 
 //! Protocol structs for working with protocol-level data in Rust.
 
+#[cfg(feature = "build-info")]
+use std::collections::BTreeMap;
+
 pub mod authorization;
 pub mod component;
 pub mod network;
 pub mod service;
+#[cfg(feature = "service-protocol-version")]
+pub mod service_version;
 
 // Peer authorization protocol versions
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
@@ -25,3 +30,32 @@ pub const PEER_AUTHORIZATION_PROTOCOL_VERSION: u32 = 1;
 
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
 pub(crate) const PEER_AUTHORIZATION_PROTOCOL_MIN: u32 = 1;
+
+// Service message protocol versions
+#[cfg(feature = "service-protocol-version")]
+pub const SERVICE_PROTOCOL_VERSION: u32 = 1;
+
+#[cfg(feature = "service-protocol-version")]
+pub(crate) const SERVICE_PROTOCOL_MIN: u32 = 1;
+
+/// Returns the current version of each protocol supported by this build of the library, by
+/// protocol name.
+#[cfg(feature = "build-info")]
+pub fn protocol_versions() -> BTreeMap<&'static str, u32> {
+    #[allow(unused_mut)]
+    let mut versions = BTreeMap::new();
+
+    #[cfg(feature = "admin-service")]
+    versions.insert(
+        "admin",
+        crate::admin::service::ADMIN_SERVICE_PROTOCOL_VERSION,
+    );
+    #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
+    versions.insert("peer_authorization", PEER_AUTHORIZATION_PROTOCOL_VERSION);
+    #[cfg(feature = "rest-api")]
+    versions.insert("rest_api", crate::rest_api::SPLINTER_PROTOCOL_VERSION);
+    #[cfg(feature = "service-protocol-version")]
+    versions.insert("service", SERVICE_PROTOCOL_VERSION);
+
+    versions
+}
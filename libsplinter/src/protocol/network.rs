@@ -23,6 +23,8 @@ use super::authorization::AuthorizationMessage;
 pub enum NetworkMessage {
     NetworkEcho(NetworkEcho),
     NetworkHeartbeat(NetworkHeartbeat),
+    NetworkPing(NetworkPing),
+    NetworkPong(NetworkPong),
     Circuit(Vec<u8>),
     Authorization(AuthorizationMessage),
 }
@@ -39,6 +41,19 @@ pub struct NetworkEcho {
 #[derive(Debug)]
 pub struct NetworkHeartbeat;
 
+/// This message is used to measure the round-trip time to a peer, which replies with a
+/// `NetworkPong` carrying the same nonce
+#[derive(Debug)]
+pub struct NetworkPing {
+    pub nonce: u64,
+}
+
+/// This message is the reply to a `NetworkPing`
+#[derive(Debug)]
+pub struct NetworkPong {
+    pub nonce: u64,
+}
+
 impl FromProto<network::NetworkEcho> for NetworkEcho {
     fn from_proto(mut source: network::NetworkEcho) -> Result<Self, ProtoConversionError> {
         Ok(Self {
@@ -72,6 +87,40 @@ impl FromNative<NetworkHeartbeat> for network::NetworkHeartbeat {
     }
 }
 
+impl FromProto<network::NetworkPing> for NetworkPing {
+    fn from_proto(source: network::NetworkPing) -> Result<Self, ProtoConversionError> {
+        Ok(Self {
+            nonce: source.get_nonce(),
+        })
+    }
+}
+
+impl FromNative<NetworkPing> for network::NetworkPing {
+    fn from_native(source: NetworkPing) -> Result<Self, ProtoConversionError> {
+        let mut proto_ping = network::NetworkPing::new();
+        proto_ping.set_nonce(source.nonce);
+
+        Ok(proto_ping)
+    }
+}
+
+impl FromProto<network::NetworkPong> for NetworkPong {
+    fn from_proto(source: network::NetworkPong) -> Result<Self, ProtoConversionError> {
+        Ok(Self {
+            nonce: source.get_nonce(),
+        })
+    }
+}
+
+impl FromNative<NetworkPong> for network::NetworkPong {
+    fn from_native(source: NetworkPong) -> Result<Self, ProtoConversionError> {
+        let mut proto_pong = network::NetworkPong::new();
+        proto_pong.set_nonce(source.nonce);
+
+        Ok(proto_pong)
+    }
+}
+
 impl FromProto<network::NetworkMessage> for NetworkMessage {
     fn from_proto(mut source: network::NetworkMessage) -> Result<Self, ProtoConversionError> {
         use network::NetworkMessageType::*;
@@ -86,6 +135,16 @@ impl FromProto<network::NetworkMessage> for NetworkMessage {
             >::from_bytes(
                 source.get_payload()
             )?)),
+            NETWORK_PING => Ok(NetworkMessage::NetworkPing(FromBytes::<
+                network::NetworkPing,
+            >::from_bytes(
+                source.get_payload()
+            )?)),
+            NETWORK_PONG => Ok(NetworkMessage::NetworkPong(FromBytes::<
+                network::NetworkPong,
+            >::from_bytes(
+                source.get_payload()
+            )?)),
             CIRCUIT => Ok(NetworkMessage::Circuit(source.take_payload())),
             AUTHORIZATION => Ok(NetworkMessage::Authorization(
                 AuthorizationMessage::from_bytes(source.get_payload())?,
@@ -111,6 +170,14 @@ impl FromNative<NetworkMessage> for network::NetworkMessage {
                 message.set_message_type(NETWORK_HEARTBEAT);
                 message.set_payload(IntoBytes::<network::NetworkHeartbeat>::into_bytes(payload)?);
             }
+            NetworkMessage::NetworkPing(payload) => {
+                message.set_message_type(NETWORK_PING);
+                message.set_payload(IntoBytes::<network::NetworkPing>::into_bytes(payload)?);
+            }
+            NetworkMessage::NetworkPong(payload) => {
+                message.set_message_type(NETWORK_PONG);
+                message.set_payload(IntoBytes::<network::NetworkPong>::into_bytes(payload)?);
+            }
             NetworkMessage::Circuit(payload) => {
                 message.set_message_type(CIRCUIT);
                 message.set_payload(payload);
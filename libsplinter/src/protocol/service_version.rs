@@ -0,0 +1,334 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Versioning for service-level messages
+//!
+//! Services that evolve their messages declare the range of service protocol versions they
+//! support. The range is negotiated with the splinter node when the service connects, and each
+//! message may be wrapped in a [`VersionedServiceMessage`] so that the receiver can reject
+//! messages written with a version it does not understand, rather than failing to parse them.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::error::InvalidArgumentError;
+use crate::protos::prelude::*;
+use crate::protos::service;
+
+use super::{SERVICE_PROTOCOL_MIN, SERVICE_PROTOCOL_VERSION};
+
+/// An inclusive range of service protocol versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProtocolVersionRange {
+    min: u32,
+    max: u32,
+}
+
+impl ProtocolVersionRange {
+    /// Constructs a new range of supported versions, from `min` to `max` inclusive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if `min` is 0, which is reserved for services that do
+    /// not version their messages, or if `min` is greater than `max`.
+    pub fn new(min: u32, max: u32) -> Result<Self, InvalidArgumentError> {
+        if min == 0 {
+            return Err(InvalidArgumentError::new(
+                "min",
+                "protocol version 0 is reserved for unversioned services",
+            ));
+        }
+        if min > max {
+            return Err(InvalidArgumentError::new(
+                "min",
+                format!("must not be greater than the maximum version {}", max),
+            ));
+        }
+
+        Ok(Self { min, max })
+    }
+
+    /// Constructs a range that contains only the given version.
+    pub fn single(version: u32) -> Result<Self, InvalidArgumentError> {
+        Self::new(version, version)
+    }
+
+    /// Returns the range of service protocol versions supported by this node.
+    pub fn node_supported() -> Self {
+        Self {
+            min: SERVICE_PROTOCOL_MIN,
+            max: SERVICE_PROTOCOL_VERSION,
+        }
+    }
+
+    /// Returns the lowest supported version.
+    pub fn min(&self) -> u32 {
+        self.min
+    }
+
+    /// Returns the highest supported version.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Returns whether or not the given version is in this range.
+    pub fn contains(&self, version: u32) -> bool {
+        self.min <= version && version <= self.max
+    }
+
+    /// Returns the highest version supported by both this range and the remote range.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProtocolVersionError`] describing both ranges if they do not overlap.
+    pub fn negotiate(&self, remote: &ProtocolVersionRange) -> Result<u32, ProtocolVersionError> {
+        let version = self.max.min(remote.max);
+        if version >= self.min && version >= remote.min {
+            Ok(version)
+        } else {
+            Err(ProtocolVersionError::NoCommonVersion {
+                local: *self,
+                remote: *remote,
+            })
+        }
+    }
+}
+
+impl fmt::Display for ProtocolVersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "{} through {}", self.min, self.max)
+        }
+    }
+}
+
+/// Errors that occur when two parties do not agree on a service protocol version.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProtocolVersionError {
+    /// The local and remote ranges of supported versions do not overlap.
+    NoCommonVersion {
+        local: ProtocolVersionRange,
+        remote: ProtocolVersionRange,
+    },
+    /// A message was written with a version outside of the supported range.
+    UnsupportedVersion {
+        version: u32,
+        supported: ProtocolVersionRange,
+    },
+    /// No range of supported versions has been registered for the service type.
+    UnknownServiceType(String),
+}
+
+impl Error for ProtocolVersionError {}
+
+impl fmt::Display for ProtocolVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProtocolVersionError::NoCommonVersion { local, remote } => write!(
+                f,
+                "no common service protocol version: this side supports version {}, but the \
+                 other side supports version {}; upgrade the side with the older version",
+                local, remote
+            ),
+            ProtocolVersionError::UnsupportedVersion { version, supported } => write!(
+                f,
+                "message uses service protocol version {}, but only version {} is supported",
+                version, supported
+            ),
+            ProtocolVersionError::UnknownServiceType(service_type) => write!(
+                f,
+                "no service protocol versions are registered for service type {}",
+                service_type
+            ),
+        }
+    }
+}
+
+/// The service protocol versions supported by each service type.
+///
+/// Services register the range of versions they support when they are set up, and the registry is
+/// consulted when a service of that type connects or receives a message.
+#[derive(Clone, Debug, Default)]
+pub struct ServiceProtocolVersions {
+    versions: HashMap<String, ProtocolVersionRange>,
+}
+
+impl ServiceProtocolVersions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the range of versions supported by the given service type, replacing any
+    /// previously registered range.
+    pub fn register(&mut self, service_type: &str, versions: ProtocolVersionRange) {
+        self.versions.insert(service_type.to_string(), versions);
+    }
+
+    /// Returns the range of versions registered for the given service type, if any.
+    pub fn get(&self, service_type: &str) -> Option<&ProtocolVersionRange> {
+        self.versions.get(service_type)
+    }
+
+    /// Negotiates a version for the given service type against the remote range.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProtocolVersionError`] if no range has been registered for the service type, or
+    /// if the ranges do not overlap.
+    pub fn negotiate(
+        &self,
+        service_type: &str,
+        remote: &ProtocolVersionRange,
+    ) -> Result<u32, ProtocolVersionError> {
+        self.get(service_type)
+            .ok_or_else(|| ProtocolVersionError::UnknownServiceType(service_type.to_string()))?
+            .negotiate(remote)
+    }
+}
+
+/// A service message, along with the service protocol version it was written with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionedServiceMessage {
+    /// The service protocol version the payload was written with
+    pub protocol_version: u32,
+    /// The service-specific message
+    pub payload: Vec<u8>,
+}
+
+impl VersionedServiceMessage {
+    pub fn new(protocol_version: u32, payload: Vec<u8>) -> Self {
+        Self {
+            protocol_version,
+            payload,
+        }
+    }
+
+    /// Returns the payload if its version is in the supported range.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ProtocolVersionError`] if the payload was written with an unsupported version.
+    pub fn into_payload(
+        self,
+        supported: &ProtocolVersionRange,
+    ) -> Result<Vec<u8>, ProtocolVersionError> {
+        if supported.contains(self.protocol_version) {
+            Ok(self.payload)
+        } else {
+            Err(ProtocolVersionError::UnsupportedVersion {
+                version: self.protocol_version,
+                supported: *supported,
+            })
+        }
+    }
+}
+
+impl FromProto<service::VersionedServiceMessage> for VersionedServiceMessage {
+    fn from_proto(mut msg: service::VersionedServiceMessage) -> Result<Self, ProtoConversionError> {
+        Ok(Self {
+            protocol_version: msg.get_protocol_version(),
+            payload: msg.take_payload(),
+        })
+    }
+}
+
+impl FromNative<VersionedServiceMessage> for service::VersionedServiceMessage {
+    fn from_native(msg: VersionedServiceMessage) -> Result<Self, ProtoConversionError> {
+        let mut proto_msg = service::VersionedServiceMessage::new();
+        proto_msg.set_protocol_version(msg.protocol_version);
+        proto_msg.set_payload(msg.payload);
+
+        Ok(proto_msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that invalid ranges are rejected.
+    #[test]
+    fn range_validation() {
+        assert!(ProtocolVersionRange::new(0, 1).is_err());
+        assert!(ProtocolVersionRange::new(3, 2).is_err());
+        assert!(ProtocolVersionRange::new(2, 2).is_ok());
+    }
+
+    /// Verifies that negotiation picks the highest common version, and reports both ranges when
+    /// there is none.
+    #[test]
+    fn negotiate() {
+        let local = ProtocolVersionRange::new(1, 3).unwrap();
+
+        assert_eq!(
+            local.negotiate(&ProtocolVersionRange::new(2, 5).unwrap()),
+            Ok(3)
+        );
+        assert_eq!(
+            local.negotiate(&ProtocolVersionRange::single(1).unwrap()),
+            Ok(1)
+        );
+
+        let remote = ProtocolVersionRange::new(4, 5).unwrap();
+        let err = local.negotiate(&remote).unwrap_err();
+        assert_eq!(err, ProtocolVersionError::NoCommonVersion { local, remote });
+        assert!(err.to_string().contains("1 through 3"));
+        assert!(err.to_string().contains("4 through 5"));
+    }
+
+    /// Verifies that registered service types negotiate against their own range, and that
+    /// unregistered types are reported.
+    #[test]
+    fn service_protocol_versions() {
+        let mut versions = ServiceProtocolVersions::new();
+        versions.register("scabbard", ProtocolVersionRange::new(1, 2).unwrap());
+
+        assert_eq!(
+            versions.negotiate("scabbard", &ProtocolVersionRange::new(2, 4).unwrap()),
+            Ok(2)
+        );
+        assert_eq!(
+            versions.negotiate("echo", &ProtocolVersionRange::single(1).unwrap()),
+            Err(ProtocolVersionError::UnknownServiceType("echo".into()))
+        );
+    }
+
+    /// Verifies that a versioned message survives a round trip through bytes, and that its
+    /// payload is only returned for a supported version.
+    #[test]
+    fn versioned_message() {
+        let msg = VersionedServiceMessage::new(2, b"payload".to_vec());
+        let bytes = IntoBytes::<service::VersionedServiceMessage>::into_bytes(msg)
+            .expect("Failed to serialize");
+        let msg: VersionedServiceMessage =
+            FromBytes::<service::VersionedServiceMessage>::from_bytes(&bytes)
+                .expect("Failed to deserialize");
+        assert_eq!(msg, VersionedServiceMessage::new(2, b"payload".to_vec()));
+
+        assert_eq!(
+            msg.clone()
+                .into_payload(&ProtocolVersionRange::new(1, 2).unwrap()),
+            Ok(b"payload".to_vec())
+        );
+        assert_eq!(
+            msg.into_payload(&ProtocolVersionRange::single(1).unwrap()),
+            Err(ProtocolVersionError::UnsupportedVersion {
+                version: 2,
+                supported: ProtocolVersionRange::single(1).unwrap(),
+            })
+        );
+    }
+}
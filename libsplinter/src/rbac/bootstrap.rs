@@ -0,0 +1,463 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Declarative bootstrapping of role-based authorization data.
+//!
+//! Granting permissions through the REST API requires a client that already has permissions,
+//! which makes configuring role-based authorization on a brand new node a chicken-and-egg
+//! problem. An [`RbacBootstrap`] solves this by describing a set of roles and assignments in a
+//! YAML file that is applied directly to a [`RoleBasedAuthorizationStore`], typically when the
+//! node starts.
+//!
+//! The bootstrap file has the following format:
+//!
+//! ```yaml
+//! roles:
+//!   - id: circuit_admin
+//!     display_name: Circuit Administrator
+//!     permissions:
+//!       - circuit.read
+//!       - circuit.write
+//! assignments:
+//!   - key: 0384781b5e5ba9d8d3c4f2af2bc4b8a48c7f5e7bd5c44b1a4f2e9b2e7b6e1e1b2d
+//!     roles:
+//!       - admin
+//!   - user: 5ab1e2d0-2a1e-4c3f-a0a4-6d1e1a3f4b5c
+//!     roles:
+//!       - circuit_admin
+//! ```
+//!
+//! Each assignment must specify exactly one of `key` or `user`.
+//!
+//! [`RbacBootstrap`]: struct.RbacBootstrap.html
+//! [`RoleBasedAuthorizationStore`]: ../store/trait.RoleBasedAuthorizationStore.html
+
+use std::collections::HashSet;
+use std::fs::File;
+
+use crate::error::{InternalError, InvalidArgumentError, InvalidStateError};
+
+use super::store::{
+    AssignmentBuilder, Identity, Role, RoleBasedAuthorizationStore,
+    RoleBasedAuthorizationStoreError, RoleBuilder, ADMIN_ROLE_ID,
+};
+
+/// A declarative set of roles and assignments to seed into a role-based authorization store.
+///
+/// Applying the bootstrap is idempotent:
+///
+/// * Roles that do not exist are added; roles that exist with a different display name or
+///   permissions are updated to match the bootstrap.
+/// * Assignments that do not exist are added; existing assignments are extended with any missing
+///   roles. Roles that were assigned by other means are never removed.
+///
+/// Because the bootstrap is typically applied every time the node starts, an assignment that
+/// should no longer be granted must be removed from the bootstrap as well as from the store.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RbacBootstrap {
+    #[serde(default)]
+    roles: Vec<BootstrapRole>,
+    #[serde(default)]
+    assignments: Vec<BootstrapAssignment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BootstrapRole {
+    id: String,
+    display_name: String,
+    permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BootstrapAssignment {
+    key: Option<String>,
+    user: Option<String>,
+    roles: Vec<String>,
+}
+
+impl BootstrapAssignment {
+    fn identity(&self) -> Result<Identity, InvalidArgumentError> {
+        match (&self.key, &self.user) {
+            (Some(key), None) => Ok(Identity::Key(key.clone())),
+            (None, Some(user)) => Ok(Identity::User(user.clone())),
+            _ => Err(InvalidArgumentError::new(
+                "assignments",
+                "each assignment must specify exactly one of 'key' or 'user'",
+            )),
+        }
+    }
+}
+
+impl RbacBootstrap {
+    /// Reads and validates a bootstrap from the YAML file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the file cannot be opened or parsed, or if the bootstrap is
+    /// invalid (see [`RbacBootstrap::from_yaml_str`]).
+    pub fn from_yaml_file(path: &str) -> Result<Self, InternalError> {
+        let file = File::open(path).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to open RBAC bootstrap file '{}'", path),
+            )
+        })?;
+        let bootstrap: Self = serde_yaml::from_reader(file).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to parse RBAC bootstrap file '{}'", path),
+            )
+        })?;
+        bootstrap.validate().map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Invalid RBAC bootstrap file '{}'", path),
+            )
+        })?;
+
+        Ok(bootstrap)
+    }
+
+    /// Parses and validates a bootstrap from a YAML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the YAML cannot be parsed or if:
+    ///
+    /// * a role redefines the predefined `admin` role
+    /// * a role ID is defined more than once
+    /// * a role is not valid (empty ID, display name, or permissions)
+    /// * an assignment does not specify exactly one of `key` or `user`
+    /// * an assignment has no roles
+    pub fn from_yaml_str(yaml: &str) -> Result<Self, InvalidArgumentError> {
+        let bootstrap: Self = serde_yaml::from_str(yaml)
+            .map_err(|err| InvalidArgumentError::new("yaml", err.to_string()))?;
+        bootstrap.validate()?;
+        Ok(bootstrap)
+    }
+
+    fn validate(&self) -> Result<(), InvalidArgumentError> {
+        let mut role_ids = HashSet::new();
+        for role in &self.roles {
+            if role.id == ADMIN_ROLE_ID {
+                return Err(InvalidArgumentError::new(
+                    "roles",
+                    format!(
+                        "the '{}' role is predefined and cannot be bootstrapped",
+                        role.id
+                    ),
+                ));
+            }
+            if !role_ids.insert(role.id.as_str()) {
+                return Err(InvalidArgumentError::new(
+                    "roles",
+                    format!("role '{}' is defined more than once", role.id),
+                ));
+            }
+            build_role(role)?;
+        }
+
+        for assignment in &self.assignments {
+            assignment.identity()?;
+            if assignment.roles.is_empty() {
+                return Err(InvalidArgumentError::new(
+                    "assignments",
+                    "each assignment must have at least one role",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the bootstrap's roles and assignments to the given store.
+    ///
+    /// Roles are applied before assignments, so assignments may refer to roles defined in the
+    /// same bootstrap.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RoleBasedAuthorizationStoreError`] if an operation on the store fails, or an
+    /// `InvalidState` error if an assignment refers to a role that does not exist.
+    pub fn apply(
+        &self,
+        store: &dyn RoleBasedAuthorizationStore,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        for role in &self.roles {
+            let new_role =
+                build_role(role).map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+            match store.get_role(&role.id)? {
+                None => {
+                    debug!("Bootstrapping role '{}'", role.id);
+                    store.add_role(new_role)?;
+                }
+                Some(existing)
+                    if existing.display_name() != role.display_name
+                        || existing.permissions() != role.permissions.as_slice() =>
+                {
+                    debug!("Updating bootstrapped role '{}'", role.id);
+                    store.update_role(new_role)?;
+                }
+                Some(_) => (),
+            }
+        }
+
+        for assignment in &self.assignments {
+            let identity = assignment
+                .identity()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+            for role_id in &assignment.roles {
+                if store.get_role(role_id)?.is_none() {
+                    return Err(RoleBasedAuthorizationStoreError::InvalidState(
+                        InvalidStateError::with_message(format!(
+                            "Bootstrapped assignment refers to unknown role '{}'",
+                            role_id
+                        )),
+                    ));
+                }
+            }
+
+            let (roles, is_new) = match store.get_assignment(&identity)? {
+                None => (assignment.roles.clone(), true),
+                Some(existing) => {
+                    let mut roles = existing.roles().to_vec();
+                    let missing = assignment
+                        .roles
+                        .iter()
+                        .filter(|role| !roles.contains(role))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if missing.is_empty() {
+                        continue;
+                    }
+                    roles.extend(missing);
+                    (roles, false)
+                }
+            };
+
+            let new_assignment = AssignmentBuilder::new()
+                .with_identity(identity)
+                .with_roles(roles)
+                .build()?;
+
+            if is_new {
+                debug!(
+                    "Bootstrapping assignment for {:?}",
+                    new_assignment.identity()
+                );
+                store.add_assignment(new_assignment)?;
+            } else {
+                debug!("Updating assignment for {:?}", new_assignment.identity());
+                store.update_assignment(new_assignment)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn build_role(role: &BootstrapRole) -> Result<Role, InvalidArgumentError> {
+    RoleBuilder::new()
+        .with_id(role.id.clone())
+        .with_display_name(role.display_name.clone())
+        .with_permissions(role.permissions.clone())
+        .build()
+        .map_err(|err| InvalidArgumentError::new("roles", err.to_string()))
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use crate::rbac::store::DieselRoleBasedAuthorizationStore;
+    use crate::store::sqlite::create_sqlite_connection_pool;
+
+    const BOOTSTRAP: &str = r#"
+roles:
+  - id: circuit_admin
+    display_name: Circuit Administrator
+    permissions:
+      - circuit.read
+      - circuit.write
+assignments:
+  - key: abcd
+    roles:
+      - admin
+      - circuit_admin
+  - user: some-user
+    roles:
+      - circuit_admin
+"#;
+
+    /// Verifies that invalid bootstraps are rejected when parsed:
+    ///
+    /// 1. An assignment with both a key and a user
+    /// 2. An assignment with neither a key nor a user
+    /// 3. A role that redefines the admin role
+    /// 4. A duplicate role
+    /// 5. A role without permissions
+    /// 6. An assignment without roles
+    #[test]
+    fn invalid_bootstraps() {
+        assert!(RbacBootstrap::from_yaml_str(
+            "assignments:\n  - key: abcd\n    user: someone\n    roles: [admin]"
+        )
+        .is_err());
+        assert!(RbacBootstrap::from_yaml_str("assignments:\n  - roles: [admin]").is_err());
+        assert!(RbacBootstrap::from_yaml_str(
+            "roles:\n  - id: admin\n    display_name: Admin\n    permissions: [a]"
+        )
+        .is_err());
+        assert!(RbacBootstrap::from_yaml_str(
+            "roles:\n  - id: r\n    display_name: R\n    permissions: [a]\n  \
+             - id: r\n    display_name: R\n    permissions: [b]"
+        )
+        .is_err());
+        assert!(RbacBootstrap::from_yaml_str(
+            "roles:\n  - id: r\n    display_name: R\n    permissions: []"
+        )
+        .is_err());
+        assert!(
+            RbacBootstrap::from_yaml_str("assignments:\n  - key: abcd\n    roles: []").is_err()
+        );
+    }
+
+    /// Verifies that applying a bootstrap adds its roles and assignments, and that applying it a
+    /// second time leaves the store unchanged.
+    #[test]
+    fn apply_is_idempotent() {
+        let store = create_store();
+        let bootstrap = RbacBootstrap::from_yaml_str(BOOTSTRAP).expect("Invalid bootstrap");
+
+        bootstrap.apply(&store).expect("Unable to apply bootstrap");
+        bootstrap
+            .apply(&store)
+            .expect("Unable to apply bootstrap a second time");
+
+        let role = store
+            .get_role("circuit_admin")
+            .expect("Unable to get role")
+            .expect("Role not found");
+        assert_eq!("Circuit Administrator", role.display_name());
+        assert_eq!(
+            &["circuit.read".to_string(), "circuit.write".to_string()],
+            role.permissions()
+        );
+
+        let assignment = store
+            .get_assignment(&Identity::Key("abcd".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment not found");
+        assert_eq!(
+            &["admin".to_string(), "circuit_admin".to_string()],
+            assignment.roles()
+        );
+
+        let assignment = store
+            .get_assignment(&Identity::User("some-user".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment not found");
+        assert_eq!(&["circuit_admin".to_string()], assignment.roles());
+
+        assert_eq!(
+            2,
+            store
+                .list_assignments()
+                .expect("Unable to list assignments")
+                .len()
+        );
+    }
+
+    /// Verifies that applying a bootstrap updates modified roles and extends existing assignments
+    /// without removing roles that were assigned by other means.
+    #[test]
+    fn apply_updates_existing_data() {
+        let store = create_store();
+
+        store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("circuit_admin".into())
+                    .with_display_name("Old Name".into())
+                    .with_permissions(vec!["circuit.read".into()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+        store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("other".into())
+                    .with_display_name("Other".into())
+                    .with_permissions(vec!["status.read".into()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+        store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(Identity::User("some-user".into()))
+                    .with_roles(vec!["other".into()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+
+        RbacBootstrap::from_yaml_str(BOOTSTRAP)
+            .expect("Invalid bootstrap")
+            .apply(&store)
+            .expect("Unable to apply bootstrap");
+
+        let role = store
+            .get_role("circuit_admin")
+            .expect("Unable to get role")
+            .expect("Role not found");
+        assert_eq!("Circuit Administrator", role.display_name());
+        assert_eq!(2, role.permissions().len());
+
+        let assignment = store
+            .get_assignment(&Identity::User("some-user".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment not found");
+        assert_eq!(
+            &["other".to_string(), "circuit_admin".to_string()],
+            assignment.roles()
+        );
+    }
+
+    /// Verifies that an assignment to a role that is neither in the bootstrap nor the store fails.
+    #[test]
+    fn apply_unknown_role() {
+        let store = create_store();
+
+        let bootstrap = RbacBootstrap::from_yaml_str("assignments:\n  - key: abcd\n    roles: [x]")
+            .expect("Invalid bootstrap");
+
+        assert!(matches!(
+            bootstrap.apply(&store),
+            Err(RoleBasedAuthorizationStoreError::InvalidState(_))
+        ));
+    }
+
+    fn create_store() -> DieselRoleBasedAuthorizationStore<diesel::sqlite::SqliteConnection> {
+        DieselRoleBasedAuthorizationStore::new(
+            create_sqlite_connection_pool(":memory:").expect("Failed to build connection pool"),
+        )
+    }
+}
@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "rbac-bootstrap")]
+pub mod bootstrap;
 #[cfg(feature = "store")]
 pub mod store;
@@ -0,0 +1,110 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types for applying a batch of node operations to a registry in a single transaction.
+
+use super::Node;
+
+/// A single change to apply to the registry as part of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeOperation {
+    /// Add a new node to the registry.
+    Create(Node),
+    /// Replace an existing node in the registry.
+    Update(Node),
+    /// Remove the node with the given identity from the registry.
+    Delete(String),
+}
+
+impl NodeOperation {
+    /// Returns the identity of the node this operation applies to.
+    pub fn identity(&self) -> &str {
+        match self {
+            NodeOperation::Create(node) | NodeOperation::Update(node) => node.identity(),
+            NodeOperation::Delete(identity) => identity,
+        }
+    }
+}
+
+/// The outcome of a single operation within a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeOperationResult {
+    identity: String,
+    error: Option<String>,
+}
+
+impl NodeOperationResult {
+    /// Creates a result for an operation that was applied successfully.
+    pub fn succeeded<S: Into<String>>(identity: S) -> Self {
+        Self {
+            identity: identity.into(),
+            error: None,
+        }
+    }
+
+    /// Creates a result for an operation that could not be applied.
+    pub fn failed<S: Into<String>, E: Into<String>>(identity: S, error: E) -> Self {
+        Self {
+            identity: identity.into(),
+            error: Some(error.into()),
+        }
+    }
+
+    /// Returns the identity of the node the operation applied to.
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// Returns the reason the operation failed, if it did.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns `true` if the operation was applied successfully.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The outcome of applying a batch of node operations.
+///
+/// The batch is applied atomically: if any operation fails, none of the operations are committed
+/// and `committed` is `false`. The per-operation results are returned in the same order as the
+/// operations were submitted either way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeBatchResult {
+    committed: bool,
+    results: Vec<NodeOperationResult>,
+}
+
+impl NodeBatchResult {
+    /// Creates a new batch result.
+    pub fn new(committed: bool, results: Vec<NodeOperationResult>) -> Self {
+        Self { committed, results }
+    }
+
+    /// Returns `true` if the batch was committed to the registry.
+    pub fn committed(&self) -> bool {
+        self.committed
+    }
+
+    /// Returns the per-operation results, in the order the operations were submitted.
+    pub fn results(&self) -> &[NodeOperationResult] {
+        &self.results
+    }
+
+    /// Consumes the batch result, returning the per-operation results.
+    pub fn into_results(self) -> Vec<NodeOperationResult> {
+        self.results
+    }
+}
@@ -17,6 +17,7 @@
 use reqwest::{blocking::Client, StatusCode};
 
 use crate::error::InternalError;
+use crate::http_client::build_client;
 use crate::rest_api::SPLINTER_PROTOCOL_VERSION;
 
 use super::{RegistryClient, RegistryNode, RegistryNodeListSlice};
@@ -31,18 +32,25 @@ struct ServerError {
 pub struct ReqwestRegistryClient {
     pub url: String,
     pub auth: String,
+    // Shared by every request, so that connections are reused
+    client: Client,
 }
 
 impl ReqwestRegistryClient {
     pub fn new(url: String, auth: String) -> Self {
-        ReqwestRegistryClient { url, auth }
+        ReqwestRegistryClient {
+            url,
+            auth,
+            client: build_client(),
+        }
     }
 }
 
 impl RegistryClient for ReqwestRegistryClient {
     /// Add the given `node` to the registry.
     fn add_node(&self, node: &RegistryNode) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .post(&format!("{}/registry/nodes", self.url))
             .json(&node)
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
@@ -80,7 +88,8 @@ impl RegistryClient for ReqwestRegistryClient {
 
     /// Retrieve the node with the given `identity` from the registry.
     fn get_node(&self, identity: &str) -> Result<Option<RegistryNode>, InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .get(&format!("{}/registry/nodes/{}", self.url, &identity))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
@@ -130,7 +139,8 @@ impl RegistryClient for ReqwestRegistryClient {
             url = format!("{}&filter={}", &url, &filter);
         }
 
-        let request = Client::new()
+        let request = self
+            .client
             .get(&url)
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
@@ -176,7 +186,8 @@ impl RegistryClient for ReqwestRegistryClient {
 
     /// Update the node in the registry with the same id as the given `node`.
     fn update_node(&self, node: &RegistryNode) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .put(&format!("{}/registry/nodes/{}", self.url, node.identity))
             .json(&node)
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
@@ -214,7 +225,8 @@ impl RegistryClient for ReqwestRegistryClient {
 
     /// Delete the node with the given `identity` from the registry.
     fn delete_node(&self, identity: &str) -> Result<(), InternalError> {
-        let request = Client::new()
+        let request = self
+            .client
             .delete(&format!("{}/registry/nodes/{}", self.url, identity))
             .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
             .header("Authorization", &self.auth);
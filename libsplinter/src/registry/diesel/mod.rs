@@ -33,8 +33,12 @@ use crate::store::pool::ConnectionPool;
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
+#[cfg(feature = "registry-batch")]
+use super::{NodeBatchResult, NodeOperation};
 
 use operations::add_node::RegistryAddNodeOperation as _;
+#[cfg(feature = "registry-batch")]
+use operations::apply_batch::RegistryApplyBatchOperation as _;
 use operations::count_nodes::RegistryCountNodesOperation as _;
 use operations::delete_node::RegistryDeleteNodeOperation as _;
 use operations::get_node::RegistryFetchNodeOperation as _;
@@ -144,6 +148,15 @@ impl RegistryWriter for DieselRegistry<diesel::pg::PgConnection> {
         self.connection_pool
             .execute_write(|conn| RegistryOperations::new(conn).delete_node(identity))
     }
+
+    #[cfg(feature = "registry-batch")]
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        self.connection_pool
+            .execute_write(|conn| RegistryOperations::new(conn).apply_batch(operations))
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -162,6 +175,15 @@ impl RegistryWriter for DieselRegistry<diesel::sqlite::SqliteConnection> {
         self.connection_pool
             .execute_write(|conn| RegistryOperations::new(conn).delete_node(identity))
     }
+
+    #[cfg(feature = "registry-batch")]
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        self.connection_pool
+            .execute_write(|conn| RegistryOperations::new(conn).apply_batch(operations))
+    }
 }
 
 #[cfg(feature = "postgres")]
@@ -726,6 +748,97 @@ pub mod tests {
         assert_eq!(count, 2);
     }
 
+    /// Verifies that `apply_batch` commits all operations when every operation succeeds.
+    ///
+    /// 1. Setup sqlite database
+    /// 2. Insert node 1
+    /// 3. Apply a batch that creates node 2 and 3, updates node 1, and deletes node 3
+    /// 4. Verify the batch was committed and the registry reflects each operation
+    #[cfg(feature = "registry-batch")]
+    #[test]
+    fn test_apply_batch_committed() {
+        let pool = create_connection_pool_and_migrate();
+        let registry = DieselRegistry::new(pool);
+
+        registry
+            .add_node(get_node_1())
+            .expect("Unable to insert node");
+
+        let mut updated_node_1 = get_node_1();
+        updated_node_1.display_name = "Changed Name".to_string();
+
+        let result = registry
+            .apply_batch(vec![
+                NodeOperation::Create(get_node_2()),
+                NodeOperation::Create(get_node_3()),
+                NodeOperation::Update(updated_node_1.clone()),
+                NodeOperation::Delete(get_node_3().identity),
+            ])
+            .expect("Failed to apply batch");
+
+        assert!(result.committed());
+        assert_eq!(result.results().len(), 4);
+        assert!(result.results().iter().all(|res| res.is_success()));
+
+        assert_eq!(
+            registry
+                .get_node(&get_node_1().identity)
+                .expect("Failed to fetch node"),
+            Some(updated_node_1)
+        );
+        assert_eq!(
+            registry
+                .get_node(&get_node_2().identity)
+                .expect("Failed to fetch node"),
+            Some(get_node_2())
+        );
+        assert!(!registry
+            .has_node(&get_node_3().identity)
+            .expect("Failed to check node"));
+    }
+
+    /// Verifies that `apply_batch` rolls back every operation when one of them fails, while still
+    /// reporting the result of each operation.
+    ///
+    /// 1. Setup sqlite database
+    /// 2. Insert node 1
+    /// 3. Apply a batch that creates node 2, creates node 1 again, and deletes node 3 (which does
+    ///    not exist)
+    /// 4. Verify the batch was not committed, only the first result succeeded, and node 2 was not
+    ///    added
+    #[cfg(feature = "registry-batch")]
+    #[test]
+    fn test_apply_batch_rolled_back() {
+        let pool = create_connection_pool_and_migrate();
+        let registry = DieselRegistry::new(pool);
+
+        registry
+            .add_node(get_node_1())
+            .expect("Unable to insert node");
+
+        let result = registry
+            .apply_batch(vec![
+                NodeOperation::Create(get_node_2()),
+                NodeOperation::Create(get_node_1()),
+                NodeOperation::Delete(get_node_3().identity),
+            ])
+            .expect("Failed to apply batch");
+
+        assert!(!result.committed());
+        let results = result.results();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_success());
+        assert_eq!(results[1].identity(), get_node_1().identity);
+        assert!(results[1].error().is_some());
+        assert_eq!(results[2].identity(), get_node_3().identity);
+        assert!(results[2].error().is_some());
+
+        assert!(!registry
+            .has_node(&get_node_2().identity)
+            .expect("Failed to check node"));
+        assert_eq!(registry.count_nodes(&[]).expect("Failed to count nodes"), 1);
+    }
+
     fn get_node_1() -> Node {
         Node::builder("Node-123")
             .with_endpoint("tcps://12.0.0.123:8431")
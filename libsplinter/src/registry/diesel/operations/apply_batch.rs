@@ -0,0 +1,93 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::prelude::*;
+
+use crate::error::InvalidStateError;
+use crate::registry::{NodeBatchResult, NodeOperation, NodeOperationResult, RegistryError};
+
+use super::{
+    add_node::RegistryAddNodeOperation, delete_node::RegistryDeleteNodeOperation,
+    update_node::RegistryUpdateNodeOperation, RegistryOperations,
+};
+
+pub(in crate::registry::diesel) trait RegistryApplyBatchOperation {
+    fn apply_batch(&self, operations: Vec<NodeOperation>)
+        -> Result<NodeBatchResult, RegistryError>;
+}
+
+impl<'a, C> RegistryApplyBatchOperation for RegistryOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    Self: RegistryAddNodeOperation + RegistryUpdateNodeOperation + RegistryDeleteNodeOperation,
+{
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        let mut results = Vec::with_capacity(operations.len());
+        let mut failed = false;
+
+        // Each operation runs in its own nested transaction (a savepoint), so a failed operation
+        // does not poison the ones that follow it; the outer transaction is rolled back at the end
+        // if anything failed.
+        let outcome = self.conn.transaction::<(), RegistryError, _>(|| {
+            for operation in operations {
+                let identity = operation.identity().to_string();
+                let res = match operation {
+                    NodeOperation::Create(node) => self.add_node(node),
+                    NodeOperation::Update(node) => self.update_node(node),
+                    NodeOperation::Delete(identity) => {
+                        self.delete_node(&identity).and_then(|node| match node {
+                            Some(_) => Ok(()),
+                            None => Err(RegistryError::InvalidStateError(
+                                InvalidStateError::with_message(format!(
+                                    "Node does not exist: {}",
+                                    identity
+                                )),
+                            )),
+                        })
+                    }
+                };
+
+                match res {
+                    Ok(()) => results.push(NodeOperationResult::succeeded(identity)),
+                    Err(err @ RegistryError::InvalidStateError(_))
+                    | Err(err @ RegistryError::ConstraintViolationError(_)) => {
+                        failed = true;
+                        results.push(NodeOperationResult::failed(identity, err.to_string()));
+                    }
+                    // Anything else is not a problem with the operation itself, so abort the batch
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if failed {
+                Err(RegistryError::InvalidStateError(
+                    InvalidStateError::with_message(
+                        "One or more operations failed; batch rolled back".to_string(),
+                    ),
+                ))
+            } else {
+                Ok(())
+            }
+        });
+
+        match outcome {
+            Ok(()) => Ok(NodeBatchResult::new(true, results)),
+            Err(_) if failed => Ok(NodeBatchResult::new(false, results)),
+            Err(err) => Err(err),
+        }
+    }
+}
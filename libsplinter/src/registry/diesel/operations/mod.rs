@@ -15,6 +15,8 @@
 //! Provides database operations for the `DieselRegistry`.
 
 pub(super) mod add_node;
+#[cfg(feature = "registry-batch")]
+pub(super) mod apply_batch;
 pub(super) mod count_nodes;
 pub(super) mod delete_node;
 pub(super) mod get_node;
@@ -24,11 +24,17 @@
 //! [`RegistryWriter`]: trait.RegistryWriter.html
 //! [`RwRegistry`]: trait.RwRegistry.html
 
+#[cfg(feature = "registry-batch")]
+mod batch;
 #[cfg(feature = "registry-client")]
 pub mod client;
 #[cfg(feature = "diesel")]
 mod diesel;
 mod error;
+#[cfg(feature = "registry-node-signing")]
+mod signing;
+#[cfg(feature = "registry-sources")]
+pub mod sources;
 mod unified;
 mod yaml;
 
@@ -37,8 +43,16 @@ use std::iter::ExactSizeIterator;
 
 #[cfg(feature = "diesel")]
 pub use self::diesel::DieselRegistry;
+#[cfg(feature = "registry-batch")]
+pub use batch::{NodeBatchResult, NodeOperation, NodeOperationResult};
 pub use error::{InvalidNodeError, RegistryError};
+#[cfg(feature = "registry-node-signing")]
+pub use signing::{sign_node, verify_node_signature, NODE_SIGNATURE_METADATA_KEY};
 pub use unified::UnifiedRegistry;
+#[cfg(feature = "registry-parallel-fetch")]
+pub use yaml::RemoteYamlFetchOptions;
+#[cfg(feature = "registry-remote-refresh")]
+pub use yaml::RemoteYamlRefreshHandle;
 pub use yaml::{LocalYamlRegistry, YamlNode};
 #[cfg(feature = "registry-remote")]
 pub use yaml::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
@@ -303,6 +317,30 @@ pub trait RegistryWriter: Send + Sync {
     ///
     ///  * `identity` - The Splinter identity of the node.
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError>;
+
+    /// Applies the given operations to the registry in a single transaction.
+    ///
+    /// Every operation is attempted and its result is reported. If any operation fails, the whole
+    /// batch is rolled back and the returned result is marked as not committed. An `Err` is only
+    /// returned if the batch could not be attempted at all.
+    ///
+    /// Registries that do not support transactional batches return an `InvalidStateError`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `operations` - The operations to apply, in order.
+    #[cfg(feature = "registry-batch")]
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        let _ = operations;
+        Err(RegistryError::InvalidStateError(
+            crate::error::InvalidStateError::with_message(
+                "This registry does not support batch operations".to_string(),
+            ),
+        ))
+    }
 }
 
 /// Provides a marker trait for a clonable, readable and writable registry.
@@ -377,6 +415,14 @@ where
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
         (**self).delete_node(identity)
     }
+
+    #[cfg(feature = "registry-batch")]
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        (**self).apply_batch(operations)
+    }
 }
 
 /// Returns `Err` if not all `nodes` are valid.
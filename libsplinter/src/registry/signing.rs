@@ -0,0 +1,159 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing and verification of registry node entries.
+//!
+//! A node entry is signed by one of the node's own keys. The signature covers the node's
+//! identity, endpoints, and keys, and is stored in the node's metadata under
+//! [`NODE_SIGNATURE_METADATA_KEY`], so it is preserved by every registry implementation.
+//!
+//! [`NODE_SIGNATURE_METADATA_KEY`]: constant.NODE_SIGNATURE_METADATA_KEY.html
+
+use cylinder::{PublicKey, Signature, Signer, Verifier};
+
+use crate::error::InternalError;
+use crate::hex::parse_hex;
+
+use super::Node;
+
+/// The metadata key under which a node's signature is stored.
+pub const NODE_SIGNATURE_METADATA_KEY: &str = "signature";
+
+/// Sign the given node with the given signer, returning the node with its signature added to its
+/// metadata. Any existing signature is replaced.
+///
+/// The signer's public key should be one of the node's keys; otherwise the signature will not
+/// verify.
+pub fn sign_node(mut node: Node, signer: &dyn Signer) -> Result<Node, InternalError> {
+    let signature = signer
+        .sign(&signing_payload(&node)?)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    node.metadata
+        .insert(NODE_SIGNATURE_METADATA_KEY.into(), signature.as_hex());
+
+    Ok(node)
+}
+
+/// Check whether the given node has a signature that was made by one of the node's keys over the
+/// node's current identity, endpoints, and keys.
+///
+/// Returns `false` if the node has no signature, if the signature is malformed, or if it was not
+/// made by any of the node's keys.
+pub fn verify_node_signature(node: &Node, verifier: &dyn Verifier) -> Result<bool, InternalError> {
+    let signature = match node
+        .metadata
+        .get(NODE_SIGNATURE_METADATA_KEY)
+        .map(|signature| Signature::from_hex(signature))
+    {
+        Some(Ok(signature)) => signature,
+        Some(Err(_)) | None => return Ok(false),
+    };
+
+    let payload = signing_payload(node)?;
+
+    for key in &node.keys {
+        let public_key = match parse_hex(key) {
+            Ok(bytes) => PublicKey::new(bytes),
+            Err(_) => continue,
+        };
+
+        if verifier
+            .verify(&payload, &signature, &public_key)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Build the bytes that are signed for a node: a JSON object with the node's identity, endpoints,
+/// and keys. The object's fields are serialized in sorted order, so the payload is deterministic.
+fn signing_payload(node: &Node) -> Result<Vec<u8>, InternalError> {
+    serde_json::to_vec(&serde_json::json!({
+        "identity": node.identity,
+        "endpoints": node.endpoints,
+        "keys": node.keys,
+    }))
+    .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    fn signer() -> Box<dyn Signer> {
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        context.new_signer(private_key)
+    }
+
+    fn node_for(signer: &dyn Signer) -> Node {
+        Node::builder("node-1")
+            .with_endpoint("tcps://12.0.0.123:8431")
+            .with_display_name("Node 1")
+            .with_key(
+                signer
+                    .public_key()
+                    .expect("Failed to get public key")
+                    .as_hex(),
+            )
+            .build()
+            .expect("Failed to build node")
+    }
+
+    /// Verify that a node signed by one of its own keys is verified.
+    #[test]
+    fn sign_and_verify() {
+        let signer = signer();
+        let node = sign_node(node_for(&*signer), &*signer).expect("Failed to sign node");
+
+        assert!(node.metadata().contains_key(NODE_SIGNATURE_METADATA_KEY));
+        assert!(
+            verify_node_signature(&node, &*Secp256k1Context::new().new_verifier())
+                .expect("Failed to verify node")
+        );
+    }
+
+    /// Verify that unsigned nodes and nodes signed by a key that does not belong to the node are
+    /// not verified.
+    #[test]
+    fn verify_unsigned_or_foreign_signer() {
+        let signer = signer();
+        let verifier = Secp256k1Context::new().new_verifier();
+
+        let node = node_for(&*signer);
+        assert!(!verify_node_signature(&node, &*verifier).expect("Failed to verify node"));
+
+        let node = sign_node(node, &*self::signer()).expect("Failed to sign node");
+        assert!(!verify_node_signature(&node, &*verifier).expect("Failed to verify node"));
+    }
+
+    /// Verify that a signature no longer verifies after the node's endpoints are changed.
+    #[test]
+    fn verify_modified_node() {
+        let signer = signer();
+        let mut node = sign_node(node_for(&*signer), &*signer).expect("Failed to sign node");
+        node.endpoints = vec!["tcps://12.0.0.200:8431".into()];
+
+        assert!(
+            !verify_node_signature(&node, &*Secp256k1Context::new().new_verifier())
+                .expect("Failed to verify node")
+        );
+    }
+}
@@ -0,0 +1,651 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Remote registries that may be added and removed while the node is running.
+//!
+//! The [`RegistrySources`] tracks two kinds of remote registry sources: the static sources that
+//! were configured when the node started, and the dynamic sources that were added at runtime.
+//! Dynamic sources are persisted in a [`RegistrySourceStore`] so that they are restored when the
+//! node restarts. Only dynamic sources may be removed.
+//!
+//! [`RegistrySources`] implements [`RegistryReader`](crate::registry::RegistryReader) over the
+//! dynamic sources, so that it may be provided to a
+//! [`UnifiedRegistry`](crate::registry::UnifiedRegistry) as a single read-only registry.
+
+pub mod store;
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::error::{InternalError, InvalidStateError};
+use crate::threading::lifecycle::ShutdownHandle;
+
+#[cfg(feature = "registry-parallel-fetch")]
+use super::RemoteYamlFetchOptions;
+use super::{
+    MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RemoteYamlRefreshHandle,
+    RemoteYamlRegistry, RemoteYamlShutdownHandle,
+};
+
+#[cfg(feature = "registry-parallel-fetch")]
+pub use store::RegistryRefreshStats;
+pub use store::{RegistrySourceStore, RegistrySourceStoreError};
+
+/// The current status of a remote registry source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrySourceStatus {
+    pub(crate) url: String,
+    pub(crate) dynamic: bool,
+    pub(crate) last_refresh: Option<SystemTime>,
+    pub(crate) node_count: usize,
+    pub(crate) error: Option<String>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub(crate) refresh_stats: Option<RegistryRefreshStats>,
+}
+
+impl RegistrySourceStatus {
+    /// Returns the URL of the remote registry file.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns `true` if the source was added at runtime, or `false` if it was configured when
+    /// the node started.
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+
+    /// Returns the time the remote registry file was last fetched successfully, if ever.
+    pub fn last_refresh(&self) -> Option<SystemTime> {
+        self.last_refresh
+    }
+
+    /// Returns the number of nodes in the locally cached copy of the registry.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Returns the error of the last refresh attempt, if it failed.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the recorded history of attempts to fetch the remote registry file, if any.
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn refresh_stats(&self) -> Option<&RegistryRefreshStats> {
+        self.refresh_stats.as_ref()
+    }
+}
+
+/// Manages the remote registry sources of a node.
+///
+/// Cloned instances share the same set of sources.
+#[derive(Clone)]
+pub struct RegistrySources {
+    store: Box<dyn RegistrySourceStore>,
+    cache_dir: String,
+    automatic_refresh_period: Option<Duration>,
+    forced_refresh_period: Option<Duration>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    fetch_timeout: Option<Duration>,
+    static_sources: Vec<RemoteYamlRefreshHandle>,
+    dynamic_sources: Arc<Mutex<DynamicSources>>,
+}
+
+impl RegistrySources {
+    /// Constructs a new `RegistrySources`, starting a [`RemoteYamlRegistry`] for every source in
+    /// the given store.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Store that dynamic sources are persisted in.
+    /// * `cache_dir` - Directory that the local caches of the dynamic sources will be stored in.
+    /// * `automatic_refresh_period` - Automatic refresh period of the dynamic sources; see
+    ///   [`RemoteYamlRegistry::new`].
+    /// * `forced_refresh_period` - Forced refresh period of the dynamic sources; see
+    ///   [`RemoteYamlRegistry::new`].
+    /// * `static_sources` - Refresh handles of the remote registries configured on startup; these
+    ///   are reported by [`list_sources`](Self::list_sources), but may not be removed.
+    /// * `fetch_timeout` - Time allowed for fetching the remote file of a dynamic source.
+    ///
+    /// With the `registry-parallel-fetch` feature, the stored sources are fetched concurrently,
+    /// and the outcome of every fetch is recorded in the store.
+    pub fn new(
+        store: Box<dyn RegistrySourceStore>,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        static_sources: Vec<RemoteYamlRefreshHandle>,
+        #[cfg(feature = "registry-parallel-fetch")] fetch_timeout: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        let sources = Self {
+            store,
+            cache_dir: cache_dir.to_string(),
+            automatic_refresh_period,
+            forced_refresh_period,
+            #[cfg(feature = "registry-parallel-fetch")]
+            fetch_timeout,
+            static_sources,
+            dynamic_sources: Arc::new(Mutex::new(DynamicSources::default())),
+        };
+
+        let urls = sources
+            .store
+            .list_sources()?
+            .into_iter()
+            .filter(|url| !sources.is_static(url))
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "registry-parallel-fetch")]
+        let registries = RemoteYamlRegistry::new_concurrently(
+            &urls,
+            &sources.cache_dir,
+            sources.automatic_refresh_period,
+            sources.forced_refresh_period,
+            sources.fetch_options(),
+        );
+        #[cfg(not(feature = "registry-parallel-fetch"))]
+        let registries = urls
+            .iter()
+            .map(|url| {
+                RemoteYamlRegistry::new(
+                    url,
+                    &sources.cache_dir,
+                    sources.automatic_refresh_period,
+                    sources.forced_refresh_period,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let started = registries
+            .into_iter()
+            .zip(urls)
+            .filter_map(|(registry, url)| {
+                match registry.and_then(|registry| DynamicSource::new(&url, registry)) {
+                    Ok(source) => Some(source),
+                    Err(err) => {
+                        error!("Failed to start registry source '{}': {}", url, err);
+                        None
+                    }
+                }
+            })
+            .collect();
+        sources.lock_dynamic_sources()?.sources = started;
+
+        Ok(sources)
+    }
+
+    /// Adds a remote registry source, fetching the remote file immediately and persisting the
+    /// source so that it is restored on restart.
+    ///
+    /// If the initial fetch fails, the source is still added and the failure is reported in the
+    /// returned status; the fetch is retried the next time the source is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - the HTTP(S) URL of the remote registry file
+    pub fn add_source(&self, url: &str) -> Result<RegistrySourceStatus, RegistryError> {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "Registry source must be an http or https URL: {}",
+                    url
+                )),
+            ));
+        }
+
+        self.check_can_add(&*self.lock_dynamic_sources()?, url)?;
+
+        // Starting the source fetches the remote file, so the lock is not held while doing so
+        let mut source = self.start_source(url)?;
+
+        let mut dynamic_sources = self.lock_dynamic_sources()?;
+        if let Err(err) = self.check_can_add(&*dynamic_sources, url) {
+            drop(dynamic_sources);
+            source.shutdown();
+            return Err(err);
+        }
+
+        if let Err(err) = self.store.add_source(url) {
+            drop(dynamic_sources);
+            source.shutdown();
+            return Err(err.into());
+        }
+
+        let status = source.refresh_handle.status(true)?;
+        dynamic_sources.sources.push(source);
+
+        Ok(status)
+    }
+
+    /// Removes a dynamic remote registry source, returning `false` if there was no such source.
+    ///
+    /// Returns an `InvalidStateError` if the source was configured on startup.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - the URL of the remote registry file
+    pub fn remove_source(&self, url: &str) -> Result<bool, RegistryError> {
+        if self.is_static(url) {
+            return Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "Registry source was configured on startup and cannot be removed: {}",
+                    url
+                )),
+            ));
+        }
+
+        let mut source = {
+            let mut dynamic_sources = self.lock_dynamic_sources()?;
+            let index = match dynamic_sources
+                .sources
+                .iter()
+                .position(|source| source.url == url)
+            {
+                Some(index) => index,
+                None => return Ok(false),
+            };
+
+            self.store.remove_source(url)?;
+            dynamic_sources.sources.remove(index)
+        };
+
+        source.shutdown();
+
+        Ok(true)
+    }
+
+    /// Lists the status of all remote registry sources; static sources are listed first,
+    /// followed by the dynamic sources in the order they were added.
+    pub fn list_sources(&self) -> Result<Vec<RegistrySourceStatus>, RegistryError> {
+        let dynamic_handles = self
+            .lock_dynamic_sources()?
+            .sources
+            .iter()
+            .map(|source| source.refresh_handle.clone())
+            .collect::<Vec<_>>();
+
+        #[cfg(not(feature = "registry-parallel-fetch"))]
+        {
+            self.static_sources
+                .iter()
+                .map(|handle| handle.status(false))
+                .chain(dynamic_handles.iter().map(|handle| handle.status(true)))
+                .collect()
+        }
+
+        #[cfg(feature = "registry-parallel-fetch")]
+        {
+            let mut refresh_stats = self.store.list_refresh_stats()?;
+            self.static_sources
+                .iter()
+                .map(|handle| handle.status(false))
+                .chain(dynamic_handles.iter().map(|handle| handle.status(true)))
+                .map(|status| {
+                    status.map(|mut status| {
+                        status.refresh_stats = refresh_stats
+                            .iter()
+                            .position(|stats| stats.url() == status.url())
+                            .map(|index| refresh_stats.swap_remove(index));
+                        status
+                    })
+                })
+                .collect()
+        }
+    }
+
+    /// Returns a handle for shutting down the dynamic sources' background refresh threads. Once
+    /// shutdown is signaled, no more sources may be added.
+    pub fn shutdown_handle(&self) -> RegistrySourcesShutdownHandle {
+        RegistrySourcesShutdownHandle {
+            dynamic_sources: self.dynamic_sources.clone(),
+            shutdown_handles: vec![],
+        }
+    }
+
+    fn is_static(&self, url: &str) -> bool {
+        self.static_sources.iter().any(|handle| handle.url() == url)
+    }
+
+    fn check_can_add(
+        &self,
+        dynamic_sources: &DynamicSources,
+        url: &str,
+    ) -> Result<(), RegistryError> {
+        if dynamic_sources.shutdown {
+            return Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message("Registry sources have been shut down".into()),
+            ));
+        }
+
+        if self.is_static(url)
+            || dynamic_sources
+                .sources
+                .iter()
+                .any(|source| source.url == url)
+        {
+            return Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message(format!("Registry source already exists: {}", url)),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn start_source(&self, url: &str) -> Result<DynamicSource, RegistryError> {
+        #[cfg(feature = "registry-parallel-fetch")]
+        let registry = RemoteYamlRegistry::new_with_fetch_options(
+            url,
+            &self.cache_dir,
+            self.automatic_refresh_period,
+            self.forced_refresh_period,
+            self.fetch_options(),
+        )?;
+        #[cfg(not(feature = "registry-parallel-fetch"))]
+        let registry = RemoteYamlRegistry::new(
+            url,
+            &self.cache_dir,
+            self.automatic_refresh_period,
+            self.forced_refresh_period,
+        )?;
+
+        DynamicSource::new(url, registry)
+    }
+
+    /// Returns the options the dynamic sources fetch their remote files with, which record the
+    /// outcome of each fetch in the store.
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn fetch_options(&self) -> RemoteYamlFetchOptions {
+        let fetch_options = RemoteYamlFetchOptions::default().with_stats_store(self.store.clone());
+        match self.fetch_timeout {
+            Some(timeout) => fetch_options.with_timeout(timeout),
+            None => fetch_options,
+        }
+    }
+
+    fn lock_dynamic_sources(&self) -> Result<MutexGuard<DynamicSources>, RegistryError> {
+        self.dynamic_sources.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Registry sources lock poisoned".into(),
+            ))
+        })
+    }
+
+    /// Returns the registries of the dynamic sources, in order of precedence.
+    fn registries(&self) -> Result<Vec<Arc<RemoteYamlRegistry>>, RegistryError> {
+        Ok(self
+            .lock_dynamic_sources()?
+            .sources
+            .iter()
+            .map(|source| source.registry.clone())
+            .collect())
+    }
+}
+
+/// Reads the nodes of the dynamic sources. If more than one source contains a node with the same
+/// identity, the node from the source that was added first is used.
+impl RegistryReader for RegistrySources {
+    fn list_nodes<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        let mut identities = HashSet::new();
+        let mut nodes = vec![];
+        for registry in self.registries()? {
+            for node in registry.list_nodes(&[])? {
+                if identities.insert(node.identity().to_string()) {
+                    nodes.push(node);
+                }
+            }
+        }
+
+        nodes.retain(|node| predicates.iter().all(|predicate| predicate.apply(node)));
+        Ok(Box::new(nodes.into_iter()))
+    }
+
+    fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+        Ok(self.list_nodes(predicates)?.count() as u32)
+    }
+
+    fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        for registry in self.registries()? {
+            if let Some(node) = registry.get_node(identity)? {
+                return Ok(Some(node));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Default)]
+struct DynamicSources {
+    sources: Vec<DynamicSource>,
+    shutdown: bool,
+}
+
+struct DynamicSource {
+    url: String,
+    registry: Arc<RemoteYamlRegistry>,
+    refresh_handle: RemoteYamlRefreshHandle,
+    shutdown_handle: Option<RemoteYamlShutdownHandle>,
+}
+
+impl DynamicSource {
+    fn new(url: &str, mut registry: RemoteYamlRegistry) -> Result<Self, RegistryError> {
+        let shutdown_handle = registry.take_shutdown_handle();
+        let refresh_handle = registry.refresh_handle()?;
+
+        Ok(DynamicSource {
+            url: url.to_string(),
+            registry: Arc::new(registry),
+            refresh_handle,
+            shutdown_handle,
+        })
+    }
+
+    /// Stops the source's background refresh thread, if it has one.
+    fn shutdown(&mut self) {
+        if let Some(mut shutdown_handle) = self.shutdown_handle.take() {
+            shutdown_handle.signal_shutdown();
+            if let Err(err) = shutdown_handle.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down registry source '{}': {}",
+                    self.url, err
+                );
+            }
+        }
+    }
+}
+
+/// Handle for shutting down the dynamic sources of a [`RegistrySources`].
+pub struct RegistrySourcesShutdownHandle {
+    dynamic_sources: Arc<Mutex<DynamicSources>>,
+    shutdown_handles: Vec<RemoteYamlShutdownHandle>,
+}
+
+impl ShutdownHandle for RegistrySourcesShutdownHandle {
+    fn signal_shutdown(&mut self) {
+        let mut dynamic_sources = match self.dynamic_sources.lock() {
+            Ok(dynamic_sources) => dynamic_sources,
+            Err(_) => {
+                error!("Registry sources lock poisoned; unable to signal shutdown");
+                return;
+            }
+        };
+
+        dynamic_sources.shutdown = true;
+        self.shutdown_handles.extend(
+            dynamic_sources
+                .sources
+                .iter_mut()
+                .filter_map(|source| source.shutdown_handle.take()),
+        );
+        self.shutdown_handles
+            .iter_mut()
+            .for_each(|handle| handle.signal_shutdown());
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        let mut errors = vec![];
+        for handle in self.shutdown_handles {
+            if let Err(err) = handle.wait_for_shutdown() {
+                errors.push(err);
+            }
+        }
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(InternalError::with_message(format!(
+                "Multiple errors occurred during shutdown: {}",
+                errors
+                    .into_iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use tempfile::Builder;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    use store::diesel::DieselRegistrySourceStore;
+
+    // Nothing listens on port 1, so fetching from this URL fails immediately
+    const UNREACHABLE_URL: &str = "http://127.0.0.1:1/registry.yaml";
+
+    /// Verifies that a source can be added, that the failure of its initial fetch is reported in
+    /// its status, that invalid and duplicate sources are rejected, that sources are restored from
+    /// the store, and that sources can be removed.
+    #[test]
+    fn add_list_and_remove_sources() {
+        let temp_dir = Builder::new()
+            .prefix("add_list_and_remove_sources")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let cache_dir = temp_dir.path().to_str().expect("Invalid temp dir path");
+        let store = DieselRegistrySourceStore::new(create_connection_pool_and_migrate());
+
+        let sources = RegistrySources::new(
+            store.clone_box(),
+            cache_dir,
+            None,
+            None,
+            vec![],
+            #[cfg(feature = "registry-parallel-fetch")]
+            None,
+        )
+        .expect("Failed to create registry sources");
+        assert!(sources
+            .list_sources()
+            .expect("Failed to list sources")
+            .is_empty());
+
+        assert!(matches!(
+            sources.add_source("file:///registry.yaml"),
+            Err(RegistryError::InvalidStateError(_))
+        ));
+
+        let status = sources
+            .add_source(UNREACHABLE_URL)
+            .expect("Failed to add source");
+        assert_eq!(status.url(), UNREACHABLE_URL);
+        assert!(status.is_dynamic());
+        assert_eq!(status.node_count(), 0);
+        assert!(status.last_refresh().is_none());
+        assert!(status.error().is_some());
+
+        assert!(matches!(
+            sources.add_source(UNREACHABLE_URL),
+            Err(RegistryError::InvalidStateError(_))
+        ));
+        assert_eq!(sources.count_nodes(&[]).expect("Failed to count nodes"), 0);
+
+        // Reading the nodes retried the failed fetch, so two failures have been recorded
+        #[cfg(feature = "registry-parallel-fetch")]
+        {
+            let listed = sources.list_sources().expect("Failed to list sources");
+            let refresh_stats = listed[0]
+                .refresh_stats()
+                .expect("Refresh stats were not recorded");
+            assert_eq!(refresh_stats.success_count(), 0);
+            assert_eq!(refresh_stats.failure_count(), 2);
+            assert!(refresh_stats.last_success().is_none());
+            assert!(refresh_stats.last_error().is_some());
+        }
+
+        let restored = RegistrySources::new(
+            store.clone_box(),
+            cache_dir,
+            None,
+            None,
+            vec![],
+            #[cfg(feature = "registry-parallel-fetch")]
+            None,
+        )
+        .expect("Failed to create registry sources");
+        let listed = restored.list_sources().expect("Failed to list sources");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].url(), UNREACHABLE_URL);
+
+        assert!(sources
+            .remove_source(UNREACHABLE_URL)
+            .expect("Failed to remove source"));
+        assert!(!sources
+            .remove_source(UNREACHABLE_URL)
+            .expect("Failed to remove source"));
+        assert!(store
+            .list_sources()
+            .expect("Failed to list stored sources")
+            .is_empty());
+
+        let mut shutdown_handle = sources.shutdown_handle();
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Failed to shut down registry sources");
+        assert!(matches!(
+            sources.add_source(UNREACHABLE_URL),
+            Err(RegistryError::InvalidStateError(_))
+        ));
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
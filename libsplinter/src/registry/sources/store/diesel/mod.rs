@@ -0,0 +1,280 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A database-backed [RegistrySourceStore](super::RegistrySourceStore), powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "registry-parallel-fetch")]
+use std::time::{Duration, SystemTime};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+#[cfg(feature = "registry-parallel-fetch")]
+use super::RegistryRefreshStats;
+use super::{RegistrySourceStore, RegistrySourceStoreError};
+
+use operations::{
+    add_source::RegistrySourceStoreAddSourceOperation,
+    list_sources::RegistrySourceStoreListSourcesOperation,
+    remove_source::RegistrySourceStoreRemoveSourceOperation, RegistrySourceStoreOperations,
+};
+#[cfg(feature = "registry-parallel-fetch")]
+use operations::{
+    list_refresh_stats::RegistrySourceStoreListRefreshStatsOperation,
+    record_refresh::RegistrySourceStoreRecordRefreshOperation,
+};
+
+pub struct DieselRegistrySourceStore<C: diesel::Connection + 'static> {
+    pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselRegistrySourceStore<C> {
+    /// Creates a new `DieselRegistrySourceStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: connection pool for the database
+    pub fn new(pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselRegistrySourceStore { pool: pool.into() }
+    }
+
+    /// Create a new `DieselRegistrySourceStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(pool: Arc<RwLock<Pool<ConnectionManager<C>>>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselRegistrySourceStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl RegistrySourceStore for DieselRegistrySourceStore<diesel::pg::PgConnection> {
+    fn add_source(&self, url: &str) -> Result<(), RegistrySourceStoreError> {
+        self.pool
+            .execute_write(|conn| RegistrySourceStoreOperations::new(conn).add_source(url))
+    }
+
+    fn remove_source(&self, url: &str) -> Result<bool, RegistrySourceStoreError> {
+        self.pool
+            .execute_write(|conn| RegistrySourceStoreOperations::new(conn).remove_source(url))
+    }
+
+    fn list_sources(&self) -> Result<Vec<String>, RegistrySourceStoreError> {
+        self.pool
+            .execute_read(|conn| RegistrySourceStoreOperations::new(conn).list_sources())
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn record_refresh(
+        &self,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<(), RegistrySourceStoreError> {
+        self.pool.execute_write(|conn| {
+            RegistrySourceStoreOperations::new(conn).record_refresh(
+                url,
+                attempted_at,
+                duration,
+                error,
+            )
+        })
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn list_refresh_stats(&self) -> Result<Vec<RegistryRefreshStats>, RegistrySourceStoreError> {
+        self.pool
+            .execute_read(|conn| RegistrySourceStoreOperations::new(conn).list_refresh_stats())
+    }
+
+    fn clone_box(&self) -> Box<dyn RegistrySourceStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl RegistrySourceStore for DieselRegistrySourceStore<diesel::sqlite::SqliteConnection> {
+    fn add_source(&self, url: &str) -> Result<(), RegistrySourceStoreError> {
+        self.pool
+            .execute_write(|conn| RegistrySourceStoreOperations::new(conn).add_source(url))
+    }
+
+    fn remove_source(&self, url: &str) -> Result<bool, RegistrySourceStoreError> {
+        self.pool
+            .execute_write(|conn| RegistrySourceStoreOperations::new(conn).remove_source(url))
+    }
+
+    fn list_sources(&self) -> Result<Vec<String>, RegistrySourceStoreError> {
+        self.pool
+            .execute_read(|conn| RegistrySourceStoreOperations::new(conn).list_sources())
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn record_refresh(
+        &self,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<(), RegistrySourceStoreError> {
+        self.pool.execute_write(|conn| {
+            RegistrySourceStoreOperations::new(conn).record_refresh(
+                url,
+                attempted_at,
+                duration,
+                error,
+            )
+        })
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn list_refresh_stats(&self) -> Result<Vec<RegistryRefreshStats>, RegistrySourceStoreError> {
+        self.pool
+            .execute_read(|conn| RegistrySourceStoreOperations::new(conn).list_refresh_stats())
+    }
+
+    fn clone_box(&self) -> Box<dyn RegistrySourceStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    use std::time::UNIX_EPOCH;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that sources can be added to a SQLite-backed store, that adding a source twice has
+    /// no effect, and that sources can be listed and removed.
+    #[test]
+    fn sqlite_add_list_and_remove_sources() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselRegistrySourceStore::new(pool);
+
+        store
+            .add_source("https://example.com/registry.yaml")
+            .expect("Unable to add source");
+        store
+            .add_source("https://example.org/registry.yaml")
+            .expect("Unable to add source");
+        store
+            .add_source("https://example.com/registry.yaml")
+            .expect("Unable to add source");
+
+        assert_eq!(
+            store.list_sources().expect("Unable to list sources"),
+            vec![
+                "https://example.com/registry.yaml".to_string(),
+                "https://example.org/registry.yaml".to_string(),
+            ]
+        );
+
+        assert!(store
+            .remove_source("https://example.com/registry.yaml")
+            .expect("Unable to remove source"));
+        assert!(!store
+            .remove_source("https://example.com/registry.yaml")
+            .expect("Unable to remove source"));
+        assert_eq!(
+            store.list_sources().expect("Unable to list sources"),
+            vec!["https://example.org/registry.yaml".to_string()]
+        );
+    }
+
+    /// Verify that refresh attempts are recorded in a SQLite-backed store, with successes and
+    /// failures counted separately and the time of the last success kept across failures.
+    #[cfg(feature = "registry-parallel-fetch")]
+    #[test]
+    fn sqlite_record_refresh() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselRegistrySourceStore::new(pool);
+        let url = "https://example.com/registry.yaml";
+
+        assert!(store
+            .list_refresh_stats()
+            .expect("Unable to list refresh stats")
+            .is_empty());
+
+        let first_attempt = UNIX_EPOCH + Duration::from_secs(1000);
+        store
+            .record_refresh(url, first_attempt, Duration::from_millis(250), None)
+            .expect("Unable to record refresh");
+        store
+            .record_refresh(
+                url,
+                first_attempt + Duration::from_secs(60),
+                Duration::from_secs(10),
+                Some("timed out"),
+            )
+            .expect("Unable to record refresh");
+
+        let stats = store
+            .list_refresh_stats()
+            .expect("Unable to list refresh stats");
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].url(), url);
+        assert_eq!(
+            stats[0].last_attempt(),
+            first_attempt + Duration::from_secs(60)
+        );
+        assert_eq!(stats[0].last_success(), Some(first_attempt));
+        assert_eq!(stats[0].last_duration(), Duration::from_secs(10));
+        assert_eq!(stats[0].success_count(), 1);
+        assert_eq!(stats[0].failure_count(), 1);
+        assert_eq!(stats[0].last_error(), Some("timed out"));
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+#[cfg(feature = "registry-parallel-fetch")]
+use std::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::InternalError;
+#[cfg(feature = "registry-parallel-fetch")]
+use crate::registry::sources::RegistryRefreshStats;
+use crate::registry::sources::RegistrySourceStoreError;
+
+#[cfg(feature = "registry-parallel-fetch")]
+use super::schema::registry_refresh_stats;
+use super::schema::registry_source;
+
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "registry_source"]
+pub struct RegistrySourceModel {
+    pub url: String,
+    pub added_at: i64,
+}
+
+impl RegistrySourceModel {
+    /// Creates a model for the given URL, added at the current time.
+    pub fn new(url: &str) -> Result<Self, RegistrySourceStoreError> {
+        Ok(RegistrySourceModel {
+            url: url.to_string(),
+            added_at: to_epoch_secs(SystemTime::now())?,
+        })
+    }
+}
+
+#[cfg(feature = "registry-parallel-fetch")]
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "registry_refresh_stats"]
+pub struct RegistryRefreshStatsModel {
+    pub url: String,
+    pub last_attempt: i64,
+    pub last_success: Option<i64>,
+    pub last_duration_ms: i64,
+    pub success_count: i64,
+    pub failure_count: i64,
+    pub last_error: Option<String>,
+}
+
+#[cfg(feature = "registry-parallel-fetch")]
+impl RegistryRefreshStatsModel {
+    /// Creates the model that results from recording a refresh attempt on top of the registry's
+    /// existing stats, if it has any.
+    pub fn record(
+        existing: Option<Self>,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<Self, RegistrySourceStoreError> {
+        let last_attempt = to_epoch_secs(attempted_at)?;
+        let last_duration_ms = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+        let (last_success, success_count, failure_count) = match &existing {
+            Some(existing) => (
+                existing.last_success,
+                existing.success_count,
+                existing.failure_count,
+            ),
+            None => (None, 0, 0),
+        };
+
+        Ok(match error {
+            Some(error) => RegistryRefreshStatsModel {
+                url: url.to_string(),
+                last_attempt,
+                last_success,
+                last_duration_ms,
+                success_count,
+                failure_count: failure_count + 1,
+                last_error: Some(error.to_string()),
+            },
+            None => RegistryRefreshStatsModel {
+                url: url.to_string(),
+                last_attempt,
+                last_success: Some(last_attempt),
+                last_duration_ms,
+                success_count: success_count + 1,
+                failure_count,
+                last_error: None,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "registry-parallel-fetch")]
+impl TryFrom<RegistryRefreshStatsModel> for RegistryRefreshStats {
+    type Error = RegistrySourceStoreError;
+
+    fn try_from(model: RegistryRefreshStatsModel) -> Result<Self, Self::Error> {
+        let to_u64 = |value: i64| {
+            u64::try_from(value).map_err(|err| {
+                RegistrySourceStoreError::Internal(InternalError::from_source(Box::new(err)))
+            })
+        };
+
+        Ok(RegistryRefreshStats {
+            url: model.url,
+            last_attempt: UNIX_EPOCH + Duration::from_secs(to_u64(model.last_attempt)?),
+            last_success: model
+                .last_success
+                .map(|secs| Ok(UNIX_EPOCH + Duration::from_secs(to_u64(secs)?)))
+                .transpose()?,
+            last_duration: Duration::from_millis(to_u64(model.last_duration_ms)?),
+            success_count: to_u64(model.success_count)?,
+            failure_count: to_u64(model.failure_count)?,
+            last_error: model.last_error,
+        })
+    }
+}
+
+/// Converts the given time to seconds since the Unix epoch.
+fn to_epoch_secs(time: SystemTime) -> Result<i64, RegistrySourceStoreError> {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| {
+            RegistrySourceStoreError::Internal(InternalError::from_source(Box::new(err)))
+        })?
+        .as_secs();
+    i64::try_from(secs).map_err(|err| {
+        RegistrySourceStoreError::Internal(InternalError::from_source(Box::new(err)))
+    })
+}
@@ -0,0 +1,73 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::registry::sources::{
+    store::diesel::{models::RegistrySourceModel, schema::registry_source},
+    RegistrySourceStoreError,
+};
+
+use super::RegistrySourceStoreOperations;
+
+pub(in crate::registry::sources::store::diesel) trait RegistrySourceStoreAddSourceOperation {
+    fn add_source(&self, url: &str) -> Result<(), RegistrySourceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RegistrySourceStoreAddSourceOperation
+    for RegistrySourceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_source(&self, url: &str) -> Result<(), RegistrySourceStoreError> {
+        let model = RegistrySourceModel::new(url)?;
+        self.conn.transaction::<_, RegistrySourceStoreError, _>(|| {
+            let exists = registry_source::table
+                .filter(registry_source::url.eq(&model.url))
+                .count()
+                .get_result::<i64>(self.conn)?
+                > 0;
+
+            if !exists {
+                insert_into(registry_source::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RegistrySourceStoreAddSourceOperation
+    for RegistrySourceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_source(&self, url: &str) -> Result<(), RegistrySourceStoreError> {
+        let model = RegistrySourceModel::new(url)?;
+        self.conn.transaction::<_, RegistrySourceStoreError, _>(|| {
+            let exists = registry_source::table
+                .filter(registry_source::url.eq(&model.url))
+                .count()
+                .get_result::<i64>(self.conn)?
+                > 0;
+
+            if !exists {
+                insert_into(registry_source::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
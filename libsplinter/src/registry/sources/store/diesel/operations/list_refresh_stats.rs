@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::registry::sources::{
+    store::diesel::{models::RegistryRefreshStatsModel, schema::registry_refresh_stats},
+    RegistryRefreshStats, RegistrySourceStoreError,
+};
+
+use super::RegistrySourceStoreOperations;
+
+pub(in crate::registry::sources::store::diesel) trait RegistrySourceStoreListRefreshStatsOperation {
+    fn list_refresh_stats(&self) -> Result<Vec<RegistryRefreshStats>, RegistrySourceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RegistrySourceStoreListRefreshStatsOperation
+    for RegistrySourceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_refresh_stats(&self) -> Result<Vec<RegistryRefreshStats>, RegistrySourceStoreError> {
+        registry_refresh_stats::table
+            .order(registry_refresh_stats::url.asc())
+            .load::<RegistryRefreshStatsModel>(self.conn)?
+            .into_iter()
+            .map(RegistryRefreshStats::try_from)
+            .collect()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RegistrySourceStoreListRefreshStatsOperation
+    for RegistrySourceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_refresh_stats(&self) -> Result<Vec<RegistryRefreshStats>, RegistrySourceStoreError> {
+        registry_refresh_stats::table
+            .order(registry_refresh_stats::url.asc())
+            .load::<RegistryRefreshStatsModel>(self.conn)?
+            .into_iter()
+            .map(RegistryRefreshStats::try_from)
+            .collect()
+    }
+}
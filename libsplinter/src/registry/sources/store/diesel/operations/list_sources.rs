@@ -0,0 +1,53 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::prelude::*;
+
+use crate::registry::sources::{
+    store::diesel::{models::RegistrySourceModel, schema::registry_source},
+    RegistrySourceStoreError,
+};
+
+use super::RegistrySourceStoreOperations;
+
+pub(in crate::registry::sources::store::diesel) trait RegistrySourceStoreListSourcesOperation {
+    fn list_sources(&self) -> Result<Vec<String>, RegistrySourceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RegistrySourceStoreListSourcesOperation
+    for RegistrySourceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_sources(&self) -> Result<Vec<String>, RegistrySourceStoreError> {
+        Ok(registry_source::table
+            .order((registry_source::added_at.asc(), registry_source::url.asc()))
+            .load::<RegistrySourceModel>(self.conn)?
+            .into_iter()
+            .map(|model| model.url)
+            .collect())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RegistrySourceStoreListSourcesOperation
+    for RegistrySourceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_sources(&self) -> Result<Vec<String>, RegistrySourceStoreError> {
+        Ok(registry_source::table
+            .order((registry_source::added_at.asc(), registry_source::url.asc()))
+            .load::<RegistrySourceModel>(self.conn)?
+            .into_iter()
+            .map(|model| model.url)
+            .collect())
+    }
+}
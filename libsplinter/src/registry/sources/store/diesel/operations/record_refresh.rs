@@ -0,0 +1,120 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::{Duration, SystemTime};
+
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+use crate::registry::sources::{
+    store::diesel::{models::RegistryRefreshStatsModel, schema::registry_refresh_stats},
+    RegistrySourceStoreError,
+};
+
+use super::RegistrySourceStoreOperations;
+
+pub(in crate::registry::sources::store::diesel) trait RegistrySourceStoreRecordRefreshOperation {
+    fn record_refresh(
+        &self,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<(), RegistrySourceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RegistrySourceStoreRecordRefreshOperation
+    for RegistrySourceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn record_refresh(
+        &self,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<(), RegistrySourceStoreError> {
+        self.conn.transaction::<_, RegistrySourceStoreError, _>(|| {
+            let existing = registry_refresh_stats::table
+                .filter(registry_refresh_stats::url.eq(url))
+                .first::<RegistryRefreshStatsModel>(self.conn)
+                .optional()?;
+            let exists = existing.is_some();
+            let model =
+                RegistryRefreshStatsModel::record(existing, url, attempted_at, duration, error)?;
+
+            if exists {
+                update(registry_refresh_stats::table.filter(registry_refresh_stats::url.eq(url)))
+                    .set((
+                        registry_refresh_stats::last_attempt.eq(model.last_attempt),
+                        registry_refresh_stats::last_success.eq(model.last_success),
+                        registry_refresh_stats::last_duration_ms.eq(model.last_duration_ms),
+                        registry_refresh_stats::success_count.eq(model.success_count),
+                        registry_refresh_stats::failure_count.eq(model.failure_count),
+                        registry_refresh_stats::last_error.eq(&model.last_error),
+                    ))
+                    .execute(self.conn)?;
+            } else {
+                insert_into(registry_refresh_stats::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RegistrySourceStoreRecordRefreshOperation
+    for RegistrySourceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn record_refresh(
+        &self,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<(), RegistrySourceStoreError> {
+        self.conn.transaction::<_, RegistrySourceStoreError, _>(|| {
+            let existing = registry_refresh_stats::table
+                .filter(registry_refresh_stats::url.eq(url))
+                .first::<RegistryRefreshStatsModel>(self.conn)
+                .optional()?;
+            let exists = existing.is_some();
+            let model =
+                RegistryRefreshStatsModel::record(existing, url, attempted_at, duration, error)?;
+
+            if exists {
+                update(registry_refresh_stats::table.filter(registry_refresh_stats::url.eq(url)))
+                    .set((
+                        registry_refresh_stats::last_attempt.eq(model.last_attempt),
+                        registry_refresh_stats::last_success.eq(model.last_success),
+                        registry_refresh_stats::last_duration_ms.eq(model.last_duration_ms),
+                        registry_refresh_stats::success_count.eq(model.success_count),
+                        registry_refresh_stats::failure_count.eq(model.failure_count),
+                        registry_refresh_stats::last_error.eq(&model.last_error),
+                    ))
+                    .execute(self.conn)?;
+            } else {
+                insert_into(registry_refresh_stats::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
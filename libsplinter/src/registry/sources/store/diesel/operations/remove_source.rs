@@ -0,0 +1,35 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{dsl::delete, prelude::*};
+
+use crate::registry::sources::{store::diesel::schema::registry_source, RegistrySourceStoreError};
+
+use super::RegistrySourceStoreOperations;
+
+pub(in crate::registry::sources::store::diesel) trait RegistrySourceStoreRemoveSourceOperation {
+    fn remove_source(&self, url: &str) -> Result<bool, RegistrySourceStoreError>;
+}
+
+impl<'a, C> RegistrySourceStoreRemoveSourceOperation for RegistrySourceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::serialize::ToSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn remove_source(&self, url: &str) -> Result<bool, RegistrySourceStoreError> {
+        delete(registry_source::table.filter(registry_source::url.eq(url.to_string())))
+            .execute(self.conn)
+            .map(|removed| removed > 0)
+            .map_err(RegistrySourceStoreError::from)
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+table! {
+    registry_source (url) {
+        url -> Text,
+        added_at -> BigInt,
+    }
+}
+
+table! {
+    registry_refresh_stats (url) {
+        url -> Text,
+        last_attempt -> BigInt,
+        last_success -> Nullable<BigInt>,
+        last_duration_ms -> BigInt,
+        success_count -> BigInt,
+        failure_count -> BigInt,
+        last_error -> Nullable<Text>,
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error types for registry source stores.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::error::{InternalError, ResourceTemporarilyUnavailableError};
+use crate::registry::RegistryError;
+
+/// Error states for fallible [RegistrySourceStore](super::RegistrySourceStore) operations.
+#[derive(Debug)]
+pub enum RegistrySourceStoreError {
+    Internal(InternalError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl fmt::Display for RegistrySourceStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistrySourceStoreError::Internal(err) => err.fmt(f),
+            RegistrySourceStoreError::ResourceTemporarilyUnavailable(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for RegistrySourceStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RegistrySourceStoreError::Internal(err) => Some(err),
+            RegistrySourceStoreError::ResourceTemporarilyUnavailable(err) => Some(err),
+        }
+    }
+}
+
+impl From<InternalError> for RegistrySourceStoreError {
+    fn from(err: InternalError) -> Self {
+        RegistrySourceStoreError::Internal(err)
+    }
+}
+
+impl From<RegistrySourceStoreError> for RegistryError {
+    fn from(err: RegistrySourceStoreError) -> Self {
+        match err {
+            RegistrySourceStoreError::Internal(err) => RegistryError::InternalError(err),
+            RegistrySourceStoreError::ResourceTemporarilyUnavailable(err) => {
+                RegistryError::ResourceTemporarilyUnavailableError(err)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for RegistrySourceStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        RegistrySourceStoreError::Internal(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for RegistrySourceStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        RegistrySourceStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Storage of the remote registry sources added at runtime.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+#[cfg(feature = "registry-parallel-fetch")]
+use std::time::{Duration, SystemTime};
+
+pub use error::RegistrySourceStoreError;
+
+/// The history of attempts to fetch a remote registry file.
+#[cfg(feature = "registry-parallel-fetch")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryRefreshStats {
+    pub(crate) url: String,
+    pub(crate) last_attempt: SystemTime,
+    pub(crate) last_success: Option<SystemTime>,
+    pub(crate) last_duration: Duration,
+    pub(crate) success_count: u64,
+    pub(crate) failure_count: u64,
+    pub(crate) last_error: Option<String>,
+}
+
+#[cfg(feature = "registry-parallel-fetch")]
+impl RegistryRefreshStats {
+    /// Returns the URL of the remote registry file.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the time of the last attempt to fetch the remote registry file.
+    pub fn last_attempt(&self) -> SystemTime {
+        self.last_attempt
+    }
+
+    /// Returns the time the remote registry file was last fetched successfully, if ever.
+    pub fn last_success(&self) -> Option<SystemTime> {
+        self.last_success
+    }
+
+    /// Returns how long the last attempt took, whether or not it succeeded.
+    pub fn last_duration(&self) -> Duration {
+        self.last_duration
+    }
+
+    /// Returns the number of successful fetches.
+    pub fn success_count(&self) -> u64 {
+        self.success_count
+    }
+
+    /// Returns the number of failed fetches.
+    pub fn failure_count(&self) -> u64 {
+        self.failure_count
+    }
+
+    /// Returns the error of the last attempt, if it failed.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+pub trait RegistrySourceStore: Send + Sync {
+    /// Adds the URL of a remote registry to the store. Adding a URL that is already in the store
+    /// has no effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - the URL of the remote registry file
+    fn add_source(&self, url: &str) -> Result<(), RegistrySourceStoreError>;
+
+    /// Removes the URL of a remote registry from the store, returning false if it was not present.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - the URL of the remote registry file
+    fn remove_source(&self, url: &str) -> Result<bool, RegistrySourceStoreError>;
+
+    /// Lists the URLs in the store, in the order they were added.
+    fn list_sources(&self) -> Result<Vec<String>, RegistrySourceStoreError>;
+
+    /// Records an attempt to fetch a remote registry file, updating the refresh stats of that
+    /// registry. Stats are kept for any remote registry, not only those added to the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - the URL of the remote registry file
+    /// * `attempted_at` - the time the attempt started
+    /// * `duration` - how long the attempt took
+    /// * `error` - the reason the attempt failed, if it did
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn record_refresh(
+        &self,
+        url: &str,
+        attempted_at: SystemTime,
+        duration: Duration,
+        error: Option<&str>,
+    ) -> Result<(), RegistrySourceStoreError>;
+
+    /// Lists the refresh stats of every remote registry that has been fetched.
+    #[cfg(feature = "registry-parallel-fetch")]
+    fn list_refresh_stats(&self) -> Result<Vec<RegistryRefreshStats>, RegistrySourceStoreError>;
+
+    fn clone_box(&self) -> Box<dyn RegistrySourceStore>;
+}
+
+impl Clone for Box<dyn RegistrySourceStore> {
+    fn clone(&self) -> Box<dyn RegistrySourceStore> {
+        self.clone_box()
+    }
+}
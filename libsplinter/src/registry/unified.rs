@@ -26,6 +26,8 @@ use std::sync::Arc;
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
+#[cfg(feature = "registry-batch")]
+use super::{NodeBatchResult, NodeOperation};
 
 /// A registry with multiple sources.
 ///
@@ -211,6 +213,14 @@ impl RegistryWriter for UnifiedRegistry {
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
         self.internal_source.delete_node(identity)
     }
+
+    #[cfg(feature = "registry-batch")]
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        self.internal_source.apply_batch(operations)
+    }
 }
 
 impl RwRegistry for UnifiedRegistry {
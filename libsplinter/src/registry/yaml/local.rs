@@ -20,16 +20,20 @@
 //! [`LocalYamlRegistry`]: struct.LocalYamlRegistry.html
 //! [`RwRegistry`]: ../../trait.RwRegistry.html
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Write};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::SystemTime;
 
+use serde::de::{Deserialize, Deserializer, IgnoredAny, SeqAccess, Visitor};
+
 use crate::registry::{
-    error::InvalidNodeError, validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError,
-    RegistryReader, RegistryWriter, RwRegistry,
+    check_node_required_fields_are_not_empty, error::InvalidNodeError, MetadataPredicate, Node,
+    NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
 
 use crate::error::{InternalError, InvalidStateError};
@@ -45,7 +49,12 @@ use super::YamlNode;
 /// The contents of the YAML file are cached in-memory by the registry; this means that the registry
 /// will continue to be available even if the backing YAML file becomes unavailable. Each time the
 /// registry is read, it will check the backing file for any changes since the last read and
-/// refresh the internal cache if necessary.
+/// refresh the internal cache if necessary. The cache is indexed by node identity and endpoint, so
+/// single-node lookups and validation of new nodes do not scan the whole registry.
+///
+/// Nodes are converted one at a time as the file is parsed. When a node is added and the backing
+/// file has not been modified since the registry last wrote it, the new node is appended to the
+/// file rather than rewriting the entire file.
 ///
 /// On initializaion, the registry will check if its backing file already exists. If the backing
 /// file already exists, the registry will attempt to load, parse, and validate it. If the backing
@@ -74,37 +83,28 @@ impl LocalYamlRegistry {
 
     /// Get all nodes in the registry.
     pub(super) fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
-        Ok(self
-            .internal
-            .lock()
-            .map_err(|_| {
-                RegistryError::InternalError(InternalError::with_message(
-                    "YAML registry's internal lock poisoned".into(),
-                ))
-            })?
-            .get_nodes())
+        Ok(self.lock()?.get_nodes())
     }
 
     /// Write the given list of nodes to the backing YAML file.
     pub(super) fn write_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
-        self.internal
-            .lock()
-            .map_err(|_| {
-                RegistryError::InternalError(InternalError::with_message(
-                    "YAML registry's internal lock poisoned".into(),
-                ))
-            })?
-            .write_nodes(nodes)
+        self.lock()?.write_nodes(nodes)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<Internal>, RegistryError> {
+        self.internal.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "YAML registry's internal lock poisoned".into(),
+            ))
+        })
     }
 }
 
 impl RegistryReader for LocalYamlRegistry {
     fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
-        Ok(self
-            .get_nodes()?
-            .iter()
-            .find(|node| node.identity == identity)
-            .cloned())
+        let mut internal = self.lock()?;
+        internal.refresh();
+        Ok(internal.get_node(identity).cloned())
     }
 
     fn list_nodes<'a, 'b: 'a>(
@@ -117,34 +117,37 @@ impl RegistryReader for LocalYamlRegistry {
     }
 
     fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
-        Ok(self
-            .get_nodes()?
+        let mut internal = self.lock()?;
+        internal.refresh();
+        Ok(internal
+            .cached_nodes
             .iter()
             .filter(move |node| predicates.iter().all(|predicate| predicate.apply(node)))
             .count() as u32)
     }
 
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
-        Ok(self
-            .get_nodes()?
-            .iter()
-            .any(|node| node.identity == identity))
+        let mut internal = self.lock()?;
+        internal.refresh();
+        Ok(internal.get_node(identity).is_some())
     }
 }
 
 impl RegistryWriter for LocalYamlRegistry {
     fn add_node(&self, node: Node) -> Result<(), RegistryError> {
-        let mut nodes = self.get_nodes()?;
-        nodes.push(node);
-        self.write_nodes(nodes)
+        let mut internal = self.lock()?;
+        internal.refresh();
+        internal.add_node(node)
     }
 
     fn update_node(&self, node: Node) -> Result<(), RegistryError> {
-        let mut nodes = self.get_nodes()?;
-        if nodes.iter().any(|n| n.identity == node.identity) {
+        let mut internal = self.lock()?;
+        internal.refresh();
+        if internal.get_node(&node.identity).is_some() {
+            let mut nodes = internal.cached_nodes.clone();
             nodes.retain(|existing_node| existing_node.identity != node.identity);
             nodes.push(node);
-            self.write_nodes(nodes)
+            internal.write_nodes(nodes)
         } else {
             Err(RegistryError::InvalidStateError(
                 InvalidStateError::with_message(format!(
@@ -156,17 +159,12 @@ impl RegistryWriter for LocalYamlRegistry {
     }
 
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
-        let mut nodes = self.get_nodes()?;
-        let mut index = None;
-        for (i, node) in nodes.iter().enumerate() {
-            if node.identity == identity {
-                index = Some(i);
-                break;
-            }
-        }
-        let opt = index.map(|i| nodes.remove(i));
+        let mut internal = self.lock()?;
+        internal.refresh();
+        let mut nodes = internal.cached_nodes.clone();
+        let opt = internal.index.position(identity).map(|i| nodes.remove(i));
 
-        self.write_nodes(nodes)?;
+        internal.write_nodes(nodes)?;
 
         Ok(opt)
     }
@@ -190,7 +188,11 @@ impl RwRegistry for LocalYamlRegistry {
 struct Internal {
     file_path: String,
     cached_nodes: Vec<Node>,
+    index: NodeIndex,
     last_read: SystemTime,
+    /// The length of the backing file after the registry last wrote it; `None` if the file's
+    /// current contents were not written by the registry, in which case it cannot be appended to.
+    written_len: Option<u64>,
 }
 
 impl Internal {
@@ -198,7 +200,9 @@ impl Internal {
         let mut internal = Self {
             file_path: file_path.into(),
             cached_nodes: vec![],
+            index: NodeIndex::default(),
             last_read: SystemTime::UNIX_EPOCH,
+            written_len: None,
         };
 
         // If file already exists, read it; otherwise initialize it.
@@ -214,6 +218,19 @@ impl Internal {
     /// Get the internal list of nodes. If the backing file has been modified since the last read,
     /// attempt to refresh the cache.
     fn get_nodes(&mut self) -> Vec<Node> {
+        self.refresh();
+        self.cached_nodes.clone()
+    }
+
+    /// Get a node from the cache by its identity, without checking the backing file.
+    fn get_node(&self, identity: &str) -> Option<&Node> {
+        self.index
+            .position(identity)
+            .map(|position| &self.cached_nodes[position])
+    }
+
+    /// If the backing file has been modified since the last read, attempt to refresh the cache.
+    fn refresh(&mut self) {
         let file_read_result = std::fs::metadata(&self.file_path)
             .and_then(|metadata| metadata.modified())
             .map_err(|err| {
@@ -238,111 +255,286 @@ impl Internal {
                 err
             );
         }
-
-        self.cached_nodes.clone()
     }
 
     /// Read the backing file, verify that it's valid, and cache its contents.
     fn read_nodes(&mut self) -> Result<(), RegistryError> {
-        let mut file = File::open(&self.file_path).map_err(|err| {
+        let file = File::open(&self.file_path).map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
                 Box::new(err),
                 "Failed to open YAML registry file".into(),
             ))
         })?;
 
-        let mut buffer = vec![];
-        file.read_to_end(&mut buffer).map_err(|err| {
-            RegistryError::InternalError(InternalError::from_source_with_message(
-                Box::new(err),
-                format!("Failed to read YAML registry file {}", self.file_path),
-            ))
-        })?;
+        let is_empty = file
+            .metadata()
+            .map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Failed to read YAML registry file {}", self.file_path),
+                ))
+            })?
+            .len()
+            == 0;
 
-        let nodes = if buffer.is_empty() {
+        let nodes = if is_empty {
             vec![]
         } else {
-            let yaml_nodes: Option<Vec<YamlNode>> =
-                serde_yaml::from_slice(&buffer).map_err(|err| {
-                    RegistryError::InternalError(InternalError::from_source_with_message(
-                        Box::new(err),
-                        "Failed to read YAML registry file".into(),
-                    ))
-                })?;
-
-            let nodes: Vec<Node> = yaml_nodes
-                .unwrap_or_default()
-                .into_iter()
-                .map(Node::try_from)
-                .collect::<Result<Vec<Node>, InvalidNodeError>>()
+            let parsed_nodes: Option<ParsedNodes> = serde_yaml::from_reader(BufReader::new(file))
+                .map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    "Failed to read YAML registry file".into(),
+                ))
+            })?;
+
+            parsed_nodes
+                .map(|parsed| parsed.0)
+                .unwrap_or_else(|| Ok(vec![]))
                 .map_err(|err| {
                     RegistryError::InvalidStateError(InvalidStateError::with_message(format!(
                         "Unable to get node list: {}",
                         err
                     )))
-                })?;
-
-            validate_nodes(&nodes).map_err(|err| {
-                RegistryError::InvalidStateError(InvalidStateError::with_message(err.to_string()))
-            })?;
-
-            nodes
+                })?
         };
 
+        self.index = NodeIndex::build(&nodes).map_err(|err| {
+            RegistryError::InvalidStateError(InvalidStateError::with_message(err.to_string()))
+        })?;
         self.cached_nodes = nodes;
         self.last_read = SystemTime::now();
+        self.written_len = None;
 
         Ok(())
     }
 
-    /// Verify that the given nodes represent a valid registry, write them to the backing file, and
-    /// update the in-memory cache.
-    fn write_nodes(&mut self, nodes: Vec<Node>) -> Result<(), RegistryError> {
-        validate_nodes(&nodes).map_err(|err| {
+    /// Verify that the given node can be added to the registry, append it to the backing file if
+    /// possible (or rewrite the file otherwise), and update the in-memory cache.
+    fn add_node(&mut self, node: Node) -> Result<(), RegistryError> {
+        self.index.check_insert(&node).map_err(|err| {
             RegistryError::InvalidStateError(InvalidStateError::with_message(err.to_string()))
         })?;
 
-        let yaml_nodes: Vec<YamlNode> = nodes
-            .iter()
-            .map(|node| YamlNode::from(node.clone()))
-            .collect::<Vec<YamlNode>>();
+        match self.appendable_file()? {
+            Some(file) => {
+                self.append_node(file, &node)?;
+                self.index.insert(&node, self.cached_nodes.len());
+                self.cached_nodes.push(node);
+                self.last_read = SystemTime::now();
+                Ok(())
+            }
+            None => {
+                let mut nodes = self.cached_nodes.clone();
+                nodes.push(node);
+                self.write_nodes(nodes)
+            }
+        }
+    }
+
+    /// Opens the backing file for appending, if its contents are a non-empty YAML sequence that was
+    /// last written by this registry and has not been modified since.
+    fn appendable_file(&self) -> Result<Option<File>, RegistryError> {
+        let written_len = match self.written_len {
+            Some(len) if !self.cached_nodes.is_empty() => len,
+            _ => return Ok(None),
+        };
+
+        let metadata = match std::fs::metadata(&self.file_path) {
+            Ok(metadata) => metadata,
+            Err(_) => return Ok(None),
+        };
+        let unmodified = metadata
+            .modified()
+            .map(|last_modified| last_modified <= self.last_read)
+            .unwrap_or(false);
+        if !unmodified || metadata.len() != written_len {
+            return Ok(None);
+        }
+
+        OpenOptions::new()
+            .append(true)
+            .open(&self.file_path)
+            .map(Some)
+            .map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Failed to open YAML registry file '{}'", self.file_path),
+                ))
+            })
+    }
 
-        let output = serde_yaml::to_vec(&yaml_nodes).map_err(|err| {
+    /// Append the given node to the sequence in the backing file.
+    fn append_node(&mut self, mut file: File, node: &Node) -> Result<(), RegistryError> {
+        let yaml_node = YamlNode::from(node.clone());
+        let output = serde_yaml::to_string(std::slice::from_ref(&yaml_node)).map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
                 Box::new(err),
-                "Failed to write nodes to YAML".into(),
+                "Failed to write node to YAML".into(),
             ))
         })?;
+        // The node is serialized as a single-item sequence; drop the document start marker so the
+        // item continues the sequence that is already in the file
+        let output = output.strip_prefix("---\n").unwrap_or(&output);
 
-        let mut file = File::create(&self.file_path).map_err(|err| {
+        writeln!(file, "{}", output).map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
                 Box::new(err),
-                format!("Failed to open YAML registry file '{}'", self.file_path),
+                format!("Failed to write to YAML registry file '{}'", self.file_path),
             ))
         })?;
 
-        file.write_all(&output).map_err(|err| {
+        self.written_len = self.written_len.map(|len| len + output.len() as u64 + 1);
+
+        Ok(())
+    }
+
+    /// Verify that the given nodes represent a valid registry, write them to the backing file, and
+    /// update the in-memory cache.
+    fn write_nodes(&mut self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        let index = NodeIndex::build(&nodes).map_err(|err| {
+            RegistryError::InvalidStateError(InvalidStateError::with_message(err.to_string()))
+        })?;
+
+        let yaml_nodes: Vec<YamlNode> = nodes
+            .iter()
+            .map(|node| YamlNode::from(node.clone()))
+            .collect::<Vec<YamlNode>>();
+
+        let file = File::create(&self.file_path).map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
                 Box::new(err),
-                format!("Failed to write to YAML registry file '{}'", self.file_path),
+                format!("Failed to open YAML registry file '{}'", self.file_path),
             ))
         })?;
+        let mut writer = BufWriter::new(file);
 
-        // Append newline to file
-        writeln!(file).map_err(|err| {
+        serde_yaml::to_writer(&mut writer, &yaml_nodes).map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
                 Box::new(err),
                 format!("Failed to write to YAML registry file '{}'", self.file_path),
             ))
         })?;
 
+        // Append newline to file
+        writeln!(writer)
+            .and_then(|_| writer.flush())
+            .map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Failed to write to YAML registry file '{}'", self.file_path),
+                ))
+            })?;
+
+        self.written_len = writer
+            .get_ref()
+            .metadata()
+            .map(|metadata| metadata.len())
+            .ok();
         self.cached_nodes = nodes;
+        self.index = index;
         self.last_read = SystemTime::now();
 
         Ok(())
     }
 }
 
+/// Indexes of the cached nodes, used to look up nodes and to validate new nodes without scanning
+/// the whole registry.
+#[derive(Default)]
+struct NodeIndex {
+    /// Maps each node's identity to its position in the cached list of nodes
+    positions: HashMap<String, usize>,
+    /// Maps each endpoint to the identity of the node that has it
+    endpoints: HashMap<String, String>,
+}
+
+impl NodeIndex {
+    /// Build the index for the given nodes, verifying that they represent a valid registry.
+    fn build(nodes: &[Node]) -> Result<Self, InvalidNodeError> {
+        let mut index = Self::default();
+        for (position, node) in nodes.iter().enumerate() {
+            index.check_insert(node)?;
+            index.insert(node, position);
+        }
+        Ok(index)
+    }
+
+    /// Get the position of the node with the given identity.
+    fn position(&self, identity: &str) -> Option<usize> {
+        self.positions.get(identity).copied()
+    }
+
+    /// Verify that the given node is valid and does not duplicate the identity or any endpoint of
+    /// an indexed node.
+    fn check_insert(&self, node: &Node) -> Result<(), InvalidNodeError> {
+        check_node_required_fields_are_not_empty(node)?;
+
+        if self.positions.contains_key(&node.identity) {
+            return Err(InvalidNodeError::DuplicateIdentity(node.identity.clone()));
+        }
+
+        match node
+            .endpoints
+            .iter()
+            .find(|endpoint| self.endpoints.contains_key(*endpoint))
+        {
+            Some(endpoint) => Err(InvalidNodeError::DuplicateEndpoint(endpoint.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn insert(&mut self, node: &Node, position: usize) {
+        self.positions.insert(node.identity.clone(), position);
+        for endpoint in &node.endpoints {
+            self.endpoints
+                .insert(endpoint.clone(), node.identity.clone());
+        }
+    }
+}
+
+/// The nodes parsed from a registry file. Each node is converted as soon as it is parsed, so the
+/// intermediate YAML representation of the whole file is never held in memory; the first node
+/// that fails to convert is returned as the error.
+struct ParsedNodes(Result<Vec<Node>, InvalidNodeError>);
+
+impl<'de> Deserialize<'de> for ParsedNodes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(ParsedNodesVisitor)
+    }
+}
+
+struct ParsedNodesVisitor;
+
+impl<'de> Visitor<'de> for ParsedNodesVisitor {
+    type Value = ParsedNodes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of nodes")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut nodes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(yaml_node) = seq.next_element::<YamlNode>()? {
+            match Node::try_from(yaml_node) {
+                Ok(node) => nodes.push(node),
+                Err(err) => {
+                    // The remaining elements must still be consumed
+                    while seq.next_element::<IgnoredAny>()?.is_some() {}
+                    return Ok(ParsedNodes(Err(err)));
+                }
+            }
+        }
+        Ok(ParsedNodes(Ok(nodes)))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1372,6 +1564,133 @@ mod test {
         assert_eq!(nodes, vec![get_node_1()]);
     }
 
+    ///
+    /// Verifies that nodes added to a file written by the registry are appended to the file as a
+    /// single YAML sequence, which is read back as the full list of nodes.
+    ///
+    #[test]
+    fn test_add_node_appends() {
+        let temp_dir = Builder::new()
+            .prefix("test_add_node_appends")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("registry.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+        let registry = LocalYamlRegistry::new(&path).expect("Failed to create LocalYamlRegistry");
+        registry
+            .add_node(get_node_1())
+            .expect("Failed to add node 1");
+        registry
+            .add_node(get_node_2())
+            .expect("Failed to add node 2");
+        registry
+            .add_node(get_node_3())
+            .expect("Failed to add node 3");
+
+        let file = File::open(&path).expect("Failed to open file");
+        let file_contents: Vec<YamlNode> =
+            serde_yaml::from_reader(file).expect("Failed to deserialize file");
+        assert_eq!(
+            file_contents,
+            vec![
+                YamlNode::from(get_node_1()),
+                YamlNode::from(get_node_2()),
+                YamlNode::from(get_node_3())
+            ]
+        );
+
+        // A new registry reading the file must see the same nodes
+        let reloaded = LocalYamlRegistry::new(&path).expect("Failed to create LocalYamlRegistry");
+        assert_eq!(
+            reloaded.get_nodes().expect("Failed to get nodes"),
+            vec![get_node_1(), get_node_2(), get_node_3()]
+        );
+        assert_eq!(
+            reloaded
+                .get_node(&get_node_2().identity)
+                .expect("Failed to get node"),
+            Some(get_node_2())
+        );
+    }
+
+    ///
+    /// Verifies that adding a node to a file that was not written by the registry rewrites the
+    /// file, so the node is added to the file's sequence regardless of its formatting.
+    ///
+    #[test]
+    fn test_add_node_to_external_file() {
+        let temp_dir = Builder::new()
+            .prefix("test_add_node_to_external_file")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("registry.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        // Written without a trailing newline
+        write_to_file(&[get_node_1()], &path);
+
+        let registry = LocalYamlRegistry::new(&path).expect("Failed to create LocalYamlRegistry");
+        registry
+            .add_node(get_node_2())
+            .expect("Failed to add node 2");
+
+        let file = File::open(&path).expect("Failed to open file");
+        let file_contents: Vec<YamlNode> =
+            serde_yaml::from_reader(file).expect("Failed to deserialize file");
+        assert_eq!(
+            file_contents,
+            vec![YamlNode::from(get_node_1()), YamlNode::from(get_node_2())]
+        );
+    }
+
+    ///
+    /// Verifies that the identity index is kept up to date when nodes are deleted, so the
+    /// remaining nodes can still be found.
+    ///
+    #[test]
+    fn test_index_after_delete() {
+        let temp_dir = Builder::new()
+            .prefix("test_index_after_delete")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("registry.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        write_to_file(&[get_node_1(), get_node_2(), get_node_3()], &path);
+
+        let registry = LocalYamlRegistry::new(&path).expect("Failed to create LocalYamlRegistry");
+        registry
+            .delete_node(&get_node_1().identity)
+            .expect("Failed to delete node");
+
+        assert!(!registry
+            .has_node(&get_node_1().identity)
+            .expect("Failed to check node"));
+        assert_eq!(
+            registry
+                .get_node(&get_node_3().identity)
+                .expect("Failed to get node"),
+            Some(get_node_3())
+        );
+
+        // The deleted node's endpoint may now be reused
+        let mut node = get_node_1();
+        node.identity = "Node-000".to_string();
+        registry.add_node(node).expect("Failed to add node");
+    }
+
     fn get_node_1() -> Node {
         Node::builder("Node-123")
             .with_endpoint("tcps://12.0.0.123:8431")
@@ -26,6 +26,10 @@ pub use crate::registry::error::InvalidNodeError;
 use super::Node;
 
 pub use local::LocalYamlRegistry;
+#[cfg(feature = "registry-parallel-fetch")]
+pub use remote::RemoteYamlFetchOptions;
+#[cfg(feature = "registry-remote-refresh")]
+pub use remote::RemoteYamlRefreshHandle;
 #[cfg(feature = "registry-remote")]
 pub use remote::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
 
@@ -27,12 +27,18 @@ use std::sync::{
     Arc, Mutex,
 };
 use std::thread;
+#[cfg(feature = "registry-sources")]
+use std::time::SystemTime;
 use std::time::{Duration, Instant};
 
 use openssl::hash::{hash, MessageDigest};
 
 use crate::error::{InternalError, InvalidStateError};
 use crate::hex::to_hex;
+#[cfg(feature = "registry-sources")]
+use crate::registry::sources::RegistrySourceStatus;
+#[cfg(feature = "registry-parallel-fetch")]
+use crate::registry::sources::RegistrySourceStore;
 use crate::registry::{
     error::InvalidNodeError, validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError,
     RegistryReader,
@@ -71,6 +77,11 @@ use super::{LocalYamlRegistry, YamlNode};
 /// and the previously cached registry values will continue to be used. The next time the registry
 /// is read, it will try again to refresh the cache.
 ///
+/// A timeout for fetching the remote file, and a store that records the outcome of each fetch, may
+/// be set with `RemoteYamlFetchOptions` when the registry is constructed with
+/// `new_with_fetch_options`. Several registries may be constructed at once with
+/// `new_concurrently`, so that one slow remote file does not delay the others.
+///
 /// [`Node`]: struct.Node.html
 /// [`RegistryReader`]: trait.RegistryReader.html
 /// [`constructor`]: struct.RemoteYamlRegistry.html#method.new
@@ -98,11 +109,112 @@ impl RemoteYamlRegistry {
         automatic_refresh_period: Option<Duration>,
         forced_refresh_period: Option<Duration>,
     ) -> Result<Self, RegistryError> {
-        let internal = Arc::new(Mutex::new(Internal::new(
-            url,
-            cache_dir,
-            forced_refresh_period,
-        )?));
+        Self::start(
+            Internal::new(
+                url,
+                cache_dir,
+                forced_refresh_period,
+                None,
+                #[cfg(feature = "registry-parallel-fetch")]
+                None,
+            )?,
+            automatic_refresh_period,
+        )
+    }
+
+    /// Construct a new `RemoteYamlRegistry` that fetches its remote file with the given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the registry's backing YAML file.
+    /// * `cache_dir` - Directory that the local registry cache will be stored in.
+    /// * `automatic_refresh_period` - see [`new`](Self::new)
+    /// * `forced_refresh_period` - see [`new`](Self::new)
+    /// * `fetch_options` - the timeout and stats store used when fetching the remote file
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn new_with_fetch_options(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        fetch_options: RemoteYamlFetchOptions,
+    ) -> Result<Self, RegistryError> {
+        Self::start(
+            Internal::new(
+                url,
+                cache_dir,
+                forced_refresh_period,
+                fetch_options.timeout,
+                fetch_options.stats_store,
+            )?,
+            automatic_refresh_period,
+        )
+    }
+
+    /// Construct a `RemoteYamlRegistry` for each of the given URLs, fetching the remote files
+    /// concurrently. The results are returned in the same order as the URLs; the failure of one
+    /// registry does not affect the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `urls` - URLs of the registries' backing YAML files.
+    /// * `cache_dir` - Directory that the local registry caches will be stored in.
+    /// * `automatic_refresh_period` - see [`new`](Self::new)
+    /// * `forced_refresh_period` - see [`new`](Self::new)
+    /// * `fetch_options` - the timeout and stats store used when fetching the remote files
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn new_concurrently(
+        urls: &[String],
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        fetch_options: RemoteYamlFetchOptions,
+    ) -> Vec<Result<Self, RegistryError>> {
+        let join_handles = urls
+            .iter()
+            .map(|url| {
+                let thread_url = url.clone();
+                let thread_cache_dir = cache_dir.to_string();
+                let thread_fetch_options = fetch_options.clone();
+                thread::Builder::new()
+                    .name(format!("Remote Registry Fetch: {}", url))
+                    .spawn(move || {
+                        Self::new_with_fetch_options(
+                            &thread_url,
+                            &thread_cache_dir,
+                            automatic_refresh_period,
+                            forced_refresh_period,
+                            thread_fetch_options,
+                        )
+                    })
+                    .map_err(|err| {
+                        RegistryError::InternalError(InternalError::from_source_with_message(
+                            Box::new(err),
+                            format!("Failed to spawn fetch thread for remote registry '{}'", url),
+                        ))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        join_handles
+            .into_iter()
+            .zip(urls)
+            .map(|(join_handle, url)| {
+                join_handle?.join().unwrap_or_else(|_| {
+                    Err(RegistryError::InternalError(InternalError::with_message(
+                        format!("Fetch thread for remote registry '{}' panicked", url),
+                    )))
+                })
+            })
+            .collect()
+    }
+
+    fn start(
+        internal: Internal,
+        automatic_refresh_period: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        let url = internal.url.clone();
+        let internal = Arc::new(Mutex::new(internal));
 
         let (running, join_handle) = {
             if let Some(refresh_period) = automatic_refresh_period {
@@ -151,6 +263,26 @@ impl RemoteYamlRegistry {
         self.shutdown_handle.take()
     }
 
+    /// Get a handle that can be used to force an immediate refresh of this registry's cache.
+    #[cfg(feature = "registry-remote-refresh")]
+    pub fn refresh_handle(&self) -> Result<RemoteYamlRefreshHandle, RegistryError> {
+        let url = self
+            .internal
+            .lock()
+            .map_err(|_| {
+                RegistryError::InternalError(InternalError::with_message(
+                    "Internal lock poisoned".into(),
+                ))
+            })?
+            .url
+            .clone();
+
+        Ok(RemoteYamlRefreshHandle {
+            url,
+            internal: self.internal.clone(),
+        })
+    }
+
     /// Acquire the lock for the internal cache and get the nodes from it.
     fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
         self.internal
@@ -190,6 +322,30 @@ impl RegistryReader for RemoteYamlRegistry {
     }
 }
 
+/// Options for fetching the remote file of a [`RemoteYamlRegistry`].
+#[cfg(feature = "registry-parallel-fetch")]
+#[derive(Clone, Default)]
+pub struct RemoteYamlFetchOptions {
+    timeout: Option<Duration>,
+    stats_store: Option<Box<dyn RegistrySourceStore>>,
+}
+
+#[cfg(feature = "registry-parallel-fetch")]
+impl RemoteYamlFetchOptions {
+    /// Sets the time allowed for fetching the remote file, after which the fetch fails. Without a
+    /// timeout, the HTTP client's default is used.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the store that the outcome of each fetch is recorded in.
+    pub fn with_stats_store(mut self, stats_store: Box<dyn RegistrySourceStore>) -> Self {
+        self.stats_store = Some(stats_store);
+        self
+    }
+}
+
 /// Holds the internal state of the remote registry.
 struct Internal {
     url: String,
@@ -197,6 +353,13 @@ struct Internal {
     last_refresh_successful: bool,
     forced_refresh_period: Option<Duration>,
     next_forced_refresh: Option<Instant>,
+    fetch_timeout: Option<Duration>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    stats_store: Option<Box<dyn RegistrySourceStore>>,
+    #[cfg(feature = "registry-sources")]
+    last_refresh: Option<SystemTime>,
+    #[cfg(feature = "registry-sources")]
+    last_refresh_error: Option<String>,
 }
 
 impl Internal {
@@ -205,6 +368,10 @@ impl Internal {
         url: &str,
         cache_dir: &str,
         forced_refresh_period: Option<Duration>,
+        fetch_timeout: Option<Duration>,
+        #[cfg(feature = "registry-parallel-fetch")] stats_store: Option<
+            Box<dyn RegistrySourceStore>,
+        >,
     ) -> Result<Self, RegistryError> {
         let url = url.to_string();
 
@@ -216,6 +383,13 @@ impl Internal {
             last_refresh_successful: false,
             forced_refresh_period,
             next_forced_refresh: None,
+            fetch_timeout,
+            #[cfg(feature = "registry-parallel-fetch")]
+            stats_store,
+            #[cfg(feature = "registry-sources")]
+            last_refresh: None,
+            #[cfg(feature = "registry-sources")]
+            last_refresh_error: None,
         };
 
         // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
@@ -232,14 +406,26 @@ impl Internal {
 
     /// Attempt to refresh the internal cache and update state accordingly.
     fn refresh_cache(&mut self) -> Result<(), RegistryError> {
-        fetch_nodes_from_remote(&self.url)
+        #[cfg(feature = "registry-parallel-fetch")]
+        let (attempted_at, started) = (SystemTime::now(), Instant::now());
+
+        let result = fetch_nodes_from_remote(&self.url, self.fetch_timeout)
             .and_then(|nodes| self.cache.write_nodes(nodes))
             .map_err(|err| {
                 self.last_refresh_successful = false;
+                #[cfg(feature = "registry-sources")]
+                {
+                    self.last_refresh_error = Some(err.to_string());
+                }
                 err
             })
             .and_then(|_| {
                 self.last_refresh_successful = true;
+                #[cfg(feature = "registry-sources")]
+                {
+                    self.last_refresh = Some(SystemTime::now());
+                    self.last_refresh_error = None;
+                }
                 // If a forced refresh period was configured, set the next time a forced refresh
                 // will be required
                 self.next_forced_refresh = self
@@ -255,7 +441,25 @@ impl Internal {
                     })
                     .transpose()?;
                 Ok(())
-            })
+            });
+
+        #[cfg(feature = "registry-parallel-fetch")]
+        if let Some(stats_store) = &self.stats_store {
+            let error = result.as_ref().err().map(|err| err.to_string());
+            if let Err(err) = stats_store.record_refresh(
+                &self.url,
+                attempted_at,
+                started.elapsed(),
+                error.as_deref(),
+            ) {
+                warn!(
+                    "Failed to record refresh of remote registry '{}': {}",
+                    self.url, err
+                );
+            }
+        }
+
+        result
     }
 
     /// Attempt to refresh the internal cache if necessary and return the cache's contents.
@@ -312,9 +516,19 @@ fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, Registry
         .to_string())
 }
 
-/// Fetch, parse, and validate the YAML registry file at the given URL.
-fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
-    let bytes = reqwest::blocking::get(url)
+/// Fetch, parse, and validate the YAML registry file at the given URL, failing if it takes longer
+/// than the given timeout.
+fn fetch_nodes_from_remote(
+    url: &str,
+    timeout: Option<Duration>,
+) -> Result<Vec<Node>, RegistryError> {
+    let mut client_builder = reqwest::blocking::Client::builder();
+    if let Some(timeout) = timeout {
+        client_builder = client_builder.timeout(timeout);
+    }
+    let bytes = client_builder
+        .build()
+        .and_then(|client| client.get(url).send())
         .and_then(|response| response.error_for_status())
         .map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
@@ -403,6 +617,107 @@ fn automatic_refresh_loop(
     }
 }
 
+/// Handle for forcing an immediate refresh of a `RemoteYamlRegistry`'s cache.
+///
+/// Refreshing through this handle bypasses the automatic and forced refresh periods; the remote
+/// file is fetched and cached right away. The handle may be cloned and shared between threads.
+#[cfg(feature = "registry-remote-refresh")]
+#[derive(Clone)]
+pub struct RemoteYamlRefreshHandle {
+    url: String,
+    internal: Arc<Mutex<Internal>>,
+}
+
+#[cfg(feature = "registry-remote-refresh")]
+impl RemoteYamlRefreshHandle {
+    /// Get the URL of the remote registry this handle refreshes.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Fetch the remote file and update the registry's cache.
+    ///
+    /// Returns the number of nodes in the refreshed cache. If the refresh fails, the previously
+    /// cached nodes continue to be used and the error is returned.
+    pub fn refresh(&self) -> Result<usize, RegistryError> {
+        let mut internal = self.internal.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Internal lock poisoned".into(),
+            ))
+        })?;
+
+        internal.refresh_cache()?;
+        internal.cache.get_nodes().map(|nodes| nodes.len())
+    }
+
+    /// Refresh each of the given registries concurrently, so that one slow remote file does not
+    /// delay the others. The results are returned in the same order as the handles.
+    ///
+    /// # Arguments
+    ///
+    /// * `handles` - the refresh handles of the registries to refresh
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn refresh_all(handles: &[RemoteYamlRefreshHandle]) -> Vec<Result<usize, RegistryError>> {
+        let join_handles = handles
+            .iter()
+            .map(|handle| {
+                let thread_handle = handle.clone();
+                thread::Builder::new()
+                    .name(format!("Remote Registry Refresh: {}", handle.url))
+                    .spawn(move || thread_handle.refresh())
+                    .map_err(|err| {
+                        RegistryError::InternalError(InternalError::from_source_with_message(
+                            Box::new(err),
+                            format!(
+                                "Failed to spawn refresh thread for remote registry '{}'",
+                                handle.url
+                            ),
+                        ))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        join_handles
+            .into_iter()
+            .zip(handles)
+            .map(|(join_handle, handle)| {
+                join_handle?.join().unwrap_or_else(|_| {
+                    Err(RegistryError::InternalError(InternalError::with_message(
+                        format!(
+                            "Refresh thread for remote registry '{}' panicked",
+                            handle.url
+                        ),
+                    )))
+                })
+            })
+            .collect()
+    }
+
+    /// Get the current status of the remote registry, without attempting to refresh it.
+    ///
+    /// # Arguments
+    ///
+    /// * `dynamic` - whether the registry was added at runtime, rather than configured on startup
+    #[cfg(feature = "registry-sources")]
+    pub fn status(&self, dynamic: bool) -> Result<RegistrySourceStatus, RegistryError> {
+        let internal = self.internal.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Internal lock poisoned".into(),
+            ))
+        })?;
+
+        Ok(RegistrySourceStatus {
+            url: internal.url.clone(),
+            dynamic,
+            last_refresh: internal.last_refresh,
+            node_count: internal.cache.get_nodes()?.len(),
+            error: internal.last_refresh_error.clone(),
+            #[cfg(feature = "registry-parallel-fetch")]
+            refresh_stats: None,
+        })
+    }
+}
+
 /// Handle for signaling the `RemoteYamlRegistry` to shutdown.
 pub struct RemoteYamlShutdownHandle {
     running: Option<Arc<AtomicBool>>,
@@ -1069,6 +1384,149 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that a refresh handle immediately fetches the remote file, even when automatic and
+    /// forced refreshes are disabled, and that a failed refresh leaves the cache untouched.
+    #[cfg(feature = "registry-remote-refresh")]
+    #[test]
+    fn refresh_handle() {
+        let test_config = TestConfig::setup("refresh_handle", Some(mock_registry()));
+
+        let mut remote_registry =
+            RemoteYamlRegistry::new(test_config.url(), test_config.path(), None, None)
+                .expect("Failed to create registry");
+
+        let refresh_handle = remote_registry
+            .refresh_handle()
+            .expect("Unable to get refresh handle");
+        assert_eq!(refresh_handle.url(), test_config.url());
+
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        test_config.update_registry(Some(vec![]));
+
+        // The cache is not refreshed on read, but is when explicitly requested
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+        assert_eq!(refresh_handle.refresh().expect("Failed to refresh"), 0);
+        verify_internal_cache(&test_config, &remote_registry, vec![]);
+
+        test_config.update_registry(Some(mock_registry()));
+        assert_eq!(
+            refresh_handle.refresh().expect("Failed to refresh"),
+            mock_registry().len()
+        );
+
+        // If the remote file is unavailable, the refresh fails and the cache is kept
+        test_config.update_registry(None);
+        assert!(refresh_handle.refresh().is_err());
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        let mut shutdown_handle = remote_registry
+            .take_shutdown_handle()
+            .expect("Unable to get shutdown handle");
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown remote registry");
+        test_config.shutdown();
+    }
+
+    /// Verifies that registries constructed and refreshed concurrently report their results in
+    /// order, and that an unreachable registry does not affect a reachable one.
+    #[cfg(feature = "registry-parallel-fetch")]
+    #[test]
+    fn new_concurrently_and_refresh_all() {
+        let test_config = TestConfig::setup("new_concurrently", Some(mock_registry()));
+        // Nothing listens on port 1, so fetching from this URL fails immediately
+        let urls = vec![
+            test_config.url().to_string(),
+            "http://127.0.0.1:1/registry.yaml".to_string(),
+        ];
+
+        let fetch_options = RemoteYamlFetchOptions::default().with_timeout(Duration::from_secs(5));
+        let mut registries = RemoteYamlRegistry::new_concurrently(
+            &urls,
+            test_config.path(),
+            None,
+            None,
+            fetch_options,
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to create registries");
+        assert_eq!(registries.len(), 2);
+        verify_internal_cache(&test_config, &registries[0], mock_registry());
+
+        let refresh_handles = registries
+            .iter()
+            .map(|registry| registry.refresh_handle())
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Unable to get refresh handles");
+        let results = RemoteYamlRefreshHandle::refresh_all(&refresh_handles);
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            *results[0].as_ref().expect("Failed to refresh"),
+            mock_registry().len()
+        );
+        assert!(results[1].is_err());
+
+        for registry in registries.iter_mut() {
+            let mut shutdown_handle = registry
+                .take_shutdown_handle()
+                .expect("Unable to get shutdown handle");
+            shutdown_handle.signal_shutdown();
+            shutdown_handle
+                .wait_for_shutdown()
+                .expect("Unable to shutdown remote registry");
+        }
+        test_config.shutdown();
+    }
+
+    /// Verifies that the status reported by a refresh handle tracks the time of the last successful
+    /// refresh, the number of cached nodes, and the error of the last failed refresh.
+    #[cfg(feature = "registry-sources")]
+    #[test]
+    fn refresh_handle_status() {
+        let test_config = TestConfig::setup("refresh_handle_status", Some(mock_registry()));
+
+        let mut remote_registry =
+            RemoteYamlRegistry::new(test_config.url(), test_config.path(), None, None)
+                .expect("Failed to create registry");
+        let refresh_handle = remote_registry
+            .refresh_handle()
+            .expect("Unable to get refresh handle");
+
+        let status = refresh_handle.status(false).expect("Failed to get status");
+        assert_eq!(status.url(), test_config.url());
+        assert!(!status.is_dynamic());
+        assert_eq!(status.node_count(), mock_registry().len());
+        assert!(status.error().is_none());
+        let last_refresh = status.last_refresh().expect("Refresh time not set");
+
+        // A failed refresh records the error, but keeps the previous refresh time and nodes
+        test_config.update_registry(None);
+        assert!(refresh_handle.refresh().is_err());
+        let status = refresh_handle.status(false).expect("Failed to get status");
+        assert_eq!(status.node_count(), mock_registry().len());
+        assert!(status.error().is_some());
+        assert_eq!(status.last_refresh(), Some(last_refresh));
+
+        // A successful refresh clears the error
+        test_config.update_registry(Some(vec![]));
+        refresh_handle.refresh().expect("Failed to refresh");
+        let status = refresh_handle.status(false).expect("Failed to get status");
+        assert_eq!(status.node_count(), 0);
+        assert!(status.error().is_none());
+
+        let mut shutdown_handle = remote_registry
+            .take_shutdown_handle()
+            .expect("Unable to get shutdown handle");
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown remote registry");
+        test_config.shutdown();
+    }
+
     /// Verifies that any changes made to the remote file are fetched on restart if the remote file
     /// is available.
     #[test]
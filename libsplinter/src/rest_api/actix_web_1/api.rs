@@ -25,8 +25,18 @@ use crate::rest_api::auth::authorization::{
 use crate::rest_api::auth::{actix::Authorization, identity::IdentityProvider};
 #[cfg(feature = "rest-api-cors")]
 use crate::rest_api::cors::Cors;
+#[cfg(feature = "rest-api-read-only")]
+use crate::rest_api::read_only::ReadOnly;
+#[cfg(feature = "tracing")]
+use crate::rest_api::tracing::RequestTracing;
+#[cfg(feature = "rest-api-multi-bind")]
+use crate::rest_api::BindAuth;
 use crate::rest_api::{BindConfig, RestApiServerError};
+#[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+use crate::transport::tls::TlsPolicy;
 
+#[cfg(feature = "rest-api-openapi")]
+use super::openapi::openapi_resource;
 use super::Resource;
 #[cfg(feature = "authorization")]
 use super::RestResourceProvider;
@@ -48,15 +58,91 @@ impl RestApiShutdownHandle {
     }
 }
 
+/// A bind target that has been prepared for use by an `HttpServer`.
+enum BindInfo {
+    Http(String),
+    #[cfg(feature = "https-bind")]
+    Https(String, openssl::ssl::SslAcceptorBuilder),
+    #[cfg(feature = "rest-api-multi-bind")]
+    Unix(String),
+}
+
+impl BindInfo {
+    fn from_config(bind: BindConfig) -> Result<Self, RestApiServerError> {
+        match bind {
+            #[cfg(feature = "https-bind")]
+            BindConfig::Https {
+                bind,
+                cert_path,
+                key_path,
+            } => {
+                let mut acceptor =
+                    openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
+                acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
+                acceptor.set_certificate_chain_file(&cert_path)?;
+                acceptor.check_private_key()?;
+
+                Ok(BindInfo::Https(bind, acceptor))
+            }
+            BindConfig::Http(bind) => Ok(BindInfo::Http(bind)),
+            #[cfg(feature = "rest-api-multi-bind")]
+            BindConfig::Unix(path) => Ok(BindInfo::Unix(path)),
+        }
+    }
+
+    /// Restricts an HTTPS bind to the protocol versions and cipher suites allowed by the policy;
+    /// other binds are left unchanged.
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    fn apply_tls_policy(&mut self, policy: &TlsPolicy) -> Result<(), RestApiServerError> {
+        if let BindInfo::Https(_, acceptor) = self {
+            policy.apply(acceptor)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for BindInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BindInfo::Http(bind) => write!(f, "{}", bind),
+            #[cfg(feature = "https-bind")]
+            BindInfo::Https(bind, _) => write!(f, "{}", bind),
+            #[cfg(feature = "rest-api-multi-bind")]
+            BindInfo::Unix(path) => write!(f, "unix://{}", path),
+        }
+    }
+}
+
+/// Removes a socket file left behind by a previous run, so that the path may be bound again.
+///
+/// Files at the path that are not sockets are left in place, in which case binding will fail.
+#[cfg(feature = "rest-api-multi-bind")]
+fn remove_stale_socket(path: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
 /// `RestApi` is used to create an instance of a restful web server.
 pub struct RestApi {
     pub(super) resources: Vec<Resource>,
     pub(super) bind: BindConfig,
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub(super) additional_binds: Vec<(BindConfig, BindAuth)>,
     #[cfg(feature = "rest-api-cors")]
     pub(super) allow_list: Option<Vec<String>>,
     pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-read-only")]
+    pub(super) read_only: bool,
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    pub(super) tls_policy: TlsPolicy,
 }
 
 impl RestApi {
@@ -77,10 +163,25 @@ impl RestApi {
     ) -> Result<(RestApiShutdownHandle, thread::JoinHandle<()>), RestApiServerError> {
         let (tx, rx) = mpsc::channel();
 
-        let bind_config_for_err = self.bind.clone();
-        let resources = self.resources;
+        #[allow(unused_mut)]
+        let mut resources = self.resources;
+        #[cfg(feature = "rest-api-openapi")]
+        {
+            #[cfg(feature = "authorization")]
+            let documented = resources
+                .iter()
+                .cloned()
+                .chain(AuthorizationResourceProvider::new(vec![]).resources())
+                .collect::<Vec<_>>();
+            #[cfg(not(feature = "authorization"))]
+            let documented = resources.clone();
+
+            resources.push(openapi_resource(&documented));
+        }
         #[cfg(feature = "rest-api-cors")]
         let allow_list = self.allow_list;
+        #[cfg(feature = "rest-api-read-only")]
+        let read_only = self.read_only;
         let authorization = Authorization::new(
             self.identity_providers.to_owned(),
             #[cfg(feature = "authorization")]
@@ -93,108 +194,149 @@ impl RestApi {
             None => Cors::new_allow_any(),
         };
 
-        #[cfg(feature = "https-bind")]
-        let bind_info = match self.bind {
-            BindConfig::Https {
-                bind,
-                cert_path,
-                key_path,
-            } => {
-                let mut acceptor =
-                    openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
-                acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
-                acceptor.set_certificate_chain_file(&cert_path)?;
-                acceptor.check_private_key()?;
-
-                (bind, Some(acceptor))
-            }
-            BindConfig::Http(bind) => (bind, None),
-        };
+        // The primary bind always requires authorization; each additional bind carries its own
+        // setting.
+        #[allow(unused_mut)]
+        let mut binds = vec![(self.bind, true)];
+        #[cfg(feature = "rest-api-multi-bind")]
+        binds.extend(
+            self.additional_binds
+                .into_iter()
+                .map(|(bind, auth)| (bind, auth == BindAuth::Required)),
+        );
 
-        #[cfg(not(feature = "https-bind"))]
-        let BindConfig::Http(bind_info) = self.bind;
+        #[allow(unused_mut)]
+        let mut bind_infos = binds
+            .into_iter()
+            .map(|(bind, auth_required)| Ok((BindInfo::from_config(bind)?, auth_required)))
+            .collect::<Result<Vec<_>, RestApiServerError>>()?;
+        #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+        for (bind_info, _) in bind_infos.iter_mut() {
+            bind_info.apply_tls_policy(&self.tls_policy)?;
+        }
 
         let join_handle = thread::Builder::new()
             .name("SplinterDRestApi".into())
             .spawn(move || {
                 let sys = actix::System::new("SplinterD-Rest-API");
-                let server = HttpServer::new(move || {
-                    let app = App::new();
 
+                let mut servers = Vec::with_capacity(bind_infos.len());
+                for (bind_info, auth_required) in bind_infos {
+                    let resources = resources.clone();
                     #[cfg(feature = "rest-api-cors")]
-                    let app = app.wrap(cors.clone());
+                    let cors = cors.clone();
+                    let authorization = authorization.clone();
 
-                    let mut app = app
-                        .wrap(authorization.clone())
-                        .wrap(middleware::Logger::default());
+                    let server = HttpServer::new(move || {
+                        let app = App::new();
 
-                    #[cfg(feature = "authorization")]
-                    let mut permission_map = PermissionMap::new();
+                        // Mutating requests are rejected after they have been authorized, so
+                        // that clients without access still receive an authorization error.
+                        #[cfg(feature = "rest-api-read-only")]
+                        let app = app.wrap(middleware::Condition::new(read_only, ReadOnly::new()));
+
+                        #[cfg(feature = "rest-api-cors")]
+                        let app = app.wrap(cors.clone());
+
+                        #[cfg(feature = "tracing")]
+                        let app = app.wrap(RequestTracing::new());
+
+                        let mut app = app
+                            .wrap(middleware::Condition::new(
+                                auth_required,
+                                authorization.clone(),
+                            ))
+                            .wrap(middleware::Logger::default());
 
-                    for resource in resources.clone() {
                         #[cfg(feature = "authorization")]
-                        {
-                            let (route, mut permissions) = resource.into_route();
-                            permission_map.append(&mut permissions);
-                            app = app.service(route);
+                        let mut permission_map = PermissionMap::new();
+
+                        for resource in resources.clone() {
+                            #[cfg(feature = "authorization")]
+                            {
+                                let (route, mut permissions) = resource.into_route();
+                                permission_map.append(&mut permissions);
+                                app = app.service(route);
+                            }
+                            #[cfg(not(feature = "authorization"))]
+                            {
+                                app = app.service(resource.into_route());
+                            }
                         }
-                        #[cfg(not(feature = "authorization"))]
-                        {
-                            app = app.service(resource.into_route());
-                        }
-                    }
 
-                    #[cfg(feature = "authorization")]
-                    {
-                        // Add authorization's own endpoints
-                        for resource in AuthorizationResourceProvider::new(
-                            permission_map.permissions().collect(),
-                        )
-                        .resources()
+                        #[cfg(feature = "authorization")]
                         {
-                            let (route, mut permissions) = resource.into_route();
-                            permission_map.append(&mut permissions);
-                            app = app.service(route);
+                            // Add authorization's own endpoints
+                            let routes = permission_map
+                                .routes()
+                                .map(|(method, endpoint, perm)| {
+                                    (format!("{} {}", method, endpoint), perm)
+                                })
+                                .collect();
+                            for resource in AuthorizationResourceProvider::new(
+                                permission_map.permissions().collect(),
+                            )
+                            .with_routes(routes)
+                            .resources()
+                            {
+                                let (route, mut permissions) = resource.into_route();
+                                permission_map.append(&mut permissions);
+                                app = app.service(route);
+                            }
+
+                            // Add the permission map to actix data
+                            app = app.data(permission_map);
                         }
 
-                        // Add the permission map to actix data
-                        app = app.data(permission_map);
-                    }
-
-                    app
-                });
-
-                #[cfg(feature = "https-bind")]
-                let (bind_url, opt_acceptor) = bind_info;
-                #[cfg(not(feature = "https-bind"))]
-                let bind_url = bind_info;
-
-                #[cfg(feature = "https-bind")]
-                let server = if let Some(acceptor) = opt_acceptor {
-                    server.bind_ssl(&bind_url, acceptor)
-                } else {
-                    server.bind(&bind_url)
-                };
-
-                #[cfg(not(feature = "https-bind"))]
-                let server = server.bind(&bind_url);
-
-                let server = match server {
-                    Ok(server) => server,
-                    Err(err) => {
-                        let error_msg = format!("Invalid REST API bind {}: {}", bind_url, err);
-                        error!("{}", error_msg);
-                        if let Err(err) = tx.send(Err(error_msg)) {
-                            error!("Failed to notify receiver of bind error: {}", err);
+                        app
+                    });
+
+                    let bind_name = bind_info.to_string();
+                    let server = match bind_info {
+                        BindInfo::Http(bind_url) => server.bind(&bind_url),
+                        #[cfg(feature = "https-bind")]
+                        BindInfo::Https(bind_url, acceptor) => server.bind_ssl(&bind_url, acceptor),
+                        #[cfg(feature = "rest-api-multi-bind")]
+                        BindInfo::Unix(path) => {
+                            remove_stale_socket(&path).and_then(|_| server.bind_uds(&path))
+                        }
+                    };
+
+                    match server {
+                        Ok(server) => servers.push(server),
+                        Err(err) => {
+                            let error_msg = format!("Invalid REST API bind {}: {}", bind_name, err);
+                            error!("{}", error_msg);
+                            if let Err(err) = tx.send(Err((bind_name, error_msg))) {
+                                error!("Failed to notify receiver of bind error: {}", err);
+                            }
+                            return;
                         }
-                        return;
                     }
-                };
-                let port_numbers = server.addrs().iter().map(|addrs| addrs.port()).collect();
+                }
 
-                let addr = server.disable_signals().system_exit().start();
+                let port_numbers = servers
+                    .iter()
+                    .flat_map(|server| server.addrs())
+                    .map(|addr| addr.port())
+                    .collect();
+
+                // Only the primary server stops the actix system when it is shut down; it is
+                // stopped last, after any additional servers.
+                let addrs = servers
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, server)| {
+                        let server = server.disable_signals();
+                        if i == 0 {
+                            server.system_exit().start()
+                        } else {
+                            server.start()
+                        }
+                    })
+                    .collect::<Vec<_>>();
 
-                if let Err(err) = tx.send(Ok((addr, port_numbers))) {
+                if let Err(err) = tx.send(Ok((addrs, port_numbers))) {
                     error!("Unable to send Server Addr: {}", err);
                 }
 
@@ -205,22 +347,24 @@ impl RestApi {
                 info!("Rest API terminating");
             })?;
 
-        let (addr, port_numbers) = rx
+        let (addrs, port_numbers) = rx
             .recv()
             .map_err(|err| {
                 RestApiServerError::StartUpError(format!("Unable to receive Server Addr: {}", err))
             })?
-            .map_err(|err| {
+            .map_err(|(bind_name, err)| {
                 RestApiServerError::BindError(format!(
                     "Failed to bind to URL {}: {}",
-                    bind_config_for_err, err
+                    bind_name, err
                 ))
             })?;
 
         let do_shutdown = Box::new(move || {
             debug!("Shutting down Rest API");
-            if let Err(err) = addr.stop(true).wait() {
-                error!("An error occurred while shutting down rest API: {:?}", err);
+            for addr in addrs.iter().rev() {
+                if let Err(err) = addr.stop(true).wait() {
+                    error!("An error occurred while shutting down rest API: {:?}", err);
+                }
             }
             debug!("Graceful signal sent to Rest API");
 
@@ -243,19 +387,24 @@ impl RestApi {
     ) -> Result<(RestApiShutdownHandle, thread::JoinHandle<()>), RestApiServerError> {
         let (tx, rx) = mpsc::channel();
 
-        #[cfg(feature = "https-bind")]
         let bind_url = match self.bind.clone() {
+            #[cfg(feature = "https-bind")]
             BindConfig::Https { bind, .. } => bind,
-
             BindConfig::Http(bind) => bind,
+            #[cfg(feature = "rest-api-multi-bind")]
+            BindConfig::Unix(_) => {
+                return Err(RestApiServerError::BindError(format!(
+                    "Unix socket binds are not supported by run_insecure: {}",
+                    self.bind
+                )))
+            }
         };
 
-        #[cfg(not(feature = "https-bind"))]
-        let BindConfig::Http(bind_url) = self.bind.clone();
-
         let resources = self.resources.to_owned();
         #[cfg(feature = "rest-api-cors")]
         let allow_list = self.allow_list.to_owned();
+        #[cfg(feature = "rest-api-read-only")]
+        let read_only = self.read_only;
 
         #[cfg(feature = "rest-api-cors")]
         let cors = match &allow_list {
@@ -270,9 +419,15 @@ impl RestApi {
                 let mut server = HttpServer::new(move || {
                     let app = App::new();
 
+                    #[cfg(feature = "rest-api-read-only")]
+                    let app = app.wrap(middleware::Condition::new(read_only, ReadOnly::new()));
+
                     #[cfg(feature = "rest-api-cors")]
                     let app = app.wrap(cors.clone());
 
+                    #[cfg(feature = "tracing")]
+                    let app = app.wrap(RequestTracing::new());
+
                     let mut app = app.wrap(middleware::Logger::default());
 
                     for resource in resources.clone() {
@@ -24,11 +24,15 @@ use crate::oauth::{GithubOAuthClientBuilder, OpenIdOAuthClientBuilder};
 use crate::rest_api::auth::authorization::AuthorizationHandler;
 #[cfg(feature = "cylinder-jwt")]
 use crate::rest_api::auth::identity::cylinder::CylinderKeyIdentityProvider;
+#[cfg(feature = "rest-api-multi-bind")]
+use crate::rest_api::BindAuth;
 #[cfg(feature = "oauth")]
 use crate::rest_api::{
     auth::identity::oauth::OAuthUserIdentityProvider, OAuthConfig, OAuthResourceProvider,
 };
 use crate::rest_api::{auth::identity::IdentityProvider, BindConfig, RestApiServerError};
+#[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+use crate::transport::tls::TlsPolicy;
 
 use super::AuthConfig;
 #[cfg(any(feature = "biome-credentials", feature = "oauth"))]
@@ -40,11 +44,17 @@ use super::{Resource, RestApi};
 pub struct RestApiBuilder {
     resources: Vec<Resource>,
     bind: Option<BindConfig>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    additional_binds: Vec<(BindConfig, BindAuth)>,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
     auth_configs: Vec<AuthConfig>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-read-only")]
+    read_only: bool,
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    tls_policy: TlsPolicy,
 }
 
 impl RestApiBuilder {
@@ -64,6 +74,17 @@ impl RestApiBuilder {
         self
     }
 
+    /// Adds an additional binding for the REST API.
+    ///
+    /// The REST API will serve the same resources on the additional binding as on the primary
+    /// binding set with `with_bind`. Whether requests received on the additional binding must be
+    /// authorized is determined by `auth`; the primary binding always requires authorization.
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn add_bind(mut self, bind: BindConfig, auth: BindAuth) -> Self {
+        self.additional_binds.push((bind, auth));
+        self
+    }
+
     pub fn add_resource(mut self, value: Resource) -> Self {
         self.resources.push(value);
         self
@@ -110,6 +131,26 @@ impl RestApiBuilder {
         self
     }
 
+    /// Sets whether the REST API is read-only.
+    ///
+    /// A read-only REST API serves `GET`, `HEAD` and `OPTIONS` requests, and rejects all other
+    /// requests with a `403 Forbidden` response before they are dispatched to a resource.
+    /// Defaults to `false`.
+    #[cfg(feature = "rest-api-read-only")]
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the TLS protocol versions and cipher suites allowed on HTTPS binds.
+    ///
+    /// Defaults to the OpenSSL settings for a modern TLS configuration.
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    pub fn with_tls_policy(mut self, tls_policy: TlsPolicy) -> Self {
+        self.tls_policy = tls_policy;
+        self
+    }
+
     // Allowing unused_mut because self must be mutable if feature `auth` is enabled
     #[allow(unused_mut)]
     pub fn build(mut self) -> Result<RestApi, RestApiServerError> {
@@ -224,11 +265,15 @@ impl RestApiBuilder {
                             }
                         };
 
-                        identity_providers.push(Box::new(OAuthUserIdentityProvider::new(
+                        let oauth_identity_provider = OAuthUserIdentityProvider::new(
                             oauth_client.clone(),
                             oauth_user_session_store.clone(),
                             None,
-                        )));
+                        );
+                        #[cfg(feature = "oauth-profile-refresh")]
+                        let oauth_identity_provider = oauth_identity_provider
+                            .with_user_profile_store(user_profile_store.clone_box());
+                        identity_providers.push(Box::new(oauth_identity_provider));
                         self.resources.append(
                             &mut OAuthResourceProvider::new(
                                 oauth_client,
@@ -255,12 +300,18 @@ impl RestApiBuilder {
 
         Ok(RestApi {
             bind,
+            #[cfg(feature = "rest-api-multi-bind")]
+            additional_binds: self.additional_binds,
             resources: self.resources,
             #[cfg(feature = "rest-api-cors")]
             allow_list: self.allow_list,
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers,
+            #[cfg(feature = "rest-api-read-only")]
+            read_only: self.read_only,
+            #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+            tls_policy: self.tls_policy,
         })
     }
 }
@@ -313,6 +364,45 @@ mod test {
         ));
     }
 
+    /// Verifies that the `RestApiBuilder` passes additional bindings, along with their auth
+    /// settings, through to the built `RestApi`.
+    #[cfg(feature = "rest-api-multi-bind")]
+    #[test]
+    fn rest_api_builder_additional_binds() {
+        let mut builder = RestApiBuilder::new();
+
+        #[cfg(not(feature = "https-bind"))]
+        {
+            builder = builder.with_bind("test");
+        }
+        #[cfg(feature = "https-bind")]
+        {
+            builder = builder.with_bind(BindConfig::Http("test".into()));
+        }
+
+        let rest_api = builder
+            .add_bind(
+                BindConfig::Unix("/var/run/splinter/rest_api.sock".into()),
+                BindAuth::Disabled,
+            )
+            .add_bind(BindConfig::Http("other".into()), BindAuth::Required)
+            .with_auth_configs(vec![AuthConfig::Custom {
+                resources: vec![],
+                identity_provider: Box::new(MockIdentityProvider),
+            }])
+            .build()
+            .expect("Failed to build REST API");
+
+        assert_eq!(rest_api.additional_binds.len(), 2);
+        assert_eq!(
+            &rest_api.additional_binds[0].0.to_string(),
+            "unix:///var/run/splinter/rest_api.sock"
+        );
+        assert_eq!(rest_api.additional_binds[0].1, BindAuth::Disabled);
+        assert_eq!(&rest_api.additional_binds[1].0.to_string(), "other");
+        assert_eq!(rest_api.additional_binds[1].1, BindAuth::Required);
+    }
+
     #[derive(Clone)]
     struct MockIdentityProvider;
 
@@ -341,17 +431,23 @@ mod test {
             let bind = match bind {
                 #[cfg(feature = "https-bind")]
                 BindConfig::Https { bind, .. } => BindConfig::Http(bind),
-                insecure @ BindConfig::Http(_) => insecure,
+                insecure => insecure,
             };
 
             Ok(RestApi {
                 bind,
+                #[cfg(feature = "rest-api-multi-bind")]
+                additional_binds: vec![],
                 resources: self.resources,
                 #[cfg(feature = "rest-api-cors")]
                 allow_list: self.allow_list,
                 identity_providers: vec![],
                 #[cfg(feature = "authorization")]
                 authorization_handlers: vec![],
+                #[cfg(feature = "rest-api-read-only")]
+                read_only: self.read_only,
+                #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+                tls_policy: self.tls_policy,
             })
         }
     }
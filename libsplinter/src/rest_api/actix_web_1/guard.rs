@@ -16,6 +16,8 @@ use super::Method;
 use actix_web::{Error as ActixError, HttpRequest, HttpResponse};
 use futures::{Future, IntoFuture};
 
+use crate::rest_api::{ErrorCode, ErrorResponse};
+
 /// A continuation indicates whether or not a guard should allow a given request to continue, or to
 /// return a result.
 pub enum Continuation {
@@ -82,6 +84,21 @@ impl ProtocolVersionRangeGuard {
         self.method = Some(method);
         self
     }
+
+    fn protocol_mismatch(&self, message: &str, requested: u32) -> ErrorResponse {
+        ErrorResponse::new(ErrorCode::UnsupportedProtocolVersion, message)
+            .with_detail("requested_protocol", &requested.to_string())
+            .with_detail("splinter_protocol", &self.max.to_string())
+            .with_detail(
+                "libsplinter_version",
+                &format!(
+                    "{}.{}.{}",
+                    env!("CARGO_PKG_VERSION_MAJOR"),
+                    env!("CARGO_PKG_VERSION_MINOR"),
+                    env!("CARGO_PKG_VERSION_PATCH")
+                ),
+            )
+    }
 }
 
 impl RequestGuard for ProtocolVersionRangeGuard {
@@ -108,46 +125,29 @@ impl RequestGuard for ProtocolVersionRangeGuard {
             match parsed_header {
                 Err(msg) => Continuation::terminate(
                     HttpResponse::BadRequest()
-                        .json(json!({
-                            "message": msg,
-                        }))
+                        .json(ErrorResponse::bad_request(&msg))
                         .into_future(),
                 ),
                 Ok(version) if version < self.min => Continuation::terminate(
                     HttpResponse::BadRequest()
-                        .json(json!({
-                            "message": format!(
+                        .json(self.protocol_mismatch(
+                            &format!(
                                 "Client must support protocol version {} or greater.",
                                 self.min,
                             ),
-                            "requested_protocol": version,
-                            "splinter_protocol": self.max,
-                            "libsplinter_version": format!(
-                                "{}.{}.{}",
-                                env!("CARGO_PKG_VERSION_MAJOR"),
-                                env!("CARGO_PKG_VERSION_MINOR"),
-                                env!("CARGO_PKG_VERSION_PATCH")
-                            )
-                        }))
+                            version,
+                        ))
                         .into_future(),
                 ),
                 Ok(version) if version > self.max => Continuation::terminate(
                     HttpResponse::BadRequest()
-                        .json(json!({
-                            "message": format!(
+                        .json(self.protocol_mismatch(
+                            &format!(
                                 "Client requires a newer protocol than can be provided: {} > {}",
-                                version,
-                                self.max,
+                                version, self.max,
                             ),
-                            "requested_protocol": version,
-                            "splinter_protocol": self.max,
-                            "libsplinter_version": format!(
-                                "{}.{}.{}",
-                                env!("CARGO_PKG_VERSION_MAJOR"),
-                                env!("CARGO_PKG_VERSION_MINOR"),
-                                env!("CARGO_PKG_VERSION_PATCH")
-                            )
-                        }))
+                            version,
+                        ))
                         .into_future(),
                 ),
                 Ok(_) => Continuation::Continue,
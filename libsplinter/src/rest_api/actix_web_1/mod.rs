@@ -17,6 +17,8 @@ mod auth;
 mod builder;
 mod error;
 mod guard;
+#[cfg(feature = "rest-api-openapi")]
+mod openapi;
 mod resource;
 mod websocket;
 
@@ -0,0 +1,274 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of an OpenAPI document describing the REST API.
+//!
+//! The document is generated from the resources that are registered with the REST API when it is
+//! started, so it describes exactly the endpoints that are compiled into and enabled in the
+//! running binary.
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use futures::IntoFuture;
+use serde_json::{json, Map, Value};
+
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::Permission;
+
+use super::{Method, Resource};
+
+/// The route the OpenAPI document is served at.
+pub(super) const OPENAPI_ROUTE: &str = "/openapi.json";
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// The authorization required to call an operation.
+#[cfg_attr(not(feature = "authorization"), allow(dead_code))]
+pub(super) enum OperationAuth {
+    /// The operation does not require the client to be authenticated.
+    Unauthenticated,
+    /// The operation requires the client to be authenticated, but no specific permission.
+    Authenticated,
+    /// The operation requires the client to have the permission with the given ID.
+    #[cfg(feature = "authorization")]
+    Permission(&'static str),
+}
+
+#[cfg(feature = "authorization")]
+impl From<&Permission> for OperationAuth {
+    fn from(permission: &Permission) -> Self {
+        match permission {
+            Permission::Check { permission_id, .. } => OperationAuth::Permission(*permission_id),
+            Permission::AllowAuthenticated => OperationAuth::Authenticated,
+            Permission::AllowUnauthenticated => OperationAuth::Unauthenticated,
+        }
+    }
+}
+
+/// Build the resource that serves the OpenAPI document for the given resources. The document also
+/// describes the OpenAPI resource itself.
+pub(super) fn openapi_resource(resources: &[Resource]) -> Resource {
+    let mut operations = resources
+        .iter()
+        .map(|resource| (resource.route().to_string(), resource.operations()))
+        .collect::<Vec<_>>();
+    operations.push((
+        OPENAPI_ROUTE.to_string(),
+        vec![(Method::Get, OperationAuth::Authenticated)],
+    ));
+
+    let document = Arc::new(generate_document(operations));
+
+    let resource = Resource::build(OPENAPI_ROUTE);
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, Permission::AllowAuthenticated, move |_, _| {
+            Box::new(HttpResponse::Ok().json(&*document).into_future())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| {
+            Box::new(HttpResponse::Ok().json(&*document).into_future())
+        })
+    }
+}
+
+/// Generate an OpenAPI document from the given routes and their operations.
+fn generate_document(operations: Vec<(String, Vec<(Method, OperationAuth)>)>) -> Value {
+    let mut paths = Map::new();
+
+    for (route, route_operations) in operations {
+        let (path, parameters) = parse_route(&route);
+
+        let path_item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        for (method, auth) in route_operations {
+            let mut operation = json!({
+                "operationId": format!("{} {}", method, route),
+                "responses": {
+                    "default": {
+                        "description": "Response",
+                    },
+                    "400": error_response("The request was invalid"),
+                    "401": error_response("The client is not authenticated"),
+                    "403": error_response("The client is not permitted to perform this operation"),
+                    "500": error_response("The server encountered an error"),
+                },
+            });
+
+            if !parameters.is_empty() {
+                operation["parameters"] = Value::Array(
+                    parameters
+                        .iter()
+                        .map(|name| {
+                            json!({
+                                "name": name,
+                                "in": "path",
+                                "required": true,
+                                "schema": { "type": "string" },
+                            })
+                        })
+                        .collect(),
+                );
+            }
+
+            match auth {
+                OperationAuth::Unauthenticated => operation["security"] = json!([]),
+                OperationAuth::Authenticated => (),
+                #[cfg(feature = "authorization")]
+                OperationAuth::Permission(permission_id) => {
+                    operation["x-splinter-permission"] = json!(permission_id)
+                }
+            }
+
+            path_item[method.to_string().to_lowercase()] = operation;
+        }
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "Splinter REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "security": [{ "bearerAuth": [] }],
+        "components": {
+            "securitySchemes": {
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                },
+            },
+            "schemas": {
+                "ErrorResponse": {
+                    "type": "object",
+                    "required": ["code", "message"],
+                    "properties": {
+                        "code": { "type": "string" },
+                        "message": { "type": "string" },
+                        "details": {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" },
+                        },
+                    },
+                },
+            },
+        },
+    })
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": {
+                "schema": { "$ref": "#/components/schemas/ErrorResponse" },
+            },
+        },
+    })
+}
+
+/// Convert an actix route into an OpenAPI path, returning the path and the names of its path
+/// parameters. Custom regular expressions on route segments (`{name:regex}`) are removed.
+fn parse_route(route: &str) -> (String, Vec<String>) {
+    let mut path = String::with_capacity(route.len());
+    let mut parameters = vec![];
+
+    let mut rest = route;
+    while let Some(start) = rest.find('{') {
+        path.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = rest[start + 1..end]
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        path.push('{');
+        path.push_str(&name);
+        path.push('}');
+        parameters.push(name);
+        rest = &rest[end + 1..];
+    }
+    path.push_str(rest);
+
+    (path, parameters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that routes are converted to OpenAPI paths with their path parameters.
+    #[test]
+    fn route_parsing() {
+        assert_eq!(parse_route("/status"), ("/status".to_string(), vec![]));
+        assert_eq!(
+            parse_route("/admin/circuits/{circuit_id}"),
+            (
+                "/admin/circuits/{circuit_id}".to_string(),
+                vec!["circuit_id".to_string()]
+            )
+        );
+        assert_eq!(
+            parse_route("/scabbard/{circuit}/{service_id}/state/{address:.*}"),
+            (
+                "/scabbard/{circuit}/{service_id}/state/{address}".to_string(),
+                vec![
+                    "circuit".to_string(),
+                    "service_id".to_string(),
+                    "address".to_string()
+                ]
+            )
+        );
+    }
+
+    /// Verify that the generated document contains an operation for each method of each route,
+    /// with the route's path parameters and authorization requirements.
+    #[test]
+    fn document_generation() {
+        let document = generate_document(vec![
+            (
+                "/registry/nodes/{identity}".to_string(),
+                vec![
+                    (Method::Get, OperationAuth::Authenticated),
+                    (Method::Delete, OperationAuth::Unauthenticated),
+                ],
+            ),
+            (
+                "/status".to_string(),
+                vec![(Method::Get, OperationAuth::Unauthenticated)],
+            ),
+        ]);
+
+        assert_eq!(document["openapi"], OPENAPI_VERSION);
+
+        let node = &document["paths"]["/registry/nodes/{identity}"];
+        assert_eq!(node["get"]["parameters"][0]["name"], "identity");
+        assert!(node["get"].get("security").is_none());
+        assert_eq!(node["delete"]["security"], json!([]));
+
+        let status = &document["paths"]["/status"];
+        assert!(status["get"].get("parameters").is_none());
+        assert_eq!(status["get"]["security"], json!([]));
+    }
+}
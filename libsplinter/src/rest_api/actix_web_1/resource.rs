@@ -26,6 +26,8 @@ use std::cmp::PartialEq;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::{Permission, PermissionMap};
 
+#[cfg(feature = "rest-api-openapi")]
+use super::openapi::OperationAuth;
 use super::{Continuation, RequestGuard};
 
 /// Rest methods compatible with `RestApi`.
@@ -233,6 +235,31 @@ impl Resource {
         self
     }
 
+    /// The route of this resource.
+    #[cfg(feature = "rest-api-openapi")]
+    pub(super) fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// The methods of this resource, with the authorization each method requires.
+    #[cfg(feature = "rest-api-openapi")]
+    pub(super) fn operations(&self) -> Vec<(Method, OperationAuth)> {
+        #[cfg(feature = "authorization")]
+        {
+            self.methods
+                .iter()
+                .map(|method| (method.method, OperationAuth::from(&method.permission)))
+                .collect()
+        }
+        #[cfg(not(feature = "authorization"))]
+        {
+            self.methods
+                .iter()
+                .map(|(method, _)| (*method, OperationAuth::Authenticated))
+                .collect()
+        }
+    }
+
     #[cfg(feature = "authorization")]
     pub(super) fn into_route(self) -> (actix_web::Resource, PermissionMap<Method>) {
         let mut resource = web::resource(&self.route);
@@ -21,6 +21,8 @@ mod authorization_handler_result;
 pub mod maintenance;
 mod permission;
 mod permission_map;
+#[cfg(feature = "authorization-handler-provider")]
+mod provider;
 #[cfg(feature = "authorization-handler-rbac")]
 pub mod rbac;
 pub(in crate::rest_api) mod routes;
@@ -32,6 +34,8 @@ use super::identity::Identity;
 pub use authorization_handler_result::AuthorizationHandlerResult;
 pub use permission::Permission;
 pub use permission_map::PermissionMap;
+#[cfg(feature = "authorization-handler-provider")]
+pub use provider::AuthorizationHandlerProvider;
 
 #[cfg(test)]
 pub use permission_map::Method;
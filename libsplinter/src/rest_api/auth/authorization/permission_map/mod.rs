@@ -51,6 +51,14 @@ impl<M: PartialEq + Clone> PermissionMap<M> {
         self.internal.iter().map(|(_, perm)| *perm)
     }
 
+    /// Gets a list of all (method, endpoint) pairs with the permission that guards them. Endpoints
+    /// are returned as they were added, including any path variables.
+    pub fn routes(&self) -> impl Iterator<Item = (&M, &str, Permission)> + '_ {
+        self.internal
+            .iter()
+            .map(|(req, perm)| (req.method(), req.endpoint(), *perm))
+    }
+
     /// Takes the contents of another `PermissionMap` and merges them into itself. This consumes the
     /// contents of the other map.
     pub fn append(&mut self, other: &mut PermissionMap<M>) {
@@ -169,4 +177,34 @@ mod tests {
             None
         );
     }
+
+    /// Verifies that `PermissionMap::routes` returns each (method, endpoint) pair as it was added,
+    /// along with the permission that guards it
+    #[test]
+    fn permission_map_routes() {
+        let perm1 = Permission::Check {
+            permission_id: "perm1",
+            permission_display_name: "",
+            permission_description: "",
+        };
+
+        let mut map = PermissionMap::new();
+        map.add_permission(Actix1Method::Get, "/test/endpoint", perm1);
+        map.add_permission(Actix1Method::Put, "/test/endpoint/{variable}", perm1);
+        map.add_permission(Actix1Method::Get, "/open", Permission::AllowUnauthenticated);
+
+        let routes = map
+            .routes()
+            .map(|(method, endpoint, perm)| (format!("{} {}", method, endpoint), perm))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            routes,
+            vec![
+                ("GET /test/endpoint".to_string(), perm1),
+                ("PUT /test/endpoint/{variable}".to_string(), perm1),
+                ("GET /open".to_string(), Permission::AllowUnauthenticated),
+            ]
+        );
+    }
 }
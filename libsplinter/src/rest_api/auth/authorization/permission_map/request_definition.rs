@@ -19,6 +19,7 @@ use super::PathComponent;
 /// A (method, endpoint) definition that will be used to match requests
 pub struct RequestDefinition<M> {
     method: M,
+    endpoint: String,
     path: Vec<PathComponent>,
 }
 
@@ -32,7 +33,21 @@ impl<M: PartialEq + Clone> RequestDefinition<M> {
             .map(PathComponent::from)
             .collect();
 
-        Self { method, path }
+        Self {
+            method,
+            endpoint: endpoint.to_string(),
+            path,
+        }
+    }
+
+    /// Returns the method of this definition
+    pub fn method(&self) -> &M {
+        &self.method
+    }
+
+    /// Returns the endpoint of this definition, as it was provided
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
     }
 
     /// Checks if the given request matches this definition, considering any variable path
@@ -0,0 +1,38 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! An extension point for adding authorization handlers to a node's REST API at startup.
+
+use crate::error::InternalError;
+use crate::rest_api::actix_web_1::Resource;
+
+use super::AuthorizationHandler;
+
+/// Provides an authorization handler, along with any REST API resources for managing it.
+///
+/// Providers are registered with a node before its REST API is started. The node's configuration
+/// refers to each provider by name, both to enable its handler and to set the order in which the
+/// handlers are consulted.
+pub trait AuthorizationHandlerProvider: Send {
+    /// Returns the name that identifies the handler in the node's configuration.
+    fn name(&self) -> &str;
+
+    /// Creates the authorization handler.
+    fn authorization_handler(&self) -> Result<Box<dyn AuthorizationHandler>, InternalError>;
+
+    /// Returns the REST API resources for managing the authorization handler. By default, the
+    /// handler has no resources.
+    fn resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+}
@@ -28,8 +28,21 @@ use crate::rest_api::{
 use super::{resources::PermissionResponse, AUTHORIZATION_PERMISSIONS_READ_PERMISSION};
 
 const AUTHORIZATION_PERMISSIONS_MIN: u32 = 1;
+const AUTHORIZATION_PERMISSIONS_ROUTE: &str = "GET /authorization/permissions";
+
+pub fn make_permissions_resource(
+    permissions: Vec<Permission>,
+    routes: Vec<(String, Permission)>,
+) -> Resource {
+    let routes = routes
+        .into_iter()
+        // Add this endpoint's own route
+        .chain(std::iter::once((
+            AUTHORIZATION_PERMISSIONS_ROUTE.to_string(),
+            AUTHORIZATION_PERMISSIONS_READ_PERMISSION,
+        )))
+        .collect::<Vec<_>>();
 
-pub fn make_permissions_resource(permissions: Vec<Permission>) -> Resource {
     let permissions = permissions
         .into_iter()
         // Add this endpoint's own permission
@@ -47,10 +60,19 @@ pub fn make_permissions_resource(permissions: Vec<Permission>) -> Resource {
                     .iter()
                     .any(|existing_perm| permission_id == existing_perm.permission_id)
                 {
+                    let mut guarded_routes = routes
+                        .iter()
+                        .filter(|(_, route_perm)| route_perm == &perm)
+                        .map(|(route, _)| route.clone())
+                        .collect::<Vec<_>>();
+                    guarded_routes.sort();
+                    guarded_routes.dedup();
+
                     perms.push(PermissionResponse {
                         permission_id,
                         permission_display_name,
                         permission_description,
+                        routes: guarded_routes,
                     });
                 }
             }
@@ -107,13 +129,21 @@ mod tests {
     #[test]
     fn get_permissions() {
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_permissions_resource(vec![
-                PERM1,
-                PERM1,
-                PERM2,
-                Permission::AllowAuthenticated,
-                Permission::AllowUnauthenticated,
-            ])]);
+            run_rest_api_on_open_port(vec![make_permissions_resource(
+                vec![
+                    PERM1,
+                    PERM1,
+                    PERM2,
+                    Permission::AllowAuthenticated,
+                    Permission::AllowUnauthenticated,
+                ],
+                vec![
+                    ("GET /one".to_string(), PERM1),
+                    ("PUT /one/{id}".to_string(), PERM1),
+                    ("GET /two".to_string(), PERM2),
+                    ("GET /open".to_string(), Permission::AllowUnauthenticated),
+                ],
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/permissions", bind_url))
             .expect("Failed to parse URL");
@@ -139,7 +169,8 @@ mod tests {
                     .iter()
                     .any(|perm| perm.permission_id == permission_id
                         && perm.permission_display_name == permission_display_name
-                        && perm.permission_description == permission_description));
+                        && perm.permission_description == permission_description
+                        && perm.routes == vec!["GET /one", "PUT /one/{id}"]));
             }
             _ => unreachable!(),
         }
@@ -153,7 +184,8 @@ mod tests {
                     .iter()
                     .any(|perm| perm.permission_id == permission_id
                         && perm.permission_display_name == permission_display_name
-                        && perm.permission_description == permission_description));
+                        && perm.permission_description == permission_description
+                        && perm.routes == vec!["GET /two"]));
             }
             _ => unreachable!(),
         }
@@ -167,7 +199,8 @@ mod tests {
                     .iter()
                     .any(|perm| perm.permission_id == permission_id
                         && perm.permission_display_name == permission_display_name
-                        && perm.permission_description == permission_description));
+                        && perm.permission_description == permission_description
+                        && perm.routes == vec![AUTHORIZATION_PERMISSIONS_ROUTE]));
             }
             _ => unreachable!(),
         }
@@ -188,6 +221,7 @@ mod tests {
         permission_id: String,
         permission_display_name: String,
         permission_description: String,
+        routes: Vec<String>,
     }
 
     fn run_rest_api_on_open_port(
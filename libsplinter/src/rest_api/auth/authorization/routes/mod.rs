@@ -41,6 +41,8 @@ const AUTHORIZATION_PERMISSIONS_READ_PERMISSION: Permission = Permission::Check
 pub struct AuthorizationResourceProvider {
     #[cfg(feature = "rest-api-actix-web-1")]
     permissions: Vec<Permission>,
+    #[cfg(feature = "rest-api-actix-web-1")]
+    routes: Vec<(String, Permission)>,
 }
 
 impl AuthorizationResourceProvider {
@@ -49,8 +51,18 @@ impl AuthorizationResourceProvider {
         Self {
             #[cfg(feature = "rest-api-actix-web-1")]
             permissions,
+            #[cfg(feature = "rest-api-actix-web-1")]
+            routes: vec![],
         }
     }
+
+    /// Sets the routes guarded by each permission, which are listed with the permissions. Each
+    /// route should be formatted as `METHOD /path`.
+    #[cfg(feature = "rest-api-actix-web-1")]
+    pub fn with_routes(mut self, routes: Vec<(String, Permission)>) -> Self {
+        self.routes = routes;
+        self
+    }
 }
 
 /// `AuthorizationResourceProvider` provides the following endpoints as REST API resources:
@@ -69,7 +81,10 @@ impl RestResourceProvider for AuthorizationResourceProvider {
 
         #[cfg(feature = "rest-api-actix-web-1")]
         {
-            resources.push(actix::make_permissions_resource(self.permissions.clone()));
+            resources.push(actix::make_permissions_resource(
+                self.permissions.clone(),
+                self.routes.clone(),
+            ));
         }
 
         resources
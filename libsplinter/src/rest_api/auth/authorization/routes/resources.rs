@@ -19,4 +19,6 @@ pub struct PermissionResponse {
     pub permission_id: &'static str,
     pub permission_display_name: &'static str,
     pub permission_description: &'static str,
+    /// The routes guarded by the permission, formatted as `METHOD /path`
+    pub routes: Vec<String>,
 }
@@ -18,6 +18,8 @@ use std::sync::Arc;
 
 use jsonwebtoken::{decode, DecodingKey, Validation};
 
+#[cfg(feature = "biome-user-deactivation")]
+use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
 use crate::error::InternalError;
 use crate::rest_api::{
     auth::{AuthorizationHeader, BearerToken},
@@ -31,10 +33,14 @@ use super::{Identity, IdentityProvider};
 ///
 /// This provider only accepts `AuthorizationHeader::Bearer(BearerToken::Biome(token))`
 /// authorizations, and the inner token must be a valid Biome JWT.
+///
+/// If a credentials store is provided, tokens that belong to deactivated users are rejected.
 #[derive(Clone)]
 pub struct BiomeUserIdentityProvider {
     token_secret_manager: Arc<dyn SecretManager>,
     validation: Validation,
+    #[cfg(feature = "biome-user-deactivation")]
+    credentials_store: Option<Arc<dyn CredentialsStore>>,
 }
 
 impl BiomeUserIdentityProvider {
@@ -43,6 +49,31 @@ impl BiomeUserIdentityProvider {
         Self {
             token_secret_manager,
             validation,
+            #[cfg(feature = "biome-user-deactivation")]
+            credentials_store: None,
+        }
+    }
+
+    /// Sets the store used to check whether or not a token's user is still active
+    #[cfg(feature = "biome-user-deactivation")]
+    pub fn with_credentials_store(mut self, credentials_store: Arc<dyn CredentialsStore>) -> Self {
+        self.credentials_store = Some(credentials_store);
+        self
+    }
+
+    /// Returns whether or not the given user may be authenticated; users that have been
+    /// deactivated may not.
+    #[cfg(feature = "biome-user-deactivation")]
+    fn is_user_active(&self, user_id: &str) -> Result<bool, InternalError> {
+        let credentials_store = match &self.credentials_store {
+            Some(credentials_store) => credentials_store,
+            None => return Ok(true),
+        };
+
+        match credentials_store.fetch_credential_by_user_id(user_id) {
+            Ok(credentials) => Ok(credentials.active),
+            Err(CredentialsStoreError::NotFoundError(_)) => Ok(true),
+            Err(err) => Err(InternalError::from_source(Box::new(err))),
         }
     }
 }
@@ -62,13 +93,22 @@ impl IdentityProvider for BiomeUserIdentityProvider {
             .secret()
             .map_err(|err| InternalError::from_source(err.into()))?;
 
-        Ok(decode::<Claims>(
+        let user_id = match decode::<Claims>(
             token,
             &DecodingKey::from_secret(secret.as_ref()),
             &self.validation,
-        )
-        .map(|token_data| Identity::User(token_data.claims.user_id()))
-        .ok())
+        ) {
+            Ok(token_data) => token_data.claims.user_id(),
+            Err(_) => return Ok(None),
+        };
+
+        #[cfg(feature = "biome-user-deactivation")]
+        if !self.is_user_active(&user_id)? {
+            debug!("Rejecting token of deactivated user {}", user_id);
+            return Ok(None);
+        }
+
+        Ok(Some(Identity::User(user_id)))
     }
 
     fn clone_box(&self) -> Box<dyn IdentityProvider> {
@@ -17,7 +17,11 @@
 use std::time::Duration;
 
 use crate::biome::OAuthUserSessionStore;
+#[cfg(feature = "oauth-profile-refresh")]
+use crate::biome::UserProfileStore;
 use crate::error::InternalError;
+#[cfg(feature = "oauth-profile-refresh")]
+use crate::oauth::profile::save_profile;
 use crate::oauth::OAuthClient;
 use crate::rest_api::auth::{AuthorizationHeader, BearerToken};
 
@@ -41,6 +45,12 @@ const DEFAULT_REAUTHENTICATION_INTERVAL: Duration = Duration::from_secs(3600); /
 /// This identity provider will also use a session's refresh token (if it has one) to get a new
 /// OAuth access token for the session as needed.
 ///
+/// If a user profile store is provided, the user's profile details are fetched from the OAuth
+/// server and saved to the store each time the session is re-authenticated, so the profile stays
+/// up to date with the OAuth provider.
+///
+/// Sessions that belong to users who have been deactivated are not accepted.
+///
 /// This provider only accepts `AuthorizationHeader::Bearer(BearerToken::OAuth2(token))`
 /// authorizations, and the inner token must be a valid Splinter access token for an OAuth user.
 #[derive(Clone)]
@@ -48,6 +58,8 @@ pub struct OAuthUserIdentityProvider {
     oauth_client: OAuthClient,
     oauth_user_session_store: Box<dyn OAuthUserSessionStore>,
     reauthentication_interval: Duration,
+    #[cfg(feature = "oauth-profile-refresh")]
+    user_profile_store: Option<Box<dyn UserProfileStore>>,
 }
 
 impl OAuthUserIdentityProvider {
@@ -71,6 +83,40 @@ impl OAuthUserIdentityProvider {
             oauth_user_session_store,
             reauthentication_interval: reauthentication_interval
                 .unwrap_or(DEFAULT_REAUTHENTICATION_INTERVAL),
+            #[cfg(feature = "oauth-profile-refresh")]
+            user_profile_store: None,
+        }
+    }
+
+    /// Sets the store that users' profile details are saved to when their sessions are
+    /// re-authenticated.
+    #[cfg(feature = "oauth-profile-refresh")]
+    pub fn with_user_profile_store(
+        mut self,
+        user_profile_store: Box<dyn UserProfileStore>,
+    ) -> Self {
+        self.user_profile_store = Some(user_profile_store);
+        self
+    }
+
+    /// Fetches the profile details for the given access token and saves them as the profile of
+    /// the given user. Failures are logged, since they should not prevent the user from being
+    /// authenticated.
+    #[cfg(feature = "oauth-profile-refresh")]
+    fn refresh_profile(&self, user_id: &str, access_token: &str) {
+        let user_profile_store = match &self.user_profile_store {
+            Some(user_profile_store) => user_profile_store,
+            None => return,
+        };
+
+        match self.oauth_client.get_profile(access_token) {
+            Ok(Some(profile)) => {
+                if let Err(err) = save_profile(&**user_profile_store, user_id, &profile) {
+                    error!("Failed to save profile for user {}: {}", user_id, err);
+                }
+            }
+            Ok(None) => debug!("No profile details found for user {}", user_id),
+            Err(err) => error!("Failed to get profile for user {}: {}", user_id, err),
         }
     }
 }
@@ -94,6 +140,15 @@ impl IdentityProvider for OAuthUserIdentityProvider {
             None => return Ok(None),
         };
 
+        #[cfg(feature = "biome-user-deactivation")]
+        if !session.user().is_active() {
+            debug!(
+                "Rejecting session of deactivated user {}",
+                session.user().user_id()
+            );
+            return Ok(None);
+        }
+
         let user_id = session.user().user_id().to_string();
 
         let time_since_authenticated = session
@@ -103,6 +158,8 @@ impl IdentityProvider for OAuthUserIdentityProvider {
         if time_since_authenticated >= self.reauthentication_interval {
             match self.oauth_client.get_subject(session.oauth_access_token()) {
                 Ok(Some(_)) => {
+                    #[cfg(feature = "oauth-profile-refresh")]
+                    self.refresh_profile(&user_id, session.oauth_access_token());
                     let updated_session = session.into_update_builder().build();
                     self.oauth_user_session_store
                         .update_session(updated_session)
@@ -133,7 +190,11 @@ impl IdentityProvider for OAuthUserIdentityProvider {
                                     // get Ok(None) or Err(_)), something's wrong that can't be
                                     // handled here.
                                     match self.oauth_client.get_subject(&access_token)? {
-                                        Some(_) => Ok(Some(Identity::User(user_id))),
+                                        Some(_) => {
+                                            #[cfg(feature = "oauth-profile-refresh")]
+                                            self.refresh_profile(&user_id, &access_token);
+                                            Ok(Some(Identity::User(user_id)))
+                                        }
                                         None => Err(InternalError::with_message(
                                             "failed to authenticate user with new access token"
                                                 .into(),
@@ -191,6 +252,8 @@ mod tests {
 
     use crate::biome::oauth::store::InsertableOAuthUserSessionBuilder;
     use crate::biome::MemoryOAuthUserSessionStore;
+    #[cfg(feature = "oauth-profile-refresh")]
+    use crate::biome::{MemoryUserProfileStore, UserProfileStore};
     use crate::oauth::{
         store::MemoryInflightOAuthRequestStore, OAuthClientBuilder, SubjectProvider,
     };
@@ -323,6 +386,56 @@ mod tests {
         assert!(new_session.last_authenticated() > original_session.last_authenticated());
     }
 
+    /// Verifies that the `OAuthUserIdentityProvider` saves the user's profile to the user profile
+    /// store when a session is re-authenticated.
+    ///
+    /// 1. Create a new `OAuthUserSessionStore` and add a session to it
+    /// 2. Create a new `OAuthUserIdentityProvider` with the session store, an empty user profile
+    ///    store, an OAuth client that always successfully gets a subject and profile, and a
+    ///    re-authentication interval of 0 (the session will expire immediately).
+    /// 3. Call the `get_identity` method with the session's access token and verify that the
+    ///    user's profile has been added to the user profile store.
+    #[cfg(feature = "oauth-profile-refresh")]
+    #[test]
+    fn get_identity_reauthentication_refreshes_profile() {
+        let session_store = Box::new(MemoryOAuthUserSessionStore::new());
+        let profile_store = MemoryUserProfileStore::new();
+
+        let splinter_access_token = "splinter_access_token";
+        let session = InsertableOAuthUserSessionBuilder::new()
+            .with_splinter_access_token(splinter_access_token.into())
+            .with_subject("subject".into())
+            .with_oauth_access_token("oauth_access_token".into())
+            .build()
+            .expect("Failed to build session");
+        session_store
+            .add_session(session)
+            .expect("Failed to add session");
+
+        let identity_provider = OAuthUserIdentityProvider::new(
+            always_some_client(),
+            session_store.clone(),
+            Some(Duration::from_secs(0)),
+        )
+        .with_user_profile_store(Box::new(profile_store.clone()));
+
+        let authorization_header =
+            AuthorizationHeader::Bearer(BearerToken::OAuth2(splinter_access_token.into()));
+        let user_id = match identity_provider
+            .get_identity(&authorization_header)
+            .expect("Failed to get identity")
+            .expect("Identity not found")
+        {
+            Identity::User(user_id) => user_id,
+            identity => panic!("Unexpected identity: {:?}", identity),
+        };
+
+        let profile = profile_store
+            .get_profile(&user_id)
+            .expect("Failed to get profile");
+        assert_eq!(profile.subject(), "subject");
+    }
+
     /// Verifies that the `OAuthUserIdentityProvider` correctly handles the case where the internal
     /// subect provider returns `Ok(None)` when re-authenticating a session without a refresh token.
     ///
@@ -24,6 +24,9 @@ pub enum BindConfig {
     },
     /// A insecure HTTP binding.
     Http(String),
+    #[cfg(feature = "rest-api-multi-bind")]
+    /// A unix domain socket binding, given as the path to the socket file.
+    Unix(String),
 }
 
 impl std::fmt::Display for BindConfig {
@@ -32,6 +35,22 @@ impl std::fmt::Display for BindConfig {
             #[cfg(feature = "https-bind")]
             BindConfig::Https { bind, .. } => write!(f, "{}", bind),
             BindConfig::Http(bind) => write!(f, "{}", bind),
+            #[cfg(feature = "rest-api-multi-bind")]
+            BindConfig::Unix(path) => write!(f, "unix://{}", path),
         }
     }
 }
+
+/// The authentication requirements for a single REST API binding.
+#[cfg(feature = "rest-api-multi-bind")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindAuth {
+    /// Requests must be authorized using the REST API's configured identity providers and
+    /// authorization handlers.
+    Required,
+    /// Requests are not checked for authorization.
+    ///
+    /// This should only be used for bindings that are not reachable by untrusted clients, such as
+    /// a unix domain socket whose file permissions restrict access to local administrators.
+    Disabled,
+}
@@ -77,21 +77,27 @@ mod errors;
 #[cfg(feature = "oauth")]
 mod oauth_config;
 pub mod paging;
+#[cfg(feature = "rest-api-read-only")]
+pub mod read_only;
 mod response_models;
 pub mod secrets;
 pub mod sessions;
+#[cfg(all(feature = "tracing", feature = "rest-api-actix-web-1"))]
+pub mod tracing;
 
 use percent_encoding::{AsciiSet, CONTROLS};
 
 #[cfg(all(feature = "oauth", feature = "rest-api-actix-web-1"))]
 use crate::oauth::rest_api::OAuthResourceProvider;
 
+#[cfg(feature = "rest-api-multi-bind")]
+pub use bind_config::BindAuth;
 pub use bind_config::BindConfig;
 pub use errors::{RequestError, RestApiServerError};
 #[cfg(feature = "oauth")]
 pub use oauth_config::OAuthConfig;
 
-pub use response_models::ErrorResponse;
+pub use response_models::{ErrorCode, ErrorResponse};
 
 #[cfg(feature = "rest-api-actix-web-1")]
 pub use actix_web_1::{
@@ -106,9 +112,10 @@ pub use actix_web_1::{
     feature = "authorization",
     feature = "biome-credentials",
     feature = "biome-key-management",
+    feature = "build-info",
     all(feature = "oauth", feature = "rest-api-actix-web-1"),
 ))]
-pub(crate) const SPLINTER_PROTOCOL_VERSION: u32 = 2;
+pub(crate) const SPLINTER_PROTOCOL_VERSION: u32 = 3;
 
 const QUERY_ENCODE_SET: &AsciiSet = &CONTROLS
     .add(b' ')
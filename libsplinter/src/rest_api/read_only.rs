@@ -0,0 +1,143 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides a read-only mode for the REST API
+//!
+//! When a REST API is read-only, every request that could change the node's state is rejected
+//! with a `403 Forbidden` response before it reaches a handler. Only `GET`, `HEAD` and `OPTIONS`
+//! requests are dispatched. This allows a node, such as a reporting replica, to serve all of its
+//! queries without exposing proposals, votes, registry writes, or role changes.
+
+use actix_web::dev::*;
+use actix_web::{http::Method, Error as ActixError, HttpResponse};
+use futures::{
+    future::{ok, FutureResult},
+    Future, IntoFuture, Poll,
+};
+
+use crate::rest_api::ErrorResponse;
+
+/// Middleware that rejects any request which is not a `GET`, `HEAD` or `OPTIONS` request
+#[derive(Clone, Default)]
+pub struct ReadOnly;
+
+impl ReadOnly {
+    pub fn new() -> Self {
+        ReadOnly
+    }
+}
+
+impl<S, B> Transform<S> for ReadOnly
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = S::Error;
+    type InitError = ();
+    type Transform = ReadOnlyMiddleware<S>;
+    type Future = FutureResult<Self::Transform, Self::InitError>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ReadOnlyMiddleware { service })
+    }
+}
+
+#[doc(hidden)]
+pub struct ReadOnlyMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service for ReadOnlyMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = S::Error;
+    type Future = Box<dyn Future<Item = Self::Response, Error = Self::Error>>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        match *req.method() {
+            Method::GET | Method::HEAD | Method::OPTIONS => Box::new(self.service.call(req)),
+            _ => {
+                debug!(
+                    "Rejecting {} {}; the REST API is read-only",
+                    req.method(),
+                    req.path()
+                );
+                Box::new(
+                    req.into_response(
+                        HttpResponse::Forbidden()
+                            .json(ErrorResponse::forbidden("The REST API is read-only"))
+                            .into_body(),
+                    )
+                    .into_future(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::{http::StatusCode, test, web, App};
+
+    /// Verifies that the read-only middleware dispatches `GET` requests to their handlers.
+    #[test]
+    fn read_only_allows_get() {
+        let mut app = test::init_service(
+            App::new()
+                .wrap(ReadOnly::new())
+                .route("/", web::get().to(|| HttpResponse::Ok())),
+        );
+
+        let req = test::TestRequest::with_uri("/").to_request();
+        let resp = test::block_on(app.call(req)).unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    /// Verifies that the read-only middleware rejects mutating requests with a `403 Forbidden`
+    /// response, without dispatching them to their handlers.
+    #[test]
+    fn read_only_rejects_mutating_requests() {
+        let mut app = test::init_service(
+            App::new()
+                .wrap(ReadOnly::new())
+                .route("/", web::post().to(|| HttpResponse::Ok()))
+                .route("/", web::put().to(|| HttpResponse::Ok()))
+                .route("/", web::patch().to(|| HttpResponse::Ok()))
+                .route("/", web::delete().to(|| HttpResponse::Ok())),
+        );
+
+        for method in &[Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+            let req = test::TestRequest::with_uri("/")
+                .method(method.clone())
+                .to_request();
+            let resp = test::block_on(app.call(req)).unwrap();
+
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN, "{}", method);
+        }
+    }
+}
@@ -12,60 +12,168 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Machine-readable code identifying the kind of error in an [`ErrorResponse`]
+///
+/// Clients should branch on the code rather than the message, which is intended for humans and
+/// may change between releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    RequestTimeout,
+    Conflict,
+    Gone,
+    UnsupportedProtocolVersion,
+    InternalError,
+    /// A code sent by a newer server that this version does not recognize
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Returns the code as it appears in a serialized response
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::RequestTimeout => "REQUEST_TIMEOUT",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::Gone => "GONE",
+            ErrorCode::UnsupportedProtocolVersion => "UNSUPPORTED_PROTOCOL_VERSION",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Model for a error response to an REST request
+///
+/// Serialized as `{"code": ..., "message": ..., "details": {...}}`, where `details` is omitted if
+/// there are none.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
-    code: String,
+    code: ErrorCode,
     message: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    details: BTreeMap<String, String>,
 }
 
 impl ErrorResponse {
-    pub fn internal_error() -> ErrorResponse {
+    pub fn new(code: ErrorCode, message: &str) -> ErrorResponse {
         ErrorResponse {
-            code: "500".to_string(),
-            message: "The server encountered an error".to_string(),
+            code,
+            message: message.to_string(),
+            details: BTreeMap::new(),
         }
     }
 
+    /// Adds a machine-readable detail, such as the name of an invalid field, to the response
+    pub fn with_detail(mut self, key: &str, value: &str) -> ErrorResponse {
+        self.details.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn details(&self) -> &BTreeMap<String, String> {
+        &self.details
+    }
+
+    pub fn internal_error() -> ErrorResponse {
+        ErrorResponse::new(ErrorCode::InternalError, "The server encountered an error")
+    }
+
     pub fn bad_request(message: &str) -> ErrorResponse {
-        ErrorResponse {
-            code: "400".to_string(),
-            message: message.to_string(),
-        }
+        ErrorResponse::new(ErrorCode::BadRequest, message)
     }
 
     pub fn not_found(message: &str) -> ErrorResponse {
-        ErrorResponse {
-            code: "404".to_string(),
-            message: message.to_string(),
-        }
+        ErrorResponse::new(ErrorCode::NotFound, message)
     }
 
     pub fn unauthorized() -> ErrorResponse {
-        ErrorResponse {
-            code: "401".to_string(),
-            message: "Client is not authorized".to_string(),
-        }
+        ErrorResponse::new(ErrorCode::Unauthorized, "Client is not authorized")
     }
 
     pub fn forbidden(message: &str) -> ErrorResponse {
-        ErrorResponse {
-            code: "403".to_string(),
-            message: message.to_string(),
-        }
+        ErrorResponse::new(ErrorCode::Forbidden, message)
     }
 
     pub fn request_timeout(message: &str) -> ErrorResponse {
-        ErrorResponse {
-            code: "408".to_string(),
-            message: message.to_string(),
-        }
+        ErrorResponse::new(ErrorCode::RequestTimeout, message)
     }
 
     pub fn conflict(message: &str) -> ErrorResponse {
-        ErrorResponse {
-            code: "409".to_string(),
-            message: message.to_string(),
-        }
+        ErrorResponse::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn gone(message: &str) -> ErrorResponse {
+        ErrorResponse::new(ErrorCode::Gone, message)
+    }
+
+    /// Returns a bad request response for a `SplinterProtocolVersion` the endpoint cannot serve
+    pub fn unsupported_protocol_version(protocol_version: &str) -> ErrorResponse {
+        ErrorResponse::new(
+            ErrorCode::UnsupportedProtocolVersion,
+            &format!("Unsupported SplinterProtocolVersion: {}", protocol_version),
+        )
+        .with_detail("protocol_version", protocol_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that an error response serializes to the documented envelope, omitting the details
+    /// if there are none.
+    #[test]
+    fn test_error_response_serialization() {
+        let json = serde_json::to_value(ErrorResponse::not_found("Circuit not found")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"code": "NOT_FOUND", "message": "Circuit not found"})
+        );
+
+        let json = serde_json::to_value(ErrorResponse::unsupported_protocol_version("7")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "UNSUPPORTED_PROTOCOL_VERSION",
+                "message": "Unsupported SplinterProtocolVersion: 7",
+                "details": {"protocol_version": "7"},
+            })
+        );
+    }
+
+    /// Verify that a code this version does not know about deserializes as `Unknown`, rather than
+    /// failing, so older clients can still read the message of a newer server's errors.
+    #[test]
+    fn test_error_response_unknown_code() {
+        let response: ErrorResponse =
+            serde_json::from_str(r#"{"code": "SOMETHING_NEW", "message": "oops"}"#).unwrap();
+        assert_eq!(response.code(), ErrorCode::Unknown);
+        assert_eq!(response.message(), "oops");
+        assert!(response.details().is_empty());
     }
 }
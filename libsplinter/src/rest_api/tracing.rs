@@ -0,0 +1,101 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Records a span for each request handled by the REST API
+//!
+//! If a request carries a W3C `traceparent` header, its span continues the caller's trace;
+//! otherwise, a new trace is started.
+
+use actix_web::dev::*;
+use actix_web::Error as ActixError;
+use futures::{
+    future::{ok, FutureResult},
+    Future, Poll,
+};
+
+use crate::tracing::{Span, SpanContext};
+
+/// Middleware that records a span for each request
+#[derive(Clone, Default)]
+pub struct RequestTracing;
+
+impl RequestTracing {
+    pub fn new() -> Self {
+        RequestTracing
+    }
+}
+
+impl<S, B> Transform<S> for RequestTracing
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = S::Error;
+    type InitError = ();
+    type Transform = RequestTracingMiddleware<S>;
+    type Future = FutureResult<Self::Transform, Self::InitError>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestTracingMiddleware { service })
+    }
+}
+
+#[doc(hidden)]
+pub struct RequestTracingMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service for RequestTracingMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = S::Error;
+    type Future = Box<dyn Future<Item = Self::Response, Error = Self::Error>>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let parent = req
+            .headers()
+            .get("traceparent")
+            .and_then(|value| value.to_str().ok())
+            .and_then(SpanContext::from_traceparent);
+
+        let mut span = Span::server(&format!("{} {}", req.method(), req.path()), parent)
+            .with_attribute("http.method", req.method().as_str())
+            .with_attribute("http.target", &req.uri().to_string());
+
+        Box::new(self.service.call(req).then(move |res| {
+            match &res {
+                Ok(response) => {
+                    let status = response.status();
+                    span.set_attribute("http.status_code", status.as_str());
+                    if status.is_server_error() {
+                        span.set_error(&status.to_string());
+                    }
+                }
+                Err(err) => span.set_error(&err.to_string()),
+            }
+            res
+        }))
+    }
+}
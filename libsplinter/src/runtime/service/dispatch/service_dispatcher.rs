@@ -19,6 +19,8 @@ use crate::service::{
 
 use super::task::MessageHandlerTaskRunner;
 use super::type_resolver::ServiceTypeResolver;
+#[cfg(feature = "service-replay-protection")]
+use crate::runtime::service::replay::ReplayGuard;
 
 type BoxedByteMessageHandlerFactory =
     Box<dyn MessageHandlerFactory<MessageHandler = Box<dyn MessageHandler<Message = Vec<u8>>>>>;
@@ -28,6 +30,8 @@ pub struct ServiceDispatcher {
     message_sender_factory: Box<dyn MessageSenderFactory<Vec<u8>>>,
     type_resolver: Box<dyn ServiceTypeResolver + Send>,
     task_runner: Box<dyn MessageHandlerTaskRunner + Send>,
+    #[cfg(feature = "service-replay-protection")]
+    replay_guard: ReplayGuard,
 }
 
 impl ServiceDispatcher {
@@ -42,6 +46,8 @@ impl ServiceDispatcher {
             message_sender_factory,
             type_resolver,
             task_runner,
+            #[cfg(feature = "service-replay-protection")]
+            replay_guard: ReplayGuard::default(),
         }
     }
 
@@ -87,6 +93,21 @@ impl ServiceDispatcher {
                 ))
             })?;
 
+        #[cfg(feature = "service-replay-protection")]
+        let message = match self.type_resolver.resolve_replay_window(&to_service)? {
+            Some(window_size) => {
+                match self
+                    .replay_guard
+                    .open(&to_service, &from_service, window_size, message)?
+                {
+                    Some(message) => message,
+                    // The replay has been logged and counted; drop it
+                    None => return Ok(()),
+                }
+            }
+            None => message,
+        };
+
         self.task_runner.execute(
             &**factory,
             &*self.message_sender_factory,
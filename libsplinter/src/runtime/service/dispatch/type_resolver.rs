@@ -20,4 +20,14 @@ pub trait ServiceTypeResolver {
         &self,
         service_id: &FullyQualifiedServiceId,
     ) -> Result<Option<ServiceType>, InternalError>;
+
+    /// Returns the size of the replay window for messages sent to the given service, or `None`
+    /// if the service does not require replay protection.
+    #[cfg(feature = "service-replay-protection")]
+    fn resolve_replay_window(
+        &self,
+        _service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<u64>, InternalError> {
+        Ok(None)
+    }
 }
@@ -16,6 +16,9 @@ use crate::circuit::routing::{RoutingTableReader, ServiceId as RoutingServiceId}
 use crate::error::InternalError;
 use crate::service::{FullyQualifiedServiceId, ServiceType};
 
+#[cfg(feature = "service-replay-protection")]
+use crate::runtime::service::replay::replay_window_from_args;
+
 use super::type_resolver::ServiceTypeResolver;
 
 pub struct RoutingTableServiceTypeResolver {
@@ -47,4 +50,20 @@ impl ServiceTypeResolver for RoutingTableServiceTypeResolver {
             })
             .transpose()
     }
+
+    #[cfg(feature = "service-replay-protection")]
+    fn resolve_replay_window(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<u64>, InternalError> {
+        self.routing_table_reader
+            .get_service(&RoutingServiceId::new(
+                service_id.circuit_id().to_string(),
+                service_id.service_id().to_string(),
+            ))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .map(|service| replay_window_from_args(service.arguments()))
+            .transpose()
+            .map(Option::flatten)
+    }
 }
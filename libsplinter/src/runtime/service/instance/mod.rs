@@ -15,11 +15,23 @@
 mod orchestrator;
 mod processor;
 
+#[cfg(feature = "circuit-ready-hooks")]
+pub use orchestrator::CircuitReadyHook;
 pub use orchestrator::{
     AddServiceError, InitializeServiceError, ListServicesError, ManagedService,
     NewOrchestratorError, OrchestratorError, ServiceDefinition, ServiceOrchestrator,
     ServiceOrchestratorBuilder, ShutdownServiceError,
 };
+#[cfg(feature = "service-orchestrator-adapters")]
+pub use orchestrator::{
+    ExternalService, ExternalServiceFactory, ServiceAdapterError, ServiceAdapterInfo,
+    ServiceAdapterRegistry, ServiceAdapterRegistryBuilder, ServiceAdapterRegistryShutdownHandle,
+    SERVICE_ADAPTER_PROTOCOL_VERSION,
+};
+#[cfg(feature = "service-orchestrator-limits")]
+pub use orchestrator::{
+    ResourceLimits, RestartPolicy, ServiceHealth, ServiceHealthMonitor, ServiceStatus,
+};
 
 pub use processor::{
     registry::StandardServiceNetworkRegistry, JoinHandles, ServiceProcessor,
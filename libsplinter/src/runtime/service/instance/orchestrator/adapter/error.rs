@@ -0,0 +1,48 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::error::Error;
+
+/// Errors that may occur while passing a lifecycle command to a service adapter.
+#[derive(Debug)]
+pub enum ServiceAdapterError {
+    /// No connected adapter has claimed the service's type
+    NotConnected(String),
+    /// The adapter did not report the result of the command in time
+    Timeout(String),
+    /// The adapter reported that the command failed
+    Rejected(String),
+    /// An internal error occurred while sending the command
+    Internal(String),
+}
+
+impl Error for ServiceAdapterError {}
+
+impl std::fmt::Display for ServiceAdapterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ServiceAdapterError::NotConnected(service_type) => write!(
+                f,
+                "no service adapter is connected for service type {}",
+                service_type
+            ),
+            ServiceAdapterError::Timeout(msg) => {
+                write!(f, "service adapter did not respond: {}", msg)
+            }
+            ServiceAdapterError::Rejected(msg) => {
+                write!(f, "service adapter rejected command: {}", msg)
+            }
+            ServiceAdapterError::Internal(msg) => f.write_str(msg),
+        }
+    }
+}
@@ -0,0 +1,1011 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for services run outside of the orchestrator's process.
+//!
+//! A service adapter is a separate process that connects to the [ServiceAdapterRegistry] and
+//! claims one or more service types. Services of a claimed type are created by the registry's
+//! [ExternalServiceFactory] and are run by the adapter: lifecycle commands and circuit messages
+//! are passed to the adapter using the messages defined in `service_adapter.proto`, and messages
+//! sent by the adapter's services are forwarded to the circuit.
+//!
+//! The registry sends a heartbeat to each adapter at a regular interval. An adapter that has not
+//! been heard from within the heartbeat timeout is disconnected and its service types are
+//! released. When an adapter reconnects and claims a type again, the services of that type that
+//! the orchestrator is running are started on the adapter.
+
+mod error;
+mod service;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use protobuf::Message;
+use uuid::Uuid;
+
+use crate::error::{InternalError, InvalidStateError};
+use crate::mesh::{Envelope, Mesh, RecvTimeoutError};
+use crate::protos::service_adapter::{
+    SAHeartbeat, SALifecycleCommand, SALifecycleCommand_Command, SALifecycleResult,
+    SARegisterRequest, SARegisterResponse, SARegisterResponse_Status, SASendMessage,
+    SAServiceArgument, ServiceAdapterMessage, ServiceAdapterMessageType,
+};
+use crate::service::instance::{ServiceMessageContext, ServiceNetworkSender};
+use crate::threading::lifecycle::ShutdownHandle;
+use crate::threading::lock::lock_or_recover;
+use crate::transport::Connection;
+
+pub use self::error::ServiceAdapterError;
+pub use self::service::{ExternalService, ExternalServiceFactory};
+
+/// The version of the service adapter protocol supported by this registry.
+pub const SERVICE_ADAPTER_PROTOCOL_VERSION: u32 = 1;
+
+const DEFAULT_INCOMING_CAPACITY: usize = 512;
+const DEFAULT_OUTGOING_CAPACITY: usize = 512;
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builds new [ServiceAdapterRegistry] instances.
+#[derive(Default)]
+pub struct ServiceAdapterRegistryBuilder {
+    service_types: Vec<String>,
+    incoming_capacity: Option<usize>,
+    outgoing_capacity: Option<usize>,
+    heartbeat_interval: Option<Duration>,
+    heartbeat_timeout: Option<Duration>,
+    command_timeout: Option<Duration>,
+}
+
+impl ServiceAdapterRegistryBuilder {
+    /// Constructs a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service type that may be claimed by an adapter.
+    ///
+    /// At least one service type is required to construct the final ServiceAdapterRegistry.
+    pub fn with_service_type<S: Into<String>>(mut self, service_type: S) -> Self {
+        self.service_types.push(service_type.into());
+        self
+    }
+
+    /// Sets the incoming message capacity for the adapter connections.
+    ///
+    /// Defaults to 512.
+    pub fn with_incoming_capacity(mut self, incoming_capacity: usize) -> Self {
+        self.incoming_capacity = Some(incoming_capacity);
+        self
+    }
+
+    /// Sets the outgoing message capacity for the adapter connections.
+    ///
+    /// Defaults to 512.
+    pub fn with_outgoing_capacity(mut self, outgoing_capacity: usize) -> Self {
+        self.outgoing_capacity = Some(outgoing_capacity);
+        self
+    }
+
+    /// Sets how often a heartbeat is sent to each connected adapter.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn with_heartbeat_interval(mut self, heartbeat_interval: Duration) -> Self {
+        self.heartbeat_interval = Some(heartbeat_interval);
+        self
+    }
+
+    /// Sets how long an adapter may go without sending a message before it is disconnected.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn with_heartbeat_timeout(mut self, heartbeat_timeout: Duration) -> Self {
+        self.heartbeat_timeout = Some(heartbeat_timeout);
+        self
+    }
+
+    /// Sets how long to wait for an adapter to report the result of a lifecycle command.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn with_command_timeout(mut self, command_timeout: Duration) -> Self {
+        self.command_timeout = Some(command_timeout);
+        self
+    }
+
+    /// Construct the ServiceAdapterRegistry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an InvalidStateError if no service types were provided or if the heartbeat timeout
+    /// is not longer than the heartbeat interval.
+    pub fn build(self) -> Result<ServiceAdapterRegistry, InvalidStateError> {
+        if self.service_types.is_empty() {
+            return Err(InvalidStateError::with_message(
+                "A service adapter registry requires at least one service type".into(),
+            ));
+        }
+
+        let heartbeat_interval = self
+            .heartbeat_interval
+            .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL);
+        let heartbeat_timeout = self.heartbeat_timeout.unwrap_or(DEFAULT_HEARTBEAT_TIMEOUT);
+        if heartbeat_timeout <= heartbeat_interval {
+            return Err(InvalidStateError::with_message(
+                "The heartbeat timeout must be longer than the heartbeat interval".into(),
+            ));
+        }
+
+        let mesh = Mesh::new(
+            self.incoming_capacity.unwrap_or(DEFAULT_INCOMING_CAPACITY),
+            self.outgoing_capacity.unwrap_or(DEFAULT_OUTGOING_CAPACITY),
+        );
+
+        Ok(ServiceAdapterRegistry {
+            mesh,
+            shared: Arc::new(Shared {
+                service_types: self.service_types,
+                command_timeout: self.command_timeout.unwrap_or(DEFAULT_COMMAND_TIMEOUT),
+                state: Mutex::new(AdapterState::default()),
+            }),
+            heartbeat_interval,
+            heartbeat_timeout,
+        })
+    }
+}
+
+/// Information about a connected service adapter.
+#[derive(Clone, Debug)]
+pub struct ServiceAdapterInfo {
+    adapter_id: String,
+    service_types: Vec<String>,
+    last_seen: Instant,
+}
+
+impl ServiceAdapterInfo {
+    /// Returns the ID assigned to the adapter's connection.
+    pub fn adapter_id(&self) -> &str {
+        &self.adapter_id
+    }
+
+    /// Returns the service types claimed by the adapter; empty if it has not yet registered.
+    pub fn service_types(&self) -> &[String] {
+        &self.service_types
+    }
+
+    /// Returns the time since a message was last received from the adapter.
+    pub fn time_since_last_seen(&self) -> Duration {
+        self.last_seen.elapsed()
+    }
+}
+
+/// Accepts connections from service adapters and passes lifecycle commands and messages between
+/// the orchestrator and the services they run.
+#[derive(Clone)]
+pub struct ServiceAdapterRegistry {
+    mesh: Mesh,
+    shared: Arc<Shared>,
+    heartbeat_interval: Duration,
+    heartbeat_timeout: Duration,
+}
+
+impl ServiceAdapterRegistry {
+    /// Adds a connection from a service adapter.
+    ///
+    /// The adapter must send a register request before any services are run on it.
+    ///
+    /// # Returns
+    ///
+    /// The ID assigned to the adapter.
+    pub fn add_connection(&self, connection: Box<dyn Connection>) -> Result<String, InternalError> {
+        let adapter_id = format!("{}", Uuid::new_v4());
+        lock_or_recover(&self.shared.state, "service adapter state")
+            .adapters
+            .insert(
+                adapter_id.clone(),
+                ConnectedAdapter {
+                    service_types: vec![],
+                    last_seen: Instant::now(),
+                },
+            );
+
+        if let Err(err) = self.mesh.add(connection, adapter_id.clone()) {
+            lock_or_recover(&self.shared.state, "service adapter state")
+                .adapters
+                .remove(&adapter_id);
+            return Err(InternalError::from_source(Box::new(err)));
+        }
+
+        debug!("Service adapter {} connected", adapter_id);
+        Ok(adapter_id)
+    }
+
+    /// Returns a factory for the services run by adapters.
+    ///
+    /// The factory should be added to the service orchestrator.
+    pub fn service_factory(&self) -> ExternalServiceFactory {
+        ExternalServiceFactory::new(self.mesh.clone(), self.shared.clone())
+    }
+
+    /// Lists the connected service adapters.
+    pub fn list_adapters(&self) -> Vec<ServiceAdapterInfo> {
+        lock_or_recover(&self.shared.state, "service adapter state")
+            .adapters
+            .iter()
+            .map(|(adapter_id, adapter)| ServiceAdapterInfo {
+                adapter_id: adapter_id.clone(),
+                service_types: adapter.service_types.clone(),
+                last_seen: adapter.last_seen,
+            })
+            .collect()
+    }
+
+    /// Starts the thread that handles messages from adapters and checks their health.
+    ///
+    /// # Errors
+    ///
+    /// Returns an InternalError if the thread cannot be started.
+    pub fn start(&self) -> Result<ServiceAdapterRegistryShutdownHandle, InternalError> {
+        let running = Arc::new(AtomicBool::new(true));
+
+        let mesh = self.mesh.clone();
+        let shared = self.shared.clone();
+        let thread_running = running.clone();
+        let heartbeat_interval = self.heartbeat_interval;
+        let heartbeat_timeout = self.heartbeat_timeout;
+        let join_handle = thread::Builder::new()
+            .name("ServiceAdapterRegistry".into())
+            .spawn(move || {
+                run_adapter_loop(
+                    mesh,
+                    shared,
+                    thread_running,
+                    heartbeat_interval,
+                    heartbeat_timeout,
+                )
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(ServiceAdapterRegistryShutdownHandle {
+            mesh: self.mesh.clone(),
+            running,
+            join_handle: Some(join_handle),
+        })
+    }
+}
+
+/// Shuts down the thread started by [ServiceAdapterRegistry::start].
+pub struct ServiceAdapterRegistryShutdownHandle {
+    mesh: Mesh,
+    running: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl ShutdownHandle for ServiceAdapterRegistryShutdownHandle {
+    fn signal_shutdown(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.mesh.signal_shutdown();
+    }
+
+    fn wait_for_shutdown(mut self) -> Result<(), InternalError> {
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().map_err(|_| {
+                InternalError::with_message("Unable to join service adapter registry thread".into())
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// State shared between the registry, its message-handling thread, and the external services.
+struct Shared {
+    service_types: Vec<String>,
+    command_timeout: Duration,
+    state: Mutex<AdapterState>,
+}
+
+#[derive(Default)]
+struct AdapterState {
+    // Connected adapters, by adapter ID
+    adapters: HashMap<String, ConnectedAdapter>,
+    // The adapter ID that has claimed each service type
+    claims: HashMap<String, String>,
+    // Lifecycle commands awaiting a result, by correlation ID
+    pending: HashMap<String, PendingCommand>,
+    // The started services, by (circuit, service ID)
+    services: HashMap<(String, String), StartedService>,
+}
+
+impl AdapterState {
+    /// Removes an adapter, releasing its service types and failing its pending commands.
+    fn remove_adapter(&mut self, adapter_id: &str) {
+        self.adapters.remove(adapter_id);
+        self.claims.retain(|_, claimant| claimant != adapter_id);
+        // Dropping the result senders notifies the waiting services
+        self.pending
+            .retain(|_, pending| pending.adapter_id != adapter_id);
+    }
+}
+
+struct ConnectedAdapter {
+    service_types: Vec<String>,
+    last_seen: Instant,
+}
+
+struct PendingCommand {
+    adapter_id: String,
+    sender: Sender<Result<(), String>>,
+}
+
+struct StartedService {
+    service_type: String,
+    arguments: HashMap<String, String>,
+    network_sender: Box<dyn ServiceNetworkSender>,
+}
+
+fn run_adapter_loop(
+    mesh: Mesh,
+    shared: Arc<Shared>,
+    running: Arc<AtomicBool>,
+    heartbeat_interval: Duration,
+    heartbeat_timeout: Duration,
+) {
+    let mut last_heartbeat = Instant::now();
+    while running.load(Ordering::SeqCst) {
+        let timeout = heartbeat_interval
+            .checked_sub(last_heartbeat.elapsed())
+            .unwrap_or_default();
+        match mesh.recv_timeout(timeout) {
+            Ok(envelope) => {
+                if let Err(err) = handle_envelope(&mesh, &shared, envelope) {
+                    error!("Unable to handle service adapter message: {}", err);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(RecvTimeoutError::Shutdown) | Err(RecvTimeoutError::Disconnected) => {
+                debug!("Service adapter mesh has shutdown");
+                break;
+            }
+            Err(err) => {
+                error!("Unable to receive service adapter message: {}", err);
+                break;
+            }
+        }
+
+        if last_heartbeat.elapsed() >= heartbeat_interval {
+            check_adapters(&mesh, &shared, heartbeat_timeout);
+            last_heartbeat = Instant::now();
+        }
+    }
+}
+
+/// Disconnects the adapters that have timed out and sends a heartbeat to the rest.
+fn check_adapters(mesh: &Mesh, shared: &Shared, heartbeat_timeout: Duration) {
+    let (expired, connected) = {
+        let mut state = lock_or_recover(&shared.state, "service adapter state");
+        let expired = state
+            .adapters
+            .iter()
+            .filter(|(_, adapter)| adapter.last_seen.elapsed() > heartbeat_timeout)
+            .map(|(adapter_id, _)| adapter_id.clone())
+            .collect::<Vec<_>>();
+        for adapter_id in expired.iter() {
+            state.remove_adapter(adapter_id);
+        }
+        (expired, state.adapters.keys().cloned().collect::<Vec<_>>())
+    };
+
+    for adapter_id in expired {
+        warn!(
+            "Service adapter {} has not responded within {:?}; disconnecting",
+            adapter_id, heartbeat_timeout
+        );
+        if let Err(err) = mesh.remove(&adapter_id) {
+            debug!("Unable to remove service adapter {}: {}", adapter_id, err);
+        }
+    }
+
+    for adapter_id in connected {
+        if let Err(err) = send_message(
+            mesh,
+            &adapter_id,
+            ServiceAdapterMessageType::SA_HEARTBEAT,
+            &SAHeartbeat::new(),
+        ) {
+            debug!(
+                "Unable to send heartbeat to service adapter {}: {}",
+                adapter_id, err
+            );
+        }
+    }
+}
+
+fn handle_envelope(mesh: &Mesh, shared: &Shared, envelope: Envelope) -> Result<(), InternalError> {
+    let adapter_id = envelope.id().to_string();
+    let mut message = ServiceAdapterMessage::parse_from_bytes(envelope.payload())
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    match lock_or_recover(&shared.state, "service adapter state")
+        .adapters
+        .get_mut(&adapter_id)
+    {
+        Some(adapter) => adapter.last_seen = Instant::now(),
+        None => {
+            debug!(
+                "Ignoring message from disconnected service adapter {}",
+                adapter_id
+            );
+            return Ok(());
+        }
+    }
+
+    match message.get_message_type() {
+        ServiceAdapterMessageType::SA_HEARTBEAT => Ok(()),
+        ServiceAdapterMessageType::SA_REGISTER_REQUEST => {
+            let request = SARegisterRequest::parse_from_bytes(&message.take_payload())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            handle_register_request(mesh, shared, &adapter_id, request)
+        }
+        ServiceAdapterMessageType::SA_LIFECYCLE_RESULT => {
+            let result = SALifecycleResult::parse_from_bytes(&message.take_payload())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            handle_lifecycle_result(shared, &adapter_id, result);
+            Ok(())
+        }
+        ServiceAdapterMessageType::SA_SEND_MESSAGE => {
+            let send_request = SASendMessage::parse_from_bytes(&message.take_payload())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            handle_send_message(shared, &adapter_id, send_request)
+        }
+        message_type => {
+            warn!(
+                "Received unexpected message type {:?} from service adapter {}",
+                message_type, adapter_id
+            );
+            Ok(())
+        }
+    }
+}
+
+fn handle_register_request(
+    mesh: &Mesh,
+    shared: &Shared,
+    adapter_id: &str,
+    request: SARegisterRequest,
+) -> Result<(), InternalError> {
+    let mut response = SARegisterResponse::new();
+    response.set_protocol_version(SERVICE_ADAPTER_PROTOCOL_VERSION);
+
+    let mut restarts = vec![];
+    {
+        let mut state = lock_or_recover(&shared.state, "service adapter state");
+        let service_types = request.get_service_types();
+        let unknown = service_types
+            .iter()
+            .find(|service_type| !shared.service_types.contains(service_type));
+        let claimed = service_types.iter().find(|service_type| {
+            state
+                .claims
+                .get(*service_type)
+                .map(|claimant| claimant != adapter_id)
+                .unwrap_or(false)
+        });
+
+        if request.get_protocol_version() != SERVICE_ADAPTER_PROTOCOL_VERSION {
+            response.set_status(SARegisterResponse_Status::ERROR_UNSUPPORTED_PROTOCOL_VERSION);
+            response.set_error_message(format!(
+                "Unsupported protocol version {}; expected {}",
+                request.get_protocol_version(),
+                SERVICE_ADAPTER_PROTOCOL_VERSION
+            ));
+        } else if service_types.is_empty() {
+            response.set_status(SARegisterResponse_Status::ERROR_UNKNOWN_SERVICE_TYPE);
+            response.set_error_message("At least one service type must be claimed".into());
+        } else if let Some(service_type) = unknown {
+            response.set_status(SARegisterResponse_Status::ERROR_UNKNOWN_SERVICE_TYPE);
+            response.set_error_message(format!("Unknown service type {}", service_type));
+        } else if let Some(service_type) = claimed {
+            response.set_status(SARegisterResponse_Status::ERROR_SERVICE_TYPE_CLAIMED);
+            response.set_error_message(format!(
+                "Service type {} has been claimed by another adapter",
+                service_type
+            ));
+        } else {
+            for service_type in service_types {
+                state
+                    .claims
+                    .insert(service_type.clone(), adapter_id.to_string());
+            }
+            if let Some(adapter) = state.adapters.get_mut(adapter_id) {
+                adapter.service_types = service_types.to_vec();
+            }
+
+            // Services that were started while no adapter was connected, or that were running on
+            // a previous adapter, are started on this one.
+            restarts = state
+                .services
+                .iter()
+                .filter(|(_, service)| service_types.contains(&service.service_type))
+                .map(|((circuit, service_id), service)| {
+                    lifecycle_command(
+                        SALifecycleCommand_Command::START,
+                        circuit,
+                        service_id,
+                        &service.service_type,
+                        &service.arguments,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            response.set_status(SARegisterResponse_Status::OK);
+            info!(
+                "Service adapter {} registered for service types {:?}",
+                adapter_id, service_types
+            );
+        }
+    }
+
+    send_message(
+        mesh,
+        adapter_id,
+        ServiceAdapterMessageType::SA_REGISTER_RESPONSE,
+        &response,
+    )
+    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    for command in restarts {
+        // The results of these commands are not awaited; failures are logged when the result is
+        // received.
+        if let Err(err) = send_message(
+            mesh,
+            adapter_id,
+            ServiceAdapterMessageType::SA_LIFECYCLE_COMMAND,
+            &command,
+        ) {
+            error!(
+                "Unable to start service {}::{} on service adapter {}: {}",
+                command.get_circuit(),
+                command.get_service_id(),
+                adapter_id,
+                err
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_lifecycle_result(shared: &Shared, adapter_id: &str, result: SALifecycleResult) {
+    let pending = lock_or_recover(&shared.state, "service adapter state")
+        .pending
+        .remove(result.get_correlation_id());
+
+    let outcome = if result.get_success() {
+        Ok(())
+    } else {
+        Err(result.get_error_message().to_string())
+    };
+
+    match pending {
+        Some(pending) => {
+            // The service may have stopped waiting for the result
+            let _ = pending.sender.send(outcome);
+        }
+        None => {
+            if let Err(err) = outcome {
+                warn!(
+                    "Service adapter {} reported failed lifecycle command {}: {}",
+                    adapter_id,
+                    result.get_correlation_id(),
+                    err
+                );
+            }
+        }
+    }
+}
+
+fn handle_send_message(
+    shared: &Shared,
+    adapter_id: &str,
+    message: SASendMessage,
+) -> Result<(), InternalError> {
+    let network_sender = {
+        let state = lock_or_recover(&shared.state, "service adapter state");
+        state
+            .services
+            .get(&(
+                message.get_circuit().to_string(),
+                message.get_service_id().to_string(),
+            ))
+            .filter(|service| {
+                state.claims.get(&service.service_type).map(String::as_str) == Some(adapter_id)
+            })
+            .ok_or_else(|| {
+                InternalError::with_message(format!(
+                    "Service adapter {} attempted to send a message from service {}::{}, which it \
+                 does not run",
+                    adapter_id,
+                    message.get_circuit(),
+                    message.get_service_id()
+                ))
+            })?
+            .network_sender
+            .clone()
+    };
+
+    let result = if message.get_correlation_id().is_empty() {
+        network_sender.send(message.get_recipient(), message.get_payload())
+    } else {
+        network_sender.reply(
+            &ServiceMessageContext {
+                sender: message.get_recipient().to_string(),
+                circuit: message.get_circuit().to_string(),
+                correlation_id: message.get_correlation_id().to_string(),
+            },
+            message.get_payload(),
+        )
+    };
+
+    result.map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+fn lifecycle_command(
+    command: SALifecycleCommand_Command,
+    circuit: &str,
+    service_id: &str,
+    service_type: &str,
+    arguments: &HashMap<String, String>,
+) -> SALifecycleCommand {
+    let mut lifecycle_command = SALifecycleCommand::new();
+    lifecycle_command.set_correlation_id(format!("{}", Uuid::new_v4()));
+    lifecycle_command.set_command(command);
+    lifecycle_command.set_circuit(circuit.into());
+    lifecycle_command.set_service_id(service_id.into());
+    lifecycle_command.set_service_type(service_type.into());
+    if command == SALifecycleCommand_Command::START {
+        lifecycle_command.set_arguments(
+            arguments
+                .iter()
+                .map(|(key, value)| {
+                    let mut argument = SAServiceArgument::new();
+                    argument.set_key(key.clone());
+                    argument.set_value(value.clone());
+                    argument
+                })
+                .collect(),
+        );
+    }
+    lifecycle_command
+}
+
+/// Sends a message of the given type to an adapter.
+fn send_message<M: Message>(
+    mesh: &Mesh,
+    adapter_id: &str,
+    message_type: ServiceAdapterMessageType,
+    message: &M,
+) -> Result<(), ServiceAdapterError> {
+    let bytes = wrap_message(message_type, message)?;
+    mesh.send(Envelope::new(adapter_id.to_string(), bytes))
+        .map_err(|err| ServiceAdapterError::Internal(err.to_string()))
+}
+
+fn wrap_message<M: Message>(
+    message_type: ServiceAdapterMessageType,
+    message: &M,
+) -> Result<Vec<u8>, ServiceAdapterError> {
+    let mut adapter_message = ServiceAdapterMessage::new();
+    adapter_message.set_message_type(message_type);
+    adapter_message.set_payload(
+        message
+            .write_to_bytes()
+            .map_err(|err| ServiceAdapterError::Internal(err.to_string()))?,
+    );
+    adapter_message
+        .write_to_bytes()
+        .map_err(|err| ServiceAdapterError::Internal(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, Sender};
+
+    use crate::protos::service_adapter::SACircuitMessage;
+    use crate::service::instance::{
+        OrchestratableServiceFactory, ServiceConnectionError, ServiceDisconnectionError,
+        ServiceNetworkRegistry, ServiceSendError,
+    };
+    use crate::transport::inproc::InprocTransport;
+    use crate::transport::Transport;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Verify that an adapter may register for a service type, that services of that type are
+    /// started on the adapter, and that messages are passed between the adapter and the circuit.
+    ///
+    /// 1. Start a registry for the "test" service type and connect an adapter to it
+    /// 2. Register the adapter and verify the response is OK
+    /// 3. Start a service and verify the adapter receives the start command; report success
+    /// 4. Handle a message and verify the adapter receives it
+    /// 5. Send a message from the adapter and verify it is sent by the network sender
+    #[test]
+    fn test_adapter_lifecycle() {
+        let registry = ServiceAdapterRegistryBuilder::new()
+            .with_service_type("test")
+            .with_command_timeout(TIMEOUT)
+            .build()
+            .expect("Unable to build registry");
+        let mut shutdown_handle = registry.start().expect("Unable to start registry");
+
+        let adapter_mesh = connect_adapter(&registry);
+
+        send_to_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_REGISTER_REQUEST,
+            &register_request(SERVICE_ADAPTER_PROTOCOL_VERSION, "test"),
+        );
+        let response: SARegisterResponse = recv_from_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_REGISTER_RESPONSE,
+        );
+        assert_eq!(response.get_status(), SARegisterResponse_Status::OK);
+
+        let adapters = registry.list_adapters();
+        assert_eq!(adapters.len(), 1);
+        assert_eq!(adapters[0].service_types(), &["test".to_string()]);
+
+        let service = registry
+            .service_factory()
+            .create_orchestratable_service("abcd".into(), "test", "circuit", HashMap::new())
+            .expect("Unable to create service");
+
+        let (tx, rx) = channel();
+        let start_service = service.clone_box();
+        let start_handle = thread::spawn(move || {
+            let mut service = start_service;
+            service.start(&MockNetworkRegistry { tx })
+        });
+
+        let command: SALifecycleCommand = recv_from_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_LIFECYCLE_COMMAND,
+        );
+        assert_eq!(command.get_command(), SALifecycleCommand_Command::START);
+        assert_eq!(command.get_circuit(), "circuit");
+        assert_eq!(command.get_service_id(), "abcd");
+
+        let mut result = SALifecycleResult::new();
+        result.set_correlation_id(command.get_correlation_id().into());
+        result.set_success(true);
+        send_to_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_LIFECYCLE_RESULT,
+            &result,
+        );
+        start_handle
+            .join()
+            .expect("Start thread panicked")
+            .expect("Unable to start service");
+
+        service
+            .handle_message(
+                b"hello",
+                &ServiceMessageContext {
+                    sender: "efgh".into(),
+                    circuit: "circuit".into(),
+                    correlation_id: "".into(),
+                },
+            )
+            .expect("Unable to handle message");
+        let circuit_message: SACircuitMessage =
+            recv_from_registry(&adapter_mesh, ServiceAdapterMessageType::SA_CIRCUIT_MESSAGE);
+        assert_eq!(circuit_message.get_service_id(), "abcd");
+        assert_eq!(circuit_message.get_sender(), "efgh");
+        assert_eq!(circuit_message.get_payload(), b"hello");
+
+        let mut send_request = SASendMessage::new();
+        send_request.set_circuit("circuit".into());
+        send_request.set_service_id("abcd".into());
+        send_request.set_recipient("efgh".into());
+        send_request.set_payload(b"world".to_vec());
+        send_to_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_SEND_MESSAGE,
+            &send_request,
+        );
+        assert_eq!(
+            rx.recv_timeout(TIMEOUT).expect("No message was sent"),
+            ("efgh".to_string(), b"world".to_vec())
+        );
+
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown registry");
+    }
+
+    /// Verify that a register request is rejected if it uses an unsupported protocol version or
+    /// claims a service type the registry does not know.
+    #[test]
+    fn test_register_rejected() {
+        let registry = ServiceAdapterRegistryBuilder::new()
+            .with_service_type("test")
+            .build()
+            .expect("Unable to build registry");
+        let mut shutdown_handle = registry.start().expect("Unable to start registry");
+
+        let adapter_mesh = connect_adapter(&registry);
+
+        send_to_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_REGISTER_REQUEST,
+            &register_request(SERVICE_ADAPTER_PROTOCOL_VERSION + 1, "test"),
+        );
+        let response: SARegisterResponse = recv_from_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_REGISTER_RESPONSE,
+        );
+        assert_eq!(
+            response.get_status(),
+            SARegisterResponse_Status::ERROR_UNSUPPORTED_PROTOCOL_VERSION
+        );
+
+        send_to_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_REGISTER_REQUEST,
+            &register_request(SERVICE_ADAPTER_PROTOCOL_VERSION, "unknown"),
+        );
+        let response: SARegisterResponse = recv_from_registry(
+            &adapter_mesh,
+            ServiceAdapterMessageType::SA_REGISTER_RESPONSE,
+        );
+        assert_eq!(
+            response.get_status(),
+            SARegisterResponse_Status::ERROR_UNKNOWN_SERVICE_TYPE
+        );
+
+        assert!(registry.list_adapters()[0].service_types().is_empty());
+
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown registry");
+    }
+
+    /// Verify that the builder requires a service type and a heartbeat timeout that is longer
+    /// than the heartbeat interval.
+    #[test]
+    fn test_builder_validation() {
+        assert!(ServiceAdapterRegistryBuilder::new().build().is_err());
+        assert!(ServiceAdapterRegistryBuilder::new()
+            .with_service_type("test")
+            .with_heartbeat_interval(Duration::from_secs(10))
+            .with_heartbeat_timeout(Duration::from_secs(5))
+            .build()
+            .is_err());
+    }
+
+    fn connect_adapter(registry: &ServiceAdapterRegistry) -> Mesh {
+        let mut transport = InprocTransport::default();
+        let mut listener = transport.listen("adapter").expect("Unable to listen");
+        let adapter_connection = transport.connect("adapter").expect("Unable to connect");
+        let registry_connection = listener.accept().expect("Unable to accept");
+
+        registry
+            .add_connection(registry_connection)
+            .expect("Unable to add connection");
+
+        let adapter_mesh = Mesh::new(8, 8);
+        adapter_mesh
+            .add(adapter_connection, "registry".into())
+            .expect("Unable to add adapter connection");
+        adapter_mesh
+    }
+
+    fn register_request(protocol_version: u32, service_type: &str) -> SARegisterRequest {
+        let mut request = SARegisterRequest::new();
+        request.set_protocol_version(protocol_version);
+        request.set_service_types(vec![service_type.to_string()].into());
+        request
+    }
+
+    fn send_to_registry<M: Message>(
+        mesh: &Mesh,
+        message_type: ServiceAdapterMessageType,
+        message: &M,
+    ) {
+        send_message(mesh, "registry", message_type, message).expect("Unable to send message");
+    }
+
+    /// Receives the next message of the given type, skipping heartbeats.
+    fn recv_from_registry<M: Message>(mesh: &Mesh, message_type: ServiceAdapterMessageType) -> M {
+        loop {
+            let envelope = mesh.recv_timeout(TIMEOUT).expect("No message received");
+            let mut message = ServiceAdapterMessage::parse_from_bytes(envelope.payload())
+                .expect("Unable to parse message");
+            if message.get_message_type() == ServiceAdapterMessageType::SA_HEARTBEAT {
+                continue;
+            }
+            assert_eq!(message.get_message_type(), message_type);
+            return M::parse_from_bytes(&message.take_payload()).expect("Unable to parse payload");
+        }
+    }
+
+    struct MockNetworkRegistry {
+        tx: Sender<(String, Vec<u8>)>,
+    }
+
+    impl ServiceNetworkRegistry for MockNetworkRegistry {
+        fn connect(
+            &self,
+            _service_id: &str,
+        ) -> Result<Box<dyn ServiceNetworkSender>, ServiceConnectionError> {
+            Ok(Box::new(MockNetworkSender {
+                tx: self.tx.clone(),
+            }))
+        }
+
+        fn disconnect(&self, _service_id: &str) -> Result<(), ServiceDisconnectionError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockNetworkSender {
+        tx: Sender<(String, Vec<u8>)>,
+    }
+
+    impl ServiceNetworkSender for MockNetworkSender {
+        fn send(&self, recipient: &str, message: &[u8]) -> Result<(), ServiceSendError> {
+            self.tx
+                .send((recipient.to_string(), message.to_vec()))
+                .expect("Unable to send test message");
+            Ok(())
+        }
+
+        fn send_and_await(
+            &self,
+            _recipient: &str,
+            _message: &[u8],
+        ) -> Result<Vec<u8>, ServiceSendError> {
+            panic!("MockNetworkSender.send_and_await unexpectedly called")
+        }
+
+        fn reply(
+            &self,
+            _message_origin: &ServiceMessageContext,
+            _message: &[u8],
+        ) -> Result<(), ServiceSendError> {
+            panic!("MockNetworkSender.reply unexpectedly called")
+        }
+
+        fn clone_box(&self) -> Box<dyn ServiceNetworkSender> {
+            Box::new(self.clone())
+        }
+
+        fn send_with_sender(
+            &mut self,
+            _recipient: &str,
+            _message: &[u8],
+            _sender: &str,
+        ) -> Result<(), ServiceSendError> {
+            panic!("MockNetworkSender.send_with_sender unexpectedly called")
+        }
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The services and service factory backed by service adapters.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+
+use crate::error::InternalError;
+use crate::mesh::Mesh;
+use crate::protos::service_adapter::{
+    SACircuitMessage, SALifecycleCommand_Command, ServiceAdapterMessageType,
+};
+use crate::service::instance::{
+    FactoryCreateError, OrchestratableService, OrchestratableServiceFactory, ServiceDestroyError,
+    ServiceError, ServiceFactory, ServiceInstance, ServiceMessageContext, ServiceNetworkRegistry,
+    ServiceStartError, ServiceStopError,
+};
+use crate::threading::lock::lock_or_recover;
+
+use super::{
+    lifecycle_command, send_message, PendingCommand, ServiceAdapterError, Shared, StartedService,
+};
+
+/// Creates services that are run by service adapters.
+pub struct ExternalServiceFactory {
+    mesh: Mesh,
+    shared: Arc<Shared>,
+}
+
+impl ExternalServiceFactory {
+    pub(super) fn new(mesh: Mesh, shared: Arc<Shared>) -> Self {
+        Self { mesh, shared }
+    }
+
+    fn create_external_service(
+        &self,
+        service_id: String,
+        service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<ExternalService, FactoryCreateError> {
+        if !self.shared.service_types.iter().any(|t| t == service_type) {
+            return Err(FactoryCreateError::InvalidArguments(format!(
+                "{} is not a service type run by a service adapter",
+                service_type
+            )));
+        }
+
+        Ok(ExternalService {
+            circuit: circuit_id.to_string(),
+            service_id,
+            service_type: service_type.to_string(),
+            arguments: args,
+            mesh: self.mesh.clone(),
+            shared: self.shared.clone(),
+        })
+    }
+}
+
+impl ServiceFactory for ExternalServiceFactory {
+    fn available_service_types(&self) -> &[String] {
+        &self.shared.service_types
+    }
+
+    fn create(
+        &self,
+        service_id: String,
+        service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn ServiceInstance>, FactoryCreateError> {
+        self.create_external_service(service_id, service_type, circuit_id, args)
+            .map(|service| Box::new(service) as Box<dyn ServiceInstance>)
+    }
+}
+
+impl OrchestratableServiceFactory for ExternalServiceFactory {
+    fn create_orchestratable_service(
+        &self,
+        service_id: String,
+        service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn OrchestratableService>, FactoryCreateError> {
+        self.create_external_service(service_id, service_type, circuit_id, args)
+            .map(|service| Box::new(service) as Box<dyn OrchestratableService>)
+    }
+}
+
+/// A service run by a service adapter.
+///
+/// Lifecycle operations are sent to the adapter that has claimed the service's type and block
+/// until the adapter reports the result. If no adapter is connected, the service is started once
+/// an adapter claims its type.
+#[derive(Clone)]
+pub struct ExternalService {
+    circuit: String,
+    service_id: String,
+    service_type: String,
+    arguments: HashMap<String, String>,
+    mesh: Mesh,
+    shared: Arc<Shared>,
+}
+
+impl ExternalService {
+    fn key(&self) -> (String, String) {
+        (self.circuit.clone(), self.service_id.clone())
+    }
+
+    /// Sends a lifecycle command to the adapter running this service and waits for the result.
+    fn send_command(&self, command: SALifecycleCommand_Command) -> Result<(), ServiceAdapterError> {
+        let lifecycle_command = lifecycle_command(
+            command,
+            &self.circuit,
+            &self.service_id,
+            &self.service_type,
+            &self.arguments,
+        );
+        let correlation_id = lifecycle_command.get_correlation_id().to_string();
+
+        let (sender, receiver) = mpsc::channel();
+        let adapter_id = {
+            let mut state = lock_or_recover(&self.shared.state, "service adapter state");
+            let adapter_id = state
+                .claims
+                .get(&self.service_type)
+                .cloned()
+                .ok_or_else(|| ServiceAdapterError::NotConnected(self.service_type.clone()))?;
+            state.pending.insert(
+                correlation_id.clone(),
+                PendingCommand {
+                    adapter_id: adapter_id.clone(),
+                    sender,
+                },
+            );
+            adapter_id
+        };
+
+        if let Err(err) = send_message(
+            &self.mesh,
+            &adapter_id,
+            ServiceAdapterMessageType::SA_LIFECYCLE_COMMAND,
+            &lifecycle_command,
+        ) {
+            self.remove_pending(&correlation_id);
+            return Err(err);
+        }
+
+        match receiver.recv_timeout(self.shared.command_timeout) {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(msg)) => Err(ServiceAdapterError::Rejected(msg)),
+            Err(RecvTimeoutError::Timeout) => {
+                self.remove_pending(&correlation_id);
+                Err(ServiceAdapterError::Timeout(format!(
+                    "no result for {:?} of service {}::{} within {:?}",
+                    command, self.circuit, self.service_id, self.shared.command_timeout
+                )))
+            }
+            // The adapter was disconnected before reporting the result
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(ServiceAdapterError::NotConnected(self.service_type.clone()))
+            }
+        }
+    }
+
+    fn remove_pending(&self, correlation_id: &str) {
+        lock_or_recover(&self.shared.state, "service adapter state")
+            .pending
+            .remove(correlation_id);
+    }
+}
+
+impl ServiceInstance for ExternalService {
+    fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    fn service_type(&self) -> &str {
+        &self.service_type
+    }
+
+    fn start(
+        &mut self,
+        service_registry: &dyn ServiceNetworkRegistry,
+    ) -> Result<(), ServiceStartError> {
+        if lock_or_recover(&self.shared.state, "service adapter state")
+            .services
+            .contains_key(&self.key())
+        {
+            return Err(ServiceStartError::AlreadyStarted);
+        }
+
+        let network_sender = service_registry
+            .connect(&self.service_id)
+            .map_err(ServiceStartError::UnableToConnect)?;
+
+        lock_or_recover(&self.shared.state, "service adapter state")
+            .services
+            .insert(
+                self.key(),
+                StartedService {
+                    service_type: self.service_type.clone(),
+                    arguments: self.arguments.clone(),
+                    network_sender,
+                },
+            );
+
+        match self.send_command(SALifecycleCommand_Command::START) {
+            Ok(()) => Ok(()),
+            Err(ServiceAdapterError::NotConnected(_)) => {
+                info!(
+                    "No service adapter is connected for service {}::{}; it will be started \
+                     when an adapter for {} connects",
+                    self.circuit, self.service_id, self.service_type
+                );
+                Ok(())
+            }
+            Err(err) => {
+                lock_or_recover(&self.shared.state, "service adapter state")
+                    .services
+                    .remove(&self.key());
+                if let Err(err) = service_registry.disconnect(&self.service_id) {
+                    warn!("Unable to disconnect service {}: {}", self.service_id, err);
+                }
+                Err(ServiceStartError::Internal(err.to_string()))
+            }
+        }
+    }
+
+    fn stop(
+        &mut self,
+        service_registry: &dyn ServiceNetworkRegistry,
+    ) -> Result<(), ServiceStopError> {
+        lock_or_recover(&self.shared.state, "service adapter state")
+            .services
+            .remove(&self.key())
+            .ok_or(ServiceStopError::NotStarted)?;
+
+        let result = match self.send_command(SALifecycleCommand_Command::STOP) {
+            Ok(()) | Err(ServiceAdapterError::NotConnected(_)) => Ok(()),
+            Err(err) => Err(ServiceStopError::Internal(Box::new(err))),
+        };
+
+        service_registry
+            .disconnect(&self.service_id)
+            .map_err(ServiceStopError::UnableToDisconnect)?;
+
+        result
+    }
+
+    fn destroy(self: Box<Self>) -> Result<(), ServiceDestroyError> {
+        if lock_or_recover(&self.shared.state, "service adapter state")
+            .services
+            .contains_key(&self.key())
+        {
+            return Err(ServiceDestroyError::NotStopped);
+        }
+
+        match self.send_command(SALifecycleCommand_Command::DESTROY) {
+            Ok(()) | Err(ServiceAdapterError::NotConnected(_)) => Ok(()),
+            Err(err) => Err(ServiceDestroyError::Internal(Box::new(err))),
+        }
+    }
+
+    fn purge(&mut self) -> Result<(), InternalError> {
+        self.send_command(SALifecycleCommand_Command::PURGE)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn handle_message(
+        &self,
+        message_bytes: &[u8],
+        message_context: &ServiceMessageContext,
+    ) -> Result<(), ServiceError> {
+        let adapter_id = lock_or_recover(&self.shared.state, "service adapter state")
+            .claims
+            .get(&self.service_type)
+            .cloned()
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(ServiceAdapterError::NotConnected(
+                    self.service_type.clone(),
+                )))
+            })?;
+
+        let mut circuit_message = SACircuitMessage::new();
+        circuit_message.set_circuit(message_context.circuit.clone());
+        circuit_message.set_service_id(self.service_id.clone());
+        circuit_message.set_sender(message_context.sender.clone());
+        circuit_message.set_correlation_id(message_context.correlation_id.clone());
+        circuit_message.set_payload(message_bytes.to_vec());
+
+        send_message(
+            &self.mesh,
+            &adapter_id,
+            ServiceAdapterMessageType::SA_CIRCUIT_MESSAGE,
+            &circuit_message,
+        )
+        .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl OrchestratableService for ExternalService {
+    fn clone_box(&self) -> Box<dyn OrchestratableService> {
+        Box::new(self.clone())
+    }
+
+    fn as_service(&self) -> &dyn ServiceInstance {
+        self
+    }
+}
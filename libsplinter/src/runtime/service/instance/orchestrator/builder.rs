@@ -14,11 +14,18 @@
 
 //! Builder for constructing new service orchestrators.
 
+#[cfg(feature = "service-orchestrator-limits")]
+use std::collections::HashMap;
+
 use crate::error::InvalidStateError;
 use crate::service::instance::OrchestratableServiceFactory;
 use crate::transport::Connection;
 
+#[cfg(feature = "circuit-ready-hooks")]
+use super::ready_hook::CircuitReadyHook;
 use super::runnable::RunnableServiceOrchestrator;
+#[cfg(feature = "service-orchestrator-limits")]
+use super::supervisor::{ResourceLimits, RestartPolicy};
 
 const DEFAULT_INCOMING_CAPACITY: usize = 512;
 const DEFAULT_OUTGOING_CAPACITY: usize = 512;
@@ -32,6 +39,12 @@ pub struct ServiceOrchestratorBuilder {
     outgoing_capacity: Option<usize>,
     channel_capacity: Option<usize>,
     service_factories: Vec<Box<dyn OrchestratableServiceFactory>>,
+    #[cfg(feature = "circuit-ready-hooks")]
+    circuit_ready_hooks: Vec<Box<dyn CircuitReadyHook>>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    restart_policies: HashMap<String, RestartPolicy>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    resource_limits: HashMap<String, ResourceLimits>,
 }
 
 impl ServiceOrchestratorBuilder {
@@ -87,6 +100,45 @@ impl ServiceOrchestratorBuilder {
         self
     }
 
+    /// Adds a hook which will be invoked once all of the orchestrator's services on a circuit have
+    /// been initialized.
+    ///
+    /// This function may be called more than once to add additional hooks.
+    #[cfg(feature = "circuit-ready-hooks")]
+    pub fn with_circuit_ready_hook(mut self, hook: Box<dyn CircuitReadyHook>) -> Self {
+        self.circuit_ready_hooks.push(hook);
+
+        self
+    }
+
+    /// Sets the restart policy for services of the given type.
+    ///
+    /// Services of types without a restart policy are not restarted when they fail.
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn with_restart_policy<S: Into<String>>(
+        mut self,
+        service_type: S,
+        restart_policy: RestartPolicy,
+    ) -> Self {
+        self.restart_policies
+            .insert(service_type.into(), restart_policy);
+        self
+    }
+
+    /// Sets the resource limits for services of the given type.
+    ///
+    /// Services of types without resource limits use the default limits.
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn with_resource_limits<S: Into<String>>(
+        mut self,
+        service_type: S,
+        resource_limits: ResourceLimits,
+    ) -> Self {
+        self.resource_limits
+            .insert(service_type.into(), resource_limits);
+        self
+    }
+
     /// Construct the RunnableServiceOrchestrator.
     ///
     /// # Errors
@@ -116,6 +168,12 @@ impl ServiceOrchestratorBuilder {
             connection,
             service_factories: self.service_factories,
             supported_service_types,
+            #[cfg(feature = "circuit-ready-hooks")]
+            circuit_ready_hooks: self.circuit_ready_hooks,
+            #[cfg(feature = "service-orchestrator-limits")]
+            restart_policies: self.restart_policies,
+            #[cfg(feature = "service-orchestrator-limits")]
+            resource_limits: self.resource_limits,
             incoming_capacity,
             outgoing_capacity,
             channel_capacity,
@@ -14,11 +14,17 @@
 
 //! Dynamic service orchestration.
 
+#[cfg(feature = "service-orchestrator-adapters")]
+mod adapter;
 mod builder;
 #[cfg(feature = "rest-api-actix-web-1")]
 mod endpoint_provider;
 mod error;
+#[cfg(feature = "circuit-ready-hooks")]
+mod ready_hook;
 mod runnable;
+#[cfg(feature = "service-orchestrator-limits")]
+mod supervisor;
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -43,14 +49,29 @@ use crate::runtime::service::instance::StandardServiceNetworkRegistry;
 use crate::service::instance::ServiceMessageContext;
 use crate::service::instance::{OrchestratableService, OrchestratableServiceFactory};
 use crate::threading::lifecycle::ShutdownHandle;
+use crate::threading::lock::lock_or_recover;
 use crate::transport::Connection;
 
+#[cfg(feature = "service-orchestrator-adapters")]
+pub use self::adapter::{
+    ExternalService, ExternalServiceFactory, ServiceAdapterError, ServiceAdapterInfo,
+    ServiceAdapterRegistry, ServiceAdapterRegistryBuilder, ServiceAdapterRegistryShutdownHandle,
+    SERVICE_ADAPTER_PROTOCOL_VERSION,
+};
 pub use self::builder::ServiceOrchestratorBuilder;
 pub use self::error::{
     AddServiceError, InitializeServiceError, ListServicesError, NewOrchestratorError,
     OrchestratorError, ShutdownServiceError,
 };
+#[cfg(feature = "circuit-ready-hooks")]
+pub use self::ready_hook::CircuitReadyHook;
 pub use self::runnable::RunnableServiceOrchestrator;
+#[cfg(feature = "service-orchestrator-limits")]
+use self::supervisor::Supervisor;
+#[cfg(feature = "service-orchestrator-limits")]
+pub use self::supervisor::{
+    ResourceLimits, RestartPolicy, ServiceHealth, ServiceHealthMonitor, ServiceStatus,
+};
 
 // Recv timeout in secs
 const TIMEOUT_SEC: u64 = 2;
@@ -92,6 +113,12 @@ pub struct ServiceOrchestrator {
     /// A (ServiceDefinition, ManagedService) map of services that have been stopped, but yet to
     /// be completely destroyed
     stopped_services: Arc<Mutex<HashMap<ServiceDefinition, Box<dyn OrchestratableService>>>>,
+    /// Hooks invoked once all of the orchestrator's services on a circuit have been initialized
+    #[cfg(feature = "circuit-ready-hooks")]
+    circuit_ready_hooks: Vec<Box<dyn CircuitReadyHook>>,
+    /// Applies restart policies and resource limits to the running services
+    #[cfg(feature = "service-orchestrator-limits")]
+    supervisor: Arc<Supervisor>,
 
     /// `running` and `join_handles` are used to shutdown the orchestrator's background threads
     running: Arc<AtomicBool>,
@@ -175,15 +202,52 @@ impl ServiceOrchestrator {
             .start(&registry)
             .map_err(|err| InitializeServiceError::InitializationFailed(Box::new(err)))?;
 
+        #[cfg(feature = "service-orchestrator-limits")]
+        self.supervisor.track(&service_definition);
+
         // Save the service.
-        self.services
-            .lock()
-            .map_err(|_| InitializeServiceError::LockPoisoned)?
+        lock_or_recover(&self.services, "orchestrator services")
             .insert(service_definition, ManagedService { service, registry });
 
         Ok(())
     }
 
+    /// Notify the registered [`CircuitReadyHook`]s that all of this orchestrator's services on the
+    /// given circuit have been initialized.
+    ///
+    /// This should be called once every service on the circuit that is run by this orchestrator
+    /// has been successfully initialized. Hooks are not invoked if the orchestrator does not run
+    /// any services on the circuit. Errors returned by hooks are logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the orchestrator's services cannot be listed.
+    #[cfg(feature = "circuit-ready-hooks")]
+    pub fn notify_circuit_ready(&self, circuit_id: &str) -> Result<(), InternalError> {
+        if self.circuit_ready_hooks.is_empty() {
+            return Ok(());
+        }
+
+        let services = self
+            .list_services(vec![circuit_id.to_string()], vec![])
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        if services.is_empty() {
+            return Ok(());
+        }
+
+        for hook in &self.circuit_ready_hooks {
+            if let Err(err) = hook.circuit_ready(circuit_id, &services) {
+                error!(
+                    "Circuit ready hook failed for circuit {}: {}",
+                    circuit_id, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     /// Stop the specified service.
     pub fn stop_service(
         &self,
@@ -192,20 +256,23 @@ impl ServiceOrchestrator {
         let ManagedService {
             mut service,
             registry,
-        } = self
-            .services
-            .lock()
-            .map_err(|_| ShutdownServiceError::LockPoisoned)?
+        } = lock_or_recover(&self.services, "orchestrator services")
             .remove(service_definition)
             .ok_or(ShutdownServiceError::UnknownService)?;
 
-        service.stop(&registry).map_err(|err| {
-            ShutdownServiceError::ShutdownFailed((service_definition.clone(), Box::new(err)))
-        })?;
+        // A service whose restart failed has already been stopped
+        #[cfg(feature = "service-orchestrator-limits")]
+        let started = self.supervisor.untrack(service_definition);
+        #[cfg(not(feature = "service-orchestrator-limits"))]
+        let started = true;
 
-        self.stopped_services
-            .lock()
-            .map_err(|_| ShutdownServiceError::LockPoisoned)?
+        if started {
+            service.stop(&registry).map_err(|err| {
+                ShutdownServiceError::ShutdownFailed((service_definition.clone(), Box::new(err)))
+            })?;
+        }
+
+        lock_or_recover(&self.stopped_services, "orchestrator stopped services")
             .insert(service_definition.clone(), service);
 
         Ok(())
@@ -216,13 +283,9 @@ impl ServiceOrchestrator {
         &self,
         service_definition: &ServiceDefinition,
     ) -> Result<(), InternalError> {
-        if let Some(mut service) = self
-            .stopped_services
-            .lock()
-            .map_err(|_| {
-                InternalError::with_message("Orchestrator stopped service lock was poisoned".into())
-            })?
-            .remove(service_definition)
+        if let Some(mut service) =
+            lock_or_recover(&self.stopped_services, "orchestrator stopped services")
+                .remove(service_definition)
         {
             service.purge()
         } else {
@@ -230,13 +293,27 @@ impl ServiceOrchestrator {
         }
     }
 
+    /// Returns the number of bytes of persistent state that purging the specified service would
+    /// remove, if known.
+    ///
+    /// Only stopped services may be purged, so running or unknown services report `None`.
+    #[cfg(feature = "circuit-purge-preview")]
+    pub fn service_state_size(
+        &self,
+        service_definition: &ServiceDefinition,
+    ) -> Result<Option<u64>, InternalError> {
+        match lock_or_recover(&self.stopped_services, "orchestrator stopped services")
+            .get(service_definition)
+        {
+            Some(service) => service.state_size(),
+            None => Ok(None),
+        }
+    }
+
     /// Shut down (stop and destroy) all services managed by this `ServiceOrchestrator` and single
     /// the `ServiceOrchestrator` to shutdown
     pub fn shutdown_all_services(&self) -> Result<(), ShutdownServiceError> {
-        let mut services = self
-            .services
-            .lock()
-            .map_err(|_| ShutdownServiceError::LockPoisoned)?;
+        let mut services = lock_or_recover(&self.services, "orchestrator services");
 
         for (service_definition, managed_service) in services.drain() {
             let ManagedService {
@@ -262,10 +339,7 @@ impl ServiceOrchestrator {
         circuits: Vec<String>,
         service_types: Vec<String>,
     ) -> Result<Vec<ServiceDefinition>, ListServicesError> {
-        Ok(self
-            .services
-            .lock()
-            .map_err(|_| ListServicesError::LockPoisoned)?
+        Ok(lock_or_recover(&self.services, "orchestrator services")
             .iter()
             .filter_map(|(service, _)| {
                 if (circuits.is_empty() || circuits.contains(&service.circuit))
@@ -319,9 +393,7 @@ impl ServiceOrchestrator {
         )?;
 
         // Save the service to `stopped_services`.
-        self.stopped_services
-            .lock()
-            .map_err(|_| AddServiceError::LockPoisoned)?
+        lock_or_recover(&self.stopped_services, "orchestrator stopped services")
             .insert(service_definition, service);
 
         Ok(())
@@ -340,6 +412,13 @@ impl ServiceOrchestrator {
     pub fn services(&self) -> Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>> {
         self.services.clone()
     }
+
+    /// Returns a monitor for the restart and resource limit status of the orchestrator's
+    /// services.
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn service_health_monitor(&self) -> ServiceHealthMonitor {
+        ServiceHealthMonitor::new(self.supervisor.clone())
+    }
 }
 
 pub struct JoinHandles<T> {
@@ -370,23 +449,17 @@ pub struct ServiceOrchestratorShutdownHandle {
 
 impl ShutdownHandle for ServiceOrchestratorShutdownHandle {
     fn signal_shutdown(&mut self) {
-        match self.services.lock() {
-            Ok(mut services) => {
-                for (service_definition, managed_service) in services.drain() {
-                    let ManagedService {
-                        mut service,
-                        registry,
-                    } = managed_service;
-                    if let Err(err) = service.stop(&registry) {
-                        error!("Unable to stop service {}: {}", service_definition, err);
-                    }
-                    if let Err(err) = service.destroy() {
-                        error!("Unable to destroy service {}: {}", service_definition, err);
-                    }
-                }
+        let mut services = lock_or_recover(&self.services, "orchestrator services");
+        for (service_definition, managed_service) in services.drain() {
+            let ManagedService {
+                mut service,
+                registry,
+            } = managed_service;
+            if let Err(err) = service.stop(&registry) {
+                error!("Unable to stop service {}: {}", service_definition, err);
             }
-            Err(_) => {
-                error!("Service orchestrator service lock was poisoned; unable to cleanly shutdown")
+            if let Err(err) = service.destroy() {
+                error!("Unable to destroy service {}: {}", service_definition, err);
             }
         }
         self.running.store(false, Ordering::SeqCst);
@@ -529,6 +602,7 @@ fn run_inbound_loop(
     services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
     inbound_receiver: Receiver<Result<(CircuitMessageType, Vec<u8>), channel::RecvError>>,
     inbound_running: Arc<AtomicBool>,
+    #[cfg(feature = "service-orchestrator-limits")] supervisor: Arc<Supervisor>,
 ) -> Result<(), OrchestratorError> {
     let timeout = Duration::from_secs(TIMEOUT_SEC);
     while inbound_running.load(Ordering::SeqCst) {
@@ -547,20 +621,30 @@ fn run_inbound_loop(
                 let mut admin_direct_message: AdminDirectMessage = Message::parse_from_bytes(&msg)
                     .map_err(|err| OrchestratorError::Internal(Box::new(err)))?;
 
-                let services = services
-                    .lock()
-                    .map_err(|_| OrchestratorError::LockPoisoned)?;
+                let services = lock_or_recover(&services, "orchestrator services");
 
                 match services.iter().find_map(|(service_def, managed_service)| {
                     if service_def.circuit == admin_direct_message.get_circuit()
                         && service_def.service_id == admin_direct_message.get_recipient()
                     {
-                        Some(&managed_service.service)
+                        Some((service_def, &managed_service.service))
                     } else {
                         None
                     }
                 }) {
-                    Some(service) => {
+                    Some((service_def, service)) => {
+                        #[cfg(feature = "service-orchestrator-limits")]
+                        {
+                            if !supervisor_accepts(
+                                &supervisor,
+                                service_def,
+                                CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                                msg,
+                            ) {
+                                continue;
+                            }
+                        }
+
                         let msg_context = ServiceMessageContext {
                             sender: admin_direct_message.take_sender(),
                             circuit: admin_direct_message.take_circuit(),
@@ -570,7 +654,12 @@ fn run_inbound_loop(
                         if let Err(err) =
                             service.handle_message(admin_direct_message.get_payload(), &msg_context)
                         {
-                            error!("unable to handle admin direct message: {}", err);
+                            error!(
+                                "unable to handle admin direct message for {}: {}",
+                                service_def, err
+                            );
+                            #[cfg(feature = "service-orchestrator-limits")]
+                            supervisor.report(service_def, &err);
                         }
                     }
                     None => warn!(
@@ -585,20 +674,30 @@ fn run_inbound_loop(
                     Message::parse_from_bytes(&msg)
                         .map_err(|err| OrchestratorError::Internal(Box::new(err)))?;
 
-                let services = services
-                    .lock()
-                    .map_err(|_| OrchestratorError::LockPoisoned)?;
+                let services = lock_or_recover(&services, "orchestrator services");
 
                 match services.iter().find_map(|(service_def, managed_service)| {
                     if service_def.circuit == circuit_direct_message.get_circuit()
                         && service_def.service_id == circuit_direct_message.get_recipient()
                     {
-                        Some(&managed_service.service)
+                        Some((service_def, &managed_service.service))
                     } else {
                         None
                     }
                 }) {
-                    Some(service) => {
+                    Some((service_def, service)) => {
+                        #[cfg(feature = "service-orchestrator-limits")]
+                        {
+                            if !supervisor_accepts(
+                                &supervisor,
+                                service_def,
+                                CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                                msg,
+                            ) {
+                                continue;
+                            }
+                        }
+
                         let msg_context = ServiceMessageContext {
                             sender: circuit_direct_message.take_sender(),
                             circuit: circuit_direct_message.take_circuit(),
@@ -608,7 +707,12 @@ fn run_inbound_loop(
                         if let Err(err) = service
                             .handle_message(circuit_direct_message.get_payload(), &msg_context)
                         {
-                            error!("unable to handle direct message: {}", err);
+                            error!(
+                                "unable to handle direct message for {}: {}",
+                                service_def, err
+                            );
+                            #[cfg(feature = "service-orchestrator-limits")]
+                            supervisor.report(service_def, &err);
                         }
                     }
                     None => warn!(
@@ -627,6 +731,48 @@ fn run_inbound_loop(
     Ok(())
 }
 
+/// Returns `true` if the service should handle the message now. Messages for a restarting service
+/// are queued for delivery after the restart, and messages for a failed service are dropped.
+#[cfg(feature = "service-orchestrator-limits")]
+fn supervisor_accepts(
+    supervisor: &Supervisor,
+    service_def: &ServiceDefinition,
+    message_type: CircuitMessageType,
+    msg: Vec<u8>,
+) -> bool {
+    match supervisor.status(service_def) {
+        ServiceStatus::Running => true,
+        ServiceStatus::Restarting => {
+            if !supervisor.enqueue(service_def, (message_type, msg)) {
+                warn!(
+                    "Message queue for restarting service {} is full; dropping message",
+                    service_def
+                );
+            }
+            false
+        }
+        ServiceStatus::Failed => {
+            warn!("Service {} has failed; dropping message", service_def);
+            false
+        }
+    }
+}
+
+/// Periodically restarts failed services and enforces resource limits until the orchestrator
+/// shuts down.
+#[cfg(feature = "service-orchestrator-limits")]
+fn run_supervisor_loop(
+    services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+    supervisor: Arc<Supervisor>,
+    supervisor_running: Arc<AtomicBool>,
+) -> Result<(), OrchestratorError> {
+    while supervisor_running.load(Ordering::SeqCst) {
+        supervisor.check(&services);
+        thread::sleep(Duration::from_secs(1));
+    }
+    Ok(())
+}
+
 fn run_outgoing_loop(
     outgoing_mesh: Mesh,
     outgoing_running: Arc<AtomicBool>,
@@ -0,0 +1,58 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hooks for in-process notification of circuit readiness.
+
+use crate::error::InternalError;
+
+use super::ServiceDefinition;
+
+/// A hook that is invoked by the [`ServiceOrchestrator`] once all of its services on a circuit have
+/// been successfully initialized.
+///
+/// Hooks allow components running in the same process as the orchestrator to learn when a
+/// circuit's services may be used, without polling the admin service.
+///
+/// [`ServiceOrchestrator`]: super::ServiceOrchestrator
+pub trait CircuitReadyHook: Send + Sync {
+    /// Called once the orchestrator's services on the given circuit have been initialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The ID of the circuit whose services are ready
+    /// * `services` - The services on the circuit that are run by the orchestrator
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the hook fails. The error is logged by the orchestrator,
+    /// but does not prevent other hooks from being invoked.
+    fn circuit_ready(
+        &self,
+        circuit_id: &str,
+        services: &[ServiceDefinition],
+    ) -> Result<(), InternalError>;
+}
+
+impl<F> CircuitReadyHook for F
+where
+    F: Fn(&str, &[ServiceDefinition]) -> Result<(), InternalError> + Send + Sync,
+{
+    fn circuit_ready(
+        &self,
+        circuit_id: &str,
+        services: &[ServiceDefinition],
+    ) -> Result<(), InternalError> {
+        (*self)(circuit_id, services)
+    }
+}
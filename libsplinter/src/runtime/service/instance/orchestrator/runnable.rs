@@ -26,6 +26,10 @@ use crate::mesh::Mesh;
 use crate::network::reply::InboundRouter;
 use crate::transport::Connection;
 
+#[cfg(feature = "circuit-ready-hooks")]
+use super::ready_hook::CircuitReadyHook;
+#[cfg(feature = "service-orchestrator-limits")]
+use super::supervisor::{ResourceLimits, RestartPolicy, Supervisor};
 use super::{JoinHandles, OrchestratableServiceFactory, ServiceOrchestrator};
 
 /// A runnable service orchestrator is configured, but not started ServiceOrchestrator. It may only
@@ -37,6 +41,12 @@ pub struct RunnableServiceOrchestrator {
     pub(super) channel_capacity: usize,
     pub(super) service_factories: Vec<Box<dyn OrchestratableServiceFactory>>,
     pub(super) supported_service_types: Vec<String>,
+    #[cfg(feature = "circuit-ready-hooks")]
+    pub(super) circuit_ready_hooks: Vec<Box<dyn CircuitReadyHook>>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub(super) restart_policies: HashMap<String, RestartPolicy>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub(super) resource_limits: HashMap<String, ResourceLimits>,
 }
 
 impl RunnableServiceOrchestrator {
@@ -68,6 +78,9 @@ impl RunnableServiceOrchestrator {
 
         let running = Arc::new(AtomicBool::new(true));
 
+        #[cfg(feature = "service-orchestrator-limits")]
+        let supervisor = Arc::new(Supervisor::new(self.restart_policies, self.resource_limits));
+
         let (network_sender, network_receiver) = crossbeam_channel::bounded(self.channel_capacity);
         let (inbound_sender, inbound_receiver) = crossbeam_channel::bounded(self.channel_capacity);
         let inbound_router = InboundRouter::new(Box::new(inbound_sender));
@@ -96,12 +109,18 @@ impl RunnableServiceOrchestrator {
         // Start thread that handles messages that do not have a matching correlation id.
         let inbound_services = services.clone();
         let inbound_running = running.clone();
+        #[cfg(feature = "service-orchestrator-limits")]
+        let inbound_supervisor = supervisor.clone();
         let inbound_join_handle = thread::Builder::new()
             .name("Orchestrator Inbound".into())
             .spawn(move || {
-                if let Err(err) =
-                    super::run_inbound_loop(inbound_services, inbound_receiver, inbound_running)
-                {
+                if let Err(err) = super::run_inbound_loop(
+                    inbound_services,
+                    inbound_receiver,
+                    inbound_running,
+                    #[cfg(feature = "service-orchestrator-limits")]
+                    inbound_supervisor,
+                ) {
                     error!(
                         "Terminating orchestrator inbound thread due to error: {}",
                         err
@@ -132,16 +151,40 @@ impl RunnableServiceOrchestrator {
             })
             .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
+        // Start thread that restarts failed services and enforces resource limits.
+        #[cfg(feature = "service-orchestrator-limits")]
+        let supervisor_join_handle = {
+            let supervisor_services = services.clone();
+            let loop_supervisor = supervisor.clone();
+            let supervisor_running = running.clone();
+            thread::Builder::new()
+                .name("Orchestrator Supervisor".into())
+                .spawn(move || {
+                    super::run_supervisor_loop(
+                        supervisor_services,
+                        loop_supervisor,
+                        supervisor_running,
+                    )
+                })
+                .map_err(|err| InternalError::from_source(Box::new(err)))?
+        };
+
         let join_handles = JoinHandles::new(vec![
             incoming_join_handle,
             inbound_join_handle,
             outgoing_join_handle,
+            #[cfg(feature = "service-orchestrator-limits")]
+            supervisor_join_handle,
         ]);
 
         info!("Service orchestrator started");
         Ok(ServiceOrchestrator {
             services,
             stopped_services,
+            #[cfg(feature = "circuit-ready-hooks")]
+            circuit_ready_hooks: self.circuit_ready_hooks,
+            #[cfg(feature = "service-orchestrator-limits")]
+            supervisor,
             service_factories,
             supported_service_types,
             network_sender,
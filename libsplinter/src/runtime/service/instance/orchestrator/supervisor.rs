@@ -0,0 +1,567 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Restart policies and resource guards for orchestrated services.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use protobuf::Message;
+
+use crate::protos::circuit::{AdminDirectMessage, CircuitDirectMessage, CircuitMessageType};
+use crate::service::instance::{ServiceError, ServiceMessageContext};
+use crate::threading::lock::lock_or_recover;
+
+use super::{ManagedService, ServiceDefinition};
+
+const DEFAULT_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_MESSAGE_QUEUE: usize = 128;
+
+/// Determines how the orchestrator restarts a service that has failed.
+///
+/// A service is considered failed when it can no longer handle messages, for example because it
+/// was not started or one of its locks was poisoned. Each restart waits for the backoff, which
+/// doubles after every attempt up to the maximum backoff. Once `max_restarts` is exceeded, the
+/// service is marked as failed and no longer receives messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RestartPolicy {
+    max_restarts: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    /// Creates a policy that restarts a service up to `max_restarts` times.
+    pub fn new(max_restarts: u32) -> Self {
+        Self {
+            max_restarts,
+            backoff: DEFAULT_RESTART_BACKOFF,
+            max_backoff: DEFAULT_MAX_RESTART_BACKOFF,
+        }
+    }
+
+    /// Sets the time to wait before the first restart attempt.
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self.max_backoff = self.max_backoff.max(backoff);
+        self
+    }
+
+    /// Sets the longest time to wait before a restart attempt.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff.max(self.backoff);
+        self
+    }
+
+    pub fn max_restarts(&self) -> u32 {
+        self.max_restarts
+    }
+
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Returns the time to wait before the given restart attempt, starting at 1.
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+/// Limits on the resources an orchestrated service may use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceLimits {
+    max_message_queue: usize,
+    max_state_size: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of messages that are held for a service while it is restarting; further
+    /// messages are dropped.
+    pub fn with_max_message_queue(mut self, max_message_queue: usize) -> Self {
+        self.max_message_queue = max_message_queue;
+        self
+    }
+
+    /// Sets the number of bytes of persistent state a service may hold before it is marked as
+    /// failed and no longer receives messages.
+    pub fn with_max_state_size(mut self, max_state_size: u64) -> Self {
+        self.max_state_size = Some(max_state_size);
+        self
+    }
+
+    pub fn max_message_queue(&self) -> usize {
+        self.max_message_queue
+    }
+
+    pub fn max_state_size(&self) -> Option<u64> {
+        self.max_state_size
+    }
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_message_queue: DEFAULT_MAX_MESSAGE_QUEUE,
+            max_state_size: None,
+        }
+    }
+}
+
+/// The supervision status of an orchestrated service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceStatus {
+    /// The service is handling messages.
+    Running,
+    /// The service has failed and is waiting to be restarted; messages are queued.
+    Restarting,
+    /// The service has failed and will not be restarted; messages are dropped.
+    Failed,
+}
+
+impl fmt::Display for ServiceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServiceStatus::Running => f.write_str("running"),
+            ServiceStatus::Restarting => f.write_str("restarting"),
+            ServiceStatus::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+/// A snapshot of the supervision state of an orchestrated service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceHealth {
+    service: ServiceDefinition,
+    status: ServiceStatus,
+    restarts: u32,
+    queued_messages: usize,
+    last_error: Option<String>,
+}
+
+impl ServiceHealth {
+    pub fn service(&self) -> &ServiceDefinition {
+        &self.service
+    }
+
+    pub fn status(&self) -> ServiceStatus {
+        self.status
+    }
+
+    /// The number of times the service has been restarted.
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    /// The number of messages held for the service while it is restarting.
+    pub fn queued_messages(&self) -> usize {
+        self.queued_messages
+    }
+
+    /// The most recent failure reported for the service, if any.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+/// Provides the supervision state of the services run by a `ServiceOrchestrator`.
+#[derive(Clone)]
+pub struct ServiceHealthMonitor {
+    supervisor: Arc<Supervisor>,
+}
+
+impl ServiceHealthMonitor {
+    pub(super) fn new(supervisor: Arc<Supervisor>) -> Self {
+        Self { supervisor }
+    }
+
+    /// Returns the health of every service run by the orchestrator.
+    pub fn list_service_health(&self) -> Vec<ServiceHealth> {
+        self.supervisor.list_service_health()
+    }
+}
+
+struct SupervisedService {
+    status: ServiceStatus,
+    started: bool,
+    restarts: u32,
+    next_restart: Option<Instant>,
+    queue: VecDeque<(CircuitMessageType, Vec<u8>)>,
+    last_error: Option<String>,
+}
+
+impl SupervisedService {
+    fn new() -> Self {
+        Self {
+            status: ServiceStatus::Running,
+            started: true,
+            restarts: 0,
+            next_restart: None,
+            queue: VecDeque::new(),
+            last_error: None,
+        }
+    }
+}
+
+/// Tracks the status of orchestrated services and applies the configured restart policies and
+/// resource limits.
+pub(super) struct Supervisor {
+    restart_policies: HashMap<String, RestartPolicy>,
+    resource_limits: HashMap<String, ResourceLimits>,
+    services: Mutex<HashMap<ServiceDefinition, SupervisedService>>,
+}
+
+impl Supervisor {
+    pub(super) fn new(
+        restart_policies: HashMap<String, RestartPolicy>,
+        resource_limits: HashMap<String, ResourceLimits>,
+    ) -> Self {
+        Self {
+            restart_policies,
+            resource_limits,
+            services: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts supervising a service that has just been started.
+    pub(super) fn track(&self, service: &ServiceDefinition) {
+        lock_or_recover(&self.services, "supervised services")
+            .insert(service.clone(), SupervisedService::new());
+    }
+
+    /// Stops supervising a service, returning whether the service is still started.
+    pub(super) fn untrack(&self, service: &ServiceDefinition) -> bool {
+        lock_or_recover(&self.services, "supervised services")
+            .remove(service)
+            .map(|supervised| supervised.started)
+            .unwrap_or(true)
+    }
+
+    /// Returns the status of the given service; services that are not supervised are running.
+    pub(super) fn status(&self, service: &ServiceDefinition) -> ServiceStatus {
+        lock_or_recover(&self.services, "supervised services")
+            .get(service)
+            .map(|supervised| supervised.status)
+            .unwrap_or(ServiceStatus::Running)
+    }
+
+    /// Holds a message for a restarting service, returning `false` if the queue is full.
+    pub(super) fn enqueue(
+        &self,
+        service: &ServiceDefinition,
+        message: (CircuitMessageType, Vec<u8>),
+    ) -> bool {
+        let max_message_queue = self.limits_for(service).max_message_queue();
+        let mut services = lock_or_recover(&self.services, "supervised services");
+        match services.get_mut(service) {
+            Some(supervised) if supervised.queue.len() < max_message_queue => {
+                supervised.queue.push_back(message);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Records the result of a service handling a message.
+    ///
+    /// Errors that leave the service unable to handle further messages mark it for a restart, if
+    /// a restart policy is configured for its type.
+    pub(super) fn report(&self, service: &ServiceDefinition, err: &ServiceError) {
+        let fatal = matches!(
+            err,
+            ServiceError::PoisonedLock(_) | ServiceError::NotStarted
+        );
+        let policy = self.restart_policies.get(&service.service_type);
+
+        let mut services = lock_or_recover(&self.services, "supervised services");
+        let supervised = match services.get_mut(service) {
+            Some(supervised) => supervised,
+            None => return,
+        };
+        supervised.last_error = Some(err.to_string());
+
+        if let (true, Some(policy), ServiceStatus::Running) = (fatal, policy, supervised.status) {
+            schedule_restart(service, supervised, policy);
+        }
+    }
+
+    /// Restarts services whose backoff has elapsed and enforces the resource limits. This is run
+    /// periodically by the orchestrator's supervisor thread.
+    pub(super) fn check(
+        &self,
+        managed_services: &Mutex<HashMap<ServiceDefinition, ManagedService>>,
+    ) {
+        let mut managed_services = lock_or_recover(managed_services, "orchestrator services");
+        let mut services = lock_or_recover(&self.services, "supervised services");
+        let now = Instant::now();
+
+        for (definition, supervised) in services.iter_mut() {
+            let managed = match managed_services.get_mut(definition) {
+                Some(managed) => managed,
+                None => continue,
+            };
+
+            match supervised.status {
+                ServiceStatus::Running => {
+                    let max_state_size = match self.limits_for(definition).max_state_size() {
+                        Some(max_state_size) => max_state_size,
+                        None => continue,
+                    };
+                    match managed.service.state_size() {
+                        Ok(Some(size)) if size > max_state_size => {
+                            let reason = format!(
+                                "State size of {} bytes exceeds the limit of {} bytes",
+                                size, max_state_size
+                            );
+                            error!("Service {} failed: {}", definition, reason);
+                            supervised.status = ServiceStatus::Failed;
+                            supervised.last_error = Some(reason);
+                        }
+                        Ok(_) => (),
+                        Err(err) => {
+                            warn!(
+                                "Unable to get state size of service {}: {}",
+                                definition, err
+                            )
+                        }
+                    }
+                }
+                ServiceStatus::Restarting => {
+                    if supervised
+                        .next_restart
+                        .map(|next_restart| next_restart > now)
+                        .unwrap_or(false)
+                    {
+                        continue;
+                    }
+                    restart(
+                        definition,
+                        supervised,
+                        managed,
+                        self.restart_policies.get(&definition.service_type),
+                    );
+                }
+                ServiceStatus::Failed => (),
+            }
+        }
+    }
+
+    fn limits_for(&self, service: &ServiceDefinition) -> ResourceLimits {
+        self.resource_limits
+            .get(&service.service_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn list_service_health(&self) -> Vec<ServiceHealth> {
+        lock_or_recover(&self.services, "supervised services")
+            .iter()
+            .map(|(definition, supervised)| ServiceHealth {
+                service: definition.clone(),
+                status: supervised.status,
+                restarts: supervised.restarts,
+                queued_messages: supervised.queue.len(),
+                last_error: supervised.last_error.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Marks the service as restarting, or as failed if it has been restarted too many times.
+fn schedule_restart(
+    definition: &ServiceDefinition,
+    supervised: &mut SupervisedService,
+    policy: &RestartPolicy,
+) {
+    if supervised.restarts >= policy.max_restarts() {
+        error!(
+            "Service {} failed after {} restarts; it will no longer receive messages",
+            definition, supervised.restarts
+        );
+        supervised.status = ServiceStatus::Failed;
+        supervised.next_restart = None;
+        supervised.queue.clear();
+    } else {
+        supervised.restarts += 1;
+        let backoff = policy.backoff_for(supervised.restarts);
+        warn!(
+            "Service {} failed; restarting in {:?} (attempt {} of {})",
+            definition,
+            backoff,
+            supervised.restarts,
+            policy.max_restarts()
+        );
+        supervised.status = ServiceStatus::Restarting;
+        supervised.next_restart = Some(Instant::now() + backoff);
+    }
+}
+
+/// Stops and starts the service, then delivers the messages that were queued while it was
+/// restarting.
+fn restart(
+    definition: &ServiceDefinition,
+    supervised: &mut SupervisedService,
+    managed: &mut ManagedService,
+    policy: Option<&RestartPolicy>,
+) {
+    if supervised.started {
+        if let Err(err) = managed.service.stop(&managed.registry) {
+            debug!("Unable to stop service {} for restart: {}", definition, err);
+        }
+        supervised.started = false;
+    }
+
+    if let Err(err) = managed.service.start(&managed.registry) {
+        supervised.last_error = Some(err.to_string());
+        match policy {
+            Some(policy) => schedule_restart(definition, supervised, policy),
+            None => supervised.status = ServiceStatus::Failed,
+        }
+        return;
+    }
+
+    info!("Restarted service {}", definition);
+    supervised.started = true;
+    supervised.status = ServiceStatus::Running;
+    supervised.next_restart = None;
+
+    for (message_type, message) in supervised.queue.drain(..) {
+        if let Err(err) = deliver_queued(managed, message_type, &message) {
+            error!(
+                "Unable to handle queued message for service {}: {}",
+                definition, err
+            );
+        }
+    }
+}
+
+fn deliver_queued(
+    managed: &ManagedService,
+    message_type: CircuitMessageType,
+    message: &[u8],
+) -> Result<(), ServiceError> {
+    let (payload, context) = match message_type {
+        CircuitMessageType::ADMIN_DIRECT_MESSAGE => {
+            let mut msg: AdminDirectMessage = Message::parse_from_bytes(message)
+                .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+            let context = ServiceMessageContext {
+                sender: msg.take_sender(),
+                circuit: msg.take_circuit(),
+                correlation_id: msg.take_correlation_id(),
+            };
+            (msg.take_payload(), context)
+        }
+        _ => {
+            let mut msg: CircuitDirectMessage = Message::parse_from_bytes(message)
+                .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+            let context = ServiceMessageContext {
+                sender: msg.take_sender(),
+                circuit: msg.take_circuit(),
+                correlation_id: msg.take_correlation_id(),
+            };
+            (msg.take_payload(), context)
+        }
+    };
+    managed.service.handle_message(&payload, &context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the restart backoff doubles with every attempt and is capped at the maximum.
+    #[test]
+    fn test_restart_policy_backoff() {
+        let policy = RestartPolicy::new(5)
+            .with_backoff(Duration::from_secs(1))
+            .with_max_backoff(Duration::from_secs(5));
+
+        assert_eq!(policy.backoff_for(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff_for(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff_for(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff_for(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff_for(40), Duration::from_secs(5));
+    }
+
+    /// Verify that a fatal error schedules a restart until the maximum number of restarts is
+    /// reached, that messages are only queued up to the limit, and that non-fatal errors are
+    /// recorded without changing the status.
+    #[test]
+    fn test_report_and_enqueue() {
+        let definition = ServiceDefinition {
+            circuit: "abcde-01234".into(),
+            service_id: "a000".into(),
+            service_type: "test".into(),
+        };
+        let mut restart_policies = HashMap::new();
+        restart_policies.insert(
+            "test".to_string(),
+            RestartPolicy::new(1).with_backoff(Duration::from_secs(60)),
+        );
+        let mut resource_limits = HashMap::new();
+        resource_limits.insert(
+            "test".to_string(),
+            ResourceLimits::new().with_max_message_queue(1),
+        );
+        let supervisor = Supervisor::new(restart_policies, resource_limits);
+        supervisor.track(&definition);
+
+        supervisor.report(
+            &definition,
+            &ServiceError::InvalidMessageFormat(Box::new(
+                crate::error::InternalError::with_message("bad message".into()),
+            )),
+        );
+        assert_eq!(supervisor.status(&definition), ServiceStatus::Running);
+
+        supervisor.report(&definition, &ServiceError::NotStarted);
+        assert_eq!(supervisor.status(&definition), ServiceStatus::Restarting);
+
+        assert!(supervisor.enqueue(
+            &definition,
+            (CircuitMessageType::CIRCUIT_DIRECT_MESSAGE, vec![1])
+        ));
+        assert!(!supervisor.enqueue(
+            &definition,
+            (CircuitMessageType::CIRCUIT_DIRECT_MESSAGE, vec![2])
+        ));
+
+        let health = supervisor.list_service_health();
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].restarts(), 1);
+        assert_eq!(health[0].queued_messages(), 1);
+
+        // Simulate the restart attempt failing; the policy only allows one restart
+        {
+            let mut services = lock_or_recover(&supervisor.services, "supervised services");
+            let supervised = services.get_mut(&definition).expect("service not tracked");
+            schedule_restart(&definition, supervised, &RestartPolicy::new(1));
+        }
+        assert_eq!(supervisor.status(&definition), ServiceStatus::Failed);
+        assert_eq!(supervisor.list_service_health()[0].queued_messages(), 0);
+    }
+}
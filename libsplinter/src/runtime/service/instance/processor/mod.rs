@@ -39,7 +39,7 @@ use crate::protos::prelude::*;
 use crate::service::instance::{ServiceInstance, ServiceMessageContext};
 use crate::threading::lifecycle::ShutdownHandle;
 use crate::transport::Connection;
-use crate::{rwlock_read_unwrap, rwlock_write_unwrap};
+use crate::{rwlock_read_recover, rwlock_write_recover};
 
 use self::registry::StandardServiceNetworkRegistry;
 use self::sender::{ProcessorMessage, ServiceMessage};
@@ -170,7 +170,7 @@ impl ServiceProcessor {
         let running = Arc::new(AtomicBool::new(true));
         let mut join_handles = vec![];
         for service in self.services.into_iter() {
-            let mut shared_state = rwlock_write_unwrap!(self.shared_state);
+            let mut shared_state = rwlock_write_recover!(self.shared_state);
             let service_id = service.service_id().to_string();
 
             let (send, recv) = crossbeam_channel::bounded(self.channel_capacity);
@@ -321,7 +321,7 @@ impl ServiceProcessor {
         // Service processor
         let do_shutdown = Box::new(move || {
             debug!("Shutting down service processor");
-            let shared_state = rwlock_write_unwrap!(shutdown_shared_state);
+            let shared_state = rwlock_write_recover!(shutdown_shared_state);
             // send shutdown to the services and wait for join
             for (service_id, service_sender) in shared_state.services.iter() {
                 info!("Shutting down {}", service_id);
@@ -594,7 +594,7 @@ fn handle_circuit_direct_msg(
     direct_message: CircuitDirectMessage,
     shared_state: &Arc<RwLock<SharedState>>,
 ) -> Result<(), ServiceProcessorError> {
-    let shared_state = rwlock_read_unwrap!(shared_state);
+    let shared_state = rwlock_read_recover!(shared_state);
 
     if let Some(service_sender) = shared_state.services.get(direct_message.get_recipient()) {
         service_sender
@@ -617,7 +617,7 @@ fn handle_admin_direct_msg(
     admin_direct_message: AdminDirectMessage,
     shared_state: &Arc<RwLock<SharedState>>,
 ) -> Result<(), ServiceProcessorError> {
-    let shared_state = rwlock_read_unwrap!(shared_state);
+    let shared_state = rwlock_read_recover!(shared_state);
 
     if let Some(service_sender) = shared_state
         .services
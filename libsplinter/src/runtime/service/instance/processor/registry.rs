@@ -16,6 +16,8 @@ use protobuf::Message;
 use uuid::Uuid;
 
 use crate::network::reply::InboundRouter;
+#[cfg(feature = "service-protocol-version")]
+use crate::protocol::service_version::ProtocolVersionRange;
 use crate::protos::circuit::{
     CircuitMessageType, ServiceConnectRequest, ServiceConnectResponse,
     ServiceConnectResponse_Status, ServiceDisconnectRequest, ServiceDisconnectResponse,
@@ -34,6 +36,8 @@ pub struct StandardServiceNetworkRegistry {
     circuit: String,
     outgoing_sender: Sender<Vec<u8>>,
     inbound_router: InboundRouter<CircuitMessageType>,
+    #[cfg(feature = "service-protocol-version")]
+    protocol_versions: Option<ProtocolVersionRange>,
 }
 
 /// This is an implementation of ServiceNetworkRegistry that can be used by a standard service
@@ -48,8 +52,22 @@ impl StandardServiceNetworkRegistry {
             circuit,
             outgoing_sender,
             inbound_router,
+            #[cfg(feature = "service-protocol-version")]
+            protocol_versions: None,
         }
     }
+
+    /// Sets the range of service protocol versions supported by the services that connect through
+    /// this registry.
+    ///
+    /// The range is sent with each connection request, and the connection is rejected by the
+    /// splinter node if it does not support any version in the range. If no range is set, services
+    /// connect without a version.
+    #[cfg(feature = "service-protocol-version")]
+    pub fn with_protocol_versions(mut self, protocol_versions: ProtocolVersionRange) -> Self {
+        self.protocol_versions = Some(protocol_versions);
+        self
+    }
 }
 
 impl ServiceNetworkRegistry for StandardServiceNetworkRegistry {
@@ -64,6 +82,11 @@ impl ServiceNetworkRegistry for StandardServiceNetworkRegistry {
         connect_msg.set_circuit(self.circuit.to_string());
         connect_msg.set_service_id(service_id.to_string());
         connect_msg.set_correlation_id(correlation_id.clone());
+        #[cfg(feature = "service-protocol-version")]
+        if let Some(protocol_versions) = &self.protocol_versions {
+            connect_msg.set_protocol_min(protocol_versions.min());
+            connect_msg.set_protocol_max(protocol_versions.max());
+        }
 
         let connect_msg_bytes = connect_msg
             .write_to_bytes()
@@ -91,6 +114,15 @@ impl ServiceNetworkRegistry for StandardServiceNetworkRegistry {
             ));
         }
 
+        #[cfg(feature = "service-protocol-version")]
+        if self.protocol_versions.is_some() {
+            debug!(
+                "Service {} connected using service protocol version {}",
+                service_id,
+                response.get_protocol_version()
+            );
+        }
+
         if self.circuit == ADMIN_CIRCUIT_NAME {
             let admin_network_sender = AdminServiceNetworkSender::new(
                 self.outgoing_sender.clone(),
@@ -19,6 +19,8 @@ pub mod instance;
 mod lifecycle_executor;
 #[cfg(feature = "service-message-sender-factory-peer")]
 mod network_sender_factory;
+#[cfg(feature = "service-replay-protection")]
+mod replay;
 #[cfg(feature = "service-timer")]
 mod timer;
 
@@ -60,5 +62,7 @@ pub use lifecycle_executor::{
 };
 #[cfg(feature = "service-message-sender-factory-peer")]
 pub use network_sender_factory::NetworkMessageSenderFactory;
+#[cfg(feature = "service-replay-protection")]
+pub use replay::REPLAY_WINDOW_ARG;
 #[cfg(feature = "service-timer")]
 pub use timer::Timer;
@@ -16,14 +16,17 @@ use std::sync::Arc;
 
 use protobuf::Message;
 
-use crate::circuit::routing::{RoutingTableReader, ServiceId as RoutingServiceId};
+use crate::circuit::routing::{Circuit, RoutingTableReader, ServiceId as RoutingServiceId};
 use crate::error::InternalError;
 use crate::network::dispatch::{MessageSender as NetworkDispatchMessageSender, PeerId};
-use crate::peer::PeerTokenPair;
+use crate::peer::{PeerAuthorizationToken, PeerTokenPair};
 use crate::protos::circuit::{CircuitDirectMessage, CircuitMessage, CircuitMessageType};
 use crate::protos::network::{NetworkMessage, NetworkMessageType};
 use crate::service::{FullyQualifiedServiceId, MessageSender, MessageSenderFactory, ServiceId};
 
+#[cfg(feature = "service-replay-protection")]
+use super::replay::{replay_window_from_args, ReplaySequencer};
+
 #[derive(Clone)]
 pub struct NetworkMessageSenderFactory<S>
 where
@@ -32,6 +35,8 @@ where
     node_id: Arc<str>,
     network_dispatch_message_sender: S,
     routing_table_reader: Box<dyn RoutingTableReader>,
+    #[cfg(feature = "service-replay-protection")]
+    sequencer: Arc<ReplaySequencer>,
 }
 
 impl<S> NetworkMessageSenderFactory<S>
@@ -47,6 +52,8 @@ where
             node_id: node_id.into(),
             network_dispatch_message_sender,
             routing_table_reader,
+            #[cfg(feature = "service-replay-protection")]
+            sequencer: Arc::new(ReplaySequencer::new()),
         }
     }
 }
@@ -64,6 +71,8 @@ where
             scope: scope.clone(),
             network_dispatch_message_sender: self.network_dispatch_message_sender.clone(),
             routing_table_reader: self.routing_table_reader.clone(),
+            #[cfg(feature = "service-replay-protection")]
+            sequencer: self.sequencer.clone(),
         }))
     }
 
@@ -77,26 +86,16 @@ pub struct NetworkMessageSender<S> {
     scope: FullyQualifiedServiceId,
     network_dispatch_message_sender: S,
     routing_table_reader: Box<dyn RoutingTableReader>,
+    #[cfg(feature = "service-replay-protection")]
+    sequencer: Arc<ReplaySequencer>,
 }
 
-impl<S> MessageSender<Vec<u8>> for NetworkMessageSender<S>
+impl<S> NetworkMessageSender<S>
 where
     S: NetworkDispatchMessageSender<PeerId>,
 {
-    fn send(&self, to_service: &ServiceId, message: Vec<u8>) -> Result<(), InternalError> {
-        let mut direct_message = CircuitDirectMessage::new();
-        direct_message.set_circuit(self.scope.circuit_id().to_string());
-        direct_message.set_sender(self.scope.service_id().to_string());
-
-        direct_message.set_recipient(to_service.as_str().to_string());
-        direct_message.set_payload(message);
-
-        let bytes = direct_message
-            .write_to_bytes()
-            .map_err(|err| InternalError::from_source(Box::new(err)))?;
-
-        let circuit = self
-            .routing_table_reader
+    fn get_circuit(&self) -> Result<Circuit, InternalError> {
+        self.routing_table_reader
             .get_circuit(self.scope.circuit_id().as_str())
             .map_err(|err| InternalError::from_source(Box::new(err)))?
             .ok_or_else(|| {
@@ -104,26 +103,61 @@ where
                     "Circuit {} is not routable",
                     self.scope.circuit_id(),
                 ))
-            })?;
+            })
+    }
 
-        let service = self
-            .routing_table_reader
-            .get_service(&RoutingServiceId::new(
-                self.scope.circuit_id().to_string(),
-                to_service.to_string(),
-            ))
+    fn get_local_peer_id(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<PeerAuthorizationToken, InternalError> {
+        self.routing_table_reader
+            .get_node(&self.node_id)
             .map_err(|err| InternalError::from_source(Box::new(err)))?
             .ok_or_else(|| {
                 InternalError::with_message(format!(
-                    "Service {}::{} is not routable",
-                    self.scope.circuit_id(),
-                    to_service
+                    "Unable to lookup local node with node id {}",
+                    self.node_id,
                 ))
-            })?;
+            })?
+            .get_peer_auth_token(circuit.authorization_type())
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    /// Sends the message to a service on the node with the given ID, using the already resolved
+    /// circuit and local peer ID.
+    ///
+    /// If the recipient requires replay protection, the message is prefixed with the next
+    /// sequence number for the recipient.
+    fn send_to_node(
+        &self,
+        circuit: &Circuit,
+        local_peer_id: &PeerAuthorizationToken,
+        to_service: &str,
+        to_node_id: &str,
+        #[cfg(feature = "service-replay-protection")] to_service_args: &[(String, String)],
+        message: Vec<u8>,
+    ) -> Result<(), InternalError> {
+        #[cfg(feature = "service-replay-protection")]
+        let message = if replay_window_from_args(to_service_args)?.is_some() {
+            self.sequencer.seal(&self.scope, to_service, message)?
+        } else {
+            message
+        };
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit(self.scope.circuit_id().to_string());
+        direct_message.set_sender(self.scope.service_id().to_string());
+
+        direct_message.set_recipient(to_service.to_string());
+        direct_message.set_payload(message);
+
+        let bytes = direct_message
+            .write_to_bytes()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
         let remote_peer_id = self
             .routing_table_reader
-            .get_node(service.node_id())
+            .get_node(to_node_id)
             .map_err(|err| InternalError::from_source(Box::new(err)))?
             .ok_or_else(|| {
                 InternalError::with_message(format!(
@@ -135,20 +169,8 @@ where
             .get_peer_auth_token(circuit.authorization_type())
             .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
-        let local_peer_id = self
-            .routing_table_reader
-            .get_node(&self.node_id)
-            .map_err(|err| InternalError::from_source(Box::new(err)))?
-            .ok_or_else(|| {
-                InternalError::with_message(format!(
-                    "Unable to lookup local node with node id {}",
-                    self.node_id,
-                ))
-            })?
-            .get_peer_auth_token(circuit.authorization_type())
-            .map_err(|err| InternalError::from_source(Box::new(err)))?;
-
-        let target_peer_id: PeerId = PeerTokenPair::new(remote_peer_id, local_peer_id).into();
+        let target_peer_id: PeerId =
+            PeerTokenPair::new(remote_peer_id, local_peer_id.clone()).into();
 
         let msg = create_message(bytes, CircuitMessageType::CIRCUIT_DIRECT_MESSAGE)
             .map_err(|err| InternalError::from_source(Box::new(err)))?;
@@ -163,6 +185,72 @@ where
     }
 }
 
+impl<S> MessageSender<Vec<u8>> for NetworkMessageSender<S>
+where
+    S: NetworkDispatchMessageSender<PeerId>,
+{
+    fn send(&self, to_service: &ServiceId, message: Vec<u8>) -> Result<(), InternalError> {
+        let circuit = self.get_circuit()?;
+
+        let service = self
+            .routing_table_reader
+            .get_service(&RoutingServiceId::new(
+                self.scope.circuit_id().to_string(),
+                to_service.to_string(),
+            ))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .ok_or_else(|| {
+                InternalError::with_message(format!(
+                    "Service {}::{} is not routable",
+                    self.scope.circuit_id(),
+                    to_service
+                ))
+            })?;
+
+        let local_peer_id = self.get_local_peer_id(&circuit)?;
+
+        self.send_to_node(
+            &circuit,
+            &local_peer_id,
+            to_service.as_str(),
+            service.node_id(),
+            #[cfg(feature = "service-replay-protection")]
+            service.arguments(),
+            message,
+        )
+    }
+
+    #[cfg(feature = "service-message-sender-broadcast")]
+    fn broadcast(
+        &self,
+        message: Vec<u8>,
+    ) -> Result<Vec<(ServiceId, Result<(), InternalError>)>, InternalError> {
+        // Resolve the circuit and the local peer once, rather than for every recipient
+        let circuit = self.get_circuit()?;
+        let local_peer_id = self.get_local_peer_id(&circuit)?;
+
+        circuit
+            .roster()
+            .iter()
+            .filter(|service| service.service_id() != self.scope.service_id().as_str())
+            .map(|service| {
+                let service_id = ServiceId::new(service.service_id())
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                let result = self.send_to_node(
+                    &circuit,
+                    &local_peer_id,
+                    service.service_id(),
+                    service.node_id(),
+                    #[cfg(feature = "service-replay-protection")]
+                    service.arguments(),
+                    message.clone(),
+                );
+                Ok((service_id, result))
+            })
+            .collect()
+    }
+}
+
 /// Helper function for creating a NetworkMessage with a Circuit message type
 ///
 /// # Arguments
@@ -183,3 +271,111 @@ fn create_message(
     network_msg.set_payload(circuit_bytes);
     network_msg.write_to_bytes()
 }
+
+#[cfg(all(test, feature = "service-message-sender-broadcast"))]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use crate::circuit::routing::memory::RoutingTable;
+    use crate::circuit::routing::{
+        AuthorizationType, CircuitNode, RoutingTableWriter, Service as RoutingService,
+    };
+
+    /// Test that a broadcast is sent to every other service on the circuit and that a failure to
+    /// reach one recipient is reported without preventing delivery to the others.
+    ///
+    /// 1. Add a circuit with four services to the routing table; a000 is local, a001 and a002
+    ///    are on known peers and a003 is on a node that is not in the routing table
+    /// 2. Broadcast a message from a000
+    /// 3. Verify a result is returned for each of a001, a002 and a003, in roster order, and that
+    ///    only the message to a003 failed
+    /// 4. Verify the messages were sent to node-1 and node-2 only
+    #[test]
+    fn test_broadcast() {
+        let routing_table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(routing_table.clone());
+
+        let roster = (0..4)
+            .map(|x| {
+                RoutingService::new(
+                    format!("a00{}", x),
+                    "test".to_string(),
+                    format!("node-{}", x),
+                    vec![],
+                )
+            })
+            .collect::<Vec<_>>();
+        let nodes = (0..3)
+            .map(|x| CircuitNode::new(format!("node-{}", x), vec![], None))
+            .collect::<Vec<_>>();
+        let members = (0..3).map(|x| format!("node-{}", x)).collect::<Vec<_>>();
+
+        writer
+            .add_circuit(
+                "abcde-12345".to_string(),
+                Circuit::new(
+                    "abcde-12345".to_string(),
+                    roster,
+                    members,
+                    AuthorizationType::Trust,
+                ),
+                nodes,
+            )
+            .expect("Unable to add circuit");
+
+        let network_sender = MockSender::default();
+        let factory = NetworkMessageSenderFactory::new(
+            "node-0",
+            network_sender.clone(),
+            Box::new(routing_table),
+        );
+        let message_sender = factory
+            .new_message_sender(
+                &FullyQualifiedServiceId::new_from_string("abcde-12345::a000").unwrap(),
+            )
+            .expect("Unable to create message sender");
+
+        let results = message_sender
+            .broadcast(b"hello".to_vec())
+            .expect("Unable to broadcast");
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|(service_id, result)| (service_id.as_str(), result.is_ok()))
+                .collect::<Vec<_>>(),
+            vec![("a001", true), ("a002", true), ("a003", false)]
+        );
+
+        let local = PeerAuthorizationToken::from_peer_id("node-0");
+        let expected: Vec<PeerId> = vec![
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("node-1"),
+                local.clone(),
+            )
+            .into(),
+            PeerTokenPair::new(PeerAuthorizationToken::from_peer_id("node-2"), local).into(),
+        ];
+        assert_eq!(network_sender.sent_to(), expected);
+    }
+
+    #[derive(Clone, Default)]
+    struct MockSender {
+        outbound: Arc<Mutex<Vec<PeerId>>>,
+    }
+
+    impl MockSender {
+        fn sent_to(&self) -> Vec<PeerId> {
+            self.outbound.lock().expect("lock was poisoned").clone()
+        }
+    }
+
+    impl NetworkDispatchMessageSender<PeerId> for MockSender {
+        fn send(&self, id: PeerId, _message: Vec<u8>) -> Result<(), (PeerId, Vec<u8>)> {
+            self.outbound.lock().expect("lock was poisoned").push(id);
+            Ok(())
+        }
+    }
+}
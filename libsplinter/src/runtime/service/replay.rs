@@ -0,0 +1,317 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Replay protection for service-to-service messages.
+//!
+//! Replay protection is enabled for messages sent to a service whose arguments include
+//! `replay_window`, set to the number of sequence numbers the service will accept out of order.
+//! Messages to such a service are prefixed with a header containing the sender's epoch, the time
+//! in milliseconds at which its message sender factory was created, and a sequence number that
+//! increases with every message sent from one service to another.
+//!
+//! The receiving node's dispatcher rejects a message if its epoch is older than the latest epoch
+//! seen from the sender, or if its sequence number has already been seen or is older than the
+//! window. Replay state is held in memory and is lost when the node restarts.
+//!
+//! Rejected messages are counted by the `splinter.service.replays_rejected` metric, labeled by
+//! circuit and reason.
+
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryInto;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::InternalError;
+use crate::service::FullyQualifiedServiceId;
+
+/// The service argument used to enable replay protection and set the size of the replay window.
+pub const REPLAY_WINDOW_ARG: &str = "replay_window";
+
+const HEADER_LEN: usize = 16;
+
+/// Returns the replay window configured in the given service arguments, if any.
+pub(crate) fn replay_window_from_args(
+    arguments: &[(String, String)],
+) -> Result<Option<u64>, InternalError> {
+    arguments
+        .iter()
+        .find(|(key, _)| key == REPLAY_WINDOW_ARG)
+        .map(|(_, value)| match value.parse::<u64>() {
+            Ok(window) if window > 0 => Ok(window),
+            _ => Err(InternalError::with_message(format!(
+                "Invalid {} argument: must be a positive integer, got {}",
+                REPLAY_WINDOW_ARG, value
+            ))),
+        })
+        .transpose()
+}
+
+/// Prefixes a message with the replay protection header.
+fn seal(epoch: u64, sequence: u64, message: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + message.len());
+    bytes.extend_from_slice(&epoch.to_be_bytes());
+    bytes.extend_from_slice(&sequence.to_be_bytes());
+    bytes.extend(message);
+    bytes
+}
+
+/// Splits a message into its replay protection header and payload.
+fn open(mut message: Vec<u8>) -> Result<(u64, u64, Vec<u8>), InternalError> {
+    if message.len() < HEADER_LEN {
+        return Err(InternalError::with_message(
+            "Message is too short to contain a replay protection header".into(),
+        ));
+    }
+
+    let payload = message.split_off(HEADER_LEN);
+    // The lengths of the slices are guaranteed by the check above
+    let epoch = u64::from_be_bytes(message[..8].try_into().expect("header length checked"));
+    let sequence = u64::from_be_bytes(message[8..].try_into().expect("header length checked"));
+
+    Ok((epoch, sequence, payload))
+}
+
+/// Assigns sequence numbers to outgoing messages.
+pub(crate) struct ReplaySequencer {
+    epoch: u64,
+    sequences: Mutex<HashMap<(FullyQualifiedServiceId, String), u64>>,
+}
+
+impl Default for ReplaySequencer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplaySequencer {
+    pub fn new() -> Self {
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+
+        Self {
+            epoch,
+            sequences: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Prefixes a message from `from_service` to `to_service` with the next sequence number.
+    pub fn seal(
+        &self,
+        from_service: &FullyQualifiedServiceId,
+        to_service: &str,
+        message: Vec<u8>,
+    ) -> Result<Vec<u8>, InternalError> {
+        let mut sequences = self
+            .sequences
+            .lock()
+            .map_err(|_| InternalError::with_message("Sequence lock was poisoned".into()))?;
+        let sequence = sequences
+            .entry((from_service.clone(), to_service.to_string()))
+            .or_insert(0);
+        *sequence += 1;
+
+        Ok(seal(self.epoch, *sequence, message))
+    }
+}
+
+/// Why a message was rejected by a replay window.
+#[derive(Debug, PartialEq)]
+enum Rejection {
+    Duplicate,
+    StaleEpoch,
+    OutsideWindow,
+}
+
+impl Rejection {
+    fn label(&self) -> &'static str {
+        match self {
+            Rejection::Duplicate => "duplicate",
+            Rejection::StaleEpoch => "stale_epoch",
+            Rejection::OutsideWindow => "outside_window",
+        }
+    }
+}
+
+/// The sequence numbers seen from one sender in its latest epoch.
+struct ReplayWindow {
+    epoch: u64,
+    highest: u64,
+    seen: BTreeSet<u64>,
+}
+
+impl ReplayWindow {
+    fn new(epoch: u64) -> Self {
+        Self {
+            epoch,
+            highest: 0,
+            seen: BTreeSet::new(),
+        }
+    }
+
+    fn check(&mut self, epoch: u64, sequence: u64, size: u64) -> Result<(), Rejection> {
+        if epoch < self.epoch {
+            return Err(Rejection::StaleEpoch);
+        }
+        if epoch > self.epoch {
+            *self = ReplayWindow::new(epoch);
+        }
+
+        if sequence.saturating_add(size) <= self.highest {
+            return Err(Rejection::OutsideWindow);
+        }
+        if !self.seen.insert(sequence) {
+            return Err(Rejection::Duplicate);
+        }
+
+        if sequence > self.highest {
+            self.highest = sequence;
+            let floor = self.highest.saturating_sub(size);
+            self.seen = self.seen.split_off(&(floor + 1));
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks incoming messages against the replay window of each sender.
+#[derive(Default)]
+pub(crate) struct ReplayGuard {
+    windows: Mutex<HashMap<(FullyQualifiedServiceId, FullyQualifiedServiceId), ReplayWindow>>,
+}
+
+impl ReplayGuard {
+    /// Removes the replay protection header from a message, returning the payload if the message
+    /// has not been seen before, or `None` if it is a replay.
+    pub fn open(
+        &self,
+        to_service: &FullyQualifiedServiceId,
+        from_service: &FullyQualifiedServiceId,
+        window_size: u64,
+        message: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, InternalError> {
+        let (epoch, sequence, payload) = open(message)?;
+
+        let mut windows = self
+            .windows
+            .lock()
+            .map_err(|_| InternalError::with_message("Replay window lock was poisoned".into()))?;
+
+        let result = windows
+            .entry((to_service.clone(), from_service.clone()))
+            .or_insert_with(|| ReplayWindow::new(epoch))
+            .check(epoch, sequence, window_size);
+
+        match result {
+            Ok(()) => Ok(Some(payload)),
+            Err(rejection) => {
+                warn!(
+                    "Rejected replayed message from {} to {} ({}, sequence {})",
+                    from_service,
+                    to_service,
+                    rejection.label(),
+                    sequence
+                );
+                counter!(
+                    "splinter.service.replays_rejected",
+                    1,
+                    "circuit" => to_service.circuit_id().to_string(),
+                    "reason" => rejection.label()
+                );
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that messages are accepted once, in any order within the window, and that messages
+    /// older than the window or from an older epoch are rejected.
+    #[test]
+    fn test_replay_window() {
+        let mut window = ReplayWindow::new(10);
+
+        assert_eq!(Ok(()), window.check(10, 1, 4));
+        assert_eq!(Ok(()), window.check(10, 3, 4));
+        assert_eq!(Err(Rejection::Duplicate), window.check(10, 3, 4));
+        assert_eq!(Ok(()), window.check(10, 2, 4));
+        assert_eq!(Ok(()), window.check(10, 8, 4));
+        // 4 is outside of the window of 4 below the highest sequence number, 8
+        assert_eq!(Err(Rejection::OutsideWindow), window.check(10, 4, 4));
+        assert_eq!(Ok(()), window.check(10, 5, 4));
+        assert_eq!(Err(Rejection::StaleEpoch), window.check(9, 9, 4));
+
+        // A new epoch resets the window
+        assert_eq!(Ok(()), window.check(11, 1, 4));
+        assert_eq!(Err(Rejection::StaleEpoch), window.check(10, 9, 4));
+    }
+
+    /// Test that a message sealed by a sequencer is opened by a guard exactly once.
+    #[test]
+    fn test_seal_and_open() {
+        let from = FullyQualifiedServiceId::new_from_string("abcde-12345::a000")
+            .expect("Unable to parse service id");
+        let to = FullyQualifiedServiceId::new_from_string("abcde-12345::a001")
+            .expect("Unable to parse service id");
+
+        let sequencer = ReplaySequencer::new();
+        let guard = ReplayGuard::default();
+
+        let first = sequencer
+            .seal(&from, "a001", b"hello".to_vec())
+            .expect("Unable to seal message");
+        let second = sequencer
+            .seal(&from, "a001", b"world".to_vec())
+            .expect("Unable to seal message");
+
+        assert_eq!(
+            Some(b"world".to_vec()),
+            guard
+                .open(&to, &from, 8, second.clone())
+                .expect("Unable to open message")
+        );
+        assert_eq!(
+            Some(b"hello".to_vec()),
+            guard
+                .open(&to, &from, 8, first)
+                .expect("Unable to open message")
+        );
+        assert_eq!(
+            None,
+            guard
+                .open(&to, &from, 8, second)
+                .expect("Unable to open message")
+        );
+
+        assert!(guard.open(&to, &from, 8, vec![0; 4]).is_err());
+    }
+
+    /// Test that the replay window is read from service arguments.
+    #[test]
+    fn test_replay_window_from_args() {
+        assert_eq!(None, replay_window_from_args(&[]).expect("no window"));
+        assert_eq!(
+            Some(64),
+            replay_window_from_args(&[(REPLAY_WINDOW_ARG.to_string(), "64".to_string())])
+                .expect("window")
+        );
+        assert!(
+            replay_window_from_args(&[(REPLAY_WINDOW_ARG.to_string(), "0".to_string())]).is_err()
+        );
+    }
+}
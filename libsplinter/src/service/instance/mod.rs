@@ -18,6 +18,8 @@ mod message_context;
 mod network_registry;
 mod network_sender;
 mod orchestrator;
+#[cfg(feature = "service-arg-schema")]
+mod schema;
 mod service_instance;
 mod validation;
 
@@ -28,6 +30,8 @@ pub use message_context::ServiceMessageContext;
 pub use network_registry::ServiceNetworkRegistry;
 pub use network_sender::ServiceNetworkSender;
 pub use orchestrator::{OrchestratableService, OrchestratableServiceFactory};
+#[cfg(feature = "service-arg-schema")]
+pub use schema::{ServiceArgDefinition, ServiceArgSchema, ServiceArgType};
 pub use service_instance::ServiceInstance;
 pub use validation::ServiceArgValidator;
 
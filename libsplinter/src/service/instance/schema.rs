@@ -0,0 +1,362 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Declarative schemas for service arguments.
+//!
+//! A `ServiceArgSchema` describes the argument names and types a service type accepts. It is used
+//! by the admin service to reject proposals with unknown, missing, or malformed arguments before
+//! any service is started.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::InvalidArgumentError;
+
+use super::ServiceArgValidator;
+
+/// The maximum edit distance at which an unknown argument name is reported as a likely misspelling
+/// of a known argument.
+const SUGGESTION_MAX_DISTANCE: usize = 2;
+
+/// The type of a service argument's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceArgType {
+    /// Any string value.
+    String,
+    /// A non-negative integer.
+    Integer,
+    /// A floating point number.
+    Float,
+    /// Either `true` or `false`.
+    Boolean,
+    /// A list of strings, either as a JSON array or as comma-separated values.
+    List,
+}
+
+impl ServiceArgType {
+    fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            ServiceArgType::String => Ok(()),
+            ServiceArgType::Integer => value
+                .parse::<u64>()
+                .map(|_| ())
+                .map_err(|_| format!("{:?} is not a valid integer", value)),
+            ServiceArgType::Float => value
+                .parse::<f64>()
+                .map(|_| ())
+                .map_err(|_| format!("{:?} is not a valid number", value)),
+            ServiceArgType::Boolean => match value {
+                "true" | "false" => Ok(()),
+                _ => Err(format!("{:?} is not a valid boolean", value)),
+            },
+            ServiceArgType::List => {
+                if value.starts_with('[') {
+                    serde_json::from_str::<Vec<String>>(value)
+                        .map(|_| ())
+                        .map_err(|err| format!("{:?} is not a valid list: {}", value, err))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ServiceArgType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServiceArgType::String => f.write_str("string"),
+            ServiceArgType::Integer => f.write_str("integer"),
+            ServiceArgType::Float => f.write_str("float"),
+            ServiceArgType::Boolean => f.write_str("boolean"),
+            ServiceArgType::List => f.write_str("list"),
+        }
+    }
+}
+
+/// The definition of a single service argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceArgDefinition {
+    name: String,
+    arg_type: ServiceArgType,
+    required: bool,
+}
+
+impl ServiceArgDefinition {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn arg_type(&self) -> ServiceArgType {
+        self.arg_type
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+/// The set of arguments accepted by a service type.
+///
+/// The schema may be combined with a `ServiceArgValidator` for checks that cannot be expressed
+/// declaratively, such as the format of list entries; the validator is only run if the arguments
+/// match the schema.
+#[derive(Default)]
+pub struct ServiceArgSchema {
+    definitions: Vec<ServiceArgDefinition>,
+    validator: Option<Box<dyn ServiceArgValidator + Send>>,
+}
+
+impl ServiceArgSchema {
+    /// Constructs an empty schema, which accepts no arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an argument that must be provided.
+    pub fn with_required_arg(mut self, name: &str, arg_type: ServiceArgType) -> Self {
+        self.definitions.push(ServiceArgDefinition {
+            name: name.to_string(),
+            arg_type,
+            required: true,
+        });
+        self
+    }
+
+    /// Adds an argument that may be provided.
+    pub fn with_optional_arg(mut self, name: &str, arg_type: ServiceArgType) -> Self {
+        self.definitions.push(ServiceArgDefinition {
+            name: name.to_string(),
+            arg_type,
+            required: false,
+        });
+        self
+    }
+
+    /// Sets a validator that is run after the arguments have been checked against the schema.
+    pub fn with_validator(mut self, validator: Box<dyn ServiceArgValidator + Send>) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Returns the argument definitions of this schema.
+    pub fn definitions(&self) -> &[ServiceArgDefinition] {
+        &self.definitions
+    }
+
+    fn definition(&self, name: &str) -> Option<&ServiceArgDefinition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.name == name)
+    }
+
+    /// Returns the known argument name closest to the given unknown name, if it is close enough
+    /// to likely be a misspelling.
+    fn suggestion(&self, name: &str) -> Option<&str> {
+        self.definitions
+            .iter()
+            .map(|definition| (edit_distance(name, &definition.name), &definition.name))
+            .filter(|(distance, _)| *distance <= SUGGESTION_MAX_DISTANCE)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+impl fmt::Debug for ServiceArgSchema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ServiceArgSchema")
+            .field("definitions", &self.definitions)
+            .field("has_validator", &self.validator.is_some())
+            .finish()
+    }
+}
+
+impl ServiceArgValidator for ServiceArgSchema {
+    fn validate(&self, args: &HashMap<String, String>) -> Result<(), InvalidArgumentError> {
+        let mut names = args.keys().collect::<Vec<_>>();
+        names.sort();
+
+        for name in names {
+            let definition = match self.definition(name) {
+                Some(definition) => definition,
+                None => {
+                    let message = match self.suggestion(name) {
+                        Some(suggestion) => {
+                            format!("unknown argument; did you mean {:?}?", suggestion)
+                        }
+                        None => "unknown argument".to_string(),
+                    };
+                    return Err(InvalidArgumentError::new(name.as_str(), message));
+                }
+            };
+
+            definition.arg_type.check(&args[name]).map_err(|err| {
+                InvalidArgumentError::new(
+                    name.as_str(),
+                    format!("expected {}: {}", definition.arg_type, err),
+                )
+            })?;
+        }
+
+        if let Some(missing) = self
+            .definitions
+            .iter()
+            .find(|definition| definition.required && !args.contains_key(&definition.name))
+        {
+            return Err(InvalidArgumentError::new(
+                missing.name.as_str(),
+                "required argument not provided",
+            ));
+        }
+
+        match &self.validator {
+            Some(validator) => validator.validate(args),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b_chars.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b_chars.len() + 1];
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> ServiceArgSchema {
+        ServiceArgSchema::new()
+            .with_required_arg("admin_keys", ServiceArgType::List)
+            .with_required_arg("peer_services", ServiceArgType::List)
+            .with_optional_arg("coordinator_timeout", ServiceArgType::Integer)
+    }
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// Verify that arguments matching the schema are accepted, with or without optional arguments.
+    #[test]
+    fn test_valid_args() {
+        let schema = schema();
+
+        assert!(schema
+            .validate(&args(&[
+                ("admin_keys", r#"["key"]"#),
+                ("peer_services", "a,b"),
+            ]))
+            .is_ok());
+        assert!(schema
+            .validate(&args(&[
+                ("admin_keys", "key"),
+                ("peer_services", "a,b"),
+                ("coordinator_timeout", "30"),
+            ]))
+            .is_ok());
+    }
+
+    /// Verify that a misspelled argument is reported as unknown, with a suggestion of the argument
+    /// it likely should have been.
+    #[test]
+    fn test_misspelled_arg() {
+        let err = schema()
+            .validate(&args(&[("admin_key", "key"), ("peer_services", "a,b")]))
+            .expect_err("misspelled argument was accepted");
+
+        assert_eq!(err.argument(), "admin_key");
+        assert!(err.message().contains("did you mean \"admin_keys\""));
+
+        let err = schema()
+            .validate(&args(&[
+                ("admin_keys", "key"),
+                ("peer_services", "a,b"),
+                ("colour", "red"),
+            ]))
+            .expect_err("unknown argument was accepted");
+
+        assert_eq!(err.argument(), "colour");
+        assert_eq!(err.message(), "unknown argument");
+    }
+
+    /// Verify that missing required arguments and values of the wrong type are rejected.
+    #[test]
+    fn test_missing_and_mistyped_args() {
+        let err = schema()
+            .validate(&args(&[("admin_keys", "key")]))
+            .expect_err("missing argument was accepted");
+        assert_eq!(err.argument(), "peer_services");
+
+        let err = schema()
+            .validate(&args(&[
+                ("admin_keys", "key"),
+                ("peer_services", "a,b"),
+                ("coordinator_timeout", "soon"),
+            ]))
+            .expect_err("invalid integer was accepted");
+        assert_eq!(err.argument(), "coordinator_timeout");
+
+        let err = schema()
+            .validate(&args(&[("admin_keys", "[key"), ("peer_services", "a,b")]))
+            .expect_err("invalid list was accepted");
+        assert_eq!(err.argument(), "admin_keys");
+    }
+
+    /// Verify that the validator is run after the schema checks pass.
+    #[test]
+    fn test_with_validator() {
+        struct NoEmptyKeys;
+
+        impl ServiceArgValidator for NoEmptyKeys {
+            fn validate(&self, args: &HashMap<String, String>) -> Result<(), InvalidArgumentError> {
+                if args.get("admin_keys").map(|v| v.is_empty()).unwrap_or(true) {
+                    return Err(InvalidArgumentError::new("admin_keys", "must not be empty"));
+                }
+                Ok(())
+            }
+        }
+
+        let schema = schema().with_validator(Box::new(NoEmptyKeys));
+
+        assert!(schema
+            .validate(&args(&[("admin_keys", "key"), ("peer_services", "a")]))
+            .is_ok());
+        assert!(schema
+            .validate(&args(&[("admin_keys", ""), ("peer_services", "a")]))
+            .is_err());
+    }
+
+    /// Verify the edit distance calculation.
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("admin_keys", "admin_keys"), 0);
+        assert_eq!(edit_distance("admin_key", "admin_keys"), 1);
+        assert_eq!(edit_distance("admn_kyes", "admin_keys"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
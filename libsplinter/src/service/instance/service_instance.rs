@@ -59,6 +59,14 @@ pub trait ServiceInstance: Send {
     /// Purge any persistent state maintained by this service.
     fn purge(&mut self) -> Result<(), InternalError>;
 
+    /// Returns the number of bytes of persistent state that `purge` would remove, if known.
+    ///
+    /// The default implementation reports that the size is unknown.
+    #[cfg(feature = "circuit-purge-preview")]
+    fn state_size(&self) -> Result<Option<u64>, InternalError> {
+        Ok(None)
+    }
+
     /// Handle any incoming message intended for this service instance.
     ///
     /// Messages received by this service are provided in raw bytes.  The format of the service
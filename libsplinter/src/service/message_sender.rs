@@ -31,6 +31,29 @@ pub trait MessageSender<M> {
     /// * `to_service` - The service ID for the recipient of this message
     /// * `message` - The message to be sent
     fn send(&self, to_service: &ServiceId, message: M) -> Result<(), InternalError>;
+
+    /// Send a message of type `M` to every other service on the circuit
+    ///
+    /// The message is not sent to the sending service itself. Returns the delivery result for
+    /// each recipient, in roster order; a failure to deliver to one recipient does not prevent
+    /// delivery to the others. An error is only returned if the recipients could not be
+    /// determined.
+    ///
+    /// The default implementation returns an error, as broadcasting requires knowledge of the
+    /// circuit's roster.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to be sent
+    #[cfg(feature = "service-message-sender-broadcast")]
+    fn broadcast(
+        &self,
+        _message: M,
+    ) -> Result<Vec<(ServiceId, Result<(), InternalError>)>, InternalError> {
+        Err(InternalError::with_message(
+            "Broadcast is not supported by this message sender".into(),
+        ))
+    }
 }
 
 #[cfg(any(feature = "service-timer-handler", feature = "service-message-handler"))]
@@ -60,4 +83,12 @@ impl<'s, 'c, L, R> MessageSender<L> for IntoMessageSender<'s, 'c, L, R> {
         self.inner
             .send(to_service, self.converter.to_right(message)?)
     }
+
+    #[cfg(feature = "service-message-sender-broadcast")]
+    fn broadcast(
+        &self,
+        message: L,
+    ) -> Result<Vec<(ServiceId, Result<(), InternalError>)>, InternalError> {
+        self.inner.broadcast(self.converter.to_right(message)?)
+    }
 }
@@ -0,0 +1,235 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A database-backed [LeaseStore](super::LeaseStore), powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{Lease, LeaseStore, LeaseStoreError};
+
+use operations::{
+    acquire_lease::LeaseStoreAcquireLeaseOperation, release_lease::LeaseStoreReleaseLeaseOperation,
+    renew_lease::LeaseStoreRenewLeaseOperation, LeaseStoreOperations,
+};
+
+pub struct DieselLeaseStore<C: diesel::Connection + 'static> {
+    pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselLeaseStore<C> {
+    /// Creates a new `DieselLeaseStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: connection pool for the database
+    pub fn new(pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselLeaseStore { pool: pool.into() }
+    }
+
+    /// Create a new `DieselLeaseStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(pool: Arc<RwLock<Pool<ConnectionManager<C>>>>) -> Self {
+        Self { pool: pool.into() }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselLeaseStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl LeaseStore for DieselLeaseStore<diesel::pg::PgConnection> {
+    fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+        steal: bool,
+    ) -> Result<Lease, LeaseStoreError> {
+        self.pool.execute_write(|conn| {
+            LeaseStoreOperations::new(conn).acquire_lease(name, holder, expires_at, steal)
+        })
+    }
+
+    fn renew_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+    ) -> Result<bool, LeaseStoreError> {
+        self.pool.execute_write(|conn| {
+            LeaseStoreOperations::new(conn).renew_lease(name, holder, expires_at)
+        })
+    }
+
+    fn release_lease(&self, name: &str, holder: &str) -> Result<bool, LeaseStoreError> {
+        self.pool
+            .execute_write(|conn| LeaseStoreOperations::new(conn).release_lease(name, holder))
+    }
+
+    fn clone_box(&self) -> Box<dyn LeaseStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl LeaseStore for DieselLeaseStore<diesel::sqlite::SqliteConnection> {
+    fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+        steal: bool,
+    ) -> Result<Lease, LeaseStoreError> {
+        self.pool.execute_write(|conn| {
+            LeaseStoreOperations::new(conn).acquire_lease(name, holder, expires_at, steal)
+        })
+    }
+
+    fn renew_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+    ) -> Result<bool, LeaseStoreError> {
+        self.pool.execute_write(|conn| {
+            LeaseStoreOperations::new(conn).renew_lease(name, holder, expires_at)
+        })
+    }
+
+    fn release_lease(&self, name: &str, holder: &str) -> Result<bool, LeaseStoreError> {
+        self.pool
+            .execute_write(|conn| LeaseStoreOperations::new(conn).release_lease(name, holder))
+    }
+
+    fn clone_box(&self) -> Box<dyn LeaseStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a lease held by a live holder cannot be taken by another holder, but can be
+    /// renewed by its holder, stolen, and taken once released.
+    #[test]
+    fn sqlite_acquire_renew_and_release() {
+        let store = DieselLeaseStore::new(create_connection_pool_and_migrate());
+        let later = SystemTime::now() + Duration::from_secs(60);
+
+        let lease = store
+            .acquire_lease("splinterd", "alpha", later, false)
+            .expect("Unable to acquire lease");
+        assert_eq!(lease.holder(), "alpha");
+
+        let lease = store
+            .acquire_lease("splinterd", "beta", later, false)
+            .expect("Unable to attempt lease");
+        assert_eq!(lease.holder(), "alpha");
+
+        assert!(store
+            .renew_lease("splinterd", "alpha", later + Duration::from_secs(60))
+            .expect("Unable to renew lease"));
+        assert!(!store
+            .renew_lease("splinterd", "beta", later)
+            .expect("Unable to renew lease"));
+
+        let lease = store
+            .acquire_lease("splinterd", "beta", later, true)
+            .expect("Unable to steal lease");
+        assert_eq!(lease.holder(), "beta");
+        assert!(!store
+            .release_lease("splinterd", "alpha")
+            .expect("Unable to release lease"));
+        assert!(store
+            .release_lease("splinterd", "beta")
+            .expect("Unable to release lease"));
+
+        let lease = store
+            .acquire_lease("splinterd", "alpha", later, false)
+            .expect("Unable to acquire lease");
+        assert_eq!(lease.holder(), "alpha");
+    }
+
+    /// Verify that an expired lease may be taken by another holder.
+    #[test]
+    fn sqlite_acquire_expired() {
+        let store = DieselLeaseStore::new(create_connection_pool_and_migrate());
+
+        store
+            .acquire_lease(
+                "splinterd",
+                "alpha",
+                SystemTime::now() - Duration::from_secs(60),
+                false,
+            )
+            .expect("Unable to acquire lease");
+
+        let lease = store
+            .acquire_lease(
+                "splinterd",
+                "beta",
+                SystemTime::now() + Duration::from_secs(60),
+                false,
+            )
+            .expect("Unable to acquire lease");
+        assert_eq!(lease.holder(), "beta");
+        assert!(!lease.is_expired());
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::InternalError;
+use crate::store::lease::{Lease, LeaseStoreError};
+
+use super::schema::store_lease;
+
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "store_lease"]
+pub struct LeaseModel {
+    pub name: String,
+    pub holder: String,
+    pub expires_at: i64,
+}
+
+impl From<LeaseModel> for Lease {
+    fn from(model: LeaseModel) -> Self {
+        Lease::new(
+            &model.name,
+            &model.holder,
+            UNIX_EPOCH + Duration::from_secs(u64::try_from(model.expires_at).unwrap_or(0)),
+        )
+    }
+}
+
+/// Converts a time to the number of seconds since the epoch, as stored in the database.
+pub fn to_epoch_secs(time: SystemTime) -> Result<i64, LeaseStoreError> {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| LeaseStoreError::Internal(InternalError::from_source(Box::new(err))))?
+        .as_secs();
+    i64::try_from(secs)
+        .map_err(|err| LeaseStoreError::Internal(InternalError::from_source(Box::new(err))))
+}
@@ -0,0 +1,165 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::SystemTime;
+
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+use crate::store::lease::{
+    diesel::{
+        models::{to_epoch_secs, LeaseModel},
+        schema::store_lease,
+    },
+    Lease, LeaseStoreError,
+};
+
+use super::LeaseStoreOperations;
+
+pub(in crate::store::lease::diesel) trait LeaseStoreAcquireLeaseOperation {
+    fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+        steal: bool,
+    ) -> Result<Lease, LeaseStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LeaseStoreAcquireLeaseOperation for LeaseStoreOperations<'a, diesel::pg::PgConnection> {
+    fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+        steal: bool,
+    ) -> Result<Lease, LeaseStoreError> {
+        let now = to_epoch_secs(SystemTime::now())?;
+        let expires_at = to_epoch_secs(expires_at)?;
+        self.conn.transaction::<_, LeaseStoreError, _>(|| {
+            // Take the lease if it is free to take; the condition is part of the update so that
+            // two processes cannot both take an expired lease
+            let updated = if steal {
+                update(store_lease::table.find(name))
+                    .set((
+                        store_lease::holder.eq(holder),
+                        store_lease::expires_at.eq(expires_at),
+                    ))
+                    .execute(self.conn)?
+            } else {
+                update(
+                    store_lease::table.find(name).filter(
+                        store_lease::holder
+                            .eq(holder)
+                            .or(store_lease::expires_at.le(now)),
+                    ),
+                )
+                .set((
+                    store_lease::holder.eq(holder),
+                    store_lease::expires_at.eq(expires_at),
+                ))
+                .execute(self.conn)?
+            };
+
+            if updated == 0 {
+                let exists = store_lease::table
+                    .find(name)
+                    .count()
+                    .get_result::<i64>(self.conn)?
+                    > 0;
+
+                if !exists {
+                    insert_into(store_lease::table)
+                        .values(&LeaseModel {
+                            name: name.to_string(),
+                            holder: holder.to_string(),
+                            expires_at,
+                        })
+                        .execute(self.conn)?;
+                }
+            }
+
+            Ok(store_lease::table
+                .find(name)
+                .first::<LeaseModel>(self.conn)?
+                .into())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LeaseStoreAcquireLeaseOperation
+    for LeaseStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+        steal: bool,
+    ) -> Result<Lease, LeaseStoreError> {
+        let now = to_epoch_secs(SystemTime::now())?;
+        let expires_at = to_epoch_secs(expires_at)?;
+        self.conn.transaction::<_, LeaseStoreError, _>(|| {
+            // Take the lease if it is free to take; the condition is part of the update so that
+            // two processes cannot both take an expired lease
+            let updated = if steal {
+                update(store_lease::table.find(name))
+                    .set((
+                        store_lease::holder.eq(holder),
+                        store_lease::expires_at.eq(expires_at),
+                    ))
+                    .execute(self.conn)?
+            } else {
+                update(
+                    store_lease::table.find(name).filter(
+                        store_lease::holder
+                            .eq(holder)
+                            .or(store_lease::expires_at.le(now)),
+                    ),
+                )
+                .set((
+                    store_lease::holder.eq(holder),
+                    store_lease::expires_at.eq(expires_at),
+                ))
+                .execute(self.conn)?
+            };
+
+            if updated == 0 {
+                let exists = store_lease::table
+                    .find(name)
+                    .count()
+                    .get_result::<i64>(self.conn)?
+                    > 0;
+
+                if !exists {
+                    insert_into(store_lease::table)
+                        .values(&LeaseModel {
+                            name: name.to_string(),
+                            holder: holder.to_string(),
+                            expires_at,
+                        })
+                        .execute(self.conn)?;
+                }
+            }
+
+            Ok(store_lease::table
+                .find(name)
+                .first::<LeaseModel>(self.conn)?
+                .into())
+        })
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{dsl::delete, prelude::*};
+
+use crate::store::lease::{diesel::schema::store_lease, LeaseStoreError};
+
+use super::LeaseStoreOperations;
+
+pub(in crate::store::lease::diesel) trait LeaseStoreReleaseLeaseOperation {
+    fn release_lease(&self, name: &str, holder: &str) -> Result<bool, LeaseStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LeaseStoreReleaseLeaseOperation for LeaseStoreOperations<'a, diesel::pg::PgConnection> {
+    fn release_lease(&self, name: &str, holder: &str) -> Result<bool, LeaseStoreError> {
+        delete(
+            store_lease::table
+                .find(name)
+                .filter(store_lease::holder.eq(holder)),
+        )
+        .execute(self.conn)
+        .map(|released| released > 0)
+        .map_err(LeaseStoreError::from)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LeaseStoreReleaseLeaseOperation
+    for LeaseStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn release_lease(&self, name: &str, holder: &str) -> Result<bool, LeaseStoreError> {
+        delete(
+            store_lease::table
+                .find(name)
+                .filter(store_lease::holder.eq(holder)),
+        )
+        .execute(self.conn)
+        .map(|released| released > 0)
+        .map_err(LeaseStoreError::from)
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::SystemTime;
+
+use diesel::{dsl::update, prelude::*};
+
+use crate::store::lease::{
+    diesel::{models::to_epoch_secs, schema::store_lease},
+    LeaseStoreError,
+};
+
+use super::LeaseStoreOperations;
+
+pub(in crate::store::lease::diesel) trait LeaseStoreRenewLeaseOperation {
+    fn renew_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+    ) -> Result<bool, LeaseStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LeaseStoreRenewLeaseOperation for LeaseStoreOperations<'a, diesel::pg::PgConnection> {
+    fn renew_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+    ) -> Result<bool, LeaseStoreError> {
+        let expires_at = to_epoch_secs(expires_at)?;
+        update(
+            store_lease::table
+                .find(name)
+                .filter(store_lease::holder.eq(holder)),
+        )
+        .set(store_lease::expires_at.eq(expires_at))
+        .execute(self.conn)
+        .map(|renewed| renewed > 0)
+        .map_err(LeaseStoreError::from)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LeaseStoreRenewLeaseOperation
+    for LeaseStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn renew_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+    ) -> Result<bool, LeaseStoreError> {
+        let expires_at = to_epoch_secs(expires_at)?;
+        update(
+            store_lease::table
+                .find(name)
+                .filter(store_lease::holder.eq(holder)),
+        )
+        .set(store_lease::expires_at.eq(expires_at))
+        .execute(self.conn)
+        .map(|renewed| renewed > 0)
+        .map_err(LeaseStoreError::from)
+    }
+}
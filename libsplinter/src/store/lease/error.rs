@@ -0,0 +1,123 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error types for leases and lease stores.
+
+use std::error::Error;
+use std::fmt;
+use std::time::SystemTime;
+
+use crate::error::{InternalError, ResourceTemporarilyUnavailableError};
+
+use super::Lease;
+
+/// Error states for fallible [LeaseStore](super::LeaseStore) operations.
+#[derive(Debug)]
+pub enum LeaseStoreError {
+    Internal(InternalError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl fmt::Display for LeaseStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaseStoreError::Internal(err) => err.fmt(f),
+            LeaseStoreError::ResourceTemporarilyUnavailable(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for LeaseStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LeaseStoreError::Internal(err) => Some(err),
+            LeaseStoreError::ResourceTemporarilyUnavailable(err) => Some(err),
+        }
+    }
+}
+
+impl From<InternalError> for LeaseStoreError {
+    fn from(err: InternalError) -> Self {
+        LeaseStoreError::Internal(err)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for LeaseStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        LeaseStoreError::Internal(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for LeaseStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        LeaseStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
+
+/// Errors that may occur when taking a lease with a
+/// [LeaseHeartbeat](super::LeaseHeartbeat).
+#[derive(Debug)]
+pub enum LeaseError {
+    /// The lease is held by another live holder
+    Held(Lease),
+    /// The lease store could not be accessed
+    Store(LeaseStoreError),
+    Internal(InternalError),
+}
+
+impl fmt::Display for LeaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaseError::Held(lease) => write!(
+                f,
+                "lease {} is held by {} for another {}s",
+                lease.name(),
+                lease.holder(),
+                lease
+                    .expires_at()
+                    .duration_since(SystemTime::now())
+                    .map(|remaining| remaining.as_secs())
+                    .unwrap_or(0)
+            ),
+            LeaseError::Store(err) => err.fmt(f),
+            LeaseError::Internal(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for LeaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LeaseError::Held(_) => None,
+            LeaseError::Store(err) => Some(err),
+            LeaseError::Internal(err) => Some(err),
+        }
+    }
+}
+
+impl From<LeaseStoreError> for LeaseError {
+    fn from(err: LeaseStoreError) -> Self {
+        LeaseError::Store(err)
+    }
+}
+
+impl From<InternalError> for LeaseError {
+    fn from(err: InternalError) -> Self {
+        LeaseError::Internal(err)
+    }
+}
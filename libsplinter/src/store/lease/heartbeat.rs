@@ -0,0 +1,222 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keeps a lease held by renewing it in the background.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::{LeaseError, LeaseStore};
+
+/// Holds a lease for as long as it is running, renewing the lease well before it expires and
+/// releasing it on shutdown.
+pub struct LeaseHeartbeat {
+    sender: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl LeaseHeartbeat {
+    /// Takes the named lease and starts renewing it. The lease is renewed three times per
+    /// `duration`, so that a renewal which fails because the store is briefly unavailable does
+    /// not lose the lease.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store that holds the lease
+    /// * `name` - The name of the leased resource
+    /// * `holder` - The ID of the holder taking the lease; must be unique to this process
+    /// * `duration` - How long the lease is valid after each renewal
+    /// * `steal` - Whether to take the lease from a live holder
+    /// * `on_lost` - Called if the lease is taken by another holder while it is being renewed
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeaseError::Held`] if another live holder has the lease, or an error if the
+    /// store cannot be accessed or the heartbeat's thread cannot be started.
+    pub fn start(
+        store: Box<dyn LeaseStore>,
+        name: &str,
+        holder: &str,
+        duration: Duration,
+        steal: bool,
+        on_lost: Box<dyn FnOnce() + Send>,
+    ) -> Result<Self, LeaseError> {
+        let lease = store.acquire_lease(name, holder, expires_after(duration)?, steal)?;
+        if lease.holder() != holder {
+            return Err(LeaseError::Held(lease));
+        }
+
+        let (sender, receiver) = channel();
+        let name = name.to_string();
+        let holder = holder.to_string();
+        let interval = duration / 3;
+
+        let join_handle = thread::Builder::new()
+            .name("LeaseHeartbeat".into())
+            .spawn(move || loop {
+                match receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                        match store.release_lease(&name, &holder) {
+                            Ok(true) => debug!("Released lease {}", name),
+                            Ok(false) => warn!("Lease {} was not held at shutdown", name),
+                            Err(err) => error!("Unable to release lease {}: {}", name, err),
+                        }
+                        break;
+                    }
+                }
+
+                let renewed =
+                    expires_after(duration)
+                        .map_err(LeaseError::from)
+                        .and_then(|expires_at| {
+                            store
+                                .renew_lease(&name, &holder, expires_at)
+                                .map_err(LeaseError::from)
+                        });
+                match renewed {
+                    Ok(true) => (),
+                    Ok(false) => {
+                        error!("Lease {} has been taken by another holder", name);
+                        on_lost();
+                        break;
+                    }
+                    Err(err) => warn!("Unable to renew lease {}: {}", name, err),
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            sender,
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for LeaseHeartbeat {
+    fn signal_shutdown(&mut self) {
+        if self.sender.send(()).is_err() {
+            warn!("Lease heartbeat is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "Lease heartbeat thread did not shutdown correctly: {:?}",
+                err
+            ))
+        })
+    }
+}
+
+fn expires_after(duration: Duration) -> Result<SystemTime, InternalError> {
+    SystemTime::now()
+        .checked_add(duration)
+        .ok_or_else(|| InternalError::with_message("Lease duration is too long".into()))
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::store::lease::diesel::DieselLeaseStore;
+
+    /// Verify that a heartbeat cannot take a lease held by another live heartbeat unless it
+    /// steals the lease, that the original heartbeat reports the lease as lost, and that the
+    /// lease is released on shutdown.
+    #[test]
+    fn test_heartbeat_held_and_stolen() {
+        let store = DieselLeaseStore::new(create_connection_pool_and_migrate());
+        let lost = Arc::new(AtomicBool::new(false));
+
+        let on_lost = lost.clone();
+        let first = LeaseHeartbeat::start(
+            Box::new(store.clone()),
+            "test",
+            "first",
+            Duration::from_millis(300),
+            false,
+            Box::new(move || on_lost.store(true, Ordering::SeqCst)),
+        )
+        .expect("Unable to start first heartbeat");
+
+        match LeaseHeartbeat::start(
+            Box::new(store.clone()),
+            "test",
+            "second",
+            Duration::from_secs(30),
+            false,
+            Box::new(|| ()),
+        ) {
+            Err(LeaseError::Held(lease)) => assert_eq!(lease.holder(), "first"),
+            Err(err) => panic!("Unexpected error: {}", err),
+            Ok(_) => panic!("Lease should be held by the first heartbeat"),
+        }
+
+        let mut second = LeaseHeartbeat::start(
+            Box::new(store.clone()),
+            "test",
+            "second",
+            Duration::from_secs(30),
+            true,
+            Box::new(|| ()),
+        )
+        .expect("Unable to steal lease");
+
+        first
+            .wait_for_shutdown()
+            .expect("Unable to shutdown first heartbeat");
+        assert!(lost.load(Ordering::SeqCst));
+
+        second.signal_shutdown();
+        second
+            .wait_for_shutdown()
+            .expect("Unable to shutdown second heartbeat");
+        assert!(!store
+            .release_lease("test", "second")
+            .expect("Unable to release lease"));
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
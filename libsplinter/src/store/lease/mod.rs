@@ -0,0 +1,130 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A lease on a named resource, shared through the database, that lets one process at a time
+//! claim exclusive use of that database.
+//!
+//! A lease is held until its expiry time; the holder keeps it by renewing it before it expires.
+//! A process that stops without releasing its lease gives it up once the lease expires.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+mod heartbeat;
+
+use std::time::SystemTime;
+
+pub use error::{LeaseError, LeaseStoreError};
+pub use heartbeat::LeaseHeartbeat;
+
+/// A claim on a named resource by a single holder, valid until it expires.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lease {
+    name: String,
+    holder: String,
+    expires_at: SystemTime,
+}
+
+impl Lease {
+    /// Creates a new lease.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the leased resource
+    /// * `holder` - The ID of the lease's holder
+    /// * `expires_at` - The time at which the lease expires unless renewed
+    pub fn new(name: &str, holder: &str, expires_at: SystemTime) -> Self {
+        Self {
+            name: name.to_string(),
+            holder: holder.to_string(),
+            expires_at,
+        }
+    }
+
+    /// Returns the name of the leased resource.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the ID of the lease's holder.
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// Returns the time at which the lease expires unless renewed.
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    /// Returns `true` if the lease has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+}
+
+pub trait LeaseStore: Send + Sync {
+    /// Attempts to take the named lease for the given holder, returning the lease as stored after
+    /// the attempt. The caller holds the lease if the returned lease's holder is the given
+    /// holder.
+    ///
+    /// The lease is taken if it has no holder, if it has expired, or if it is already held by
+    /// the given holder. If `steal` is `true`, the lease is taken regardless of its current
+    /// holder.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the leased resource
+    /// * `holder` - The ID of the holder taking the lease
+    /// * `expires_at` - The time at which the lease expires unless renewed
+    /// * `steal` - Whether to take the lease from a live holder
+    fn acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+        steal: bool,
+    ) -> Result<Lease, LeaseStoreError>;
+
+    /// Extends the named lease held by the given holder, returning `false` if the holder no
+    /// longer holds the lease.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the leased resource
+    /// * `holder` - The ID of the lease's holder
+    /// * `expires_at` - The new time at which the lease expires unless renewed
+    fn renew_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: SystemTime,
+    ) -> Result<bool, LeaseStoreError>;
+
+    /// Releases the named lease held by the given holder, returning `false` if the holder did
+    /// not hold the lease.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the leased resource
+    /// * `holder` - The ID of the lease's holder
+    fn release_lease(&self, name: &str, holder: &str) -> Result<bool, LeaseStoreError>;
+
+    fn clone_box(&self) -> Box<dyn LeaseStore>;
+}
+
+impl Clone for Box<dyn LeaseStore> {
+    fn clone(&self) -> Box<dyn LeaseStore> {
+        self.clone_box()
+    }
+}
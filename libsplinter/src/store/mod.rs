@@ -15,6 +15,8 @@
 //! Contains a `StoreFactory` trait, which is an abstract factory for building stores
 //! backed by a single storage mechanism (e.g. database)
 pub mod command;
+#[cfg(feature = "store-lease")]
+pub mod lease;
 #[cfg(all(feature = "store-factory", feature = "memory"))]
 pub mod memory;
 #[cfg(feature = "diesel")]
@@ -65,6 +67,26 @@ pub trait StoreFactory {
     #[cfg(feature = "node-id-store")]
     fn get_node_id_store(&self) -> Box<dyn crate::node_id::store::NodeIdStore>;
 
+    /// Get a new `AuditStore`
+    #[cfg(feature = "network-audit")]
+    fn get_network_audit_store(&self) -> Box<dyn crate::network::audit::AuditStore>;
+
+    /// Get a new `AccessLogStore`
+    #[cfg(feature = "circuit-access-log")]
+    fn get_circuit_access_log_store(&self) -> Box<dyn crate::circuit::access_log::AccessLogStore>;
+
+    /// Get a new `VoteDelegationStore`
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn get_vote_delegation_store(&self) -> Box<dyn crate::admin::delegation::VoteDelegationStore>;
+
+    /// Get a new `RegistrySourceStore`
+    #[cfg(feature = "registry-sources")]
+    fn get_registry_source_store(&self) -> Box<dyn crate::registry::sources::RegistrySourceStore>;
+
     #[cfg(feature = "service-lifecycle-store")]
     fn get_lifecycle_store(&self) -> Box<dyn crate::runtime::service::LifecycleStore + Send>;
+
+    /// Get a new `LeaseStore`
+    #[cfg(feature = "store-lease")]
+    fn get_lease_store(&self) -> Box<dyn crate::store::lease::LeaseStore>;
 }
@@ -136,10 +136,49 @@ impl StoreFactory for PgStoreFactory {
         ))
     }
 
+    #[cfg(feature = "network-audit")]
+    fn get_network_audit_store(&self) -> Box<dyn crate::network::audit::AuditStore> {
+        Box::new(crate::network::audit::store::diesel::DieselAuditStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    fn get_circuit_access_log_store(&self) -> Box<dyn crate::circuit::access_log::AccessLogStore> {
+        Box::new(
+            crate::circuit::access_log::store::diesel::DieselAccessLogStore::new(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "admin-service-vote-delegation")]
+    fn get_vote_delegation_store(&self) -> Box<dyn crate::admin::delegation::VoteDelegationStore> {
+        Box::new(
+            crate::admin::delegation::store::diesel::DieselVoteDelegationStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "registry-sources")]
+    fn get_registry_source_store(&self) -> Box<dyn crate::registry::sources::RegistrySourceStore> {
+        Box::new(
+            crate::registry::sources::store::diesel::DieselRegistrySourceStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
+
     #[cfg(feature = "service-lifecycle-store")]
     fn get_lifecycle_store(&self) -> Box<dyn crate::runtime::service::LifecycleStore + Send> {
         Box::new(crate::runtime::service::DieselLifecycleStore::new(
             self.pool.clone(),
         ))
     }
+
+    #[cfg(feature = "store-lease")]
+    fn get_lease_store(&self) -> Box<dyn crate::store::lease::LeaseStore> {
+        Box::new(crate::store::lease::diesel::DieselLeaseStore::new(
+            self.pool.clone(),
+        ))
+    }
 }
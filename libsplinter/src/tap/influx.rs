@@ -92,7 +92,16 @@ pub struct InfluxRecorder {
 }
 
 impl InfluxRecorder {
-    fn new(
+    /// Creates an InfluxRecorder that will connect to the InfluxDB instance. Unlike `init`, the
+    /// recorder is not installed as the global recorder.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_url` - The URL to connect the InfluxDB database for metrics collection
+    /// * `db_name` - The name of the InfluxDB database for metrics Collection.
+    /// * `username` - The username used for authorization with the InfluxDB.
+    /// * `password` - The password used for authorization with the InfluxDB.
+    pub fn new(
         db_url: &str,
         db_name: &str,
         username: &str,
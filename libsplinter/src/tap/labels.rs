@@ -0,0 +1,200 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Labels that are applied to every metric, regardless of where it is emitted.
+//!
+//! `LabeledRecorder` wraps any [metrics::Recorder] and adds a shared set of labels, such as the
+//! node ID, to each metric before passing it on. The labels may be changed after the recorder has
+//! been installed, since some of them are not known until the node has started.
+
+use std::sync::{Arc, RwLock};
+
+use metrics::{GaugeValue, Key, Label, Recorder, Unit};
+
+use crate::error::InternalError;
+
+use super::snapshot::{MetricsSnapshot, SnapshotRecorder};
+
+/// A shared, updatable set of labels applied to every metric by a `LabeledRecorder`.
+#[derive(Clone, Default)]
+pub struct GlobalLabels {
+    labels: Arc<RwLock<Vec<Label>>>,
+}
+
+impl GlobalLabels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of a label, replacing any existing value for the same key.
+    pub fn insert(&self, key: &str, value: &str) {
+        let mut labels = match self.labels.write() {
+            Ok(labels) => labels,
+            Err(err) => err.into_inner(),
+        };
+        labels.retain(|label| label.key() != key);
+        labels.push(Label::new(key.to_string(), value.to_string()));
+    }
+
+    /// Returns the given key with the global labels added. A label already present on the key
+    /// takes precedence over a global label with the same name.
+    fn apply(&self, key: &Key) -> Key {
+        let labels = match self.labels.read() {
+            Ok(labels) => labels,
+            Err(err) => err.into_inner(),
+        };
+        if labels.is_empty() {
+            return key.clone();
+        }
+
+        let (name, mut key_labels) = key.clone().into_parts();
+        for label in labels.iter() {
+            if !key_labels.iter().any(|l| l.key() == label.key()) {
+                key_labels.push(label.clone());
+            }
+        }
+        Key::from_parts(name, key_labels)
+    }
+}
+
+/// A recorder that adds the global labels to every metric before passing it to the inner
+/// recorder.
+pub struct LabeledRecorder<R: Recorder> {
+    inner: R,
+    labels: GlobalLabels,
+}
+
+impl<R: Recorder> LabeledRecorder<R> {
+    pub fn new(inner: R, labels: GlobalLabels) -> Self {
+        Self { inner, labels }
+    }
+}
+
+impl<R: Recorder> Recorder for LabeledRecorder<R> {
+    fn register_counter(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_counter(&self.labels.apply(key), unit, description)
+    }
+
+    fn register_gauge(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_gauge(&self.labels.apply(key), unit, description)
+    }
+
+    fn register_histogram(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_histogram(&self.labels.apply(key), unit, description)
+    }
+
+    fn increment_counter(&self, key: &Key, value: u64) {
+        self.inner.increment_counter(&self.labels.apply(key), value)
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        self.inner.update_gauge(&self.labels.apply(key), value)
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        self.inner.record_histogram(&self.labels.apply(key), value)
+    }
+}
+
+/// Installs the given recorder as the global metrics recorder, wrapped so that the global labels
+/// are applied to every metric.
+///
+/// If a snapshot is given, the latest value of every metric is also kept in it. The snapshot does
+/// not include the global labels.
+///
+/// # Arguments
+///
+/// * `recorder` - The recorder that sends metrics to a backend
+/// * `labels` - The labels to apply to every metric
+/// * `snapshot` - The snapshot in which to keep the latest metric values, if any
+pub fn init_recorder<R>(
+    recorder: R,
+    labels: GlobalLabels,
+    snapshot: Option<MetricsSnapshot>,
+) -> Result<(), InternalError>
+where
+    R: Recorder + 'static,
+{
+    let recorder = LabeledRecorder::new(recorder, labels);
+    match snapshot {
+        Some(snapshot) => {
+            metrics::set_boxed_recorder(Box::new(SnapshotRecorder::new(recorder, snapshot)))
+        }
+        None => metrics::set_boxed_recorder(Box::new(recorder)),
+    }
+    .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct CapturingRecorder {
+        keys: Arc<Mutex<Vec<Key>>>,
+    }
+
+    impl Recorder for CapturingRecorder {
+        fn register_counter(&self, _: &Key, _: Option<Unit>, _: Option<&'static str>) {}
+        fn register_gauge(&self, _: &Key, _: Option<Unit>, _: Option<&'static str>) {}
+        fn register_histogram(&self, _: &Key, _: Option<Unit>, _: Option<&'static str>) {}
+        fn increment_counter(&self, key: &Key, _: u64) {
+            self.keys.lock().unwrap().push(key.clone());
+        }
+        fn update_gauge(&self, _: &Key, _: GaugeValue) {}
+        fn record_histogram(&self, _: &Key, _: f64) {}
+    }
+
+    /// Verify that the global labels are added to each metric, that labels set after the
+    /// recorder is created are applied, and that labels set at the emission site take
+    /// precedence.
+    #[test]
+    fn global_labels_applied() {
+        let inner = CapturingRecorder::default();
+        let labels = GlobalLabels::new();
+        let recorder = LabeledRecorder::new(inner.clone(), labels.clone());
+
+        recorder.increment_counter(&Key::from_name("test.counter"), 1);
+        labels.insert("node_id", "node-a");
+        labels.insert("node_id", "node-b");
+        recorder.increment_counter(
+            &Key::from_parts(
+                "test.counter",
+                vec![
+                    Label::new("circuit", "abcde-01234"),
+                    Label::new("node_id", "local"),
+                ],
+            ),
+            1,
+        );
+        recorder.increment_counter(&Key::from_name("test.counter"), 1);
+
+        let keys = inner.keys.lock().unwrap();
+        assert_eq!(keys[0].labels().count(), 0);
+
+        let second: Vec<(&str, &str)> = keys[1].labels().map(|l| (l.key(), l.value())).collect();
+        assert_eq!(
+            second,
+            vec![("circuit", "abcde-01234"), ("node_id", "local")]
+        );
+
+        let third: Vec<(&str, &str)> = keys[2].labels().map(|l| (l.key(), l.value())).collect();
+        assert_eq!(third, vec![("node_id", "node-b")]);
+    }
+}
@@ -16,6 +16,9 @@
 //!
 //! Includes a default no-op implementation.
 //! The `metrics` feature turns an implementation for sending metrics to an InfluxDB instance.
+//! The `tap-backends` feature adds implementations for statsd and OpenTelemetry collectors,
+//! support for labels, such as the node ID, that are applied to every metric, and an in-process
+//! snapshot of the latest metric values.
 //!
 //! The following macros are available:
 //! - `counter`: Increments a counter.
@@ -24,6 +27,17 @@
 
 #[cfg(feature = "tap")]
 pub mod influx;
+#[cfg(feature = "tap-backends")]
+mod labels;
+#[cfg(feature = "tap-backends")]
+pub mod otlp;
+#[cfg(feature = "tap-backends")]
+pub mod snapshot;
+#[cfg(feature = "tap-backends")]
+pub mod statsd;
+
+#[cfg(feature = "tap-backends")]
+pub use labels::{init_recorder, GlobalLabels, LabeledRecorder};
 
 /// no-op `counter` macro for when the `metrics` feature is not enabled
 #[cfg(not(feature = "tap"))]
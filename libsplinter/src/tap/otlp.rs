@@ -0,0 +1,390 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains an OpenTelemetry (OTLP) implementation of the [metrics::Recorder] trait.
+//! `OtlpRecorder` aggregates metrics in memory and periodically exports them to an OTLP
+//! collector using the OTLP/HTTP JSON encoding.
+//!
+//! Counters are exported as cumulative monotonic sums, gauges as gauges and histograms as
+//! cumulative histograms with a single bucket (count and sum only).
+//!
+//! Available if the `tap-backends` feature is enabled
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use metrics::{GaugeValue, Key, Label, Recorder, Unit};
+use serde_json::{json, Value};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+const DEFAULT_EXPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+enum MetricRequest {
+    Counter { key: Key, value: u64 },
+    Gauge { key: Key, value: GaugeValue },
+    Histogram { key: Key, value: f64 },
+    Shutdown,
+}
+
+/// A metric identity: its name and its labels, sorted so that the same labels in a different
+/// order refer to the same metric.
+type MetricId = (String, Vec<(String, String)>);
+
+#[derive(Default)]
+struct HistogramState {
+    count: u64,
+    sum: f64,
+}
+
+/// The aggregated value of every metric recorded since the recorder started.
+#[derive(Default)]
+struct Aggregates {
+    counters: BTreeMap<MetricId, u64>,
+    gauges: BTreeMap<MetricId, f64>,
+    histograms: BTreeMap<MetricId, HistogramState>,
+}
+
+fn metric_id(key: &Key) -> MetricId {
+    let (name, labels) = key.clone().into_parts();
+    let mut labels: Vec<(String, String)> = labels
+        .iter()
+        .map(|label: &Label| (label.key().to_string(), label.value().to_string()))
+        .collect();
+    labels.sort();
+    (name.to_string(), labels)
+}
+
+impl Aggregates {
+    fn record(&mut self, request: MetricRequest) {
+        match request {
+            MetricRequest::Counter { key, value } => {
+                *self.counters.entry(metric_id(&key)).or_insert(0) += value;
+            }
+            MetricRequest::Gauge { key, value } => {
+                let gauge = self.gauges.entry(metric_id(&key)).or_insert(0.0);
+                match value {
+                    GaugeValue::Absolute(total) => *gauge = total,
+                    GaugeValue::Increment(amount) => *gauge += amount,
+                    GaugeValue::Decrement(amount) => *gauge -= amount,
+                }
+            }
+            MetricRequest::Histogram { key, value } => {
+                let histogram = self.histograms.entry(metric_id(&key)).or_default();
+                histogram.count += 1;
+                histogram.sum += value;
+            }
+            MetricRequest::Shutdown => (),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.gauges.is_empty() && self.histograms.is_empty()
+    }
+
+    /// Builds an OTLP `ExportMetricsServiceRequest` containing every aggregated metric.
+    fn to_export_request(&self, start_time: u64, time: u64) -> Value {
+        let mut metrics = vec![];
+
+        for ((name, labels), value) in &self.counters {
+            metrics.push(json!({
+                "name": name,
+                "sum": {
+                    "dataPoints": [{
+                        "attributes": attributes(labels),
+                        "startTimeUnixNano": start_time.to_string(),
+                        "timeUnixNano": time.to_string(),
+                        "asInt": value.to_string(),
+                    }],
+                    "aggregationTemporality": 2,
+                    "isMonotonic": true,
+                },
+            }));
+        }
+
+        for ((name, labels), value) in &self.gauges {
+            metrics.push(json!({
+                "name": name,
+                "gauge": {
+                    "dataPoints": [{
+                        "attributes": attributes(labels),
+                        "timeUnixNano": time.to_string(),
+                        "asDouble": value,
+                    }],
+                },
+            }));
+        }
+
+        for ((name, labels), state) in &self.histograms {
+            metrics.push(json!({
+                "name": name,
+                "histogram": {
+                    "dataPoints": [{
+                        "attributes": attributes(labels),
+                        "startTimeUnixNano": start_time.to_string(),
+                        "timeUnixNano": time.to_string(),
+                        "count": state.count.to_string(),
+                        "sum": state.sum,
+                        "bucketCounts": [state.count.to_string()],
+                        "explicitBounds": [],
+                    }],
+                    "aggregationTemporality": 2,
+                },
+            }));
+        }
+
+        json!({
+            "resourceMetrics": [{
+                "resource": {
+                    "attributes": [{
+                        "key": "service.name",
+                        "value": { "stringValue": "splinterd" },
+                    }],
+                },
+                "scopeMetrics": [{
+                    "scope": { "name": "splinter" },
+                    "metrics": metrics,
+                }],
+            }],
+        })
+    }
+}
+
+fn attributes(labels: &[(String, String)]) -> Value {
+    Value::Array(
+        labels
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+            .collect(),
+    )
+}
+
+fn now_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Enables using the metrics macros and exporting the metrics data to an OpenTelemetry collector
+pub struct OtlpRecorder {
+    sender: Sender<MetricRequest>,
+    join_handle: JoinHandle<()>,
+}
+
+impl OtlpRecorder {
+    /// Creates a recorder that exports metrics to the OTLP/HTTP collector at the given URL every
+    /// ten seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The base URL of the collector, such as `http://localhost:4318`; metrics are sent
+    ///   to `<url>/v1/metrics`
+    pub fn new(url: &str) -> Result<Self, InternalError> {
+        Self::with_interval(url, DEFAULT_EXPORT_INTERVAL)
+    }
+
+    /// Creates a recorder that exports metrics to the OTLP/HTTP collector at the given URL at the
+    /// given interval.
+    pub fn with_interval(url: &str, interval: Duration) -> Result<Self, InternalError> {
+        let endpoint = format!("{}/v1/metrics", url.trim_end_matches('/'));
+        let (sender, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("OtlpRecorder".into())
+            .spawn(move || {
+                let client = reqwest::blocking::Client::new();
+                let start_time = now_nanos();
+                let mut aggregates = Aggregates::default();
+                let mut next_export = Instant::now() + interval;
+
+                loop {
+                    let timeout = next_export.saturating_duration_since(Instant::now());
+                    let shutdown = match receiver.recv_timeout(timeout) {
+                        Ok(MetricRequest::Shutdown) | Err(RecvTimeoutError::Disconnected) => true,
+                        Ok(request) => {
+                            aggregates.record(request);
+                            continue;
+                        }
+                        Err(RecvTimeoutError::Timeout) => false,
+                    };
+
+                    if !aggregates.is_empty() {
+                        let request = aggregates.to_export_request(start_time, now_nanos());
+                        match client.post(&endpoint).json(&request).send() {
+                            Ok(response) if !response.status().is_success() => {
+                                error!("OTLP collector rejected metrics: {}", response.status())
+                            }
+                            Ok(_) => (),
+                            Err(err) => {
+                                error!("Unable to export metrics to OTLP collector: {}", err)
+                            }
+                        }
+                    }
+
+                    if shutdown {
+                        info!("Received MetricRequest::Shutdown");
+                        break;
+                    }
+                    next_export = Instant::now() + interval;
+                }
+            })
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    "Unable to start OTLP metrics thread".into(),
+                )
+            })?;
+
+        Ok(Self {
+            sender,
+            join_handle,
+        })
+    }
+
+    fn send(&self, request: MetricRequest) {
+        if let Err(err) = self.sender.send(request) {
+            error!("Unable to record metric, {}", err);
+        }
+    }
+}
+
+impl ShutdownHandle for OtlpRecorder {
+    fn signal_shutdown(&mut self) {
+        if self.sender.send(MetricRequest::Shutdown).is_err() {
+            error!("Unable to send shutdown message to OtlpRecorder");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!("Unable to join OtlpRecorder thread: {:?}", err))
+        })
+    }
+}
+
+impl Recorder for OtlpRecorder {
+    fn increment_counter(&self, key: &Key, value: u64) {
+        self.send(MetricRequest::Counter {
+            key: key.clone(),
+            value,
+        });
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        self.send(MetricRequest::Gauge {
+            key: key.clone(),
+            value,
+        });
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        self.send(MetricRequest::Histogram {
+            key: key.clone(),
+            value,
+        });
+    }
+
+    fn register_counter(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
+        self.increment_counter(key, 0);
+    }
+
+    fn register_gauge(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
+        self.update_gauge(key, GaugeValue::Increment(0.0));
+    }
+
+    fn register_histogram(
+        &self,
+        _key: &Key,
+        _unit: Option<Unit>,
+        _description: Option<&'static str>,
+    ) {
+        // a histogram with no samples has nothing to export
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that metrics are aggregated by name and labels, regardless of label order, and
+    /// exported in the OTLP JSON format.
+    #[test]
+    fn export_request() {
+        let mut aggregates = Aggregates::default();
+        aggregates.record(MetricRequest::Counter {
+            key: Key::from_parts(
+                "splinter.counter",
+                vec![
+                    Label::new("circuit", "abcde-01234"),
+                    Label::new("node_id", "a"),
+                ],
+            ),
+            value: 2,
+        });
+        aggregates.record(MetricRequest::Counter {
+            key: Key::from_parts(
+                "splinter.counter",
+                vec![
+                    Label::new("node_id", "a"),
+                    Label::new("circuit", "abcde-01234"),
+                ],
+            ),
+            value: 3,
+        });
+        aggregates.record(MetricRequest::Gauge {
+            key: Key::from_name("splinter.gauge"),
+            value: GaugeValue::Absolute(4.0),
+        });
+        aggregates.record(MetricRequest::Gauge {
+            key: Key::from_name("splinter.gauge"),
+            value: GaugeValue::Decrement(1.5),
+        });
+        aggregates.record(MetricRequest::Histogram {
+            key: Key::from_name("splinter.histogram"),
+            value: 1.0,
+        });
+        aggregates.record(MetricRequest::Histogram {
+            key: Key::from_name("splinter.histogram"),
+            value: 2.0,
+        });
+
+        let request = aggregates.to_export_request(1, 2);
+        let metrics = &request["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics.as_array().map(Vec::len), Some(3));
+
+        let counter = &metrics[0]["sum"]["dataPoints"][0];
+        assert_eq!(metrics[0]["name"], "splinter.counter");
+        assert_eq!(counter["asInt"], "5");
+        assert_eq!(counter["startTimeUnixNano"], "1");
+        assert_eq!(
+            counter["attributes"],
+            json!([
+                { "key": "circuit", "value": { "stringValue": "abcde-01234" } },
+                { "key": "node_id", "value": { "stringValue": "a" } },
+            ])
+        );
+
+        assert_eq!(metrics[1]["name"], "splinter.gauge");
+        assert_eq!(metrics[1]["gauge"]["dataPoints"][0]["asDouble"], 2.5);
+
+        let histogram = &metrics[2]["histogram"]["dataPoints"][0];
+        assert_eq!(metrics[2]["name"], "splinter.histogram");
+        assert_eq!(histogram["count"], "2");
+        assert_eq!(histogram["sum"], 3.0);
+    }
+}
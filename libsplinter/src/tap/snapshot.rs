@@ -0,0 +1,271 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-process snapshot of the node's metrics.
+//!
+//! `SnapshotRecorder` wraps another [metrics::Recorder] and keeps the latest value of every
+//! metric it passes on: the total of each counter, the current value of each gauge, and the
+//! count, sum, minimum and maximum of each histogram. The snapshot can then be read through a
+//! `MetricsSnapshot`, for example to serve it from the REST API on nodes that have no metrics
+//! stack.
+//!
+//! Available if the `tap-backends` feature is enabled
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use metrics::{GaugeValue, Key, Label, NoopRecorder, Recorder, Unit};
+
+use crate::error::InternalError;
+
+/// A metric identity: its name and its labels, sorted so that the same labels in a different
+/// order refer to the same metric.
+type MetricId = (String, Vec<(String, String)>);
+
+/// The latest value of a metric.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetricValue {
+    /// The total of every increment of a counter
+    Counter(u64),
+    /// The current value of a gauge
+    Gauge(f64),
+    /// A summary of the values recorded by a histogram
+    Histogram {
+        count: u64,
+        sum: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+/// A single metric in a snapshot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metric {
+    name: String,
+    labels: Vec<(String, String)>,
+    value: MetricValue,
+}
+
+impl Metric {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the metric's labels, sorted by key.
+    pub fn labels(&self) -> &[(String, String)] {
+        &self.labels
+    }
+
+    pub fn value(&self) -> &MetricValue {
+        &self.value
+    }
+}
+
+/// A shared view of the metrics recorded by a `SnapshotRecorder`.
+#[derive(Clone, Default)]
+pub struct MetricsSnapshot {
+    metrics: Arc<RwLock<BTreeMap<MetricId, MetricValue>>>,
+}
+
+impl MetricsSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns every metric recorded so far, ordered by name and labels.
+    pub fn metrics(&self) -> Vec<Metric> {
+        let metrics = match self.metrics.read() {
+            Ok(metrics) => metrics,
+            Err(err) => err.into_inner(),
+        };
+        metrics
+            .iter()
+            .map(|((name, labels), value)| Metric {
+                name: name.clone(),
+                labels: labels.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+
+    fn update<F>(&self, key: &Key, default: MetricValue, f: F)
+    where
+        F: FnOnce(&mut MetricValue),
+    {
+        let mut metrics = match self.metrics.write() {
+            Ok(metrics) => metrics,
+            Err(err) => err.into_inner(),
+        };
+        f(metrics.entry(metric_id(key)).or_insert(default))
+    }
+}
+
+fn metric_id(key: &Key) -> MetricId {
+    let (name, labels) = key.clone().into_parts();
+    let mut labels: Vec<(String, String)> = labels
+        .iter()
+        .map(|label: &Label| (label.key().to_string(), label.value().to_string()))
+        .collect();
+    labels.sort();
+    (name.to_string(), labels)
+}
+
+/// A recorder that keeps the latest value of every metric in a `MetricsSnapshot` before passing
+/// it to the inner recorder.
+pub struct SnapshotRecorder<R: Recorder> {
+    inner: R,
+    snapshot: MetricsSnapshot,
+}
+
+impl<R: Recorder> SnapshotRecorder<R> {
+    pub fn new(inner: R, snapshot: MetricsSnapshot) -> Self {
+        Self { inner, snapshot }
+    }
+}
+
+impl<R: Recorder> Recorder for SnapshotRecorder<R> {
+    fn register_counter(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner.register_counter(key, unit, description)
+    }
+
+    fn register_gauge(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner.register_gauge(key, unit, description)
+    }
+
+    fn register_histogram(&self, key: &Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner.register_histogram(key, unit, description)
+    }
+
+    fn increment_counter(&self, key: &Key, value: u64) {
+        self.snapshot
+            .update(key, MetricValue::Counter(0), |metric| {
+                if let MetricValue::Counter(total) = metric {
+                    *total += value;
+                }
+            });
+        self.inner.increment_counter(key, value)
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        self.snapshot
+            .update(key, MetricValue::Gauge(0.0), |metric| {
+                if let MetricValue::Gauge(gauge) = metric {
+                    match &value {
+                        GaugeValue::Absolute(total) => *gauge = *total,
+                        GaugeValue::Increment(amount) => *gauge += *amount,
+                        GaugeValue::Decrement(amount) => *gauge -= *amount,
+                    }
+                }
+            });
+        self.inner.update_gauge(key, value)
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        self.snapshot.update(
+            key,
+            MetricValue::Histogram {
+                count: 0,
+                sum: 0.0,
+                min: value,
+                max: value,
+            },
+            |metric| {
+                if let MetricValue::Histogram {
+                    count,
+                    sum,
+                    min,
+                    max,
+                } = metric
+                {
+                    *count += 1;
+                    *sum += value;
+                    *min = min.min(value);
+                    *max = max.max(value);
+                }
+            },
+        );
+        self.inner.record_histogram(key, value)
+    }
+}
+
+/// Installs a global metrics recorder that only keeps the latest value of every metric in the
+/// given snapshot, for nodes that do not send metrics to a backend.
+///
+/// # Arguments
+///
+/// * `snapshot` - The snapshot in which to keep the latest metric values
+pub fn init_snapshot_only_recorder(snapshot: MetricsSnapshot) -> Result<(), InternalError> {
+    metrics::set_boxed_recorder(Box::new(SnapshotRecorder::new(NoopRecorder, snapshot)))
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the snapshot keeps the total of each counter, the latest value of each gauge
+    /// and a summary of each histogram, and that metrics with the same labels in a different order
+    /// are treated as the same metric.
+    #[test]
+    fn test_snapshot_recorder() {
+        let snapshot = MetricsSnapshot::new();
+        let recorder = SnapshotRecorder::new(NoopRecorder, snapshot.clone());
+
+        let counter = Key::from_parts(
+            "test.counter",
+            vec![Label::new("a", "1"), Label::new("b", "2")],
+        );
+        let reordered = Key::from_parts(
+            "test.counter",
+            vec![Label::new("b", "2"), Label::new("a", "1")],
+        );
+        recorder.increment_counter(&counter, 2);
+        recorder.increment_counter(&reordered, 3);
+
+        let gauge = Key::from_name("test.gauge");
+        recorder.update_gauge(&gauge, GaugeValue::Absolute(10.0));
+        recorder.update_gauge(&gauge, GaugeValue::Decrement(4.0));
+
+        let histogram = Key::from_name("test.histogram");
+        recorder.record_histogram(&histogram, 0.5);
+        recorder.record_histogram(&histogram, 1.5);
+
+        let metrics = snapshot.metrics();
+        assert_eq!(metrics.len(), 3);
+
+        assert_eq!(metrics[0].name(), "test.counter");
+        assert_eq!(
+            metrics[0].labels(),
+            &[
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string())
+            ]
+        );
+        assert_eq!(metrics[0].value(), &MetricValue::Counter(5));
+
+        assert_eq!(metrics[1].name(), "test.gauge");
+        assert_eq!(metrics[1].value(), &MetricValue::Gauge(6.0));
+
+        assert_eq!(metrics[2].name(), "test.histogram");
+        assert_eq!(
+            metrics[2].value(),
+            &MetricValue::Histogram {
+                count: 2,
+                sum: 2.0,
+                min: 0.5,
+                max: 1.5
+            }
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains a statsd implementation of the [metrics::Recorder] trait. `StatsdRecorder` sends each
+//! metric to a statsd daemon over UDP as soon as it is recorded.
+//!
+//! Labels are sent using the DogStatsD tag extension (`|#key:value,...`), which is understood by
+//! most statsd implementations that support tags.
+//!
+//! Available if the `tap-backends` feature is enabled
+
+use std::net::UdpSocket;
+
+use metrics::{GaugeValue, Key, Label, Recorder, Unit};
+
+use crate::error::InternalError;
+
+/// Enables using the metrics macros and sending the metrics data to a statsd daemon
+pub struct StatsdRecorder {
+    socket: UdpSocket,
+}
+
+impl StatsdRecorder {
+    /// Creates a recorder that sends metrics to the statsd daemon at the given address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The `host:port` address of the statsd daemon
+    pub fn new(address: &str) -> Result<Self, InternalError> {
+        let bind_address = if address.starts_with('[') {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        };
+        let socket = UdpSocket::bind(bind_address).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "Unable to bind statsd socket".into(),
+            )
+        })?;
+        socket.connect(address).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to resolve statsd address {}", address),
+            )
+        })?;
+        socket.set_nonblocking(true).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "Unable to configure statsd socket".into(),
+            )
+        })?;
+
+        Ok(Self { socket })
+    }
+
+    fn send(&self, lines: &[String]) {
+        for line in lines {
+            // statsd is best effort; a dropped datagram only loses a single sample
+            if let Err(err) = self.socket.send(line.as_bytes()) {
+                trace!("Unable to send metric to statsd: {}", err);
+            }
+        }
+    }
+}
+
+/// Formats a single statsd line, such as `name:1|c|#circuit:abcde-01234`.
+fn format_line(name: &str, value: &str, metric_type: &str, labels: &[Label]) -> String {
+    let mut line = format!("{}:{}|{}", name, value, metric_type);
+    if !labels.is_empty() {
+        let tags = labels
+            .iter()
+            .map(|label| format!("{}:{}", label.key(), label.value()))
+            .collect::<Vec<_>>()
+            .join(",");
+        line.push_str("|#");
+        line.push_str(&tags);
+    }
+    line
+}
+
+/// Formats the statsd lines for a gauge update.
+///
+/// statsd interprets a signed gauge value as a change to the gauge, so an absolute negative value
+/// must be sent as a reset to zero followed by a decrement.
+fn format_gauge(name: &str, value: GaugeValue, labels: &[Label]) -> Vec<String> {
+    match value {
+        GaugeValue::Absolute(value) if value < 0.0 => vec![
+            format_line(name, "0", "g", labels),
+            format_line(name, &value.to_string(), "g", labels),
+        ],
+        GaugeValue::Absolute(value) => vec![format_line(name, &value.to_string(), "g", labels)],
+        GaugeValue::Increment(amount) => {
+            vec![format_line(name, &format!("+{}", amount), "g", labels)]
+        }
+        GaugeValue::Decrement(amount) => {
+            vec![format_line(name, &format!("-{}", amount), "g", labels)]
+        }
+    }
+}
+
+impl Recorder for StatsdRecorder {
+    fn increment_counter(&self, key: &Key, value: u64) {
+        let (name, labels) = key.clone().into_parts();
+        self.send(&[format_line(&name, &value.to_string(), "c", &labels)]);
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        let (name, labels) = key.clone().into_parts();
+        self.send(&format_gauge(&name, value, &labels));
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        let (name, labels) = key.clone().into_parts();
+        self.send(&[format_line(&name, &value.to_string(), "h", &labels)]);
+    }
+
+    // statsd creates metrics when they are first sent, so registration is a no-op
+
+    fn register_counter(
+        &self,
+        _key: &Key,
+        _unit: Option<Unit>,
+        _description: Option<&'static str>,
+    ) {
+    }
+
+    fn register_gauge(&self, _key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {}
+
+    fn register_histogram(
+        &self,
+        _key: &Key,
+        _unit: Option<Unit>,
+        _description: Option<&'static str>,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that counters and histograms are formatted with their tags.
+    #[test]
+    fn format_with_tags() {
+        assert_eq!(
+            format_line("splinter.test", "1", "c", &[]),
+            "splinter.test:1|c".to_string()
+        );
+        assert_eq!(
+            format_line(
+                "splinter.test",
+                "2.5",
+                "h",
+                &[
+                    Label::new("circuit", "abcde-01234"),
+                    Label::new("node_id", "alpha")
+                ]
+            ),
+            "splinter.test:2.5|h|#circuit:abcde-01234,node_id:alpha".to_string()
+        );
+    }
+
+    /// Verify that gauge updates are formatted as absolute values or changes, and that negative
+    /// absolute values are sent as a reset followed by a decrement.
+    #[test]
+    fn format_gauges() {
+        assert_eq!(
+            format_gauge("g", GaugeValue::Absolute(3.0), &[]),
+            vec!["g:3|g".to_string()]
+        );
+        assert_eq!(
+            format_gauge("g", GaugeValue::Increment(2.0), &[]),
+            vec!["g:+2|g".to_string()]
+        );
+        assert_eq!(
+            format_gauge("g", GaugeValue::Decrement(2.0), &[]),
+            vec!["g:-2|g".to_string()]
+        );
+        assert_eq!(
+            format_gauge("g", GaugeValue::Absolute(-4.0), &[]),
+            vec!["g:0|g".to_string(), "g:-4|g".to_string()]
+        );
+    }
+
+    /// Verify that metrics are delivered to the statsd address.
+    #[test]
+    fn send_to_socket() {
+        let server = UdpSocket::bind("127.0.0.1:0").expect("Unable to bind test socket");
+        let address = server
+            .local_addr()
+            .expect("Unable to get address")
+            .to_string();
+
+        let recorder = StatsdRecorder::new(&address).expect("Unable to create recorder");
+        recorder.increment_counter(&Key::from_name("splinter.test"), 5);
+
+        let mut buf = [0; 128];
+        let len = server.recv(&mut buf).expect("Unable to receive metric");
+        assert_eq!(&buf[..len], b"splinter.test:5|c");
+    }
+}
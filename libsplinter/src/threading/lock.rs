@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Lock acquisition that recovers from poisoning.
+//!
+//! The standard library marks a `Mutex` or `RwLock` as poisoned when a thread panics while
+//! holding it. Unwrapping such a lock propagates the panic to every other thread that touches
+//! it, which can take down the entire daemon. The functions in this module instead log the
+//! poisoning, increment the `splinter.lock.poisoned` counter, and return the guard for the data
+//! as it was left by the panicking thread.
+//!
+//! Callers should only use these functions for locks whose data remains consistent (or is safe
+//! to keep using) after a partial update.
+
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Acquire the given mutex, recovering the guard if the mutex has been poisoned.
+///
+/// The `name` is used to identify the lock in the log message and the metric.
+pub fn lock_or_recover<'a, T>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|err| recover(err, name))
+}
+
+/// Acquire a read guard for the given lock, recovering the guard if the lock has been poisoned.
+///
+/// The `name` is used to identify the lock in the log message and the metric.
+pub fn read_or_recover<'a, T>(lock: &'a RwLock<T>, name: &str) -> RwLockReadGuard<'a, T> {
+    lock.read().unwrap_or_else(|err| recover(err, name))
+}
+
+/// Acquire a write guard for the given lock, recovering the guard if the lock has been poisoned.
+///
+/// The `name` is used to identify the lock in the log message and the metric.
+pub fn write_or_recover<'a, T>(lock: &'a RwLock<T>, name: &str) -> RwLockWriteGuard<'a, T> {
+    lock.write().unwrap_or_else(|err| recover(err, name))
+}
+
+fn recover<G>(err: PoisonError<G>, name: &str) -> G {
+    warn!(
+        "Lock {} was poisoned by a panicked thread; recovering its data",
+        name
+    );
+    counter!("splinter.lock.poisoned", 1, "lock" => name.to_string());
+    err.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+    use std::thread;
+
+    /// Verify that a poisoned mutex can be acquired and that it holds the value written before
+    /// the panic.
+    #[test]
+    fn test_lock_or_recover_poisoned() {
+        let mutex = Arc::new(Mutex::new(0));
+
+        let thread_mutex = mutex.clone();
+        let result = thread::spawn(move || {
+            let mut guard = thread_mutex.lock().unwrap();
+            *guard = 1;
+            panic!("poison the mutex");
+        })
+        .join();
+
+        assert!(result.is_err());
+        assert!(mutex.is_poisoned());
+
+        let mut guard = lock_or_recover(&mutex, "test");
+        assert_eq!(*guard, 1);
+        *guard = 2;
+        drop(guard);
+
+        assert_eq!(*lock_or_recover(&mutex, "test"), 2);
+    }
+
+    /// Verify that a poisoned RwLock can be acquired for both reading and writing.
+    #[test]
+    fn test_rwlock_or_recover_poisoned() {
+        let lock = Arc::new(RwLock::new(vec![1]));
+
+        let thread_lock = lock.clone();
+        let result = thread::spawn(move || {
+            let mut guard = thread_lock.write().unwrap();
+            guard.push(2);
+            panic!("poison the lock");
+        })
+        .join();
+
+        assert!(result.is_err());
+        assert!(lock.is_poisoned());
+
+        write_or_recover(&lock, "test").push(3);
+        assert_eq!(*read_or_recover(&lock, "test"), vec![1, 2, 3]);
+    }
+
+    /// Verify that healthy locks are acquired normally.
+    #[test]
+    fn test_lock_or_recover_healthy() {
+        let mutex = Mutex::new("value");
+        assert_eq!(*lock_or_recover(&mutex, "test"), "value");
+
+        let lock = RwLock::new(5);
+        *write_or_recover(&lock, "test") += 1;
+        assert_eq!(*read_or_recover(&lock, "test"), 6);
+    }
+}
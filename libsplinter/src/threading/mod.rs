@@ -16,5 +16,6 @@
 
 pub(crate) mod error;
 pub mod lifecycle;
+pub mod lock;
 pub(crate) mod pacemaker;
 pub(crate) mod pool;
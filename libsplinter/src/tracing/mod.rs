@@ -0,0 +1,297 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Tracing of requests as they flow through the REST API, the network and services.
+//!
+//! A [`Span`] records a named unit of work, such as a REST request or the execution of a batch,
+//! along with when it started and ended and a set of attributes, such as the circuit ID. A span
+//! started while another span is entered on the same thread becomes its child, so the spans of a
+//! request form a tree that shares a single trace ID. A trace may be continued from another
+//! process using a W3C `traceparent` value; see [`SpanContext::from_traceparent`].
+//!
+//! Spans are only recorded once an exporter has been installed with [`init_tracer`]; until then,
+//! creating a span does nothing. The [`otlp`] module provides an exporter that sends spans to an
+//! OpenTelemetry collector, from which they may be viewed with tools such as Jaeger or Tempo.
+//!
+//! Available if the `tracing` feature is enabled
+
+pub mod otlp;
+
+use std::cell::RefCell;
+use std::time::SystemTime;
+
+use once_cell::sync::OnceCell;
+
+use crate::error::InternalError;
+use crate::hex::to_hex;
+
+static EXPORTER: OnceCell<Box<dyn SpanExporter>> = OnceCell::new();
+
+thread_local! {
+    // The context of the span currently entered on this thread, if any
+    static CURRENT_CONTEXT: RefCell<Option<SpanContext>> = RefCell::new(None);
+}
+
+/// Receives the spans that have ended.
+pub trait SpanExporter: Send + Sync {
+    /// Exports a completed span. This is called on the thread that ended the span, so it should
+    /// not block.
+    fn export(&self, span: SpanData);
+}
+
+/// Installs the exporter that receives every span that ends. Spans are only recorded once an
+/// exporter has been installed.
+///
+/// # Errors
+///
+/// Returns an `InternalError` if an exporter has already been installed.
+pub fn init_tracer(exporter: Box<dyn SpanExporter>) -> Result<(), InternalError> {
+    EXPORTER
+        .set(exporter)
+        .map_err(|_| InternalError::with_message("A span exporter is already installed".into()))
+}
+
+/// Identifies a span and the trace it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpanContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+}
+
+impl SpanContext {
+    /// Parses a W3C trace context `traceparent` value, such as
+    /// `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`.
+    ///
+    /// Returns `None` if the value is not valid.
+    pub fn from_traceparent(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parse_id::<16>(parts.next()?)?;
+        let span_id = parse_id::<8>(parts.next()?)?;
+        let _flags = parts.next().filter(|flags| flags.len() == 2)?;
+
+        if version != "00" || parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self { trace_id, span_id })
+    }
+
+    /// Returns the W3C trace context `traceparent` value for this span, which may be sent to
+    /// another process to continue the trace.
+    pub fn to_traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id(), self.span_id())
+    }
+
+    /// Returns the hex-encoded trace ID.
+    pub fn trace_id(&self) -> String {
+        to_hex(&self.trace_id)
+    }
+
+    /// Returns the hex-encoded span ID.
+    pub fn span_id(&self) -> String {
+        to_hex(&self.span_id)
+    }
+}
+
+/// The role of a span in a trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpanKind {
+    /// Work done within the node
+    Internal,
+    /// The handling of a request received from a client
+    Server,
+}
+
+/// A completed span, as it is passed to the [`SpanExporter`].
+#[derive(Clone, Debug)]
+pub struct SpanData {
+    pub name: String,
+    pub kind: SpanKind,
+    pub context: SpanContext,
+    pub parent_span_id: Option<[u8; 8]>,
+    pub start_time: SystemTime,
+    pub end_time: SystemTime,
+    pub attributes: Vec<(String, String)>,
+    /// The error message, if the work failed
+    pub error: Option<String>,
+}
+
+/// A unit of work that is recorded when it is dropped.
+///
+/// ```ignore
+/// let mut span = Span::new("scabbard.execute_batch");
+/// span.set_attribute("circuit_id", circuit_id);
+/// let _guard = span.enter();
+/// // spans started here are children of `span`
+/// ```
+pub struct Span {
+    // `None` if no exporter is installed, in which case nothing is recorded
+    data: Option<SpanData>,
+}
+
+impl Span {
+    /// Starts an internal span. It is a child of the span entered on the current thread, if
+    /// any; otherwise, it starts a new trace.
+    pub fn new(name: &str) -> Self {
+        let parent = CURRENT_CONTEXT.with(|current| *current.borrow());
+        Self::start(name, SpanKind::Internal, parent)
+    }
+
+    /// Starts a span for a request received from a client. If the client sent the context of
+    /// its own span, the new span continues that trace.
+    pub fn server(name: &str, parent: Option<SpanContext>) -> Self {
+        Self::start(name, SpanKind::Server, parent)
+    }
+
+    fn start(name: &str, kind: SpanKind, parent: Option<SpanContext>) -> Self {
+        if EXPORTER.get().is_none() {
+            return Self { data: None };
+        }
+
+        let (trace_id, parent_span_id) = match parent {
+            Some(parent) => (parent.trace_id, Some(parent.span_id)),
+            None => (random_id::<16>(), None),
+        };
+
+        Self {
+            data: Some(SpanData {
+                name: name.to_string(),
+                kind,
+                context: SpanContext {
+                    trace_id,
+                    span_id: random_id::<8>(),
+                },
+                parent_span_id,
+                start_time: SystemTime::now(),
+                end_time: SystemTime::now(),
+                attributes: vec![],
+                error: None,
+            }),
+        }
+    }
+
+    /// Adds an attribute to the span, such as the ID of the circuit the work is for.
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        if let Some(data) = self.data.as_mut() {
+            data.attributes.push((key.to_string(), value.to_string()));
+        }
+    }
+
+    /// Adds an attribute to the span and returns it.
+    pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+        self.set_attribute(key, value);
+        self
+    }
+
+    /// Marks the work as failed with the given message.
+    pub fn set_error(&mut self, message: &str) {
+        if let Some(data) = self.data.as_mut() {
+            data.error = Some(message.to_string());
+        }
+    }
+
+    /// Returns the context of this span, or `None` if spans are not being recorded.
+    pub fn context(&self) -> Option<SpanContext> {
+        self.data.as_ref().map(|data| data.context)
+    }
+
+    /// Makes this the current span of the thread until the returned guard is dropped, so that
+    /// spans started in the meantime become its children.
+    pub fn enter(&self) -> SpanGuard {
+        let previous = self
+            .context()
+            .map(|context| CURRENT_CONTEXT.with(|current| current.borrow_mut().replace(context)));
+
+        SpanGuard { previous }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let (Some(mut data), Some(exporter)) = (self.data.take(), EXPORTER.get()) {
+            data.end_time = SystemTime::now();
+            exporter.export(data);
+        }
+    }
+}
+
+/// Restores the previously entered span of the thread when dropped.
+#[must_use = "the span is exited when the guard is dropped"]
+pub struct SpanGuard {
+    // `None` if the span was not entered, otherwise the context that was current before it
+    previous: Option<Option<SpanContext>>,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            CURRENT_CONTEXT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Generates a random, non-zero ID; an ID of all zeros is invalid.
+fn random_id<const N: usize>() -> [u8; N] {
+    loop {
+        let id = [(); N].map(|_| rand::random::<u8>());
+        if id.iter().any(|byte| *byte != 0) {
+            return id;
+        }
+    }
+}
+
+fn parse_id<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut id = [0; N];
+    for (i, byte) in id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    if id.iter().all(|byte| *byte == 0) {
+        None
+    } else {
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a `traceparent` value is parsed and written back unchanged, and that invalid
+    /// values are rejected.
+    #[test]
+    fn traceparent_round_trip() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = SpanContext::from_traceparent(traceparent).expect("Unable to parse");
+
+        assert_eq!(context.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(context.span_id(), "00f067aa0ba902b7");
+        assert_eq!(context.to_traceparent(), traceparent);
+
+        assert!(SpanContext::from_traceparent(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        )
+        .is_none());
+        assert!(SpanContext::from_traceparent(
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+        )
+        .is_none());
+        assert!(SpanContext::from_traceparent("00-4bf92f35-00f067aa0ba902b7-01").is_none());
+        assert!(SpanContext::from_traceparent("not a traceparent").is_none());
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Contains an OpenTelemetry (OTLP) implementation of the [SpanExporter] trait.
+//! `OtlpSpanExporter` collects completed spans and periodically exports them to an OTLP
+//! collector using the OTLP/HTTP JSON encoding. Each span is exported with the ID of the node as
+//! a resource attribute.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::error::InternalError;
+use crate::hex::to_hex;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::{SpanData, SpanExporter, SpanKind};
+
+const DEFAULT_EXPORT_INTERVAL: Duration = Duration::from_secs(5);
+// Spans are exported early if this many are waiting, to bound the size of a request
+const MAX_BATCH_SIZE: usize = 512;
+
+enum ExportRequest {
+    Span(Box<SpanData>),
+    Shutdown,
+}
+
+/// Exports spans to an OpenTelemetry collector
+pub struct OtlpSpanExporter {
+    sender: Sender<ExportRequest>,
+    join_handle: JoinHandle<()>,
+}
+
+impl OtlpSpanExporter {
+    /// Creates an exporter that sends spans to the OTLP/HTTP collector at the given URL every
+    /// five seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The base URL of the collector, such as `http://localhost:4318`; spans are sent
+    ///   to `<url>/v1/traces`
+    /// * `node_id` - The ID of the local node, which is added to every span
+    pub fn new(url: &str, node_id: &str) -> Result<Self, InternalError> {
+        Self::with_interval(url, node_id, DEFAULT_EXPORT_INTERVAL)
+    }
+
+    /// Creates an exporter that sends spans to the OTLP/HTTP collector at the given URL at the
+    /// given interval.
+    pub fn with_interval(
+        url: &str,
+        node_id: &str,
+        interval: Duration,
+    ) -> Result<Self, InternalError> {
+        let endpoint = format!("{}/v1/traces", url.trim_end_matches('/'));
+        let node_id = node_id.to_string();
+        let (sender, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("OtlpSpanExporter".into())
+            .spawn(move || {
+                let client = reqwest::blocking::Client::new();
+                let mut spans = vec![];
+                let mut next_export = Instant::now() + interval;
+
+                loop {
+                    let timeout = next_export.saturating_duration_since(Instant::now());
+                    let shutdown = match receiver.recv_timeout(timeout) {
+                        Ok(ExportRequest::Shutdown) | Err(RecvTimeoutError::Disconnected) => true,
+                        Ok(ExportRequest::Span(span)) => {
+                            spans.push(*span);
+                            if spans.len() < MAX_BATCH_SIZE {
+                                continue;
+                            }
+                            false
+                        }
+                        Err(RecvTimeoutError::Timeout) => false,
+                    };
+
+                    if !spans.is_empty() {
+                        let request = to_export_request(&spans, &node_id);
+                        spans.clear();
+                        match client.post(&endpoint).json(&request).send() {
+                            Ok(response) if !response.status().is_success() => {
+                                error!("OTLP collector rejected spans: {}", response.status())
+                            }
+                            Ok(_) => (),
+                            Err(err) => error!("Unable to export spans to OTLP collector: {}", err),
+                        }
+                    }
+
+                    if shutdown {
+                        info!("Received ExportRequest::Shutdown");
+                        break;
+                    }
+                    next_export = Instant::now() + interval;
+                }
+            })
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    "Unable to start OTLP span exporter thread".into(),
+                )
+            })?;
+
+        Ok(Self {
+            sender,
+            join_handle,
+        })
+    }
+
+    /// Returns a [SpanExporter] that passes spans to this exporter, to be installed with
+    /// [`init_tracer`](super::init_tracer).
+    pub fn span_exporter(&self) -> Box<dyn SpanExporter> {
+        Box::new(OtlpSpanSender {
+            sender: Mutex::new(self.sender.clone()),
+        })
+    }
+}
+
+impl ShutdownHandle for OtlpSpanExporter {
+    fn signal_shutdown(&mut self) {
+        if self.sender.send(ExportRequest::Shutdown).is_err() {
+            error!("Unable to send shutdown message to OtlpSpanExporter");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "Unable to join OtlpSpanExporter thread: {:?}",
+                err
+            ))
+        })
+    }
+}
+
+struct OtlpSpanSender {
+    sender: Mutex<Sender<ExportRequest>>,
+}
+
+impl SpanExporter for OtlpSpanSender {
+    fn export(&self, span: SpanData) {
+        let sender = match self.sender.lock() {
+            Ok(sender) => sender,
+            Err(err) => err.into_inner(),
+        };
+        // spans that end after the exporter has shut down are dropped
+        if sender.send(ExportRequest::Span(Box::new(span))).is_err() {
+            trace!("Dropping span; the OTLP span exporter has shut down");
+        }
+    }
+}
+
+/// Builds an OTLP `ExportTraceServiceRequest` containing the given spans.
+fn to_export_request(spans: &[SpanData], node_id: &str) -> Value {
+    let spans = spans
+        .iter()
+        .map(|span| {
+            let mut value = json!({
+                "traceId": span.context.trace_id(),
+                "spanId": span.context.span_id(),
+                "name": span.name,
+                "kind": match span.kind {
+                    SpanKind::Internal => 1,
+                    SpanKind::Server => 2,
+                },
+                "startTimeUnixNano": unix_nanos(span.start_time).to_string(),
+                "endTimeUnixNano": unix_nanos(span.end_time).to_string(),
+                "attributes": attributes(&span.attributes),
+                "status": match &span.error {
+                    Some(message) => json!({ "code": 2, "message": message }),
+                    None => json!({ "code": 1 }),
+                },
+            });
+            if let Some(parent_span_id) = &span.parent_span_id {
+                value["parentSpanId"] = json!(to_hex(parent_span_id));
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "splinterd" } },
+                    { "key": "node_id", "value": { "stringValue": node_id } },
+                ],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "splinter" },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn attributes(attributes: &[(String, String)]) -> Value {
+    Value::Array(
+        attributes
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+            .collect(),
+    )
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tracing::SpanContext;
+
+    /// Verify that spans are exported in the OTLP JSON format, with the node ID as a resource
+    /// attribute and the parent span ID only set on child spans.
+    #[test]
+    fn export_request() {
+        let context = SpanContext::from_traceparent(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .expect("Unable to parse traceparent");
+        let start_time = UNIX_EPOCH + Duration::from_secs(1);
+        let end_time = UNIX_EPOCH + Duration::from_secs(2);
+
+        let spans = vec![
+            SpanData {
+                name: "GET /admin/circuits".into(),
+                kind: SpanKind::Server,
+                context,
+                parent_span_id: None,
+                start_time,
+                end_time,
+                attributes: vec![("http.status_code".into(), "200".into())],
+                error: None,
+            },
+            SpanData {
+                name: "admin.submit".into(),
+                kind: SpanKind::Internal,
+                context,
+                parent_span_id: Some([1; 8]),
+                start_time,
+                end_time,
+                attributes: vec![("circuit_id".into(), "abcde-01234".into())],
+                error: Some("invalid payload".into()),
+            },
+        ];
+
+        let request = to_export_request(&spans, "node-a");
+        let resource = &request["resourceSpans"][0]["resource"];
+        assert_eq!(resource["attributes"][1]["value"]["stringValue"], "node-a");
+
+        let exported = &request["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(exported.as_array().map(Vec::len), Some(2));
+
+        assert_eq!(exported[0]["traceId"], "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(exported[0]["spanId"], "00f067aa0ba902b7");
+        assert_eq!(exported[0]["kind"], 2);
+        assert_eq!(exported[0]["startTimeUnixNano"], "1000000000");
+        assert_eq!(exported[0]["endTimeUnixNano"], "2000000000");
+        assert_eq!(exported[0]["status"]["code"], 1);
+        assert!(exported[0].get("parentSpanId").is_none());
+
+        assert_eq!(exported[1]["parentSpanId"], "0101010101010101");
+        assert_eq!(exported[1]["status"]["code"], 2);
+        assert_eq!(exported[1]["status"]["message"], "invalid payload");
+        assert_eq!(
+            exported[1]["attributes"],
+            json!([{ "key": "circuit_id", "value": { "stringValue": "abcde-01234" } }])
+        );
+    }
+}
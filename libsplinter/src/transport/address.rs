@@ -0,0 +1,308 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for working with IPv4 and IPv6 network endpoints.
+//!
+//! IPv6 literals must be enclosed in brackets when combined with a port, as in
+//! `tcp://[::1]:8044`. Binding to the unspecified IPv6 address (`[::]`) listens on both IPv4 and
+//! IPv6, regardless of the operating system's default for `IPV6_V6ONLY`.
+
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, SocketAddr, TcpListener, ToSocketAddrs};
+use std::str::FromStr;
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+use crate::error::InvalidArgumentError;
+
+const LISTEN_BACKLOG: i32 = 128;
+
+/// The protocols whose endpoints are socket addresses; endpoints of other protocols, such as
+/// `unix://`, are not checked.
+const SOCKET_PROTOCOLS: &[&str] = &["tcp", "tcps", "tls", "ws", "wss"];
+
+/// An IP address family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressFamily {
+    Ipv4,
+    Ipv6,
+}
+
+impl AddressFamily {
+    /// Returns the address family of the given endpoint's host, or `None` if the host is a name
+    /// rather than an IP address.
+    pub fn of_endpoint(endpoint: &str) -> Option<AddressFamily> {
+        let host = host(strip_protocol(endpoint));
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(_)) => Some(AddressFamily::Ipv4),
+            Ok(IpAddr::V6(_)) => Some(AddressFamily::Ipv6),
+            Err(_) => None,
+        }
+    }
+}
+
+impl FromStr for AddressFamily {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ipv4" | "v4" => Ok(AddressFamily::Ipv4),
+            "ipv6" | "v6" => Ok(AddressFamily::Ipv6),
+            _ => Err(InvalidArgumentError::new(
+                "address_family",
+                format!("\"{}\" is not one of ipv4 or ipv6", s),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AddressFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressFamily::Ipv4 => f.write_str("ipv4"),
+            AddressFamily::Ipv6 => f.write_str("ipv6"),
+        }
+    }
+}
+
+/// Orders the given endpoints so that those of the preferred address family come first,
+/// followed by endpoints using host names, followed by the remaining endpoints. The relative
+/// order of endpoints within each group is kept.
+pub fn order_endpoints(endpoints: &mut [String], preferred: AddressFamily) {
+    endpoints.sort_by_key(|endpoint| match AddressFamily::of_endpoint(endpoint) {
+        Some(family) if family == preferred => 0,
+        None => 1,
+        Some(_) => 2,
+    });
+}
+
+/// Checks that the given endpoint, with or without a protocol prefix, is of the form `host:port`
+/// with any IPv6 literal enclosed in brackets.
+pub fn validate_endpoint(endpoint: &str) -> Result<(), InvalidArgumentError> {
+    if let Some(index) = endpoint.find("://") {
+        let protocol = endpoint[..index].trim_end_matches("+trust");
+        if !SOCKET_PROTOCOLS.contains(&protocol) {
+            return Ok(());
+        }
+    }
+
+    let address = strip_protocol(endpoint);
+
+    if address.starts_with('[') {
+        let port = address
+            .find(']')
+            .map(|end| &address[end + 1..])
+            .ok_or_else(|| {
+                InvalidArgumentError::new(
+                    "endpoint",
+                    format!("missing closing bracket in endpoint \"{}\"", endpoint),
+                )
+            })?;
+        if !port.starts_with(':') {
+            return Err(InvalidArgumentError::new(
+                "endpoint",
+                format!("missing port in endpoint \"{}\"", endpoint),
+            ));
+        }
+        return Ok(());
+    }
+
+    match address.matches(':').count() {
+        0 => Err(InvalidArgumentError::new(
+            "endpoint",
+            format!("missing port in endpoint \"{}\"", endpoint),
+        )),
+        1 => Ok(()),
+        _ => Err(InvalidArgumentError::new(
+            "endpoint",
+            format!(
+                "IPv6 addresses must be enclosed in brackets, such as tcp://[::1]:8044; \
+                 found \"{}\"",
+                endpoint
+            ),
+        )),
+    }
+}
+
+/// Binds a listener to the given `host:port` address. The unspecified IPv6 address is bound in
+/// dual-stack mode, so that the listener also accepts IPv4 connections.
+pub(crate) fn bind(address: &str) -> io::Result<TcpListener> {
+    let mut last_err = None;
+    for socket_addr in address.to_socket_addrs()? {
+        match bind_socket_addr(socket_addr) {
+            Ok(listener) => return Ok(listener),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "could not resolve to any addresses",
+        )
+    }))
+}
+
+fn bind_socket_addr(socket_addr: SocketAddr) -> io::Result<TcpListener> {
+    match socket_addr {
+        SocketAddr::V6(addr) if addr.ip().is_unspecified() => {
+            let socket = Socket::new(Domain::IPV6, Type::STREAM, Some(Protocol::TCP))?;
+            socket.set_only_v6(false)?;
+            socket.set_reuse_address(true)?;
+            socket.bind(&socket_addr.into())?;
+            socket.listen(LISTEN_BACKLOG)?;
+            Ok(socket.into())
+        }
+        _ => TcpListener::bind(socket_addr),
+    }
+}
+
+fn strip_protocol(endpoint: &str) -> &str {
+    match endpoint.find("://") {
+        Some(index) => &endpoint[index + 3..],
+        None => endpoint,
+    }
+}
+
+/// Returns the host part of a `host:port` address, without any IPv6 brackets.
+fn host(address: &str) -> &str {
+    if let Some(rest) = address.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+
+    match address.rfind(':') {
+        Some(index) if address.matches(':').count() == 1 => &address[..index],
+        _ => address,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpStream;
+
+    /// Verify that the address family of an endpoint is determined from its host, with or
+    /// without a protocol prefix, and that host names have no address family.
+    #[test]
+    fn test_address_family_of_endpoint() {
+        assert_eq!(
+            AddressFamily::of_endpoint("tcp://127.0.0.1:8044"),
+            Some(AddressFamily::Ipv4)
+        );
+        assert_eq!(
+            AddressFamily::of_endpoint("127.0.0.1:8044"),
+            Some(AddressFamily::Ipv4)
+        );
+        assert_eq!(
+            AddressFamily::of_endpoint("tcps://[::1]:8044"),
+            Some(AddressFamily::Ipv6)
+        );
+        assert_eq!(
+            AddressFamily::of_endpoint("ws://[fe80::1]:8044"),
+            Some(AddressFamily::Ipv6)
+        );
+        assert_eq!(AddressFamily::of_endpoint("tcp://localhost:8044"), None);
+    }
+
+    /// Verify that endpoints are ordered by the preferred address family, with host names in
+    /// between, and that the order within each group is kept.
+    #[test]
+    fn test_order_endpoints() {
+        let endpoints = vec![
+            "tcps://10.0.0.1:8044".to_string(),
+            "tcps://node.example.com:8044".to_string(),
+            "tcps://[2001:db8::1]:8044".to_string(),
+            "tcps://10.0.0.2:8044".to_string(),
+            "tcps://[2001:db8::2]:8044".to_string(),
+        ];
+
+        let mut v6_first = endpoints.clone();
+        order_endpoints(&mut v6_first, AddressFamily::Ipv6);
+        assert_eq!(
+            v6_first,
+            vec![
+                "tcps://[2001:db8::1]:8044".to_string(),
+                "tcps://[2001:db8::2]:8044".to_string(),
+                "tcps://node.example.com:8044".to_string(),
+                "tcps://10.0.0.1:8044".to_string(),
+                "tcps://10.0.0.2:8044".to_string(),
+            ]
+        );
+
+        let mut v4_first = endpoints;
+        order_endpoints(&mut v4_first, AddressFamily::Ipv4);
+        assert_eq!(
+            v4_first,
+            vec![
+                "tcps://10.0.0.1:8044".to_string(),
+                "tcps://10.0.0.2:8044".to_string(),
+                "tcps://node.example.com:8044".to_string(),
+                "tcps://[2001:db8::1]:8044".to_string(),
+                "tcps://[2001:db8::2]:8044".to_string(),
+            ]
+        );
+    }
+
+    /// Verify that endpoints with bracketed IPv6 literals, IPv4 literals, and host names are
+    /// valid, and that unbracketed IPv6 literals and missing ports are rejected.
+    #[test]
+    fn test_validate_endpoint() {
+        assert!(validate_endpoint("tcp://[::1]:8044").is_ok());
+        assert!(validate_endpoint("[::]:8044").is_ok());
+        assert!(validate_endpoint("tcps://127.0.0.1:8044").is_ok());
+        assert!(validate_endpoint("ws://node.example.com:8044").is_ok());
+        assert!(validate_endpoint("tcps+trust://[::1]:8044").is_ok());
+        assert!(validate_endpoint("unix:///var/run/splinter.sock").is_ok());
+
+        assert!(validate_endpoint("tcp://::1:8044").is_err());
+        assert!(validate_endpoint("tcp://[::1]").is_err());
+        assert!(validate_endpoint("tcp://[::1:8044").is_err());
+        assert!(validate_endpoint("tcp://127.0.0.1").is_err());
+        assert!(validate_endpoint("tcps+trust://::1:8044").is_err());
+    }
+
+    #[test]
+    fn test_address_family_from_str() {
+        assert_eq!(
+            "ipv4".parse::<AddressFamily>().ok(),
+            Some(AddressFamily::Ipv4)
+        );
+        assert_eq!(
+            "IPv6".parse::<AddressFamily>().ok(),
+            Some(AddressFamily::Ipv6)
+        );
+        assert!("ipv5".parse::<AddressFamily>().is_err());
+    }
+
+    /// Verify that a listener bound to the unspecified IPv6 address accepts connections over
+    /// both IPv6 and IPv4.
+    ///
+    /// The test is skipped if the host does not support IPv6.
+    #[test]
+    fn test_bind_dual_stack() {
+        let listener = match bind("[::]:0") {
+            Ok(listener) => listener,
+            Err(_) => return,
+        };
+        let port = listener.local_addr().expect("no local address").port();
+
+        TcpStream::connect(("::1", port)).expect("unable to connect over IPv6");
+        listener.accept().expect("unable to accept IPv6 connection");
+
+        TcpStream::connect(("127.0.0.1", port)).expect("unable to connect over IPv4");
+        listener.accept().expect("unable to accept IPv4 connection");
+    }
+}
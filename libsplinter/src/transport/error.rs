@@ -302,6 +302,38 @@ impl std::fmt::Display for ConnectionMatrixSendError {
     }
 }
 
+#[derive(Debug)]
+pub enum ConnectionMatrixTrySendError {
+    /// The connection's outgoing queue is full; contains the message that was not sent
+    Full(Vec<u8>),
+    /// The send failed for a reason other than a full queue
+    Send(ConnectionMatrixSendError),
+}
+
+impl Error for ConnectionMatrixTrySendError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConnectionMatrixTrySendError::Full(_) => None,
+            ConnectionMatrixTrySendError::Send(err) => Some(err),
+        }
+    }
+}
+
+impl std::fmt::Display for ConnectionMatrixTrySendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConnectionMatrixTrySendError::Full(_) => f.write_str("connection send queue is full"),
+            ConnectionMatrixTrySendError::Send(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<ConnectionMatrixSendError> for ConnectionMatrixTrySendError {
+    fn from(err: ConnectionMatrixSendError) -> Self {
+        ConnectionMatrixTrySendError::Send(err)
+    }
+}
+
 #[derive(Debug)]
 pub enum ConnectionMatrixRecvError {
     Disconnected,
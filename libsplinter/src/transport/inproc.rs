@@ -22,6 +22,7 @@ use std::sync::{
     Arc, Mutex,
 };
 
+use crate::threading::lock::lock_or_recover;
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
     SendError, Transport,
@@ -54,7 +55,7 @@ impl Transport for InprocTransport {
             endpoint
         };
 
-        match self.incoming.lock().unwrap().get(address) {
+        match lock_or_recover(&self.incoming, "inproc incoming").get(address) {
             Some(sender) => {
                 let (p0, p1) = Pair::new();
                 sender.send(p0).unwrap();
@@ -81,7 +82,7 @@ impl Transport for InprocTransport {
         };
 
         let (tx, rx) = channel();
-        self.incoming.lock().unwrap().insert(address.into(), tx);
+        lock_or_recover(&self.incoming, "inproc incoming").insert(address.into(), tx);
         Ok(Box::new(InprocListener::new(address.into(), rx)))
     }
 }
@@ -36,7 +36,7 @@ use super::Connection;
 
 pub use super::error::{
     ConnectionMatrixAddError, ConnectionMatrixRecvError, ConnectionMatrixRecvTimeoutError,
-    ConnectionMatrixRemoveError, ConnectionMatrixSendError,
+    ConnectionMatrixRemoveError, ConnectionMatrixSendError, ConnectionMatrixTrySendError,
 };
 
 /// Contains a payload and the identifier for the connection on which the payload was received
@@ -130,6 +130,31 @@ pub trait ConnectionMatrixSender: Clone + Send {
     ///
     /// If the send failed, a `ConnectionMatrixSendError` will be returned.
     fn send(&self, id: String, message: Vec<u8>) -> Result<(), ConnectionMatrixSendError>;
+
+    /// Attempts to send a message over the specified connection without blocking.
+    ///
+    /// If the connection's outgoing queue is full, `ConnectionMatrixTrySendError::Full` is
+    /// returned with the message. The default implementation is equivalent to `send`.
+    fn try_send(&self, id: String, message: Vec<u8>) -> Result<(), ConnectionMatrixTrySendError> {
+        self.send(id, message)
+            .map_err(ConnectionMatrixTrySendError::from)
+    }
+
+    /// Sends a message over the specified connection, waiting up to `timeout` for room in the
+    /// connection's outgoing queue.
+    ///
+    /// If the queue is still full when the timeout expires,
+    /// `ConnectionMatrixTrySendError::Full` is returned with the message. The default
+    /// implementation is equivalent to `try_send`.
+    fn send_timeout(
+        &self,
+        id: String,
+        message: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<(), ConnectionMatrixTrySendError> {
+        let _ = timeout;
+        self.try_send(id, message)
+    }
 }
 
 /// Defines functions to receive messages from connections within the connection matrix
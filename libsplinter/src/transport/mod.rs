@@ -35,6 +35,8 @@
 //! [`Listener::accept`]: trait.Listener.html#tymethod.accept
 //! [`Transport`]: trait.Transport.html
 
+#[cfg(feature = "transport-ipv6")]
+pub mod address;
 mod error;
 pub mod inproc;
 pub(crate) mod matrix;
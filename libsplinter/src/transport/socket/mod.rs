@@ -13,11 +13,19 @@
 // limitations under the License.
 
 mod frame;
+#[cfg(feature = "transport-tcp-options")]
+mod options;
 mod tcp;
 mod tls;
+#[cfg(feature = "unix-transport")]
+mod unix;
 
+#[cfg(feature = "transport-tcp-options")]
+pub use options::TcpOptions;
 pub use tcp::TcpTransport;
 pub use tls::{TlsConnection, TlsInitError, TlsTransport};
+#[cfg(feature = "unix-transport")]
+pub use unix::UnixTransport;
 
 #[cfg(test)]
 pub mod tests {
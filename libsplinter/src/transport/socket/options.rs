@@ -0,0 +1,144 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Socket options applied to the TCP connections of the socket transports.
+
+use std::io;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use socket2::{SockRef, TcpKeepalive};
+
+/// Options applied to every TCP connection created or accepted by a `TcpTransport` or
+/// `TlsTransport`.
+///
+/// By default, no options are changed from the operating system's defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcpOptions {
+    keepalive: Option<Duration>,
+    user_timeout: Option<Duration>,
+    nodelay: bool,
+}
+
+impl TcpOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables TCP keepalive, sending probes after a connection has been idle for the given
+    /// duration.
+    ///
+    /// Keepalive probes stop middleboxes, such as NAT gateways and firewalls, from dropping
+    /// long-lived idle connections.
+    pub fn with_keepalive(mut self, idle: Duration) -> Self {
+        self.keepalive = Some(idle);
+        self
+    }
+
+    /// Sets the longest time that transmitted data may remain unacknowledged before the
+    /// connection is closed (`TCP_USER_TIMEOUT`).
+    ///
+    /// This option is only supported on Linux, and is ignored on other platforms.
+    pub fn with_user_timeout(mut self, timeout: Duration) -> Self {
+        self.user_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether Nagle's algorithm is disabled (`TCP_NODELAY`), so small messages are sent
+    /// immediately rather than being coalesced.
+    pub fn with_nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = nodelay;
+        self
+    }
+
+    pub fn keepalive(&self) -> Option<Duration> {
+        self.keepalive
+    }
+
+    pub fn user_timeout(&self) -> Option<Duration> {
+        self.user_timeout
+    }
+
+    pub fn nodelay(&self) -> bool {
+        self.nodelay
+    }
+
+    /// Applies the options to the given stream.
+    pub(super) fn apply(&self, stream: &TcpStream) -> io::Result<()> {
+        let socket = SockRef::from(stream);
+
+        if let Some(idle) = self.keepalive {
+            socket.set_tcp_keepalive(&TcpKeepalive::new().with_time(idle))?;
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(timeout) = self.user_timeout {
+            socket.set_tcp_user_timeout(Some(timeout))?;
+        }
+
+        if self.nodelay {
+            stream.set_nodelay(true)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+
+    /// Verify that the options are applied to a connected stream.
+    #[test]
+    fn test_apply() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let stream = TcpStream::connect(listener.local_addr().expect("No local address"))
+            .expect("Unable to connect");
+
+        let options = TcpOptions::new()
+            .with_keepalive(Duration::from_secs(30))
+            .with_user_timeout(Duration::from_secs(60))
+            .with_nodelay(true);
+        options.apply(&stream).expect("Unable to apply options");
+
+        let socket = SockRef::from(&stream);
+        assert!(socket.keepalive().expect("Unable to get keepalive"));
+        assert!(stream.nodelay().expect("Unable to get nodelay"));
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            socket
+                .tcp_user_timeout()
+                .expect("Unable to get user timeout"),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    /// Verify that the default options leave the stream unchanged.
+    #[test]
+    fn test_apply_default() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let stream = TcpStream::connect(listener.local_addr().expect("No local address"))
+            .expect("Unable to connect");
+
+        TcpOptions::default()
+            .apply(&stream)
+            .expect("Unable to apply options");
+
+        assert!(!SockRef::from(&stream)
+            .keepalive()
+            .expect("Unable to get keepalive"));
+        assert!(!stream.nodelay().expect("Unable to get nodelay"));
+    }
+}
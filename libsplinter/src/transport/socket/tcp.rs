@@ -16,17 +16,33 @@ use mio::{net::TcpStream as MioTcpStream, Evented};
 
 use std::net::{Shutdown, TcpListener as StdTcpListener, TcpStream};
 
+#[cfg(feature = "transport-ipv6")]
+use crate::transport::address;
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
     SendError, Transport,
 };
 
 use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+#[cfg(feature = "transport-tcp-options")]
+use super::TcpOptions;
 
 const PROTOCOL_PREFIX: &str = "tcp://";
 
 #[derive(Default)]
-pub struct TcpTransport {}
+pub struct TcpTransport {
+    #[cfg(feature = "transport-tcp-options")]
+    options: TcpOptions,
+}
+
+impl TcpTransport {
+    /// Sets the socket options applied to every connection created or accepted by the transport.
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn with_tcp_options(mut self, options: TcpOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
 
 impl Transport for TcpTransport {
     fn accepts(&self, address: &str) -> bool {
@@ -46,8 +62,15 @@ impl Transport for TcpTransport {
         } else {
             endpoint
         };
+
+        #[cfg(feature = "transport-ipv6")]
+        address::validate_endpoint(address)
+            .map_err(|err| ConnectError::ProtocolError(err.to_string()))?;
+
         // Connect a std::net::TcpStream to make sure connect() block
         let mut stream = TcpStream::connect(address)?;
+        #[cfg(feature = "transport-tcp-options")]
+        self.options.apply(&stream)?;
 
         let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
             .negotiate(&mut stream)
@@ -80,21 +103,36 @@ impl Transport for TcpTransport {
             bind
         };
 
+        #[cfg(feature = "transport-ipv6")]
+        let listener = {
+            address::validate_endpoint(address)
+                .map_err(|err| ListenError::ProtocolError(err.to_string()))?;
+            address::bind(address)
+        };
+        #[cfg(not(feature = "transport-ipv6"))]
+        let listener = StdTcpListener::bind(address);
+
         Ok(Box::new(TcpListener {
-            listener: StdTcpListener::bind(address).map_err(|err| {
+            listener: listener.map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
+            #[cfg(feature = "transport-tcp-options")]
+            options: self.options.clone(),
         }))
     }
 }
 
 struct TcpListener {
     listener: StdTcpListener,
+    #[cfg(feature = "transport-tcp-options")]
+    options: TcpOptions,
 }
 
 impl Listener for TcpListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (mut stream, _) = self.listener.accept()?;
+        #[cfg(feature = "transport-tcp-options")]
+        self.options.apply(&stream)?;
 
         let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
             .negotiate(&mut stream)
@@ -196,4 +234,79 @@ mod tests {
         let transport = TcpTransport::default();
         tests::test_poll(transport, "127.0.0.1:0");
     }
+
+    /// Verify that the transport works with socket options applied to its connections.
+    #[cfg(feature = "transport-tcp-options")]
+    #[test]
+    fn test_transport_with_tcp_options() {
+        let transport = TcpTransport::default().with_tcp_options(
+            TcpOptions::new()
+                .with_keepalive(std::time::Duration::from_secs(30))
+                .with_nodelay(true),
+        );
+
+        tests::test_transport(transport, "127.0.0.1:0");
+    }
+
+    /// Verify that the transport accepts, listens on, and connects to bracketed IPv6 endpoints.
+    ///
+    /// The test is skipped if the host does not support IPv6.
+    #[test]
+    fn test_transport_ipv6() {
+        if StdTcpListener::bind("[::1]:0").is_err() {
+            return;
+        }
+
+        let transport = TcpTransport::default();
+        assert!(transport.accepts("tcp://[::1]:0"));
+
+        tests::test_transport(transport, "tcp://[::1]:0");
+    }
+
+    /// Verify that an unbracketed IPv6 endpoint is rejected with a protocol error.
+    #[cfg(feature = "transport-ipv6")]
+    #[test]
+    fn test_unbracketed_ipv6() {
+        let mut transport = TcpTransport::default();
+
+        match transport.listen("tcp://::1:0") {
+            Err(ListenError::ProtocolError(_)) => (),
+            res => panic!("expected protocol error, got {:?}", res.map(|_| ())),
+        }
+        match transport.connect("tcp://::1:8044") {
+            Err(ConnectError::ProtocolError(_)) => (),
+            res => panic!("expected protocol error, got {:?}", res.map(|_| ())),
+        }
+    }
+
+    /// Verify that a listener bound to the unspecified IPv6 address accepts IPv4 connections.
+    ///
+    /// The test is skipped if the host does not support IPv6.
+    #[cfg(feature = "transport-ipv6")]
+    #[test]
+    fn test_dual_stack_listen() {
+        if StdTcpListener::bind("[::1]:0").is_err() {
+            return;
+        }
+
+        let mut transport = TcpTransport::default();
+        let mut listener = transport.listen("tcp://[::]:0").expect("unable to listen");
+        let port = listener
+            .endpoint()
+            .rsplit(':')
+            .next()
+            .and_then(|port| port.parse::<u16>().ok())
+            .expect("no port in listener endpoint");
+
+        let handle = std::thread::spawn(move || {
+            let mut transport = TcpTransport::default();
+            transport
+                .connect(&format!("tcp://127.0.0.1:{}", port))
+                .map(|_| ())
+                .expect("unable to connect over IPv4");
+        });
+
+        listener.accept().expect("unable to accept IPv4 connection");
+        handle.join().expect("connecting thread panicked");
+    }
 }
@@ -27,12 +27,18 @@ use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
 
+#[cfg(feature = "transport-ipv6")]
+use crate::transport::address;
+#[cfg(feature = "tls-policy")]
+use crate::transport::tls::TlsPolicy;
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
     SendError, Transport,
 };
 
 use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+#[cfg(feature = "transport-tcp-options")]
+use super::TcpOptions;
 
 /// tls:// is deprecated, tcps:// should be used instead
 const DEPRECATED_PROTOCOL_PREFIX: &str = "tls://";
@@ -41,6 +47,8 @@ const PROTOCOL_PREFIX: &str = "tcps://";
 pub struct TlsTransport {
     connector: SslConnector,
     acceptor: SslAcceptor,
+    #[cfg(feature = "transport-tcp-options")]
+    options: TcpOptions,
 }
 
 impl TlsTransport {
@@ -50,6 +58,52 @@ impl TlsTransport {
         client_cert: String,
         server_key: String,
         server_cert: String,
+    ) -> Result<Self, TlsInitError> {
+        #[cfg(not(feature = "tls-policy"))]
+        {
+            Self::build(ca_cert, client_key, client_cert, server_key, server_cert)
+        }
+        #[cfg(feature = "tls-policy")]
+        {
+            Self::build(
+                ca_cert,
+                client_key,
+                client_cert,
+                server_key,
+                server_cert,
+                &TlsPolicy::default(),
+            )
+        }
+    }
+
+    /// Creates a `TlsTransport` whose connections are restricted to the protocol versions and
+    /// cipher suites allowed by the given policy.
+    #[cfg(feature = "tls-policy")]
+    pub fn new_with_policy(
+        ca_cert: Option<String>,
+        client_key: String,
+        client_cert: String,
+        server_key: String,
+        server_cert: String,
+        policy: &TlsPolicy,
+    ) -> Result<Self, TlsInitError> {
+        Self::build(
+            ca_cert,
+            client_key,
+            client_cert,
+            server_key,
+            server_cert,
+            policy,
+        )
+    }
+
+    fn build(
+        ca_cert: Option<String>,
+        client_key: String,
+        client_cert: String,
+        server_key: String,
+        server_cert: String,
+        #[cfg(feature = "tls-policy")] policy: &TlsPolicy,
     ) -> Result<Self, TlsInitError> {
         let client_cert_path = Path::new(&client_cert);
         let client_key_path = Path::new(&client_key);
@@ -81,14 +135,29 @@ impl TlsTransport {
                 acceptor.set_verify(SslVerifyMode::NONE);
             }
 
+            #[cfg(feature = "tls-policy")]
+            {
+                policy.apply(&mut connector)?;
+                policy.apply(&mut acceptor)?;
+            }
+
             (acceptor.build(), connector.build())
         };
 
         Ok(TlsTransport {
             connector,
             acceptor,
+            #[cfg(feature = "transport-tcp-options")]
+            options: TcpOptions::default(),
         })
     }
+
+    /// Sets the socket options applied to every connection created or accepted by the transport.
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn with_tcp_options(mut self, options: TcpOptions) -> Self {
+        self.options = options;
+        self
+    }
 }
 
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
@@ -127,9 +196,15 @@ impl Transport for TlsTransport {
             endpoint
         };
 
+        #[cfg(feature = "transport-ipv6")]
+        address::validate_endpoint(address)
+            .map_err(|err| ConnectError::ProtocolError(err.to_string()))?;
+
         let dns_name = endpoint_to_dns_name(address)?;
 
         let stream = TcpStream::connect(address)?;
+        #[cfg(feature = "transport-tcp-options")]
+        self.options.apply(&stream)?;
         let mut tls_stream = self.connector.connect(&dns_name, stream)?;
 
         let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
@@ -166,11 +241,22 @@ impl Transport for TlsTransport {
             bind
         };
 
+        #[cfg(feature = "transport-ipv6")]
+        let listener = {
+            address::validate_endpoint(address)
+                .map_err(|err| ListenError::ProtocolError(err.to_string()))?;
+            address::bind(address)
+        };
+        #[cfg(not(feature = "transport-ipv6"))]
+        let listener = TcpListener::bind(address);
+
         Ok(Box::new(TlsListener {
-            listener: TcpListener::bind(address).map_err(|err| {
+            listener: listener.map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
             acceptor: self.acceptor.clone(),
+            #[cfg(feature = "transport-tcp-options")]
+            options: self.options.clone(),
         }))
     }
 }
@@ -178,11 +264,15 @@ impl Transport for TlsTransport {
 pub struct TlsListener {
     listener: TcpListener,
     acceptor: SslAcceptor,
+    #[cfg(feature = "transport-tcp-options")]
+    options: TcpOptions,
 }
 
 impl Listener for TlsListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (stream, _) = self.listener.accept()?;
+        #[cfg(feature = "transport-tcp-options")]
+        self.options.apply(&stream)?;
         let mut tls_stream = self.acceptor.accept(stream)?;
 
         let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
@@ -456,6 +546,19 @@ pub(crate) mod tests {
         tests::test_transport(transport, "127.0.0.1:0");
     }
 
+    /// Verify that the transport listens on and connects to bracketed IPv6 endpoints.
+    ///
+    /// The test is skipped if the host does not support IPv6.
+    #[test]
+    fn test_transport_ipv6() {
+        if TcpListener::bind("[::1]:0").is_err() {
+            return;
+        }
+
+        let transport = create_test_tls_transport(true);
+        tests::test_transport(transport, "tcps://[::1]:0");
+    }
+
     #[cfg(not(unix))]
     #[test]
     fn test_poll_no_verify() {
@@ -0,0 +1,273 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A transport over unix domain sockets.
+//!
+//! This transport is intended for services running on the same host as splinterd, for which it
+//! avoids the overhead of TCP and does not require any network ports to be opened. Endpoints are
+//! given as `unix://` followed by the path of the socket file, for example
+//! `unix:///var/run/splinter/service.sock`.
+
+use std::io;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener as StdUnixListener, UnixStream};
+use std::path::Path;
+
+use mio::unix::EventedFd;
+use mio::{Evented, Poll, PollOpt, Ready, Token};
+
+use crate::transport::{
+    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
+    SendError, Transport,
+};
+
+use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+
+const PROTOCOL_PREFIX: &str = "unix://";
+
+#[derive(Default)]
+pub struct UnixTransport {}
+
+impl Transport for UnixTransport {
+    fn accepts(&self, address: &str) -> bool {
+        address.starts_with(PROTOCOL_PREFIX)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        let path = endpoint.strip_prefix(PROTOCOL_PREFIX).ok_or_else(|| {
+            ConnectError::ProtocolError(format!("Invalid protocol \"{}\"", endpoint))
+        })?;
+
+        let mut stream = UnixStream::connect(path)?;
+
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+            .negotiate(&mut stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => ConnectError::ProtocolError(
+                    "Unable to connect; remote version is not with in range".into(),
+                ),
+                FrameError::IoError(err) => ConnectError::from(err),
+                e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
+            })?;
+
+        stream.set_nonblocking(true)?;
+
+        Ok(Box::new(UnixConnection {
+            frame_version,
+            stream: EventedUnixStream(stream),
+            local_endpoint: PROTOCOL_PREFIX.to_string(),
+            remote_endpoint: endpoint.to_string(),
+        }))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        let path = bind
+            .strip_prefix(PROTOCOL_PREFIX)
+            .ok_or_else(|| ListenError::ProtocolError(format!("Invalid protocol \"{}\"", bind)))?;
+
+        remove_stale_socket(path).map_err(|err| {
+            ListenError::IoError(format!("Failed to remove stale socket {}", path), err)
+        })?;
+
+        Ok(Box::new(UnixListener {
+            listener: StdUnixListener::bind(path)
+                .map_err(|err| ListenError::IoError(format!("Failed to bind to {}", path), err))?,
+            endpoint: bind.to_string(),
+        }))
+    }
+}
+
+/// Removes a socket file left behind by a previous listener at the given path.
+///
+/// Files at the path that are not sockets are left in place, in which case binding will fail.
+fn remove_stale_socket(path: &str) -> io::Result<()> {
+    match Path::new(path).symlink_metadata() {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+struct UnixListener {
+    listener: StdUnixListener,
+    endpoint: String,
+}
+
+impl Listener for UnixListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        let (mut stream, _) = self.listener.accept()?;
+
+        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+            .negotiate(&mut stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
+                    "Local {} protocol version {} not supported by remote",
+                    PROTOCOL_PREFIX,
+                    FrameVersion::V1
+                )),
+                FrameError::IoError(err) => AcceptError::from(err),
+                err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
+            })?;
+
+        stream.set_nonblocking(true)?;
+
+        // The connecting side of a unix socket is typically unnamed, so inbound connections are
+        // identified by the listener's endpoint.
+        Ok(Box::new(UnixConnection {
+            frame_version,
+            stream: EventedUnixStream(stream),
+            local_endpoint: self.endpoint.clone(),
+            remote_endpoint: self.endpoint.clone(),
+        }))
+    }
+
+    fn endpoint(&self) -> String {
+        self.endpoint.clone()
+    }
+}
+
+/// A `UnixStream` which may be registered with a `mio::Poll`.
+struct EventedUnixStream(UnixStream);
+
+impl Evented for EventedUnixStream {
+    fn register(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &Poll) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).deregister(poll)
+    }
+}
+
+struct UnixConnection {
+    frame_version: FrameVersion,
+    stream: EventedUnixStream,
+    local_endpoint: String,
+    remote_endpoint: String,
+}
+
+impl Connection for UnixConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        match FrameRef::new(self.frame_version, message).write(&mut self.stream.0) {
+            Err(FrameError::IoError(e)) => Err(SendError::from(e)),
+            Err(err) => Err(SendError::ProtocolError(err.to_string())),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        match Frame::read(&mut self.stream.0) {
+            Err(FrameError::IoError(e)) => Err(RecvError::from(e)),
+            Err(err) => Err(RecvError::ProtocolError(err.to_string())),
+            Ok(frame) => Ok(frame.into_inner()),
+        }
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.remote_endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        self.stream
+            .0
+            .shutdown(std::net::Shutdown::Both)
+            .map_err(DisconnectError::from)
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        &self.stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::tests;
+
+    use tempfile::Builder;
+
+    #[test]
+    fn test_accepts() {
+        let transport = UnixTransport::default();
+        assert!(transport.accepts("unix:///var/run/splinter/service.sock"));
+        assert!(transport.accepts("unix://relative/service.sock"));
+
+        assert!(!transport.accepts("/var/run/splinter/service.sock"));
+        assert!(!transport.accepts("tcp://127.0.0.1:8080"));
+        assert!(!transport.accepts("127.0.0.1:8080"));
+    }
+
+    #[test]
+    fn test_transport() {
+        let dir = Builder::new()
+            .prefix("test_transport")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let bind = format!("unix://{}", dir.path().join("test.sock").display());
+
+        tests::test_transport(UnixTransport::default(), &bind);
+    }
+
+    #[test]
+    fn test_poll() {
+        let dir = Builder::new()
+            .prefix("test_poll")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let bind = format!("unix://{}", dir.path().join("test.sock").display());
+
+        tests::test_poll(UnixTransport::default(), &bind);
+    }
+
+    /// Verify that a listener may be bound to a path at which a socket file was left behind by a
+    /// previous listener.
+    #[test]
+    fn test_rebind_stale_socket() {
+        let dir = Builder::new()
+            .prefix("test_rebind_stale_socket")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let bind = format!("unix://{}", dir.path().join("test.sock").display());
+
+        let mut transport = UnixTransport::default();
+        let listener = transport.listen(&bind).expect("Failed to listen");
+        drop(listener);
+
+        transport
+            .listen(&bind)
+            .expect("Failed to listen on stale socket");
+    }
+}
@@ -12,13 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "tls-policy")]
+use std::fmt;
 #[cfg(feature = "ws-transport")]
 use std::path::Path;
+#[cfg(feature = "tls-policy")]
+use std::str::FromStr;
 
-#[cfg(feature = "ws-transport")]
+#[cfg(any(feature = "ws-transport", feature = "tls-policy"))]
 use openssl::error::ErrorStack;
+#[cfg(any(feature = "ws-transport", feature = "tls-policy"))]
+use openssl::ssl::SslMethod;
+#[cfg(feature = "tls-policy")]
+use openssl::ssl::{cipher_name, SslContextBuilder, SslVersion};
 #[cfg(feature = "ws-transport")]
-use openssl::ssl::{SslAcceptor, SslConnector, SslFiletype, SslMethod, SslVerifyMode};
+use openssl::ssl::{SslAcceptor, SslConnector, SslFiletype, SslVerifyMode};
+
+#[cfg(feature = "tls-policy")]
+use crate::error::InvalidArgumentError;
 
 pub struct TlsConfig {
     ca_certs_file: Option<String>,
@@ -26,6 +37,8 @@ pub struct TlsConfig {
     server_private_key_file: String,
     client_cert_file: String,
     client_private_key_file: String,
+    #[cfg(feature = "tls-policy")]
+    policy: TlsPolicy,
 }
 
 impl TlsConfig {
@@ -48,6 +61,12 @@ impl TlsConfig {
     pub fn client_private_key_file(&self) -> &str {
         &self.client_private_key_file
     }
+
+    /// Returns the protocol version and cipher suite policy applied to TLS connections.
+    #[cfg(feature = "tls-policy")]
+    pub fn policy(&self) -> &TlsPolicy {
+        &self.policy
+    }
 }
 
 #[derive(Default)]
@@ -57,6 +76,8 @@ pub struct TlsConfigBuilder {
     server_private_key_file: Option<String>,
     client_cert_file: Option<String>,
     client_private_key_file: Option<String>,
+    #[cfg(feature = "tls-policy")]
+    policy: Option<TlsPolicy>,
 }
 
 impl TlsConfigBuilder {
@@ -67,6 +88,8 @@ impl TlsConfigBuilder {
             server_private_key_file: None,
             client_cert_file: None,
             client_private_key_file: None,
+            #[cfg(feature = "tls-policy")]
+            policy: None,
         }
     }
 
@@ -95,6 +118,14 @@ impl TlsConfigBuilder {
         self
     }
 
+    /// Sets the protocol version and cipher suite policy; if not set, the OpenSSL defaults are
+    /// used.
+    #[cfg(feature = "tls-policy")]
+    pub fn with_policy(mut self, policy: TlsPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
     pub fn build(self) -> Result<TlsConfig, TlsConfigBuilderError> {
         let ca_certs_file = self.ca_certs_file;
         let server_cert_file = self
@@ -116,6 +147,8 @@ impl TlsConfigBuilder {
             server_private_key_file,
             client_cert_file,
             client_private_key_file,
+            #[cfg(feature = "tls-policy")]
+            policy: self.policy.unwrap_or_default(),
         })
     }
 }
@@ -137,6 +170,183 @@ impl std::fmt::Display for TlsConfigBuilderError {
     }
 }
 
+/// A minimum TLS protocol version that may be required by a [`TlsPolicy`].
+///
+/// Versions older than TLS 1.2 are not supported.
+#[cfg(feature = "tls-policy")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+    Tls12,
+    Tls13,
+}
+
+#[cfg(feature = "tls-policy")]
+impl TlsVersion {
+    fn to_ssl_version(self) -> SslVersion {
+        match self {
+            TlsVersion::Tls12 => SslVersion::TLS1_2,
+            TlsVersion::Tls13 => SslVersion::TLS1_3,
+        }
+    }
+}
+
+#[cfg(feature = "tls-policy")]
+impl FromStr for TlsVersion {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim_start_matches("tlsv") {
+            "1.2" => Ok(TlsVersion::Tls12),
+            "1.3" => Ok(TlsVersion::Tls13),
+            _ => Err(InvalidArgumentError::new(
+                "tls_min_version",
+                format!(
+                    "unsupported TLS version '{}'; expected one of 1.2 or 1.3",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "tls-policy")]
+impl fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TlsVersion::Tls12 => f.write_str("TLSv1.2"),
+            TlsVersion::Tls13 => f.write_str("TLSv1.3"),
+        }
+    }
+}
+
+/// The TLS protocol versions and cipher suites that may be negotiated on a connection.
+///
+/// Cipher suites may be given by their IANA names (for example, `TLS_AES_256_GCM_SHA384` or
+/// `TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384`) or by their OpenSSL names (for example,
+/// `ECDHE-RSA-AES256-GCM-SHA384`). TLS 1.3 cipher suites and TLS 1.2 ciphers are configured
+/// separately; if none of a given kind are listed, the defaults for that kind are left in place.
+#[cfg(feature = "tls-policy")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsPolicy {
+    min_version: Option<TlsVersion>,
+    cipher_suites: Vec<String>,
+}
+
+#[cfg(feature = "tls-policy")]
+impl TlsPolicy {
+    /// Creates a new policy, verifying that every cipher suite is known to OpenSSL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if a cipher suite is empty or is not recognized.
+    pub fn new(
+        min_version: Option<TlsVersion>,
+        cipher_suites: Vec<String>,
+    ) -> Result<Self, InvalidArgumentError> {
+        for cipher_suite in &cipher_suites {
+            if cipher_suite.is_empty() {
+                return Err(InvalidArgumentError::new(
+                    "tls_cipher_suites",
+                    "cipher suite names must not be empty",
+                ));
+            }
+
+            let valid = match to_openssl_cipher(cipher_suite) {
+                Some((_, true)) => true,
+                Some((name, false)) => SslContextBuilder::new(SslMethod::tls())
+                    .and_then(|mut builder| builder.set_cipher_list(&name))
+                    .is_ok(),
+                None => false,
+            };
+            if !valid {
+                return Err(InvalidArgumentError::new(
+                    "tls_cipher_suites",
+                    format!("unknown cipher suite '{}'", cipher_suite),
+                ));
+            }
+        }
+
+        Ok(TlsPolicy {
+            min_version,
+            cipher_suites,
+        })
+    }
+
+    /// Returns the minimum protocol version, if one is required.
+    pub fn min_version(&self) -> Option<TlsVersion> {
+        self.min_version
+    }
+
+    /// Returns the allowed cipher suites; an empty list allows the OpenSSL defaults.
+    pub fn cipher_suites(&self) -> &[String] {
+        &self.cipher_suites
+    }
+
+    /// Applies the policy to an OpenSSL context, such as an acceptor or connector builder.
+    pub fn apply(&self, builder: &mut SslContextBuilder) -> Result<(), ErrorStack> {
+        if let Some(min_version) = self.min_version {
+            builder.set_min_proto_version(Some(min_version.to_ssl_version()))?;
+        }
+
+        apply_cipher_suites(builder, &self.cipher_suites)
+    }
+}
+
+#[cfg(feature = "tls-policy")]
+impl fmt::Display for TlsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.min_version {
+            Some(min_version) => write!(f, "minimum version {}", min_version)?,
+            None => f.write_str("default minimum version")?,
+        }
+
+        if self.cipher_suites.is_empty() {
+            f.write_str(", default cipher suites")
+        } else {
+            write!(f, ", cipher suites {}", self.cipher_suites.join(":"))
+        }
+    }
+}
+
+/// Converts a cipher suite name to its OpenSSL name, paired with whether it is a TLS 1.3 cipher
+/// suite. Returns `None` if an IANA name is not known to OpenSSL.
+#[cfg(feature = "tls-policy")]
+fn to_openssl_cipher(name: &str) -> Option<(String, bool)> {
+    if !name.starts_with("TLS_") {
+        return Some((name.to_string(), false));
+    }
+
+    // OpenSSL uses the IANA names for TLS 1.3 cipher suites, but its own names for the rest
+    match cipher_name(name) {
+        "(NONE)" => None,
+        openssl_name => Some((openssl_name.to_string(), openssl_name.starts_with("TLS_"))),
+    }
+}
+
+/// Sets the TLS 1.3 cipher suites and TLS 1.2 cipher list from the given names, leaving the
+/// defaults for a kind with no names in place.
+#[cfg(feature = "tls-policy")]
+fn apply_cipher_suites(
+    builder: &mut SslContextBuilder,
+    cipher_suites: &[String],
+) -> Result<(), ErrorStack> {
+    let (tls13, tls12): (Vec<_>, Vec<_>) = cipher_suites
+        .iter()
+        .filter_map(|name| to_openssl_cipher(name))
+        .partition(|(_, is_tls13)| *is_tls13);
+    let tls13: Vec<String> = tls13.into_iter().map(|(name, _)| name).collect();
+    let tls12: Vec<String> = tls12.into_iter().map(|(name, _)| name).collect();
+
+    if !tls13.is_empty() {
+        builder.set_ciphersuites(&tls13.join(":"))?;
+    }
+    if !tls12.is_empty() {
+        builder.set_cipher_list(&tls12.join(":"))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "ws-transport")]
 pub(super) fn build_connector(config: &TlsConfig) -> Result<SslConnector, ErrorStack> {
     let mut builder = SslConnector::builder(SslMethod::tls())?;
@@ -154,6 +364,9 @@ pub(super) fn build_connector(config: &TlsConfig) -> Result<SslConnector, ErrorS
         builder.set_verify(SslVerifyMode::NONE);
     }
 
+    #[cfg(feature = "tls-policy")]
+    config.policy().apply(&mut builder)?;
+
     Ok(builder.build())
 }
 
@@ -174,11 +387,17 @@ pub(super) fn build_acceptor(config: &TlsConfig) -> Result<SslAcceptor, ErrorSta
         builder.set_verify(SslVerifyMode::NONE);
     }
 
+    #[cfg(feature = "tls-policy")]
+    config.policy().apply(&mut builder)?;
+
     Ok(builder.build())
 }
 
 #[cfg(test)]
 pub(super) mod tests {
+    #[cfg(feature = "tls-policy")]
+    use super::*;
+
     use openssl::asn1::Asn1Time;
     use openssl::bn::{BigNum, MsbOption};
     use openssl::hash::MessageDigest;
@@ -269,4 +488,49 @@ pub(super) mod tests {
 
         (privkey, cert)
     }
+
+    /// Verify that TLS versions are parsed with or without the `TLSv` prefix and that versions
+    /// older than TLS 1.2 are rejected.
+    #[cfg(feature = "tls-policy")]
+    #[test]
+    fn test_tls_version_from_str() {
+        assert_eq!("1.2".parse::<TlsVersion>().unwrap(), TlsVersion::Tls12);
+        assert_eq!("TLSv1.3".parse::<TlsVersion>().unwrap(), TlsVersion::Tls13);
+        assert!("1.1".parse::<TlsVersion>().is_err());
+        assert!("ssl3".parse::<TlsVersion>().is_err());
+    }
+
+    /// Verify that a policy with known cipher suites can be created and applied, and that
+    /// unknown cipher suites are rejected.
+    #[cfg(feature = "tls-policy")]
+    #[test]
+    fn test_tls_policy() {
+        let policy = TlsPolicy::new(
+            Some(TlsVersion::Tls12),
+            vec![
+                "TLS_AES_256_GCM_SHA384".to_string(),
+                "ECDHE-RSA-AES256-GCM-SHA384".to_string(),
+            ],
+        )
+        .expect("Unable to create policy");
+
+        let mut builder = SslContextBuilder::new(SslMethod::tls()).unwrap();
+        policy.apply(&mut builder).expect("Unable to apply policy");
+
+        assert_eq!(
+            policy.to_string(),
+            "minimum version TLSv1.2, cipher suites \
+             TLS_AES_256_GCM_SHA384:ECDHE-RSA-AES256-GCM-SHA384"
+        );
+
+        TlsPolicy::new(
+            None,
+            vec!["TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384".to_string()],
+        )
+        .expect("IANA names for TLS 1.2 ciphers should be accepted");
+
+        assert!(TlsPolicy::new(None, vec!["NOT-A-CIPHER".to_string()]).is_err());
+        assert!(TlsPolicy::new(None, vec!["TLS_NOT_A_CIPHER".to_string()]).is_err());
+        assert!(TlsPolicy::new(None, vec!["".to_string()]).is_err());
+    }
 }
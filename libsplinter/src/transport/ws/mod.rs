@@ -117,6 +117,21 @@ mod tests {
         tests::test_transport(transport, "ws://127.0.0.1:18080");
     }
 
+    /// Verify that the transport listens on and connects to bracketed IPv6 endpoints.
+    ///
+    /// The test is skipped if the host does not support IPv6.
+    #[test]
+    fn test_ws_transport_ipv6() {
+        if std::net::TcpListener::bind("[::1]:0").is_err() {
+            return;
+        }
+
+        let transport = WsTransport::default();
+        assert!(transport.accepts("ws://[::1]:18084"));
+
+        tests::test_transport(transport, "ws://[::1]:18084");
+    }
+
     #[test]
     fn test_ws_poll() {
         let transport = WsTransport::default();
@@ -21,6 +21,8 @@ use openssl::ssl::{SslAcceptor, SslConnector};
 use tungstenite::{client, handshake::HandshakeError};
 use url::{ParseError, Url};
 
+#[cfg(feature = "transport-ipv6")]
+use crate::transport::address;
 use crate::transport::tls::{build_acceptor, build_connector, TlsConfig};
 use crate::transport::{ConnectError, Connection, ListenError, Listener, Transport};
 
@@ -132,6 +134,10 @@ impl Transport for WsTransport {
     }
 
     fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        #[cfg(feature = "transport-ipv6")]
+        address::validate_endpoint(endpoint)
+            .map_err(|err| ConnectError::ProtocolError(err.to_string()))?;
+
         if let Some(address) = endpoint.strip_prefix(WS_PROTOCOL_PREFIX) {
             let stream = TcpStream::connect(address)?;
 
@@ -213,8 +219,12 @@ impl Transport for WsTransport {
     }
 
     fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        #[cfg(feature = "transport-ipv6")]
+        address::validate_endpoint(bind)
+            .map_err(|err| ListenError::ProtocolError(err.to_string()))?;
+
         if let Some(address) = bind.strip_prefix(WS_PROTOCOL_PREFIX) {
-            let tcp_listener = TcpListener::bind(address).map_err(|err| {
+            let tcp_listener = bind_address(address).map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?;
             let local_endpoint = format!(
@@ -237,7 +247,7 @@ impl Transport for WsTransport {
                 )
             })?;
 
-            let tcp_listener = TcpListener::bind(address).map_err(|err| {
+            let tcp_listener = bind_address(address).map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?;
             let local_endpoint = format!(
@@ -262,6 +272,16 @@ impl Transport for WsTransport {
     }
 }
 
+#[cfg(feature = "transport-ipv6")]
+fn bind_address(address: &str) -> std::io::Result<TcpListener> {
+    address::bind(address)
+}
+
+#[cfg(not(feature = "transport-ipv6"))]
+fn bind_address(address: &str) -> std::io::Result<TcpListener> {
+    TcpListener::bind(address)
+}
+
 impl From<tungstenite::error::Error> for ConnectError {
     fn from(err: tungstenite::error::Error) -> Self {
         match err {
@@ -22,6 +22,7 @@ use futures::{future::IntoFuture, Future};
 use std::collections::HashMap;
 
 use splinter::admin::store::{AdminServiceStore, CircuitPredicate, CircuitStatus};
+use splinter::public_key::PublicKey;
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
     paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
@@ -29,6 +30,9 @@ use splinter::rest_api::{
 };
 use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
 
+#[cfg(feature = "admin-service-requester-filter")]
+use crate::hex::parse_hex;
+
 use super::error::CircuitListError;
 use super::resources;
 #[cfg(feature = "authorization")]
@@ -119,6 +123,68 @@ fn list_circuits(
         }
         None => None,
     };
+
+    #[cfg(feature = "admin-service-requester-filter")]
+    let requester_filter = match query.get("requester") {
+        Some(value) => match parse_hex(value) {
+            Ok(requester) => {
+                new_queries.push(format!("requester={}", value));
+                Some(PublicKey::from_bytes(requester))
+            }
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid requester value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "admin-service-requester-filter"))]
+    let requester_filter = None;
+
+    #[cfg_attr(
+        not(any(feature = "admin-service-type-filter", feature = "circuit-tombstones")),
+        allow(unused_mut)
+    )]
+    let mut type_filters = vec![];
+    #[cfg(feature = "admin-service-type-filter")]
+    {
+        if let Some(value) = query.get("management_type") {
+            new_queries.push(format!("management_type={}", value));
+            type_filters.push(CircuitPredicate::ManagementTypeEq(value.to_string()));
+        }
+        if let Some(value) = query.get("service_type") {
+            new_queries.push(format!("service_type={}", value));
+            type_filters.push(CircuitPredicate::ServiceTypeEq(value.to_string()));
+        }
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    if let Some(value) = query.get("include_deleted") {
+        match value.parse::<bool>() {
+            Ok(true) => {
+                new_queries.push(format!("include_deleted={}", value));
+                type_filters.push(CircuitPredicate::IncludeTombstones);
+            }
+            Ok(false) => (),
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid include_deleted value passed: {}",
+                            value
+                        )))
+                        .into_future(),
+                )
+            }
+        }
+    }
+
     let mut link = req.uri().path().to_string();
     if !new_queries.is_empty() {
         if let Err(e) = write!(link, "?{}&", new_queries.join("&")) {
@@ -151,17 +217,26 @@ fn list_circuits(
         link,
         member_filter,
         status_filter,
+        requester_filter,
+        type_filters,
         Some(offset),
         Some(limit),
         protocol_version,
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_list_circuits(
     store: web::Data<Box<dyn AdminServiceStore>>,
     link: String,
     member_filter: Option<String>,
     status_filter: Option<String>,
+    #[cfg_attr(
+        not(feature = "admin-service-requester-filter"),
+        allow(unused_variables)
+    )]
+    requester_filter: Option<PublicKey>,
+    type_filters: Vec<CircuitPredicate>,
     offset: Option<usize>,
     limit: Option<usize>,
     protocol_version: String,
@@ -180,6 +255,11 @@ fn query_list_circuits(
                     .map_err(|e| CircuitListError::CircuitStatusError(e.to_string()))?,
             ));
         }
+        #[cfg(feature = "admin-service-requester-filter")]
+        if let Some(requester) = requester_filter {
+            filters.push(CircuitPredicate::RequesterEq(requester));
+        }
+        filters.extend(type_filters);
 
         let circuits = store
             .list_circuits(&filters)
@@ -229,8 +309,8 @@ fn query_list_circuits(
                     )
                 }
 
-                // Handles 2
-                "2" => {
+                // Handles 2 and 3
+                "2" | "3" => {
                     let paging = PagingBuilder::new(link, total_count);
                     let paging = if let Some(limit) = limit {
                         paging.with_limit(limit)
@@ -252,12 +332,9 @@ fn query_list_circuits(
                         }),
                     )
                 }
-                _ => Ok(
-                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
-                        "Unsupported SplinterProtocolVersion: {}",
-                        protocol_version
-                    ))),
-                ),
+                _ => Ok(HttpResponse::BadRequest().json(
+                    ErrorResponse::unsupported_protocol_version(&protocol_version),
+                )),
             }
         }
         Err(err) => match err {
@@ -492,6 +569,56 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[cfg(feature = "admin-service-type-filter")]
+    #[test]
+    /// Tests a GET /admin/circuits request with the `service_type` and `management_type` filters
+    /// returns only the circuits matching both.
+    fn test_list_circuit_with_type_filters_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/circuits?service_type=other_type&management_type=circuit_2_type",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::circuits::CircuitResponse::from(
+                &get_circuit_2().0
+            )])
+            .expect("failed to convert expected data"),
+        );
+
+        assert_eq!(
+            circuits.get("paging").expect("no paging field in response"),
+            &to_value(create_test_paging_response(
+                0,
+                100,
+                0,
+                0,
+                0,
+                1,
+                &format!("/admin/circuits?management_type=circuit_2_type&service_type=other_type&"),
+            ))
+            .expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     #[test]
     /// Tests a GET /admin/circuits request with the `status` filter returns the expected circuit.
     fn test_list_circuit_with_filter_and_status_ok() {
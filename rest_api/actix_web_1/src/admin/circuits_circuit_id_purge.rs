@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the `GET /admin/circuits/{circuit_id}/purge` endpoint for previewing the
+//! local services that purging a circuit would remove, or the results of a recent purge.
+
+use actix_web::{HttpRequest, HttpResponse};
+use futures::IntoFuture;
+
+use splinter::admin::service::{
+    AdminCommands, CircuitPurgeReport, ServicePurgeReport, ServicePurgeStatus,
+};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_READ_PERMISSION;
+
+const ADMIN_CIRCUIT_PURGE_REPORT_MIN: u32 = 2;
+
+pub fn make_circuit_purge_report_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/circuits/{circuit_id}/purge").add_request_guard(
+        ProtocolVersionRangeGuard::new(ADMIN_CIRCUIT_PURGE_REPORT_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |request, _| {
+            Box::new(fetch_purge_report(request, &admin_commands).into_future())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |request, _| {
+            Box::new(fetch_purge_report(request, &admin_commands).into_future())
+        })
+    }
+}
+
+fn fetch_purge_report<A: AdminCommands>(request: HttpRequest, admin_commands: &A) -> HttpResponse {
+    let circuit_id = request.match_info().get("circuit_id").unwrap_or("");
+
+    match admin_commands.purge_report(circuit_id) {
+        Ok(Some(report)) => HttpResponse::Ok().json(CircuitPurgeReportResponse::from(&report)),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+            "Unable to find circuit: {}",
+            circuit_id
+        ))),
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CircuitPurgeReportResponse<'a> {
+    circuit_id: &'a str,
+    purged: bool,
+    services: Vec<ServicePurgeReportResponse<'a>>,
+}
+
+impl<'a> From<&'a CircuitPurgeReport> for CircuitPurgeReportResponse<'a> {
+    fn from(report: &'a CircuitPurgeReport) -> Self {
+        Self {
+            circuit_id: report.circuit_id(),
+            purged: report.is_complete(),
+            services: report
+                .services()
+                .iter()
+                .map(ServicePurgeReportResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ServicePurgeReportResponse<'a> {
+    service_id: &'a str,
+    service_type: &'a str,
+    state_size: Option<u64>,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a ServicePurgeReport> for ServicePurgeReportResponse<'a> {
+    fn from(report: &'a ServicePurgeReport) -> Self {
+        let (status, error) = match report.status() {
+            ServicePurgeStatus::Pending => ("pending", None),
+            ServicePurgeStatus::Purged => ("purged", None),
+            ServicePurgeStatus::Kept => ("kept", None),
+            ServicePurgeStatus::Failed(err) => ("failed", Some(err.as_str())),
+        };
+
+        Self {
+            service_id: report.service_id(),
+            service_type: report.service_type(),
+            state_size: report.state_size(),
+            status,
+            error,
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /admin/circuits/schema` endpoint for reporting the schema
+//! versions of the circuits in Splinter's state, and the `POST /admin/circuits/schema` endpoint
+//! for migrating circuits stored with an older schema version to the current one.
+
+use std::collections::{BTreeMap, HashMap};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::admin::store::{
+    migrate_circuit_schemas, AdminServiceStore, CircuitSchemaMigration, CircuitSchemaReport,
+    MigrationEligibility,
+};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::{CIRCUIT_READ_PERMISSION, CIRCUIT_WRITE_PERMISSION};
+
+const ADMIN_CIRCUIT_SCHEMA_MIN: u32 = 2;
+
+pub fn make_circuit_schema_resource(store: Box<dyn AdminServiceStore>) -> Resource {
+    let resource = Resource::build("/admin/circuits/schema").add_request_guard(
+        ProtocolVersionRangeGuard::new(ADMIN_CIRCUIT_SCHEMA_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    let migrate_store = store.clone();
+
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |_, _| {
+                run_migration(store.clone(), true)
+            })
+            .add_method(Method::Post, CIRCUIT_WRITE_PERMISSION, move |r, _| {
+                migrate_circuits(r, migrate_store.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| run_migration(store.clone(), true))
+            .add_method(Method::Post, move |r, _| {
+                migrate_circuits(r, migrate_store.clone())
+            })
+    }
+}
+
+fn migrate_circuits(
+    req: HttpRequest,
+    store: Box<dyn AdminServiceStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        match web::Query::from_query(req.query_string()) {
+            Ok(query) => query,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid query"))
+                        .into_future(),
+                )
+            }
+        };
+
+    let dry_run = match query.get("dry_run").map(|value| value.parse::<bool>()) {
+        Some(Ok(dry_run)) => dry_run,
+        Some(Err(_)) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid dry_run value passed: {}",
+                        query.get("dry_run").map(String::as_str).unwrap_or_default()
+                    )))
+                    .into_future(),
+            )
+        }
+        None => false,
+    };
+
+    run_migration(store, dry_run)
+}
+
+fn run_migration(
+    store: Box<dyn AdminServiceStore>,
+    dry_run: bool,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || {
+            migrate_circuit_schemas(&*store, dry_run).map_err(|err| err.to_string())
+        })
+        .then(|res| match res {
+            Ok(report) => Ok(HttpResponse::Ok().json(CircuitSchemaResponse::from(&report))),
+            Err(BlockingError::Error(err)) => {
+                error!("Unable to migrate circuit schemas: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+            Err(err) => {
+                error!("{}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct CircuitSchemaResponse<'a> {
+    dry_run: bool,
+    versions: BTreeMap<String, usize>,
+    circuits: Vec<CircuitSchemaMigrationResponse<'a>>,
+}
+
+impl<'a> From<&'a CircuitSchemaReport> for CircuitSchemaResponse<'a> {
+    fn from(report: &'a CircuitSchemaReport) -> Self {
+        Self {
+            dry_run: report.is_dry_run(),
+            versions: report
+                .versions()
+                .iter()
+                .map(|(version, count)| (version.to_string(), *count))
+                .collect(),
+            circuits: report
+                .circuits()
+                .iter()
+                .map(CircuitSchemaMigrationResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CircuitSchemaMigrationResponse<'a> {
+    circuit_id: &'a str,
+    from_version: i32,
+    to_version: i32,
+    eligible: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+    migrated: bool,
+    changes: Vec<CircuitFieldChangeResponse<'a>>,
+}
+
+impl<'a> From<&'a CircuitSchemaMigration> for CircuitSchemaMigrationResponse<'a> {
+    fn from(migration: &'a CircuitSchemaMigration) -> Self {
+        let (eligible, reason) = match migration.eligibility() {
+            MigrationEligibility::Eligible => (true, None),
+            MigrationEligibility::Ineligible(reason) => (false, Some(reason.as_str())),
+        };
+
+        Self {
+            circuit_id: migration.circuit_id(),
+            from_version: migration.from_version(),
+            to_version: migration.to_version(),
+            eligible,
+            reason,
+            migrated: migration.is_migrated(),
+            changes: migration
+                .changes()
+                .iter()
+                .map(|change| CircuitFieldChangeResponse {
+                    field: change.field(),
+                    from: change.from(),
+                    to: change.to(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CircuitFieldChangeResponse<'a> {
+    field: &'a str,
+    from: &'a str,
+    to: &'a str,
+}
@@ -14,13 +14,23 @@
 
 mod circuits;
 mod circuits_circuit_id;
+#[cfg(feature = "circuit-purge-preview")]
+mod circuits_circuit_id_purge;
+#[cfg(feature = "circuit-schema-migration")]
+mod circuits_schema;
 mod error;
 mod proposals;
 mod proposals_circuit_id;
+#[cfg(feature = "admin-service-proposal-validation")]
+mod proposals_validate;
 mod resources;
 mod submit;
+#[cfg(feature = "admin-service-vote-delegation")]
+mod vote_delegations;
 mod ws_register_type;
 
+#[cfg(feature = "rest-api-split")]
+use splinter::admin::service::proposal_store::AdminServiceProposalsFactory;
 use splinter::admin::service::AdminService;
 use splinter::admin::store::AdminServiceStore;
 #[cfg(feature = "authorization")]
@@ -29,13 +39,13 @@ use splinter::rest_api::Resource;
 use splinter::rest_api::RestResourceProvider;
 
 #[cfg(feature = "authorization")]
-const CIRCUIT_READ_PERMISSION: Permission = Permission::Check {
+pub(crate) const CIRCUIT_READ_PERMISSION: Permission = Permission::Check {
     permission_id: "circuit.read",
     permission_display_name: "Circuit read",
     permission_description: "Allows the client to read circuit state",
 };
 #[cfg(feature = "authorization")]
-const CIRCUIT_WRITE_PERMISSION: Permission = Permission::Check {
+pub(crate) const CIRCUIT_WRITE_PERMISSION: Permission = Permission::Check {
     permission_id: "circuit.write",
     permission_display_name: "Circuit write",
     permission_description: "Allows the client to modify circuit state",
@@ -47,12 +57,31 @@ pub struct AdminServiceRestProvider {
 
 impl AdminServiceRestProvider {
     pub fn new(source: &AdminService) -> Self {
-        let resources = vec![
+        #[allow(unused_mut)]
+        let mut resources = vec![
             ws_register_type::make_application_handler_registration_route(source.commands()),
             submit::make_submit_route(source.commands()),
             proposals_circuit_id::make_fetch_proposal_resource(source.proposal_store_factory()),
             proposals::make_list_proposals_resource(source.proposal_store_factory()),
         ];
+        // Inserted ahead of `/admin/proposals/{circuit_id}`, which would otherwise match the path
+        #[cfg(feature = "admin-service-proposal-validation")]
+        resources.insert(
+            0,
+            proposals_validate::make_validate_proposal_route(source.commands()),
+        );
+        #[cfg(feature = "circuit-purge-preview")]
+        resources
+            .push(circuits_circuit_id_purge::make_circuit_purge_report_resource(source.commands()));
+        #[cfg(feature = "admin-service-vote-delegation")]
+        {
+            resources.push(vote_delegations::make_vote_delegations_resource(
+                source.commands(),
+            ));
+            resources.push(vote_delegations::make_vote_delegation_resource(
+                source.commands(),
+            ));
+        }
         Self { resources }
     }
 }
@@ -91,6 +120,10 @@ impl CircuitResourceProvider {
 /// * `GET /admin/circuits` - List circuits in Splinter's state
 /// * `GET /admin/circuits/{circuit_id}` - Fetch a specific circuit in Splinter's state by circuit
 ///   ID
+/// * `GET /admin/circuits/schema` - Report the schema versions of the stored circuits (requires
+///   the `circuit-schema-migration` feature)
+/// * `POST /admin/circuits/schema` - Migrate stored circuits to the current schema version
+///   (requires the `circuit-schema-migration` feature)
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -102,6 +135,12 @@ impl RestResourceProvider for CircuitResourceProvider {
         #[allow(unused_mut)]
         let mut resources = Vec::new();
 
+        // Added ahead of `/admin/circuits/{circuit_id}`, which would otherwise match the path
+        #[cfg(feature = "circuit-schema-migration")]
+        resources.push(circuits_schema::make_circuit_schema_resource(
+            self.store.clone(),
+        ));
+
         resources.append(&mut vec![
             circuits_circuit_id::make_fetch_circuit_resource(self.store.clone()),
             circuits::make_list_circuits_resource(self.store.clone()),
@@ -109,3 +148,38 @@ impl RestResourceProvider for CircuitResourceProvider {
         resources
     }
 }
+
+/// Provides the REST API [`Resource`](crate::rest_api::Resource) definitions for listing and
+/// fetching circuit proposals directly from the admin service store, without a running admin
+/// service.
+///
+/// The following endpoints are provided:
+///
+/// * `GET /admin/proposals` - List circuit proposals
+/// * `GET /admin/proposals/{circuit_id}` - Fetch a specific circuit proposal by circuit ID
+#[cfg(feature = "rest-api-split")]
+#[derive(Clone)]
+pub struct ProposalResourceProvider {
+    store: Box<dyn AdminServiceStore>,
+}
+
+#[cfg(feature = "rest-api-split")]
+impl ProposalResourceProvider {
+    pub fn new(store: Box<dyn AdminServiceStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[cfg(feature = "rest-api-split")]
+impl RestResourceProvider for ProposalResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            proposals_circuit_id::make_fetch_proposal_resource(AdminServiceProposalsFactory::new(
+                self.store.clone(),
+            )),
+            proposals::make_list_proposals_resource(AdminServiceProposalsFactory::new(
+                self.store.clone(),
+            )),
+        ]
+    }
+}
@@ -20,8 +20,14 @@ use std::fmt::Write as _;
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 
+#[cfg(feature = "admin-service-vote-status-filter")]
+use splinter::admin::messages::CircuitProposal;
+
 use splinter::admin::service::proposal_store::ProposalStoreFactory;
 use splinter::admin::store::CircuitPredicate;
+use splinter::public_key::PublicKey;
+#[cfg(feature = "admin-service-vote-status-filter")]
+use splinter::rest_api::auth::identity::Identity;
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
     paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
@@ -29,6 +35,12 @@ use splinter::rest_api::{
 };
 use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
 
+#[cfg(any(
+    feature = "admin-service-requester-filter",
+    feature = "admin-service-vote-status-filter"
+))]
+use crate::hex::parse_hex;
+
 use super::error::ProposalListError;
 use super::resources;
 #[cfg(feature = "authorization")]
@@ -36,6 +48,81 @@ use super::CIRCUIT_READ_PERMISSION;
 
 const ADMIN_LIST_PROPOSALS_PROTOCOL_MIN: u32 = 1;
 
+/// The status of a proposal's vote relative to the public key of the caller.
+#[cfg(feature = "admin-service-vote-status-filter")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum VoteStatus {
+    /// The caller, or the node the caller administers, has not yet voted on the proposal
+    Pending,
+    /// The caller, or the node the caller administers, has voted on or requested the proposal
+    Voted,
+}
+
+#[cfg(feature = "admin-service-vote-status-filter")]
+impl VoteStatus {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "pending" => Ok(VoteStatus::Pending),
+            "voted" => Ok(VoteStatus::Voted),
+            _ => Err(format!(
+                "Invalid vote_status value passed: {}. Expected pending or voted",
+                value
+            )),
+        }
+    }
+}
+
+/// Filters proposals by their vote status relative to the caller's public key.
+#[cfg(feature = "admin-service-vote-status-filter")]
+struct VoteStatusFilter {
+    vote_status: VoteStatus,
+    public_key: Vec<u8>,
+}
+
+#[cfg(feature = "admin-service-vote-status-filter")]
+impl VoteStatusFilter {
+    fn matches(&self, proposal: &CircuitProposal) -> bool {
+        let voted = has_voted(proposal, &self.public_key);
+        match self.vote_status {
+            VoteStatus::Pending => !voted,
+            VoteStatus::Voted => voted,
+        }
+    }
+}
+
+/// Returns whether the given public key has voted on the proposal.
+///
+/// The requester's vote is implied by submitting the proposal. If the key is a member node's
+/// public key, or one of its authorized keys, a vote recorded for that node counts as well, since
+/// each required voter node only needs to vote once.
+#[cfg(feature = "admin-service-vote-status-filter")]
+fn has_voted(proposal: &CircuitProposal, public_key: &[u8]) -> bool {
+    if proposal.requester == public_key {
+        return true;
+    }
+
+    let caller_node_id = proposal
+        .circuit
+        .members
+        .iter()
+        .find(|member| {
+            member.public_key.as_deref() == Some(public_key)
+                || member
+                    .authorized_public_keys
+                    .iter()
+                    .any(|key| key == public_key)
+        })
+        .map(|member| member.node_id.as_str());
+
+    if caller_node_id == Some(proposal.requester_node_id.as_str()) {
+        return true;
+    }
+
+    proposal.votes.iter().any(|vote| {
+        vote.public_key == public_key || Some(vote.voter_node_id.as_str()) == caller_node_id
+    })
+}
+
 pub fn make_list_proposals_resource<PSF: ProposalStoreFactory + 'static>(
     proposal_store_factory: PSF,
 ) -> Resource {
@@ -118,6 +205,72 @@ fn list_proposals<PSF: ProposalStoreFactory + 'static>(
         member.to_string()
     });
 
+    #[cfg(feature = "admin-service-requester-filter")]
+    let requester_filter = match query.get("requester") {
+        Some(value) => match parse_hex(value) {
+            Ok(requester) => {
+                new_queries.push(format!("requester={}", value));
+                Some(PublicKey::from_bytes(requester))
+            }
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid requester value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "admin-service-requester-filter"))]
+    let requester_filter = None;
+
+    #[cfg(feature = "admin-service-vote-status-filter")]
+    let vote_status_filter = match query.get("vote_status") {
+        Some(value) => {
+            let vote_status = match VoteStatus::parse(value) {
+                Ok(vote_status) => vote_status,
+                Err(msg) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&msg))
+                            .into_future(),
+                    )
+                }
+            };
+            let public_key = match req.extensions().get::<Identity>() {
+                Some(Identity::Key(key)) => parse_hex(key),
+                _ => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(
+                                "The vote_status filter requires a caller identified by a \
+                                 public key",
+                            ))
+                            .into_future(),
+                    )
+                }
+            };
+            match public_key {
+                Ok(public_key) => {
+                    new_queries.push(format!("vote_status={}", value));
+                    Some(VoteStatusFilter {
+                        vote_status,
+                        public_key,
+                    })
+                }
+                Err(err) => {
+                    error!("Unable to parse caller public key: {}", err);
+                    return Box::new(HttpResponse::InternalServerError().finish().into_future());
+                }
+            }
+        }
+        None => None,
+    };
+
     let mut link = req.uri().path().to_string();
     if !new_queries.is_empty() {
         if let Err(e) = write!(link, "?{}&", new_queries.join("&")) {
@@ -150,6 +303,9 @@ fn list_proposals<PSF: ProposalStoreFactory + 'static>(
         link,
         management_type_filter,
         member_filter,
+        requester_filter,
+        #[cfg(feature = "admin-service-vote-status-filter")]
+        vote_status_filter,
         Some(offset),
         Some(limit),
         protocol_version,
@@ -161,6 +317,12 @@ fn query_list_proposals<PSF: ProposalStoreFactory + 'static>(
     link: String,
     management_type_filter: Option<String>,
     member_filter: Option<String>,
+    #[cfg_attr(
+        not(feature = "admin-service-requester-filter"),
+        allow(unused_variables)
+    )]
+    requester_filter: Option<PublicKey>,
+    #[cfg(feature = "admin-service-vote-status-filter")] vote_filter: Option<VoteStatusFilter>,
     offset: Option<usize>,
     limit: Option<usize>,
     protocol_version: String,
@@ -173,12 +335,29 @@ fn query_list_proposals<PSF: ProposalStoreFactory + 'static>(
         if let Some(member) = member_filter {
             filters.push(CircuitPredicate::MembersInclude(vec![member]));
         }
+        #[cfg(feature = "admin-service-requester-filter")]
+        if let Some(requester) = requester_filter {
+            filters.push(CircuitPredicate::RequesterEq(requester));
+        }
 
         let proposals = proposal_store_factory
             .new_proposal_store()
             .proposals(filters)
             .map_err(|err| ProposalListError::InternalError(err.to_string()))?;
+        // The vote status depends on the caller, so it is applied after the store filters and
+        // before paging, keeping the total consistent with the filtered results.
+        #[cfg(feature = "admin-service-vote-status-filter")]
+        let proposals = proposals
+            .filter(|proposal| match &vote_filter {
+                Some(vote_filter) => vote_filter.matches(proposal),
+                None => true,
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
         let offset_value = offset.unwrap_or(0);
+        #[cfg(feature = "admin-service-vote-status-filter")]
+        let total = proposals.len();
+        #[cfg(not(feature = "admin-service-vote-status-filter"))]
         let total = proposals.total() as usize;
         let limit_value = limit.unwrap_or(total);
 
@@ -214,8 +393,8 @@ fn query_list_proposals<PSF: ProposalStoreFactory + 'static>(
                         }),
                     )
                 }
-                // Handles 2
-                "2" => {
+                // Handles 2 and 3
+                "2" | "3" => {
                     let proposal_responses = match proposals
                         .iter()
                         .map(resources::v2::proposals::ProposalResponse::try_from)
@@ -246,12 +425,9 @@ fn query_list_proposals<PSF: ProposalStoreFactory + 'static>(
                         }),
                     )
                 }
-                _ => Ok(
-                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
-                        "Unsupported SplinterProtocolVersion: {}",
-                        protocol_version
-                    ))),
-                ),
+                _ => Ok(HttpResponse::BadRequest().json(
+                    ErrorResponse::unsupported_protocol_version(&protocol_version),
+                )),
             }
         }
         Err(err) => match err {
@@ -276,14 +452,14 @@ mod tests {
     use splinter::admin::{
         messages::{
             AuthorizationType, CircuitProposal, CircuitStatus, CreateCircuit, DurabilityType,
-            PersistenceType, ProposalType, RouteType, SplinterNode,
+            PersistenceType, ProposalType, RouteType, SplinterNode, Vote, VoteRecord,
         },
         service::proposal_store::{
             error::ProposalStoreError, proposal_iter::ProposalIter, ProposalStore,
         },
         store::{
             self, CircuitPredicate, CircuitProposal as StoreProposal, CircuitProposalBuilder,
-            ProposedCircuitBuilder, ProposedNodeBuilder,
+            ProposedCircuitBuilder, ProposedNodeBuilder, VoteRecordBuilder,
         },
     };
     use splinter::error::InternalError;
@@ -669,6 +845,125 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[cfg(feature = "admin-service-vote-status-filter")]
+    #[test]
+    /// Tests a GET /admin/proposals request with the `vote_status` filter returns the proposals
+    /// the calling key has or has not voted on, with a total that reflects the filter.
+    fn test_list_proposals_with_vote_status_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_proposals_resource(MockProposalStoreFactory)]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?vote_status=voted",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "key 0102")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let proposals: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            proposals.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::proposals::ProposalResponse::try_from(
+                &get_proposal_2()
+            )
+            .expect("Unable to get ProposalResponse")])
+            .expect("failed to convert expected data"),
+        );
+        assert_eq!(
+            proposals
+                .get("paging")
+                .expect("no paging field in response"),
+            &to_value(create_test_paging_response(
+                0,
+                100,
+                0,
+                0,
+                0,
+                1,
+                "/admin/proposals?vote_status=voted&"
+            ))
+            .expect("failed to convert expected paging")
+        );
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?vote_status=pending",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "key 0102")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let proposals: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            proposals.get("data").expect("no data field in response"),
+            &to_value(vec![
+                resources::v2::proposals::ProposalResponse::try_from(&get_proposal_1())
+                    .expect("Unable to get ProposalResponse"),
+                resources::v2::proposals::ProposalResponse::try_from(&get_proposal_3())
+                    .expect("Unable to get ProposalResponse"),
+            ])
+            .expect("failed to convert expected data"),
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[cfg(feature = "admin-service-vote-status-filter")]
+    #[test]
+    /// Tests a GET /admin/proposals request with the `vote_status` filter is rejected when the
+    /// caller is not identified by a public key, or when the status is unknown.
+    fn test_list_proposals_with_vote_status_bad_request() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_proposals_resource(MockProposalStoreFactory)]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?vote_status=pending",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?vote_status=maybe",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "key 0102")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     fn create_test_paging_response(
         offset: usize,
         limit: usize,
@@ -782,6 +1077,12 @@ mod tests {
                         .build()
                         .expect("Unable to create proposed circuit"),
                 )
+                .with_votes(&[VoteRecordBuilder::new()
+                    .with_public_key(&PublicKey::from_bytes(vec![1, 2]))
+                    .with_vote(&store::Vote::Accept)
+                    .with_voter_node_id("node_2")
+                    .build()
+                    .expect("Unable to build vote record")])
                 .with_requester(&PublicKey::from_bytes(vec![]))
                 .with_requester_node_id("node_id")
                 .build()
@@ -827,6 +1128,7 @@ mod tests {
                     node_id: "node_id".into(),
                     endpoints: vec!["".into()],
                     public_key: None,
+                    authorized_public_keys: vec![],
                 }],
                 authorization_type: AuthorizationType::Trust,
                 persistence: PersistenceType::Any,
@@ -838,6 +1140,8 @@ mod tests {
                 display_name: Some("circuit_1".into()),
                 circuit_version: 1,
                 circuit_status: CircuitStatus::Active,
+                attachments: vec![],
+                max_message_size: None,
             },
             votes: vec![],
             requester: vec![],
@@ -864,8 +1168,15 @@ mod tests {
                 display_name: Some("circuit_2".into()),
                 circuit_version: 2,
                 circuit_status: CircuitStatus::Active,
+                attachments: vec![],
+                max_message_size: None,
             },
-            votes: vec![],
+            votes: vec![VoteRecord {
+                public_key: vec![1, 2],
+                vote: Vote::Accept,
+                voter_node_id: "node_2".into(),
+                comment: None,
+            }],
             requester: vec![],
             requester_node_id: "node_id".into(),
         }
@@ -883,6 +1194,7 @@ mod tests {
                     node_id: "node_id".into(),
                     endpoints: vec!["".into()],
                     public_key: None,
+                    authorized_public_keys: vec![],
                 }],
                 authorization_type: AuthorizationType::Trust,
                 persistence: PersistenceType::Any,
@@ -894,6 +1206,8 @@ mod tests {
                 display_name: None,
                 circuit_version: 1,
                 circuit_status: CircuitStatus::Active,
+                attachments: vec![],
+                max_message_size: None,
             },
             votes: vec![],
             requester: vec![],
@@ -938,9 +1252,14 @@ mod tests {
     impl IdentityProvider for MockIdentityProvider {
         fn get_identity(
             &self,
-            _authorization: &AuthorizationHeader,
+            authorization: &AuthorizationHeader,
         ) -> Result<Option<Identity>, InternalError> {
-            Ok(Some(Identity::Custom("custom".to_string())))
+            match authorization {
+                AuthorizationHeader::Custom(value) if value.starts_with("key ") => {
+                    Ok(Some(Identity::Key(value["key ".len()..].to_string())))
+                }
+                _ => Ok(Some(Identity::Custom("custom".to_string()))),
+            }
         }
         fn clone_box(&self) -> Box<dyn IdentityProvider> {
             Box::new(self.clone())
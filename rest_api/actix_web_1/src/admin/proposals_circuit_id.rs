@@ -20,6 +20,9 @@ use std::convert::TryFrom;
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::Future;
 
+use splinter::admin::messages::CircuitProposal;
+#[cfg(feature = "admin-service-rejection-reasons")]
+use splinter::admin::messages::ProposalRejection;
 use splinter::admin::service::proposal_store::ProposalStoreFactory;
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
@@ -34,6 +37,14 @@ use super::CIRCUIT_READ_PERMISSION;
 
 const ADMIN_FETCH_PROPOSALS_PROTOCOL_MIN: u32 = 1;
 
+/// A proposal found by circuit ID, along with the reason it was rejected if it is no longer
+/// pending.
+struct FetchedProposal {
+    proposal: CircuitProposal,
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    rejection: Option<ProposalRejection>,
+}
+
 pub fn make_fetch_proposal_resource<PSF: ProposalStoreFactory + 'static>(
     proposal_store_factory: PSF,
 ) -> Resource {
@@ -80,25 +91,59 @@ fn fetch_proposal<PSF: ProposalStoreFactory + 'static>(
 
     Box::new(
         web::block(move || {
-            let proposal = proposal_store_factory
-                .new_proposal_store()
+            let proposal_store = proposal_store_factory.new_proposal_store();
+            let proposal = proposal_store
                 .proposal(&circuit_id)
-                .map_err(|err| ProposalFetchError::InternalError(err.to_string()))?
-                .ok_or_else(|| {
-                    ProposalFetchError::NotFound(format!("Unable to find proposal: {}", circuit_id))
-                })?;
-
-            Ok((proposal, protocol_version?))
+                .map_err(|err| ProposalFetchError::InternalError(err.to_string()))?;
+
+            // A proposal that is no longer pending may have been rejected
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            let (proposal, rejection) = match proposal {
+                Some(proposal) => (Some(proposal), None),
+                None => proposal_store
+                    .rejected_proposal(&circuit_id)
+                    .map_err(|err| ProposalFetchError::InternalError(err.to_string()))?
+                    .map(|(proposal, rejection)| (Some(proposal), Some(rejection)))
+                    .unwrap_or((None, None)),
+            };
+
+            let proposal = proposal.ok_or_else(|| {
+                ProposalFetchError::NotFound(format!("Unable to find proposal: {}", circuit_id))
+            })?;
+
+            let fetched = FetchedProposal {
+                proposal,
+                #[cfg(feature = "admin-service-rejection-reasons")]
+                rejection,
+            };
+
+            Ok((fetched, protocol_version?))
         })
         .then(|res| match res {
-            Ok((proposal, protocol_version)) => match protocol_version.as_str() {
+            Ok((fetched, protocol_version)) => match protocol_version.as_str() {
                 "1" => Ok(HttpResponse::Ok().json(
-                    resources::v1::proposals_circuit_id::ProposalResponse::from(&proposal),
+                    resources::v1::proposals_circuit_id::ProposalResponse::from(&fetched.proposal),
                 )),
-                // Handles 2
-                "2" => {
-                    match resources::v2::proposals_circuit_id::ProposalResponse::try_from(&proposal)
-                    {
+                // Handles 2 and 3
+                "2" | "3" => {
+                    #[cfg(feature = "admin-service-rejection-reasons")]
+                    let response = match &fetched.rejection {
+                        Some(rejection) => {
+                            resources::v2::proposals_circuit_id::ProposalResponse::rejected(
+                                &fetched.proposal,
+                                rejection,
+                            )
+                        }
+                        None => resources::v2::proposals_circuit_id::ProposalResponse::try_from(
+                            &fetched.proposal,
+                        ),
+                    };
+                    #[cfg(not(feature = "admin-service-rejection-reasons"))]
+                    let response = resources::v2::proposals_circuit_id::ProposalResponse::try_from(
+                        &fetched.proposal,
+                    );
+
+                    match response {
                         Ok(proposal_response) => Ok(HttpResponse::Ok().json(proposal_response)),
                         Err(err) => {
                             error!("{}", err);
@@ -107,12 +152,9 @@ fn fetch_proposal<PSF: ProposalStoreFactory + 'static>(
                         }
                     }
                 }
-                _ => Ok(
-                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
-                        "Unsupported SplinterProtocolVersion: {}",
-                        protocol_version
-                    ))),
-                ),
+                _ => Ok(HttpResponse::BadRequest().json(
+                    ErrorResponse::unsupported_protocol_version(&protocol_version),
+                )),
             },
             Err(err) => match err {
                 BlockingError::Error(err) => match err {
@@ -144,6 +186,8 @@ mod tests {
     use reqwest::{blocking::Client, StatusCode, Url};
     use serde_json::{to_value, Value as JsonValue};
 
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    use splinter::admin::messages::{Vote, VoteRecord};
     use splinter::admin::{
         messages::{
             AuthorizationType, CircuitProposal, CircuitStatus, CreateCircuit, DurabilityType,
@@ -235,6 +279,51 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    #[test]
+    /// Tests a GET /admin/proposals/{circuit_id} request for a proposal that was rejected returns
+    /// the proposal along with the reason it was rejected.
+    fn test_fetch_rejected_proposal_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_fetch_proposal_resource(MockProposalStoreFactory)]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals/{}",
+            bind_url,
+            get_rejected_proposal().0.circuit_id
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let proposal: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        let (expected_proposal, rejection) = get_rejected_proposal();
+        assert_eq!(
+            proposal,
+            to_value(
+                resources::v2::proposals_circuit_id::ProposalResponse::rejected(
+                    &expected_proposal,
+                    &rejection
+                )
+                .expect("Unable to get ProposalResponse")
+            )
+            .expect("failed to convert expected data")
+        );
+        assert_eq!(proposal["rejection"]["type"], "Vote");
+        assert_eq!(proposal["rejection"]["voter_node_id"], "node_b");
+        assert_eq!(proposal["rejection"]["comment"], "wrong endpoints");
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     #[test]
     /// Tests a GET /admin/proposals/{circuit_id} request returns NotFound when an invalid
     /// circuit_id is passed.
@@ -291,6 +380,36 @@ mod tests {
                 None
             })
         }
+
+        #[cfg(feature = "admin-service-rejection-reasons")]
+        fn rejected_proposal(
+            &self,
+            circuit_id: &str,
+        ) -> Result<Option<(CircuitProposal, ProposalRejection)>, ProposalStoreError> {
+            let rejected = get_rejected_proposal();
+            Ok(if circuit_id == &rejected.0.circuit_id {
+                Some(rejected)
+            } else {
+                None
+            })
+        }
+    }
+
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    fn get_rejected_proposal() -> (CircuitProposal, ProposalRejection) {
+        let vote_record = VoteRecord {
+            public_key: vec![1, 2, 3],
+            vote: Vote::Reject,
+            voter_node_id: "node_b".into(),
+            comment: Some("wrong endpoints".into()),
+        };
+
+        let mut proposal = get_proposal();
+        proposal.circuit_id = "circuit2".into();
+        proposal.circuit.circuit_id = "circuit2".into();
+        proposal.votes = vec![vote_record.clone()];
+
+        (proposal, ProposalRejection::Vote(vote_record))
     }
 
     fn get_proposal() -> CircuitProposal {
@@ -312,6 +431,8 @@ mod tests {
                 display_name: Some("test_circuit".into()),
                 circuit_version: 1,
                 circuit_status: CircuitStatus::Active,
+                attachments: vec![],
+                max_message_size: None,
             },
             votes: vec![],
             requester: vec![],
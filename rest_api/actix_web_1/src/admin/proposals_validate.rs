@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::HttpResponse;
+use futures::Future;
+
+use splinter::admin::service::{AdminCommands, AdminServiceError, PayloadValidation};
+use splinter::protos::admin::CircuitManagementPayload;
+use splinter::rest_api::actix_web_1::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource};
+use splinter::service::instance::ServiceError;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_WRITE_PERMISSION;
+
+const ADMIN_PROPOSALS_VALIDATE_PROTOCOL_MIN: u32 = 1;
+
+pub fn make_validate_proposal_route<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/proposals/validate").add_request_guard(
+        ProtocolVersionRangeGuard::new(
+            ADMIN_PROPOSALS_VALIDATE_PROTOCOL_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ),
+    );
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, CIRCUIT_WRITE_PERMISSION, move |_, payload| {
+            let admin_commands = admin_commands.clone();
+            Box::new(
+                into_protobuf::<CircuitManagementPayload>(payload)
+                    .and_then(move |payload| validate_proposal(&admin_commands, &payload)),
+            )
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |_, payload| {
+            let admin_commands = admin_commands.clone();
+            Box::new(
+                into_protobuf::<CircuitManagementPayload>(payload)
+                    .and_then(move |payload| validate_proposal(&admin_commands, &payload)),
+            )
+        })
+    }
+}
+
+fn validate_proposal<A: AdminCommands>(
+    admin_commands: &A,
+    payload: &CircuitManagementPayload,
+) -> Result<HttpResponse, actix_web::Error> {
+    match admin_commands.validate_circuit_change(payload) {
+        Ok(validation) => Ok(HttpResponse::Ok().json(to_json(&validation))),
+        Err(AdminServiceError::ServiceError(ServiceError::UnableToHandleMessage(err))) => {
+            debug!("{}", err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "message": format!("Unable to handle message: {}", err)
+            })))
+        }
+        Err(AdminServiceError::ServiceError(ServiceError::InvalidMessageFormat(err))) => {
+            Ok(HttpResponse::BadRequest().json(json!({
+                "message": format!("Failed to parse payload: {}", err)
+            })))
+        }
+        Err(err) => {
+            error!("{}", err);
+            Ok(HttpResponse::InternalServerError().finish())
+        }
+    }
+}
+
+fn to_json(validation: &PayloadValidation) -> serde_json::Value {
+    json!({
+        "valid": validation.is_valid(),
+        "errors": validation.errors(),
+        "warnings": validation.warnings(),
+    })
+}
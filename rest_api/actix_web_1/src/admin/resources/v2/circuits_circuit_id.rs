@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::collections::BTreeMap;
+#[cfg(feature = "circuit-tombstones")]
+use std::time::UNIX_EPOCH;
 
 use splinter::admin::store::{Circuit, CircuitNode, CircuitStatus, Service};
 
@@ -27,6 +29,15 @@ pub(crate) struct CircuitResponse<'a> {
     pub display_name: &'a Option<String>,
     pub circuit_version: i32,
     pub circuit_status: &'a CircuitStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u64>,
+    /// The time the circuit was purged, in seconds since the Unix epoch, if it was tombstoned
+    #[cfg(feature = "circuit-tombstones")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_reason: Option<&'a str>,
 }
 
 impl<'a> From<&'a Circuit> for CircuitResponse<'a> {
@@ -43,6 +54,17 @@ impl<'a> From<&'a Circuit> for CircuitResponse<'a> {
             display_name: circuit.display_name(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status(),
+            max_message_size: circuit.max_message_size(),
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_at: circuit.tombstone().map(|tombstone| {
+                tombstone
+                    .deleted_at()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0)
+            }),
+            #[cfg(feature = "circuit-tombstones")]
+            deleted_reason: circuit.tombstone().map(|tombstone| tombstone.reason()),
         }
     }
 }
@@ -75,6 +97,8 @@ pub(crate) struct CircuitNodeResponse<'a> {
     pub node_id: &'a str,
     pub endpoints: &'a [String],
     pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authorized_public_keys: Vec<String>,
 }
 
 impl<'a> From<&'a CircuitNode> for CircuitNodeResponse<'a> {
@@ -86,6 +110,11 @@ impl<'a> From<&'a CircuitNode> for CircuitNodeResponse<'a> {
                 .public_key()
                 .as_ref()
                 .map(|public_key| to_hex(public_key.as_slice())),
+            authorized_public_keys: node_def
+                .authorized_public_keys()
+                .iter()
+                .map(|public_key| to_hex(public_key.as_slice()))
+                .collect(),
         }
     }
 }
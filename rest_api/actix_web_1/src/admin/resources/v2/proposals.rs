@@ -14,8 +14,8 @@
 use std::convert::TryFrom;
 
 use splinter::admin::messages::{
-    CircuitProposal, CircuitStatus, CreateCircuit, ProposalType, SplinterNode, SplinterService,
-    Vote, VoteRecord,
+    CircuitAttachment, CircuitProposal, CircuitStatus, CreateCircuit, ProposalType, SplinterNode,
+    SplinterService, Vote, VoteRecord,
 };
 use splinter::rest_api::paging::Paging;
 
@@ -69,6 +69,8 @@ pub(crate) struct VoteResponse<'a> {
     pub public_key: &'a [u8],
     pub vote: &'a str,
     pub voter_node_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<&'a str>,
 }
 
 impl<'a> From<&'a VoteRecord> for VoteResponse<'a> {
@@ -82,6 +84,7 @@ impl<'a> From<&'a VoteRecord> for VoteResponse<'a> {
             public_key: &record.public_key,
             vote,
             voter_node_id: &record.voter_node_id,
+            comment: record.comment.as_deref(),
         }
     }
 }
@@ -98,6 +101,10 @@ pub(crate) struct CircuitResponse<'a> {
     pub display_name: &'a Option<String>,
     pub circuit_version: i32,
     pub circuit_status: &'a CircuitStatus,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub attachments: &'a [CircuitAttachment],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u64>,
 }
 
 impl<'a> TryFrom<&'a CreateCircuit> for CircuitResponse<'a> {
@@ -118,6 +125,8 @@ impl<'a> TryFrom<&'a CreateCircuit> for CircuitResponse<'a> {
             display_name: &circuit.display_name,
             circuit_version: circuit.circuit_version,
             circuit_status: &circuit.circuit_status,
+            attachments: &circuit.attachments,
+            max_message_size: circuit.max_message_size,
         })
     }
 }
@@ -127,6 +136,8 @@ pub(crate) struct NodeResponse<'a> {
     pub node_id: &'a str,
     pub endpoints: &'a [String],
     pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authorized_public_keys: Vec<String>,
 }
 
 impl<'a> From<&'a SplinterNode> for NodeResponse<'a> {
@@ -138,6 +149,11 @@ impl<'a> From<&'a SplinterNode> for NodeResponse<'a> {
                 .public_key
                 .as_ref()
                 .map(|public_key| to_hex(public_key)),
+            authorized_public_keys: node
+                .authorized_public_keys
+                .iter()
+                .map(|public_key| to_hex(public_key))
+                .collect(),
         }
     }
 }
@@ -14,6 +14,8 @@
 
 use std::convert::TryFrom;
 
+#[cfg(feature = "admin-service-rejection-reasons")]
+use splinter::admin::messages::ProposalRejection;
 use splinter::admin::messages::{
     CircuitProposal, CircuitStatus, CreateCircuit, ProposalType, SplinterNode, SplinterService,
     Vote, VoteRecord,
@@ -32,6 +34,22 @@ pub(crate) struct ProposalResponse<'a> {
     #[serde(serialize_with = "as_hex")]
     pub requester: &'a [u8],
     pub requester_node_id: &'a str,
+    #[cfg(feature = "admin-service-rejection-reasons")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<RejectionResponse<'a>>,
+}
+
+#[cfg(feature = "admin-service-rejection-reasons")]
+impl<'a> ProposalResponse<'a> {
+    /// Creates a response for a proposal that has been rejected, including the reason
+    pub fn rejected(
+        proposal: &'a CircuitProposal,
+        rejection: &'a ProposalRejection,
+    ) -> Result<Self, &'static str> {
+        let mut response = Self::try_from(proposal)?;
+        response.rejection = Some(RejectionResponse::from(rejection));
+        Ok(response)
+    }
 }
 
 impl<'a> TryFrom<&'a CircuitProposal> for ProposalResponse<'a> {
@@ -54,16 +72,44 @@ impl<'a> TryFrom<&'a CircuitProposal> for ProposalResponse<'a> {
             votes: proposal.votes.iter().map(VoteResponse::from).collect(),
             requester: &proposal.requester,
             requester_node_id: &proposal.requester_node_id,
+            #[cfg(feature = "admin-service-rejection-reasons")]
+            rejection: None,
         })
     }
 }
 
+/// The reason a proposal was rejected
+#[cfg(feature = "admin-service-rejection-reasons")]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub(crate) enum RejectionResponse<'a> {
+    ValidationFailed { node_id: &'a str, reason: &'a str },
+    Vote(VoteResponse<'a>),
+}
+
+#[cfg(feature = "admin-service-rejection-reasons")]
+impl<'a> From<&'a ProposalRejection> for RejectionResponse<'a> {
+    fn from(rejection: &'a ProposalRejection) -> Self {
+        match rejection {
+            ProposalRejection::ValidationFailed(failure) => RejectionResponse::ValidationFailed {
+                node_id: &failure.node_id,
+                reason: &failure.reason,
+            },
+            ProposalRejection::Vote(vote_record) => {
+                RejectionResponse::Vote(VoteResponse::from(vote_record))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub(crate) struct VoteResponse<'a> {
     #[serde(serialize_with = "as_hex")]
     pub public_key: &'a [u8],
     pub vote: &'a str,
     pub voter_node_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<&'a str>,
 }
 
 impl<'a> From<&'a VoteRecord> for VoteResponse<'a> {
@@ -77,6 +123,7 @@ impl<'a> From<&'a VoteRecord> for VoteResponse<'a> {
             public_key: &record.public_key,
             vote,
             voter_node_id: &record.voter_node_id,
+            comment: record.comment.as_deref(),
         }
     }
 }
@@ -93,6 +140,8 @@ pub(crate) struct CircuitResponse<'a> {
     pub display_name: &'a Option<String>,
     pub circuit_version: i32,
     pub circuit_status: &'a CircuitStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u64>,
 }
 
 impl<'a> TryFrom<&'a CreateCircuit> for CircuitResponse<'a> {
@@ -113,6 +162,7 @@ impl<'a> TryFrom<&'a CreateCircuit> for CircuitResponse<'a> {
             display_name: &circuit.display_name,
             circuit_version: circuit.circuit_version,
             circuit_status: &circuit.circuit_status,
+            max_message_size: circuit.max_message_size,
         })
     }
 }
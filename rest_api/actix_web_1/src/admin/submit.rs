@@ -14,6 +14,8 @@
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
 
+#[cfg(feature = "admin-service-proposal-limits")]
+use splinter::admin::service::ProposalThrottled;
 use splinter::admin::service::{AdminCommands, AdminServiceError};
 use splinter::protos::admin::CircuitManagementPayload;
 use splinter::rest_api::actix_web_1::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource};
@@ -25,6 +27,20 @@ use super::CIRCUIT_WRITE_PERMISSION;
 
 const ADMIN_SUBMIT_PROTOCOL_MIN: u32 = 1;
 
+/// Builds the response to a proposal that exceeded the admin service's proposal limits, with a
+/// `Retry-After` header if the requester may retry after a known delay.
+#[cfg(feature = "admin-service-proposal-limits")]
+fn throttled_response(throttled: &ProposalThrottled) -> HttpResponse {
+    debug!("Proposal throttled: {}", throttled);
+    let mut response = HttpResponse::TooManyRequests();
+    if let Some(retry_after) = throttled.retry_after() {
+        response.header("Retry-After", retry_after.as_secs().max(1).to_string());
+    }
+    response.json(json!({
+        "message": format!("Proposal throttled: {}", throttled)
+    }))
+}
+
 pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A) -> Resource {
     let resource = Resource::build("/admin/submit").add_request_guard(
         ProtocolVersionRangeGuard::new(ADMIN_SUBMIT_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION),
@@ -38,6 +54,10 @@ pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A)
                 into_protobuf::<CircuitManagementPayload>(payload).and_then(move |payload| {
                     match admin_commands.submit_circuit_change(payload) {
                         Ok(()) => HttpResponse::Accepted().finish().into_future(),
+                        #[cfg(feature = "admin-service-proposal-limits")]
+                        Err(AdminServiceError::Throttled(throttled)) => {
+                            throttled_response(&throttled).into_future()
+                        }
                         Err(AdminServiceError::ServiceError(
                             ServiceError::UnableToHandleMessage(err),
                         )) => {
@@ -72,6 +92,10 @@ pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A)
                 into_protobuf::<CircuitManagementPayload>(payload).and_then(move |payload| {
                     match admin_commands.submit_circuit_change(payload) {
                         Ok(()) => HttpResponse::Accepted().finish().into_future(),
+                        #[cfg(feature = "admin-service-proposal-limits")]
+                        Err(AdminServiceError::Throttled(throttled)) => {
+                            throttled_response(&throttled).into_future()
+                        }
                         Err(AdminServiceError::ServiceError(
                             ServiceError::UnableToHandleMessage(err),
                         )) => {
@@ -0,0 +1,204 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the following endpoints:
+//!
+//! * `GET /admin/vote_delegations` for listing the vote delegations held by the node
+//! * `POST /admin/vote_delegations` for adding a signed vote delegation
+//! * `DELETE /admin/vote_delegations/{delegator}/{delegate}` for removing a vote delegation
+
+use std::convert::TryFrom;
+
+use actix_web::{HttpRequest, HttpResponse};
+use futures::{Future, IntoFuture};
+
+use splinter::admin::delegation::VoteDelegation;
+use splinter::admin::service::{AdminCommands, AdminServiceError};
+use splinter::protos::admin::VoteDelegation as VoteDelegationProto;
+use splinter::public_key::PublicKey;
+use splinter::rest_api::{
+    actix_web_1::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter::service::instance::ServiceError;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use crate::hex::{parse_hex, to_hex};
+
+#[cfg(feature = "authorization")]
+use super::{CIRCUIT_READ_PERMISSION, CIRCUIT_WRITE_PERMISSION};
+
+const ADMIN_VOTE_DELEGATIONS_MIN: u32 = 2;
+
+pub fn make_vote_delegations_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let admin_commands1 = admin_commands.clone();
+    let resource = Resource::build("/admin/vote_delegations").add_request_guard(
+        ProtocolVersionRangeGuard::new(ADMIN_VOTE_DELEGATIONS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |_, _| {
+                Box::new(list_vote_delegations(&admin_commands).into_future())
+            })
+            .add_method(Method::Post, CIRCUIT_WRITE_PERMISSION, move |_, payload| {
+                let admin_commands = admin_commands1.clone();
+                Box::new(
+                    into_protobuf::<VoteDelegationProto>(payload)
+                        .map(move |delegation| add_vote_delegation(delegation, &admin_commands)),
+                )
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| {
+                Box::new(list_vote_delegations(&admin_commands).into_future())
+            })
+            .add_method(Method::Post, move |_, payload| {
+                let admin_commands = admin_commands1.clone();
+                Box::new(
+                    into_protobuf::<VoteDelegationProto>(payload)
+                        .map(move |delegation| add_vote_delegation(delegation, &admin_commands)),
+                )
+            })
+    }
+}
+
+pub fn make_vote_delegation_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource =
+        Resource::build("/admin/vote_delegations/{delegator}/{delegate}").add_request_guard(
+            ProtocolVersionRangeGuard::new(ADMIN_VOTE_DELEGATIONS_MIN, SPLINTER_PROTOCOL_VERSION),
+        );
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Delete,
+            CIRCUIT_WRITE_PERMISSION,
+            move |request, _| {
+                Box::new(remove_vote_delegation(request, &admin_commands).into_future())
+            },
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Delete, move |request, _| {
+            Box::new(remove_vote_delegation(request, &admin_commands).into_future())
+        })
+    }
+}
+
+fn list_vote_delegations<A: AdminCommands>(admin_commands: &A) -> HttpResponse {
+    match admin_commands.list_vote_delegations() {
+        Ok(delegations) => HttpResponse::Ok().json(json!({
+            "data": delegations
+                .iter()
+                .map(VoteDelegationResponse::from)
+                .collect::<Vec<_>>(),
+        })),
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
+
+fn add_vote_delegation<A: AdminCommands>(
+    delegation: VoteDelegationProto,
+    admin_commands: &A,
+) -> HttpResponse {
+    let delegation = match VoteDelegation::try_from(delegation) {
+        Ok(delegation) => delegation,
+        Err(err) => {
+            return HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                "Invalid vote delegation: {}",
+                err
+            )))
+        }
+    };
+
+    match admin_commands.add_vote_delegation(delegation) {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(AdminServiceError::ServiceError(ServiceError::UnableToHandleMessage(err))) => {
+            HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                "Invalid vote delegation: {}",
+                err
+            )))
+        }
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
+
+fn remove_vote_delegation<A: AdminCommands>(
+    request: HttpRequest,
+    admin_commands: &A,
+) -> HttpResponse {
+    let (delegator, delegate) = match (
+        parse_path_key(&request, "delegator"),
+        parse_path_key(&request, "delegate"),
+    ) {
+        (Ok(delegator), Ok(delegate)) => (delegator, delegate),
+        (Err(response), _) | (_, Err(response)) => return response,
+    };
+
+    match admin_commands.remove_vote_delegation(&delegator, &delegate) {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+            "Unable to find vote delegation from {} to {}",
+            to_hex(delegator.as_slice()),
+            to_hex(delegate.as_slice())
+        ))),
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
+
+fn parse_path_key(request: &HttpRequest, name: &str) -> Result<PublicKey, HttpResponse> {
+    let key = request.match_info().get(name).unwrap_or("");
+    parse_hex(key).map(PublicKey::from_bytes).map_err(|_| {
+        HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+            "Invalid {} public key: {}",
+            name, key
+        )))
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct VoteDelegationResponse<'a> {
+    delegator_public_key: String,
+    delegate_public_key: String,
+    node_id: &'a str,
+    circuit_management_types: &'a [String],
+}
+
+impl<'a> From<&'a VoteDelegation> for VoteDelegationResponse<'a> {
+    fn from(delegation: &'a VoteDelegation) -> Self {
+        Self {
+            delegator_public_key: to_hex(delegation.delegator_public_key().as_slice()),
+            delegate_public_key: to_hex(delegation.delegate_public_key().as_slice()),
+            node_id: delegation.node_id(),
+            circuit_management_types: delegation.circuit_management_types(),
+        }
+    }
+}
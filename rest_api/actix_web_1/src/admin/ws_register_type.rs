@@ -22,6 +22,8 @@ use std::time;
 
 use splinter::admin::messages::v1;
 use splinter::admin::messages::AdminServiceEvent;
+#[cfg(feature = "admin-service-event-retention")]
+use splinter::admin::service::AdminServiceError;
 use splinter::admin::service::{
     AdminCommands, AdminServiceEventSubscriber, AdminServiceStatus, AdminSubscriberError,
 };
@@ -147,6 +149,14 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                                 }
                             }
                         }
+                        #[cfg(feature = "admin-service-event-retention")]
+                        Err(err @ AdminServiceError::EventsTruncated { .. }) => {
+                            return Box::new(
+                                HttpResponse::Gone()
+                                    .json(ErrorResponse::gone(&err.to_string()))
+                                    .into_future(),
+                            );
+                        }
                         Err(err) => {
                             error!(
                                 "Unable to load initial set of admin events for {}: {}",
@@ -275,6 +285,14 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                             }
                         }
                     }
+                    #[cfg(feature = "admin-service-event-retention")]
+                    Err(err @ AdminServiceError::EventsTruncated { .. }) => {
+                        return Box::new(
+                            HttpResponse::Gone()
+                                .json(ErrorResponse::gone(&err.to_string()))
+                                .into_future(),
+                        );
+                    }
                     Err(err) => {
                         error!(
                             "Unable to load initial set of admin events for {}: {}",
@@ -366,8 +384,8 @@ impl JsonAdminEvent {
                 })?),
                 event_id: Some(*event.event_id()),
             }),
-            // Handles 2
-            2 => Ok(Self {
+            // Handles 2 and 3
+            2 | 3 => Ok(Self {
                 timestamp: time::SystemTime::now(),
                 event: Some(AdminServiceEvent::from(event)),
                 event_v1: None,
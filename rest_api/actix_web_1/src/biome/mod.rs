@@ -14,3 +14,5 @@
 
 #[cfg(feature = "biome-key-management")]
 pub mod key_management;
+#[cfg(feature = "biome-notifications")]
+pub mod notifications;
@@ -0,0 +1,139 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the websocket endpoint for Biome notifications.
+
+use actix_web::HttpResponse;
+use futures::IntoFuture;
+use splinter::biome::notifications::{
+    BiomeNotification, BiomeNotificationSubscriber, BiomeNotificationSubscriberError,
+    BiomeNotifier, NotificationSubject,
+};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::rest_api::{
+    actix_web_1::{
+        new_websocket_event_sender, EventSender, HandlerFunction, Method,
+        ProtocolVersionRangeGuard, Request,
+    },
+    auth::identity::Identity,
+    ErrorResponse, Resource, RestResourceProvider,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+const BIOME_NOTIFICATIONS_PROTOCOL_MIN: u32 = 1;
+
+/// Provides the following REST API endpoint for Biome notifications:
+///
+/// * `GET /biome/notifications` - Open a websocket that receives a notification whenever a key
+///   is added to or removed from the authorized identity, or its role assignment changes
+pub struct BiomeNotificationsRestResourceProvider {
+    notifier: BiomeNotifier,
+}
+
+impl BiomeNotificationsRestResourceProvider {
+    pub fn new(notifier: BiomeNotifier) -> Self {
+        Self { notifier }
+    }
+}
+
+impl RestResourceProvider for BiomeNotificationsRestResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![make_notifications_route(self.notifier.clone())]
+    }
+}
+
+/// Defines a REST endpoint for subscribing to notifications for the authorized identity
+fn make_notifications_route(notifier: BiomeNotifier) -> Resource {
+    let resource = Resource::build("/biome/notifications").add_request_guard(
+        ProtocolVersionRangeGuard::new(BIOME_NOTIFICATIONS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Get,
+            Permission::AllowAuthenticated,
+            handle_get(notifier),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, handle_get(notifier))
+    }
+}
+
+/// Opens a websocket and subscribes it to the notifications that concern the authorized identity
+fn handle_get(notifier: BiomeNotifier) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let subject = match request.extensions().get::<Identity>() {
+            Some(Identity::User(user_id)) => NotificationSubject::User(user_id.clone()),
+            Some(Identity::Key(public_key)) => NotificationSubject::Key(public_key.clone()),
+            _ => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+        };
+
+        let request = Request::from((request, payload));
+        match new_websocket_event_sender(request, Box::new(std::iter::empty())) {
+            Ok((sender, res)) => {
+                if let Err(err) = notifier
+                    .add_subscriber(Box::new(WsBiomeNotificationSubscriber { subject, sender }))
+                {
+                    error!("Unable to add Biome notification subscriber: {}", err);
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+                debug!("Websocket response: {:?}", res);
+                Box::new(res.into_future())
+            }
+            Err(err) => {
+                debug!("Failed to create websocket: {:?}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Forwards the notifications that concern a single identity to its websocket
+struct WsBiomeNotificationSubscriber {
+    subject: NotificationSubject,
+    sender: EventSender<BiomeNotification>,
+}
+
+impl BiomeNotificationSubscriber for WsBiomeNotificationSubscriber {
+    fn handle_notification(
+        &self,
+        notification: &BiomeNotification,
+    ) -> Result<(), BiomeNotificationSubscriberError> {
+        if !notification.concerns(&self.subject) {
+            return Ok(());
+        }
+
+        self.sender.send(notification.clone()).map_err(|_| {
+            debug!("Dropping Biome notification and unsubscribing due to websocket being closed");
+            BiomeNotificationSubscriberError::Unsubscribe
+        })
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for reading circuit access logs.
+
+mod records;
+mod resources;
+
+use splinter::circuit::access_log::AccessLogStore;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "authorization")]
+const CIRCUIT_ACCESS_LOG_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "circuit.access_log.read",
+    permission_display_name: "Circuit access log read",
+    permission_description: "Allows the client to read the access logs of circuits",
+};
+
+/// The `CircuitAccessLogResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /admin/circuits/{circuit_id}/access_log` - List the access records of a circuit,
+///   optionally filtered by `service_id`, `identity`, `since` and `until`
+pub struct CircuitAccessLogResourceProvider {
+    store: Box<dyn AccessLogStore>,
+}
+
+impl CircuitAccessLogResourceProvider {
+    pub fn new(store: Box<dyn AccessLogStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl RestResourceProvider for CircuitAccessLogResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![records::make_access_log_resource(self.store.clone())]
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /admin/circuits/{circuit_id}/access_log` for listing a circuit's access records
+
+use std::collections::HashMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::circuit::access_log::{AccessLogStore, AccessLogStoreError, AccessRecordFilter};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    percent_encode_filter_query, ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{AccessRecordResponse, ListAccessRecordsResponse};
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_ACCESS_LOG_READ_PERMISSION;
+
+const CIRCUIT_ACCESS_LOG_MIN: u32 = 2;
+
+pub fn make_access_log_resource(store: Box<dyn AccessLogStore>) -> Resource {
+    let resource = Resource::build("/admin/circuits/{circuit_id}/access_log").add_request_guard(
+        ProtocolVersionRangeGuard::new(CIRCUIT_ACCESS_LOG_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Get,
+            CIRCUIT_ACCESS_LOG_READ_PERMISSION,
+            move |r, _| list_access_records(r, web::Data::new(store.clone())),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| {
+            list_access_records(r, web::Data::new(store.clone()))
+        })
+    }
+}
+
+fn list_access_records(
+    req: HttpRequest,
+    store: web::Data<Box<dyn AccessLogStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = req.match_info().get("circuit_id").unwrap_or("").to_string();
+
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(req.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let offset = match parse_usize(&query, "offset") {
+        Ok(value) => value.unwrap_or(DEFAULT_OFFSET),
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    let limit = match parse_usize(&query, "limit") {
+        Ok(value) => value.unwrap_or(DEFAULT_LIMIT),
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    let mut link = format!("{}?", req.uri().path());
+    let mut filter = AccessRecordFilter::default().with_circuit_id(&circuit_id);
+
+    if let Some(service_id) = query.get("service_id") {
+        link.push_str(&format!(
+            "service_id={}&",
+            percent_encode_filter_query(service_id)
+        ));
+        filter = filter.with_service_id(service_id);
+    }
+
+    if let Some(identity) = query.get("identity") {
+        link.push_str(&format!(
+            "identity={}&",
+            percent_encode_filter_query(identity)
+        ));
+        filter = filter.with_identity(identity);
+    }
+
+    for key in &["since", "until"] {
+        let secs = match query.get(*key) {
+            Some(value) => match value.parse::<u64>() {
+                Ok(secs) => secs,
+                Err(err) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid {} value passed: {}. Error: {}",
+                                key, value, err
+                            )))
+                            .into_future(),
+                    )
+                }
+            },
+            None => continue,
+        };
+        link.push_str(&format!("{}={}&", key, secs));
+        let time = UNIX_EPOCH + Duration::from_secs(secs);
+        filter = if *key == "since" {
+            filter.with_since(time)
+        } else {
+            filter.with_until(time)
+        };
+    }
+
+    Box::new(
+        web::block(move || {
+            let records = store.list_records(&filter)?;
+            let total = records.len();
+            let records = records
+                .skip(offset)
+                .take(limit)
+                .map(|record| AccessRecordResponse::from(&record))
+                .collect::<Vec<_>>();
+            Ok::<_, AccessLogStoreError>((records, total))
+        })
+        .then(
+            move |res: Result<_, BlockingError<AccessLogStoreError>>| match res {
+                Ok((records, total)) => {
+                    let paging = PagingBuilder::new(link, total)
+                        .with_limit(limit)
+                        .with_offset(offset)
+                        .build();
+                    Ok(HttpResponse::Ok().json(ListAccessRecordsResponse {
+                        data: records,
+                        paging,
+                    }))
+                }
+                Err(err) => {
+                    error!("Unable to list circuit access records: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
+
+fn parse_usize(query: &HashMap<String, String>, key: &str) -> Result<Option<usize>, HttpResponse> {
+    match query.get(key) {
+        Some(value) => value.parse::<usize>().map(Some).map_err(|err| {
+            HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                "Invalid {} value passed: {}. Error: {}",
+                key, value, err
+            )))
+        }),
+        None => Ok(None),
+    }
+}
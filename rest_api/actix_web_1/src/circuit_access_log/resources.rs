@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use splinter::circuit::access_log::AccessRecord;
+use splinter::rest_api::paging::Paging;
+
+#[derive(Debug, Serialize)]
+pub struct ListAccessRecordsResponse {
+    pub data: Vec<AccessRecordResponse>,
+    pub paging: Paging,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccessRecordResponse {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub circuit_id: String,
+    pub service_id: String,
+    pub identity: String,
+    pub payload_hash: String,
+}
+
+impl From<&AccessRecord> for AccessRecordResponse {
+    fn from(record: &AccessRecord) -> Self {
+        Self {
+            timestamp: record
+                .timestamp()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            circuit_id: record.circuit_id().to_string(),
+            service_id: record.service_id().to_string(),
+            identity: record.identity().to_string(),
+            payload_hash: record.payload_hash().to_string(),
+        }
+    }
+}
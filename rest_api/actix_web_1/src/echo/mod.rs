@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API resources for inspecting echo services, used when soak testing the network between
+//! Splinter nodes.
+
+mod resources;
+mod stats;
+
+use std::sync::{Arc, Mutex};
+
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter_echo::store::PooledEchoStoreFactory;
+
+#[cfg(feature = "authorization")]
+const ECHO_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "echo.read",
+    permission_display_name: "Echo read",
+    permission_description: "Allows the client to read the request statistics of echo services",
+};
+
+pub struct EchoStatsResourceProvider {
+    store_factory: Arc<Mutex<Box<dyn PooledEchoStoreFactory>>>,
+}
+
+impl EchoStatsResourceProvider {
+    pub fn new(store_factory: Box<dyn PooledEchoStoreFactory>) -> Self {
+        Self {
+            store_factory: Arc::new(Mutex::new(store_factory)),
+        }
+    }
+}
+
+impl RestResourceProvider for EchoStatsResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![stats::make_echo_stats_resource(self.store_factory.clone())]
+    }
+}
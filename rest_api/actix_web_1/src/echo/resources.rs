@@ -0,0 +1,43 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Serialize;
+use splinter_echo::service::EchoServiceStats;
+
+#[derive(Debug, Serialize)]
+pub struct EchoServiceStatsResponse {
+    pub requests: u64,
+    pub sent: u64,
+    pub acknowledged: u64,
+    pub errors: u64,
+    pub bytes_sent: u64,
+    /// Average round trip time in milliseconds
+    pub average_round_trip: Option<u128>,
+    /// Longest round trip time in milliseconds
+    pub max_round_trip: Option<u128>,
+}
+
+impl From<EchoServiceStats> for EchoServiceStatsResponse {
+    fn from(stats: EchoServiceStats) -> Self {
+        Self {
+            requests: stats.requests,
+            sent: stats.sent,
+            acknowledged: stats.acknowledged,
+            errors: stats.errors,
+            bytes_sent: stats.bytes_sent,
+            average_round_trip: stats.average_round_trip.map(|time| time.as_millis()),
+            max_round_trip: stats.max_round_trip.map(|time| time.as_millis()),
+        }
+    }
+}
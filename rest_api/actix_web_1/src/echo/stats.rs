@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::error::InternalError;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter::service::FullyQualifiedServiceId;
+use splinter_echo::store::PooledEchoStoreFactory;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::EchoServiceStatsResponse;
+#[cfg(feature = "authorization")]
+use super::ECHO_READ_PERMISSION;
+
+const ECHO_STATS_MIN: u32 = 2;
+
+type EchoStoreFactory = Arc<Mutex<Box<dyn PooledEchoStoreFactory>>>;
+
+pub fn make_echo_stats_resource(store_factory: EchoStoreFactory) -> Resource {
+    let resource = Resource::build("/echo/{circuit_id}/{service_id}/stats").add_request_guard(
+        ProtocolVersionRangeGuard::new(ECHO_STATS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, ECHO_READ_PERMISSION, move |r, _| {
+            get_echo_stats(r, store_factory.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| {
+            get_echo_stats(r, store_factory.clone())
+        })
+    }
+}
+
+fn get_echo_stats(
+    req: HttpRequest,
+    store_factory: EchoStoreFactory,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = req.match_info().get("circuit_id").unwrap_or("");
+    let service_id = req.match_info().get("service_id").unwrap_or("");
+
+    let service =
+        match FullyQualifiedServiceId::new_from_string(format!("{}::{}", circuit_id, service_id)) {
+            Ok(service) => service,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid service: {}",
+                            err
+                        )))
+                        .into_future(),
+                )
+            }
+        };
+
+    let not_found_message = format!("Echo service {} not found", service);
+
+    Box::new(
+        web::block(move || {
+            let store = store_factory
+                .lock()
+                .map_err(|_| {
+                    InternalError::with_message("Echo store factory lock was poisoned".into())
+                })?
+                .new_store();
+            store.get_service_stats(&service)
+        })
+        .then(
+            move |res: Result<_, BlockingError<InternalError>>| match res {
+                Ok(Some(stats)) => {
+                    Ok(HttpResponse::Ok().json(EchoServiceStatsResponse::from(stats)))
+                }
+                Ok(None) => {
+                    Ok(HttpResponse::NotFound().json(ErrorResponse::not_found(&not_found_message)))
+                }
+                Err(err) => {
+                    error!("Unable to get echo service stats: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
@@ -0,0 +1,488 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines REST API resources that forward requests to a Splinter daemon running in
+//! another process.
+//!
+//! This allows the REST API to be run separately from the Splinter daemon: endpoints backed only
+//! by the database are served directly, while endpoints that need the running daemon, such as
+//! submitting admin payloads or reading network state, are forwarded to it.
+
+mod proxy;
+
+use splinter::rest_api::actix_web_1::{Method, Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+#[cfg(feature = "service")]
+use splinter::service::rest_api::ServiceEndpointProvider;
+
+#[cfg(all(feature = "authorization", feature = "admin-service"))]
+use crate::admin::{CIRCUIT_READ_PERMISSION, CIRCUIT_WRITE_PERMISSION};
+#[cfg(all(feature = "authorization", feature = "metrics-snapshot"))]
+use crate::metrics::METRICS_READ_PERMISSION;
+#[cfg(all(
+    feature = "authorization",
+    any(feature = "peer-endpoint-pin", feature = "peer-ref-diagnostics")
+))]
+use crate::network::NETWORK_PEERS_READ_PERMISSION;
+#[cfg(all(
+    feature = "authorization",
+    any(
+        feature = "peer-disconnect",
+        feature = "peer-endpoint-pin",
+        feature = "peer-ref-diagnostics"
+    )
+))]
+use crate::network::NETWORK_PEERS_WRITE_PERMISSION;
+#[cfg(all(feature = "authorization", feature = "mesh-bandwidth-throttle"))]
+use crate::network::{NETWORK_THROTTLE_READ_PERMISSION, NETWORK_THROTTLE_WRITE_PERMISSION};
+#[cfg(all(feature = "authorization", feature = "registry"))]
+use crate::registry::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
+#[cfg(feature = "authorization")]
+use crate::status::STATUS_READ_PERMISSION;
+#[cfg(all(feature = "authorization", feature = "node-metadata"))]
+use crate::status::STATUS_WRITE_PERMISSION;
+
+/// A method of a forwarded route, with the permission the client needs to use it.
+///
+/// The permission is checked before the request is forwarded, using the same authorization
+/// configuration as the Splinter daemon.
+struct ForwardedMethod {
+    method: Method,
+    #[cfg(feature = "authorization")]
+    permission: Permission,
+}
+
+fn forwarded(
+    method: Method,
+    #[cfg(feature = "authorization")] permission: Permission,
+) -> ForwardedMethod {
+    ForwardedMethod {
+        method,
+        #[cfg(feature = "authorization")]
+        permission,
+    }
+}
+
+/// The `CoreForwardResourceProvider` struct forwards the endpoints that require a running
+/// Splinter daemon to that daemon's REST API. The following endpoints are forwarded, depending
+/// on the enabled features:
+///
+/// * `GET /status`, `PATCH /status`
+/// * `POST /admin/submit`, `GET /admin/circuits/{circuit_id}/purge`
+/// * `GET /registry/nodes`, `POST /registry/nodes`
+/// * `GET /registry/nodes/{identity}`, `PUT /registry/nodes/{identity}`,
+///   `DELETE /registry/nodes/{identity}`
+/// * `POST /registry/refresh`
+/// * `GET /metrics`
+/// * `POST /network/peers/{peer_id}/disconnect`
+/// * `GET /network/throttle`, `PUT /network/throttle`
+///
+/// The endpoints of orchestrated services may be added with
+/// [`with_service_endpoints`](CoreForwardResourceProvider::with_service_endpoints).
+///
+/// Websocket endpoints, such as `/ws/admin/register/{type}`, cannot be forwarded; clients must
+/// connect to the Splinter daemon directly to subscribe to events.
+pub struct CoreForwardResourceProvider {
+    core_endpoint: String,
+    resources: Vec<Resource>,
+}
+
+impl CoreForwardResourceProvider {
+    /// Creates a provider that forwards requests to the Splinter daemon's REST API at
+    /// `core_endpoint`, for example `http://127.0.0.1:8085`.
+    pub fn new(core_endpoint: &str) -> Self {
+        let core_endpoint = core_endpoint.trim_end_matches('/').to_string();
+        let resources = core_routes()
+            .into_iter()
+            .map(|(route, methods)| make_forward_resource(&core_endpoint, route, methods))
+            .collect();
+
+        Self {
+            core_endpoint,
+            resources,
+        }
+    }
+
+    /// Forward the endpoints of an orchestrated service type, such as scabbard, which are served
+    /// at `/{service_type}/{circuit}/{service_id}/...` by the Splinter daemon.
+    #[cfg(feature = "service")]
+    pub fn with_service_endpoints(mut self, provider: &dyn ServiceEndpointProvider) -> Self {
+        for endpoint in provider.endpoints() {
+            let route = format!(
+                "/{}/{{circuit}}/{{service_id}}{}",
+                endpoint.service_type, endpoint.route
+            );
+            self.resources.push(make_forward_resource(
+                &self.core_endpoint,
+                &route,
+                vec![forwarded(
+                    endpoint.method,
+                    #[cfg(feature = "authorization")]
+                    endpoint.permission,
+                )],
+            ));
+        }
+        self
+    }
+}
+
+impl RestResourceProvider for CoreForwardResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+/// The routes that can only be served by the Splinter daemon itself.
+fn core_routes() -> Vec<(&'static str, Vec<ForwardedMethod>)> {
+    #[allow(unused_mut)]
+    let mut status_methods = vec![forwarded(
+        Method::Get,
+        #[cfg(feature = "authorization")]
+        STATUS_READ_PERMISSION,
+    )];
+    #[cfg(feature = "node-metadata")]
+    status_methods.push(forwarded(
+        Method::Patch,
+        #[cfg(feature = "authorization")]
+        STATUS_WRITE_PERMISSION,
+    ));
+
+    #[allow(unused_mut)]
+    let mut routes = vec![("/status", status_methods)];
+
+    #[cfg(feature = "admin-service")]
+    routes.push((
+        "/admin/submit",
+        vec![forwarded(
+            Method::Post,
+            #[cfg(feature = "authorization")]
+            CIRCUIT_WRITE_PERMISSION,
+        )],
+    ));
+
+    #[cfg(feature = "circuit-purge-preview")]
+    routes.push((
+        "/admin/circuits/{circuit_id}/purge",
+        vec![forwarded(
+            Method::Get,
+            #[cfg(feature = "authorization")]
+            CIRCUIT_READ_PERMISSION,
+        )],
+    ));
+
+    #[cfg(feature = "registry")]
+    {
+        routes.push((
+            "/registry/nodes",
+            vec![
+                forwarded(
+                    Method::Get,
+                    #[cfg(feature = "authorization")]
+                    REGISTRY_READ_PERMISSION,
+                ),
+                forwarded(
+                    Method::Post,
+                    #[cfg(feature = "authorization")]
+                    REGISTRY_WRITE_PERMISSION,
+                ),
+            ],
+        ));
+        routes.push((
+            "/registry/nodes/{identity}",
+            vec![
+                forwarded(
+                    Method::Get,
+                    #[cfg(feature = "authorization")]
+                    REGISTRY_READ_PERMISSION,
+                ),
+                forwarded(
+                    Method::Put,
+                    #[cfg(feature = "authorization")]
+                    REGISTRY_WRITE_PERMISSION,
+                ),
+                forwarded(
+                    Method::Delete,
+                    #[cfg(feature = "authorization")]
+                    REGISTRY_WRITE_PERMISSION,
+                ),
+            ],
+        ));
+    }
+
+    #[cfg(feature = "registry-refresh")]
+    routes.push((
+        "/registry/refresh",
+        vec![forwarded(
+            Method::Post,
+            #[cfg(feature = "authorization")]
+            REGISTRY_WRITE_PERMISSION,
+        )],
+    ));
+
+    #[cfg(feature = "metrics-snapshot")]
+    routes.push((
+        "/metrics",
+        vec![forwarded(
+            Method::Get,
+            #[cfg(feature = "authorization")]
+            METRICS_READ_PERMISSION,
+        )],
+    ));
+
+    #[cfg(feature = "peer-disconnect")]
+    routes.push((
+        "/network/peers/{peer_id}/disconnect",
+        vec![forwarded(
+            Method::Post,
+            #[cfg(feature = "authorization")]
+            NETWORK_PEERS_WRITE_PERMISSION,
+        )],
+    ));
+
+    #[cfg(feature = "peer-endpoint-pin")]
+    routes.push((
+        "/network/peers/{peer_id}/endpoint",
+        vec![
+            forwarded(
+                Method::Get,
+                #[cfg(feature = "authorization")]
+                NETWORK_PEERS_READ_PERMISSION,
+            ),
+            forwarded(
+                Method::Put,
+                #[cfg(feature = "authorization")]
+                NETWORK_PEERS_WRITE_PERMISSION,
+            ),
+            forwarded(
+                Method::Delete,
+                #[cfg(feature = "authorization")]
+                NETWORK_PEERS_WRITE_PERMISSION,
+            ),
+        ],
+    ));
+
+    #[cfg(feature = "peer-ref-diagnostics")]
+    routes.push((
+        "/network/debug/peer_refs",
+        vec![
+            forwarded(
+                Method::Get,
+                #[cfg(feature = "authorization")]
+                NETWORK_PEERS_READ_PERMISSION,
+            ),
+            forwarded(
+                Method::Put,
+                #[cfg(feature = "authorization")]
+                NETWORK_PEERS_WRITE_PERMISSION,
+            ),
+        ],
+    ));
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    routes.push((
+        "/network/throttle",
+        vec![
+            forwarded(
+                Method::Get,
+                #[cfg(feature = "authorization")]
+                NETWORK_THROTTLE_READ_PERMISSION,
+            ),
+            forwarded(
+                Method::Put,
+                #[cfg(feature = "authorization")]
+                NETWORK_THROTTLE_WRITE_PERMISSION,
+            ),
+        ],
+    ));
+
+    routes
+}
+
+fn make_forward_resource(
+    core_endpoint: &str,
+    route: &str,
+    methods: Vec<ForwardedMethod>,
+) -> Resource {
+    methods
+        .into_iter()
+        .fold(Resource::build(route), |resource, forwarded_method| {
+            let core_endpoint = core_endpoint.to_string();
+            resource.add_method(
+                forwarded_method.method,
+                #[cfg(feature = "authorization")]
+                forwarded_method.permission,
+                move |request, payload| proxy::forward_request(&core_endpoint, request, payload),
+            )
+        })
+}
+
+#[cfg(all(test, feature = "authorization"))]
+mod tests {
+    use super::*;
+
+    use actix_web::HttpResponse;
+    use futures::IntoFuture;
+    use reqwest::{blocking::Client, StatusCode, Url};
+    use serde_json::Value as JsonValue;
+
+    use splinter::error::InternalError;
+    use splinter::rest_api::actix_web_1::AuthConfig;
+    use splinter::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a request to a forwarded route is answered by the core REST API, with the
+    /// method, path, query and body of the original request.
+    fn test_forward_to_core() {
+        let core_resource = Resource::build("/status").add_method(
+            Method::Get,
+            Permission::AllowAuthenticated,
+            |request, _| {
+                Box::new(
+                    HttpResponse::Ok()
+                        .json(json!({
+                            "path": request.uri().path(),
+                            "query": request.uri().query(),
+                        }))
+                        .into_future(),
+                )
+            },
+        );
+        let (core_shutdown_handle, core_join_handle, core_bind_url) =
+            run_rest_api_on_open_port(vec![core_resource]);
+
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(
+            CoreForwardResourceProvider::new(&format!("http://{}/", core_bind_url)).resources(),
+        );
+
+        let url = Url::parse(&format!("http://{}/status?format=json", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+        assert_eq!(body, json!({"path": "/status", "query": "format=json"}));
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+        core_shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown core rest api");
+        core_join_handle
+            .join()
+            .expect("Unable to join core rest api thread");
+    }
+
+    #[test]
+    /// Tests that a `502 Bad Gateway` is returned when the core REST API cannot be reached.
+    fn test_forward_core_unavailable() {
+        // Reserve a port and release it, so that nothing is listening on it
+        let core_port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("Unable to reserve a port")
+            .port();
+
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(
+            CoreForwardResourceProvider::new(&format!("http://127.0.0.1:{}", core_port))
+                .resources(),
+        );
+
+        let url = Url::parse(&format!("http://{}/status", bind_url)).expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources)
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
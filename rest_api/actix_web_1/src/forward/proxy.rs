@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use actix_web::client::Client;
+use actix_web::http::header;
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::Future;
+
+/// How long to wait for the Splinter daemon to respond to a forwarded request.
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Forwards the request to the same path on the Splinter daemon's REST API at `core_endpoint`,
+/// streaming the request and response bodies through unchanged.
+///
+/// Every request header, including `Authorization`, is passed along, so the Splinter daemon may
+/// still authorize the request itself. A `502 Bad Gateway` is returned if the daemon cannot be
+/// reached.
+pub fn forward_request(
+    core_endpoint: &str,
+    request: HttpRequest,
+    payload: web::Payload,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let mut url = format!("{}{}", core_endpoint, request.uri().path());
+    if let Some(query) = request.uri().query() {
+        url.push('?');
+        url.push_str(query);
+    }
+
+    let mut forwarded = Client::build()
+        .timeout(FORWARD_TIMEOUT)
+        .finish()
+        .request_from(url.as_str(), request.head())
+        .no_decompress();
+    if let Some(addr) = request.head().peer_addr {
+        forwarded = forwarded.header("X-Forwarded-For", addr.ip().to_string());
+    }
+
+    Box::new(forwarded.send_stream(payload).then(move |res| match res {
+        Ok(response) => {
+            let mut builder = HttpResponse::build(response.status());
+            for (name, value) in response
+                .headers()
+                .iter()
+                .filter(|(name, _)| **name != header::CONNECTION)
+            {
+                builder.header(name.clone(), value.clone());
+            }
+            Ok::<_, Error>(builder.streaming(response))
+        }
+        Err(err) => {
+            error!("Unable to forward request to {}: {}", url, err);
+            Ok(HttpResponse::BadGateway().json(json!({
+                "message": "Unable to reach the Splinter daemon"
+            })))
+        }
+    }))
+}
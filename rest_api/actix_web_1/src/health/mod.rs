@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for probing the health of the node.
+//!
+//! The endpoints do not require authorization, so that they can be used by liveness and
+//! readiness probes, such as those of Kubernetes.
+
+mod probes;
+mod resources;
+
+use splinter::health::HealthReader;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+
+/// The `HealthResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /health/live` - Check whether the node's health checks are still running; responds
+///   with `503 Service Unavailable` if they have stopped completing
+/// * `GET /health/ready` - Check whether the node is ready to serve requests; responds with
+///   `503 Service Unavailable` until the health checks have completed, and while any of them fail
+///
+/// Both endpoints respond with the results of the most recent health checks.
+pub struct HealthResourceProvider {
+    reader: HealthReader,
+}
+
+impl HealthResourceProvider {
+    pub fn new(reader: HealthReader) -> Self {
+        Self { reader }
+    }
+}
+
+impl RestResourceProvider for HealthResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            probes::make_live_resource(self.reader.clone()),
+            probes::make_ready_resource(self.reader.clone()),
+        ]
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /health/live` for the liveness of the node
+//! * `GET /health/ready` for the readiness of the node
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::health::HealthReader;
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::HealthResponse;
+
+const HEALTH_MIN: u32 = 2;
+
+pub fn make_live_resource(reader: HealthReader) -> Resource {
+    make_probe_resource("/health/live", reader, HealthReader::is_live)
+}
+
+pub fn make_ready_resource(reader: HealthReader) -> Resource {
+    make_probe_resource("/health/ready", reader, HealthReader::is_ready)
+}
+
+fn make_probe_resource(
+    route: &str,
+    reader: HealthReader,
+    probe: fn(&HealthReader) -> bool,
+) -> Resource {
+    let resource = Resource::build(route).add_request_guard(ProtocolVersionRangeGuard::new(
+        HEALTH_MIN,
+        SPLINTER_PROTOCOL_VERSION,
+    ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Get,
+            Permission::AllowUnauthenticated,
+            move |_, _| get_probe(&reader, probe),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| get_probe(&reader, probe))
+    }
+}
+
+fn get_probe(
+    reader: &HealthReader,
+    probe: fn(&HealthReader) -> bool,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let response = HealthResponse::new(reader);
+    let mut builder = if probe(reader) {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    };
+    Box::new(builder.json(response).into_future())
+}
@@ -0,0 +1,71 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use splinter::health::{CheckResult, HealthReader};
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub live: bool,
+    pub ready: bool,
+    /// The worst status of the most recent checks, or `None` if they have not completed yet
+    pub status: Option<String>,
+    /// When the most recent checks completed, in seconds since the epoch
+    pub completed_at: Option<u64>,
+    pub checks: Vec<CheckResponse>,
+}
+
+impl HealthResponse {
+    pub fn new(reader: &HealthReader) -> Self {
+        let report = reader.report();
+        Self {
+            live: reader.is_live(),
+            ready: reader.is_ready(),
+            status: report.as_ref().map(|report| report.status().to_string()),
+            completed_at: report.as_ref().map(|report| {
+                report
+                    .completed_at()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0)
+            }),
+            checks: report
+                .as_ref()
+                .map(|report| report.checks().iter().map(CheckResponse::from).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckResponse {
+    pub name: String,
+    pub status: String,
+    pub details: String,
+    /// How long the check took, in milliseconds
+    pub duration_ms: u64,
+}
+
+impl From<&CheckResult> for CheckResponse {
+    fn from(result: &CheckResult) -> Self {
+        Self {
+            name: result.name().to_string(),
+            status: result.status().to_string(),
+            details: result.details().to_string(),
+            duration_ms: result.duration().as_millis() as u64,
+        }
+    }
+}
@@ -13,22 +13,66 @@
 // limitations under the License.
 
 #[macro_use]
-#[cfg(any(feature = "admin-service", feature = "service"))]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "biome-notifications",
+    feature = "circuit-access-log",
+    feature = "echo-service",
+    feature = "mesh-bandwidth-throttle",
+    feature = "network-audit",
+    feature = "node-metadata",
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ping",
+    feature = "peer-ref-diagnostics",
+    feature = "registry-refresh",
+    feature = "relay-service",
+    feature = "rest-api-split",
+    feature = "service"
+))]
 extern crate log;
 #[macro_use]
-#[cfg(feature = "admin-service")]
+#[cfg(any(feature = "admin-service", feature = "relay-service"))]
 extern crate serde;
 #[macro_use]
-#[cfg(any(feature = "admin-service", feature = "service"))]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "mesh-bandwidth-throttle",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics",
+    feature = "rest-api-split",
+    feature = "service"
+))]
 extern crate serde_json;
 
 #[cfg(feature = "admin-service")]
 pub mod admin;
 #[cfg(feature = "biome")]
 pub mod biome;
+#[cfg(feature = "circuit-access-log")]
+pub mod circuit_access_log;
+#[cfg(feature = "echo-service")]
+pub mod echo;
+#[cfg(feature = "rest-api-split")]
+pub mod forward;
+#[cfg(feature = "health-service")]
+pub mod health;
+#[cfg(feature = "metrics-snapshot")]
+pub mod metrics;
+#[cfg(any(
+    feature = "mesh-bandwidth-throttle",
+    feature = "network-audit",
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ping",
+    feature = "peer-ref-diagnostics"
+))]
+pub mod network;
 pub mod open_api;
 #[cfg(feature = "registry")]
 pub mod registry;
+#[cfg(feature = "relay-service")]
+pub mod relay;
 #[cfg(feature = "scabbard-service")]
 pub mod scabbard;
 #[cfg(feature = "service")]
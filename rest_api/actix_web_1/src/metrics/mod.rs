@@ -0,0 +1,49 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoint for reading a snapshot of the node's metrics.
+
+mod resources;
+mod snapshot;
+
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::tap::snapshot::MetricsSnapshot;
+
+#[cfg(feature = "authorization")]
+pub(crate) const METRICS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "metrics.read",
+    permission_display_name: "Metrics read",
+    permission_description: "Allows the client to read the node's metrics",
+};
+
+/// The `MetricsResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /metrics` - Get the latest value of every metric the node has recorded
+pub struct MetricsResourceProvider {
+    snapshot: MetricsSnapshot,
+}
+
+impl MetricsResourceProvider {
+    pub fn new(snapshot: MetricsSnapshot) -> Self {
+        Self { snapshot }
+    }
+}
+
+impl RestResourceProvider for MetricsResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![snapshot::make_metrics_resource(self.snapshot.clone())]
+    }
+}
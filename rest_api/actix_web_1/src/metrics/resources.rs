@@ -0,0 +1,72 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use splinter::tap::snapshot::{Metric, MetricValue};
+
+#[derive(Debug, Serialize)]
+pub struct MetricsResponse {
+    pub data: Vec<MetricResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricResponse {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub value: MetricValueResponse,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MetricValueResponse {
+    Counter {
+        value: u64,
+    },
+    Gauge {
+        value: f64,
+    },
+    Histogram {
+        count: u64,
+        sum: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl From<&Metric> for MetricResponse {
+    fn from(metric: &Metric) -> Self {
+        Self {
+            name: metric.name().to_string(),
+            labels: metric.labels().iter().cloned().collect(),
+            value: match metric.value() {
+                MetricValue::Counter(value) => MetricValueResponse::Counter { value: *value },
+                MetricValue::Gauge(value) => MetricValueResponse::Gauge { value: *value },
+                MetricValue::Histogram {
+                    count,
+                    sum,
+                    min,
+                    max,
+                } => MetricValueResponse::Histogram {
+                    count: *count,
+                    sum: *sum,
+                    min: *min,
+                    max: *max,
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /metrics` for fetching the latest value of every metric the node has recorded
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+use splinter::tap::snapshot::MetricsSnapshot;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{MetricResponse, MetricsResponse};
+#[cfg(feature = "authorization")]
+use super::METRICS_READ_PERMISSION;
+
+const METRICS_MIN: u32 = 2;
+
+pub fn make_metrics_resource(snapshot: MetricsSnapshot) -> Resource {
+    let resource = Resource::build("/metrics").add_request_guard(ProtocolVersionRangeGuard::new(
+        METRICS_MIN,
+        SPLINTER_PROTOCOL_VERSION,
+    ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, METRICS_READ_PERMISSION, move |_, _| {
+            get_metrics(&snapshot)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| get_metrics(&snapshot))
+    }
+}
+
+fn get_metrics(snapshot: &MetricsSnapshot) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::Ok()
+            .json(MetricsResponse {
+                data: snapshot
+                    .metrics()
+                    .iter()
+                    .map(MetricResponse::from)
+                    .collect(),
+            })
+            .into_future(),
+    )
+}
@@ -0,0 +1,173 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /network/audit` for listing connection audit records
+
+use std::collections::HashMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::network::audit::{AuditOutcome, AuditRecordFilter, AuditStore, AuditStoreError};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    percent_encode_filter_query, ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{AuditRecordResponse, ListAuditRecordsResponse};
+#[cfg(feature = "authorization")]
+use super::NETWORK_AUDIT_READ_PERMISSION;
+
+const NETWORK_AUDIT_MIN: u32 = 2;
+
+pub fn make_audit_resource(store: Box<dyn AuditStore>) -> Resource {
+    let resource = Resource::build("/network/audit").add_request_guard(
+        ProtocolVersionRangeGuard::new(NETWORK_AUDIT_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, NETWORK_AUDIT_READ_PERMISSION, move |r, _| {
+            list_audit_records(r, web::Data::new(store.clone()))
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| {
+            list_audit_records(r, web::Data::new(store.clone()))
+        })
+    }
+}
+
+fn list_audit_records(
+    req: HttpRequest,
+    store: web::Data<Box<dyn AuditStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(req.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let offset = match parse_usize(&query, "offset") {
+        Ok(value) => value.unwrap_or(DEFAULT_OFFSET),
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    let limit = match parse_usize(&query, "limit") {
+        Ok(value) => value.unwrap_or(DEFAULT_LIMIT),
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    let mut link = format!("{}?", req.uri().path());
+    let mut filter = AuditRecordFilter::default();
+
+    if let Some(peer) = query.get("peer") {
+        link.push_str(&format!("peer={}&", percent_encode_filter_query(peer)));
+        filter = filter.with_peer(peer);
+    }
+
+    for key in &["since", "until"] {
+        let secs = match query.get(*key) {
+            Some(value) => match value.parse::<u64>() {
+                Ok(secs) => secs,
+                Err(err) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid {} value passed: {}. Error: {}",
+                                key, value, err
+                            )))
+                            .into_future(),
+                    )
+                }
+            },
+            None => continue,
+        };
+        link.push_str(&format!("{}={}&", key, secs));
+        let time = UNIX_EPOCH + Duration::from_secs(secs);
+        filter = if *key == "since" {
+            filter.with_since(time)
+        } else {
+            filter.with_until(time)
+        };
+    }
+
+    if let Some(value) = query.get("outcome") {
+        match value.parse::<AuditOutcome>() {
+            Ok(outcome) => {
+                link.push_str(&format!("outcome={}&", outcome));
+                filter = filter.with_outcome(outcome);
+            }
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&err.to_string()))
+                        .into_future(),
+                )
+            }
+        }
+    }
+
+    Box::new(
+        web::block(move || {
+            let records = store.list_records(&filter)?;
+            let total = records.len();
+            let records = records
+                .skip(offset)
+                .take(limit)
+                .map(|record| AuditRecordResponse::from(&record))
+                .collect::<Vec<_>>();
+            Ok::<_, AuditStoreError>((records, total))
+        })
+        .then(
+            move |res: Result<_, BlockingError<AuditStoreError>>| match res {
+                Ok((records, total)) => {
+                    let paging = PagingBuilder::new(link, total)
+                        .with_limit(limit)
+                        .with_offset(offset)
+                        .build();
+                    Ok(HttpResponse::Ok().json(ListAuditRecordsResponse {
+                        data: records,
+                        paging,
+                    }))
+                }
+                Err(err) => {
+                    error!("Unable to list audit records: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
+
+fn parse_usize(query: &HashMap<String, String>, key: &str) -> Result<Option<usize>, HttpResponse> {
+    match query.get(key) {
+        Some(value) => value.parse::<usize>().map(Some).map_err(|err| {
+            HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                "Invalid {} value passed: {}. Error: {}",
+                key, value, err
+            )))
+        }),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for inspecting and managing the network.
+
+#[cfg(feature = "network-audit")]
+mod audit;
+#[cfg(feature = "peer-disconnect")]
+mod peers;
+#[cfg(feature = "peer-endpoint-pin")]
+mod pin;
+#[cfg(feature = "peer-ping")]
+mod ping;
+#[cfg(feature = "peer-ref-diagnostics")]
+mod ref_counts;
+mod resources;
+#[cfg(feature = "mesh-bandwidth-throttle")]
+mod throttle;
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+use splinter::mesh::BandwidthThrottle;
+#[cfg(feature = "network-audit")]
+use splinter::network::audit::AuditStore;
+#[cfg(feature = "peer-ping")]
+use splinter::network::ping::PeerPinger;
+#[cfg(any(
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics"
+))]
+use splinter::peer::PeerManagerConnector;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(all(feature = "authorization", feature = "network-audit"))]
+const NETWORK_AUDIT_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "network.audit.read",
+    permission_display_name: "Network audit log read",
+    permission_description: "Allows the client to read the peer connection audit log",
+};
+
+#[cfg(all(
+    feature = "authorization",
+    any(
+        feature = "peer-disconnect",
+        feature = "peer-endpoint-pin",
+        feature = "peer-ref-diagnostics"
+    )
+))]
+pub(crate) const NETWORK_PEERS_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "network.peers.write",
+    permission_display_name: "Network peers write",
+    permission_description: "Allows the client to disconnect, ban and pin the endpoints of peers",
+};
+
+#[cfg(all(
+    feature = "authorization",
+    any(
+        feature = "peer-endpoint-pin",
+        feature = "peer-ping",
+        feature = "peer-ref-diagnostics"
+    )
+))]
+pub(crate) const NETWORK_PEERS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "network.peers.read",
+    permission_display_name: "Network peers read",
+    permission_description: "Allows the client to ping peers and view their pinned endpoints",
+};
+
+#[cfg(all(feature = "authorization", feature = "mesh-bandwidth-throttle"))]
+pub(crate) const NETWORK_THROTTLE_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "network.throttle.read",
+    permission_display_name: "Network throttle read",
+    permission_description: "Allows the client to view outbound bandwidth limits and statistics",
+};
+#[cfg(all(feature = "authorization", feature = "mesh-bandwidth-throttle"))]
+pub(crate) const NETWORK_THROTTLE_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "network.throttle.write",
+    permission_display_name: "Network throttle write",
+    permission_description: "Allows the client to change outbound bandwidth limits",
+};
+
+/// The `NetworkAuditResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /network/audit` - List connection audit records, optionally filtered by `peer`,
+///   `since`, `until` and `outcome`
+#[cfg(feature = "network-audit")]
+pub struct NetworkAuditResourceProvider {
+    store: Box<dyn AuditStore>,
+}
+
+#[cfg(feature = "network-audit")]
+impl NetworkAuditResourceProvider {
+    pub fn new(store: Box<dyn AuditStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[cfg(feature = "network-audit")]
+impl RestResourceProvider for NetworkAuditResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![audit::make_audit_resource(self.store.clone())]
+    }
+}
+
+/// The `NetworkPeersResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `POST /network/peers/{peer_id}/disconnect` - Close all connections to a peer, optionally
+///   banning it from reconnecting for `ban_secs` seconds
+/// * `GET /network/peers/{peer_id}/endpoint` - Fetch the endpoint a peer is pinned to
+/// * `PUT /network/peers/{peer_id}/endpoint` - Pin a peer to an endpoint, reconnecting it if it
+///   is connected over a different one
+/// * `DELETE /network/peers/{peer_id}/endpoint` - Remove a peer's pinned endpoint
+/// * `GET /network/debug/peer_refs` - Fetch the reference count settings and the reference count
+///   of each peer
+/// * `PUT /network/debug/peer_refs` - Enable or disable strict reference counts and reference
+///   count diagnostics
+#[cfg(any(
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics"
+))]
+pub struct NetworkPeersResourceProvider {
+    connector: PeerManagerConnector,
+}
+
+#[cfg(any(
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics"
+))]
+impl NetworkPeersResourceProvider {
+    pub fn new(connector: PeerManagerConnector) -> Self {
+        Self { connector }
+    }
+}
+
+#[cfg(any(
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics"
+))]
+impl RestResourceProvider for NetworkPeersResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        #[allow(clippy::vec_init_then_push)]
+        let mut resources = Vec::new();
+        #[cfg(feature = "peer-disconnect")]
+        resources.push(peers::make_disconnect_resource(self.connector.clone()));
+        #[cfg(feature = "peer-endpoint-pin")]
+        resources.push(pin::make_pin_resource(self.connector.clone()));
+        #[cfg(feature = "peer-ref-diagnostics")]
+        resources.push(ref_counts::make_ref_counts_resource(self.connector.clone()));
+        resources
+    }
+}
+
+/// The `NetworkPingResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `POST /network/peers/{peer_id}/ping` - Ping a connected peer and return the round-trip time,
+///   waiting up to `timeout` seconds for the reply
+#[cfg(feature = "peer-ping")]
+pub struct NetworkPingResourceProvider {
+    pinger: PeerPinger,
+}
+
+#[cfg(feature = "peer-ping")]
+impl NetworkPingResourceProvider {
+    pub fn new(pinger: PeerPinger) -> Self {
+        Self { pinger }
+    }
+}
+
+#[cfg(feature = "peer-ping")]
+impl RestResourceProvider for NetworkPingResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![ping::make_ping_resource(self.pinger.clone())]
+    }
+}
+
+/// The `NetworkThrottleResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /network/throttle` - Fetch the outbound bandwidth limits and throttling statistics
+/// * `PUT /network/throttle` - Replace the outbound bandwidth limits
+#[cfg(feature = "mesh-bandwidth-throttle")]
+pub struct NetworkThrottleResourceProvider {
+    throttle: BandwidthThrottle,
+}
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+impl NetworkThrottleResourceProvider {
+    pub fn new(throttle: BandwidthThrottle) -> Self {
+        Self { throttle }
+    }
+}
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+impl RestResourceProvider for NetworkThrottleResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![throttle::make_throttle_resource(self.throttle.clone())]
+    }
+}
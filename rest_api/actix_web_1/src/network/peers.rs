@@ -0,0 +1,137 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `POST /network/peers/{peer_id}/disconnect` for forcibly disconnecting a peer
+
+use std::time::Duration;
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+use splinter::peer::PeerManagerConnector;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{PeerDisconnectRequest, PeerDisconnectResponse};
+#[cfg(feature = "authorization")]
+use super::NETWORK_PEERS_WRITE_PERMISSION;
+
+const NETWORK_PEERS_MIN: u32 = 2;
+
+pub fn make_disconnect_resource(connector: PeerManagerConnector) -> Resource {
+    let resource = Resource::build("/network/peers/{peer_id}/disconnect").add_request_guard(
+        ProtocolVersionRangeGuard::new(NETWORK_PEERS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, NETWORK_PEERS_WRITE_PERMISSION, move |r, p| {
+            disconnect_peer(r, p, connector.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |r, p| {
+            disconnect_peer(r, p, connector.clone())
+        })
+    }
+}
+
+fn disconnect_peer(
+    req: HttpRequest,
+    payload: web::Payload,
+    connector: PeerManagerConnector,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = match req.match_info().get("peer_id") {
+        Some(peer_id) => peer_id.to_string(),
+        None => {
+            error!("Peer ID should have been validated by actix");
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    };
+
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                // An empty body disconnects the peer without banning it
+                let request = if body.is_empty() {
+                    PeerDisconnectRequest::default()
+                } else {
+                    match serde_json::from_slice::<PeerDisconnectRequest>(&body) {
+                        Ok(request) => request,
+                        Err(err) => {
+                            return Box::new(
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&format!(
+                                        "Invalid disconnect request: {}",
+                                        err
+                                    )))
+                                    .into_future(),
+                            )
+                                as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                        }
+                    }
+                };
+
+                let ban = request.ban_secs.map(Duration::from_secs);
+                let response_peer_id = peer_id.clone();
+                Box::new(
+                    web::block(move || connector.disconnect_peer(&peer_id, ban)).then(move |res| {
+                        Ok(match res {
+                            Ok(0) if ban.is_none() => {
+                                HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+                                    "Peer {} is not connected",
+                                    response_peer_id
+                                )))
+                            }
+                            Ok(connections_closed) => {
+                                info!(
+                                    "Disconnected peer {} ({} connection(s) closed)",
+                                    response_peer_id, connections_closed
+                                );
+                                HttpResponse::Ok().json(PeerDisconnectResponse {
+                                    peer_id: response_peer_id,
+                                    connections_closed,
+                                    ban_secs: request.ban_secs,
+                                })
+                            }
+                            Err(BlockingError::Error(err)) => {
+                                error!("Unable to disconnect peer: {}", err);
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                            }
+                            Err(BlockingError::Canceled) => {
+                                error!("Peer disconnect was canceled");
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                            }
+                        })
+                    }),
+                )
+            }),
+    )
+}
@@ -0,0 +1,235 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /network/peers/{peer_id}/endpoint` for fetching the endpoint a peer is pinned to
+//! * `PUT /network/peers/{peer_id}/endpoint` for pinning a peer to an endpoint
+//! * `DELETE /network/peers/{peer_id}/endpoint` for unpinning a peer
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+use splinter::peer::PeerManagerConnector;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{PeerEndpointPinRequest, PeerEndpointPinResponse};
+#[cfg(feature = "authorization")]
+use super::{NETWORK_PEERS_READ_PERMISSION, NETWORK_PEERS_WRITE_PERMISSION};
+
+const NETWORK_PEERS_MIN: u32 = 2;
+
+pub fn make_pin_resource(connector: PeerManagerConnector) -> Resource {
+    let connector1 = connector.clone();
+    let connector2 = connector.clone();
+    let resource = Resource::build("/network/peers/{peer_id}/endpoint").add_request_guard(
+        ProtocolVersionRangeGuard::new(NETWORK_PEERS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, NETWORK_PEERS_READ_PERMISSION, move |r, _| {
+                get_pinned_endpoint(r, connector.clone())
+            })
+            .add_method(Method::Put, NETWORK_PEERS_WRITE_PERMISSION, move |r, p| {
+                pin_endpoint(r, p, connector1.clone())
+            })
+            .add_method(
+                Method::Delete,
+                NETWORK_PEERS_WRITE_PERMISSION,
+                move |r, _| unpin_endpoint(r, connector2.clone()),
+            )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |r, _| {
+                get_pinned_endpoint(r, connector.clone())
+            })
+            .add_method(Method::Put, move |r, p| {
+                pin_endpoint(r, p, connector1.clone())
+            })
+            .add_method(Method::Delete, move |r, _| {
+                unpin_endpoint(r, connector2.clone())
+            })
+    }
+}
+
+fn get_peer_id(req: &HttpRequest) -> Result<String, HttpResponse> {
+    req.match_info()
+        .get("peer_id")
+        .map(String::from)
+        .ok_or_else(|| {
+            error!("Peer ID should have been validated by actix");
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })
+}
+
+fn get_pinned_endpoint(
+    req: HttpRequest,
+    connector: PeerManagerConnector,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = match get_peer_id(&req) {
+        Ok(peer_id) => peer_id,
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    let response_peer_id = peer_id.clone();
+    Box::new(
+        web::block(move || connector.pinned_endpoint(&peer_id)).then(move |res| {
+            Ok(match res {
+                Ok(Some(endpoint)) => HttpResponse::Ok().json(PeerEndpointPinResponse {
+                    peer_id: response_peer_id,
+                    endpoint,
+                }),
+                Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+                    "Peer {} is not pinned to an endpoint",
+                    response_peer_id
+                ))),
+                Err(BlockingError::Error(err)) => {
+                    error!("Unable to get pinned endpoint: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+                Err(BlockingError::Canceled) => {
+                    error!("Getting pinned endpoint was canceled");
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn pin_endpoint(
+    req: HttpRequest,
+    payload: web::Payload,
+    connector: PeerManagerConnector,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = match get_peer_id(&req) {
+        Ok(peer_id) => peer_id,
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let request = match serde_json::from_slice::<PeerEndpointPinRequest>(&body) {
+                    Ok(request) => request,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid pin request: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                    }
+                };
+
+                if !is_valid_endpoint(&request.endpoint) {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid endpoint '{}': must be of the form <protocol>://<address>",
+                                request.endpoint
+                            )))
+                            .into_future(),
+                    );
+                }
+
+                let response_peer_id = peer_id.clone();
+                let endpoint = request.endpoint.clone();
+                Box::new(
+                    web::block(move || connector.pin_endpoint(&peer_id, &request.endpoint)).then(
+                        move |res| {
+                            Ok(match res {
+                                Ok(()) => {
+                                    info!(
+                                        "Pinned peer {} to endpoint {}",
+                                        response_peer_id, endpoint
+                                    );
+                                    HttpResponse::Ok().json(PeerEndpointPinResponse {
+                                        peer_id: response_peer_id,
+                                        endpoint,
+                                    })
+                                }
+                                Err(BlockingError::Error(err)) => {
+                                    error!("Unable to pin endpoint: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                                Err(BlockingError::Canceled) => {
+                                    error!("Pinning endpoint was canceled");
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        },
+                    ),
+                )
+            }),
+    )
+}
+
+fn unpin_endpoint(
+    req: HttpRequest,
+    connector: PeerManagerConnector,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = match get_peer_id(&req) {
+        Ok(peer_id) => peer_id,
+        Err(response) => return Box::new(response.into_future()),
+    };
+
+    let response_peer_id = peer_id.clone();
+    Box::new(
+        web::block(move || connector.unpin_endpoint(&peer_id)).then(move |res| {
+            Ok(match res {
+                Ok(Some(endpoint)) => HttpResponse::Ok().json(PeerEndpointPinResponse {
+                    peer_id: response_peer_id,
+                    endpoint,
+                }),
+                Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+                    "Peer {} is not pinned to an endpoint",
+                    response_peer_id
+                ))),
+                Err(BlockingError::Error(err)) => {
+                    error!("Unable to unpin endpoint: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+                Err(BlockingError::Canceled) => {
+                    error!("Unpinning endpoint was canceled");
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+/// Checks that the endpoint is of the form `<protocol>://<address>`
+fn is_valid_endpoint(endpoint: &str) -> bool {
+    match endpoint.split_once("://") {
+        Some((protocol, address)) => !protocol.is_empty() && !address.is_empty(),
+        None => false,
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the following endpoints:
+//!
+//! * `POST /network/peers/{peer_id}/ping` for measuring the round-trip time to a peer
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::network::ping::{PeerPingError, PeerPinger};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::PeerPingResponse;
+#[cfg(feature = "authorization")]
+use super::NETWORK_PEERS_READ_PERMISSION;
+
+const NETWORK_PEERS_MIN: u32 = 2;
+
+const DEFAULT_PING_TIMEOUT_SECS: u64 = 5;
+const MAX_PING_TIMEOUT_SECS: u64 = 60;
+
+pub fn make_ping_resource(pinger: PeerPinger) -> Resource {
+    let resource = Resource::build("/network/peers/{peer_id}/ping").add_request_guard(
+        ProtocolVersionRangeGuard::new(NETWORK_PEERS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, NETWORK_PEERS_READ_PERMISSION, move |r, _| {
+            ping_peer(r, pinger.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |r, _| ping_peer(r, pinger.clone()))
+    }
+}
+
+fn ping_peer(
+    req: HttpRequest,
+    pinger: PeerPinger,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = match req.match_info().get("peer_id") {
+        Some(peer_id) => peer_id.to_string(),
+        None => {
+            error!("Peer ID should have been validated by actix");
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    };
+
+    let query: web::Query<HashMap<String, String>> =
+        match web::Query::from_query(req.query_string()) {
+            Ok(query) => query,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid query"))
+                        .into_future(),
+                )
+            }
+        };
+
+    let timeout_secs = match query.get("timeout") {
+        Some(timeout) => match timeout.parse::<u64>() {
+            Ok(timeout) if timeout > 0 && timeout <= MAX_PING_TIMEOUT_SECS => timeout,
+            _ => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid timeout '{}': must be between 1 and {} seconds",
+                            timeout, MAX_PING_TIMEOUT_SECS
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => DEFAULT_PING_TIMEOUT_SECS,
+    };
+
+    let response_peer_id = peer_id.clone();
+    Box::new(
+        web::block(move || pinger.ping(&peer_id, Duration::from_secs(timeout_secs))).then(
+            move |res| {
+                Ok(match res {
+                    Ok(rtt) => HttpResponse::Ok().json(PeerPingResponse {
+                        peer_id: response_peer_id,
+                        rtt_ms: rtt.as_secs_f64() * 1000.0,
+                    }),
+                    Err(BlockingError::Error(PeerPingError::NotConnected(_))) => {
+                        HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+                            "Peer {} is not connected",
+                            response_peer_id
+                        )))
+                    }
+                    Err(BlockingError::Error(PeerPingError::Timeout(_))) => {
+                        HttpResponse::RequestTimeout().json(ErrorResponse::request_timeout(
+                            &format!(
+                                "Peer {} did not reply within {} seconds",
+                                response_peer_id, timeout_secs
+                            ),
+                        ))
+                    }
+                    Err(BlockingError::Error(err)) => {
+                        error!("Unable to ping peer: {}", err);
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                    Err(BlockingError::Canceled) => {
+                        error!("Peer ping was canceled");
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                })
+            },
+        ),
+    )
+}
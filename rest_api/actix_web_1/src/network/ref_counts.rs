@@ -0,0 +1,121 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /network/debug/peer_refs` for fetching the peer manager's reference count settings
+//!   and the reference count of each peer
+//! * `PUT /network/debug/peer_refs` for changing the reference count settings
+
+use actix_web::{error::BlockingError, web, Error, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+use splinter::peer::{PeerManagerConnector, RefCountReport};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{PeerRefCountsRequest, PeerRefCountsResponse};
+#[cfg(feature = "authorization")]
+use super::{NETWORK_PEERS_READ_PERMISSION, NETWORK_PEERS_WRITE_PERMISSION};
+
+const NETWORK_PEERS_MIN: u32 = 2;
+
+pub fn make_ref_counts_resource(connector: PeerManagerConnector) -> Resource {
+    let connector1 = connector.clone();
+    let resource = Resource::build("/network/debug/peer_refs").add_request_guard(
+        ProtocolVersionRangeGuard::new(NETWORK_PEERS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, NETWORK_PEERS_READ_PERMISSION, move |_, _| {
+                get_ref_counts(connector.clone())
+            })
+            .add_method(Method::Put, NETWORK_PEERS_WRITE_PERMISSION, move |_, p| {
+                set_ref_count_mode(p, connector1.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| get_ref_counts(connector.clone()))
+            .add_method(Method::Put, move |_, p| {
+                set_ref_count_mode(p, connector1.clone())
+            })
+    }
+}
+
+fn get_ref_counts(
+    connector: PeerManagerConnector,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || connector.ref_counts().map_err(|err| err.to_string())).then(to_response),
+    )
+}
+
+fn set_ref_count_mode(
+    payload: web::Payload,
+    connector: PeerManagerConnector,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let request = match serde_json::from_slice::<PeerRefCountsRequest>(&body) {
+                    Ok(request) => request,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid reference count settings: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                    }
+                };
+
+                Box::new(
+                    web::block(move || {
+                        connector
+                            .set_ref_count_mode(request.strict, request.diagnostics)
+                            .map_err(|err| err.to_string())
+                    })
+                    .then(to_response),
+                )
+            }),
+    )
+}
+
+fn to_response(res: Result<RefCountReport, BlockingError<String>>) -> Result<HttpResponse, Error> {
+    Ok(match res {
+        Ok(report) => HttpResponse::Ok().json(PeerRefCountsResponse::from(report)),
+        Err(BlockingError::Error(err)) => {
+            error!("Unable to get peer reference counts: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+        Err(BlockingError::Canceled) => {
+            error!("Getting peer reference counts was canceled");
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    })
+}
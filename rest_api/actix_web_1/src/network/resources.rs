@@ -0,0 +1,197 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "network-audit")]
+use std::time::UNIX_EPOCH;
+
+#[cfg(any(
+    feature = "mesh-bandwidth-throttle",
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics"
+))]
+use serde::Deserialize;
+use serde::Serialize;
+#[cfg(feature = "mesh-bandwidth-throttle")]
+use splinter::mesh::{BandwidthLimits, ThrottleStats};
+#[cfg(feature = "network-audit")]
+use splinter::network::audit::AuditRecord;
+#[cfg(feature = "peer-ref-diagnostics")]
+use splinter::peer::RefCountReport;
+#[cfg(feature = "network-audit")]
+use splinter::rest_api::paging::Paging;
+
+#[cfg(feature = "network-audit")]
+#[derive(Debug, Serialize)]
+pub struct ListAuditRecordsResponse {
+    pub data: Vec<AuditRecordResponse>,
+    pub paging: Paging,
+}
+
+#[cfg(feature = "network-audit")]
+#[derive(Debug, Serialize)]
+pub struct AuditRecordResponse {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub endpoint: String,
+    pub connection_id: String,
+    pub identity: Option<String>,
+    pub outcome: String,
+    pub reason: Option<String>,
+}
+
+#[cfg(feature = "network-audit")]
+impl From<&AuditRecord> for AuditRecordResponse {
+    fn from(record: &AuditRecord) -> Self {
+        Self {
+            timestamp: record
+                .timestamp()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            endpoint: record.endpoint().to_string(),
+            connection_id: record.connection_id().to_string(),
+            identity: record.identity().map(String::from),
+            outcome: record.outcome().to_string(),
+            reason: record.reason().map(String::from),
+        }
+    }
+}
+
+#[cfg(feature = "peer-disconnect")]
+#[derive(Debug, Default, Deserialize)]
+pub struct PeerDisconnectRequest {
+    /// How long to prevent the peer from reconnecting; `None` allows it to reconnect immediately
+    #[serde(default)]
+    pub ban_secs: Option<u64>,
+}
+
+#[cfg(feature = "peer-disconnect")]
+#[derive(Debug, Serialize)]
+pub struct PeerDisconnectResponse {
+    pub peer_id: String,
+    pub connections_closed: usize,
+    pub ban_secs: Option<u64>,
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+#[derive(Debug, Deserialize)]
+pub struct PeerEndpointPinRequest {
+    /// The endpoint all outbound connections to the peer should use
+    pub endpoint: String,
+}
+
+#[cfg(feature = "peer-endpoint-pin")]
+#[derive(Debug, Serialize)]
+pub struct PeerEndpointPinResponse {
+    pub peer_id: String,
+    pub endpoint: String,
+}
+
+#[cfg(feature = "peer-ping")]
+#[derive(Debug, Serialize)]
+pub struct PeerPingResponse {
+    pub peer_id: String,
+    /// The round-trip time, in milliseconds
+    pub rtt_ms: f64,
+}
+
+#[cfg(feature = "peer-ref-diagnostics")]
+#[derive(Debug, Deserialize)]
+pub struct PeerRefCountsRequest {
+    /// Whether removing a reference that does not exist panics; `None` leaves it unchanged
+    #[serde(default)]
+    pub strict: Option<bool>,
+    /// Whether the call stacks that remove references are logged; `None` leaves it unchanged
+    #[serde(default)]
+    pub diagnostics: Option<bool>,
+}
+
+#[cfg(feature = "peer-ref-diagnostics")]
+#[derive(Debug, Serialize)]
+pub struct PeerRefCountsResponse {
+    pub strict: bool,
+    pub diagnostics: bool,
+    pub peers: Vec<PeerRefCountResponse>,
+}
+
+#[cfg(feature = "peer-ref-diagnostics")]
+#[derive(Debug, Serialize)]
+pub struct PeerRefCountResponse {
+    pub peer_id: String,
+    pub local_id: String,
+    pub ref_count: u64,
+}
+
+#[cfg(feature = "peer-ref-diagnostics")]
+impl From<RefCountReport> for PeerRefCountsResponse {
+    fn from(report: RefCountReport) -> Self {
+        Self {
+            strict: report.strict,
+            diagnostics: report.diagnostics,
+            peers: report
+                .peers
+                .into_iter()
+                .map(|peer| PeerRefCountResponse {
+                    peer_id: peer.peer_id.peer_id().id_as_string(),
+                    local_id: peer.peer_id.local_id().id_as_string(),
+                    ref_count: peer.ref_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+#[derive(Debug, Deserialize)]
+pub struct ThrottleLimitsRequest {
+    /// Bytes per second allowed across all connections; `None` removes the limit
+    pub global: Option<u64>,
+    /// Bytes per second allowed on each connection; `None` removes the limit
+    pub per_connection: Option<u64>,
+}
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+#[derive(Debug, Serialize)]
+pub struct ThrottleResponse {
+    pub limits: ThrottleLimitsResponse,
+    pub throttled_bytes: u64,
+    pub throttled_messages: u64,
+    pub throttled_bytes_by_connection: std::collections::BTreeMap<String, u64>,
+}
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+#[derive(Debug, Serialize)]
+pub struct ThrottleLimitsResponse {
+    pub global: Option<u64>,
+    pub per_connection: Option<u64>,
+}
+
+#[cfg(feature = "mesh-bandwidth-throttle")]
+impl ThrottleResponse {
+    pub fn new(limits: BandwidthLimits, stats: ThrottleStats) -> Self {
+        Self {
+            limits: ThrottleLimitsResponse {
+                global: limits.global,
+                per_connection: limits.per_connection,
+            },
+            throttled_bytes: stats.throttled_bytes,
+            throttled_messages: stats.throttled_messages,
+            throttled_bytes_by_connection: stats
+                .throttled_bytes_by_connection
+                .into_iter()
+                .collect(),
+        }
+    }
+}
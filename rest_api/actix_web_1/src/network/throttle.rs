@@ -0,0 +1,109 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the following endpoints:
+//!
+//! * `GET /network/throttle` for fetching the outbound bandwidth limits and throttling statistics
+//! * `PUT /network/throttle` for changing the outbound bandwidth limits
+
+use actix_web::{web, Error, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+use splinter::mesh::{BandwidthLimits, BandwidthThrottle};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{ThrottleLimitsRequest, ThrottleResponse};
+#[cfg(feature = "authorization")]
+use super::{NETWORK_THROTTLE_READ_PERMISSION, NETWORK_THROTTLE_WRITE_PERMISSION};
+
+const NETWORK_THROTTLE_MIN: u32 = 2;
+
+pub fn make_throttle_resource(throttle: BandwidthThrottle) -> Resource {
+    let throttle1 = throttle.clone();
+    let resource = Resource::build("/network/throttle").add_request_guard(
+        ProtocolVersionRangeGuard::new(NETWORK_THROTTLE_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(
+                Method::Get,
+                NETWORK_THROTTLE_READ_PERMISSION,
+                move |_, _| get_throttle(&throttle),
+            )
+            .add_method(
+                Method::Put,
+                NETWORK_THROTTLE_WRITE_PERMISSION,
+                move |_, p| set_limits(p, throttle1.clone()),
+            )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| get_throttle(&throttle))
+            .add_method(Method::Put, move |_, p| set_limits(p, throttle1.clone()))
+    }
+}
+
+fn get_throttle(
+    throttle: &BandwidthThrottle,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::Ok()
+            .json(ThrottleResponse::new(throttle.limits(), throttle.stats()))
+            .into_future(),
+    )
+}
+
+fn set_limits(
+    payload: web::Payload,
+    throttle: BandwidthThrottle,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(
+                move |body| match serde_json::from_slice::<ThrottleLimitsRequest>(&body) {
+                    Ok(request) => {
+                        if request.global == Some(0) || request.per_connection == Some(0) {
+                            return Ok(HttpResponse::BadRequest().json(
+                                ErrorResponse::bad_request(
+                                    "Bandwidth limits must be greater than 0; \
+                                     use null to remove a limit",
+                                ),
+                            ));
+                        }
+                        let limits = BandwidthLimits {
+                            global: request.global,
+                            per_connection: request.per_connection,
+                        };
+                        info!("Updating outbound bandwidth limits: {:?}", limits);
+                        throttle.set_limits(limits);
+                        Ok(HttpResponse::Ok()
+                            .json(ThrottleResponse::new(throttle.limits(), throttle.stats())))
+                    }
+                    Err(err) => Ok(HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                        &format!("Invalid bandwidth limits: {}", err),
+                    ))),
+                },
+            ),
+    )
+}
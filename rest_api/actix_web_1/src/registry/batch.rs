@@ -0,0 +1,144 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryFrom;
+
+use actix_web::{error::BlockingError, web, Error, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+use splinter::registry::{NodeOperation, RegistryWriter, RwRegistry};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::error::RegistryRestApiError;
+use super::resources::batch::{NodeBatchRequest, NodeBatchResponse};
+#[cfg(feature = "authorization")]
+use super::REGISTRY_WRITE_PERMISSION;
+
+const REGISTRY_NODES_BATCH_MIN: u32 = 1;
+
+pub fn make_nodes_batch_resource(registry: Box<dyn RwRegistry>) -> Resource {
+    let resource = Resource::build("/registry/nodes/batch").add_request_guard(
+        ProtocolVersionRangeGuard::new(REGISTRY_NODES_BATCH_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, REGISTRY_WRITE_PERMISSION, move |_, p| {
+            apply_batch(p, web::Data::new(registry.clone()))
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |_, p| {
+            apply_batch(p, web::Data::new(registry.clone()))
+        })
+    }
+}
+
+fn apply_batch(
+    payload: web::Payload,
+    registry: web::Data<Box<dyn RwRegistry>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let request = match serde_json::from_slice::<NodeBatchRequest>(&body) {
+                    Ok(request) => request,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid batch: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                    }
+                };
+
+                if request.operations.is_empty() {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(
+                                "Invalid batch: no operations provided",
+                            ))
+                            .into_future(),
+                    );
+                }
+
+                let mut operations = Vec::with_capacity(request.operations.len());
+                for (idx, operation) in request.operations.into_iter().enumerate() {
+                    match NodeOperation::try_from(operation) {
+                        Ok(operation) => operations.push(operation),
+                        Err(err) => {
+                            return Box::new(
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&format!(
+                                        "Invalid node in operation {}: {}",
+                                        idx, err
+                                    )))
+                                    .into_future(),
+                            );
+                        }
+                    }
+                }
+
+                Box::new(
+                    web::block(move || {
+                        registry
+                            .apply_batch(operations)
+                            .map_err(RegistryRestApiError::from)
+                    })
+                    .then(|res| {
+                        Ok(match res {
+                            Ok(result) => {
+                                let committed = result.committed();
+                                let response = NodeBatchResponse {
+                                    committed,
+                                    data: result
+                                        .into_results()
+                                        .into_iter()
+                                        .map(From::from)
+                                        .collect(),
+                                };
+                                if committed {
+                                    HttpResponse::Ok().json(response)
+                                } else {
+                                    HttpResponse::BadRequest().json(response)
+                                }
+                            }
+                            Err(BlockingError::Error(RegistryRestApiError::InvalidStateError(
+                                err,
+                            ))) => HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                                &format!("Unable to apply batch: {}", err),
+                            )),
+                            Err(err) => {
+                                error!("Unable to apply registry batch: {}", err);
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                            }
+                        })
+                    }),
+                )
+            }),
+    )
+}
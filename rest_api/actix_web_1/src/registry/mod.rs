@@ -14,25 +14,35 @@
 
 //! This module defines the REST API endpoints for interacting with registries.
 
+#[cfg(feature = "registry-batch")]
+mod batch;
 mod error;
 mod nodes;
 mod nodes_identity;
+#[cfg(feature = "registry-refresh")]
+mod refresh;
 mod resources;
+#[cfg(feature = "registry-sources")]
+mod sources;
 
 use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
 #[cfg(feature = "authorization")]
 use splinter::rest_api::auth::authorization::Permission;
 
+#[cfg(feature = "registry-sources")]
+use splinter::registry::sources::RegistrySources;
+#[cfg(feature = "registry-refresh")]
+use splinter::registry::RemoteYamlRefreshHandle;
 use splinter::registry::RwRegistry;
 
 #[cfg(feature = "authorization")]
-const REGISTRY_READ_PERMISSION: Permission = Permission::Check {
+pub(crate) const REGISTRY_READ_PERMISSION: Permission = Permission::Check {
     permission_id: "registry.read",
     permission_display_name: "Registry read",
     permission_description: "Allows the client to read the registry",
 };
 #[cfg(feature = "authorization")]
-const REGISTRY_WRITE_PERMISSION: Permission = Permission::Check {
+pub(crate) const REGISTRY_WRITE_PERMISSION: Permission = Permission::Check {
     permission_id: "registry.write",
     permission_display_name: "Registry write",
     permission_description: "Allows the client to modify the registry",
@@ -45,11 +55,33 @@ pub struct RwRegistryRestResourceProvider {
 impl RwRegistryRestResourceProvider {
     pub fn new(registry: &dyn RwRegistry) -> Self {
         let resources = vec![
+            // The batch endpoint must be registered before `/registry/nodes/{identity}`, which
+            // would otherwise match its path.
+            #[cfg(feature = "registry-batch")]
+            batch::make_nodes_batch_resource(registry.clone_box()),
             nodes_identity::make_nodes_identity_resource(registry.clone_box()),
             nodes::make_nodes_resource(registry.clone_box()),
         ];
         Self { resources }
     }
+
+    /// Add the `POST /registry/refresh` endpoint, which forces an immediate refresh of the remote
+    /// registries represented by the given handles.
+    #[cfg(feature = "registry-refresh")]
+    pub fn with_refresh_handles(mut self, refresh_handles: Vec<RemoteYamlRefreshHandle>) -> Self {
+        self.resources
+            .push(refresh::make_refresh_resource(refresh_handles));
+        self
+    }
+
+    /// Add the `/registry/sources` endpoints, which list, add and remove the remote registries
+    /// managed by the given `RegistrySources`.
+    #[cfg(feature = "registry-sources")]
+    pub fn with_registry_sources(mut self, registry_sources: RegistrySources) -> Self {
+        self.resources
+            .push(sources::make_sources_resource(registry_sources));
+        self
+    }
 }
 
 /// The `RwRegistryRestResourceProvider` struct provides the following endpoints
@@ -60,6 +92,13 @@ impl RwRegistryRestResourceProvider {
 /// * `GET /registry/nodes/{identity}` - Fetch a specific node in the registry
 /// * `PUT /registry/nodes/{identity}` - Replace a node in the registry
 /// * `DELETE /registry/nodes/{identity}` - Delete a node from the registry
+/// * `POST /registry/refresh` - Force an immediate refresh of the remote registries (only if
+///   refresh handles were provided)
+/// * `GET /registry/sources` - List the remote registries and their status (only if registry
+///   sources were provided)
+/// * `POST /registry/sources` - Add a remote registry (only if registry sources were provided)
+/// * `DELETE /registry/sources?url=<url>` - Remove a remote registry that was added at runtime
+///   (only if registry sources were provided)
 impl RestResourceProvider for RwRegistryRestResourceProvider {
     fn resources(&self) -> Vec<Resource> {
         self.resources.clone()
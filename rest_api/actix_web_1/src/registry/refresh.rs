@@ -0,0 +1,104 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the following endpoints:
+//!
+//! * `POST /registry/refresh` for forcing an immediate refresh of the remote registries
+
+use actix_web::{error::BlockingError, web, Error, HttpResponse};
+use futures::Future;
+use splinter::registry::RemoteYamlRefreshHandle;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::refresh::{RefreshRegistriesResponse, RegistryRefreshResult};
+#[cfg(feature = "authorization")]
+use super::REGISTRY_WRITE_PERMISSION;
+
+const REGISTRY_REFRESH_MIN: u32 = 1;
+
+pub fn make_refresh_resource(refresh_handles: Vec<RemoteYamlRefreshHandle>) -> Resource {
+    let resource = Resource::build("/registry/refresh").add_request_guard(
+        ProtocolVersionRangeGuard::new(REGISTRY_REFRESH_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, REGISTRY_WRITE_PERMISSION, move |_, _| {
+            refresh_registries(refresh_handles.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |_, _| {
+            refresh_registries(refresh_handles.clone())
+        })
+    }
+}
+
+fn refresh_registries(
+    refresh_handles: Vec<RemoteYamlRefreshHandle>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || {
+            // Refresh the registries concurrently, so that one slow registry does not delay the
+            // others
+            #[cfg(feature = "registry-parallel-fetch")]
+            let refreshes = RemoteYamlRefreshHandle::refresh_all(&refresh_handles);
+            #[cfg(not(feature = "registry-parallel-fetch"))]
+            let refreshes = refresh_handles
+                .iter()
+                .map(|handle| handle.refresh())
+                .collect::<Vec<_>>();
+
+            let results = refresh_handles
+                .iter()
+                .zip(refreshes)
+                .map(|(handle, refresh)| match refresh {
+                    Ok(node_count) => {
+                        debug!("Refreshed remote registry '{}'", handle.url());
+                        RegistryRefreshResult {
+                            url: handle.url().to_string(),
+                            success: true,
+                            node_count: Some(node_count),
+                            error: None,
+                        }
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Failed to refresh remote registry '{}': {}",
+                            handle.url(),
+                            err
+                        );
+                        RegistryRefreshResult {
+                            url: handle.url().to_string(),
+                            success: false,
+                            node_count: None,
+                            error: Some(err.to_string()),
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            Ok::<_, ()>(results)
+        })
+        .then(|res: Result<_, BlockingError<()>>| match res {
+            Ok(data) => Ok(HttpResponse::Ok().json(RefreshRegistriesResponse { data })),
+            Err(_) => {
+                error!("Unable to refresh remote registries");
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
@@ -0,0 +1,128 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+use splinter::registry::{InvalidNodeError, Node, NodeOperation, NodeOperationResult};
+
+use super::nodes::NewNode;
+
+#[derive(Debug, Deserialize)]
+pub struct NodeBatchRequest {
+    /// The operations to apply, in order; all of them are committed or none are.
+    pub operations: Vec<NodeOperationRequest>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum NodeOperationRequest {
+    Create { node: NewNode },
+    Update { node: NewNode },
+    Delete { identity: String },
+}
+
+impl TryFrom<NodeOperationRequest> for NodeOperation {
+    type Error = InvalidNodeError;
+
+    fn try_from(request: NodeOperationRequest) -> Result<Self, Self::Error> {
+        match request {
+            NodeOperationRequest::Create { node } => {
+                Ok(NodeOperation::Create(Node::try_from(node)?))
+            }
+            NodeOperationRequest::Update { node } => {
+                Ok(NodeOperation::Update(Node::try_from(node)?))
+            }
+            NodeOperationRequest::Delete { identity } => {
+                if identity.is_empty() {
+                    Err(InvalidNodeError::EmptyIdentity)
+                } else {
+                    Ok(NodeOperation::Delete(identity))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeBatchResponse {
+    /// Whether the operations were committed to the registry
+    pub committed: bool,
+    /// The result of each operation, in the order they were submitted
+    pub data: Vec<NodeOperationResultResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeOperationResultResponse {
+    /// The identity of the node the operation applied to
+    pub identity: String,
+    /// Whether the operation succeeded
+    pub success: bool,
+    /// The reason the operation failed, if it was unsuccessful
+    pub error: Option<String>,
+}
+
+impl From<NodeOperationResult> for NodeOperationResultResponse {
+    fn from(result: NodeOperationResult) -> Self {
+        Self {
+            identity: result.identity().to_string(),
+            success: result.is_success(),
+            error: result.error().map(String::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that a batch request body is deserialized into the expected operations and that
+    /// each operation is converted into a registry `NodeOperation`.
+    #[test]
+    fn test_node_batch_request_conversion() {
+        let body = r#"{
+            "operations": [
+                {
+                    "op": "create",
+                    "node": {
+                        "identity": "node-1",
+                        "endpoints": ["tcps://127.0.0.1:8044"],
+                        "display_name": "Node 1",
+                        "keys": ["0123"],
+                        "metadata": {}
+                    }
+                },
+                {"op": "delete", "identity": "node-2"}
+            ]
+        }"#;
+
+        let request: NodeBatchRequest =
+            serde_json::from_str(body).expect("Failed to deserialize request");
+        let operations = request
+            .operations
+            .into_iter()
+            .map(NodeOperation::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to convert operations");
+
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].identity(), "node-1");
+        assert!(matches!(operations[0], NodeOperation::Create(_)));
+        assert_eq!(operations[1], NodeOperation::Delete("node-2".to_string()));
+
+        assert!(NodeOperation::try_from(NodeOperationRequest::Delete {
+            identity: "".into()
+        })
+        .is_err());
+    }
+}
@@ -12,5 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "registry-batch")]
+pub(super) mod batch;
 pub(super) mod nodes;
 pub(super) mod nodes_identity;
+#[cfg(feature = "registry-refresh")]
+pub(super) mod refresh;
+#[cfg(feature = "registry-sources")]
+pub(super) mod sources;
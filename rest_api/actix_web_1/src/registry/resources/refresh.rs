@@ -0,0 +1,32 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RefreshRegistriesResponse {
+    pub data: Vec<RegistryRefreshResult>,
+}
+
+/// The outcome of refreshing a single remote registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegistryRefreshResult {
+    /// The URL of the remote registry
+    pub url: String,
+    /// Whether the registry was successfully fetched and cached
+    pub success: bool,
+    /// The number of nodes in the registry after the refresh, if it was successful
+    pub node_count: Option<usize>,
+    /// The reason the refresh failed, if it was unsuccessful
+    pub error: Option<String>,
+}
@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry-parallel-fetch")]
+use splinter::registry::sources::RegistryRefreshStats;
+use splinter::registry::sources::RegistrySourceStatus;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListRegistrySourcesResponse {
+    pub data: Vec<RegistrySourceResponse>,
+}
+
+/// The status of a single remote registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegistrySourceResponse {
+    /// The URL of the remote registry
+    pub url: String,
+    /// Whether the registry was added at runtime, rather than configured on startup
+    pub dynamic: bool,
+    /// Seconds since the Unix epoch of the last successful refresh, if any
+    pub last_refresh: Option<u64>,
+    /// The number of nodes in the locally cached copy of the registry
+    pub node_count: usize,
+    /// The reason the last refresh failed, if it was unsuccessful
+    pub error: Option<String>,
+    /// The recorded history of attempts to fetch the remote registry
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub refresh_stats: Option<RegistryRefreshStatsResponse>,
+}
+
+/// The recorded history of attempts to fetch a remote registry
+#[cfg(feature = "registry-parallel-fetch")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegistryRefreshStatsResponse {
+    /// Seconds since the Unix epoch of the last attempt
+    pub last_attempt: u64,
+    /// Seconds since the Unix epoch of the last successful attempt, if any
+    pub last_success: Option<u64>,
+    /// How long the last attempt took, in milliseconds
+    pub last_duration_ms: u64,
+    /// The number of successful attempts
+    pub success_count: u64,
+    /// The number of failed attempts
+    pub failure_count: u64,
+    /// The reason the last attempt failed, if it was unsuccessful
+    pub last_error: Option<String>,
+}
+
+#[cfg(feature = "registry-parallel-fetch")]
+impl From<&RegistryRefreshStats> for RegistryRefreshStatsResponse {
+    fn from(stats: &RegistryRefreshStats) -> Self {
+        Self {
+            last_attempt: to_epoch_secs(stats.last_attempt()),
+            last_success: stats.last_success().map(to_epoch_secs),
+            last_duration_ms: stats.last_duration().as_millis() as u64,
+            success_count: stats.success_count(),
+            failure_count: stats.failure_count(),
+            last_error: stats.last_error().map(ToOwned::to_owned),
+        }
+    }
+}
+
+impl From<&RegistrySourceStatus> for RegistrySourceResponse {
+    fn from(status: &RegistrySourceStatus) -> Self {
+        Self {
+            url: status.url().to_string(),
+            dynamic: status.is_dynamic(),
+            last_refresh: status.last_refresh().map(to_epoch_secs),
+            node_count: status.node_count(),
+            error: status.error().map(ToOwned::to_owned),
+            #[cfg(feature = "registry-parallel-fetch")]
+            refresh_stats: status
+                .refresh_stats()
+                .map(RegistryRefreshStatsResponse::from),
+        }
+    }
+}
+
+fn to_epoch_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// The body of a request to add a remote registry
+#[derive(Debug, Deserialize)]
+pub struct NewRegistrySource {
+    pub url: String,
+}
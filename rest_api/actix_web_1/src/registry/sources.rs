@@ -0,0 +1,192 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the following endpoints:
+//!
+//! * `GET /registry/sources` for listing the remote registries and their status
+//! * `POST /registry/sources` for adding a remote registry
+//! * `DELETE /registry/sources?url=<url>` for removing a remote registry that was added at runtime
+
+use std::collections::HashMap;
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+use splinter::registry::{sources::RegistrySources, RegistryError};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::sources::{
+    ListRegistrySourcesResponse, NewRegistrySource, RegistrySourceResponse,
+};
+#[cfg(feature = "authorization")]
+use super::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
+
+const REGISTRY_SOURCES_MIN: u32 = 1;
+
+pub fn make_sources_resource(registry_sources: RegistrySources) -> Resource {
+    let registry_sources1 = registry_sources.clone();
+    let registry_sources2 = registry_sources.clone();
+    let resource = Resource::build("/registry/sources").add_request_guard(
+        ProtocolVersionRangeGuard::new(REGISTRY_SOURCES_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, REGISTRY_READ_PERMISSION, move |_, _| {
+                list_sources(registry_sources.clone())
+            })
+            .add_method(Method::Post, REGISTRY_WRITE_PERMISSION, move |_, p| {
+                add_source(p, registry_sources1.clone())
+            })
+            .add_method(Method::Delete, REGISTRY_WRITE_PERMISSION, move |r, _| {
+                remove_source(r, registry_sources2.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| {
+                list_sources(registry_sources.clone())
+            })
+            .add_method(Method::Post, move |_, p| {
+                add_source(p, registry_sources1.clone())
+            })
+            .add_method(Method::Delete, move |r, _| {
+                remove_source(r, registry_sources2.clone())
+            })
+    }
+}
+
+fn list_sources(
+    registry_sources: RegistrySources,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || registry_sources.list_sources()).then(|res| {
+            Ok(match res {
+                Ok(statuses) => HttpResponse::Ok().json(ListRegistrySourcesResponse {
+                    data: statuses.iter().map(RegistrySourceResponse::from).collect(),
+                }),
+                Err(err) => {
+                    error!("Unable to list registry sources: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn add_source(
+    payload: web::Payload,
+    registry_sources: RegistrySources,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(
+                move |body| match serde_json::from_slice::<NewRegistrySource>(&body) {
+                    Ok(source) => Box::new(
+                        web::block(move || registry_sources.add_source(&source.url)).then(|res| {
+                            Ok(match res {
+                                Ok(status) => {
+                                    if let Some(err) = status.error() {
+                                        warn!(
+                                            "Added registry source '{}', but initial fetch failed: {}",
+                                            status.url(),
+                                            err
+                                        );
+                                    } else {
+                                        info!("Added registry source '{}'", status.url());
+                                    }
+                                    HttpResponse::Ok().json(RegistrySourceResponse::from(&status))
+                                }
+                                Err(BlockingError::Error(RegistryError::InvalidStateError(
+                                    err,
+                                ))) => HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                                    &format!("Invalid registry source: {}", err),
+                                )),
+                                Err(err) => {
+                                    error!("Unable to add registry source: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        }),
+                    )
+                        as Box<dyn Future<Item = HttpResponse, Error = Error>>,
+                    Err(err) => Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid registry source: {}",
+                                err
+                            )))
+                            .into_future(),
+                    ),
+                },
+            ),
+    )
+}
+
+fn remove_source(
+    req: HttpRequest,
+    registry_sources: RegistrySources,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let url = match web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|mut query| query.remove("url"))
+    {
+        Some(url) => url,
+        None => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(
+                        "The 'url' query parameter is required",
+                    ))
+                    .into_future(),
+            )
+        }
+    };
+
+    Box::new(
+        web::block(move || {
+            registry_sources
+                .remove_source(&url)
+                .map(|removed| (url, removed))
+        })
+        .then(|res| {
+            Ok(match res {
+                Ok((url, true)) => {
+                    info!("Removed registry source '{}'", url);
+                    HttpResponse::Ok().finish()
+                }
+                Ok((url, false)) => HttpResponse::NotFound().json(ErrorResponse::not_found(
+                    &format!("Registry source not found: {}", url),
+                )),
+                Err(BlockingError::Error(RegistryError::InvalidStateError(err))) => {
+                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(&err.to_string()))
+                }
+                Err(err) => {
+                    error!("Unable to remove registry source: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
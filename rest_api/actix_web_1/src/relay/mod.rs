@@ -0,0 +1,57 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API endpoints for relay services.
+
+pub mod payloads;
+
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::service::rest_api::{ServiceEndpoint, ServiceEndpointProvider};
+
+#[cfg(feature = "authorization")]
+pub const RELAY_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "relay.read",
+    permission_display_name: "Relay read",
+    permission_description:
+        "Allows the client to poll relay services for payloads and delivery statuses",
+};
+#[cfg(feature = "authorization")]
+pub const RELAY_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "relay.write",
+    permission_display_name: "Relay write",
+    permission_description: "Allows the client to submit payloads to relay services",
+};
+
+pub struct RelayServiceEndpointProvider {
+    endpoints: Vec<ServiceEndpoint>,
+}
+
+impl ServiceEndpointProvider for RelayServiceEndpointProvider {
+    fn endpoints(&self) -> Vec<ServiceEndpoint> {
+        self.endpoints.clone()
+    }
+}
+
+impl Default for RelayServiceEndpointProvider {
+    fn default() -> Self {
+        Self {
+            endpoints: vec![
+                payloads::make_submit_payload_endpoint(),
+                payloads::make_poll_payloads_endpoint(),
+                payloads::make_get_payload_status_endpoint(),
+            ],
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `POST /payloads`, `GET /payloads` and `GET /payloads/{id}` endpoints of relay
+//! services.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{web, Error as ActixError, HttpResponse};
+use futures::{stream::Stream, Future, IntoFuture};
+use splinter::{
+    rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
+    service::rest_api::ServiceEndpoint,
+};
+use splinter_relay::service::{RelayService, RELAY_PROTOCOL_VERSION, SERVICE_TYPE};
+
+use crate::hex::to_hex;
+
+#[cfg(feature = "authorization")]
+use super::{RELAY_READ_PERMISSION, RELAY_WRITE_PERMISSION};
+
+const RELAY_PAYLOADS_PROTOCOL_MIN: u32 = 1;
+
+#[derive(Serialize)]
+struct SubmitPayloadResponse {
+    id: u64,
+}
+
+#[derive(Serialize)]
+struct ReceivedPayloadResponse {
+    seq: u64,
+    payload: String,
+}
+
+#[derive(Serialize)]
+struct PollPayloadsResponse {
+    data: Vec<ReceivedPayloadResponse>,
+}
+
+#[derive(Serialize)]
+struct PayloadStatusResponse {
+    id: u64,
+    delivered: bool,
+}
+
+/// Submits the request body as an opaque payload to be forwarded to the relay's peer.
+pub fn make_submit_payload_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/payloads".into(),
+        method: Method::Post,
+        handler: Arc::new(move |_, payload, service| {
+            let relay = match service.as_any().downcast_ref::<RelayService>() {
+                Some(relay) => relay.clone(),
+                None => {
+                    error!("Failed to downcast to relay service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            Box::new(
+                payload
+                    .from_err::<ActixError>()
+                    .fold(web::BytesMut::new(), move |mut body, chunk| {
+                        body.extend_from_slice(&chunk);
+                        Ok::<_, ActixError>(body)
+                    })
+                    .into_future()
+                    .and_then(move |body| match relay.submit(body.to_vec()) {
+                        Ok(id) => HttpResponse::Accepted()
+                            .json(SubmitPayloadResponse { id })
+                            .into_future(),
+                        Err(err) => {
+                            error!("Failed to submit relay payload: {}", err);
+                            HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                                .into_future()
+                        }
+                    }),
+            )
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            RELAY_PAYLOADS_PROTOCOL_MIN,
+            RELAY_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: RELAY_WRITE_PERMISSION,
+    }
+}
+
+/// Returns the payloads received from the relay's peer after the `since` sequence number.
+///
+/// Payloads up to and including `since` are considered consumed and are dropped by the service.
+pub fn make_poll_payloads_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/payloads".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let relay = match service.as_any().downcast_ref::<RelayService>() {
+                Some(relay) => relay,
+                None => {
+                    error!("Failed to downcast to relay service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let query: web::Query<HashMap<String, String>> =
+                match web::Query::from_query(request.query_string()) {
+                    Ok(query) => query,
+                    Err(_) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request("Invalid query"))
+                                .into_future(),
+                        )
+                    }
+                };
+
+            let since = match query.get("since").map(|since| since.parse::<u64>()) {
+                Some(Ok(since)) => since,
+                Some(Err(err)) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid since value passed: {}",
+                                err
+                            )))
+                            .into_future(),
+                    )
+                }
+                None => 0,
+            };
+
+            Box::new(match relay.received_since(since) {
+                Ok(received) => HttpResponse::Ok()
+                    .json(PollPayloadsResponse {
+                        data: received
+                            .iter()
+                            .map(|received| ReceivedPayloadResponse {
+                                seq: received.seq(),
+                                payload: to_hex(received.payload()),
+                            })
+                            .collect(),
+                    })
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to poll relay payloads: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            RELAY_PAYLOADS_PROTOCOL_MIN,
+            RELAY_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: RELAY_READ_PERMISSION,
+    }
+}
+
+/// Returns whether a submitted payload has been acknowledged by the relay's peer.
+pub fn make_get_payload_status_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/payloads/{id}".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let relay = match service.as_any().downcast_ref::<RelayService>() {
+                Some(relay) => relay,
+                None => {
+                    error!("Failed to downcast to relay service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let id = match request.match_info().get("id").map(|id| id.parse::<u64>()) {
+                Some(Ok(id)) => id,
+                _ => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("id must be a positive integer"))
+                            .into_future(),
+                    )
+                }
+            };
+
+            Box::new(match relay.is_delivered(id) {
+                Ok(delivered) => HttpResponse::Ok()
+                    .json(PayloadStatusResponse { id, delivered })
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to get relay payload status: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            RELAY_PAYLOADS_PROTOCOL_MIN,
+            RELAY_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: RELAY_READ_PERMISSION,
+    }
+}
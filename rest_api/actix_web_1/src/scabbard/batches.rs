@@ -25,6 +25,8 @@ use splinter::{
 };
 
 use scabbard::protocol;
+#[cfg(feature = "batch-idempotency-keys")]
+use scabbard::service::IdempotentSubmission;
 use scabbard::service::{Scabbard, SERVICE_TYPE};
 use splinter_rest_api_common::scabbard::batches::BatchLinkResponse;
 #[cfg(feature = "authorization")]
@@ -35,7 +37,7 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
         service_type: SERVICE_TYPE.into(),
         route: "/batches".into(),
         method: Method::Post,
-        handler: Arc::new(move |_, payload, service| {
+        handler: Arc::new(move |_req, payload, service| {
             let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
                 Some(s) => s,
                 None => {
@@ -49,6 +51,22 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
             }
             .clone();
 
+            #[cfg(feature = "batch-idempotency-keys")]
+            let idempotency_key =
+                match _req.headers().get("Idempotency-Key") {
+                    Some(header_value) => match header_value.to_str() {
+                        Ok(key) if !key.is_empty() => Some(key.to_string()),
+                        _ => return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(
+                                    "Invalid Idempotency-Key header: must be a non-empty string",
+                                ))
+                                .into_future(),
+                        ),
+                    },
+                    None => None,
+                };
+
             Box::new(
                 payload
                     .from_err::<ActixError>()
@@ -83,6 +101,35 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
                             }
                         };
 
+                        #[cfg(feature = "batch-idempotency-keys")]
+                        if let Some(idempotency_key) = idempotency_key {
+                            return match scabbard
+                                .add_batches_with_idempotency_key(&idempotency_key, batches)
+                            {
+                                Ok(IdempotentSubmission::Added(Some(link)))
+                                | Ok(IdempotentSubmission::Duplicate(link)) => {
+                                    HttpResponse::Accepted()
+                                        .json(BatchLinkResponse::from(link.as_str()))
+                                        .into_future()
+                                }
+                                Ok(IdempotentSubmission::Added(None)) => HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request("No valid batches provided"))
+                                    .into_future(),
+                                Ok(IdempotentSubmission::KeyReused) => HttpResponse::Conflict()
+                                    .json(ErrorResponse::conflict(
+                                        "Idempotency-Key was already used to submit different \
+                                         batches",
+                                    ))
+                                    .into_future(),
+                                Err(err) => {
+                                    error!("Failed to add batches: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                        .into_future()
+                                }
+                            };
+                        }
+
                         match scabbard.add_batches(batches) {
                             Ok(Some(link)) => HttpResponse::Accepted()
                                 .json(BatchLinkResponse::from(link.as_str()))
@@ -14,6 +14,8 @@
 
 pub mod batch_statuses;
 pub mod batches;
+#[cfg(feature = "transaction-receipts")]
+pub mod receipts;
 pub mod state;
 pub mod state_address;
 pub mod state_root;
@@ -46,6 +48,8 @@ impl Default for ScabbardServiceEndpointProvider {
             state_address::make_get_state_at_address_endpoint(),
             state::make_get_state_with_prefix_endpoint(),
             state_root::make_get_state_root_endpoint(),
+            #[cfg(feature = "transaction-receipts")]
+            receipts::make_get_receipts_endpoint(),
         ];
         Self::new(endpoints)
     }
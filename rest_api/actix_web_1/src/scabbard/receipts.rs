@@ -0,0 +1,178 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse};
+use futures::IntoFuture;
+use splinter::{
+    rest_api::{
+        paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
+        ErrorResponse, Method, ProtocolVersionRangeGuard,
+    },
+    service::rest_api::ServiceEndpoint,
+};
+
+use scabbard::protocol;
+use scabbard::service::{ReceiptQuery, Scabbard, SERVICE_TYPE};
+use splinter_rest_api_common::scabbard::receipts::{ListReceiptsResponse, ReceiptResponse};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+
+pub fn make_get_receipts_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/receipts".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let query: web::Query<HashMap<String, String>> =
+                if let Ok(q) = web::Query::from_query(request.query_string()) {
+                    q
+                } else {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid query"))
+                            .into_future(),
+                    );
+                };
+
+            let mut new_queries = vec![];
+            let mut receipt_query = ReceiptQuery::default();
+
+            if let Some(value) = query.get("transaction_id") {
+                new_queries.push(format!("transaction_id={}", value));
+                receipt_query.transaction_ids = Some(
+                    value
+                        .split(',')
+                        .filter(|id| !id.is_empty())
+                        .map(String::from)
+                        .collect(),
+                );
+            }
+
+            if let Some(value) = query.get("batch_id") {
+                new_queries.push(format!("batch_id={}", value));
+                receipt_query.batch_id = Some(value.to_string());
+            }
+
+            let mut parse_param = |name: &str| match query.get(name) {
+                Some(value) => match value.parse::<u64>() {
+                    Ok(val) => {
+                        new_queries.push(format!("{}={}", name, value));
+                        Ok(Some(val))
+                    }
+                    Err(err) => Err(HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid {} value passed: {}. Error: {}",
+                            name, value, err
+                        )))
+                        .into_future()),
+                },
+                None => Ok(None),
+            };
+
+            receipt_query.start_index = match parse_param("start") {
+                Ok(start) => start,
+                Err(response) => return Box::new(response),
+            };
+            receipt_query.end_index = match parse_param("end") {
+                Ok(end) => end,
+                Err(response) => return Box::new(response),
+            };
+
+            let offset = match query.get("offset") {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(val) => val,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid offset value passed: {}. Error: {}",
+                                    value, err
+                                )))
+                                .into_future(),
+                        )
+                    }
+                },
+                None => DEFAULT_OFFSET,
+            };
+
+            let limit = match query.get("limit") {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(val) => val,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid limit value passed: {}. Error: {}",
+                                    value, err
+                                )))
+                                .into_future(),
+                        )
+                    }
+                },
+                None => DEFAULT_LIMIT,
+            };
+
+            let mut link = request.uri().path().to_string();
+            if !new_queries.is_empty() {
+                link = format!("{}?{}&", link, new_queries.join("&"));
+            }
+
+            Box::new(match scabbard.list_receipts(&receipt_query) {
+                Ok(receipts) => {
+                    let paging = PagingBuilder::new(link, receipts.len())
+                        .with_limit(limit)
+                        .with_offset(offset)
+                        .build();
+                    HttpResponse::Ok()
+                        .json(ListReceiptsResponse {
+                            data: receipts
+                                .iter()
+                                .skip(offset)
+                                .take(limit)
+                                .map(ReceiptResponse::from)
+                                .collect(),
+                            paging,
+                        })
+                        .into_future()
+                }
+                Err(err) => {
+                    error!("Failed to list transaction receipts: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            splinter_rest_api_common::scabbard::SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
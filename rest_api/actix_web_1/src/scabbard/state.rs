@@ -59,6 +59,15 @@ pub fn make_get_state_with_prefix_endpoint() -> ServiceEndpoint {
 
             let prefix = query.get("prefix").map(String::as_str);
 
+            #[cfg(feature = "historical-state")]
+            {
+                if let Some(state_root) = query.get("state_root") {
+                    return Box::new(
+                        get_state_with_prefix_at_root(scabbard, state_root, prefix).into_future(),
+                    );
+                }
+            }
+
             Box::new(match scabbard.get_state_with_prefix(prefix) {
                 Ok(state_iter) => {
                     let res = state_iter.collect::<Result<Vec<_>, _>>();
@@ -96,6 +105,52 @@ pub fn make_get_state_with_prefix_endpoint() -> ServiceEndpoint {
     }
 }
 
+/// Lists the entries under `prefix` in state as of the given historical `state_root`.
+///
+/// Responds with `400 Bad Request` if the state root is not a valid hash and with `410 Gone` if
+/// the state root is no longer available, either because it was pruned or never existed.
+#[cfg(feature = "historical-state")]
+fn get_state_with_prefix_at_root(
+    scabbard: &Scabbard,
+    state_root: &str,
+    prefix: Option<&str>,
+) -> HttpResponse {
+    if !is_valid_state_root(state_root) {
+        return HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+            "state_root must be a 64 character hex string",
+        ));
+    }
+
+    match scabbard.get_state_with_prefix_at_root(state_root, prefix) {
+        Ok(Some(state_iter)) => match state_iter.collect::<Result<Vec<_>, _>>() {
+            Ok(entries) => HttpResponse::Ok().json(
+                entries
+                    .iter()
+                    .map(StateEntryResponse::from)
+                    .collect::<Vec<_>>(),
+            ),
+            Err(err) => {
+                error!("Failed to consume state iterator: {}", err);
+                HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+            }
+        },
+        Ok(None) => HttpResponse::Gone().json(ErrorResponse::gone(&format!(
+            "State root {} is not available; it may have been pruned",
+            state_root
+        ))),
+        Err(err) => {
+            error!("Failed to get state with prefix at state root: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
+
+/// Checks that the given `state_root` is a 64 character hex string.
+#[cfg(feature = "historical-state")]
+pub(super) fn is_valid_state_root(state_root: &str) -> bool {
+    state_root.len() == 64 && state_root.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 #[cfg(feature = "sqlite")]
 #[cfg(test)]
 mod tests {
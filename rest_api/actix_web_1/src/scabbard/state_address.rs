@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "historical-state")]
+use std::collections::HashMap;
 use std::sync::Arc;
 
+#[cfg(feature = "historical-state")]
+use actix_web::web;
 use actix_web::HttpResponse;
 use futures::IntoFuture;
 use scabbard::protocol;
@@ -56,6 +60,26 @@ pub fn make_get_state_at_address_endpoint() -> ServiceEndpoint {
                 }
             };
 
+            #[cfg(feature = "historical-state")]
+            {
+                let query: web::Query<HashMap<String, String>> =
+                    if let Ok(q) = web::Query::from_query(request.query_string()) {
+                        q
+                    } else {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request("Invalid query"))
+                                .into_future(),
+                        );
+                    };
+
+                if let Some(state_root) = query.get("state_root") {
+                    return Box::new(
+                        get_state_at_address_at_root(scabbard, state_root, address).into_future(),
+                    );
+                }
+            }
+
             Box::new(match scabbard.get_state_at_address(address) {
                 Ok(Some(value)) => HttpResponse::Ok().json(value).into_future(),
                 Ok(None) => HttpResponse::NotFound()
@@ -78,6 +102,52 @@ pub fn make_get_state_at_address_endpoint() -> ServiceEndpoint {
     }
 }
 
+/// Gets the value at `address` in state as of the given historical `state_root`.
+///
+/// Responds with `404 Not Found` if the address is not set at that state root and with
+/// `410 Gone` if the state root is no longer available.
+#[cfg(feature = "historical-state")]
+fn get_state_at_address_at_root(
+    scabbard: &Scabbard,
+    state_root: &str,
+    address: &str,
+) -> HttpResponse {
+    if !super::state::is_valid_state_root(state_root) {
+        return HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+            "state_root must be a 64 character hex string",
+        ));
+    }
+
+    let state_iter = match scabbard.get_state_with_prefix_at_root(state_root, Some(address)) {
+        Ok(Some(state_iter)) => state_iter,
+        Ok(None) => {
+            return HttpResponse::Gone().json(ErrorResponse::gone(&format!(
+                "State root {} is not available; it may have been pruned",
+                state_root
+            )))
+        }
+        Err(err) => {
+            error!("Failed to get state at address at state root: {}", err);
+            return HttpResponse::InternalServerError().json(ErrorResponse::internal_error());
+        }
+    };
+
+    for entry in state_iter {
+        match entry {
+            Ok((entry_address, value)) if entry_address == address => {
+                return HttpResponse::Ok().json(value)
+            }
+            Ok(_) => (),
+            Err(err) => {
+                error!("Failed to consume state iterator: {}", err);
+                return HttpResponse::InternalServerError().json(ErrorResponse::internal_error());
+            }
+        }
+    }
+
+    HttpResponse::NotFound().json(ErrorResponse::not_found("Address not set"))
+}
+
 #[cfg(feature = "sqlite")]
 #[cfg(test)]
 mod tests {
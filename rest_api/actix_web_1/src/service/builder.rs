@@ -134,6 +134,16 @@ impl ServiceOrchestratorRestResourceProviderBuilder {
                 acc.append(&mut resources);
                 acc
             });
+
+        #[cfg(feature = "service-orchestrator-limits")]
+        let resources = {
+            let mut resources = resources;
+            resources.push(super::status::make_service_status_resource(
+                orchestrator.service_health_monitor(),
+            ));
+            resources
+        };
+
         ServiceOrchestratorRestResourceProvider { resources }
     }
 }
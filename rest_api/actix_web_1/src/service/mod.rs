@@ -13,6 +13,8 @@
 // limitations under the License.
 
 mod builder;
+#[cfg(feature = "service-orchestrator-limits")]
+mod status;
 
 use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
 
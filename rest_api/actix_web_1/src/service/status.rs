@@ -0,0 +1,89 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use serde::Serialize;
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+use splinter::runtime::service::instance::{ServiceHealth, ServiceHealthMonitor};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use crate::admin::CIRCUIT_READ_PERMISSION;
+
+const ORCHESTRATOR_SERVICES_MIN: u32 = 2;
+
+#[derive(Debug, Serialize)]
+struct ServiceStatusListResponse {
+    data: Vec<ServiceStatusResponse>,
+}
+
+#[derive(Debug, Serialize)]
+struct ServiceStatusResponse {
+    circuit: String,
+    service_id: String,
+    service_type: String,
+    status: String,
+    restarts: u32,
+    queued_messages: usize,
+    last_error: Option<String>,
+}
+
+impl From<ServiceHealth> for ServiceStatusResponse {
+    fn from(health: ServiceHealth) -> Self {
+        Self {
+            circuit: health.service().circuit.clone(),
+            service_id: health.service().service_id.clone(),
+            service_type: health.service().service_type.clone(),
+            status: health.status().to_string(),
+            restarts: health.restarts(),
+            queued_messages: health.queued_messages(),
+            last_error: health.last_error().map(String::from),
+        }
+    }
+}
+
+/// Makes the `GET /orchestrator/services` endpoint, which reports the restart and resource limit
+/// status of each service run by the orchestrator.
+pub fn make_service_status_resource(monitor: ServiceHealthMonitor) -> Resource {
+    let resource = Resource::build("/orchestrator/services").add_request_guard(
+        ProtocolVersionRangeGuard::new(ORCHESTRATOR_SERVICES_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |_, _| {
+            list_service_status(&monitor)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| list_service_status(&monitor))
+    }
+}
+
+fn list_service_status(
+    monitor: &ServiceHealthMonitor,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let mut data = monitor
+        .list_service_health()
+        .into_iter()
+        .map(ServiceStatusResponse::from)
+        .collect::<Vec<_>>();
+    data.sort_by(|a, b| (&a.circuit, &a.service_id).cmp(&(&b.circuit, &b.service_id)));
+
+    Box::new(
+        HttpResponse::Ok()
+            .json(ServiceStatusListResponse { data })
+            .into_future(),
+    )
+}
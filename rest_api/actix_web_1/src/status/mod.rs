@@ -15,13 +15,32 @@
 
 mod resource_provider;
 
+#[cfg(feature = "node-metadata")]
+use std::collections::BTreeMap;
+#[cfg(feature = "node-metadata")]
+use std::sync::Arc;
+
+#[cfg(feature = "node-metadata")]
+use actix_web::{error::BlockingError, web};
 use actix_web::{Error, HttpResponse};
+#[cfg(feature = "node-metadata")]
+use futures::stream::Stream;
 use futures::{Future, IntoFuture};
+#[cfg(feature = "node-metadata")]
+use splinter::node_id::store::{error::NodeIdStoreError, NodeIdStore};
 #[cfg(feature = "authorization")]
 use splinter::rest_api::auth::authorization::Permission;
+#[cfg(feature = "node-metadata")]
+use splinter::rest_api::ErrorResponse;
 use splinter_rest_api_common::status::Status;
+#[cfg(feature = "node-metadata")]
+use splinter_rest_api_common::status::StatusUpdate;
 
 pub use resource_provider::StatusResourceProvider;
+#[cfg(feature = "build-info")]
+pub use splinter_rest_api_common::status::BuildInfo;
+#[cfg(feature = "state-dir-health")]
+pub use splinter_rest_api_common::status::{StateDirMonitor, DEFAULT_DEGRADED_THRESHOLD_PERCENT};
 
 #[cfg(feature = "authorization")]
 pub const STATUS_READ_PERMISSION: Permission = Permission::Check {
@@ -30,12 +49,22 @@ pub const STATUS_READ_PERMISSION: Permission = Permission::Check {
     permission_description: "Allows the client to get node status info",
 };
 
+#[cfg(all(feature = "authorization", feature = "node-metadata"))]
+pub const STATUS_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "status.write",
+    permission_display_name: "Status write",
+    permission_description: "Allows the client to update the node's display name and metadata",
+};
+
 pub fn get_status(
     node_id: String,
     display_name: String,
     #[cfg(feature = "service-endpoint")] service_endpoint: String,
     network_endpoints: Vec<String>,
     advertised_endpoints: Vec<String>,
+    #[cfg(feature = "node-metadata")] metadata: BTreeMap<String, String>,
+    #[cfg(feature = "state-dir-health")] state_dir_monitor: Option<&StateDirMonitor>,
+    #[cfg(feature = "build-info")] build_info: &BuildInfo,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let status = Status::new(
         node_id,
@@ -46,5 +75,148 @@ pub fn get_status(
         advertised_endpoints,
     );
 
+    #[cfg(feature = "node-metadata")]
+    let status = status.with_metadata(metadata);
+
+    #[cfg(feature = "state-dir-health")]
+    let status = match state_dir_monitor.map(StateDirMonitor::usage) {
+        Some(Ok(usage)) => status.with_state_dir_usage(usage),
+        Some(Err(err)) => {
+            warn!("Unable to measure state directory usage: {}", err);
+            status
+        }
+        None => status,
+    };
+
+    #[cfg(feature = "build-info")]
+    let status = status.with_build_info(build_info.clone());
+
     Box::new(HttpResponse::Ok().json(status).into_future())
 }
+
+/// Loads the node's display name and metadata from the store. The display name given at startup
+/// is used until one has been set at runtime.
+#[cfg(feature = "node-metadata")]
+fn load_node_info(
+    store: &dyn NodeIdStore,
+    default_display_name: &str,
+) -> Result<(String, BTreeMap<String, String>), NodeIdStoreError> {
+    let display_name = store
+        .get_display_name()?
+        .unwrap_or_else(|| default_display_name.to_string());
+
+    Ok((display_name, store.get_metadata()?))
+}
+
+#[cfg(feature = "node-metadata")]
+pub fn update_status(
+    payload: web::Payload,
+    store: Arc<dyn NodeIdStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(
+                move |body| match serde_json::from_slice::<StatusUpdate>(&body) {
+                    Ok(update) => {
+                        if let Err(msg) = validate_update(&update) {
+                            return Box::new(
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&msg))
+                                    .into_future(),
+                            )
+                                as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                        }
+
+                        Box::new(
+                            web::block(move || {
+                                if let Some(display_name) = update.display_name {
+                                    store.set_display_name(display_name)?;
+                                }
+                                if let Some(metadata) = update.metadata {
+                                    store.set_metadata(metadata)?;
+                                }
+                                Ok::<_, NodeIdStoreError>(())
+                            })
+                            .then(|res| {
+                                Ok(match res {
+                                    Ok(()) => HttpResponse::Ok().finish(),
+                                    Err(BlockingError::Error(err)) => {
+                                        error!("Unable to update node status: {}", err);
+                                        HttpResponse::InternalServerError()
+                                            .json(ErrorResponse::internal_error())
+                                    }
+                                    Err(BlockingError::Canceled) => {
+                                        error!("Node status update was canceled");
+                                        HttpResponse::InternalServerError()
+                                            .json(ErrorResponse::internal_error())
+                                    }
+                                })
+                            }),
+                        )
+                    }
+                    Err(err) => Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid status update: {}",
+                                err
+                            )))
+                            .into_future(),
+                    ),
+                },
+            ),
+    )
+}
+
+#[cfg(feature = "node-metadata")]
+fn validate_update(update: &StatusUpdate) -> Result<(), String> {
+    if update.display_name.is_none() && update.metadata.is_none() {
+        return Err("A display name or metadata must be provided".into());
+    }
+    if let Some(display_name) = &update.display_name {
+        if display_name.trim().is_empty() {
+            return Err("Display name must not be empty".into());
+        }
+    }
+    if let Some(metadata) = &update.metadata {
+        if metadata.keys().any(|key| key.is_empty()) {
+            return Err("Metadata keys must not be empty".into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "node-metadata"))]
+mod tests {
+    use super::*;
+
+    /// Verifies that updates must change something, and must not contain empty names or keys.
+    #[test]
+    fn validate_status_update() {
+        assert!(validate_update(&StatusUpdate::default()).is_err());
+        assert!(validate_update(&StatusUpdate {
+            display_name: Some(" ".into()),
+            metadata: None,
+        })
+        .is_err());
+
+        let mut metadata = BTreeMap::new();
+        metadata.insert("".to_string(), "value".to_string());
+        assert!(validate_update(&StatusUpdate {
+            display_name: None,
+            metadata: Some(metadata),
+        })
+        .is_err());
+
+        assert!(validate_update(&StatusUpdate {
+            display_name: Some("Acme Node".into()),
+            metadata: Some(BTreeMap::new()),
+        })
+        .is_ok());
+    }
+}
@@ -13,32 +13,88 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "node-metadata")]
+use std::sync::Arc;
+
+#[cfg(feature = "node-metadata")]
+use actix_web::HttpResponse;
+#[cfg(feature = "node-metadata")]
+use futures::{Future, IntoFuture};
+#[cfg(feature = "node-metadata")]
+use splinter::node_id::store::NodeIdStore;
+#[cfg(feature = "node-metadata")]
+use splinter::rest_api::ErrorResponse;
 use splinter::rest_api::{Resource, RestResourceProvider};
+#[cfg(feature = "build-info")]
+use splinter_rest_api_common::status::BuildInfo;
+#[cfg(feature = "state-dir-health")]
+use splinter_rest_api_common::status::StateDirMonitor;
 
 use super::get_status;
 #[cfg(feature = "authorization")]
 use super::STATUS_READ_PERMISSION;
+#[cfg(all(feature = "authorization", feature = "node-metadata"))]
+use super::STATUS_WRITE_PERMISSION;
+#[cfg(feature = "node-metadata")]
+use super::{load_node_info, update_status};
 
 pub struct StatusResourceProvider {
     resources: Vec<Resource>,
 }
 
 impl StatusResourceProvider {
+    /// Creates the provider for the `/status` resource.
+    ///
+    /// With the `node-metadata` feature, the node's display name and metadata are read from the
+    /// given store, and may be updated with a `PATCH` request; the given display name is used
+    /// until one has been stored.
+    ///
+    /// With the `build-info` feature, the given build information is included in the status.
     pub fn new(
         node_id: String,
         display_name: String,
         #[cfg(feature = "service-endpoint")] service_endpoint: String,
         network_endpoints: Vec<String>,
         advertised_endpoints: Vec<String>,
+        #[cfg(feature = "state-dir-health")] state_dir_monitor: Option<StateDirMonitor>,
+        #[cfg(feature = "build-info")] build_info: BuildInfo,
+        #[cfg(feature = "node-metadata")] node_id_store: Box<dyn NodeIdStore>,
     ) -> Self {
+        #[cfg(feature = "node-metadata")]
+        let node_id_store: Arc<dyn NodeIdStore> = Arc::from(node_id_store);
+        #[cfg(feature = "node-metadata")]
+        let update_store = node_id_store.clone();
+
         let handle = move |_, _| {
+            #[cfg(feature = "node-metadata")]
+            let (display_name, metadata) = match load_node_info(&*node_id_store, &display_name) {
+                Ok(info) => info,
+                Err(err) => {
+                    error!("Unable to load node display name and metadata: {}", err);
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    )
+                        as Box<dyn Future<Item = HttpResponse, Error = actix_web::Error>>;
+                }
+            };
+            #[cfg(not(feature = "node-metadata"))]
+            let display_name = display_name.clone();
+
             get_status(
                 node_id.clone(),
-                display_name.clone(),
+                display_name,
                 #[cfg(feature = "service-endpoint")]
                 service_endpoint.clone(),
                 network_endpoints.clone(),
                 advertised_endpoints.clone(),
+                #[cfg(feature = "node-metadata")]
+                metadata,
+                #[cfg(feature = "state-dir-health")]
+                state_dir_monitor.as_ref(),
+                #[cfg(feature = "build-info")]
+                &build_info,
             )
         };
         #[cfg(feature = "authorization")]
@@ -48,6 +104,12 @@ impl StatusResourceProvider {
                 STATUS_READ_PERMISSION,
                 handle,
             );
+            #[cfg(feature = "node-metadata")]
+            let status_resource = status_resource.add_method(
+                splinter::rest_api::Method::Patch,
+                STATUS_WRITE_PERMISSION,
+                move |_, payload| update_status(payload, update_store.clone()),
+            );
             let resources = vec![status_resource];
             Self { resources }
         }
@@ -55,6 +117,11 @@ impl StatusResourceProvider {
         {
             let status_resource =
                 Resource::build("/status").add_method(splinter::rest_api::Method::Get, handle);
+            #[cfg(feature = "node-metadata")]
+            let status_resource = status_resource
+                .add_method(splinter::rest_api::Method::Patch, move |_, payload| {
+                    update_status(payload, update_store.clone())
+                });
             let resources = vec![status_resource];
             Self { resources }
         }
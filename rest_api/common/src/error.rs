@@ -52,4 +52,15 @@ impl ResponseError {
     pub fn internal_error<S: Into<String>>(msg: S, err: Option<Box<dyn Error>>) -> Self {
         Self::InternalError(msg.into(), err)
     }
+
+    /// Returns the machine-readable code sent to clients in the `code` field of an error
+    /// response.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ResponseError::BadRequest(_) => "BAD_REQUEST",
+            ResponseError::NotFound(_) => "NOT_FOUND",
+            ResponseError::InternalError(_, _) => "INTERNAL_ERROR",
+            ResponseError::NotAuthorized => "UNAUTHORIZED",
+        }
+    }
 }
@@ -18,4 +18,4 @@ pub mod paging;
 pub mod scabbard;
 pub mod status;
 
-pub const SPLINTER_PROTOCOL_VERSION: u32 = 2;
+pub const SPLINTER_PROTOCOL_VERSION: u32 = 3;
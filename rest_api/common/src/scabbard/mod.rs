@@ -14,6 +14,8 @@
 
 pub mod batch_statuses;
 pub mod batches;
+#[cfg(feature = "transaction-receipts")]
+pub mod receipts;
 pub mod state;
 
 #[cfg(feature = "authorization")]
@@ -38,3 +40,5 @@ pub const SCABBARD_BATCH_STATUSES_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_GET_STATE_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_LIST_STATE_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_STATE_ROOT_PROTOCOL_MIN: u32 = 1;
+#[cfg(feature = "transaction-receipts")]
+pub const SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN: u32 = 1;
@@ -0,0 +1,178 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use serde::Serialize;
+use splinter::rest_api::paging::Paging;
+use transact::protocol::receipt::{Event, StateChange, TransactionResult};
+
+use scabbard::service::CommittedReceipt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListReceiptsResponse<'a> {
+    pub data: Vec<ReceiptResponse<'a>>,
+    pub paging: Paging,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReceiptResponse<'a> {
+    pub index: u64,
+    pub transaction_id: &'a str,
+    #[serde(flatten)]
+    pub result: ReceiptResultResponse<'a>,
+}
+
+impl<'a> From<&'a CommittedReceipt> for ReceiptResponse<'a> {
+    fn from(committed: &'a CommittedReceipt) -> Self {
+        Self {
+            index: committed.index,
+            transaction_id: &committed.receipt.transaction_id,
+            result: ReceiptResultResponse::from(&committed.receipt.transaction_result),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum ReceiptResultResponse<'a> {
+    Valid {
+        state_changes: Vec<StateChangeResponse<'a>>,
+        events: Vec<EventResponse<'a>>,
+        data: &'a [Vec<u8>],
+    },
+    Invalid {
+        error_message: &'a str,
+        error_data: &'a [u8],
+    },
+}
+
+impl<'a> From<&'a TransactionResult> for ReceiptResultResponse<'a> {
+    fn from(result: &'a TransactionResult) -> Self {
+        match result {
+            TransactionResult::Valid {
+                state_changes,
+                events,
+                data,
+            } => ReceiptResultResponse::Valid {
+                state_changes: state_changes
+                    .iter()
+                    .map(StateChangeResponse::from)
+                    .collect(),
+                events: events.iter().map(EventResponse::from).collect(),
+                data,
+            },
+            TransactionResult::Invalid {
+                error_message,
+                error_data,
+            } => ReceiptResultResponse::Invalid {
+                error_message,
+                error_data,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StateChangeResponse<'a> {
+    Set { key: &'a str, value: &'a [u8] },
+    Delete { key: &'a str },
+}
+
+impl<'a> From<&'a StateChange> for StateChangeResponse<'a> {
+    fn from(change: &'a StateChange) -> Self {
+        match change {
+            StateChange::Set { key, value } => StateChangeResponse::Set { key, value },
+            StateChange::Delete { key } => StateChangeResponse::Delete { key },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventResponse<'a> {
+    pub event_type: &'a str,
+    pub attributes: &'a [(String, String)],
+    pub data: &'a [u8],
+}
+
+impl<'a> From<&'a Event> for EventResponse<'a> {
+    fn from(event: &'a Event) -> Self {
+        Self {
+            event_type: &event.event_type,
+            attributes: &event.attributes,
+            data: &event.data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{self, Value};
+
+    fn assert_json(actual: &impl Serialize, expected: &str) {
+        assert_eq!(
+            serde_json::to_value(actual).expect("error serializing"),
+            serde_json::from_str::<Value>(expected).expect("error deserializing")
+        );
+    }
+
+    #[test]
+    fn receipt_response_serializes_correctly() {
+        assert_json(
+            &ReceiptResponse {
+                index: 3,
+                transaction_id: "txid",
+                result: ReceiptResultResponse::Valid {
+                    state_changes: vec![
+                        StateChangeResponse::Set {
+                            key: "abcd",
+                            value: &[1],
+                        },
+                        StateChangeResponse::Delete { key: "ef01" },
+                    ],
+                    events: vec![],
+                    data: &[],
+                },
+            },
+            r#"{
+              "index": 3,
+              "transaction_id": "txid",
+              "status": "valid",
+              "state_changes": [
+                { "type": "set", "key": "abcd", "value": [1] },
+                { "type": "delete", "key": "ef01" }
+              ],
+              "events": [],
+              "data": []
+            }"#,
+        );
+
+        assert_json(
+            &ReceiptResponse {
+                index: 0,
+                transaction_id: "txid",
+                result: ReceiptResultResponse::Invalid {
+                    error_message: "message",
+                    error_data: &[0, 1],
+                },
+            },
+            r#"{
+              "index": 0,
+              "transaction_id": "txid",
+              "status": "invalid",
+              "error_message": "message",
+              "error_data": [0, 1]
+            }"#,
+        );
+    }
+}
@@ -13,8 +13,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "state-dir-health")]
+mod state_dir;
+
+#[cfg(any(feature = "build-info", feature = "node-metadata"))]
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "state-dir-health")]
+pub use state_dir::{
+    HealthStatus, StateDirMonitor, StateDirUsage, DEFAULT_DEGRADED_THRESHOLD_PERCENT,
+};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Status {
     node_id: String,
@@ -23,7 +34,22 @@ pub struct Status {
     service_endpoint: String,
     network_endpoints: Vec<String>,
     advertised_endpoints: Vec<String>,
+    /// Whether the node has no network listeners, and therefore only connects to its peers
+    #[cfg(feature = "outbound-only")]
+    outbound_only: bool,
     version: String,
+    #[cfg(feature = "node-metadata")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+    #[cfg(feature = "state-dir-health")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health: Option<HealthStatus>,
+    #[cfg(feature = "state-dir-health")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    state_dir: Option<StateDirUsage>,
+    #[cfg(feature = "build-info")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    build: Option<BuildInfo>,
 }
 
 impl Status {
@@ -39,11 +65,68 @@ impl Status {
             display_name,
             #[cfg(feature = "service-endpoint")]
             service_endpoint,
+            #[cfg(feature = "outbound-only")]
+            outbound_only: network_endpoints.is_empty(),
             network_endpoints,
             advertised_endpoints,
             version: get_version(),
+            #[cfg(feature = "node-metadata")]
+            metadata: BTreeMap::new(),
+            #[cfg(feature = "state-dir-health")]
+            health: None,
+            #[cfg(feature = "state-dir-health")]
+            state_dir: None,
+            #[cfg(feature = "build-info")]
+            build: None,
         }
     }
+
+    /// Adds the node's metadata to the status.
+    #[cfg(feature = "node-metadata")]
+    pub fn with_metadata(mut self, metadata: BTreeMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Adds the state directory's disk usage to the status; the node's health is taken from
+    /// the usage's health.
+    #[cfg(feature = "state-dir-health")]
+    pub fn with_state_dir_usage(mut self, state_dir_usage: StateDirUsage) -> Self {
+        self.health = Some(state_dir_usage.health);
+        self.state_dir = Some(state_dir_usage);
+        self
+    }
+
+    /// Adds information about how the node was built to the status.
+    #[cfg(feature = "build-info")]
+    pub fn with_build_info(mut self, build_info: BuildInfo) -> Self {
+        self.build = Some(build_info);
+        self
+    }
+}
+
+/// How a node was built: the commit it was built from, the cargo features it was compiled with
+/// and the current version of each protocol it supports.
+#[cfg(feature = "build-info")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BuildInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_hash: Option<String>,
+    pub features: Vec<String>,
+    pub protocol_versions: BTreeMap<String, u32>,
+}
+
+/// The body of a request to update a node's display name and metadata.
+///
+/// Fields that are not present are left unchanged. If `metadata` is present, it replaces all of
+/// the node's metadata.
+#[cfg(feature = "node-metadata")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StatusUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, String>>,
 }
 
 fn get_version() -> String {
@@ -0,0 +1,219 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Disk usage reporting for a node's state directory.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The default percentage of the state directory's volume that may be in use before the node's
+/// health is reported as degraded.
+pub const DEFAULT_DEGRADED_THRESHOLD_PERCENT: u8 = 90;
+
+/// The health of a node, as reported by the status endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+}
+
+/// Disk usage of a node's state directory, broken down by the kind of file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDirUsage {
+    pub path: String,
+    pub total_bytes: u64,
+    pub lmdb_bytes: u64,
+    pub yaml_bytes: u64,
+    pub receipt_bytes: u64,
+    pub other_bytes: u64,
+    pub volume_size_bytes: Option<u64>,
+    pub volume_available_bytes: Option<u64>,
+    pub health: HealthStatus,
+}
+
+impl StateDirUsage {
+    /// Returns the percentage of the volume that is in use, if the volume size is known.
+    pub fn volume_used_percent(&self) -> Option<u8> {
+        match (self.volume_size_bytes, self.volume_available_bytes) {
+            (Some(size), Some(available)) if size > 0 => {
+                let used = size.saturating_sub(available);
+                Some(((used as u128 * 100) / size as u128) as u8)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Measures the disk usage of a state directory on request.
+#[derive(Clone, Debug)]
+pub struct StateDirMonitor {
+    path: PathBuf,
+    degraded_threshold_percent: u8,
+}
+
+impl StateDirMonitor {
+    /// Creates a new monitor for the given state directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The node's state directory
+    /// * `degraded_threshold_percent` - The percentage of the volume that may be in use before
+    ///   the node's health is reported as degraded
+    pub fn new<P: Into<PathBuf>>(path: P, degraded_threshold_percent: u8) -> Self {
+        Self {
+            path: path.into(),
+            degraded_threshold_percent,
+        }
+    }
+
+    /// Walks the state directory and returns its current disk usage.
+    pub fn usage(&self) -> Result<StateDirUsage, io::Error> {
+        let mut usage = StateDirUsage {
+            path: self.path.to_string_lossy().into(),
+            total_bytes: 0,
+            lmdb_bytes: 0,
+            yaml_bytes: 0,
+            receipt_bytes: 0,
+            other_bytes: 0,
+            volume_size_bytes: None,
+            volume_available_bytes: None,
+            health: HealthStatus::Ok,
+        };
+
+        add_dir_usage(&self.path, &mut usage)?;
+
+        if let Some((size, available)) = volume_stats(&self.path) {
+            usage.volume_size_bytes = Some(size);
+            usage.volume_available_bytes = Some(available);
+        }
+
+        if usage
+            .volume_used_percent()
+            .map(|percent| percent >= self.degraded_threshold_percent)
+            .unwrap_or(false)
+        {
+            usage.health = HealthStatus::Degraded;
+        }
+
+        Ok(usage)
+    }
+}
+
+fn add_dir_usage(dir: &Path, usage: &mut StateDirUsage) -> Result<(), io::Error> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            add_dir_usage(&entry.path(), usage)?;
+            continue;
+        }
+
+        let size = metadata.len();
+        let name = entry.file_name().to_string_lossy().to_string();
+        usage.total_bytes += size;
+        if name.contains("receipts") {
+            usage.receipt_bytes += size;
+        } else if name.ends_with(".lmdb") || name.ends_with(".lmdb-lock") {
+            usage.lmdb_bytes += size;
+        } else if name.ends_with(".yaml") || name.ends_with(".yml") {
+            usage.yaml_bytes += size;
+        } else {
+            usage.other_bytes += size;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the total and available bytes of the volume containing `path`.
+#[cfg(unix)]
+fn volume_stats(path: &Path) -> Option<(u64, u64)> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+
+    let block_size = stats.f_frsize as u64;
+    Some((
+        stats.f_blocks as u64 * block_size,
+        stats.f_bavail as u64 * block_size,
+    ))
+}
+
+#[cfg(not(unix))]
+fn volume_stats(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+    use std::io::Write;
+
+    /// Verify that the files in a state directory are counted in the expected categories,
+    /// including files in subdirectories.
+    #[test]
+    fn test_usage_categories() {
+        let temp_dir = tempfile::tempdir().expect("Unable to create temp dir");
+        write_file(&temp_dir.path().join("circuits.yaml"), 10);
+        write_file(&temp_dir.path().join("abcde-01234::a000.lmdb"), 100);
+        write_file(&temp_dir.path().join("abcde-01234::a000.lmdb-lock"), 5);
+        write_file(&temp_dir.path().join("abcde-01234::a000-receipts.lmdb"), 40);
+        fs::create_dir(temp_dir.path().join("keys")).expect("Unable to create dir");
+        write_file(&temp_dir.path().join("keys").join("node.priv"), 7);
+
+        let usage = StateDirMonitor::new(temp_dir.path(), 100)
+            .usage()
+            .expect("Unable to measure usage");
+
+        assert_eq!(usage.total_bytes, 162);
+        assert_eq!(usage.yaml_bytes, 10);
+        assert_eq!(usage.lmdb_bytes, 105);
+        assert_eq!(usage.receipt_bytes, 40);
+        assert_eq!(usage.other_bytes, 7);
+    }
+
+    /// Verify that the health is degraded once the volume usage reaches the threshold.
+    #[test]
+    fn test_degraded_threshold() {
+        let temp_dir = tempfile::tempdir().expect("Unable to create temp dir");
+
+        let usage = StateDirMonitor::new(temp_dir.path(), 0)
+            .usage()
+            .expect("Unable to measure usage");
+        if usage.volume_size_bytes.is_some() {
+            assert_eq!(usage.health, HealthStatus::Degraded);
+        }
+
+        let usage = StateDirMonitor::new(temp_dir.path(), 101)
+            .usage()
+            .expect("Unable to measure usage");
+        assert_eq!(usage.health, HealthStatus::Ok);
+    }
+
+    fn write_file(path: &Path, len: usize) {
+        File::create(path)
+            .and_then(|mut file| file.write_all(&vec![0; len]))
+            .expect("Unable to write file");
+    }
+}
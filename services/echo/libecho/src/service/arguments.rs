@@ -14,17 +14,23 @@
 
 use std::time::Duration;
 
+#[cfg(feature = "service-arg-schema")]
+use splinter::service::instance::{ServiceArgSchema, ServiceArgType};
 use splinter::{error::InvalidArgumentError, service::ServiceId};
 
 const DEFAULT_JITTER: u64 = 5;
 const DEFAULT_FREQUENCY: u64 = 10;
 const DEFAULT_ERROR_RATE: f32 = 0.1;
+// The size of the "test" message sent by earlier versions of the service
+const DEFAULT_PAYLOAD_SIZE: usize = 4;
 
 pub struct EchoArguments {
     peers: Vec<ServiceId>,
     frequency: Duration,
     jitter: Duration,
     error_rate: f32,
+    min_payload_size: usize,
+    max_payload_size: usize,
 }
 
 impl EchoArguments {
@@ -34,12 +40,12 @@ impl EchoArguments {
         jitter: Duration,
         error_rate: f32,
     ) -> Result<Self, InvalidArgumentError> {
-        Ok(EchoArguments {
-            peers,
-            frequency,
-            jitter,
-            error_rate,
-        })
+        EchoArgumentsBuilder::new()
+            .with_peers(peers)
+            .with_frequency(frequency)
+            .with_jitter(jitter)
+            .with_error_rate(error_rate)
+            .build()
     }
 
     pub fn peers(&self) -> &Vec<ServiceId> {
@@ -54,9 +60,33 @@ impl EchoArguments {
         &self.jitter
     }
 
+    /// Returns the number of errors per second the service will inject in place of sending a
+    /// request; a rate of `0.0` disables error injection.
     pub fn error_rate(&self) -> f32 {
         self.error_rate
     }
+
+    /// Returns the smallest payload, in bytes, the service will send in a request.
+    pub fn min_payload_size(&self) -> usize {
+        self.min_payload_size
+    }
+
+    /// Returns the largest payload, in bytes, the service will send in a request.
+    pub fn max_payload_size(&self) -> usize {
+        self.max_payload_size
+    }
+
+    /// Returns the schema of the service arguments accepted by the echo service.
+    #[cfg(feature = "service-arg-schema")]
+    pub fn schema() -> ServiceArgSchema {
+        ServiceArgSchema::new()
+            .with_required_arg("peer_services", ServiceArgType::List)
+            .with_optional_arg("frequency", ServiceArgType::Integer)
+            .with_optional_arg("jitter", ServiceArgType::Integer)
+            .with_optional_arg("error_rate", ServiceArgType::Float)
+            .with_optional_arg("min_payload_size", ServiceArgType::Integer)
+            .with_optional_arg("max_payload_size", ServiceArgType::Integer)
+    }
 }
 
 #[derive(Default)]
@@ -65,6 +95,8 @@ pub struct EchoArgumentsBuilder {
     frequency: Option<Duration>,
     jitter: Option<Duration>,
     error_rate: Option<f32>,
+    min_payload_size: Option<usize>,
+    max_payload_size: Option<usize>,
 }
 
 impl EchoArgumentsBuilder {
@@ -74,6 +106,8 @@ impl EchoArgumentsBuilder {
             frequency: None,
             jitter: None,
             error_rate: None,
+            min_payload_size: None,
+            max_payload_size: None,
         }
     }
 
@@ -97,6 +131,16 @@ impl EchoArgumentsBuilder {
         self
     }
 
+    pub fn with_min_payload_size(mut self, min_payload_size: usize) -> Self {
+        self.min_payload_size = Some(min_payload_size);
+        self
+    }
+
+    pub fn with_max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = Some(max_payload_size);
+        self
+    }
+
     pub fn build(self) -> Result<EchoArguments, InvalidArgumentError> {
         let peers = self
             .peers
@@ -109,12 +153,34 @@ impl EchoArgumentsBuilder {
         let jitter = self.jitter.unwrap_or(Duration::from_secs(DEFAULT_JITTER));
 
         let error_rate = self.error_rate.unwrap_or(DEFAULT_ERROR_RATE);
+        if !error_rate.is_finite() || error_rate < 0.0 {
+            return Err(InvalidArgumentError::new(
+                "error_rate",
+                "must be a non-negative number",
+            ));
+        }
+
+        // if only one bound is given, send payloads of exactly that size
+        let (min_payload_size, max_payload_size) =
+            match (self.min_payload_size, self.max_payload_size) {
+                (Some(min), Some(max)) => (min, max),
+                (Some(size), None) | (None, Some(size)) => (size, size),
+                (None, None) => (DEFAULT_PAYLOAD_SIZE, DEFAULT_PAYLOAD_SIZE),
+            };
+        if min_payload_size > max_payload_size {
+            return Err(InvalidArgumentError::new(
+                "min_payload_size",
+                "must not be greater than max_payload_size",
+            ));
+        }
 
         Ok(EchoArguments {
             peers,
             frequency,
             jitter,
             error_rate,
+            min_payload_size,
+            max_payload_size,
         })
     }
 }
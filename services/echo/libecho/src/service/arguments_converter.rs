@@ -38,6 +38,14 @@ impl ArgumentsConverter<EchoArguments, Vec<(String, String)>> for EchoArgumentsV
             ),
             ("jitter".to_string(), left.jitter().as_secs().to_string()),
             ("error_rate".to_string(), left.error_rate().to_string()),
+            (
+                "min_payload_size".to_string(),
+                left.min_payload_size().to_string(),
+            ),
+            (
+                "max_payload_size".to_string(),
+                left.max_payload_size().to_string(),
+            ),
         ];
         Ok(arguments)
     }
@@ -76,6 +84,22 @@ impl ArgumentsConverter<EchoArguments, Vec<(String, String)>> for EchoArgumentsV
                     })?;
                     arg_builder = arg_builder.with_error_rate(error_rate);
                 }
+                "min_payload_size" => {
+                    let min_payload_size = value.parse::<usize>().map_err(|_| {
+                        InternalError::with_message(
+                            "Unable to convert min_payload_size to usize".into(),
+                        )
+                    })?;
+                    arg_builder = arg_builder.with_min_payload_size(min_payload_size);
+                }
+                "max_payload_size" => {
+                    let max_payload_size = value.parse::<usize>().map_err(|_| {
+                        InternalError::with_message(
+                            "Unable to convert max_payload_size to usize".into(),
+                        )
+                    })?;
+                    arg_builder = arg_builder.with_max_payload_size(max_payload_size);
+                }
                 _ => {
                     return Err(InternalError::with_message(format!(
                         "Received unknown argument: {}",
@@ -50,7 +50,14 @@ impl MessageHandler for EchoMessageHandler {
                 message,
                 correlation_id,
             } => {
-                info!("[service:{}] [from:{}] [id:{}] received echo request, sending echo response: \"{}\"", to_service, from_service, correlation_id, message);
+                info!(
+                    "[service:{}] [from:{}] [id:{}] received echo request, sending echo \
+                    response ({} bytes)",
+                    to_service,
+                    from_service,
+                    correlation_id,
+                    message.len()
+                );
                 sender.send(
                     from_service.service_id(),
                     EchoMessage::Response {
@@ -64,8 +71,11 @@ impl MessageHandler for EchoMessageHandler {
                 correlation_id,
             } => {
                 info!(
-                    "[service:{}] [from:{}] [id:{}] received echo response: \"{}\"",
-                    to_service, from_service, correlation_id, message
+                    "[service:{}] [from:{}] [id:{}] received echo response ({} bytes)",
+                    to_service,
+                    from_service,
+                    correlation_id,
+                    message.len()
                 );
                 let ack_at = SystemTime::now();
                 let ack_at = i64::try_from(
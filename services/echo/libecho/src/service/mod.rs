@@ -20,6 +20,7 @@ mod message_converter;
 mod message_handler;
 mod message_handler_factory;
 mod request;
+mod stats;
 mod status;
 mod timer_filter;
 mod timer_handler;
@@ -33,6 +34,7 @@ pub use message_converter::EchoMessageByteConverter;
 pub use message_handler::EchoMessageHandler;
 pub use message_handler_factory::EchoMessageHandlerFactory;
 pub use request::{EchoRequest, RequestStatus};
+pub use stats::EchoServiceStats;
 pub use status::EchoServiceStatus;
 pub use timer_filter::EchoTimerFilter;
 pub use timer_handler::EchoTimerHandler;
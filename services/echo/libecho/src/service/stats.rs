@@ -0,0 +1,143 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use super::{EchoRequest, RequestStatus};
+
+/// Summary of the requests sent by an echo service, used to evaluate the network between the
+/// service and its peers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EchoServiceStats {
+    /// Number of requests that have been created, whether or not they have been sent
+    pub requests: u64,
+    /// Number of requests that have been sent to a peer
+    pub sent: u64,
+    /// Number of sent requests that have received a response
+    pub acknowledged: u64,
+    /// Number of errors injected in place of sending a request
+    pub errors: u64,
+    /// Total size, in bytes, of the payloads of all sent requests
+    pub bytes_sent: u64,
+    /// Average time between sending a request and receiving its response
+    pub average_round_trip: Option<Duration>,
+    /// Longest time between sending a request and receiving its response
+    pub max_round_trip: Option<Duration>,
+}
+
+impl EchoServiceStats {
+    pub(crate) fn from_requests(requests: &[EchoRequest], errors: u64) -> Self {
+        let mut stats = EchoServiceStats {
+            requests: requests.len() as u64,
+            errors,
+            ..Default::default()
+        };
+
+        let mut round_trips = vec![];
+        for request in requests {
+            if let RequestStatus::Sent = request.sent {
+                stats.sent += 1;
+                stats.bytes_sent += request.message.len() as u64;
+            }
+
+            if let RequestStatus::Sent = request.ack {
+                stats.acknowledged += 1;
+                if let (Some(sent_at), Some(ack_at)) = (request.sent_at, request.ack_at) {
+                    // timestamps are stored in seconds; clamp clock skew between sent and ack
+                    round_trips.push(Duration::from_secs((ack_at - sent_at).max(0) as u64));
+                }
+            }
+        }
+
+        if !round_trips.is_empty() {
+            let total: Duration = round_trips.iter().sum();
+            stats.average_round_trip = Some(total / round_trips.len() as u32);
+            stats.max_round_trip = round_trips.into_iter().max();
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use splinter::service::{FullyQualifiedServiceId, ServiceId};
+
+    fn request(
+        correlation_id: i64,
+        message: &str,
+        sent_at: Option<i64>,
+        ack_at: Option<i64>,
+    ) -> EchoRequest {
+        EchoRequest {
+            sender_service_id: FullyQualifiedServiceId::new_from_string("abcde-fghij::aa00")
+                .expect("creating FullyQualifiedServiceId from string 'abcde-fghij::aa00'"),
+            correlation_id,
+            receiver_service_id: ServiceId::new("bb00").expect("creating ServiceId 'bb00'"),
+            message: message.to_string(),
+            sent: match sent_at {
+                Some(_) => RequestStatus::Sent,
+                None => RequestStatus::NotSent,
+            },
+            sent_at,
+            ack: match ack_at {
+                Some(_) => RequestStatus::Sent,
+                None => RequestStatus::NotSent,
+            },
+            ack_at,
+        }
+    }
+
+    /// Verify that stats are calculated from requests in each state:
+    ///
+    /// 1. A request that has not been sent only counts towards the total
+    /// 2. A sent request counts its payload towards the bytes sent
+    /// 3. Acknowledged requests contribute their round trip times
+    #[test]
+    fn test_stats_from_requests() {
+        let requests = vec![
+            request(1, "unsent", None, None),
+            request(2, "pending", Some(100), None),
+            request(3, "ack", Some(100), Some(102)),
+            request(4, "ack", Some(100), Some(106)),
+        ];
+
+        let stats = EchoServiceStats::from_requests(&requests, 3);
+
+        assert_eq!(
+            stats,
+            EchoServiceStats {
+                requests: 4,
+                sent: 3,
+                acknowledged: 2,
+                errors: 3,
+                bytes_sent: 13,
+                average_round_trip: Some(Duration::from_secs(4)),
+                max_round_trip: Some(Duration::from_secs(6)),
+            }
+        );
+    }
+
+    /// Verify that a service that has not received any responses has no round trip times.
+    #[test]
+    fn test_stats_without_responses() {
+        let stats = EchoServiceStats::from_requests(&[request(1, "unsent", None, None)], 0);
+
+        assert_eq!(stats.requests, 1);
+        assert_eq!(stats.average_round_trip, None);
+        assert_eq!(stats.max_round_trip, None);
+    }
+}
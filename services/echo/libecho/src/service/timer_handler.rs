@@ -17,7 +17,7 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use log::error;
 
-use rand::Rng;
+use rand::{distributions::Alphanumeric, Rng};
 use splinter::{
     error::InternalError,
     service::{FullyQualifiedServiceId, MessageSender, TimerHandler},
@@ -50,12 +50,14 @@ impl TimerHandler for EchoTimerHandler {
     ) -> Result<(), InternalError> {
         // get the arguments for this service
         let service_args = self.store.get_service_arguments(&service)?;
-        let error_wait_time =
-            std::time::Duration::from_millis(((1.0 / service_args.error_rate()) * 1000.0) as u64);
+        let error_wait_time = get_error_wait_time(service_args.error_rate());
 
         for peer in service_args.peers() {
             let actual_jitter = get_jitter(service_args.jitter().as_secs())?; // collect all requests sent to this peer and find average jitter?
-            let message = "test";
+            let message = generate_payload(
+                service_args.min_payload_size(),
+                service_args.max_payload_size(),
+            );
             match self.store.get_last_sent(&service, peer)? {
                 // send a message to those who haven't received a message in
                 // frequency+actual_jitter
@@ -69,16 +71,16 @@ impl TimerHandler for EchoTimerHandler {
                             )
                         })?;
                     if time_to_add_request(time, actual_jitter, service_args.frequency())? {
-                        self.store.insert_request(&service, peer, message)?;
+                        self.store.insert_request(&service, peer, &message)?;
                     }
                 }
                 None => {
                     // the service hasn't been sent any messages yet
-                    let correlation_id = self.store.insert_request(&service, peer, message)?;
+                    let correlation_id = self.store.insert_request(&service, peer, &message)?;
                     sender.send(
                         peer,
                         EchoMessage::Request {
-                            message: message.to_string(),
+                            message,
                             correlation_id: correlation_id as u64,
                         },
                     )?;
@@ -95,7 +97,10 @@ impl TimerHandler for EchoTimerHandler {
 
         for unsent in unsent_requests {
             // determine whether to emulate an error
-            if Instant::now() > self.stamp + error_wait_time {
+            let inject_error = error_wait_time
+                .map(|wait_time| Instant::now() > self.stamp + wait_time)
+                .unwrap_or(false);
+            if inject_error {
                 let error_message = "test error";
                 let error_at = i64::try_from(
                     SystemTime::now()
@@ -155,6 +160,33 @@ fn get_jitter(jitter: u64) -> Result<i64, InternalError> {
     }
 }
 
+/// Returns how long to wait between injected errors for the given rate of errors per second, or
+/// `None` if errors should not be injected.
+fn get_error_wait_time(error_rate: f32) -> Option<Duration> {
+    let wait_secs = 1.0 / error_rate;
+    if error_rate > 0.0 && wait_secs.is_finite() {
+        Some(Duration::from_secs_f32(wait_secs))
+    } else {
+        None
+    }
+}
+
+/// Generates a random alphanumeric payload with a length between `min_size` and `max_size` bytes,
+/// inclusive.
+fn generate_payload(min_size: usize, max_size: usize) -> String {
+    let mut rng = rand::thread_rng();
+    let size = if min_size < max_size {
+        rng.gen_range(min_size..=max_size)
+    } else {
+        min_size
+    };
+
+    rng.sample_iter(&Alphanumeric)
+        .take(size)
+        .map(char::from)
+        .collect()
+}
+
 fn time_to_add_request(
     time_last_sent: SystemTime,
     jitter: i64,
@@ -216,4 +248,24 @@ mod tests {
             .expect("failed to get frequency");
         assert!(time_to_add);
     }
+
+    #[test]
+    fn test_get_error_wait_time() {
+        assert_eq!(get_error_wait_time(0.5), Some(Duration::from_secs(2)));
+        assert_eq!(get_error_wait_time(4.0), Some(Duration::from_millis(250)));
+        // a rate of zero disables error injection
+        assert_eq!(get_error_wait_time(0.0), None);
+    }
+
+    #[test]
+    fn test_generate_payload() {
+        for _ in 0..100 {
+            let payload = generate_payload(10, 20);
+            assert!(payload.len() >= 10);
+            assert!(payload.len() <= 20);
+        }
+
+        assert_eq!(generate_payload(16, 16).len(), 16);
+        assert!(generate_payload(0, 0).is_empty());
+    }
 }
@@ -34,6 +34,7 @@ use pool::ConnectionPool;
 
 use crate::service::EchoArguments;
 use crate::service::EchoRequest;
+use crate::service::EchoServiceStats;
 use crate::service::EchoServiceStatus;
 use crate::service::RequestStatus;
 
@@ -42,6 +43,7 @@ use super::EchoStore;
 use operations::add_service::AddServiceOperation as _;
 use operations::get_last_sent::GetLastSentOperation as _;
 use operations::get_service_arguments::GetServiceArgumentsOperation as _;
+use operations::get_service_stats::GetServiceStatsOperation as _;
 use operations::get_service_status::GetServiceStatusOperation as _;
 use operations::insert_request::InsertRequestOperation as _;
 use operations::insert_request_error::InsertRequestErrorOperation as _;
@@ -190,6 +192,14 @@ impl EchoStore for DieselEchoStore<SqliteConnection> {
         self.pool
             .execute_read(|conn| EchoStoreOperations::new(conn).get_service_status(service))
     }
+
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError> {
+        self.pool
+            .execute_read(|conn| EchoStoreOperations::new(conn).get_service_stats(service))
+    }
 }
 
 #[cfg(feature = "postgres")]
@@ -309,6 +319,14 @@ impl EchoStore for DieselEchoStore<PgConnection> {
         self.pool
             .execute_read(|conn| EchoStoreOperations::new(conn).get_service_status(service))
     }
+
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError> {
+        self.pool
+            .execute_read(|conn| EchoStoreOperations::new(conn).get_service_stats(service))
+    }
 }
 
 pub struct DieselConnectionEchoStore<'a, C>
@@ -437,6 +455,13 @@ impl<'a> EchoStore for DieselConnectionEchoStore<'a, SqliteConnection> {
     ) -> Result<EchoServiceStatus, InternalError> {
         EchoStoreOperations::new(self.connection).get_service_status(service)
     }
+
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError> {
+        EchoStoreOperations::new(self.connection).get_service_stats(service)
+    }
 }
 
 #[cfg(feature = "postgres")]
@@ -547,6 +572,13 @@ impl<'a> EchoStore for DieselConnectionEchoStore<'a, PgConnection> {
     ) -> Result<EchoServiceStatus, InternalError> {
         EchoStoreOperations::new(self.connection).get_service_status(service)
     }
+
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError> {
+        EchoStoreOperations::new(self.connection).get_service_stats(service)
+    }
 }
 
 #[cfg(all(test, feature = "sqlite"))]
@@ -557,6 +589,7 @@ pub mod tests {
     use std::time::SystemTime;
 
     use crate::migrations::run_sqlite_migrations;
+    use crate::service::EchoArgumentsBuilder;
 
     use diesel::{
         r2d2::{ConnectionManager, Pool},
@@ -1131,6 +1164,96 @@ pub mod tests {
         assert_eq!(status, EchoServiceStatus::Prepared)
     }
 
+    #[test]
+    fn echo_store_sqlite_get_service_args_payload_size() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselEchoStore::new(pool);
+
+        let fqsi = FullyQualifiedServiceId::new_from_string("abcde-fghij::aa00")
+            .expect("creating FullyQualifiedServiceId from string 'abcde-fghij::aa00'");
+
+        let echo_args = EchoArgumentsBuilder::new()
+            .with_peers(vec![ServiceId::new_random()])
+            .with_min_payload_size(64)
+            .with_max_payload_size(4096)
+            .build()
+            .expect("failed to create echo arguments");
+
+        store
+            .add_service(&fqsi, &echo_args)
+            .expect("failed to add echo service");
+
+        let service_args = store
+            .get_service_arguments(&fqsi)
+            .expect("failed to get service args");
+
+        assert_eq!(service_args.min_payload_size(), 64);
+        assert_eq!(service_args.max_payload_size(), 4096);
+    }
+
+    #[test]
+    fn echo_store_sqlite_get_service_stats() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselEchoStore::new(pool);
+
+        let fqsi = FullyQualifiedServiceId::new_from_string("abcde-fghij::aa00")
+            .expect("creating FullyQualifiedServiceId from string 'abcde-fghij::aa00'");
+
+        let peer_service = ServiceId::new_random();
+
+        let echo_args = EchoArguments::new(
+            vec![peer_service.clone()],
+            std::time::Duration::from_secs(2),
+            std::time::Duration::from_secs(1),
+            0.5,
+        )
+        .expect("failed to create echo arguments");
+
+        assert_eq!(
+            store
+                .get_service_stats(&fqsi)
+                .expect("failed to get service stats"),
+            None
+        );
+
+        store
+            .add_service(&fqsi, &echo_args)
+            .expect("failed to add echo service");
+
+        let correlation_id = store
+            .insert_request(&fqsi, &peer_service, "payload")
+            .expect("failed to insert request");
+        store
+            .insert_request(&fqsi, &peer_service, "unsent")
+            .expect("failed to insert request");
+        store
+            .update_request_sent(&fqsi, correlation_id as i64, RequestStatus::Sent, Some(10))
+            .expect("failed to update request sent");
+        store
+            .update_request_ack(&fqsi, correlation_id as i64, RequestStatus::Sent, Some(13))
+            .expect("failed to update request ack");
+        store
+            .insert_request_error(&fqsi, "test error", 11)
+            .expect("failed to insert request error");
+
+        let stats = store
+            .get_service_stats(&fqsi)
+            .expect("failed to get service stats")
+            .expect("service stats not found");
+
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.sent, 1);
+        assert_eq!(stats.acknowledged, 1);
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.bytes_sent, 7);
+        assert_eq!(
+            stats.average_round_trip,
+            Some(std::time::Duration::from_secs(3))
+        );
+    }
+
     fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
         let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
         let pool = Pool::builder()
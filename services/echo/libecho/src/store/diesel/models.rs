@@ -43,6 +43,8 @@ pub(crate) struct EchoService {
     pub jitter: Option<i64>,
     pub error_rate: Option<f32>,
     pub status: EchoServiceStatusModel,
+    pub min_payload_size: Option<i64>,
+    pub max_payload_size: Option<i64>,
 }
 
 #[repr(i16)]
@@ -63,6 +63,8 @@ impl<'a> AddServiceOperation for EchoStoreOperations<'a, SqliteConnection> {
                 jitter: Some(arguments.jitter().as_millis() as i64),
                 error_rate: Some(arguments.error_rate()),
                 status: EchoServiceStatusModel::Prepared,
+                min_payload_size: Some(arguments.min_payload_size() as i64),
+                max_payload_size: Some(arguments.max_payload_size() as i64),
             };
 
             insert_into(echo_services::table)
@@ -119,6 +121,8 @@ impl<'a> AddServiceOperation for EchoStoreOperations<'a, PgConnection> {
                 jitter: Some(arguments.jitter().as_millis() as i64),
                 error_rate: Some(arguments.error_rate()),
                 status: EchoServiceStatusModel::Prepared,
+                min_payload_size: Some(arguments.min_payload_size() as i64),
+                max_payload_size: Some(arguments.max_payload_size() as i64),
             };
 
             insert_into(echo_services::table)
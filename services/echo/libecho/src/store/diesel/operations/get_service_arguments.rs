@@ -15,7 +15,7 @@
 use diesel::{prelude::*, result::Error::NotFound};
 use splinter::{error::InternalError, service::FullyQualifiedServiceId, service::ServiceId};
 
-use crate::service::EchoArguments;
+use crate::service::{EchoArguments, EchoArgumentsBuilder};
 use crate::store::diesel::{
     models::{EchoPeer, EchoService},
     schema::{echo_peers, echo_services},
@@ -76,13 +76,25 @@ where
                 echo_service.jitter,
                 echo_service.error_rate,
             ) {
-                (Some(frequency), Some(jitter), Some(error_rate)) => EchoArguments::new(
-                    peers,
-                    std::time::Duration::from_millis(frequency as u64),
-                    std::time::Duration::from_millis(jitter as u64),
-                    error_rate,
-                )
-                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                (Some(frequency), Some(jitter), Some(error_rate)) => {
+                    let mut builder = EchoArgumentsBuilder::new()
+                        .with_peers(peers)
+                        .with_frequency(std::time::Duration::from_millis(frequency as u64))
+                        .with_jitter(std::time::Duration::from_millis(jitter as u64))
+                        .with_error_rate(error_rate);
+
+                    // services added before payload sizes were configurable leave these unset
+                    if let Some(min_payload_size) = echo_service.min_payload_size {
+                        builder = builder.with_min_payload_size(min_payload_size as usize);
+                    }
+                    if let Some(max_payload_size) = echo_service.max_payload_size {
+                        builder = builder.with_max_payload_size(max_payload_size as usize);
+                    }
+
+                    builder
+                        .build()
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?
+                }
                 _ => {
                     return Err(InternalError::with_message(format!(
                         "Failed to get service arguments, service {} contains unset values",
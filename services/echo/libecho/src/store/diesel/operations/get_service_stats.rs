@@ -0,0 +1,79 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::count_star, prelude::*};
+use splinter::{error::InternalError, service::FullyQualifiedServiceId};
+
+use crate::service::{EchoRequest, EchoServiceStats};
+use crate::store::diesel::{
+    models::EchoRequest as EchoRequestModel,
+    schema::{echo_request_errors, echo_requests, echo_services},
+};
+
+use super::EchoStoreOperations;
+
+pub(in crate::store::diesel) trait GetServiceStatsOperation {
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError>;
+}
+
+impl<'a, C> GetServiceStatsOperation for EchoStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+{
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError> {
+        self.conn.transaction::<_, _, _>(|| {
+            let service_count = echo_services::table
+                .filter(echo_services::service_id.eq(format!("{}", service)))
+                .select(count_star())
+                .first::<i64>(self.conn)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+            if service_count == 0 {
+                return Ok(None);
+            }
+
+            let requests = echo_requests::table
+                .filter(echo_requests::sender_service_id.eq(format!("{}", service)))
+                .select(echo_requests::all_columns)
+                .load::<EchoRequestModel>(self.conn)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?
+                .into_iter()
+                .map(EchoRequest::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+            let errors = echo_request_errors::table
+                .filter(echo_request_errors::service_id.eq(format!("{}", service)))
+                .select(count_star())
+                .first::<i64>(self.conn)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+            Ok(Some(EchoServiceStats::from_requests(
+                &requests,
+                errors as u64,
+            )))
+        })
+    }
+}
@@ -15,6 +15,7 @@
 pub(super) mod add_service;
 pub(super) mod get_last_sent;
 pub(super) mod get_service_arguments;
+pub(super) mod get_service_stats;
 pub(super) mod get_service_status;
 pub(super) mod insert_request;
 pub(super) mod insert_request_error;
@@ -48,6 +48,8 @@ table! {
         jitter -> Nullable<BigInt>,
         error_rate -> Nullable<Float>,
         status -> SmallInt,
+        min_payload_size -> Nullable<BigInt>,
+        max_payload_size -> Nullable<BigInt>,
     }
 }
 
@@ -19,7 +19,9 @@ mod factory;
 
 use splinter::{error::InternalError, service::FullyQualifiedServiceId, service::ServiceId};
 
-use crate::service::{EchoArguments, EchoRequest, EchoServiceStatus, RequestStatus};
+use crate::service::{
+    EchoArguments, EchoRequest, EchoServiceStats, EchoServiceStatus, RequestStatus,
+};
 
 #[cfg(any(feature = "sqlite", feature = "postgres"))]
 pub use self::diesel::DieselEchoStore;
@@ -102,4 +104,11 @@ pub trait EchoStore {
         &self,
         service: &FullyQualifiedServiceId,
     ) -> Result<EchoServiceStatus, InternalError>;
+
+    /// Returns the request statistics of the given service, or `None` if the service does not
+    /// exist.
+    fn get_service_stats(
+        &self,
+        service: &FullyQualifiedServiceId,
+    ) -> Result<Option<EchoServiceStats>, InternalError>;
 }
@@ -0,0 +1,27 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splinter Relay is a service that forwards opaque payloads between exactly two services on a
+//! circuit.
+//!
+//! Relay services keep no persistent state: they provide secure transport for applications that
+//! only need to exchange messages between two circuit members. See the [`service`] module for
+//! details on the delivery guarantees.
+
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde;
+
+pub mod service;
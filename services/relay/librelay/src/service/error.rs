@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+use splinter::service::instance::ServiceSendError;
+
+/// Errors that may be returned by the relay service.
+#[derive(Debug)]
+pub enum RelayError {
+    /// The service has not been started, so it is not connected to the network
+    NotConnected,
+    /// A lock used by the service was poisoned
+    PoisonedLock(String),
+    /// A message could not be sent to the peer service
+    SendError(ServiceSendError),
+    /// A message could not be serialized
+    SerializationError(serde_json::Error),
+}
+
+impl Error for RelayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RelayError::NotConnected => None,
+            RelayError::PoisonedLock(_) => None,
+            RelayError::SendError(err) => Some(err),
+            RelayError::SerializationError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for RelayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RelayError::NotConnected => f.write_str("relay service is not connected"),
+            RelayError::PoisonedLock(msg) => write!(f, "lock poisoned: {}", msg),
+            RelayError::SendError(err) => write!(f, "failed to send message: {}", err),
+            RelayError::SerializationError(err) => {
+                write!(f, "failed to serialize message: {}", err)
+            }
+        }
+    }
+}
+
+impl From<ServiceSendError> for RelayError {
+    fn from(err: ServiceSendError) -> Self {
+        RelayError::SendError(err)
+    }
+}
+
+impl From<serde_json::Error> for RelayError {
+    fn from(err: serde_json::Error) -> Self {
+        RelayError::SerializationError(err)
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use splinter::error::InvalidArgumentError;
+use splinter::service::instance::{
+    FactoryCreateError, OrchestratableService, OrchestratableServiceFactory, ServiceArgValidator,
+    ServiceFactory, ServiceInstance,
+};
+
+use super::{RelayService, DEFAULT_RESEND_INTERVAL, SERVICE_TYPE};
+
+/// Validates the arguments of relay services in circuit proposals.
+pub struct RelayArgValidator;
+
+impl ServiceArgValidator for RelayArgValidator {
+    fn validate(&self, args: &HashMap<String, String>) -> Result<(), InvalidArgumentError> {
+        parse_peer_service(args).map_err(|msg| InvalidArgumentError::new("peer_services", msg))?;
+        parse_resend_interval(args)
+            .map_err(|msg| InvalidArgumentError::new("resend_interval", msg))?;
+
+        Ok(())
+    }
+}
+
+/// Creates relay services.
+pub struct RelayServiceFactory {
+    service_types: Vec<String>,
+}
+
+impl Default for RelayServiceFactory {
+    fn default() -> Self {
+        Self {
+            service_types: vec![SERVICE_TYPE.into()],
+        }
+    }
+}
+
+impl RelayServiceFactory {
+    fn create_relay(
+        &self,
+        service_id: String,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<RelayService, FactoryCreateError> {
+        let peer_service_id = parse_peer_service(&args).map_err(|msg| {
+            FactoryCreateError::InvalidArguments(format!("invalid peer_services: {}", msg))
+        })?;
+        let resend_interval = parse_resend_interval(&args).map_err(|msg| {
+            FactoryCreateError::InvalidArguments(format!("invalid resend_interval: {}", msg))
+        })?;
+
+        Ok(RelayService::new(
+            service_id,
+            circuit_id,
+            peer_service_id,
+            resend_interval,
+        ))
+    }
+}
+
+impl ServiceFactory for RelayServiceFactory {
+    fn available_service_types(&self) -> &[String] {
+        self.service_types.as_slice()
+    }
+
+    /// `args` must include the following:
+    /// - `peer_services`: the other relay service on the same circuit that payloads are forwarded
+    ///   to, formatted as a serialized JSON array of strings or a comma-separated list containing
+    ///   exactly one service ID
+    ///
+    /// `args` may include the following optional entries:
+    /// - `resend_interval`: the length of time (in milliseconds) to wait before resending payloads
+    ///   that have not been acknowledged (if not provided, default is 5 seconds)
+    fn create(
+        &self,
+        service_id: String,
+        _service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn ServiceInstance>, FactoryCreateError> {
+        Ok(Box::new(self.create_relay(service_id, circuit_id, args)?))
+    }
+}
+
+impl OrchestratableServiceFactory for RelayServiceFactory {
+    fn create_orchestratable_service(
+        &self,
+        service_id: String,
+        _service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn OrchestratableService>, FactoryCreateError> {
+        Ok(Box::new(self.create_relay(service_id, circuit_id, args)?))
+    }
+}
+
+/// Parse the `peer_services` argument, which must name exactly one peer.
+fn parse_peer_service(args: &HashMap<String, String>) -> Result<String, String> {
+    let peer_services = args
+        .get("peer_services")
+        .ok_or_else(|| "argument not provided".to_string())?;
+
+    let mut peer_services = if peer_services.starts_with('[') {
+        serde_json::from_str::<Vec<String>>(peer_services)
+            .map_err(|err| format!("failed to parse list: {}", err))?
+    } else {
+        peer_services.split(',').map(String::from).collect()
+    };
+
+    match (peer_services.pop(), peer_services.is_empty()) {
+        (Some(peer), true) if !peer.is_empty() => Ok(peer),
+        _ => Err("a relay must have exactly one peer service".into()),
+    }
+}
+
+/// Parse the optional `resend_interval` argument, given in milliseconds.
+fn parse_resend_interval(args: &HashMap<String, String>) -> Result<Duration, String> {
+    match args.get("resend_interval") {
+        Some(interval) => match interval.parse::<u64>() {
+            Ok(0) => Err("must be greater than 0".into()),
+            Ok(millis) => Ok(Duration::from_millis(millis)),
+            Err(err) => Err(format!("failed to parse: {}", err)),
+        },
+        None => Ok(DEFAULT_RESEND_INTERVAL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the relay argument validator requires exactly one peer service and a positive
+    /// resend interval.
+    #[test]
+    fn validate_arguments() {
+        let validator = RelayArgValidator;
+
+        let mut args = HashMap::new();
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("peer_services".to_string(), "".to_string());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("peer_services".to_string(), "a000,b000".to_string());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("peer_services".to_string(), "[\"b000\"]".to_string());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("resend_interval".to_string(), "0".to_string());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("resend_interval".to_string(), "250".to_string());
+        assert!(validator.validate(&args).is_ok());
+    }
+
+    /// Verify that the factory creates a relay service with the configured peer.
+    #[test]
+    fn create_relay_service() {
+        let factory = RelayServiceFactory::default();
+        assert_eq!(
+            factory.available_service_types(),
+            &[SERVICE_TYPE.to_string()]
+        );
+
+        let mut args = HashMap::new();
+        args.insert("peer_services".to_string(), "b000".to_string());
+        let service = factory
+            .create_relay("a000".into(), "abcde-01234", args)
+            .expect("Failed to create relay service");
+
+        assert_eq!(service.service_id(), "a000");
+        assert_eq!(service.circuit_id(), "abcde-01234");
+        assert_eq!(service.peer_service_id(), "b000");
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Messages exchanged between relay services.
+
+/// A message sent between the two services of a relay.
+///
+/// Every message carries the `session` of the service that originated the payload. A service picks
+/// a new session each time it is created, which lets its peer tell a restarted sender's payload IDs
+/// apart from the ones it has already received.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelayMessage {
+    /// An opaque payload submitted to the sending service
+    Payload {
+        session: u64,
+        id: u64,
+        payload: Vec<u8>,
+    },
+    /// Acknowledges receipt of the payload with the given session and ID
+    Ack { session: u64, id: u64 },
+}
+
+impl RelayMessage {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that relay messages survive a round trip through their byte representation.
+    #[test]
+    fn message_round_trip() {
+        let payload = RelayMessage::Payload {
+            session: 7,
+            id: 1,
+            payload: b"hello".to_vec(),
+        };
+        let bytes = payload.to_bytes().expect("Failed to serialize payload");
+        assert_eq!(
+            RelayMessage::from_bytes(&bytes).expect("Failed to deserialize payload"),
+            payload
+        );
+
+        let ack = RelayMessage::Ack { session: 7, id: 1 };
+        let bytes = ack.to_bytes().expect("Failed to serialize ack");
+        assert_eq!(
+            RelayMessage::from_bytes(&bytes).expect("Failed to deserialize ack"),
+            ack
+        );
+    }
+}
@@ -0,0 +1,546 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The relay service forwards opaque payloads between exactly two services on a circuit.
+//!
+//! A payload submitted to a relay service is sent to its peer and kept until the peer acknowledges
+//! it. Unacknowledged payloads are resent periodically, so delivery is at-least-once. The receiving
+//! service acknowledges every copy of a payload but only makes the first one available to be
+//! polled, so applications see each payload once.
+
+mod error;
+mod factory;
+mod message;
+mod shared;
+
+use std::any::Any;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{Builder, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use splinter::error::InternalError;
+use splinter::service::instance::{
+    OrchestratableService, ServiceDestroyError, ServiceError, ServiceInstance,
+    ServiceMessageContext, ServiceNetworkRegistry, ServiceNetworkSender, ServiceStartError,
+    ServiceStopError,
+};
+
+pub use error::RelayError;
+pub use factory::{RelayArgValidator, RelayServiceFactory};
+pub use shared::ReceivedPayload;
+
+use message::RelayMessage;
+use shared::{Receipt, RelayShared};
+
+/// The service type of relay services
+pub const SERVICE_TYPE: &str = "relay";
+
+/// The version of the relay service's REST API
+pub const RELAY_PROTOCOL_VERSION: u32 = 1;
+
+/// The default interval at which unacknowledged payloads are resent
+pub const DEFAULT_RESEND_INTERVAL: Duration = Duration::from_secs(5);
+
+type NetworkSender = Arc<Mutex<Option<Box<dyn ServiceNetworkSender>>>>;
+
+/// A service that forwards opaque payloads to a single peer service.
+#[derive(Clone)]
+pub struct RelayService {
+    service_id: String,
+    circuit_id: String,
+    peer_service_id: String,
+    resend_interval: Duration,
+    shared: Arc<Mutex<RelayShared>>,
+    network_sender: NetworkSender,
+    resender: Arc<Mutex<Option<Resender>>>,
+}
+
+impl RelayService {
+    /// Creates a new relay service that forwards payloads to `peer_service_id`.
+    pub fn new(
+        service_id: String,
+        circuit_id: &str,
+        peer_service_id: String,
+        resend_interval: Duration,
+    ) -> Self {
+        // A new session for every instance lets the peer tell this instance's payload IDs apart
+        // from those of a previous instance with the same service ID
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self {
+            service_id,
+            circuit_id: circuit_id.into(),
+            peer_service_id,
+            resend_interval,
+            shared: Arc::new(Mutex::new(RelayShared::new(session))),
+            network_sender: Arc::new(Mutex::new(None)),
+            resender: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the ID of the circuit this service belongs to.
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the ID of the service that payloads are forwarded to.
+    pub fn peer_service_id(&self) -> &str {
+        &self.peer_service_id
+    }
+
+    /// Submits a payload to be forwarded to the peer service and returns the payload's ID.
+    ///
+    /// The payload is sent immediately; if sending fails, it will be retried with the other
+    /// unacknowledged payloads.
+    pub fn submit(&self, payload: Vec<u8>) -> Result<u64, RelayError> {
+        let mut shared = self
+            .shared
+            .lock()
+            .map_err(|_| RelayError::PoisonedLock("shared lock poisoned".into()))?;
+        let network_sender = self
+            .network_sender
+            .lock()
+            .map_err(|_| RelayError::PoisonedLock("network sender lock poisoned".into()))?;
+        let network_sender = network_sender.as_ref().ok_or(RelayError::NotConnected)?;
+
+        let id = shared.add_outgoing(payload.clone());
+        let bytes = RelayMessage::Payload {
+            session: shared.session(),
+            id,
+            payload,
+        }
+        .to_bytes()?;
+        if let Err(err) = network_sender.send(&self.peer_service_id, &bytes) {
+            warn!(
+                "Failed to send payload {} to {}, it will be resent: {}",
+                id, self.peer_service_id, err
+            );
+        }
+
+        Ok(id)
+    }
+
+    /// Returns the payloads received from the peer service with a sequence number greater than
+    /// `since`.
+    ///
+    /// Received payloads with a sequence number less than or equal to `since` are considered
+    /// consumed and are dropped by the service.
+    pub fn received_since(&self, since: u64) -> Result<Vec<ReceivedPayload>, RelayError> {
+        Ok(self
+            .shared
+            .lock()
+            .map_err(|_| RelayError::PoisonedLock("shared lock poisoned".into()))?
+            .received_since(since))
+    }
+
+    /// Returns whether the payload with the given ID has been acknowledged by the peer service.
+    pub fn is_delivered(&self, id: u64) -> Result<bool, RelayError> {
+        Ok(self
+            .shared
+            .lock()
+            .map_err(|_| RelayError::PoisonedLock("shared lock poisoned".into()))?
+            .is_delivered(id))
+    }
+
+    /// Returns the number of submitted payloads that have not been acknowledged.
+    pub fn pending_count(&self) -> Result<usize, RelayError> {
+        Ok(self
+            .shared
+            .lock()
+            .map_err(|_| RelayError::PoisonedLock("shared lock poisoned".into()))?
+            .pending_count())
+    }
+
+    fn handle_relay_message(&self, message: RelayMessage, sender: &str) -> Result<(), RelayError> {
+        let mut shared = self
+            .shared
+            .lock()
+            .map_err(|_| RelayError::PoisonedLock("shared lock poisoned".into()))?;
+
+        match message {
+            RelayMessage::Payload {
+                session,
+                id,
+                payload,
+            } => {
+                if shared.receive(session, id, payload) == Receipt::Duplicate {
+                    debug!("Received duplicate payload {} from {}", id, sender);
+                }
+
+                let ack = RelayMessage::Ack { session, id }.to_bytes()?;
+                self.network_sender
+                    .lock()
+                    .map_err(|_| RelayError::PoisonedLock("network sender lock poisoned".into()))?
+                    .as_ref()
+                    .ok_or(RelayError::NotConnected)?
+                    .send(sender, &ack)?;
+            }
+            RelayMessage::Ack { session, id } => shared.acknowledge(session, id),
+        }
+
+        Ok(())
+    }
+}
+
+impl ServiceInstance for RelayService {
+    fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    fn service_type(&self) -> &str {
+        SERVICE_TYPE
+    }
+
+    fn start(
+        &mut self,
+        service_registry: &dyn ServiceNetworkRegistry,
+    ) -> Result<(), ServiceStartError> {
+        let mut resender = self
+            .resender
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("resender lock poisoned".into()))?;
+
+        if resender.is_some() {
+            return Err(ServiceStartError::AlreadyStarted);
+        }
+
+        self.network_sender
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("network sender lock poisoned".into()))?
+            .replace(service_registry.connect(self.service_id())?);
+
+        resender.replace(Resender::start(
+            &self.service_id,
+            self.peer_service_id.clone(),
+            self.resend_interval,
+            self.shared.clone(),
+            self.network_sender.clone(),
+        )?);
+
+        Ok(())
+    }
+
+    fn stop(
+        &mut self,
+        service_registry: &dyn ServiceNetworkRegistry,
+    ) -> Result<(), ServiceStopError> {
+        debug!("Stopping relay service with id {}", self.service_id);
+
+        self.resender
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("resender lock poisoned".into()))?
+            .take()
+            .ok_or(ServiceStopError::NotStarted)?
+            .shutdown();
+
+        self.network_sender
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("network sender lock poisoned".into()))?
+            .take()
+            .ok_or_else(|| ServiceStopError::Internal(Box::new(RelayError::NotConnected)))?;
+
+        service_registry.disconnect(self.service_id())?;
+
+        Ok(())
+    }
+
+    fn destroy(self: Box<Self>) -> Result<(), ServiceDestroyError> {
+        if self
+            .resender
+            .lock()
+            .map_err(|_| ServiceDestroyError::PoisonedLock("resender lock poisoned".into()))?
+            .is_some()
+        {
+            Err(ServiceDestroyError::NotStopped)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn purge(&mut self) -> Result<(), InternalError> {
+        // Relay services do not persist any state
+        Ok(())
+    }
+
+    fn handle_message(
+        &self,
+        message_bytes: &[u8],
+        message_context: &ServiceMessageContext,
+    ) -> Result<(), ServiceError> {
+        if message_context.sender != self.peer_service_id {
+            return Err(ServiceError::UnableToHandleMessage(Box::new(
+                InternalError::with_message(format!(
+                    "relay service {} only accepts messages from {}, not {}",
+                    self.service_id, self.peer_service_id, message_context.sender
+                )),
+            )));
+        }
+
+        let message = RelayMessage::from_bytes(message_bytes)
+            .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+        self.handle_relay_message(message, &message_context.sender)
+            .map_err(|err| match err {
+                RelayError::NotConnected => ServiceError::NotStarted,
+                RelayError::PoisonedLock(msg) => ServiceError::PoisonedLock(msg),
+                RelayError::SendError(err) => ServiceError::UnableToSendMessage(Box::new(err)),
+                err => ServiceError::UnableToHandleMessage(Box::new(err)),
+            })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl OrchestratableService for RelayService {
+    fn clone_box(&self) -> Box<dyn OrchestratableService> {
+        Box::new(self.clone())
+    }
+
+    fn as_service(&self) -> &dyn ServiceInstance {
+        self
+    }
+}
+
+/// Periodically resends the payloads that have not been acknowledged by the peer service.
+struct Resender {
+    shutdown_sender: Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl Resender {
+    fn start(
+        service_id: &str,
+        peer_service_id: String,
+        interval: Duration,
+        shared: Arc<Mutex<RelayShared>>,
+        network_sender: NetworkSender,
+    ) -> Result<Self, ServiceStartError> {
+        let (shutdown_sender, shutdown_receiver) = channel();
+
+        let join_handle = Builder::new()
+            .name(format!("Relay Resender {}", service_id))
+            .spawn(move || loop {
+                match shutdown_receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Err(err) =
+                            resend_unacknowledged(&peer_service_id, &shared, &network_sender)
+                        {
+                            error!("Failed to resend relay payloads: {}", err);
+                        }
+                    }
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to start resender thread: {}", err))
+            })?;
+
+        Ok(Self {
+            shutdown_sender,
+            join_handle,
+        })
+    }
+
+    fn shutdown(self) {
+        if self.shutdown_sender.send(()).is_err() {
+            warn!("Relay resender thread already exited");
+        }
+        if self.join_handle.join().is_err() {
+            error!("Relay resender thread panicked");
+        }
+    }
+}
+
+fn resend_unacknowledged(
+    peer_service_id: &str,
+    shared: &Mutex<RelayShared>,
+    network_sender: &NetworkSender,
+) -> Result<(), RelayError> {
+    let shared = shared
+        .lock()
+        .map_err(|_| RelayError::PoisonedLock("shared lock poisoned".into()))?;
+    let network_sender = network_sender
+        .lock()
+        .map_err(|_| RelayError::PoisonedLock("network sender lock poisoned".into()))?;
+    let network_sender = network_sender.as_ref().ok_or(RelayError::NotConnected)?;
+
+    for (id, payload) in shared.unacknowledged() {
+        let bytes = RelayMessage::Payload {
+            session: shared.session(),
+            id: *id,
+            payload: payload.clone(),
+        }
+        .to_bytes()?;
+        network_sender.send(peer_service_id, &bytes)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use splinter::service::instance::{
+        ServiceConnectionError, ServiceDisconnectionError, ServiceSendError,
+    };
+
+    type Sent = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+    /// Verify that a payload submitted to one relay service is delivered once to its peer and
+    /// removed from the sender's outbox when it is acknowledged.
+    ///
+    /// 1. Start two relay services that are peers of each other
+    /// 2. Submit a payload to the first service and deliver the sent message to the second
+    /// 3. Deliver the same message again, simulating a resend
+    /// 4. Verify the second service has the payload once and acknowledged both copies
+    /// 5. Deliver the acknowledgment to the first service and verify the payload is delivered
+    #[test]
+    fn relay_payload_between_peers() {
+        let a_sent = Sent::default();
+        let b_sent = Sent::default();
+
+        let mut service_a = RelayService::new(
+            "a000".into(),
+            "abcde-01234",
+            "b000".into(),
+            Duration::from_secs(60),
+        );
+        let mut service_b = RelayService::new(
+            "b000".into(),
+            "abcde-01234",
+            "a000".into(),
+            Duration::from_secs(60),
+        );
+        service_a
+            .start(&MockRegistry(a_sent.clone()))
+            .expect("Failed to start service a");
+        service_b
+            .start(&MockRegistry(b_sent.clone()))
+            .expect("Failed to start service b");
+
+        let id = service_a
+            .submit(b"hello".to_vec())
+            .expect("Failed to submit payload");
+        assert_eq!(service_a.pending_count().expect("Failed to get count"), 1);
+
+        let (recipient, message) = a_sent.lock().unwrap().remove(0);
+        assert_eq!(recipient, "b000");
+        service_b
+            .handle_message(&message, &context("a000"))
+            .expect("Failed to handle payload");
+        service_b
+            .handle_message(&message, &context("a000"))
+            .expect("Failed to handle resent payload");
+
+        let received = service_b
+            .received_since(0)
+            .expect("Failed to get received payloads");
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].payload(), b"hello");
+
+        let acks = b_sent.lock().unwrap().drain(..).collect::<Vec<_>>();
+        assert_eq!(acks.len(), 2);
+        service_a
+            .handle_message(&acks[0].1, &context("b000"))
+            .expect("Failed to handle ack");
+        assert!(service_a
+            .is_delivered(id)
+            .expect("Failed to check delivery"));
+        assert_eq!(service_a.pending_count().expect("Failed to get count"), 0);
+
+        // messages from services other than the peer are rejected
+        assert!(service_a
+            .handle_message(&acks[1].1, &context("c000"))
+            .is_err());
+
+        service_a
+            .stop(&MockRegistry(a_sent))
+            .expect("Failed to stop service a");
+        service_b
+            .stop(&MockRegistry(b_sent))
+            .expect("Failed to stop service b");
+    }
+
+    fn context(sender: &str) -> ServiceMessageContext {
+        ServiceMessageContext {
+            sender: sender.into(),
+            circuit: "abcde-01234".into(),
+            correlation_id: "correlation".into(),
+        }
+    }
+
+    struct MockRegistry(Sent);
+
+    impl ServiceNetworkRegistry for MockRegistry {
+        fn connect(
+            &self,
+            _service_id: &str,
+        ) -> Result<Box<dyn ServiceNetworkSender>, ServiceConnectionError> {
+            Ok(Box::new(MockSender(self.0.clone())))
+        }
+
+        fn disconnect(&self, _service_id: &str) -> Result<(), ServiceDisconnectionError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockSender(Sent);
+
+    impl ServiceNetworkSender for MockSender {
+        fn send(&self, recipient: &str, message: &[u8]) -> Result<(), ServiceSendError> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((recipient.to_string(), message.to_vec()));
+            Ok(())
+        }
+
+        fn send_and_await(
+            &self,
+            _recipient: &str,
+            _message: &[u8],
+        ) -> Result<Vec<u8>, ServiceSendError> {
+            unimplemented!()
+        }
+
+        fn reply(
+            &self,
+            _message_origin: &ServiceMessageContext,
+            _message: &[u8],
+        ) -> Result<(), ServiceSendError> {
+            unimplemented!()
+        }
+
+        fn clone_box(&self) -> Box<dyn ServiceNetworkSender> {
+            Box::new(self.clone())
+        }
+
+        fn send_with_sender(
+            &mut self,
+            _recipient: &str,
+            _message: &[u8],
+            _sender: &str,
+        ) -> Result<(), ServiceSendError> {
+            unimplemented!()
+        }
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracks the payloads a relay service has sent and received.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// A payload that was received from the peer service.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceivedPayload {
+    seq: u64,
+    payload: Vec<u8>,
+}
+
+impl ReceivedPayload {
+    /// The position of this payload in the order it was received by the local service
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// The opaque bytes submitted to the peer service
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+/// The outcome of receiving a payload from the peer service.
+#[derive(Debug, PartialEq)]
+pub(super) enum Receipt {
+    /// The payload had not been received before and was added to the inbox
+    New,
+    /// The payload was already received; it must be acknowledged again, but not delivered
+    Duplicate,
+}
+
+pub(super) struct RelayShared {
+    /// The session used for payloads sent by this service
+    session: u64,
+    /// The ID that will be assigned to the next submitted payload
+    next_id: u64,
+    /// Submitted payloads that have not been acknowledged by the peer
+    outbox: BTreeMap<u64, Vec<u8>>,
+    /// The session of the peer's payloads that are being tracked
+    peer_session: Option<u64>,
+    /// All of the peer's payloads up to and including this ID have been received
+    received_through: u64,
+    /// IDs of the peer's payloads received after a gap in `received_through`
+    received_ahead: BTreeSet<u64>,
+    /// The sequence number that will be assigned to the next received payload
+    next_seq: u64,
+    /// Received payloads that have not been consumed
+    inbox: VecDeque<ReceivedPayload>,
+}
+
+impl RelayShared {
+    pub fn new(session: u64) -> Self {
+        Self {
+            session,
+            next_id: 1,
+            outbox: BTreeMap::new(),
+            peer_session: None,
+            received_through: 0,
+            received_ahead: BTreeSet::new(),
+            next_seq: 1,
+            inbox: VecDeque::new(),
+        }
+    }
+
+    pub fn session(&self) -> u64 {
+        self.session
+    }
+
+    /// Adds a payload to the outbox and returns its ID.
+    pub fn add_outgoing(&mut self, payload: Vec<u8>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.outbox.insert(id, payload);
+        id
+    }
+
+    /// Returns the payloads that have not yet been acknowledged, in submission order.
+    pub fn unacknowledged(&self) -> impl Iterator<Item = (&u64, &Vec<u8>)> {
+        self.outbox.iter()
+    }
+
+    /// Returns the number of payloads that have not yet been acknowledged.
+    pub fn pending_count(&self) -> usize {
+        self.outbox.len()
+    }
+
+    /// Returns whether the payload with the given ID has been acknowledged by the peer.
+    pub fn is_delivered(&self, id: u64) -> bool {
+        id > 0 && id < self.next_id && !self.outbox.contains_key(&id)
+    }
+
+    /// Removes an acknowledged payload from the outbox.
+    ///
+    /// Acknowledgments for another session were sent for a previous instance of this service and
+    /// are ignored.
+    pub fn acknowledge(&mut self, session: u64, id: u64) {
+        if session == self.session {
+            self.outbox.remove(&id);
+        }
+    }
+
+    /// Records a payload received from the peer.
+    pub fn receive(&mut self, session: u64, id: u64, payload: Vec<u8>) -> Receipt {
+        if self.peer_session != Some(session) {
+            // The peer was recreated, so its payload IDs have started over
+            self.peer_session = Some(session);
+            self.received_through = 0;
+            self.received_ahead.clear();
+        }
+
+        if id <= self.received_through || !self.received_ahead.insert(id) {
+            return Receipt::Duplicate;
+        }
+
+        while self.received_ahead.remove(&(self.received_through + 1)) {
+            self.received_through += 1;
+        }
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.inbox.push_back(ReceivedPayload { seq, payload });
+
+        Receipt::New
+    }
+
+    /// Returns the received payloads with a sequence number greater than `since`.
+    ///
+    /// Payloads with a sequence number less than or equal to `since` have been consumed by the
+    /// caller and are dropped.
+    pub fn received_since(&mut self, since: u64) -> Vec<ReceivedPayload> {
+        while self
+            .inbox
+            .front()
+            .map(|received| received.seq <= since)
+            .unwrap_or(false)
+        {
+            self.inbox.pop_front();
+        }
+
+        self.inbox.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that submitted payloads stay in the outbox until they are acknowledged with the
+    /// service's own session.
+    #[test]
+    fn outbox_acknowledgment() {
+        let mut shared = RelayShared::new(1);
+
+        let first = shared.add_outgoing(b"first".to_vec());
+        let second = shared.add_outgoing(b"second".to_vec());
+        assert_eq!(shared.pending_count(), 2);
+        assert!(!shared.is_delivered(first));
+
+        // an ack from a different session is ignored
+        shared.acknowledge(2, first);
+        assert_eq!(shared.pending_count(), 2);
+
+        shared.acknowledge(1, first);
+        assert!(shared.is_delivered(first));
+        assert!(!shared.is_delivered(second));
+        assert_eq!(
+            shared.unacknowledged().collect::<Vec<_>>(),
+            vec![(&second, &b"second".to_vec())]
+        );
+
+        // IDs that were never assigned are not delivered
+        assert!(!shared.is_delivered(0));
+        assert!(!shared.is_delivered(10));
+    }
+
+    /// Verify that resent payloads are only delivered once, including when they arrive out of
+    /// order, and that a new peer session starts tracking IDs over.
+    #[test]
+    fn receive_deduplication() {
+        let mut shared = RelayShared::new(1);
+
+        assert_eq!(shared.receive(5, 2, b"two".to_vec()), Receipt::New);
+        assert_eq!(shared.receive(5, 1, b"one".to_vec()), Receipt::New);
+        assert_eq!(shared.receive(5, 2, b"two".to_vec()), Receipt::Duplicate);
+        assert_eq!(shared.receive(5, 1, b"one".to_vec()), Receipt::Duplicate);
+
+        let received = shared.received_since(0);
+        assert_eq!(
+            received
+                .iter()
+                .map(|received| received.payload().to_vec())
+                .collect::<Vec<_>>(),
+            vec![b"two".to_vec(), b"one".to_vec()]
+        );
+
+        // the peer restarted, so ID 1 is a new payload
+        assert_eq!(shared.receive(6, 1, b"again".to_vec()), Receipt::New);
+
+        // consuming through the first two payloads only leaves the last one
+        let received = shared.received_since(received[1].seq());
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].payload(), b"again");
+    }
+}
@@ -16,10 +16,16 @@
 extern crate log;
 
 mod error;
+#[cfg(feature = "progress")]
+mod progress;
+#[cfg(feature = "contract-package")]
+mod scar;
 mod signing;
 
 use std::fs::File;
 use std::io::{BufReader, Read};
+#[cfg(feature = "contract-package")]
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -59,6 +65,175 @@ fn main() {
 }
 
 fn run() -> Result<(), CliError> {
+    let contract_command = SubCommand::with_name("contract")
+        .about("List, show, or upload a Sabre smart contract")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("upload")
+                .about("Upload a Sabre contract")
+                .args(&[
+                    Arg::with_name("scar")
+                        .long_help(
+                            "Name and version requirement of the smart contract archive \
+                             (scar) file to upload",
+                        )
+                        .required(true),
+                    Arg::with_name("path")
+                        .long_help(
+                            "Directory path(s) that may contain the desired .scar file; \
+                             if not provided, the system's default .scar path(s) will be \
+                             used.",
+                        )
+                        .long("path")
+                        .short("p")
+                        .takes_value(true)
+                        .multiple(true),
+                    Arg::with_name("key")
+                        .long_help(
+                            "Key for signing transactions (either a file path or the name \
+                             of a .priv file in $HOME/.splinter/keys)",
+                        )
+                        .short("k")
+                        .long("key")
+                        .takes_value(true),
+                    Arg::with_name("url")
+                        .help("URL to the scabbard REST API")
+                        .short("U")
+                        .long("url")
+                        .takes_value(true),
+                    Arg::with_name("service-id")
+                        .long_help(
+                            "Fully-qualified service ID of the scabbard service (must be \
+                             of the form 'circuit_id::service_id')",
+                        )
+                        .long("service-id")
+                        .takes_value(true)
+                        .required(true),
+                    Arg::with_name("wait")
+                        .help("Time (in seconds) to wait for batches to be committed")
+                        .long("wait")
+                        .takes_value(true)
+                        .default_value("300"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("List all registered Sabre smart contracts")
+                .args(&[
+                    Arg::with_name("url")
+                        .help("URL to the scabbard REST API")
+                        .short("U")
+                        .long("url")
+                        .takes_value(true),
+                    Arg::with_name("service-id")
+                        .long_help(
+                            "Fully-qualified service ID of the scabbard service (must be \
+                             of the form 'circuit_id::service_id')",
+                        )
+                        .long("service-id")
+                        .takes_value(true)
+                        .required(true),
+                    Arg::with_name("format")
+                        .help("Format to display list of smart contracts in")
+                        .short("f")
+                        .long("format")
+                        .takes_value(true)
+                        .possible_values(&["human", "csv"])
+                        .default_value("human"),
+                    Arg::with_name("key")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Show details about a registered Sabre smart contract")
+                .args(&[
+                    Arg::with_name("url")
+                        .help("URL to the scabbard REST API")
+                        .short("U")
+                        .long("url")
+                        .takes_value(true),
+                    Arg::with_name("service-id")
+                        .long_help(
+                            "Fully-qualified service ID of the scabbard service (must be \
+                             of the form 'circuit_id::service_id')",
+                        )
+                        .long("service-id")
+                        .takes_value(true)
+                        .required(true),
+                    Arg::with_name("contract")
+                        .help(
+                            "Name and version of the smart contract in the form \
+                             'name:version'",
+                        )
+                        .takes_value(true)
+                        .required(true),
+                    Arg::with_name("key")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ]),
+        );
+
+    #[cfg(feature = "contract-package")]
+    let contract_command = contract_command
+        .subcommand(
+            SubCommand::with_name("package")
+                .about("Compile a Sabre smart contract and package it as a .scar file")
+                .args(&[
+                    Arg::with_name("manifest")
+                        .help("Path to the Cargo.toml of the smart contract")
+                        .long("manifest")
+                        .short("m")
+                        .takes_value(true)
+                        .default_value("./Cargo.toml"),
+                    Arg::with_name("wasm")
+                        .long_help(
+                            "Path to a pre-built WebAssembly module to package; if not \
+                             provided, the smart contract is compiled with cargo",
+                        )
+                        .long("wasm")
+                        .takes_value(true),
+                    Arg::with_name("inputs")
+                        .long_help(
+                            "Input addresses used by the contract; overrides the inputs in the \
+                             [package.metadata.scar] table of the manifest",
+                        )
+                        .long("inputs")
+                        .takes_value(true)
+                        .multiple(true),
+                    Arg::with_name("outputs")
+                        .long_help(
+                            "Output addresses used by the contract; overrides the outputs in \
+                             the [package.metadata.scar] table of the manifest",
+                        )
+                        .long("outputs")
+                        .takes_value(true)
+                        .multiple(true),
+                    Arg::with_name("out")
+                        .long_help(
+                            "Path of the .scar file to write; defaults to \
+                             '<name>_<version>.scar' in the current directory",
+                        )
+                        .long("out")
+                        .short("o")
+                        .takes_value(true),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("Validate a .scar file and display its manifest")
+                .arg(
+                    Arg::with_name("scar")
+                        .help("Path to the .scar file")
+                        .required(true),
+                ),
+        );
+
     let mut app = App::new("scabbard");
 
     app = app
@@ -73,121 +248,15 @@ fn run() -> Result<(), CliError> {
                 .global(true)
                 .multiple(true),
         )
-        .subcommand(
-            SubCommand::with_name("contract")
-                .about("List, show, or upload a Sabre smart contract")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("upload")
-                        .about("Upload a Sabre contract")
-                        .args(&[
-                            Arg::with_name("scar")
-                                .long_help(
-                                    "Name and version requirement of the smart contract archive \
-                                     (scar) file to upload",
-                                )
-                                .required(true),
-                            Arg::with_name("path")
-                                .long_help(
-                                    "Directory path(s) that may contain the desired .scar file; \
-                                     if not provided, the system's default .scar path(s) will be \
-                                     used.",
-                                )
-                                .long("path")
-                                .short("p")
-                                .takes_value(true)
-                                .multiple(true),
-                            Arg::with_name("key")
-                                .long_help(
-                                    "Key for signing transactions (either a file path or the name \
-                                     of a .priv file in $HOME/.splinter/keys)",
-                                )
-                                .short("k")
-                                .long("key")
-                                .takes_value(true),
-                            Arg::with_name("url")
-                                .help("URL to the scabbard REST API")
-                                .short("U")
-                                .long("url")
-                                .takes_value(true),
-                            Arg::with_name("service-id")
-                                .long_help(
-                                    "Fully-qualified service ID of the scabbard service (must be \
-                                     of the form 'circuit_id::service_id')",
-                                )
-                                .long("service-id")
-                                .takes_value(true)
-                                .required(true),
-                            Arg::with_name("wait")
-                                .help("Time (in seconds) to wait for batches to be committed")
-                                .long("wait")
-                                .takes_value(true)
-                                .default_value("300"),
-                        ]),
-                )
-                .subcommand(
-                    SubCommand::with_name("list")
-                        .about("List all registered Sabre smart contracts")
-                        .args(&[
-                            Arg::with_name("url")
-                                .help("URL to the scabbard REST API")
-                                .short("U")
-                                .long("url")
-                                .takes_value(true),
-                            Arg::with_name("service-id")
-                                .long_help(
-                                    "Fully-qualified service ID of the scabbard service (must be \
-                                     of the form 'circuit_id::service_id')",
-                                )
-                                .long("service-id")
-                                .takes_value(true)
-                                .required(true),
-                            Arg::with_name("format")
-                                .help("Format to display list of smart contracts in")
-                                .short("f")
-                                .long("format")
-                                .takes_value(true)
-                                .possible_values(&["human", "csv"])
-                                .default_value("human"),
-                            Arg::with_name("key")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        ]),
-                )
-                .subcommand(
-                    SubCommand::with_name("show")
-                        .about("Show details about a registered Sabre smart contract")
-                        .args(&[
-                            Arg::with_name("url")
-                                .help("URL to the scabbard REST API")
-                                .short("U")
-                                .long("url")
-                                .takes_value(true),
-                            Arg::with_name("service-id")
-                                .long_help(
-                                    "Fully-qualified service ID of the scabbard service (must be \
-                                     of the form 'circuit_id::service_id')",
-                                )
-                                .long("service-id")
-                                .takes_value(true)
-                                .required(true),
-                            Arg::with_name("contract")
-                                .help(
-                                    "Name and version of the smart contract in the form \
-                                     'name:version'",
-                                )
-                                .takes_value(true)
-                                .required(true),
-                            Arg::with_name("key")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        ]),
-                ),
+        .arg(
+            Arg::with_name("quiet")
+                .help("Do not display output")
+                .short("q")
+                .long("quiet")
+                .global(true)
+                .conflicts_with("verbose"),
         )
+        .subcommand(contract_command)
         .subcommand(
             SubCommand::with_name("exec")
                 .about("Execute a Sabre contract")
@@ -577,11 +646,15 @@ fn run() -> Result<(), CliError> {
 
     let matches = app.get_matches();
 
-    let log_level = match matches.occurrences_of("verbose") {
-        0 => log::LevelFilter::Warn,
-        1 => log::LevelFilter::Info,
-        2 => log::LevelFilter::Debug,
-        _ => log::LevelFilter::Trace,
+    let log_level = if matches.is_present("quiet") {
+        log::LevelFilter::Error
+    } else {
+        match matches.occurrences_of("verbose") {
+            0 => log::LevelFilter::Warn,
+            1 => log::LevelFilter::Info,
+            2 => log::LevelFilter::Debug,
+            _ => log::LevelFilter::Trace,
+        }
     };
     setup_logging(log_level)?;
 
@@ -628,6 +701,9 @@ fn run() -> Result<(), CliError> {
                     None => default_scar_path(),
                 };
 
+                #[cfg(feature = "progress")]
+                let spinner =
+                    progress::Spinner::start(matches, format!("Loading contract {}", scar));
                 let smart_contract = SmartContractArchive::from_scar_file(name, version, &paths)?;
 
                 let batch = CreateContractActionBuilder::new()
@@ -641,6 +717,11 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                spinner.set_message(format!(
+                    "Uploading contract {}; waiting up to {}s for it to be committed",
+                    scar, wait
+                ));
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             ("list", Some(matches)) => {
@@ -758,6 +839,107 @@ fn run() -> Result<(), CliError> {
 
                 Ok(())
             }
+            #[cfg(feature = "contract-package")]
+            ("package", Some(matches)) => {
+                let manifest_path = Path::new(
+                    matches
+                        .value_of("manifest")
+                        .expect("default not set for --manifest"),
+                );
+                let contract = scar::read_cargo_contract(manifest_path)?;
+
+                let wasm_path = match matches.value_of("wasm") {
+                    Some(wasm_path) => PathBuf::from(wasm_path),
+                    None => {
+                        #[cfg(feature = "progress")]
+                        let _spinner = progress::Spinner::start(
+                            matches,
+                            format!("Compiling contract {}", contract.name),
+                        );
+                        scar::build_contract(manifest_path)?;
+                        contract.wasm_path
+                    }
+                };
+                let wasm = std::fs::read(&wasm_path).map_err(|err| {
+                    CliError::action_error_with_source(
+                        &format!("failed to read {}", wasm_path.display()),
+                        err.into(),
+                    )
+                })?;
+
+                let manifest = scar::ScarManifest {
+                    inputs: matches
+                        .values_of("inputs")
+                        .map(|inputs| inputs.map(String::from).collect())
+                        .unwrap_or(contract.inputs),
+                    outputs: matches
+                        .values_of("outputs")
+                        .map(|outputs| outputs.map(String::from).collect())
+                        .unwrap_or(contract.outputs),
+                    name: contract.name,
+                    version: contract.version,
+                };
+
+                let out = matches
+                    .value_of("out")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(manifest.file_name()));
+
+                // Validate before creating the output file, so an invalid package leaves nothing
+                // behind
+                manifest.validate()?;
+                scar::validate_wasm(&wasm)?;
+
+                let file = File::create(&out).map_err(|err| {
+                    CliError::action_error_with_source(
+                        &format!("failed to create {}", out.display()),
+                        err.into(),
+                    )
+                })?;
+                scar::write_scar(file, &manifest, &wasm)?;
+
+                println!(
+                    "Packaged {} {} into {}",
+                    manifest.name,
+                    manifest.version,
+                    out.display()
+                );
+
+                Ok(())
+            }
+            #[cfg(feature = "contract-package")]
+            ("verify", Some(matches)) => {
+                let path = Path::new(
+                    matches
+                        .value_of("scar")
+                        .ok_or_else(|| CliError::MissingArgument("scar".into()))?,
+                );
+                let (manifest, wasm) = scar::read_scar_file(path)?;
+
+                println!("{} {}", manifest.name, manifest.version);
+                println!("  inputs:");
+                for input in &manifest.inputs {
+                    println!("  - {}", input);
+                }
+                println!("  outputs:");
+                for output in &manifest.outputs {
+                    println!("  - {}", output);
+                }
+                println!("  contract size: {} bytes", wasm.len());
+
+                let expected_file_name = manifest.file_name();
+                if path.file_name().and_then(|name| name.to_str())
+                    != Some(expected_file_name.as_str())
+                {
+                    warn!(
+                        "File name does not match the manifest; the archive must be named '{}' \
+                         to be found by 'scabbard contract upload'",
+                        expected_file_name
+                    );
+                }
+
+                Ok(())
+            }
             _ => Err(CliError::InvalidSubcommand),
         },
         ("exec", Some(matches)) => {
@@ -822,6 +1004,8 @@ fn run() -> Result<(), CliError> {
                 .into_batch_builder(&*signer)?
                 .build(&*signer)?;
 
+            #[cfg(feature = "progress")]
+            let _spinner = progress::Spinner::for_batch(matches, wait);
             Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
         }
         ("ns", Some(matches)) => match matches.subcommand() {
@@ -869,6 +1053,8 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                let _spinner = progress::Spinner::for_batch(matches, wait);
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             ("update", Some(matches)) => {
@@ -915,6 +1101,8 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                let _spinner = progress::Spinner::for_batch(matches, wait);
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             ("delete", Some(matches)) => {
@@ -955,6 +1143,8 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                let _spinner = progress::Spinner::for_batch(matches, wait);
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             _ => Err(CliError::InvalidSubcommand),
@@ -1014,6 +1204,8 @@ fn run() -> Result<(), CliError> {
                 .into_batch_builder(&*signer)?
                 .build(&*signer)?;
 
+            #[cfg(feature = "progress")]
+            let _spinner = progress::Spinner::for_batch(matches, wait);
             Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
         }
         ("cr", Some(matches)) => match matches.subcommand() {
@@ -1061,6 +1253,8 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                let _spinner = progress::Spinner::for_batch(matches, wait);
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             ("update", Some(matches)) => {
@@ -1107,6 +1301,8 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                let _spinner = progress::Spinner::for_batch(matches, wait);
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             ("delete", Some(matches)) => {
@@ -1147,6 +1343,8 @@ fn run() -> Result<(), CliError> {
                     .into_batch_builder(&*signer)?
                     .build(&*signer)?;
 
+                #[cfg(feature = "progress")]
+                let _spinner = progress::Spinner::for_batch(matches, wait);
                 Ok(client.submit(&service_id, vec![batch], Some(Duration::from_secs(wait)))?)
             }
             _ => Err(CliError::InvalidSubcommand),
@@ -0,0 +1,440 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Packaging and inspection of smart contract archive (.scar) files.
+//!
+//! A .scar file is a bzip2-compressed tar archive containing a `manifest.yaml`, which describes
+//! the contract's name, version, inputs, and outputs, and the contract's compiled WebAssembly.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use bzip2::{read::BzDecoder, write::BzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::CliError;
+
+/// The name of the manifest entry in a .scar file.
+const MANIFEST_FILE_NAME: &str = "manifest.yaml";
+/// The target used to compile smart contracts.
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+/// The magic number that begins every WebAssembly module.
+const WASM_MAGIC: &[u8] = b"\0asm";
+/// The maximum length, in hex characters, of a state address or address prefix.
+const MAX_ADDRESS_LENGTH: usize = 70;
+
+/// The manifest of a smart contract archive.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ScarManifest {
+    pub name: String,
+    pub version: String,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl ScarManifest {
+    /// Checks that the manifest has a name, a semantic version, and valid input and output
+    /// address prefixes.
+    pub fn validate(&self) -> Result<(), CliError> {
+        // Underscores separate the name from the version in .scar file names
+        if self.name.is_empty() || self.name.contains(|c: char| c == '_' || c.is_whitespace()) {
+            return Err(CliError::InvalidArgument(format!(
+                "contract name '{}' must be non-empty and contain no underscores or whitespace",
+                self.name
+            )));
+        }
+        if !is_semver(&self.version) {
+            return Err(CliError::InvalidArgument(format!(
+                "contract version '{}' is not a semantic version (major.minor.patch)",
+                self.version
+            )));
+        }
+        validate_addresses("inputs", &self.inputs)?;
+        validate_addresses("outputs", &self.outputs)
+    }
+
+    /// The file name of an archive with this manifest, in the form `<name>_<version>.scar`.
+    ///
+    /// This is the name used to find the archive when it is uploaded by name and version.
+    pub fn file_name(&self) -> String {
+        format!("{}_{}.scar", self.name, self.version)
+    }
+}
+
+/// A smart contract package read from a Cargo manifest.
+pub struct CargoContract {
+    pub name: String,
+    pub version: String,
+    /// The inputs from the `[package.metadata.scar]` table, if any
+    pub inputs: Vec<String>,
+    /// The outputs from the `[package.metadata.scar]` table, if any
+    pub outputs: Vec<String>,
+    /// The path the compiled WebAssembly is written to by `cargo build`
+    pub wasm_path: PathBuf,
+}
+
+/// Reads the smart contract package defined by the given Cargo manifest, using `cargo metadata`.
+pub fn read_cargo_contract(manifest_path: &Path) -> Result<CargoContract, CliError> {
+    let manifest_path = manifest_path.canonicalize().map_err(|err| {
+        CliError::action_error_with_source(
+            &format!("failed to find manifest {}", manifest_path.display()),
+            err.into(),
+        )
+    })?;
+
+    let output = Command::new(cargo())
+        .args(&["metadata", "--format-version", "1", "--no-deps"])
+        .arg("--manifest-path")
+        .arg(&manifest_path)
+        .output()
+        .map_err(|err| CliError::action_error_with_source("failed to run cargo", err.into()))?;
+    if !output.status.success() {
+        return Err(CliError::action_error(&format!(
+            "failed to read manifest {}: {}",
+            manifest_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let metadata: CargoMetadata = serde_json::from_slice(&output.stdout).map_err(|err| {
+        CliError::action_error_with_source("failed to parse cargo metadata", err.into())
+    })?;
+
+    let package = metadata
+        .packages
+        .into_iter()
+        .find(|package| package.manifest_path == manifest_path)
+        .ok_or_else(|| {
+            CliError::action_error(&format!(
+                "manifest {} does not define a package",
+                manifest_path.display()
+            ))
+        })?;
+
+    let target = package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| kind == "cdylib"))
+        .or_else(|| {
+            package
+                .targets
+                .iter()
+                .find(|target| target.kind.iter().any(|kind| kind == "bin"))
+        })
+        .ok_or_else(|| {
+            CliError::action_error(&format!(
+                "package '{}' has no cdylib or bin target to compile",
+                package.name
+            ))
+        })?;
+
+    let wasm_path = metadata
+        .target_directory
+        .join(WASM_TARGET)
+        .join("release")
+        .join(format!("{}.wasm", target.name.replace('-', "_")));
+
+    let scar_metadata = package
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("scar"))
+        .cloned()
+        .map(serde_json::from_value::<ScarMetadata>)
+        .transpose()
+        .map_err(|err| {
+            CliError::action_error_with_source(
+                "invalid [package.metadata.scar] table in manifest",
+                err.into(),
+            )
+        })?
+        .unwrap_or_default();
+
+    Ok(CargoContract {
+        name: package.name,
+        version: package.version,
+        inputs: scar_metadata.inputs,
+        outputs: scar_metadata.outputs,
+        wasm_path,
+    })
+}
+
+/// Compiles the package defined by the given Cargo manifest to WebAssembly in release mode.
+pub fn build_contract(manifest_path: &Path) -> Result<(), CliError> {
+    let status = Command::new(cargo())
+        .args(&["build", "--release", "--target", WASM_TARGET])
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .status()
+        .map_err(|err| CliError::action_error_with_source("failed to run cargo", err.into()))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CliError::action_error(&format!(
+            "failed to compile {} to WebAssembly",
+            manifest_path.display()
+        )))
+    }
+}
+
+/// Checks that the given bytes are a WebAssembly module.
+pub fn validate_wasm(wasm: &[u8]) -> Result<(), CliError> {
+    if wasm.starts_with(WASM_MAGIC) {
+        Ok(())
+    } else {
+        Err(CliError::InvalidArgument(
+            "contract is not a WebAssembly module".into(),
+        ))
+    }
+}
+
+/// Writes a .scar archive containing the manifest and the contract's WebAssembly.
+pub fn write_scar<W: Write>(
+    writer: W,
+    manifest: &ScarManifest,
+    wasm: &[u8],
+) -> Result<(), CliError> {
+    manifest.validate()?;
+    validate_wasm(wasm)?;
+
+    let manifest_bytes = serde_yaml::to_vec(manifest).map_err(|err| {
+        CliError::action_error_with_source("failed to serialize manifest", err.into())
+    })?;
+
+    let mut builder = tar::Builder::new(BzEncoder::new(writer, Compression::best()));
+    append_entry(&mut builder, MANIFEST_FILE_NAME, &manifest_bytes)?;
+    append_entry(&mut builder, &format!("{}.wasm", manifest.name), wasm)?;
+
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map(|_| ())
+        .map_err(|err| CliError::action_error_with_source("failed to write archive", err.into()))
+}
+
+/// Reads a .scar archive, returning its manifest and the contract's WebAssembly.
+///
+/// The archive must contain exactly one manifest and one WebAssembly module, and the manifest
+/// must be valid.
+pub fn read_scar<R: Read>(reader: R) -> Result<(ScarManifest, Vec<u8>), CliError> {
+    let map_read_err = |err: std::io::Error| {
+        CliError::action_error_with_source("failed to read archive", err.into())
+    };
+
+    let mut archive = tar::Archive::new(BzDecoder::new(reader));
+    let mut manifest = None;
+    let mut wasm = None;
+    for entry in archive.entries().map_err(map_read_err)? {
+        let mut entry = entry.map_err(map_read_err)?;
+        let path = entry.path().map_err(map_read_err)?.into_owned();
+
+        let mut contents = vec![];
+        entry.read_to_end(&mut contents).map_err(map_read_err)?;
+
+        if path == Path::new(MANIFEST_FILE_NAME) {
+            if manifest.is_some() {
+                return Err(CliError::action_error(
+                    "archive contains multiple manifests",
+                ));
+            }
+            manifest = Some(
+                serde_yaml::from_slice::<ScarManifest>(&contents).map_err(|err| {
+                    CliError::action_error_with_source("failed to parse manifest", err.into())
+                })?,
+            );
+        } else if path.extension().map(|ext| ext == "wasm").unwrap_or(false) {
+            if wasm.is_some() {
+                return Err(CliError::action_error(
+                    "archive contains multiple WebAssembly modules",
+                ));
+            }
+            wasm = Some(contents);
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| CliError::action_error("archive has no manifest"))?;
+    let wasm = wasm.ok_or_else(|| CliError::action_error("archive has no WebAssembly module"))?;
+    manifest.validate()?;
+    validate_wasm(&wasm)?;
+
+    Ok((manifest, wasm))
+}
+
+/// Reads a .scar archive from the given file.
+pub fn read_scar_file(path: &Path) -> Result<(ScarManifest, Vec<u8>), CliError> {
+    let file = File::open(path).map_err(|err| {
+        CliError::action_error_with_source(
+            &format!("failed to open {}", path.display()),
+            err.into(),
+        )
+    })?;
+    read_scar(file)
+}
+
+fn append_entry<W: Write>(
+    builder: &mut tar::Builder<W>,
+    name: &str,
+    contents: &[u8],
+) -> Result<(), CliError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, name, contents)
+        .map_err(|err| CliError::action_error_with_source("failed to write archive", err.into()))
+}
+
+fn validate_addresses(field: &str, addresses: &[String]) -> Result<(), CliError> {
+    if addresses.is_empty() {
+        return Err(CliError::InvalidArgument(format!(
+            "contract {} must not be empty",
+            field
+        )));
+    }
+
+    for address in addresses {
+        if address.len() % 2 != 0
+            || address.len() > MAX_ADDRESS_LENGTH
+            || !address.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(CliError::InvalidArgument(format!(
+                "contract {} must be hex address prefixes of at most {} characters, \
+                 got '{}'",
+                field, MAX_ADDRESS_LENGTH, address
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks for a `major.minor.patch` version, optionally followed by pre-release or build
+/// metadata.
+fn is_semver(version: &str) -> bool {
+    let core = version
+        .split(|c| c == '-' || c == '+')
+        .next()
+        .unwrap_or_default();
+    let parts = core.split('.').collect::<Vec<_>>();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The cargo executable, honoring the `CARGO` environment variable set when run from cargo.
+fn cargo() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".into())
+}
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+    packages: Vec<CargoPackage>,
+    target_directory: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+    version: String,
+    manifest_path: PathBuf,
+    targets: Vec<CargoTarget>,
+    metadata: Option<BTreeMap<String, Value>>,
+}
+
+#[derive(Deserialize)]
+struct CargoTarget {
+    name: String,
+    kind: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct ScarMetadata {
+    #[serde(default)]
+    inputs: Vec<String>,
+    #[serde(default)]
+    outputs: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> ScarManifest {
+        ScarManifest {
+            name: "xo".into(),
+            version: "0.4.2".into(),
+            inputs: vec!["5b7349".into()],
+            outputs: vec!["5b7349".into(), "00ec01".into()],
+        }
+    }
+
+    /// Verify that an archive written by `write_scar` is read back with the same manifest and
+    /// WebAssembly.
+    #[test]
+    fn test_scar_round_trip() {
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+
+        let mut scar = vec![];
+        write_scar(&mut scar, &manifest(), &wasm).expect("Unable to write scar");
+
+        let (read_manifest, read_wasm) = read_scar(&scar[..]).expect("Unable to read scar");
+        assert_eq!(read_manifest, manifest());
+        assert_eq!(read_wasm, wasm);
+        assert_eq!(read_manifest.file_name(), "xo_0.4.2.scar");
+    }
+
+    /// Verify that invalid manifests and contracts are rejected when packaging.
+    #[test]
+    fn test_write_scar_invalid() {
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+
+        assert!(write_scar(vec![], &manifest(), b"not wasm").is_err());
+
+        let mut invalid = manifest();
+        invalid.name = "x_o".into();
+        assert!(write_scar(vec![], &invalid, &wasm).is_err());
+
+        let mut invalid = manifest();
+        invalid.version = "1.0".into();
+        assert!(write_scar(vec![], &invalid, &wasm).is_err());
+
+        let mut invalid = manifest();
+        invalid.inputs = vec![];
+        assert!(write_scar(vec![], &invalid, &wasm).is_err());
+
+        let mut invalid = manifest();
+        invalid.outputs = vec!["5b734".into()];
+        assert!(write_scar(vec![], &invalid, &wasm).is_err());
+
+        let mut invalid = manifest();
+        invalid.outputs = vec!["not-hex!".into()];
+        assert!(write_scar(vec![], &invalid, &wasm).is_err());
+    }
+
+    /// Verify that semantic versions are recognized.
+    #[test]
+    fn test_is_semver() {
+        assert!(is_semver("1.0.0"));
+        assert!(is_semver("0.4.2-rc.1"));
+        assert!(is_semver("1.2.3+build"));
+        assert!(!is_semver("1.0"));
+        assert!(!is_semver("1.x.0"));
+        assert!(!is_semver(""));
+    }
+}
@@ -22,6 +22,8 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use transact::protocol::batch::Batch;
+#[cfg(feature = "transaction-receipts")]
+use transact::protocol::receipt::TransactionReceipt;
 
 pub use self::error::ScabbardClientError;
 #[cfg(feature = "reqwest")]
@@ -114,6 +116,71 @@ impl StateEntry {
     }
 }
 
+/// Selects the transaction receipts returned by `ScabbardClient::get_receipts`. Receipts must
+/// match every criteria that is set.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Clone, Debug, Default)]
+pub struct ReceiptFilter {
+    transaction_ids: Vec<String>,
+    batch_id: Option<String>,
+    start: Option<u64>,
+    end: Option<u64>,
+}
+
+#[cfg(feature = "transaction-receipts")]
+impl ReceiptFilter {
+    /// Only include the receipts of the given transactions.
+    pub fn with_transaction_ids(mut self, transaction_ids: Vec<String>) -> Self {
+        self.transaction_ids = transaction_ids;
+        self
+    }
+
+    /// Only include the receipts of the transactions in the given batch.
+    pub fn with_batch_id(mut self, batch_id: &str) -> Self {
+        self.batch_id = Some(batch_id.into());
+        self
+    }
+
+    /// Only include receipts at or after the given position in the commit order.
+    pub fn with_start(mut self, start: u64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Only include receipts before the given position in the commit order.
+    pub fn with_end(mut self, end: u64) -> Self {
+        self.end = Some(end);
+        self
+    }
+}
+
+/// A transaction receipt and its position in the order the scabbard service committed
+/// transactions.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, PartialEq)]
+pub struct Receipt {
+    index: u64,
+    receipt: TransactionReceipt,
+}
+
+#[cfg(feature = "transaction-receipts")]
+impl Receipt {
+    /// Get the position of the receipt in the commit order, starting from 0.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Get the transaction receipt.
+    pub fn receipt(&self) -> &TransactionReceipt {
+        &self.receipt
+    }
+
+    /// Consume the `Receipt`, returning the transaction receipt.
+    pub fn into_receipt(self) -> TransactionReceipt {
+        self.receipt
+    }
+}
+
 pub trait ScabbardClient {
     /// Submit the given `batches` to the scabbard service with the given `service_id`. If a `wait`
     /// time is specified, wait the given amount of time for the batches to commit.
@@ -131,6 +198,27 @@ pub trait ScabbardClient {
         wait: Option<Duration>,
     ) -> Result<(), ScabbardClientError>;
 
+    /// Submit the given `batches` to the scabbard service with the given `service_id`, using the
+    /// given `idempotency_key` to deduplicate the submission. If the same batches were recently
+    /// submitted with the key, they are not submitted again. If a `wait` time is specified, wait
+    /// the given amount of time for the batches to commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The key was recently used to submit different batches
+    /// * One or more batches were invalid (if `wait` provided)
+    /// * The `wait` time has elapsed and the batches have not been committed (if `wait` provided)
+    /// * An internal error based on the underlying implementation
+    #[cfg(feature = "batch-idempotency-keys")]
+    fn submit_with_idempotency_key(
+        &self,
+        service_id: &ServiceId,
+        batches: Vec<Batch>,
+        idempotency_key: &str,
+        wait: Option<Duration>,
+    ) -> Result<(), ScabbardClientError>;
+
     /// Get the value at the given `address` in state for the scabbard instance with the given
     /// `service_id`. Returns `None` if there is no entry at the given address.
     ///
@@ -161,6 +249,45 @@ pub trait ScabbardClient {
         prefix: Option<&str>,
     ) -> Result<Vec<StateEntry>, ScabbardClientError>;
 
+    /// Get the value at the given `address` in state as of the given historical `state_root` for
+    /// the scabbard instance with the given `service_id`. Returns `None` if there is no entry at
+    /// the given address in that state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The given address is not a valid hex address
+    /// * The given `state_root` is not a valid state root hash
+    /// * The state root is not available because it was pruned
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    #[cfg(feature = "historical-state")]
+    fn get_state_at_address_at_root(
+        &self,
+        service_id: &ServiceId,
+        address: &str,
+        state_root: &str,
+    ) -> Result<Option<Vec<u8>>, ScabbardClientError>;
+
+    /// Get all entries under the given address `prefix` in state as of the given historical
+    /// `state_root` for the scabbard instance with the given `service_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The given `prefix` is not a valid hex address prefix
+    /// * The given `state_root` is not a valid state root hash
+    /// * The state root is not available because it was pruned
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    #[cfg(feature = "historical-state")]
+    fn get_state_with_prefix_at_root(
+        &self,
+        service_id: &ServiceId,
+        prefix: Option<&str>,
+        state_root: &str,
+    ) -> Result<Vec<StateEntry>, ScabbardClientError>;
+
     /// Get the current state root hash of the scabbard instance with the given `service_id`.
     ///
     /// # Errors
@@ -170,6 +297,22 @@ pub trait ScabbardClient {
     /// * An internal error based on the underlying implementation
     fn get_current_state_root(&self, service_id: &ServiceId)
         -> Result<String, ScabbardClientError>;
+
+    /// Get the receipts of the committed transactions that match the given `filter` for the
+    /// scabbard instance with the given `service_id`, in the order the transactions were
+    /// committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    #[cfg(feature = "transaction-receipts")]
+    fn get_receipts(
+        &self,
+        service_id: &ServiceId,
+        filter: &ReceiptFilter,
+    ) -> Result<Vec<Receipt>, ScabbardClientError>;
 }
 
 #[cfg(test)]
@@ -23,6 +23,8 @@ use reqwest::{
     Url,
 };
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "transaction-receipts")]
+use transact::protocol::receipt::{Event, StateChange, TransactionReceipt, TransactionResult};
 use transact::{protocol::batch::Batch, protos::IntoBytes};
 
 use crate::hex::parse_hex;
@@ -30,6 +32,8 @@ use crate::protocol::SCABBARD_PROTOCOL_VERSION;
 
 use super::error::ScabbardClientError;
 use super::ScabbardClient;
+#[cfg(feature = "transaction-receipts")]
+use super::{Receipt, ReceiptFilter};
 use super::{ServiceId, StateEntry};
 
 pub use builder::ReqwestScabbardClientBuilder;
@@ -40,22 +44,12 @@ pub struct ReqwestScabbardClient {
     auth: String,
 }
 
-impl ScabbardClient for ReqwestScabbardClient {
-    /// Submit the given `batches` to the scabbard service with the given `service_id`. If a `wait`
-    /// time is specified, wait the given amount of time for the batches to commit.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error in any of the following cases:
-    /// * The client's URL was invalid
-    /// * A REST API request failed
-    /// * An internal server error occurred in the scabbard service
-    /// * One or more batches were invalid (if `wait` provided)
-    /// * The `wait` time has elapsed and the batches have not been committed (if `wait` provided)
-    fn submit(
+impl ReqwestScabbardClient {
+    fn submit_batches(
         &self,
         service_id: &ServiceId,
         batches: Vec<Batch>,
+        idempotency_key: Option<&str>,
         wait: Option<Duration>,
     ) -> Result<(), ScabbardClientError> {
         let url = parse_http_url(&format!(
@@ -68,10 +62,13 @@ impl ScabbardClient for ReqwestScabbardClient {
         let body = batches.into_bytes()?;
 
         debug!("Submitting batches via {}", url);
-        let request = Client::new()
+        let mut request = Client::new()
             .post(url)
             .body(body)
             .header("Authorization", &self.auth);
+        if let Some(idempotency_key) = idempotency_key {
+            request = request.header("Idempotency-Key", idempotency_key);
+        }
         let response = perform_request(request)?;
 
         let batch_link: Link = response.json().map_err(|err| {
@@ -87,6 +84,52 @@ impl ScabbardClient for ReqwestScabbardClient {
             Ok(())
         }
     }
+}
+
+impl ScabbardClient for ReqwestScabbardClient {
+    /// Submit the given `batches` to the scabbard service with the given `service_id`. If a `wait`
+    /// time is specified, wait the given amount of time for the batches to commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * A REST API request failed
+    /// * An internal server error occurred in the scabbard service
+    /// * One or more batches were invalid (if `wait` provided)
+    /// * The `wait` time has elapsed and the batches have not been committed (if `wait` provided)
+    fn submit(
+        &self,
+        service_id: &ServiceId,
+        batches: Vec<Batch>,
+        wait: Option<Duration>,
+    ) -> Result<(), ScabbardClientError> {
+        self.submit_batches(service_id, batches, None, wait)
+    }
+
+    /// Submit the given `batches` to the scabbard service with the given `service_id`, setting the
+    /// `Idempotency-Key` header to the given `idempotency_key`. If a `wait` time is specified,
+    /// wait the given amount of time for the batches to commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * A REST API request failed
+    /// * The key was recently used to submit different batches
+    /// * An internal server error occurred in the scabbard service
+    /// * One or more batches were invalid (if `wait` provided)
+    /// * The `wait` time has elapsed and the batches have not been committed (if `wait` provided)
+    #[cfg(feature = "batch-idempotency-keys")]
+    fn submit_with_idempotency_key(
+        &self,
+        service_id: &ServiceId,
+        batches: Vec<Batch>,
+        idempotency_key: &str,
+        wait: Option<Duration>,
+    ) -> Result<(), ScabbardClientError> {
+        self.submit_batches(service_id, batches, Some(idempotency_key), wait)
+    }
 
     /// Get the value at the given `address` in state for the scabbard instance with the given
     /// `service_id`. Returns `None` if there is no entry at the given address.
@@ -212,6 +255,153 @@ impl ScabbardClient for ReqwestScabbardClient {
         }
     }
 
+    /// Get the value at the given `address` in state as of the given historical `state_root` for
+    /// the scabbard instance with the given `service_id`. Returns `None` if there is no entry at
+    /// the given address in that state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * The given address is not a valid hex address
+    /// * The given `state_root` is not a valid state root hash
+    /// * The state root is not available because it was pruned
+    /// * The REST API request failed
+    /// * An internal server error occurred in the scabbard service
+    #[cfg(feature = "historical-state")]
+    fn get_state_at_address_at_root(
+        &self,
+        service_id: &ServiceId,
+        address: &str,
+        state_root: &str,
+    ) -> Result<Option<Vec<u8>>, ScabbardClientError> {
+        parse_hex(address)
+            .map_err(|err| ScabbardClientError::new_with_source("invalid address", err.into()))?;
+        check_state_root(state_root)?;
+
+        let url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/state/{}?state_root={}",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id(),
+            address,
+            state_root
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+
+        let response = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            Ok(Some(response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize response body",
+                    err.into(),
+                )
+            })?))
+        } else if response.status().as_u16() == 404 {
+            Ok(None)
+        } else if response.status().as_u16() == 410 {
+            Err(state_root_unavailable(state_root))
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to get state at address: {}: {}",
+                status, msg
+            )))
+        }
+    }
+
+    /// Get all entries under the given address `prefix` in state as of the given historical
+    /// `state_root` for the scabbard instance with the given `service_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * The given `prefix` is not a valid hex address prefix
+    /// * The given `state_root` is not a valid state root hash
+    /// * The state root is not available because it was pruned
+    /// * The REST API request failed
+    /// * An internal server error occurred in the scabbard service
+    #[cfg(feature = "historical-state")]
+    fn get_state_with_prefix_at_root(
+        &self,
+        service_id: &ServiceId,
+        prefix: Option<&str>,
+        state_root: &str,
+    ) -> Result<Vec<StateEntry>, ScabbardClientError> {
+        check_state_root(state_root)?;
+
+        let mut url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/state",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+        match prefix {
+            Some(prefix) => {
+                parse_hex(prefix).map_err(|err| {
+                    ScabbardClientError::new_with_source("invalid prefix", err.into())
+                })?;
+                if prefix.len() > 70 {
+                    return Err(ScabbardClientError::new(
+                        "prefix must be less than 70 characters",
+                    ));
+                }
+                url.set_query(Some(&format!(
+                    "prefix={}&state_root={}",
+                    prefix, state_root
+                )))
+            }
+            None => url.set_query(Some(&format!("state_root={}", state_root))),
+        }
+
+        let response = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            response
+                .json::<Vec<JsonStateEntry>>()
+                .map(|entries| entries.into_iter().map(StateEntry::from).collect())
+                .map_err(|err| {
+                    ScabbardClientError::new_with_source(
+                        "failed to deserialize response body",
+                        err.into(),
+                    )
+                })
+        } else if response.status().as_u16() == 410 {
+            Err(state_root_unavailable(state_root))
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to get state with prefix: {}: {}",
+                status, msg
+            )))
+        }
+    }
+
     /// Get the current state root hash of the scabbard instance with the given `service_id`.
     fn get_current_state_root(
         &self,
@@ -253,6 +443,83 @@ impl ScabbardClient for ReqwestScabbardClient {
             )))
         }
     }
+
+    /// Get the receipts of the committed transactions that match the given `filter` for the
+    /// scabbard instance with the given `service_id`. Every page of results is fetched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The REST API request failed
+    /// * An internal server error occurred in the scabbard service
+    #[cfg(feature = "transaction-receipts")]
+    fn get_receipts(
+        &self,
+        service_id: &ServiceId,
+        filter: &ReceiptFilter,
+    ) -> Result<Vec<Receipt>, ScabbardClientError> {
+        let mut url = parse_http_url(&format!(
+            "{}/scabbard/{}/{}/receipts",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))?;
+        {
+            let mut query = url.query_pairs_mut();
+            if !filter.transaction_ids.is_empty() {
+                query.append_pair("transaction_id", &filter.transaction_ids.join(","));
+            }
+            if let Some(batch_id) = &filter.batch_id {
+                query.append_pair("batch_id", batch_id);
+            }
+            if let Some(start) = filter.start {
+                query.append_pair("start", &start.to_string());
+            }
+            if let Some(end) = filter.end {
+                query.append_pair("end", &end.to_string());
+            }
+        }
+
+        let mut receipts = vec![];
+        loop {
+            let response = Client::new()
+                .get(url.clone())
+                .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+                .header("Authorization", &self.auth)
+                .send()
+                .map_err(|err| {
+                    ScabbardClientError::new_with_source("request failed", err.into())
+                })?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let msg: ErrorResponse = response.json().map_err(|err| {
+                    ScabbardClientError::new_with_source(
+                        "failed to deserialize error response body",
+                        err.into(),
+                    )
+                })?;
+                return Err(ScabbardClientError::new(&format!(
+                    "failed to get receipts: {}: {}",
+                    status, msg
+                )));
+            }
+
+            let page: ReceiptListPage = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize response body",
+                    err.into(),
+                )
+            })?;
+            receipts.extend(page.data.into_iter().map(Receipt::from));
+
+            if page.paging.offset + page.paging.limit >= page.paging.total || page.paging.limit == 0
+            {
+                return Ok(receipts);
+            }
+            url = parse_http_url(&format!("{}{}", self.url, page.paging.next))?;
+        }
+    }
 }
 
 /// Using the given `base_url` and `batch_link` to check batch statuses, `wait` the given duration
@@ -335,6 +602,25 @@ fn wait_for_batches(
     }
 }
 
+/// Checks that the given `state_root` is a valid state root hash.
+#[cfg(feature = "historical-state")]
+fn check_state_root(state_root: &str) -> Result<(), ScabbardClientError> {
+    parse_hex(state_root)
+        .map_err(|err| ScabbardClientError::new_with_source("invalid state root", err.into()))?;
+    if state_root.len() != 64 {
+        return Err(ScabbardClientError::new("state root must be 64 characters"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "historical-state")]
+fn state_root_unavailable(state_root: &str) -> ScabbardClientError {
+    ScabbardClientError::new(&format!(
+        "state root {} is not available; it may have been pruned",
+        state_root
+    ))
+}
+
 /// Parses the given `url`, returning an error if it is invalid.
 fn parse_http_url(url: &str) -> Result<Url, ScabbardClientError> {
     let url = Url::parse(url)
@@ -419,6 +705,111 @@ struct InvalidTransaction {
     pub error_data: Vec<u8>,
 }
 
+/// Used for deserializing `GET /receipts` responses.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, Serialize, Deserialize)]
+struct ReceiptListPage {
+    data: Vec<JsonReceipt>,
+    paging: Paging,
+}
+
+/// Used by `ReceiptListPage` for deserializing the paging information of `GET /receipts`
+/// responses.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, Serialize, Deserialize)]
+struct Paging {
+    offset: usize,
+    limit: usize,
+    total: usize,
+    next: String,
+}
+
+/// Used by `ReceiptListPage` for deserializing transaction receipts.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonReceipt {
+    index: u64,
+    transaction_id: String,
+    #[serde(flatten)]
+    result: JsonReceiptResult,
+}
+
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum JsonReceiptResult {
+    Valid {
+        state_changes: Vec<JsonStateChange>,
+        events: Vec<JsonEvent>,
+        data: Vec<Vec<u8>>,
+    },
+    Invalid {
+        error_message: String,
+        error_data: Vec<u8>,
+    },
+}
+
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonStateChange {
+    Set { key: String, value: Vec<u8> },
+    Delete { key: String },
+}
+
+#[cfg(feature = "transaction-receipts")]
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonEvent {
+    event_type: String,
+    attributes: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "transaction-receipts")]
+impl From<JsonReceipt> for Receipt {
+    fn from(json: JsonReceipt) -> Self {
+        let transaction_result = match json.result {
+            JsonReceiptResult::Valid {
+                state_changes,
+                events,
+                data,
+            } => TransactionResult::Valid {
+                state_changes: state_changes
+                    .into_iter()
+                    .map(|change| match change {
+                        JsonStateChange::Set { key, value } => StateChange::Set { key, value },
+                        JsonStateChange::Delete { key } => StateChange::Delete { key },
+                    })
+                    .collect(),
+                events: events
+                    .into_iter()
+                    .map(|event| Event {
+                        event_type: event.event_type,
+                        attributes: event.attributes,
+                        data: event.data,
+                    })
+                    .collect(),
+                data,
+            },
+            JsonReceiptResult::Invalid {
+                error_message,
+                error_data,
+            } => TransactionResult::Invalid {
+                error_message,
+                error_data,
+            },
+        };
+
+        Self {
+            index: json.index,
+            receipt: TransactionReceipt {
+                transaction_id: json.transaction_id,
+                transaction_result,
+            },
+        }
+    }
+}
+
 /// Used for deserializing error responses from the Scabbard REST API.
 #[derive(Debug, Serialize, Deserialize)]
 struct ErrorResponse {
@@ -470,6 +861,11 @@ mod tests {
     const MOCK_SERVICE_ID: &str = "ABCD";
     const MOCK_BATCH_ID: &str = "batch_id";
     const MOCK_STATE_ROOT_HASH: &str = "abcd";
+    #[cfg(feature = "historical-state")]
+    const MOCK_HISTORICAL_STATE_ROOT: &str =
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    const MOCK_PRUNED_STATE_ROOT: &str =
+        "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
 
     const MOCK_AUTH: &str = "Bearer Cylinder:eyJhbGciOiJzZWNwMjU2azEiLCJ0eXAiOiJjeWxpbmRlcitqd3QifQ==.\
     eyJpc3MiOiIwMjA5MWEwNmNjNDZjNWUwZDg4ZTg5Mjg0OTM2ZWRiMTY4MDBiMDNiNTZhOGYxYjdlYzI5MmYyMzJiN2M4Mzg1YTIifQ==.\
@@ -660,6 +1056,53 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    /// Verify that the `ScabbardClient::get_state_with_prefix_at_root` method works properly.
+    #[cfg(feature = "historical-state")]
+    #[test]
+    fn get_state_with_prefix_at_root() {
+        let resource_manager = ResourceManager::new();
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(resource_manager.resources());
+
+        let client = ReqwestScabbardClientBuilder::new()
+            .with_url(&format!("http://{}", bind_url))
+            .with_auth(MOCK_AUTH)
+            .build()
+            .expect("unable to build client");
+        let service_id = ServiceId::new(MOCK_CIRCUIT_ID, MOCK_SERVICE_ID);
+
+        // Verify that a request for an available state root is successful and returns the right
+        // value
+        let entries = client
+            .get_state_with_prefix_at_root(&service_id, None, MOCK_HISTORICAL_STATE_ROOT)
+            .expect("Failed to get all entries at state root");
+        assert_eq!(entries, vec![mock_state_entry().into()]);
+
+        let entries = client
+            .get_state_with_prefix_at_root(&service_id, Some("01"), MOCK_HISTORICAL_STATE_ROOT)
+            .expect("Failed to get entries under prefix at state root");
+        assert_eq!(entries, vec![]);
+
+        // Verify that a pruned state root results in a distinct error
+        let err = client
+            .get_state_with_prefix_at_root(&service_id, None, MOCK_PRUNED_STATE_ROOT)
+            .expect_err("Pruned state root should result in an error");
+        assert!(err.to_string().contains("may have been pruned"));
+
+        // Verify that an invalid state root results in an error being returned without a request
+        assert!(client
+            .get_state_with_prefix_at_root(&service_id, None, "not a state root")
+            .is_err());
+        assert!(client
+            .get_state_with_prefix_at_root(&service_id, None, "abcd")
+            .is_err());
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     /// Verify that the `ScabbardClient::get_current_state_root` method works properly.
     #[test]
     fn get_current_state_root() {
@@ -959,6 +1402,7 @@ mod tests {
                             web::Query::from_query(request.query_string())
                                 .expect("Failed to get query string");
                         let prefix = query.get("prefix").map(String::as_str);
+                        let state_root = query.get("state_root").map(String::as_str);
 
                         if internal_server_error_clone.load(Ordering::SeqCst) {
                             let response = ErrorResponse {
@@ -969,6 +1413,11 @@ mod tests {
                                     .json(response)
                                     .into_future(),
                             )
+                        } else if state_root == Some(MOCK_PRUNED_STATE_ROOT) {
+                            let response = ErrorResponse {
+                                message: "State root is not available".into(),
+                            };
+                            Box::new(HttpResponse::Gone().json(response).into_future())
                         } else {
                             let return_entry = match prefix {
                                 Some(prefix) => mock_state_entry().address.starts_with(prefix),
@@ -990,6 +1439,7 @@ mod tests {
                         web::Query::from_query(request.query_string())
                             .expect("Failed to get query string");
                     let prefix = query.get("prefix").map(String::as_str);
+                    let state_root = query.get("state_root").map(String::as_str);
 
                     if internal_server_error_clone.load(Ordering::SeqCst) {
                         let response = ErrorResponse {
@@ -1000,6 +1450,11 @@ mod tests {
                                 .json(response)
                                 .into_future(),
                         )
+                    } else if state_root == Some(MOCK_PRUNED_STATE_ROOT) {
+                        let response = ErrorResponse {
+                            message: "State root is not available".into(),
+                        };
+                        Box::new(HttpResponse::Gone().json(response).into_future())
                     } else {
                         let return_entry = match prefix {
                             Some(prefix) => mock_state_entry().address.starts_with(prefix),
@@ -16,6 +16,9 @@
 
 embed_migrations!("./src/migrations/diesel/sqlite/migrations");
 
+#[cfg(feature = "store-schema-check")]
+use std::collections::HashSet;
+
 use diesel::sqlite::SqliteConnection;
 use diesel::Connection;
 use diesel_migrations::MigrationConnection;
@@ -67,3 +70,34 @@ pub fn any_pending_migrations(conn: &SqliteConnection) -> Result<bool, InternalE
 
     Ok(current_version == latest_version)
 }
+
+/// List the versions of the migrations which have not yet been applied, oldest first
+///
+/// # Arguments
+///
+/// * `conn` - Connection to SQLite database
+///
+#[cfg(feature = "store-schema-check")]
+pub fn list_pending_migrations(conn: &SqliteConnection) -> Result<Vec<String>, InternalError> {
+    let applied_versions = conn.previously_run_migration_versions().unwrap_or_default();
+
+    // Diesel 1.4 only allows access to the list of migrations via attempting
+    // to run the migrations, so we'll do that in a test transaction.
+    let expected_versions = conn
+        .test_transaction::<Result<HashSet<String>, InternalError>, (), _>(|| {
+            Ok(match run_migrations(conn) {
+                Ok(_) => conn
+                    .previously_run_migration_versions()
+                    .map_err(|err| InternalError::from_source(Box::new(err))),
+                Err(err) => Err(InternalError::from_source(Box::new(err))),
+            })
+        })?;
+
+    let mut pending = expected_versions
+        .difference(&applied_versions)
+        .cloned()
+        .collect::<Vec<_>>();
+    pending.sort();
+
+    Ok(pending)
+}
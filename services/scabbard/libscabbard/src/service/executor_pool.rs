@@ -0,0 +1,417 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A pool of executor threads shared by the scabbard services on a node.
+//!
+//! By default, each scabbard service starts its own transact executor, which costs several
+//! threads per service. When an [`ExecutorPool`] is configured, services instead submit their
+//! batches to the pool, where a fixed number of workers execute them. Pending batches are queued
+//! per service and the workers serve the services in round-robin order, so a busy service cannot
+//! starve the others.
+
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Condvar, Mutex,
+};
+use std::thread;
+
+use splinter::error::{InternalError, InvalidArgumentError};
+use transact::{
+    context::{manager::sync::ContextManager, ContextId},
+    handler::{ApplyError, ContextError, TransactionContext, TransactionHandler},
+    protocol::receipt::Event,
+    scheduler::{
+        ExecutionTask, ExecutionTaskCompletionNotification, ExecutionTaskCompletionNotifier,
+        InvalidTransactionResult,
+    },
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A fixed-size pool of threads that executes batches for many scabbard services.
+///
+/// The pool is cheap to clone; all clones share the same workers. The workers are stopped once
+/// the last clone is dropped.
+#[derive(Clone)]
+pub struct ExecutorPool {
+    shared: Arc<PoolShared>,
+    size: usize,
+    _shutdown: Arc<ShutdownOnDrop>,
+}
+
+impl ExecutorPool {
+    /// Starts a pool with the given number of worker threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if `size` is 0 or if a worker thread could not be started.
+    pub fn new(size: usize) -> Result<Self, InternalError> {
+        if size == 0 {
+            return Err(InternalError::from_source(Box::new(
+                InvalidArgumentError::new("size", "must be greater than 0"),
+            )));
+        }
+
+        let shared = Arc::new(PoolShared::default());
+        for _ in 0..size {
+            spawn_worker(shared.clone())?;
+        }
+
+        Ok(Self {
+            _shutdown: Arc::new(ShutdownOnDrop(shared.clone())),
+            shared,
+            size,
+        })
+    }
+
+    /// Returns the number of worker threads in the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Queues a job on behalf of the given service.
+    pub(super) fn submit(&self, service: &str, job: Job) -> Result<(), InternalError> {
+        self.shared
+            .queue
+            .lock()
+            .map_err(|_| InternalError::with_message("Executor pool lock poisoned".into()))?
+            .push(service, job);
+        self.shared.available.notify_one();
+        Ok(())
+    }
+
+    /// Starts a new worker to take the place of one that is blocked on a job which will not
+    /// complete in a reasonable time. The next worker to finish a job exits, so the pool returns
+    /// to its original size once the blocked job returns.
+    pub(super) fn replace_blocked_worker(&self) -> Result<(), InternalError> {
+        self.shared
+            .queue
+            .lock()
+            .map_err(|_| InternalError::with_message("Executor pool lock poisoned".into()))?
+            .retiring += 1;
+        spawn_worker(self.shared.clone())
+    }
+}
+
+#[derive(Default)]
+struct PoolShared {
+    queue: Mutex<PoolQueue>,
+    available: Condvar,
+    worker_count: AtomicUsize,
+}
+
+/// The pending jobs of each service, along with the order in which the services are served.
+#[derive(Default)]
+struct PoolQueue {
+    jobs: HashMap<String, VecDeque<Job>>,
+    ready: VecDeque<String>,
+    /// The number of workers that should exit instead of taking another job
+    retiring: usize,
+    shutdown: bool,
+}
+
+impl PoolQueue {
+    fn push(&mut self, service: &str, job: Job) {
+        match self.jobs.entry(service.to_string()) {
+            Entry::Occupied(mut entry) => entry.get_mut().push_back(job),
+            Entry::Vacant(entry) => {
+                entry.insert(VecDeque::from(vec![job]));
+                self.ready.push_back(service.to_string());
+            }
+        }
+    }
+
+    /// Takes the next job of the service at the front of the ready queue. If the service has
+    /// further jobs, it is moved to the back of the queue.
+    fn next(&mut self) -> Option<Job> {
+        let service = self.ready.pop_front()?;
+        let jobs = self.jobs.get_mut(&service)?;
+        let job = jobs.pop_front();
+        if jobs.is_empty() {
+            self.jobs.remove(&service);
+        } else {
+            self.ready.push_back(service);
+        }
+        job
+    }
+}
+
+struct ShutdownOnDrop(Arc<PoolShared>);
+
+impl Drop for ShutdownOnDrop {
+    fn drop(&mut self) {
+        match self.0.queue.lock() {
+            Ok(mut queue) => queue.shutdown = true,
+            Err(_) => error!("Unable to stop executor pool; lock poisoned"),
+        }
+        self.0.available.notify_all();
+    }
+}
+
+fn spawn_worker(shared: Arc<PoolShared>) -> Result<(), InternalError> {
+    let worker_id = shared.worker_count.fetch_add(1, Ordering::SeqCst);
+    thread::Builder::new()
+        .name(format!("Thread-ScabbardExecutorPool-{}", worker_id))
+        .spawn(move || run_worker(&shared))
+        .map(|_| ())
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+fn run_worker(shared: &PoolShared) {
+    while let Some(job) = next_job(shared) {
+        if catch_unwind(AssertUnwindSafe(job)).is_err() {
+            error!("Batch execution panicked in executor pool worker");
+        }
+
+        match shared.queue.lock() {
+            Ok(mut queue) if queue.retiring > 0 => {
+                queue.retiring -= 1;
+                return;
+            }
+            Ok(_) => (),
+            Err(_) => {
+                error!("Executor pool lock poisoned; stopping worker");
+                return;
+            }
+        }
+    }
+}
+
+/// Waits for the next job. Returns `None` if the pool has been shut down.
+fn next_job(shared: &PoolShared) -> Option<Job> {
+    let mut queue = match shared.queue.lock() {
+        Ok(queue) => queue,
+        Err(_) => {
+            error!("Executor pool lock poisoned; stopping worker");
+            return None;
+        }
+    };
+    loop {
+        if queue.shutdown {
+            return None;
+        }
+        if let Some(job) = queue.next() {
+            return Some(job);
+        }
+        queue = match shared.available.wait(queue) {
+            Ok(queue) => queue,
+            Err(_) => {
+                error!("Executor pool lock poisoned; stopping worker");
+                return None;
+            }
+        };
+    }
+}
+
+/// Executes the tasks of a scheduler with the given transaction handlers, notifying the
+/// scheduler as each task completes. Returns once the scheduler has no more tasks.
+pub(super) fn execute_tasks(
+    handlers: &[Box<dyn TransactionHandler>],
+    context_manager: &ContextManager,
+    tasks: Box<dyn Iterator<Item = ExecutionTask> + Send>,
+    notifier: Box<dyn ExecutionTaskCompletionNotifier>,
+) {
+    for task in tasks {
+        let (pair, context_id) = task.take();
+        let header = pair.header();
+        let transaction_id = pair.transaction().header_signature().to_string();
+
+        let result = match handlers.iter().find(|handler| {
+            handler.family_name() == header.family_name()
+                && handler
+                    .family_versions()
+                    .iter()
+                    .any(|version| version == header.family_version())
+        }) {
+            Some(handler) => handler.apply(
+                &pair,
+                &mut PooledContext {
+                    context_manager,
+                    context_id: &context_id,
+                },
+            ),
+            None => Err(ApplyError::InvalidTransaction(format!(
+                "no handler for transaction family {} {}",
+                header.family_name(),
+                header.family_version()
+            ))),
+        };
+
+        let notification = match result {
+            Ok(()) => ExecutionTaskCompletionNotification::Valid(context_id, transaction_id),
+            Err(ApplyError::InvalidTransaction(error_message)) => {
+                ExecutionTaskCompletionNotification::Invalid(
+                    context_id,
+                    InvalidTransactionResult {
+                        transaction_id,
+                        error_message,
+                        error_data: vec![],
+                    },
+                )
+            }
+            Err(err) => ExecutionTaskCompletionNotification::Invalid(
+                context_id,
+                InvalidTransactionResult {
+                    transaction_id,
+                    error_message: err.to_string(),
+                    error_data: vec![],
+                },
+            ),
+        };
+        notifier.notify(notification);
+    }
+}
+
+/// Gives a transaction handler access to the context created for its transaction.
+struct PooledContext<'a> {
+    context_manager: &'a ContextManager,
+    context_id: &'a ContextId,
+}
+
+impl<'a> TransactionContext for PooledContext<'a> {
+    fn get_state_entries(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+        self.context_manager
+            .get(self.context_id, addresses)
+            .map_err(to_context_error)
+    }
+
+    fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+        for (address, value) in entries {
+            self.context_manager
+                .set_state(self.context_id, address, value)
+                .map_err(to_context_error)?;
+        }
+        Ok(())
+    }
+
+    fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, ContextError> {
+        let mut deleted = vec![];
+        for address in addresses {
+            if self
+                .context_manager
+                .delete_state(self.context_id, address)
+                .map_err(to_context_error)?
+                .is_some()
+            {
+                deleted.push(address.clone());
+            }
+        }
+        Ok(deleted)
+    }
+
+    fn add_receipt_data(&self, data: Vec<u8>) -> Result<(), ContextError> {
+        self.context_manager
+            .add_data(self.context_id, data)
+            .map_err(to_context_error)
+    }
+
+    fn add_event(
+        &self,
+        event_type: String,
+        attributes: Vec<(String, String)>,
+        data: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        self.context_manager
+            .add_event(
+                self.context_id,
+                Event {
+                    event_type,
+                    attributes,
+                    data,
+                },
+            )
+            .map_err(to_context_error)
+    }
+}
+
+fn to_context_error<E: std::fmt::Display>(err: E) -> ContextError {
+    ContextError::ResponseAttributeError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+    use std::time::Duration;
+
+    /// Verify that jobs of different services are taken in round-robin order, regardless of the
+    /// order in which they were queued.
+    #[test]
+    fn test_queue_round_robin() {
+        let (tx, rx) = channel();
+        let mut queue = PoolQueue::default();
+        for (service, n) in &[("a", 1), ("a", 2), ("a", 3), ("b", 1), ("c", 1), ("b", 2)] {
+            let tx = tx.clone();
+            queue.push(
+                service,
+                Box::new(move || tx.send(format!("{}{}", service, n)).unwrap()),
+            );
+        }
+
+        while let Some(job) = queue.next() {
+            job();
+        }
+
+        let order = rx.try_iter().collect::<Vec<_>>();
+        assert_eq!(order, vec!["a1", "b1", "c1", "a2", "b2", "a3"]);
+        assert!(queue.jobs.is_empty());
+    }
+
+    /// Verify that a pool runs submitted jobs, and keeps running jobs after a worker is replaced
+    /// while blocked.
+    #[test]
+    fn test_pool_runs_jobs() {
+        let pool = ExecutorPool::new(1).expect("Failed to start pool");
+        let (tx, rx) = channel();
+        let (unblock_tx, unblock_rx) = channel::<()>();
+
+        pool.submit(
+            "blocked",
+            Box::new(move || {
+                let _ = unblock_rx.recv();
+            }),
+        )
+        .expect("Failed to submit job");
+        pool.replace_blocked_worker()
+            .expect("Failed to replace worker");
+
+        for i in 0..3 {
+            let tx = tx.clone();
+            pool.submit("svc", Box::new(move || tx.send(i).unwrap()))
+                .expect("Failed to submit job");
+        }
+
+        let results = (0..3)
+            .map(|_| {
+                rx.recv_timeout(Duration::from_secs(5))
+                    .expect("Job did not run")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![0, 1, 2]);
+
+        unblock_tx.send(()).unwrap();
+    }
+
+    /// Verify that a pool cannot be created without workers.
+    #[test]
+    fn test_empty_pool() {
+        assert!(ExecutorPool::new(0).is_err());
+    }
+}
@@ -42,14 +42,23 @@ use splinter::service::instance::{
     FactoryCreateError, ServiceArgValidator, ServiceFactory, ServiceInstance,
 };
 use splinter::service::instance::{OrchestratableService, OrchestratableServiceFactory};
+#[cfg(feature = "service-arg-schema")]
+use splinter::service::instance::{ServiceArgSchema, ServiceArgType};
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use transact::database::Database;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use transact::state::merkle::sql;
 
 use crate::hex::parse_hex;
-#[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
+#[cfg(all(
+    feature = "shared-executor-pool",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use crate::service::ExecutorPool;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
 use crate::service::ScabbardStatePurgeHandler;
+#[cfg(feature = "transaction-families")]
+use crate::service::TransactionFamily;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use crate::service::{
     error::ScabbardError,
@@ -161,6 +170,8 @@ pub struct ScabbardFactoryBuilder {
     storage_configuration: Option<ScabbardStorageConfiguration>,
     signature_verifier_factory: Option<Arc<Mutex<Box<dyn VerifierFactory>>>>,
     enable_state_autocleanup: Option<bool>,
+    #[cfg(feature = "shared-executor-pool")]
+    executor_pool_size: Option<usize>,
 }
 
 impl ScabbardFactoryBuilder {
@@ -227,6 +238,15 @@ impl ScabbardFactoryBuilder {
         self
     }
 
+    /// Sets the number of threads in a pool that executes the batches of all services created by
+    /// the resulting factory. By default, or if the size is 0, each service starts its own
+    /// executor.
+    #[cfg(feature = "shared-executor-pool")]
+    pub fn with_executor_pool_size(mut self, executor_pool_size: usize) -> Self {
+        self.executor_pool_size = Some(executor_pool_size);
+        self
+    }
+
     pub fn with_storage_configuration(
         mut self,
         storage_configuration: ScabbardStorageConfiguration,
@@ -315,6 +335,19 @@ impl ScabbardFactoryBuilder {
 
         let state_autocleanup_enabled = self.enable_state_autocleanup.unwrap_or_default();
 
+        #[cfg(feature = "shared-executor-pool")]
+        let executor_pool = self
+            .executor_pool_size
+            .filter(|size| *size > 0)
+            .map(ExecutorPool::new)
+            .transpose()
+            .map_err(|err| {
+                InvalidStateError::with_message(format!(
+                    "Unable to start the scabbard executor pool: {}",
+                    err
+                ))
+            })?;
+
         Ok(ScabbardFactory {
             service_types: vec![SERVICE_TYPE.into()],
             #[cfg(feature = "lmdb")]
@@ -324,6 +357,8 @@ impl ScabbardFactoryBuilder {
             state_autocleanup_enabled,
             store_factory_config,
             signature_verifier_factory,
+            #[cfg(feature = "shared-executor-pool")]
+            executor_pool,
         })
     }
 
@@ -493,10 +528,39 @@ pub struct ScabbardFactory {
     signature_verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
     #[cfg(any(feature = "postgres", feature = "sqlite"))]
     state_autocleanup_enabled: bool,
+    #[cfg(all(
+        feature = "shared-executor-pool",
+        any(feature = "postgres", feature = "sqlite")
+    ))]
+    executor_pool: Option<ExecutorPool>,
 }
 
 pub struct ScabbardArgValidator;
 
+impl ScabbardArgValidator {
+    /// Returns the schema of the arguments accepted by scabbard, with this validator attached to
+    /// check the contents of the arguments.
+    #[cfg(feature = "service-arg-schema")]
+    pub fn schema() -> ServiceArgSchema {
+        let schema = ServiceArgSchema::new()
+            .with_required_arg("admin_keys", ServiceArgType::List)
+            .with_required_arg("peer_services", ServiceArgType::List)
+            .with_optional_arg("coordinator_timeout", ServiceArgType::Integer)
+            .with_optional_arg("version", ServiceArgType::String);
+
+        #[cfg(feature = "batch-idempotency-keys")]
+        let schema = schema.with_optional_arg("idempotency_window", ServiceArgType::Integer);
+
+        #[cfg(feature = "execution-timeout")]
+        let schema = schema.with_optional_arg("execution_timeout", ServiceArgType::Integer);
+
+        #[cfg(feature = "transaction-families")]
+        let schema = schema.with_optional_arg("transaction_families", ServiceArgType::List);
+
+        schema.with_validator(Box::new(ScabbardArgValidator))
+    }
+}
+
 impl ServiceArgValidator for ScabbardArgValidator {
     fn validate(&self, args: &HashMap<String, String>) -> Result<(), InvalidArgumentError> {
         let peer_services_str = args
@@ -547,6 +611,44 @@ impl ServiceArgValidator for ScabbardArgValidator {
             }
         }
 
+        #[cfg(feature = "batch-idempotency-keys")]
+        if let Some(window) = args.get("idempotency_window") {
+            window.parse::<u64>().map_err(|err| {
+                InvalidArgumentError::new(
+                    "idempotency_window",
+                    format!("{:?} is not a valid number of seconds: {}", window, err),
+                )
+            })?;
+        }
+
+        #[cfg(feature = "execution-timeout")]
+        if let Some(timeout) = args.get("execution_timeout") {
+            match timeout.parse::<u64>() {
+                Ok(0) => {
+                    return Err(InvalidArgumentError::new(
+                        "execution_timeout",
+                        "must be greater than 0",
+                    ))
+                }
+                Ok(_) => (),
+                Err(err) => {
+                    return Err(InvalidArgumentError::new(
+                        "execution_timeout",
+                        format!(
+                            "{:?} is not a valid number of milliseconds: {}",
+                            timeout, err
+                        ),
+                    ))
+                }
+            }
+        }
+
+        #[cfg(feature = "transaction-families")]
+        if let Some(families) = args.get("transaction_families") {
+            parse_transaction_families(families)
+                .map_err(|err| InvalidArgumentError::new("transaction_families", err))?;
+        }
+
         Ok(())
     }
 }
@@ -566,6 +668,12 @@ impl ServiceFactory for ScabbardFactory {
     /// - `coordinator_timeout`: the length of time (in milliseconds) that the network has to
     ///   commit a proposal before the coordinator rejects it (if not provided, default is 30
     ///   seconds)
+    /// - `execution_timeout`: the length of time (in milliseconds) that a batch may take to
+    ///   execute before it is marked as invalid (if not provided, default is 5 minutes)
+    /// - `idempotency_window`: the length of time (in seconds) for which batch submissions made
+    ///   with an idempotency key are remembered (if not provided, default is 5 minutes)
+    /// - `transaction_families`: list of Transact transaction families to run in addition to
+    ///   Sabre (possible values depend on the enabled features: "command", "xo")
     /// - `version`: the protocol version for scabbard (possible values: "1", "2") (default: "1")
     #[cfg(any(feature = "postgres", feature = "sqlite"))]
     fn create(
@@ -663,6 +771,42 @@ impl ScabbardFactory {
                 ))),
             })
             .transpose()?;
+        #[cfg(feature = "batch-idempotency-keys")]
+        let idempotency_window = args
+            .get("idempotency_window")
+            .map(|window| match window.parse::<u64>() {
+                Ok(window) => Ok(Duration::from_secs(window)),
+                Err(err) => Err(FactoryCreateError::InvalidArguments(format!(
+                    "invalid idempotency_window: {}",
+                    err
+                ))),
+            })
+            .transpose()?;
+        #[cfg(feature = "execution-timeout")]
+        let execution_timeout = args
+            .get("execution_timeout")
+            .map(|timeout| match timeout.parse::<u64>() {
+                Ok(0) => Err(FactoryCreateError::InvalidArguments(
+                    "invalid execution_timeout: must be greater than 0".into(),
+                )),
+                Ok(timeout) => Ok(Duration::from_millis(timeout)),
+                Err(err) => Err(FactoryCreateError::InvalidArguments(format!(
+                    "invalid execution_timeout: {}",
+                    err
+                ))),
+            })
+            .transpose()?;
+        #[cfg(feature = "transaction-families")]
+        let transaction_families = args
+            .get("transaction_families")
+            .map(|families| parse_transaction_families(families))
+            .transpose()
+            .map_err(|err| {
+                FactoryCreateError::InvalidArguments(format!(
+                    "invalid transaction_families: {}",
+                    err
+                ))
+            })?;
         let version = ScabbardVersion::try_from(args.get("version").map(String::as_str))
             .map_err(FactoryCreateError::InvalidArguments)?;
 
@@ -747,6 +891,14 @@ impl ScabbardFactory {
             ),
         };
 
+        #[cfg(feature = "purge-service-stores")]
+        let state_purge: Box<dyn ScabbardStatePurgeHandler> = Box::new(ServiceStoresPurgeHandler {
+            service: format!("{}::{}", circuit_id, service_id),
+            state_purge,
+            receipt_store: receipt_store.clone(),
+            commit_hash_store: commit_hash_store.clone(),
+        });
+
         Scabbard::new(
             service_id,
             circuit_id,
@@ -766,6 +918,34 @@ impl ScabbardFactory {
             admin_keys,
             coordinator_timeout,
         )
+        .map(|scabbard| {
+            #[cfg(feature = "batch-idempotency-keys")]
+            if let Some(window) = idempotency_window {
+                return scabbard.with_idempotency_window(window);
+            }
+            scabbard
+        })
+        .and_then(|scabbard| {
+            #[cfg(feature = "execution-timeout")]
+            if let Some(timeout) = execution_timeout {
+                return scabbard.with_execution_timeout(timeout);
+            }
+            Ok(scabbard)
+        })
+        .and_then(|scabbard| {
+            #[cfg(feature = "transaction-families")]
+            if let Some(families) = transaction_families {
+                return scabbard.with_transaction_families(families);
+            }
+            Ok(scabbard)
+        })
+        .and_then(|scabbard| {
+            #[cfg(feature = "shared-executor-pool")]
+            if let Some(executor_pool) = &self.executor_pool {
+                return scabbard.with_executor_pool(executor_pool.clone());
+            }
+            Ok(scabbard)
+        })
         .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))
     }
 
@@ -880,6 +1060,15 @@ fn parse_list(values_list: &str) -> Result<Vec<String>, String> {
     }
 }
 
+/// Parse the `transaction_families` service argument into the list of families to run.
+#[cfg(feature = "transaction-families")]
+fn parse_transaction_families(families: &str) -> Result<Vec<TransactionFamily>, String> {
+    parse_list(families)?
+        .iter()
+        .map(|family| family.trim().parse::<TransactionFamily>())
+        .collect()
+}
+
 #[cfg(feature = "postgres")]
 fn get_postgres_pool(
     url: &str,
@@ -923,6 +1112,79 @@ impl ScabbardStatePurgeHandler for LmdbScabbardPurgeHandler {
     fn purge_state(&self) -> Result<(), InternalError> {
         self.db_purge_handle.purge()
     }
+
+    #[cfg(feature = "purge-service-stores")]
+    fn state_size(&self) -> Option<u64> {
+        match self.db_purge_handle.size() {
+            Ok(size) => Some(size),
+            Err(err) => {
+                warn!("Unable to determine size of LMDB state: {}", err);
+                None
+            }
+        }
+    }
+}
+
+/// Purges all of a service's stored data: its merkle state, transaction receipts, and commit
+/// hash. The amount of data removed is logged once the purge is complete.
+#[cfg(all(
+    feature = "purge-service-stores",
+    any(feature = "postgres", feature = "sqlite")
+))]
+struct ServiceStoresPurgeHandler {
+    service: String,
+    state_purge: Box<dyn ScabbardStatePurgeHandler>,
+    receipt_store: Arc<dyn ReceiptStore>,
+    commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
+}
+
+#[cfg(all(
+    feature = "purge-service-stores",
+    any(feature = "postgres", feature = "sqlite")
+))]
+impl ScabbardStatePurgeHandler for ServiceStoresPurgeHandler {
+    fn purge_state(&self) -> Result<(), InternalError> {
+        let state_size = self.state_size();
+        self.state_purge.purge_state()?;
+
+        let receipt_ids = self
+            .receipt_store
+            .list_receipts_since(None)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .map(|receipt| receipt.map(|receipt| receipt.transaction_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        for id in &receipt_ids {
+            self.receipt_store
+                .remove_txn_receipt_by_id(id.clone())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        }
+
+        self.commit_hash_store
+            .remove_current_commit_hash()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        match state_size {
+            Some(size) => info!(
+                "Purged stores for service {}: reclaimed {} bytes of state and removed {} \
+                 transaction receipts",
+                self.service,
+                size,
+                receipt_ids.len()
+            ),
+            None => info!(
+                "Purged stores for service {}: removed state and {} transaction receipts",
+                self.service,
+                receipt_ids.len()
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn state_size(&self) -> Option<u64> {
+        self.state_purge.state_size()
+    }
 }
 
 #[cfg(feature = "postgres")]
@@ -1095,6 +1357,56 @@ mod tests {
         assert!(validator.validate(&args).is_err());
     }
 
+    /// Verify arg validation returns an error if the `idempotency_window` argument is not a valid
+    /// number of seconds
+    #[cfg(feature = "batch-idempotency-keys")]
+    #[test]
+    fn test_invalid_idempotency_window_validation() {
+        let validator = ScabbardArgValidator;
+        let mut args = get_mock_args();
+        args.insert("idempotency_window".into(), "60".into());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("idempotency_window".into(), "one minute".into());
+        assert!(validator.validate(&args).is_err());
+    }
+
+    /// Verify arg validation accepts the supported transaction families and returns an error for
+    /// an unknown transaction family
+    #[cfg(feature = "transaction-families")]
+    #[test]
+    fn test_transaction_families_validation() {
+        let validator = ScabbardArgValidator;
+        let mut args = get_mock_args();
+        args.insert("transaction_families".into(), "sabre".into());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert(
+            "transaction_families".into(),
+            "[\"sabre\",\"unknown\"]".into(),
+        );
+        assert!(validator.validate(&args).is_err());
+    }
+
+    /// Verify that the scabbard argument schema accepts valid arguments and rejects a misspelled
+    /// `admin_keys` argument by name, rather than only reporting the missing argument
+    #[cfg(feature = "service-arg-schema")]
+    #[test]
+    fn test_argument_schema_validation() {
+        let schema = ScabbardArgValidator::schema();
+        let mut args = get_mock_args();
+        args.insert("coordinator_timeout".into(), "30".into());
+        assert!(schema.validate(&args).is_ok());
+
+        let admin_keys = args.remove("admin_keys").expect("admin_keys not set");
+        args.insert("admin_key".into(), admin_keys);
+        let err = schema
+            .validate(&args)
+            .expect_err("misspelled argument was accepted");
+        assert_eq!(err.argument(), "admin_key");
+        assert!(err.message().contains("admin_keys"));
+    }
+
     fn get_factory() -> ScabbardFactory {
         let connection_manager = ConnectionManager::<diesel::SqliteConnection>::new(":memory:");
         let pool = Pool::builder()
@@ -1126,6 +1438,8 @@ mod tests {
             state_autocleanup_enabled: false,
             store_factory_config,
             signature_verifier_factory: Arc::new(Mutex::new(Box::new(Secp256k1Context::new()))),
+            #[cfg(feature = "shared-executor-pool")]
+            executor_pool: None,
         }
     }
 
@@ -0,0 +1,133 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deduplication of batch submissions by idempotency key.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The default length of time for which an idempotency key is remembered.
+pub(super) const DEFAULT_IDEMPOTENCY_WINDOW: Duration = Duration::from_secs(300); // 5 minutes
+
+/// The result of adding batches with an idempotency key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum IdempotentSubmission {
+    /// The batches were not previously submitted with the key. Contains the batch status link, or
+    /// `None` if no valid batches were provided.
+    Added(Option<String>),
+    /// The same batches were already submitted with the key. Contains the batch status link that
+    /// was returned for the original submission.
+    Duplicate(String),
+    /// The key was already used to submit different batches.
+    KeyReused,
+}
+
+struct CachedSubmission {
+    batch_ids: Vec<String>,
+    link: String,
+    submitted_at: Instant,
+}
+
+/// Remembers the batches submitted with each idempotency key for a fixed window of time.
+pub(super) struct IdempotencyCache {
+    window: Duration,
+    submissions: HashMap<String, CachedSubmission>,
+}
+
+impl IdempotencyCache {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            submissions: HashMap::new(),
+        }
+    }
+
+    /// Checks whether the given batches were already submitted with the given key within the
+    /// window. Returns `None` if the key has not been used.
+    pub fn check(&mut self, key: &str, batch_ids: &[String]) -> Option<IdempotentSubmission> {
+        self.remove_expired();
+
+        self.submissions.get(key).map(|submission| {
+            if submission.batch_ids == batch_ids {
+                IdempotentSubmission::Duplicate(submission.link.clone())
+            } else {
+                IdempotentSubmission::KeyReused
+            }
+        })
+    }
+
+    /// Records that the given batches were submitted with the given key, resulting in `link`.
+    pub fn insert(&mut self, key: String, batch_ids: Vec<String>, link: String) {
+        self.submissions.insert(
+            key,
+            CachedSubmission {
+                batch_ids,
+                link,
+                submitted_at: Instant::now(),
+            },
+        );
+    }
+
+    fn remove_expired(&mut self) {
+        let window = self.window;
+        self.submissions
+            .retain(|_, submission| submission.submitted_at.elapsed() < window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a resubmission of the same batches with the same key is reported as a
+    /// duplicate, with the original link.
+    #[test]
+    fn duplicate_submission() {
+        let mut cache = IdempotencyCache::new(DEFAULT_IDEMPOTENCY_WINDOW);
+        let batch_ids = vec!["abc".to_string(), "def".to_string()];
+
+        assert_eq!(cache.check("key", &batch_ids), None);
+        cache.insert("key".into(), batch_ids.clone(), "/link".into());
+
+        assert_eq!(
+            cache.check("key", &batch_ids),
+            Some(IdempotentSubmission::Duplicate("/link".into()))
+        );
+        assert_eq!(cache.check("other-key", &batch_ids), None);
+    }
+
+    /// Verify that using a key to submit different batches is reported.
+    #[test]
+    fn key_reused() {
+        let mut cache = IdempotencyCache::new(DEFAULT_IDEMPOTENCY_WINDOW);
+        cache.insert("key".into(), vec!["abc".into()], "/link".into());
+
+        assert_eq!(
+            cache.check("key", &["def".to_string()]),
+            Some(IdempotentSubmission::KeyReused)
+        );
+    }
+
+    /// Verify that keys are forgotten once the window has elapsed.
+    #[test]
+    fn expired_submission() {
+        let mut cache = IdempotencyCache::new(Duration::from_millis(10));
+        let batch_ids = vec!["abc".to_string()];
+        cache.insert("key".into(), batch_ids.clone(), "/link".into());
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.check("key", &batch_ids), None);
+    }
+}
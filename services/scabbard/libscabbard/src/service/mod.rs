@@ -18,7 +18,11 @@
 
 mod consensus;
 mod error;
+#[cfg(feature = "shared-executor-pool")]
+mod executor_pool;
 pub(crate) mod factory;
+#[cfg(feature = "batch-idempotency-keys")]
+mod idempotency;
 mod shared;
 mod state;
 #[cfg(feature = "scabbardv3")]
@@ -53,16 +57,26 @@ use super::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type};
 use consensus::ScabbardConsensusManager;
 use error::ScabbardError;
 pub use error::StateSubscriberError;
+#[cfg(feature = "shared-executor-pool")]
+pub use executor_pool::ExecutorPool;
 pub use factory::ConnectionUri;
 pub use factory::ScabbardArgValidator;
 pub use factory::{ScabbardFactory, ScabbardFactoryBuilder, ScabbardStorageConfiguration};
+#[cfg(feature = "batch-idempotency-keys")]
+pub use idempotency::IdempotentSubmission;
+#[cfg(feature = "batch-idempotency-keys")]
+use idempotency::{IdempotencyCache, DEFAULT_IDEMPOTENCY_WINDOW};
 use shared::ScabbardShared;
 use state::merkle_state::MerkleState;
 use state::ScabbardState;
+#[cfg(feature = "transaction-families")]
+pub use state::TransactionFamily;
 pub use state::{
     BatchInfo, BatchInfoIter, BatchStatus, Events, InvalidTransaction, StateChange,
     StateChangeEvent, StateIter, StateSubscriber, ValidTransaction,
 };
+#[cfg(feature = "transaction-receipts")]
+pub use state::{CommittedReceipt, ReceiptQuery};
 
 pub const SERVICE_TYPE: &str = "scabbard";
 
@@ -92,6 +106,12 @@ impl TryFrom<Option<&str>> for ScabbardVersion {
 pub trait ScabbardStatePurgeHandler: Send + Sync {
     /// Purge the scabbard instances state.
     fn purge_state(&self) -> Result<(), splinter::error::InternalError>;
+
+    /// Returns the number of bytes that will be reclaimed by purging the state, if known.
+    #[cfg(feature = "purge-service-stores")]
+    fn state_size(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// A service for running Sawtooth Sabre smart contracts with two-phase commit consensus.
@@ -106,6 +126,9 @@ pub struct Scabbard {
     /// The coordinator timeout for the two-phase commit consensus engine
     coordinator_timeout: Duration,
     consensus: Arc<Mutex<Option<ScabbardConsensusManager>>>,
+    /// Batch submissions that were made with an idempotency key
+    #[cfg(feature = "batch-idempotency-keys")]
+    idempotency_cache: Arc<Mutex<IdempotencyCache>>,
 }
 
 impl Scabbard {
@@ -146,9 +169,9 @@ impl Scabbard {
             state_autocleanup_enabled,
             commit_hash_store,
             receipt_store,
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             service_id.clone(),
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             circuit_id.to_string(),
             admin_keys,
         )
@@ -166,9 +189,59 @@ impl Scabbard {
             purge_handler: purge_handler.into(),
             coordinator_timeout,
             consensus: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "batch-idempotency-keys")]
+            idempotency_cache: Arc::new(Mutex::new(IdempotencyCache::new(
+                DEFAULT_IDEMPOTENCY_WINDOW,
+            ))),
         })
     }
 
+    /// Set the length of time for which submissions made with an idempotency key are remembered;
+    /// the default is 5 minutes.
+    #[cfg(feature = "batch-idempotency-keys")]
+    pub fn with_idempotency_window(mut self, window: Duration) -> Self {
+        self.idempotency_cache = Arc::new(Mutex::new(IdempotencyCache::new(window)));
+        self
+    }
+
+    /// Set how long a batch may take to execute before it is marked as invalid.
+    #[cfg(feature = "execution-timeout")]
+    pub fn with_execution_timeout(
+        self,
+        execution_timeout: Duration,
+    ) -> Result<Self, ScabbardError> {
+        self.state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .set_execution_timeout(execution_timeout);
+        Ok(self)
+    }
+
+    /// Set the Transact transaction families that this service will run, in addition to Sabre.
+    #[cfg(feature = "transaction-families")]
+    pub fn with_transaction_families(
+        self,
+        transaction_families: Vec<TransactionFamily>,
+    ) -> Result<Self, ScabbardError> {
+        self.state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .set_transaction_families(transaction_families);
+        Ok(self)
+    }
+
+    /// Execute this service's batches on the given shared executor pool instead of starting a
+    /// dedicated executor when the service is started.
+    #[cfg(feature = "shared-executor-pool")]
+    pub fn with_executor_pool(self, executor_pool: ExecutorPool) -> Result<Self, ScabbardError> {
+        let service = format!("{}::{}", self.circuit_id, self.service_id);
+        self.state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .set_executor_pool(executor_pool, service);
+        Ok(self)
+    }
+
     /// Fetch the value at the given `address` in the scabbard service's state. Returns `None` if
     /// the `address` is not set.
     pub fn get_state_at_address(&self, address: &str) -> Result<Option<Vec<u8>>, ScabbardError> {
@@ -190,6 +263,25 @@ impl Scabbard {
             .get_state_with_prefix(prefix)?)
     }
 
+    /// Fetch a list of entries in the scabbard service's state as of the given historical
+    /// `state_root`. If a `prefix` is provided, only return entries whose addresses are under the
+    /// given address prefix.
+    ///
+    /// Returns `None` if the state root is not available, either because it never existed or
+    /// because it was pruned and its entries have been removed.
+    #[cfg(feature = "historical-state")]
+    pub fn get_state_with_prefix_at_root(
+        &self,
+        state_root: &str,
+        prefix: Option<&str>,
+    ) -> Result<Option<StateIter>, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .get_state_with_prefix_at_root(state_root, prefix)?)
+    }
+
     /// Get the current state root hash of the scabbard service's state.
     pub fn get_current_state_root(&self) -> Result<String, ScabbardError> {
         Ok(self
@@ -272,6 +364,40 @@ impl Scabbard {
         }
     }
 
+    /// Add the given batches, deduplicating the submission by the given idempotency key.
+    ///
+    /// If the same batches were already submitted with the key within the idempotency window, the
+    /// batches are not added again and the original batch status link is returned. If the key was
+    /// used to submit different batches within the window, the batches are rejected.
+    #[cfg(feature = "batch-idempotency-keys")]
+    pub fn add_batches_with_idempotency_key(
+        &self,
+        idempotency_key: &str,
+        batches: Vec<BatchPair>,
+    ) -> Result<IdempotentSubmission, ScabbardError> {
+        // The cache is held for the whole submission so concurrent retries can't both be added
+        let mut cache = self
+            .idempotency_cache
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?;
+
+        let batch_ids = batches
+            .iter()
+            .map(|batch| batch.batch().header_signature().to_string())
+            .collect::<Vec<_>>();
+
+        if let Some(submission) = cache.check(idempotency_key, &batch_ids) {
+            return Ok(submission);
+        }
+
+        let link = self.add_batches(batches)?;
+        if let Some(link) = &link {
+            cache.insert(idempotency_key.to_string(), batch_ids, link.clone());
+        }
+
+        Ok(IdempotentSubmission::Added(link))
+    }
+
     /// Get the `BatchInfo` for each specified batch.
     ///
     /// # Arguments
@@ -291,6 +417,20 @@ impl Scabbard {
         Ok(state.batch_history().get_batch_info(ids, wait)?)
     }
 
+    /// Get the receipts of the committed transactions that match the given query, in the order
+    /// the transactions were committed.
+    #[cfg(feature = "transaction-receipts")]
+    pub fn list_receipts(
+        &self,
+        query: &ReceiptQuery,
+    ) -> Result<Vec<CommittedReceipt>, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .list_receipts(query)?)
+    }
+
     pub fn get_events_since(&self, event_id: Option<String>) -> Result<Events, ScabbardError> {
         Ok(self
             .state
@@ -414,6 +554,11 @@ impl ServiceInstance for Scabbard {
         self.purge_handler.purge_state()
     }
 
+    #[cfg(feature = "circuit-purge-preview")]
+    fn state_size(&self) -> Result<Option<u64>, splinter::error::InternalError> {
+        Ok(self.purge_handler.state_size())
+    }
+
     fn handle_message(
         &self,
         message_bytes: &[u8],
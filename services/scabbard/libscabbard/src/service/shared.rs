@@ -133,6 +133,7 @@ impl ScabbardShared {
         gauge!(
             "splinter.scabbard.pending_batches",
             _batches,
+            "circuit" => self.circuit_id.clone(),
             "service" => format!("{}::{}", self.circuit_id, self.service_id)
         );
     }
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 pub mod merkle_state;
+#[cfg(feature = "transaction-families")]
+mod transaction_family;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::convert::TryFrom;
 use std::fmt;
+#[cfg(feature = "shared-executor-pool")]
+use std::sync::Mutex;
 use std::sync::{
-    mpsc::{channel, Receiver, Sender, TryRecvError},
+    mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError},
     Arc,
 };
 use std::time::{Duration, Instant, SystemTime};
@@ -31,15 +35,17 @@ use serde::{
 };
 #[cfg(feature = "events")]
 use splinter::events::{ParseBytes, ParseError};
+#[cfg(feature = "tracing")]
+use splinter::tracing::Span;
 #[cfg(test)]
 use transact::families::command::CommandTransactionHandler;
+#[cfg(not(feature = "transaction-families"))]
+use transact::families::sabre::{admin::SettingsAdminPermission, handler::SabreTransactionHandler};
 use transact::{
     context::manager::sync::ContextManager,
     execution::{adapter::static_adapter::StaticExecutionAdapter, executor::Executor},
-    families::sabre::{
-        admin::SettingsAdminPermission, handler::SabreTransactionHandler,
-        ADMINISTRATORS_SETTING_ADDRESS, ADMINISTRATORS_SETTING_KEY,
-    },
+    families::sabre::{ADMINISTRATORS_SETTING_ADDRESS, ADMINISTRATORS_SETTING_KEY},
+    handler::TransactionHandler,
     protocol::{
         batch::BatchPair,
         receipt::{TransactionReceipt, TransactionResult},
@@ -53,8 +59,13 @@ use transact::{
 
 use crate::protos::scabbard::{Setting, Setting_Entry};
 use crate::service::error::{ScabbardStateError, StateSubscriberError};
+#[cfg(feature = "shared-executor-pool")]
+use crate::service::executor_pool::{execute_tasks, ExecutorPool};
 use crate::store::CommitHashStore;
 
+#[cfg(feature = "transaction-families")]
+pub use transaction_family::TransactionFamily;
+
 const EXECUTION_TIMEOUT: u64 = 300; // five minutes
 const ITER_CACHE_SIZE: usize = 64;
 const COMPLETED_BATCH_INFO_ITER_RETRY: Duration = Duration::from_millis(100);
@@ -71,13 +82,26 @@ pub struct ScabbardState {
     executor: Option<Executor>,
     current_state_root: String,
     receipt_store: Arc<dyn ReceiptStore>,
-    pending_changes: Option<(String, Vec<TransactionReceipt>)>,
+    /// The signature and receipts of the prepared batch, and when it was prepared
+    pending_changes: Option<(String, Vec<TransactionReceipt>, Instant)>,
     event_subscribers: Vec<Box<dyn StateSubscriber>>,
-    #[cfg(feature = "metrics")]
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
     service_id: String,
-    #[cfg(feature = "metrics")]
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
     circuit_id: String,
     batch_history: BatchHistory,
+    /// The transaction families that the executor will run
+    #[cfg(feature = "transaction-families")]
+    transaction_families: Vec<TransactionFamily>,
+    /// How long a batch may take to execute before it is considered invalid
+    execution_timeout: Duration,
+    /// The shared pool that executes batches in place of a dedicated executor, along with the
+    /// name this service's batches are queued under
+    #[cfg(feature = "shared-executor-pool")]
+    executor_pool: Option<(ExecutorPool, String)>,
+    /// The transaction handlers run by the shared pool while the service is started
+    #[cfg(feature = "shared-executor-pool")]
+    pooled_handlers: Option<Arc<Mutex<Vec<Box<dyn TransactionHandler>>>>>,
 }
 
 impl ScabbardState {
@@ -86,8 +110,8 @@ impl ScabbardState {
         state_autocleanup_enabled: bool,
         commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
         receipt_store: Arc<dyn ReceiptStore>,
-        #[cfg(feature = "metrics")] service_id: String,
-        #[cfg(feature = "metrics")] circuit_id: String,
+        #[cfg(any(feature = "metrics", feature = "tracing"))] service_id: String,
+        #[cfg(any(feature = "metrics", feature = "tracing"))] circuit_id: String,
         admin_keys: Vec<String>,
     ) -> Result<Self, ScabbardStateError> {
         let current_state_root = if let Some(current_state_root) = commit_hash_store
@@ -149,23 +173,78 @@ impl ScabbardState {
             receipt_store,
             pending_changes: None,
             event_subscribers: vec![],
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             service_id,
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             circuit_id,
             batch_history: BatchHistory::new(),
+            #[cfg(feature = "transaction-families")]
+            transaction_families: vec![TransactionFamily::Sabre],
+            execution_timeout: Duration::from_secs(EXECUTION_TIMEOUT),
+            #[cfg(feature = "shared-executor-pool")]
+            executor_pool: None,
+            #[cfg(feature = "shared-executor-pool")]
+            pooled_handlers: None,
         })
     }
 
+    /// Set how long a batch may take to execute. A batch that exceeds this timeout is marked as
+    /// invalid, and the executor is replaced so that subsequent batches can be processed.
+    #[cfg(feature = "execution-timeout")]
+    pub fn set_execution_timeout(&mut self, execution_timeout: Duration) {
+        self.execution_timeout = execution_timeout;
+    }
+
+    /// Set the transaction families that will be run when the executor is started. Sabre is
+    /// always run, whether or not it is included.
+    #[cfg(feature = "transaction-families")]
+    pub fn set_transaction_families(&mut self, transaction_families: Vec<TransactionFamily>) {
+        let mut families = vec![TransactionFamily::Sabre];
+        for family in transaction_families {
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+        self.transaction_families = families;
+    }
+
+    /// Execute batches on the given shared pool instead of starting a dedicated executor. The
+    /// service's batches are queued under `service`.
+    #[cfg(feature = "shared-executor-pool")]
+    pub fn set_executor_pool(&mut self, executor_pool: ExecutorPool, service: String) {
+        self.executor_pool = Some((executor_pool, service));
+    }
+
     pub fn start_executor(&mut self) -> Result<(), ScabbardStateError> {
+        #[cfg(feature = "transaction-families")]
+        #[allow(unused_mut)]
+        let mut handlers: Vec<Box<dyn TransactionHandler>> = self
+            .transaction_families
+            .iter()
+            .map(TransactionFamily::handler)
+            .collect();
+        #[cfg(not(feature = "transaction-families"))]
+        #[allow(unused_mut)]
+        let mut handlers: Vec<Box<dyn TransactionHandler>> = vec![Box::new(
+            SabreTransactionHandler::new(Box::new(SettingsAdminPermission)),
+        )];
+
+        #[cfg(test)]
+        if !handlers
+            .iter()
+            .any(|handler| handler.family_name() == "command")
+        {
+            handlers.push(Box::new(CommandTransactionHandler::new()));
+        }
+
+        #[cfg(feature = "shared-executor-pool")]
+        if self.executor_pool.is_some() {
+            self.pooled_handlers = Some(Arc::new(Mutex::new(handlers)));
+            return Ok(());
+        }
+
         let mut executor = Executor::new(vec![Box::new(StaticExecutionAdapter::new_adapter(
-            vec![
-                Box::new(SabreTransactionHandler::new(Box::new(
-                    SettingsAdminPermission,
-                ))),
-                #[cfg(test)]
-                Box::new(CommandTransactionHandler::new()),
-            ],
+            handlers,
             self.context_manager.clone(),
         )?)]);
         executor
@@ -181,6 +260,10 @@ impl ScabbardState {
         if let Some(executor) = self.executor.take() {
             executor.stop();
         }
+        #[cfg(feature = "shared-executor-pool")]
+        {
+            self.pooled_handlers = None;
+        }
     }
 
     fn write_current_state_root(&self) -> Result<(), ScabbardStateError> {
@@ -220,15 +303,56 @@ impl ScabbardState {
         ))
     }
 
+    /// Fetch a list of entries in state as of the given `state_root`. If a `prefix` is provided,
+    /// only return entries whose addresses are under the given address prefix.
+    ///
+    /// Returns `None` if the state root is not available, either because it never existed or
+    /// because it was pruned and its entries have been removed.
+    #[cfg(feature = "historical-state")]
+    pub fn get_state_with_prefix_at_root(
+        &self,
+        state_root: &str,
+        prefix: Option<&str>,
+    ) -> Result<Option<StateIter>, ScabbardStateError> {
+        match self.merkle_state.leaves(&state_root.to_string(), prefix) {
+            Ok(leaves) => {
+                Ok(Some(Box::new(leaves.map(|res| {
+                    res.map_err(|e| ScabbardStateError(e.to_string()))
+                }))))
+            }
+            Err(MerkleRadixLeafReadError::InvalidStateError(_)) => Ok(None),
+            Err(err) => Err(ScabbardStateError(err.to_string())),
+        }
+    }
+
     /// Get the current state root hash.
     pub fn current_state_root(&self) -> &str {
         &self.current_state_root
     }
 
     pub fn prepare_change(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
-        let executor = self.executor.as_ref().ok_or_else(|| {
-            ScabbardStateError("attempting to prepare a change on a stopped service".into())
-        })?;
+        #[cfg(feature = "tracing")]
+        let mut span = Span::new("scabbard.execute_batch")
+            .with_attribute("circuit_id", &self.circuit_id)
+            .with_attribute("service_id", &self.service_id)
+            .with_attribute("batch_id", batch.batch().header_signature());
+        #[cfg(feature = "tracing")]
+        let _guard = span.enter();
+
+        let result = self.execute_batch(batch);
+
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            span.set_error(&err.to_string());
+        }
+
+        result
+    }
+
+    /// Executes the batch and, if it is valid, sets its results as the pending change. Returns
+    /// the state root that results from the change.
+    fn execute_batch(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
+        let prepared_at = Instant::now();
         // Setup the transact scheduler
         let (result_tx, result_rx) = std::sync::mpsc::channel();
         let mut scheduler = SerialScheduler::new(
@@ -244,20 +368,27 @@ impl ScabbardState {
         // Add the batch to, finalize, and execute the scheduler
         scheduler.add_batch(batch.clone())?;
         scheduler.finalize()?;
-        executor.execute(scheduler.take_task_iterator()?, scheduler.new_notifier()?)?;
+        self.execute_scheduled_tasks(&mut scheduler)?;
 
         let mut recv_result: Option<BatchExecutionResult> = None;
+        let deadline = prepared_at + self.execution_timeout;
 
         // Get the results and shutdown the scheduler
         // after receiving the batch result wait until the receiver gets a `None` response
         // from the scheduler before shutting down
         loop {
-            match result_rx.recv_timeout(Duration::from_secs(EXECUTION_TIMEOUT)) {
+            match result_rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
                 Ok(Some(res)) => recv_result = Some(res),
                 Ok(None) => break,
-                Err(_) => {
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Err(err) = scheduler.cancel() {
+                        warn!("Failed to cancel timed out batch execution: {}", err);
+                    }
+                    return Err(self.handle_execution_timeout(&batch));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
                     return Err(ScabbardStateError(
-                        "Failed to receive result in reasonable time".into(),
+                        "Failed to receive result; scheduler disconnected".into(),
                     ))
                 }
             }
@@ -287,13 +418,92 @@ impl ScabbardState {
             &self.current_state_root,
             &receipts_into_transact_state_changes(&txn_receipts)?,
         )?;
-        self.pending_changes = Some((signature.to_string(), txn_receipts));
+        self.pending_changes = Some((signature.to_string(), txn_receipts, prepared_at));
         Ok(state_root)
     }
 
+    /// Passes the scheduler's tasks to the dedicated executor or, if one is configured, to the
+    /// shared executor pool.
+    fn execute_scheduled_tasks(
+        &self,
+        scheduler: &mut SerialScheduler,
+    ) -> Result<(), ScabbardStateError> {
+        #[cfg(feature = "shared-executor-pool")]
+        if let Some((executor_pool, service)) = &self.executor_pool {
+            let handlers = self.pooled_handlers.clone().ok_or_else(|| {
+                ScabbardStateError("attempting to prepare a change on a stopped service".into())
+            })?;
+            let context_manager = self.context_manager.clone();
+            let tasks = scheduler.take_task_iterator()?;
+            let notifier = scheduler.new_notifier()?;
+
+            return executor_pool
+                .submit(
+                    service,
+                    Box::new(move || match handlers.lock() {
+                        Ok(handlers) => execute_tasks(&handlers, &context_manager, tasks, notifier),
+                        Err(_) => error!("Unable to execute batch; handlers lock poisoned"),
+                    }),
+                )
+                .map_err(|err| ScabbardStateError(err.to_string()));
+        }
+
+        let executor = self.executor.as_ref().ok_or_else(|| {
+            ScabbardStateError("attempting to prepare a change on a stopped service".into())
+        })?;
+        executor.execute(scheduler.take_task_iterator()?, scheduler.new_notifier()?)?;
+        Ok(())
+    }
+
+    /// Marks a batch whose execution exceeded the execution timeout as invalid, then replaces the
+    /// executor, which may still be blocked on the batch, so that later batches can be executed.
+    fn handle_execution_timeout(&mut self, batch: &BatchPair) -> ScabbardStateError {
+        let signature = batch.batch().header_signature();
+        let reason = format!(
+            "batch execution exceeded the timeout of {} ms",
+            self.execution_timeout.as_millis()
+        );
+        warn!("Batch {} is invalid: {}", signature, reason);
+
+        let invalid_txns = batch
+            .batch()
+            .transactions()
+            .iter()
+            .map(|txn| {
+                InvalidTransaction::new(txn.header_signature().to_string(), reason.clone(), vec![])
+            })
+            .collect();
+        self.batch_history
+            .update_batch_status(signature, BatchStatus::Invalid(invalid_txns));
+
+        if let Some(executor) = self.executor.take() {
+            // Stopping the executor waits on the blocked execution, so it is done in the
+            // background
+            std::thread::spawn(move || executor.stop());
+        }
+        // A pool worker may likewise still be blocked on the batch, so another worker is started
+        // in its place
+        #[cfg(feature = "shared-executor-pool")]
+        if let Some((executor_pool, _)) = &self.executor_pool {
+            if let Err(err) = executor_pool.replace_blocked_worker() {
+                warn!("Failed to replace blocked executor pool worker: {}", err);
+            }
+        }
+        if let Err(err) = self.start_executor() {
+            return ScabbardStateError(format!(
+                "{}; failed to restart executor: {}",
+                reason, err.0
+            ));
+        }
+
+        ScabbardStateError(format!("transaction failed: {}", reason))
+    }
+
     pub fn commit(&mut self) -> Result<(), ScabbardStateError> {
         match self.pending_changes.take() {
-            Some((signature, txn_receipts)) => {
+            // The preparation time is underscored since it is only used by the metrics macro,
+            // which is a no-op when the metrics feature is disabled
+            Some((signature, txn_receipts, _prepared_at)) => {
                 let state_changes = receipts_into_transact_state_changes(&txn_receipts)?;
 
                 let previous_state_root = self.current_state_root.clone();
@@ -342,6 +552,10 @@ impl ScabbardState {
                     "circuit" => self.circuit_id.clone(),
                     "service" => format!("{}::{}", &self.circuit_id, &self.service_id)
                 );
+                histogram!("splinter.scabbard.commit_latency", _prepared_at.elapsed().as_secs_f64(),
+                    "circuit" => self.circuit_id.clone(),
+                    "service" => format!("{}::{}", &self.circuit_id, &self.service_id)
+                );
 
                 if previous_state_root != self.current_state_root {
                     self.merkle_state
@@ -371,7 +585,7 @@ impl ScabbardState {
 
     pub fn rollback(&mut self) -> Result<(), ScabbardStateError> {
         match self.pending_changes.take() {
-            Some((_, txn_receipts)) => info!(
+            Some((_, txn_receipts, _)) => info!(
                 "discarded {} change(s)",
                 receipts_into_transact_state_changes(&txn_receipts)?.len()
             ),
@@ -389,6 +603,71 @@ impl ScabbardState {
         Events::new(self.receipt_store.clone(), event_id)
     }
 
+    /// Returns the receipts of committed transactions that match the given query, in the order
+    /// the transactions were committed.
+    ///
+    /// Batches are only tracked for a limited time after they are submitted, so a query by batch
+    /// ID returns no receipts if the batch is no longer in the batch history.
+    #[cfg(feature = "transaction-receipts")]
+    pub fn list_receipts(
+        &self,
+        query: &ReceiptQuery,
+    ) -> Result<Vec<CommittedReceipt>, ScabbardStateError> {
+        let batch_transaction_ids = match &query.batch_id {
+            Some(batch_id) => match self.batch_history.history.get(batch_id) {
+                Some(BatchInfo {
+                    status: BatchStatus::Committed(txns),
+                    ..
+                }) => Some(
+                    txns.iter()
+                        .map(|txn| txn.transaction_id.clone())
+                        .collect::<HashSet<_>>(),
+                ),
+                _ => return Ok(vec![]),
+            },
+            None => None,
+        };
+
+        let receipts = self
+            .receipt_store
+            .list_receipts_since(None)
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to get transaction receipts from store: {}",
+                    err
+                ))
+            })?;
+
+        let mut committed_receipts = vec![];
+        for (index, receipt) in receipts.enumerate() {
+            let index = index as u64;
+            if query.end_index.map(|end| index >= end).unwrap_or(false) {
+                break;
+            }
+            if query
+                .start_index
+                .map(|start| index < start)
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let receipt = receipt.map_err(|err| {
+                ScabbardStateError(format!("failed to get transaction receipt: {}", err))
+            })?;
+            let included = |ids: &Option<HashSet<String>>| {
+                ids.as_ref()
+                    .map(|ids| ids.contains(&receipt.transaction_id))
+                    .unwrap_or(true)
+            };
+            if included(&query.transaction_ids) && included(&batch_transaction_ids) {
+                committed_receipts.push(CommittedReceipt { index, receipt });
+            }
+        }
+
+        Ok(committed_receipts)
+    }
+
     pub fn add_subscriber(&mut self, subscriber: Box<dyn StateSubscriber>) {
         self.event_subscribers.push(subscriber);
     }
@@ -398,6 +677,30 @@ impl ScabbardState {
     }
 }
 
+/// Selects the transaction receipts returned by `ScabbardState::list_receipts`. Receipts must
+/// match every criteria that is set.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Clone, Debug, Default)]
+pub struct ReceiptQuery {
+    /// Only include the receipts of these transactions
+    pub transaction_ids: Option<HashSet<String>>,
+    /// Only include the receipts of the transactions in this batch
+    pub batch_id: Option<String>,
+    /// Only include receipts at or after this position in the commit order
+    pub start_index: Option<u64>,
+    /// Only include receipts before this position in the commit order
+    pub end_index: Option<u64>,
+}
+
+/// A transaction receipt and its position in the order transactions were committed, starting
+/// from 0.
+#[cfg(feature = "transaction-receipts")]
+#[derive(Clone, Debug)]
+pub struct CommittedReceipt {
+    pub index: u64,
+    pub receipt: TransactionReceipt,
+}
+
 fn receipts_into_transact_state_changes(
     receipts: &[TransactionReceipt],
 ) -> Result<Vec<TransactStateChange>, ScabbardStateError> {
@@ -957,6 +1260,9 @@ mod tests {
         state::merkle::INDEXES,
     };
 
+    #[cfg(feature = "execution-timeout")]
+    use transact::protocol::command::{Sleep, SleepType};
+
     use crate::store::transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX};
 
     use super::merkle_state::{MerkleState, MerkleStateConfig};
@@ -1194,9 +1500,9 @@ mod tests {
             true,
             Arc::new(commit_hash_store),
             receipt_store,
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             "svc0".to_string(),
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             "vzrQS-rvwf4".to_string(),
             vec![],
         )
@@ -1244,6 +1550,85 @@ mod tests {
         state.stop_executor();
     }
 
+    /// Verify that services sharing an executor pool execute their batches on it.
+    ///
+    /// 1. Initialize two new, empty `ScabbardState`s that share a pool with a single worker.
+    /// 2. Set a different value in the state of each service.
+    /// 3. Verify that each service only has the value that was set in its own state.
+    #[cfg(feature = "shared-executor-pool")]
+    #[test]
+    fn shared_executor_pool() {
+        let executor_pool = ExecutorPool::new(1).expect("Failed to start executor pool");
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+
+        let mut states = ["svc0", "svc1"]
+            .iter()
+            .map(|service_id| {
+                let receipt_store = Arc::new(DieselReceiptStore::new(
+                    create_connection_pool_and_migrate(":memory:".to_string()),
+                    None,
+                ));
+                let db = create_btree_db();
+                let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+                    .expect("Unable to create merkle state");
+                let commit_hash_store = TransactCommitHashStore::new(db);
+
+                let mut state = ScabbardState::new(
+                    merkle_state,
+                    true,
+                    Arc::new(commit_hash_store),
+                    receipt_store,
+                    #[cfg(any(feature = "metrics", feature = "tracing"))]
+                    service_id.to_string(),
+                    #[cfg(any(feature = "metrics", feature = "tracing"))]
+                    "vzrQS-rvwf4".to_string(),
+                    vec![],
+                )
+                .expect("Failed to initialize state");
+                state.set_executor_pool(
+                    executor_pool.clone(),
+                    format!("vzrQS-rvwf4::{}", service_id),
+                );
+                state.start_executor().expect("Failed to start executor");
+                state
+            })
+            .collect::<Vec<_>>();
+
+        for (i, state) in states.iter_mut().enumerate() {
+            let batch = CommandTransactionBuilder::new()
+                .with_commands(vec![Command::SetState(SetState::new(vec![
+                    BytesEntry::new(format!("abcdef0{}", i), b"value".to_vec()),
+                ]))])
+                .into_transaction_builder()
+                .expect("failed to convert to transaction builder")
+                .into_batch_builder(&*signer)
+                .expect("failed to build transaction")
+                .build_pair(&*signer)
+                .expect("Failed to build batch");
+            state
+                .prepare_change(batch)
+                .expect("Failed to prepare change");
+            state.commit().expect("Failed to commit change");
+        }
+
+        for (i, state) in states.iter_mut().enumerate() {
+            assert_eq!(
+                state
+                    .get_state_at_address(&format!("abcdef0{}", i))
+                    .expect("Failed to get state for set address"),
+                Some(b"value".to_vec()),
+            );
+            assert_eq!(
+                state
+                    .get_state_at_address(&format!("abcdef0{}", 1 - i))
+                    .expect("Failed to get state for unset address"),
+                None,
+            );
+            state.stop_executor();
+        }
+    }
+
     /// Verify that the `ScabbardState::get_state_with_prefix` method works properly.
     ///
     /// 1. Initialize a new, empty `ScabbardState`.
@@ -1272,9 +1657,9 @@ mod tests {
             true,
             Arc::new(commit_hash_store),
             receipt_store,
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             "svc0".to_string(),
-            #[cfg(feature = "metrics")]
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
             "vzrQS-rvwf4".to_string(),
             vec![],
         )
@@ -1343,6 +1728,174 @@ mod tests {
         state.stop_executor();
     }
 
+    /// Verify that the `ScabbardState::get_state_with_prefix_at_root` method works properly.
+    ///
+    /// 1. Initialize a new, empty `ScabbardState` backed by a key-value database, which removes
+    ///    the entries of previous state roots as soon as they are pruned.
+    /// 2. Set a value in state and record the resulting state root.
+    /// 3. Call `get_state_with_prefix_at_root` with the state root and verify the entry is
+    ///    returned.
+    /// 4. Update the value in state, then verify that the previous state root is reported as
+    ///    unavailable while the new one returns the updated entry.
+    /// 5. Verify that a state root that never existed is reported as unavailable.
+    #[cfg(feature = "historical-state")]
+    #[test]
+    fn get_state_with_prefix_at_root() {
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(":memory:".to_string()),
+            None,
+        ));
+
+        let db = create_btree_db();
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let commit_hash_store = TransactCommitHashStore::new(db);
+
+        let mut state = ScabbardState::new(
+            merkle_state,
+            true,
+            Arc::new(commit_hash_store),
+            receipt_store,
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            "svc0".to_string(),
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            "vzrQS-rvwf4".to_string(),
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        state.start_executor().expect("Failed to start executor");
+
+        let address = "abcdef01".to_string();
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let mut set_value = |value: &[u8]| {
+            let batch = CommandTransactionBuilder::new()
+                .with_commands(vec![Command::SetState(SetState::new(vec![
+                    BytesEntry::new(address.clone(), value.to_vec()),
+                ]))])
+                .into_transaction_builder()
+                .expect("failed to convert to transaction builder")
+                .into_batch_builder(&*signer)
+                .expect("failed to build transaction")
+                .build_pair(&*signer)
+                .expect("Failed to build batch");
+            state
+                .prepare_change(batch)
+                .expect("Failed to prepare change");
+            state.commit().expect("Failed to commit change");
+            state.current_state_root().to_string()
+        };
+
+        let first_root = set_value(b"value1");
+        let second_root = set_value(b"value2");
+
+        let entries = state
+            .get_state_with_prefix_at_root(&second_root, Some(&address))
+            .expect("Failed to get entries")
+            .expect("State root should be available")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to collect entries");
+        assert_eq!(entries, vec![(address.clone(), b"value2".to_vec())]);
+
+        assert!(state
+            .get_state_with_prefix_at_root(&first_root, Some(&address))
+            .expect("Failed to get entries")
+            .is_none());
+
+        assert!(state
+            .get_state_with_prefix_at_root(&"0".repeat(64), None)
+            .expect("Failed to get entries")
+            .is_none());
+
+        state.stop_executor();
+    }
+
+    /// Verify that `list_receipts` filters receipts by transaction ID, batch ID and position in
+    /// the commit order.
+    #[cfg(feature = "transaction-receipts")]
+    #[test]
+    fn list_receipts() {
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(":memory:".to_string()),
+            None,
+        ));
+
+        let db = create_btree_db();
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let commit_hash_store = TransactCommitHashStore::new(db);
+
+        let mut state = ScabbardState::new(
+            merkle_state,
+            true,
+            Arc::new(commit_hash_store),
+            receipt_store.clone(),
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            "svc0".to_string(),
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            "vzrQS-rvwf4".to_string(),
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        receipt_store
+            .add_txn_receipts(vec![
+                mock_transaction_receipt("txn-0"),
+                mock_transaction_receipt("txn-1"),
+                mock_transaction_receipt("txn-2"),
+                mock_transaction_receipt("txn-3"),
+            ])
+            .expect("Failed to add receipts");
+        state.batch_history().update_batch_status(
+            "batch-1",
+            BatchStatus::Committed(vec![
+                ValidTransaction::new("txn-1".into()),
+                ValidTransaction::new("txn-2".into()),
+            ]),
+        );
+        state.batch_history().add_batch("batch-2");
+
+        let ids = |query: ReceiptQuery| -> Vec<(u64, String)> {
+            state
+                .list_receipts(&query)
+                .expect("Failed to list receipts")
+                .into_iter()
+                .map(|committed| (committed.index, committed.receipt.transaction_id))
+                .collect()
+        };
+
+        assert_eq!(ids(ReceiptQuery::default()).len(), 4);
+        assert_eq!(
+            ids(ReceiptQuery {
+                transaction_ids: Some(vec!["txn-3".to_string()].into_iter().collect()),
+                ..Default::default()
+            }),
+            vec![(3, "txn-3".to_string())]
+        );
+        assert_eq!(
+            ids(ReceiptQuery {
+                batch_id: Some("batch-1".into()),
+                ..Default::default()
+            }),
+            vec![(1, "txn-1".to_string()), (2, "txn-2".to_string())]
+        );
+        // A batch that has not been committed has no receipts
+        assert!(ids(ReceiptQuery {
+            batch_id: Some("batch-2".into()),
+            ..Default::default()
+        })
+        .is_empty());
+        assert_eq!(
+            ids(ReceiptQuery {
+                start_index: Some(2),
+                end_index: Some(3),
+                ..Default::default()
+            }),
+            vec![(2, "txn-2".to_string())]
+        );
+    }
+
     fn mock_transaction_receipt(id: &str) -> TransactionReceipt {
         TransactionReceipt {
             transaction_id: id.into(),
@@ -1354,6 +1907,99 @@ mod tests {
         }
     }
 
+    /// Verify that a batch which exceeds the execution timeout is marked invalid and that
+    /// subsequent batches can still be executed.
+    ///
+    /// 1. Initialize a new `ScabbardState` with an execution timeout of 100ms.
+    /// 2. Prepare a batch whose transaction sleeps for 2 seconds and verify that it fails and the
+    ///    batch is marked as invalid with a timeout reason.
+    /// 3. Prepare and commit a batch that sets a value and verify the value is set.
+    #[cfg(feature = "execution-timeout")]
+    #[test]
+    fn execution_timeout() {
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(":memory:".to_string()),
+            None,
+        ));
+
+        let db = create_btree_db();
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let commit_hash_store = TransactCommitHashStore::new(db);
+
+        let mut state = ScabbardState::new(
+            merkle_state,
+            true,
+            Arc::new(commit_hash_store),
+            receipt_store,
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            "svc0".to_string(),
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            "vzrQS-rvwf4".to_string(),
+            vec![],
+        )
+        .expect("Failed to initialize state");
+        state.set_execution_timeout(Duration::from_millis(100));
+
+        state.start_executor().expect("Failed to start executor");
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+
+        let slow_batch = CommandTransactionBuilder::new()
+            .with_commands(vec![Command::Sleep(Sleep::new(2000, SleepType::Wait))])
+            .into_transaction_builder()
+            .expect("failed to convert to transaction builder")
+            .into_batch_builder(&*signer)
+            .expect("failed to build transaction")
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+        let slow_batch_id = slow_batch.batch().header_signature().to_string();
+
+        assert!(state.prepare_change(slow_batch).is_err());
+
+        let batch_info = state
+            .batch_history()
+            .get_batch_info(vec![slow_batch_id].into_iter().collect(), None)
+            .expect("Failed to get batch info")
+            .next()
+            .expect("Batch info not returned")
+            .expect("Failed to get batch info");
+        match batch_info.status {
+            BatchStatus::Invalid(txns) => {
+                assert_eq!(txns.len(), 1);
+                assert!(txns[0].error_message.contains("timeout"));
+            }
+            status => panic!("Expected batch to be invalid, got {:?}", status),
+        }
+
+        let address = "abcdef".to_string();
+        let value = b"value".to_vec();
+        let batch = CommandTransactionBuilder::new()
+            .with_commands(vec![Command::SetState(SetState::new(vec![
+                BytesEntry::new(address.clone(), value.clone()),
+            ]))])
+            .into_transaction_builder()
+            .expect("failed to convert to transaction builder")
+            .into_batch_builder(&*signer)
+            .expect("failed to build transaction")
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+        state
+            .prepare_change(batch)
+            .expect("Failed to prepare change after timeout");
+        state.commit().expect("Failed to commit change");
+
+        assert_eq!(
+            state
+                .get_state_at_address(&address)
+                .expect("Failed to get state for set address"),
+            Some(value),
+        );
+
+        state.stop_executor();
+    }
+
     fn create_connection_pool_and_migrate(
         connection_string: String,
     ) -> Pool<ConnectionManager<SqliteConnection>> {
@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The Transact transaction families that may be run by a scabbard service.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "transaction-family-command")]
+use transact::families::command::CommandTransactionHandler;
+#[cfg(feature = "transaction-family-xo")]
+use transact::families::xo::XoTransactionHandler;
+use transact::{
+    families::sabre::{admin::SettingsAdminPermission, handler::SabreTransactionHandler},
+    handler::TransactionHandler,
+};
+
+/// A transaction family whose handler is compiled into scabbard.
+///
+/// Sabre is always run by scabbard; the other families are only available when their feature is
+/// enabled, and must be requested using the `transaction_families` service argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionFamily {
+    Sabre,
+    #[cfg(feature = "transaction-family-command")]
+    Command,
+    #[cfg(feature = "transaction-family-xo")]
+    Xo,
+}
+
+impl TransactionFamily {
+    /// Create the transaction handler for this family.
+    pub(crate) fn handler(&self) -> Box<dyn TransactionHandler> {
+        match self {
+            TransactionFamily::Sabre => Box::new(SabreTransactionHandler::new(Box::new(
+                SettingsAdminPermission,
+            ))),
+            #[cfg(feature = "transaction-family-command")]
+            TransactionFamily::Command => Box::new(CommandTransactionHandler::new()),
+            #[cfg(feature = "transaction-family-xo")]
+            TransactionFamily::Xo => Box::new(XoTransactionHandler::new()),
+        }
+    }
+}
+
+impl FromStr for TransactionFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sabre" => Ok(TransactionFamily::Sabre),
+            #[cfg(feature = "transaction-family-command")]
+            "command" => Ok(TransactionFamily::Command),
+            #[cfg(feature = "transaction-family-xo")]
+            "xo" => Ok(TransactionFamily::Xo),
+            _ => Err(format!("transaction family {:?} is not supported", s)),
+        }
+    }
+}
+
+impl fmt::Display for TransactionFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionFamily::Sabre => f.write_str("sabre"),
+            #[cfg(feature = "transaction-family-command")]
+            TransactionFamily::Command => f.write_str("command"),
+            #[cfg(feature = "transaction-family-xo")]
+            TransactionFamily::Xo => f.write_str("xo"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that transaction family names are parsed, and that unknown families are rejected.
+    #[test]
+    fn test_parse_transaction_family() {
+        assert_eq!(
+            "sabre".parse::<TransactionFamily>(),
+            Ok(TransactionFamily::Sabre)
+        );
+        #[cfg(feature = "transaction-family-command")]
+        assert_eq!(
+            "command".parse::<TransactionFamily>(),
+            Ok(TransactionFamily::Command)
+        );
+        #[cfg(feature = "transaction-family-xo")]
+        assert_eq!("xo".parse::<TransactionFamily>(), Ok(TransactionFamily::Xo));
+        assert!("unknown".parse::<TransactionFamily>().is_err());
+    }
+}
@@ -25,6 +25,8 @@ use crate::store::pool::ConnectionPool;
 use super::{CommitHashStore, CommitHashStoreError};
 
 use operations::get_current_commit_hash::CommitHashStoreGetCurrentCommitHashOperation as _;
+#[cfg(feature = "purge-service-stores")]
+use operations::remove_current_commit_hash::CommitHashStoreRemoveCurrentCommitHashOperation as _;
 use operations::set_current_commit_hash::CommitHashStoreSetCurrentCommitHashOperation as _;
 use operations::CommitHashStoreOperations;
 
@@ -93,6 +95,14 @@ impl CommitHashStore for DieselCommitHashStore<diesel::pg::PgConnection> {
             )
         })
     }
+
+    #[cfg(feature = "purge-service-stores")]
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        self.pool.execute_write(|conn| {
+            CommitHashStoreOperations::new(conn)
+                .remove_current_commit_hash(&*self.circuit_id, &*self.service_id)
+        })
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -113,6 +123,14 @@ impl CommitHashStore for DieselCommitHashStore<diesel::sqlite::SqliteConnection>
             )
         })
     }
+
+    #[cfg(feature = "purge-service-stores")]
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        self.pool.execute_write(|conn| {
+            CommitHashStoreOperations::new(conn)
+                .remove_current_commit_hash(&*self.circuit_id, &*self.service_id)
+        })
+    }
 }
 
 /// Database backed [CommitHashStore] implementation.
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub(super) mod get_current_commit_hash;
+#[cfg(feature = "purge-service-stores")]
+pub(super) mod remove_current_commit_hash;
 pub(super) mod set_current_commit_hash;
 
 pub struct CommitHashStoreOperations<'a, C> {
@@ -0,0 +1,69 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::prelude::*;
+use splinter::error::InternalError;
+
+use crate::store::{diesel::schema::scabbard_commit_hash, CommitHashStoreError};
+
+use super::CommitHashStoreOperations;
+
+pub(in crate::store::commit_hash::diesel) trait CommitHashStoreRemoveCurrentCommitHashOperation {
+    fn remove_current_commit_hash(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<(), CommitHashStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CommitHashStoreRemoveCurrentCommitHashOperation
+    for CommitHashStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_current_commit_hash(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<(), CommitHashStoreError> {
+        diesel::delete(
+            scabbard_commit_hash::table
+                .filter(scabbard_commit_hash::circuit_id.eq(circuit_id))
+                .filter(scabbard_commit_hash::service_id.eq(service_id)),
+        )
+        .execute(self.conn)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CommitHashStoreRemoveCurrentCommitHashOperation
+    for CommitHashStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_current_commit_hash(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<(), CommitHashStoreError> {
+        diesel::delete(
+            scabbard_commit_hash::table
+                .filter(scabbard_commit_hash::circuit_id.eq(circuit_id))
+                .filter(scabbard_commit_hash::service_id.eq(service_id)),
+        )
+        .execute(self.conn)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
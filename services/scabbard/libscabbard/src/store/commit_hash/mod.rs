@@ -38,4 +38,13 @@ pub trait CommitHashStore {
     ///
     /// * `current_commit_hash` - the new "current" commit hash.
     fn set_current_commit_hash(&self, commit_hash: &str) -> Result<(), CommitHashStoreError>;
+
+    /// Removes the current commit hash for the instance.
+    ///
+    /// The default implementation does nothing, for stores that keep the commit hash alongside
+    /// the state itself.
+    #[cfg(feature = "purge-service-stores")]
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        Ok(())
+    }
 }
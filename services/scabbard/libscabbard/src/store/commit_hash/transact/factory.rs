@@ -130,6 +130,23 @@ pub struct LmdbDatabasePurgeHandle {
 }
 
 impl LmdbDatabasePurgeHandle {
+    /// Returns the size, in bytes, of the LMDB files that will be removed by `purge`.
+    #[cfg(feature = "purge-service-stores")]
+    pub fn size(&self) -> Result<u64, InternalError> {
+        [
+            self.lmdb_path.with_extension("lmdb"),
+            self.lmdb_path.with_extension("lmdb-lock"),
+        ]
+        .iter()
+        .filter(|path| path.exists())
+        .map(|path| {
+            std::fs::metadata(path)
+                .map(|metadata| metadata.len())
+                .map_err(|err| InternalError::from_source(Box::new(err)))
+        })
+        .sum()
+    }
+
     pub fn purge(&self) -> Result<(), InternalError> {
         let db_path = self.lmdb_path.with_extension("lmdb");
         let db_lock_file_path = self.lmdb_path.with_extension("lmdb-lock");
@@ -21,12 +21,17 @@ use std::process::Command;
 
 const FORCE_PANDOC: &str = "SPLINTER_FORCE_PANDOC";
 const PATH: &str = "PATH";
+const GIT_HASH: &str = "SPLINTER_GIT_HASH";
+const FEATURES: &str = "SPLINTER_FEATURES";
+const CARGO_FEATURE_PREFIX: &str = "CARGO_FEATURE_";
 
 /// This build script will take the markdown files in the /man directory and convert them to
 /// man pages stored in packaging/man. This build script will check if pandoc is installed locally
 /// and skip generating the manpages if it is not. If the build should fail if man pages cannot be
 /// generated set environment variable SPLINTER_FORCE_PANDOC=true
 fn main() -> Result<(), BuildError> {
+    emit_build_info();
+
     let paths = env::var(PATH)
         .map_err(|_| BuildError("Unable to read PATH environment variable".into()))?;
     let mut pandoc_exist = false;
@@ -139,6 +144,32 @@ fn main() -> Result<(), BuildError> {
     Ok(())
 }
 
+/// Passes the commit being built and the enabled cargo features to the compiler as the
+/// SPLINTER_GIT_HASH and SPLINTER_FEATURES environment variables. The commit is left unset if it
+/// cannot be determined, such as when building from a source archive.
+fn emit_build_info() {
+    if let Ok(output) = Command::new("git")
+        .args(&["rev-parse", "--short", "HEAD"])
+        .output()
+    {
+        if output.status.success() {
+            let hash = String::from_utf8_lossy(&output.stdout);
+            println!("cargo:rustc-env={}={}", GIT_HASH, hash.trim());
+        }
+    }
+
+    // Cargo sets CARGO_FEATURE_<name> for each enabled feature, with the name upper-cased and
+    // dashes replaced by underscores
+    let mut features = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix(CARGO_FEATURE_PREFIX)
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+    println!("cargo:rustc-env={}={}", FEATURES, features.join(","));
+}
+
 pub struct BuildError(String);
 
 impl Error for BuildError {}
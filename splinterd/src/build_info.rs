@@ -0,0 +1,118 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Information about how splinterd was built, reported by `splinterd --version --verbose` and
+//! the `/status` endpoint.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+
+use splinter_rest_api_actix_web_1::status::BuildInfo;
+
+/// Returns the short hash of the commit splinterd was built from, if it was known at build time.
+pub fn git_hash() -> Option<&'static str> {
+    option_env!("SPLINTER_GIT_HASH")
+}
+
+/// Returns the cargo features splinterd was compiled with, sorted by name.
+pub fn features() -> Vec<&'static str> {
+    option_env!("SPLINTER_FEATURES")
+        .unwrap_or("")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect()
+}
+
+/// Returns the current version of each protocol supported by splinterd, by protocol name.
+pub fn protocol_versions() -> BTreeMap<String, u32> {
+    let mut versions = splinter::protocol::protocol_versions()
+        .into_iter()
+        .map(|(name, version)| (name.to_string(), version))
+        .collect::<BTreeMap<_, _>>();
+    versions.insert(
+        "scabbard".to_string(),
+        scabbard::protocol::SCABBARD_PROTOCOL_VERSION,
+    );
+    versions
+}
+
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        git_hash: git_hash().map(String::from),
+        features: features().into_iter().map(String::from).collect(),
+        protocol_versions: protocol_versions(),
+    }
+}
+
+/// Returns true if the arguments request both the version and verbose output, in any order.
+///
+/// This is checked before the arguments are parsed, as clap exits as soon as it sees the version
+/// flag.
+pub fn is_verbose_version_request(args: &[OsString]) -> bool {
+    let is_version = |arg: &OsString| arg == "--version" || arg == "-V";
+    let is_verbose = |arg: &OsString| {
+        arg == "--verbose"
+            || arg
+                .to_str()
+                .and_then(|arg| arg.strip_prefix('-'))
+                .map(|flags| !flags.is_empty() && flags.chars().all(|flag| flag == 'v'))
+                .unwrap_or(false)
+    };
+
+    args.iter().skip(1).any(is_version) && args.iter().skip(1).any(is_verbose)
+}
+
+/// Formats the version of splinterd along with how it was built.
+pub fn verbose_version() -> String {
+    let mut version = format!(
+        "splinterd {}\ngit hash: {}\nfeatures: {}\nprotocol versions:",
+        crate_version!(),
+        git_hash().unwrap_or("unknown"),
+        features().join(", "),
+    );
+    for (name, protocol_version) in protocol_versions() {
+        version.push_str(&format!("\n  {}: {}", name, protocol_version));
+    }
+    version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that a verbose version request is recognized with either flag order and with
+    /// short or long flags, and that other requests are not.
+    #[test]
+    fn verbose_version_request() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+
+        assert!(is_verbose_version_request(&args(&[
+            "splinterd",
+            "--version",
+            "--verbose"
+        ])));
+        assert!(is_verbose_version_request(&args(&[
+            "splinterd",
+            "-vv",
+            "-V"
+        ])));
+        assert!(!is_verbose_version_request(&args(&[
+            "splinterd",
+            "--version"
+        ])));
+        assert!(!is_verbose_version_request(&args(&["splinterd", "-v"])));
+        // The program name is not an argument
+        assert!(!is_verbose_version_request(&args(&["-v", "--version"])));
+    }
+}
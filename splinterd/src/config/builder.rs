@@ -182,6 +182,18 @@ impl ConfigBuilder {
             .find_map(|p| p.network_endpoints().map(|v| (v, p.source())))
             .ok_or_else(|| ConfigError::MissingValue("network endpoints".to_string()))?;
 
+        #[cfg(feature = "outbound-only")]
+        let outbound_only = self
+            .partial_configs
+            .iter()
+            .find_map(|p| p.outbound_only().map(|v| (v, p.source())));
+        // An outbound-only node does not listen on the default network endpoint
+        #[cfg(feature = "outbound-only")]
+        let network_endpoints = match (&outbound_only, network_endpoints) {
+            (Some((true, _)), (_, ConfigSource::Default)) => (vec![], ConfigSource::Default),
+            (_, network_endpoints) => network_endpoints,
+        };
+
         let state_dir = self
             .partial_configs
             .iter()
@@ -242,6 +254,16 @@ impl ConfigBuilder {
                 .iter()
                 .find_map(|p| p.rest_api_endpoint().map(|v| (v, p.source())))
                 .ok_or_else(|| ConfigError::MissingValue("rest api endpoint".to_string()))?,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_additional_endpoints: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_additional_endpoints().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_unauthenticated_endpoints: self.partial_configs.iter().find_map(|p| {
+                p.rest_api_unauthenticated_endpoints()
+                    .map(|v| (v, p.source()))
+            }),
             database,
             registries: self
                 .partial_configs
@@ -262,6 +284,11 @@ impl ConfigBuilder {
                 .ok_or_else(|| {
                     ConfigError::MissingValue("registry forced refresh interval".to_string())
                 })?,
+            #[cfg(feature = "registry-node-signing")]
+            registry_require_signed_nodes: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.registry_require_signed_nodes().map(|v| (v, p.source()))),
             heartbeat: self
                 .partial_configs
                 .iter()
@@ -356,6 +383,21 @@ impl ConfigBuilder {
                 .partial_configs
                 .iter()
                 .find_map(|p| p.influx_password().map(|v| (v, p.source()))),
+            #[cfg(feature = "tap-backends")]
+            metrics_backend: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.metrics_backend().map(|v| (v, p.source()))),
+            #[cfg(feature = "tap-backends")]
+            statsd_address: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.statsd_address().map(|v| (v, p.source()))),
+            #[cfg(feature = "tap-backends")]
+            otlp_url: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.otlp_url().map(|v| (v, p.source()))),
             peering_key: self
                 .partial_configs
                 .iter()
@@ -436,6 +478,183 @@ impl ConfigBuilder {
                 .iter()
                 .find_map(|p| p.scabbard_autocleanup().map(|v| (v, p.source())))
                 .ok_or_else(|| ConfigError::MissingValue("scabbard_autocleanup".to_string()))?,
+            #[cfg(feature = "rbac-bootstrap")]
+            rbac_bootstrap_file: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rbac_bootstrap_file().map(|v| (v, p.source()))),
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            bandwidth_limit_global: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.bandwidth_limit_global().map(|v| (v, p.source()))),
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            bandwidth_limit_per_peer: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.bandwidth_limit_per_peer().map(|v| (v, p.source()))),
+            #[cfg(feature = "auth-handshake-limits")]
+            auth_handshake_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.auth_handshake_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "auth-handshake-limits")]
+            max_unauthorized_connections: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.max_unauthorized_connections().map(|v| (v, p.source()))),
+            #[cfg(feature = "auth-handshake-limits")]
+            connection_rate_limit: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.connection_rate_limit().map(|v| (v, p.source()))),
+            #[cfg(feature = "state-dir-health")]
+            state_dir_degraded_threshold: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.state_dir_degraded_threshold().map(|v| (v, p.source()))),
+            #[cfg(feature = "challenge-endpoint-binding")]
+            challenge_endpoint_binding: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.challenge_endpoint_binding().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-timeout")]
+            admin_max_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_max_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_verification_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_retries: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_verification_retries().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_backoff: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_verification_backoff().map(|v| (v, p.source()))),
+            #[cfg(feature = "health-service")]
+            health_check_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.health_check_interval().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-max-message-size")]
+            max_message_size: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.max_message_size().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-tombstones")]
+            circuit_tombstones: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_tombstones().map(|v| (v, p.source()))),
+            #[cfg(feature = "tracing")]
+            tracing_otlp_url: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tracing_otlp_url().map(|v| (v, p.source()))),
+            #[cfg(feature = "registry-parallel-fetch")]
+            registry_fetch_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.registry_fetch_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_proposal_rate_limit: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_proposal_rate_limit().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_pending_proposals: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_max_pending_proposals().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-shared-executor-pool")]
+            scabbard_executor_pool_size: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_executor_pool_size().map(|v| (v, p.source()))),
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_keepalive: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tcp_keepalive().map(|v| (v, p.source()))),
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_user_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tcp_user_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_nodelay: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tcp_nodelay().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-read-only")]
+            rest_api_read_only: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_read_only().map(|v| (v, p.source()))),
+            #[cfg(feature = "tls-policy")]
+            tls_min_version: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tls_min_version().map(|v| (v, p.source()))),
+            #[cfg(feature = "tls-policy")]
+            tls_cipher_suites: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tls_cipher_suites().map(|v| (v, p.source()))),
+            #[cfg(feature = "store-schema-check")]
+            auto_migrate: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.auto_migrate().map(|v| (v, p.source()))),
+            #[cfg(feature = "store-lease")]
+            steal_lease: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.steal_lease().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_access_log().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log_retention: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_access_log_retention().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention_age: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_event_retention_age().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention_count: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_event_retention_count().map(|v| (v, p.source()))),
+            #[cfg(feature = "transport-ipv6")]
+            preferred_address_family: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.preferred_address_family().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-split")]
+            core_endpoint: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.core_endpoint().map(|v| (v, p.source()))),
+            #[cfg(feature = "outbound-only")]
+            outbound_only,
+            #[cfg(feature = "authorization-handler-provider")]
+            authorization_handlers: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.authorization_handlers().map(|v| (v, p.source()))),
             #[cfg(feature = "service2")]
             service_timer_interval: self
                 .partial_configs
@@ -450,6 +669,11 @@ impl ConfigBuilder {
                 .ok_or_else(|| {
                     ConfigError::MissingValue("lifecycle_executor_interval".to_string())
                 })?,
+            #[cfg(feature = "service-orchestrator-limits")]
+            service_limits: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_limits().map(|v| (v, p.source()))),
         })
     }
 }
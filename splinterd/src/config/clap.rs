@@ -112,6 +112,32 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 .with_service_endpoint(self.matches.value_of("service_endpoint").map(String::from))
         }
 
+        #[cfg(feature = "rest-api-multi-bind")]
+        {
+            partial_config = partial_config
+                .with_rest_api_additional_endpoints(
+                    self.matches
+                        .values_of("rest_api_additional_endpoints")
+                        .map(|values| values.map(String::from).collect::<Vec<String>>()),
+                )
+                .with_rest_api_unauthenticated_endpoints(
+                    self.matches
+                        .values_of("rest_api_unauthenticated_endpoints")
+                        .map(|values| values.map(String::from).collect::<Vec<String>>()),
+                )
+        }
+
+        #[cfg(feature = "registry-node-signing")]
+        {
+            partial_config = partial_config.with_registry_require_signed_nodes(
+                if self.matches.is_present("registry_require_signed_nodes") {
+                    Some(true)
+                } else {
+                    None
+                },
+            )
+        }
+
         #[cfg(feature = "rest-api-cors")]
         {
             partial_config = partial_config.with_allow_list(
@@ -184,6 +210,263 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 .with_influx_password(self.matches.value_of("influx_password").map(String::from))
         }
 
+        #[cfg(feature = "tap-backends")]
+        {
+            partial_config = partial_config
+                .with_metrics_backend(self.matches.value_of("metrics_backend").map(String::from))
+                .with_statsd_address(self.matches.value_of("statsd_address").map(String::from))
+                .with_otlp_url(self.matches.value_of("otlp_url").map(String::from));
+        }
+
+        #[cfg(feature = "rbac-bootstrap")]
+        {
+            partial_config = partial_config.with_rbac_bootstrap_file(
+                self.matches
+                    .value_of("rbac_bootstrap_file")
+                    .map(String::from),
+            );
+        }
+
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        {
+            partial_config = partial_config
+                .with_bandwidth_limit_global(parse_value(&self.matches, "bandwidth_limit_global")?)
+                .with_bandwidth_limit_per_peer(parse_value(
+                    &self.matches,
+                    "bandwidth_limit_per_peer",
+                )?);
+        }
+
+        #[cfg(feature = "auth-handshake-limits")]
+        {
+            partial_config = partial_config
+                .with_auth_handshake_timeout(parse_value(&self.matches, "auth_handshake_timeout")?)
+                .with_max_unauthorized_connections(parse_value(
+                    &self.matches,
+                    "max_unauthorized_connections",
+                )?)
+                .with_connection_rate_limit(parse_value(&self.matches, "connection_rate_limit")?);
+        }
+
+        #[cfg(feature = "state-dir-health")]
+        {
+            partial_config = partial_config.with_state_dir_degraded_threshold(parse_value(
+                &self.matches,
+                "state_dir_degraded_threshold",
+            )?);
+        }
+
+        #[cfg(feature = "challenge-endpoint-binding")]
+        {
+            partial_config = partial_config.with_challenge_endpoint_binding(
+                self.matches
+                    .value_of("challenge_endpoint_binding")
+                    .map(str::parse)
+                    .transpose()
+                    .map_err(|err| ConfigError::InvalidArgument(format!("{}", err)))?,
+            );
+        }
+
+        #[cfg(feature = "admin-service-proposal-timeout")]
+        {
+            partial_config = partial_config
+                .with_admin_max_timeout(parse_value(&self.matches, "admin_max_timeout")?);
+        }
+
+        #[cfg(feature = "admin-service-consensus-retry")]
+        {
+            partial_config = partial_config
+                .with_admin_verification_timeout(parse_value(
+                    &self.matches,
+                    "admin_verification_timeout",
+                )?)
+                .with_admin_verification_retries(parse_value(
+                    &self.matches,
+                    "admin_verification_retries",
+                )?)
+                .with_admin_verification_backoff(parse_value(
+                    &self.matches,
+                    "admin_verification_backoff",
+                )?);
+        }
+
+        #[cfg(feature = "health-service")]
+        {
+            partial_config = partial_config
+                .with_health_check_interval(parse_value(&self.matches, "health_check_interval")?);
+        }
+
+        #[cfg(feature = "circuit-max-message-size")]
+        {
+            partial_config = partial_config
+                .with_max_message_size(parse_value(&self.matches, "max_message_size")?);
+        }
+
+        #[cfg(feature = "circuit-tombstones")]
+        {
+            partial_config = partial_config.with_circuit_tombstones(
+                if self.matches.is_present("circuit_tombstones") {
+                    Some(true)
+                } else {
+                    None
+                },
+            );
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            partial_config = partial_config
+                .with_tracing_otlp_url(self.matches.value_of("tracing_otlp_url").map(String::from));
+        }
+
+        #[cfg(feature = "registry-parallel-fetch")]
+        {
+            partial_config = partial_config
+                .with_registry_fetch_timeout(parse_value(&self.matches, "registry_fetch_timeout")?);
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        {
+            partial_config = partial_config
+                .with_admin_proposal_rate_limit(parse_value(
+                    &self.matches,
+                    "admin_proposal_rate_limit",
+                )?)
+                .with_admin_max_pending_proposals(parse_value(
+                    &self.matches,
+                    "admin_max_pending_proposals",
+                )?);
+        }
+
+        #[cfg(feature = "scabbard-shared-executor-pool")]
+        {
+            partial_config = partial_config.with_scabbard_executor_pool_size(parse_value(
+                &self.matches,
+                "scabbard_executor_pool_size",
+            )?);
+        }
+
+        #[cfg(feature = "transport-tcp-options")]
+        {
+            partial_config = partial_config
+                .with_tcp_keepalive(parse_value(&self.matches, "tcp_keepalive")?)
+                .with_tcp_user_timeout(parse_value(&self.matches, "tcp_user_timeout")?)
+                .with_tcp_nodelay(if self.matches.is_present("tcp_nodelay") {
+                    Some(true)
+                } else {
+                    None
+                });
+        }
+
+        #[cfg(feature = "rest-api-read-only")]
+        {
+            partial_config = partial_config.with_rest_api_read_only(
+                if self.matches.is_present("rest_api_read_only") {
+                    Some(true)
+                } else {
+                    None
+                },
+            );
+        }
+
+        #[cfg(feature = "tls-policy")]
+        {
+            partial_config = partial_config
+                .with_tls_min_version(
+                    self.matches
+                        .value_of("tls_min_version")
+                        .map(str::parse)
+                        .transpose()
+                        .map_err(|err| ConfigError::InvalidArgument(format!("{}", err)))?,
+                )
+                .with_tls_cipher_suites(
+                    self.matches
+                        .values_of("tls_cipher_suites")
+                        .map(|values| values.map(String::from).collect::<Vec<String>>()),
+                );
+        }
+
+        #[cfg(feature = "store-schema-check")]
+        {
+            partial_config =
+                partial_config.with_auto_migrate(if self.matches.is_present("auto_migrate") {
+                    Some(true)
+                } else {
+                    None
+                });
+        }
+
+        #[cfg(feature = "store-lease")]
+        {
+            partial_config =
+                partial_config.with_steal_lease(if self.matches.is_present("steal_lease") {
+                    Some(true)
+                } else {
+                    None
+                });
+        }
+
+        #[cfg(feature = "circuit-access-log")]
+        {
+            partial_config = partial_config
+                .with_circuit_access_log(
+                    self.matches
+                        .values_of("circuit_access_log")
+                        .map(|values| values.map(String::from).collect::<Vec<String>>()),
+                )
+                .with_circuit_access_log_retention(parse_value(
+                    &self.matches,
+                    "circuit_access_log_retention",
+                )?);
+        }
+
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            partial_config = partial_config
+                .with_admin_event_retention_age(parse_value(
+                    &self.matches,
+                    "admin_event_retention_age",
+                )?)
+                .with_admin_event_retention_count(parse_value(
+                    &self.matches,
+                    "admin_event_retention_count",
+                )?);
+        }
+
+        #[cfg(feature = "transport-ipv6")]
+        {
+            partial_config = partial_config.with_preferred_address_family(
+                self.matches
+                    .value_of("preferred_address_family")
+                    .map(String::from),
+            );
+        }
+
+        #[cfg(feature = "rest-api-split")]
+        {
+            partial_config = partial_config
+                .with_core_endpoint(self.matches.value_of("core_endpoint").map(String::from));
+        }
+
+        #[cfg(feature = "outbound-only")]
+        {
+            partial_config =
+                partial_config.with_outbound_only(if self.matches.is_present("outbound_only") {
+                    Some(true)
+                } else {
+                    None
+                });
+        }
+
+        #[cfg(feature = "authorization-handler-provider")]
+        {
+            partial_config = partial_config.with_authorization_handlers(
+                self.matches
+                    .values_of("authorization_handlers")
+                    .map(|values| values.map(String::from).collect::<Vec<String>>()),
+            );
+        }
+
         #[cfg(feature = "service-timer-interval")]
         {
             partial_config = partial_config.with_service_timer_interval(
@@ -32,7 +32,7 @@ pub struct LogConfig {
     pub loggers: Vec<LoggerConfig>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LoggerConfig {
     pub name: String,
     pub appenders: Option<Vec<String>>,
@@ -45,13 +45,13 @@ pub struct UnnamedLoggerConfig {
     pub level: Option<Level>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RootConfig {
     pub appenders: Vec<String>,
     pub level: Level,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct AppenderConfig {
     pub name: String,
     pub encoder: LogEncoder,
@@ -68,7 +68,7 @@ pub struct UnnamedAppenderConfig {
     pub level: Option<Level>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum LogTarget {
     Stdout,
     Stderr,
@@ -84,7 +84,7 @@ pub enum RawLogTarget {
     RollingFile,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LogEncoder {
     value: String,
 }
@@ -27,8 +27,15 @@ mod logging;
 mod partial;
 mod toml;
 
+#[cfg(feature = "service-orchestrator-limits")]
+use std::collections::HashMap;
 use std::time::Duration;
 
+#[cfg(feature = "challenge-endpoint-binding")]
+use splinter::network::auth::EndpointBindingMode;
+#[cfg(feature = "tls-policy")]
+use splinter::transport::tls::TlsVersion;
+
 pub use crate::config::clap::ClapPartialConfigBuilder;
 pub use crate::config::default::DefaultPartialConfigBuilder;
 pub use crate::config::env::EnvPartialConfigBuilder;
@@ -43,7 +50,7 @@ pub use logging::{
 
 /// `Config` is the final representation of configuration values. This final config object assembles
 /// values from `PartialConfig` builder objects generated from various sources.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Config {
     config_dir: (String, ConfigSource),
     tls_cert_dir: (String, ConfigSource),
@@ -64,10 +71,16 @@ pub struct Config {
     node_id: Option<(String, ConfigSource)>,
     display_name: Option<(String, ConfigSource)>,
     rest_api_endpoint: (String, ConfigSource),
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_additional_endpoints: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_unauthenticated_endpoints: Option<(Vec<String>, ConfigSource)>,
     database: (String, ConfigSource),
     registries: (Vec<String>, ConfigSource),
     registry_auto_refresh: (u64, ConfigSource),
     registry_forced_refresh: (u64, ConfigSource),
+    #[cfg(feature = "registry-node-signing")]
+    registry_require_signed_nodes: Option<(bool, ConfigSource)>,
     heartbeat: (u64, ConfigSource),
     admin_timeout: (Duration, ConfigSource),
     state_dir: (String, ConfigSource),
@@ -100,6 +113,12 @@ pub struct Config {
     influx_username: Option<(String, ConfigSource)>,
     #[cfg(feature = "tap")]
     influx_password: Option<(String, ConfigSource)>,
+    #[cfg(feature = "tap-backends")]
+    metrics_backend: Option<(String, ConfigSource)>,
+    #[cfg(feature = "tap-backends")]
+    statsd_address: Option<(String, ConfigSource)>,
+    #[cfg(feature = "tap-backends")]
+    otlp_url: Option<(String, ConfigSource)>,
     peering_key: (String, ConfigSource),
     root_logger: (RootConfig, ConfigSource),
     appenders: Option<Vec<(AppenderConfig, ConfigSource)>>,
@@ -107,12 +126,86 @@ pub struct Config {
     verbosity: (log::Level, ConfigSource),
     #[cfg(feature = "config-allow-keys")]
     allow_keys_file: (String, ConfigSource),
+    #[cfg(feature = "rbac-bootstrap")]
+    rbac_bootstrap_file: Option<(String, ConfigSource)>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limit_global: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limit_per_peer: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "auth-handshake-limits")]
+    auth_handshake_timeout: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "auth-handshake-limits")]
+    max_unauthorized_connections: Option<(usize, ConfigSource)>,
+    #[cfg(feature = "auth-handshake-limits")]
+    connection_rate_limit: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "state-dir-health")]
+    state_dir_degraded_threshold: Option<(u8, ConfigSource)>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    challenge_endpoint_binding: Option<(EndpointBindingMode, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    admin_max_timeout: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_timeout: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_retries: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_backoff: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "health-service")]
+    health_check_interval: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: Option<(bool, ConfigSource)>,
+    #[cfg(feature = "tracing")]
+    tracing_otlp_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    registry_fetch_timeout: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_proposal_rate_limit: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_pending_proposals: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    scabbard_executor_pool_size: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_keepalive: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_user_timeout: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_nodelay: Option<(bool, ConfigSource)>,
+    #[cfg(feature = "rest-api-read-only")]
+    rest_api_read_only: Option<(bool, ConfigSource)>,
+    #[cfg(feature = "tls-policy")]
+    tls_min_version: Option<(TlsVersion, ConfigSource)>,
+    #[cfg(feature = "tls-policy")]
+    tls_cipher_suites: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "store-schema-check")]
+    auto_migrate: Option<(bool, ConfigSource)>,
+    #[cfg(feature = "store-lease")]
+    steal_lease: Option<(bool, ConfigSource)>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log_retention: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention_age: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention_count: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "transport-ipv6")]
+    preferred_address_family: Option<(String, ConfigSource)>,
+    #[cfg(feature = "rest-api-split")]
+    core_endpoint: Option<(String, ConfigSource)>,
+    #[cfg(feature = "outbound-only")]
+    outbound_only: Option<(bool, ConfigSource)>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handlers: Option<(Vec<String>, ConfigSource)>,
     scabbard_state: (ScabbardState, ConfigSource),
     scabbard_autocleanup: (bool, ConfigSource),
     #[cfg(feature = "service2")]
     service_timer_interval: (Duration, ConfigSource),
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: (Duration, ConfigSource),
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_limits: Option<(HashMap<String, ServiceLimitsConfig>, ConfigSource)>,
 }
 
 impl Config {
@@ -191,6 +284,24 @@ impl Config {
         &self.rest_api_endpoint.0
     }
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn rest_api_additional_endpoints(&self) -> Option<&[String]> {
+        if let Some((endpoints, _)) = &self.rest_api_additional_endpoints {
+            Some(endpoints)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn rest_api_unauthenticated_endpoints(&self) -> Option<&[String]> {
+        if let Some((endpoints, _)) = &self.rest_api_unauthenticated_endpoints {
+            Some(endpoints)
+        } else {
+            None
+        }
+    }
+
     pub fn database(&self) -> &str {
         &self.database.0
     }
@@ -207,6 +318,14 @@ impl Config {
         self.registry_forced_refresh.0
     }
 
+    #[cfg(feature = "registry-node-signing")]
+    pub fn registry_require_signed_nodes(&self) -> bool {
+        self.registry_require_signed_nodes
+            .as_ref()
+            .map(|(require, _)| *require)
+            .unwrap_or(false)
+    }
+
     pub fn heartbeat(&self) -> u64 {
         self.heartbeat.0
     }
@@ -344,10 +463,277 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "tap-backends")]
+    pub fn metrics_backend(&self) -> Option<&str> {
+        self.metrics_backend
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn statsd_address(&self) -> Option<&str> {
+        self.statsd_address
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn otlp_url(&self) -> Option<&str> {
+        self.otlp_url.as_ref().map(|(value, _)| value.as_str())
+    }
+
     pub fn peering_key(&self) -> &str {
         &self.peering_key.0
     }
 
+    #[cfg(feature = "rbac-bootstrap")]
+    pub fn rbac_bootstrap_file(&self) -> Option<&str> {
+        if let Some((file, _)) = &self.rbac_bootstrap_file {
+            Some(file)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_limit_global(&self) -> Option<u64> {
+        self.bandwidth_limit_global
+            .as_ref()
+            .map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_limit_per_peer(&self) -> Option<u64> {
+        self.bandwidth_limit_per_peer
+            .as_ref()
+            .map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn auth_handshake_timeout(&self) -> Option<u64> {
+        self.auth_handshake_timeout
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn max_unauthorized_connections(&self) -> Option<usize> {
+        self.max_unauthorized_connections
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn connection_rate_limit(&self) -> Option<u32> {
+        self.connection_rate_limit.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "state-dir-health")]
+    pub fn state_dir_degraded_threshold(&self) -> Option<u8> {
+        self.state_dir_degraded_threshold
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn challenge_endpoint_binding(&self) -> Option<EndpointBindingMode> {
+        self.challenge_endpoint_binding
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    pub fn admin_max_timeout(&self) -> Option<Duration> {
+        self.admin_max_timeout.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_timeout(&self) -> Option<Duration> {
+        self.admin_verification_timeout
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_retries(&self) -> Option<u32> {
+        self.admin_verification_retries
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_backoff(&self) -> Option<u32> {
+        self.admin_verification_backoff
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "health-service")]
+    pub fn health_check_interval(&self) -> Option<Duration> {
+        self.health_check_interval.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn circuit_tombstones(&self) -> bool {
+        self.circuit_tombstones
+            .as_ref()
+            .map(|(tombstones, _)| *tombstones)
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn tracing_otlp_url(&self) -> Option<&str> {
+        self.tracing_otlp_url
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn registry_fetch_timeout(&self) -> Option<Duration> {
+        self.registry_fetch_timeout
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_proposal_rate_limit(&self) -> Option<u32> {
+        self.admin_proposal_rate_limit
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_pending_proposals(&self) -> Option<u32> {
+        self.admin_max_pending_proposals
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    pub fn scabbard_executor_pool_size(&self) -> Option<u32> {
+        self.scabbard_executor_pool_size
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_user_timeout(&self) -> Option<Duration> {
+        self.tcp_user_timeout.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_nodelay(&self) -> bool {
+        self.tcp_nodelay
+            .as_ref()
+            .map(|(nodelay, _)| *nodelay)
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "rest-api-read-only")]
+    pub fn rest_api_read_only(&self) -> bool {
+        self.rest_api_read_only
+            .as_ref()
+            .map(|(read_only, _)| *read_only)
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "tls-policy")]
+    pub fn tls_min_version(&self) -> Option<TlsVersion> {
+        self.tls_min_version.as_ref().map(|(version, _)| *version)
+    }
+
+    #[cfg(feature = "tls-policy")]
+    pub fn tls_cipher_suites(&self) -> &[String] {
+        self.tls_cipher_suites
+            .as_ref()
+            .map(|(cipher_suites, _)| cipher_suites.as_slice())
+            .unwrap_or(&[])
+    }
+
+    #[cfg(feature = "store-schema-check")]
+    pub fn auto_migrate(&self) -> bool {
+        self.auto_migrate
+            .as_ref()
+            .map(|(auto_migrate, _)| *auto_migrate)
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "store-lease")]
+    pub fn steal_lease(&self) -> bool {
+        self.steal_lease
+            .as_ref()
+            .map(|(steal_lease, _)| *steal_lease)
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn circuit_access_log(&self) -> &[String] {
+        self.circuit_access_log
+            .as_ref()
+            .map(|(circuits, _)| circuits.as_slice())
+            .unwrap_or(&[])
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn circuit_access_log_retention(&self) -> Option<u64> {
+        self.circuit_access_log_retention
+            .as_ref()
+            .map(|(days, _)| *days)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_retention_age(&self) -> Option<u64> {
+        self.admin_event_retention_age
+            .as_ref()
+            .map(|(days, _)| *days)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_retention_count(&self) -> Option<u64> {
+        self.admin_event_retention_count
+            .as_ref()
+            .map(|(count, _)| *count)
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    pub fn preferred_address_family(&self) -> Option<&str> {
+        self.preferred_address_family
+            .as_ref()
+            .map(|(family, _)| family.as_str())
+    }
+
+    #[cfg(feature = "rest-api-split")]
+    pub fn core_endpoint(&self) -> Option<&str> {
+        self.core_endpoint
+            .as_ref()
+            .map(|(endpoint, _)| endpoint.as_str())
+    }
+
+    #[cfg(feature = "outbound-only")]
+    pub fn outbound_only(&self) -> bool {
+        self.outbound_only
+            .as_ref()
+            .map(|(outbound_only, _)| *outbound_only)
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "authorization-handler-provider")]
+    pub fn authorization_handlers(&self) -> Option<&[String]> {
+        self.authorization_handlers
+            .as_ref()
+            .map(|(handlers, _)| handlers.as_slice())
+    }
+
     #[cfg(feature = "service2")]
     pub fn service_timer_interval(&self) -> Duration {
         self.service_timer_interval.0
@@ -358,6 +744,11 @@ impl Config {
         self.lifecycle_executor_interval.0
     }
 
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn service_limits(&self) -> Option<&HashMap<String, ServiceLimitsConfig>> {
+        self.service_limits.as_ref().map(|(limits, _)| limits)
+    }
+
     pub fn config_dir_source(&self) -> &ConfigSource {
         &self.config_dir.1
     }
@@ -433,6 +824,24 @@ impl Config {
         &self.rest_api_endpoint.1
     }
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    fn rest_api_additional_endpoints_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.rest_api_additional_endpoints {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "rest-api-multi-bind")]
+    fn rest_api_unauthenticated_endpoints_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.rest_api_unauthenticated_endpoints {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
     fn database_source(&self) -> &ConfigSource {
         &self.database.1
     }
@@ -449,6 +858,13 @@ impl Config {
         &self.registry_forced_refresh.1
     }
 
+    #[cfg(feature = "registry-node-signing")]
+    fn registry_require_signed_nodes_source(&self) -> Option<&ConfigSource> {
+        self.registry_require_signed_nodes
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
     fn heartbeat_source(&self) -> &ConfigSource {
         &self.heartbeat.1
     }
@@ -586,6 +1002,21 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "tap-backends")]
+    pub fn metrics_backend_source(&self) -> Option<&ConfigSource> {
+        self.metrics_backend.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn statsd_address_source(&self) -> Option<&ConfigSource> {
+        self.statsd_address.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn otlp_url_source(&self) -> Option<&ConfigSource> {
+        self.otlp_url.as_ref().map(|(_, source)| source)
+    }
+
     fn peering_key_source(&self) -> &ConfigSource {
         &self.peering_key.1
     }
@@ -644,6 +1075,230 @@ impl Config {
         &self.scabbard_autocleanup.1
     }
 
+    #[cfg(feature = "rbac-bootstrap")]
+    pub fn rbac_bootstrap_file_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.rbac_bootstrap_file {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_limit_global_source(&self) -> Option<&ConfigSource> {
+        self.bandwidth_limit_global
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_limit_per_peer_source(&self) -> Option<&ConfigSource> {
+        self.bandwidth_limit_per_peer
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn auth_handshake_timeout_source(&self) -> Option<&ConfigSource> {
+        self.auth_handshake_timeout
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn max_unauthorized_connections_source(&self) -> Option<&ConfigSource> {
+        self.max_unauthorized_connections
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn connection_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.connection_rate_limit
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "state-dir-health")]
+    pub fn state_dir_degraded_threshold_source(&self) -> Option<&ConfigSource> {
+        self.state_dir_degraded_threshold
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn challenge_endpoint_binding_source(&self) -> Option<&ConfigSource> {
+        self.challenge_endpoint_binding
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    pub fn admin_max_timeout_source(&self) -> Option<&ConfigSource> {
+        self.admin_max_timeout.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_timeout_source(&self) -> Option<&ConfigSource> {
+        self.admin_verification_timeout
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_retries_source(&self) -> Option<&ConfigSource> {
+        self.admin_verification_retries
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_backoff_source(&self) -> Option<&ConfigSource> {
+        self.admin_verification_backoff
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "health-service")]
+    pub fn health_check_interval_source(&self) -> Option<&ConfigSource> {
+        self.health_check_interval
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn max_message_size_source(&self) -> Option<&ConfigSource> {
+        self.max_message_size.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn circuit_tombstones_source(&self) -> Option<&ConfigSource> {
+        self.circuit_tombstones.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn tracing_otlp_url_source(&self) -> Option<&ConfigSource> {
+        self.tracing_otlp_url.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn registry_fetch_timeout_source(&self) -> Option<&ConfigSource> {
+        self.registry_fetch_timeout
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_proposal_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.admin_proposal_rate_limit
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_pending_proposals_source(&self) -> Option<&ConfigSource> {
+        self.admin_max_pending_proposals
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    pub fn scabbard_executor_pool_size_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_executor_pool_size
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_keepalive_source(&self) -> Option<&ConfigSource> {
+        self.tcp_keepalive.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_user_timeout_source(&self) -> Option<&ConfigSource> {
+        self.tcp_user_timeout.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_nodelay_source(&self) -> Option<&ConfigSource> {
+        self.tcp_nodelay.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-read-only")]
+    pub fn rest_api_read_only_source(&self) -> Option<&ConfigSource> {
+        self.rest_api_read_only.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "tls-policy")]
+    pub fn tls_min_version_source(&self) -> Option<&ConfigSource> {
+        self.tls_min_version.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "tls-policy")]
+    pub fn tls_cipher_suites_source(&self) -> Option<&ConfigSource> {
+        self.tls_cipher_suites.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "store-schema-check")]
+    pub fn auto_migrate_source(&self) -> Option<&ConfigSource> {
+        self.auto_migrate.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "store-lease")]
+    pub fn steal_lease_source(&self) -> Option<&ConfigSource> {
+        self.steal_lease.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn circuit_access_log_source(&self) -> Option<&ConfigSource> {
+        self.circuit_access_log.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn circuit_access_log_retention_source(&self) -> Option<&ConfigSource> {
+        self.circuit_access_log_retention
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_retention_age_source(&self) -> Option<&ConfigSource> {
+        self.admin_event_retention_age
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_retention_count_source(&self) -> Option<&ConfigSource> {
+        self.admin_event_retention_count
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    pub fn preferred_address_family_source(&self) -> Option<&ConfigSource> {
+        self.preferred_address_family
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-split")]
+    pub fn core_endpoint_source(&self) -> Option<&ConfigSource> {
+        self.core_endpoint.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "outbound-only")]
+    pub fn outbound_only_source(&self) -> Option<&ConfigSource> {
+        self.outbound_only.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "authorization-handler-provider")]
+    pub fn authorization_handlers_source(&self) -> Option<&ConfigSource> {
+        self.authorization_handlers
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
     #[cfg(feature = "service2")]
     pub fn service_timer_interval_source(&self) -> &ConfigSource {
         &self.service_timer_interval.1
@@ -654,6 +1309,11 @@ impl Config {
         &self.lifecycle_executor_interval.1
     }
 
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn service_limits_source(&self) -> Option<&ConfigSource> {
+        self.service_limits.as_ref().map(|(_, source)| source)
+    }
+
     #[allow(clippy::cognitive_complexity)]
     /// Displays the configuration value along with where the value was sourced from.
     pub fn log_as_debug(&self) {
@@ -742,6 +1402,8 @@ impl Config {
             self.rest_api_endpoint(),
             self.rest_api_endpoint_source()
         );
+        #[cfg(feature = "rest-api-multi-bind")]
+        self.log_rest_api_additional_endpoints();
         debug!(
             "Config: registries: {:?} (source: {:?})",
             self.registries(),
@@ -757,6 +1419,14 @@ impl Config {
             self.registry_forced_refresh(),
             self.registry_forced_refresh_source()
         );
+        #[cfg(feature = "registry-node-signing")]
+        if let Some(source) = self.registry_require_signed_nodes_source() {
+            debug!(
+                "Config: registry_require_signed_nodes: {} (source: {:?})",
+                self.registry_require_signed_nodes(),
+                source
+            );
+        }
         debug!(
             "Config: state_dir: {} (source: {:?})",
             self.state_dir(),
@@ -883,6 +1553,25 @@ impl Config {
                 debug!("Config: influx_password: <HIDDEN> (source: {:?})", source,);
             }
         }
+        #[cfg(feature = "tap-backends")]
+        {
+            if let (Some(value), Some(source)) =
+                (self.metrics_backend(), self.metrics_backend_source())
+            {
+                debug!(
+                    "Config: metrics_backend: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+            if let (Some(value), Some(source)) =
+                (self.statsd_address(), self.statsd_address_source())
+            {
+                debug!("Config: statsd_address: {:?} (source: {:?})", value, source);
+            }
+            if let (Some(value), Some(source)) = (self.otlp_url(), self.otlp_url_source()) {
+                debug!("Config: otlp_url: {:?} (source: {:?})", value, source);
+            }
+        }
         if let Some(loggers) = &self.loggers {
             for logger in loggers {
                 debug!("Config: logger: {:?} (source: {:?})", logger.0, logger.1);
@@ -927,6 +1616,388 @@ impl Config {
             self.scabbard_autocleanup_source()
         );
 
+        #[cfg(feature = "rbac-bootstrap")]
+        if let (Some(file), Some(source)) = (
+            self.rbac_bootstrap_file(),
+            self.rbac_bootstrap_file_source(),
+        ) {
+            debug!(
+                "Config: rbac_bootstrap_file: {} (source: {:?})",
+                file, source
+            );
+        }
+
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        {
+            if let (Some(limit), Some(source)) = (
+                self.bandwidth_limit_global(),
+                self.bandwidth_limit_global_source(),
+            ) {
+                debug!(
+                    "Config: bandwidth_limit_global: {} (source: {:?})",
+                    limit, source
+                );
+            }
+            if let (Some(limit), Some(source)) = (
+                self.bandwidth_limit_per_peer(),
+                self.bandwidth_limit_per_peer_source(),
+            ) {
+                debug!(
+                    "Config: bandwidth_limit_per_peer: {} (source: {:?})",
+                    limit, source
+                );
+            }
+        }
+
+        #[cfg(feature = "auth-handshake-limits")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.auth_handshake_timeout(),
+                self.auth_handshake_timeout_source(),
+            ) {
+                debug!(
+                    "Config: auth_handshake_timeout: {} (source: {:?})",
+                    value, source
+                );
+            }
+            if let (Some(value), Some(source)) = (
+                self.max_unauthorized_connections(),
+                self.max_unauthorized_connections_source(),
+            ) {
+                debug!(
+                    "Config: max_unauthorized_connections: {} (source: {:?})",
+                    value, source
+                );
+            }
+            if let (Some(value), Some(source)) = (
+                self.connection_rate_limit(),
+                self.connection_rate_limit_source(),
+            ) {
+                debug!(
+                    "Config: connection_rate_limit: {} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "state-dir-health")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.state_dir_degraded_threshold(),
+                self.state_dir_degraded_threshold_source(),
+            ) {
+                debug!(
+                    "Config: state_dir_degraded_threshold: {} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "challenge-endpoint-binding")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.challenge_endpoint_binding(),
+                self.challenge_endpoint_binding_source(),
+            ) {
+                debug!(
+                    "Config: challenge_endpoint_binding: {} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "admin-service-proposal-timeout")]
+        {
+            if let (Some(value), Some(source)) =
+                (self.admin_max_timeout(), self.admin_max_timeout_source())
+            {
+                debug!(
+                    "Config: admin_max_timeout: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "admin-service-consensus-retry")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.admin_verification_timeout(),
+                self.admin_verification_timeout_source(),
+            ) {
+                debug!(
+                    "Config: admin_verification_timeout: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+            if let (Some(value), Some(source)) = (
+                self.admin_verification_retries(),
+                self.admin_verification_retries_source(),
+            ) {
+                debug!(
+                    "Config: admin_verification_retries: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+            if let (Some(value), Some(source)) = (
+                self.admin_verification_backoff(),
+                self.admin_verification_backoff_source(),
+            ) {
+                debug!(
+                    "Config: admin_verification_backoff: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "health-service")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.health_check_interval(),
+                self.health_check_interval_source(),
+            ) {
+                debug!(
+                    "Config: health_check_interval: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "circuit-max-message-size")]
+        {
+            if let (Some(value), Some(source)) =
+                (self.max_message_size(), self.max_message_size_source())
+            {
+                debug!(
+                    "Config: max_message_size: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "circuit-tombstones")]
+        {
+            if let Some(source) = self.circuit_tombstones_source() {
+                debug!(
+                    "Config: circuit_tombstones: {} (source: {:?})",
+                    self.circuit_tombstones(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            if let (Some(value), Some(source)) =
+                (self.tracing_otlp_url(), self.tracing_otlp_url_source())
+            {
+                debug!(
+                    "Config: tracing_otlp_url: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "registry-parallel-fetch")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.registry_fetch_timeout(),
+                self.registry_fetch_timeout_source(),
+            ) {
+                debug!(
+                    "Config: registry_fetch_timeout: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.admin_proposal_rate_limit(),
+                self.admin_proposal_rate_limit_source(),
+            ) {
+                debug!(
+                    "Config: admin_proposal_rate_limit: {} (source: {:?})",
+                    value, source
+                );
+            }
+            if let (Some(value), Some(source)) = (
+                self.admin_max_pending_proposals(),
+                self.admin_max_pending_proposals_source(),
+            ) {
+                debug!(
+                    "Config: admin_max_pending_proposals: {} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "scabbard-shared-executor-pool")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.scabbard_executor_pool_size(),
+                self.scabbard_executor_pool_size_source(),
+            ) {
+                debug!(
+                    "Config: scabbard_executor_pool_size: {} (source: {:?})",
+                    value, source
+                );
+            }
+        }
+
+        #[cfg(feature = "transport-tcp-options")]
+        {
+            if let (Some(value), Some(source)) = (self.tcp_keepalive(), self.tcp_keepalive_source())
+            {
+                debug!("Config: tcp_keepalive: {:?} (source: {:?})", value, source);
+            }
+            if let (Some(value), Some(source)) =
+                (self.tcp_user_timeout(), self.tcp_user_timeout_source())
+            {
+                debug!(
+                    "Config: tcp_user_timeout: {:?} (source: {:?})",
+                    value, source
+                );
+            }
+            if let Some(source) = self.tcp_nodelay_source() {
+                debug!(
+                    "Config: tcp_nodelay: {} (source: {:?})",
+                    self.tcp_nodelay(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "rest-api-read-only")]
+        {
+            if let Some(source) = self.rest_api_read_only_source() {
+                debug!(
+                    "Config: rest_api_read_only: {} (source: {:?})",
+                    self.rest_api_read_only(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "tls-policy")]
+        {
+            if let (Some(version), Some(source)) =
+                (self.tls_min_version(), self.tls_min_version_source())
+            {
+                debug!(
+                    "Config: tls_min_version: {} (source: {:?})",
+                    version, source
+                );
+            }
+            if let Some(source) = self.tls_cipher_suites_source() {
+                debug!(
+                    "Config: tls_cipher_suites: {:?} (source: {:?})",
+                    self.tls_cipher_suites(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "store-schema-check")]
+        {
+            if let Some(source) = self.auto_migrate_source() {
+                debug!(
+                    "Config: auto_migrate: {} (source: {:?})",
+                    self.auto_migrate(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "store-lease")]
+        {
+            if let Some(source) = self.steal_lease_source() {
+                debug!(
+                    "Config: steal_lease: {} (source: {:?})",
+                    self.steal_lease(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "circuit-access-log")]
+        {
+            if let Some(source) = self.circuit_access_log_source() {
+                debug!(
+                    "Config: circuit_access_log: {:?} (source: {:?})",
+                    self.circuit_access_log(),
+                    source
+                );
+            }
+            if let Some(source) = self.circuit_access_log_retention_source() {
+                debug!(
+                    "Config: circuit_access_log_retention: {:?} (source: {:?})",
+                    self.circuit_access_log_retention(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            if let Some(source) = self.admin_event_retention_age_source() {
+                debug!(
+                    "Config: admin_event_retention_age: {:?} (source: {:?})",
+                    self.admin_event_retention_age(),
+                    source
+                );
+            }
+            if let Some(source) = self.admin_event_retention_count_source() {
+                debug!(
+                    "Config: admin_event_retention_count: {:?} (source: {:?})",
+                    self.admin_event_retention_count(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "transport-ipv6")]
+        if let Some(source) = self.preferred_address_family_source() {
+            debug!(
+                "Config: preferred_address_family: {:?} (source: {:?})",
+                self.preferred_address_family(),
+                source
+            );
+        }
+
+        #[cfg(feature = "rest-api-split")]
+        {
+            if let Some(source) = self.core_endpoint_source() {
+                debug!(
+                    "Config: core_endpoint: {:?} (source: {:?})",
+                    self.core_endpoint(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "outbound-only")]
+        {
+            if let Some(source) = self.outbound_only_source() {
+                debug!(
+                    "Config: outbound_only: {} (source: {:?})",
+                    self.outbound_only(),
+                    source
+                );
+            }
+        }
+
+        #[cfg(feature = "authorization-handler-provider")]
+        {
+            if let Some(source) = self.authorization_handlers_source() {
+                debug!(
+                    "Config: authorization_handlers: {:?} (source: {:?})",
+                    self.authorization_handlers(),
+                    source
+                );
+            }
+        }
+
         #[cfg(feature = "service2")]
         {
             debug!(
@@ -941,6 +2012,39 @@ impl Config {
                 self.lifecycle_executor_interval_source()
             );
         }
+
+        #[cfg(feature = "service-orchestrator-limits")]
+        {
+            if let Some(source) = self.service_limits_source() {
+                debug!(
+                    "Config: service_limits: {:?} (source: {:?})",
+                    self.service_limits(),
+                    source
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "rest-api-multi-bind")]
+    fn log_rest_api_additional_endpoints(&self) {
+        if let (Some(endpoints), Some(source)) = (
+            self.rest_api_additional_endpoints(),
+            self.rest_api_additional_endpoints_source(),
+        ) {
+            debug!(
+                "Config: rest_api_additional_endpoints: {:?} (source: {:?})",
+                endpoints, source,
+            );
+        }
+        if let (Some(endpoints), Some(source)) = (
+            self.rest_api_unauthenticated_endpoints(),
+            self.rest_api_unauthenticated_endpoints_source(),
+        ) {
+            debug!(
+                "Config: rest_api_unauthenticated_endpoints: {:?} (source: {:?})",
+                endpoints, source,
+            );
+        }
     }
 
     #[cfg(feature = "rest-api-cors")]
@@ -957,6 +2061,17 @@ pub enum ScabbardState {
     Lmdb,
 }
 
+/// The restart policy and resource limits configured for a single service type.
+#[cfg(feature = "service-orchestrator-limits")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceLimitsConfig {
+    pub max_restarts: Option<u32>,
+    pub restart_backoff: Option<Duration>,
+    pub max_restart_backoff: Option<Duration>,
+    pub max_message_queue: Option<usize>,
+    pub max_state_size: Option<u64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1607,6 +2722,51 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "outbound-only")]
+    #[test]
+    /// This test verifies that an outbound-only node does not listen on, or advertise, the default
+    /// network endpoint, but keeps network endpoints that were set explicitly so that they can be
+    /// rejected at startup.
+    fn test_outbound_only_network_endpoints() {
+        let toml_string = r#"
+        version = "1"
+        outbound_only = true
+        "#;
+        let toml = || {
+            TomlPartialConfigBuilder::new(toml_string.to_string(), TEST_TOML.to_string())
+                .expect("Could not deserialize toml")
+                .build()
+                .expect("A config error has occurred")
+        };
+        let default = || {
+            DefaultPartialConfigBuilder::new()
+                .build()
+                .expect("Could not build default partial config")
+        };
+        let config = ConfigBuilder::new()
+            .with_partial_config(toml())
+            .with_partial_config(default())
+            .build()
+            .expect("Could not build final Config");
+        assert!(config.outbound_only());
+        assert!(config.network_endpoints().is_empty());
+        assert!(config.advertised_endpoints().is_empty());
+
+        let explicit = PartialConfig::new(ConfigSource::Environment)
+            .with_network_endpoints(Some(vec![EXAMPLE_NETWORK_ENDPOINT.to_string()]));
+        let config = ConfigBuilder::new()
+            .with_partial_config(explicit)
+            .with_partial_config(toml())
+            .with_partial_config(default())
+            .build()
+            .expect("Could not build final Config");
+        assert!(config.outbound_only());
+        assert_eq!(
+            config.network_endpoints(),
+            &[EXAMPLE_NETWORK_ENDPOINT.to_string()] as &[String]
+        );
+    }
+
     #[test]
     /// This tests verifies that the log options take the right order of precedence and that you
     /// are able to override values where appropriate.
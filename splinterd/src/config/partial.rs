@@ -18,8 +18,15 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
+#[cfg(feature = "challenge-endpoint-binding")]
+use splinter::network::auth::EndpointBindingMode;
+#[cfg(feature = "tls-policy")]
+use splinter::transport::tls::TlsVersion;
+
 use super::logging::{RootConfig, UnnamedAppenderConfig, UnnamedLoggerConfig};
 use super::ScabbardState;
+#[cfg(feature = "service-orchestrator-limits")]
+use super::ServiceLimitsConfig;
 
 /// `ConfigSource` displays the source of configuration values, used to identify which of the various
 /// config modules were used to create a particular `PartialConfig` object.
@@ -56,10 +63,16 @@ pub struct PartialConfig {
     node_id: Option<String>,
     display_name: Option<String>,
     rest_api_endpoint: Option<String>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_additional_endpoints: Option<Vec<String>>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_unauthenticated_endpoints: Option<Vec<String>>,
     database: Option<String>,
     registries: Option<Vec<String>>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-node-signing")]
+    registry_require_signed_nodes: Option<bool>,
     heartbeat: Option<u64>,
     admin_timeout: Option<Duration>,
     state_dir: Option<String>,
@@ -92,6 +105,12 @@ pub struct PartialConfig {
     influx_username: Option<String>,
     #[cfg(feature = "tap")]
     influx_password: Option<String>,
+    #[cfg(feature = "tap-backends")]
+    metrics_backend: Option<String>,
+    #[cfg(feature = "tap-backends")]
+    statsd_address: Option<String>,
+    #[cfg(feature = "tap-backends")]
+    otlp_url: Option<String>,
     peering_key: Option<String>,
     root_logger: Option<RootConfig>,
     appenders: Option<HashMap<String, UnnamedAppenderConfig>>,
@@ -99,12 +118,86 @@ pub struct PartialConfig {
     verbosity: Option<log::Level>,
     #[cfg(feature = "config-allow-keys")]
     allow_keys_file: Option<String>,
+    #[cfg(feature = "rbac-bootstrap")]
+    rbac_bootstrap_file: Option<String>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limit_global: Option<u64>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limit_per_peer: Option<u64>,
+    #[cfg(feature = "auth-handshake-limits")]
+    auth_handshake_timeout: Option<u64>,
+    #[cfg(feature = "auth-handshake-limits")]
+    max_unauthorized_connections: Option<usize>,
+    #[cfg(feature = "auth-handshake-limits")]
+    connection_rate_limit: Option<u32>,
+    #[cfg(feature = "state-dir-health")]
+    state_dir_degraded_threshold: Option<u8>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    challenge_endpoint_binding: Option<EndpointBindingMode>,
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    admin_max_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_retries: Option<u32>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_backoff: Option<u32>,
+    #[cfg(feature = "health-service")]
+    health_check_interval: Option<Duration>,
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: Option<bool>,
+    #[cfg(feature = "tracing")]
+    tracing_otlp_url: Option<String>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    registry_fetch_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_proposal_rate_limit: Option<u32>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_pending_proposals: Option<u32>,
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    scabbard_executor_pool_size: Option<u32>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_keepalive: Option<Duration>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_user_timeout: Option<Duration>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_nodelay: Option<bool>,
+    #[cfg(feature = "rest-api-read-only")]
+    rest_api_read_only: Option<bool>,
+    #[cfg(feature = "tls-policy")]
+    tls_min_version: Option<TlsVersion>,
+    #[cfg(feature = "tls-policy")]
+    tls_cipher_suites: Option<Vec<String>>,
+    #[cfg(feature = "store-schema-check")]
+    auto_migrate: Option<bool>,
+    #[cfg(feature = "store-lease")]
+    steal_lease: Option<bool>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log: Option<Vec<String>>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log_retention: Option<u64>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention_age: Option<u64>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention_count: Option<u64>,
+    #[cfg(feature = "transport-ipv6")]
+    preferred_address_family: Option<String>,
+    #[cfg(feature = "rest-api-split")]
+    core_endpoint: Option<String>,
+    #[cfg(feature = "outbound-only")]
+    outbound_only: Option<bool>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handlers: Option<Vec<String>>,
     scabbard_state: Option<ScabbardState>,
     scabbard_autocleanup: Option<bool>,
     #[cfg(feature = "service2")]
     service_timer_interval: Option<Duration>,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Option<Duration>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_limits: Option<HashMap<String, ServiceLimitsConfig>>,
 }
 
 impl PartialConfig {
@@ -131,10 +224,16 @@ impl PartialConfig {
             node_id: None,
             display_name: None,
             rest_api_endpoint: None,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_additional_endpoints: None,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_unauthenticated_endpoints: None,
             database: None,
             registries: None,
             registry_auto_refresh: None,
             registry_forced_refresh: None,
+            #[cfg(feature = "registry-node-signing")]
+            registry_require_signed_nodes: None,
             heartbeat: None,
             admin_timeout: None,
             state_dir: None,
@@ -167,6 +266,12 @@ impl PartialConfig {
             influx_username: None,
             #[cfg(feature = "tap")]
             influx_password: None,
+            #[cfg(feature = "tap-backends")]
+            metrics_backend: None,
+            #[cfg(feature = "tap-backends")]
+            statsd_address: None,
+            #[cfg(feature = "tap-backends")]
+            otlp_url: None,
             peering_key: None,
             appenders: None,
             loggers: None,
@@ -174,12 +279,86 @@ impl PartialConfig {
             verbosity: None,
             #[cfg(feature = "config-allow-keys")]
             allow_keys_file: None,
+            #[cfg(feature = "rbac-bootstrap")]
+            rbac_bootstrap_file: None,
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            bandwidth_limit_global: None,
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            bandwidth_limit_per_peer: None,
+            #[cfg(feature = "auth-handshake-limits")]
+            auth_handshake_timeout: None,
+            #[cfg(feature = "auth-handshake-limits")]
+            max_unauthorized_connections: None,
+            #[cfg(feature = "auth-handshake-limits")]
+            connection_rate_limit: None,
+            #[cfg(feature = "state-dir-health")]
+            state_dir_degraded_threshold: None,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            challenge_endpoint_binding: None,
+            #[cfg(feature = "admin-service-proposal-timeout")]
+            admin_max_timeout: None,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_timeout: None,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_retries: None,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_backoff: None,
+            #[cfg(feature = "health-service")]
+            health_check_interval: None,
+            #[cfg(feature = "circuit-max-message-size")]
+            max_message_size: None,
+            #[cfg(feature = "circuit-tombstones")]
+            circuit_tombstones: None,
+            #[cfg(feature = "tracing")]
+            tracing_otlp_url: None,
+            #[cfg(feature = "registry-parallel-fetch")]
+            registry_fetch_timeout: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_proposal_rate_limit: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_pending_proposals: None,
+            #[cfg(feature = "scabbard-shared-executor-pool")]
+            scabbard_executor_pool_size: None,
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_keepalive: None,
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_user_timeout: None,
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_nodelay: None,
+            #[cfg(feature = "rest-api-read-only")]
+            rest_api_read_only: None,
+            #[cfg(feature = "tls-policy")]
+            tls_min_version: None,
+            #[cfg(feature = "tls-policy")]
+            tls_cipher_suites: None,
+            #[cfg(feature = "store-schema-check")]
+            auto_migrate: None,
+            #[cfg(feature = "store-lease")]
+            steal_lease: None,
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log: None,
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log_retention: None,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention_age: None,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention_count: None,
+            #[cfg(feature = "transport-ipv6")]
+            preferred_address_family: None,
+            #[cfg(feature = "rest-api-split")]
+            core_endpoint: None,
+            #[cfg(feature = "outbound-only")]
+            outbound_only: None,
+            #[cfg(feature = "authorization-handler-provider")]
+            authorization_handlers: None,
             scabbard_state: None,
             scabbard_autocleanup: None,
             #[cfg(feature = "service2")]
             service_timer_interval: None,
             #[cfg(feature = "service2")]
             lifecycle_executor_interval: None,
+            #[cfg(feature = "service-orchestrator-limits")]
+            service_limits: None,
         }
     }
 
@@ -254,6 +433,16 @@ impl PartialConfig {
         self.rest_api_endpoint.clone()
     }
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn rest_api_additional_endpoints(&self) -> Option<Vec<String>> {
+        self.rest_api_additional_endpoints.clone()
+    }
+
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn rest_api_unauthenticated_endpoints(&self) -> Option<Vec<String>> {
+        self.rest_api_unauthenticated_endpoints.clone()
+    }
+
     pub fn database(&self) -> Option<String> {
         self.database.clone()
     }
@@ -270,6 +459,11 @@ impl PartialConfig {
         self.registry_forced_refresh
     }
 
+    #[cfg(feature = "registry-node-signing")]
+    pub fn registry_require_signed_nodes(&self) -> Option<bool> {
+        self.registry_require_signed_nodes
+    }
+
     pub fn heartbeat(&self) -> Option<u64> {
         self.heartbeat
     }
@@ -359,6 +553,21 @@ impl PartialConfig {
         self.influx_password.clone()
     }
 
+    #[cfg(feature = "tap-backends")]
+    pub fn metrics_backend(&self) -> Option<String> {
+        self.metrics_backend.clone()
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn statsd_address(&self) -> Option<String> {
+        self.statsd_address.clone()
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn otlp_url(&self) -> Option<String> {
+        self.otlp_url.clone()
+    }
+
     pub fn peering_key(&self) -> Option<String> {
         self.peering_key.clone()
     }
@@ -384,6 +593,186 @@ impl PartialConfig {
         self.allow_keys_file.clone()
     }
 
+    #[cfg(feature = "rbac-bootstrap")]
+    pub fn rbac_bootstrap_file(&self) -> Option<String> {
+        self.rbac_bootstrap_file.clone()
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_limit_global(&self) -> Option<u64> {
+        self.bandwidth_limit_global
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn bandwidth_limit_per_peer(&self) -> Option<u64> {
+        self.bandwidth_limit_per_peer
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn auth_handshake_timeout(&self) -> Option<u64> {
+        self.auth_handshake_timeout
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn max_unauthorized_connections(&self) -> Option<usize> {
+        self.max_unauthorized_connections
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn connection_rate_limit(&self) -> Option<u32> {
+        self.connection_rate_limit
+    }
+
+    #[cfg(feature = "state-dir-health")]
+    pub fn state_dir_degraded_threshold(&self) -> Option<u8> {
+        self.state_dir_degraded_threshold
+    }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn challenge_endpoint_binding(&self) -> Option<EndpointBindingMode> {
+        self.challenge_endpoint_binding
+    }
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    pub fn admin_max_timeout(&self) -> Option<Duration> {
+        self.admin_max_timeout
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_timeout(&self) -> Option<Duration> {
+        self.admin_verification_timeout
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_retries(&self) -> Option<u32> {
+        self.admin_verification_retries
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn admin_verification_backoff(&self) -> Option<u32> {
+        self.admin_verification_backoff
+    }
+
+    #[cfg(feature = "health-service")]
+    pub fn health_check_interval(&self) -> Option<Duration> {
+        self.health_check_interval
+    }
+
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn max_message_size(&self) -> Option<u64> {
+        self.max_message_size
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn circuit_tombstones(&self) -> Option<bool> {
+        self.circuit_tombstones
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn tracing_otlp_url(&self) -> Option<String> {
+        self.tracing_otlp_url.clone()
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn registry_fetch_timeout(&self) -> Option<Duration> {
+        self.registry_fetch_timeout
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_proposal_rate_limit(&self) -> Option<u32> {
+        self.admin_proposal_rate_limit
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_pending_proposals(&self) -> Option<u32> {
+        self.admin_max_pending_proposals
+    }
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    pub fn scabbard_executor_pool_size(&self) -> Option<u32> {
+        self.scabbard_executor_pool_size
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_user_timeout(&self) -> Option<Duration> {
+        self.tcp_user_timeout
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    pub fn tcp_nodelay(&self) -> Option<bool> {
+        self.tcp_nodelay
+    }
+
+    #[cfg(feature = "rest-api-read-only")]
+    pub fn rest_api_read_only(&self) -> Option<bool> {
+        self.rest_api_read_only
+    }
+
+    #[cfg(feature = "tls-policy")]
+    pub fn tls_min_version(&self) -> Option<TlsVersion> {
+        self.tls_min_version
+    }
+
+    #[cfg(feature = "tls-policy")]
+    pub fn tls_cipher_suites(&self) -> Option<Vec<String>> {
+        self.tls_cipher_suites.clone()
+    }
+
+    #[cfg(feature = "store-schema-check")]
+    pub fn auto_migrate(&self) -> Option<bool> {
+        self.auto_migrate
+    }
+
+    #[cfg(feature = "store-lease")]
+    pub fn steal_lease(&self) -> Option<bool> {
+        self.steal_lease
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn circuit_access_log(&self) -> Option<Vec<String>> {
+        self.circuit_access_log.clone()
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn circuit_access_log_retention(&self) -> Option<u64> {
+        self.circuit_access_log_retention
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_retention_age(&self) -> Option<u64> {
+        self.admin_event_retention_age
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_retention_count(&self) -> Option<u64> {
+        self.admin_event_retention_count
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    pub fn preferred_address_family(&self) -> Option<String> {
+        self.preferred_address_family.clone()
+    }
+
+    #[cfg(feature = "rest-api-split")]
+    pub fn core_endpoint(&self) -> Option<String> {
+        self.core_endpoint.clone()
+    }
+
+    #[cfg(feature = "outbound-only")]
+    pub fn outbound_only(&self) -> Option<bool> {
+        self.outbound_only
+    }
+
+    #[cfg(feature = "authorization-handler-provider")]
+    pub fn authorization_handlers(&self) -> Option<Vec<String>> {
+        self.authorization_handlers.clone()
+    }
+
     pub fn scabbard_state(&self) -> Option<ScabbardState> {
         self.scabbard_state
     }
@@ -402,6 +791,11 @@ impl PartialConfig {
         self.lifecycle_executor_interval
     }
 
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn service_limits(&self) -> Option<HashMap<String, ServiceLimitsConfig>> {
+        self.service_limits.clone()
+    }
+
     /// Adds a `config_dir` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -584,6 +978,38 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    /// Adds a `rest_api_additional_endpoints` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_additional_endpoints` - Additional endpoints the REST API will be bound to,
+    ///   which require authorization.
+    ///
+    pub fn with_rest_api_additional_endpoints(
+        mut self,
+        rest_api_additional_endpoints: Option<Vec<String>>,
+    ) -> Self {
+        self.rest_api_additional_endpoints = rest_api_additional_endpoints;
+        self
+    }
+
+    #[cfg(feature = "rest-api-multi-bind")]
+    /// Adds a `rest_api_unauthenticated_endpoints` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_unauthenticated_endpoints` - Additional endpoints the REST API will be bound
+    ///   to, which do not require authorization.
+    ///
+    pub fn with_rest_api_unauthenticated_endpoints(
+        mut self,
+        rest_api_unauthenticated_endpoints: Option<Vec<String>>,
+    ) -> Self {
+        self.rest_api_unauthenticated_endpoints = rest_api_unauthenticated_endpoints;
+        self
+    }
+
     /// Adds a `database` value to the `PartialConfig` object, when the `database`
     /// feature flag is used.
     ///
@@ -631,6 +1057,22 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "registry-node-signing")]
+    /// Adds a `registry_require_signed_nodes` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_require_signed_nodes` - Only trust registry entries that were signed by the
+    ///   node itself when validating circuit proposals.
+    ///
+    pub fn with_registry_require_signed_nodes(
+        mut self,
+        registry_require_signed_nodes: Option<bool>,
+    ) -> Self {
+        self.registry_require_signed_nodes = registry_require_signed_nodes;
+        self
+    }
+
     /// Adds a `heartbeat` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -859,6 +1301,42 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "tap-backends")]
+    /// Adds a `metrics_backend` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics_backend` - The metrics backend to send metrics to: influx, statsd or otlp
+    ///
+    pub fn with_metrics_backend(mut self, metrics_backend: Option<String>) -> Self {
+        self.metrics_backend = metrics_backend;
+        self
+    }
+
+    #[cfg(feature = "tap-backends")]
+    /// Adds a `statsd_address` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `statsd_address` - The `host:port` address of the statsd daemon used for metrics
+    ///
+    pub fn with_statsd_address(mut self, statsd_address: Option<String>) -> Self {
+        self.statsd_address = statsd_address;
+        self
+    }
+
+    #[cfg(feature = "tap-backends")]
+    /// Adds a `otlp_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `otlp_url` - The URL of the OpenTelemetry collector used for metrics
+    ///
+    pub fn with_otlp_url(mut self, otlp_url: Option<String>) -> Self {
+        self.otlp_url = otlp_url;
+        self
+    }
+
     /// Adds an `peering_key` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -926,6 +1404,497 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "rbac-bootstrap")]
+    /// Adds a `rbac_bootstrap_file` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rbac_bootstrap_file` - File of roles and assignments to seed the RBAC store with
+    ///
+    pub fn with_rbac_bootstrap_file(mut self, rbac_bootstrap_file: Option<String>) -> Self {
+        self.rbac_bootstrap_file = rbac_bootstrap_file;
+        self
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    /// Adds a `bandwidth_limit_global` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `bandwidth_limit_global` - Maximum outbound bytes per second across all peers
+    ///
+    pub fn with_bandwidth_limit_global(mut self, bandwidth_limit_global: Option<u64>) -> Self {
+        self.bandwidth_limit_global = bandwidth_limit_global;
+        self
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    /// Adds a `bandwidth_limit_per_peer` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `bandwidth_limit_per_peer` - Maximum outbound bytes per second to any single peer
+    ///
+    pub fn with_bandwidth_limit_per_peer(mut self, bandwidth_limit_per_peer: Option<u64>) -> Self {
+        self.bandwidth_limit_per_peer = bandwidth_limit_per_peer;
+        self
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    /// Adds a `auth_handshake_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `auth_handshake_timeout` - Seconds a connection may take to complete authorization
+    ///
+    pub fn with_auth_handshake_timeout(mut self, auth_handshake_timeout: Option<u64>) -> Self {
+        self.auth_handshake_timeout = auth_handshake_timeout;
+        self
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    /// Adds a `max_unauthorized_connections` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_unauthorized_connections` - Maximum number of inbound connections waiting on authorization
+    ///
+    pub fn with_max_unauthorized_connections(
+        mut self,
+        max_unauthorized_connections: Option<usize>,
+    ) -> Self {
+        self.max_unauthorized_connections = max_unauthorized_connections;
+        self
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    /// Adds a `connection_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_rate_limit` - Maximum number of inbound connections per minute from one IP address
+    ///
+    pub fn with_connection_rate_limit(mut self, connection_rate_limit: Option<u32>) -> Self {
+        self.connection_rate_limit = connection_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "state-dir-health")]
+    /// Adds a `state_dir_degraded_threshold` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_dir_degraded_threshold` - Percentage of the state directory's volume in use at
+    ///    which the node reports degraded health
+    ///
+    pub fn with_state_dir_degraded_threshold(
+        mut self,
+        state_dir_degraded_threshold: Option<u8>,
+    ) -> Self {
+        self.state_dir_degraded_threshold = state_dir_degraded_threshold;
+        self
+    }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    /// Adds a `challenge_endpoint_binding` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge_endpoint_binding` - How challenge authorization binds signatures to the
+    ///    endpoint that was dialed
+    ///
+    pub fn with_challenge_endpoint_binding(
+        mut self,
+        challenge_endpoint_binding: Option<EndpointBindingMode>,
+    ) -> Self {
+        self.challenge_endpoint_binding = challenge_endpoint_binding;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    /// Adds an `admin_max_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The longest coordinator timeout, in seconds, that a circuit management
+    ///    payload may request for its proposal
+    ///
+    pub fn with_admin_max_timeout(mut self, timeout: Option<u64>) -> Self {
+        self.admin_max_timeout = timeout.map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    /// Adds an `admin_verification_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long, in seconds, the two-phase commit coordinator waits for
+    ///    verification responses before re-sending its verification request
+    ///
+    pub fn with_admin_verification_timeout(mut self, timeout: Option<u64>) -> Self {
+        self.admin_verification_timeout = timeout.map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    /// Adds an `admin_verification_retries` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `retries` - How many times the two-phase commit coordinator re-sends its verification
+    ///    request before rejecting the proposal
+    ///
+    pub fn with_admin_verification_retries(mut self, retries: Option<u32>) -> Self {
+        self.admin_verification_retries = retries;
+        self
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    /// Adds an `admin_verification_backoff` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `backoff` - The factor by which the two-phase commit coordinator multiplies its wait
+    ///    after each verification retry
+    ///
+    pub fn with_admin_verification_backoff(mut self, backoff: Option<u32>) -> Self {
+        self.admin_verification_backoff = backoff;
+        self
+    }
+
+    #[cfg(feature = "health-service")]
+    /// Adds a `health_check_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - How long, in seconds, the health monitor waits between runs of the node's
+    ///    health checks
+    ///
+    pub fn with_health_check_interval(mut self, interval: Option<u64>) -> Self {
+        self.health_check_interval = interval.map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "circuit-max-message-size")]
+    /// Adds a `max_message_size` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_message_size` - The largest service message payload, in bytes, that the node
+    ///    accepts on a circuit
+    ///
+    pub fn with_max_message_size(mut self, max_message_size: Option<u64>) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    /// Adds a `circuit_tombstones` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_tombstones` - Keep purged circuits in the admin store as tombstones instead of
+    ///    removing them
+    ///
+    pub fn with_circuit_tombstones(mut self, circuit_tombstones: Option<bool>) -> Self {
+        self.circuit_tombstones = circuit_tombstones;
+        self
+    }
+
+    #[cfg(feature = "tracing")]
+    /// Adds a `tracing_otlp_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tracing_otlp_url` - The URL of the OpenTelemetry collector that spans are exported to
+    ///
+    pub fn with_tracing_otlp_url(mut self, tracing_otlp_url: Option<String>) -> Self {
+        self.tracing_otlp_url = tracing_otlp_url;
+        self
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    /// Adds a `registry_fetch_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_fetch_timeout` - The time, in seconds, allowed for fetching a remote registry
+    ///    file; 0 uses the HTTP client's default
+    ///
+    pub fn with_registry_fetch_timeout(mut self, registry_fetch_timeout: Option<u64>) -> Self {
+        self.registry_fetch_timeout = registry_fetch_timeout
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    /// Adds a `admin_proposal_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_proposal_rate_limit` - The number of proposals each requester key may submit per
+    ///    minute; 0 means unlimited
+    ///
+    pub fn with_admin_proposal_rate_limit(
+        mut self,
+        admin_proposal_rate_limit: Option<u32>,
+    ) -> Self {
+        self.admin_proposal_rate_limit = admin_proposal_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    /// Adds a `admin_max_pending_proposals` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_max_pending_proposals` - The number of pending proposals at which new proposals
+    ///    are rejected; 0 means unlimited
+    ///
+    pub fn with_admin_max_pending_proposals(
+        mut self,
+        admin_max_pending_proposals: Option<u32>,
+    ) -> Self {
+        self.admin_max_pending_proposals = admin_max_pending_proposals;
+        self
+    }
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    /// Adds a `scabbard_executor_pool_size` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_executor_pool_size` - The number of threads shared by all scabbard services
+    ///    for executing batches; 0 means each service starts its own executor
+    ///
+    pub fn with_scabbard_executor_pool_size(
+        mut self,
+        scabbard_executor_pool_size: Option<u32>,
+    ) -> Self {
+        self.scabbard_executor_pool_size = scabbard_executor_pool_size;
+        self
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    /// Adds a `tcp_keepalive` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tcp_keepalive` - The time, in seconds, a connection may be idle before TCP keepalive
+    ///    probes are sent; 0 leaves keepalive disabled
+    ///
+    pub fn with_tcp_keepalive(mut self, tcp_keepalive: Option<u64>) -> Self {
+        self.tcp_keepalive = tcp_keepalive
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    /// Adds a `tcp_user_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tcp_user_timeout` - The time, in seconds, transmitted data may remain unacknowledged
+    ///    before a connection is closed; 0 leaves the operating system's default
+    ///
+    pub fn with_tcp_user_timeout(mut self, tcp_user_timeout: Option<u64>) -> Self {
+        self.tcp_user_timeout = tcp_user_timeout
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "transport-tcp-options")]
+    /// Adds a `tcp_nodelay` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tcp_nodelay` - Disable Nagle's algorithm on TCP connections
+    ///
+    pub fn with_tcp_nodelay(mut self, tcp_nodelay: Option<bool>) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    #[cfg(feature = "rest-api-read-only")]
+    /// Adds a `rest_api_read_only` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_read_only` - Serve only `GET` requests on the REST API, rejecting all
+    ///    mutating requests
+    ///
+    pub fn with_rest_api_read_only(mut self, rest_api_read_only: Option<bool>) -> Self {
+        self.rest_api_read_only = rest_api_read_only;
+        self
+    }
+
+    #[cfg(feature = "tls-policy")]
+    /// Adds a `tls_min_version` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_min_version` - The oldest TLS protocol version accepted on TLS connections
+    ///
+    pub fn with_tls_min_version(mut self, tls_min_version: Option<TlsVersion>) -> Self {
+        self.tls_min_version = tls_min_version;
+        self
+    }
+
+    #[cfg(feature = "tls-policy")]
+    /// Adds a `tls_cipher_suites` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_cipher_suites` - The cipher suites allowed on TLS connections
+    ///
+    pub fn with_tls_cipher_suites(mut self, tls_cipher_suites: Option<Vec<String>>) -> Self {
+        self.tls_cipher_suites = tls_cipher_suites;
+        self
+    }
+
+    #[cfg(feature = "store-schema-check")]
+    /// Adds an `auto_migrate` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `auto_migrate` - Apply pending database migrations at startup instead of failing
+    ///
+    pub fn with_auto_migrate(mut self, auto_migrate: Option<bool>) -> Self {
+        self.auto_migrate = auto_migrate;
+        self
+    }
+
+    #[cfg(feature = "store-lease")]
+    /// Adds a `steal_lease` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `steal_lease` - Take the database lease even if another live daemon holds it
+    ///
+    pub fn with_steal_lease(mut self, steal_lease: Option<bool>) -> Self {
+        self.steal_lease = steal_lease;
+        self
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    /// Adds a `circuit_access_log` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_access_log` - The circuits whose service payloads are recorded in the access
+    ///   log, or `*` for all circuits
+    ///
+    pub fn with_circuit_access_log(mut self, circuit_access_log: Option<Vec<String>>) -> Self {
+        self.circuit_access_log = circuit_access_log;
+        self
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    /// Adds a `circuit_access_log_retention` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_access_log_retention` - The number of days access records are kept
+    ///
+    pub fn with_circuit_access_log_retention(
+        mut self,
+        circuit_access_log_retention: Option<u64>,
+    ) -> Self {
+        self.circuit_access_log_retention = circuit_access_log_retention;
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    /// Adds an `admin_event_retention_age` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_event_retention_age` - The number of days admin service events are kept
+    ///
+    pub fn with_admin_event_retention_age(
+        mut self,
+        admin_event_retention_age: Option<u64>,
+    ) -> Self {
+        self.admin_event_retention_age = admin_event_retention_age;
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    /// Adds an `admin_event_retention_count` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_event_retention_count` - The maximum number of admin service events kept
+    ///
+    pub fn with_admin_event_retention_count(
+        mut self,
+        admin_event_retention_count: Option<u64>,
+    ) -> Self {
+        self.admin_event_retention_count = admin_event_retention_count;
+        self
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    /// Adds a `preferred_address_family` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `preferred_address_family` - The address family, `ipv4` or `ipv6`, whose peer
+    ///   endpoints are tried first
+    ///
+    pub fn with_preferred_address_family(
+        mut self,
+        preferred_address_family: Option<String>,
+    ) -> Self {
+        self.preferred_address_family = preferred_address_family;
+        self
+    }
+
+    #[cfg(feature = "rest-api-split")]
+    /// Adds a `core_endpoint` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `core_endpoint` - The REST API endpoint of the Splinter daemon that requests are
+    ///   forwarded to, when only the REST API is run
+    ///
+    pub fn with_core_endpoint(mut self, core_endpoint: Option<String>) -> Self {
+        self.core_endpoint = core_endpoint;
+        self
+    }
+
+    #[cfg(feature = "outbound-only")]
+    /// Adds an `outbound_only` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `outbound_only` - Run without network listeners, connecting to all peers outbound
+    ///
+    pub fn with_outbound_only(mut self, outbound_only: Option<bool>) -> Self {
+        self.outbound_only = outbound_only;
+        self
+    }
+
+    #[cfg(feature = "authorization-handler-provider")]
+    /// Adds an `authorization_handlers` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `authorization_handlers` - The names of the enabled REST API authorization handlers, in
+    ///   the order they are consulted
+    ///
+    pub fn with_authorization_handlers(
+        mut self,
+        authorization_handlers: Option<Vec<String>>,
+    ) -> Self {
+        self.authorization_handlers = authorization_handlers;
+        self
+    }
+
     /// Adds a `scabbard_state` value to the  `PartialConfig` object.
     ///
     /// # Arguments
@@ -962,4 +1931,19 @@ impl PartialConfig {
         self.lifecycle_executor_interval = lifecycle_executor_interval;
         self
     }
+
+    #[cfg(feature = "service-orchestrator-limits")]
+    /// Adds a `service_limits` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_limits` - The restart policy and resource limits, keyed by service type.
+    ///
+    pub fn with_service_limits(
+        mut self,
+        service_limits: Option<HashMap<String, ServiceLimitsConfig>>,
+    ) -> Self {
+        self.service_limits = service_limits;
+        self
+    }
 }
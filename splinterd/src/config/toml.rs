@@ -22,11 +22,13 @@ use serde::Deserialize as DeserializeTrait;
 use serde_derive::Deserialize;
 use std::collections::HashMap;
 use std::convert::TryInto;
-#[cfg(feature = "service2")]
+#[cfg(any(feature = "service2", feature = "service-orchestrator-limits"))]
 use std::time::Duration;
 
 use super::logging::{UnnamedAppenderConfig, UnnamedLoggerConfig};
 use super::ScabbardState;
+#[cfg(feature = "service-orchestrator-limits")]
+use super::ServiceLimitsConfig;
 
 /// `TOML_VERSION` represents the version of the toml config file.
 /// The version determines the most current valid toml config entries.
@@ -171,10 +173,16 @@ struct TomlConfig {
     node_id: Option<String>,
     display_name: Option<String>,
     rest_api_endpoint: Option<String>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_additional_endpoints: Option<Vec<String>>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_unauthenticated_endpoints: Option<Vec<String>>,
     database: Option<String>,
     registries: Option<Vec<String>>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-node-signing")]
+    registry_require_signed_nodes: Option<bool>,
     heartbeat: Option<u64>,
     admin_timeout: Option<u64>,
     version: Option<String>,
@@ -202,6 +210,12 @@ struct TomlConfig {
     influx_username: Option<String>,
     #[cfg(feature = "tap")]
     influx_password: Option<String>,
+    #[cfg(feature = "tap-backends")]
+    metrics_backend: Option<String>,
+    #[cfg(feature = "tap-backends")]
+    statsd_address: Option<String>,
+    #[cfg(feature = "tap-backends")]
+    otlp_url: Option<String>,
     peering_key: Option<String>,
     appenders: Option<HashMap<String, TomlUnnamedAppenderConfig>>,
     loggers: Option<HashMap<String, TomlUnnamedLoggerConfig>>,
@@ -210,10 +224,82 @@ struct TomlConfig {
     scabbard_enable_autocleanup: Option<bool>,
     config_dir: Option<String>,
     state_dir: Option<String>,
+    #[cfg(feature = "rbac-bootstrap")]
+    rbac_bootstrap_file: Option<String>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limit_global: Option<u64>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limit_per_peer: Option<u64>,
+    #[cfg(feature = "auth-handshake-limits")]
+    auth_handshake_timeout: Option<u64>,
+    #[cfg(feature = "auth-handshake-limits")]
+    max_unauthorized_connections: Option<usize>,
+    #[cfg(feature = "auth-handshake-limits")]
+    connection_rate_limit: Option<u32>,
+    #[cfg(feature = "state-dir-health")]
+    state_dir_degraded_threshold: Option<u8>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    challenge_endpoint_binding: Option<String>,
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    admin_max_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_retries: Option<u32>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_backoff: Option<u32>,
+    #[cfg(feature = "health-service")]
+    health_check_interval: Option<u64>,
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: Option<bool>,
+    #[cfg(feature = "tracing")]
+    tracing_otlp_url: Option<String>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    registry_fetch_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_proposal_rate_limit: Option<u32>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_pending_proposals: Option<u32>,
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    scabbard_executor_pool_size: Option<u32>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_keepalive: Option<u64>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_user_timeout: Option<u64>,
+    #[cfg(feature = "transport-tcp-options")]
+    tcp_nodelay: Option<bool>,
+    #[cfg(feature = "rest-api-read-only")]
+    rest_api_read_only: Option<bool>,
+    #[cfg(feature = "tls-policy")]
+    tls_min_version: Option<String>,
+    #[cfg(feature = "tls-policy")]
+    tls_cipher_suites: Option<Vec<String>>,
+    #[cfg(feature = "store-schema-check")]
+    auto_migrate: Option<bool>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log: Option<Vec<String>>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log_retention: Option<u64>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention_age: Option<u64>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention_count: Option<u64>,
+    #[cfg(feature = "transport-ipv6")]
+    preferred_address_family: Option<String>,
+    #[cfg(feature = "rest-api-split")]
+    core_endpoint: Option<String>,
+    #[cfg(feature = "outbound-only")]
+    outbound_only: Option<bool>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handlers: Option<Vec<String>>,
     #[cfg(feature = "service-timer-interval")]
     service_timer_interval: Option<u64>,
     #[cfg(feature = "lifecycle-executor-interval")]
     lifecycle_executor_interval: Option<u64>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_limits: Option<HashMap<String, TomlServiceLimits>>,
 
     // Deprecated values
     cert_dir: Option<String>,
@@ -319,6 +405,21 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
             partial_config = partial_config.with_service_endpoint(self.toml_config.service_endpoint)
         }
 
+        #[cfg(feature = "rest-api-multi-bind")]
+        {
+            partial_config = partial_config
+                .with_rest_api_additional_endpoints(self.toml_config.rest_api_additional_endpoints)
+                .with_rest_api_unauthenticated_endpoints(
+                    self.toml_config.rest_api_unauthenticated_endpoints,
+                );
+        }
+
+        #[cfg(feature = "registry-node-signing")]
+        {
+            partial_config = partial_config
+                .with_registry_require_signed_nodes(self.toml_config.registry_require_signed_nodes);
+        }
+
         #[cfg(feature = "rest-api-cors")]
         {
             partial_config = partial_config.with_allow_list(self.toml_config.allow_list);
@@ -345,6 +446,179 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
                 .with_influx_password(self.toml_config.influx_password)
         }
 
+        #[cfg(feature = "tap-backends")]
+        {
+            partial_config = partial_config
+                .with_metrics_backend(self.toml_config.metrics_backend)
+                .with_statsd_address(self.toml_config.statsd_address)
+                .with_otlp_url(self.toml_config.otlp_url);
+        }
+
+        #[cfg(feature = "rbac-bootstrap")]
+        {
+            partial_config =
+                partial_config.with_rbac_bootstrap_file(self.toml_config.rbac_bootstrap_file);
+        }
+
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        {
+            partial_config = partial_config
+                .with_bandwidth_limit_global(self.toml_config.bandwidth_limit_global)
+                .with_bandwidth_limit_per_peer(self.toml_config.bandwidth_limit_per_peer);
+        }
+
+        #[cfg(feature = "auth-handshake-limits")]
+        {
+            partial_config = partial_config
+                .with_auth_handshake_timeout(self.toml_config.auth_handshake_timeout)
+                .with_max_unauthorized_connections(self.toml_config.max_unauthorized_connections)
+                .with_connection_rate_limit(self.toml_config.connection_rate_limit);
+        }
+
+        #[cfg(feature = "state-dir-health")]
+        {
+            partial_config = partial_config
+                .with_state_dir_degraded_threshold(self.toml_config.state_dir_degraded_threshold);
+        }
+
+        #[cfg(feature = "challenge-endpoint-binding")]
+        {
+            partial_config = partial_config.with_challenge_endpoint_binding(
+                self.toml_config
+                    .challenge_endpoint_binding
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()
+                    .map_err(|err| ConfigError::InvalidArgument(format!("{}", err)))?,
+            );
+        }
+
+        #[cfg(feature = "admin-service-proposal-timeout")]
+        {
+            partial_config =
+                partial_config.with_admin_max_timeout(self.toml_config.admin_max_timeout);
+        }
+
+        #[cfg(feature = "admin-service-consensus-retry")]
+        {
+            partial_config = partial_config
+                .with_admin_verification_timeout(self.toml_config.admin_verification_timeout)
+                .with_admin_verification_retries(self.toml_config.admin_verification_retries)
+                .with_admin_verification_backoff(self.toml_config.admin_verification_backoff);
+        }
+
+        #[cfg(feature = "health-service")]
+        {
+            partial_config =
+                partial_config.with_health_check_interval(self.toml_config.health_check_interval);
+        }
+
+        #[cfg(feature = "circuit-max-message-size")]
+        {
+            partial_config =
+                partial_config.with_max_message_size(self.toml_config.max_message_size);
+        }
+
+        #[cfg(feature = "circuit-tombstones")]
+        {
+            partial_config =
+                partial_config.with_circuit_tombstones(self.toml_config.circuit_tombstones);
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            partial_config =
+                partial_config.with_tracing_otlp_url(self.toml_config.tracing_otlp_url);
+        }
+
+        #[cfg(feature = "registry-parallel-fetch")]
+        {
+            partial_config =
+                partial_config.with_registry_fetch_timeout(self.toml_config.registry_fetch_timeout);
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        {
+            partial_config = partial_config
+                .with_admin_proposal_rate_limit(self.toml_config.admin_proposal_rate_limit)
+                .with_admin_max_pending_proposals(self.toml_config.admin_max_pending_proposals);
+        }
+
+        #[cfg(feature = "scabbard-shared-executor-pool")]
+        {
+            partial_config = partial_config
+                .with_scabbard_executor_pool_size(self.toml_config.scabbard_executor_pool_size);
+        }
+
+        #[cfg(feature = "transport-tcp-options")]
+        {
+            partial_config = partial_config
+                .with_tcp_keepalive(self.toml_config.tcp_keepalive)
+                .with_tcp_user_timeout(self.toml_config.tcp_user_timeout)
+                .with_tcp_nodelay(self.toml_config.tcp_nodelay);
+        }
+
+        #[cfg(feature = "rest-api-read-only")]
+        {
+            partial_config =
+                partial_config.with_rest_api_read_only(self.toml_config.rest_api_read_only);
+        }
+
+        #[cfg(feature = "tls-policy")]
+        {
+            partial_config = partial_config
+                .with_tls_min_version(
+                    self.toml_config
+                        .tls_min_version
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .map_err(|err| ConfigError::InvalidArgument(format!("{}", err)))?,
+                )
+                .with_tls_cipher_suites(self.toml_config.tls_cipher_suites);
+        }
+
+        #[cfg(feature = "store-schema-check")]
+        {
+            partial_config = partial_config.with_auto_migrate(self.toml_config.auto_migrate);
+        }
+
+        #[cfg(feature = "circuit-access-log")]
+        {
+            partial_config = partial_config
+                .with_circuit_access_log(self.toml_config.circuit_access_log)
+                .with_circuit_access_log_retention(self.toml_config.circuit_access_log_retention);
+        }
+
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            partial_config = partial_config
+                .with_admin_event_retention_age(self.toml_config.admin_event_retention_age)
+                .with_admin_event_retention_count(self.toml_config.admin_event_retention_count);
+        }
+
+        #[cfg(feature = "transport-ipv6")]
+        {
+            partial_config = partial_config
+                .with_preferred_address_family(self.toml_config.preferred_address_family);
+        }
+
+        #[cfg(feature = "rest-api-split")]
+        {
+            partial_config = partial_config.with_core_endpoint(self.toml_config.core_endpoint);
+        }
+
+        #[cfg(feature = "outbound-only")]
+        {
+            partial_config = partial_config.with_outbound_only(self.toml_config.outbound_only);
+        }
+
+        #[cfg(feature = "authorization-handler-provider")]
+        {
+            partial_config =
+                partial_config.with_authorization_handlers(self.toml_config.authorization_handlers);
+        }
+
         #[cfg(feature = "service-timer-interval")]
         {
             partial_config = partial_config.with_service_timer_interval(
@@ -363,6 +637,18 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
             );
         }
 
+        #[cfg(feature = "service-orchestrator-limits")]
+        {
+            partial_config = partial_config.with_service_limits(
+                self.toml_config.service_limits.map(|service_limits| {
+                    service_limits
+                        .into_iter()
+                        .map(|(service_type, limits)| (service_type, limits.into()))
+                        .collect()
+                }),
+            );
+        }
+
         if let Some(mut loggers) = self.toml_config.loggers {
             if let Some(unnamed) = loggers.remove("root") {
                 partial_config = partial_config
@@ -450,6 +736,32 @@ pub enum ScabbardStateToml {
     Lmdb,
 }
 
+/// The restart policy and resource limits for a service type, defined in a
+/// `[service_limits.<service type>]` table.
+#[cfg(feature = "service-orchestrator-limits")]
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TomlServiceLimits {
+    max_restarts: Option<u32>,
+    restart_backoff: Option<u64>,
+    max_restart_backoff: Option<u64>,
+    max_message_queue: Option<usize>,
+    max_state_size: Option<u64>,
+}
+
+#[cfg(feature = "service-orchestrator-limits")]
+impl From<TomlServiceLimits> for ServiceLimitsConfig {
+    fn from(other: TomlServiceLimits) -> Self {
+        ServiceLimitsConfig {
+            max_restarts: other.max_restarts,
+            restart_backoff: other.restart_backoff.map(Duration::from_secs),
+            max_restart_backoff: other.max_restart_backoff.map(Duration::from_secs),
+            max_message_queue: other.max_message_queue,
+            max_state_size: other.max_state_size,
+        }
+    }
+}
+
 impl From<ScabbardStateToml> for ScabbardState {
     fn from(other: ScabbardStateToml) -> Self {
         match other {
@@ -916,4 +1228,48 @@ mod tests {
         assert!(matches!(appenders.get(0), Some(val) if val == "stdout"));
         assert!(matches!(appenders.get(1), Some(val) if val == "rolling_file"));
     }
+
+    #[cfg(feature = "service-orchestrator-limits")]
+    #[test]
+    /// Verify that `[service_limits.<service type>]` tables are read into the `PartialConfig`,
+    /// with the backoff values converted from seconds and unset values left as `None`.
+    fn test_service_limits_toml_config() {
+        let toml = TomlPartialConfigBuilder::new(
+            r#"
+            version = "1"
+            [service_limits.scabbard]
+            max_restarts = 3
+            restart_backoff = 2
+            max_restart_backoff = 30
+            max_message_queue = 64
+            max_state_size = 1048576
+            [service_limits.echo]
+            max_message_queue = 8
+            "#
+            .to_string(),
+            "fake_file_path".to_string(),
+        )
+        .expect("Could not deserialize toml")
+        .build()
+        .expect("A config error has occurred");
+
+        let service_limits = toml.service_limits().expect("service_limits not set");
+        assert_eq!(
+            service_limits.get("scabbard"),
+            Some(&ServiceLimitsConfig {
+                max_restarts: Some(3),
+                restart_backoff: Some(Duration::from_secs(2)),
+                max_restart_backoff: Some(Duration::from_secs(30)),
+                max_message_queue: Some(64),
+                max_state_size: Some(1_048_576),
+            })
+        );
+        assert_eq!(
+            service_limits.get("echo"),
+            Some(&ServiceLimitsConfig {
+                max_message_queue: Some(8),
+                ..Default::default()
+            })
+        );
+    }
 }
@@ -0,0 +1,326 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Providers for the built-in authorization handlers of the REST API, and the selection of the
+//! enabled handlers from the node's configuration.
+
+use std::collections::HashSet;
+
+use splinter::error::InternalError;
+#[cfg(feature = "authorization-handler-rbac")]
+use splinter::rbac::store::RoleBasedAuthorizationStore;
+#[cfg(feature = "authorization-handler-allow-keys")]
+use splinter::rest_api::auth::authorization::allow_keys::AllowKeysAuthorizationHandler;
+#[cfg(feature = "authorization-handler-maintenance")]
+use splinter::rest_api::auth::authorization::maintenance::MaintenanceModeAuthorizationHandler;
+#[cfg(feature = "authorization-handler-rbac")]
+use splinter::rest_api::auth::authorization::rbac::{
+    rest_api::RoleBasedAuthorizationResourceProvider, RoleBasedAuthorizationHandler,
+};
+use splinter::rest_api::auth::authorization::{AuthorizationHandler, AuthorizationHandlerProvider};
+use splinter::rest_api::Resource;
+#[cfg(any(
+    feature = "authorization-handler-maintenance",
+    feature = "authorization-handler-rbac"
+))]
+use splinter::rest_api::RestResourceProvider;
+
+use super::error::StartError;
+
+#[cfg(feature = "authorization-handler-allow-keys")]
+const ALLOW_KEYS: &str = "allow-keys";
+#[cfg(feature = "authorization-handler-maintenance")]
+const MAINTENANCE: &str = "maintenance";
+#[cfg(feature = "authorization-handler-rbac")]
+const RBAC: &str = "rbac";
+
+/// Provides the handler that permits the keys listed in the allow keys file.
+#[cfg(feature = "authorization-handler-allow-keys")]
+pub struct AllowKeysProvider {
+    allow_keys_path: String,
+}
+
+#[cfg(feature = "authorization-handler-allow-keys")]
+impl AllowKeysProvider {
+    pub fn new(allow_keys_path: String) -> Self {
+        Self { allow_keys_path }
+    }
+}
+
+#[cfg(feature = "authorization-handler-allow-keys")]
+impl AuthorizationHandlerProvider for AllowKeysProvider {
+    fn name(&self) -> &str {
+        ALLOW_KEYS
+    }
+
+    fn authorization_handler(&self) -> Result<Box<dyn AuthorizationHandler>, InternalError> {
+        debug!(
+            "Reading allow keys authorization handler file: {:?}",
+            self.allow_keys_path
+        );
+
+        Ok(Box::new(
+            AllowKeysAuthorizationHandler::new(&self.allow_keys_path).map_err(|err| {
+                InternalError::with_message(format!(
+                    "Failed to initialize allow keys authorization handler: {}",
+                    err
+                ))
+            })?,
+        ))
+    }
+}
+
+/// Provides the handler that denies writes while the node is in maintenance mode, along with the
+/// endpoint for toggling maintenance mode.
+#[cfg(feature = "authorization-handler-maintenance")]
+pub struct MaintenanceProvider {
+    handler: MaintenanceModeAuthorizationHandler,
+}
+
+#[cfg(feature = "authorization-handler-maintenance")]
+impl MaintenanceProvider {
+    pub fn new(handler: MaintenanceModeAuthorizationHandler) -> Self {
+        Self { handler }
+    }
+}
+
+#[cfg(feature = "authorization-handler-maintenance")]
+impl AuthorizationHandlerProvider for MaintenanceProvider {
+    fn name(&self) -> &str {
+        MAINTENANCE
+    }
+
+    fn authorization_handler(&self) -> Result<Box<dyn AuthorizationHandler>, InternalError> {
+        // The handler shares its maintenance mode state with its resources, so the same handler
+        // is used for both
+        Ok(Box::new(self.handler.clone()))
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.handler.resources()
+    }
+}
+
+/// Provides the role-based authorization handler, along with the endpoints for managing roles and
+/// assignments.
+#[cfg(feature = "authorization-handler-rbac")]
+pub struct RbacProvider {
+    rbac_store: Box<dyn RoleBasedAuthorizationStore>,
+}
+
+#[cfg(feature = "authorization-handler-rbac")]
+impl RbacProvider {
+    pub fn new(rbac_store: Box<dyn RoleBasedAuthorizationStore>) -> Self {
+        Self { rbac_store }
+    }
+}
+
+#[cfg(feature = "authorization-handler-rbac")]
+impl AuthorizationHandlerProvider for RbacProvider {
+    fn name(&self) -> &str {
+        RBAC
+    }
+
+    fn authorization_handler(&self) -> Result<Box<dyn AuthorizationHandler>, InternalError> {
+        Ok(Box::new(RoleBasedAuthorizationHandler::new(
+            self.rbac_store.clone(),
+        )))
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        RoleBasedAuthorizationResourceProvider::new(self.rbac_store.clone()).resources()
+    }
+}
+
+/// Selects the providers of the enabled authorization handlers, in the order the handlers are to
+/// be consulted.
+///
+/// If `enabled` is `None`, every provider is selected in the order given. Otherwise, only the
+/// named providers are selected, in the order they are named.
+///
+/// Returns an error if two providers have the same name, or if `enabled` names a provider that
+/// does not exist or names a provider more than once.
+pub fn select_providers<'a>(
+    providers: Vec<&'a dyn AuthorizationHandlerProvider>,
+    enabled: Option<&[String]>,
+) -> Result<Vec<&'a dyn AuthorizationHandlerProvider>, StartError> {
+    let mut names = HashSet::new();
+    for provider in &providers {
+        if !names.insert(provider.name().to_string()) {
+            return Err(StartError::RestApiError(format!(
+                "More than one authorization handler is named '{}'",
+                provider.name()
+            )));
+        }
+    }
+
+    let enabled = match enabled {
+        Some(enabled) => enabled,
+        None => return Ok(providers),
+    };
+
+    let mut selected = HashSet::new();
+    for name in enabled {
+        if !names.contains(name) {
+            return Err(StartError::UserError(format!(
+                "Unknown authorization handler '{}'; available handlers: {}",
+                name,
+                providers
+                    .iter()
+                    .map(|provider| provider.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        if !selected.insert(name.as_str()) {
+            return Err(StartError::UserError(format!(
+                "Authorization handler '{}' is enabled more than once",
+                name
+            )));
+        }
+    }
+
+    let mut providers = providers
+        .into_iter()
+        .filter(|provider| selected.contains(provider.name()))
+        .collect::<Vec<_>>();
+    providers.sort_by_key(|provider| {
+        enabled
+            .iter()
+            .position(|name| name == provider.name())
+            .unwrap_or(usize::MAX)
+    });
+
+    Ok(providers)
+}
+
+/// Creates the handlers of the given providers, in order, along with their resources.
+pub fn create_handlers(
+    providers: &[&dyn AuthorizationHandlerProvider],
+) -> Result<(Vec<Box<dyn AuthorizationHandler>>, Vec<Resource>), StartError> {
+    let mut handlers = Vec::with_capacity(providers.len());
+    let mut resources = vec![];
+    for provider in providers {
+        debug!("Enabling authorization handler: {}", provider.name());
+        handlers.push(provider.authorization_handler()?);
+        resources.append(&mut provider.resources());
+    }
+
+    if handlers.is_empty() {
+        warn!(
+            "No authorization handlers are enabled; all requests that require a permission \
+             will be denied"
+        );
+    }
+
+    Ok((handlers, resources))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use splinter::rest_api::auth::authorization::AuthorizationHandlerResult;
+    use splinter::rest_api::auth::identity::Identity;
+
+    #[derive(Clone)]
+    struct MockAuthorizationHandler;
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Continue)
+        }
+
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+
+    struct MockProvider(&'static str);
+
+    impl AuthorizationHandlerProvider for MockProvider {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn authorization_handler(&self) -> Result<Box<dyn AuthorizationHandler>, InternalError> {
+            Ok(Box::new(MockAuthorizationHandler))
+        }
+    }
+
+    fn providers(names: &[&'static str]) -> Vec<MockProvider> {
+        names.iter().map(|name| MockProvider(name)).collect()
+    }
+
+    fn refs(providers: &[MockProvider]) -> Vec<&dyn AuthorizationHandlerProvider> {
+        providers
+            .iter()
+            .map(|provider| provider as &dyn AuthorizationHandlerProvider)
+            .collect()
+    }
+
+    fn names<'a>(providers: &[&'a dyn AuthorizationHandlerProvider]) -> Vec<&'a str> {
+        providers.iter().map(|provider| provider.name()).collect()
+    }
+
+    /// Verifies that all providers are selected, in the order given, when the enabled handlers are
+    /// not configured.
+    #[test]
+    fn test_select_providers_default() {
+        let providers = providers(&["allow-keys", "maintenance", "rbac"]);
+        let selected =
+            select_providers(refs(&providers), None).expect("failed to select providers");
+
+        assert_eq!(names(&selected), vec!["allow-keys", "maintenance", "rbac"]);
+    }
+
+    /// Verifies that only the enabled providers are selected, in the configured order.
+    #[test]
+    fn test_select_providers_enabled_order() {
+        let providers = providers(&["allow-keys", "maintenance", "rbac", "custom"]);
+
+        let enabled = vec!["custom".to_string(), "rbac".to_string()];
+        let selected =
+            select_providers(refs(&providers), Some(&enabled)).expect("failed to select providers");
+        assert_eq!(names(&selected), vec!["custom", "rbac"]);
+
+        let selected =
+            select_providers(refs(&providers), Some(&[])).expect("failed to select providers");
+        assert!(selected.is_empty());
+    }
+
+    /// Verifies that unknown, repeated, and ambiguous handler names are rejected.
+    #[test]
+    fn test_select_providers_invalid() {
+        let unknown = vec!["rbac".to_string(), "ldap".to_string()];
+        assert!(matches!(
+            select_providers(refs(&providers(&["allow-keys", "rbac"])), Some(&unknown)),
+            Err(StartError::UserError(_))
+        ));
+
+        let repeated = vec!["rbac".to_string(), "rbac".to_string()];
+        assert!(matches!(
+            select_providers(refs(&providers(&["allow-keys", "rbac"])), Some(&repeated)),
+            Err(StartError::UserError(_))
+        ));
+
+        assert!(matches!(
+            select_providers(refs(&providers(&["rbac", "rbac"])), None),
+            Err(StartError::RestApiError(_))
+        ));
+    }
+}
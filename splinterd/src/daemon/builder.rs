@@ -12,13 +12,47 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "service-orchestrator-limits")]
+use std::collections::HashMap;
 use std::time::Duration;
 
 use cylinder::Signer;
+#[cfg(feature = "admin-service-proposal-limits")]
+use splinter::admin::service::ProposalLimits;
+#[cfg(feature = "admin-service-event-retention")]
+use splinter::admin::store::EventRetention;
+#[cfg(feature = "biome-notifications")]
+use splinter::biome::notifications::BiomeNotifier;
+#[cfg(feature = "admin-service-consensus-retry")]
+use splinter::consensus::two_phase::v1::VerificationRetry;
+#[cfg(feature = "health-service")]
+use splinter::health;
+#[cfg(feature = "mesh-bandwidth-throttle")]
+use splinter::mesh::BandwidthLimits;
 use splinter::mesh::Mesh;
+#[cfg(feature = "challenge-endpoint-binding")]
+use splinter::network::auth::EndpointBindingMode;
+#[cfg(feature = "auth-handshake-limits")]
+use splinter::network::connection_manager::InboundConnectionLimits;
 use splinter::peer::PeerAuthorizationToken;
+#[cfg(feature = "authorization-handler-provider")]
+use splinter::rest_api::auth::authorization::AuthorizationHandlerProvider;
+#[cfg(feature = "service-orchestrator-limits")]
+use splinter::runtime::service::instance::{ResourceLimits, RestartPolicy};
+#[cfg(feature = "metrics-snapshot")]
+use splinter::tap::snapshot::MetricsSnapshot;
+#[cfg(feature = "tap-backends")]
+use splinter::tap::GlobalLabels;
+#[cfg(feature = "transport-ipv6")]
+use splinter::transport::address::AddressFamily;
+#[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+use splinter::transport::tls::TlsPolicy;
+#[cfg(feature = "state-dir-health")]
+use splinter_rest_api_actix_web_1::status;
 
 use crate::daemon::error::CreateError;
+#[cfg(feature = "config-reload")]
+use crate::daemon::reload::ConfigReload;
 use crate::daemon::SplinterDaemon;
 
 #[derive(Default)]
@@ -42,8 +76,14 @@ pub struct SplinterDaemonBuilder {
     registries: Vec<String>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-node-signing")]
+    registry_require_signed_nodes: bool,
     heartbeat: Option<u64>,
     admin_timeout: Duration,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_additional_endpoints: Vec<String>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_unauthenticated_endpoints: Vec<String>,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
     #[cfg(feature = "biome-credentials")]
@@ -71,6 +111,74 @@ pub struct SplinterDaemonBuilder {
     service_timer_interval: Option<Duration>,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Option<Duration>,
+    #[cfg(feature = "rbac-bootstrap")]
+    rbac_bootstrap_file: Option<String>,
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    bandwidth_limits: BandwidthLimits,
+    #[cfg(feature = "auth-handshake-limits")]
+    auth_handshake_timeout: Option<Duration>,
+    #[cfg(feature = "auth-handshake-limits")]
+    inbound_connection_limits: InboundConnectionLimits,
+    #[cfg(feature = "state-dir-health")]
+    state_dir_degraded_threshold: Option<u8>,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    challenge_endpoint_binding: Option<EndpointBindingMode>,
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    admin_max_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_retries: Option<u32>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_backoff: Option<u32>,
+    #[cfg(feature = "health-service")]
+    health_check_interval: Option<Duration>,
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: bool,
+    #[cfg(feature = "tracing")]
+    tracing_otlp_url: Option<String>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    registry_fetch_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_proposal_limits: Option<ProposalLimits>,
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    scabbard_executor_pool_size: Option<usize>,
+    #[cfg(feature = "rest-api-read-only")]
+    rest_api_read_only: bool,
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    tls_policy: TlsPolicy,
+    #[cfg(feature = "tap-backends")]
+    metrics_labels: GlobalLabels,
+    #[cfg(feature = "metrics-snapshot")]
+    metrics_snapshot: Option<MetricsSnapshot>,
+    #[cfg(feature = "store-schema-check")]
+    auto_migrate: bool,
+    #[cfg(feature = "store-lease")]
+    steal_lease: bool,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log: Vec<String>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log_retention: Option<Duration>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention: EventRetention,
+    #[cfg(feature = "transport-ipv6")]
+    preferred_address_family: Option<AddressFamily>,
+    #[cfg(feature = "rest-api-split")]
+    core_endpoint: Option<String>,
+    #[cfg(feature = "outbound-only")]
+    outbound_only: bool,
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_restart_policies: HashMap<String, RestartPolicy>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_resource_limits: HashMap<String, ResourceLimits>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handlers: Option<Vec<String>>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handler_providers: Vec<Box<dyn AuthorizationHandlerProvider>>,
+    #[cfg(feature = "config-reload")]
+    config_reload: Option<ConfigReload>,
 }
 
 impl SplinterDaemonBuilder {
@@ -157,6 +265,12 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "registry-node-signing")]
+    pub fn with_registry_require_signed_nodes(mut self, value: bool) -> Self {
+        self.registry_require_signed_nodes = value;
+        self
+    }
+
     pub fn with_heartbeat(mut self, value: u64) -> Self {
         self.heartbeat = Some(value);
         self
@@ -167,6 +281,18 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn with_rest_api_additional_endpoints(mut self, value: Vec<String>) -> Self {
+        self.rest_api_additional_endpoints = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-multi-bind")]
+    pub fn with_rest_api_unauthenticated_endpoints(mut self, value: Vec<String>) -> Self {
+        self.rest_api_unauthenticated_endpoints = value;
+        self
+    }
+
     #[allow(dead_code)]
     #[cfg(feature = "rest-api-cors")]
     #[deprecated(since = "0.7.0", note = "please use `with_allow_list` instead")]
@@ -269,12 +395,242 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "rbac-bootstrap")]
+    pub fn with_rbac_bootstrap_file(mut self, value: Option<String>) -> Self {
+        self.rbac_bootstrap_file = value;
+        self
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    pub fn with_bandwidth_limits(
+        mut self,
+        global: Option<u64>,
+        per_connection: Option<u64>,
+    ) -> Self {
+        self.bandwidth_limits = BandwidthLimits {
+            global,
+            per_connection,
+        };
+        self
+    }
+
+    #[cfg(feature = "tap-backends")]
+    pub fn with_metrics_labels(mut self, value: GlobalLabels) -> Self {
+        self.metrics_labels = value;
+        self
+    }
+
+    #[cfg(feature = "metrics-snapshot")]
+    pub fn with_metrics_snapshot(mut self, value: MetricsSnapshot) -> Self {
+        self.metrics_snapshot = Some(value);
+        self
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn with_auth_handshake_timeout(mut self, value: Option<Duration>) -> Self {
+        self.auth_handshake_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    pub fn with_inbound_connection_limits(
+        mut self,
+        max_unauthorized_connections: Option<usize>,
+        max_connections_per_ip_per_minute: Option<u32>,
+    ) -> Self {
+        self.inbound_connection_limits = InboundConnectionLimits {
+            max_unauthorized_connections,
+            max_connections_per_ip_per_minute,
+        };
+        self
+    }
+
+    #[cfg(feature = "state-dir-health")]
+    pub fn with_state_dir_degraded_threshold(mut self, value: Option<u8>) -> Self {
+        self.state_dir_degraded_threshold = value;
+        self
+    }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    pub fn with_challenge_endpoint_binding(mut self, value: Option<EndpointBindingMode>) -> Self {
+        self.challenge_endpoint_binding = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    pub fn with_admin_max_timeout(mut self, value: Option<Duration>) -> Self {
+        self.admin_max_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn with_admin_verification_timeout(mut self, value: Option<Duration>) -> Self {
+        self.admin_verification_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn with_admin_verification_retries(mut self, value: Option<u32>) -> Self {
+        self.admin_verification_retries = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    pub fn with_admin_verification_backoff(mut self, value: Option<u32>) -> Self {
+        self.admin_verification_backoff = value;
+        self
+    }
+
+    #[cfg(feature = "health-service")]
+    pub fn with_health_check_interval(mut self, value: Option<Duration>) -> Self {
+        self.health_check_interval = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-max-message-size")]
+    pub fn with_max_message_size(mut self, value: Option<u64>) -> Self {
+        self.max_message_size = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    pub fn with_circuit_tombstones(mut self, value: bool) -> Self {
+        self.circuit_tombstones = value;
+        self
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn with_tracing_otlp_url(mut self, value: Option<String>) -> Self {
+        self.tracing_otlp_url = value;
+        self
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    pub fn with_registry_fetch_timeout(mut self, value: Option<Duration>) -> Self {
+        self.registry_fetch_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_admin_proposal_limits(mut self, value: Option<ProposalLimits>) -> Self {
+        self.admin_proposal_limits = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    pub fn with_scabbard_executor_pool_size(mut self, value: Option<usize>) -> Self {
+        self.scabbard_executor_pool_size = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-read-only")]
+    pub fn with_rest_api_read_only(mut self, value: bool) -> Self {
+        self.rest_api_read_only = value;
+        self
+    }
+
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    pub fn with_tls_policy(mut self, value: TlsPolicy) -> Self {
+        self.tls_policy = value;
+        self
+    }
+
+    #[cfg(feature = "store-schema-check")]
+    pub fn with_auto_migrate(mut self, value: bool) -> Self {
+        self.auto_migrate = value;
+        self
+    }
+
+    #[cfg(feature = "store-lease")]
+    pub fn with_steal_lease(mut self, value: bool) -> Self {
+        self.steal_lease = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn with_circuit_access_log(mut self, value: Vec<String>) -> Self {
+        self.circuit_access_log = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    pub fn with_circuit_access_log_retention(mut self, value: Option<Duration>) -> Self {
+        self.circuit_access_log_retention = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn with_admin_event_retention(mut self, value: EventRetention) -> Self {
+        self.admin_event_retention = value;
+        self
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    pub fn with_preferred_address_family(mut self, value: Option<AddressFamily>) -> Self {
+        self.preferred_address_family = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-split")]
+    pub fn with_core_endpoint(mut self, value: Option<String>) -> Self {
+        self.core_endpoint = value;
+        self
+    }
+
+    #[cfg(feature = "outbound-only")]
+    pub fn with_outbound_only(mut self, value: bool) -> Self {
+        self.outbound_only = value;
+        self
+    }
+
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn with_service_restart_policies(mut self, value: HashMap<String, RestartPolicy>) -> Self {
+        self.service_restart_policies = value;
+        self
+    }
+
+    #[cfg(feature = "service-orchestrator-limits")]
+    pub fn with_service_resource_limits(mut self, value: HashMap<String, ResourceLimits>) -> Self {
+        self.service_resource_limits = value;
+        self
+    }
+
+    /// Sets the names of the enabled authorization handlers, in the order they are consulted. If
+    /// not set, all built-in and added handlers are enabled.
+    #[cfg(feature = "authorization-handler-provider")]
+    pub fn with_authorization_handlers(mut self, value: Option<Vec<String>>) -> Self {
+        self.authorization_handlers = value;
+        self
+    }
+
+    /// Adds a provider for a custom authorization handler, which can be enabled by name alongside
+    /// the built-in handlers. The provider's handler is created when the REST API is started.
+    #[allow(dead_code)]
+    #[cfg(feature = "authorization-handler-provider")]
+    pub fn add_authorization_handler_provider(
+        mut self,
+        provider: Box<dyn AuthorizationHandlerProvider>,
+    ) -> Self {
+        self.authorization_handler_providers.push(provider);
+        self
+    }
+
+    /// Sets the reloader used to apply configuration changes when the daemon receives `SIGHUP`.
+    /// If not set, the configuration is only read on startup.
+    #[cfg(feature = "config-reload")]
+    pub fn with_config_reload(mut self, value: ConfigReload) -> Self {
+        self.config_reload = Some(value);
+        self
+    }
+
     pub fn build(self) -> Result<SplinterDaemon, CreateError> {
         let heartbeat = self.heartbeat.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: heartbeat".to_string())
         })?;
 
         let mesh = Mesh::new(512, 128);
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        mesh.bandwidth_throttle().set_limits(self.bandwidth_limits);
 
         #[cfg(feature = "authorization-handler-allow-keys")]
         let config_dir = self.config_dir.ok_or_else(|| {
@@ -361,6 +717,25 @@ impl SplinterDaemonBuilder {
             CreateError::MissingRequiredField("Missing field: enable_biome_credentials".to_string())
         })?;
 
+        // Verification requests are only retried by default if one of the options is configured;
+        // the verification timeout defaults to the admin timeout
+        #[cfg(feature = "admin-service-consensus-retry")]
+        let admin_verification_retry = if self.admin_verification_timeout.is_some()
+            || self.admin_verification_retries.is_some()
+            || self.admin_verification_backoff.is_some()
+        {
+            Some(
+                VerificationRetry::new(
+                    self.admin_verification_timeout
+                        .unwrap_or(self.admin_timeout),
+                )
+                .with_retries(self.admin_verification_retries.unwrap_or(0))
+                .with_backoff(self.admin_verification_backoff.unwrap_or(1)),
+            )
+        } else {
+            None
+        };
+
         let strict_ref_counts = self.strict_ref_counts.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: strict_ref_counts".to_string())
         })?;
@@ -404,7 +779,13 @@ impl SplinterDaemonBuilder {
             registries: self.registries,
             registry_auto_refresh,
             registry_forced_refresh,
+            #[cfg(feature = "registry-node-signing")]
+            registry_require_signed_nodes: self.registry_require_signed_nodes,
             admin_timeout: self.admin_timeout,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_additional_endpoints: self.rest_api_additional_endpoints,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_unauthenticated_endpoints: self.rest_api_unauthenticated_endpoints,
             #[cfg(feature = "rest-api-cors")]
             allow_list: self.allow_list,
             #[cfg(feature = "biome-credentials")]
@@ -433,6 +814,74 @@ impl SplinterDaemonBuilder {
             service_timer_interval,
             #[cfg(feature = "service2")]
             lifecycle_executor_interval,
+            #[cfg(feature = "rbac-bootstrap")]
+            rbac_bootstrap_file: self.rbac_bootstrap_file,
+            #[cfg(feature = "auth-handshake-limits")]
+            auth_handshake_timeout: self.auth_handshake_timeout,
+            #[cfg(feature = "auth-handshake-limits")]
+            inbound_connection_limits: self.inbound_connection_limits,
+            #[cfg(feature = "state-dir-health")]
+            state_dir_degraded_threshold: self
+                .state_dir_degraded_threshold
+                .unwrap_or(status::DEFAULT_DEGRADED_THRESHOLD_PERCENT),
+            #[cfg(feature = "challenge-endpoint-binding")]
+            challenge_endpoint_binding: self.challenge_endpoint_binding.unwrap_or_default(),
+            #[cfg(feature = "admin-service-proposal-timeout")]
+            admin_max_timeout: self.admin_max_timeout,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_retry,
+            #[cfg(feature = "health-service")]
+            health_check_interval: self
+                .health_check_interval
+                .unwrap_or(health::DEFAULT_CHECK_INTERVAL),
+            #[cfg(feature = "circuit-max-message-size")]
+            max_message_size: self.max_message_size,
+            #[cfg(feature = "circuit-tombstones")]
+            circuit_tombstones: self.circuit_tombstones,
+            #[cfg(feature = "tracing")]
+            tracing_otlp_url: self.tracing_otlp_url,
+            #[cfg(feature = "registry-parallel-fetch")]
+            registry_fetch_timeout: self.registry_fetch_timeout,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_proposal_limits: self.admin_proposal_limits,
+            #[cfg(feature = "scabbard-shared-executor-pool")]
+            scabbard_executor_pool_size: self.scabbard_executor_pool_size,
+            #[cfg(feature = "rest-api-read-only")]
+            rest_api_read_only: self.rest_api_read_only,
+            #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+            tls_policy: self.tls_policy,
+            #[cfg(feature = "tap-backends")]
+            metrics_labels: self.metrics_labels,
+            #[cfg(feature = "metrics-snapshot")]
+            metrics_snapshot: self.metrics_snapshot,
+            #[cfg(feature = "store-schema-check")]
+            auto_migrate: self.auto_migrate,
+            #[cfg(feature = "store-lease")]
+            steal_lease: self.steal_lease,
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log: self.circuit_access_log,
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log_retention: self.circuit_access_log_retention,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention: self.admin_event_retention,
+            #[cfg(feature = "transport-ipv6")]
+            preferred_address_family: self.preferred_address_family,
+            #[cfg(feature = "rest-api-split")]
+            core_endpoint: self.core_endpoint,
+            #[cfg(feature = "outbound-only")]
+            outbound_only: self.outbound_only,
+            #[cfg(feature = "service-orchestrator-limits")]
+            service_restart_policies: self.service_restart_policies,
+            #[cfg(feature = "service-orchestrator-limits")]
+            service_resource_limits: self.service_resource_limits,
+            #[cfg(feature = "authorization-handler-provider")]
+            authorization_handlers: self.authorization_handlers,
+            #[cfg(feature = "authorization-handler-provider")]
+            authorization_handler_providers: self.authorization_handler_providers,
+            #[cfg(feature = "config-reload")]
+            config_reload: self.config_reload,
+            #[cfg(feature = "biome-notifications")]
+            biome_notifier: BiomeNotifier::new(),
         })
     }
 }
@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "authorization-handler-provider")]
+mod authorization;
 pub mod builder;
 mod error;
 #[cfg(feature = "service2")]
 mod lifecycle;
 mod registry;
+#[cfg(feature = "config-reload")]
+pub mod reload;
+#[cfg(feature = "rest-api-split")]
+mod rest_api_only;
 mod store;
 #[cfg(feature = "service2")]
 mod timer;
@@ -31,7 +37,7 @@ use std::sync::{mpsc::channel, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use cylinder::{secp256k1::Secp256k1Context, Signer, SigningError, VerifierFactory};
+use cylinder::{secp256k1::Secp256k1Context, Signer, SigningError, Verifier, VerifierFactory};
 #[cfg(feature = "scabbardv3")]
 use scabbard::service::v3::{ScabbardMessageByteConverter, ScabbardMessageHandlerFactory};
 use scabbard::service::ScabbardArgValidator;
@@ -39,28 +45,65 @@ use scabbard::service::ScabbardFactoryBuilder;
 #[cfg(feature = "service2")]
 use splinter::admin::lifecycle::sync::SyncLifecycleInterface;
 use splinter::admin::lifecycle::LifecycleDispatch;
-use splinter::admin::service::{admin_service_id, AdminService, AdminServiceBuilder};
+#[cfg(feature = "admin-service-proposal-limits")]
+use splinter::admin::service::ProposalLimits;
+#[cfg(feature = "registry-node-signing")]
+use splinter::admin::service::SignedRegistryKeyVerifier;
+use splinter::admin::service::{
+    admin_service_id, AdminKeyVerifier, AdminService, AdminServiceBuilder,
+};
+#[cfg(feature = "admin-service-event-retention")]
+use splinter::admin::store::{EventPruner, EventRetention};
 #[cfg(feature = "biome-credentials")]
 use splinter::biome::credentials::rest_api::BiomeCredentialsRestResourceProviderBuilder;
+#[cfg(all(
+    feature = "biome-notifications",
+    feature = "authorization-handler-rbac"
+))]
+use splinter::biome::notifications::NotifyingRoleBasedAuthorizationStore;
+#[cfg(feature = "biome-notifications")]
+use splinter::biome::notifications::{BiomeNotifier, NotifyingKeyStore};
 #[cfg(feature = "biome-profile")]
 use splinter::biome::profile::rest_api::BiomeProfileRestResourceProvider;
+#[cfg(feature = "biome-key-management")]
+use splinter::biome::KeyStore;
+#[cfg(feature = "circuit-access-log")]
+use splinter::circuit::access_log::{AccessLogCircuits, AccessLogRecorder, AccessLogger};
 use splinter::circuit::handlers::{
     AdminDirectMessageHandler, CircuitDirectMessageHandler, CircuitErrorHandler,
     CircuitMessageHandler, ServiceConnectRequestHandler, ServiceDisconnectRequestHandler,
 };
 use splinter::circuit::routing::{memory::RoutingTable, RoutingTableReader, RoutingTableWriter};
+#[cfg(feature = "admin-service-consensus-retry")]
+use splinter::consensus::two_phase::v1::VerificationRetry;
 #[cfg(feature = "service2")]
 use splinter::error::InternalError;
+#[cfg(feature = "health-service")]
+use splinter::health::{
+    DiskSpaceCheck, HealthCheck, HealthMonitor, PeerCheck, RestApiCheck, StoreCheck,
+};
 use splinter::keys::insecure::AllowAllKeyPermissionManager;
 use splinter::mesh::Mesh;
+#[cfg(feature = "network-audit")]
+use splinter::network::audit::AuditLogger;
 use splinter::network::auth::AuthorizationManager;
+#[cfg(feature = "challenge-trust-policy")]
+use splinter::network::auth::ChallengeTrustPolicy;
+#[cfg(feature = "challenge-endpoint-binding")]
+use splinter::network::auth::EndpointBindingMode;
+#[cfg(feature = "auth-handshake-limits")]
+use splinter::network::connection_manager::InboundConnectionLimits;
 use splinter::network::connection_manager::{
     authorizers::Authorizers, authorizers::InprocAuthorizer, ConnectionManager, Connector,
 };
+#[cfg(feature = "dispatch-priority-lanes")]
+use splinter::network::dispatch::MessagePriority;
 use splinter::network::dispatch::{
     dispatch_channel, DispatchLoopBuilder, DispatchMessageSender, Dispatcher,
 };
 use splinter::network::handlers::{NetworkEchoHandler, NetworkHeartbeatHandler};
+#[cfg(feature = "peer-ping")]
+use splinter::network::ping::{NetworkPingHandler, PeerPinger};
 use splinter::peer::interconnect::NetworkMessageSender;
 use splinter::peer::interconnect::PeerInterconnectBuilder;
 use splinter::peer::PeerAuthorizationToken;
@@ -68,26 +111,52 @@ use splinter::peer::PeerManager;
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
 use splinter::public_key::PublicKey;
+#[cfg(feature = "rbac-bootstrap")]
+use splinter::rbac::bootstrap::RbacBootstrap;
+#[cfg(all(
+    feature = "biome-notifications",
+    feature = "authorization-handler-rbac"
+))]
+use splinter::rbac::store::RoleBasedAuthorizationStore;
+#[cfg(feature = "registry-sources")]
+use splinter::registry::sources::RegistrySources;
 use splinter::registry::{
     LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RwRegistry, UnifiedRegistry,
 };
-#[cfg(feature = "authorization-handler-allow-keys")]
+#[cfg(feature = "registry-parallel-fetch")]
+use splinter::registry::{RegistryError, RemoteYamlFetchOptions};
+#[cfg(all(
+    feature = "authorization-handler-allow-keys",
+    not(feature = "authorization-handler-provider")
+))]
 use splinter::rest_api::auth::authorization::allow_keys::AllowKeysAuthorizationHandler;
 #[cfg(feature = "authorization-handler-maintenance")]
 use splinter::rest_api::auth::authorization::maintenance::MaintenanceModeAuthorizationHandler;
-#[cfg(feature = "authorization-handler-rbac")]
+#[cfg(all(
+    feature = "authorization-handler-rbac",
+    not(feature = "authorization-handler-provider")
+))]
 use splinter::rest_api::auth::authorization::rbac::{
     rest_api::RoleBasedAuthorizationResourceProvider, RoleBasedAuthorizationHandler,
 };
-#[cfg(any(
-    feature = "authorization-handler-rbac",
-    feature = "authorization-handler-maintenance",
-    feature = "authorization-handler-allow-keys"
+#[cfg(all(
+    any(
+        feature = "authorization-handler-rbac",
+        feature = "authorization-handler-maintenance",
+        feature = "authorization-handler-allow-keys"
+    ),
+    not(feature = "authorization-handler-provider")
 ))]
 use splinter::rest_api::auth::authorization::AuthorizationHandler;
+#[cfg(feature = "authorization-handler-provider")]
+use splinter::rest_api::auth::authorization::AuthorizationHandlerProvider;
+#[cfg(feature = "rest-api-multi-bind")]
+use splinter::rest_api::BindAuth;
 #[cfg(feature = "oauth")]
 use splinter::rest_api::OAuthConfig;
 use splinter::rest_api::{AuthConfig, RestApiBuilder, RestResourceProvider};
+#[cfg(feature = "service-orchestrator-limits")]
+use splinter::runtime::service::instance::{ResourceLimits, RestartPolicy};
 use splinter::runtime::service::instance::{
     ServiceOrchestratorBuilder, ServiceProcessor, ServiceProcessorShutdownHandle,
 };
@@ -99,18 +168,57 @@ use splinter::runtime::service::{
 use splinter::service::instance::ServiceArgValidator;
 #[cfg(any(feature = "scabbardv3", feature = "service-echo"))]
 use splinter::service::{MessageHandler, MessageHandlerFactory, ServiceType};
+#[cfg(feature = "store-lease")]
+use splinter::store::lease::{LeaseError, LeaseHeartbeat};
+use splinter::store::StoreFactory;
+#[cfg(feature = "metrics-snapshot")]
+use splinter::tap::snapshot::MetricsSnapshot;
+#[cfg(feature = "tap-backends")]
+use splinter::tap::GlobalLabels;
 use splinter::threading::lifecycle::ShutdownHandle;
+#[cfg(feature = "tracing")]
+use splinter::tracing::{init_tracer, otlp::OtlpSpanExporter};
+#[cfg(feature = "transport-ipv6")]
+use splinter::transport::address::AddressFamily;
+#[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+use splinter::transport::tls::TlsPolicy;
 use splinter::transport::{
     inproc::InprocTransport, multi::MultiTransport, AcceptError, Connection, Incoming, Listener,
     Transport,
 };
 #[cfg(feature = "service-echo")]
 use splinter_echo::service::{EchoMessageByteConverter, EchoMessageHandlerFactory};
+#[cfg(feature = "service-relay")]
+use splinter_relay::service::{RelayArgValidator, RelayServiceFactory};
 use splinter_rest_api_actix_web_1::admin::{AdminServiceRestProvider, CircuitResourceProvider};
 #[cfg(feature = "biome-key-management")]
 use splinter_rest_api_actix_web_1::biome::key_management::BiomeKeyManagementRestResourceProvider;
+#[cfg(feature = "biome-notifications")]
+use splinter_rest_api_actix_web_1::biome::notifications::BiomeNotificationsRestResourceProvider;
+#[cfg(feature = "circuit-access-log")]
+use splinter_rest_api_actix_web_1::circuit_access_log::CircuitAccessLogResourceProvider;
+#[cfg(feature = "service-echo")]
+use splinter_rest_api_actix_web_1::echo::EchoStatsResourceProvider;
+#[cfg(feature = "health-service")]
+use splinter_rest_api_actix_web_1::health::HealthResourceProvider;
+#[cfg(feature = "metrics-snapshot")]
+use splinter_rest_api_actix_web_1::metrics::MetricsResourceProvider;
+#[cfg(feature = "network-audit")]
+use splinter_rest_api_actix_web_1::network::NetworkAuditResourceProvider;
+#[cfg(any(
+    feature = "peer-disconnect",
+    feature = "peer-endpoint-pin",
+    feature = "peer-ref-diagnostics"
+))]
+use splinter_rest_api_actix_web_1::network::NetworkPeersResourceProvider;
+#[cfg(feature = "peer-ping")]
+use splinter_rest_api_actix_web_1::network::NetworkPingResourceProvider;
+#[cfg(feature = "mesh-bandwidth-throttle")]
+use splinter_rest_api_actix_web_1::network::NetworkThrottleResourceProvider;
 use splinter_rest_api_actix_web_1::open_api;
 use splinter_rest_api_actix_web_1::registry::RwRegistryRestResourceProvider;
+#[cfg(feature = "service-relay")]
+use splinter_rest_api_actix_web_1::relay::RelayServiceEndpointProvider;
 use splinter_rest_api_actix_web_1::scabbard::ScabbardServiceEndpointProvider;
 use splinter_rest_api_actix_web_1::service::ServiceOrchestratorRestResourceProviderBuilder;
 use splinter_rest_api_actix_web_1::status;
@@ -119,6 +227,10 @@ use crate::node_id::get_node_id;
 
 pub use error::{CreateError, StartError};
 use registry::RegistryShutdownHandle;
+#[cfg(feature = "config-reload")]
+use registry::{RegistryReloader, ReloadableRegistry};
+#[cfg(feature = "config-reload")]
+use reload::ConfigReload;
 pub use store::ConnectionUri;
 
 const ADMIN_SERVICE_PROCESSOR_INCOMING_CAPACITY: usize = 8;
@@ -126,6 +238,12 @@ const ADMIN_SERVICE_PROCESSOR_OUTGOING_CAPACITY: usize = 8;
 const ADMIN_SERVICE_PROCESSOR_CHANNEL_CAPACITY: usize = 8;
 #[cfg(feature = "service2")]
 const ADMIN_SERVICE_LIFECYCLE_TIMEOUT: u64 = 30;
+// The name of the lease a daemon holds on its database while running
+#[cfg(feature = "store-lease")]
+const DATABASE_LEASE_NAME: &str = "splinterd";
+// How long the database lease remains held after the daemon stops renewing it
+#[cfg(feature = "store-lease")]
+const DATABASE_LEASE_DURATION: Duration = Duration::from_secs(30);
 #[cfg(feature = "scabbardv3")]
 const SCABBARD_SERVICE_TYPE: ServiceType = ServiceType::new_static("scabbard:v3");
 #[cfg(feature = "service-echo")]
@@ -154,7 +272,13 @@ pub struct SplinterDaemon {
     registries: Vec<String>,
     registry_auto_refresh: u64,
     registry_forced_refresh: u64,
+    #[cfg(feature = "registry-node-signing")]
+    registry_require_signed_nodes: bool,
     admin_timeout: Duration,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_additional_endpoints: Vec<String>,
+    #[cfg(feature = "rest-api-multi-bind")]
+    rest_api_unauthenticated_endpoints: Vec<String>,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
     #[cfg(feature = "biome-credentials")]
@@ -185,6 +309,70 @@ pub struct SplinterDaemon {
     service_timer_interval: Duration,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Duration,
+    #[cfg(feature = "rbac-bootstrap")]
+    rbac_bootstrap_file: Option<String>,
+    #[cfg(feature = "auth-handshake-limits")]
+    auth_handshake_timeout: Option<Duration>,
+    #[cfg(feature = "auth-handshake-limits")]
+    inbound_connection_limits: InboundConnectionLimits,
+    #[cfg(feature = "state-dir-health")]
+    state_dir_degraded_threshold: u8,
+    #[cfg(feature = "challenge-endpoint-binding")]
+    challenge_endpoint_binding: EndpointBindingMode,
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    admin_max_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-consensus-retry")]
+    admin_verification_retry: Option<VerificationRetry>,
+    #[cfg(feature = "health-service")]
+    health_check_interval: Duration,
+    #[cfg(feature = "circuit-max-message-size")]
+    max_message_size: Option<u64>,
+    #[cfg(feature = "circuit-tombstones")]
+    circuit_tombstones: bool,
+    #[cfg(feature = "tracing")]
+    tracing_otlp_url: Option<String>,
+    #[cfg(feature = "registry-parallel-fetch")]
+    registry_fetch_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_proposal_limits: Option<ProposalLimits>,
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    scabbard_executor_pool_size: Option<usize>,
+    #[cfg(feature = "rest-api-read-only")]
+    rest_api_read_only: bool,
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    tls_policy: TlsPolicy,
+    #[cfg(feature = "tap-backends")]
+    metrics_labels: GlobalLabels,
+    #[cfg(feature = "metrics-snapshot")]
+    metrics_snapshot: Option<MetricsSnapshot>,
+    #[cfg(feature = "store-schema-check")]
+    auto_migrate: bool,
+    #[cfg(feature = "store-lease")]
+    steal_lease: bool,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log: Vec<String>,
+    #[cfg(feature = "circuit-access-log")]
+    circuit_access_log_retention: Option<Duration>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention: EventRetention,
+    #[cfg(feature = "transport-ipv6")]
+    preferred_address_family: Option<AddressFamily>,
+    #[cfg(feature = "rest-api-split")]
+    core_endpoint: Option<String>,
+    #[cfg(feature = "outbound-only")]
+    outbound_only: bool,
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_restart_policies: HashMap<String, RestartPolicy>,
+    #[cfg(feature = "service-orchestrator-limits")]
+    service_resource_limits: HashMap<String, ResourceLimits>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handlers: Option<Vec<String>>,
+    #[cfg(feature = "authorization-handler-provider")]
+    authorization_handler_providers: Vec<Box<dyn AuthorizationHandlerProvider>>,
+    #[cfg(feature = "config-reload")]
+    config_reload: Option<ConfigReload>,
+    #[cfg(feature = "biome-notifications")]
+    biome_notifier: BiomeNotifier,
 }
 
 impl SplinterDaemon {
@@ -195,6 +383,10 @@ impl SplinterDaemon {
         let mut service_transport = InprocTransport::default();
         transport.add_transport(Box::new(service_transport.clone()));
 
+        #[cfg(feature = "store-schema-check")]
+        store::check_store_schemas(&self.db_url, self.auto_migrate)
+            .map_err(|err| StartError::StorageError(err.to_string()))?;
+
         let connection_pool = store::create_connection_pool(&self.db_url).map_err(|err| {
             StartError::StorageError(format!("Failed to initialize connection pool: {}", err))
         })?;
@@ -202,6 +394,40 @@ impl SplinterDaemon {
             StartError::StorageError(format!("Failed to initialize store factory: {}", err))
         })?;
 
+        let (shutdown_tx, shutdown_rx) = channel();
+
+        // Only one daemon may run against a database at a time; the lease is taken before
+        // anything reads from or writes to the stores
+        #[cfg(feature = "store-lease")]
+        let mut lease_heartbeat = {
+            let lease_shutdown_tx = shutdown_tx.clone();
+            LeaseHeartbeat::start(
+                store_factory.get_lease_store(),
+                DATABASE_LEASE_NAME,
+                &format!("pid {} ({:08x})", std::process::id(), rand::random::<u32>()),
+                DATABASE_LEASE_DURATION,
+                self.steal_lease,
+                Box::new(move || {
+                    error!("Another splinterd has taken over the database; shutting down");
+                    let _ = lease_shutdown_tx.send(());
+                }),
+            )
+            .map_err(|err| match err {
+                LeaseError::Held(_) => StartError::StorageError(format!(
+                    "another splinterd appears to be running against this database ({}); \
+                    use --steal-lease to start anyway",
+                    err
+                )),
+                err => {
+                    StartError::StorageError(format!("Unable to acquire database lease: {}", err))
+                }
+            })?
+        };
+        #[cfg(feature = "store-lease")]
+        if self.steal_lease {
+            warn!("Took the database lease regardless of any other running splinterd");
+        }
+
         let circuits_location = Path::new(&self.state_dir).join("circuits.yaml");
         let proposals_location = Path::new(&self.state_dir).join("circuit_proposals.yaml");
 
@@ -256,6 +482,10 @@ impl SplinterDaemon {
                 .map(|listener| listener.endpoint())
                 .collect::<Vec<_>>(),
         );
+        #[cfg(feature = "outbound-only")]
+        if self.outbound_only {
+            info!("Running as an outbound-only node; peers will only be connected to outbound");
+        }
 
         #[cfg(feature = "service-endpoint")]
         let service_listener = transport.listen(&self.service_endpoint)?;
@@ -280,6 +510,21 @@ impl SplinterDaemon {
         )?;
 
         info!("Starting SpinterNode with ID {}", &node_id);
+        #[cfg(feature = "tap-backends")]
+        self.metrics_labels.insert("node_id", &node_id);
+        #[cfg(feature = "tracing")]
+        let span_exporter = match &self.tracing_otlp_url {
+            Some(url) => {
+                let span_exporter = OtlpSpanExporter::new(url, &node_id).map_err(|err| {
+                    StartError::InternalError(format!("Unable to start span exporter: {}", err))
+                })?;
+                if let Err(err) = init_tracer(span_exporter.span_exporter()) {
+                    warn!("Spans will not be exported: {}", err);
+                }
+                Some(span_exporter)
+            }
+            None => None,
+        };
         let authorization_manager = AuthorizationManager::new(
             node_id.to_string(),
             self.signers.clone(),
@@ -288,6 +533,22 @@ impl SplinterDaemon {
         .map_err(|err| {
             StartError::NetworkError(format!("Unable to create authorization manager: {}", err))
         })?;
+        #[cfg(feature = "auth-handshake-limits")]
+        let authorization_manager = match self.auth_handshake_timeout {
+            Some(timeout) => authorization_manager.with_handshake_timeout(timeout),
+            None => authorization_manager,
+        };
+        #[cfg(feature = "challenge-endpoint-binding")]
+        let authorization_manager = authorization_manager.with_endpoint_binding(
+            self.challenge_endpoint_binding,
+            self.advertised_endpoints.clone(),
+        );
+        // Shared with the admin service, which maintains the authorized keys of circuit members
+        #[cfg(feature = "challenge-trust-policy")]
+        let challenge_trust_policy = ChallengeTrustPolicy::new();
+        #[cfg(feature = "challenge-trust-policy")]
+        let authorization_manager =
+            authorization_manager.with_trust_policy(challenge_trust_policy.clone());
 
         let inproc_ids = vec![
             (
@@ -306,26 +567,48 @@ impl SplinterDaemon {
         authorizers.add_authorizer("inproc", inproc_authorizer);
         authorizers.add_authorizer("", authorization_manager.authorization_connector());
 
-        let mut connection_manager = ConnectionManager::builder()
+        let connection_manager = ConnectionManager::builder()
             .with_authorizer(Box::new(authorizers))
             .with_matrix_life_cycle(self.mesh.get_life_cycle())
             .with_matrix_sender(self.mesh.get_sender())
             .with_transport(Box::new(transport))
-            .with_heartbeat_interval(self.heartbeat)
-            .start()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to start connection manager: {}", err))
-            })?;
+            .with_heartbeat_interval(self.heartbeat);
+        #[cfg(feature = "auth-handshake-limits")]
+        let connection_manager =
+            connection_manager.with_inbound_limits(self.inbound_connection_limits.clone());
+        let mut connection_manager = connection_manager.start().map_err(|err| {
+            StartError::NetworkError(format!("Unable to start connection manager: {}", err))
+        })?;
         let connection_connector = connection_manager.connector();
 
-        let mut peer_manager = PeerManager::builder()
+        #[cfg(feature = "network-audit")]
+        let mut audit_logger = AuditLogger::start(
+            connection_connector.clone(),
+            store_factory.get_network_audit_store(),
+            true,
+        )
+        .map_err(|err| {
+            StartError::NetworkError(format!("Unable to start connection audit logger: {}", err))
+        })?;
+
+        let mut peer_manager_builder = PeerManager::builder()
             .with_connector(connection_connector.clone())
             .with_identity(node_id.to_string())
-            .with_strict_ref_counts(self.strict_ref_counts)
-            .start()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to start peer manager: {}", err))
-            })?;
+            .with_strict_ref_counts(self.strict_ref_counts);
+
+        #[cfg(feature = "transport-ipv6")]
+        if let Some(family) = self.preferred_address_family {
+            peer_manager_builder = peer_manager_builder.with_preferred_address_family(family);
+        }
+
+        #[cfg(feature = "outbound-only")]
+        {
+            peer_manager_builder = peer_manager_builder.with_outbound_only(self.outbound_only);
+        }
+
+        let mut peer_manager = peer_manager_builder.start().map_err(|err| {
+            StartError::NetworkError(format!("Unable to start peer manager: {}", err))
+        })?;
 
         let peer_connector = peer_manager.connector();
 
@@ -370,6 +653,9 @@ impl SplinterDaemon {
 
         let network_sender = interconnect.new_network_sender();
 
+        #[cfg(feature = "peer-ping")]
+        let peer_pinger = PeerPinger::new(peer_connector.clone(), network_sender.clone());
+
         #[cfg(feature = "service2")]
         let mut message_handler_task_pool = MessageHandlerTaskPoolBuilder::new()
             .with_size(8)
@@ -397,6 +683,10 @@ impl SplinterDaemon {
         #[cfg(feature = "service-echo")]
         let echo_store_factory = store::create_echo_store_factory(&connection_pool)?;
 
+        #[cfg(feature = "service-echo")]
+        let echo_stats_resource_provider =
+            EchoStatsResourceProvider::new(echo_store_factory.clone());
+
         #[cfg(feature = "service2")]
         let message_handlers: Vec<BoxedByteMessageHandlerFactory> = vec![
             #[cfg(feature = "scabbardv3")]
@@ -409,6 +699,40 @@ impl SplinterDaemon {
                 .into_boxed(),
         ];
 
+        #[cfg(feature = "circuit-access-log")]
+        let mut access_logger = if self.circuit_access_log.is_empty() {
+            None
+        } else {
+            Some(
+                AccessLogger::start(
+                    store_factory.get_circuit_access_log_store(),
+                    AccessLogCircuits::from(&self.circuit_access_log[..]),
+                    self.circuit_access_log_retention,
+                )
+                .map_err(|err| {
+                    StartError::StorageError(format!(
+                        "Unable to start circuit access logger: {}",
+                        err
+                    ))
+                })?,
+            )
+        };
+
+        #[cfg(feature = "admin-service-event-retention")]
+        let mut event_pruner = if self.admin_event_retention.is_unlimited() {
+            None
+        } else {
+            Some(
+                EventPruner::start(
+                    Box::new(store_factory.get_admin_service_store()),
+                    self.admin_event_retention.clone(),
+                )
+                .map_err(|err| {
+                    StartError::StorageError(format!("Unable to start admin event pruner: {}", err))
+                })?,
+            )
+        };
+
         // Set up the Circuit dispatcher
         let circuit_dispatcher = set_up_circuit_dispatcher(
             network_sender.clone(),
@@ -430,14 +754,18 @@ impl SplinterDaemon {
             message_handlers,
             #[cfg(feature = "service2")]
             message_handler_task_pool.task_runner(),
+            #[cfg(feature = "circuit-access-log")]
+            access_logger.as_ref().map(AccessLogger::recorder),
         );
-        let mut circuit_dispatch_loop = DispatchLoopBuilder::new()
+        let circuit_dispatch_loop_builder = DispatchLoopBuilder::new()
             .with_dispatcher(circuit_dispatcher)
-            .with_thread_name("CircuitDispatchLoop".to_string())
-            .build()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to create circuit dispatch loop: {}", err))
-            })?;
+            .with_thread_name("CircuitDispatchLoop".to_string());
+        #[cfg(feature = "dispatch-priority-lanes")]
+        let circuit_dispatch_loop_builder =
+            circuit_dispatch_loop_builder.with_priority_classifier(circuit_message_priority);
+        let mut circuit_dispatch_loop = circuit_dispatch_loop_builder.build().map_err(|err| {
+            StartError::NetworkError(format!("Unable to create circuit dispatch loop: {}", err))
+        })?;
         let circuit_dispatch_sender = circuit_dispatch_loop.new_dispatcher_sender();
 
         #[cfg(not(feature = "service2"))]
@@ -449,14 +777,24 @@ impl SplinterDaemon {
         let network_dispatcher =
             set_up_network_dispatcher(network_sender, &node_id, circuit_dispatch_sender);
 
-        let mut network_dispatch_loop = DispatchLoopBuilder::new()
+        #[cfg(feature = "peer-ping")]
+        let network_dispatcher = {
+            let mut network_dispatcher = network_dispatcher;
+            network_dispatcher.set_handler(Box::new(NetworkPingHandler::default()));
+            network_dispatcher.set_handler(Box::new(peer_pinger.pong_handler()));
+            network_dispatcher
+        };
+
+        let network_dispatch_loop_builder = DispatchLoopBuilder::new()
             .with_dispatcher(network_dispatcher)
             .with_thread_name("NetworkDispatchLoop".to_string())
-            .with_dispatch_channel((network_dispatcher_sender, network_dispatch_receiver))
-            .build()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to create network dispatch loop: {}", err))
-            })?;
+            .with_dispatch_channel((network_dispatcher_sender, network_dispatch_receiver));
+        #[cfg(feature = "dispatch-priority-lanes")]
+        let network_dispatch_loop_builder =
+            network_dispatch_loop_builder.with_priority_classifier(network_message_priority);
+        let mut network_dispatch_loop = network_dispatch_loop_builder.build().map_err(|err| {
+            StartError::NetworkError(format!("Unable to create network dispatch loop: {}", err))
+        })?;
 
         // setup threads to listen on the network ports and add incoming connections to the network
         // these threads will just be dropped on shutdown
@@ -547,13 +885,38 @@ impl SplinterDaemon {
             .with_lmdb_state_enabled(self.enable_lmdb_state)
             .with_state_autocleanup_enabled(self.enable_state_autocleanup);
 
+        #[cfg(feature = "scabbard-shared-executor-pool")]
+        if let Some(pool_size) = self.scabbard_executor_pool_size {
+            scabbard_factory_builder = scabbard_factory_builder.with_executor_pool_size(pool_size);
+        }
+
         let scabbard_factory = scabbard_factory_builder
             .build()
             .map_err(|err| StartError::UserError(err.to_string()))?;
 
-        let mut orchestrator = ServiceOrchestratorBuilder::new()
+        let orchestrator_builder = ServiceOrchestratorBuilder::new()
             .with_connection(orchestrator_connection)
-            .with_service_factory(Box::new(scabbard_factory))
+            .with_service_factory(Box::new(scabbard_factory));
+
+        #[cfg(feature = "service-relay")]
+        let orchestrator_builder =
+            orchestrator_builder.with_service_factory(Box::new(RelayServiceFactory::default()));
+
+        #[cfg(feature = "service-orchestrator-limits")]
+        let orchestrator_builder = {
+            let mut orchestrator_builder = orchestrator_builder;
+            for (service_type, policy) in self.service_restart_policies.iter() {
+                orchestrator_builder =
+                    orchestrator_builder.with_restart_policy(service_type, policy.clone());
+            }
+            for (service_type, limits) in self.service_resource_limits.iter() {
+                orchestrator_builder =
+                    orchestrator_builder.with_resource_limits(service_type, limits.clone());
+            }
+            orchestrator_builder
+        };
+
+        let mut orchestrator = orchestrator_builder
             .build()
             .map_err(|err| {
                 StartError::OrchestratorError(format!("failed to create new orchestrator: {}", err))
@@ -563,11 +926,19 @@ impl SplinterDaemon {
                 StartError::OrchestratorError(format!("failed to start orchestrator: {}", err))
             })?;
 
-        let orchestrator_resources = ServiceOrchestratorRestResourceProviderBuilder::new()
+        let orchestrator_resources_builder = ServiceOrchestratorRestResourceProviderBuilder::new()
             .with_endpoint_factory(
                 scabbard::service::SERVICE_TYPE,
                 Box::new(ScabbardServiceEndpointProvider::default()),
-            )
+            );
+
+        #[cfg(feature = "service-relay")]
+        let orchestrator_resources_builder = orchestrator_resources_builder.with_endpoint_factory(
+            splinter_relay::service::SERVICE_TYPE,
+            Box::new(RelayServiceEndpointProvider::default()),
+        );
+
+        let orchestrator_resources = orchestrator_resources_builder
             .build(&orchestrator)
             .resources();
         let mut orchestator_shutdown_handle =
@@ -583,8 +954,47 @@ impl SplinterDaemon {
             self.registry_auto_refresh,
             self.registry_forced_refresh,
             &*store_factory,
+            #[cfg(feature = "registry-parallel-fetch")]
+            self.registry_fetch_timeout,
         );
 
+        #[cfg(feature = "config-reload")]
+        let config_reload = match (
+            self.config_reload.take(),
+            registry_shutdown.reloadable_registry(),
+        ) {
+            (Some(config_reload), Some(reloadable_registry)) => {
+                Some(config_reload.with_registry_reloader(RegistryReloader::new(
+                    reloadable_registry,
+                    store_factory.get_registry_store(),
+                    #[cfg(feature = "registry-sources")]
+                    registry_shutdown.registry_sources(),
+                    &self.state_dir,
+                    #[cfg(feature = "registry-parallel-fetch")]
+                    remote_fetch_options(&*store_factory, self.registry_fetch_timeout),
+                )))
+            }
+            (config_reload, _) => config_reload,
+        };
+
+        let admin_key_verifier: Box<dyn AdminKeyVerifier> = {
+            #[cfg(feature = "registry-node-signing")]
+            {
+                if self.registry_require_signed_nodes {
+                    Box::new(SignedRegistryKeyVerifier::new(
+                        registry.clone_box_as_reader(),
+                        Secp256k1Context::new().new_verifier(),
+                    ))
+                } else {
+                    Box::new(registry.clone_box_as_reader())
+                }
+            }
+            #[cfg(not(feature = "registry-node-signing"))]
+            {
+                Box::new(registry.clone_box_as_reader())
+            }
+        };
+
         let mut admin_service_builder = AdminServiceBuilder::new();
 
         // allow unused mut, needs to be mutable if service2 is enabled
@@ -607,17 +1017,26 @@ impl SplinterDaemon {
             Duration::from_secs(ADMIN_SERVICE_LIFECYCLE_TIMEOUT),
         )));
 
+        #[cfg(any(
+            feature = "peer-disconnect",
+            feature = "peer-endpoint-pin",
+            feature = "peer-ref-diagnostics"
+        ))]
+        let network_peers_connector = peer_connector.clone();
+        #[cfg(feature = "health-service")]
+        let health_peer_connector = peer_connector.clone();
+
         admin_service_builder = admin_service_builder
             .with_node_id(node_id.clone())
             .with_lifecycle_dispatch(lifecycle_dispatches)
             .with_peer_manager_connector(peer_connector)
             .with_admin_service_store(store_factory.get_admin_service_store())
             .with_signature_verifier(admin_service_verifier)
-            .with_admin_key_verifier(Box::new(registry.clone_box_as_reader()))
+            .with_admin_key_verifier(admin_key_verifier)
             .with_key_permission_manager(Box::new(AllowAllKeyPermissionManager))
             .with_coordinator_timeout(self.admin_timeout)
             .with_routing_table_writer(routing_writer.clone())
-            .with_admin_event_store(store_factory.get_admin_service_store())
+            .with_admin_event_store(Box::new(store_factory.get_admin_service_store()))
             .with_public_keys(
                 self.signers
                     .iter()
@@ -632,8 +1051,61 @@ impl SplinterDaemon {
                     })?,
             );
 
+        #[cfg(feature = "admin-service-proposal-timeout")]
+        if let Some(admin_max_timeout) = self.admin_max_timeout {
+            admin_service_builder =
+                admin_service_builder.with_max_coordinator_timeout(admin_max_timeout);
+        }
+
+        #[cfg(feature = "admin-service-consensus-retry")]
+        if let Some(verification_retry) = self.admin_verification_retry {
+            admin_service_builder =
+                admin_service_builder.with_verification_retry(verification_retry);
+        }
+
+        #[cfg(feature = "circuit-max-message-size")]
+        if let Some(max_message_size) = self.max_message_size {
+            admin_service_builder = admin_service_builder.with_max_message_size(max_message_size);
+        }
+
+        #[cfg(feature = "circuit-tombstones")]
+        {
+            admin_service_builder =
+                admin_service_builder.with_circuit_tombstones(self.circuit_tombstones);
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let Some(proposal_limits) = self.admin_proposal_limits.take() {
+            admin_service_builder = admin_service_builder.with_proposal_limits(proposal_limits);
+        }
+
+        #[cfg(feature = "challenge-trust-policy")]
+        {
+            admin_service_builder =
+                admin_service_builder.with_challenge_trust_policy(challenge_trust_policy);
+        }
+
+        #[cfg(feature = "admin-service-vote-delegation")]
+        {
+            admin_service_builder = admin_service_builder
+                .with_vote_delegation_store(store_factory.get_vote_delegation_store());
+        }
+
         let mut validators: HashMap<String, Box<dyn ServiceArgValidator + Send>> = HashMap::new();
+        #[cfg(not(feature = "service-arg-schema"))]
         validators.insert("scabbard".into(), Box::new(ScabbardArgValidator));
+        #[cfg(feature = "service-arg-schema")]
+        validators.insert("scabbard".into(), Box::new(ScabbardArgValidator::schema()));
+        #[cfg(all(feature = "service-arg-schema", feature = "service-echo"))]
+        validators.insert(
+            ECHO_SERVICE_TYPE.to_string(),
+            Box::new(splinter_echo::service::EchoArguments::schema()),
+        );
+        #[cfg(feature = "service-relay")]
+        validators.insert(
+            splinter_relay::service::SERVICE_TYPE.into(),
+            Box::new(RelayArgValidator),
+        );
 
         admin_service_builder = admin_service_builder.with_service_arg_validators(validators);
 
@@ -654,6 +1126,48 @@ impl SplinterDaemon {
         let circuit_resource_provider =
             CircuitResourceProvider::new(store_factory.get_admin_service_store());
 
+        let registry_resource_provider = RwRegistryRestResourceProvider::new(&registry);
+        #[cfg(feature = "registry-refresh")]
+        let registry_resource_provider = registry_resource_provider
+            .with_refresh_handles(registry_shutdown.remote_yaml_refresh_handles());
+        #[cfg(feature = "registry-sources")]
+        let registry_resource_provider = match registry_shutdown.registry_sources() {
+            Some(registry_sources) => {
+                registry_resource_provider.with_registry_sources(registry_sources)
+            }
+            None => registry_resource_provider,
+        };
+
+        #[cfg(feature = "health-service")]
+        let mut health_monitor = {
+            let rest_api_address = self
+                .rest_api_endpoint
+                .strip_prefix("https://")
+                .or_else(|| self.rest_api_endpoint.strip_prefix("http://"))
+                .unwrap_or(&self.rest_api_endpoint)
+                .to_string();
+            #[cfg(feature = "state-dir-health")]
+            let disk_space_threshold = self.state_dir_degraded_threshold;
+            #[cfg(not(feature = "state-dir-health"))]
+            let disk_space_threshold = splinter::health::DEFAULT_DISK_SPACE_THRESHOLD;
+
+            let checks: Vec<Box<dyn HealthCheck>> = vec![
+                Box::new(StoreCheck::new(store_factory.get_admin_service_store())),
+                Box::new(PeerCheck::new(
+                    node_id.clone(),
+                    health_peer_connector,
+                    store_factory.get_admin_service_store(),
+                )),
+                Box::new(RestApiCheck::new(
+                    rest_api_address,
+                    self.rest_api_endpoint.starts_with("https://"),
+                )),
+                Box::new(DiskSpaceCheck::new(&self.state_dir, disk_space_threshold)),
+            ];
+
+            HealthMonitor::new(checks, self.health_check_interval)
+        };
+
         #[cfg(not(feature = "https-bind"))]
         let bind = self
             .rest_api_endpoint
@@ -661,14 +1175,12 @@ impl SplinterDaemon {
             .unwrap_or(&self.rest_api_endpoint);
 
         #[cfg(feature = "https-bind")]
-        let bind = self.build_rest_api_bind()?;
+        let bind = self.build_rest_api_bind(&self.rest_api_endpoint)?;
 
-        // Allowing unused_mut because rest_api_builder must be mutable if feature biome is enabled
-        #[allow(unused_mut)]
         let mut rest_api_builder = RestApiBuilder::new()
             .with_bind(bind)
             .add_resources(AdminServiceRestProvider::new(&admin_service).resources())
-            .add_resources(RwRegistryRestResourceProvider::new(&registry).resources())
+            .add_resources(registry_resource_provider.resources())
             .add_resources(orchestrator_resources)
             .add_resources(circuit_resource_provider.resources())
             .add_resources(
@@ -679,12 +1191,346 @@ impl SplinterDaemon {
                     service_endpoint,
                     network_endpoints,
                     advertised_endpoints,
+                    #[cfg(feature = "state-dir-health")]
+                    Some(status::StateDirMonitor::new(
+                        &self.state_dir,
+                        self.state_dir_degraded_threshold,
+                    )),
+                    #[cfg(feature = "build-info")]
+                    crate::build_info::build_info(),
+                    #[cfg(feature = "node-metadata")]
+                    store_factory.get_node_id_store(),
                 )
                 .resources(),
             )
             .add_resources(open_api::OpenApiResourceProvider::default().resources());
 
         #[cfg(feature = "authorization")]
+        {
+            rest_api_builder =
+                self.configure_rest_api_authorization(rest_api_builder, &*store_factory)?;
+        }
+
+        rest_api_builder = self.configure_rest_api_server(rest_api_builder)?;
+
+        rest_api_builder = rest_api_builder
+            .with_auth_configs(self.build_auth_configs(&*store_factory, auth_config_verifier)?);
+
+        rest_api_builder = self.add_store_resources(rest_api_builder, &*store_factory);
+
+        #[cfg(feature = "service-echo")]
+        {
+            rest_api_builder =
+                rest_api_builder.add_resources(echo_stats_resource_provider.resources());
+        }
+
+        #[cfg(feature = "metrics-snapshot")]
+        if let Some(metrics_snapshot) = &self.metrics_snapshot {
+            rest_api_builder = rest_api_builder
+                .add_resources(MetricsResourceProvider::new(metrics_snapshot.clone()).resources());
+        }
+
+        #[cfg(feature = "mesh-bandwidth-throttle")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                NetworkThrottleResourceProvider::new(self.mesh.bandwidth_throttle()).resources(),
+            );
+        }
+
+        #[cfg(any(
+            feature = "peer-disconnect",
+            feature = "peer-endpoint-pin",
+            feature = "peer-ref-diagnostics"
+        ))]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                NetworkPeersResourceProvider::new(network_peers_connector).resources(),
+            );
+        }
+
+        #[cfg(feature = "peer-ping")]
+        {
+            rest_api_builder = rest_api_builder
+                .add_resources(NetworkPingResourceProvider::new(peer_pinger).resources());
+        }
+
+        #[cfg(feature = "health-service")]
+        {
+            rest_api_builder = rest_api_builder
+                .add_resources(HealthResourceProvider::new(health_monitor.reader()).resources());
+        }
+
+        let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api_builder.build()?.run()?;
+
+        // The health checks are started once the REST API is running, so that the first run does
+        // not report the REST API as unreachable
+        #[cfg(feature = "health-service")]
+        health_monitor
+            .start()
+            .map_err(|err| StartError::HealthServiceError(err.to_string()))?;
+
+        let mut admin_shutdown_handle = Self::start_admin_service(admin_connection, admin_service)?;
+
+        #[cfg(feature = "config-reload")]
+        let mut config_reload_listener = config_reload
+            .map(|config_reload| config_reload.listen())
+            .transpose()
+            .map_err(|err| StartError::InternalError(err.to_string()))?;
+
+        ctrlc::set_handler(move || {
+            if shutdown_tx.send(()).is_err() {
+                // This was the second ctrl-c (as the receiver is dropped after the first one).
+                std::process::exit(0);
+            }
+        })
+        .expect("Error setting Ctrl-C handler");
+
+        // recv that value, ignoring the result.
+        let _ = shutdown_rx.recv();
+        drop(shutdown_rx);
+        info!("Initiating graceful shutdown (press Ctrl+C again to force)");
+
+        running.store(false, Ordering::SeqCst);
+
+        // Stop reloading the configuration before the components it changes are shut down
+        #[cfg(feature = "config-reload")]
+        if let Some(mut config_reload_listener) = config_reload_listener.take() {
+            config_reload_listener.signal_shutdown();
+            if let Err(err) = config_reload_listener.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down config reload listener: {}",
+                    err
+                );
+            }
+        }
+
+        admin_shutdown_handle.signal_shutdown();
+        orchestator_shutdown_handle.signal_shutdown();
+
+        if let Err(err) = admin_shutdown_handle.wait_for_shutdown() {
+            error!("Unable to cleanly shut down Admin service: {}", err);
+        }
+
+        if let Err(err) = orchestator_shutdown_handle.wait_for_shutdown() {
+            error!("Unable to cleanly shut down Orchestrator service: {}", err);
+        }
+
+        if let Err(err) = rest_api_shutdown_handle.shutdown() {
+            error!("Unable to cleanly shut down REST API server: {}", err);
+        }
+        circuit_dispatch_loop.signal_shutdown();
+        network_dispatch_loop.signal_shutdown();
+
+        if let Err(err) = circuit_dispatch_loop.wait_for_shutdown() {
+            error!("Unable to cleanly shut down circuit dispatch loop: {}", err);
+        }
+
+        if let Err(err) = network_dispatch_loop.wait_for_shutdown() {
+            error!("Unable to cleanly shut down network dispatch loop: {}", err);
+        }
+
+        registry_shutdown.signal_shutdown();
+        if let Err(err) = registry_shutdown.wait_for_shutdown() {
+            error!("Unable to cleanly shut down network dispatch loop: {}", err);
+        }
+
+        interconnect.signal_shutdown();
+
+        // Join threads and shutdown network components
+        let _ = rest_api_join_handle.join();
+
+        peer_manager.signal_shutdown();
+        if let Err(err) = peer_manager.wait_for_shutdown() {
+            error!("Unable to cleanly shut down PeerManager: {}", err);
+        }
+
+        #[cfg(feature = "network-audit")]
+        {
+            audit_logger.signal_shutdown();
+            if let Err(err) = audit_logger.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down connection audit logger: {}",
+                    err
+                );
+            }
+        }
+
+        #[cfg(feature = "circuit-access-log")]
+        if let Some(mut access_logger) = access_logger.take() {
+            access_logger.signal_shutdown();
+            if let Err(err) = access_logger.wait_for_shutdown() {
+                error!("Unable to cleanly shut down circuit access logger: {}", err);
+            }
+        }
+
+        #[cfg(feature = "admin-service-event-retention")]
+        if let Some(mut event_pruner) = event_pruner.take() {
+            event_pruner.signal_shutdown();
+            if let Err(err) = event_pruner.wait_for_shutdown() {
+                error!("Unable to cleanly shut down admin event pruner: {}", err);
+            }
+        }
+
+        #[cfg(feature = "health-service")]
+        {
+            health_monitor.signal_shutdown();
+            if let Err(err) = health_monitor.wait_for_shutdown() {
+                error!("Unable to cleanly shut down health monitor: {}", err);
+            }
+        }
+
+        connection_manager.signal_shutdown();
+        if let Err(err) = connection_manager.wait_for_shutdown() {
+            error!("Unable to cleanly shut down ConnectionManager: {}", err);
+        }
+
+        self.mesh.signal_shutdown();
+        if let Err(err) = interconnect.wait_for_shutdown() {
+            error!("Unable to cleanly shut down peer interconnect: {}", err);
+        }
+        if let Err(err) = self.mesh.clone().wait_for_shutdown() {
+            error!("Unable to cleanly shut down Mesh: {}", err);
+        }
+
+        #[cfg(feature = "service2")]
+        {
+            timer.signal_shutdown();
+            if let Err(err) = timer.wait_for_shutdown() {
+                error!("Unable to cleanly shut down service timer: {}", err);
+            }
+
+            executor.signal_shutdown();
+            if let Err(err) = executor.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down service lifecycle executor: {}",
+                    err
+                )
+            }
+
+            message_handler_task_pool.signal_shutdown();
+            if let Err(err) = message_handler_task_pool.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down message handler task pool: {}",
+                    err
+                );
+            }
+
+            #[cfg(feature = "scabbardv3")]
+            supervisor.signal_shutdown();
+            #[cfg(feature = "scabbardv3")]
+            if let Err(err) = supervisor.wait_for_shutdown() {
+                error!("Unable to cleanly shut down scabbard supervisor: {}", err);
+            }
+        }
+
+        #[cfg(feature = "store-lease")]
+        {
+            lease_heartbeat.signal_shutdown();
+            if let Err(err) = lease_heartbeat.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down database lease heartbeat: {}",
+                    err
+                );
+            }
+        }
+
+        // The span exporter is shut down last, so that spans recorded during shutdown are sent
+        #[cfg(feature = "tracing")]
+        if let Some(mut span_exporter) = span_exporter {
+            span_exporter.signal_shutdown();
+            if let Err(err) = span_exporter.wait_for_shutdown() {
+                error!("Unable to cleanly shut down span exporter: {}", err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the authorization handlers, and the endpoints for managing them, to the REST API.
+    #[cfg(feature = "authorization")]
+    #[allow(unused_variables, unused_mut)]
+    fn configure_rest_api_authorization(
+        &self,
+        mut rest_api_builder: RestApiBuilder,
+        store_factory: &dyn StoreFactory,
+    ) -> Result<RestApiBuilder, StartError> {
+        #[cfg(feature = "authorization-handler-rbac")]
+        let rbac_store = store_factory.get_role_based_authorization_store();
+
+        #[cfg(feature = "rbac-bootstrap")]
+        if let Some(rbac_bootstrap_file) = &self.rbac_bootstrap_file {
+            debug!("Applying RBAC bootstrap file: {}", rbac_bootstrap_file);
+            RbacBootstrap::from_yaml_file(rbac_bootstrap_file)
+                .map_err(|err| StartError::StorageError(err.to_string()))?
+                .apply(&*rbac_store)
+                .map_err(|err| {
+                    StartError::StorageError(format!(
+                        "Failed to apply RBAC bootstrap file '{}': {}",
+                        rbac_bootstrap_file, err
+                    ))
+                })?;
+        }
+
+        // Assignments changed through the REST API are published as Biome notifications
+        #[cfg(all(
+            feature = "authorization-handler-rbac",
+            feature = "biome-notifications"
+        ))]
+        let rbac_store: Box<dyn RoleBasedAuthorizationStore> = Box::new(
+            NotifyingRoleBasedAuthorizationStore::new(rbac_store, self.biome_notifier.clone()),
+        );
+
+        #[cfg(feature = "authorization-handler-provider")]
+        {
+            // The built-in handlers come first, in their default order, followed by the handlers
+            // registered with the daemon builder
+            #[allow(unused_mut)]
+            let mut builtin_providers: Vec<Box<dyn AuthorizationHandlerProvider>> = vec![];
+
+            #[cfg(feature = "authorization-handler-allow-keys")]
+            builtin_providers.push(Box::new(authorization::AllowKeysProvider::new(
+                create_allow_keys_path(
+                    &self.config_dir,
+                    #[cfg(feature = "config-allow-keys")]
+                    &self.allow_keys_file,
+                    #[cfg(not(feature = "config-allow-keys"))]
+                    "allow_keys",
+                )
+                .to_str()
+                .expect("path built from &str cannot be invalid")
+                .to_string(),
+            )));
+
+            #[cfg(feature = "authorization-handler-maintenance")]
+            {
+                #[cfg(feature = "authorization-handler-rbac")]
+                let maintenance_mode_auth_handler =
+                    MaintenanceModeAuthorizationHandler::new(Some(rbac_store.clone()));
+                #[cfg(not(feature = "authorization-handler-rbac"))]
+                let maintenance_mode_auth_handler = MaintenanceModeAuthorizationHandler::default();
+                builtin_providers.push(Box::new(authorization::MaintenanceProvider::new(
+                    maintenance_mode_auth_handler,
+                )));
+            }
+
+            #[cfg(feature = "authorization-handler-rbac")]
+            builtin_providers.push(Box::new(authorization::RbacProvider::new(rbac_store)));
+
+            let providers = authorization::select_providers(
+                builtin_providers
+                    .iter()
+                    .chain(self.authorization_handler_providers.iter())
+                    .map(|provider| &**provider)
+                    .collect(),
+                self.authorization_handlers.as_deref(),
+            )?;
+            let (authorization_handlers, resources) = authorization::create_handlers(&providers)?;
+
+            Ok(rest_api_builder
+                .add_resources(resources)
+                .with_authorization_handlers(authorization_handlers))
+        }
+
+        #[cfg(not(feature = "authorization-handler-provider"))]
         {
             // Allowing unused_mut because authorization_handlers must be mutable if
             // `authorization-handler-allow-keys` or `auth-handler-maintenance` are enabled
@@ -704,9 +1550,6 @@ impl SplinterDaemon {
                 )?,
             ];
 
-            #[cfg(feature = "authorization-handler-rbac")]
-            let rbac_store = store_factory.get_role_based_authorization_store();
-
             #[cfg(feature = "authorization-handler-maintenance")]
             {
                 #[cfg(feature = "authorization-handler-rbac")]
@@ -721,17 +1564,45 @@ impl SplinterDaemon {
 
             #[cfg(feature = "authorization-handler-rbac")]
             {
-                authorization_handlers
-                    .push(Box::new(RoleBasedAuthorizationHandler::new(rbac_store)));
+                authorization_handlers.push(Box::new(RoleBasedAuthorizationHandler::new(
+                    rbac_store.clone(),
+                )));
                 rest_api_builder = rest_api_builder.add_resources(
-                    RoleBasedAuthorizationResourceProvider::new(
-                        store_factory.get_role_based_authorization_store(),
-                    )
-                    .resources(),
+                    RoleBasedAuthorizationResourceProvider::new(rbac_store).resources(),
                 );
             }
 
-            rest_api_builder = rest_api_builder.with_authorization_handlers(authorization_handlers)
+            Ok(rest_api_builder.with_authorization_handlers(authorization_handlers))
+        }
+    }
+
+    /// Applies the additional binds and server settings of the REST API.
+    #[allow(unused_mut)]
+    fn configure_rest_api_server(
+        &self,
+        mut rest_api_builder: RestApiBuilder,
+    ) -> Result<RestApiBuilder, StartError> {
+        #[cfg(feature = "rest-api-multi-bind")]
+        {
+            for endpoint in &self.rest_api_additional_endpoints {
+                rest_api_builder = rest_api_builder.add_bind(
+                    self.build_additional_rest_api_bind(endpoint)?,
+                    BindAuth::Required,
+                );
+            }
+            for endpoint in &self.rest_api_unauthenticated_endpoints {
+                if !endpoint.starts_with("unix://") {
+                    warn!(
+                        "REST API endpoint {} does not require authorization, but is not a \
+                        unix socket",
+                        endpoint
+                    );
+                }
+                rest_api_builder = rest_api_builder.add_bind(
+                    self.build_additional_rest_api_bind(endpoint)?,
+                    BindAuth::Disabled,
+                );
+            }
         }
 
         #[cfg(feature = "rest-api-cors")]
@@ -742,6 +1613,29 @@ impl SplinterDaemon {
             }
         }
 
+        #[cfg(feature = "rest-api-read-only")]
+        {
+            if self.rest_api_read_only {
+                info!("REST API is read-only; mutating requests will be rejected");
+            }
+            rest_api_builder = rest_api_builder.with_read_only(self.rest_api_read_only);
+        }
+
+        #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+        {
+            rest_api_builder = rest_api_builder.with_tls_policy(self.tls_policy.clone());
+        }
+
+        Ok(rest_api_builder)
+    }
+
+    /// Builds the authentication configuration of the REST API.
+    #[allow(unused_variables)]
+    fn build_auth_configs(
+        &self,
+        store_factory: &dyn StoreFactory,
+        auth_config_verifier: Box<dyn Verifier>,
+    ) -> Result<Vec<AuthConfig>, StartError> {
         #[allow(unused_mut)]
         let mut auth_configs = vec![
             // Add Cylinder JWT as an auth provider
@@ -763,7 +1657,7 @@ impl SplinterDaemon {
             #[cfg(feature = "biome-key-management")]
             {
                 biome_credentials_builder =
-                    biome_credentials_builder.with_key_store(store_factory.get_biome_key_store())
+                    biome_credentials_builder.with_key_store(self.biome_key_store(store_factory))
             }
 
             let biome_credentials_resource_provider =
@@ -854,18 +1748,50 @@ impl SplinterDaemon {
             }
         }
 
-        rest_api_builder = rest_api_builder.with_auth_configs(auth_configs);
+        Ok(auth_configs)
+    }
+
+    /// Returns the Biome key store, publishing Biome notifications when keys are added or removed.
+    #[cfg(feature = "biome-key-management")]
+    fn biome_key_store(&self, store_factory: &dyn StoreFactory) -> Box<dyn KeyStore> {
+        #[cfg(feature = "biome-notifications")]
+        {
+            Box::new(NotifyingKeyStore::new(
+                store_factory.get_biome_key_store(),
+                self.biome_notifier.clone(),
+            ))
+        }
+        #[cfg(not(feature = "biome-notifications"))]
+        {
+            store_factory.get_biome_key_store()
+        }
+    }
 
+    /// Adds the endpoints that are backed only by the database to the REST API.
+    #[allow(unused_mut, unused_variables)]
+    fn add_store_resources(
+        &self,
+        mut rest_api_builder: RestApiBuilder,
+        store_factory: &dyn StoreFactory,
+    ) -> RestApiBuilder {
         #[cfg(feature = "biome-key-management")]
         {
             rest_api_builder = rest_api_builder.add_resources(
                 BiomeKeyManagementRestResourceProvider::new(Arc::new(
-                    store_factory.get_biome_key_store(),
+                    self.biome_key_store(store_factory),
                 ))
                 .resources(),
             );
         }
 
+        #[cfg(feature = "biome-notifications")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                BiomeNotificationsRestResourceProvider::new(self.biome_notifier.clone())
+                    .resources(),
+            );
+        }
+
         #[cfg(feature = "biome-profile")]
         {
             rest_api_builder = rest_api_builder.add_resources(
@@ -876,115 +1802,31 @@ impl SplinterDaemon {
             );
         }
 
-        let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api_builder.build()?.run()?;
-
-        let mut admin_shutdown_handle = Self::start_admin_service(admin_connection, admin_service)?;
-
-        let (shutdown_tx, shutdown_rx) = channel();
-        ctrlc::set_handler(move || {
-            if shutdown_tx.send(()).is_err() {
-                // This was the second ctrl-c (as the receiver is dropped after the first one).
-                std::process::exit(0);
-            }
-        })
-        .expect("Error setting Ctrl-C handler");
-
-        // recv that value, ignoring the result.
-        let _ = shutdown_rx.recv();
-        drop(shutdown_rx);
-        info!("Initiating graceful shutdown (press Ctrl+C again to force)");
-
-        running.store(false, Ordering::SeqCst);
-
-        admin_shutdown_handle.signal_shutdown();
-        orchestator_shutdown_handle.signal_shutdown();
-
-        if let Err(err) = admin_shutdown_handle.wait_for_shutdown() {
-            error!("Unable to cleanly shut down Admin service: {}", err);
-        }
-
-        if let Err(err) = orchestator_shutdown_handle.wait_for_shutdown() {
-            error!("Unable to cleanly shut down Orchestrator service: {}", err);
-        }
-
-        if let Err(err) = rest_api_shutdown_handle.shutdown() {
-            error!("Unable to cleanly shut down REST API server: {}", err);
-        }
-        circuit_dispatch_loop.signal_shutdown();
-        network_dispatch_loop.signal_shutdown();
-
-        if let Err(err) = circuit_dispatch_loop.wait_for_shutdown() {
-            error!("Unable to cleanly shut down circuit dispatch loop: {}", err);
-        }
-
-        if let Err(err) = network_dispatch_loop.wait_for_shutdown() {
-            error!("Unable to cleanly shut down network dispatch loop: {}", err);
-        }
-
-        registry_shutdown.signal_shutdown();
-        if let Err(err) = registry_shutdown.wait_for_shutdown() {
-            error!("Unable to cleanly shut down network dispatch loop: {}", err);
-        }
-
-        interconnect.signal_shutdown();
-
-        // Join threads and shutdown network components
-        let _ = rest_api_join_handle.join();
-
-        peer_manager.signal_shutdown();
-        if let Err(err) = peer_manager.wait_for_shutdown() {
-            error!("Unable to cleanly shut down PeerManager: {}", err);
-        }
-
-        connection_manager.signal_shutdown();
-        if let Err(err) = connection_manager.wait_for_shutdown() {
-            error!("Unable to cleanly shut down ConnectionManager: {}", err);
-        }
-
-        self.mesh.signal_shutdown();
-        if let Err(err) = interconnect.wait_for_shutdown() {
-            error!("Unable to cleanly shut down peer interconnect: {}", err);
-        }
-        if let Err(err) = self.mesh.clone().wait_for_shutdown() {
-            error!("Unable to cleanly shut down Mesh: {}", err);
+        #[cfg(feature = "network-audit")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                NetworkAuditResourceProvider::new(store_factory.get_network_audit_store())
+                    .resources(),
+            );
         }
 
-        #[cfg(feature = "service2")]
+        #[cfg(feature = "circuit-access-log")]
         {
-            timer.signal_shutdown();
-            if let Err(err) = timer.wait_for_shutdown() {
-                error!("Unable to cleanly shut down service timer: {}", err);
-            }
-
-            executor.signal_shutdown();
-            if let Err(err) = executor.wait_for_shutdown() {
-                error!(
-                    "Unable to cleanly shut down service lifecycle executor: {}",
-                    err
-                )
-            }
-
-            message_handler_task_pool.signal_shutdown();
-            if let Err(err) = message_handler_task_pool.wait_for_shutdown() {
-                error!(
-                    "Unable to cleanly shut down message handler task pool: {}",
-                    err
-                );
-            }
-
-            #[cfg(feature = "scabbardv3")]
-            supervisor.signal_shutdown();
-            #[cfg(feature = "scabbardv3")]
-            if let Err(err) = supervisor.wait_for_shutdown() {
-                error!("Unable to cleanly shut down scabbard supervisor: {}", err);
-            }
+            rest_api_builder = rest_api_builder.add_resources(
+                CircuitAccessLogResourceProvider::new(store_factory.get_circuit_access_log_store())
+                    .resources(),
+            );
         }
-        Ok(())
+
+        rest_api_builder
     }
 
     #[cfg(feature = "https-bind")]
-    fn build_rest_api_bind(&self) -> Result<splinter::rest_api::BindConfig, StartError> {
-        match self.rest_api_endpoint.strip_prefix("http://") {
+    fn build_rest_api_bind(
+        &self,
+        endpoint: &str,
+    ) -> Result<splinter::rest_api::BindConfig, StartError> {
+        match endpoint.strip_prefix("http://") {
             Some(insecure_endpoint) => Ok(splinter::rest_api::BindConfig::Http(
                 insecure_endpoint.into(),
             )),
@@ -993,10 +1835,9 @@ impl SplinterDaemon {
                     self.rest_api_ssl_settings.as_ref()
                 {
                     Ok(splinter::rest_api::BindConfig::Https {
-                        bind: self
-                            .rest_api_endpoint
+                        bind: endpoint
                             .strip_prefix("https://")
-                            .or(Some(&self.rest_api_endpoint))
+                            .or(Some(endpoint))
                             .map(String::from)
                             .expect("There should be a value, due to the above or"),
                         cert_path: rest_api_server_cert.clone(),
@@ -1013,6 +1854,29 @@ impl SplinterDaemon {
         }
     }
 
+    /// Builds the bind configuration for an additional REST API endpoint, which may be a unix
+    /// socket given as `unix:///path/to/socket`.
+    #[cfg(feature = "rest-api-multi-bind")]
+    fn build_additional_rest_api_bind(
+        &self,
+        endpoint: &str,
+    ) -> Result<splinter::rest_api::BindConfig, StartError> {
+        if let Some(path) = endpoint.strip_prefix("unix://") {
+            return Ok(splinter::rest_api::BindConfig::Unix(path.into()));
+        }
+
+        #[cfg(feature = "https-bind")]
+        {
+            self.build_rest_api_bind(endpoint)
+        }
+        #[cfg(not(feature = "https-bind"))]
+        {
+            Ok(splinter::rest_api::BindConfig::Http(
+                endpoint.strip_prefix("http://").unwrap_or(endpoint).into(),
+            ))
+        }
+    }
+
     fn listen_for_services(
         connection_connector: Connector,
         internal_service_listeners: Vec<Box<dyn Listener>>,
@@ -1103,6 +1967,26 @@ impl SplinterDaemon {
     }
 }
 
+/// Admin messages and service connection management are control messages, so they are not blocked
+/// behind service traffic.
+#[cfg(feature = "dispatch-priority-lanes")]
+fn circuit_message_priority(message_type: &CircuitMessageType) -> MessagePriority {
+    match message_type {
+        CircuitMessageType::CIRCUIT_DIRECT_MESSAGE => MessagePriority::Data,
+        _ => MessagePriority::Control,
+    }
+}
+
+/// Authorization and heartbeat messages are control messages, so peers are not disconnected while
+/// a backlog of circuit messages is forwarded.
+#[cfg(feature = "dispatch-priority-lanes")]
+fn network_message_priority(message_type: &NetworkMessageType) -> MessagePriority {
+    match message_type {
+        NetworkMessageType::CIRCUIT => MessagePriority::Data,
+        _ => MessagePriority::Control,
+    }
+}
+
 fn set_up_network_dispatcher(
     network_sender: NetworkMessageSender,
     node_id: &str,
@@ -1133,6 +2017,7 @@ fn set_up_circuit_dispatcher(
     #[cfg(feature = "service2")] message_handler_task_runner: impl MessageHandlerTaskRunner
         + Send
         + 'static,
+    #[cfg(feature = "circuit-access-log")] access_log: Option<AccessLogRecorder>,
 ) -> Dispatcher<CircuitMessageType> {
     #[cfg(not(feature = "service2"))]
     let mut dispatcher = Dispatcher::<CircuitMessageType>::new(Box::new(network_sender));
@@ -1165,6 +2050,11 @@ fn set_up_circuit_dispatcher(
             Box::new(message_handler_task_runner),
         ),
     );
+    #[cfg(feature = "circuit-access-log")]
+    let direct_message_handler = match access_log {
+        Some(access_log) => direct_message_handler.with_access_log(access_log),
+        None => direct_message_handler,
+    };
     dispatcher.set_handler(Box::new(direct_message_handler));
 
     let circuit_error_handler =
@@ -1184,13 +2074,103 @@ fn create_registry(
     registries: &[String],
     auto_refresh_interval: u64,
     forced_refresh_interval: u64,
-    store_factory: &dyn splinter::store::StoreFactory,
+    store_factory: &dyn StoreFactory,
+    #[cfg(feature = "registry-parallel-fetch")] fetch_timeout: Option<Duration>,
 ) -> (Box<dyn RwRegistry>, RegistryShutdownHandle) {
     let mut registry_shutdown_handle = RegistryShutdownHandle::new();
 
     let local_registry = store_factory.get_registry_store();
 
-    let read_only_registries = registries
+    let auto_refresh_interval = refresh_interval(auto_refresh_interval);
+    let forced_refresh_interval = refresh_interval(forced_refresh_interval);
+
+    // The configured remote registries are shut down with the reloadable registry, so that they
+    // may be replaced when the configuration is reloaded
+    #[cfg(feature = "config-reload")]
+    let mut configured_shutdown_handle = RegistryShutdownHandle::new();
+
+    let read_only_registries = create_read_only_registries(
+        state_dir,
+        registries,
+        auto_refresh_interval,
+        forced_refresh_interval,
+        #[cfg(feature = "registry-parallel-fetch")]
+        remote_fetch_options(store_factory, fetch_timeout),
+        #[cfg(feature = "config-reload")]
+        &mut configured_shutdown_handle,
+        #[cfg(not(feature = "config-reload"))]
+        &mut registry_shutdown_handle,
+    );
+
+    #[cfg(all(feature = "config-reload", feature = "registry-refresh"))]
+    for refresh_handle in configured_shutdown_handle.remote_yaml_refresh_handles() {
+        registry_shutdown_handle.add_remote_yaml_refresh_handle(refresh_handle);
+    }
+
+    // Remote registries added at runtime are read after those configured on startup
+    #[cfg(feature = "registry-sources")]
+    let read_only_registries = {
+        let mut read_only_registries = read_only_registries;
+        match RegistrySources::new(
+            store_factory.get_registry_source_store(),
+            state_dir,
+            auto_refresh_interval,
+            forced_refresh_interval,
+            registry_shutdown_handle.remote_yaml_refresh_handles(),
+            #[cfg(feature = "registry-parallel-fetch")]
+            fetch_timeout,
+        ) {
+            Ok(registry_sources) => {
+                read_only_registries.push(Box::new(registry_sources.clone()));
+                registry_shutdown_handle.set_registry_sources(registry_sources);
+            }
+            Err(err) => error!("Failed to load registry sources: {}", err),
+        }
+        read_only_registries
+    };
+
+    let unified_registry = UnifiedRegistry::new(local_registry, read_only_registries);
+
+    #[cfg(feature = "config-reload")]
+    let unified_registry = {
+        let registry = ReloadableRegistry::new(unified_registry, configured_shutdown_handle);
+        registry_shutdown_handle.set_reloadable_registry(registry.clone());
+        registry
+    };
+
+    (Box::new(unified_registry), registry_shutdown_handle)
+}
+
+/// Creates the read-only registries for the given registry locations, adding the shutdown and
+/// refresh handles of the remote registries to the given shutdown handle.
+fn create_read_only_registries(
+    state_dir: &str,
+    registries: &[String],
+    auto_refresh_interval: Option<Duration>,
+    forced_refresh_interval: Option<Duration>,
+    #[cfg(feature = "registry-parallel-fetch")] fetch_options: RemoteYamlFetchOptions,
+    registry_shutdown_handle: &mut RegistryShutdownHandle,
+) -> Vec<Box<dyn RegistryReader>> {
+    // Fetch the remote registries concurrently, so that one slow registry does not delay the
+    // others; the outcome of every fetch is recorded in the registry source store
+    #[cfg(feature = "registry-parallel-fetch")]
+    let mut remote_registries = {
+        let remote_urls = registries
+            .iter()
+            .filter(|registry| matches!(parse_registry_arg(registry).0, "http" | "https"))
+            .cloned()
+            .collect::<Vec<_>>();
+        RemoteYamlRegistry::new_concurrently(
+            &remote_urls,
+            state_dir,
+            auto_refresh_interval,
+            forced_refresh_interval,
+            fetch_options,
+        )
+        .into_iter()
+    };
+
+    registries
         .iter()
         .filter_map(|registry| {
             let (scheme, path) = parse_registry_arg(registry);
@@ -1215,22 +2195,20 @@ fn create_registry(
                     "Attempting to add remote read-only registry from URL: {}",
                     registry
                 );
-                let auto_refresh_interval = if auto_refresh_interval != 0 {
-                    Some(Duration::from_secs(auto_refresh_interval))
-                } else {
-                    None
-                };
-                let forced_refresh_interval = if forced_refresh_interval != 0 {
-                    Some(Duration::from_secs(forced_refresh_interval))
-                } else {
-                    None
-                };
-                match RemoteYamlRegistry::new(
+                #[cfg(feature = "registry-parallel-fetch")]
+                let remote_registry = remote_registries.next().unwrap_or_else(|| {
+                    Err(RegistryError::InternalError(InternalError::with_message(
+                        "Remote registry was not started".into(),
+                    )))
+                });
+                #[cfg(not(feature = "registry-parallel-fetch"))]
+                let remote_registry = RemoteYamlRegistry::new(
                     registry,
                     state_dir,
                     auto_refresh_interval,
                     forced_refresh_interval,
-                ) {
+                );
+                match remote_registry {
                     Ok(mut registry) => {
                         // this should always return some
                         if let Some(shutdown_handle) = registry.take_shutdown_handle() {
@@ -1238,6 +2216,16 @@ fn create_registry(
                                 .add_remote_yaml_shutdown_handle(shutdown_handle)
                         }
 
+                        #[cfg(feature = "registry-refresh")]
+                        match registry.refresh_handle() {
+                            Ok(refresh_handle) => registry_shutdown_handle
+                                .add_remote_yaml_refresh_handle(refresh_handle),
+                            Err(err) => error!(
+                                "Failed to get refresh handle for RemoteYamlRegistry '{}://{}': {}",
+                                scheme, path, err
+                            ),
+                        }
+
                         Some(Box::new(registry) as Box<dyn RegistryReader>)
                     }
                     Err(err) => {
@@ -1256,11 +2244,30 @@ fn create_registry(
                 None
             }
         })
-        .collect();
+        .collect()
+}
 
-    let unified_registry = Box::new(UnifiedRegistry::new(local_registry, read_only_registries));
+// Converts a refresh interval in seconds to a duration, where an interval of 0 turns off the
+// refresh
+fn refresh_interval(secs: u64) -> Option<Duration> {
+    if secs != 0 {
+        Some(Duration::from_secs(secs))
+    } else {
+        None
+    }
+}
 
-    (unified_registry, registry_shutdown_handle)
+#[cfg(feature = "registry-parallel-fetch")]
+fn remote_fetch_options(
+    store_factory: &dyn StoreFactory,
+    fetch_timeout: Option<Duration>,
+) -> RemoteYamlFetchOptions {
+    let fetch_options = RemoteYamlFetchOptions::default()
+        .with_stats_store(store_factory.get_registry_source_store());
+    match fetch_timeout {
+        Some(timeout) => fetch_options.with_timeout(timeout),
+        None => fetch_options,
+    }
 }
 
 // Parses a registry argument, returning the uri scheme (defaulting to file) and remaining uri data
@@ -1336,7 +2343,10 @@ mod tests {
     }
 }
 
-#[cfg(feature = "authorization-handler-allow-keys")]
+#[cfg(all(
+    feature = "authorization-handler-allow-keys",
+    not(feature = "authorization-handler-provider")
+))]
 fn create_allow_keys_authorization_handler(
     allow_keys_path: &str,
 ) -> Result<Box<dyn AuthorizationHandler>, StartError> {
@@ -12,13 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "config-reload")]
+use std::sync::{Arc, Mutex, RwLock};
+
 use splinter::error::InternalError;
+#[cfg(feature = "registry-sources")]
+use splinter::registry::sources::{RegistrySources, RegistrySourcesShutdownHandle};
+#[cfg(all(feature = "config-reload", feature = "registry-parallel-fetch"))]
+use splinter::registry::RemoteYamlFetchOptions;
+#[cfg(feature = "registry-refresh")]
+use splinter::registry::RemoteYamlRefreshHandle;
 use splinter::registry::RemoteYamlShutdownHandle;
+#[cfg(feature = "config-reload")]
+use splinter::registry::{
+    MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
+    UnifiedRegistry,
+};
+#[cfg(all(feature = "config-reload", feature = "registry-batch"))]
+use splinter::registry::{NodeBatchResult, NodeOperation};
 use splinter::threading::lifecycle::ShutdownHandle;
 
 #[derive(Default)]
 pub struct RegistryShutdownHandle {
     remote_yaml_shutdown_handles: Vec<RemoteYamlShutdownHandle>,
+    #[cfg(feature = "registry-refresh")]
+    remote_yaml_refresh_handles: Vec<RemoteYamlRefreshHandle>,
+    #[cfg(feature = "registry-sources")]
+    registry_sources: Option<RegistrySources>,
+    #[cfg(feature = "registry-sources")]
+    registry_sources_shutdown_handle: Option<RegistrySourcesShutdownHandle>,
+    #[cfg(feature = "config-reload")]
+    reloadable_registry: Option<ReloadableRegistry>,
 }
 
 impl RegistryShutdownHandle {
@@ -29,6 +53,41 @@ impl RegistryShutdownHandle {
     pub fn add_remote_yaml_shutdown_handle(&mut self, handle: RemoteYamlShutdownHandle) {
         self.remote_yaml_shutdown_handles.push(handle);
     }
+
+    #[cfg(feature = "registry-refresh")]
+    pub fn add_remote_yaml_refresh_handle(&mut self, handle: RemoteYamlRefreshHandle) {
+        self.remote_yaml_refresh_handles.push(handle);
+    }
+
+    /// Returns the handles used to force a refresh of the remote registries
+    #[cfg(feature = "registry-refresh")]
+    pub fn remote_yaml_refresh_handles(&self) -> Vec<RemoteYamlRefreshHandle> {
+        self.remote_yaml_refresh_handles.clone()
+    }
+
+    #[cfg(feature = "registry-sources")]
+    pub fn set_registry_sources(&mut self, registry_sources: RegistrySources) {
+        self.registry_sources_shutdown_handle = Some(registry_sources.shutdown_handle());
+        self.registry_sources = Some(registry_sources);
+    }
+
+    /// Returns the manager of the remote registries that may be added and removed at runtime
+    #[cfg(feature = "registry-sources")]
+    pub fn registry_sources(&self) -> Option<RegistrySources> {
+        self.registry_sources.clone()
+    }
+
+    /// Sets the registry whose configured remote registries are shut down along with this handle
+    #[cfg(feature = "config-reload")]
+    pub fn set_reloadable_registry(&mut self, registry: ReloadableRegistry) {
+        self.reloadable_registry = Some(registry);
+    }
+
+    /// Returns the registry that is replaced when the node's configuration is reloaded
+    #[cfg(feature = "config-reload")]
+    pub fn reloadable_registry(&self) -> Option<ReloadableRegistry> {
+        self.reloadable_registry.clone()
+    }
 }
 
 impl ShutdownHandle for RegistryShutdownHandle {
@@ -36,6 +95,16 @@ impl ShutdownHandle for RegistryShutdownHandle {
         self.remote_yaml_shutdown_handles
             .iter_mut()
             .for_each(|handle| handle.signal_shutdown());
+
+        #[cfg(feature = "registry-sources")]
+        if let Some(handle) = self.registry_sources_shutdown_handle.as_mut() {
+            handle.signal_shutdown();
+        }
+
+        #[cfg(feature = "config-reload")]
+        if let Some(registry) = self.reloadable_registry.as_mut() {
+            registry.signal_shutdown();
+        }
     }
 
     fn wait_for_shutdown(self) -> Result<(), InternalError> {
@@ -46,6 +115,20 @@ impl ShutdownHandle for RegistryShutdownHandle {
             }
         }
 
+        #[cfg(feature = "registry-sources")]
+        if let Some(handle) = self.registry_sources_shutdown_handle {
+            if let Err(err) = handle.wait_for_shutdown() {
+                errors.push(err);
+            }
+        }
+
+        #[cfg(feature = "config-reload")]
+        if let Some(registry) = self.reloadable_registry {
+            if let Err(err) = registry.wait_for_shutdown() {
+                errors.push(err);
+            }
+        }
+
         match errors.len() {
             0 => Ok(()),
             1 => Err(errors.remove(0)),
@@ -60,3 +143,233 @@ impl ShutdownHandle for RegistryShutdownHandle {
         }
     }
 }
+
+/// A registry that may be replaced while the node is running, so that changes to the configured
+/// registries take effect when the node's configuration is reloaded.
+///
+/// Cloned instances share the same underlying registry. The shutdown handle of the remote
+/// registries that were configured for the current registry is replaced along with it.
+#[cfg(feature = "config-reload")]
+#[derive(Clone)]
+pub struct ReloadableRegistry {
+    current: Arc<RwLock<UnifiedRegistry>>,
+    remote_shutdown_handle: Arc<Mutex<Option<RegistryShutdownHandle>>>,
+}
+
+#[cfg(feature = "config-reload")]
+impl ReloadableRegistry {
+    pub fn new(registry: UnifiedRegistry, remote_shutdown_handle: RegistryShutdownHandle) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(registry)),
+            remote_shutdown_handle: Arc::new(Mutex::new(Some(remote_shutdown_handle))),
+        }
+    }
+
+    /// Replaces the current registry, shutting down the remote registries of the previous one.
+    pub fn replace(
+        &self,
+        registry: UnifiedRegistry,
+        remote_shutdown_handle: RegistryShutdownHandle,
+    ) -> Result<(), InternalError> {
+        *self.current.write().map_err(|_| {
+            InternalError::with_message("Reloadable registry lock was poisoned".into())
+        })? = registry;
+
+        let previous = self
+            .remote_shutdown_handle
+            .lock()
+            .map_err(|_| {
+                InternalError::with_message("Registry shutdown handle lock was poisoned".into())
+            })?
+            .replace(remote_shutdown_handle);
+
+        if let Some(mut previous) = previous {
+            previous.signal_shutdown();
+            previous.wait_for_shutdown()?;
+        }
+
+        Ok(())
+    }
+
+    fn current(&self) -> Result<UnifiedRegistry, RegistryError> {
+        self.current
+            .read()
+            .map(|registry| registry.clone())
+            .map_err(|_| {
+                RegistryError::InternalError(InternalError::with_message(
+                    "Reloadable registry lock was poisoned".into(),
+                ))
+            })
+    }
+
+    fn signal_shutdown(&mut self) {
+        match self.remote_shutdown_handle.lock() {
+            Ok(mut handle) => {
+                if let Some(handle) = handle.as_mut() {
+                    handle.signal_shutdown();
+                }
+            }
+            Err(_) => error!("Registry shutdown handle lock was poisoned"),
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        let handle = self
+            .remote_shutdown_handle
+            .lock()
+            .map_err(|_| {
+                InternalError::with_message("Registry shutdown handle lock was poisoned".into())
+            })?
+            .take();
+
+        match handle {
+            Some(handle) => handle.wait_for_shutdown(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "config-reload")]
+impl RegistryReader for ReloadableRegistry {
+    fn list_nodes<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        // The nodes are collected, since the registry may be replaced while they are read
+        let nodes = self.current()?.list_nodes(predicates)?.collect::<Vec<_>>();
+        Ok(Box::new(nodes.into_iter()))
+    }
+
+    fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+        self.current()?.count_nodes(predicates)
+    }
+
+    fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        self.current()?.get_node(identity)
+    }
+
+    fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
+        self.current()?.has_node(identity)
+    }
+}
+
+#[cfg(feature = "config-reload")]
+impl RegistryWriter for ReloadableRegistry {
+    fn add_node(&self, node: Node) -> Result<(), RegistryError> {
+        self.current()?.add_node(node)
+    }
+
+    fn update_node(&self, node: Node) -> Result<(), RegistryError> {
+        self.current()?.update_node(node)
+    }
+
+    fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        self.current()?.delete_node(identity)
+    }
+
+    #[cfg(feature = "registry-batch")]
+    fn apply_batch(
+        &self,
+        operations: Vec<NodeOperation>,
+    ) -> Result<NodeBatchResult, RegistryError> {
+        self.current()?.apply_batch(operations)
+    }
+}
+
+#[cfg(feature = "config-reload")]
+impl RwRegistry for ReloadableRegistry {
+    fn clone_box(&self) -> Box<dyn RwRegistry> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box_as_reader(&self) -> Box<dyn RegistryReader> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box_as_writer(&self) -> Box<dyn RegistryWriter> {
+        Box::new(self.clone())
+    }
+}
+
+/// Rebuilds the node's registry from the configured registry locations and refresh intervals when
+/// the node's configuration is reloaded.
+///
+/// The local registry and the remote registries added at runtime are kept as they are; only the
+/// configured read-only registries are recreated.
+#[cfg(feature = "config-reload")]
+pub struct RegistryReloader {
+    registry: ReloadableRegistry,
+    local_registry: Box<dyn RwRegistry>,
+    #[cfg(feature = "registry-sources")]
+    registry_sources: Option<RegistrySources>,
+    state_dir: String,
+    #[cfg(feature = "registry-parallel-fetch")]
+    fetch_options: RemoteYamlFetchOptions,
+}
+
+#[cfg(feature = "config-reload")]
+impl RegistryReloader {
+    pub fn new(
+        registry: ReloadableRegistry,
+        local_registry: Box<dyn RwRegistry>,
+        #[cfg(feature = "registry-sources")] registry_sources: Option<RegistrySources>,
+        state_dir: &str,
+        #[cfg(feature = "registry-parallel-fetch")] fetch_options: RemoteYamlFetchOptions,
+    ) -> Self {
+        Self {
+            registry,
+            local_registry,
+            #[cfg(feature = "registry-sources")]
+            registry_sources,
+            state_dir: state_dir.to_string(),
+            #[cfg(feature = "registry-parallel-fetch")]
+            fetch_options,
+        }
+    }
+
+    /// Replaces the configured read-only registries, fetching the remote registries right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `registries` - The locations of the read-only registries
+    /// * `auto_refresh_interval` - Seconds between automatic refreshes of the remote registries
+    /// * `forced_refresh_interval` - Seconds after which a read forces a refresh of the remote
+    ///   registries
+    pub fn reload(
+        &self,
+        registries: &[String],
+        auto_refresh_interval: u64,
+        forced_refresh_interval: u64,
+    ) -> Result<(), InternalError> {
+        let mut remote_shutdown_handle = RegistryShutdownHandle::new();
+        #[allow(unused_mut)]
+        let mut read_only_registries = super::create_read_only_registries(
+            &self.state_dir,
+            registries,
+            super::refresh_interval(auto_refresh_interval),
+            super::refresh_interval(forced_refresh_interval),
+            #[cfg(feature = "registry-parallel-fetch")]
+            self.fetch_options.clone(),
+            &mut remote_shutdown_handle,
+        );
+
+        #[cfg(feature = "registry-sources")]
+        if let Some(registry_sources) = &self.registry_sources {
+            read_only_registries.push(Box::new(registry_sources.clone()));
+        }
+
+        self.registry.replace(
+            UnifiedRegistry::new(self.local_registry.clone_box(), read_only_registries),
+            remote_shutdown_handle,
+        )?;
+
+        // The refresh endpoint holds the refresh handles of the registries created on startup
+        #[cfg(feature = "registry-refresh")]
+        warn!(
+            "Reloaded registries are refreshed on their configured intervals; the registry \
+            refresh endpoint will not refresh them until the node is restarted"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,487 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Reloads the node's configuration when the process receives `SIGHUP`.
+//!
+//! The configuration is read again from its sources and compared against the running
+//! configuration. The logging configuration and the read-only registries are applied right away;
+//! any other changed setting is reported as requiring a restart.
+
+use std::thread;
+
+use log4rs::Handle;
+use signal_hook::consts::SIGHUP;
+use signal_hook::iterator::{Handle as SignalsHandle, Signals};
+use splinter::error::InternalError;
+use splinter::threading::lifecycle::ShutdownHandle;
+
+use crate::config::Config;
+use crate::error::UserError;
+use crate::logging::configure_logging;
+
+use super::registry::RegistryReloader;
+
+/// The settings that are applied when the logging configuration is reloaded.
+const LOGGING_SETTINGS: &[&str] = &["root_logger", "appenders", "loggers", "verbosity"];
+
+/// The settings that are applied when the read-only registries are reloaded.
+const REGISTRY_SETTINGS: &[&str] = &[
+    "registries",
+    "registry_auto_refresh",
+    "registry_forced_refresh",
+];
+
+/// Loads the node's configuration from its sources.
+pub type ConfigLoader = Box<dyn Fn() -> Result<Config, UserError> + Send>;
+
+/// Returns the names of the settings that differ between the two configurations.
+///
+/// Each setting is compared through the `Config` getter of the same name.
+macro_rules! changed_settings {
+    ($running:expr, $loaded:expr, [$($(#[$attr:meta])* $setting:ident,)*]) => {{
+        let mut changed: Vec<&'static str> = vec![];
+        $(
+            $(#[$attr])*
+            if $running.$setting() != $loaded.$setting() {
+                changed.push(stringify!($setting));
+            }
+        )*
+        changed
+    }};
+}
+
+/// Reloads the node's configuration and applies the settings that can be changed while the node
+/// is running.
+pub struct ConfigReload {
+    loader: ConfigLoader,
+    // The configuration the node was started with; settings that differ from it require a restart
+    started: Config,
+    // The configuration the reloadable settings were last applied from
+    applied: Config,
+    log_handle: Handle,
+    registry_reloader: Option<RegistryReloader>,
+}
+
+impl ConfigReload {
+    /// Creates a new `ConfigReload`.
+    ///
+    /// # Arguments
+    ///
+    /// * `loader` - Loads the configuration from the same sources the node was started with
+    /// * `config` - The configuration the node was started with
+    /// * `log_handle` - The handle used to replace the logging configuration
+    pub fn new(loader: ConfigLoader, config: Config, log_handle: Handle) -> Self {
+        Self {
+            loader,
+            started: config.clone(),
+            applied: config,
+            log_handle,
+            registry_reloader: None,
+        }
+    }
+
+    /// Sets the reloader used to replace the node's read-only registries.
+    pub(super) fn with_registry_reloader(mut self, registry_reloader: RegistryReloader) -> Self {
+        self.registry_reloader = Some(registry_reloader);
+        self
+    }
+
+    /// Loads the configuration and applies the reloadable settings that have changed.
+    ///
+    /// If the configuration cannot be loaded or a setting cannot be applied, the node keeps the
+    /// configuration it is running with and an error is returned.
+    pub fn reload(&mut self) -> Result<ReloadReport, InternalError> {
+        let loaded = (self.loader)().map_err(|err| {
+            InternalError::with_message(format!("Unable to load configuration: {}", err))
+        })?;
+
+        let report = ReloadReport::new(&self.started, &self.applied, &loaded);
+
+        if report.includes_any(LOGGING_SETTINGS) {
+            configure_logging(&loaded, &self.log_handle).map_err(|err| {
+                InternalError::with_message(format!("Unable to reload logging: {}", err))
+            })?;
+        }
+
+        if report.includes_any(REGISTRY_SETTINGS) {
+            match &self.registry_reloader {
+                Some(registry_reloader) => registry_reloader.reload(
+                    loaded.registries(),
+                    loaded.registry_auto_refresh(),
+                    loaded.registry_forced_refresh(),
+                )?,
+                None => {
+                    return Err(InternalError::with_message(
+                        "Unable to reload registries: the registry is not reloadable".into(),
+                    ))
+                }
+            }
+        }
+
+        self.applied = loaded;
+
+        Ok(report)
+    }
+
+    /// Starts a thread that reloads the configuration each time the process receives `SIGHUP`.
+    pub(super) fn listen(mut self) -> Result<ConfigReloadListener, InternalError> {
+        let mut signals = Signals::new(&[SIGHUP]).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "Unable to register SIGHUP handler".into(),
+            )
+        })?;
+        let signals_handle = signals.handle();
+
+        let join_handle = thread::Builder::new()
+            .name("ConfigReload".into())
+            .spawn(move || {
+                for _ in signals.forever() {
+                    info!("Received SIGHUP, reloading configuration");
+                    match self.reload() {
+                        Ok(report) => report.log(),
+                        Err(err) => error!("Configuration was not reloaded: {}", err),
+                    }
+                }
+            })
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    "Unable to start config reload thread".into(),
+                )
+            })?;
+
+        Ok(ConfigReloadListener {
+            signals_handle,
+            join_handle,
+        })
+    }
+}
+
+/// The result of a configuration reload.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReloadReport {
+    applied: Vec<&'static str>,
+    restart_required: Vec<&'static str>,
+}
+
+impl ReloadReport {
+    /// Compares the loaded configuration against the running one.
+    ///
+    /// Reloadable settings are compared against the configuration they were last applied from;
+    /// all other settings are compared against the configuration the node was started with, so
+    /// that they are reported until the node is restarted.
+    fn new(started: &Config, applied: &Config, loaded: &Config) -> Self {
+        Self {
+            applied: changed_settings(applied, loaded)
+                .into_iter()
+                .filter(|setting| is_reloadable(setting))
+                .collect(),
+            restart_required: changed_settings(started, loaded)
+                .into_iter()
+                .filter(|setting| !is_reloadable(setting))
+                .collect(),
+        }
+    }
+
+    fn includes_any(&self, settings: &[&str]) -> bool {
+        self.applied
+            .iter()
+            .any(|setting| settings.contains(setting))
+    }
+
+    fn log(&self) {
+        if self.applied.is_empty() {
+            info!("Configuration reloaded, no reloadable settings changed");
+        } else {
+            info!(
+                "Configuration reloaded, applied changes to: {}",
+                self.applied.join(", ")
+            );
+        }
+
+        if !self.restart_required.is_empty() {
+            warn!(
+                "Changes to the following settings require a restart to take effect: {}",
+                self.restart_required.join(", ")
+            );
+        }
+    }
+}
+
+/// Shuts down the thread started by [`ConfigReload::listen`].
+pub struct ConfigReloadListener {
+    signals_handle: SignalsHandle,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl ShutdownHandle for ConfigReloadListener {
+    fn signal_shutdown(&mut self) {
+        self.signals_handle.close();
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message("Config reload thread panicked during shutdown".into())
+        })
+    }
+}
+
+fn is_reloadable(setting: &str) -> bool {
+    LOGGING_SETTINGS.contains(&setting) || REGISTRY_SETTINGS.contains(&setting)
+}
+
+/// Returns the names of all settings that differ between the two configurations.
+fn changed_settings(running: &Config, loaded: &Config) -> Vec<&'static str> {
+    changed_settings!(
+        running,
+        loaded,
+        [
+            config_dir,
+            tls_cert_dir,
+            tls_ca_file,
+            tls_client_cert,
+            tls_client_key,
+            tls_server_cert,
+            tls_server_key,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_cert,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_key,
+            #[cfg(feature = "service-endpoint")]
+            service_endpoint,
+            network_endpoints,
+            advertised_endpoints,
+            peers,
+            node_id,
+            display_name,
+            rest_api_endpoint,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_additional_endpoints,
+            #[cfg(feature = "rest-api-multi-bind")]
+            rest_api_unauthenticated_endpoints,
+            database,
+            registries,
+            registry_auto_refresh,
+            registry_forced_refresh,
+            #[cfg(feature = "registry-node-signing")]
+            registry_require_signed_nodes,
+            heartbeat,
+            admin_timeout,
+            state_dir,
+            tls_insecure,
+            no_tls,
+            #[cfg(feature = "rest-api-cors")]
+            allow_list,
+            #[cfg(feature = "biome-credentials")]
+            enable_biome_credentials,
+            #[cfg(feature = "oauth")]
+            oauth_provider,
+            #[cfg(feature = "oauth")]
+            oauth_client_id,
+            #[cfg(feature = "oauth")]
+            oauth_client_secret,
+            #[cfg(feature = "oauth")]
+            oauth_redirect_url,
+            #[cfg(feature = "oauth")]
+            oauth_openid_url,
+            #[cfg(feature = "oauth")]
+            oauth_openid_auth_params,
+            #[cfg(feature = "oauth")]
+            oauth_openid_scopes,
+            strict_ref_counts,
+            #[cfg(feature = "tap")]
+            influx_db,
+            #[cfg(feature = "tap")]
+            influx_url,
+            #[cfg(feature = "tap")]
+            influx_username,
+            #[cfg(feature = "tap")]
+            influx_password,
+            #[cfg(feature = "tap-backends")]
+            metrics_backend,
+            #[cfg(feature = "tap-backends")]
+            statsd_address,
+            #[cfg(feature = "tap-backends")]
+            otlp_url,
+            peering_key,
+            #[cfg(feature = "rbac-bootstrap")]
+            rbac_bootstrap_file,
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            bandwidth_limit_global,
+            #[cfg(feature = "mesh-bandwidth-throttle")]
+            bandwidth_limit_per_peer,
+            #[cfg(feature = "auth-handshake-limits")]
+            auth_handshake_timeout,
+            #[cfg(feature = "auth-handshake-limits")]
+            max_unauthorized_connections,
+            #[cfg(feature = "auth-handshake-limits")]
+            connection_rate_limit,
+            #[cfg(feature = "state-dir-health")]
+            state_dir_degraded_threshold,
+            #[cfg(feature = "challenge-endpoint-binding")]
+            challenge_endpoint_binding,
+            #[cfg(feature = "admin-service-proposal-timeout")]
+            admin_max_timeout,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_timeout,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_retries,
+            #[cfg(feature = "admin-service-consensus-retry")]
+            admin_verification_backoff,
+            #[cfg(feature = "health-service")]
+            health_check_interval,
+            #[cfg(feature = "circuit-max-message-size")]
+            max_message_size,
+            #[cfg(feature = "circuit-tombstones")]
+            circuit_tombstones,
+            #[cfg(feature = "tracing")]
+            tracing_otlp_url,
+            #[cfg(feature = "registry-parallel-fetch")]
+            registry_fetch_timeout,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_proposal_rate_limit,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_pending_proposals,
+            #[cfg(feature = "scabbard-shared-executor-pool")]
+            scabbard_executor_pool_size,
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_keepalive,
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_user_timeout,
+            #[cfg(feature = "transport-tcp-options")]
+            tcp_nodelay,
+            #[cfg(feature = "rest-api-read-only")]
+            rest_api_read_only,
+            #[cfg(feature = "tls-policy")]
+            tls_min_version,
+            #[cfg(feature = "tls-policy")]
+            tls_cipher_suites,
+            #[cfg(feature = "store-schema-check")]
+            auto_migrate,
+            #[cfg(feature = "store-lease")]
+            steal_lease,
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log,
+            #[cfg(feature = "circuit-access-log")]
+            circuit_access_log_retention,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention_age,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention_count,
+            #[cfg(feature = "transport-ipv6")]
+            preferred_address_family,
+            #[cfg(feature = "rest-api-split")]
+            core_endpoint,
+            #[cfg(feature = "outbound-only")]
+            outbound_only,
+            #[cfg(feature = "authorization-handler-provider")]
+            authorization_handlers,
+            #[cfg(feature = "service2")]
+            service_timer_interval,
+            #[cfg(feature = "service2")]
+            lifecycle_executor_interval,
+            #[cfg(feature = "service-orchestrator-limits")]
+            service_limits,
+            root_logger,
+            appenders,
+            loggers,
+            verbosity,
+            scabbard_state,
+            scabbard_autocleanup,
+        ]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::{ConfigBuilder, DefaultPartialConfigBuilder, TomlPartialConfigBuilder};
+
+    static TEST_TOML: &str = "config_test.toml";
+
+    fn build_config(toml_string: &str) -> Config {
+        let toml = TomlPartialConfigBuilder::new(toml_string.to_string(), TEST_TOML.to_string())
+            .expect("Could not deserialize toml")
+            .build()
+            .expect("A config error has occurred");
+        let default = DefaultPartialConfigBuilder::new()
+            .build()
+            .expect("Could not build default partial config");
+        ConfigBuilder::new()
+            .with_partial_config(toml)
+            .with_partial_config(default)
+            .build()
+            .expect("Could not build final Config")
+    }
+
+    /// Verifies that `changed_settings` returns exactly the settings whose values differ.
+    #[test]
+    fn test_changed_settings() {
+        let running = build_config(
+            r#"
+            version = "1"
+            rest_api_endpoint = "127.0.0.1:8080"
+            heartbeat = 30
+            "#,
+        );
+        let loaded = build_config(
+            r#"
+            version = "1"
+            rest_api_endpoint = "127.0.0.1:9090"
+            heartbeat = 30
+            registry_auto_refresh = 10
+            "#,
+        );
+
+        assert!(changed_settings(&running, &running.clone()).is_empty());
+        assert_eq!(
+            changed_settings(&running, &loaded),
+            vec!["rest_api_endpoint", "registry_auto_refresh"]
+        );
+    }
+
+    /// Verifies that a report separates the settings that are applied from those that require a
+    /// restart, and that a setting requiring a restart is reported until the node is restarted
+    /// while an applied setting is only reported once.
+    #[test]
+    fn test_reload_report() {
+        let started = build_config(
+            r#"
+            version = "1"
+            heartbeat = 30
+            "#,
+        );
+        let loaded = build_config(
+            r#"
+            version = "1"
+            heartbeat = 60
+            registries = ["file://./registry.yaml"]
+            registry_forced_refresh = 20
+            "#,
+        );
+
+        let report = ReloadReport::new(&started, &started, &started);
+        assert_eq!(report, ReloadReport::default());
+
+        let report = ReloadReport::new(&started, &started, &loaded);
+        assert_eq!(report.applied, &["registries", "registry_forced_refresh"]);
+        assert_eq!(report.restart_required, &["heartbeat"]);
+
+        // the reloadable settings have since been applied from the loaded configuration
+        let report = ReloadReport::new(&started, &loaded, &loaded);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.restart_required, &["heartbeat"]);
+    }
+}
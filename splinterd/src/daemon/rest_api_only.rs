@@ -0,0 +1,134 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runs the REST API of a Splinter daemon in its own process, separate from the daemon that
+//! runs the network and services.
+
+use std::sync::mpsc::channel;
+
+use cylinder::{secp256k1::Secp256k1Context, VerifierFactory};
+use splinter::rest_api::{RestApiBuilder, RestResourceProvider};
+use splinter_rest_api_actix_web_1::admin::{CircuitResourceProvider, ProposalResourceProvider};
+#[cfg(feature = "service-echo")]
+use splinter_rest_api_actix_web_1::echo::EchoStatsResourceProvider;
+use splinter_rest_api_actix_web_1::forward::CoreForwardResourceProvider;
+use splinter_rest_api_actix_web_1::open_api;
+#[cfg(feature = "service-relay")]
+use splinter_rest_api_actix_web_1::relay::RelayServiceEndpointProvider;
+use splinter_rest_api_actix_web_1::scabbard::ScabbardServiceEndpointProvider;
+
+use super::{store, SplinterDaemon, StartError};
+
+impl SplinterDaemon {
+    /// Starts only the REST API of the Splinter daemon.
+    ///
+    /// Endpoints backed by the database, such as circuits, proposals and Biome, are served
+    /// directly. Every other endpoint is forwarded to the Splinter daemon at the configured core
+    /// endpoint, which must use the same database. Requests are authorized before they are
+    /// forwarded, so the authorization configuration must match the daemon's. Any number of REST
+    /// API processes may be run against one daemon.
+    pub fn start_rest_api(&mut self) -> Result<(), StartError> {
+        let core_endpoint = self.core_endpoint.clone().ok_or_else(|| {
+            StartError::RestApiError("missing core endpoint configuration".into())
+        })?;
+
+        // The daemon owns the database schema, so pending migrations are never applied here
+        #[cfg(feature = "store-schema-check")]
+        store::check_store_schemas(&self.db_url, false)
+            .map_err(|err| StartError::StorageError(err.to_string()))?;
+
+        let connection_pool = store::create_connection_pool(&self.db_url).map_err(|err| {
+            StartError::StorageError(format!("Failed to initialize connection pool: {}", err))
+        })?;
+        let store_factory = store::create_store_factory(&connection_pool).map_err(|err| {
+            StartError::StorageError(format!("Failed to initialize store factory: {}", err))
+        })?;
+
+        #[cfg(not(feature = "https-bind"))]
+        let bind = self
+            .rest_api_endpoint
+            .strip_prefix("http://")
+            .unwrap_or(&self.rest_api_endpoint);
+
+        #[cfg(feature = "https-bind")]
+        let bind = self.build_rest_api_bind(&self.rest_api_endpoint)?;
+
+        let forward_resource_provider = CoreForwardResourceProvider::new(&core_endpoint)
+            .with_service_endpoints(&ScabbardServiceEndpointProvider::default());
+
+        #[cfg(feature = "service-relay")]
+        let forward_resource_provider = forward_resource_provider
+            .with_service_endpoints(&RelayServiceEndpointProvider::default());
+
+        let mut rest_api_builder = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(forward_resource_provider.resources())
+            .add_resources(
+                CircuitResourceProvider::new(store_factory.get_admin_service_store()).resources(),
+            )
+            .add_resources(
+                ProposalResourceProvider::new(store_factory.get_admin_service_store()).resources(),
+            )
+            .add_resources(open_api::OpenApiResourceProvider::default().resources());
+
+        #[cfg(feature = "authorization")]
+        {
+            rest_api_builder =
+                self.configure_rest_api_authorization(rest_api_builder, &*store_factory)?;
+        }
+
+        rest_api_builder = self.configure_rest_api_server(rest_api_builder)?;
+
+        rest_api_builder = rest_api_builder.with_auth_configs(
+            self.build_auth_configs(&*store_factory, Secp256k1Context::new().new_verifier())?,
+        );
+
+        rest_api_builder = self.add_store_resources(rest_api_builder, &*store_factory);
+
+        #[cfg(feature = "service-echo")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                EchoStatsResourceProvider::new(store::create_echo_store_factory(&connection_pool)?)
+                    .resources(),
+            );
+        }
+
+        info!(
+            "Starting REST API; forwarding requests to the Splinter daemon at {}",
+            core_endpoint
+        );
+        let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api_builder.build()?.run()?;
+
+        let (shutdown_tx, shutdown_rx) = channel();
+        ctrlc::set_handler(move || {
+            if shutdown_tx.send(()).is_err() {
+                // This was the second ctrl-c (as the receiver is dropped after the first one).
+                std::process::exit(0);
+            }
+        })
+        .expect("Error setting Ctrl-C handler");
+
+        // recv that value, ignoring the result.
+        let _ = shutdown_rx.recv();
+        drop(shutdown_rx);
+        info!("Initiating graceful shutdown (press Ctrl+C again to force)");
+
+        if let Err(err) = rest_api_shutdown_handle.shutdown() {
+            error!("Unable to cleanly shut down REST API server: {}", err);
+        }
+        let _ = rest_api_join_handle.join();
+
+        Ok(())
+    }
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(all(feature = "store-schema-check", feature = "database-sqlite"))]
+use std::path::Path;
 #[cfg(any(feature = "database-sqlite", feature = "scabbardv3"))]
 use std::sync::Arc;
 #[cfg(feature = "database-sqlite")]
@@ -19,6 +21,11 @@ use std::sync::RwLock;
 
 #[cfg(feature = "diesel")]
 use diesel::r2d2::{ConnectionManager, Pool};
+#[cfg(all(
+    feature = "store-schema-check",
+    any(feature = "database-postgres", feature = "database-sqlite")
+))]
+use diesel::Connection;
 #[cfg(feature = "database-postgres")]
 use splinter::store::postgres;
 #[cfg(feature = "database-sqlite")]
@@ -86,6 +93,151 @@ pub fn create_connection_pool(
     }
 }
 
+/// Checks that the database schema matches the schema expected by every store enabled in this
+/// build.
+///
+/// If any store has pending migrations, they are applied when `auto_migrate` is true; otherwise
+/// an error listing the pending migrations of each store is returned. The in-memory database is
+/// always created with the current schema, so it is not checked.
+///
+/// # Arguments
+///
+/// * `connection_uri` - The identifier of the database to check
+/// * `auto_migrate` - Whether pending migrations should be applied instead of returning an error
+#[cfg(feature = "store-schema-check")]
+pub fn check_store_schemas(
+    connection_uri: &ConnectionUri,
+    auto_migrate: bool,
+) -> Result<(), InternalError> {
+    match connection_uri {
+        #[cfg(feature = "database-postgres")]
+        ConnectionUri::Postgres(url) => {
+            let conn = diesel::pg::PgConnection::establish(url)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            check_schemas(&conn, &postgres_store_migrations(), auto_migrate)
+        }
+        #[cfg(feature = "database-sqlite")]
+        ConnectionUri::Sqlite(conn_str) => {
+            // A missing database file is reported when the connection pool is created
+            if !Path::new(conn_str).exists() {
+                return Ok(());
+            }
+            let conn = diesel::SqliteConnection::establish(conn_str)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            check_schemas(&conn, &sqlite_store_migrations(), auto_migrate)
+        }
+        ConnectionUri::Memory => Ok(()),
+    }
+}
+
+/// The functions used to inspect and apply the migrations of a single store
+#[cfg(all(
+    feature = "store-schema-check",
+    any(feature = "database-postgres", feature = "database-sqlite")
+))]
+struct StoreMigrations<C> {
+    store: &'static str,
+    list_pending: fn(&C) -> Result<Vec<String>, InternalError>,
+    run: fn(&C) -> Result<(), InternalError>,
+}
+
+#[cfg(all(feature = "store-schema-check", feature = "database-postgres"))]
+fn postgres_store_migrations() -> Vec<StoreMigrations<diesel::pg::PgConnection>> {
+    let stores = vec![
+        StoreMigrations {
+            store: "splinter",
+            list_pending: splinter::migrations::list_pending_postgres_migrations,
+            run: splinter::migrations::run_postgres_migrations,
+        },
+        StoreMigrations {
+            store: "scabbard",
+            list_pending: scabbard::migrations::list_pending_postgres_migrations,
+            run: scabbard::migrations::run_postgres_migrations,
+        },
+    ];
+
+    #[cfg(feature = "service-echo")]
+    let stores = {
+        let mut stores = stores;
+        stores.push(StoreMigrations {
+            store: "echo",
+            list_pending: splinter_echo::migrations::list_pending_postgres_migrations,
+            run: splinter_echo::migrations::run_postgres_migrations,
+        });
+        stores
+    };
+
+    stores
+}
+
+#[cfg(all(feature = "store-schema-check", feature = "database-sqlite"))]
+fn sqlite_store_migrations() -> Vec<StoreMigrations<diesel::SqliteConnection>> {
+    let stores = vec![
+        StoreMigrations {
+            store: "splinter",
+            list_pending: splinter::migrations::list_pending_sqlite_migrations,
+            run: splinter::migrations::run_sqlite_migrations,
+        },
+        StoreMigrations {
+            store: "scabbard",
+            list_pending: scabbard::migrations::list_pending_sqlite_migrations,
+            run: scabbard::migrations::run_sqlite_migrations,
+        },
+    ];
+
+    #[cfg(feature = "service-echo")]
+    let stores = {
+        let mut stores = stores;
+        stores.push(StoreMigrations {
+            store: "echo",
+            list_pending: splinter_echo::migrations::list_pending_sqlite_migrations,
+            run: splinter_echo::migrations::run_sqlite_migrations,
+        });
+        stores
+    };
+
+    stores
+}
+
+#[cfg(all(
+    feature = "store-schema-check",
+    any(feature = "database-postgres", feature = "database-sqlite")
+))]
+fn check_schemas<C>(
+    conn: &C,
+    stores: &[StoreMigrations<C>],
+    auto_migrate: bool,
+) -> Result<(), InternalError> {
+    let mut outdated = vec![];
+    for store in stores {
+        let pending = (store.list_pending)(conn)?;
+        if pending.is_empty() {
+            continue;
+        }
+
+        if auto_migrate {
+            info!(
+                "Applying pending {} store migrations: {}",
+                store.store,
+                pending.join(", ")
+            );
+            (store.run)(conn)?;
+        } else {
+            outdated.push(format!("{} ({})", store.store, pending.join(", ")));
+        }
+    }
+
+    if outdated.is_empty() {
+        Ok(())
+    } else {
+        Err(InternalError::with_message(format!(
+            "The database schema is out of date; pending migrations for stores: {}. \
+            Run `splinter database migrate` or set `auto_migrate = true` to apply them",
+            outdated.join("; ")
+        )))
+    }
+}
+
 /// Creates a `StoreFactory` backed by the given connection
 ///
 /// # Arguments
@@ -17,6 +17,8 @@ extern crate log;
 #[macro_use]
 extern crate clap;
 
+#[cfg(feature = "build-info")]
+mod build_info;
 mod config;
 mod daemon;
 mod error;
@@ -24,20 +26,40 @@ mod logging;
 pub mod node_id;
 mod transport;
 
-use cylinder::{load_key_from_path, secp256k1::Secp256k1Context, Context, Signer};
+#[cfg(not(feature = "key-pem"))]
+use cylinder::load_key_from_path;
+use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
 use log4rs::Handle;
 use logging::{configure_logging, default_log_settings};
 
+#[cfg(feature = "admin-service-proposal-limits")]
+use splinter::admin::service::ProposalLimits;
 use splinter::error::InternalError;
+#[cfg(feature = "key-pem")]
+use splinter::keys::encoding::decode_private_key;
 use splinter::peer::PeerAuthorizationToken;
+#[cfg(feature = "service-orchestrator-limits")]
+use splinter::runtime::service::instance::{ResourceLimits, RestartPolicy};
 #[cfg(feature = "tap")]
 use splinter::tap::influx::InfluxRecorder;
+#[cfg(feature = "tap-backends")]
+use splinter::tap::{
+    init_recorder,
+    snapshot::{init_snapshot_only_recorder, MetricsSnapshot},
+    GlobalLabels,
+};
+#[cfg(feature = "tap-backends")]
+use splinter::tap::{otlp::OtlpRecorder, statsd::StatsdRecorder};
+#[cfg(feature = "tls-policy")]
+use splinter::transport::tls::TlsPolicy;
 
 use crate::config::{
     ClapPartialConfigBuilder, Config, ConfigBuilder, ConfigError, DefaultPartialConfigBuilder,
     EnvPartialConfigBuilder, PartialConfigBuilder, TomlPartialConfigBuilder,
 };
 use crate::daemon::builder::SplinterDaemonBuilder;
+#[cfg(feature = "config-reload")]
+use crate::daemon::reload::ConfigReload;
 use clap::{clap_app, crate_version};
 use clap::{Arg, ArgMatches};
 
@@ -123,9 +145,26 @@ fn load_signer_keys(
             .path();
 
         if path.extension() == Some(OsStr::new("priv")) {
+            #[cfg(not(feature = "key-pem"))]
             let private_key = load_key_from_path(&path).map_err(|err| {
                 UserError::InternalError(InternalError::from_source(Box::new(err)))
             })?;
+            #[cfg(feature = "key-pem")]
+            let private_key = fs::read_to_string(&path)
+                .map_err(|err| {
+                    UserError::io_err_with_source(
+                        &format!("Unable to read key file {}", path.display()),
+                        Box::new(err),
+                    )
+                })
+                .and_then(|contents| {
+                    decode_private_key(&contents).map_err(|err| {
+                        UserError::InternalError(InternalError::from_source_with_message(
+                            Box::new(err),
+                            format!("Unable to load key file {}", path.display()),
+                        ))
+                    })
+                })?;
             let signing_key = Secp256k1Context::new().new_signer(private_key);
 
             if path.file_stem() == Some(OsStr::new(peering_key)) {
@@ -197,6 +236,14 @@ fn load_signer_keys(
 }
 
 fn main() {
+    #[cfg(feature = "build-info")]
+    {
+        if build_info::is_verbose_version_request(&env::args_os().collect::<Vec<_>>()) {
+            println!("{}", build_info::verbose_version());
+            return;
+        }
+    }
+
     let app = clap_app!(splinterd =>
         (version: crate_version!())
         (about: "Splinter Daemon")
@@ -258,7 +305,10 @@ fn main() {
         .arg(
             Arg::with_name("service_endpoint")
                 .long("service-endpoint")
-                .long_help("Endpoint that service will connect to, tcp://ip:port")
+                .long_help(
+                    "Endpoint that service will connect to, tcp://ip:port or \
+                    unix:///path/to/socket",
+                )
                 .takes_value(true)
                 .hidden(!cfg!(feature = "service-endpoint")),
         )
@@ -381,6 +431,41 @@ fn main() {
             .alias("rest-api-key"),
     );
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    let app = app
+        .arg(
+            Arg::with_name("rest_api_additional_endpoints")
+                .long("rest-api-additional-endpoint")
+                .multiple(true)
+                .takes_value(true)
+                .use_delimiter(true)
+                .help(
+                    "Additional endpoint for the REST API which requires authorization, \
+                    http://ip:port, https://ip:port or unix:///path/to/socket",
+                ),
+        )
+        .arg(
+            Arg::with_name("rest_api_unauthenticated_endpoints")
+                .long("rest-api-unauthenticated-endpoint")
+                .multiple(true)
+                .takes_value(true)
+                .use_delimiter(true)
+                .help(
+                    "Additional endpoint for the REST API which does not require \
+                    authorization, usually unix:///path/to/socket",
+                ),
+        );
+
+    #[cfg(feature = "registry-node-signing")]
+    let app = app.arg(
+        Arg::with_name("registry_require_signed_nodes")
+            .long("registry-require-signed-nodes")
+            .help(
+                "Only trust registry entries that were signed by the node itself when \
+                 validating circuit proposals",
+            ),
+    );
+
     #[cfg(feature = "rest-api-cors")]
     let app = app.arg(
         Arg::with_name("allow_list")
@@ -487,6 +572,447 @@ fn main() {
                 .takes_value(true),
         );
 
+    #[cfg(feature = "tap-backends")]
+    let app = app
+        .arg(
+            Arg::with_name("metrics_backend")
+                .long("metrics-backend")
+                .value_name("backend")
+                .possible_values(&["influx", "statsd", "otlp"])
+                .long_help(
+                    "The backend metrics are sent to; defaults to influx if the InfluxDB \
+                    options are set",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("statsd_address")
+                .long("statsd-address")
+                .value_name("host:port")
+                .long_help("The address of the statsd daemon metrics are sent to")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("otlp_url")
+                .long("otlp-url")
+                .value_name("url")
+                .long_help(
+                    "The URL of the OpenTelemetry collector metrics are exported to, using \
+                    OTLP/HTTP",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "rbac-bootstrap")]
+    let app = app.arg(
+        Arg::with_name("rbac_bootstrap_file")
+            .long("rbac-bootstrap-file")
+            .value_name("file")
+            .long_help(
+                "YAML file of roles and assignments to seed the role-based authorization \
+                store with at startup; relative paths are resolved against the config \
+                directory",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    let app = app
+        .arg(
+            Arg::with_name("bandwidth_limit_global")
+                .long("bandwidth-limit-global")
+                .value_name("bytes-per-second")
+                .long_help(
+                    "Maximum number of bytes per second this node sends to all of its peers \
+                    combined",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("bandwidth_limit_per_peer")
+                .long("bandwidth-limit-per-peer")
+                .value_name("bytes-per-second")
+                .long_help("Maximum number of bytes per second this node sends to any one peer")
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "auth-handshake-limits")]
+    let app = app
+        .arg(
+            Arg::with_name("auth_handshake_timeout")
+                .long("auth-handshake-timeout")
+                .value_name("seconds")
+                .long_help(
+                    "Number of seconds a connection may take to complete authorization before \
+                    it is dropped",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max_unauthorized_connections")
+                .long("max-unauthorized-connections")
+                .value_name("count")
+                .long_help(
+                    "Maximum number of inbound connections that may be waiting on authorization \
+                    at once",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("connection_rate_limit")
+                .long("connection-rate-limit")
+                .value_name("connections-per-minute")
+                .long_help(
+                    "Maximum number of inbound connections accepted from a single IP address \
+                    per minute",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "state-dir-health")]
+    let app = app.arg(
+        Arg::with_name("state_dir_degraded_threshold")
+            .long("state-dir-degraded-threshold")
+            .value_name("percent")
+            .long_help(
+                "Percentage of the state directory's volume that may be in use before the node \
+                reports degraded health",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    let app = app.arg(
+        Arg::with_name("challenge_endpoint_binding")
+            .long("challenge-endpoint-binding")
+            .value_name("mode")
+            .possible_values(&["off", "warn", "enforce"])
+            .long_help(
+                "How challenge authorization binds peer signatures to the endpoint that was \
+                dialed; defaults to off",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    let app = app.arg(
+        Arg::with_name("admin_max_timeout")
+            .long("admin-max-timeout")
+            .value_name("timeout")
+            .long_help(
+                "The longest coordinator timeout, in seconds, that a circuit management payload \
+                may request for its proposal; defaults to 300 seconds, or the admin timeout if \
+                that is longer",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    let app = app
+        .arg(
+            Arg::with_name("admin_verification_timeout")
+                .long("admin-verification-timeout")
+                .value_name("timeout")
+                .long_help(
+                    "How long, in seconds, the two-phase commit coordinator waits for \
+                    verification responses before re-sending its verification request; defaults \
+                    to the admin timeout",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin_verification_retries")
+                .long("admin-verification-retries")
+                .value_name("retries")
+                .long_help(
+                    "How many times the two-phase commit coordinator re-sends its verification \
+                    request to members that have not responded before rejecting the proposal; \
+                    defaults to 0",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin_verification_backoff")
+                .long("admin-verification-backoff")
+                .value_name("factor")
+                .long_help(
+                    "The factor by which the two-phase commit coordinator multiplies its wait \
+                    after each verification retry; defaults to 1",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "health-service")]
+    let app = app.arg(
+        Arg::with_name("health_check_interval")
+            .long("health-check-interval")
+            .value_name("interval")
+            .long_help(
+                "How long, in seconds, to wait between runs of the node's health checks; \
+                defaults to 30",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "circuit-max-message-size")]
+    let app = app.arg(
+        Arg::with_name("max_message_size")
+            .long("max-message-size")
+            .value_name("bytes")
+            .long_help(
+                "The largest service message payload, in bytes, that this node accepts on a \
+                circuit; new circuits are limited to the smallest value configured by their \
+                members",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "circuit-tombstones")]
+    let app = app.arg(
+        Arg::with_name("circuit_tombstones")
+            .long("circuit-tombstones")
+            .long_help(
+                "Keep purged circuits in the admin store as tombstones, marked with the time and \
+                reason they were purged, instead of removing them; tombstones can be removed \
+                later with `splinter database gc`",
+            ),
+    );
+
+    #[cfg(feature = "tracing")]
+    let app = app.arg(
+        Arg::with_name("tracing_otlp_url")
+            .long("tracing-otlp-url")
+            .value_name("URL")
+            .long_help(
+                "Export trace spans for REST API requests, admin proposals, peer message \
+                dispatch and scabbard batch execution to the OpenTelemetry collector at this \
+                URL, using OTLP over HTTP",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    let app = app
+        .arg(
+            Arg::with_name("admin_proposal_rate_limit")
+                .long("admin-proposal-rate-limit")
+                .value_name("COUNT")
+                .long_help(
+                    "The number of proposals each requester key may submit per minute; proposals \
+                    beyond the limit are rejected until the requester's oldest proposal is more \
+                    than a minute old. Defaults to 0, which is unlimited",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin_max_pending_proposals")
+                .long("admin-max-pending-proposals")
+                .value_name("COUNT")
+                .long_help(
+                    "The number of pending proposals at which new proposals are rejected; votes \
+                    are still accepted. Defaults to 0, which is unlimited",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    let app = app.arg(
+        Arg::with_name("scabbard_executor_pool_size")
+            .long("scabbard-executor-pool-size")
+            .value_name("COUNT")
+            .long_help(
+                "The number of threads shared by all scabbard services for executing batches. \
+                Defaults to 0, in which case each service starts its own executor",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    let app = app.arg(
+        Arg::with_name("registry_fetch_timeout")
+            .long("registry-fetch-timeout")
+            .value_name("SECONDS")
+            .long_help(
+                "Time, in seconds, allowed for fetching a remote registry file before the fetch \
+                fails; remote registries are fetched concurrently, each with its own timeout",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "transport-tcp-options")]
+    let app = app
+        .arg(
+            Arg::with_name("tcp_keepalive")
+                .long("tcp-keepalive")
+                .value_name("seconds")
+                .long_help(
+                    "The time, in seconds, a peer connection may be idle before TCP keepalive \
+                    probes are sent; keepalive is disabled by default",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tcp_user_timeout")
+                .long("tcp-user-timeout")
+                .value_name("seconds")
+                .long_help(
+                    "The time, in seconds, data sent on a peer connection may remain \
+                    unacknowledged before the connection is closed; only supported on Linux",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tcp_nodelay")
+                .long("tcp-nodelay")
+                .help("Disable Nagle's algorithm on peer connections"),
+        );
+
+    #[cfg(feature = "rest-api-read-only")]
+    let app = app.arg(
+        Arg::with_name("rest_api_read_only")
+            .long("rest-api-read-only")
+            .help(
+                "Serve only GET requests on the REST API; all mutating requests are rejected \
+                 with 403 Forbidden",
+            ),
+    );
+
+    #[cfg(feature = "tls-policy")]
+    let app = app
+        .arg(
+            Arg::with_name("tls_min_version")
+                .long("tls-min-version")
+                .value_name("version")
+                .possible_values(&["1.2", "1.3"])
+                .long_help(
+                    "The oldest TLS protocol version accepted on TLS network connections and \
+                    the HTTPS REST API; defaults to the OpenSSL settings",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tls_cipher_suites")
+                .long("tls-cipher-suites")
+                .value_name("cipher-suites")
+                .multiple(true)
+                .use_delimiter(true)
+                .long_help(
+                    "The cipher suites allowed on TLS network connections and the HTTPS REST \
+                    API, by IANA or OpenSSL name; defaults to the OpenSSL settings",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "store-schema-check")]
+    let app = app.arg(Arg::with_name("auto_migrate").long("auto-migrate").help(
+        "Apply pending database migrations at startup instead of exiting with an error \
+                 when the database schema is out of date",
+    ));
+
+    #[cfg(feature = "store-lease")]
+    let app = app.arg(Arg::with_name("steal_lease").long("steal-lease").help(
+        "Start even if another splinterd appears to be running against the same database, \
+                 taking over its lease on the database",
+    ));
+
+    #[cfg(feature = "circuit-access-log")]
+    let app = app
+        .arg(
+            Arg::with_name("circuit_access_log")
+                .long("circuit-access-log")
+                .value_name("circuit-ids")
+                .multiple(true)
+                .use_delimiter(true)
+                .long_help(
+                    "The circuits whose service payloads are recorded in the circuit access \
+                    log, or '*' for all circuits",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("circuit_access_log_retention")
+                .long("circuit-access-log-retention")
+                .value_name("days")
+                .long_help(
+                    "The number of days circuit access records are kept; records are kept \
+                    indefinitely if not set",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "admin-service-event-retention")]
+    let app = app
+        .arg(
+            Arg::with_name("admin_event_retention_age")
+                .long("admin-event-retention-age")
+                .value_name("days")
+                .long_help(
+                    "The number of days admin service events are kept; events are kept \
+                    indefinitely if neither this nor --admin-event-retention-count is set",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin_event_retention_count")
+                .long("admin-event-retention-count")
+                .value_name("count")
+                .long_help(
+                    "The maximum number of admin service events kept; older events are \
+                    removed once this count is exceeded",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "transport-ipv6")]
+    let app = app.arg(
+        Arg::with_name("preferred_address_family")
+            .long("preferred-address-family")
+            .value_name("family")
+            .possible_values(&["ipv4", "ipv6"])
+            .long_help(
+                "The address family whose peer endpoints are tried first when a peer has \
+                both IPv4 and IPv6 endpoints",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "outbound-only")]
+    let app = app.arg(
+        Arg::with_name("outbound_only")
+            .long("outbound-only")
+            .conflicts_with_all(&["network_endpoints", "advertised_endpoints"])
+            .long_help(
+                "Run without network listeners, connecting to all peers outbound; for nodes \
+                that cannot accept inbound connections, such as those behind NAT",
+            ),
+    );
+
+    #[cfg(feature = "authorization-handler-provider")]
+    let app = app.arg(
+        Arg::with_name("authorization_handlers")
+            .long("authorization-handlers")
+            .value_name("handlers")
+            .multiple(true)
+            .use_delimiter(true)
+            .long_help(
+                "The REST API authorization handlers to enable, in the order they are \
+                consulted, such as allow-keys, maintenance, or rbac",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "rest-api-split")]
+    let app = app.arg(
+        Arg::with_name("core_endpoint")
+            .long("core-endpoint")
+            .value_name("url")
+            .long_help(
+                "Run only the REST API, forwarding requests that need the running daemon to \
+                the REST API of the splinterd at this URL, such as http://127.0.0.1:8085",
+            )
+            .takes_value(true),
+    );
+
     #[cfg(feature = "service-timer-interval")]
     let app = app.arg(
         Arg::with_name("service_timer_interval")
@@ -543,30 +1069,47 @@ fn main() {
     }
 }
 
+/// Returns the InfluxDB url, database, username and password, if any of them are configured.
 #[cfg(feature = "tap")]
-fn setup_metrics_recorder(config: &Config) -> Result<(), UserError> {
+fn influx_settings(config: &Config) -> Result<Option<(&str, &str, &str, &str)>, UserError> {
     let metrics_configured = config.influx_db().is_some()
         || config.influx_url().is_some()
         || config.influx_username().is_some()
         || config.influx_password().is_some();
 
-    if metrics_configured {
-        let influx_db = config.influx_db().ok_or_else(|| {
-            UserError::MissingArgument("missing metrics db provider configuration".into())
-        })?;
+    if !metrics_configured {
+        return Ok(None);
+    }
 
-        let influx_url = config.influx_url().ok_or_else(|| {
-            UserError::MissingArgument("missing metrics url provider configuration".into())
-        })?;
+    let influx_db = config.influx_db().ok_or_else(|| {
+        UserError::MissingArgument("missing metrics db provider configuration".into())
+    })?;
 
-        let influx_username = config.influx_username().ok_or_else(|| {
-            UserError::MissingArgument("missing metrics username provider configuration".into())
-        })?;
+    let influx_url = config.influx_url().ok_or_else(|| {
+        UserError::MissingArgument("missing metrics url provider configuration".into())
+    })?;
 
-        let influx_password = config.influx_password().ok_or_else(|| {
-            UserError::MissingArgument("missing metrics password provider configuration".into())
-        })?;
+    let influx_username = config.influx_username().ok_or_else(|| {
+        UserError::MissingArgument("missing metrics username provider configuration".into())
+    })?;
+
+    let influx_password = config.influx_password().ok_or_else(|| {
+        UserError::MissingArgument("missing metrics password provider configuration".into())
+    })?;
+
+    Ok(Some((
+        influx_url,
+        influx_db,
+        influx_username,
+        influx_password,
+    )))
+}
 
+#[cfg(all(feature = "tap", not(feature = "tap-backends")))]
+fn setup_metrics_recorder(config: &Config) -> Result<(), UserError> {
+    if let Some((influx_url, influx_db, influx_username, influx_password)) =
+        influx_settings(config)?
+    {
         InfluxRecorder::init(influx_url, influx_db, influx_username, influx_password)
             .map_err(UserError::InternalError)?
     }
@@ -574,6 +1117,74 @@ fn setup_metrics_recorder(config: &Config) -> Result<(), UserError> {
     Ok(())
 }
 
+/// Installs the recorder for the configured metrics backend. The given labels are applied to
+/// every metric the node emits. If a snapshot is given, the latest metric values are kept in it,
+/// even if no backend is configured.
+#[cfg(feature = "tap-backends")]
+fn setup_metrics_recorder(
+    config: &Config,
+    labels: GlobalLabels,
+    snapshot: Option<MetricsSnapshot>,
+) -> Result<(), UserError> {
+    let backend = match config.metrics_backend() {
+        Some(backend) => backend,
+        None if influx_settings(config)?.is_some() => "influx",
+        None => {
+            if let Some(snapshot) = snapshot {
+                init_snapshot_only_recorder(snapshot).map_err(UserError::InternalError)?;
+            }
+            return Ok(());
+        }
+    };
+
+    match backend {
+        "influx" => {
+            let (influx_url, influx_db, influx_username, influx_password) =
+                influx_settings(config)?.ok_or_else(|| {
+                    UserError::MissingArgument("missing metrics url provider configuration".into())
+                })?;
+            InfluxRecorder::new(influx_url, influx_db, influx_username, influx_password)
+                .and_then(|recorder| init_recorder(recorder, labels, snapshot))
+        }
+        "statsd" => {
+            let statsd_address = config.statsd_address().ok_or_else(|| {
+                UserError::MissingArgument("missing statsd address configuration".into())
+            })?;
+            StatsdRecorder::new(statsd_address)
+                .and_then(|recorder| init_recorder(recorder, labels, snapshot))
+        }
+        "otlp" => {
+            let otlp_url = config.otlp_url().ok_or_else(|| {
+                UserError::MissingArgument("missing OTLP collector url configuration".into())
+            })?;
+            OtlpRecorder::new(otlp_url)
+                .and_then(|recorder| init_recorder(recorder, labels, snapshot))
+        }
+        backend => {
+            return Err(UserError::InvalidArgument(format!(
+                "unknown metrics backend: {}",
+                backend
+            )))
+        }
+    }
+    .map_err(UserError::InternalError)
+}
+
+/// Builds the TLS policy from the configuration, rejecting unknown cipher suites so that a typo
+/// does not silently fall back to weaker defaults.
+#[cfg(feature = "tls-policy")]
+fn build_tls_policy(config: &Config) -> Result<TlsPolicy, UserError> {
+    let tls_policy = TlsPolicy::new(
+        config.tls_min_version(),
+        config.tls_cipher_suites().to_vec(),
+    )
+    .map_err(|err| UserError::InvalidArgument(format!("Invalid TLS policy: {}", err)))?;
+
+    info!("Using TLS policy: {}", tls_policy);
+
+    Ok(tls_policy)
+}
+
 fn get_config_file(matches: &'_ ArgMatches) -> Result<String, UserError> {
     if let Some(value) = matches.value_of("config") {
         return Ok(value.to_string());
@@ -605,7 +1216,7 @@ fn get_config_file(matches: &'_ ArgMatches) -> Result<String, UserError> {
     Ok("/etc/splinter/splinterd.toml".to_string())
 }
 
-fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError> {
+fn start_daemon(matches: ArgMatches<'static>, log_handle: Handle) -> Result<(), UserError> {
     // get provided config file or search default location
     let config_file = get_config_file(&matches)?;
 
@@ -642,11 +1253,58 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
         }
     }
 
+    #[cfg(feature = "transport-ipv6")]
+    for endpoint in config
+        .network_endpoints()
+        .iter()
+        .chain(config.advertised_endpoints())
+        .chain(config.peers())
+    {
+        splinter::transport::address::validate_endpoint(endpoint)
+            .map_err(|err| UserError::InvalidArgument(err.to_string()))?;
+    }
+
+    #[cfg(feature = "outbound-only")]
+    if config.outbound_only() {
+        if !config.network_endpoints().is_empty() {
+            return Err(UserError::InvalidArgument(format!(
+                "an outbound-only node cannot listen on network endpoints {:?}",
+                config.network_endpoints()
+            )));
+        }
+        if !config.advertised_endpoints().is_empty() {
+            return Err(UserError::InvalidArgument(format!(
+                "an outbound-only node cannot advertise endpoints {:?}",
+                config.advertised_endpoints()
+            )));
+        }
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    let preferred_address_family = config
+        .preferred_address_family()
+        .map(|family| {
+            family
+                .parse::<splinter::transport::address::AddressFamily>()
+                .map_err(|err| UserError::InvalidArgument(err.to_string()))
+        })
+        .transpose()?;
+
     // set up metric recorder as soon as possible
-    #[cfg(feature = "tap")]
+    #[cfg(all(feature = "tap", not(feature = "tap-backends")))]
     setup_metrics_recorder(&config)?;
-
-    let transport = build_transport(&config)?;
+    #[cfg(feature = "tap-backends")]
+    let metrics_labels = GlobalLabels::new();
+    // the snapshot is only kept if it can be read through the REST API
+    #[cfg(all(feature = "tap-backends", not(feature = "metrics-snapshot")))]
+    let metrics_snapshot = None;
+    #[cfg(feature = "metrics-snapshot")]
+    let metrics_snapshot = Some(MetricsSnapshot::new());
+    #[cfg(feature = "tap-backends")]
+    setup_metrics_recorder(&config, metrics_labels.clone(), metrics_snapshot.clone())?;
+
+    #[cfg(feature = "tls-policy")]
+    let tls_policy = build_tls_policy(&config)?;
 
     let rest_api_endpoint = config.rest_api_endpoint();
 
@@ -702,6 +1360,29 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
         }
     }
 
+    #[cfg(feature = "rest-api-multi-bind")]
+    {
+        daemon_builder = daemon_builder
+            .with_rest_api_additional_endpoints(
+                config
+                    .rest_api_additional_endpoints()
+                    .map(ToOwned::to_owned)
+                    .unwrap_or_default(),
+            )
+            .with_rest_api_unauthenticated_endpoints(
+                config
+                    .rest_api_unauthenticated_endpoints()
+                    .map(ToOwned::to_owned)
+                    .unwrap_or_default(),
+            );
+    }
+
+    #[cfg(feature = "registry-node-signing")]
+    {
+        daemon_builder = daemon_builder
+            .with_registry_require_signed_nodes(config.registry_require_signed_nodes());
+    }
+
     #[cfg(feature = "rest-api-cors")]
     {
         daemon_builder = daemon_builder.with_allow_list(config.allow_list().map(ToOwned::to_owned));
@@ -733,6 +1414,202 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
         }
     }
 
+    #[cfg(feature = "tap-backends")]
+    {
+        daemon_builder = daemon_builder.with_metrics_labels(metrics_labels);
+    }
+
+    #[cfg(feature = "metrics-snapshot")]
+    if let Some(metrics_snapshot) = metrics_snapshot {
+        daemon_builder = daemon_builder.with_metrics_snapshot(metrics_snapshot);
+    }
+
+    #[cfg(feature = "rbac-bootstrap")]
+    {
+        daemon_builder = daemon_builder.with_rbac_bootstrap_file(
+            config
+                .rbac_bootstrap_file()
+                .map(|file| Path::new(config.config_dir()).join(file))
+                .map(|path| path.to_string_lossy().into_owned()),
+        );
+    }
+
+    #[cfg(feature = "mesh-bandwidth-throttle")]
+    {
+        daemon_builder = daemon_builder.with_bandwidth_limits(
+            config.bandwidth_limit_global(),
+            config.bandwidth_limit_per_peer(),
+        );
+    }
+
+    #[cfg(feature = "auth-handshake-limits")]
+    {
+        daemon_builder = daemon_builder
+            .with_auth_handshake_timeout(
+                config
+                    .auth_handshake_timeout()
+                    .map(std::time::Duration::from_secs),
+            )
+            .with_inbound_connection_limits(
+                config.max_unauthorized_connections(),
+                config.connection_rate_limit(),
+            );
+    }
+
+    #[cfg(feature = "state-dir-health")]
+    {
+        daemon_builder =
+            daemon_builder.with_state_dir_degraded_threshold(config.state_dir_degraded_threshold());
+    }
+
+    #[cfg(feature = "challenge-endpoint-binding")]
+    {
+        daemon_builder =
+            daemon_builder.with_challenge_endpoint_binding(config.challenge_endpoint_binding());
+    }
+
+    #[cfg(feature = "admin-service-proposal-timeout")]
+    {
+        daemon_builder = daemon_builder.with_admin_max_timeout(config.admin_max_timeout());
+    }
+
+    #[cfg(feature = "admin-service-consensus-retry")]
+    {
+        daemon_builder = daemon_builder
+            .with_admin_verification_timeout(config.admin_verification_timeout())
+            .with_admin_verification_retries(config.admin_verification_retries())
+            .with_admin_verification_backoff(config.admin_verification_backoff());
+    }
+
+    #[cfg(feature = "health-service")]
+    {
+        daemon_builder = daemon_builder.with_health_check_interval(config.health_check_interval());
+    }
+
+    #[cfg(feature = "circuit-max-message-size")]
+    {
+        daemon_builder = daemon_builder.with_max_message_size(config.max_message_size());
+    }
+
+    #[cfg(feature = "circuit-tombstones")]
+    {
+        daemon_builder = daemon_builder.with_circuit_tombstones(config.circuit_tombstones());
+    }
+
+    #[cfg(feature = "tracing")]
+    {
+        daemon_builder =
+            daemon_builder.with_tracing_otlp_url(config.tracing_otlp_url().map(String::from));
+    }
+
+    #[cfg(feature = "registry-parallel-fetch")]
+    {
+        daemon_builder =
+            daemon_builder.with_registry_fetch_timeout(config.registry_fetch_timeout());
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    {
+        let rate_limit = config
+            .admin_proposal_rate_limit()
+            .filter(|limit| *limit > 0);
+        let max_pending = config
+            .admin_max_pending_proposals()
+            .filter(|limit| *limit > 0);
+        let proposal_limits = if rate_limit.is_some() || max_pending.is_some() {
+            let mut proposal_limits = ProposalLimits::new();
+            if let Some(rate_limit) = rate_limit {
+                proposal_limits = proposal_limits
+                    .with_requester_rate(rate_limit, std::time::Duration::from_secs(60));
+            }
+            if let Some(max_pending) = max_pending {
+                proposal_limits = proposal_limits.with_max_pending(max_pending);
+            }
+            Some(proposal_limits)
+        } else {
+            None
+        };
+        daemon_builder = daemon_builder.with_admin_proposal_limits(proposal_limits);
+    }
+
+    #[cfg(feature = "scabbard-shared-executor-pool")]
+    {
+        daemon_builder = daemon_builder.with_scabbard_executor_pool_size(
+            config
+                .scabbard_executor_pool_size()
+                .filter(|size| *size > 0)
+                .map(|size| size as usize),
+        );
+    }
+
+    #[cfg(feature = "rest-api-read-only")]
+    {
+        daemon_builder = daemon_builder.with_rest_api_read_only(config.rest_api_read_only());
+    }
+
+    #[cfg(feature = "store-schema-check")]
+    {
+        daemon_builder = daemon_builder.with_auto_migrate(config.auto_migrate());
+    }
+
+    #[cfg(feature = "store-lease")]
+    {
+        daemon_builder = daemon_builder.with_steal_lease(config.steal_lease());
+    }
+
+    #[cfg(feature = "circuit-access-log")]
+    {
+        daemon_builder = daemon_builder
+            .with_circuit_access_log(config.circuit_access_log().to_vec())
+            .with_circuit_access_log_retention(
+                config
+                    .circuit_access_log_retention()
+                    .map(|days| std::time::Duration::from_secs(days * 24 * 60 * 60)),
+            );
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    {
+        let mut retention = splinter::admin::store::EventRetention::new();
+        if let Some(days) = config.admin_event_retention_age() {
+            retention = retention.with_max_age(std::time::Duration::from_secs(days * 24 * 60 * 60));
+        }
+        if let Some(count) = config.admin_event_retention_count() {
+            retention = retention.with_max_count(count);
+        }
+        daemon_builder = daemon_builder.with_admin_event_retention(retention);
+    }
+
+    #[cfg(feature = "transport-ipv6")]
+    {
+        daemon_builder = daemon_builder.with_preferred_address_family(preferred_address_family);
+    }
+
+    #[cfg(all(feature = "https-bind", feature = "tls-policy"))]
+    {
+        daemon_builder = daemon_builder.with_tls_policy(tls_policy.clone());
+    }
+
+    #[cfg(feature = "rest-api-split")]
+    {
+        daemon_builder =
+            daemon_builder.with_core_endpoint(config.core_endpoint().map(ToOwned::to_owned));
+    }
+
+    #[cfg(feature = "outbound-only")]
+    {
+        daemon_builder = daemon_builder.with_outbound_only(config.outbound_only());
+    }
+
+    #[cfg(feature = "authorization-handler-provider")]
+    {
+        daemon_builder = daemon_builder.with_authorization_handlers(
+            config
+                .authorization_handlers()
+                .map(|handlers| handlers.to_vec()),
+        );
+    }
+
     let (signers, peering_token) = load_signer_keys(config.config_dir(), config.peering_key())?;
     daemon_builder = daemon_builder
         .with_signers(signers)
@@ -747,9 +1624,73 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
             daemon_builder.with_lifecycle_executor_interval(config.lifecycle_executor_interval());
     }
 
+    #[cfg(feature = "service-orchestrator-limits")]
+    {
+        if let Some(service_limits) = config.service_limits() {
+            let restart_policies = service_limits
+                .iter()
+                .filter_map(|(service_type, limits)| {
+                    let max_restarts = limits.max_restarts?;
+                    let mut policy = RestartPolicy::new(max_restarts);
+                    if let Some(backoff) = limits.restart_backoff {
+                        policy = policy.with_backoff(backoff);
+                    }
+                    if let Some(max_backoff) = limits.max_restart_backoff {
+                        policy = policy.with_max_backoff(max_backoff);
+                    }
+                    Some((service_type.clone(), policy))
+                })
+                .collect();
+
+            let resource_limits = service_limits
+                .iter()
+                .map(|(service_type, limits)| {
+                    let mut resource_limits = ResourceLimits::new();
+                    if let Some(max_message_queue) = limits.max_message_queue {
+                        resource_limits = resource_limits.with_max_message_queue(max_message_queue);
+                    }
+                    if let Some(max_state_size) = limits.max_state_size {
+                        resource_limits = resource_limits.with_max_state_size(max_state_size);
+                    }
+                    (service_type.clone(), resource_limits)
+                })
+                .collect();
+
+            daemon_builder = daemon_builder
+                .with_service_restart_policies(restart_policies)
+                .with_service_resource_limits(resource_limits);
+        }
+    }
+
+    // On SIGHUP, the configuration is read again from the same file and arguments
+    #[cfg(feature = "config-reload")]
+    {
+        let config_file_path = config_file_path.map(String::from);
+        let reload_matches = matches.clone();
+        daemon_builder = daemon_builder.with_config_reload(ConfigReload::new(
+            Box::new(move || create_config(config_file_path.as_deref(), reload_matches.clone())),
+            config.clone(),
+            log_handle,
+        ));
+    }
+
     let mut node = daemon_builder.build().map_err(|err| {
         UserError::daemon_err_with_source("unable to build the Splinter daemon", Box::new(err))
     })?;
+
+    // When a core endpoint is configured, only the REST API is run; the network transport is not
+    // needed.
+    #[cfg(feature = "rest-api-split")]
+    if config.core_endpoint().is_some() {
+        node.start_rest_api()?;
+        return Ok(());
+    }
+
+    #[cfg(not(feature = "tls-policy"))]
+    let transport = build_transport(&config)?;
+    #[cfg(feature = "tls-policy")]
+    let transport = build_transport(&config, &tls_policy)?;
+
     node.start(transport)?;
     Ok(())
 }
@@ -19,6 +19,7 @@ use std::time::Duration;
 
 use cylinder::VerifierFactory;
 use scabbard::service::ScabbardFactoryBuilder;
+use splinter::admin::store::AdminServiceEventStore;
 use splinter::circuit::routing::RoutingTableWriter;
 use splinter::error::InternalError;
 use splinter::peer::PeerManagerConnector;
@@ -41,6 +42,7 @@ pub struct AdminSubsystemBuilder {
     node_id: Option<String>,
     admin_timeout: Option<Duration>,
     store_factory: Option<Box<dyn StoreFactory>>,
+    admin_event_store: Option<Box<dyn AdminServiceEventStore>>,
     peer_connector: Option<PeerManagerConnector>,
     routing_writer: Option<Box<dyn RoutingTableWriter>>,
     service_transport: Option<InprocTransport>,
@@ -57,6 +59,7 @@ impl AdminSubsystemBuilder {
             node_id: None,
             admin_timeout: None,
             store_factory: None,
+            admin_event_store: None,
             peer_connector: None,
             routing_writer: None,
             service_transport: None,
@@ -86,6 +89,16 @@ impl AdminSubsystemBuilder {
         self
     }
 
+    /// Specifies the store used to record admin service events. Defaults to the admin service
+    /// store provided by the store factory.
+    pub fn with_admin_event_store(
+        mut self,
+        admin_event_store: Box<dyn AdminServiceEventStore>,
+    ) -> Self {
+        self.admin_event_store = Some(admin_event_store);
+        self
+    }
+
     /// Specifies the peer connector to use with the node
     pub fn with_peer_connector(mut self, peer_connector: PeerManagerConnector) -> Self {
         self.peer_connector = Some(peer_connector);
@@ -152,6 +165,11 @@ impl AdminSubsystemBuilder {
             None => Box::new(MemoryStoreFactory::new()?),
         };
 
+        let admin_event_store = self
+            .admin_event_store
+            .take()
+            .unwrap_or_else(|| Box::new(store_factory.get_admin_service_store()));
+
         let peer_connector = self.peer_connector.take().ok_or_else(|| {
             InternalError::with_message(
                 "Cannot build AdminSubsystem without a peer connector".to_string(),
@@ -219,6 +237,7 @@ impl AdminSubsystemBuilder {
             node_id,
             admin_timeout,
             store_factory,
+            admin_event_store,
             peer_connector,
             routing_writer,
             service_transport,
@@ -24,6 +24,7 @@ use std::time::Duration;
 
 use cylinder::{secp256k1::Secp256k1Context, Context, Signer, Verifier, VerifierFactory};
 use rand::{thread_rng, Rng};
+use splinter::admin::store::AdminServiceEventStore;
 use splinter::biome::credentials::rest_api::{
     BiomeCredentialsRestResourceProvider, BiomeCredentialsRestResourceProviderBuilder,
 };
@@ -147,6 +148,18 @@ impl NodeBuilder {
         self
     }
 
+    /// Specifies the store used to record admin service events, such as a
+    /// `MemoryAdminServiceEventStore`. Defaults to the store factory's admin service store.
+    pub fn with_admin_event_store(
+        mut self,
+        admin_event_store: Box<dyn AdminServiceEventStore>,
+    ) -> Self {
+        self.admin_subsystem_builder = self
+            .admin_subsystem_builder
+            .with_admin_event_store(admin_event_store);
+        self
+    }
+
     pub fn with_admin_service_event_client_variant(
         mut self,
         admin_service_event_client_variant: AdminServiceEventClientVariant,
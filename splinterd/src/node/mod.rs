@@ -13,8 +13,13 @@
 // limitations under the License.
 
 //! Contains the implementation of `NodeBuilder`, `RunnableNode`, and `Node`.
+//!
+//! With the `node-test-network` feature, the [`network`] module provides a harness for running
+//! several peered nodes in one process.
 
 mod builder;
+#[cfg(feature = "node-test-network")]
+pub mod network;
 mod runnable;
 mod running;
 
@@ -0,0 +1,405 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Contains the implementation of `NetworkBuilder` and `Network`, a harness for running several
+//! in-process nodes that are peered with each other.
+//!
+//! Each node is given a temporary directory containing a migrated SQLite database, which backs
+//! the node's stores and scabbard's receipts. Every node's registry is populated with all of the
+//! nodes in the network, so circuits may be proposed between them as soon as the network is
+//! built. Typed clients for each node are available through the running [`Node`].
+//!
+//! Node IDs are assigned in order (`<prefix>-000`, `<prefix>-001`, ...) and, when a key seed is
+//! provided, the nodes' admin keys are derived from it, so a test builds the same network each
+//! time it is run.
+
+use std::collections::HashMap;
+use std::fs::File;
+
+use cylinder::{secp256k1::Secp256k1Context, Context, PrivateKey, Signer};
+use diesel::Connection;
+use splinter::error::{InternalError, InvalidArgumentError};
+use splinter::migrations::run_sqlite_migrations;
+use splinter::registry::Node as RegistryNode;
+use splinter::store::sqlite::{
+    create_sqlite_connection_pool_with_write_exclusivity, SqliteStoreFactory,
+};
+use splinter::threading::lifecycle::ShutdownHandle;
+use tempfile::{Builder, TempDir};
+
+use super::{
+    Node, NodeBuilder, PermissionConfig, RestApiVariant, RunnableNode, ScabbardConfigBuilder,
+};
+
+const DEFAULT_NODE_ID_PREFIX: &str = "test-node";
+
+/// Builds a [`Network`] of running, in-process nodes.
+pub struct NetworkBuilder {
+    num_of_nodes: usize,
+    node_id_prefix: String,
+    rest_api_variant: RestApiVariant,
+    num_of_keys: usize,
+    key_seed: Option<u64>,
+    cylinder_auth: bool,
+    permission_config: Option<Vec<PermissionConfig>>,
+    admin_signer: Option<Box<dyn Signer>>,
+    external_registries: Option<Vec<String>>,
+    auth: Option<String>,
+}
+
+impl NetworkBuilder {
+    pub fn new() -> Self {
+        Self {
+            num_of_nodes: 0,
+            node_id_prefix: DEFAULT_NODE_ID_PREFIX.to_string(),
+            rest_api_variant: RestApiVariant::ActixWeb1,
+            num_of_keys: 1,
+            key_seed: None,
+            cylinder_auth: true,
+            permission_config: None,
+            admin_signer: None,
+            external_registries: None,
+            auth: None,
+        }
+    }
+
+    /// Specifies the number of nodes in the network.
+    pub fn with_nodes(mut self, num_of_nodes: usize) -> Self {
+        self.num_of_nodes = num_of_nodes;
+        self
+    }
+
+    /// Specifies the prefix of the nodes' IDs. Defaults to `test-node`.
+    pub fn with_node_id_prefix(mut self, node_id_prefix: &str) -> Self {
+        self.node_id_prefix = node_id_prefix.to_string();
+        self
+    }
+
+    /// Specifies the REST API variant used by every node. Defaults to Actix Web 1.
+    pub fn with_rest_api_variant(mut self, variant: RestApiVariant) -> Self {
+        self.rest_api_variant = variant;
+        self
+    }
+
+    /// Specifies the number of signing keys given to each node. Defaults to 1.
+    pub fn with_num_of_keys(mut self, num_of_keys: usize) -> Self {
+        self.num_of_keys = num_of_keys;
+        self
+    }
+
+    /// Derives every node's admin and signing keys from the given seed instead of generating
+    /// random keys.
+    ///
+    /// The derived keys are trivially guessable and must only be used in tests.
+    pub fn with_key_seed(mut self, key_seed: u64) -> Self {
+        self.key_seed = Some(key_seed);
+        self
+    }
+
+    /// Specifies whether the nodes' REST APIs require Cylinder JWT authorization. Defaults to
+    /// `true`.
+    pub fn with_cylinder_auth(mut self, cylinder_auth: bool) -> Self {
+        self.cylinder_auth = cylinder_auth;
+        self
+    }
+
+    /// Specifies the permissions configured on each node's REST API.
+    pub fn with_permission_config(mut self, permission_config: Vec<PermissionConfig>) -> Self {
+        self.permission_config = Some(permission_config);
+        self
+    }
+
+    /// Specifies a single admin signer shared by every node. Takes precedence over the key seed.
+    pub fn with_admin_signer(mut self, signer: Box<dyn Signer>) -> Self {
+        self.admin_signer = Some(signer);
+        self
+    }
+
+    /// Specifies external registry files added to every node's unified registry.
+    pub fn with_external_registries(mut self, registries: Vec<String>) -> Self {
+        self.external_registries = Some(registries);
+        self
+    }
+
+    /// Specifies the authorization used by the nodes' clients.
+    pub fn with_client_auth(mut self, auth: String) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Starts the nodes and populates each node's registry with every node in the network.
+    pub fn build(self) -> Result<Network, InternalError> {
+        let context = Secp256k1Context::new();
+        let mut key_index = 0;
+        let mut next_signer = || -> Box<dyn Signer> {
+            key_index += 1;
+            match self.key_seed {
+                Some(seed) => context.new_signer(seeded_private_key(seed, key_index)),
+                None => context.new_signer(context.new_random_private_key()),
+            }
+        };
+
+        let mut nodes = Vec::with_capacity(self.num_of_nodes);
+        let mut temp_dirs = HashMap::new();
+        for index in 0..self.num_of_nodes {
+            let admin_signer = match self.admin_signer {
+                Some(ref signer) => signer.clone_box(),
+                None => next_signer(),
+            };
+            let signers = (0..self.num_of_keys)
+                .map(|_| next_signer())
+                .collect::<Vec<_>>();
+
+            let temp_dir = Builder::new()
+                .prefix("scabbard_data")
+                .tempdir()
+                .map_err(|e| InternalError::from_source(Box::new(e)))?;
+            let temp_db_path = temp_dir.path().join("sqlite_receipt_store.db");
+
+            File::create(temp_db_path.clone())
+                .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+            run_sqlite_migrations(
+                &diesel::SqliteConnection::establish(&temp_db_path.to_string_lossy())
+                    .map_err(|e| InternalError::from_source(Box::new(e)))?,
+            )?;
+
+            let pool = create_sqlite_connection_pool_with_write_exclusivity(
+                &temp_db_path.to_string_lossy(),
+            )?;
+
+            let store_factory = SqliteStoreFactory::new_with_write_exclusivity(pool.clone());
+
+            let mut builder = NodeBuilder::new()
+                .with_node_id(format!("{}-{:03}", self.node_id_prefix, index))
+                .with_rest_api_variant(self.rest_api_variant)
+                .with_scabbard(
+                    ScabbardConfigBuilder::new()
+                        .with_data_dir(temp_dir.path().to_path_buf())
+                        .with_connection_pool(pool)
+                        .build()?,
+                )
+                .with_admin_signer(admin_signer)
+                .with_signers(signers)
+                .with_external_registries(self.external_registries.clone())
+                .with_store_factory(Box::new(store_factory))
+                .with_biome_enabled()
+                .with_permission_config(self.permission_config.clone())
+                .with_client_auth(self.auth.clone());
+            if self.cylinder_auth {
+                builder = builder.with_cylinder_auth(Box::new(Secp256k1Context::new()));
+            }
+
+            let node = builder.build()?.run()?;
+
+            temp_dirs.insert(node.node_id().to_string(), temp_dir);
+            nodes.push(NetworkNode::Node(node));
+        }
+
+        let network = Network { nodes, temp_dirs };
+        network.register_nodes()?;
+
+        Ok(network)
+    }
+}
+
+impl Default for NetworkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A node in a [`Network`], which may be running or stopped.
+pub enum NetworkNode {
+    Node(Node),
+    RunnableNode(RunnableNode),
+}
+
+/// A network of in-process nodes, built by a [`NetworkBuilder`].
+///
+/// The nodes' temporary directories are removed when the network is dropped.
+pub struct Network {
+    nodes: Vec<NetworkNode>,
+    temp_dirs: HashMap<String, TempDir>,
+}
+
+impl Network {
+    /// Returns the running node at the given index.
+    pub fn node(&self, n: usize) -> Result<&Node, InvalidArgumentError> {
+        match self.nodes.get(n) {
+            Some(NetworkNode::Node(node)) => Ok(node),
+            Some(NetworkNode::RunnableNode(_)) => Err(InvalidArgumentError::new(
+                "n".to_string(),
+                "node is stopped".to_string(),
+            )),
+            None => Err(InvalidArgumentError::new(
+                "n".to_string(),
+                "out of range".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the running nodes in the network, in order.
+    pub fn running_nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter().filter_map(|node| match node {
+            NetworkNode::Node(node) => Some(node),
+            NetworkNode::RunnableNode(_) => None,
+        })
+    }
+
+    /// Returns the number of nodes in the network, running or stopped.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the network contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Restarts the stopped node at the given index, updating its endpoints in its registry.
+    pub fn start(mut self, index: usize) -> Result<Network, InternalError> {
+        if index >= self.nodes.len() {
+            return Err(InternalError::with_message(format!(
+                "node index {} is out of range",
+                index
+            )));
+        }
+
+        let node = match self.nodes.remove(index) {
+            NetworkNode::RunnableNode(runnable_node) => runnable_node.run()?,
+            NetworkNode::Node(node) => {
+                self.nodes.insert(index, NetworkNode::Node(node));
+                return Err(InternalError::with_message(
+                    "node is already running".to_string(),
+                ));
+            }
+        };
+
+        node.registry_writer()
+            .update_node(registry_node(
+                node.node_id(),
+                node.network_endpoints(),
+                node.admin_signer(),
+            )?)
+            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+        self.nodes.insert(index, NetworkNode::Node(node));
+
+        Ok(self)
+    }
+
+    /// Stops the running node at the given index. Its state is kept so it may be restarted.
+    pub fn stop(mut self, index: usize) -> Result<Network, InternalError> {
+        if index >= self.nodes.len() {
+            return Err(InternalError::with_message(format!(
+                "node index {} is out of range",
+                index
+            )));
+        }
+
+        let runnable_node = match self.nodes.remove(index) {
+            NetworkNode::Node(node) => node.stop()?,
+            NetworkNode::RunnableNode(runnable_node) => {
+                self.nodes
+                    .insert(index, NetworkNode::RunnableNode(runnable_node));
+                return Err(InternalError::with_message(
+                    "node is already stopped".to_string(),
+                ));
+            }
+        };
+        self.nodes
+            .insert(index, NetworkNode::RunnableNode(runnable_node));
+
+        Ok(self)
+    }
+
+    /// Adds every node in the network to every node's registry.
+    fn register_nodes(&self) -> Result<(), InternalError> {
+        let registry_nodes = self
+            .running_nodes()
+            .map(|node| {
+                registry_node(
+                    node.node_id(),
+                    node.network_endpoints(),
+                    node.admin_signer(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for node in self.running_nodes() {
+            let registry_writer = node.registry_writer();
+            for registry_node in &registry_nodes {
+                registry_writer
+                    .add_node(registry_node.clone())
+                    .map_err(|e| InternalError::from_source(Box::new(e)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the temporary directory holding the state of the node with the given ID.
+    pub fn temp_dir(&self, node_id: &str) -> Option<&TempDir> {
+        self.temp_dirs.get(node_id)
+    }
+}
+
+impl ShutdownHandle for Network {
+    fn signal_shutdown(&mut self) {
+        for node in &mut self.nodes {
+            match node {
+                NetworkNode::Node(node) => node.signal_shutdown(),
+                NetworkNode::RunnableNode(_) => (),
+            }
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        for node in self.nodes.into_iter() {
+            match node {
+                NetworkNode::Node(node) => node.wait_for_shutdown()?,
+                NetworkNode::RunnableNode(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn registry_node(
+    node_id: &str,
+    endpoints: &[String],
+    admin_signer: &dyn Signer,
+) -> Result<RegistryNode, InternalError> {
+    RegistryNode::builder(node_id)
+        .with_display_name(node_id)
+        .with_endpoints(endpoints.to_vec())
+        .with_key(
+            admin_signer
+                .public_key()
+                .map_err(|e| InternalError::from_source(Box::new(e)))?
+                .as_hex(),
+        )
+        .build()
+        .map_err(|e| InternalError::from_source(Box::new(e)))
+}
+
+/// Derives a secp256k1 private key from a seed and a key index. The seed fills the high bytes
+/// and the index the low bytes, which keeps the key non-zero and below the curve order.
+fn seeded_private_key(seed: u64, key_index: u64) -> PrivateKey {
+    let mut bytes = vec![0; 32];
+    bytes[..8].copy_from_slice(&seed.to_be_bytes());
+    bytes[24..].copy_from_slice(&key_index.to_be_bytes());
+    PrivateKey::new(bytes)
+}
@@ -19,6 +19,7 @@ use std::time::Duration;
 use cylinder::Verifier;
 use scabbard::service::ScabbardFactory;
 use splinter::admin::service::{AdminCommands, AdminServiceBuilder, AdminServiceStatus};
+use splinter::admin::store::AdminServiceEventStore;
 use splinter::circuit::routing::RoutingTableWriter;
 use splinter::error::InternalError;
 use splinter::events::Reactor;
@@ -44,6 +45,7 @@ pub struct RunnableAdminSubsystem {
     pub node_id: String,
     pub admin_timeout: Duration,
     pub store_factory: Box<dyn StoreFactory>,
+    pub admin_event_store: Box<dyn AdminServiceEventStore>,
     pub peer_connector: PeerManagerConnector,
     pub routing_writer: Box<dyn RoutingTableWriter>,
     pub service_transport: InprocTransport,
@@ -136,7 +138,6 @@ impl RunnableAdminSubsystem {
             .with_lifecycle_dispatch(vec![Box::new(orchestrator)])
             .with_peer_manager_connector(peer_connector.clone())
             .with_admin_service_store(store_factory.get_admin_service_store())
-            .with_admin_event_store(store_factory.get_admin_service_store())
             .with_signature_verifier(self.admin_service_verifier)
             .with_admin_key_verifier(Box::new(registry.clone_box_as_reader()))
             .with_key_permission_manager(Box::new(
@@ -144,7 +145,7 @@ impl RunnableAdminSubsystem {
             ))
             .with_coordinator_timeout(admin_timeout)
             .with_routing_table_writer(routing_writer)
-            .with_admin_event_store(store_factory.get_admin_service_store())
+            .with_admin_event_store(self.admin_event_store)
             .with_public_keys(self.public_keys.to_vec());
 
         let circuit_resource_provider =
@@ -16,8 +16,14 @@ use std::fs;
 use std::path::Path;
 
 use splinter::transport::multi::MultiTransport;
+#[cfg(feature = "transport-tcp-options")]
+use splinter::transport::socket::TcpOptions;
 use splinter::transport::socket::TcpTransport;
 use splinter::transport::socket::TlsTransport;
+#[cfg(feature = "unix-transport")]
+use splinter::transport::socket::UnixTransport;
+#[cfg(feature = "tls-policy")]
+use splinter::transport::tls::TlsPolicy;
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
@@ -28,28 +34,59 @@ use crate::error::GetTransportError;
 
 type SendableTransport = Box<dyn Transport + Send>;
 
-pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportError> {
+pub fn build_transport(
+    config: &Config,
+    #[cfg(feature = "tls-policy")] tls_policy: &TlsPolicy,
+) -> Result<MultiTransport, GetTransportError> {
+    #[cfg(feature = "transport-tcp-options")]
+    let tcp_options = build_tcp_options(config);
+
+    #[cfg(not(feature = "transport-tcp-options"))]
+    let tcp_transport = TcpTransport::default();
+    #[cfg(feature = "transport-tcp-options")]
+    let tcp_transport = TcpTransport::default().with_tcp_options(tcp_options.clone());
+
     let mut transports: Vec<SendableTransport> = vec![
         // add tcp transport
         // this will be default for endpoints without a prefix
-        Box::new(TcpTransport::default()),
+        Box::new(tcp_transport),
     ];
 
+    // add unix domain socket transport, for services co-located with the daemon
+    #[cfg(feature = "unix-transport")]
+    transports.push(Box::new(UnixTransport::default()));
+
     // add web socket transport
 
     // add tls transport
     if !config.no_tls() {
+        #[cfg(not(feature = "tls-policy"))]
         let tls_config = build_tls_config(config)?;
+        #[cfg(feature = "tls-policy")]
+        let tls_config = build_tls_config(config, tls_policy)?;
         validate_tls_config(&tls_config)?;
         print_tls_config(&tls_config)?;
 
-        transports.push(Box::new(TlsTransport::new(
+        #[cfg(not(feature = "tls-policy"))]
+        let tls_transport = TlsTransport::new(
             tls_config.ca_certs_file().to_owned(),
             tls_config.client_private_key_file().to_string(),
             tls_config.client_cert_file().to_string(),
             tls_config.server_private_key_file().to_string(),
             tls_config.server_cert_file().to_string(),
-        )?));
+        )?;
+        #[cfg(feature = "tls-policy")]
+        let tls_transport = TlsTransport::new_with_policy(
+            tls_config.ca_certs_file().to_owned(),
+            tls_config.client_private_key_file().to_string(),
+            tls_config.client_cert_file().to_string(),
+            tls_config.server_private_key_file().to_string(),
+            tls_config.server_cert_file().to_string(),
+            tls_config.policy(),
+        )?;
+        #[cfg(feature = "transport-tcp-options")]
+        let tls_transport = tls_transport.with_tcp_options(tcp_options);
+        transports.push(Box::new(tls_transport));
 
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::new(Some(&tls_config)).map_err(
@@ -63,13 +100,34 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
     Ok(MultiTransport::new(transports))
 }
 
-fn build_tls_config(config: &Config) -> Result<TlsConfig, GetTransportError> {
+/// Builds the socket options applied to the connections of the TCP and TLS transports.
+#[cfg(feature = "transport-tcp-options")]
+fn build_tcp_options(config: &Config) -> TcpOptions {
+    let mut options = TcpOptions::new().with_nodelay(config.tcp_nodelay());
+    if let Some(keepalive) = config.tcp_keepalive() {
+        options = options.with_keepalive(keepalive);
+    }
+    if let Some(user_timeout) = config.tcp_user_timeout() {
+        options = options.with_user_timeout(user_timeout);
+    }
+    options
+}
+
+fn build_tls_config(
+    config: &Config,
+    #[cfg(feature = "tls-policy")] tls_policy: &TlsPolicy,
+) -> Result<TlsConfig, GetTransportError> {
     let mut builder = TlsConfigBuilder::new()
         .with_client_cert_file(config.tls_client_cert().to_string())
         .with_client_private_key_file(config.tls_client_key().to_string())
         .with_server_cert_file(config.tls_server_cert().to_string())
         .with_server_private_key_file(config.tls_server_key().to_string());
 
+    #[cfg(feature = "tls-policy")]
+    {
+        builder = builder.with_policy(tls_policy.clone());
+    }
+
     if config.tls_insecure() {
         warn!("Starting TlsTransport in insecure mode");
     } else {
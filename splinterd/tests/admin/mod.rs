@@ -25,6 +25,8 @@ mod node_lifecycle;
 pub(super) mod payload;
 mod registry;
 mod rest_api;
+#[cfg(feature = "node-test-network")]
+mod test_network;
 
 use scabbard::client::ServiceId;
 use splinterd::node::Node;
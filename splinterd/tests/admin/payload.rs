@@ -133,6 +133,7 @@ pub(crate) fn make_circuit_proposal_vote_payload(
         circuit_id: proposal.circuit_id.to_string(),
         circuit_hash: proposal.circuit_hash,
         vote,
+        comment: None,
     }
     .into_proto();
 
@@ -0,0 +1,92 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Integration tests for the `NetworkBuilder` test harness provided by splinterd.
+
+use splinterd::node::network::NetworkBuilder;
+use splinterd::node::RestApiVariant;
+
+use crate::framework::circuit_builder::{CircuitBuilder, CircuitData, ScabbardCircuitBuilderVeil};
+
+/// Test that networks built with the same key seed have the same node IDs and admin keys.
+///
+/// 1. Build two single-node networks with the same node ID prefix and key seed
+/// 2. Validate the node IDs are assigned in order from the prefix
+/// 3. Validate the nodes' admin public keys are the same
+#[test]
+pub fn test_network_builder_is_deterministic() {
+    let mut network_a = NetworkBuilder::new()
+        .with_nodes(1)
+        .with_node_id_prefix("seeded")
+        .with_key_seed(7)
+        .build()
+        .expect("Unable to start first network");
+    let mut network_b = NetworkBuilder::new()
+        .with_nodes(1)
+        .with_node_id_prefix("seeded")
+        .with_key_seed(7)
+        .build()
+        .expect("Unable to start second network");
+
+    let node_a = network_a.node(0).expect("Unable to get node");
+    let node_b = network_b.node(0).expect("Unable to get node");
+
+    assert_eq!(node_a.node_id(), "seeded-000");
+    assert_eq!(node_b.node_id(), "seeded-000");
+    assert_eq!(
+        node_a
+            .admin_signer()
+            .public_key()
+            .expect("Unable to get public key"),
+        node_b
+            .admin_signer()
+            .public_key()
+            .expect("Unable to get public key"),
+    );
+
+    shutdown!(network_a, network_b).expect("Unable to shutdown networks");
+}
+
+/// Test that the nodes in a network built by the `NetworkBuilder` are registered with each other,
+/// so that a circuit may be created between them.
+///
+/// 1. Build a 2-node network
+/// 2. Create a scabbard circuit between both nodes
+/// 3. Validate the circuit is available on each node
+#[test]
+pub fn test_network_builder_circuit() {
+    let mut network = NetworkBuilder::new()
+        .with_nodes(2)
+        .with_rest_api_variant(RestApiVariant::ActixWeb1)
+        .build()
+        .expect("Unable to start 2-node network");
+    assert_eq!(network.len(), 2);
+
+    let CircuitData { circuit_id, .. } = CircuitBuilder::new(&network, &[0, 1])
+        .expect("Could not create builder")
+        .veil::<ScabbardCircuitBuilderVeil<_>>()
+        .add_service_group(&[0, 1])
+        .unwrap()
+        .build()
+        .expect("Could not create circuit");
+
+    for node in network.running_nodes() {
+        let circuit = node
+            .admin_service_client()
+            .fetch_circuit(&circuit_id)
+            .expect("Unable to fetch circuit");
+        assert!(circuit.is_some());
+    }
+
+    shutdown!(network).expect("Unable to shutdown network");
+}
@@ -37,3 +37,10 @@ impl NodeCollection for Network {
         Network::node(&self, id)
     }
 }
+
+#[cfg(feature = "node-test-network")]
+impl NodeCollection for splinterd::node::network::Network {
+    fn node(&self, id: usize) -> Result<&Node, InvalidArgumentError> {
+        splinterd::node::network::Network::node(self, id)
+    }
+}